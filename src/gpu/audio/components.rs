@@ -4,6 +4,13 @@
 
 use ultraviolet::Vec3;
 
+use crate::gpu::blocks::{BlockCategory, BlockDefinition, ToolKind};
+
+use super::utils::rand_simple;
+
+/// Минимальная разница сдвигов высоты двух звуков подряд
+const MIN_JITTER_STEP: f32 = 0.3;
+
 /// Состояние системы шагов
 pub struct FootstepState {
     pub last_position: Vec3,
@@ -50,6 +57,111 @@ impl Default for JumpState {
     }
 }
 
+/// Материал блока для звука установки
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SoundMaterial {
+    Stone,
+    Earth,
+    Wood,
+    Foliage,
+    Glass,
+    Metal,
+}
+
+impl SoundMaterial {
+    pub const ALL: [SoundMaterial; 6] = [
+        SoundMaterial::Stone,
+        SoundMaterial::Earth,
+        SoundMaterial::Wood,
+        SoundMaterial::Foliage,
+        SoundMaterial::Glass,
+        SoundMaterial::Metal,
+    ];
+    
+    /// Материал по категории, прозрачности и инструменту блока
+    pub fn of(def: &BlockDefinition) -> Self {
+        if def.category == BlockCategory::Metal {
+            SoundMaterial::Metal
+        } else if def.transparent && def.tool != ToolKind::Shovel {
+            // Листва мягкая, стекло и лёд - твёрже
+            if def.hardness < 0.25 { SoundMaterial::Foliage } else { SoundMaterial::Glass }
+        } else if def.category == BlockCategory::Wood || def.tool == ToolKind::Axe {
+            SoundMaterial::Wood
+        } else if def.tool == ToolKind::Shovel {
+            SoundMaterial::Earth
+        } else {
+            SoundMaterial::Stone
+        }
+    }
+    
+    /// Имя в файлах звуков (sounds/place_<name>.wav)
+    pub fn name(self) -> &'static str {
+        match self {
+            SoundMaterial::Stone => "stone",
+            SoundMaterial::Earth => "earth",
+            SoundMaterial::Wood => "wood",
+            SoundMaterial::Foliage => "foliage",
+            SoundMaterial::Glass => "glass",
+            SoundMaterial::Metal => "metal",
+        }
+    }
+    
+    /// Базовые громкость и высота звука установки
+    pub fn place_tone(self) -> (f32, f32) {
+        match self {
+            SoundMaterial::Stone => (0.45, 0.9),
+            SoundMaterial::Earth => (0.35, 0.8),
+            SoundMaterial::Wood => (0.4, 1.0),
+            SoundMaterial::Foliage => (0.3, 1.15),
+            SoundMaterial::Glass => (0.35, 1.3),
+            SoundMaterial::Metal => (0.45, 1.1),
+        }
+    }
+}
+
+/// Состояние звуков установки: свой генератор случайных чисел и
+/// прошлый сдвиг высоты, чтобы щелчки подряд не звучали одинаково
+pub struct PlaceSoundState {
+    pub rng: u32,
+    pub last_jitter: f32,
+}
+
+impl PlaceSoundState {
+    pub fn new() -> Self {
+        Self {
+            rng: (rand_simple() * u32::MAX as f32) as u32 | 1,
+            last_jitter: 0.0,
+        }
+    }
+    
+    /// Случайное число 0-1 (xorshift)
+    pub fn next_random(&mut self) -> f32 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 17;
+        self.rng ^= self.rng << 5;
+        (self.rng >> 8) as f32 / (1u32 << 24) as f32
+    }
+    
+    /// Сдвиг высоты -1..1, заметно отличающийся от прошлого
+    pub fn next_jitter(&mut self) -> f32 {
+        let mut jitter = self.next_random() * 2.0 - 1.0;
+        for _ in 0..3 {
+            if (jitter - self.last_jitter).abs() >= MIN_JITTER_STEP {
+                break;
+            }
+            jitter = self.next_random() * 2.0 - 1.0;
+        }
+        self.last_jitter = jitter;
+        jitter
+    }
+}
+
+impl Default for PlaceSoundState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Тип окружения для звука
 #[derive(Clone, Copy, Debug, PartialEq, Default)]
 pub enum EnvironmentType {
@@ -83,6 +195,11 @@ impl Default for EnvironmentParams {
     }
 }
 
+/// Частота среза, при которой фильтр ничего не глушит (Гц)
+pub const OPEN_LOWPASS_CUTOFF: f32 = 20000.0;
+/// Частота среза под водой (Гц)
+const UNDERWATER_LOWPASS_CUTOFF: f32 = 700.0;
+
 /// Модификаторы звука на основе окружения
 #[derive(Clone, Copy, Debug)]
 pub struct SoundModifiers {
@@ -90,6 +207,8 @@ pub struct SoundModifiers {
     pub pitch_mult: f32,
     pub reverb_amount: f32,
     pub muffling: f32,
+    /// Срез фильтра низких частот на общей дорожке (Гц)
+    pub lowpass_cutoff: f32,
 }
 
 impl Default for SoundModifiers {
@@ -107,34 +226,58 @@ impl SoundModifiers {
                 pitch_mult: 1.0,
                 reverb_amount: 0.05,
                 muffling: 0.0,
+                lowpass_cutoff: OPEN_LOWPASS_CUTOFF,
             },
             EnvironmentType::Forest => Self {
                 volume_mult: 0.95,
                 pitch_mult: 1.0,
                 reverb_amount: 0.15,
                 muffling: 0.05,
+                lowpass_cutoff: OPEN_LOWPASS_CUTOFF,
             },
             EnvironmentType::Cave => Self {
                 volume_mult: 1.1,
                 pitch_mult: 0.98,
                 reverb_amount: 0.4 + env.enclosure * 0.3,
                 muffling: 0.1,
+                lowpass_cutoff: OPEN_LOWPASS_CUTOFF,
             },
             EnvironmentType::TightSpace => Self {
                 volume_mult: 1.2,
                 pitch_mult: 0.95,
                 reverb_amount: 0.6,
                 muffling: 0.15,
+                lowpass_cutoff: OPEN_LOWPASS_CUTOFF,
             },
             EnvironmentType::DeepUnderground => Self {
                 volume_mult: 1.15,
                 pitch_mult: 0.92,
                 reverb_amount: 0.5,
                 muffling: 0.2,
+                lowpass_cutoff: OPEN_LOWPASS_CUTOFF,
             },
         }
     }
     
+    /// Заглушить звуки шумом дождя/метели (damping 0-1)
+    pub fn with_weather(mut self, damping: f32) -> Self {
+        let damping = damping.clamp(0.0, 1.0);
+        self.volume_mult *= 1.0 - 0.35 * damping;
+        self.muffling = (self.muffling + 0.4 * damping).min(1.0);
+        self
+    }
+    
+    /// Камера под водой: всё глухо, тише и ниже
+    pub fn with_underwater(mut self, underwater: bool) -> Self {
+        if underwater {
+            self.volume_mult *= 0.7;
+            self.pitch_mult *= 0.9;
+            self.muffling = 1.0;
+            self.lowpass_cutoff = UNDERWATER_LOWPASS_CUTOFF;
+        }
+        self
+    }
+    
     /// Применить модификаторы к базовым настройкам звука
     pub fn apply(&self, base_volume: f32, base_pitch: f32) -> (f64, f64) {
         let pitch_with_reverb = base_pitch * self.pitch_mult * (1.0 - self.muffling * 0.1);
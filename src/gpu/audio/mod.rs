@@ -15,7 +15,16 @@ pub use environment::*;
 pub use systems::*;
 pub use utils::rand_simple;
 
+use std::time::Duration;
+
 use kira::manager::{AudioManager, AudioManagerSettings, backend::DefaultBackend};
+use kira::track::TrackBuilder;
+use kira::track::effect::filter::{FilterBuilder, FilterHandle};
+use kira::tween::Tween;
+use kira::Volume;
+
+use crate::gpu::blocks::BlockType;
+use crate::gpu::subvoxel::SubVoxelLevel;
 
 /// Главная аудио система - фасад для всех подсистем
 pub struct AudioSystem {
@@ -24,18 +33,33 @@ pub struct AudioSystem {
     environment: EnvironmentAnalyzer,
     current_modifiers: SoundModifiers,
     block_checker: Option<BlockSolidChecker>,
+    /// Заглушение от погоды (0 = ясно)
+    weather_damping: f32,
+    /// Камера под водой
+    underwater: bool,
+    /// Фильтр низких частот на главной дорожке и его текущий срез
+    lowpass: FilterHandle,
+    lowpass_cutoff: f32,
     
     // Состояния подсистем
     footstep_state: FootstepState,
     jump_state: JumpState,
+    place_state: PlaceSoundState,
 }
 
 impl AudioSystem {
     pub fn new() -> Result<Self, String> {
-        let manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())
+        // Все звуки идут через главную дорожку - фильтр на ней глушит всё сразу
+        let mut main_track = TrackBuilder::new();
+        let lowpass = main_track.add_effect(FilterBuilder::new().cutoff(OPEN_LOWPASS_CUTOFF as f64));
+        let settings = AudioManagerSettings {
+            main_track_builder: main_track,
+            ..Default::default()
+        };
+        let manager = AudioManager::<DefaultBackend>::new(settings)
             .map_err(|e| format!("Failed to create audio manager: {:?}", e))?;
         
-        println!("[AUDIO] Аудио система с рейтрейсингом инициализирована");
+        log::info!("Аудио система с рейтрейсингом инициализирована");
         
         Ok(Self {
             manager,
@@ -43,8 +67,13 @@ impl AudioSystem {
             environment: EnvironmentAnalyzer::new(),
             current_modifiers: SoundModifiers::default(),
             block_checker: None,
+            weather_damping: 0.0,
+            underwater: false,
+            lowpass,
+            lowpass_cutoff: OPEN_LOWPASS_CUTOFF,
             footstep_state: FootstepState::new(),
             jump_state: JumpState::new(),
+            place_state: PlaceSoundState::new(),
         })
     }
     
@@ -56,13 +85,37 @@ impl AudioSystem {
         self.block_checker = Some(Box::new(checker));
     }
     
+    /// Установить заглушение от осадков (0-1)
+    pub fn set_weather_damping(&mut self, damping: f32) {
+        self.weather_damping = damping;
+    }
+    
+    /// Камера под водой: глухой звук через фильтр низких частот
+    pub fn set_underwater(&mut self, underwater: bool) {
+        self.underwater = underwater;
+    }
+    
+    /// Общая громкость (0-1) на главной дорожке
+    pub fn set_master_volume(&mut self, volume: f32) {
+        self.manager
+            .main_track()
+            .set_volume(Volume::Amplitude(volume.clamp(0.0, 1.0) as f64), Tween::default());
+    }
+    
     pub fn load_sounds(&mut self) -> Result<(), String> {
         self.sounds.load_all()
     }
     
-    /// Проиграть звук установки блока
-    pub fn play_place_block(&mut self) {
-        systems::play_place_block(&mut self.manager, &self.sounds, &self.current_modifiers);
+    /// Проиграть звук установки блока (level - размер суб-вокселя, Full для блока)
+    pub fn play_place_block(&mut self, block: BlockType, level: SubVoxelLevel) {
+        systems::play_place_block(
+            &mut self.manager,
+            &self.sounds,
+            &mut self.place_state,
+            block,
+            level,
+            &self.current_modifiers,
+        );
     }
     
     /// Обновить систему (вызывать каждый кадр)
@@ -74,13 +127,31 @@ impl AudioSystem {
         is_moving: bool,
         is_on_ground: bool,
         is_sprinting: bool,
+        is_sneaking: bool,
         is_jumping: bool,
         dt: f32,
     ) {
         // Анализируем окружение
         if let Some(ref checker) = self.block_checker {
             let env_params = self.environment.analyze(player_pos, dt, |x, y, z| checker(x, y, z));
-            self.current_modifiers = SoundModifiers::from_environment(&env_params);
+            // Под крышей и в пещерах дождь почти не слышен
+            let exposure = match env_params.env_type {
+                EnvironmentType::OpenField | EnvironmentType::Forest => 1.0,
+                _ => 0.2,
+            };
+            self.current_modifiers = SoundModifiers::from_environment(&env_params)
+                .with_weather(self.weather_damping * exposure)
+                .with_underwater(self.underwater);
+        }
+        
+        // Срез фильтра меняется плавно и только при смене среды
+        let cutoff = self.current_modifiers.lowpass_cutoff;
+        if cutoff != self.lowpass_cutoff {
+            self.lowpass_cutoff = cutoff;
+            self.lowpass.set_cutoff(cutoff as f64, Tween {
+                duration: Duration::from_millis(250),
+                ..Default::default()
+            });
         }
         
         // Система шагов
@@ -92,6 +163,7 @@ impl AudioSystem {
             is_moving,
             is_on_ground,
             is_sprinting,
+            is_sneaking,
             &self.current_modifiers,
             dt,
         );
@@ -2,13 +2,26 @@
 // Audio Resources - Загруженные звуки (ECS)
 // ============================================
 
+use std::collections::HashMap;
+use std::io::Cursor;
+
 use kira::sound::static_sound::StaticSoundData;
+use kira::sound::FromFileError;
+
+use crate::gpu::blocks::{global_registry, BlockType};
+use crate::gpu::resource_pack::global_resource_packs;
+
+use super::SoundMaterial;
 
 /// Ресурсы звуков - загруженные аудио данные
 pub struct SoundResources {
     pub footstep: Option<StaticSoundData>,
     pub jump: Option<StaticSoundData>,
     pub place_block: Option<StaticSoundData>,
+    /// Звуки установки по материалам (необязательные)
+    pub place_by_material: HashMap<SoundMaterial, StaticSoundData>,
+    /// Звуки установки отдельных блоков (поле sounds.place в JSON)
+    pub place_by_block: HashMap<BlockType, StaticSoundData>,
 }
 
 impl SoundResources {
@@ -17,44 +30,98 @@ impl SoundResources {
             footstep: None,
             jump: None,
             place_block: None,
+            place_by_material: HashMap::new(),
+            place_by_block: HashMap::new(),
         }
     }
     
     /// Загрузить все звуки
+    /// Пакеты ресурсов переопределяют звуки по пути sounds/<name>.wav
     pub fn load_all(&mut self) -> Result<(), String> {
-        self.load_footstep("assets/music/grass-foot-step.wav")?;
-        self.load_jump("assets/music/jump.wav")?;
-        self.load_place_block("assets/music/place.wav")?;
+        self.load_footstep("sounds/footstep.wav", "assets/music/grass-foot-step.wav")?;
+        self.load_jump("sounds/jump.wav", "assets/music/jump.wav")?;
+        self.load_place_block("sounds/place.wav", "assets/music/place.wav")?;
+        self.load_place_variants();
         Ok(())
     }
     
-    fn load_footstep(&mut self, path: &str) -> Result<(), String> {
-        match StaticSoundData::from_file(path) {
+    /// Звук установки: свой у блока, затем по материалу, затем общий
+    pub fn place_sound(&self, block: BlockType, material: SoundMaterial) -> Option<&StaticSoundData> {
+        self.place_by_block
+            .get(&block)
+            .or_else(|| self.place_by_material.get(&material))
+            .or(self.place_block.as_ref())
+    }
+    
+    /// Загрузить звук из пакета ресурсов или из встроенного файла
+    fn load_sound(pack_path: &str, path: &str) -> Result<StaticSoundData, FromFileError> {
+        let pack_bytes = global_resource_packs()
+            .read()
+            .ok()
+            .and_then(|packs| packs.read(pack_path));
+        
+        match pack_bytes {
+            Some(bytes) => StaticSoundData::from_cursor(Cursor::new(bytes)),
+            None => StaticSoundData::from_file(path),
+        }
+    }
+    
+    fn load_footstep(&mut self, pack_path: &str, path: &str) -> Result<(), String> {
+        match Self::load_sound(pack_path, path) {
             Ok(sound) => {
                 self.footstep = Some(sound);
-                println!("[AUDIO] Загружен звук шага: {}", path);
+                log::debug!("Загружен звук шага: {}", path);
                 Ok(())
             }
             Err(e) => Err(format!("Failed to load footstep sound: {:?}", e))
         }
     }
     
-    fn load_jump(&mut self, path: &str) -> Result<(), String> {
-        match StaticSoundData::from_file(path) {
+    fn load_jump(&mut self, pack_path: &str, path: &str) -> Result<(), String> {
+        match Self::load_sound(pack_path, path) {
             Ok(sound) => {
                 self.jump = Some(sound);
-                println!("[AUDIO] Загружен звук прыжка: {}", path);
+                log::debug!("Загружен звук прыжка: {}", path);
                 Ok(())
             }
             Err(e) => Err(format!("Failed to load jump sound: {:?}", e))
         }
     }
     
-    fn load_place_block(&mut self, path: &str) -> Result<(), String> {
-        match StaticSoundData::from_file(path) {
+    /// Звуки по материалам (sounds/place_<material>.wav) и звуки блоков.
+    /// Отсутствующие файлы не ошибка - играет общий звук
+    fn load_place_variants(&mut self) {
+        for material in SoundMaterial::ALL {
+            let pack_path = format!("sounds/place_{}.wav", material.name());
+            let path = format!("assets/music/place_{}.wav", material.name());
+            if let Ok(sound) = Self::load_sound(&pack_path, &path) {
+                self.place_by_material.insert(material, sound);
+                log::debug!("Загружен звук установки ({}): {}", material.name(), path);
+            }
+        }
+        
+        let overrides: Vec<(BlockType, String)> = match global_registry().read() {
+            Ok(registry) => registry
+                .all_blocks()
+                .filter_map(|def| Some((def.numeric_id, def.sounds.place.clone()?)))
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        for (block, path) in overrides {
+            match Self::load_sound(&path, &path) {
+                Ok(sound) => {
+                    self.place_by_block.insert(block, sound);
+                }
+                Err(e) => log::warn!("Звук установки блока {} не загружен ({}): {:?}", block, path, e),
+            }
+        }
+    }
+    
+    fn load_place_block(&mut self, pack_path: &str, path: &str) -> Result<(), String> {
+        match Self::load_sound(pack_path, path) {
             Ok(sound) => {
                 self.place_block = Some(sound);
-                println!("[AUDIO] Загружен звук установки блока: {}", path);
+                log::debug!("Загружен звук установки блока: {}", path);
                 Ok(())
             }
             Err(e) => Err(format!("Failed to load place block sound: {:?}", e))
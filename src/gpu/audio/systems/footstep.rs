@@ -11,6 +11,9 @@ use ultraviolet::Vec3;
 
 use crate::gpu::audio::{FootstepState, SoundResources, SoundModifiers, rand_simple};
 
+/// Громкость шагов крадучись (от обычной)
+const SNEAK_VOLUME: f32 = 0.3;
+
 /// Система обработки шагов
 pub fn footstep_system(
     audio: &mut AudioManager,
@@ -20,6 +23,7 @@ pub fn footstep_system(
     is_moving: bool,
     is_on_ground: bool,
     is_sprinting: bool,
+    is_sneaking: bool,
     modifiers: &SoundModifiers,
     dt: f32,
 ) {
@@ -53,17 +57,18 @@ pub fn footstep_system(
     if state.distance_traveled >= step_distance && state.time_since_last_step >= min_interval {
         state.distance_traveled = 0.0;
         state.time_since_last_step = 0.0;
-        play_footstep(audio, sounds, modifiers);
+        let volume = if is_sneaking { SNEAK_VOLUME } else { 1.0 };
+        play_footstep(audio, sounds, modifiers, volume);
     }
 }
 
 /// Воспроизвести звук шага
-fn play_footstep(audio: &mut AudioManager, sounds: &SoundResources, modifiers: &SoundModifiers) {
+fn play_footstep(audio: &mut AudioManager, sounds: &SoundResources, modifiers: &SoundModifiers, volume_scale: f32) {
     if let Some(ref sound_data) = sounds.footstep {
         let volume_variation = 0.85 + rand_simple() * 0.3;
         let pitch_variation = 0.92 + rand_simple() * 0.16;
         
-        let base_volume = 0.25 * volume_variation;
+        let base_volume = 0.25 * volume_variation * volume_scale;
         let base_pitch = pitch_variation;
         
         let (volume, pitch) = modifiers.apply(base_volume, base_pitch);
@@ -1,6 +1,9 @@
 // ============================================
 // Place Block System - Система установки блоков
 // ============================================
+// Звук зависит от материала блока (свой файл или сдвиг высоты общего),
+// суб-воксели звучат выше и тише полного блока. Высота и громкость
+// каждый раз немного другие, чтобы стройка не сливалась в один щелчок.
 
 use kira::{
     manager::AudioManager,
@@ -8,20 +11,42 @@ use kira::{
     Volume,
 };
 
-use crate::gpu::audio::{SoundResources, SoundModifiers, rand_simple};
+use crate::gpu::audio::{PlaceSoundState, SoundMaterial, SoundResources, SoundModifiers};
+use crate::gpu::blocks::{global_registry, BlockType};
+use crate::gpu::subvoxel::SubVoxelLevel;
+
+/// Разброс высоты и громкости (доля от базовых)
+const PITCH_JITTER: f32 = 0.08;
+const VOLUME_JITTER: f32 = 0.15;
 
 /// Воспроизвести звук установки блока
 pub fn play_place_block(
     audio: &mut AudioManager,
     sounds: &SoundResources,
+    state: &mut PlaceSoundState,
+    block: BlockType,
+    level: SubVoxelLevel,
     modifiers: &SoundModifiers,
 ) {
-    if let Some(ref sound_data) = sounds.place_block {
-        let volume_variation = 0.9 + rand_simple() * 0.2;
-        let pitch_variation = 0.95 + rand_simple() * 0.1;
+    let material = global_registry()
+        .read()
+        .ok()
+        .and_then(|registry| registry.get_by_numeric(block).map(SoundMaterial::of))
+        .unwrap_or(SoundMaterial::Stone);
+    
+    if let Some(sound_data) = sounds.place_sound(block, material) {
+        let (material_volume, material_pitch) = material.place_tone();
+        
+        // Чем меньше суб-воксель, тем выше и тише звук
+        let size = level.size();
+        let size_pitch = 1.0 + (1.0 - size) * 0.6;
+        let size_volume = 0.5 + size * 0.5;
+        
+        let pitch_variation = 1.0 + state.next_jitter() * PITCH_JITTER;
+        let volume_variation = 1.0 + (state.next_random() * 2.0 - 1.0) * VOLUME_JITTER;
         
-        let base_volume = 0.4 * volume_variation;
-        let base_pitch = pitch_variation;
+        let base_volume = material_volume * size_volume * volume_variation;
+        let base_pitch = material_pitch * size_pitch * pitch_variation;
         
         let (volume, pitch) = modifiers.apply(base_volume, base_pitch);
         
@@ -0,0 +1,122 @@
+// ============================================
+// Benchmark - Замер производительности по сценарию
+// ============================================
+// Запуск с --benchmark: мир с фиксированным сидом создаётся заново,
+// время суток стоит на полудне, камера облетает маршрут path вокруг
+// спавна. Каждый кадр пишется время кадра, сколько чанков пришло от
+// генератора и память; в конце - отчёт в benchmarks/ и выход. Отчёты
+// разных коммитов сравнимы между собой.
+
+mod path;
+mod report;
+
+use ultraviolet::Vec3;
+
+use crate::gpu::player::Camera;
+use crate::gpu::render::Renderer;
+
+pub use report::{summarize, write_report, FrameSample, Summary, BENCHMARK_DIR_NAME};
+
+/// Сид мира прогона
+pub const BENCHMARK_SEED: u64 = 20_240_601;
+/// Папка мира прогона в saves/ (пересоздаётся при каждом запуске)
+pub const BENCHMARK_WORLD: &str = "benchmark";
+
+/// Прогрев на первой точке маршрута: компиляция шейдеров и первые
+/// чанки не попадают в замер (секунды)
+const WARMUP: f32 = 3.0;
+/// Как часто читать память процесса (секунды)
+const MEMORY_INTERVAL: f32 = 0.5;
+
+/// Идущий прогон
+pub struct Benchmark {
+    /// Спавн, от которого строится маршрут (задаётся в первом кадре)
+    origin: Option<Vec3>,
+    /// Секунды от первого кадра (с прогревом)
+    elapsed: f32,
+    /// Чанков получено к концу прогрева
+    chunks_at_start: u64,
+    memory_timer: f32,
+    process_memory: Option<u64>,
+    samples: Vec<FrameSample>,
+}
+
+impl Benchmark {
+    pub fn new() -> Self {
+        Self {
+            origin: None,
+            elapsed: 0.0,
+            chunks_at_start: 0,
+            memory_timer: 0.0,
+            process_memory: None,
+            samples: Vec::new(),
+        }
+    }
+
+    /// Кадр прогона: камера на маршруте и замер прошлого кадра.
+    /// true - маршрут пройден, пора писать отчёт
+    pub fn frame(&mut self, dt: f32, spawn: Vec3, camera: &mut Camera, renderer: &mut Renderer) -> bool {
+        let origin = *self.origin.get_or_insert_with(|| {
+            renderer.set_time_of_day(0.5);
+            renderer.set_time_speed(0.0);
+            log::info!("Benchmark: сид {}, маршрут {:.0} с", BENCHMARK_SEED, path::duration());
+            spawn
+        });
+
+        self.elapsed += dt;
+        let time = self.elapsed - WARMUP;
+        if time < 0.0 {
+            self.chunks_at_start = renderer.chunks_received();
+        } else {
+            self.memory_timer -= dt;
+            if self.memory_timer <= 0.0 {
+                self.memory_timer = MEMORY_INTERVAL;
+                self.process_memory = process_memory_bytes();
+            }
+            self.samples.push(FrameSample {
+                time,
+                frame_ms: dt * 1000.0,
+                chunks: renderer.chunks_received() - self.chunks_at_start,
+                mesh_memory: renderer.mesh_memory_bytes(),
+                process_memory: self.process_memory,
+            });
+        }
+
+        let (position, forward) = path::sample(origin, time);
+        camera.set_look(position, forward);
+        time >= path::duration()
+    }
+
+    /// Записать отчёт и вывести сводку в журнал
+    pub fn finish(&self) {
+        let summary = summarize(&self.samples);
+        log::info!(
+            "Benchmark: {} кадров, {:.1} FPS, p95 {:.2} мс, p99 {:.2} мс, {:.1} чанков/с",
+            summary.frames, summary.avg_fps, summary.p95_ms, summary.p99_ms, summary.chunks_per_s
+        );
+        match write_report(&self.samples, BENCHMARK_SEED) {
+            Ok(path) => log::info!("Отчёт benchmark: {}", path.display()),
+            Err(e) => log::error!("Отчёт benchmark не записан: {}", e),
+        }
+    }
+}
+
+impl Default for Benchmark {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Резидентная память процесса (Linux: /proc/self/statm)
+#[cfg(target_os = "linux")]
+fn process_memory_bytes() -> Option<u64> {
+    const PAGE_SIZE: u64 = 4096;
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    let resident: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    Some(resident * PAGE_SIZE)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_memory_bytes() -> Option<u64> {
+    None
+}
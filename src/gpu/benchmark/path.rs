@@ -0,0 +1,79 @@
+// ============================================
+// Benchmark Path - Заданный маршрут камеры
+// ============================================
+// Замкнутый сплайн Catmull-Rom через фиксированные точки вокруг
+// спавна. В отличие от демо-облёта точки не выбираются по рельефу:
+// маршрут зависит только от времени, и каждый прогон видит те же
+// кадры. Высоты абсолютные - маршрут проходит над горами, у воды и
+// ныряет к рельефу, чтобы нагрузить и дальние LOD, и воксельные чанки.
+
+use ultraviolet::Vec3;
+
+use crate::gpu::player::{catmull_rom, catmull_rom_tangent};
+
+/// Точки маршрута: смещение от спавна по x/z и абсолютная высота
+const WAYPOINTS: [[f32; 3]; 8] = [
+    [0.0, 100.0, 0.0],
+    [160.0, 90.0, 40.0],
+    [260.0, 70.0, 200.0],
+    [120.0, 110.0, 340.0],
+    [-80.0, 80.0, 260.0],
+    [-240.0, 120.0, 120.0],
+    [-200.0, 75.0, -60.0],
+    [-60.0, 95.0, -120.0],
+];
+
+/// Время на отрезок между точками (секунды)
+const SEGMENT_TIME: f32 = 8.0;
+/// Наклон взгляда вниз на ландшафт
+const LOOK_DOWN: f32 = 0.25;
+
+/// Длительность маршрута (секунды)
+pub fn duration() -> f32 {
+    WAYPOINTS.len() as f32 * SEGMENT_TIME
+}
+
+/// Позиция и направление камеры через time секунд от старта.
+/// origin - спавн (берутся только x и z)
+pub fn sample(origin: Vec3, time: f32) -> (Vec3, Vec3) {
+    let count = WAYPOINTS.len();
+    let progress = time.clamp(0.0, duration()) / SEGMENT_TIME;
+    let segment = (progress.floor() as usize).min(count - 1);
+    let t = progress - segment as f32;
+
+    let point = |offset: usize| {
+        let [x, y, z] = WAYPOINTS[(segment + count + offset - 1) % count];
+        Vec3::new(origin.x + x, y, origin.z + z)
+    };
+    let (p0, p1, p2, p3) = (point(0), point(1), point(2), point(3));
+    let position = catmull_rom(p0, p1, p2, p3, t);
+    let tangent = catmull_rom_tangent(p0, p1, p2, p3, t);
+
+    let look = Vec3::new(tangent.x, 0.0, tangent.z);
+    let forward = if look.mag_sq() > 0.0001 {
+        (look.normalized() + Vec3::new(0.0, -LOOK_DOWN, 0.0)).normalized()
+    } else {
+        Vec3::unit_z()
+    };
+    (position, forward)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_is_closed_and_passes_waypoints() {
+        let origin = Vec3::new(1000.0, 64.0, -500.0);
+        let (start, _) = sample(origin, 0.0);
+        let (end, _) = sample(origin, duration());
+        assert!((start - Vec3::new(1000.0, 100.0, -500.0)).mag() < 1e-3);
+        assert!((start - end).mag() < 1e-3);
+
+        let (second, forward) = sample(origin, SEGMENT_TIME);
+        assert!((second - Vec3::new(1160.0, 90.0, -460.0)).mag() < 1e-3);
+        assert!(forward.y < 0.0);
+        // За концом маршрута камера стоит в конце
+        assert_eq!(sample(origin, duration() + 5.0).0, end);
+    }
+}
@@ -0,0 +1,161 @@
+// ============================================
+// Benchmark Report - Отчёт прогона (CSV + JSON)
+// ============================================
+// CSV - строка на кадр (для графиков), JSON - сводка: перцентили
+// времени кадра, скорость стриминга чанков и пик памяти. Оба файла
+// с одним именем по времени запуска в config_dir()/benchmarks/.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use serde_json::json;
+
+use crate::gpu::core::config_dir;
+use crate::gpu::render::timestamp_name;
+
+/// Папка отчётов в папке конфигурации
+pub const BENCHMARK_DIR_NAME: &str = "benchmarks";
+
+const MIB: f64 = 1024.0 * 1024.0;
+
+/// Замер одного кадра
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameSample {
+    /// Секунды от начала записи
+    pub time: f32,
+    pub frame_ms: f32,
+    /// Чанков террейна получено от генератора с начала записи
+    pub chunks: u64,
+    /// Память мешей террейна на GPU
+    pub mesh_memory: u64,
+    /// Память процесса (None - платформа не сообщает)
+    pub process_memory: Option<u64>,
+}
+
+/// Сводка прогона
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Summary {
+    pub frames: usize,
+    pub duration_s: f32,
+    pub avg_fps: f32,
+    pub avg_ms: f32,
+    pub p50_ms: f32,
+    pub p95_ms: f32,
+    pub p99_ms: f32,
+    pub max_ms: f32,
+    pub chunks: u64,
+    pub chunks_per_s: f32,
+    pub peak_mesh_memory_mib: f64,
+    pub peak_process_memory_mib: Option<f64>,
+}
+
+/// Перцентиль по ближайшему рангу (sorted - по возрастанию)
+fn percentile(sorted: &[f32], p: f32) -> f32 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (p / 100.0 * sorted.len() as f32).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Сводка по кадрам
+pub fn summarize(samples: &[FrameSample]) -> Summary {
+    let mut times: Vec<f32> = samples.iter().map(|sample| sample.frame_ms).collect();
+    times.sort_by(f32::total_cmp);
+    let total_ms: f32 = times.iter().sum();
+    let duration_s = total_ms / 1000.0;
+    let chunks = samples.last().map_or(0, |sample| sample.chunks);
+
+    Summary {
+        frames: samples.len(),
+        duration_s,
+        avg_fps: if total_ms > 0.0 { samples.len() as f32 / duration_s } else { 0.0 },
+        avg_ms: if times.is_empty() { 0.0 } else { total_ms / times.len() as f32 },
+        p50_ms: percentile(&times, 50.0),
+        p95_ms: percentile(&times, 95.0),
+        p99_ms: percentile(&times, 99.0),
+        max_ms: times.last().copied().unwrap_or(0.0),
+        chunks,
+        chunks_per_s: if duration_s > 0.0 { chunks as f32 / duration_s } else { 0.0 },
+        peak_mesh_memory_mib: samples.iter().map(|sample| sample.mesh_memory).max().unwrap_or(0) as f64 / MIB,
+        peak_process_memory_mib: samples.iter().filter_map(|sample| sample.process_memory).max().map(|bytes| bytes as f64 / MIB),
+    }
+}
+
+/// CSV по кадрам
+fn frames_csv(samples: &[FrameSample]) -> String {
+    let mut csv = String::from("frame,time_s,frame_ms,chunks,mesh_memory_mib,process_memory_mib\n");
+    for (i, sample) in samples.iter().enumerate() {
+        let process = sample.process_memory.map_or_else(String::new, |bytes| format!("{:.1}", bytes as f64 / MIB));
+        let _ = writeln!(
+            csv,
+            "{},{:.3},{:.3},{},{:.1},{}",
+            i,
+            sample.time,
+            sample.frame_ms,
+            sample.chunks,
+            sample.mesh_memory as f64 / MIB,
+            process,
+        );
+    }
+    csv
+}
+
+/// Записать benchmarks/<время>.csv и .json; возвращает путь JSON
+pub fn write_report(samples: &[FrameSample], seed: u64) -> Result<PathBuf, String> {
+    let dir = config_dir().join(BENCHMARK_DIR_NAME);
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs());
+    let name = timestamp_name(secs);
+
+    let csv_path = dir.join(format!("{}.csv", name));
+    std::fs::write(&csv_path, frames_csv(samples)).map_err(|e| e.to_string())?;
+
+    let json_path = dir.join(format!("{}.json", name));
+    write_summary(&json_path, samples, seed, &csv_path)?;
+    Ok(json_path)
+}
+
+fn write_summary(path: &Path, samples: &[FrameSample], seed: u64, csv_path: &Path) -> Result<(), String> {
+    let report = json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "seed": seed,
+        "frames_csv": csv_path.file_name().map(|name| name.to_string_lossy()),
+        "summary": summarize(samples),
+    });
+    let text = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+    std::fs::write(path, text).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(time: f32, frame_ms: f32, chunks: u64) -> FrameSample {
+        FrameSample { time, frame_ms, chunks, mesh_memory: 2 * 1024 * 1024, process_memory: None }
+    }
+
+    #[test]
+    fn summary_uses_nearest_rank_percentiles() {
+        let samples: Vec<FrameSample> = (1..=100).map(|i| sample(i as f32 / 100.0, i as f32, i as u64 * 2)).collect();
+        let summary = summarize(&samples);
+        assert_eq!(summary.frames, 100);
+        assert_eq!(summary.p50_ms, 50.0);
+        assert_eq!(summary.p95_ms, 95.0);
+        assert_eq!(summary.max_ms, 100.0);
+        assert_eq!(summary.chunks, 200);
+        assert_eq!(summary.peak_mesh_memory_mib, 2.0);
+        assert_eq!(summary.peak_process_memory_mib, None);
+        assert_eq!(summarize(&[]).avg_fps, 0.0);
+    }
+
+    #[test]
+    fn csv_has_row_per_frame() {
+        let csv = frames_csv(&[sample(0.0, 16.5, 0), sample(0.016, 17.0, 4)]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[2], "1,0.016,17.000,4,2.0,");
+    }
+}
@@ -0,0 +1,152 @@
+// ============================================
+// Biome Blend - Плавные переходы между биомами
+// ============================================
+// Биом считается в узлах решётки с шагом BIOME_BLEND_CELL, а в точке
+// берётся взвешенная смесь четырёх соседних узлов (билинейно + smoothstep).
+// На границе получается несколько биомов с весами, переход тянется
+// на ширину ячейки:
+// - высота: взвешенная сумма высот биомов
+// - поверхностный блок: дизеринг по весам (хеш столбца)
+// - оттенок травы: взвешенная сумма оттенков
+//
+// Узлы решётки кэшируются per-thread: генерация чанка опрашивает
+// одни и те же узлы для всех столбцов и высот.
+
+use std::cell::RefCell;
+
+use super::types::*;
+use super::climate::climate_map;
+use super::registry::biome_registry;
+use super::selector::BiomeSelector;
+
+/// Шаг решётки смешивания (блоки) - ширина перехода на границе биомов
+pub const BIOME_BLEND_CELL: f32 = 8.0;
+
+/// Максимум биомов в смеси (по числу узлов ячейки)
+const MAX_BLEND: usize = 4;
+/// Размер кэша узлов (степень двойки)
+const LATTICE_CACHE_SIZE: usize = 1024;
+
+thread_local! {
+    /// Кэш биомов в узлах решётки: (x, z, биом)
+    static LATTICE_CACHE: RefCell<Vec<(i32, i32, BiomeId)>> =
+        RefCell::new(vec![(i32::MIN, i32::MIN, 0); LATTICE_CACHE_SIZE]);
+}
+
+/// Смесь биомов в точке
+#[derive(Clone, Copy, Debug)]
+pub struct BiomeBlend {
+    biomes: [BiomeId; MAX_BLEND],
+    weights: [f32; MAX_BLEND],
+    count: usize,
+}
+
+impl BiomeBlend {
+    fn empty() -> Self {
+        Self {
+            biomes: [0; MAX_BLEND],
+            weights: [0.0; MAX_BLEND],
+            count: 0,
+        }
+    }
+
+    fn add(&mut self, biome: BiomeId, weight: f32) {
+        if weight <= 0.0 {
+            return;
+        }
+        if let Some(i) = self.biomes[..self.count].iter().position(|&b| b == biome) {
+            self.weights[i] += weight;
+        } else {
+            self.biomes[self.count] = biome;
+            self.weights[self.count] = weight;
+            self.count += 1;
+        }
+    }
+
+    /// Один биом без перехода (внутренняя часть биома)
+    #[inline]
+    pub fn is_uniform(&self) -> bool {
+        self.count == 1
+    }
+
+    /// Биомы и их веса (сумма весов = 1)
+    pub fn iter(&self) -> impl Iterator<Item = (BiomeId, f32)> + '_ {
+        self.biomes[..self.count].iter().copied().zip(self.weights[..self.count].iter().copied())
+    }
+
+    /// Выбор биома с вероятностью по весу (roll 0-1) - дизеринг границы
+    pub fn pick(&self, roll: f32) -> BiomeId {
+        let mut acc = 0.0;
+        for (biome, weight) in self.iter() {
+            acc += weight;
+            if roll < acc {
+                return biome;
+            }
+        }
+        self.biomes[self.count - 1]
+    }
+
+    /// Взвешенный оттенок травы
+    pub fn grass_tint(&self) -> [f32; 3] {
+        let registry = biome_registry();
+        let mut tint = [0.0; 3];
+        for (biome, weight) in self.iter() {
+            let t = registry.get(biome).grass_tint;
+            tint[0] += t[0] * weight;
+            tint[1] += t[1] * weight;
+            tint[2] += t[2] * weight;
+        }
+        tint
+    }
+}
+
+impl BiomeSelector {
+    /// Смесь биомов в точке (веса соседних узлов решётки)
+    pub fn get_biome_blend(&self, x: f32, z: f32) -> BiomeBlend {
+        let gx = x / BIOME_BLEND_CELL;
+        let gz = z / BIOME_BLEND_CELL;
+        let cx = gx.floor() as i32;
+        let cz = gz.floor() as i32;
+        let fx = smoothstep(gx - cx as f32);
+        let fz = smoothstep(gz - cz as f32);
+
+        let mut blend = BiomeBlend::empty();
+        blend.add(self.lattice_biome(cx, cz), (1.0 - fx) * (1.0 - fz));
+        blend.add(self.lattice_biome(cx + 1, cz), fx * (1.0 - fz));
+        blend.add(self.lattice_biome(cx, cz + 1), (1.0 - fx) * fz);
+        blend.add(self.lattice_biome(cx + 1, cz + 1), fx * fz);
+
+        // Точно в узле все веса, кроме одного, нулевые
+        if blend.count == 0 {
+            blend.add(self.lattice_biome(cx, cz), 1.0);
+        }
+        blend
+    }
+
+    /// Оттенок травы в точке с учётом переходов
+    #[inline]
+    pub fn grass_tint(&self, x: f32, z: f32) -> [f32; 3] {
+        self.get_biome_blend(x, z).grass_tint()
+    }
+
+    /// Биом в узле решётки (с кэшем)
+    fn lattice_biome(&self, cx: i32, cz: i32) -> BiomeId {
+        let slot = (cx.wrapping_mul(73_856_093) ^ cz.wrapping_mul(19_349_663)) as usize & (LATTICE_CACHE_SIZE - 1);
+        LATTICE_CACHE.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            let (kx, kz, biome) = cache[slot];
+            if kx == cx && kz == cz {
+                return biome;
+            }
+            let climate = climate_map().sample(cx as f32 * BIOME_BLEND_CELL, cz as f32 * BIOME_BLEND_CELL);
+            let biome = self.select_from_climate(&climate);
+            cache[slot] = (cx, cz, biome);
+            biome
+        })
+    }
+}
+
+#[inline]
+fn smoothstep(t: f32) -> f32 {
+    t * t * (3.0 - 2.0 * t)
+}
@@ -4,20 +4,30 @@
 
 use super::types::ClimateData;
 use crate::gpu::terrain::generation::noise::{fbm2d, noise2d};
+use crate::gpu::terrain::generation::world_gen_config;
 
 /// Генератор климатической карты
 pub struct ClimateMap {
     seed: u64,
+    /// Масштаб биомов (координаты делятся на него)
+    scale: f32,
 }
 
 impl ClimateMap {
     pub fn new(seed: u64) -> Self {
-        Self { seed }
+        Self { seed, scale: 1.0 }
+    }
+
+    /// Задать масштаб биомов
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
     }
 
     /// Получить климатические данные для координат
     #[inline]
     pub fn sample(&self, x: f32, z: f32) -> ClimateData {
+        let (x, z) = (x / self.scale, z / self.scale);
         let seed_offset = self.seed as f32 * 0.1;
         
         // Температура - крупномасштабный шум
@@ -63,6 +73,7 @@ impl ClimateMap {
     /// Быстрая версия для LOD
     #[inline]
     pub fn sample_fast(&self, x: f32, z: f32) -> ClimateData {
+        let (x, z) = (x / self.scale, z / self.scale);
         let seed_offset = self.seed as f32 * 0.1;
         
         let temperature = noise2d(
@@ -99,5 +110,8 @@ use std::sync::OnceLock;
 static CLIMATE_MAP: OnceLock<ClimateMap> = OnceLock::new();
 
 pub fn climate_map() -> &'static ClimateMap {
-    CLIMATE_MAP.get_or_init(ClimateMap::default)
+    CLIMATE_MAP.get_or_init(|| {
+        let config = world_gen_config();
+        ClimateMap::new(config.climate_seed()).with_scale(config.biome_scale)
+    })
 }
@@ -0,0 +1,180 @@
+// ============================================
+// Decorations - Мелкие декорации поверхности
+// ============================================
+// Трава, цветы, камешки, сухие кусты. Не запекаются в меш чанка,
+// а рисуются инстансингом (см. render::instanced)
+
+use std::collections::HashMap;
+
+use crate::gpu::blocks::{BlockType, AIR, GRASS, SAND, RED_SAND, STONE, SNOW};
+use crate::gpu::terrain::generation::{get_height, hash3d, terrain_generator, world_gen_config};
+use crate::gpu::terrain::voxel::CHUNK_SIZE;
+use crate::gpu::terrain::BlockPos;
+use crate::gpu::terrain::cache::column_biome;
+
+/// Тип декорации (один меш на тип)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DecorationKind {
+    GrassTuft,
+    Flower,
+    Pebble,
+    DeadBush,
+}
+
+impl DecorationKind {
+    pub const COUNT: usize = 4;
+    pub const ALL: [DecorationKind; Self::COUNT] = [
+        DecorationKind::GrassTuft,
+        DecorationKind::Flower,
+        DecorationKind::Pebble,
+        DecorationKind::DeadBush,
+    ];
+
+    #[inline]
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Размещение одной декорации в мире
+#[derive(Clone, Copy, Debug)]
+pub struct DecorationPlacement {
+    pub kind: DecorationKind,
+    /// Позиция основания (центр верхней грани блока поверхности)
+    pub position: [f32; 3],
+    /// Поворот вокруг Y (радианы)
+    pub rotation: f32,
+    /// Масштаб
+    pub scale: f32,
+    /// Цветовой оттенок
+    pub tint: [f32; 3],
+}
+
+/// Поверхность столбца открыта небу: не под водой и над ней воздух
+#[inline]
+fn is_open_surface(height: i32, sea_level: i32, above: BlockType) -> bool {
+    height >= sea_level && above == AIR
+}
+
+/// Генерирует декорации для чанка
+/// Колонки с изменениями мира на поверхности и подводные пропускаются
+pub fn scatter_chunk_decorations(
+    chunk_x: i32,
+    chunk_z: i32,
+    world_changes: &HashMap<BlockPos, BlockType>,
+) -> Vec<DecorationPlacement> {
+    // Плоский и пустой миры - без травы и цветов
+    if !terrain_generator().has_features() {
+        return Vec::new();
+    }
+
+    let generator = terrain_generator();
+    let sea_level = world_gen_config().sea_level;
+    let mut placements = Vec::new();
+    let base_x = chunk_x * CHUNK_SIZE;
+    let base_z = chunk_z * CHUNK_SIZE;
+
+    for lz in 0..CHUNK_SIZE {
+        for lx in 0..CHUNK_SIZE {
+            let world_x = base_x + lx;
+            let world_z = base_z + lz;
+            let height = get_height(world_x as f32, world_z as f32) as i32;
+
+            // Игрок мог сломать поверхность или поставить блок сверху
+            let surface = BlockPos::new(world_x, height, world_z);
+            let above = BlockPos::new(world_x, height + 1, world_z);
+            if world_changes.contains_key(&surface) || world_changes.contains_key(&above) {
+                continue;
+            }
+            // Дно океанов и озёр, навесы 3D-рельефа
+            if !is_open_surface(height, sea_level, generator.block_at(world_x, height + 1, world_z)) {
+                continue;
+            }
+
+            let biome = column_biome(world_x, world_z);
+            let rng = hash3d(world_x, height, world_z);
+            let jitter_x = hash3d(world_x, height + 17, world_z);
+            let jitter_z = hash3d(world_x, height + 31, world_z);
+
+            // Сухие кусты - на любой поверхности сухих биомов
+            let kind = if hash3d(world_x, height + 53, world_z) < biome.dead_bush_density {
+                DecorationKind::DeadBush
+            } else {
+                match biome.surface_block {
+                    GRASS => {
+                        if rng < 0.015 {
+                            DecorationKind::Flower
+                        } else if rng < 0.015 + biome.grass_density {
+                            DecorationKind::GrassTuft
+                        } else {
+                            continue;
+                        }
+                    }
+                    SAND | RED_SAND | STONE | SNOW => {
+                        if rng < 0.02 {
+                            DecorationKind::Pebble
+                        } else {
+                            continue;
+                        }
+                    }
+                    _ => continue,
+                }
+            };
+
+            let tint = match kind {
+                DecorationKind::GrassTuft => [0.35 + jitter_x * 0.1, 0.65 + jitter_z * 0.15, 0.25],
+                DecorationKind::Flower => {
+                    // Несколько фиксированных цветов лепестков
+                    match (rng * 1000.0) as i32 % 3 {
+                        0 => [0.9, 0.2, 0.2],
+                        1 => [0.95, 0.85, 0.2],
+                        _ => [0.6, 0.4, 0.9],
+                    }
+                }
+                DecorationKind::Pebble => [0.55 + jitter_x * 0.1; 3],
+                DecorationKind::DeadBush => [0.5 + jitter_x * 0.08, 0.36 + jitter_z * 0.06, 0.2],
+            };
+
+            placements.push(DecorationPlacement {
+                kind,
+                position: [
+                    world_x as f32 + 0.2 + jitter_x * 0.6,
+                    height as f32 + 1.0,
+                    world_z as f32 + 0.2 + jitter_z * 0.6,
+                ],
+                rotation: rng * std::f32::consts::TAU * 37.0 % std::f32::consts::TAU,
+                scale: 0.8 + jitter_z * 0.4,
+                tint,
+            });
+        }
+    }
+
+    placements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::blocks::WATER;
+
+    #[test]
+    fn underwater_columns_are_not_decorated() {
+        let sea_level = 10;
+        assert!(is_open_surface(10, sea_level, AIR));
+        // Дно под водой
+        assert!(!is_open_surface(4, sea_level, AIR));
+        assert!(!is_open_surface(9, sea_level, WATER));
+        // Сверху блок
+        assert!(!is_open_surface(30, sea_level, STONE));
+
+        let sea_level = world_gen_config().sea_level;
+        let changes = HashMap::new();
+        for (chunk_x, chunk_z) in [(0, 0), (7, -3), (-12, 20), (40, 40)] {
+            for placement in scatter_chunk_decorations(chunk_x, chunk_z, &changes) {
+                let [x, y, z] = placement.position;
+                let height = get_height(x.floor(), z.floor()) as i32;
+                assert!(height >= sea_level, "декорация на дне в ({}, {}, {})", x, y, z);
+            }
+        }
+    }
+}
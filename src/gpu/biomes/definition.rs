@@ -0,0 +1,194 @@
+// ============================================
+// Data-Driven Biome Definition
+// ============================================
+// Структуры для загрузки биомов из JSON (assets/biomes).
+// Блоки указываются строковыми ID реестра блоков.
+
+use serde::Deserialize;
+
+use crate::gpu::blocks::{global_registry, BlockType};
+use super::features::{FeatureKind, FeatureRule, TreeType};
+use super::types::{BiomeDefinition, ClimateRange, TerrainType, ValueRange};
+
+/// Файл с биомами
+#[derive(Debug, Deserialize)]
+pub struct BiomesFile {
+    pub biomes: Vec<BiomeDef>,
+}
+
+/// Биом в JSON
+#[derive(Debug, Deserialize)]
+pub struct BiomeDef {
+    /// Уникальный строковый ID
+    pub id: String,
+    /// Числовой ID (хранится в кэшах столбцов)
+    pub numeric_id: u8,
+    /// Порядок проверки климата: больший - раньше
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub climate: ClimateDef,
+    pub blocks: BiomeBlocksDef,
+    pub terrain: TerrainDef,
+    #[serde(default)]
+    pub trees: TreesDef,
+    /// Прочие структуры: [{"type": "cactus", "density": 0.004}]
+    #[serde(default)]
+    pub features: Vec<FeatureDef>,
+    #[serde(default)]
+    pub decorations: DecorationsDef,
+    #[serde(default)]
+    pub colors: BiomeColorsDef,
+}
+
+/// Диапазон параметра: нет границы - не ограничен
+#[derive(Debug, Default, Deserialize)]
+pub struct RangeDef {
+    pub min: Option<f32>,
+    pub max: Option<f32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ClimateDef {
+    #[serde(default)]
+    pub temperature: RangeDef,
+    #[serde(default)]
+    pub humidity: RangeDef,
+    #[serde(default)]
+    pub continentalness: RangeDef,
+    #[serde(default)]
+    pub erosion: RangeDef,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BiomeBlocksDef {
+    pub surface: String,
+    pub subsurface: String,
+    #[serde(default = "default_deep_block")]
+    pub deep: String,
+    /// Слои породы снизу вверх (повтор блока - толще слой)
+    #[serde(default)]
+    pub strata: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TerrainDef {
+    pub style: TerrainType,
+    pub base_height: f32,
+    pub height_variation: f32,
+    /// Сила 3D шума (только для mountains_3d)
+    #[serde(default)]
+    pub noise_3d: f32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TreesDef {
+    #[serde(default)]
+    pub density: f32,
+    #[serde(default = "default_tree_types")]
+    pub types: Vec<TreeType>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FeatureDef {
+    #[serde(rename = "type")]
+    pub kind: FeatureKind,
+    pub density: f32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DecorationsDef {
+    #[serde(default = "default_grass_density")]
+    pub grass_density: f32,
+    #[serde(default)]
+    pub dead_bush_density: f32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BiomeColorsDef {
+    #[serde(default = "default_grass_tint")]
+    pub grass_tint: [f32; 3],
+}
+
+fn default_deep_block() -> String { "stone".to_string() }
+fn default_tree_types() -> Vec<TreeType> { vec![TreeType::Oak] }
+fn default_grass_density() -> f32 { 0.04 }
+fn default_grass_tint() -> [f32; 3] { [1.0, 1.0, 1.0] }
+
+impl Default for TreesDef {
+    fn default() -> Self {
+        Self { density: 0.0, types: default_tree_types() }
+    }
+}
+
+impl Default for DecorationsDef {
+    fn default() -> Self {
+        Self { grass_density: default_grass_density(), dead_bush_density: 0.0 }
+    }
+}
+
+impl Default for BiomeColorsDef {
+    fn default() -> Self {
+        Self { grass_tint: default_grass_tint() }
+    }
+}
+
+impl RangeDef {
+    fn resolve(&self, biome: &str, name: &str) -> Result<ValueRange, String> {
+        let range = ValueRange {
+            min: self.min.unwrap_or(f32::NEG_INFINITY),
+            max: self.max.unwrap_or(f32::INFINITY),
+        };
+        if range.min >= range.max {
+            return Err(format!("biome '{}': empty {} range", biome, name));
+        }
+        Ok(range)
+    }
+}
+
+impl BiomeDef {
+    /// Проверить и перевести в определение (блоки - через реестр блоков)
+    pub fn resolve(self) -> Result<BiomeDefinition, String> {
+        let climate = ClimateRange {
+            temperature: self.climate.temperature.resolve(&self.id, "temperature")?,
+            humidity: self.climate.humidity.resolve(&self.id, "humidity")?,
+            continentalness: self.climate.continentalness.resolve(&self.id, "continentalness")?,
+            erosion: self.climate.erosion.resolve(&self.id, "erosion")?,
+        };
+        let block = |id: &str| resolve_biome_block(&self.id, id);
+        let strata = self.blocks.strata.iter().map(|id| block(id)).collect::<Result<Vec<_>, _>>()?;
+        let features = self.features
+            .iter()
+            .map(|feature| FeatureRule { kind: feature.kind, density: feature.density.max(0.0) })
+            .collect();
+        let tree_types = if self.trees.types.is_empty() { default_tree_types() } else { self.trees.types };
+
+        Ok(BiomeDefinition {
+            id: self.numeric_id,
+            priority: self.priority,
+            climate,
+            surface_block: block(&self.blocks.surface)?,
+            subsurface_block: block(&self.blocks.subsurface)?,
+            deep_block: block(&self.blocks.deep)?,
+            strata,
+            base_height: self.terrain.base_height,
+            height_variation: self.terrain.height_variation,
+            terrain_type: self.terrain.style,
+            noise_3d_strength: self.terrain.noise_3d.clamp(0.0, 1.0),
+            tree_density: self.trees.density.max(0.0),
+            tree_types,
+            features,
+            grass_density: self.decorations.grass_density.max(0.0),
+            dead_bush_density: self.decorations.dead_bush_density.max(0.0),
+            grass_tint: self.colors.grass_tint,
+            name: self.id,
+        })
+    }
+}
+
+fn resolve_biome_block(biome: &str, id: &str) -> Result<BlockType, String> {
+    let registry = global_registry().read().map_err(|_| "block registry lock poisoned".to_string())?;
+    registry
+        .get_numeric_id(id)
+        .ok_or_else(|| format!("biome '{}': unknown block '{}'", biome, id))
+}
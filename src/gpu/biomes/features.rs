@@ -1,20 +1,145 @@
 // ============================================
 // Biome Features - Генерация структур (деревья)
 // ============================================
+// Кроме деревьев - кактусы и огромные грибы (features в JSON биома)
 
 use std::collections::HashMap;
-use crate::gpu::blocks::{BlockType, AIR, OAK_LOG, OAK_LEAVES, BIRCH_LOG, BIRCH_LEAVES, SPRUCE_LOG, SPRUCE_LEAVES};
+use serde::Deserialize;
+use crate::gpu::blocks::{BlockType, AIR, GRASS, DIRT, SAND, RED_SAND, MYCELIUM, MUD, OAK_LOG, OAK_LEAVES, BIRCH_LOG, BIRCH_LEAVES,
+    SPRUCE_LOG, SPRUCE_LEAVES, CACTUS, MUSHROOM_STEM, RED_MUSHROOM_BLOCK, BROWN_MUSHROOM_BLOCK, is_sapling};
 use crate::gpu::terrain::voxel::constants::{CHUNK_SIZE, MIN_HEIGHT, WORLD_HEIGHT};
+use crate::gpu::terrain::voxel::SectionColumn;
+use crate::gpu::terrain::generation::hash3d;
 use crate::gpu::terrain::BlockPos;
+use crate::gpu::terrain::cache::column_biome;
+use crate::gpu::biomes::biome_registry;
 
 /// Тип дерева
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum TreeType {
     Oak,
     Birch,
     Spruce,
 }
 
+impl TreeType {
+    pub const COUNT: usize = 3;
+    pub const ALL: [TreeType; Self::COUNT] = [TreeType::Oak, TreeType::Birch, TreeType::Spruce];
+
+    #[inline]
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Тип дерева для биома (та же логика, что в воксельном проходе)
+    pub fn for_biome(biome_id: u8, rng: f32) -> Self {
+        biome_registry().get(biome_id).tree_type(rng)
+    }
+}
+
+/// Структура биома, кроме деревьев
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeatureKind {
+    Cactus,
+    HugeRedMushroom,
+    HugeBrownMushroom,
+}
+
+impl FeatureKind {
+    /// Может ли структура стоять на этом блоке
+    pub fn fits_on(self, surface: BlockType) -> bool {
+        match self {
+            FeatureKind::Cactus => matches!(surface, SAND | RED_SAND),
+            FeatureKind::HugeRedMushroom | FeatureKind::HugeBrownMushroom => {
+                matches!(surface, MYCELIUM | GRASS | DIRT | MUD)
+            }
+        }
+    }
+
+    /// Радиус по горизонтали: структура ставится, только если
+    /// целиком помещается в чанк (шляпка не обрезается на границе)
+    pub fn radius(self) -> i32 {
+        match self {
+            FeatureKind::Cactus => 0,
+            FeatureKind::HugeRedMushroom => 2,
+            FeatureKind::HugeBrownMushroom => 3,
+        }
+    }
+
+    /// Высота по случайному числу столбца
+    pub fn height(self, rng: f32) -> i32 {
+        let variant = (rng * 10_000.0) as i32 % 3;
+        match self {
+            FeatureKind::Cactus => 1 + variant,
+            FeatureKind::HugeRedMushroom | FeatureKind::HugeBrownMushroom => 5 + variant,
+        }
+    }
+}
+
+/// Структура и доля столбцов биома, где она стоит
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FeatureRule {
+    pub kind: FeatureKind,
+    pub density: f32,
+}
+
+/// Дерево-импостор для дальних LOD (billboard вместо блоков)
+#[derive(Clone, Copy, Debug)]
+pub struct TreeImpostor {
+    /// Позиция основания ствола
+    pub position: [f32; 3],
+    /// Высота ствола в блоках
+    pub height: f32,
+    pub tree_type: TreeType,
+}
+
+/// Выборка деревьев для LOD чанка
+/// Колонки берутся с шагом scale, поэтому вероятность дерева
+/// умножается на площадь ячейки - плотность леса сохраняется
+pub fn sample_lod_trees(
+    base_x: i32,
+    base_z: i32,
+    scale: i32,
+    height_at: impl Fn(i32, i32) -> f32,
+) -> Vec<TreeImpostor> {
+    let mut trees = Vec::new();
+    let cell_area = (scale * scale) as f32;
+
+    for lz in 0..CHUNK_SIZE {
+        for lx in 0..CHUNK_SIZE {
+            let world_x = base_x + lx * scale;
+            let world_z = base_z + lz * scale;
+
+            let biome = column_biome(world_x, world_z);
+            if biome.tree_density <= 0.0001 || !matches!(biome.surface_block, GRASS | DIRT) {
+                continue;
+            }
+
+            let terrain_height = height_at(lx, lz);
+            let rng = hash3d(world_x, terrain_height as i32, world_z);
+            let density = (biome.tree_density * cell_area).min(0.6);
+            if rng >= density {
+                continue;
+            }
+
+            // Случайный сдвиг внутри ячейки, чтобы не было сетки
+            let jitter_x = hash3d(world_x, 7, world_z) * (scale - 1) as f32;
+            let jitter_z = hash3d(world_x, 13, world_z) * (scale - 1) as f32;
+            let tree_height = 5 + ((rng * 1000.0) as i32 % 3);
+
+            trees.push(TreeImpostor {
+                position: [world_x as f32 + 0.5 + jitter_x, terrain_height, world_z as f32 + 0.5 + jitter_z],
+                height: tree_height as f32,
+                tree_type: TreeType::for_biome(biome.id, rng),
+            });
+        }
+    }
+
+    trees
+}
+
 /// Данные для размещения субвокселя листвы (для экспорта)
 #[derive(Clone, Copy)]
 pub struct LeafSubVoxel {
@@ -24,9 +149,21 @@ pub struct LeafSubVoxel {
     pub block_type: BlockType,
 }
 
+/// Куда пишет ChunkWriter
+enum WriteTarget<'a> {
+    /// Генерация: столбец секций чанка
+    Column(&'a mut SectionColumn),
+    /// Рост в готовом мире: блоки читаются из мира, записи копятся
+    /// для WorldChanges
+    World {
+        block_at: &'a dyn Fn(i32, i32, i32) -> BlockType,
+        placed: Vec<(BlockPos, BlockType)>,
+    },
+}
+
 /// Хелпер для безопасной записи в массив блоков чанка
 pub struct ChunkWriter<'a> {
-    blocks: &'a mut Vec<BlockType>,
+    target: WriteTarget<'a>,
     world_changes: Option<&'a HashMap<BlockPos, BlockType>>,
     base_x: i32,
     base_z: i32,
@@ -36,13 +173,13 @@ pub struct ChunkWriter<'a> {
 
 impl<'a> ChunkWriter<'a> {
     pub fn new(
-        blocks: &'a mut Vec<BlockType>,
+        blocks: &'a mut SectionColumn,
         world_changes: Option<&'a HashMap<BlockPos, BlockType>>,
         base_x: i32,
         base_z: i32,
     ) -> Self {
         Self { 
-            blocks, 
+            target: WriteTarget::Column(blocks), 
             world_changes, 
             base_x, 
             base_z,
@@ -50,6 +187,37 @@ impl<'a> ChunkWriter<'a> {
         }
     }
 
+    /// Запись в готовый мир (рост саженца). Координаты, как и при
+    /// генерации, локальные от base_x/base_z и ограничены одним чанком
+    pub fn for_world(block_at: &'a dyn Fn(i32, i32, i32) -> BlockType, base_x: i32, base_z: i32) -> Self {
+        Self {
+            target: WriteTarget::World { block_at, placed: Vec::new() },
+            world_changes: None,
+            base_x,
+            base_z,
+            leaf_positions: Vec::new(),
+        }
+    }
+
+    fn get(&self, lx: i32, y: i32, lz: i32) -> BlockType {
+        match &self.target {
+            WriteTarget::Column(blocks) => blocks.get(lx, y, lz),
+            WriteTarget::World { block_at, placed } => {
+                let pos = BlockPos::new(self.base_x + lx, y, self.base_z + lz);
+                // Последняя запись важнее мира
+                placed.iter().rev().find(|(p, _)| *p == pos).map_or_else(|| block_at(pos.x, y, pos.z), |&(_, block)| block)
+            }
+        }
+    }
+
+    fn set(&mut self, lx: i32, y: i32, lz: i32, block: BlockType) {
+        let pos = BlockPos::new(self.base_x + lx, y, self.base_z + lz);
+        match &mut self.target {
+            WriteTarget::Column(blocks) => blocks.set(lx, y, lz, block),
+            WriteTarget::World { placed, .. } => placed.push((pos, block)),
+        }
+    }
+
     /// Безопасная установка блока
     pub fn set_block(&mut self, lx: i32, y: i32, lz: i32, block: BlockType) {
         if lx < 0 || lx >= CHUNK_SIZE || lz < 0 || lz >= CHUNK_SIZE || y < MIN_HEIGHT || y >= WORLD_HEIGHT {
@@ -63,9 +231,8 @@ impl<'a> ChunkWriter<'a> {
             }
         }
         
-        let idx = Self::index(lx, y, lz);
-        if self.blocks[idx] == AIR {
-            self.blocks[idx] = block;
+        if self.get(lx, y, lz) == AIR {
+            self.set(lx, y, lz, block);
         }
     }
     
@@ -82,8 +249,7 @@ impl<'a> ChunkWriter<'a> {
             }
         }
         
-        let idx = Self::index(lx, y, lz);
-        if self.blocks[idx] != AIR {
+        if self.get(lx, y, lz) != AIR {
             return;
         }
         
@@ -97,7 +263,8 @@ impl<'a> ChunkWriter<'a> {
         });
     }
     
-    /// Принудительная установка (для ствола)
+    /// Принудительная установка (для ствола). В готовом мире ствол
+    /// занимает только воздух и саженцы - постройки игрока не ломаются
     pub fn set_solid(&mut self, lx: i32, y: i32, lz: i32, block: BlockType) {
         if lx < 0 || lx >= CHUNK_SIZE || lz < 0 || lz >= CHUNK_SIZE || y < MIN_HEIGHT || y >= WORLD_HEIGHT {
             return;
@@ -110,18 +277,23 @@ impl<'a> ChunkWriter<'a> {
             }
         }
         
-        let idx = Self::index(lx, y, lz);
-        self.blocks[idx] = block;
+        if matches!(self.target, WriteTarget::World { .. }) {
+            let current = self.get(lx, y, lz);
+            if current != AIR && !is_sapling(current) {
+                return;
+            }
+        }
+        self.set(lx, y, lz, block);
     }
 
-    #[inline]
-    fn index(lx: i32, y: i32, lz: i32) -> usize {
-        let ly = y - MIN_HEIGHT;
-        (ly as usize) * (CHUNK_SIZE as usize * CHUNK_SIZE as usize)
-            + (lz as usize) * (CHUNK_SIZE as usize)
-            + (lx as usize)
+    /// Забрать блоки, записанные в готовый мир
+    pub fn take_placed_blocks(&mut self) -> Vec<(BlockPos, BlockType)> {
+        match &mut self.target {
+            WriteTarget::Column(_) => Vec::new(),
+            WriteTarget::World { placed, .. } => std::mem::take(placed),
+        }
     }
-    
+
     /// Получить позиции листвы
     pub fn take_leaf_subvoxels(&mut self) -> Vec<LeafSubVoxel> {
         std::mem::take(&mut self.leaf_positions)
@@ -195,3 +367,77 @@ pub fn place_spruce_tree(writer: &mut ChunkWriter, lx: i32, base_y: i32, lz: i32
         }
     }
 }
+
+/// Поставить структуру (основание на base_y)
+pub fn place_feature(writer: &mut ChunkWriter, lx: i32, base_y: i32, lz: i32, kind: FeatureKind, height: i32) {
+    match kind {
+        FeatureKind::Cactus => {
+            for y in 0..height {
+                writer.set_solid(lx, base_y + y, lz, CACTUS);
+            }
+        }
+        FeatureKind::HugeRedMushroom => place_red_mushroom(writer, lx, base_y, lz, height),
+        FeatureKind::HugeBrownMushroom => place_brown_mushroom(writer, lx, base_y, lz, height),
+    }
+}
+
+/// Красный гриб: купол радиуса 2 над ножкой
+fn place_red_mushroom(writer: &mut ChunkWriter, lx: i32, base_y: i32, lz: i32, height: i32) {
+    let top_y = base_y + height;
+    for y in (top_y - 3)..=top_y {
+        // Верхний слой - крышка 3x3, ниже - стенки 5x5 без углов
+        let radius = if y == top_y { 1 } else { 2 };
+        for x in -radius..=radius {
+            for z in -radius..=radius {
+                let wall = x.abs() == radius || z.abs() == radius;
+                if y < top_y && (!wall || (x.abs() == radius && z.abs() == radius)) {
+                    continue;
+                }
+                writer.set_block(lx + x, y, lz + z, RED_MUSHROOM_BLOCK);
+            }
+        }
+    }
+
+    for y in 0..height {
+        writer.set_solid(lx, base_y + y, lz, MUSHROOM_STEM);
+    }
+}
+
+/// Коричневый гриб: плоская шляпка радиуса 3 на вершине ножки
+fn place_brown_mushroom(writer: &mut ChunkWriter, lx: i32, base_y: i32, lz: i32, height: i32) {
+    let cap_y = base_y + height;
+    for x in -3i32..=3 {
+        for z in -3i32..=3 {
+            if x.abs() == 3 && z.abs() == 3 {
+                continue;
+            }
+            writer.set_block(lx + x, cap_y, lz + z, BROWN_MUSHROOM_BLOCK);
+        }
+    }
+
+    for y in 0..height {
+        writer.set_solid(lx, base_y + y, lz, MUSHROOM_STEM);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn features_stand_on_matching_ground() {
+        assert!(FeatureKind::Cactus.fits_on(RED_SAND));
+        assert!(!FeatureKind::Cactus.fits_on(GRASS));
+        assert!(FeatureKind::HugeBrownMushroom.fits_on(MYCELIUM));
+        assert!(!FeatureKind::HugeRedMushroom.fits_on(SAND));
+    }
+
+    #[test]
+    fn feature_heights_stay_in_range() {
+        for i in 0..100 {
+            let rng = i as f32 / 100.0;
+            assert!((1..=3).contains(&FeatureKind::Cactus.height(rng)));
+            assert!((5..=7).contains(&FeatureKind::HugeRedMushroom.height(rng)));
+        }
+    }
+}
@@ -6,8 +6,9 @@
 use std::collections::HashSet;
 use crate::gpu::blocks::{BlockType, OAK_LEAVES, BIRCH_LEAVES, SPRUCE_LEAVES};
 use crate::gpu::terrain::generation::hash3d;
-use crate::gpu::subvoxel::{SubVoxelPos, SubVoxelLevel, SubVoxelStorage};
-use crate::gpu::biomes::{biome_selector, BIOME_TAIGA, BIOME_TUNDRA, BIOME_FOREST};
+use crate::gpu::subvoxel::{SubVoxelPos, SubVoxelLevel, SubVoxelWorld};
+use crate::gpu::terrain::cache::column_biome;
+use crate::gpu::biomes::features::TreeType;
 use crate::gpu::terrain::voxel::CHUNK_SIZE;
 
 /// Проверяет, является ли блок листвой
@@ -36,7 +37,7 @@ impl FoliageCache {
     /// Генерирует субвоксели для деревьев в радиусе видимости
     pub fn update(
         &mut self,
-        storage: &mut SubVoxelStorage,
+        storage: &mut SubVoxelWorld,
         player_x: f32,
         player_z: f32,
         _render_distance: i32,
@@ -44,8 +45,8 @@ impl FoliageCache {
         let player_cx = (player_x / CHUNK_SIZE as f32).floor() as i32;
         let player_cz = (player_z / CHUNK_SIZE as f32).floor() as i32;
         
-        // Лимит субвокселей
-        if storage.count() > 2_000_000 {
+        // Лимит листвы: блок листвы - ~25 субвокселей
+        if storage.block_count() > 80_000 {
             return;
         }
         
@@ -82,7 +83,11 @@ impl FoliageCache {
     }
     
     /// Генерация листвы для одного чанка
-    fn generate_chunk_foliage(&self, storage: &mut SubVoxelStorage, chunk_x: i32, chunk_z: i32) {
+    fn generate_chunk_foliage(&self, storage: &mut SubVoxelWorld, chunk_x: i32, chunk_z: i32) {
+        if !crate::gpu::terrain::generation::terrain_generator().has_features() {
+            return;
+        }
+
         let base_x = chunk_x * CHUNK_SIZE;
         let base_z = chunk_z * CHUNK_SIZE;
         
@@ -95,7 +100,7 @@ impl FoliageCache {
                 // Получаем высоту terrain
                 let terrain_height = crate::gpu::terrain::generation::get_height(world_x as f32, world_z as f32) as i32;
                 
-                let biome = biome_selector().get_biome_def(world_x, world_z);
+                let biome = column_biome(world_x, world_z);
                 
                 if biome.tree_density > 0.0001 {
                     let rng = hash3d(world_x, terrain_height, world_z);
@@ -104,21 +109,16 @@ impl FoliageCache {
                         let tree_height = 5 + ((rng * 1000.0) as i32 % 3);
                         let base_y = terrain_height + 1;
                         
-                        // Определяем тип листвы
-                        let leaf_type = match biome.id {
-                            BIOME_TAIGA | BIOME_TUNDRA => SPRUCE_LEAVES,
-                            BIOME_FOREST => {
-                                if ((rng * 100.0) as i32) % 5 == 0 {
-                                    BIRCH_LEAVES
-                                } else {
-                                    OAK_LEAVES
-                                }
-                            },
-                            _ => OAK_LEAVES,
+                        // Порода дерева задаёт тип листвы
+                        let tree_type = biome.tree_type(rng);
+                        let leaf_type = match tree_type {
+                            TreeType::Spruce => SPRUCE_LEAVES,
+                            TreeType::Birch => BIRCH_LEAVES,
+                            TreeType::Oak => OAK_LEAVES,
                         };
                         
                         // Генерируем листву как субвоксели
-                        self.generate_tree_foliage(storage, world_x, base_y, world_z, tree_height, leaf_type, biome.id);
+                        self.generate_tree_foliage(storage, world_x, base_y, world_z, tree_height, leaf_type, tree_type);
                     }
                 }
             }
@@ -128,15 +128,15 @@ impl FoliageCache {
     /// Генерация субвокселей листвы для одного дерева
     fn generate_tree_foliage(
         &self,
-        storage: &mut SubVoxelStorage,
+        storage: &mut SubVoxelWorld,
         tree_x: i32,
         base_y: i32,
         tree_z: i32,
         height: i32,
         leaf_type: BlockType,
-        biome_id: u8,
+        tree_type: TreeType,
     ) {
-        let is_spruce = matches!(biome_id, BIOME_TAIGA | BIOME_TUNDRA);
+        let is_spruce = tree_type == TreeType::Spruce;
         
         if is_spruce {
             self.generate_spruce_foliage(storage, tree_x, base_y, tree_z, height, leaf_type);
@@ -148,7 +148,7 @@ impl FoliageCache {
     /// Листва для дуба/березы
     fn generate_basic_foliage(
         &self,
-        storage: &mut SubVoxelStorage,
+        storage: &mut SubVoxelWorld,
         tree_x: i32,
         base_y: i32,
         tree_z: i32,
@@ -181,7 +181,7 @@ impl FoliageCache {
     /// Листва для ели (конусом)
     fn generate_spruce_foliage(
         &self,
-        storage: &mut SubVoxelStorage,
+        storage: &mut SubVoxelWorld,
         tree_x: i32,
         base_y: i32,
         tree_z: i32,
@@ -219,32 +219,13 @@ impl FoliageCache {
     /// Генерирует субвоксели для одного блока листвы
     fn generate_leaf_subvoxels(
         &self,
-        storage: &mut SubVoxelStorage,
+        storage: &mut SubVoxelWorld,
         world_x: i32,
         world_y: i32,
         world_z: i32,
         leaf_type: BlockType,
     ) {
-        // Quarter level (4x4x4 = 64 субвокселей) для детальной листвы
-        let level = SubVoxelLevel::Quarter;
-        
-        for sy in 0..4u8 {
-            for sz in 0..4u8 {
-                for sx in 0..4u8 {
-                    let noise = hash3d(
-                        world_x * 4 + sx as i32,
-                        world_y * 4 + sy as i32,
-                        world_z * 4 + sz as i32
-                    );
-                    
-                    // ~40% заполнение для воздушной листвы
-                    if noise < 0.4 {
-                        let pos = SubVoxelPos::new(world_x, world_y, world_z, sx, sy, sz, level);
-                        storage.set(pos, leaf_type);
-                    }
-                }
-            }
-        }
+        place_leaf_subvoxels(storage, world_x, world_y, world_z, leaf_type);
     }
     
     /// Очистка далеких чанков
@@ -255,7 +236,7 @@ impl FoliageCache {
     }
     
     /// Очистка субвокселей далеких чанков
-    fn cleanup_subvoxels(&self, storage: &mut SubVoxelStorage, center_x: i32, center_z: i32, max_distance: i32) {
+    fn cleanup_subvoxels(&self, storage: &mut SubVoxelWorld, center_x: i32, center_z: i32, max_distance: i32) {
         let min_x = (center_x - max_distance) * CHUNK_SIZE;
         let max_x = (center_x + max_distance + 1) * CHUNK_SIZE;
         let min_z = (center_z - max_distance) * CHUNK_SIZE;
@@ -273,6 +254,31 @@ impl FoliageCache {
     }
 }
 
+/// Субвоксели одного блока листвы (тот же узор, что у сгенерированных
+/// деревьев; им же одеваются выросшие из саженцев)
+pub fn place_leaf_subvoxels(storage: &mut SubVoxelWorld, world_x: i32, world_y: i32, world_z: i32, leaf_type: BlockType) {
+    // Quarter level (4x4x4 = 64 субвокселей) для детальной листвы
+    let level = SubVoxelLevel::Quarter;
+    
+    for sy in 0..4u8 {
+        for sz in 0..4u8 {
+            for sx in 0..4u8 {
+                let noise = hash3d(
+                    world_x * 4 + sx as i32,
+                    world_y * 4 + sy as i32,
+                    world_z * 4 + sz as i32
+                );
+                
+                // ~40% заполнение для воздушной листвы
+                if noise < 0.4 {
+                    let pos = SubVoxelPos::new(world_x, world_y, world_z, sx, sy, sz, level);
+                    storage.set(pos, leaf_type);
+                }
+            }
+        }
+    }
+}
+
 impl Default for FoliageCache {
     fn default() -> Self {
         Self::new()
@@ -8,24 +8,39 @@
 // - Continentalness: океан -> суша -> горы
 // - Erosion: плоско -> холмисто
 //
+// Биомы описаны в JSON (assets/biomes) и загружаются в BiomeRegistry
+// при старте, как блоки; в отладочной сборке правки JSON подхватываются
+// на лету.
+//
 // Каждый биом имеет свой тип генерации terrain:
 // - Flat: болота, тундра
 // - Rolling: равнины, леса
 // - Mountains3D: горы с 3D шумом (карнизы, нависания)
 // - Valley: долины с крутыми стенами
 // - Ocean: океанское дно
+//
+// На границах биомы смешиваются (blend): высота, поверхность и оттенок травы
+//
+// preview - карта сида по одному климату (выбор сида до создания мира)
 
 mod types;
+mod definition;
 mod climate;
 mod registry;
 mod selector;
 mod terrain_gen;
+mod blend;
 pub mod features;
 pub mod foliage;
+pub mod decorations;
+pub mod preview;
 
 pub use types::*;
 pub use climate::*;
 pub use registry::*;
 pub use selector::*;
 pub use terrain_gen::*;
-pub use foliage::{FoliageCache, is_leaf_block};
+pub use blend::{BiomeBlend, BIOME_BLEND_CELL};
+pub use foliage::{FoliageCache, is_leaf_block, place_leaf_subvoxels};
+pub use decorations::{DecorationKind, DecorationPlacement, scatter_chunk_decorations};
+pub use preview::{SeedPreview, DEFAULT_PREVIEW_SIZE};
@@ -0,0 +1,131 @@
+// ============================================
+// Seed Preview - Карта мира до его создания
+// ============================================
+// `--seed-preview [размер] --seed N ...`: быстрая карта низкого
+// разрешения для выбора интересного сида. Чанки не генерируются -
+// только климатическая карта сида: биом по климату и оценка высоты
+// по континентальности. Глобальные карты (climate_map, параметры
+// текущего мира) не трогаются, поэтому превью можно строить для
+// любого сида без побочных эффектов.
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use rayon::prelude::*;
+
+use crate::gpu::blocks::{get_face_colors, GRASS};
+use crate::gpu::terrain::generation::WorldGenConfig;
+
+use super::climate::ClimateMap;
+use super::registry::biome_registry;
+use super::selector::select_biome;
+use super::types::*;
+
+/// Размер карты по умолчанию (пиксели)
+pub const DEFAULT_PREVIEW_SIZE: u32 = 256;
+/// Максимальный размер карты
+pub const MAX_PREVIEW_SIZE: u32 = 2048;
+/// Блоков на пиксель (256 пикселей = 8192 блока)
+const BLOCKS_PER_PIXEL: f32 = 32.0;
+/// Высота равнин (как в BiomeTerrainGen::height_with_biome)
+const PLAINS_HEIGHT: f32 = 20.0;
+
+/// Готовое превью сида
+pub struct SeedPreview {
+    pub size: u32,
+    /// RGB8, строки с севера (-z) на юг
+    pixels: Vec<u8>,
+    /// Число пикселей каждого биома
+    biome_pixels: Vec<(BiomeId, u32)>,
+}
+
+impl SeedPreview {
+    /// Построить карту вокруг точки спавна (0, 0)
+    pub fn render(config: &WorldGenConfig, size: u32) -> Self {
+        let size = size.clamp(16, MAX_PREVIEW_SIZE);
+        let climate = ClimateMap::new(config.climate_seed()).with_scale(config.biome_scale);
+        let half = size as f32 * 0.5;
+
+        let samples: Vec<(BiomeId, [u8; 3])> = (0..size * size)
+            .into_par_iter()
+            .map(|i| {
+                let x = ((i % size) as f32 - half) * BLOCKS_PER_PIXEL;
+                let z = ((i / size) as f32 - half) * BLOCKS_PER_PIXEL;
+                let data = climate.sample(x, z);
+                let biome = biome_registry().get(select_biome(&data));
+                let height = estimate_height(biome, &data) * config.terrain_amplitude;
+                (biome.id, pixel_color(biome, height, config.sea_level as f32))
+            })
+            .collect();
+
+        let mut biome_pixels: Vec<(BiomeId, u32)> = Vec::new();
+        for &(id, _) in &samples {
+            match biome_pixels.iter_mut().find(|(biome, _)| *biome == id) {
+                Some((_, count)) => *count += 1,
+                None => biome_pixels.push((id, 1)),
+            }
+        }
+        biome_pixels.sort_by(|a, b| b.1.cmp(&a.1));
+
+        Self {
+            size,
+            pixels: samples.into_iter().flat_map(|(_, rgb)| rgb).collect(),
+            biome_pixels,
+        }
+    }
+
+    /// Сторона карты в блоках
+    pub fn extent_blocks(&self) -> u32 {
+        (self.size as f32 * BLOCKS_PER_PIXEL) as u32
+    }
+
+    /// Покрытие биомов в журнал
+    pub fn print_summary(&self) {
+        let total = (self.size * self.size).max(1) as f32;
+        for &(id, count) in &self.biome_pixels {
+            log::info!("  {:<12} {:>5.1}%", biome_registry().get(id).name, count as f32 * 100.0 / total);
+        }
+    }
+
+    /// Сохранить в PNG
+    pub fn save_png(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let file = File::create(path).map_err(|e| e.to_string())?;
+        let mut encoder = png::Encoder::new(BufWriter::new(file), self.size, self.size);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+        writer.write_image_data(&self.pixels).map_err(|e| e.to_string())
+    }
+}
+
+/// Высота по климату без шума деталей (среднее по BiomeTerrainGen)
+fn estimate_height(biome: &BiomeDefinition, climate: &ClimateData) -> f32 {
+    let c = climate.continentalness;
+    if biome.terrain_type != TerrainType::Mountains3D {
+        return biome.base_height + (c - 0.4).max(0.0) * 30.0;
+    }
+    let t = ((c - 0.3) / 0.5).clamp(0.0, 1.0);
+    let mountain_factor = t * t * (3.0 - 2.0 * t);
+    let mountain_height = biome.base_height + biome.height_variation * 1.5;
+    PLAINS_HEIGHT + (mountain_height - PLAINS_HEIGHT) * mountain_factor
+}
+
+/// Цвет пикселя: вода по глубине, суша - верх поверхностного блока с тенью по высоте
+fn pixel_color(biome: &BiomeDefinition, height: f32, sea_level: f32) -> [u8; 3] {
+    let to_u8 = |v: f32| (v.clamp(0.0, 1.0) * 255.0) as u8;
+
+    if height < sea_level {
+        let depth = ((sea_level - height) / 40.0).clamp(0.0, 1.0);
+        return [to_u8(0.15 - depth * 0.1), to_u8(0.4 - depth * 0.2), to_u8(0.8 - depth * 0.3)];
+    }
+
+    let (top, _) = get_face_colors(biome.surface_block);
+    let tint = if biome.surface_block == GRASS { biome.grass_tint } else { [1.0; 3] };
+    let shade = 0.7 + ((height - sea_level) / 120.0).clamp(0.0, 1.0) * 0.5;
+    [
+        to_u8(top[0] * tint[0] * shade),
+        to_u8(top[1] * tint[1] * shade),
+        to_u8(top[2] * tint[2] * shade),
+    ]
+}
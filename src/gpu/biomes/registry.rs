@@ -1,112 +1,88 @@
 // ============================================
-// Biome Registry - Реестр биомов
+// Biome Registry - Data-Driven из JSON
 // ============================================
+// Биомы загружаются из assets/biomes (встроенный default_biomes.json).
+// Биом по климату выбирается первым подходящим в порядке приоритета;
+// последний (наименьший приоритет) - запасной для любого климата.
+//
+// Отладочная сборка читает JSON с диска и следит за файлами: правка
+// подхватывается без перезапуска (кэши столбцов сбрасываются, новые
+// чанки генерируются по новым определениям, готовые остаются).
 
+use std::fs;
+use std::path::Path;
+use std::sync::{OnceLock, RwLock};
+
+use super::definition::BiomesFile;
 use super::types::*;
-use crate::gpu::blocks::{SAND, STONE, GRASS, DIRT, SNOW, BlockType};
-use std::sync::OnceLock;
 
 /// Реестр всех биомов
 pub struct BiomeRegistry {
+    /// Биомы по numeric ID (пропуски - заглушки)
     biomes: Vec<BiomeDefinition>,
+    /// Зарегистрированные ID в порядке проверки климата
+    selection: Vec<BiomeId>,
 }
 
 impl BiomeRegistry {
     pub fn new() -> Self {
-        let mut registry = Self { biomes: Vec::new() };
-        registry.register_default_biomes();
-        registry
+        Self { biomes: Vec::new(), selection: Vec::new() }
     }
 
-    fn register_default_biomes(&mut self) {
-        // Океан - глубоко под водой
-        self.register(
-            BiomeDefinition::new(BIOME_OCEAN, "ocean", SAND, SAND, STONE)
-                .with_terrain(-15.0, 5.0, TerrainType::Ocean)
-                .with_climate(0.5, 1.0)
-        );
-
-        // Равнины - стандартный биом (редкие деревья)
-        self.register(
-            BiomeDefinition::new(BIOME_PLAINS, "plains", GRASS, DIRT, STONE)
-                .with_terrain(20.0, 8.0, TerrainType::Rolling)
-                .with_climate(0.5, 0.4)
-                .with_trees(0.001)
-        );
-
-        // Пустыня - жарко и сухо (без деревьев)
-        self.register(
-            BiomeDefinition::new(BIOME_DESERT, "desert", SAND, SAND, STONE)
-                .with_terrain(22.0, 6.0, TerrainType::Rolling)
-                .with_climate(0.9, 0.1)
-        );
-
-        // Лес - умеренный и влажный (много деревьев)
-        self.register(
-            BiomeDefinition::new(BIOME_FOREST, "forest", GRASS, DIRT, STONE)
-                .with_terrain(25.0, 12.0, TerrainType::Rolling)
-                .with_climate(0.5, 0.6)
-                .with_trees(0.015)
-        );
-
-        // Тайга - холодный лес (ели)
-        self.register(
-            BiomeDefinition::new(BIOME_TAIGA, "taiga", GRASS, DIRT, STONE)
-                .with_terrain(22.0, 10.0, TerrainType::Rolling)
-                .with_climate(0.25, 0.6)
-                .with_trees(0.012)
-        );
-
-        // Тундра - холодно и сухо (редкие ели)
-        self.register(
-            BiomeDefinition::new(BIOME_TUNDRA, "tundra", SNOW, DIRT, STONE)
-                .with_terrain(18.0, 4.0, TerrainType::Flat)
-                .with_climate(0.0, 0.3)
-                .with_trees(0.002)
-        );
-
-        // Болото - плоское, чуть ниже воды
-        self.register(
-            BiomeDefinition::new(BIOME_SWAMP, "swamp", GRASS, DIRT, STONE)
-                .with_terrain(8.0, 2.0, TerrainType::Flat)
-                .with_climate(0.6, 0.9)
-                .with_trees(0.008)
-        );
-
-        // Горы - плавные величественные склоны (без деревьев)
-        self.register(
-            BiomeDefinition::new(BIOME_MOUNTAINS, "mountains", STONE, STONE, STONE)
-                .with_terrain(25.0, 60.0, TerrainType::Mountains3D)
-                .with_climate(0.3, 0.3)
-                .with_3d_noise(0.2)
-        );
-
-        // Саванна - жарко, умеренно сухо (редкие деревья)
-        self.register(
-            BiomeDefinition::new(BIOME_SAVANNA, "savanna", GRASS, DIRT, STONE)
-                .with_terrain(20.0, 5.0, TerrainType::Flat)
-                .with_climate(0.8, 0.3)
-                .with_trees(0.002)
-        );
-
-        // Джунгли - жарко и очень влажно (очень много деревьев)
-        self.register(
-            BiomeDefinition::new(BIOME_JUNGLE, "jungle", GRASS, DIRT, STONE)
-                .with_terrain(28.0, 15.0, TerrainType::Rolling)
-                .with_climate(0.9, 0.9)
-                .with_trees(0.025)
-        );
+    /// Загрузить биомы из JSON строки
+    pub fn load_from_json(&mut self, json: &str) -> Result<usize, String> {
+        let file: BiomesFile = serde_json::from_str(json)
+            .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+
+        let count = file.biomes.len();
+        for biome in file.biomes {
+            self.register(biome.resolve()?);
+        }
+        Ok(count)
     }
 
+    /// Загрузить биомы из файла
+    pub fn load_from_file<P: AsRef<Path>>(&mut self, path: P) -> Result<usize, String> {
+        let content = fs::read_to_string(path.as_ref())
+            .map_err(|e| format!("Failed to read file: {}", e))?;
+        self.load_from_json(&content)
+    }
+
+    /// Загрузить все JSON из директории (ошибка любого файла - ошибка)
+    pub fn load_from_directory<P: AsRef<Path>>(&mut self, dir: P) -> Result<usize, String> {
+        let dir = dir.as_ref();
+        if !dir.exists() { return Ok(0); }
+
+        let mut paths: Vec<_> = fs::read_dir(dir)
+            .map_err(|e| e.to_string())?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        // Порядок важен: поздний файл переопределяет биом с тем же ID
+        paths.sort();
+
+        let mut total = 0;
+        for path in paths {
+            total += self.load_from_file(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        }
+        Ok(total)
+    }
+
+    /// Зарегистрировать биом (тот же numeric ID - замена)
     pub fn register(&mut self, biome: BiomeDefinition) {
-        let id = biome.id as usize;
-        if id >= self.biomes.len() {
-            self.biomes.resize(id + 1, self.biomes.first().cloned().unwrap_or_else(|| {
-                BiomeDefinition::new(0, "unknown", STONE, STONE, STONE)
-                    .with_terrain(20.0, 10.0, TerrainType::Rolling)
-            }));
+        let id = biome.id;
+        let index = id as usize;
+        if index >= self.biomes.len() {
+            let start = self.biomes.len();
+            self.biomes.extend((start..=index).map(|missing| BiomeDefinition::fallback(missing as BiomeId)));
+        }
+        self.biomes[index] = biome;
+
+        if !self.selection.contains(&id) {
+            self.selection.push(id);
         }
-        self.biomes[id] = biome;
+        let biomes = &self.biomes;
+        self.selection.sort_by_key(|&id| (std::cmp::Reverse(biomes[id as usize].priority), id));
     }
 
     #[inline]
@@ -114,8 +90,18 @@ impl BiomeRegistry {
         self.biomes.get(id as usize).unwrap_or(&self.biomes[0])
     }
 
+    /// Биом для климата: первый подходящий по приоритету
+    pub fn select(&self, climate: &ClimateData) -> BiomeId {
+        self.selection
+            .iter()
+            .copied()
+            .find(|&id| self.biomes[id as usize].climate.contains(climate))
+            .or_else(|| self.selection.last().copied())
+            .unwrap_or(0)
+    }
+
     pub fn count(&self) -> usize {
-        self.biomes.len()
+        self.selection.len()
     }
 }
 
@@ -125,8 +111,177 @@ impl Default for BiomeRegistry {
     }
 }
 
-static BIOME_REGISTRY: OnceLock<BiomeRegistry> = OnceLock::new();
+// ============================================
+// Global Registry
+// ============================================
+
+/// Каталог с JSON биомов (отладочная сборка читает его с диска)
+#[cfg(debug_assertions)]
+const BIOMES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/biomes");
+
+/// Текущий реестр. Перезагрузка подменяет ссылку; прежний реестр
+/// остаётся в памяти - на него могут ссылаться генераторы в потоках
+static BIOME_REGISTRY: OnceLock<RwLock<&'static BiomeRegistry>> = OnceLock::new();
 
 pub fn biome_registry() -> &'static BiomeRegistry {
-    BIOME_REGISTRY.get_or_init(BiomeRegistry::new)
+    let current = BIOME_REGISTRY.get_or_init(|| {
+        let registry = load_registry().unwrap_or_else(|e| {
+            log::error!("Биомы не загружены: {}", e);
+            fallback_registry()
+        });
+        log::info!("Загружено биомов: {}", registry.count());
+        RwLock::new(Box::leak(Box::new(registry)))
+    });
+    *current.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Встроенные биомы (и в отладке - файлы с диска поверх них)
+fn load_registry() -> Result<BiomeRegistry, String> {
+    let mut registry = BiomeRegistry::new();
+    registry.load_from_json(include_str!("../../../assets/biomes/default_biomes.json"))?;
+    #[cfg(debug_assertions)]
+    registry.load_from_directory(BIOMES_DIR)?;
+    Ok(registry)
+}
+
+/// Единственный биом на случай битого JSON
+fn fallback_registry() -> BiomeRegistry {
+    let mut registry = BiomeRegistry::new();
+    registry.register(BiomeDefinition::fallback(0));
+    registry
+}
+
+/// Следить за assets/biomes и перезагружать реестр при изменениях
+#[cfg(debug_assertions)]
+pub fn watch_biome_files() {
+    use std::time::{Duration, SystemTime};
+
+    fn latest_change() -> Option<SystemTime> {
+        fs::read_dir(BIOMES_DIR)
+            .ok()?
+            .filter_map(|entry| entry.ok()?.metadata().ok()?.modified().ok())
+            .max()
+    }
+
+    let spawned = std::thread::Builder::new()
+        .name("biome-watch".to_string())
+        .spawn(|| {
+            let mut last = latest_change();
+            loop {
+                std::thread::sleep(Duration::from_secs(1));
+                let change = latest_change();
+                if change == last {
+                    continue;
+                }
+                last = change;
+                reload_biomes();
+            }
+        });
+    if let Err(e) = spawned {
+        log::warn!("Слежение за биомами не запущено: {}", e);
+    }
+}
+
+#[cfg(debug_assertions)]
+fn reload_biomes() {
+    let registry = match load_registry() {
+        Ok(registry) => registry,
+        Err(e) => {
+            log::warn!("Биомы не перезагружены (остаются прежние): {}", e);
+            return;
+        }
+    };
+    let count = registry.count();
+    let lock = BIOME_REGISTRY.get_or_init(|| RwLock::new(Box::leak(Box::new(fallback_registry()))));
+    *lock.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Box::leak(Box::new(registry));
+    crate::gpu::terrain::cache::clear_columns();
+    log::info!("Биомы перезагружены ({}): новые чанки генерируются по новым определениям", count);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::biomes::features::FeatureKind;
+
+    fn climate(temperature: f32, humidity: f32, continentalness: f32) -> ClimateData {
+        eroded(temperature, humidity, continentalness, 0.5)
+    }
+
+    fn eroded(temperature: f32, humidity: f32, continentalness: f32, erosion: f32) -> ClimateData {
+        ClimateData { temperature, humidity, continentalness, erosion }
+    }
+
+    fn default_registry() -> BiomeRegistry {
+        let mut registry = BiomeRegistry::new();
+        registry.load_from_json(include_str!("../../../assets/biomes/default_biomes.json")).unwrap();
+        registry
+    }
+
+    fn name(registry: &BiomeRegistry, climate: ClimateData) -> &str {
+        &registry.get(registry.select(&climate)).name
+    }
+
+    #[test]
+    fn default_biomes_select_by_climate() {
+        let registry = default_registry();
+        assert_eq!(registry.count(), 15);
+        assert_eq!(name(&registry, climate(0.5, 0.5, 0.1)), "ocean");
+        assert_eq!(name(&registry, climate(0.5, 0.8, 0.2)), "mushroom_fields");
+        assert_eq!(name(&registry, climate(0.9, 0.9, 0.8)), "mountains");
+        assert_eq!(name(&registry, climate(0.1, 0.2, 0.4)), "tundra");
+        assert_eq!(name(&registry, climate(0.1, 0.6, 0.4)), "taiga");
+        assert_eq!(name(&registry, climate(0.8, 0.1, 0.4)), "desert");
+        assert_eq!(name(&registry, eroded(0.8, 0.1, 0.4, 0.7)), "badlands");
+        assert_eq!(name(&registry, climate(0.95, 0.1, 0.4)), "red_desert");
+        assert_eq!(name(&registry, climate(0.6, 0.1, 0.4)), "shrubland");
+        assert_eq!(name(&registry, climate(0.9, 0.9, 0.4)), "jungle");
+        assert_eq!(name(&registry, climate(0.9, 0.5, 0.4)), "savanna");
+        assert_eq!(name(&registry, climate(0.5, 0.9, 0.4)), "swamp");
+        assert_eq!(name(&registry, climate(0.5, 0.6, 0.4)), "forest");
+        assert_eq!(name(&registry, eroded(0.5, 0.7, 0.4, 0.2)), "dark_forest");
+        assert_eq!(name(&registry, climate(0.5, 0.7, 0.4)), "forest");
+        assert_eq!(name(&registry, climate(0.5, 0.3, 0.4)), "plains");
+
+        let badlands = registry.get(registry.select(&eroded(0.8, 0.1, 0.4, 0.7)));
+        assert_eq!(badlands.stratum(-1), badlands.strata.last().copied());
+        assert_eq!(badlands.feature(0.0), Some(FeatureKind::Cactus));
+        assert_eq!(badlands.feature(0.5), None);
+    }
+
+    #[test]
+    fn later_file_overrides_biome() {
+        let mut registry = default_registry();
+        let json = r#"{"biomes": [{
+            "id": "plains", "numeric_id": 1,
+            "blocks": {"surface": "sand", "subsurface": "sand"},
+            "terrain": {"style": "flat", "base_height": 5.0, "height_variation": 1.0},
+            "trees": {"density": 0.5, "types": ["birch", "spruce"]}
+        }]}"#;
+        registry.load_from_json(json).unwrap();
+
+        let plains = registry.get(registry.select(&climate(0.5, 0.3, 0.4)));
+        assert_eq!(plains.terrain_type, TerrainType::Flat);
+        assert_eq!(registry.count(), 15);
+        assert_eq!(plains.tree_type(0.001).index(), 1);
+        assert_eq!(plains.tree_type(0.011).index(), 2);
+    }
+
+    #[test]
+    fn invalid_biome_is_rejected() {
+        let mut registry = BiomeRegistry::new();
+        let unknown_block = r#"{"biomes": [{
+            "id": "bad", "numeric_id": 3,
+            "blocks": {"surface": "no_such_block", "subsurface": "dirt"},
+            "terrain": {"style": "rolling", "base_height": 20.0, "height_variation": 8.0}
+        }]}"#;
+        assert!(registry.load_from_json(unknown_block).unwrap_err().contains("no_such_block"));
+
+        let empty_range = r#"{"biomes": [{
+            "id": "bad", "numeric_id": 3,
+            "climate": {"humidity": {"min": 0.8, "max": 0.2}},
+            "blocks": {"surface": "grass", "subsurface": "dirt"},
+            "terrain": {"style": "rolling", "base_height": 20.0, "height_variation": 8.0}
+        }]}"#;
+        assert!(registry.load_from_json(empty_range).is_err());
+    }
 }
@@ -41,41 +41,16 @@ impl BiomeSelector {
     }
 
     /// Выбор биома по климатическим данным
-    fn select_from_climate(&self, climate: &ClimateData) -> BiomeId {
-        let t = climate.temperature;
-        let h = climate.humidity;
-        let c = climate.continentalness;
-
-        // Океан - низкая континентальность
-        if c < 0.25 {
-            return BIOME_OCEAN;
-        }
-
-        // Горы - появляются при высокой континентальности
-        // Упрощённое условие - просто высокий континентальность
-        if c > 0.55 {
-            return BIOME_MOUNTAINS;
-        }
-
-        // Температурно-влажностная сетка
-        match (t, h) {
-            // Холодно (t < 0.25)
-            (t, h) if t < 0.25 && h < 0.4 => BIOME_TUNDRA,
-            (t, _) if t < 0.25 => BIOME_TAIGA,
-
-            // Жарко (t > 0.75)
-            (t, h) if t > 0.75 && h < 0.25 => BIOME_DESERT,
-            (t, h) if t > 0.75 && h > 0.7 => BIOME_JUNGLE,
-            (t, _) if t > 0.75 => BIOME_SAVANNA,
-
-            // Умеренно (0.25 <= t <= 0.75)
-            (_, h) if h > 0.8 => BIOME_SWAMP,
-            (_, h) if h > 0.5 => BIOME_FOREST,
-            _ => BIOME_PLAINS,
-        }
+    pub(super) fn select_from_climate(&self, climate: &ClimateData) -> BiomeId {
+        select_biome(climate)
     }
 }
 
+/// Выбор биома по климату (без глобальной карты - для превью чужого сида)
+pub(super) fn select_biome(climate: &ClimateData) -> BiomeId {
+    biome_registry().select(climate)
+}
+
 impl Default for BiomeSelector {
     fn default() -> Self {
         Self::new()
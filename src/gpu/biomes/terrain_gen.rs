@@ -5,19 +5,40 @@
 use super::types::*;
 use super::selector::biome_selector;
 use super::registry::biome_registry;
+use super::climate::climate_map;
 use crate::gpu::terrain::generation::noise::{fbm2d, noise3d};
+use crate::gpu::terrain::generation::{world_gen_config, world_noise_offset};
 
 /// Генератор terrain с учётом биомов
 pub struct BiomeTerrainGen;
 
 impl BiomeTerrainGen {
     /// Получить высоту terrain с ПЛАВНЫМ переходом между биомами
-    /// Использует континентальность напрямую для плавных склонов
+    /// На границе - взвешенная сумма высот соседних биомов (см. blend)
     #[inline]
     pub fn get_height(x: f32, z: f32) -> f32 {
-        let (biome_id, climate) = biome_selector().get_biome_with_climate(x, z);
-        let biome = biome_registry().get(biome_id);
-        
+        let climate = climate_map().sample(x, z);
+        let blend = biome_selector().get_biome_blend(x, z);
+        let registry = biome_registry();
+        
+        // Сид сдвигает область шума рельефа (климат сидируется отдельно)
+        let (offset_x, offset_z) = world_noise_offset();
+        let (nx, nz) = (x + offset_x, z + offset_z);
+        
+        let height: f32 = if blend.is_uniform() {
+            Self::height_with_biome(nx, nz, registry.get(blend.pick(0.0)), &climate)
+        } else {
+            blend
+                .iter()
+                .map(|(biome, weight)| Self::height_with_biome(nx, nz, registry.get(biome), &climate) * weight)
+                .sum()
+        };
+        height * world_gen_config().terrain_amplitude
+    }
+    
+    /// Высота одного биома в точке
+    /// Использует континентальность напрямую для плавных склонов
+    fn height_with_biome(x: f32, z: f32, biome: &BiomeDefinition, climate: &ClimateData) -> f32 {
         // Континентальность определяет "горность" - это уже плавное значение из шума!
         let c = climate.continentalness;
         
@@ -28,7 +49,7 @@ impl BiomeTerrainGen {
         if biome.terrain_type != TerrainType::Mountains3D {
             // Но даже для равнин добавляем небольшой подъём при высокой континентальности
             let lift = (c - 0.4).max(0.0) * 30.0;
-            return Self::height_for_biome(x, z, biome, &climate) + lift;
+            return Self::height_for_biome(x, z, biome, climate) + lift;
         }
         
         // Для гор: плавный переход от равнины к горам на основе континентальности
@@ -139,7 +160,8 @@ impl BiomeTerrainGen {
         let height_density = base_height - y;
 
         // 3D шум для карнизов и нависаний
-        let noise_3d = Self::sample_3d_noise(x, y, z);
+        let (offset_x, offset_z) = world_noise_offset();
+        let noise_3d = Self::sample_3d_noise(x + offset_x, y, z + offset_z);
         
         // Смешиваем: чем выше noise_3d_strength, тем больше влияние 3D шума
         let blend = biome.noise_3d_strength;
@@ -2,31 +2,24 @@
 // Biome Types - Типы биомов
 // ============================================
 
-use crate::gpu::blocks::BlockType;
+use serde::Deserialize;
 
-/// ID биома
-pub type BiomeId = u8;
+use crate::gpu::blocks::{BlockType, STONE};
+use super::features::{FeatureKind, FeatureRule, TreeType};
 
-// Константы биомов
-pub const BIOME_OCEAN: BiomeId = 0;
-pub const BIOME_PLAINS: BiomeId = 1;
-pub const BIOME_DESERT: BiomeId = 2;
-pub const BIOME_FOREST: BiomeId = 3;
-pub const BIOME_TAIGA: BiomeId = 4;
-pub const BIOME_TUNDRA: BiomeId = 5;
-pub const BIOME_SWAMP: BiomeId = 6;
-pub const BIOME_MOUNTAINS: BiomeId = 7;
-pub const BIOME_SAVANNA: BiomeId = 8;
-pub const BIOME_JUNGLE: BiomeId = 9;
+/// ID биома (numeric_id в JSON)
+pub type BiomeId = u8;
 
 /// Тип генерации terrain для биома
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TerrainType {
     /// Плоский terrain (болота, равнины)
     Flat,
     /// Стандартные холмы
     Rolling,
     /// Горы с 3D шумом (карнизы, пещеры)
+    #[serde(rename = "mountains_3d")]
     Mountains3D,
     /// Долины с крутыми стенами
     Valley,
@@ -34,78 +27,139 @@ pub enum TerrainType {
     Ocean,
 }
 
-/// Определение биома
+/// Диапазон климатического параметра [min, max)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ValueRange {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl ValueRange {
+    pub const ANY: ValueRange = ValueRange { min: f32::NEG_INFINITY, max: f32::INFINITY };
+
+    #[inline]
+    pub fn contains(&self, value: f32) -> bool {
+        value >= self.min && value < self.max
+    }
+}
+
+/// Климат, в котором биом может быть выбран
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClimateRange {
+    pub temperature: ValueRange,
+    pub humidity: ValueRange,
+    pub continentalness: ValueRange,
+    pub erosion: ValueRange,
+}
+
+impl ClimateRange {
+    pub const ANY: ClimateRange = ClimateRange {
+        temperature: ValueRange::ANY,
+        humidity: ValueRange::ANY,
+        continentalness: ValueRange::ANY,
+        erosion: ValueRange::ANY,
+    };
+
+    #[inline]
+    pub fn contains(&self, climate: &ClimateData) -> bool {
+        self.temperature.contains(climate.temperature)
+            && self.humidity.contains(climate.humidity)
+            && self.continentalness.contains(climate.continentalness)
+            && self.erosion.contains(climate.erosion)
+    }
+}
+
+/// Определение биома (загружается из assets/biomes, см. definition)
 #[derive(Clone, Debug)]
 pub struct BiomeDefinition {
     pub id: BiomeId,
-    pub name: &'static str,
+    /// Строковый ID из JSON
+    pub name: String,
+    /// Порядок проверки климата: больший - раньше
+    pub priority: i32,
+    pub climate: ClimateRange,
     /// Поверхностный блок
     pub surface_block: BlockType,
     /// Подповерхностный блок
     pub subsurface_block: BlockType,
     /// Глубинный блок
     pub deep_block: BlockType,
+    /// Слои породы под поверхностью (по одному блоку по высоте, по кругу);
+    /// пусто - обычные subsurface/deep
+    pub strata: Vec<BlockType>,
     /// Базовая высота terrain
     pub base_height: f32,
     /// Амплитуда высоты
     pub height_variation: f32,
     /// Тип генерации terrain
     pub terrain_type: TerrainType,
-    /// Температура (0.0 - холодно, 1.0 - жарко)
-    pub temperature: f32,
-    /// Влажность (0.0 - сухо, 1.0 - влажно)
-    pub humidity: f32,
     /// Сила 3D шума для гор (0.0 - нет, 1.0 - максимум)
     pub noise_3d_strength: f32,
     /// Плотность деревьев (0.0 - нет, 0.015 - лес, 0.001 - редкие)
     pub tree_density: f32,
+    /// Породы деревьев; выбор по сотым долям случайного числа дерева
+    pub tree_types: Vec<TreeType>,
+    /// Прочие структуры (кактусы, огромные грибы) с долями столбцов
+    pub features: Vec<FeatureRule>,
+    /// Доля столбцов с пучками травы
+    pub grass_density: f32,
+    /// Доля столбцов с сухими кустами
+    pub dead_bush_density: f32,
+    /// Оттенок травы (множитель цвета верха GRASS)
+    pub grass_tint: [f32; 3],
 }
 
 impl BiomeDefinition {
-    pub const fn new(
-        id: BiomeId,
-        name: &'static str,
-        surface_block: BlockType,
-        subsurface_block: BlockType,
-        deep_block: BlockType,
-    ) -> Self {
+    /// Заглушка для незарегистрированных ID
+    pub fn fallback(id: BiomeId) -> Self {
         Self {
             id,
-            name,
-            surface_block,
-            subsurface_block,
-            deep_block,
+            name: "unknown".to_string(),
+            priority: i32::MIN,
+            climate: ClimateRange::ANY,
+            surface_block: STONE,
+            subsurface_block: STONE,
+            deep_block: STONE,
+            strata: Vec::new(),
             base_height: 20.0,
             height_variation: 10.0,
             terrain_type: TerrainType::Rolling,
-            temperature: 0.5,
-            humidity: 0.5,
             noise_3d_strength: 0.0,
             tree_density: 0.0,
+            tree_types: vec![TreeType::Oak],
+            features: Vec::new(),
+            grass_density: 0.04,
+            dead_bush_density: 0.0,
+            grass_tint: [1.0, 1.0, 1.0],
         }
     }
 
-    pub const fn with_terrain(mut self, base_height: f32, height_variation: f32, terrain_type: TerrainType) -> Self {
-        self.base_height = base_height;
-        self.height_variation = height_variation;
-        self.terrain_type = terrain_type;
-        self
-    }
-
-    pub const fn with_climate(mut self, temperature: f32, humidity: f32) -> Self {
-        self.temperature = temperature;
-        self.humidity = humidity;
-        self
+    /// Порода дерева по его случайному числу (rng < tree_density)
+    #[inline]
+    pub fn tree_type(&self, rng: f32) -> TreeType {
+        let index = (rng * 100.0) as usize % self.tree_types.len().max(1);
+        self.tree_types.get(index).copied().unwrap_or(TreeType::Oak)
     }
 
-    pub const fn with_3d_noise(mut self, strength: f32) -> Self {
-        self.noise_3d_strength = strength;
-        self
+    /// Структура столбца по его случайному числу (доли правил подряд)
+    pub fn feature(&self, rng: f32) -> Option<FeatureKind> {
+        let mut threshold = 0.0;
+        for rule in &self.features {
+            threshold += rule.density;
+            if rng < threshold {
+                return Some(rule.kind);
+            }
+        }
+        None
     }
 
-    pub const fn with_trees(mut self, density: f32) -> Self {
-        self.tree_density = density;
-        self
+    /// Слой породы на высоте y (для биомов со strata)
+    #[inline]
+    pub fn stratum(&self, y: i32) -> Option<BlockType> {
+        if self.strata.is_empty() {
+            return None;
+        }
+        Some(self.strata[y.rem_euclid(self.strata.len() as i32) as usize])
     }
 }
 
@@ -0,0 +1,150 @@
+// ============================================
+// Chest - Сундук с собственным хранилищем
+// ============================================
+
+use crate::gpu::items::{ItemStack, insert_stack};
+use crate::gpu::save::SavedItemStack;
+
+/// Слотов в сундуке (3 ряда по 9)
+pub const CHEST_SLOTS: usize = 27;
+
+/// Скорость открытия крышки (доля в секунду)
+const LID_SPEED: f32 = 5.0;
+
+/// Содержимое сундука
+#[derive(Debug, Clone)]
+pub struct Chest {
+    slots: Vec<Option<ItemStack>>,
+    /// Открыт ли экран сундука
+    open: bool,
+    /// Открытость крышки 0..1 (анимируется в тике)
+    lid: f32,
+}
+
+impl Default for Chest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Chest {
+    pub fn new() -> Self {
+        Self {
+            slots: vec![None; CHEST_SLOTS],
+            open: false,
+            lid: 0.0,
+        }
+    }
+
+    /// Восстановить сундук из сохранения (лишние слоты отбрасываются)
+    pub fn from_saved(saved: &[Option<SavedItemStack>]) -> Self {
+        let mut chest = Self::new();
+        for (slot, saved_item) in chest.slots.iter_mut().zip(saved) {
+            *slot = saved_item.and_then(ItemStack::from_saved);
+        }
+        chest
+    }
+
+    /// Снимок слотов для сохранения
+    pub fn to_saved(&self) -> Vec<Option<SavedItemStack>> {
+        self.slots.iter().map(|slot| slot.as_ref().map(ItemStack::to_saved)).collect()
+    }
+
+    pub fn slots(&self) -> &[Option<ItemStack>] {
+        &self.slots
+    }
+
+    /// Положить стопку. Возвращает то, что не поместилось
+    pub fn insert(&mut self, stack: ItemStack) -> Option<ItemStack> {
+        insert_stack(&mut self.slots, stack)
+    }
+
+    /// Забрать стопку из слота целиком
+    pub fn take(&mut self, index: usize) -> Option<ItemStack> {
+        self.slots.get_mut(index).and_then(Option::take)
+    }
+
+    /// Отделить меньшую половину стопки в слоте
+    pub fn take_half(&mut self, index: usize) -> Option<ItemStack> {
+        self.slots.get_mut(index)?.as_mut()?.split_half()
+    }
+
+    /// Вернуть стопку в слот (пустой или с тем же предметом)
+    pub fn restore(&mut self, index: usize, stack: ItemStack) {
+        match self.slots.get_mut(index) {
+            Some(Some(existing)) if existing.item == stack.item => existing.count += stack.count,
+            Some(slot) if slot.is_none() => *slot = Some(stack),
+            _ => {}
+        }
+    }
+
+    /// Вынуть всё содержимое (сундук сломан)
+    pub fn drain(&mut self) -> Vec<ItemStack> {
+        self.slots.iter_mut().filter_map(Option::take).collect()
+    }
+
+    pub fn set_open(&mut self, open: bool) {
+        self.open = open;
+    }
+
+    pub fn lid(&self) -> f32 {
+        self.lid
+    }
+
+    /// Крышка плавно открывается, пока открыт экран сундука
+    pub fn tick(&mut self, dt: f32) {
+        let target = if self.open { 1.0 } else { 0.0 };
+        let step = LID_SPEED * dt;
+        self.lid = if self.lid < target {
+            (self.lid + step).min(target)
+        } else {
+            (self.lid - step).max(target)
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::blocks::{BlockType, STONE, DIRT};
+
+    fn stack(block_type: BlockType, count: u32) -> ItemStack {
+        ItemStack::new(block_type.into(), count)
+    }
+
+    #[test]
+    fn insert_merges_same_block() {
+        let mut chest = Chest::new();
+        assert!(chest.insert(stack(STONE, 3)).is_none());
+        assert!(chest.insert(stack(DIRT, 1)).is_none());
+        assert!(chest.insert(stack(STONE, 5)).is_none());
+        assert_eq!(chest.slots()[0].as_ref().map(|i| i.count), Some(8));
+        assert_eq!(chest.slots()[1].as_ref().and_then(ItemStack::block), Some(DIRT));
+        assert!(chest.slots()[2].is_none());
+    }
+
+    #[test]
+    fn saved_roundtrip_keeps_slots() {
+        let mut chest = Chest::new();
+        chest.insert(stack(DIRT, 4));
+        let moved = chest.take(0).unwrap();
+        chest.restore(5, moved);
+        let restored = Chest::from_saved(&chest.to_saved());
+        assert!(restored.slots()[0].is_none());
+        assert_eq!(restored.slots()[5], Some(stack(DIRT, 4)));
+        assert_eq!(restored.slots().len(), CHEST_SLOTS);
+    }
+
+    #[test]
+    fn lid_follows_open_state() {
+        let mut chest = Chest::new();
+        chest.set_open(true);
+        chest.tick(0.1);
+        assert!((chest.lid() - 0.5).abs() < 1e-5);
+        chest.tick(1.0);
+        assert_eq!(chest.lid(), 1.0);
+        chest.set_open(false);
+        chest.tick(1.0);
+        assert_eq!(chest.lid(), 0.0);
+    }
+}
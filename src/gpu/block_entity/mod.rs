@@ -0,0 +1,189 @@
+// ============================================
+// Block Entities - Данные отдельных блоков
+// ============================================
+// Блоки с собственным состоянием (содержимое сундука) хранят его в
+// реестре по позиции. Реестр сохраняется в level.dat и тикается каждый
+// кадр; сущности, чей блок сломан или заменён, удаляются.
+
+mod chest;
+
+pub use chest::{Chest, CHEST_SLOTS};
+
+use std::collections::HashMap;
+
+use crate::gpu::blocks::{BlockType, CHEST};
+use crate::gpu::save::{SavedBlockEntity, SavedBlockEntityData};
+use crate::gpu::terrain::BlockPos;
+
+/// Данные одного блока
+#[derive(Debug, Clone)]
+pub enum BlockEntity {
+    Chest(Chest),
+}
+
+impl BlockEntity {
+    /// Сущность для блока этого типа (None - блоку она не нужна)
+    pub fn for_block(block: BlockType) -> Option<Self> {
+        match block {
+            CHEST => Some(Self::Chest(Chest::new())),
+            _ => None,
+        }
+    }
+
+    /// Тип блока, которому принадлежит сущность
+    pub fn block_type(&self) -> BlockType {
+        match self {
+            Self::Chest(_) => CHEST,
+        }
+    }
+
+    pub fn tick(&mut self, dt: f32) {
+        match self {
+            Self::Chest(chest) => chest.tick(dt),
+        }
+    }
+
+    fn to_saved(&self, pos: BlockPos) -> SavedBlockEntity {
+        let data = match self {
+            Self::Chest(chest) => SavedBlockEntityData::Chest { slots: chest.to_saved() },
+        };
+        SavedBlockEntity { pos: [pos.x, pos.y, pos.z], data }
+    }
+
+    fn from_saved(saved: &SavedBlockEntity) -> Self {
+        match &saved.data {
+            SavedBlockEntityData::Chest { slots } => Self::Chest(Chest::from_saved(slots)),
+        }
+    }
+}
+
+/// Реестр сущностей блоков по позиции
+#[derive(Debug, Default)]
+pub struct BlockEntities {
+    entities: HashMap<BlockPos, BlockEntity>,
+}
+
+impl BlockEntities {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Реестр из сохранения
+    pub fn from_saved(saved: &[SavedBlockEntity]) -> Self {
+        let entities = saved
+            .iter()
+            .map(|entity| {
+                let [x, y, z] = entity.pos;
+                (BlockPos::new(x, y, z), BlockEntity::from_saved(entity))
+            })
+            .collect();
+        Self { entities }
+    }
+
+    /// Снимок для сохранения
+    pub fn to_saved(&self) -> Vec<SavedBlockEntity> {
+        self.entities.iter().map(|(pos, entity)| entity.to_saved(*pos)).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn get_mut(&mut self, pos: &BlockPos) -> Option<&mut BlockEntity> {
+        self.entities.get_mut(pos)
+    }
+
+    /// Блок поставлен: создать ему сущность, если она нужна
+    pub fn place(&mut self, pos: BlockPos, block: BlockType) {
+        match BlockEntity::for_block(block) {
+            Some(entity) => {
+                self.entities.insert(pos, entity);
+            }
+            None => {
+                self.entities.remove(&pos);
+            }
+        }
+    }
+
+    /// Сущность блока; создаётся, если блок её требует, а её ещё нет
+    /// (блок поставлен командой /fill или без реестра)
+    pub fn get_or_create(&mut self, pos: BlockPos, block: BlockType) -> Option<&mut BlockEntity> {
+        if self.entities.get(&pos).is_none_or(|entity| entity.block_type() != block) {
+            self.entities.insert(pos, BlockEntity::for_block(block)?);
+        }
+        self.entities.get_mut(&pos)
+    }
+
+    pub fn remove(&mut self, pos: &BlockPos) -> Option<BlockEntity> {
+        self.entities.remove(pos)
+    }
+
+    /// Тик всех сущностей. `block_at` - текущий блок на позиции;
+    /// сущности, чей блок пропал, удаляются и возвращаются
+    pub fn tick(&mut self, dt: f32, block_at: impl Fn(BlockPos) -> Option<BlockType>) -> Vec<(BlockPos, BlockEntity)> {
+        let orphaned: Vec<BlockPos> = self
+            .entities
+            .iter()
+            .filter(|(pos, entity)| block_at(**pos) != Some(entity.block_type()))
+            .map(|(pos, _)| *pos)
+            .collect();
+        let removed = orphaned
+            .into_iter()
+            .filter_map(|pos| self.entities.remove(&pos).map(|entity| (pos, entity)))
+            .collect();
+
+        for entity in self.entities.values_mut() {
+            entity.tick(dt);
+        }
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::blocks::{STONE, AIR};
+    use crate::gpu::items::ItemStack;
+
+    #[test]
+    fn only_chests_get_entities() {
+        let mut entities = BlockEntities::new();
+        entities.place(BlockPos::new(0, 64, 0), STONE);
+        assert_eq!(entities.len(), 0);
+        entities.place(BlockPos::new(0, 64, 0), CHEST);
+        assert_eq!(entities.len(), 1);
+        // Блок заменён - сущность уходит
+        entities.place(BlockPos::new(0, 64, 0), STONE);
+        assert_eq!(entities.len(), 0);
+    }
+
+    #[test]
+    fn tick_drops_orphaned_entities() {
+        let mut entities = BlockEntities::new();
+        let kept = BlockPos::new(1, 64, 1);
+        let broken = BlockPos::new(2, 64, 2);
+        entities.place(kept, CHEST);
+        entities.place(broken, CHEST);
+
+        let removed = entities.tick(0.05, |pos| Some(if pos == kept { CHEST } else { AIR }));
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].0, broken);
+        assert_eq!(entities.len(), 1);
+        assert!(entities.get_mut(&kept).is_some());
+    }
+
+    #[test]
+    fn saved_roundtrip() {
+        let mut entities = BlockEntities::new();
+        let pos = BlockPos::new(-5, 30, 12);
+        if let Some(BlockEntity::Chest(chest)) = entities.get_or_create(pos, CHEST) {
+            chest.insert(ItemStack::of_block(STONE));
+        }
+
+        let restored = BlockEntities::from_saved(&entities.to_saved());
+        let Some(BlockEntity::Chest(chest)) = restored.entities.get(&pos) else {
+            panic!("сундук не восстановлен");
+        };
+        assert_eq!(chest.slots()[0].as_ref().and_then(ItemStack::block), Some(STONE));
+    }
+}
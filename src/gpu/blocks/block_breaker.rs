@@ -10,9 +10,9 @@
 use ultraviolet::Vec3;
 use std::sync::Arc;
 use std::sync::RwLock;
-use crate::gpu::blocks::BlockType;
+use crate::gpu::blocks::{BlockType, get_block_break_info};
 use crate::gpu::player::Player;
-use crate::gpu::terrain::get_height;
+use crate::gpu::terrain::generation::terrain_generator;
 use crate::gpu::terrain::WorldChanges;
 
 /// Максимальная дистанция ломания блоков
@@ -21,6 +21,9 @@ pub const MAX_BREAK_DISTANCE: f32 = 5.0;
 /// Скорость ломания (базовая, без инструментов)
 pub const BASE_BREAK_SPEED: f32 = 1.0;
 
+/// Пауза после сломанного блока при зажатой кнопке (секунды)
+pub const BREAK_COOLDOWN: f32 = 0.2;
+
 /// Результат raycast — информация о блоке под прицелом
 #[derive(Debug, Clone, Copy)]
 pub struct BlockHit {
@@ -76,9 +79,12 @@ pub struct BlockBreaker {
     /// Максимальная дистанция
     max_distance: f32,
     
-    /// Множитель скорости ломания (от инструмента)
+    /// Множитель скорости ломания (режим игры, предмет в руке)
     break_speed_multiplier: f32,
     
+    /// Оставшаяся пауза до начала ломания следующего блока
+    cooldown: f32,
+    
     /// Ссылка на изменения мира
     world_changes: Arc<RwLock<WorldChanges>>,
 }
@@ -92,61 +98,101 @@ impl BlockBreaker {
             is_placing: false,
             max_distance: MAX_BREAK_DISTANCE,
             break_speed_multiplier: 1.0,
+            cooldown: 0.0,
             world_changes,
         }
     }
     
     /// Обработка нажатия кнопки мыши
-    pub fn process_mouse_button(&mut self, button: MouseButton, pressed: bool) -> Option<BlockHit> {
+    pub fn process_mouse_button(&mut self, button: MouseButton, pressed: bool) {
         match button {
             MouseButton::Left => {
-                // Мгновенное ломание по клику
-                if pressed {
-                    if let Some(hit) = &self.target_block {
-                        // Сразу ломаем блок
-                        let broken_block = *hit;
-                        
-                        {
-                            let mut changes = self.world_changes.write().unwrap();
-                            changes.break_block(
-                                broken_block.block_pos[0],
-                                broken_block.block_pos[1],
-                                broken_block.block_pos[2],
-                            );
-                        }
-                        
-                        return Some(broken_block);
-                    }
+                // Ломание идёт в update, пока кнопка зажата
+                self.is_breaking = pressed;
+                if !pressed {
+                    self.state = BreakState::Idle;
+                    self.cooldown = 0.0;
                 }
             }
             MouseButton::Right => {
                 self.is_placing = pressed;
             }
             MouseButton::Middle => {
-                // Средняя кнопка — пока не используется (можно для pick block)
+                // Намеренно пусто: pick block делает
+                // BlockInteractionSystem::handle_pick_block
             }
         }
-        None
     }
     
-    /// Установить множитель скорости (от инструмента)
+    /// Прервать ломание (меню, потеря фокуса)
+    pub fn stop_breaking(&mut self) {
+        self.process_mouse_button(MouseButton::Left, false);
+    }
+    
+    /// Установить множитель скорости
     pub fn set_break_speed(&mut self, multiplier: f32) {
         self.break_speed_multiplier = multiplier;
     }
     
-    /// Обновление каждый кадр — только raycast для выделения
-    pub fn update(&mut self, player: &Player, _dt: f32) {
+    /// Обновление каждый кадр: raycast и прогресс ломания.
+    /// Возвращает блок, сломанный в этом кадре
+    pub fn update(&mut self, player: &Player, dt: f32) -> Option<BlockHit> {
         // Raycast для поиска блока под прицелом
         self.target_block = self.raycast_block(player);
+        self.cooldown = (self.cooldown - dt).max(0.0);
+        
+        let Some(hit) = self.target_block else {
+            self.state = BreakState::Idle;
+            return None;
+        };
+        if !self.is_breaking || self.cooldown > 0.0 {
+            self.state = BreakState::Idle;
+            return None;
+        }
+        
+        let (hardness, breakable) = get_block_break_info(hit.block_type);
+        if !breakable {
+            self.state = BreakState::Idle;
+            return None;
+        }
+        
+        // Прицел ушёл на другой блок - начинаем заново
+        let progress = match self.state {
+            BreakState::Breaking { block_pos, progress } if block_pos == hit.block_pos => progress,
+            _ => 0.0,
+        };
+        
+        // hardness - время ломания в секундах голой рукой
+        let speed = BASE_BREAK_SPEED * self.break_speed_multiplier;
+        let progress = if hardness <= 0.0 { 1.0 } else { progress + dt * speed / hardness };
+        
+        if progress < 1.0 {
+            self.state = BreakState::Breaking { block_pos: hit.block_pos, progress };
+            return None;
+        }
+        
+        {
+            let mut changes = self.world_changes.write().unwrap();
+            changes.break_block(hit.block_pos[0], hit.block_pos[1], hit.block_pos[2]);
+        }
+        self.state = BreakState::Broken { block_pos: hit.block_pos, block_type: hit.block_type };
+        self.cooldown = BREAK_COOLDOWN;
+        Some(hit)
     }
     
     /// Raycast от глаз игрока в направлении взгляда
     fn raycast_block(&self, player: &Player) -> Option<BlockHit> {
+        self.raycast(player, self.max_distance)
+    }
+    
+    /// Raycast взгляда игрока со своей дальностью
+    /// (палочка строителя дотягивается дальше обычной установки)
+    pub fn raycast(&self, player: &Player, max_distance: f32) -> Option<BlockHit> {
         let origin = player.eye_position();
         let direction = player.forward();
         
         // DDA (Digital Differential Analyzer) алгоритм для воксельного raycast
-        self.dda_raycast(origin, direction, self.max_distance)
+        self.dda_raycast(origin, direction, max_distance)
     }
     
     /// DDA Raycast через воксельную сетку
@@ -245,9 +291,6 @@ impl BlockBreaker {
     
     /// Получить тип блока в координатах
     fn get_block_at(&self, x: i32, y: i32, z: i32) -> Option<BlockType> {
-        use crate::gpu::terrain::{CaveParams, is_cave};
-        use crate::gpu::biomes::biome_selector;
-        
         // Сначала проверяем изменения мира
         {
             let changes = self.world_changes.read().unwrap();
@@ -256,36 +299,8 @@ impl BlockBreaker {
             }
         }
         
-        // Иначе используем процедурную генерацию с биомами
-        let terrain_height = get_height(x as f32, z as f32) as i32;
-        
-        // Над поверхностью — воздух
-        if y > terrain_height {
-            return Some(super::AIR);
-        }
-        
-        // Проверяем пещеры
-        let cave_params = CaveParams::default();
-        let cave_ceiling = terrain_height - cave_params.surface_offset;
-        
-        if y >= cave_params.min_height && y < cave_ceiling {
-            if is_cave(x, y, z, &cave_params) {
-                return Some(super::AIR);
-            }
-        }
-        
-        // Получаем биом и используем его блоки
-        let biome = biome_selector().get_biome_def(x, z);
-        
-        if y < -29 {
-            Some(super::DEEPSLATE)
-        } else if y < terrain_height - 4 {
-            Some(biome.deep_block)
-        } else if y < terrain_height {
-            Some(biome.subsurface_block)
-        } else {
-            Some(biome.surface_block)
-        }
+        // Иначе - блок генератора мира
+        Some(terrain_generator().block_at(x, y, z))
     }
     
     // === Getters ===
@@ -308,6 +323,14 @@ impl BlockBreaker {
         }
     }
     
+    /// Ломаемый блок и прогресс (для анимации трещин)
+    pub fn breaking_block(&self) -> Option<([i32; 3], f32)> {
+        match self.state {
+            BreakState::Breaking { block_pos, progress } => Some((block_pos, progress)),
+            _ => None,
+        }
+    }
+    
     /// Позиция блока для выделения (если есть)
     pub fn highlight_block_pos(&self) -> Option<[i32; 3]> {
         self.target_block.as_ref().map(|hit| hit.block_pos)
@@ -233,6 +233,38 @@ pub enum BlockCategory {
     Metal,
 }
 
+/// Инструмент для ломания
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolKind {
+    /// Рука (для блока - подходит любой инструмент)
+    #[default]
+    Hand,
+    Pickaxe,
+    Shovel,
+    Axe,
+}
+
+/// Уровень (материал) инструмента
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolTier {
+    Wood,
+    Stone,
+    Iron,
+    Diamond,
+}
+
+/// Действие по ПКМ на блоке (on_use). Shift+ПКМ всегда ставит блок
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UseAction {
+    /// Запомнить блок как точку возрождения (кровать)
+    SetRespawn,
+    /// Открыть экран контейнера блока (сундук)
+    OpenContainer,
+}
+
 /// Звуки блока
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct BlockSounds {
@@ -264,10 +296,29 @@ pub struct BlockDefinition {
     #[serde(default = "default_hardness")]
     pub hardness: f32,
     
+    /// Инструмент для добычи (с requires_tool); скорость ломания задаёт
+    /// предмет в руке (speeds в JSON предмета)
+    #[serde(default)]
+    pub tool: ToolKind,
+    
+    /// Минимальный уровень инструмента `tool`, без которого блок
+    /// ничего не роняет (None - роняется всегда)
+    #[serde(default)]
+    pub requires_tool: Option<ToolTier>,
+    
     /// Прозрачный ли блок
     #[serde(default)]
     pub transparent: bool,
     
+    /// Полупрозрачный (вода, стекло, лёд): грани рисуются отдельным
+    /// проходом со смешиванием, после непрозрачной геометрии
+    #[serde(default)]
+    pub translucent: bool,
+    
+    /// Непрозрачность полупрозрачного блока (0-1)
+    #[serde(default = "default_opacity")]
+    pub opacity: f32,
+    
     /// Излучает ли свет
     #[serde(default)]
     pub emissive: bool,
@@ -284,6 +335,20 @@ pub struct BlockDefinition {
     #[serde(default = "default_true")]
     pub breakable: bool,
     
+    /// Можно лезть вверх (лестница, лоза): W или Space у блока - подъём,
+    /// иначе медленное сползание
+    #[serde(default)]
+    pub climbable: bool,
+    
+    /// Блок возрождения (кровать): ПКМ по нему запоминает точку
+    /// появления игрока после смерти
+    #[serde(default)]
+    pub respawn_point: bool,
+    
+    /// Действие по ПКМ вместо установки блока (None - только установка)
+    #[serde(default)]
+    pub on_use: Option<UseAction>,
+    
     /// Категория
     #[serde(default)]
     pub category: BlockCategory,
@@ -303,6 +368,7 @@ pub struct BlockDefinition {
 
 fn default_hardness() -> f32 { 1.0 }
 fn default_true() -> bool { true }
+fn default_opacity() -> f32 { 0.6 }
 
 impl Default for BlockDefinition {
     fn default() -> Self {
@@ -312,11 +378,18 @@ impl Default for BlockDefinition {
             name: "Unknown".to_string(),
             color: ColorDef::default(),
             hardness: 1.0,
+            tool: ToolKind::Hand,
+            requires_tool: None,
             transparent: false,
+            translucent: false,
+            opacity: default_opacity(),
             emissive: false,
             light_level: 0,
             solid: true,
             breakable: true,
+            climbable: false,
+            respawn_point: false,
+            on_use: None,
             category: BlockCategory::Basic,
             textures: None,
             sounds: BlockSounds::default(),
@@ -1,81 +1,324 @@
 // ============================================
 // Block Texture Atlas
 // ============================================
-// Генерирует текстурный атлас из JSON-определений блоков
+// Генерирует текстурный атлас из JSON-определений блоков.
+//
+// Каждый тайл 16x16 лежит в ячейке 32x32 с отступом 8px, заполненным
+// по кругу (wrap) содержимым тайла - соседние тайлы не протекают даже
+// на мип-уровнях. Ячейки выровнены по степени двойки, поэтому мипы
+// считаются простым 2x2 усреднением всего атласа.
+//
+// У каждого блока три тайла (верх, низ, бок). Раскладка зависит только
+// от реестра блоков, поэтому смена пакетов ресурсов перерисовывает
+// пиксели без перестройки мешей. Тайлы с текстурой (из пакета или
+// определения блока) отмечены битовой маской: шейдер берёт для них
+// атлас, для остальных - процедурную текстуру по цвету блока.
+//
+// Атлас с отступами - холст для рисования и мипов. На GPU тайлы уходят
+// слоями текстурного массива (layer_levels): так сэмплер с Repeat
+// повторяет тайл по склеенному greedy-кваду.
+
+use std::sync::{OnceLock, RwLock};
 
 use super::{global_registry, BlockDefinition};
 use super::definition::{TextureDef, PixelValue, FaceTextures};
+use crate::gpu::resource_pack::{global_resource_packs, ResourcePackManager};
 
 /// Размер одной текстуры в атласе
 pub const TEXTURE_SIZE: u32 = 16;
-/// Максимум текстур в атласе (16x16 = 256 блоков)
-pub const ATLAS_SIZE: u32 = 16;
+/// Отступ вокруг тайла (с каждой стороны)
+pub const TILE_PADDING: u32 = 8;
+/// Размер ячейки тайла с отступами
+pub const CELL_SIZE: u32 = TEXTURE_SIZE + TILE_PADDING * 2;
+/// Ячеек по стороне атласа (32x32 = 1024 тайла)
+pub const ATLAS_SIZE: u32 = 32;
 /// Размер атласа в пикселях
-pub const ATLAS_PIXELS: u32 = ATLAS_SIZE * TEXTURE_SIZE;
+pub const ATLAS_PIXELS: u32 = ATLAS_SIZE * CELL_SIZE;
+/// Количество мип-уровней (последний: ячейка 2x2, тайл 1x1)
+pub const ATLAS_MIP_LEVELS: u32 = 5;
+/// Слоёв в текстурном массиве на GPU: по слою на тайл
+pub const ATLAS_LAYERS: u32 = ATLAS_SIZE * ATLAS_SIZE;
+/// Слов u32 в маске тайлов с текстурой (бит на тайл)
+pub const TEXTURED_MASK_WORDS: usize = (ATLAS_LAYERS / 32) as usize;
+/// Флаги воды в block_id вершины (старшие биты поля тайла, тайлу хватает 14):
+/// грань смотрит в воздух у кромки воды - мокрая полоса
+pub const VERTEX_WET: u32 = 1 << 22;
+/// Грань смотрит в воду - подводные каустики
+pub const VERTEX_SUBMERGED: u32 = 1 << 23;
+const _: () = assert!(ATLAS_LAYERS <= 1 << 14);
+
+/// Тайл 0 зарезервирован под "нет текстуры" (magenta)
+const MISSING_TILE: u16 = 0;
+
+/// Грань блока для выбора тайла
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AtlasFace {
+    Top = 0,
+    Bottom = 1,
+    Side = 2,
+}
+
+impl AtlasFace {
+    pub const ALL: [AtlasFace; 3] = [AtlasFace::Top, AtlasFace::Bottom, AtlasFace::Side];
+}
+
+/// UV прямоугольник тайла в атласе (без отступов)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AtlasUv {
+    pub u0: f32,
+    pub v0: f32,
+    pub u1: f32,
+    pub v1: f32,
+}
+
+/// Раскладка тайлов: (блок, грань) -> номер тайла
+/// Мешер пакует номер тайла в вершину: block_id | (tile << 8) | (alpha << 24),
+/// флаги воды (VERTEX_WET, VERTEX_SUBMERGED) - в битах 22-23
+#[derive(Debug, Clone, PartialEq)]
+pub struct AtlasTiles {
+    /// [block_id * 3 + face] -> тайл
+    tiles: Vec<u16>,
+    /// [block_id] -> непрозрачность полупрозрачного блока (1-255),
+    /// 0 - обычный непрозрачный блок
+    alpha: Vec<u8>,
+    /// Растёт при каждом изменении раскладки
+    version: u64,
+}
+
+impl AtlasTiles {
+    /// Построить раскладку из реестра блоков
+    pub fn from_registry() -> Self {
+        let mut tiles = vec![MISSING_TILE; 256 * 3];
+        let mut alpha = vec![0u8; 256];
+        if let Ok(registry) = global_registry().read() {
+            let mut next = MISSING_TILE + 1;
+            for def in registry.all_blocks() {
+                if def.numeric_id == 0 { continue; } // Skip air
+                if def.translucent {
+                    alpha[def.numeric_id as usize] = (def.opacity.clamp(0.0, 1.0) * 255.0).round().max(1.0) as u8;
+                }
+                if next as u32 + 3 > ATLAS_LAYERS { break; }
+                for face in AtlasFace::ALL {
+                    tiles[def.numeric_id as usize * 3 + face as usize] = next;
+                    next += 1;
+                }
+            }
+        }
+        Self { tiles, alpha, version: 0 }
+    }
+
+    /// Номер тайла для грани блока
+    #[inline]
+    pub fn tile(&self, block_id: u8, face: AtlasFace) -> u16 {
+        self.tiles[block_id as usize * 3 + face as usize]
+    }
+
+    /// Полупрозрачный ли блок (грани идут в проход со смешиванием)
+    #[inline]
+    pub fn is_translucent(&self, block_id: u8) -> bool {
+        self.alpha[block_id as usize] != 0
+    }
+
+    /// Значение block_id для вершины: тип блока, тайл атласа и
+    /// непрозрачность (по ней GpuChunk делит меш на два прохода)
+    #[inline]
+    pub fn vertex_id(&self, block_id: u8, face: AtlasFace) -> u32 {
+        block_id as u32 | (self.tile(block_id, face) as u32) << 8 | (self.alpha[block_id as usize] as u32) << 24
+    }
+
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Позиция ячейки тайла в атласе (в ячейках)
+    #[inline]
+    pub fn cell(tile: u16) -> (u32, u32) {
+        (tile as u32 % ATLAS_SIZE, tile as u32 / ATLAS_SIZE)
+    }
+
+    /// UV координаты тайла (без отступов)
+    pub fn uv(tile: u16) -> AtlasUv {
+        let (cx, cy) = Self::cell(tile);
+        let x0 = (cx * CELL_SIZE + TILE_PADDING) as f32;
+        let y0 = (cy * CELL_SIZE + TILE_PADDING) as f32;
+        let size = ATLAS_PIXELS as f32;
+        AtlasUv {
+            u0: x0 / size,
+            v0: y0 / size,
+            u1: (x0 + TEXTURE_SIZE as f32) / size,
+            v1: (y0 + TEXTURE_SIZE as f32) / size,
+        }
+    }
+}
 
 /// Текстурный атлас блоков
 pub struct BlockTextureAtlas {
-    /// RGBA данные атласа (ATLAS_PIXELS x ATLAS_PIXELS x 4)
+    /// RGBA данные атласа (ATLAS_PIXELS x ATLAS_PIXELS x 4), мип 0
     pub data: Vec<u8>,
-    /// Маппинг block_id -> позиция в атласе (x, y)
-    pub block_positions: std::collections::HashMap<u8, (u32, u32)>,
+    /// Раскладка тайлов
+    pub tiles: AtlasTiles,
+    /// Бит на тайл: есть текстура из пакета или определения блока
+    textured: [u32; TEXTURED_MASK_WORDS],
 }
 
 impl BlockTextureAtlas {
     /// Создать атлас из реестра блоков
+    /// Заодно обновляет глобальную раскладку, если реестр изменился
     pub fn from_registry() -> Self {
+        let packs = global_resource_packs().read().ok();
+        Self::build(packs.as_deref())
+    }
+    
+    /// Атлас из реестра блоков и заданных пакетов ресурсов
+    pub fn build(packs: Option<&ResourcePackManager>) -> Self {
+        let tiles = publish_atlas_tiles(AtlasTiles::from_registry());
         let mut atlas = Self {
             data: vec![0u8; (ATLAS_PIXELS * ATLAS_PIXELS * 4) as usize],
-            block_positions: std::collections::HashMap::new(),
+            tiles,
+            textured: [0; TEXTURED_MASK_WORDS],
         };
         
+        atlas.fill_magenta(MISSING_TILE);
+        atlas.pad_tile(MISSING_TILE);
+        
         if let Ok(registry) = global_registry().read() {
-            let mut slot = 0u32;
-            
             for def in registry.all_blocks() {
                 if def.numeric_id == 0 { continue; } // Skip air
                 
-                let atlas_x = slot % ATLAS_SIZE;
-                let atlas_y = slot / ATLAS_SIZE;
-                
-                atlas.block_positions.insert(def.numeric_id, (atlas_x, atlas_y));
-                atlas.render_block_texture(def, atlas_x, atlas_y);
-                
-                slot += 1;
-                if slot >= ATLAS_SIZE * ATLAS_SIZE { break; }
+                for face in AtlasFace::ALL {
+                    let tile = atlas.tiles.tile(def.numeric_id, face);
+                    if tile == MISSING_TILE { continue; }
+                    if atlas.render_block_texture(def, face, tile, packs) {
+                        atlas.textured[tile as usize / 32] |= 1 << (tile % 32);
+                    }
+                    atlas.pad_tile(tile);
+                }
             }
         }
         
         atlas
     }
     
-    /// Рендерит текстуру блока в атлас
-    fn render_block_texture(&mut self, def: &BlockDefinition, atlas_x: u32, atlas_y: u32) {
-        let base_x = atlas_x * TEXTURE_SIZE;
-        let base_y = atlas_y * TEXTURE_SIZE;
+    /// Есть ли у тайла текстура (иначе блок рисуется процедурно)
+    pub fn is_textured(&self, tile: u16) -> bool {
+        self.textured[tile as usize / 32] & (1 << (tile % 32)) != 0
+    }
+    
+    /// Маска тайлов с текстурой для шейдера
+    pub fn textured_mask(&self) -> [u32; TEXTURED_MASK_WORDS] {
+        self.textured
+    }
+    
+    /// Мип-уровни 1.. (каждый вдвое меньше предыдущего)
+    /// Усреднение в линейном пространстве, т.к. текстура sRGB
+    pub fn mip_levels(&self) -> Vec<Vec<u8>> {
+        let mut levels: Vec<Vec<u8>> = Vec::with_capacity(ATLAS_MIP_LEVELS as usize - 1);
+        let mut src_size = ATLAS_PIXELS;
+        for _ in 1..ATLAS_MIP_LEVELS {
+            let src = levels.last().unwrap_or(&self.data);
+            let dst = downsample(src, src_size);
+            src_size /= 2;
+            levels.push(dst);
+        }
+        levels
+    }
+    
+    /// Первые layers тайлов как слои текстурного массива: по мип-уровню
+    /// подряд квадраты TEXTURE_SIZE >> mip. Мипы вырезаются из мипов
+    /// атласа - отступы (wrap) уже сделали их края повторяемыми
+    pub fn layer_levels(&self, layers: u32) -> Vec<Vec<u8>> {
+        let mips = self.mip_levels();
+        std::iter::once(&self.data)
+            .chain(mips.iter())
+            .enumerate()
+            .map(|(mip, data)| {
+                let atlas_size = ATLAS_PIXELS >> mip;
+                let tile_size = TEXTURE_SIZE >> mip;
+                let row = (tile_size * 4) as usize;
+                let mut out = Vec::with_capacity(layers as usize * row * tile_size as usize);
+                for tile in 0..layers {
+                    let (x, y) = Self::tile_origin(tile as u16);
+                    let (x, y) = (x >> mip, y >> mip);
+                    for dy in 0..tile_size {
+                        let start = (((y + dy) * atlas_size + x) * 4) as usize;
+                        out.extend_from_slice(&data[start..start + row]);
+                    }
+                }
+                out
+            })
+            .collect()
+    }
+    
+    /// Левый верхний пиксель тайла (внутри отступов)
+    fn tile_origin(tile: u16) -> (u32, u32) {
+        let (cx, cy) = AtlasTiles::cell(tile);
+        (cx * CELL_SIZE + TILE_PADDING, cy * CELL_SIZE + TILE_PADDING)
+    }
+    
+    /// Заполнить отступы ячейки содержимым тайла (wrap)
+    fn pad_tile(&mut self, tile: u16) {
+        let (base_x, base_y) = Self::tile_origin(tile);
+        let cell_x = base_x - TILE_PADDING;
+        let cell_y = base_y - TILE_PADDING;
+        for y in 0..CELL_SIZE {
+            for x in 0..CELL_SIZE {
+                let inside = (TILE_PADDING..TILE_PADDING + TEXTURE_SIZE).contains(&x)
+                    && (TILE_PADDING..TILE_PADDING + TEXTURE_SIZE).contains(&y);
+                if inside { continue; }
+                let src_x = base_x + (x as i32 - TILE_PADDING as i32).rem_euclid(TEXTURE_SIZE as i32) as u32;
+                let src_y = base_y + (y as i32 - TILE_PADDING as i32).rem_euclid(TEXTURE_SIZE as i32) as u32;
+                let rgba = self.get_pixel(src_x, src_y);
+                self.set_pixel(cell_x + x, cell_y + y, rgba);
+            }
+        }
+    }
+    
+    /// Рендерит текстуру грани блока в тайл
+    /// Пакеты ресурсов переопределяют текстуру по пути textures/block/<id>[_top|_bottom].png
+    /// Возвращает false, если текстуры нет и тайл залит цветом блока
+    fn render_block_texture(&mut self, def: &BlockDefinition, face: AtlasFace, tile: u16, packs: Option<&ResourcePackManager>) -> bool {
+        let (base_x, base_y) = Self::tile_origin(tile);
         
-        // Получаем текстуру (side для отображения в инвентаре)
-        let texture = self.get_side_texture(def);
+        if let Some(packs) = packs {
+            let texture = block_texture_paths(&def.id, face)
+                .iter()
+                .find_map(|path| packs.read_texture(path));
+            if let Some((width, height, rgba)) = texture {
+                self.render_rgba(width, height, &rgba, base_x, base_y);
+                return true;
+            }
+        }
         
-        match texture {
-            Some(tex) => self.render_texture_def(&tex, base_x, base_y),
-            None => self.render_solid_color(def, base_x, base_y),
+        match self.get_face_texture(def, face) {
+            Some(tex) => {
+                self.render_texture_def(&tex, base_x, base_y, packs);
+                true
+            }
+            None => {
+                self.render_solid_color(def, base_x, base_y);
+                false
+            }
         }
     }
     
-    /// Получить текстуру боковой грани
-    fn get_side_texture(&self, def: &BlockDefinition) -> Option<TextureDef> {
+    /// Получить текстуру грани (верх/низ откатываются на бок)
+    fn get_face_texture(&self, def: &BlockDefinition, face: AtlasFace) -> Option<TextureDef> {
         match &def.textures {
             Some(FaceTextures::All(tex)) => Some(tex.clone()),
-            Some(FaceTextures::PerFace { side, north, .. }) => {
-                side.clone().or_else(|| north.clone())
+            Some(FaceTextures::PerFace { top, bottom, side, north, .. }) => {
+                let side = side.clone().or_else(|| north.clone());
+                match face {
+                    AtlasFace::Top => top.clone().or(side),
+                    AtlasFace::Bottom => bottom.clone().or(side),
+                    AtlasFace::Side => side,
+                }
             }
             None => None,
         }
     }
     
     /// Рендерит TextureDef в атлас
-    fn render_texture_def(&mut self, tex: &TextureDef, base_x: u32, base_y: u32) {
+    fn render_texture_def(&mut self, tex: &TextureDef, base_x: u32, base_y: u32, packs: Option<&ResourcePackManager>) {
         match tex {
             TextureDef::Pixels { width, height, pixels } => {
                 self.render_pixels(*width as u32, *height as u32, pixels, base_x, base_y);
@@ -86,9 +329,12 @@ impl BlockTextureAtlas {
             TextureDef::Procedural { proc_type, params } => {
                 self.render_procedural(proc_type, params, base_x, base_y);
             }
-            TextureDef::File(_) => {
-                // TODO: загрузка из файла
-                self.fill_magenta(base_x, base_y);
+            TextureDef::File(path) => {
+                // Файл ищется в пакетах ресурсов как namespaced путь
+                match packs.and_then(|packs| packs.read_texture(path)) {
+                    Some((width, height, rgba)) => self.render_rgba(width, height, &rgba, base_x, base_y),
+                    None => self.fill_magenta_at(base_x, base_y),
+                }
             }
         }
     }
@@ -113,6 +359,25 @@ impl BlockTextureAtlas {
         }
     }
     
+    /// Рендерит RGBA8 изображение (из PNG пакета ресурсов)
+    fn render_rgba(&mut self, width: u32, height: u32, rgba: &[u8], base_x: u32, base_y: u32) {
+        for y in 0..TEXTURE_SIZE {
+            for x in 0..TEXTURE_SIZE {
+                let src_x = x * width / TEXTURE_SIZE;
+                let src_y = y * height / TEXTURE_SIZE;
+                let idx = ((src_y * width + src_x) * 4) as usize;
+                
+                let pixel = if idx + 3 < rgba.len() {
+                    [rgba[idx], rgba[idx + 1], rgba[idx + 2], rgba[idx + 3]]
+                } else {
+                    [255, 0, 255, 255]
+                };
+                
+                self.set_pixel(base_x + x, base_y + y, pixel);
+            }
+        }
+    }
+    
     /// Рендерит индексированную текстуру
     fn render_indexed(&mut self, palette: &[PixelValue], indices: &[u8], width: u32, height: u32, base_x: u32, base_y: u32) {
         for y in 0..TEXTURE_SIZE {
@@ -195,8 +460,13 @@ impl BlockTextureAtlas {
         }
     }
     
-    /// Заполняет magenta (ошибка)
-    fn fill_magenta(&mut self, base_x: u32, base_y: u32) {
+    /// Заполняет тайл magenta (ошибка)
+    fn fill_magenta(&mut self, tile: u16) {
+        let (base_x, base_y) = Self::tile_origin(tile);
+        self.fill_magenta_at(base_x, base_y);
+    }
+    
+    fn fill_magenta_at(&mut self, base_x: u32, base_y: u32) {
         for y in 0..TEXTURE_SIZE {
             for x in 0..TEXTURE_SIZE {
                 let checker = (x / 4 + y / 4) % 2 == 0;
@@ -217,18 +487,91 @@ impl BlockTextureAtlas {
         }
     }
     
-    /// Получить UV координаты для блока
-    pub fn get_uv(&self, block_id: u8) -> Option<(f32, f32, f32, f32)> {
-        self.block_positions.get(&block_id).map(|&(x, y)| {
-            let u0 = x as f32 / ATLAS_SIZE as f32;
-            let v0 = y as f32 / ATLAS_SIZE as f32;
-            let u1 = (x + 1) as f32 / ATLAS_SIZE as f32;
-            let v1 = (y + 1) as f32 / ATLAS_SIZE as f32;
-            (u0, v0, u1, v1)
-        })
+    /// Читает пиксель атласа (мип 0)
+    fn get_pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let idx = ((y * ATLAS_PIXELS + x) * 4) as usize;
+        [self.data[idx], self.data[idx + 1], self.data[idx + 2], self.data[idx + 3]]
+    }
+    
+    /// Получить UV координаты грани блока
+    pub fn get_uv(&self, block_id: u8, face: AtlasFace) -> Option<AtlasUv> {
+        match self.tiles.tile(block_id, face) {
+            MISSING_TILE => None,
+            tile => Some(AtlasTiles::uv(tile)),
+        }
     }
 }
 
+/// Пути текстуры грани блока в пакете ресурсов, по приоритету
+/// ("ns:id" -> "ns:textures/block/id_top.png", "ns:textures/block/id.png")
+fn block_texture_paths(block_id: &str, face: AtlasFace) -> Vec<String> {
+    let (prefix, name) = match block_id.split_once(':') {
+        Some((namespace, name)) => (format!("{}:", namespace), name),
+        None => (String::new(), block_id),
+    };
+    let mut paths = Vec::with_capacity(2);
+    match face {
+        AtlasFace::Top => paths.push(format!("{}textures/block/{}_top.png", prefix, name)),
+        AtlasFace::Bottom => paths.push(format!("{}textures/block/{}_bottom.png", prefix, name)),
+        AtlasFace::Side => {}
+    }
+    paths.push(format!("{}textures/block/{}.png", prefix, name));
+    paths
+}
+
+/// Уменьшить RGBA изображение вдвое (2x2 усреднение, sRGB -> linear -> sRGB)
+fn downsample(src: &[u8], src_size: u32) -> Vec<u8> {
+    let dst_size = src_size / 2;
+    let mut dst = vec![0u8; (dst_size * dst_size * 4) as usize];
+    for y in 0..dst_size {
+        for x in 0..dst_size {
+            let mut sum = [0.0f32; 4];
+            for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                let idx = (((y * 2 + dy) * src_size + x * 2 + dx) * 4) as usize;
+                for c in 0..3 {
+                    sum[c] += srgb_to_linear(src[idx + c]);
+                }
+                sum[3] += src[idx + 3] as f32 / 255.0;
+            }
+            let idx = ((y * dst_size + x) * 4) as usize;
+            for c in 0..3 {
+                dst[idx + c] = linear_to_srgb(sum[c] / 4.0);
+            }
+            dst[idx + 3] = (sum[3] / 4.0 * 255.0).round() as u8;
+        }
+    }
+    dst
+}
+
+fn srgb_to_linear(value: u8) -> f32 {
+    (value as f32 / 255.0).powf(2.2)
+}
+
+fn linear_to_srgb(value: f32) -> u8 {
+    (value.powf(1.0 / 2.2) * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+// ============================================
+// Global Tiles Singleton
+// ============================================
+
+static GLOBAL_TILES: OnceLock<RwLock<AtlasTiles>> = OnceLock::new();
+
+/// Получить глобальную раскладку тайлов (для мешера)
+pub fn global_atlas_tiles() -> &'static RwLock<AtlasTiles> {
+    GLOBAL_TILES.get_or_init(|| RwLock::new(AtlasTiles::from_registry()))
+}
+
+/// Опубликовать раскладку; версия растёт только при реальном изменении
+fn publish_atlas_tiles(mut tiles: AtlasTiles) -> AtlasTiles {
+    let Ok(mut global) = global_atlas_tiles().write() else { return tiles };
+    if global.tiles != tiles.tiles {
+        tiles.version = global.version + 1;
+        *global = tiles;
+    }
+    global.clone()
+}
+
 /// Простой хеш для процедурных текстур
 fn simple_hash(x: u32, y: u32) -> u8 {
     let n = x.wrapping_mul(374761393).wrapping_add(y.wrapping_mul(668265263));
@@ -245,3 +588,58 @@ fn lerp_color(a: [u8; 4], b: [u8; 4], t: f32) -> [u8; 4] {
         255,
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layers_are_tiles_without_padding() {
+        let mut atlas = BlockTextureAtlas {
+            data: vec![0u8; (ATLAS_PIXELS * ATLAS_PIXELS * 4) as usize],
+            tiles: AtlasTiles::from_registry(),
+            textured: [0; TEXTURED_MASK_WORDS],
+        };
+        atlas.fill_magenta(MISSING_TILE);
+        atlas.pad_tile(MISSING_TILE);
+
+        let levels = atlas.layer_levels(2);
+        assert_eq!(levels.len(), ATLAS_MIP_LEVELS as usize);
+        assert_eq!(levels[0].len(), (2 * TEXTURE_SIZE * TEXTURE_SIZE * 4) as usize);
+        assert_eq!(levels[4].len(), 2 * 4);
+        // Слой 0 начинается с клетки шахматки, а не с отступа
+        assert_eq!(levels[0][..4], [255, 0, 255, 255]);
+        assert_eq!(levels[0][(4 * 4) as usize..(4 * 4 + 4) as usize], [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn pack_override_textures_a_builtin_block() {
+        let root = std::env::temp_dir().join("kimi_voxel_atlas_pack_test");
+        let dir = root.join("atlas_test_pack/assets/kimi/textures/block");
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut png_bytes = Vec::new();
+        {
+            let mut encoder = png::Encoder::new(&mut png_bytes, TEXTURE_SIZE, TEXTURE_SIZE);
+            encoder.set_color(png::ColorType::Rgba);
+            encoder.set_depth(png::BitDepth::Eight);
+            let mut writer = encoder.write_header().unwrap();
+            writer.write_image_data(&[200, 10, 20, 255].repeat((TEXTURE_SIZE * TEXTURE_SIZE) as usize)).unwrap();
+        }
+        std::fs::write(dir.join("stone.png"), png_bytes).unwrap();
+        let mut packs = ResourcePackManager::new(&root);
+        packs.toggle("atlas_test_pack");
+
+        let stone = global_registry().read().unwrap().get_numeric_id("stone").unwrap();
+        let plain = BlockTextureAtlas::build(None);
+        let packed = BlockTextureAtlas::build(Some(&packs));
+        for face in AtlasFace::ALL {
+            // Без пакета камень процедурный, с пакетом - тайл из PNG
+            let tile = packed.tiles.tile(stone, face);
+            assert!(!plain.is_textured(tile));
+            assert!(packed.is_textured(tile));
+            let (x, y) = BlockTextureAtlas::tile_origin(tile);
+            assert_eq!(packed.get_pixel(x + 3, y + 5), [200, 10, 20, 255]);
+        }
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -13,12 +13,20 @@ pub const DIRT: BlockType = 2;
 pub const GRASS: BlockType = 3;
 pub const SAND: BlockType = 4;
 pub const GRAVEL: BlockType = 5;
+pub const RED_SAND: BlockType = 6;
+pub const MYCELIUM: BlockType = 7;
+pub const MUD: BlockType = 8;
+pub const OAK_SAPLING: BlockType = 9;
 pub const COBBLESTONE: BlockType = 10;
 pub const MOSSY_COBBLESTONE: BlockType = 11;
 pub const GRANITE: BlockType = 12;
 pub const DIORITE: BlockType = 13;
 pub const ANDESITE: BlockType = 14;
 pub const DEEPSLATE: BlockType = 15;
+pub const DRIPSTONE_BLOCK: BlockType = 16;
+pub const POINTED_DRIPSTONE: BlockType = 17;
+pub const SCULK: BlockType = 18;
+pub const SCULK_SENSOR: BlockType = 19;
 pub const COAL_ORE: BlockType = 20;
 pub const IRON_ORE: BlockType = 21;
 pub const GOLD_ORE: BlockType = 22;
@@ -27,6 +35,8 @@ pub const EMERALD_ORE: BlockType = 24;
 pub const REDSTONE_ORE: BlockType = 25;
 pub const LAPIS_ORE: BlockType = 26;
 pub const COPPER_ORE: BlockType = 27;
+pub const BIRCH_SAPLING: BlockType = 28;
+pub const SPRUCE_SAPLING: BlockType = 29;
 pub const OAK_LOG: BlockType = 30;
 pub const OAK_PLANKS: BlockType = 31;
 pub const OAK_LEAVES: BlockType = 32;
@@ -36,6 +46,14 @@ pub const BIRCH_LEAVES: BlockType = 35;
 pub const SPRUCE_LOG: BlockType = 36;
 pub const SPRUCE_PLANKS: BlockType = 37;
 pub const SPRUCE_LEAVES: BlockType = 38;
+pub const CACTUS: BlockType = 41;
+pub const MUSHROOM_STEM: BlockType = 42;
+pub const RED_MUSHROOM_BLOCK: BlockType = 43;
+pub const BROWN_MUSHROOM_BLOCK: BlockType = 44;
+pub const MOSS_BLOCK: BlockType = 45;
+pub const GLOW_VINES: BlockType = 46;
+pub const CHEST: BlockType = 48;
+pub const TORCH: BlockType = 49;
 pub const WATER: BlockType = 50;
 pub const LAVA: BlockType = 51;
 pub const ICE: BlockType = 52;
@@ -67,7 +85,52 @@ pub fn is_solid(block: BlockType) -> bool {
 /// Проверка: блок прозрачный?
 #[inline]
 pub fn is_transparent(block: BlockType) -> bool {
-    matches!(block, AIR | WATER | GLASS | OAK_LEAVES | BIRCH_LEAVES | SPRUCE_LEAVES)
+    matches!(block, AIR | WATER | GLASS | OAK_LEAVES | BIRCH_LEAVES | SPRUCE_LEAVES) || is_sapling(block)
+}
+
+/// Проверка: по блоку можно лезть (флаг climbable в реестре)
+#[inline]
+pub fn is_climbable(block: BlockType) -> bool {
+    if let Ok(registry) = super::global_registry().read() {
+        if let Some(def) = registry.get_by_numeric(block) {
+            return def.climbable;
+        }
+    }
+    false
+}
+
+/// Проверка: саженец?
+#[inline]
+pub fn is_sapling(block: BlockType) -> bool {
+    matches!(block, OAK_SAPLING | BIRCH_SAPLING | SPRUCE_SAPLING)
+}
+
+/// Саженец, который выпадает из листвы
+#[inline]
+pub fn sapling_for_leaves(leaves: BlockType) -> Option<BlockType> {
+    match leaves {
+        OAK_LEAVES => Some(OAK_SAPLING),
+        BIRCH_LEAVES => Some(BIRCH_SAPLING),
+        SPRUCE_LEAVES => Some(SPRUCE_SAPLING),
+        _ => None,
+    }
+}
+
+/// Действие блока по ПКМ (on_use в реестре)
+#[inline]
+pub fn use_action(block: BlockType) -> Option<super::UseAction> {
+    super::global_registry().read().ok()?.get_by_numeric(block)?.on_use
+}
+
+/// Проверка: блок задаёт точку возрождения (флаг respawn_point в реестре)
+#[inline]
+pub fn is_respawn_point(block: BlockType) -> bool {
+    if let Ok(registry) = super::global_registry().read() {
+        if let Some(def) = registry.get_by_numeric(block) {
+            return def.respawn_point;
+        }
+    }
+    false
 }
 
 /// Получить цвет блока из реестра
@@ -113,3 +176,14 @@ pub fn get_block_hardness(block: BlockType) -> f32 {
     }
     1.0
 }
+
+/// Параметры ломания блока: (hardness, можно ли сломать)
+#[inline]
+pub fn get_block_break_info(block: BlockType) -> (f32, bool) {
+    if let Ok(registry) = super::global_registry().read() {
+        if let Some(def) = registry.get_by_numeric(block) {
+            return (def.hardness, def.breakable);
+        }
+    }
+    (1.0, true)
+}
@@ -4,6 +4,7 @@
 // Получает блоки для генерации мира из реестра по string ID
 
 use super::{BlockType, global_registry};
+use crate::gpu::terrain::voxel::constants::TERRAIN_FLOOR;
 use super::types::*;
 
 /// Кэшированные ID блоков для быстрого доступа при генерации
@@ -65,8 +66,8 @@ impl WorldGenBlocks {
     
     #[inline]
     pub fn block_at_depth(&self, y: i32, surface_y: i32, surface_height: f32) -> BlockType {
-        if y > surface_y { self.air }
-        else if y < -29 { self.bedrock }
+        if y > surface_y || y < TERRAIN_FLOOR { self.air }
+        else if y < TERRAIN_FLOOR + 3 { self.bedrock }
         else if y < surface_y - 4 { self.stone }
         else if y < surface_y { self.subsurface_block(surface_height) }
         else { self.surface_block(surface_height) }
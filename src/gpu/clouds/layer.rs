@@ -0,0 +1,134 @@
+// ============================================
+// Cloud Layer - Состояние слоя облаков
+// ============================================
+// Облака - 2D шум на высоте CLOUD_ALTITUDE, сдвинутый на offset.
+// Слой всегда медленно дрейфует, ветер погоды ускоряет его;
+// покрытие растёт с облачностью (ясно - редкие облака, гроза - сплошные).
+
+use serde::{Serialize, Deserialize};
+
+use crate::gpu::weather::WeatherParams;
+
+/// Нижняя граница облаков (выше самых высоких гор рельефа)
+pub const CLOUD_ALTITUDE: f32 = 160.0;
+/// Толщина слоя для объёмных облаков
+pub const CLOUD_THICKNESS: f32 = 24.0;
+/// Дрейф облаков в безветрие (блоков/с по X и Z)
+const BASE_DRIFT: [f32; 2] = [1.2, 0.4];
+/// Наверху ветер сильнее, чем у земли
+const WIND_FACTOR: f32 = 2.0;
+/// Покрытие в ясную погоду и под сплошными тучами
+const CLEAR_COVERAGE: f32 = 0.4;
+const OVERCAST_COVERAGE: f32 = 0.95;
+
+/// Качество облаков
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CloudQuality {
+    Off,
+    /// Плоский слой с мягкими краями
+    #[default]
+    Flat,
+    /// Raymarch сквозь слой толщиной CLOUD_THICKNESS
+    Volumetric,
+}
+
+impl CloudQuality {
+    pub fn next(self) -> Self {
+        match self {
+            CloudQuality::Off => CloudQuality::Flat,
+            CloudQuality::Flat => CloudQuality::Volumetric,
+            CloudQuality::Volumetric => CloudQuality::Off,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            CloudQuality::Off => "Off",
+            CloudQuality::Flat => "Flat",
+            CloudQuality::Volumetric => "Volumetric",
+        }
+    }
+}
+
+/// Слой облаков
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CloudLayer {
+    pub quality: CloudQuality,
+    /// Смещение шума (блоки)
+    offset: [f32; 2],
+    /// Доля неба под облаками (0-1)
+    coverage: f32,
+    /// Ветер погоды (блоков/с)
+    wind: [f32; 2],
+}
+
+impl CloudLayer {
+    pub fn new(quality: CloudQuality) -> Self {
+        Self {
+            quality,
+            offset: [0.0, 0.0],
+            coverage: CLEAR_COVERAGE,
+            wind: [0.0, 0.0],
+        }
+    }
+
+    /// Облачность и ветер погоды (облачность уже сглажена WeatherSystem)
+    pub fn set_weather(&mut self, params: &WeatherParams) {
+        let overcast = params.overcast.clamp(0.0, 1.0);
+        self.coverage = CLEAR_COVERAGE + (OVERCAST_COVERAGE - CLEAR_COVERAGE) * overcast;
+        self.wind = params.wind;
+    }
+
+    /// Сдвинуть слой по ветру
+    pub fn update(&mut self, dt: f32) {
+        for axis in 0..2 {
+            self.offset[axis] += (BASE_DRIFT[axis] + self.wind[axis] * WIND_FACTOR) * dt;
+        }
+    }
+
+    pub fn offset(&self) -> [f32; 2] {
+        self.offset
+    }
+
+    pub fn coverage(&self) -> f32 {
+        self.coverage
+    }
+
+    /// Параметры для теней в шейдере террейна:
+    /// покрытие (0 - облаков нет), высота, смещение X/Z
+    pub fn shadow_params(&self) -> [f32; 4] {
+        let coverage = if self.quality == CloudQuality::Off { 0.0 } else { self.coverage };
+        [coverage, CLOUD_ALTITUDE, self.offset[0], self.offset[1]]
+    }
+}
+
+impl Default for CloudLayer {
+    fn default() -> Self {
+        Self::new(CloudQuality::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wind_pushes_clouds_and_overcast_thickens_them() {
+        let mut calm = CloudLayer::default();
+        let mut windy = CloudLayer::default();
+        windy.set_weather(&WeatherParams { overcast: 1.0, wind: [3.0, 0.0], ..Default::default() });
+        calm.update(10.0);
+        windy.update(10.0);
+        assert!(windy.offset()[0] > calm.offset()[0]);
+        assert!(calm.offset()[1] > 0.0);
+        assert!(windy.coverage() > calm.coverage());
+    }
+
+    #[test]
+    fn no_shadows_when_off() {
+        let mut layer = CloudLayer::new(CloudQuality::Off);
+        layer.set_weather(&WeatherParams { overcast: 1.0, ..Default::default() });
+        assert_eq!(layer.shadow_params()[0], 0.0);
+        assert_eq!(CloudQuality::Volumetric.next(), CloudQuality::Off);
+    }
+}
@@ -0,0 +1,13 @@
+// ============================================
+// Clouds Module - Слой облаков
+// ============================================
+// - layer: состояние слоя (смещение по ветру, покрытие от облачности)
+// - render: плоскость облаков над миром или raymarch-объём (Volumetric)
+// Тени облаков считает шейдер террейна по тем же параметрам
+// (Uniforms::clouds) и домножает на тень CSM.
+
+mod layer;
+mod render;
+
+pub use layer::{CloudLayer, CloudQuality, CLOUD_ALTITUDE, CLOUD_THICKNESS};
+pub use render::CloudRenderer;
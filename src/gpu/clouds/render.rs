@@ -0,0 +1,206 @@
+// ============================================
+// Cloud Renderer - Плоскость облаков над миром
+// ============================================
+// Квад вокруг камеры на высоте слоя, строится в вершинном шейдере.
+// Flat - плотность шума на плоскости. Volumetric - тот же квад
+// служит точкой входа луча (нижняя грань снизу, верхняя сверху),
+// дальше raymarch сквозь CLOUD_THICKNESS с самозатенением.
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::util::DeviceExt;
+use ultraviolet::{Mat4, Vec3};
+
+use crate::gpu::lighting::DayNightCycle;
+use crate::gpu::weather::WeatherParams;
+
+use super::layer::{CloudLayer, CloudQuality, CLOUD_ALTITUDE, CLOUD_THICKNESS};
+
+/// Радиус плоскости облаков вокруг камеры (дальняя плоскость - 2000)
+const CLOUD_RADIUS: f32 = 1200.0;
+/// С какого расстояния облака растворяются в тумане
+const CLOUD_FADE_START: f32 = 600.0;
+
+/// Uniform данные - vec3 упакованы в vec4 для WGSL alignment
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct CloudUniforms {
+    pub view_proj: [[f32; 4]; 4],  // 64 bytes
+    pub camera_pos: [f32; 4],      // xyz + высота плоскости
+    pub params: [f32; 4],          // покрытие, высота слоя, толщина, режим (0 flat, 1 volumetric)
+    pub offset: [f32; 4],          // смещение X/Z, радиус, начало растворения
+    pub light_dir: [f32; 4],       // к солнцу/луне xyz + интенсивность
+    pub light_color: [f32; 4],     // rgb + ambient
+    pub fog_color: [f32; 4],       // rgb + облачность
+}
+
+impl Default for CloudUniforms {
+    fn default() -> Self {
+        Self {
+            view_proj: Mat4::identity().into(),
+            camera_pos: [0.0, 0.0, 0.0, CLOUD_ALTITUDE],
+            params: [0.0, CLOUD_ALTITUDE, CLOUD_THICKNESS, 0.0],
+            offset: [0.0, 0.0, CLOUD_RADIUS, CLOUD_FADE_START],
+            light_dir: [0.0, 1.0, 0.0, 1.0],
+            light_color: [1.0, 1.0, 1.0, 0.3],
+            fog_color: [0.7, 0.8, 0.9, 0.0],
+        }
+    }
+}
+
+pub struct CloudRenderer {
+    uniform_buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+    pipeline: wgpu::RenderPipeline,
+    layer: CloudLayer,
+}
+
+impl CloudRenderer {
+    pub fn new(device: &wgpu::Device, surface_format: wgpu::TextureFormat, sample_count: u32) -> Self {
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Cloud UB"),
+            contents: bytemuck::cast_slice(&[CloudUniforms::default()]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Cloud BGL"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Cloud BG"),
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Cloud Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/clouds.wgsl").into()),
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Cloud PL"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Cloud Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                cull_mode: None,
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::GreaterEqual, // Reversed-Z
+                stencil: Default::default(),
+                bias: Default::default(),
+            }),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                ..Default::default()
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            uniform_buffer,
+            bind_group,
+            pipeline,
+            layer: CloudLayer::default(),
+        }
+    }
+
+    pub fn layer(&self) -> &CloudLayer {
+        &self.layer
+    }
+
+    pub fn set_quality(&mut self, quality: CloudQuality) {
+        self.layer.quality = quality;
+    }
+
+    pub fn quality(&self) -> CloudQuality {
+        self.layer.quality
+    }
+
+    /// Облачность и ветер погоды
+    pub fn set_weather(&mut self, params: &WeatherParams) {
+        self.layer.set_weather(params);
+    }
+
+    /// Сдвинуть слой по ветру (до записи Uniforms террейна - тени)
+    pub fn advance(&mut self, dt: f32) {
+        self.layer.update(dt);
+    }
+
+    pub fn update(&self, queue: &wgpu::Queue, view_proj: [[f32; 4]; 4], camera_pos: Vec3, day_night: &DayNightCycle) {
+        if self.layer.quality == CloudQuality::Off {
+            return;
+        }
+
+        // Объём рисуется с ближней к камере грани слоя
+        let volumetric = self.layer.quality == CloudQuality::Volumetric;
+        let plane_y = if volumetric && camera_pos.y > CLOUD_ALTITUDE + CLOUD_THICKNESS * 0.5 {
+            CLOUD_ALTITUDE + CLOUD_THICKNESS
+        } else {
+            CLOUD_ALTITUDE
+        };
+
+        let light = day_night.primary_light();
+        let offset = self.layer.offset();
+        let uniforms = CloudUniforms {
+            view_proj,
+            camera_pos: [camera_pos.x, camera_pos.y, camera_pos.z, plane_y],
+            params: [
+                self.layer.coverage(),
+                CLOUD_ALTITUDE,
+                CLOUD_THICKNESS,
+                if volumetric { 1.0 } else { 0.0 },
+            ],
+            offset: [offset[0], offset[1], CLOUD_RADIUS, CLOUD_FADE_START],
+            light_dir: [light.direction.x, light.direction.y, light.direction.z, light.intensity],
+            light_color: [light.color.x, light.color.y, light.color.z, day_night.ambient_intensity],
+            fog_color: [day_night.fog_color.x, day_night.fog_color.y, day_night.fog_color.z, day_night.overcast],
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+    }
+
+    pub fn render<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+        if self.layer.quality == CloudQuality::Off {
+            return;
+        }
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..6, 0..1);
+    }
+}
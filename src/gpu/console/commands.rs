@@ -0,0 +1,653 @@
+// ============================================
+// Console Commands - Разбор и автодополнение команд
+// ============================================
+// Разбор чистый (без доступа к миру): строка -> Command.
+// Выполняет команды ConsoleSystem.
+
+use serde::{Serialize, Deserialize};
+
+use crate::gpu::core::{MIN_DAY_LENGTH_MINUTES, MAX_DAY_LENGTH_MINUTES};
+use crate::gpu::gui::{MIN_UI_SCALE, MAX_UI_SCALE, MAX_SAFE_AREA};
+use crate::gpu::locale::LANGUAGES;
+use crate::gpu::save::{waypoint_color, WAYPOINT_COLORS};
+
+/// Команды с описанием для /help
+pub const COMMANDS: &[(&str, &str)] = &[
+    ("/help", "список команд"),
+    ("/tp", "x y z - телепорт (~ - относительно игрока)"),
+    ("/time", "set <day|noon|sunset|night|midnight|sunrise|0..1> | query | pause | resume"),
+    ("/day", "query | set <N> - номер игрового дня"),
+    ("/daylength", "[минуты] - длина суток (без аргумента - текущая)"),
+    ("/sleep", "проспать ночь до утра"),
+    ("/give", "<block> - блок в хотбар"),
+    ("/gamemode", "<survival|creative>"),
+    ("/seed", "сид мира"),
+    ("/fill", "[x1 y1 z1 x2 y2 z2] <block> - заполнить область (без координат - выделение)"),
+    ("/replace", "[x1 y1 z1 x2 y2 z2] <from> <to> - заменить блок в области"),
+    ("/hollow", "[x1 y1 z1 x2 y2 z2] <block> - оболочка из блока, внутри воздух"),
+    ("/walls", "[x1 y1 z1 x2 y2 z2] <block> - четыре стены вокруг области"),
+    ("/undo", "откатить последнюю правку /fill, /replace, /hollow или /walls"),
+    ("/pregen", "radius N [mesh] | cancel - прегенерация вокруг игрока"),
+    ("/setspawn", "[x y z] - спавн мира (по умолчанию - позиция игрока)"),
+    ("/spawn", "телепорт к точке возрождения (кровать или спавн мира)"),
+    ("/kill", "погибнуть и возродиться"),
+    ("/spawnprotection", "<radius> - защита спавна от строительства (0 - выкл)"),
+    ("/log", "[фильтр] - уровни журнала, например info,terrain=debug (без аргумента - текущий)"),
+    ("/copy", "скопировать выделение палочкой выделения в буфер"),
+    ("/paste", "[0|90|180|270] - вставить буфер у игрока (с поворотом по часовой)"),
+    ("/schem", "save <имя> | load <имя> - буфер в файл schematics/<имя>.schem и обратно"),
+    ("/profile", "on | off | dump - оверлей таймингов, dump пишет Chrome trace в profiles/"),
+    ("/lang", "[код] - язык интерфейса: en, ru, zh или lang/<код>.json (без аргумента - текущий)"),
+    ("/waypoint", "add <имя> [x y z] [цвет] | color <имя> <цвет> | remove <имя|death> | list - метки и маяки"),
+    ("/ui", "scale <auto|0.5..3> | safearea <0..10> - масштаб интерфейса и поля по краям в % (без аргумента - текущие)"),
+];
+
+/// Уровни журнала для автодополнения /log
+const LOG_LEVELS: &[&str] = &["off", "error", "warn", "info", "debug", "trace"];
+
+/// Именованное время суток (0 - полночь, 0.5 - полдень)
+const NAMED_TIMES: &[(&str, f32)] = &[
+    ("sunrise", 0.25),
+    ("day", 0.35),
+    ("noon", 0.5),
+    ("sunset", 0.75),
+    ("night", 0.85),
+    ("midnight", 0.0),
+];
+
+/// Множитель скорости ломания в креативе (мгновенно)
+const CREATIVE_BREAK_SPEED: f32 = 1000.0;
+
+/// Предел координат X и Z в командах (и телепорта без границы мира)
+pub const MAX_COORD: f32 = 30_000_000.0;
+
+/// Координата: абсолютная или относительно игрока (~, ~5)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Coord {
+    Absolute(f32),
+    Relative(f32),
+}
+
+impl Coord {
+    fn parse(arg: &str) -> Result<Self, String> {
+        let invalid = || format!("'{}' - не координата", arg);
+        // nan, inf и 1e30 f32 разбирает, но в мире таких координат нет
+        let number = |text: &str| {
+            let value: f32 = text.parse().map_err(|_| invalid())?;
+            if !value.is_finite() {
+                return Err(invalid());
+            }
+            if value.abs() > MAX_COORD {
+                return Err(format!("'{}' - слишком далеко (предел {})", arg, MAX_COORD));
+            }
+            Ok(value)
+        };
+        match arg.strip_prefix('~') {
+            Some("") => Ok(Coord::Relative(0.0)),
+            Some(offset) => number(offset).map(Coord::Relative),
+            None => number(arg).map(Coord::Absolute),
+        }
+    }
+
+    /// Значение относительно точки игрока
+    pub fn resolve(self, origin: f32) -> f32 {
+        match self {
+            Coord::Absolute(value) => value,
+            Coord::Relative(offset) => origin + offset,
+        }
+    }
+}
+
+/// Режим игры (хранится в метаданных мира)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GameMode {
+    /// Ходьба, ломание по твёрдости
+    #[default]
+    Survival,
+    /// Полёт, мгновенное ломание
+    Creative,
+}
+
+impl GameMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameMode::Survival => "survival",
+            GameMode::Creative => "creative",
+        }
+    }
+
+    /// Множитель скорости ломания режима (в креативе - мгновенно)
+    pub fn break_speed(self) -> f32 {
+        match self {
+            GameMode::Survival => 1.0,
+            GameMode::Creative => CREATIVE_BREAK_SPEED,
+        }
+    }
+}
+
+/// Подкоманда /time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeCommand {
+    Set(f32),
+    Query,
+    /// true - остановить время, false - продолжить
+    Pause(bool),
+}
+
+/// Подкоманда /day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DayCommand {
+    Set(u32),
+    Query,
+}
+
+/// Подкоманда /pregen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PregenCommand {
+    Start { radius: i32, mesh: bool },
+    Cancel,
+}
+
+/// Подкоманда /schem
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemCommand {
+    Save(String),
+    Load(String),
+}
+
+/// Подкоманда /profile
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileCommand {
+    /// Показать/скрыть оверлей (вместе с записью)
+    Show(bool),
+    Dump,
+}
+
+/// Подкоманда /waypoint
+#[derive(Debug, Clone, PartialEq)]
+pub enum WaypointCommand {
+    /// pos: None - позиция игрока; color: None - по кругу
+    Add { name: String, pos: Option<[Coord; 3]>, color: Option<[u8; 3]> },
+    Color { name: String, color: [u8; 3] },
+    Remove(String),
+    List,
+}
+
+/// Подкоманда /ui
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UiCommand {
+    Show,
+    /// None - по DPI и высоте экрана
+    Scale(Option<f32>),
+    /// Доля экрана с каждой стороны
+    SafeArea(f32),
+}
+
+/// Область правки мира
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EditRegion {
+    /// Выделение палочкой
+    Selection,
+    Corners([Coord; 3], [Coord; 3]),
+}
+
+/// Правка мира (блоки по имени)
+#[derive(Debug, Clone, PartialEq)]
+pub enum EditCommand {
+    Fill(String),
+    Replace { from: String, to: String },
+    Hollow(String),
+    Walls(String),
+}
+
+/// Разобранная команда
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Help,
+    Teleport([Coord; 3]),
+    Time(TimeCommand),
+    Day(DayCommand),
+    /// Длина суток в минутах (None - показать текущую)
+    DayLength(Option<f32>),
+    Sleep,
+    Give(String),
+    GameMode(GameMode),
+    Seed,
+    Edit { region: EditRegion, edit: EditCommand },
+    Undo,
+    Pregen(PregenCommand),
+    /// None - позиция игрока
+    SetSpawn(Option<[Coord; 3]>),
+    Spawn,
+    Kill,
+    SpawnProtection(i32),
+    /// None - показать текущий фильтр журнала
+    Log(Option<String>),
+    /// Код языка интерфейса (None - показать текущий)
+    Lang(Option<String>),
+    Copy,
+    /// Поворот в четвертях оборота по часовой
+    Paste(u32),
+    Schem(SchemCommand),
+    Profile(ProfileCommand),
+    Waypoint(WaypointCommand),
+    Ui(UiCommand),
+}
+
+fn parse_coords(args: &[&str]) -> Result<[Coord; 3], String> {
+    Ok([Coord::parse(args[0])?, Coord::parse(args[1])?, Coord::parse(args[2])?])
+}
+
+fn usage(name: &str) -> String {
+    let help = COMMANDS.iter().find(|(command, _)| *command == name).map_or("", |(_, help)| help);
+    format!("Использование: {} {}", name, help)
+}
+
+/// /fill, /replace, /hollow, /walls: блоки в конце, углы перед ними - необязательны
+fn parse_edit(name: &str, args: &[&str]) -> Result<Command, String> {
+    let block_count = if name == "/replace" { 2 } else { 1 };
+    let (region, blocks) = if args.len() == block_count {
+        (EditRegion::Selection, args)
+    } else if args.len() == block_count + 6 {
+        (EditRegion::Corners(parse_coords(&args[0..3])?, parse_coords(&args[3..6])?), &args[6..])
+    } else {
+        return Err(usage(name));
+    };
+
+    let edit = match (name, blocks) {
+        ("/replace", [from, to]) => EditCommand::Replace { from: from.to_string(), to: to.to_string() },
+        ("/hollow", [block]) => EditCommand::Hollow(block.to_string()),
+        ("/walls", [block]) => EditCommand::Walls(block.to_string()),
+        (_, [block]) => EditCommand::Fill(block.to_string()),
+        _ => return Err(usage(name)),
+    };
+    Ok(Command::Edit { region, edit })
+}
+
+/// Имя файла схематики или метки: буквы, цифры, '-' и '_'
+fn parse_schematic_name(name: &str) -> Result<String, String> {
+    if name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        Ok(name.to_string())
+    } else {
+        Err(format!("'{}' - недопустимое имя (буквы, цифры, '-' и '_')", name))
+    }
+}
+
+/// Цвет метки по имени из WAYPOINT_COLORS
+fn parse_waypoint_color(name: &str) -> Result<[u8; 3], String> {
+    waypoint_color(name).ok_or_else(|| {
+        let names: Vec<&str> = WAYPOINT_COLORS.iter().map(|(color, _)| *color).collect();
+        format!("'{}' - не цвет ({})", name, names.join(", "))
+    })
+}
+
+/// /waypoint add <имя> [x y z] [цвет]
+fn parse_waypoint_add(args: &[&str]) -> Result<Command, String> {
+    let (pos, color) = match args.len() {
+        1 => (None, None),
+        2 => (None, Some(parse_waypoint_color(args[1])?)),
+        4 => (Some(parse_coords(&args[1..])?), None),
+        5 => (Some(parse_coords(&args[1..4])?), Some(parse_waypoint_color(args[4])?)),
+        _ => return Err(usage("/waypoint")),
+    };
+    Ok(Command::Waypoint(WaypointCommand::Add { name: parse_schematic_name(args[0])?, pos, color }))
+}
+
+/// Разобрать команду (строка с '/')
+pub fn parse_command(line: &str) -> Result<Command, String> {
+    let mut tokens = line.split_whitespace();
+    let name = tokens.next().ok_or("Пустая команда")?;
+    let args: Vec<&str> = tokens.collect();
+
+    match (name, args.as_slice()) {
+        ("/help", []) => Ok(Command::Help),
+        ("/tp", [_, _, _]) => Ok(Command::Teleport(parse_coords(&args)?)),
+        ("/time", ["query"]) => Ok(Command::Time(TimeCommand::Query)),
+        ("/time", ["pause"]) => Ok(Command::Time(TimeCommand::Pause(true))),
+        ("/time", ["resume"]) => Ok(Command::Time(TimeCommand::Pause(false))),
+        ("/time", ["set", value]) => {
+            let time = match NAMED_TIMES.iter().find(|(time_name, _)| time_name == value) {
+                Some(&(_, time)) => time,
+                None => value
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|time| (0.0..=1.0).contains(time))
+                    .ok_or_else(|| format!("'{}' - не время суток", value))?,
+            };
+            Ok(Command::Time(TimeCommand::Set(time)))
+        }
+        ("/day", [] | ["query"]) => Ok(Command::Day(DayCommand::Query)),
+        ("/day", ["set", value]) => value
+            .parse::<u32>()
+            .ok()
+            .filter(|day| *day >= 1)
+            .map(|day| Command::Day(DayCommand::Set(day)))
+            .ok_or_else(|| format!("'{}' - не номер дня", value)),
+        ("/daylength", []) => Ok(Command::DayLength(None)),
+        ("/daylength", [minutes]) => minutes
+            .parse::<f32>()
+            .ok()
+            .filter(|minutes| (MIN_DAY_LENGTH_MINUTES..=MAX_DAY_LENGTH_MINUTES).contains(minutes))
+            .map(|minutes| Command::DayLength(Some(minutes)))
+            .ok_or_else(|| format!("'{}' - не длина суток ({}..{} минут)", minutes, MIN_DAY_LENGTH_MINUTES, MAX_DAY_LENGTH_MINUTES)),
+        ("/sleep", []) => Ok(Command::Sleep),
+        ("/give", [block]) => Ok(Command::Give(block.to_string())),
+        ("/gamemode", [mode]) => match *mode {
+            "survival" | "s" | "0" => Ok(Command::GameMode(GameMode::Survival)),
+            "creative" | "c" | "1" => Ok(Command::GameMode(GameMode::Creative)),
+            other => Err(format!("Неизвестный режим '{}'", other)),
+        },
+        ("/seed", []) => Ok(Command::Seed),
+        ("/fill" | "/replace" | "/hollow" | "/walls", _) => parse_edit(name, &args),
+        ("/undo", []) => Ok(Command::Undo),
+        ("/pregen", ["cancel"]) => Ok(Command::Pregen(PregenCommand::Cancel)),
+        ("/pregen", pregen_args) => {
+            let (radius, mesh) = crate::gpu::terrain::parse_pregen_args(pregen_args)?;
+            Ok(Command::Pregen(PregenCommand::Start { radius, mesh }))
+        }
+        ("/setspawn", []) => Ok(Command::SetSpawn(None)),
+        ("/setspawn", [_, _, _]) => Ok(Command::SetSpawn(Some(parse_coords(&args)?))),
+        ("/spawn", []) => Ok(Command::Spawn),
+        ("/kill", []) => Ok(Command::Kill),
+        ("/spawnprotection", [radius]) => radius
+            .parse::<i32>()
+            .ok()
+            .filter(|radius| *radius >= 0)
+            .map(Command::SpawnProtection)
+            .ok_or_else(|| format!("'{}' - не радиус", radius)),
+        ("/log", []) => Ok(Command::Log(None)),
+        ("/log", [filter]) => Ok(Command::Log(Some(filter.to_string()))),
+        ("/lang", []) => Ok(Command::Lang(None)),
+        ("/lang", [code]) if code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') => {
+            Ok(Command::Lang(Some(code.to_ascii_lowercase())))
+        }
+        ("/lang", [code]) => Err(format!("'{}' - не код языка", code)),
+        ("/copy", []) => Ok(Command::Copy),
+        ("/paste", []) => Ok(Command::Paste(0)),
+        ("/paste", [degrees]) => match *degrees {
+            "0" => Ok(Command::Paste(0)),
+            "90" => Ok(Command::Paste(1)),
+            "180" => Ok(Command::Paste(2)),
+            "270" => Ok(Command::Paste(3)),
+            other => Err(format!("'{}' - не поворот (0, 90, 180 или 270)", other)),
+        },
+        ("/schem", ["save", name]) => Ok(Command::Schem(SchemCommand::Save(parse_schematic_name(name)?))),
+        ("/schem", ["load", name]) => Ok(Command::Schem(SchemCommand::Load(parse_schematic_name(name)?))),
+        ("/profile", ["on"]) => Ok(Command::Profile(ProfileCommand::Show(true))),
+        ("/profile", ["off"]) => Ok(Command::Profile(ProfileCommand::Show(false))),
+        ("/profile", ["dump"]) => Ok(Command::Profile(ProfileCommand::Dump)),
+        ("/waypoint", ["add", _, ..]) => parse_waypoint_add(&args[1..]),
+        ("/waypoint", ["color", name, color]) => Ok(Command::Waypoint(WaypointCommand::Color {
+            name: name.to_string(),
+            color: parse_waypoint_color(color)?,
+        })),
+        ("/waypoint", ["remove", name]) => Ok(Command::Waypoint(WaypointCommand::Remove(name.to_string()))),
+        ("/waypoint", [] | ["list"]) => Ok(Command::Waypoint(WaypointCommand::List)),
+        ("/ui", []) => Ok(Command::Ui(UiCommand::Show)),
+        ("/ui", ["scale", "auto"]) => Ok(Command::Ui(UiCommand::Scale(None))),
+        ("/ui", ["scale", scale]) => scale
+            .parse::<f32>()
+            .ok()
+            .filter(|scale| (MIN_UI_SCALE..=MAX_UI_SCALE).contains(scale))
+            .map(|scale| Command::Ui(UiCommand::Scale(Some(scale))))
+            .ok_or_else(|| format!("'{}' - не масштаб (auto или {}..{})", scale, MIN_UI_SCALE, MAX_UI_SCALE)),
+        ("/ui", ["safearea", percent]) => percent
+            .parse::<f32>()
+            .ok()
+            .map(|percent| percent / 100.0)
+            .filter(|fraction| (0.0..=MAX_SAFE_AREA).contains(fraction))
+            .map(|fraction| Command::Ui(UiCommand::SafeArea(fraction)))
+            .ok_or_else(|| format!("'{}' - не поле (0..{}%)", percent, MAX_SAFE_AREA * 100.0)),
+        _ if COMMANDS.iter().any(|(command, _)| *command == name) => Err(usage(name)),
+        _ => Err(format!("Неизвестная команда '{}' (см. /help)", name)),
+    }
+}
+
+/// Результат автодополнения
+#[derive(Debug, Clone, PartialEq)]
+pub struct Completion {
+    /// Строка ввода после дополнения
+    pub line: String,
+    /// Варианты, если однозначного дополнения нет
+    pub candidates: Vec<String>,
+}
+
+/// Варианты для аргумента команды (index - номер токена, 0 - имя команды)
+fn candidates_for<'a>(tokens: &[&str], index: usize, block_names: &'a [String]) -> Vec<&'a str> {
+    let statics: &[&'static str] = match (tokens.first().copied(), index) {
+        (_, 0) => return COMMANDS.iter().map(|(name, _)| *name).collect(),
+        (Some("/give"), 1)
+        | (Some("/fill" | "/hollow" | "/walls"), 1 | 7)
+        | (Some("/replace"), 1 | 2 | 7 | 8) => {
+            return block_names.iter().map(String::as_str).collect();
+        }
+        (Some("/time"), 1) => &["set", "query", "pause", "resume"],
+        (Some("/time"), 2) if tokens.get(1) == Some(&"set") => {
+            return NAMED_TIMES.iter().map(|(name, _)| *name).collect();
+        }
+        (Some("/day"), 1) => &["set", "query"],
+        (Some("/gamemode"), 1) => &["survival", "creative"],
+        (Some("/pregen"), 1) => &["radius", "cancel"],
+        (Some("/log"), 1) => LOG_LEVELS,
+        (Some("/lang"), 1) => LANGUAGES,
+        (Some("/paste"), 1) => &["0", "90", "180", "270"],
+        (Some("/schem"), 1) => &["save", "load"],
+        (Some("/profile"), 1) => &["on", "off", "dump"],
+        (Some("/waypoint"), 1) => &["add", "color", "remove", "list"],
+        (Some("/ui"), 1) => &["scale", "safearea"],
+        (Some("/ui"), 2) if tokens.get(1) == Some(&"scale") => &["auto", "1", "1.5", "2"],
+        (Some("/waypoint"), 3) if tokens.get(1) == Some(&"color") => {
+            return WAYPOINT_COLORS.iter().map(|(name, _)| *name).collect();
+        }
+        (Some("/waypoint"), 3 | 6) if tokens.get(1) == Some(&"add") => {
+            return WAYPOINT_COLORS.iter().map(|(name, _)| *name).collect();
+        }
+        _ => &[],
+    };
+    statics.to_vec()
+}
+
+/// Дополнить последний токен: однозначный вариант - целиком с пробелом,
+/// иначе общий префикс и список вариантов
+pub fn complete(line: &str, block_names: &[String]) -> Completion {
+    let mut tokens: Vec<&str> = line.split_whitespace().collect();
+    // Пробел в конце - дополняем новый пустой токен
+    if line.is_empty() || line.ends_with(char::is_whitespace) {
+        tokens.push("");
+    }
+    let index = tokens.len() - 1;
+    let prefix = tokens[index];
+
+    let mut candidates: Vec<&str> = candidates_for(&tokens, index, block_names)
+        .into_iter()
+        .filter(|candidate| candidate.starts_with(prefix))
+        .collect();
+    candidates.sort_unstable();
+    candidates.dedup();
+
+    let head = &line[..line.len() - prefix.len()];
+    match candidates.as_slice() {
+        [] => Completion { line: line.to_string(), candidates: Vec::new() },
+        [single] => Completion { line: format!("{}{} ", head, single), candidates: Vec::new() },
+        many => {
+            let common = many[1..].iter().fold(many[0], |common, candidate| {
+                let len = common
+                    .char_indices()
+                    .zip(candidate.chars())
+                    .take_while(|((_, a), b)| a == b)
+                    .last()
+                    .map_or(0, |((i, a), _)| i + a.len_utf8());
+                &common[..len]
+            });
+            Completion {
+                line: format!("{}{}", head, common),
+                candidates: many.iter().map(|candidate| candidate.to_string()).collect(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_teleport_with_relative_coords() {
+        let command = parse_command("/tp 10 ~ ~-2.5").unwrap();
+        let Command::Teleport([x, y, z]) = command else { panic!("ожидался /tp") };
+        assert_eq!(x.resolve(100.0), 10.0);
+        assert_eq!(y.resolve(64.0), 64.0);
+        assert_eq!(z.resolve(4.0), 1.5);
+    }
+
+    #[test]
+    fn rejects_non_finite_and_huge_coords() {
+        for arg in ["nan", "inf", "-inf", "~inf", "~nan", "1e30", "~1e30"] {
+            assert!(Coord::parse(arg).is_err(), "{}", arg);
+            assert!(parse_command(&format!("/tp {} 0 0", arg)).is_err(), "{}", arg);
+        }
+        assert_eq!(Coord::parse("-29999000"), Ok(Coord::Absolute(-29_999_000.0)));
+        assert_eq!(Coord::parse("~-12.5"), Ok(Coord::Relative(-12.5)));
+    }
+
+    #[test]
+    fn parses_time_and_fill() {
+        assert_eq!(parse_command("/time set noon").unwrap(), Command::Time(TimeCommand::Set(0.5)));
+        assert_eq!(parse_command("/time set 0.1").unwrap(), Command::Time(TimeCommand::Set(0.1)));
+        assert!(parse_command("/time set 2").is_err());
+        assert_eq!(parse_command("/day").unwrap(), Command::Day(DayCommand::Query));
+        assert_eq!(parse_command("/day set 11").unwrap(), Command::Day(DayCommand::Set(11)));
+        assert!(parse_command("/day set 0").is_err());
+        assert_eq!(parse_command("/time pause").unwrap(), Command::Time(TimeCommand::Pause(true)));
+        assert_eq!(parse_command("/daylength 10").unwrap(), Command::DayLength(Some(10.0)));
+        assert!(parse_command("/daylength 0").is_err());
+        assert_eq!(parse_command("/sleep").unwrap(), Command::Sleep);
+
+        let fill = parse_command("/fill 0 60 0 ~3 ~ ~3 stone").unwrap();
+        assert!(matches!(fill, Command::Edit { region: EditRegion::Corners(..), edit: EditCommand::Fill(ref block) } if block == "stone"));
+    }
+
+    #[test]
+    fn parses_world_edit_commands() {
+        assert_eq!(
+            parse_command("/fill stone").unwrap(),
+            Command::Edit { region: EditRegion::Selection, edit: EditCommand::Fill("stone".to_string()) },
+        );
+        assert_eq!(
+            parse_command("/replace dirt sand").unwrap(),
+            Command::Edit {
+                region: EditRegion::Selection,
+                edit: EditCommand::Replace { from: "dirt".to_string(), to: "sand".to_string() },
+            },
+        );
+        assert!(matches!(
+            parse_command("/walls 0 0 0 ~ ~ ~ stone_bricks").unwrap(),
+            Command::Edit { region: EditRegion::Corners(..), edit: EditCommand::Walls(_) },
+        ));
+        assert!(matches!(parse_command("/hollow glass").unwrap(), Command::Edit { edit: EditCommand::Hollow(_), .. }));
+        assert!(parse_command("/replace stone").unwrap_err().contains("/replace"));
+        assert!(parse_command("/fill 0 0 stone").is_err());
+        assert_eq!(parse_command("/undo").unwrap(), Command::Undo);
+    }
+
+    #[test]
+    fn parses_spawn_commands() {
+        assert_eq!(parse_command("/setspawn").unwrap(), Command::SetSpawn(None));
+        assert!(matches!(parse_command("/setspawn 0 ~ 5").unwrap(), Command::SetSpawn(Some(_))));
+        assert_eq!(parse_command("/spawn").unwrap(), Command::Spawn);
+        assert_eq!(parse_command("/kill").unwrap(), Command::Kill);
+        assert!(parse_command("/kill now").is_err());
+        assert_eq!(parse_command("/spawnprotection 8").unwrap(), Command::SpawnProtection(8));
+        assert!(parse_command("/spawnprotection -1").is_err());
+    }
+
+    #[test]
+    fn parses_log_filter() {
+        assert_eq!(parse_command("/log").unwrap(), Command::Log(None));
+        assert_eq!(parse_command("/log info,terrain=debug").unwrap(), Command::Log(Some("info,terrain=debug".to_string())));
+        assert!(parse_command("/log info terrain").is_err());
+    }
+
+    #[test]
+    fn parses_language() {
+        assert_eq!(parse_command("/lang").unwrap(), Command::Lang(None));
+        assert_eq!(parse_command("/lang RU").unwrap(), Command::Lang(Some("ru".to_string())));
+        assert!(parse_command("/lang ../secret").is_err());
+        assert_eq!(complete("/lang r", &[]).line, "/lang ru ");
+    }
+
+    #[test]
+    fn parses_ui_scale() {
+        assert_eq!(parse_command("/ui").unwrap(), Command::Ui(UiCommand::Show));
+        assert_eq!(parse_command("/ui scale auto").unwrap(), Command::Ui(UiCommand::Scale(None)));
+        assert_eq!(parse_command("/ui scale 1.5").unwrap(), Command::Ui(UiCommand::Scale(Some(1.5))));
+        assert_eq!(parse_command("/ui safearea 5").unwrap(), Command::Ui(UiCommand::SafeArea(0.05)));
+        assert!(parse_command("/ui scale 8").is_err());
+        assert!(parse_command("/ui safearea 30").is_err());
+        assert_eq!(complete("/ui sa", &[]).line, "/ui safearea ");
+    }
+
+    #[test]
+    fn parses_schematic_commands() {
+        assert_eq!(parse_command("/copy").unwrap(), Command::Copy);
+        assert_eq!(parse_command("/paste").unwrap(), Command::Paste(0));
+        assert_eq!(parse_command("/paste 270").unwrap(), Command::Paste(3));
+        assert!(parse_command("/paste 45").is_err());
+        assert_eq!(parse_command("/schem save tower_2").unwrap(), Command::Schem(SchemCommand::Save("tower_2".to_string())));
+        assert!(parse_command("/schem load ../world").is_err());
+    }
+
+    #[test]
+    fn parses_profile_commands() {
+        assert_eq!(parse_command("/profile on").unwrap(), Command::Profile(ProfileCommand::Show(true)));
+        assert_eq!(parse_command("/profile dump").unwrap(), Command::Profile(ProfileCommand::Dump));
+        assert!(parse_command("/profile").unwrap_err().contains("dump"));
+    }
+
+    #[test]
+    fn parses_waypoint_commands() {
+        assert_eq!(
+            parse_command("/waypoint add home").unwrap(),
+            Command::Waypoint(WaypointCommand::Add { name: "home".to_string(), pos: None, color: None }),
+        );
+        assert!(matches!(
+            parse_command("/waypoint add mine 10 ~ -40").unwrap(),
+            Command::Waypoint(WaypointCommand::Add { pos: Some(_), color: None, .. }),
+        ));
+        assert!(matches!(
+            parse_command("/waypoint add mine 10 ~ -40 red").unwrap(),
+            Command::Waypoint(WaypointCommand::Add { pos: Some(_), color: Some([255, 70, 70]), .. }),
+        ));
+        assert!(matches!(
+            parse_command("/waypoint add base cyan").unwrap(),
+            Command::Waypoint(WaypointCommand::Add { pos: None, color: Some(_), .. }),
+        ));
+        assert!(parse_command("/waypoint color base pink").unwrap_err().contains("yellow"));
+        assert_eq!(parse_command("/waypoint remove home").unwrap(), Command::Waypoint(WaypointCommand::Remove("home".to_string())));
+        assert_eq!(parse_command("/waypoint").unwrap(), Command::Waypoint(WaypointCommand::List));
+        assert!(parse_command("/waypoint add a/b").is_err());
+        assert!(parse_command("/waypoint add home 1 2").unwrap_err().contains("/waypoint"));
+    }
+
+    #[test]
+    fn reports_usage_and_unknown_commands() {
+        assert!(parse_command("/tp 1 2").unwrap_err().contains("/tp"));
+        assert!(parse_command("/fly").unwrap_err().contains("/help"));
+    }
+
+    #[test]
+    fn completes_commands_and_block_names() {
+        let blocks: Vec<String> = ["stone", "stone_bricks", "sand"].iter().map(|s| s.to_string()).collect();
+
+        assert_eq!(complete("/gi", &blocks).line, "/give ");
+        assert_eq!(complete("/give sa", &blocks).line, "/give sand ");
+
+        let ambiguous = complete("/give st", &blocks);
+        assert_eq!(ambiguous.line, "/give stone");
+        assert_eq!(ambiguous.candidates, vec!["stone", "stone_bricks"]);
+
+        assert_eq!(complete("/fill 0 0 0 1 1 1 sa", &blocks).line, "/fill 0 0 0 1 1 1 sand ");
+        assert_eq!(complete("/replace stone sa", &blocks).line, "/replace stone sand ");
+    }
+}
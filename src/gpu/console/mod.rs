@@ -0,0 +1,189 @@
+// ============================================
+// Console - Консоль команд в стиле чата
+// ============================================
+// Открывается на T или '/', Enter выполняет строку, Tab дополняет,
+// стрелки листают историю. Закрытая консоль показывает последние
+// сообщения несколько секунд, как чат.
+
+mod commands;
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+pub use commands::{Command, Coord, DayCommand, EditCommand, EditRegion, GameMode, PregenCommand, ProfileCommand, SchemCommand, TimeCommand, UiCommand, WaypointCommand, parse_command, complete, COMMANDS, MAX_COORD};
+
+/// Сколько строк лога хранить
+const MAX_LOG_LINES: usize = 100;
+/// Сколько команд помнить в истории
+const MAX_HISTORY: usize = 50;
+/// Сколько секунд видна строка при закрытой консоли
+pub const LINE_VISIBLE_SECS: f32 = 8.0;
+
+/// Вид строки лога (цвет в интерфейсе)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineKind {
+    /// Введённая строка
+    Input,
+    Info,
+    Error,
+}
+
+/// Строка лога консоли
+#[derive(Debug, Clone)]
+pub struct ConsoleLine {
+    pub text: String,
+    pub kind: LineKind,
+    pub time: Instant,
+}
+
+impl ConsoleLine {
+    /// Прозрачность при закрытой консоли (гаснет в последнюю секунду)
+    pub fn fade(&self, now: Instant) -> f32 {
+        let age = now.duration_since(self.time).as_secs_f32();
+        (LINE_VISIBLE_SECS - age).clamp(0.0, 1.0)
+    }
+}
+
+/// Состояние консоли
+pub struct Console {
+    open: bool,
+    input: String,
+    history: Vec<String>,
+    /// Позиция в истории при листании (None - новая строка)
+    history_pos: Option<usize>,
+    log: VecDeque<ConsoleLine>,
+}
+
+impl Default for Console {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Console {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            input: String::new(),
+            history: Vec::new(),
+            history_pos: None,
+            log: VecDeque::new(),
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Открыть с начальным текстом ("/" при открытии слешем)
+    pub fn open(&mut self, prefix: &str) {
+        self.open = true;
+        self.input = prefix.to_string();
+        self.history_pos = None;
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+        self.input.clear();
+        self.history_pos = None;
+    }
+
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Напечатанный текст (управляющие символы отбрасываются)
+    pub fn type_text(&mut self, text: &str) {
+        self.input.extend(text.chars().filter(|c| !c.is_control()));
+    }
+
+    pub fn backspace(&mut self) {
+        self.input.pop();
+    }
+
+    /// Забрать введённую строку (пустая - None) и запомнить в истории
+    pub fn submit(&mut self) -> Option<String> {
+        let line = std::mem::take(&mut self.input).trim().to_string();
+        self.history_pos = None;
+        if line.is_empty() {
+            return None;
+        }
+        if self.history.last() != Some(&line) {
+            self.history.push(line.clone());
+            if self.history.len() > MAX_HISTORY {
+                self.history.remove(0);
+            }
+        }
+        Some(line)
+    }
+
+    /// Предыдущая команда из истории (стрелка вверх)
+    pub fn history_prev(&mut self) {
+        if self.history.is_empty() {
+            return;
+        }
+        let pos = self.history_pos.map_or(self.history.len() - 1, |pos| pos.saturating_sub(1));
+        self.history_pos = Some(pos);
+        self.input = self.history[pos].clone();
+    }
+
+    /// Следующая команда из истории (стрелка вниз)
+    pub fn history_next(&mut self) {
+        let Some(pos) = self.history_pos else { return };
+        if pos + 1 < self.history.len() {
+            self.history_pos = Some(pos + 1);
+            self.input = self.history[pos + 1].clone();
+        } else {
+            self.history_pos = None;
+            self.input.clear();
+        }
+    }
+
+    /// Автодополнение по Tab (варианты печатаются в лог)
+    pub fn complete(&mut self, block_names: &[String]) {
+        let completion = complete(&self.input, block_names);
+        self.input = completion.line;
+        if !completion.candidates.is_empty() {
+            self.info(completion.candidates.join(", "));
+        }
+    }
+
+    fn push(&mut self, text: String, kind: LineKind) {
+        self.log.push_back(ConsoleLine { text, kind, time: Instant::now() });
+        if self.log.len() > MAX_LOG_LINES {
+            self.log.pop_front();
+        }
+    }
+
+    /// Эхо введённой строки
+    pub fn echo(&mut self, text: impl Into<String>) {
+        self.push(text.into(), LineKind::Input);
+    }
+
+    pub fn info(&mut self, text: impl Into<String>) {
+        self.push(text.into(), LineKind::Info);
+    }
+
+    pub fn error(&mut self, text: impl Into<String>) {
+        self.push(text.into(), LineKind::Error);
+    }
+
+    /// Строки, добавленные с момента time (ответы на команду)
+    pub fn lines_since(&self, time: Instant) -> impl Iterator<Item = &ConsoleLine> {
+        self.log.iter().filter(move |line| line.time >= time)
+    }
+
+    /// Последние строки для показа (сверху вниз): открытая консоль -
+    /// до max строк, закрытая - только свежие
+    pub fn visible_lines(&self, max: usize, now: Instant) -> Vec<&ConsoleLine> {
+        let visible_for = Duration::from_secs_f32(LINE_VISIBLE_SECS);
+        let mut lines: Vec<&ConsoleLine> = self.log
+            .iter()
+            .rev()
+            .filter(|line| self.open || now.duration_since(line.time) < visible_for)
+            .take(max)
+            .collect();
+        lines.reverse();
+        lines
+    }
+}
@@ -12,61 +12,198 @@ use winit::{
     window::{Window, WindowId},
 };
 
-use crate::gpu::core::GameResources;
+use crate::gpu::core::{run_headless, GameResources, HeadlessOptions, Settings, DEFAULT_WORLD};
+use crate::gpu::render::Renderer;
+use crate::gpu::gui::GuiRenderer;
 use crate::gpu::systems::{
     InitSystem, InputSystem, InputAction, BlockInteractionSystem,
-    MenuSystem, SaveSystem, UpdateSystem, RenderSystem,
+    MenuSystem, SaveSystem, UpdateSystem, RenderSystem, ConsoleSystem, ContainerSystem, LoadedWorld,
+    WorldMapSystem,
 };
 use crate::gpu::blocks::MouseButton;
+use crate::gpu::terrain::stats::DEFAULT_SCAN_RADIUS;
+use crate::gpu::terrain::{parse_pregen_args, PregenTask};
+use crate::gpu::terrain::generation::WorldGenConfig;
+use crate::gpu::biomes::{SeedPreview, DEFAULT_PREVIEW_SIZE};
+use crate::gpu::logging;
+use crate::gpu::locale::{self, tr};
+use crate::gpu::profiler;
+use crate::gpu::benchmark::{Benchmark, BENCHMARK_SEED, BENCHMARK_WORLD};
+
+use super::loading::WorldLoading;
 
 /// Главное приложение
 pub struct App {
-    resources: GameResources,
+    /// Ресурсы мира (None - мир ещё загружается)
+    resources: Option<GameResources>,
+    loading: Option<Loading>,
+    /// --benchmark: камера летит по маршруту, в конце отчёт и выход
+    benchmark: Option<Benchmark>,
+}
+
+/// Состояние до конца загрузки мира
+struct Loading {
+    world: WorldLoading,
+    settings: Settings,
+    /// --pregen: радиус и меши (запускается у загруженного игрока)
+    pregen: Option<(i32, bool)>,
+    /// Окно с рендерером экрана загрузки (появляется в resumed)
+    view: Option<LoadingView>,
+}
+
+struct LoadingView {
+    window: Arc<Window>,
+    renderer: Renderer,
+    gui_renderer: GuiRenderer,
 }
 
 impl App {
-    pub fn new() -> Self {
+    /// Мир загружается в фоне, пока окно показывает экран загрузки
+    pub fn new(world_name: &str, new_world: WorldGenConfig, settings: Settings) -> Self {
         Self {
-            resources: InitSystem::create_resources(),
+            resources: None,
+            loading: Some(Loading {
+                world: WorldLoading::start(world_name, new_world),
+                settings,
+                pregen: None,
+                view: None,
+            }),
+            benchmark: None,
+        }
+    }
+    
+    /// События окна, пока мир загружается
+    fn loading_event(&mut self, event_loop: &ActiveEventLoop, event: WindowEvent) {
+        let Some(loading) = &mut self.loading else { return };
+        match event {
+            WindowEvent::CloseRequested => event_loop.exit(),
+            
+            WindowEvent::Resized(physical_size) => {
+                if let Some(view) = &mut loading.view {
+                    view.renderer.resize(physical_size);
+                    view.gui_renderer.resize(view.renderer.queue(), physical_size.width, physical_size.height);
+                }
+            }
+            
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                if let Some(view) = &mut loading.view {
+                    view.gui_renderer.set_dpi(view.renderer.queue(), scale_factor);
+                }
+            }
+            
+            WindowEvent::RedrawRequested => {
+                if let Some(world) = loading.world.update(loading.view.as_mut().map(|view| &mut view.renderer)) {
+                    self.finish_loading(world);
+                    return;
+                }
+                let Some(view) = &mut loading.view else { return };
+                let (status, progress, tip) = loading.world.status();
+                view.gui_renderer.loading_screen().set(&tr(status), progress, &tr(tip));
+                
+                let gui_renderer = &mut view.gui_renderer;
+                let result = view.renderer.render_overlay(|device, encoder, target, queue| {
+                    gui_renderer.render_loading(device, encoder, target, queue);
+                });
+                if let Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) = result {
+                    let size = view.renderer.size();
+                    view.renderer.resize(size);
+                }
+                view.window.request_redraw();
+            }
+            
+            _ => {}
+        }
+    }
+    
+    /// Мир загружен: ресурсы игры получают окно и рендерер экрана загрузки
+    fn finish_loading(&mut self, world: LoadedWorld) {
+        let Some(loading) = self.loading.take() else { return };
+        let mut resources = InitSystem::create_resources(world, loading.settings);
+        if let Some((radius, mesh)) = loading.pregen {
+            let pos = resources.player.position;
+            resources.pregen = Some(PregenTask::start(pos.x, pos.z, radius, mesh));
+        }
+        if let Some(view) = loading.view {
+            InitSystem::init_rendering(&mut resources, view.window, view.renderer, view.gui_renderer);
+            // Захватываем курсор при входе в мир
+            InputSystem::grab_cursor(&mut resources, true);
         }
+        self.resources = Some(resources);
     }
 }
 
 impl ApplicationHandler for App {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
-        if self.resources.window.is_none() {
+        let Some(loading) = &mut self.loading else { return };
+        if loading.view.is_none() {
             let window_attrs = Window::default_attributes()
                 .with_title("GPU Infinite Terrain - Press F5 to toggle camera mode")
                 .with_inner_size(winit::dpi::LogicalSize::new(1280, 720));
             
             let window = Arc::new(event_loop.create_window(window_attrs).unwrap());
-            
-            InitSystem::init_rendering(&mut self.resources, window);
-            
-            // Захватываем курсор при старте
-            InputSystem::grab_cursor(&mut self.resources, true);
+            let (renderer, gui_renderer) = InitSystem::create_renderer(Arc::clone(&window), &loading.settings);
+            window.request_redraw();
+            loading.view = Some(LoadingView { window, renderer, gui_renderer });
         }
     }
 
     fn window_event(&mut self, event_loop: &ActiveEventLoop, _id: WindowId, event: WindowEvent) {
+        if self.loading.is_some() {
+            self.loading_event(event_loop, event);
+            return;
+        }
+        let Some(resources) = &mut self.resources else { return };
+        
+        // Любой ввод прерывает демо-облёт; прерывающий ввод до меню не доходит
+        match event {
+            WindowEvent::CursorMoved { position, .. } => {
+                InputSystem::process_cursor_moved(resources, position.x, position.y);
+                resources.attract.notify_input();
+                return;
+            }
+            WindowEvent::KeyboardInput { .. } | WindowEvent::MouseInput { .. } | WindowEvent::MouseWheel { .. } => {
+                if resources.attract.notify_input() {
+                    return;
+                }
+            }
+            _ => {}
+        }
+        
         match event {
             WindowEvent::CloseRequested => {
-                SaveSystem::save_world(&self.resources);
+                SaveSystem::save_world(resources);
                 event_loop.exit();
             }
             
             WindowEvent::Resized(physical_size) => {
-                if let Some(renderer) = &mut self.resources.renderer {
+                if let Some(renderer) = &mut resources.renderer {
                     renderer.resize(physical_size);
-                    self.resources.camera.resize(physical_size.width, physical_size.height);
-                    self.resources.menu.resize(physical_size.width, physical_size.height);
+                    resources.camera.resize(physical_size.width, physical_size.height);
+                    resources.menu.resize(physical_size.width, physical_size.height);
                     
-                    if let Some(gui_renderer) = &mut self.resources.gui_renderer {
+                    if let Some(gui_renderer) = &mut resources.gui_renderer {
                         gui_renderer.resize(renderer.queue(), physical_size.width, physical_size.height);
                     }
                 }
             }
             
+            // Окно перенесли на монитор с другим DPI: масштаб "auto" пересчитывается
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                if let (Some(renderer), Some(gui_renderer)) = (&resources.renderer, &mut resources.gui_renderer) {
+                    gui_renderer.set_dpi(renderer.queue(), scale_factor);
+                }
+            }
+            
+            // Alt+Tab: курсор отпускается, при возврате - захват снова
+            WindowEvent::Focused(focused) => {
+                InputSystem::focus_changed(resources, focused);
+            }
+            
+            // Открытая консоль забирает всю клавиатуру
+            WindowEvent::KeyboardInput { event, .. } if resources.console.is_open() => {
+                ConsoleSystem::handle_key(resources, &event);
+            }
+            
             WindowEvent::KeyboardInput {
                 event: KeyEvent {
                     physical_key: PhysicalKey::Code(keycode),
@@ -75,31 +212,34 @@ impl ApplicationHandler for App {
                 },
                 ..
             } => {
-                if let Some(action) = InputSystem::process_keyboard(&mut self.resources, keycode, state) {
+                if let Some(action) = InputSystem::process_keyboard(resources, keycode, state) {
                     match action {
                         InputAction::SaveWorld => {
-                            SaveSystem::save_world(&self.resources);
+                            SaveSystem::save_world(resources);
                         }
-                        InputAction::CycleTime => {
-                            if let Some(renderer) = &mut self.resources.renderer {
-                                let current = renderer.time_of_day();
-                                let next = if current < 0.25 { 0.35 }
-                                    else if current < 0.5 { 0.5 }
-                                    else if current < 0.75 { 0.7 }
-                                    else { 0.0 };
-                                renderer.set_time_of_day(next);
+                        InputAction::PaletteThemeChange => {
+                            if let Some(renderer) = &mut resources.renderer {
+                                renderer.set_palette_theme(resources.palette_theme);
                             }
                         }
                         InputAction::SlowTime => {
-                            if let Some(renderer) = &mut self.resources.renderer {
+                            if let Some(renderer) = &mut resources.renderer {
                                 renderer.set_time_speed(10.0);
                             }
                         }
                         InputAction::FastTime => {
-                            if let Some(renderer) = &mut self.resources.renderer {
+                            if let Some(renderer) = &mut resources.renderer {
                                 renderer.set_time_speed(120.0);
                             }
                         }
+                        InputAction::WorldStats => {
+                            SaveSystem::scan_world_stats(resources, DEFAULT_SCAN_RADIUS);
+                        }
+                        InputAction::OverlayModeChange => {
+                            if let Some(renderer) = &mut resources.renderer {
+                                renderer.set_overlay_mode(resources.overlay_mode);
+                            }
+                        }
                         _ => {}
                     }
                 }
@@ -107,17 +247,41 @@ impl ApplicationHandler for App {
             
             WindowEvent::RedrawRequested => {
                 let now = Instant::now();
-                let dt = (now - self.resources.last_frame).as_secs_f32();
-                self.resources.last_frame = now;
-                let time = (now - self.resources.start_time).as_secs_f32();
+                let dt = (now - resources.last_frame).as_secs_f32();
+                resources.last_frame = now;
+                let time = (now - resources.start_time).as_secs_f32();
                 
                 // Update
-                UpdateSystem::update(&mut self.resources, dt, time);
+                {
+                    let _scope = profiler::scope("update");
+                    UpdateSystem::update(resources, dt, time);
+                }
+                
+                // Benchmark: камера на маршруте вместо игрока
+                let benchmark_done = match (&mut self.benchmark, &mut resources.renderer) {
+                    (Some(benchmark), Some(renderer)) => {
+                        benchmark.frame(dt, resources.player.position, &mut resources.camera, renderer)
+                    }
+                    _ => false,
+                };
                 
                 // Render
-                RenderSystem::render(&mut self.resources, time, dt, event_loop);
+                {
+                    let _scope = profiler::scope("render");
+                    RenderSystem::render(resources, time, dt, event_loop);
+                }
+                profiler::end_frame();
                 
-                if let Some(window) = &self.resources.window {
+                // Мир прогона не сохраняется: при следующем запуске он создаётся заново
+                if benchmark_done {
+                    if let Some(benchmark) = self.benchmark.take() {
+                        benchmark.finish();
+                    }
+                    event_loop.exit();
+                    return;
+                }
+                
+                if let Some(window) = &resources.window {
                     window.request_redraw();
                 }
             }
@@ -125,8 +289,19 @@ impl ApplicationHandler for App {
             WindowEvent::MouseInput { state, button, .. } => {
                 let pressed = state == ElementState::Pressed;
                 
+                // Захват не удался (окно было без фокуса) - клик повторяет его
+                if pressed && resources.cursor.needs_regrab() {
+                    InputSystem::regrab_cursor(resources);
+                    return;
+                }
+                
+                // В фоторежиме мир на паузе - блоки не ломаются и не ставятся
+                if resources.photo_mode.is_active() {
+                    return;
+                }
+                
                 // Проверяем инвентарь первым
-                let inventory_visible = if let Some(gui) = &self.resources.gui_renderer {
+                let inventory_visible = if let Some(gui) = &resources.gui_renderer {
                     gui.inventory_ref().is_visible()
                 } else {
                     false
@@ -135,32 +310,52 @@ impl ApplicationHandler for App {
                 if inventory_visible {
                     if button == winit::event::MouseButton::Left {
                         if pressed {
-                            MenuSystem::handle_mouse_down(&mut self.resources);
+                            MenuSystem::handle_mouse_down(resources);
                         } else {
-                            MenuSystem::handle_mouse_up(&mut self.resources);
+                            MenuSystem::handle_mouse_up(resources);
+                        }
+                    } else if pressed && button == winit::event::MouseButton::Right {
+                        MenuSystem::handle_split(resources);
+                    }
+                } else if ContainerSystem::is_open(resources) {
+                    // Экран сундука: ЛКМ переносит стопку, ПКМ - половину
+                    if pressed {
+                        match button {
+                            winit::event::MouseButton::Left => ContainerSystem::handle_click(resources, false),
+                            winit::event::MouseButton::Right => ContainerSystem::handle_click(resources, true),
+                            _ => {}
                         }
                     }
-                } else if self.resources.menu.is_visible() {
+                } else if WorldMapSystem::is_open(resources) {
+                    // Карта мира: перетаскивание и метки
+                    WorldMapSystem::handle_mouse(resources, button, pressed);
+                } else if resources.menu.is_visible() {
                     // Меню открыто
                     if button == winit::event::MouseButton::Left {
-                        self.resources.menu_mouse_pressed = pressed;
+                        resources.menu_mouse_pressed = pressed;
                     }
                     
                     if pressed && button == winit::event::MouseButton::Left {
-                        MenuSystem::handle_click(&mut self.resources, event_loop);
+                        MenuSystem::handle_click(resources, event_loop);
                     }
-                } else if self.resources.cursor_grabbed {
+                } else if resources.cursor.is_grabbed() {
                     // Игровой режим
+                    if !pressed && button == winit::event::MouseButton::Left {
+                        BlockInteractionSystem::handle_break_release(resources);
+                    }
+                    if !pressed && button == winit::event::MouseButton::Right {
+                        BlockInteractionSystem::handle_place_release(resources);
+                    }
                     if pressed {
                         match button {
                             winit::event::MouseButton::Left => {
-                                BlockInteractionSystem::handle_break(&mut self.resources);
+                                BlockInteractionSystem::handle_break(resources);
                             }
                             winit::event::MouseButton::Right => {
-                                BlockInteractionSystem::handle_place(&mut self.resources);
+                                BlockInteractionSystem::handle_place(resources);
                             }
                             winit::event::MouseButton::Middle => {
-                                BlockInteractionSystem::handle_pick_block(&mut self.resources);
+                                BlockInteractionSystem::handle_pick_block(resources);
                             }
                             _ => {}
                         }
@@ -168,10 +363,6 @@ impl ApplicationHandler for App {
                 }
             }
             
-            WindowEvent::CursorMoved { position, .. } => {
-                self.resources.mouse_pos = (position.x as f32, position.y as f32);
-            }
-            
             _ => {}
         }
     }
@@ -182,17 +373,23 @@ impl ApplicationHandler for App {
         _device_id: winit::event::DeviceId,
         event: DeviceEvent,
     ) {
-        if self.resources.menu.is_visible() {
+        let Some(resources) = &mut self.resources else { return };
+        
+        if matches!(event, DeviceEvent::MouseMotion { .. } | DeviceEvent::MouseWheel { .. }) {
+            resources.attract.notify_input();
+        }
+        
+        if resources.menu.is_visible() {
             return;
         }
         
         match event {
             DeviceEvent::MouseMotion { delta } => {
-                InputSystem::process_mouse_motion(&mut self.resources, delta);
+                InputSystem::process_mouse_motion(resources, delta);
             }
             
             DeviceEvent::MouseWheel { delta } => {
-                InputSystem::process_mouse_wheel(&mut self.resources, delta);
+                InputSystem::process_mouse_wheel(resources, delta);
             }
             
             _ => {}
@@ -200,7 +397,12 @@ impl ApplicationHandler for App {
     }
 
     fn about_to_wait(&mut self, _event_loop: &ActiveEventLoop) {
-        if let Some(window) = &self.resources.window {
+        let window = match (&self.resources, &self.loading) {
+            (Some(resources), _) => resources.window.as_ref(),
+            (None, Some(loading)) => loading.view.as_ref().map(|view| &view.window),
+            (None, None) => None,
+        };
+        if let Some(window) = window {
             window.request_redraw();
         }
     }
@@ -208,27 +410,119 @@ impl ApplicationHandler for App {
 
 /// Запуск игры
 pub fn run() {
-    env_logger::init();
+    logging::init();
+    let settings = Settings::load();
+    logging::configure(&settings.logging);
+    locale::configure(&settings.locale);
+    #[cfg(debug_assertions)]
+    crate::gpu::biomes::watch_biome_files();
+    
+    let args: Vec<String> = std::env::args().collect();
+    // Параметры генерации нового мира (существующий мир хранит свои в заголовке)
+    let mut new_world = WorldGenConfig::from_args(&args);
+    // --world NAME - папка мира в saves/ (нет - создаётся новый)
+    let mut world_name = args
+        .iter()
+        .position(|arg| arg == "--world")
+        .and_then(|i| args.get(i + 1))
+        .map_or(DEFAULT_WORLD, String::as_str);
+    
+    // --benchmark - прогон по маршруту в свежем мире с фиксированным сидом
+    let benchmark = args.iter().any(|arg| arg == "--benchmark");
+    if benchmark {
+        let manager = SaveSystem::world_manager();
+        if manager.path_of(BENCHMARK_WORLD).is_ok_and(|path| path.exists()) {
+            if let Err(e) = manager.delete(BENCHMARK_WORLD) {
+                log::warn!("Мир прошлого benchmark не удалён: {:?}", e);
+            }
+        }
+        world_name = BENCHMARK_WORLD;
+        new_world = WorldGenConfig::with_seed(BENCHMARK_SEED);
+    }
+    
+    // --seed-preview [размер] - карта сида без создания мира
+    if let Some(i) = args.iter().position(|arg| arg == "--seed-preview") {
+        let size = args.get(i + 1).and_then(|s| s.parse().ok()).unwrap_or(DEFAULT_PREVIEW_SIZE);
+        let preview = SeedPreview::render(&new_world, size);
+        let path = format!("seed_preview_{}.png", new_world.seed);
+        log::info!("Seed {}: {}x{} блоков вокруг спавна", new_world.seed, preview.extent_blocks(), preview.extent_blocks());
+        preview.print_summary();
+        match preview.save_png(&path) {
+            Ok(()) => log::info!("Карта сохранена в {}", path),
+            Err(e) => log::error!("Ошибка записи {}: {}", path, e),
+        }
+        return;
+    }
+    
+    // --world-stats [радиус] - офлайн-статистика мира без запуска игры
+    if let Some(i) = args.iter().position(|arg| arg == "--world-stats") {
+        let radius = args.get(i + 1).and_then(|r| r.parse().ok()).unwrap_or(DEFAULT_SCAN_RADIUS);
+        SaveSystem::scan_world_stats_offline(radius, world_name, new_world);
+        return;
+    }
+    
+    // --pregen radius N [mesh] - прегенерация вокруг точки спавна
+    let pregen = args.iter().position(|arg| arg == "--pregen").and_then(|i| {
+        let pregen_args: Vec<&str> = args[i + 1..]
+            .iter()
+            .map(|arg| arg.as_str())
+            .take_while(|arg| !arg.starts_with("--"))
+            .collect();
+        parse_pregen_args(&pregen_args)
+            .map_err(|e| log::error!("--pregen: {}", e))
+            .ok()
+    });
+    
+    // --headless [тики] - мир без окна (сервер, CI)
+    if let Some(i) = args.iter().position(|arg| arg == "--headless") {
+        let ticks = args.get(i + 1).and_then(|ticks| ticks.parse().ok());
+        run_headless(world_name, new_world, settings, HeadlessOptions { ticks, pregen });
+        return;
+    }
     
     println!("=== Controls ===");
     println!("WASD - Move");
     println!("Mouse - Look around");
     println!("Space - Jump / Fly up");
-    println!("Shift/Ctrl - Sprint / Fly down");
+    println!("Shift - Sprint / Fly down");
+    println!("Ctrl - Sneak / Fly down (swap: controls.sneak_key in settings)");
     println!("F - Toggle flight mode");
-    println!("LMB - Break block");
+    println!("LMB (hold) - Break block");
     println!("RMB - Place block");
+    println!("MMB - Pick block (incl. sub-voxels)");
     println!("F5 - Toggle camera mode (1st/3rd person)");
     println!("F6 - Save world");
     println!("Mouse wheel / +/- - Adjust camera distance");
-    println!("T - Cycle time of day");
+    println!("T or / - Console (/help for commands)");
     println!("[ / ] - Slow/fast time speed");
+    println!("F8 - World statistics (world_stats.json)");
+    println!("F9 - Cycle overlay (light level heatmap)");
+    println!("N - Minimap zoom (64/128/256 blocks, then hidden)");
+    println!("M - World map (drag to pan, wheel to zoom, RMB to set a waypoint)");
+    println!("F10 - Pregenerate chunks around player (again to cancel)");
+    println!("F2 - Screenshot (screenshots/ in config dir)");
+    println!("F3 - Session log viewer (PageUp/PageDown to scroll)");
+    println!("F4 - Profiler overlay (/profile dump - Chrome trace)");
     println!("Escape - Open menu");
+    println!("Photo mode (pause menu): WASD fly, Q/E roll, wheel FOV, [ ] time, H hide UI, F depth of field, Z/X focus");
+    println!("World: --world NAME (saves/NAME, created if missing)");
+    println!("New world: --seed N --sea-level N --cave-density F --biome-scale F --terrain-amplitude F");
+    println!("World type: --preset default|superflat|void (superflat layers: saves/NAME/superflat.json)");
+    println!("Seed preview map: --seed-preview [size] --seed N");
+    println!("Headless server: --headless [ticks] (stdin - console commands, stop - save and exit)");
+    println!("Benchmark: --benchmark (fixed seed, scripted flight, report in benchmarks/ in config dir)");
+    println!("Log levels: RUST_LOG=info,terrain=debug or /log <filter> (logs/latest.log in config dir)");
     println!("================");
     
     let event_loop = EventLoop::new().unwrap();
     event_loop.set_control_flow(ControlFlow::Poll);
     
-    let mut app = App::new();
+    let mut app = App::new(world_name, new_world, settings);
+    if benchmark {
+        app.benchmark = Some(Benchmark::new());
+    }
+    if let Some(loading) = &mut app.loading {
+        loading.pregen = pregen;
+    }
     event_loop.run_app(&mut app).unwrap();
 }
@@ -2,8 +2,358 @@
 // Config - Константы и настройки игры
 // ============================================
 
-/// Путь к файлу сохранения
+use std::path::PathBuf;
+
+use serde::{Serialize, Deserialize};
+
+use crate::gpu::render::{SamplingSettings, FogSettings, PostSettings, AntiAliasing, ResolutionSettings};
+use crate::gpu::clouds::CloudQuality;
+use crate::gpu::input::{HapticSettings, CursorLock};
+use crate::gpu::player::SneakKey;
+use crate::gpu::logging::LogSettings;
+use crate::gpu::locale::LocaleSettings;
+use crate::gpu::gui::UiSettings;
+use crate::gpu::lighting::{TimeOfDay, DEFAULT_DAY_LENGTH_MINUTES};
+
+/// Путь к файлу сохранения старого формата (мигрируется в мир по умолчанию)
 pub const SAVE_FILE: &str = "world.dat";
 
+/// Директория мира в региональном формате до появления списка миров
+/// (переносится в SAVES_DIR/DEFAULT_WORLD)
+pub const LEGACY_WORLD_DIR: &str = "world";
+
+/// Папка со всеми мирами
+pub const SAVES_DIR: &str = "saves";
+
+/// Папка схематик (/schem save, /schem load)
+pub const SCHEMATICS_DIR: &str = "schematics";
+
+/// Мир по умолчанию (папка внутри SAVES_DIR, выбирается --world NAME)
+pub const DEFAULT_WORLD: &str = "world";
+
 /// Сид мира по умолчанию
 pub const DEFAULT_SEED: u64 = 12345;
+
+/// Дистанция камеры от игрока в режиме 3-го лица
+pub const THIRD_PERSON_DISTANCE: f32 = 4.0;
+
+/// Смещение камеры 3-го лица вправо от головы (через плечо)
+pub const SHOULDER_OFFSET: f32 = 0.6;
+
+/// Базовый угол обзора по умолчанию (градусы)
+pub const DEFAULT_FOV: f32 = 70.0;
+
+/// Диапазон угла обзора в настройках (градусы)
+pub const MIN_FOV: f32 = 50.0;
+pub const MAX_FOV: f32 = 110.0;
+
+/// Чувствительность мыши по умолчанию
+pub const DEFAULT_MOUSE_SENSITIVITY: f32 = 0.5;
+
+/// Диапазон чувствительности мыши в настройках
+pub const MIN_MOUSE_SENSITIVITY: f32 = 0.05;
+pub const MAX_MOUSE_SENSITIVITY: f32 = 2.0;
+
+/// Файл настроек в папке конфигурации пользователя
+pub const SETTINGS_FILE: &str = "settings.json";
+
+/// Имя папки игры внутри папки конфигурации
+const CONFIG_DIR_NAME: &str = "kimi-voxel";
+
+/// Папка конфигурации пользователя: %APPDATA% на Windows,
+/// $XDG_CONFIG_HOME или ~/.config на остальных. Без них - рядом с игрой
+pub fn config_dir() -> PathBuf {
+    let base = if cfg!(windows) {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+    base.map_or_else(|| PathBuf::from("."), |base| base.join(CONFIG_DIR_NAME))
+}
+
+/// Слайдер LOD (0-1) -> дистанция в чанках (4-64)
+pub fn lod_slider_to_distance(value: f32) -> i32 {
+    (value.clamp(0.0, 1.0) * 60.0 + 4.0).round() as i32
+}
+
+/// Дистанция в чанках -> положение слайдера LOD
+pub fn lod_distance_to_slider(distance: i32) -> f32 {
+    ((distance - 4) as f32 / 60.0).clamp(0.0, 1.0)
+}
+
+/// Именованные уровни качества дальности прорисовки
+/// (дистанции LOD0-LOD3 и качество дальних LOD вместе)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LodPreset {
+    Low,
+    Medium,
+    High,
+    Ultra,
+}
+
+impl LodPreset {
+    pub const ALL: [LodPreset; 4] = [LodPreset::Low, LodPreset::Medium, LodPreset::High, LodPreset::Ultra];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LodPreset::Low => "Low",
+            LodPreset::Medium => "Medium",
+            LodPreset::High => "High",
+            LodPreset::Ultra => "Ultra",
+        }
+    }
+
+    /// Дистанции LOD0-LOD3 в чанках
+    pub fn distances(self) -> [i32; 4] {
+        match self {
+            LodPreset::Low => [6, 12, 20, 32],
+            LodPreset::Medium => [8, 16, 28, 48],
+            LodPreset::High => [8, 16, 32, 64],
+            LodPreset::Ultra => [16, 32, 48, 64],
+        }
+    }
+
+    /// Качество дальних LOD (1.0 - без упрощения)
+    pub fn quality(self) -> f32 {
+        match self {
+            LodPreset::Low => 0.5,
+            LodPreset::Medium => 0.75,
+            LodPreset::High | LodPreset::Ultra => 1.0,
+        }
+    }
+
+    pub fn sliders(self) -> [f32; 4] {
+        self.distances().map(lod_distance_to_slider)
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            LodPreset::Low => LodPreset::Medium,
+            LodPreset::Medium => LodPreset::High,
+            LodPreset::High => LodPreset::Ultra,
+            LodPreset::Ultra => LodPreset::Low,
+        }
+    }
+
+    /// Какому уровню соответствуют слайдеры (None - ручная настройка)
+    pub fn detect(sliders: [f32; 4], quality: f32) -> Option<Self> {
+        let distances = sliders.map(lod_slider_to_distance);
+        Self::ALL.into_iter().find(|preset| {
+            preset.distances() == distances && (preset.quality() - quality).abs() < 0.01
+        })
+    }
+}
+
+/// Графика: дальности LOD, качество, угол обзора, фильтрация, вода, облака, туман
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GraphicsSettings {
+    /// Положения слайдеров LOD0-LOD3 (0-1)
+    pub lod_sliders: [f32; 4],
+    /// Качество дальних LOD (1.0 - без упрощения)
+    pub lod_quality: f32,
+    /// Вертикальная дальность в секциях (None - весь столбец)
+    pub vertical_distance: Option<i32>,
+    /// Базовый угол обзора (градусы)
+    pub fov: f32,
+    pub sampling: SamplingSettings,
+    /// Экранные отражения в воде (без них вода отражает только небо)
+    pub water_reflections: bool,
+    /// Слой облаков: выключен, плоский или объёмный
+    pub clouds: CloudQuality,
+    /// Плотность и начало дальнего тумана (конец - внешняя дистанция LOD)
+    pub fog: FogSettings,
+    /// Bloom, тонмаппинг, виньетка и экспозиция HDR кадра
+    pub post: PostSettings,
+    /// Сглаживание (MSAA применяется после перезапуска)
+    pub anti_aliasing: AntiAliasing,
+    /// Масштаб 3D кадра и динамический режим по цели FPS
+    pub resolution: ResolutionSettings,
+}
+
+impl GraphicsSettings {
+    /// Дистанции LOD в чанках по слайдерам
+    pub fn lod_distances(&self) -> [i32; 4] {
+        self.lod_sliders.map(lod_slider_to_distance)
+    }
+}
+
+impl Default for GraphicsSettings {
+    fn default() -> Self {
+        Self {
+            lod_sliders: LodPreset::High.sliders(),
+            lod_quality: LodPreset::High.quality(),
+            vertical_distance: None,
+            fov: DEFAULT_FOV,
+            sampling: SamplingSettings::default(),
+            water_reflections: true,
+            clouds: CloudQuality::default(),
+            fog: FogSettings::default(),
+            post: PostSettings::default(),
+            anti_aliasing: AntiAliasing::default(),
+            resolution: ResolutionSettings::default(),
+        }
+    }
+}
+
+/// Звук
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioSettings {
+    /// Общая громкость (0-1)
+    pub master_volume: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self { master_volume: 1.0 }
+    }
+}
+
+/// Дальность "палочки строителя" по умолчанию (блоки)
+pub const DEFAULT_BUILDER_WAND_REACH: f32 = 8.0;
+
+/// Управление: мышь, вибрация геймпада, помощники строительства
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ControlSettings {
+    pub mouse_sensitivity: f32,
+    /// Инверсия вертикали мыши
+    pub invert_y: bool,
+    /// Сырой ввод мыши (без ускорения ОС)
+    pub raw_input: bool,
+    pub haptics: HapticSettings,
+    /// Alt + ПКМ продлевает блок под прицелом на один наружу
+    pub builder_wand: bool,
+    /// Дальность луча для Alt + ПКМ (блоки)
+    pub builder_wand_reach: f32,
+    /// Клавиша "красться": "control" или "shift" (вторая - бег)
+    pub sneak_key: SneakKey,
+    /// Захват курсора в игре: "locked" или "confined"
+    pub cursor_lock: CursorLock,
+}
+
+impl Default for ControlSettings {
+    fn default() -> Self {
+        Self {
+            mouse_sensitivity: DEFAULT_MOUSE_SENSITIVITY,
+            invert_y: false,
+            raw_input: true,
+            haptics: HapticSettings::default(),
+            builder_wand: true,
+            builder_wand_reach: DEFAULT_BUILDER_WAND_REACH,
+            sneak_key: SneakKey::default(),
+            cursor_lock: CursorLock::default(),
+        }
+    }
+}
+
+/// Диапазон длины суток в настройках (реальные минуты)
+pub const MIN_DAY_LENGTH_MINUTES: f32 = 1.0;
+pub const MAX_DAY_LENGTH_MINUTES: f32 = 240.0;
+
+/// Смена дня и ночи
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimeSettings {
+    /// Длина игровых суток в реальных минутах
+    pub day_length_minutes: f32,
+    /// Время суток стоит на месте
+    pub paused: bool,
+}
+
+impl TimeSettings {
+    /// Скорость времени для рендерера (пауза - 0)
+    pub fn time_speed(&self) -> f32 {
+        if self.paused {
+            0.0
+        } else {
+            TimeOfDay::speed_for_day_length(self.day_length_minutes.clamp(MIN_DAY_LENGTH_MINUTES, MAX_DAY_LENGTH_MINUTES))
+        }
+    }
+}
+
+impl Default for TimeSettings {
+    fn default() -> Self {
+        Self { day_length_minutes: DEFAULT_DAY_LENGTH_MINUTES, paused: false }
+    }
+}
+
+/// Настройки игрока, общие для всех миров (пишутся по кнопке Save в меню)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub graphics: GraphicsSettings,
+    pub audio: AudioSettings,
+    pub controls: ControlSettings,
+    pub time: TimeSettings,
+    pub logging: LogSettings,
+    pub locale: LocaleSettings,
+    pub ui: UiSettings,
+}
+
+impl Settings {
+    /// Путь к файлу настроек
+    pub fn path() -> PathBuf {
+        config_dir().join(SETTINGS_FILE)
+    }
+
+    /// Загрузить настройки (нет файла или он битый - по умолчанию)
+    pub fn load() -> Self {
+        let path = Self::path();
+        match std::fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                log::warn!("Ошибка разбора {}: {}, настройки по умолчанию", path.display(), e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Записать настройки в папку конфигурации
+    pub fn save(&self) -> Result<(), String> {
+        let path = Self::path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(&path, json).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_fields_fall_back_to_defaults() {
+        let settings: Settings = serde_json::from_str(r#"{ "graphics": { "fov": 90.0 }, "audio": {} }"#).unwrap();
+        assert_eq!(settings.graphics.fov, 90.0);
+        assert_eq!(settings.graphics.lod_sliders, LodPreset::High.sliders());
+        assert!(settings.graphics.water_reflections);
+        assert_eq!(settings.graphics.clouds, CloudQuality::Flat);
+        assert_eq!(settings.graphics.fog, FogSettings::default());
+        assert!(settings.graphics.post.bloom && settings.graphics.post.tonemapping);
+        assert_eq!(settings.graphics.anti_aliasing, AntiAliasing::Off);
+        assert_eq!(settings.graphics.resolution, ResolutionSettings::default());
+        assert_eq!(settings.audio, AudioSettings::default());
+        assert_eq!(settings.controls, ControlSettings::default());
+        assert_eq!(settings.logging, LogSettings::default());
+        assert_eq!(settings.locale.language, "auto");
+        assert!(settings.ui.is_auto() && settings.ui.safe_area == 0.0);
+        assert_eq!(settings.time.time_speed(), 1.0);
+
+        let json = serde_json::to_string(&settings).unwrap();
+        assert_eq!(serde_json::from_str::<Settings>(&json).unwrap(), settings);
+    }
+
+    #[test]
+    fn presets_round_trip_through_sliders() {
+        for preset in LodPreset::ALL {
+            assert_eq!(LodPreset::detect(preset.sliders(), preset.quality()), Some(preset));
+        }
+        assert_eq!(LodPreset::detect([0.5; 4], 1.0), None);
+    }
+}
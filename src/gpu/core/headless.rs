@@ -0,0 +1,128 @@
+// ============================================
+// Headless - Мир без окна (сервер, CI)
+// ============================================
+// --headless [тики]: мир загружается и тикает без winit окна и GPU -
+// физика игрока, погода, тики блоков, прегенерация и сохранение идут
+// как в игре, рендерер остаётся None. Строки из stdin выполняются как
+// команды консоли ('/' можно не писать), "stop" сохраняет мир и
+// завершает процесс; закрытый stdin сервер не останавливает. С числом
+// тиков мир тикает без пауз и сохраняется в конце - для интеграционных
+// тестов в CI.
+//
+// Время суток живёт в рендерере, поэтому без окна сутки стоят.
+
+use std::io::BufRead;
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use std::time::{Duration, Instant};
+
+use crate::gpu::console::LineKind;
+use crate::gpu::systems::{ConsoleSystem, InitSystem, SaveSystem, UpdateSystem};
+use crate::gpu::terrain::generation::WorldGenConfig;
+use crate::gpu::terrain::PregenTask;
+
+use super::{GameResources, Settings};
+
+/// Тиков в секунду
+const TICK_RATE: u32 = 20;
+/// Автосохранение сервера (тиков)
+const AUTOSAVE_TICKS: u64 = TICK_RATE as u64 * 300;
+
+/// Параметры запуска без окна
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeadlessOptions {
+    /// Сколько тиков прогнать (None - до команды "stop")
+    pub ticks: Option<u64>,
+    /// --pregen: радиус и меши
+    pub pregen: Option<(i32, bool)>,
+}
+
+/// Запустить мир без окна
+pub fn run_headless(world_name: &str, new_world: WorldGenConfig, settings: Settings, options: HeadlessOptions) {
+    let started = Instant::now();
+    let world = SaveSystem::load_or_create(world_name, new_world);
+    let mut resources = InitSystem::create_resources(world, settings);
+    if let Some((radius, mesh)) = options.pregen {
+        let pos = resources.player.position;
+        resources.pregen = Some(PregenTask::start(pos.x, pos.z, radius, mesh));
+    }
+    log::info!("Headless: мир загружен за {:.1} с, {} тиков/с", started.elapsed().as_secs_f32(), TICK_RATE);
+
+    // Без лимита тиков команды читаются из stdin
+    let commands = options.ticks.is_none().then(spawn_stdin_reader);
+    let tick = Duration::from_secs(1) / TICK_RATE;
+    let dt = tick.as_secs_f32();
+    let mut next_tick = Instant::now();
+    let mut ticks: u64 = 0;
+
+    loop {
+        if options.ticks.is_some_and(|limit| ticks >= limit) {
+            break;
+        }
+        if let Some(commands) = &commands {
+            if !run_commands(&mut resources, commands) {
+                break;
+            }
+            // Сервер держит темп; CI прогон идёт без пауз
+            next_tick += tick;
+            let now = Instant::now();
+            if next_tick > now {
+                std::thread::sleep(next_tick - now);
+            } else {
+                next_tick = now;
+            }
+        }
+
+        UpdateSystem::update(&mut resources, dt, ticks as f32 * dt);
+        ticks += 1;
+        if ticks % AUTOSAVE_TICKS == 0 {
+            SaveSystem::save_world(&resources);
+        }
+    }
+
+    SaveSystem::save_world(&resources);
+    log::info!("Headless: {} тиков за {:.1} с", ticks, started.elapsed().as_secs_f32());
+}
+
+/// Поток чтения stdin: строка - команда
+fn spawn_stdin_reader() -> Receiver<String> {
+    let (tx, rx) = channel();
+    std::thread::Builder::new()
+        .name("headless-stdin".to_string())
+        .spawn(move || {
+            for line in std::io::stdin().lock().lines() {
+                let Ok(line) = line else { break };
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        })
+        .expect("поток чтения stdin");
+    rx
+}
+
+/// Выполнить пришедшие команды. false - пришла "stop"
+fn run_commands(resources: &mut GameResources, commands: &Receiver<String>) -> bool {
+    loop {
+        let line = match commands.try_recv() {
+            Ok(line) => line,
+            Err(TryRecvError::Empty | TryRecvError::Disconnected) => return true,
+        };
+        let line = line.trim();
+        match line {
+            "" => continue,
+            "stop" | "/stop" => return false,
+            _ => {}
+        }
+        let line = if line.starts_with('/') { line.to_string() } else { format!("/{}", line) };
+        let issued = Instant::now();
+        ConsoleSystem::execute_line(resources, &line);
+        // Ответ консоли - в терминал
+        for reply in resources.console.lines_since(issued) {
+            match reply.kind {
+                LineKind::Input => {}
+                LineKind::Info => println!("{}", reply.text),
+                LineKind::Error => eprintln!("{}", reply.text),
+            }
+        }
+    }
+}
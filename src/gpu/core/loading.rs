@@ -0,0 +1,201 @@
+// ============================================
+// World Loading - Фоновая загрузка мира
+// ============================================
+// Сохранение читается и область спавна генерируется в отдельном
+// потоке, пока окно рисует экран загрузки. Готовые меши загружаются
+// на GPU порциями по кадрам, чтобы полоса прогресса не замирала.
+// Пока идёт генерация, полоса ползёт к концу этапа по времени: сам
+// генератор прогресс не сообщает.
+
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use crate::gpu::render::Renderer;
+use crate::gpu::systems::{LoadedWorld, SaveSystem};
+use crate::gpu::terrain::generation::WorldGenConfig;
+use crate::gpu::terrain::{GeneratedMesh, HybridTerrainManager};
+
+/// Чанков начальной области, загружаемых на GPU за кадр
+const UPLOADS_PER_FRAME: usize = 48;
+/// Доля полосы к концу этапа: сохранение, генерация, загрузка на GPU
+const SAVE_END: f32 = 0.1;
+const TERRAIN_END: f32 = 0.7;
+/// Постоянная времени подползания полосы во время генерации (секунды)
+const TERRAIN_EASE_SECS: f32 = 4.0;
+
+/// Ключи советов в файле языка
+const TIPS: [&str; 8] = [
+    "loading.tip.camera",
+    "loading.tip.sprint",
+    "loading.tip.pick",
+    "loading.tip.console",
+    "loading.tip.screenshot",
+    "loading.tip.light",
+    "loading.tip.time",
+    "loading.tip.save",
+];
+
+/// Этап загрузки
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LoadingStage {
+    /// Чтение сохранения или создание мира
+    Save,
+    /// Генерация мешей вокруг игрока
+    Terrain,
+    /// Загрузка мешей на GPU
+    Upload,
+}
+
+impl LoadingStage {
+    /// Ключ подписи в файле языка
+    fn label(self) -> &'static str {
+        match self {
+            LoadingStage::Save => "loading.save",
+            LoadingStage::Terrain => "loading.terrain",
+            LoadingStage::Upload => "loading.upload",
+        }
+    }
+}
+
+/// Результат фонового потока
+struct LoadedSpawn {
+    world: LoadedWorld,
+    terrain: HybridTerrainManager,
+    mesh: GeneratedMesh,
+}
+
+enum LoadMessage {
+    Stage(LoadingStage),
+    Done(Box<LoadedSpawn>),
+}
+
+/// Загрузка мира в фоне
+pub struct WorldLoading {
+    rx: Receiver<LoadMessage>,
+    stage: LoadingStage,
+    started: Instant,
+    stage_started: Instant,
+    spawn: Option<Box<LoadedSpawn>>,
+    uploaded: usize,
+    tip: &'static str,
+}
+
+impl WorldLoading {
+    /// Запустить загрузку (сразу, ещё до создания окна)
+    pub fn start(world_name: &str, new_world: WorldGenConfig) -> Self {
+        let (tx, rx) = channel();
+        let world_name = world_name.to_string();
+        std::thread::Builder::new()
+            .name("world-loading".to_string())
+            .spawn(move || {
+                let world = SaveSystem::load_or_create(&world_name, new_world);
+                if tx.send(LoadMessage::Stage(LoadingStage::Terrain)).is_err() {
+                    return;
+                }
+                // Параметры мира уже применены - генератор их видит
+                let mut terrain = HybridTerrainManager::new();
+                let mesh = terrain.generate_initial(world.start_x, world.start_z, &world.changes, 0);
+                let _ = tx.send(LoadMessage::Done(Box::new(LoadedSpawn { world, terrain, mesh })));
+            })
+            .expect("поток загрузки мира");
+
+        let seed = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.subsec_nanos() as usize);
+        Self {
+            rx,
+            stage: LoadingStage::Save,
+            started: Instant::now(),
+            stage_started: Instant::now(),
+            spawn: None,
+            uploaded: 0,
+            tip: TIPS[seed % TIPS.len()],
+        }
+    }
+
+    /// Принять сообщения потока и загрузить порцию чанков на GPU.
+    /// Возвращает мир, когда всё готово (менеджер terrain уже в рендерере)
+    pub fn update(&mut self, renderer: Option<&mut Renderer>) -> Option<LoadedWorld> {
+        loop {
+            match self.rx.try_recv() {
+                Ok(LoadMessage::Stage(stage)) => self.set_stage(stage),
+                Ok(LoadMessage::Done(spawn)) => {
+                    self.spawn = Some(spawn);
+                    self.set_stage(LoadingStage::Upload);
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    if self.spawn.is_none() {
+                        panic!("загрузка мира прервана (ошибка в фоновом потоке)");
+                    }
+                    break;
+                }
+            }
+        }
+
+        // Меши грузятся, когда есть куда: окно может появиться позже
+        let renderer = renderer?;
+        let spawn = self.spawn.as_ref()?;
+        let chunks = &spawn.mesh.new_chunks;
+        let end = (self.uploaded + UPLOADS_PER_FRAME).min(chunks.len());
+        for chunk in &chunks[self.uploaded..end] {
+            renderer.upload_chunk(chunk);
+        }
+        self.uploaded = end;
+        if self.uploaded < chunks.len() {
+            return None;
+        }
+
+        renderer.flush_uploads();
+        let spawn = self.spawn.take()?;
+        renderer.set_terrain_manager(spawn.terrain);
+        log::info!("Мир загружен: {} чанков за {:.1} с", spawn.mesh.new_chunks.len(), self.started.elapsed().as_secs_f32());
+        Some(spawn.world)
+    }
+
+    /// Этап, доля готовности (0-1) и совет для экрана загрузки (ключи
+    /// файла языка)
+    pub fn status(&self) -> (&'static str, f32, &'static str) {
+        let total = self.spawn.as_ref().map_or(0, |spawn| spawn.mesh.new_chunks.len());
+        let elapsed = self.stage_started.elapsed().as_secs_f32();
+        (self.stage.label(), overall_progress(self.stage, elapsed, self.uploaded, total), self.tip)
+    }
+
+    fn set_stage(&mut self, stage: LoadingStage) {
+        self.stage = stage;
+        self.stage_started = Instant::now();
+    }
+}
+
+/// Общий прогресс по этапу: загрузка на GPU - по чанкам, генерация -
+/// подползание по времени, не доходящее до конца этапа
+fn overall_progress(stage: LoadingStage, stage_secs: f32, uploaded: usize, total: usize) -> f32 {
+    match stage {
+        LoadingStage::Save => 0.0,
+        LoadingStage::Terrain => {
+            let eased = 1.0 - (-stage_secs / TERRAIN_EASE_SECS).exp();
+            SAVE_END + (TERRAIN_END - SAVE_END) * eased * 0.95
+        }
+        LoadingStage::Upload if total == 0 => 1.0,
+        LoadingStage::Upload => TERRAIN_END + (1.0 - TERRAIN_END) * uploaded as f32 / total as f32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_grows_through_stages() {
+        let save = overall_progress(LoadingStage::Save, 5.0, 0, 0);
+        let terrain_start = overall_progress(LoadingStage::Terrain, 0.0, 0, 0);
+        let terrain_late = overall_progress(LoadingStage::Terrain, 60.0, 0, 0);
+        let upload_half = overall_progress(LoadingStage::Upload, 0.0, 50, 100);
+
+        assert_eq!(save, 0.0);
+        assert!((terrain_start - SAVE_END).abs() < 1e-6);
+        // Генерация не показывает конец этапа, пока не закончилась
+        assert!(terrain_late < TERRAIN_END && terrain_late > terrain_start);
+        assert!(upload_half > terrain_late);
+        assert_eq!(overall_progress(LoadingStage::Upload, 0.0, 100, 100), 1.0);
+        assert_eq!(overall_progress(LoadingStage::Upload, 0.0, 0, 0), 1.0);
+    }
+}
@@ -4,8 +4,12 @@
 
 pub mod app;
 mod resources;
+mod loading;
 mod config;
+mod headless;
 
 pub use app::App;
 pub use resources::GameResources;
-pub use config::{SAVE_FILE, DEFAULT_SEED};
+pub use headless::{run_headless, HeadlessOptions};
+pub use config::{SAVE_FILE, LEGACY_WORLD_DIR, SAVES_DIR, SCHEMATICS_DIR, DEFAULT_WORLD, DEFAULT_SEED, THIRD_PERSON_DISTANCE, SHOULDER_OFFSET, DEFAULT_FOV, MIN_FOV, MAX_FOV};
+pub use config::{Settings, GraphicsSettings, LodPreset, lod_distance_to_slider, AudioSettings, ControlSettings, TimeSettings, MIN_DAY_LENGTH_MINUTES, MAX_DAY_LENGTH_MINUTES, DEFAULT_MOUSE_SENSITIVITY, MIN_MOUSE_SENSITIVITY, MAX_MOUSE_SENSITIVITY, SETTINGS_FILE, config_dir};
@@ -4,19 +4,29 @@
 
 use std::sync::Arc;
 use std::sync::RwLock;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use winit::window::Window;
 
-use crate::gpu::player::Camera;
+use crate::gpu::player::{Camera, AttractMode, PhotoMode};
 use crate::gpu::player::{Player, PlayerController};
-use crate::gpu::render::Renderer;
+use crate::gpu::render::{Renderer, PaletteTheme};
 use crate::gpu::blocks::BlockBreaker;
-use crate::gpu::terrain::WorldChanges;
+use crate::gpu::terrain::{WorldChanges, WorldEdit, PregenTask, BlockPos};
+use crate::gpu::block_entity::BlockEntities;
 use crate::gpu::gui::{GameMenu, GuiRenderer};
-use crate::gpu::subvoxel::{SubVoxelStorage, SubVoxelLevel};
-use crate::gpu::subvoxel::SubVoxelRenderer;
+use crate::gpu::subvoxel::{SculptStroke, SculptTool, SubVoxelWorld, SubVoxelLevel};
+use crate::gpu::subvoxel::OptimizedSubVoxelRenderer;
 use crate::gpu::audio::AudioSystem;
+use crate::gpu::input::{GamepadSystem, CursorState};
+use crate::gpu::weather::WeatherSystem;
+use crate::gpu::ticks::BlockTicks;
+use crate::gpu::particles::{ParticleSystem, ParticleEmitters};
+use crate::gpu::lighting::{LightOverlay, LightSources, OverlayMode, DayCounter};
 use crate::gpu::biomes::FoliageCache;
+use crate::gpu::save::{ExploredMap, SavedHotbar, Schematic};
+use crate::gpu::console::{Console, GameMode};
+
+use super::config::Settings;
 
 /// Все игровые ресурсы в одном месте
 pub struct GameResources {
@@ -24,7 +34,7 @@ pub struct GameResources {
     pub window: Option<Arc<Window>>,
     pub renderer: Option<Renderer>,
     pub gui_renderer: Option<GuiRenderer>,
-    pub subvoxel_renderer: Option<SubVoxelRenderer>,
+    pub subvoxel_renderer: Option<OptimizedSubVoxelRenderer>,
     
     // Player entity
     pub player: Player,
@@ -32,29 +42,93 @@ pub struct GameResources {
     
     // Camera
     pub camera: Camera,
+    /// Демо-облёт в простое главного меню
+    pub attract: AttractMode,
+    /// Фоторежим: свободная камера, мир на паузе
+    pub photo_mode: PhotoMode,
     
     // Block interaction
     pub block_breaker: BlockBreaker,
     
     // World data
     pub world_changes: Arc<RwLock<WorldChanges>>,
-    pub subvoxel_storage: Arc<RwLock<SubVoxelStorage>>,
+    pub subvoxel_storage: Arc<RwLock<SubVoxelWorld>>,
+    /// Данные блоков (сундуки) по позиции
+    pub block_entities: BlockEntities,
+    /// Источники света вне сетки блоков (факелы)
+    pub light_sources: LightSources,
+    /// Углы выделения палочкой (ЛКМ - первый, ПКМ - второй)
+    pub selection: [Option<[i32; 3]>; 2],
+    /// Буфер /copy и /schem load
+    pub clipboard: Option<Schematic>,
+    /// Очередь /fill, /replace, /hollow, /walls и история /undo
+    pub world_edit: WorldEdit,
+    /// Разведанные чанки и метки карты мира (map.dat)
+    pub explored_map: ExploredMap,
     pub current_subvoxel_level: SubVoxelLevel,
+    /// Кисть лепки субвокселей (B) и начатый ПКМ мазок
+    pub sculpt_tool: SculptTool,
+    pub sculpt_stroke: Option<SculptStroke>,
     pub world_seed: u64,
+    /// Папка текущего мира в saves/
+    pub world_dir: String,
+    pub game_mode: GameMode,
+    /// Точка появления мира (/setspawn, компас)
+    pub world_spawn: [i32; 3],
+    /// Блок возрождения игрока (кровать), None - спавн мира
+    pub respawn_point: Option<[i32; 3]>,
+    /// Радиус защиты спавна (0 - нет)
+    pub spawn_protection: i32,
+    /// Оператор: может строить в защите спавна. В одиночной игре
+    /// игрок - владелец мира и всегда оператор
+    pub is_op: bool,
+    /// Игровой календарь (день сменяется на рассвете)
+    pub day_counter: DayCounter,
+    /// Время в мире до этой сессии (текущая считается от start_time)
+    pub playtime_base: Duration,
+    pub palette_theme: PaletteTheme,
     pub foliage_cache: FoliageCache,
+    /// Фоновая прегенерация (/pregen, F10)
+    pub pregen: Option<PregenTask>,
+    pub weather: WeatherSystem,
+    /// Случайные и запланированные тики блоков (трава)
+    pub block_ticks: BlockTicks,
+    
+    // Particles
+    pub particles: ParticleSystem,
+    pub particle_emitters: ParticleEmitters,
+    
+    // Overlay (F9)
+    pub overlay_mode: OverlayMode,
+    pub light_overlay: LightOverlay,
     
     // GUI
     pub menu: GameMenu,
+    /// Хотбар из сохранения, ждёт создания GUI рендерера
+    pub pending_hotbar: Option<SavedHotbar>,
+    /// Блок, чей контейнер открыт на экране (сундук)
+    pub open_container: Option<BlockPos>,
+    /// Консоль команд / чат (T или '/')
+    pub console: Console,
     
     // Audio
     pub audio_system: Option<AudioSystem>,
     
+    /// Настройки игрока (settings.json в папке конфигурации)
+    pub settings: Settings,
+    
+    // Gamepad (вибрация)
+    pub gamepad: GamepadSystem,
+    
     // Timing
     pub start_time: Instant,
     pub last_frame: Instant,
     
     // Input state
-    pub cursor_grabbed: bool,
+    /// Захват курсора (игра / интерфейс / окно без фокуса)
+    pub cursor: CursorState,
     pub mouse_pos: (f32, f32),
     pub menu_mouse_pressed: bool,
+    /// Зажат Alt: ПКМ продлевает блок под прицелом (палочка строителя)
+    pub builder_wand_held: bool,
 }
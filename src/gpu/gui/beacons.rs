@@ -0,0 +1,219 @@
+// ============================================
+// Beacons - Маяки меток на экране
+// ============================================
+// Метки карты мира и место последней смерти проецируются матрицей
+// камеры на экран: ромб цвета метки с тёмной обводкой и подпись с
+// расстоянием до игрока. Метки позади камеры и рядом с игроком не
+// показываются. Проекция - на всё окно, затем в пиксели интерфейса.
+
+use ultraviolet::{Mat4, Vec3, Vec4};
+use wgpu::util::DeviceExt;
+
+use crate::gpu::locale::{tr, tr_args};
+use crate::gpu::save::{Waypoint, MAX_WAYPOINTS};
+
+use super::{TextParams, TextAlign, UiScale, UiVertex};
+
+const MARKER_SIZE: f32 = 7.0;
+const OUTLINE_WIDTH: f32 = 1.5;
+const FONT_SIZE: f32 = 15.0;
+/// Ближе маяк не показывается (блоки)
+const ARRIVED_DISTANCE: f32 = 3.0;
+/// Маяк над блоком метки (блоки)
+const MARKER_HEIGHT: f32 = 1.5;
+const OUTLINE: [f32; 4] = [0.0, 0.0, 0.0, 0.8];
+const DEATH_COLOR: [f32; 4] = [0.9, 0.15, 0.15, 1.0];
+/// Метки и место смерти
+const MAX_BEACONS: usize = MAX_WAYPOINTS + 1;
+/// Обводка + ромб
+const MAX_VERTICES: usize = MAX_BEACONS * 12;
+
+/// Маяк на экране
+struct Beacon {
+    x: f32,
+    y: f32,
+    label: String,
+    color: [f32; 4],
+}
+
+/// Точка мира -> пиксели экрана (None - позади камеры или за краем)
+pub fn project(view_proj: Mat4, point: Vec3, width: f32, height: f32) -> Option<(f32, f32)> {
+    let clip = view_proj * Vec4::new(point.x, point.y, point.z, 1.0);
+    if clip.w <= 0.0 {
+        return None;
+    }
+    let (x, y) = (clip.x / clip.w, clip.y / clip.w);
+    if x.abs() > 1.0 || y.abs() > 1.0 {
+        return None;
+    }
+    Some(((x + 1.0) * 0.5 * width, (1.0 - y) * 0.5 * height))
+}
+
+/// Маяки меток на HUD
+pub struct Beacons {
+    pipeline: wgpu::RenderPipeline,
+    vertex_buffer: wgpu::Buffer,
+    vertex_count: u32,
+    beacons: Vec<Beacon>,
+    ui_scale: UiScale,
+    /// Размер экрана в пикселях интерфейса
+    screen_width: f32,
+    screen_height: f32,
+}
+
+impl Beacons {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat, width: u32, height: u32) -> Self {
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Beacons Vertex Buffer"),
+            contents: bytemuck::cast_slice(&[UiVertex { position: [0.0; 2], color: [0.0; 4] }; MAX_VERTICES]),
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Beacons Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/ui.wgsl").into()),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Beacons Pipeline Layout"),
+            bind_group_layouts: &[],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Beacons Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[UiVertex::desc()],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            pipeline,
+            vertex_buffer,
+            vertex_count: 0,
+            beacons: Vec::new(),
+            ui_scale: UiScale::default(),
+            screen_width: width as f32,
+            screen_height: height as f32,
+        }
+    }
+
+    pub fn resize(&mut self, ui_scale: UiScale, width: u32, height: u32) {
+        self.ui_scale = ui_scale;
+        self.screen_width = width as f32;
+        self.screen_height = height as f32;
+    }
+
+    /// Пересчитать маяки для камеры; расстояние - от игрока
+    pub fn update(&mut self, view_proj: Mat4, player: Vec3, waypoints: &[Waypoint], death: Option<[i32; 3]>) {
+        self.beacons.clear();
+        let (window_width, window_height) = self.ui_scale.window_size();
+        let targets = waypoints
+            .iter()
+            .map(|waypoint| (waypoint.name.clone(), waypoint.pos, waypoint.color.map(|c| c as f32 / 255.0)))
+            .map(|(name, pos, [r, g, b])| (name, pos, [r, g, b, 1.0]))
+            .chain(death.map(|pos| (tr("world_map.death"), pos, DEATH_COLOR)));
+        for (name, [x, y, z], color) in targets {
+            let point = Vec3::new(x as f32 + 0.5, y as f32 + MARKER_HEIGHT, z as f32 + 0.5);
+            let distance = (point - player).mag();
+            if distance < ARRIVED_DISTANCE {
+                continue;
+            }
+            if let Some((x, y)) = project(view_proj, point, window_width, window_height) {
+                let (x, y) = self.ui_scale.to_ui(x, y);
+                let meters = tr_args("unit.meters", &[("distance", &(distance.round() as i32))]);
+                let label = format!("{}  {}", name, meters);
+                self.beacons.push(Beacon { x, y, label, color });
+            }
+        }
+    }
+
+    /// Скрыть все маяки (фоторежим)
+    pub fn clear(&mut self) {
+        self.beacons.clear();
+    }
+
+    /// Ромбы маяков; подписи
+    pub fn prepare(&mut self, queue: &wgpu::Queue) -> Vec<TextParams> {
+        let to_ndc = |px: f32, py: f32| [px / self.screen_width * 2.0 - 1.0, 1.0 - py / self.screen_height * 2.0];
+        let diamond = |x: f32, y: f32, size: f32, color: [f32; 4]| {
+            [(x, y - size), (x + size, y), (x, y + size), (x, y - size), (x, y + size), (x - size, y)]
+                .map(|(px, py)| UiVertex { position: to_ndc(px, py), color })
+        };
+
+        let mut vertices: Vec<UiVertex> = Vec::with_capacity(self.beacons.len() * 12);
+        for beacon in self.beacons.iter().take(MAX_BEACONS) {
+            vertices.extend(diamond(beacon.x, beacon.y, MARKER_SIZE + OUTLINE_WIDTH, OUTLINE));
+            vertices.extend(diamond(beacon.x, beacon.y, MARKER_SIZE, beacon.color));
+        }
+        if !vertices.is_empty() {
+            queue.write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice(&vertices));
+        }
+        self.vertex_count = vertices.len() as u32;
+
+        self.beacons
+            .iter()
+            .take(MAX_BEACONS)
+            .map(|beacon| TextParams {
+                x: beacon.x,
+                y: beacon.y - MARKER_SIZE - FONT_SIZE - 4.0,
+                text: beacon.label.clone(),
+                size: FONT_SIZE,
+                color: beacon.color,
+                align: TextAlign::Center,
+                max_width: None,
+            })
+            .collect()
+    }
+
+    pub fn render<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+        if self.vertex_count == 0 {
+            return;
+        }
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.draw(0..self.vertex_count, 0..1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_project_in_front_of_the_camera_only() {
+        // Камера в начале координат смотрит на -Z
+        let projection = ultraviolet::projection::perspective_wgpu_dx(1.2, 2.0, 500.0, 0.1);
+        let center = project(projection, Vec3::new(0.0, 0.0, -10.0), 800.0, 400.0).unwrap();
+        assert!((center.0 - 400.0).abs() < 1e-3 && (center.1 - 200.0).abs() < 1e-3);
+
+        // Выше и правее центра - вверх и вправо на экране
+        let (x, y) = project(projection, Vec3::new(1.0, 1.0, -10.0), 800.0, 400.0).unwrap();
+        assert!(x > 400.0 && y < 200.0);
+
+        assert_eq!(project(projection, Vec3::new(0.0, 0.0, 10.0), 800.0, 400.0), None);
+        assert_eq!(project(projection, Vec3::new(100.0, 0.0, -1.0), 800.0, 400.0), None);
+    }
+}
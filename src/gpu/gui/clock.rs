@@ -0,0 +1,81 @@
+// ============================================
+// Clock - Часы по положению солнца
+// ============================================
+// Строка в правом верхнем углу: время суток и дорожка от восхода до
+// заката, по которой идёт солнце (O) днём и луна (C) ночью.
+// "||" - время остановлено.
+
+use crate::gpu::lighting::{SUNRISE, SUNSET};
+
+use super::text::{TextParams, TextAlign};
+
+/// Отступ от края экрана
+const PADDING: f32 = 10.0;
+const FONT_SIZE: f32 = 18.0;
+/// Клеток на дорожке солнца
+const TRACK_CELLS: usize = 11;
+
+/// Часы на HUD
+pub struct Clock {
+    text: String,
+}
+
+impl Clock {
+    pub fn new() -> Self {
+        Self { text: String::new() }
+    }
+
+    /// Пересчитать строку по времени суток (0 - полночь, 0.5 - полдень)
+    pub fn update(&mut self, time: f32, paused: bool) {
+        self.text = clock_text(time, paused);
+    }
+
+    pub fn text_params(&self, screen_width: f32) -> TextParams {
+        TextParams {
+            x: screen_width - PADDING,
+            y: PADDING,
+            text: self.text.clone(),
+            size: FONT_SIZE,
+            color: [1.0, 1.0, 1.0, 0.9],
+            align: TextAlign::Right,
+            max_width: None,
+        }
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Текст часов: "14:24 [.....O.....]"
+pub fn clock_text(time: f32, paused: bool) -> String {
+    let time = time.rem_euclid(1.0);
+    let minutes = (time * 24.0 * 60.0) as u32 % (24 * 60);
+
+    // Днём дорожка - от восхода до заката, ночью - от заката до восхода
+    let (body, progress) = if (SUNRISE..SUNSET).contains(&time) {
+        ('O', (time - SUNRISE) / (SUNSET - SUNRISE))
+    } else {
+        ('C', (time - SUNSET).rem_euclid(1.0) / (1.0 - (SUNSET - SUNRISE)))
+    };
+    let cell = ((progress * TRACK_CELLS as f32) as usize).min(TRACK_CELLS - 1);
+    let track: String = (0..TRACK_CELLS).map(|i| if i == cell { body } else { '.' }).collect();
+
+    let pause = if paused { " ||" } else { "" };
+    format!("{:02}:{:02} [{}]{}", minutes / 60, minutes % 60, track, pause)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sun_and_moon_positions() {
+        assert_eq!(clock_text(0.5, false), "12:00 [.....O.....]");
+        assert_eq!(clock_text(SUNRISE, false), "06:00 [O..........]");
+        assert_eq!(clock_text(0.0, true), "00:00 [.....C.....] ||");
+        assert_eq!(clock_text(0.2499, false), "05:59 [..........C]");
+    }
+}
@@ -0,0 +1,102 @@
+// ============================================
+// Compass - Направление на спавн мира
+// ============================================
+// Строка сверху по центру: сторона света взгляда, стрелка на спавн
+// относительно взгляда и расстояние. Север - -Z, восток - +X.
+
+use ultraviolet::Vec3;
+
+use crate::gpu::locale::{tr, tr_args};
+
+use super::text::{TextParams, TextAlign};
+
+/// Отступ от верха экрана
+const TOP_PADDING: f32 = 10.0;
+const FONT_SIZE: f32 = 18.0;
+/// Ближе спавн считается достигнутым (блоки)
+const ARRIVED_DISTANCE: f32 = 2.0;
+
+/// Стороны света по 45°, начиная с севера по часовой
+const HEADINGS: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+/// Стрелки по 45°, начиная с "вперёд" по часовой
+const ARROWS: [&str; 8] = ["^", "^>", ">", "v>", "v", "<v", "<", "<^"];
+
+/// Компас на HUD
+pub struct Compass {
+    text: String,
+}
+
+impl Compass {
+    pub fn new() -> Self {
+        Self { text: String::new() }
+    }
+
+    /// Пересчитать строку для позиции и взгляда игрока (yaw как в Player)
+    pub fn update(&mut self, position: Vec3, yaw: f32, spawn: [i32; 3]) {
+        self.text = compass_text(position, yaw, spawn);
+    }
+
+    pub fn text_params(&self, screen_width: f32) -> TextParams {
+        TextParams {
+            x: screen_width / 2.0,
+            y: TOP_PADDING,
+            text: self.text.clone(),
+            size: FONT_SIZE,
+            color: [1.0, 1.0, 1.0, 0.9],
+            align: TextAlign::Center,
+            max_width: None,
+        }
+    }
+}
+
+impl Default for Compass {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Сектор 45° для угла в радианах (0 - начало первого сектора)
+fn octant(angle: f32) -> usize {
+    let eighth = std::f32::consts::FRAC_PI_4;
+    ((angle / eighth).round() as i32).rem_euclid(8) as usize
+}
+
+/// Текст компаса: "NE  |  Spawn <^ 124 m"
+pub fn compass_text(position: Vec3, yaw: f32, spawn: [i32; 3]) -> String {
+    let (forward_x, forward_z) = (yaw.cos(), yaw.sin());
+    // Азимут взгляда: 0 - север (-Z), по часовой через восток (+X)
+    let heading = HEADINGS[octant(forward_x.atan2(-forward_z))];
+
+    let to_x = spawn[0] as f32 + 0.5 - position.x;
+    let to_z = spawn[2] as f32 + 0.5 - position.z;
+    let distance = (to_x * to_x + to_z * to_z).sqrt();
+    if distance < ARRIVED_DISTANCE {
+        return format!("{}  |  {}", heading, tr("compass.spawn"));
+    }
+
+    // Угол на спавн относительно взгляда, вправо - положительный
+    let ahead = to_x * forward_x + to_z * forward_z;
+    let right = to_z * forward_x - to_x * forward_z;
+    let arrow = ARROWS[octant(right.atan2(ahead))];
+    let meters = tr_args("unit.meters", &[("distance", &(distance.round() as i32))]);
+    format!("{}  |  {} {} {}", heading, tr("compass.spawn"), arrow, meters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    #[test]
+    fn heading_and_arrow() {
+        // yaw = -90° - взгляд на -Z (север); спавн восточнее - справа
+        let text = compass_text(Vec3::new(0.5, 64.0, 0.5), -FRAC_PI_2, [20, 64, 0]);
+        assert_eq!(text, "N  |  Spawn > 20 m");
+
+        // Взгляд на восток (+X), спавн позади
+        let text = compass_text(Vec3::new(0.5, 64.0, 0.5), 0.0, [-10, 64, 0]);
+        assert_eq!(text, "E  |  Spawn v 10 m");
+
+        assert_eq!(compass_text(Vec3::new(3.0, 70.0, 3.0), 0.0, [2, 64, 2]), "E  |  Spawn");
+    }
+}
@@ -0,0 +1,183 @@
+// ============================================
+// Console Overlay - Окно консоли/чата
+// ============================================
+// Полупрозрачные подложки под строки лога и строку ввода над
+// хотбаром. Текст рисует общий TextRenderer (один вызов за кадр).
+
+use std::time::Instant;
+
+use wgpu::util::DeviceExt;
+
+use crate::gpu::console::{Console, LineKind};
+
+use super::hotbar::{BOTTOM_PADDING, SLOT_SIZE};
+use super::{TextParams, TextAlign, UiVertex};
+
+/// Отступ от левого края экрана
+const MARGIN_X: f32 = 10.0;
+/// Высота строки
+const LINE_HEIGHT: f32 = 22.0;
+/// Размер шрифта
+const FONT_SIZE: f32 = 16.0;
+/// Максимальная ширина окна
+const MAX_WIDTH: f32 = 640.0;
+/// Строк лога при открытой и закрытой консоли
+const OPEN_LINES: usize = 12;
+const CLOSED_LINES: usize = 6;
+/// Подложек максимум (лог + ввод)
+const MAX_RECTS: usize = CLOSED_LINES + 2;
+
+const INPUT_BACKGROUND: [f32; 4] = [0.0, 0.0, 0.0, 0.6];
+const LINE_BACKGROUND: [f32; 4] = [0.0, 0.0, 0.0, 0.4];
+
+/// Рендерер окна консоли
+pub struct ConsoleOverlay {
+    pipeline: wgpu::RenderPipeline,
+    vertex_buffer: wgpu::Buffer,
+    vertex_count: u32,
+    screen_width: f32,
+    screen_height: f32,
+}
+
+impl ConsoleOverlay {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat, width: u32, height: u32) -> Self {
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Console Vertex Buffer"),
+            contents: bytemuck::cast_slice(&[UiVertex { position: [0.0; 2], color: [0.0; 4] }; MAX_RECTS * 6]),
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Console Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/ui.wgsl").into()),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Console Pipeline Layout"),
+            bind_group_layouts: &[],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Console Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[UiVertex::desc()],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            pipeline,
+            vertex_buffer,
+            vertex_count: 0,
+            screen_width: width as f32,
+            screen_height: height as f32,
+        }
+    }
+
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.screen_width = width as f32;
+        self.screen_height = height as f32;
+    }
+
+    /// Прямоугольник в пикселях -> 2 треугольника в NDC
+    fn push_rect(&self, vertices: &mut Vec<UiVertex>, x: f32, y: f32, w: f32, h: f32, color: [f32; 4]) {
+        let to_ndc = |px: f32, py: f32| [px / self.screen_width * 2.0 - 1.0, 1.0 - py / self.screen_height * 2.0];
+        let (x0, y0, x1, y1) = (x, y, x + w, y + h);
+        for (px, py) in [(x0, y0), (x1, y0), (x1, y1), (x0, y0), (x1, y1), (x0, y1)] {
+            vertices.push(UiVertex { position: to_ndc(px, py), color });
+        }
+    }
+
+    /// Подложки и текст консоли. Пусто - рисовать нечего
+    pub fn prepare(&mut self, queue: &wgpu::Queue, console: &Console) -> Vec<TextParams> {
+        let now = Instant::now();
+        let open = console.is_open();
+        let lines = console.visible_lines(if open { OPEN_LINES } else { CLOSED_LINES }, now);
+
+        let width = MAX_WIDTH.min(self.screen_width - MARGIN_X * 2.0);
+        // Строка ввода над хотбаром, лог над ней
+        let input_y = self.screen_height - BOTTOM_PADDING - SLOT_SIZE - 20.0 - LINE_HEIGHT;
+        let log_bottom = input_y - 4.0;
+
+        let mut vertices = Vec::with_capacity(MAX_RECTS * 6);
+        let mut texts = Vec::with_capacity(lines.len() + 1);
+
+        if open {
+            let log_height = lines.len() as f32 * LINE_HEIGHT;
+            if !lines.is_empty() {
+                self.push_rect(&mut vertices, MARGIN_X, log_bottom - log_height, width, log_height, LINE_BACKGROUND);
+            }
+            self.push_rect(&mut vertices, MARGIN_X, input_y, width, LINE_HEIGHT, INPUT_BACKGROUND);
+            texts.push(TextParams {
+                x: MARGIN_X + 6.0,
+                y: input_y + 3.0,
+                text: format!("> {}_", console.input()),
+                size: FONT_SIZE,
+                color: [1.0, 1.0, 1.0, 1.0],
+                align: TextAlign::Left,
+                max_width: Some(width - 12.0),
+            });
+        }
+
+        for (i, line) in lines.iter().enumerate() {
+            let y = log_bottom - (lines.len() - i) as f32 * LINE_HEIGHT;
+            let alpha = if open { 1.0 } else { line.fade(now) };
+            if !open {
+                let mut background = LINE_BACKGROUND;
+                background[3] *= alpha;
+                self.push_rect(&mut vertices, MARGIN_X, y, width, LINE_HEIGHT, background);
+            }
+            let color = match line.kind {
+                LineKind::Input => [0.7, 0.7, 0.7],
+                LineKind::Info => [1.0, 1.0, 1.0],
+                LineKind::Error => [1.0, 0.4, 0.4],
+            };
+            texts.push(TextParams {
+                x: MARGIN_X + 6.0,
+                y: y + 3.0,
+                text: line.text.clone(),
+                size: FONT_SIZE,
+                color: [color[0], color[1], color[2], alpha],
+                align: TextAlign::Left,
+                max_width: Some(width - 12.0),
+            });
+        }
+
+        self.vertex_count = vertices.len() as u32;
+        if !vertices.is_empty() {
+            queue.write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice(&vertices));
+        }
+        texts
+    }
+
+    pub fn render<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+        if self.vertex_count == 0 {
+            return;
+        }
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.draw(0..self.vertex_count, 0..1);
+    }
+}
@@ -0,0 +1,181 @@
+// ============================================
+// Container Screen - Экран хранилища блока (сундук)
+// ============================================
+// Сетка слотов над хотбаром. ЛКМ по слоту контейнера переносит стопку
+// в хотбар, ПКМ - половину; по слоту хотбара - обратно. Экран показывает копию
+// слотов; настоящие данные живут в сущности блока.
+
+use crate::gpu::items::ItemStack;
+
+use super::hotbar::{SLOT_SIZE, SLOT_GAP, BOTTOM_PADDING};
+
+/// Колонок в сетке (как слотов в хотбаре)
+pub const CONTAINER_COLS: usize = 9;
+
+/// Отступ сетки от краёв панели
+const PANEL_PADDING: f32 = 16.0;
+
+/// Высота заголовка панели
+const TITLE_HEIGHT: f32 = 40.0;
+
+/// Зазор между панелью и фоном хотбара
+const HOTBAR_GAP: f32 = 36.0;
+
+/// Насколько панель опущена, пока крышка закрыта
+const SLIDE_DISTANCE: f32 = 24.0;
+
+/// Состояние экрана контейнера
+pub struct ContainerScreen {
+    /// Ключ заголовка в файле языка ("container.chest")
+    title: &'static str,
+    slots: Vec<Option<ItemStack>>,
+    visible: bool,
+    hovered: Option<usize>,
+    /// Открытость крышки 0..1 - панель выезжает вместе с ней
+    lid: f32,
+}
+
+impl Default for ContainerScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ContainerScreen {
+    pub fn new() -> Self {
+        Self {
+            title: "",
+            slots: Vec::new(),
+            visible: false,
+            hovered: None,
+            lid: 0.0,
+        }
+    }
+
+    pub fn open(&mut self, title: &'static str, slots: &[Option<ItemStack>]) {
+        self.title = title;
+        self.set_slots(slots);
+        self.visible = true;
+        self.hovered = None;
+        self.lid = 0.0;
+    }
+
+    pub fn close(&mut self) {
+        self.visible = false;
+        self.hovered = None;
+        self.slots.clear();
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn title(&self) -> &'static str {
+        self.title
+    }
+
+    /// Обновить копию слотов после изменения контейнера
+    pub fn set_slots(&mut self, slots: &[Option<ItemStack>]) {
+        self.slots = slots.to_vec();
+    }
+
+    pub fn slots(&self) -> &[Option<ItemStack>] {
+        &self.slots
+    }
+
+    pub fn set_lid(&mut self, lid: f32) {
+        self.lid = lid.clamp(0.0, 1.0);
+    }
+
+    pub fn hovered(&self) -> Option<usize> {
+        self.hovered
+    }
+
+    pub fn set_hovered(&mut self, slot: Option<usize>) {
+        self.hovered = slot;
+    }
+
+    fn rows(&self) -> usize {
+        self.slots.len().div_ceil(CONTAINER_COLS).max(1)
+    }
+
+    /// Панель: (x, y, ширина, высота) в пикселях
+    pub fn panel_rect(&self, screen_width: f32, screen_height: f32) -> (f32, f32, f32, f32) {
+        let rows = self.rows() as f32;
+        let grid_width = CONTAINER_COLS as f32 * SLOT_SIZE + (CONTAINER_COLS - 1) as f32 * SLOT_GAP;
+        let grid_height = rows * SLOT_SIZE + (rows - 1.0) * SLOT_GAP;
+        let width = grid_width + PANEL_PADDING * 2.0;
+        let height = TITLE_HEIGHT + grid_height + PANEL_PADDING;
+
+        // Верх фона хотбара (фон на 10px больше слотов)
+        let hotbar_top = screen_height - BOTTOM_PADDING - SLOT_SIZE - 10.0;
+        let x = (screen_width - width) / 2.0;
+        let y = hotbar_top - HOTBAR_GAP - height + (1.0 - self.lid) * SLIDE_DISTANCE;
+        (x, y, width, height)
+    }
+
+    /// Левый верхний угол слота
+    pub fn slot_pos(&self, index: usize, screen_width: f32, screen_height: f32) -> (f32, f32) {
+        let (x, y, _, _) = self.panel_rect(screen_width, screen_height);
+        let col = (index % CONTAINER_COLS) as f32;
+        let row = (index / CONTAINER_COLS) as f32;
+        (
+            x + PANEL_PADDING + col * (SLOT_SIZE + SLOT_GAP),
+            y + TITLE_HEIGHT + row * (SLOT_SIZE + SLOT_GAP),
+        )
+    }
+
+    /// Слот под курсором
+    pub fn slot_at(&self, mx: f32, my: f32, screen_width: f32, screen_height: f32) -> Option<usize> {
+        if !self.visible {
+            return None;
+        }
+        (0..self.slots.len()).find(|&i| {
+            let (x, y) = self.slot_pos(i, screen_width, screen_height);
+            mx >= x && mx <= x + SLOT_SIZE && my >= y && my <= y + SLOT_SIZE
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_screen(slots: usize) -> ContainerScreen {
+        let mut screen = ContainerScreen::new();
+        screen.open("container.chest", &vec![None; slots]);
+        screen.set_lid(1.0);
+        screen
+    }
+
+    #[test]
+    fn slot_at_finds_slot_centres() {
+        let screen = open_screen(27);
+        for i in [0, 8, 9, 26] {
+            let (x, y) = screen.slot_pos(i, 1280.0, 720.0);
+            let centre = (x + SLOT_SIZE / 2.0, y + SLOT_SIZE / 2.0);
+            assert_eq!(screen.slot_at(centre.0, centre.1, 1280.0, 720.0), Some(i));
+        }
+        // Зазор между слотами и пространство вне панели
+        let (x, y) = screen.slot_pos(0, 1280.0, 720.0);
+        assert_eq!(screen.slot_at(x + SLOT_SIZE + SLOT_GAP / 2.0, y + 1.0, 1280.0, 720.0), None);
+        assert_eq!(screen.slot_at(5.0, 5.0, 1280.0, 720.0), None);
+    }
+
+    #[test]
+    fn panel_sits_above_hotbar() {
+        let screen = open_screen(27);
+        let (_, y, _, height) = screen.panel_rect(1280.0, 720.0);
+        let hotbar_top = 720.0 - BOTTOM_PADDING - SLOT_SIZE - 10.0;
+        assert!(y + height <= hotbar_top);
+        assert!(y >= 0.0);
+    }
+
+    #[test]
+    fn closed_screen_ignores_clicks() {
+        let mut screen = open_screen(9);
+        let (x, y) = screen.slot_pos(0, 1280.0, 720.0);
+        screen.close();
+        assert_eq!(screen.slot_at(x + 1.0, y + 1.0, 1280.0, 720.0), None);
+    }
+}
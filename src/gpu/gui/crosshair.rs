@@ -174,13 +174,20 @@ pub struct BlockHighlight {
 struct HighlightUniforms {
     view_proj: [[f32; 4]; 4],
     block_pos: [f32; 3],
-    block_size: f32,
+    _pad0: f32,
+    block_size: [f32; 3],
+    _pad1: f32,
 }
 
 impl BlockHighlight {
-    pub fn new(device: &wgpu::Device, surface_format: wgpu::TextureFormat) -> Self {
+    pub fn new(device: &wgpu::Device, surface_format: wgpu::TextureFormat, sample_count: u32) -> Self {
+        // Чёрный полупрозрачный
+        Self::with_color(device, surface_format, sample_count, [0.0, 0.0, 0.0, 0.6])
+    }
+    
+    /// Рамка заданного цвета
+    pub fn with_color(device: &wgpu::Device, surface_format: wgpu::TextureFormat, sample_count: u32, color: [f32; 4]) -> Self {
         // Вершины единичного куба (будет масштабироваться в шейдере)
-        let color = [0.0, 0.0, 0.0, 0.6]; // Чёрный полупрозрачный
         
         let vertices = vec![
             // 8 вершин куба (0 to 1)
@@ -220,7 +227,9 @@ impl BlockHighlight {
         let uniforms = HighlightUniforms {
             view_proj: ultraviolet::Mat4::identity().into(),
             block_pos: [0.0, 0.0, 0.0],
-            block_size: 1.0,
+            _pad0: 0.0,
+            block_size: [1.0; 3],
+            _pad1: 0.0,
         };
         
         let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -294,7 +303,10 @@ impl BlockHighlight {
                 stencil: wgpu::StencilState::default(),
                 bias: wgpu::DepthBiasState::default(),
             }),
-            multisample: wgpu::MultisampleState::default(),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                ..Default::default()
+            },
             multiview: None,
             cache: None,
         });
@@ -316,10 +328,17 @@ impl BlockHighlight {
     
     /// Обновить позицию и размер выделяемого блока (для суб-вокселей)
     pub fn update_with_size(&self, queue: &wgpu::Queue, view_proj: [[f32; 4]; 4], block_pos: [f32; 3], size: f32) {
+        self.update_box(queue, view_proj, block_pos, [size; 3]);
+    }
+    
+    /// Рамка параллелепипеда от угла block_pos с размерами size
+    pub fn update_box(&self, queue: &wgpu::Queue, view_proj: [[f32; 4]; 4], block_pos: [f32; 3], size: [f32; 3]) {
         let uniforms = HighlightUniforms {
             view_proj,
             block_pos,
+            _pad0: 0.0,
             block_size: size,
+            _pad1: 0.0,
         };
         queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
     }
@@ -332,3 +351,35 @@ impl BlockHighlight {
         render_pass.draw_indexed(0..self.index_count, 0, 0..1);
     }
 }
+
+/// Рамка выделения палочкой (углы включительно)
+pub struct SelectionBox {
+    outline: BlockHighlight,
+    visible: bool,
+}
+
+impl SelectionBox {
+    /// Зазор рамки от граней блоков (без мерцания с ними)
+    const INFLATE: f32 = 0.01;
+    
+    pub fn new(device: &wgpu::Device, surface_format: wgpu::TextureFormat, sample_count: u32) -> Self {
+        // Жёлтая, заметнее чёрной рамки блока под прицелом
+        let outline = BlockHighlight::with_color(device, surface_format, sample_count, [1.0, 0.8, 0.15, 0.9]);
+        Self { outline, visible: false }
+    }
+    
+    /// Обновить рамку (None - выделения нет)
+    pub fn update(&mut self, queue: &wgpu::Queue, view_proj: [[f32; 4]; 4], bounds: Option<([i32; 3], [i32; 3])>) {
+        self.visible = bounds.is_some();
+        let Some((min, max)) = bounds else { return };
+        let corner = min.map(|v| v as f32 - Self::INFLATE);
+        let size = [0, 1, 2].map(|i| (max[i] - min[i] + 1) as f32 + 2.0 * Self::INFLATE);
+        self.outline.update_box(queue, view_proj, corner, size);
+    }
+    
+    pub fn render<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+        if self.visible {
+            self.outline.render(render_pass);
+        }
+    }
+}
@@ -118,8 +118,8 @@ impl FpsCounter {
         }
     }
     
-    /// Вызывать каждый кадр для обновления счётчика
-    pub fn update(&mut self) {
+    /// Вызывать каждый кадр; раз в секунду возвращает новый замер FPS
+    pub fn update(&mut self) -> Option<u32> {
         self.frame_count += 1;
         
         let now = std::time::Instant::now();
@@ -133,7 +133,9 @@ impl FpsCounter {
             
             // Перестраиваем геометрию
             self.rebuild_geometry();
+            return Some(self.current_fps);
         }
+        None
     }
     
     fn rebuild_geometry(&mut self) {
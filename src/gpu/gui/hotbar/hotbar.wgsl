@@ -7,6 +7,7 @@ struct HotbarUniforms {
     screen_size: vec2<f32>,
     time: f32,
     selected_slot: f32,
+    accent: vec4<f32>,  // Акцентный цвет (из пакета ресурсов, по умолчанию cyan)
 }
 
 @group(0) @binding(0) var<uniform> uniforms: HotbarUniforms;
@@ -38,7 +39,6 @@ struct VertexOutput {
 }
 
 // Цветовая палитра Hi-Tech
-const ACCENT: vec3<f32> = vec3<f32>(0.0, 0.953, 1.0);           // #00f3ff - cyan
 const BG_DARK: vec4<f32> = vec4<f32>(0.0, 0.039, 0.078, 0.4);   // rgba(0, 10, 20, 0.4)
 const SLOT_BG: vec4<f32> = vec4<f32>(0.0, 0.078, 0.118, 0.6);   // rgba(0, 20, 30, 0.6)
 const BORDER_COLOR: vec3<f32> = vec3<f32>(0.0, 1.0, 1.0);       // Cyan border
@@ -151,7 +151,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         if (px.y < 2.0 && dist_from_center < stripe_width * 0.5) {
             let stripe_alpha = 1.0 - dist_from_center / (stripe_width * 0.5);
             let stripe_y_alpha = 1.0 - px.y / 2.0;
-            color = mix(color, vec4<f32>(ACCENT, 0.8), stripe_alpha * stripe_y_alpha * 0.7);
+            color = mix(color, vec4<f32>(uniforms.accent.rgb, 0.8), stripe_alpha * stripe_y_alpha * 0.7);
         }
         
         return color;
@@ -171,25 +171,25 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     // ========== SELECTED SLOT ==========
     if (in.is_selected == 1u) {
         // Яркий фон для выбранного слота
-        color = vec4<f32>(ACCENT * 0.15, 0.15);
+        color = vec4<f32>(uniforms.accent.rgb * 0.15, 0.15);
         
         // Яркая рамка
         if (d > -2.5) {
             let border_alpha = 1.0 - (-d / 2.5);
-            color = mix(color, vec4<f32>(ACCENT, 0.8), border_alpha);
+            color = mix(color, vec4<f32>(uniforms.accent.rgb, 0.8), border_alpha);
         }
         
         // Внутреннее свечение
         let inner_glow = glow(d, 0.2, 0.05);
-        color.r += ACCENT.r * inner_glow * 0.3;
-        color.g += ACCENT.g * inner_glow * 0.3;
-        color.b += ACCENT.b * inner_glow * 0.3;
+        color.r += uniforms.accent.rgb.r * inner_glow * 0.3;
+        color.g += uniforms.accent.rgb.g * inner_glow * 0.3;
+        color.b += uniforms.accent.rgb.b * inner_glow * 0.3;
         
         // Внешнее свечение (glow)
         let outer_glow = glow(d + 5.0, 0.6, 0.1);
-        color.r += ACCENT.r * outer_glow * 0.2;
-        color.g += ACCENT.g * outer_glow * 0.2;
-        color.b += ACCENT.b * outer_glow * 0.2;
+        color.r += uniforms.accent.rgb.r * outer_glow * 0.2;
+        color.g += uniforms.accent.rgb.g * outer_glow * 0.2;
+        color.b += uniforms.accent.rgb.b * outer_glow * 0.2;
         
         // Пульсация
         let pulse = sin(time * 3.0) * 0.1 + 0.9;
@@ -328,7 +328,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     // Подсветка области цифры (текст будет рендериться отдельно)
     if (digit_dist < digit_size) {
         let digit_alpha = 0.1 * (1.0 - digit_dist / digit_size);
-        color = mix(color, vec4<f32>(ACCENT, 0.3), digit_alpha);
+        color = mix(color, vec4<f32>(uniforms.accent.rgb, 0.3), digit_alpha);
     }
     
     return color;
@@ -7,7 +7,9 @@ mod render;
 
 pub use render::{HotbarRenderer, HotbarSlot};
 
-use crate::gpu::blocks::{BlockType, get_face_colors, AIR, STONE, DIRT, GRASS, OAK_PLANKS, COBBLESTONE, WATER};
+use crate::gpu::blocks::{BlockType, AIR, STONE, DIRT, GRASS, OAK_PLANKS, COBBLESTONE, WATER};
+use crate::gpu::items::{ItemStack, ItemType, block_item, insert_stack};
+use crate::gpu::save::SavedHotbar;
 
 /// Количество слотов в хотбаре
 pub const HOTBAR_SLOTS: usize = 9;
@@ -24,39 +26,13 @@ pub const BOTTOM_PADDING: f32 = 40.0;
 /// Состояние хотбара
 pub struct Hotbar {
     /// Слоты с предметами (None = пустой слот)
-    slots: [Option<HotbarItem>; HOTBAR_SLOTS],
+    slots: [Option<ItemStack>; HOTBAR_SLOTS],
     /// Индекс выбранного слота (0-8)
     selected: usize,
     /// Видимость хотбара
     visible: bool,
 }
 
-/// Предмет в слоте хотбара
-#[derive(Clone, Debug)]
-pub struct HotbarItem {
-    /// Тип блока
-    pub block_type: BlockType,
-    /// Количество (для стакающихся предметов)
-    pub count: u32,
-    /// Цвет верхней грани (RGB)
-    pub top_color: [f32; 3],
-    /// Цвет боковых граней (RGB)
-    pub side_color: [f32; 3],
-}
-
-impl HotbarItem {
-    /// Создать предмет из типа блока
-    pub fn from_block(block_type: BlockType) -> Self {
-        let (top, side) = get_face_colors(block_type);
-        Self {
-            block_type,
-            count: 1,
-            top_color: top,
-            side_color: side,
-        }
-    }
-}
-
 impl Default for Hotbar {
     fn default() -> Self {
         Self::new()
@@ -66,15 +42,15 @@ impl Default for Hotbar {
 impl Hotbar {
     pub fn new() -> Self {
         // Создаём хотбар с несколькими стартовыми блоками
-        let mut slots: [Option<HotbarItem>; HOTBAR_SLOTS] = Default::default();
+        let mut slots: [Option<ItemStack>; HOTBAR_SLOTS] = Default::default();
         
         // Стартовые блоки
-        slots[0] = Some(HotbarItem::from_block(STONE));
-        slots[1] = Some(HotbarItem::from_block(DIRT));
-        slots[2] = Some(HotbarItem::from_block(GRASS));
-        slots[3] = Some(HotbarItem::from_block(OAK_PLANKS));
-        slots[4] = Some(HotbarItem::from_block(COBBLESTONE));
-        slots[5] = Some(HotbarItem::from_block(WATER));
+        slots[0] = Some(ItemStack::of_block(STONE));
+        slots[1] = Some(ItemStack::of_block(DIRT));
+        slots[2] = Some(ItemStack::of_block(GRASS));
+        slots[3] = Some(ItemStack::of_block(OAK_PLANKS));
+        slots[4] = Some(ItemStack::of_block(COBBLESTONE));
+        slots[5] = Some(ItemStack::of_block(WATER));
         
         Self {
             slots,
@@ -103,22 +79,22 @@ impl Hotbar {
     }
     
     /// Получить предмет в выбранном слоте
-    pub fn selected_item(&self) -> Option<&HotbarItem> {
+    pub fn selected_item(&self) -> Option<&ItemStack> {
         self.slots[self.selected].as_ref()
     }
     
     /// Получить тип блока в выбранном слоте (для установки)
     pub fn selected_block_type(&self) -> Option<BlockType> {
-        self.slots[self.selected].as_ref().map(|item| item.block_type)
+        self.slots[self.selected].as_ref().and_then(ItemStack::block)
     }
     
     /// Получить предмет в слоте по индексу
-    pub fn get_item(&self, index: usize) -> Option<&HotbarItem> {
+    pub fn get_item(&self, index: usize) -> Option<&ItemStack> {
         self.slots.get(index).and_then(|s| s.as_ref())
     }
     
     /// Установить предмет в слот
-    pub fn set_item(&mut self, index: usize, item: Option<HotbarItem>) {
+    pub fn set_item(&mut self, index: usize, item: Option<ItemStack>) {
         if index < HOTBAR_SLOTS {
             self.slots[index] = item;
         }
@@ -128,14 +104,15 @@ impl Hotbar {
     /// Возвращает true если блок был добавлен
     pub fn pick_block(&mut self, block_type: BlockType) -> bool {
         // Не добавляем воздух
-        if block_type == AIR {
-            return false;
-        }
-        
-        // Сначала ищем этот блок в хотбаре
+        block_type != AIR && self.pick_item(block_item(block_type))
+    }
+    
+    /// Выбрать предмет: найти в хотбаре или положить в пустой/выбранный слот
+    pub fn pick_item(&mut self, item_type: ItemType) -> bool {
+        // Сначала ищем этот предмет в хотбаре
         for (i, slot) in self.slots.iter().enumerate() {
             if let Some(item) = slot {
-                if item.block_type == block_type {
+                if item.item == item_type {
                     // Блок уже есть - просто выбираем этот слот
                     self.selected = i;
                     return true;
@@ -146,22 +123,76 @@ impl Hotbar {
         // Блока нет - ищем пустой слот
         for (i, slot) in self.slots.iter_mut().enumerate() {
             if slot.is_none() {
-                *slot = Some(HotbarItem::from_block(block_type));
+                *slot = Some(ItemStack::new(item_type, 1));
                 self.selected = i;
                 return true;
             }
         }
         
         // Нет пустых слотов - заменяем текущий выбранный
-        self.slots[self.selected] = Some(HotbarItem::from_block(block_type));
+        self.slots[self.selected] = Some(ItemStack::new(item_type, 1));
         true
     }
     
     /// Получить все слоты
-    pub fn slots(&self) -> &[Option<HotbarItem>; HOTBAR_SLOTS] {
+    pub fn slots(&self) -> &[Option<ItemStack>; HOTBAR_SLOTS] {
         &self.slots
     }
     
+    /// Снимок слотов для сохранения
+    pub fn to_saved(&self) -> SavedHotbar {
+        SavedHotbar {
+            slots: self
+                .slots
+                .iter()
+                .map(|slot| slot.as_ref().map(ItemStack::to_saved))
+                .collect(),
+            selected: self.selected as u32,
+        }
+    }
+    
+    /// Восстановить слоты из сохранения
+    pub fn apply_saved(&mut self, saved: &SavedHotbar) {
+        for (i, slot) in self.slots.iter_mut().enumerate() {
+            *slot = saved.slots.get(i).copied().flatten().and_then(ItemStack::from_saved);
+        }
+        self.select(saved.selected as usize);
+    }
+    
+    /// Положить стопку в хотбар. Возвращает то, что не поместилось
+    pub fn insert(&mut self, stack: ItemStack) -> Option<ItemStack> {
+        insert_stack(&mut self.slots, stack)
+    }
+    
+    /// Забрать стопку из слота целиком
+    pub fn take(&mut self, index: usize) -> Option<ItemStack> {
+        self.slots.get_mut(index).and_then(Option::take)
+    }
+    
+    /// Положить стопку в слот: тот же предмет доливается до максимума,
+    /// другой - заменяет содержимое. Возвращает остаток
+    pub fn put(&mut self, index: usize, stack: ItemStack) -> Option<ItemStack> {
+        let slot = self.slots.get_mut(index)?;
+        if let Some(existing) = slot.as_mut().filter(|existing| existing.item == stack.item) {
+            return existing.merge(stack);
+        }
+        *slot = Some(stack);
+        None
+    }
+    
+    /// Отделить меньшую половину стопки в слоте
+    pub fn take_half(&mut self, index: usize) -> Option<ItemStack> {
+        self.slots.get_mut(index)?.as_mut()?.split_half()
+    }
+    
+    /// Разделить стопку: меньшая половина уходит в первый пустой слот
+    pub fn split(&mut self, index: usize) -> bool {
+        let Some(empty) = self.slots.iter().position(Option::is_none) else { return false };
+        let Some(half) = self.take_half(index) else { return false };
+        self.slots[empty] = Some(half);
+        true
+    }
+    
     /// Показать/скрыть хотбар
     pub fn set_visible(&mut self, visible: bool) {
         self.visible = visible;
@@ -174,26 +205,25 @@ impl Hotbar {
     
     /// Обработка клика мыши (возвращает true если клик был по хотбару)
     pub fn handle_click(&mut self, mx: f32, my: f32, screen_width: f32, screen_height: f32) -> bool {
-        if !self.visible {
-            return false;
-        }
-        
-        let hotbar_width = HOTBAR_SLOTS as f32 * SLOT_SIZE + (HOTBAR_SLOTS - 1) as f32 * SLOT_GAP;
-        let hotbar_x = (screen_width - hotbar_width) / 2.0;
-        let hotbar_y = screen_height - BOTTOM_PADDING - SLOT_SIZE;
-        
-        // Проверяем попадание в область хотбара
-        if my >= hotbar_y && my <= hotbar_y + SLOT_SIZE {
-            for i in 0..HOTBAR_SLOTS {
-                let slot_x = hotbar_x + i as f32 * (SLOT_SIZE + SLOT_GAP);
-                if mx >= slot_x && mx <= slot_x + SLOT_SIZE {
-                    self.selected = i;
-                    return true;
-                }
+        match self.slot_at(mx, my, screen_width, screen_height) {
+            Some(i) => {
+                self.selected = i;
+                true
             }
+            None => false,
+        }
+    }
+    
+    /// Слот под курсором
+    pub fn slot_at(&self, mx: f32, my: f32, screen_width: f32, screen_height: f32) -> Option<usize> {
+        if !self.visible {
+            return None;
         }
         
-        false
+        (0..HOTBAR_SLOTS).find(|&i| {
+            let (slot_x, slot_y) = slot_pos(i, screen_width, screen_height);
+            mx >= slot_x && mx <= slot_x + SLOT_SIZE && my >= slot_y && my <= slot_y + SLOT_SIZE
+        })
     }
     
     /// Прокрутка колёсиком мыши
@@ -205,3 +235,11 @@ impl Hotbar {
         }
     }
 }
+
+/// Левый верхний угол слота хотбара
+pub fn slot_pos(index: usize, screen_width: f32, screen_height: f32) -> (f32, f32) {
+    let hotbar_width = HOTBAR_SLOTS as f32 * SLOT_SIZE + (HOTBAR_SLOTS - 1) as f32 * SLOT_GAP;
+    let hotbar_x = (screen_width - hotbar_width) / 2.0;
+    let hotbar_y = screen_height - BOTTOM_PADDING - SLOT_SIZE;
+    (hotbar_x + index as f32 * (SLOT_SIZE + SLOT_GAP), hotbar_y)
+}
@@ -6,7 +6,10 @@ use bytemuck::{Pod, Zeroable};
 use wgpu::util::DeviceExt;
 use std::time::Instant;
 
-use super::{Hotbar, HotbarItem, HOTBAR_SLOTS, SLOT_SIZE, SLOT_GAP, BOTTOM_PADDING};
+use super::{Hotbar, HOTBAR_SLOTS, SLOT_SIZE, SLOT_GAP, BOTTOM_PADDING};
+use crate::gpu::items::{ItemStack, item_colors};
+use crate::gpu::block_entity::CHEST_SLOTS;
+use crate::gpu::gui::ContainerScreen;
 
 /// Uniforms для шейдера хотбара
 #[repr(C)]
@@ -15,6 +18,7 @@ pub struct HotbarUniforms {
     pub screen_size: [f32; 2],
     pub time: f32,
     pub selected_slot: f32,
+    pub accent: [f32; 4],
 }
 
 /// Данные одного слота для GPU
@@ -31,12 +35,17 @@ pub struct HotbarSlot {
     pub side_color: [f32; 4],  // Цвет боковых граней (RGBA)
 }
 
+/// Акцентный цвет по умолчанию (#00f3ff - cyan)
+const DEFAULT_ACCENT: [f32; 4] = [0.0, 0.953, 1.0, 1.0];
+
 /// GPU рендерер хотбара
 pub struct HotbarRenderer {
     // GPU ресурсы
     pipeline: wgpu::RenderPipeline,
     vertex_buffer: wgpu::Buffer,
     instance_buffer: wgpu::Buffer,
+    /// Слоты экрана контейнера (сундук) - тем же шейдером
+    container_buffer: wgpu::Buffer,
     uniform_buffer: wgpu::Buffer,
     bind_group: wgpu::BindGroup,
     
@@ -44,6 +53,7 @@ pub struct HotbarRenderer {
     screen_width: f32,
     screen_height: f32,
     start_time: Instant,
+    accent: [f32; 4],
 }
 
 impl HotbarRenderer {
@@ -73,6 +83,7 @@ impl HotbarRenderer {
             screen_size: [width as f32, height as f32],
             time: 0.0,
             selected_slot: 0.0,
+            accent: DEFAULT_ACCENT,
         };
         
         let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -111,6 +122,14 @@ impl HotbarRenderer {
             mapped_at_creation: false,
         });
         
+        // Instance buffer экрана контейнера (фон + слоты сундука)
+        let container_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Container Instance Buffer"),
+            size: (std::mem::size_of::<HotbarSlot>() * (CHEST_SLOTS + 1)) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        
         // Shader
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("Hotbar Shader"),
@@ -208,11 +227,13 @@ impl HotbarRenderer {
             pipeline,
             vertex_buffer,
             instance_buffer,
+            container_buffer,
             uniform_buffer,
             bind_group,
             screen_width: width as f32,
             screen_height: height as f32,
             start_time: Instant::now(),
+            accent: DEFAULT_ACCENT,
         }
     }
     
@@ -221,6 +242,11 @@ impl HotbarRenderer {
         self.screen_height = height as f32;
     }
     
+    /// Установить акцентный цвет (из пакета ресурсов)
+    pub fn set_accent(&mut self, accent: [f32; 3]) {
+        self.accent = [accent[0], accent[1], accent[2], 1.0];
+    }
+    
     pub fn render<'a>(
         &'a self,
         render_pass: &mut wgpu::RenderPass<'a>,
@@ -238,6 +264,7 @@ impl HotbarRenderer {
             screen_size: [self.screen_width, self.screen_height],
             time,
             selected_slot: hotbar.selected() as f32,
+            accent: self.accent,
         };
         queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
         
@@ -266,12 +293,7 @@ impl HotbarRenderer {
             let slot_x = hotbar_x + i as f32 * (SLOT_SIZE + SLOT_GAP);
             let item = hotbar.get_item(i);
             
-            let (top_color, side_color) = if let Some(it) = item {
-                ([it.top_color[0], it.top_color[1], it.top_color[2], 1.0],
-                 [it.side_color[0], it.side_color[1], it.side_color[2], 1.0])
-            } else {
-                ([0.0, 0.0, 0.0, 0.0], [0.0, 0.0, 0.0, 0.0])
-            };
+            let (top_color, side_color) = slot_colors(item);
             
             instances.push(HotbarSlot {
                 pos: [slot_x, hotbar_y],
@@ -292,5 +314,71 @@ impl HotbarRenderer {
         render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
         render_pass.set_vertex_buffer(1, self.instance_buffer.slice(..));
         render_pass.draw(0..6, 0..instances.len() as u32);
+    }    
+    /// Экран контейнера: панель и слоты (наведённый слот подсвечен)
+    pub fn render_container<'a>(
+        &'a self,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        queue: &wgpu::Queue,
+        screen: &ContainerScreen,
+    ) {
+        if !screen.is_visible() {
+            return;
+        }
+        
+        let uniforms = HotbarUniforms {
+            screen_size: [self.screen_width, self.screen_height],
+            time: self.start_time.elapsed().as_secs_f32(),
+            selected_slot: screen.hovered().map_or(-1.0, |slot| slot as f32),
+            accent: self.accent,
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+        
+        let (panel_x, panel_y, panel_w, panel_h) = screen.panel_rect(self.screen_width, self.screen_height);
+        let mut instances: Vec<HotbarSlot> = Vec::with_capacity(CHEST_SLOTS + 1);
+        instances.push(HotbarSlot {
+            pos: [panel_x, panel_y],
+            size: [panel_w, panel_h],
+            slot_index: 99,
+            is_selected: 0,
+            has_item: 0,
+            _padding: 0,
+            top_color: [0.0, 0.0, 0.0, 0.0],
+            side_color: [0.0, 0.0, 0.0, 0.0],
+        });
+        
+        for (i, item) in screen.slots().iter().enumerate().take(CHEST_SLOTS) {
+            let (slot_x, slot_y) = screen.slot_pos(i, self.screen_width, self.screen_height);
+            let (top_color, side_color) = slot_colors(item.as_ref());
+            instances.push(HotbarSlot {
+                pos: [slot_x, slot_y],
+                size: [SLOT_SIZE, SLOT_SIZE],
+                slot_index: i as u32,
+                is_selected: if screen.hovered() == Some(i) { 1 } else { 0 },
+                has_item: if item.is_some() { 1 } else { 0 },
+                _padding: 0,
+                top_color,
+                side_color,
+            });
+        }
+        
+        queue.write_buffer(&self.container_buffer, 0, bytemuck::cast_slice(&instances));
+        
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.set_vertex_buffer(1, self.container_buffer.slice(..));
+        render_pass.draw(0..6, 0..instances.len() as u32);
+    }
+}
+
+/// Цвета граней предмета для шейдера (прозрачные у пустого слота)
+fn slot_colors(item: Option<&ItemStack>) -> ([f32; 4], [f32; 4]) {
+    match item {
+        Some(stack) => {
+            let (top, side) = item_colors(stack.item);
+            ([top[0], top[1], top[2], 1.0], [side[0], side[1], side[2], 1.0])
+        }
+        None => ([0.0, 0.0, 0.0, 0.0], [0.0, 0.0, 0.0, 0.0]),
     }
 }
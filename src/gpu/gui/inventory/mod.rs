@@ -21,6 +21,7 @@ use crate::gpu::blocks::{
     IRON_BLOCK, GOLD_BLOCK, DIAMOND_BLOCK, EMERALD_BLOCK, COPPER_BLOCK,
     CUSTOM_100, CUSTOM_101, CUSTOM_102, CUSTOM_103, CUSTOM_104,
 };
+use crate::gpu::items::{ItemType, block_item, item_registry, item_colors, item_name};
 
 /// Количество колонок в инвентаре
 pub const INVENTORY_COLS: usize = 8;
@@ -51,6 +52,8 @@ pub enum BlockCategory {
     Nature,
     Building,
     Metal,
+    /// Предметы, не являющиеся блоками (инструменты)
+    Items,
 }
 
 impl BlockCategory {
@@ -64,6 +67,7 @@ impl BlockCategory {
             BlockCategory::Nature => "Nature",
             BlockCategory::Building => "Building",
             BlockCategory::Metal => "Metal",
+            BlockCategory::Items => "Items",
         }
     }
 }
@@ -71,7 +75,7 @@ impl BlockCategory {
 /// Предмет в инвентаре
 #[derive(Clone, Debug)]
 pub struct InventoryItem {
-    pub block_type: BlockType,
+    pub item: ItemType,
     pub name: &'static str,
     pub top_color: [f32; 3],
     pub side_color: [f32; 3],
@@ -85,7 +89,7 @@ impl InventoryItem {
         let category = Self::categorize(block_type);
         
         Self {
-            block_type,
+            item: block_item(block_type),
             name: Box::leak(name.into_boxed_str()),
             top_color: top,
             side_color: side,
@@ -142,14 +146,14 @@ pub struct Inventory {
     scroll: f32,
     /// Максимальный скролл
     max_scroll: f32,
-    /// Выбранный блок (для передачи в хотбар)
-    selected_block: Option<BlockType>,
+    /// Выбранный предмет (для передачи в хотбар)
+    selected_block: Option<ItemType>,
     /// Текущая категория
     category: BlockCategory,
     /// Индекс слота под курсором
     hovered_slot: Option<usize>,
-    /// Перетаскиваемый блок (drag & drop)
-    dragging_block: Option<BlockType>,
+    /// Перетаскиваемый предмет (drag & drop)
+    dragging_block: Option<ItemType>,
 }
 
 impl Default for Inventory {
@@ -198,7 +202,7 @@ impl Inventory {
                 };
                 
                 items.push(InventoryItem {
-                    block_type,
+                    item: block_item(block_type),
                     name: Box::leak(def.name.clone().into_boxed_str()),
                     top_color: def.color.top(),
                     side_color: def.color.side(),
@@ -212,8 +216,20 @@ impl Inventory {
             items = Self::create_builtin_items();
         }
         
+        // Предметы-не-блоки (инструменты) после блоков
+        for def in item_registry().all_items() {
+            let (top, side) = item_colors(def.numeric_id);
+            items.push(InventoryItem {
+                item: def.numeric_id,
+                name: Box::leak(item_name(def.numeric_id).into_boxed_str()),
+                top_color: top,
+                side_color: side,
+                category: BlockCategory::Items,
+            });
+        }
+        
         // Сортируем по numeric_id для консистентности
-        items.sort_by_key(|i| i.block_type);
+        items.sort_by_key(|i| i.item);
         
         items
     }
@@ -319,13 +335,13 @@ impl Inventory {
     }
     
     /// Получить выбранный блок и сбросить
-    pub fn take_selected_block(&mut self) -> Option<BlockType> {
+    pub fn take_selected_block(&mut self) -> Option<ItemType> {
         self.selected_block.take()
     }
     
     /// Установить выбранный блок
-    pub fn select_block(&mut self, block_type: BlockType) {
-        self.selected_block = Some(block_type);
+    pub fn select_block(&mut self, item: ItemType) {
+        self.selected_block = Some(item);
     }
     
     /// Получить текущую категорию
@@ -350,29 +366,29 @@ impl Inventory {
     }
     
     /// Обработка клика (начало drag)
-    pub fn handle_click(&mut self, slot_index: usize) -> Option<BlockType> {
+    pub fn handle_click(&mut self, slot_index: usize) -> Option<ItemType> {
         let items = self.filtered_items();
         if slot_index < items.len() {
-            let block_type = items[slot_index].block_type;
+            let item = items[slot_index].item;
             // Начинаем перетаскивание
-            self.dragging_block = Some(block_type);
-            return Some(block_type);
+            self.dragging_block = Some(item);
+            return Some(item);
         }
         None
     }
     
     /// Начать перетаскивание блока
-    pub fn start_drag(&mut self, block_type: BlockType) {
-        self.dragging_block = Some(block_type);
+    pub fn start_drag(&mut self, item: ItemType) {
+        self.dragging_block = Some(item);
     }
     
-    /// Получить перетаскиваемый блок
-    pub fn dragging(&self) -> Option<BlockType> {
+    /// Получить перетаскиваемый предмет
+    pub fn dragging(&self) -> Option<ItemType> {
         self.dragging_block
     }
     
     /// Завершить перетаскивание (drop)
-    pub fn end_drag(&mut self) -> Option<BlockType> {
+    pub fn end_drag(&mut self) -> Option<ItemType> {
         self.dragging_block.take()
     }
     
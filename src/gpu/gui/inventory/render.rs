@@ -11,7 +11,7 @@ use super::{
     INVENTORY_COLS, INV_SLOT_SIZE, INV_SLOT_GAP, INV_PADDING,
     HEADER_HEIGHT, SCROLLBAR_WIDTH,
 };
-use crate::gpu::blocks::{BlockType, get_face_colors};
+use crate::gpu::items::item_colors;
 
 /// Uniforms для шейдера инвентаря
 #[repr(C)]
@@ -476,8 +476,8 @@ impl InventoryRenderer {
         mouse_x: f32,
         mouse_y: f32,
     ) {
-        if let Some(block_type) = inventory.dragging() {
-            let (top, side) = get_face_colors(block_type);
+        if let Some(item) = inventory.dragging() {
+            let (top, side) = item_colors(item);
             
             let drag_size = 56.0; // Немного меньше слота
             
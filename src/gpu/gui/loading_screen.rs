@@ -0,0 +1,152 @@
+// ============================================
+// Loading Screen - Экран загрузки мира
+// ============================================
+// Пока мир загружается в фоне, вместо сцены рисуется тёмный фон,
+// полоса прогресса, текущий этап и совет. Состояние задаёт App через
+// set, GuiRenderer::render_loading рисует его.
+
+use wgpu::util::DeviceExt;
+
+use crate::gpu::locale::tr;
+
+use super::{TextParams, TextAlign, UiVertex};
+
+const BAR_WIDTH: f32 = 480.0;
+const BAR_HEIGHT: f32 = 14.0;
+/// Рамка полосы вокруг заполнения
+const BAR_BORDER: f32 = 2.0;
+/// Вершин: рамка, подложка и заполнение по 6
+const VERTEX_COUNT: usize = 18;
+
+pub const LOADING_BACKGROUND: wgpu::Color = wgpu::Color { r: 0.02, g: 0.03, b: 0.05, a: 1.0 };
+const BORDER_COLOR: [f32; 4] = [0.35, 0.4, 0.45, 1.0];
+const TRACK_COLOR: [f32; 4] = [0.08, 0.09, 0.12, 1.0];
+const FILL_COLOR: [f32; 4] = [0.0, 0.94, 1.0, 1.0];
+
+/// Рендерер экрана загрузки
+pub struct LoadingScreen {
+    pipeline: wgpu::RenderPipeline,
+    vertex_buffer: wgpu::Buffer,
+    status: String,
+    tip: String,
+    /// 0-1
+    progress: f32,
+    screen_width: f32,
+    screen_height: f32,
+}
+
+impl LoadingScreen {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat, width: u32, height: u32) -> Self {
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Loading Screen Vertex Buffer"),
+            contents: bytemuck::cast_slice(&[UiVertex { position: [0.0; 2], color: [0.0; 4] }; VERTEX_COUNT]),
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Loading Screen Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/ui.wgsl").into()),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Loading Screen Pipeline Layout"),
+            bind_group_layouts: &[],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Loading Screen Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[UiVertex::desc()],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            pipeline,
+            vertex_buffer,
+            status: String::new(),
+            tip: String::new(),
+            progress: 0.0,
+            screen_width: width as f32,
+            screen_height: height as f32,
+        }
+    }
+
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.screen_width = width as f32;
+        self.screen_height = height as f32;
+    }
+
+    /// Этап, прогресс (0-1) и совет
+    pub fn set(&mut self, status: &str, progress: f32, tip: &str) {
+        self.status.clear();
+        self.status.push_str(status);
+        self.tip.clear();
+        self.tip.push_str(tip);
+        self.progress = progress.clamp(0.0, 1.0);
+    }
+
+    /// Полоса прогресса и подписи
+    pub fn prepare(&self, queue: &wgpu::Queue) -> Vec<TextParams> {
+        let width = BAR_WIDTH.min(self.screen_width * 0.8);
+        let x0 = (self.screen_width - width) * 0.5;
+        let y0 = self.screen_height * 0.5;
+        let to_ndc = |px: f32, py: f32| [px / self.screen_width * 2.0 - 1.0, 1.0 - py / self.screen_height * 2.0];
+        let quad = |x0: f32, y0: f32, x1: f32, y1: f32, color: [f32; 4]| {
+            [(x0, y0), (x1, y0), (x1, y1), (x0, y0), (x1, y1), (x0, y1)]
+                .map(|(px, py)| UiVertex { position: to_ndc(px, py), color })
+        };
+
+        let fill_end = x0 + width * self.progress;
+        let mut vertices = Vec::with_capacity(VERTEX_COUNT);
+        vertices.extend(quad(x0 - BAR_BORDER, y0 - BAR_BORDER, x0 + width + BAR_BORDER, y0 + BAR_HEIGHT + BAR_BORDER, BORDER_COLOR));
+        vertices.extend(quad(x0, y0, x0 + width, y0 + BAR_HEIGHT, TRACK_COLOR));
+        vertices.extend(quad(x0, y0, fill_end, y0 + BAR_HEIGHT, FILL_COLOR));
+        queue.write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice(&vertices));
+
+        let center = self.screen_width * 0.5;
+        vec![
+            TextParams::centered(&tr("loading.title"), center, y0 - 72.0, 28.0),
+            TextParams::centered(&self.status, center, y0 - 30.0, 16.0).with_color([0.8, 0.85, 0.9, 1.0]),
+            TextParams::centered(&format!("{}%", (self.progress * 100.0) as u32), center, y0 + BAR_HEIGHT + 12.0, 14.0)
+                .with_color([0.6, 0.65, 0.7, 1.0]),
+            TextParams {
+                x: center,
+                y: self.screen_height - 64.0,
+                text: self.tip.clone(),
+                size: 15.0,
+                color: [0.7, 0.75, 0.6, 1.0],
+                align: TextAlign::Center,
+                max_width: Some(self.screen_width * 0.8),
+            },
+        ]
+    }
+
+    pub fn render<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.draw(0..VERTEX_COUNT as u32, 0..1);
+    }
+}
@@ -0,0 +1,185 @@
+// ============================================
+// Log Viewer - Окно журнала сессии (F3)
+// ============================================
+// Последние записи gpu::logging поверх игры: подложка и строки,
+// окрашенные по уровню. PageUp/PageDown листают назад по журналу.
+
+use log::Level;
+use wgpu::util::DeviceExt;
+
+use crate::gpu::logging::{self, LogEntry};
+
+use super::{TextParams, TextAlign, UiVertex};
+
+/// Отступ от краёв экрана
+const MARGIN: f32 = 10.0;
+const LINE_HEIGHT: f32 = 18.0;
+const FONT_SIZE: f32 = 14.0;
+const MAX_WIDTH: f32 = 960.0;
+/// Строк на экране
+const VISIBLE_LINES: usize = 24;
+/// Шаг прокрутки PageUp/PageDown
+const SCROLL_STEP: usize = VISIBLE_LINES / 2;
+
+const BACKGROUND: [f32; 4] = [0.0, 0.0, 0.0, 0.7];
+
+/// Рендерер окна журнала
+pub struct LogViewer {
+    pipeline: wgpu::RenderPipeline,
+    vertex_buffer: wgpu::Buffer,
+    visible: bool,
+    /// Сколько последних записей пропущено (0 - хвост журнала)
+    scroll: usize,
+    screen_width: f32,
+    screen_height: f32,
+}
+
+impl LogViewer {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat, width: u32, height: u32) -> Self {
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Log Viewer Vertex Buffer"),
+            contents: bytemuck::cast_slice(&[UiVertex { position: [0.0; 2], color: [0.0; 4] }; 6]),
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Log Viewer Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/ui.wgsl").into()),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Log Viewer Pipeline Layout"),
+            bind_group_layouts: &[],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Log Viewer Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[UiVertex::desc()],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            pipeline,
+            vertex_buffer,
+            visible: false,
+            scroll: 0,
+            screen_width: width as f32,
+            screen_height: height as f32,
+        }
+    }
+
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.screen_width = width as f32;
+        self.screen_height = height as f32;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Показать/скрыть (при открытии - к хвосту журнала)
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+        self.scroll = 0;
+    }
+
+    /// Листать к более старым записям
+    pub fn scroll_up(&mut self) {
+        self.scroll += SCROLL_STEP;
+    }
+
+    /// Листать к свежим записям
+    pub fn scroll_down(&mut self) {
+        self.scroll = self.scroll.saturating_sub(SCROLL_STEP);
+    }
+
+    fn level_color(level: Level) -> [f32; 4] {
+        match level {
+            Level::Error => [1.0, 0.4, 0.4, 1.0],
+            Level::Warn => [1.0, 0.85, 0.3, 1.0],
+            Level::Info => [1.0, 1.0, 1.0, 1.0],
+            Level::Debug => [0.65, 0.75, 0.9, 1.0],
+            Level::Trace => [0.55, 0.55, 0.55, 1.0],
+        }
+    }
+
+    /// Подложка и строки журнала. Скрыто - пусто
+    pub fn prepare(&mut self, queue: &wgpu::Queue) -> Vec<TextParams> {
+        if !self.visible {
+            return Vec::new();
+        }
+
+        // Прокрутка не уходит дальше начала буфера
+        let mut entries: Vec<LogEntry> = logging::recent(VISIBLE_LINES + self.scroll);
+        self.scroll = self.scroll.min(entries.len().saturating_sub(VISIBLE_LINES));
+        entries.truncate(entries.len() - self.scroll);
+        let skip = entries.len().saturating_sub(VISIBLE_LINES);
+
+        let width = MAX_WIDTH.min(self.screen_width - MARGIN * 2.0);
+        let height = (VISIBLE_LINES + 1) as f32 * LINE_HEIGHT + 8.0;
+        let to_ndc = |px: f32, py: f32| [px / self.screen_width * 2.0 - 1.0, 1.0 - py / self.screen_height * 2.0];
+        let (x0, y0, x1, y1) = (MARGIN, MARGIN, MARGIN + width, MARGIN + height);
+        let vertices: Vec<UiVertex> = [(x0, y0), (x1, y0), (x1, y1), (x0, y0), (x1, y1), (x0, y1)]
+            .into_iter()
+            .map(|(px, py)| UiVertex { position: to_ndc(px, py), color: BACKGROUND })
+            .collect();
+        queue.write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice(&vertices));
+
+        let header = match self.scroll {
+            0 => format!("Журнал сессии [{}]", logging::filter_spec()),
+            scroll => format!("Журнал сессии [{}] (-{})", logging::filter_spec(), scroll),
+        };
+        let mut texts = vec![TextParams {
+            x: MARGIN + 6.0,
+            y: MARGIN + 4.0,
+            text: header,
+            size: FONT_SIZE,
+            color: [0.0, 0.94, 1.0, 1.0],
+            align: TextAlign::Left,
+            max_width: Some(width - 12.0),
+        }];
+        texts.extend(entries[skip..].iter().enumerate().map(|(i, entry)| TextParams {
+            x: MARGIN + 6.0,
+            y: MARGIN + 4.0 + (i + 1) as f32 * LINE_HEIGHT,
+            text: entry.to_string(),
+            size: FONT_SIZE,
+            color: Self::level_color(entry.level),
+            align: TextAlign::Left,
+            max_width: Some(width - 12.0),
+        }));
+        texts
+    }
+
+    pub fn render<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+        if !self.visible {
+            return;
+        }
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.draw(0..6, 0..1);
+    }
+}
@@ -7,12 +7,23 @@ use bytemuck::{Pod, Zeroable};
 use wgpu::util::DeviceExt;
 use std::time::Instant;
 
+use crate::gpu::render::{SamplingSettings, PostSettings, AntiAliasing, ResolutionSettings, MIN_RENDER_SCALE, MAX_RENDER_SCALE};
+use crate::gpu::clouds::CloudQuality;
+use crate::gpu::core::{DEFAULT_FOV, MIN_FOV, MAX_FOV, LodPreset, DEFAULT_MOUSE_SENSITIVITY, MIN_MOUSE_SENSITIVITY, MAX_MOUSE_SENSITIVITY};
+use crate::gpu::terrain::voxel::{SECTION_COUNT, SECTION_SIZE};
+use crate::gpu::input::{HapticEvent, HapticSettings};
+use crate::gpu::locale::{tr, tr_args};
+
+/// Минимальная вертикальная дальность (секций в каждую сторону)
+const MIN_VERTICAL_SECTIONS: i32 = 2;
+
 /// Состояние меню
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MenuState {
     Hidden,
     Main,
     Settings,
+    ResourcePacks,
 }
 
 /// Действие из меню
@@ -23,6 +34,29 @@ pub enum MenuAction {
     Settings,
     BackToMain,
     SaveSettings,  // Сохранить настройки и применить LOD
+    ResourcePacks, // Открыт экран пакетов ресурсов (нужен свежий список)
+    TogglePack(usize),
+    MovePackUp(usize),
+    MovePackDown(usize),
+    ApplyPacks,    // Применить пакеты: пересобрать атлас, звуки, цвета UI
+    CycleTextureFilter,
+    CycleShadowFilter,
+    CycleAnisotropy,
+    CycleMipBias,
+    CycleLodPreset,
+    ToggleLodAdvanced,
+    ToggleWaterReflections,
+    CycleClouds,
+    ToggleBloom,
+    ToggleTonemapping,
+    ToggleVignette,
+    CycleExposure,
+    CycleAntiAliasing,
+    ToggleDynamicResolution,
+    ToggleHaptic(HapticEvent),
+    ToggleInvertY,
+    ToggleRawInput,
+    PhotoMode,     // Фоторежим: свободная камера, мир на паузе
     QuitToDesktop,
 }
 
@@ -48,6 +82,7 @@ pub struct MenuUniforms {
     pub screen_size: [f32; 2],
     pub time: f32,
     pub menu_state: f32,
+    pub accent: [f32; 4],
 }
 
 #[repr(C)]
@@ -147,11 +182,33 @@ impl UIElement {
     }
 }
 
+/// Акцентный цвет по умолчанию (#00f0ff - cyan)
+const DEFAULT_ACCENT: [f32; 4] = [0.0, 0.94, 1.0, 1.0];
+
+/// Сколько пакетов помещается на экране пакетов
+const MAX_PACK_ROWS: usize = 5;
+
+/// Кнопки с постоянной подписью: ключ в файле языка - "menu.<id>"
+const STATIC_LABELS: &[&str] = &[
+    "resume", "settings", "stats", "photo", "quit",
+    "save", "back", "packs",
+    "pack_up", "pack_down", "pack_apply", "pack_back",
+];
+
+/// "Вкл" / "Выкл" на текущем языке
+fn on_off(enabled: bool) -> String {
+    tr(if enabled { "common.on" } else { "common.off" })
+}
+
 /// GPU-рендерер меню в стиле Hytale
 pub struct MenuSystem {
     // UI элементы по экранам
     main_elements: Vec<UIElement>,
     settings_elements: Vec<UIElement>,
+    /// Экран пакетов: по 3 кнопки на пакет (вкл/выкл, выше, ниже) + Apply/Back
+    pack_elements: Vec<UIElement>,
+    /// Пакеты (имя, включён) в порядке приоритета
+    packs: Vec<(String, bool)>,
     
     // GPU ресурсы
     instance_buffer: wgpu::Buffer,
@@ -165,6 +222,9 @@ pub struct MenuSystem {
     screen_width: f32,
     screen_height: f32,
     start_time: Instant,
+    accent: [f32; 4],
+    /// Показаны ли слайдеры LOD (панель Advanced)
+    lod_advanced: bool,
     
     // Панели
     panel_main: UIElement,
@@ -204,6 +264,7 @@ impl MenuSystem {
             screen_size: [width as f32, height as f32],
             time: 0.0,
             menu_state: 0.0,
+            accent: DEFAULT_ACCENT,
         };
         
         let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -314,11 +375,13 @@ impl MenuSystem {
         });
         
         // ========== Главное меню ==========
+        // Постоянные подписи кнопок ставит translate_static_labels
         let main_elements = vec![
-            UIElement::new_primary("resume", "Back to Game", 380.0, 56.0),
-            UIElement::new_button("settings", "Settings", 380.0, 56.0),
-            UIElement::new_button("stats", "Statistics", 380.0, 56.0),
-            UIElement::new_danger("quit", "Quit to Menu", 380.0, 56.0),
+            UIElement::new_primary("resume", "", 380.0, 56.0),
+            UIElement::new_button("settings", "", 380.0, 56.0),
+            UIElement::new_button("stats", "", 380.0, 56.0),
+            UIElement::new_button("photo", "", 380.0, 56.0),
+            UIElement::new_danger("quit", "", 380.0, 56.0),
         ];
         
         // ========== Меню настроек ==========
@@ -327,8 +390,46 @@ impl MenuSystem {
             UIElement::new_slider("lod1", "LOD1", 160.0, 0.5),
             UIElement::new_slider("lod2", "LOD2", 160.0, 0.5),
             UIElement::new_slider("lod3", "LOD3", 160.0, 0.5),
-            UIElement::new_primary("save", "Save", 380.0, 56.0),
-            UIElement::new_button("back", "Back", 380.0, 56.0),
+            UIElement::new_primary("save", "", 380.0, 56.0),
+            UIElement::new_button("back", "", 380.0, 56.0),
+            UIElement::new_button("packs", "", 380.0, 48.0),
+            // Графика: переключатели фильтрации (подписи ставит set_sampling)
+            UIElement::new_button("tex_filter", "", 175.0, 36.0),
+            UIElement::new_button("shadow_filter", "", 175.0, 36.0),
+            UIElement::new_button("anisotropy", "", 175.0, 36.0),
+            UIElement::new_button("mip_bias", "", 175.0, 36.0),
+            // Угол обзора (значение ставит set_fov)
+            UIElement::new_slider("fov", "FOV", 350.0, 0.5),
+            // Вибрация геймпада: интенсивность и события (подписи ставит set_haptics)
+            UIElement::new_slider("haptic_intensity", "Vibration", 350.0, 0.6),
+            UIElement::new_button("haptic_break", "", 113.0, 36.0),
+            UIElement::new_button("haptic_damage", "", 113.0, 36.0),
+            UIElement::new_button("haptic_ui", "", 113.0, 36.0),
+            // Качество дальних LOD (1.0 - без упрощения мешей)
+            UIElement::new_slider("lod_quality", "LOD Quality", 160.0, 1.0),
+            // Вертикальная дальность прорисовки (1.0 - весь столбец)
+            UIElement::new_slider("vertical_distance", "Vertical", 160.0, 1.0),
+            // Уровень качества дальности и кнопка ручной настройки слайдеров LOD
+            UIElement::new_button("lod_preset", "", 175.0, 36.0),
+            UIElement::new_button("lod_advanced", "", 175.0, 36.0),
+            // Экранные отражения в воде (подпись ставит set_water_reflections)
+            UIElement::new_button("water_reflections", "", 175.0, 36.0),
+            // Мышь: чувствительность и переключатели (подписи ставит set_mouse)
+            UIElement::new_slider("mouse_sensitivity", "Sensitivity", 350.0, 0.5),
+            UIElement::new_button("invert_y", "", 175.0, 36.0),
+            UIElement::new_button("raw_input", "", 175.0, 36.0),
+            // Качество облаков (подпись ставит set_clouds)
+            UIElement::new_button("clouds", "", 175.0, 36.0),
+            // Постобработка (подписи ставит set_post)
+            UIElement::new_button("bloom", "", 175.0, 36.0),
+            UIElement::new_button("tonemapping", "", 175.0, 36.0),
+            UIElement::new_button("vignette", "", 175.0, 36.0),
+            UIElement::new_button("exposure", "", 175.0, 36.0),
+            // Сглаживание (подпись ставит set_anti_aliasing)
+            UIElement::new_button("anti_aliasing", "", 175.0, 36.0),
+            // Разрешение: динамический режим и масштаб (ставит set_resolution)
+            UIElement::new_button("dynamic_resolution", "", 175.0, 36.0),
+            UIElement::new_slider("render_scale", "Render Scale", 350.0, 0.0),
         ];
         
         // Панели
@@ -351,7 +452,7 @@ impl MenuSystem {
             x: 0.0,
             y: 0.0,
             width: 420.0,
-            height: 480.0,
+            height: 802.0,
             element_type: ElementType::Panel,
             hover: false,
             value: 0.0,
@@ -374,6 +475,8 @@ impl MenuSystem {
         let mut menu = Self {
             main_elements,
             settings_elements,
+            pack_elements: Vec::new(),
+            packs: Vec::new(),
             instance_buffer,
             uniform_buffer,
             bind_group,
@@ -383,15 +486,146 @@ impl MenuSystem {
             screen_width: width as f32,
             screen_height: height as f32,
             start_time: Instant::now(),
+            accent: DEFAULT_ACCENT,
+            lod_advanced: false,
             panel_main,
             panel_settings,
             overlay,
         };
         
-        menu.update_layout();
+        menu.set_resource_packs(Vec::new());
+        menu.set_sampling(&SamplingSettings::default());
+        menu.set_fov(DEFAULT_FOV);
+        menu.set_haptics(&HapticSettings::default());
+        menu.set_mouse(DEFAULT_MOUSE_SENSITIVITY, false, true);
+        menu.set_water_reflections(true);
+        menu.set_clouds(CloudQuality::default());
+        menu.set_post(&PostSettings::default());
+        menu.set_anti_aliasing(AntiAliasing::default());
+        menu.set_resolution(&ResolutionSettings::default());
+        menu.set_lod_advanced(false);
         menu
     }
     
+    /// Установить список пакетов ресурсов для экрана пакетов
+    pub fn set_resource_packs(&mut self, packs: Vec<(String, bool)>) {
+        self.pack_elements.clear();
+        for (name, enabled) in packs.iter().take(MAX_PACK_ROWS) {
+            let mut toggle = UIElement::new_button("pack_toggle", name, 260.0, 40.0);
+            if *enabled {
+                toggle.element_type = ElementType::ButtonPrimary;
+            }
+            self.pack_elements.push(toggle);
+            self.pack_elements.push(UIElement::new_button("pack_up", "", 50.0, 40.0));
+            self.pack_elements.push(UIElement::new_button("pack_down", "", 50.0, 40.0));
+        }
+        self.pack_elements.push(UIElement::new_primary("pack_apply", "", 380.0, 48.0));
+        self.pack_elements.push(UIElement::new_button("pack_back", "", 380.0, 48.0));
+        self.packs = packs;
+        self.translate_static_labels();
+        self.update_layout();
+    }
+    
+    /// Постоянные подписи кнопок из текущего языка
+    fn translate_static_labels(&mut self) {
+        let elements = self.main_elements.iter_mut().chain(&mut self.settings_elements).chain(&mut self.pack_elements);
+        for elem in elements.filter(|elem| STATIC_LABELS.contains(&elem.id)) {
+            elem.label = tr(&format!("menu.{}", elem.id));
+        }
+    }
+    
+    /// Перевести подписи после смены языка. Подписи со значениями
+    /// настроек заново ставят set_* (systems::MenuSystem::relabel)
+    pub fn relabel(&mut self) {
+        self.translate_static_labels();
+        self.set_lod_advanced(self.lod_advanced);
+        self.refresh_lod_preset();
+    }
+    
+    /// Обновить подписи переключателей графики
+    pub fn set_sampling(&mut self, sampling: &SamplingSettings) {
+        for elem in &mut self.settings_elements {
+            match elem.id {
+                "tex_filter" => elem.label = tr_args("menu.textures", &[("value", &sampling.filter.name())]),
+                "shadow_filter" => elem.label = tr_args("menu.shadows", &[("value", &sampling.shadow_filter.name())]),
+                "anisotropy" => {
+                    let value = if sampling.effective_anisotropy() > 1 {
+                        format!("{}x", sampling.anisotropy)
+                    } else {
+                        on_off(false)
+                    };
+                    elem.label = tr_args("menu.anisotropy", &[("value", &value)]);
+                }
+                "mip_bias" => elem.label = tr_args("menu.mip_bias", &[("value", &format!("{:+.1}", sampling.mip_bias))]),
+                _ => {}
+            }
+        }
+    }
+    
+    /// Обновить подпись переключателя отражений в воде
+    pub fn set_water_reflections(&mut self, enabled: bool) {
+        if let Some(elem) = self.settings_elements.iter_mut().find(|e| e.id == "water_reflections") {
+            elem.label = tr_args("menu.reflections", &[("value", &on_off(enabled))]);
+        }
+    }
+    
+    /// Обновить подпись переключателя облаков
+    pub fn set_clouds(&mut self, quality: CloudQuality) {
+        if let Some(elem) = self.settings_elements.iter_mut().find(|e| e.id == "clouds") {
+            elem.label = tr_args("menu.clouds", &[("value", &quality.name())]);
+        }
+    }
+    
+    /// Обновить подписи переключателей постобработки
+    pub fn set_post(&mut self, post: &PostSettings) {
+        let tonemap = if post.tonemapping { "ACES".to_string() } else { on_off(false) };
+        for elem in &mut self.settings_elements {
+            match elem.id {
+                "bloom" => elem.label = tr_args("menu.bloom", &[("value", &on_off(post.bloom))]),
+                "tonemapping" => elem.label = tr_args("menu.tonemap", &[("value", &tonemap)]),
+                "vignette" => elem.label = tr_args("menu.vignette", &[("value", &on_off(post.vignette))]),
+                "exposure" => elem.label = tr_args("menu.exposure", &[("value", &format!("{:.2}", post.exposure))]),
+                _ => {}
+            }
+        }
+    }
+    
+    /// Обновить подпись переключателя сглаживания
+    pub fn set_anti_aliasing(&mut self, anti_aliasing: AntiAliasing) {
+        if let Some(elem) = self.settings_elements.iter_mut().find(|e| e.id == "anti_aliasing") {
+            elem.label = tr_args("menu.anti_aliasing", &[("value", &anti_aliasing.name())]);
+        }
+    }
+    
+    /// Выставить слайдер масштаба и подпись динамического разрешения
+    pub fn set_resolution(&mut self, resolution: &ResolutionSettings) {
+        for elem in &mut self.settings_elements {
+            match elem.id {
+                "render_scale" => {
+                    elem.value = ((resolution.scale - MIN_RENDER_SCALE) / (MAX_RENDER_SCALE - MIN_RENDER_SCALE)).clamp(0.0, 1.0);
+                }
+                "dynamic_resolution" => {
+                    elem.label = tr_args("menu.dynamic_resolution", &[("value", &on_off(resolution.dynamic))]);
+                }
+                _ => {}
+            }
+        }
+    }
+    
+    /// Получить масштаб разрешения из слайдера (шаг 5%)
+    pub fn get_render_scale(&self) -> f32 {
+        let value = self.settings_elements
+            .iter()
+            .find(|e| e.id == "render_scale")
+            .map_or(0.0, |e| e.value);
+        ((MIN_RENDER_SCALE + value * (MAX_RENDER_SCALE - MIN_RENDER_SCALE)) * 20.0).round() / 20.0
+    }
+    
+    /// Установить акцентный цвет (из пакета ресурсов)
+    pub fn set_accent(&mut self, accent: [f32; 3]) {
+        self.accent = [accent[0], accent[1], accent[2], 1.0];
+    }
+    
     pub fn update_layout(&mut self) {
         let cx = self.screen_width / 2.0;
         let cy = self.screen_height / 2.0;
@@ -402,7 +636,7 @@ impl MenuSystem {
         
         // ========== Main Menu Layout ==========
         let panel_w = 420.0;
-        let panel_h = 380.0;
+        let panel_h = 446.0;
         self.panel_main.x = cx - panel_w / 2.0;
         self.panel_main.y = cy - panel_h / 2.0;
         self.panel_main.width = panel_w;
@@ -422,7 +656,10 @@ impl MenuSystem {
         }
         
         // ========== Settings Menu Layout ==========
-        let settings_h = 480.0;
+        // Ряды слайдеров дальности: с панелью Advanced - LOD 2x2 и качество,
+        // без неё - только вертикальная дальность
+        let lod_rows = if self.lod_advanced { 3.0 } else { 1.0 };
+        let settings_h = 928.0 + lod_rows * 50.0;
         self.panel_settings.x = cx - panel_w / 2.0;
         self.panel_settings.y = cy - settings_h / 2.0;
         self.panel_settings.width = panel_w;
@@ -431,23 +668,41 @@ impl MenuSystem {
         let settings_start_y = self.panel_settings.y + 100.0;
         let slider_spacing = 50.0;
         
-        // LOD слайдеры в сетке 2x2
         let grid_left = self.panel_settings.x + 30.0;
         let grid_right = cx + 15.0;
         
+        // Уровень качества и Advanced в первом ряду
+        if let Some(preset) = self.settings_elements.get_mut(18) {
+            preset.x = grid_left;
+            preset.y = settings_start_y - 10.0;
+        }
+        if let Some(advanced) = self.settings_elements.get_mut(19) {
+            advanced.x = grid_right - 15.0;
+            advanced.y = settings_start_y - 10.0;
+        }
+        let sliders_y = settings_start_y + slider_spacing;
+        
         if self.settings_elements.len() >= 4 {
-            // LOD0
+            // LOD слайдеры в сетке 2x2
             self.settings_elements[0].x = grid_left;
-            self.settings_elements[0].y = settings_start_y;
-            // LOD1
+            self.settings_elements[0].y = sliders_y;
             self.settings_elements[1].x = grid_right;
-            self.settings_elements[1].y = settings_start_y;
-            // LOD2
+            self.settings_elements[1].y = sliders_y;
             self.settings_elements[2].x = grid_left;
-            self.settings_elements[2].y = settings_start_y + slider_spacing;
-            // LOD3
+            self.settings_elements[2].y = sliders_y + slider_spacing;
             self.settings_elements[3].x = grid_right;
-            self.settings_elements[3].y = settings_start_y + slider_spacing;
+            self.settings_elements[3].y = sliders_y + slider_spacing;
+        }
+        
+        // Качество дальних LOD и вертикальная дальность под сеткой
+        let last_row_y = settings_start_y + slider_spacing * lod_rows;
+        if let Some(quality) = self.settings_elements.get_mut(16) {
+            quality.x = grid_left;
+            quality.y = last_row_y;
+        }
+        if let Some(vertical) = self.settings_elements.get_mut(17) {
+            vertical.x = if self.lod_advanced { grid_right } else { grid_left };
+            vertical.y = last_row_y;
         }
         
         // Кнопки внизу
@@ -459,6 +714,88 @@ impl MenuSystem {
             self.settings_elements[5].x = cx - self.settings_elements[5].width / 2.0;
             self.settings_elements[5].y = buttons_y + 60.0;
         }
+        if self.settings_elements.len() >= 7 {
+            self.settings_elements[6].x = cx - self.settings_elements[6].width / 2.0;
+            self.settings_elements[6].y = buttons_y - 60.0;
+        }
+        
+        // Переключатели графики в сетке 2x2 под LOD
+        let graphics_y = settings_start_y + slider_spacing * (lod_rows + 1.0) + 10.0;
+        for (i, elem) in self.settings_elements.iter_mut().skip(7).take(4).enumerate() {
+            elem.x = if i % 2 == 0 { grid_left } else { grid_right - 15.0 };
+            elem.y = graphics_y + (i / 2) as f32 * 42.0;
+        }
+        
+        // Отражения в воде и облака - третьим рядом графики
+        if let Some(water) = self.settings_elements.get_mut(20) {
+            water.x = grid_left;
+            water.y = graphics_y + 84.0;
+        }
+        if let Some(clouds) = self.settings_elements.get_mut(24) {
+            clouds.x = grid_right - 15.0;
+            clouds.y = graphics_y + 84.0;
+        }
+        
+        // Постобработка, сглаживание и динамическое разрешение - ещё три ряда по два
+        for (i, elem) in self.settings_elements.iter_mut().skip(25).take(6).enumerate() {
+            elem.x = if i % 2 == 0 { grid_left } else { grid_right - 15.0 };
+            elem.y = graphics_y + 126.0 + (i / 2) as f32 * 42.0;
+        }
+        
+        // Слайдер масштаба разрешения под переключателями
+        if let Some(scale) = self.settings_elements.get_mut(31) {
+            scale.x = grid_left;
+            scale.y = graphics_y + 282.0;
+        }
+        let graphics_end_y = graphics_y + 302.0;
+        
+        // Слайдер FOV на всю ширину под графикой
+        if let Some(fov) = self.settings_elements.get_mut(11) {
+            fov.x = grid_left;
+            fov.y = graphics_end_y + 30.0;
+        }
+        
+        // Вибрация: слайдер и три переключателя в ряд
+        let haptics_y = graphics_end_y + 80.0;
+        if let Some(intensity) = self.settings_elements.get_mut(12) {
+            intensity.x = grid_left;
+            intensity.y = haptics_y;
+        }
+        for (i, elem) in self.settings_elements.iter_mut().skip(13).take(3).enumerate() {
+            elem.x = grid_left + i as f32 * (elem.width + 5.5);
+            elem.y = haptics_y + 22.0;
+        }
+        
+        // Мышь: слайдер чувствительности и два переключателя под ним
+        let mouse_y = haptics_y + 100.0;
+        if let Some(sensitivity) = self.settings_elements.get_mut(21) {
+            sensitivity.x = grid_left;
+            sensitivity.y = mouse_y;
+        }
+        for (i, elem) in self.settings_elements.iter_mut().skip(22).take(2).enumerate() {
+            elem.x = if i == 0 { grid_left } else { grid_right - 15.0 };
+            elem.y = mouse_y + 22.0;
+        }
+        
+        // ========== Resource Packs Layout ==========
+        // Используем панель настроек, строки пакетов сверху вниз
+        let rows_start_y = self.panel_settings.y + 80.0;
+        let row_spacing = 46.0;
+        let row_left = self.panel_settings.x + 20.0;
+        let (rows, buttons) = self.pack_elements.split_at_mut(self.pack_elements.len().saturating_sub(2));
+        for (i, elem) in rows.iter_mut().enumerate() {
+            let row_y = rows_start_y + (i / 3) as f32 * row_spacing;
+            elem.y = row_y;
+            elem.x = match i % 3 {
+                0 => row_left,
+                1 => row_left + 270.0,
+                _ => row_left + 325.0,
+            };
+        }
+        for (i, elem) in buttons.iter_mut().enumerate() {
+            elem.x = cx - elem.width / 2.0;
+            elem.y = buttons_y + i as f32 * 60.0;
+        }
     }
     
     pub fn resize(&mut self, width: u32, height: u32) {
@@ -475,6 +812,7 @@ impl MenuSystem {
         let elements = match self.current_state {
             MenuState::Main => &mut self.main_elements,
             MenuState::Settings => &mut self.settings_elements,
+            MenuState::ResourcePacks => &mut self.pack_elements,
             MenuState::Hidden => return,
         };
         
@@ -501,6 +839,10 @@ impl MenuSystem {
                                 self.current_state = MenuState::Settings;
                                 return MenuAction::Settings;
                             }
+                            "photo" => {
+                                self.current_state = MenuState::Hidden;
+                                return MenuAction::PhotoMode;
+                            }
                             "quit" => {
                                 return MenuAction::QuitToDesktop;
                             }
@@ -510,7 +852,7 @@ impl MenuSystem {
                 }
             }
             MenuState::Settings => {
-                for elem in &self.settings_elements {
+                for elem in self.settings_elements.iter().filter(|e| e.visible) {
                     if elem.contains(mx, my) {
                         match elem.id {
                             "save" => {
@@ -521,6 +863,46 @@ impl MenuSystem {
                                 self.current_state = MenuState::Main;
                                 return MenuAction::BackToMain;
                             }
+                            "packs" => {
+                                self.current_state = MenuState::ResourcePacks;
+                                return MenuAction::ResourcePacks;
+                            }
+                            "tex_filter" => return MenuAction::CycleTextureFilter,
+                            "shadow_filter" => return MenuAction::CycleShadowFilter,
+                            "anisotropy" => return MenuAction::CycleAnisotropy,
+                            "mip_bias" => return MenuAction::CycleMipBias,
+                            "lod_preset" => return MenuAction::CycleLodPreset,
+                            "lod_advanced" => return MenuAction::ToggleLodAdvanced,
+                            "water_reflections" => return MenuAction::ToggleWaterReflections,
+                            "clouds" => return MenuAction::CycleClouds,
+                            "bloom" => return MenuAction::ToggleBloom,
+                            "tonemapping" => return MenuAction::ToggleTonemapping,
+                            "vignette" => return MenuAction::ToggleVignette,
+                            "exposure" => return MenuAction::CycleExposure,
+                            "anti_aliasing" => return MenuAction::CycleAntiAliasing,
+                            "dynamic_resolution" => return MenuAction::ToggleDynamicResolution,
+                            "haptic_break" => return MenuAction::ToggleHaptic(HapticEvent::BlockBreak),
+                            "haptic_damage" => return MenuAction::ToggleHaptic(HapticEvent::Damage),
+                            "haptic_ui" => return MenuAction::ToggleHaptic(HapticEvent::UiConfirm),
+                            "invert_y" => return MenuAction::ToggleInvertY,
+                            "raw_input" => return MenuAction::ToggleRawInput,
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            MenuState::ResourcePacks => {
+                for (i, elem) in self.pack_elements.iter().enumerate() {
+                    if elem.contains(mx, my) {
+                        match elem.id {
+                            "pack_toggle" => return MenuAction::TogglePack(i / 3),
+                            "pack_up" => return MenuAction::MovePackUp(i / 3),
+                            "pack_down" => return MenuAction::MovePackDown(i / 3),
+                            "pack_apply" => return MenuAction::ApplyPacks,
+                            "pack_back" => {
+                                self.current_state = MenuState::Settings;
+                                return MenuAction::Settings;
+                            }
                             _ => {}
                         }
                     }
@@ -538,8 +920,9 @@ impl MenuSystem {
             return;
         }
         
+        let mut lod_changed = false;
         for elem in &mut self.settings_elements {
-            if elem.element_type == ElementType::Slider {
+            if elem.element_type == ElementType::Slider && elem.visible {
                 // Расширенная зона для слайдера (по высоте)
                 let slider_hit_height = 20.0;
                 let in_y = my >= elem.y - slider_hit_height / 2.0 
@@ -548,9 +931,15 @@ impl MenuSystem {
                 
                 if in_x && in_y {
                     elem.value = ((mx - elem.x) / elem.width).clamp(0.0, 1.0);
+                    lod_changed |= elem.id.starts_with("lod");
                 }
             }
         }
+        
+        // Ручная настройка может совпасть с уровнем или увести в Custom
+        if lod_changed {
+            self.refresh_lod_preset();
+        }
     }
     
     pub fn render<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>, queue: &wgpu::Queue) {
@@ -572,8 +961,10 @@ impl MenuSystem {
             menu_state: match self.current_state {
                 MenuState::Main => 0.0,
                 MenuState::Settings => 1.0,
+                MenuState::ResourcePacks => 2.0,
                 MenuState::Hidden => 0.0,
             },
+            accent: self.accent,
         };
         queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
         
@@ -591,7 +982,7 @@ impl MenuSystem {
         // 2. Панель
         let panel = match self.current_state {
             MenuState::Main => &self.panel_main,
-            MenuState::Settings => &self.panel_settings,
+            MenuState::Settings | MenuState::ResourcePacks => &self.panel_settings,
             MenuState::Hidden => &self.panel_main,
         };
         instances.push(MenuInstance {
@@ -605,6 +996,7 @@ impl MenuSystem {
         let elements = match self.current_state {
             MenuState::Main => &self.main_elements,
             MenuState::Settings => &self.settings_elements,
+            MenuState::ResourcePacks => &self.pack_elements,
             MenuState::Hidden => &self.main_elements,
         };
         
@@ -661,6 +1053,156 @@ impl MenuSystem {
         values
     }
     
+    /// Получить качество дальних LOD из слайдера (0-1)
+    pub fn get_lod_quality(&self) -> f32 {
+        self.settings_elements
+            .iter()
+            .find(|e| e.id == "lod_quality")
+            .map_or(1.0, |e| e.value)
+    }
+    
+    /// Получить вертикальную дальность из слайдера в секциях
+    /// (None - весь столбец, крайнее правое положение)
+    pub fn get_vertical_distance(&self) -> Option<i32> {
+        let value = self.settings_elements
+            .iter()
+            .find(|e| e.id == "vertical_distance")
+            .map_or(1.0, |e| e.value);
+        let max_sections = SECTION_COUNT as i32;
+        let sections = (MIN_VERTICAL_SECTIONS as f32 + value * (max_sections - MIN_VERTICAL_SECTIONS) as f32).round() as i32;
+        (sections < max_sections).then_some(sections)
+    }
+    
+    /// Выставить LOD слайдеры (0-1)
+    pub fn set_lod_values(&mut self, values: [f32; 4]) {
+        for (elem, value) in self.settings_elements.iter_mut().take(4).zip(values) {
+            elem.value = value.clamp(0.0, 1.0);
+        }
+        self.refresh_lod_preset();
+    }
+    
+    /// Выставить слайдер качества дальних LOD (0-1)
+    pub fn set_lod_quality(&mut self, quality: f32) {
+        if let Some(elem) = self.settings_elements.iter_mut().find(|e| e.id == "lod_quality") {
+            elem.value = quality.clamp(0.0, 1.0);
+        }
+        self.refresh_lod_preset();
+    }
+    
+    /// Уровень качества, которому соответствуют слайдеры (None - Custom)
+    pub fn get_lod_preset(&self) -> Option<LodPreset> {
+        LodPreset::detect(self.get_lod_values(), self.get_lod_quality())
+    }
+    
+    /// Выставить слайдеры LOD и качество по уровню
+    pub fn set_lod_preset(&mut self, preset: LodPreset) {
+        self.set_lod_values(preset.sliders());
+        self.set_lod_quality(preset.quality());
+    }
+    
+    /// Показать или скрыть слайдеры LOD (панель Advanced)
+    pub fn set_lod_advanced(&mut self, advanced: bool) {
+        self.lod_advanced = advanced;
+        for elem in &mut self.settings_elements {
+            match elem.id {
+                "lod0" | "lod1" | "lod2" | "lod3" | "lod_quality" => elem.visible = advanced,
+                "lod_advanced" => {
+                    elem.label = tr_args("menu.advanced", &[("value", &on_off(advanced))]);
+                }
+                _ => {}
+            }
+        }
+        self.update_layout();
+    }
+    
+    pub fn toggle_lod_advanced(&mut self) {
+        self.set_lod_advanced(!self.lod_advanced);
+    }
+    
+    /// Обновить подпись кнопки уровня качества по слайдерам
+    fn refresh_lod_preset(&mut self) {
+        let name = self.get_lod_preset().map_or_else(|| tr("menu.quality_custom"), |preset| preset.name().to_string());
+        if let Some(elem) = self.settings_elements.iter_mut().find(|e| e.id == "lod_preset") {
+            elem.label = tr_args("menu.quality", &[("value", &name)]);
+        }
+    }
+    
+    /// Выставить слайдер вертикальной дальности (None - весь столбец)
+    pub fn set_vertical_distance(&mut self, sections: Option<i32>) {
+        let max_sections = SECTION_COUNT as i32;
+        let value = match sections {
+            Some(sections) => (sections - MIN_VERTICAL_SECTIONS) as f32 / (max_sections - MIN_VERTICAL_SECTIONS) as f32,
+            None => 1.0,
+        };
+        if let Some(elem) = self.settings_elements.iter_mut().find(|e| e.id == "vertical_distance") {
+            elem.value = value.clamp(0.0, 1.0);
+        }
+    }
+    
+    /// Выставить слайдер FOV (градусы)
+    pub fn set_fov(&mut self, degrees: f32) {
+        if let Some(elem) = self.settings_elements.iter_mut().find(|e| e.id == "fov") {
+            elem.value = ((degrees - MIN_FOV) / (MAX_FOV - MIN_FOV)).clamp(0.0, 1.0);
+        }
+    }
+    
+    /// Выставить слайдер и подписи переключателей вибрации
+    pub fn set_haptics(&mut self, haptics: &HapticSettings) {
+        for elem in &mut self.settings_elements {
+            let event = match elem.id {
+                "haptic_intensity" => {
+                    elem.value = haptics.intensity.clamp(0.0, 1.0);
+                    continue;
+                }
+                "haptic_break" => HapticEvent::BlockBreak,
+                "haptic_damage" => HapticEvent::Damage,
+                "haptic_ui" => HapticEvent::UiConfirm,
+                _ => continue,
+            };
+            elem.label = format!("{}: {}", event.name(), on_off(haptics.is_enabled(event)));
+        }
+    }
+    
+    /// Получить интенсивность вибрации из слайдера (0-1)
+    pub fn get_haptic_intensity(&self) -> f32 {
+        self.settings_elements
+            .iter()
+            .find(|e| e.id == "haptic_intensity")
+            .map_or(0.6, |e| e.value)
+    }
+    
+    /// Выставить слайдер чувствительности и подписи переключателей мыши
+    pub fn set_mouse(&mut self, sensitivity: f32, invert_y: bool, raw_input: bool) {
+        for elem in &mut self.settings_elements {
+            match elem.id {
+                "mouse_sensitivity" => {
+                    elem.value = ((sensitivity - MIN_MOUSE_SENSITIVITY) / (MAX_MOUSE_SENSITIVITY - MIN_MOUSE_SENSITIVITY)).clamp(0.0, 1.0);
+                }
+                "invert_y" => elem.label = tr_args("menu.invert_y", &[("value", &on_off(invert_y))]),
+                "raw_input" => elem.label = tr_args("menu.raw_input", &[("value", &on_off(raw_input))]),
+                _ => {}
+            }
+        }
+    }
+    
+    /// Получить чувствительность мыши из слайдера
+    pub fn get_mouse_sensitivity(&self) -> f32 {
+        let value = self.settings_elements
+            .iter()
+            .find(|e| e.id == "mouse_sensitivity")
+            .map_or(0.5, |e| e.value);
+        MIN_MOUSE_SENSITIVITY + value * (MAX_MOUSE_SENSITIVITY - MIN_MOUSE_SENSITIVITY)
+    }
+    
+    /// Получить значение слайдера FOV (градусы)
+    pub fn get_fov(&self) -> f32 {
+        let value = self.settings_elements
+            .iter()
+            .find(|e| e.id == "fov")
+            .map_or(0.5, |e| e.value);
+        (MIN_FOV + value * (MAX_FOV - MIN_FOV)).round()
+    }
+    
     /// Получить параметры текста для рендеринга
     pub fn get_text_params(&self) -> Vec<super::TextParams> {
         use super::{TextParams, TextAlign};
@@ -685,7 +1227,7 @@ impl MenuSystem {
                 texts.push(TextParams {
                     x: cx,
                     y: self.panel_main.y + 55.0,
-                    text: "World: Creative_Zone_01".to_string(),
+                    text: tr_args("menu.world", &[("name", &"Creative_Zone_01")]),
                     size: 12.0,
                     color: [1.0, 1.0, 1.0, 0.5],
                     align: TextAlign::Center,
@@ -694,7 +1236,6 @@ impl MenuSystem {
                 
                 // Текст кнопок
                 for elem in &self.main_elements {
-                    println!("Button text: '{}' at ({}, {})", elem.label, elem.x + elem.width / 2.0, elem.y + elem.height / 2.0 - 8.0);
                     texts.push(TextParams {
                         x: elem.x + elem.width / 2.0,
                         y: elem.y + elem.height / 2.0 - 8.0,
@@ -711,7 +1252,7 @@ impl MenuSystem {
                 texts.push(TextParams {
                     x: cx,
                     y: self.panel_settings.y + 30.0,
-                    text: "Settings".to_string(),
+                    text: tr("menu.settings"),
                     size: 22.0,
                     color: [0.0, 0.94, 1.0, 1.0],
                     align: TextAlign::Center,
@@ -722,15 +1263,28 @@ impl MenuSystem {
                 texts.push(TextParams {
                     x: self.panel_settings.x + 30.0,
                     y: self.panel_settings.y + 75.0,
-                    text: "LOD Distances".to_string(),
+                    text: tr("menu.view_distance"),
                     size: 11.0,
                     color: [1.0, 1.0, 1.0, 0.5],
                     align: TextAlign::Left,
                     max_width: None,
                 });
                 
+                // Секция графики
+                if let Some(first) = self.settings_elements.get(7) {
+                    texts.push(TextParams {
+                        x: self.panel_settings.x + 30.0,
+                        y: first.y - 20.0,
+                        text: tr("menu.graphics"),
+                        size: 11.0,
+                        color: [1.0, 1.0, 1.0, 0.5],
+                        align: TextAlign::Left,
+                        max_width: None,
+                    });
+                }
+                
                 // Лейблы и значения слайдеров
-                for (i, elem) in self.settings_elements.iter().take(4).enumerate() {
+                for (i, elem) in self.settings_elements.iter().take(4).enumerate().filter(|(_, e)| e.visible) {
                     let lod_name = format!("LOD{}", i);
                     let lod_value = format!("{}", (elem.value * 512.0) as i32);
                     
@@ -757,8 +1311,42 @@ impl MenuSystem {
                     });
                 }
                 
+                // Слайдеры FOV, вибрации, качества LOD, мыши и разрешения
+                for elem in self.settings_elements.iter().skip(11).filter(|e| e.visible) {
+                    let (name, value) = match elem.id {
+                        "fov" => ("menu.fov", format!("{}", self.get_fov() as i32)),
+                        "haptic_intensity" => ("menu.vibration", format!("{}%", (elem.value * 100.0).round() as i32)),
+                        "lod_quality" => ("menu.lod_quality", format!("{}%", (elem.value * 100.0).round() as i32)),
+                        "mouse_sensitivity" => ("menu.mouse_sensitivity", format!("{:.2}", self.get_mouse_sensitivity())),
+                        "render_scale" => ("menu.render_scale", format!("{}%", (self.get_render_scale() * 100.0).round() as i32)),
+                        "vertical_distance" => ("menu.vertical", match self.get_vertical_distance() {
+                            Some(sections) => format!("±{}", sections * SECTION_SIZE),
+                            None => tr("menu.vertical_all"),
+                        }),
+                        _ => continue,
+                    };
+                    texts.push(TextParams {
+                        x: elem.x,
+                        y: elem.y - 18.0,
+                        text: tr(name),
+                        size: 14.0,
+                        color: [1.0, 1.0, 1.0, 1.0],
+                        align: TextAlign::Left,
+                        max_width: None,
+                    });
+                    texts.push(TextParams {
+                        x: elem.x + elem.width,
+                        y: elem.y - 18.0,
+                        text: value,
+                        size: 14.0,
+                        color: [0.0, 0.94, 1.0, 1.0],
+                        align: TextAlign::Right,
+                        max_width: None,
+                    });
+                }
+                
                 // Текст кнопок
-                for elem in self.settings_elements.iter().skip(4) {
+                for elem in self.settings_elements.iter().skip(4).filter(|e| e.element_type != ElementType::Slider) {
                     texts.push(TextParams {
                         x: elem.x + elem.width / 2.0,
                         y: elem.y + elem.height / 2.0 - 8.0,
@@ -774,6 +1362,49 @@ impl MenuSystem {
                     });
                 }
             }
+            MenuState::ResourcePacks => {
+                let accent = [self.accent[0], self.accent[1], self.accent[2], 1.0];
+                texts.push(TextParams {
+                    x: cx,
+                    y: self.panel_settings.y + 30.0,
+                    text: tr("menu.packs"),
+                    size: 22.0,
+                    color: accent,
+                    align: TextAlign::Center,
+                    max_width: None,
+                });
+                
+                let hint = if self.packs.is_empty() {
+                    tr_args("menu.no_packs", &[("dir", &crate::gpu::resource_pack::RESOURCE_PACKS_DIR)])
+                } else {
+                    tr("menu.pack_priority")
+                };
+                texts.push(TextParams {
+                    x: self.panel_settings.x + 20.0,
+                    y: self.panel_settings.y + 58.0,
+                    text: hint,
+                    size: 11.0,
+                    color: [1.0, 1.0, 1.0, 0.5],
+                    align: TextAlign::Left,
+                    max_width: None,
+                });
+                
+                for elem in &self.pack_elements {
+                    texts.push(TextParams {
+                        x: elem.x + elem.width / 2.0,
+                        y: elem.y + elem.height / 2.0 - 8.0,
+                        text: elem.label.clone(),
+                        size: 14.0,
+                        color: if elem.element_type == ElementType::ButtonPrimary {
+                            [0.0, 0.0, 0.0, 1.0]
+                        } else {
+                            [1.0, 1.0, 1.0, 1.0]
+                        },
+                        align: TextAlign::Center,
+                        max_width: Some(elem.width - 12.0),
+                    });
+                }
+            }
             MenuState::Hidden => {}
         }
         
@@ -7,7 +7,8 @@ struct GlobalUniforms {
     view_proj: mat4x4<f32>,
     screen_size: vec2<f32>,
     time: f32,
-    menu_state: f32, // 0: main, 1: settings
+    menu_state: f32, // 0: main, 1: settings, 2: resource packs
+    accent: vec4<f32>, // Акцентный цвет (из пакета ресурсов, по умолчанию cyan)
 }
 
 @group(0) @binding(0) var<uniform> global: GlobalUniforms;
@@ -33,7 +34,6 @@ struct VertexOutput {
 }
 
 // Цветовая палитра Hytale
-const ACCENT: vec3<f32> = vec3<f32>(0.0, 0.94, 1.0);           // #00f0ff - cyan
 const BG_BLUR: vec4<f32> = vec4<f32>(0.039, 0.071, 0.11, 0.85); // rgba(10, 18, 28, 0.85)
 const TEXT_MAIN: vec3<f32> = vec3<f32>(1.0, 1.0, 1.0);
 const TEXT_DIM: vec3<f32> = vec3<f32>(1.0, 1.0, 1.0);
@@ -135,7 +135,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     
     // ========== PRIMARY BUTTON (state 2) - Акцентная кнопка ==========
     if (in.state == 2u) {
-        var color = vec4<f32>(ACCENT, 1.0);
+        var color = vec4<f32>(global.accent.rgb, 1.0);
         
         // Hover эффект - подсветка
         let hover_glow = glow(d, 0.3, 0.1);
@@ -186,14 +186,14 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         // Акцентная рамка
         if (d > -2.0) {
             let border_alpha = 1.0 - (-d / 2.0);
-            color = mix(color, vec4<f32>(ACCENT, 0.8), border_alpha);
+            color = mix(color, vec4<f32>(global.accent.rgb, 0.8), border_alpha);
         }
         
         // Glow эффект
         let g = glow(d, 0.15, 0.08);
-        color.r += ACCENT.r * g;
-        color.g += ACCENT.g * g;
-        color.b += ACCENT.b * g;
+        color.r += global.accent.rgb.r * g;
+        color.g += global.accent.rgb.g * g;
+        color.b += global.accent.rgb.b * g;
         
         // Сдвиг вправо (анимация) - имитируем через градиент
         let shift_gradient = smoothstep(0.0, 0.1, in.uv.x);
@@ -233,7 +233,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
             let fill_width = w * slider_value;
             if (px.x < fill_width) {
                 let fill_progress = px.x / max(fill_width, 1.0);
-                color = vec4<f32>(ACCENT * (0.7 + fill_progress * 0.3), 0.9);
+                color = vec4<f32>(global.accent.rgb * (0.7 + fill_progress * 0.3), 0.9);
             }
         }
         
@@ -247,11 +247,11 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
             // Glow вокруг ползунка
             if (thumb_dist >= thumb_radius) {
                 let glow_alpha = 1.0 - (thumb_dist - thumb_radius) / 2.0;
-                color = mix(color, vec4<f32>(ACCENT, 0.5), glow_alpha * 0.5);
+                color = mix(color, vec4<f32>(global.accent.rgb, 0.5), glow_alpha * 0.5);
             } else {
                 // Сам ползунок
                 let inner_alpha = 1.0 - thumb_dist / thumb_radius;
-                color = vec4<f32>(ACCENT, 0.9 + inner_alpha * 0.1);
+                color = vec4<f32>(global.accent.rgb, 0.9 + inner_alpha * 0.1);
                 
                 // Блик сверху
                 if (px.y < thumb_center.y - thumb_radius * 0.3) {
@@ -298,7 +298,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         // Подчёркивание
         if (in.uv.y > 0.9) {
             let line_alpha = smoothstep(0.9, 1.0, in.uv.y);
-            color = vec4<f32>(ACCENT * 0.5, line_alpha * 0.3);
+            color = vec4<f32>(global.accent.rgb * 0.5, line_alpha * 0.3);
         }
         
         return color;
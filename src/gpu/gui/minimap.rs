@@ -0,0 +1,491 @@
+// ============================================
+// Minimap - Карта окрестностей в углу экрана
+// ============================================
+// Цвета верхних блоков чанков лежат в текстуре 512x512, кольцевой по
+// мировым координатам: чанк (cx, cz) занимает слот (cx mod 32, cz mod 32).
+// Текстура обновляется по чанку: новые и изменённые чанки растеризует
+// MinimapSystem (несколько за кадр), сюда приходят готовые цвета.
+//
+// Север (-Z) сверху, восток (+X) справа; стрелка в центре - игрок.
+// N переключает масштаб (64, 128, 256 блоков по ширине) и скрывает карту.
+//
+// TileSlots - общий учёт кольцевой текстуры, им же пользуется карта мира.
+
+use std::collections::{HashMap, HashSet};
+
+use bytemuck::{Pod, Zeroable};
+use ultraviolet::Vec3;
+use wgpu::util::DeviceExt;
+
+use crate::gpu::terrain::CHUNK_SIZE;
+
+use super::text::{TextParams, TextAlign};
+
+/// Текселей в чанке (1 тексель - 1 столбец блоков)
+pub const CHUNK_TEXELS: usize = (CHUNK_SIZE * CHUNK_SIZE) as usize;
+/// Цвета столбцов чанка (RGBA, строка - одна z)
+pub type ChunkColors = [[u8; 4]; CHUNK_TEXELS];
+
+/// Чанков по стороне текстуры
+const TEXTURE_CHUNKS: i32 = 32;
+const TEXTURE_SIZE: u32 = (TEXTURE_CHUNKS * CHUNK_SIZE) as u32;
+/// Блоков по ширине карты для уровней масштаба
+const ZOOM_LEVELS: [f32; 3] = [64.0, 128.0, 256.0];
+/// Дальше этого (чанки) цвета выбрасываются
+const CACHE_RADIUS: i32 = 24;
+
+/// Размер карты и отступы на экране (пиксели)
+const MAP_SIZE: f32 = 176.0;
+const MARGIN: f32 = 10.0;
+/// Под часами в правом верхнем углу
+const TOP_OFFSET: f32 = 36.0;
+const FONT_SIZE: f32 = 16.0;
+
+/// Тексель без данных: карта рисует фон
+const UNKNOWN: [u8; 4] = [0, 0, 0, 0];
+/// Чанк без данных
+pub(super) const BLANK_TILE: ChunkColors = [UNKNOWN; CHUNK_TEXELS];
+
+/// Что лежит в слоте текстуры: чанк и есть ли его цвета
+type Slot = Option<((i32, i32), bool)>;
+
+/// Содержимое слотов кольцевой текстуры side x side чанков
+pub(super) struct TileSlots {
+    side: i32,
+    slots: Vec<Slot>,
+}
+
+impl TileSlots {
+    pub(super) fn new(side: i32) -> Self {
+        Self { side, slots: vec![None; (side * side) as usize] }
+    }
+
+    /// У чанка новые цвета: его слот перепишется
+    pub(super) fn invalidate(&mut self, chunk: (i32, i32)) {
+        let slot = slot_index(chunk, self.side);
+        if self.slots[slot].is_some_and(|(owner, _)| owner == chunk) {
+            self.slots[slot] = None;
+        }
+    }
+
+    /// Чанки области [min, max], чьи слоты надо переписать
+    /// (has_colors - есть ли у чанка цвета)
+    pub(super) fn outdated(
+        &mut self,
+        min: (i32, i32),
+        max: (i32, i32),
+        has_colors: impl Fn((i32, i32)) -> bool,
+    ) -> Vec<(i32, i32)> {
+        let mut chunks = Vec::new();
+        for cz in min.1..=max.1 {
+            for cx in min.0..=max.0 {
+                let chunk = (cx, cz);
+                let wanted = Some((chunk, has_colors(chunk)));
+                let slot = slot_index(chunk, self.side);
+                if self.slots[slot] != wanted {
+                    self.slots[slot] = wanted;
+                    chunks.push(chunk);
+                }
+            }
+        }
+        chunks
+    }
+
+    /// Записать цвета чанка в его слот текстуры
+    pub(super) fn write(&self, queue: &wgpu::Queue, texture: &wgpu::Texture, chunk: (i32, i32), colors: &ChunkColors) {
+        let slot = slot_index(chunk, self.side) as u32;
+        let chunks = self.side as u32;
+        let size = CHUNK_SIZE as u32;
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d { x: slot % chunks * size, y: slot / chunks * size, z: 0 },
+                aspect: wgpu::TextureAspect::All,
+            },
+            bytemuck::cast_slice(colors.as_slice()),
+            wgpu::TexelCopyBufferLayout { offset: 0, bytes_per_row: Some(size * 4), rows_per_image: Some(size) },
+            wgpu::Extent3d { width: size, height: size, depth_or_array_layers: 1 },
+        );
+    }
+}
+
+/// Текстура цветов чанков side x side (кольцевая)
+pub(super) fn create_tile_texture(device: &wgpu::Device, label: &str, side: i32) -> wgpu::Texture {
+    let size = (side * CHUNK_SIZE) as u32;
+    device.create_texture(&wgpu::TextureDescriptor {
+        label: Some(label),
+        size: wgpu::Extent3d { width: size, height: size, depth_or_array_layers: 1 },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    })
+}
+
+/// Цвета чанков вокруг игрока и содержимое слотов текстуры
+pub struct MinimapTiles {
+    colors: HashMap<(i32, i32), Box<ChunkColors>>,
+    /// Загруженные или изменённые чанки, ждущие растеризации
+    pending: HashSet<(i32, i32)>,
+    slots: TileSlots,
+}
+
+impl MinimapTiles {
+    pub fn new() -> Self {
+        Self {
+            colors: HashMap::new(),
+            pending: HashSet::new(),
+            slots: TileSlots::new(TEXTURE_CHUNKS),
+        }
+    }
+
+    /// Чанк загружен - растеризовать
+    pub fn mark_loaded(&mut self, chunk: (i32, i32)) {
+        self.pending.insert(chunk);
+    }
+
+    /// Чанк изменён - перерисовать, если он уже на карте
+    pub fn mark_changed(&mut self, chunk: (i32, i32)) {
+        if self.colors.contains_key(&chunk) {
+            self.pending.insert(chunk);
+        }
+    }
+
+    /// До limit ближайших к center чанков из очереди (дальние выбрасываются)
+    pub fn take_pending(&mut self, center: (i32, i32), limit: usize) -> Vec<(i32, i32)> {
+        self.pending.retain(|&chunk| chebyshev(chunk, center) <= CACHE_RADIUS);
+        let mut nearest: Vec<(i32, i32)> = self.pending.iter().copied().collect();
+        nearest.sort_unstable_by_key(|&chunk| (chebyshev(chunk, center), chunk));
+        nearest.truncate(limit);
+        for chunk in &nearest {
+            self.pending.remove(chunk);
+        }
+        nearest
+    }
+
+    /// Новые цвета чанка: слот перепишется при следующей подготовке
+    pub fn store(&mut self, chunk: (i32, i32), colors: ChunkColors) {
+        self.colors.insert(chunk, Box::new(colors));
+        self.slots.invalidate(chunk);
+    }
+
+    /// Забыть чанки дальше CACHE_RADIUS
+    pub fn evict(&mut self, center: (i32, i32)) {
+        self.colors.retain(|&chunk, _| chebyshev(chunk, center) <= CACHE_RADIUS);
+    }
+
+    /// Слоты обзора radius вокруг center, которые надо переписать:
+    /// чанк и его цвета (None - цветов ещё нет)
+    fn outdated(&mut self, center: (i32, i32), radius: i32) -> Vec<((i32, i32), Option<&ChunkColors>)> {
+        let colors = &self.colors;
+        let min = (center.0 - radius, center.1 - radius);
+        let max = (center.0 + radius, center.1 + radius);
+        let chunks = self.slots.outdated(min, max, |chunk| colors.contains_key(&chunk));
+        chunks.into_iter().map(|chunk| (chunk, colors.get(&chunk).map(|colors| &**colors))).collect()
+    }
+}
+
+impl Default for MinimapTiles {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn chebyshev(a: (i32, i32), b: (i32, i32)) -> i32 {
+    (a.0 - b.0).abs().max((a.1 - b.1).abs())
+}
+
+/// Индекс слота чанка в кольцевой текстуре side x side
+fn slot_index((cx, cz): (i32, i32), side: i32) -> usize {
+    (cz.rem_euclid(side) * side + cx.rem_euclid(side)) as usize
+}
+
+/// Цвет столбца: верхний блок с рельефной тенью (склон к северу светлее,
+/// от севера - темнее), под водой - синий тем темнее, чем глубже
+pub fn column_color(top: [f32; 3], height: i32, north_height: i32, water_depth: i32) -> [u8; 4] {
+    let shade = match height.cmp(&north_height) {
+        std::cmp::Ordering::Greater => 1.1,
+        std::cmp::Ordering::Less => 0.8,
+        std::cmp::Ordering::Equal => 0.95,
+    };
+    let mut color = top.map(|c| c * shade);
+    if water_depth > 0 {
+        let water = [0.15, 0.35, 0.75];
+        let opacity = (0.55 + water_depth as f32 * 0.05).min(0.9);
+        for (c, w) in color.iter_mut().zip(water) {
+            *c = *c * (1.0 - opacity) + w * opacity;
+        }
+    }
+    let [r, g, b] = color.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+    [r, g, b, 255]
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct MinimapUniforms {
+    /// Прямоугольник карты в NDC: x0, y0, x1, y1
+    rect: [f32; 4],
+    /// Центр (мировые x, z), блоков по ширине, yaw игрока
+    view: [f32; 4],
+    /// Размер карты в пикселях, размер текстуры, -, -
+    size: [f32; 4],
+}
+
+/// Миникарта на HUD
+pub struct Minimap {
+    tiles: MinimapTiles,
+    texture: wgpu::Texture,
+    pipeline: wgpu::RenderPipeline,
+    uniform_buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+    /// Уровень масштаба, ZOOM_LEVELS.len() - карта скрыта
+    zoom: usize,
+    position: Vec3,
+    yaw: f32,
+    screen_width: f32,
+    screen_height: f32,
+}
+
+impl Minimap {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat, width: u32, height: u32) -> Self {
+        let texture = create_tile_texture(device, "Minimap Texture", TEXTURE_CHUNKS);
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Minimap Uniform Buffer"),
+            contents: bytemuck::cast_slice(&[MinimapUniforms::zeroed()]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Minimap Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Minimap Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: uniform_buffer.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::TextureView(&view) },
+            ],
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Minimap Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/minimap.wgsl").into()),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Minimap Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Minimap Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            tiles: MinimapTiles::new(),
+            texture,
+            pipeline,
+            uniform_buffer,
+            bind_group,
+            zoom: 1,
+            position: Vec3::zero(),
+            yaw: 0.0,
+            screen_width: width as f32,
+            screen_height: height as f32,
+        }
+    }
+
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.screen_width = width as f32;
+        self.screen_height = height as f32;
+    }
+
+    pub fn tiles(&mut self) -> &mut MinimapTiles {
+        &mut self.tiles
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.zoom < ZOOM_LEVELS.len()
+    }
+
+    /// Следующий масштаб (после самого мелкого карта скрывается).
+    /// Блоков по ширине карты, None - скрыта
+    pub fn cycle_zoom(&mut self) -> Option<f32> {
+        self.zoom = (self.zoom + 1) % (ZOOM_LEVELS.len() + 1);
+        ZOOM_LEVELS.get(self.zoom).copied()
+    }
+
+    /// Радиус обзора в чанках (с запасом на край)
+    pub fn view_radius(&self) -> i32 {
+        let blocks = ZOOM_LEVELS.get(self.zoom).copied().unwrap_or(0.0);
+        (blocks / 2.0 / CHUNK_SIZE as f32).ceil() as i32 + 1
+    }
+
+    /// Позиция и взгляд игрока (yaw как в Player)
+    pub fn update(&mut self, position: Vec3, yaw: f32) {
+        self.position = position;
+        self.yaw = yaw;
+    }
+
+    fn rect(&self) -> (f32, f32) {
+        (self.screen_width - MARGIN - MAP_SIZE, TOP_OFFSET)
+    }
+
+    /// Дописать текстуру и параметры; подпись севера
+    pub fn prepare(&mut self, queue: &wgpu::Queue) -> Vec<TextParams> {
+        if !self.is_visible() {
+            return Vec::new();
+        }
+
+        let center = (
+            (self.position.x.floor() as i32).div_euclid(CHUNK_SIZE),
+            (self.position.z.floor() as i32).div_euclid(CHUNK_SIZE),
+        );
+        let radius = self.view_radius();
+        let updates: Vec<((i32, i32), ChunkColors)> = self
+            .tiles
+            .outdated(center, radius)
+            .into_iter()
+            .map(|(chunk, colors)| (chunk, colors.copied().unwrap_or(BLANK_TILE)))
+            .collect();
+        for (chunk, colors) in &updates {
+            self.tiles.slots.write(queue, &self.texture, *chunk, colors);
+        }
+
+        let (x, y) = self.rect();
+        let to_ndc = |px: f32, py: f32| [px / self.screen_width * 2.0 - 1.0, 1.0 - py / self.screen_height * 2.0];
+        let [x0, y0] = to_ndc(x, y);
+        let [x1, y1] = to_ndc(x + MAP_SIZE, y + MAP_SIZE);
+        let uniforms = MinimapUniforms {
+            rect: [x0, y0, x1, y1],
+            view: [self.position.x, self.position.z, ZOOM_LEVELS[self.zoom], self.yaw],
+            size: [MAP_SIZE, TEXTURE_SIZE as f32, 0.0, 0.0],
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+
+        vec![TextParams {
+            x: x + MAP_SIZE / 2.0,
+            y: y + 2.0,
+            text: "N".to_string(),
+            size: FONT_SIZE,
+            color: [1.0, 0.3, 0.25, 1.0],
+            align: TextAlign::Center,
+            max_width: None,
+        }]
+    }
+
+    pub fn render<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+        if !self.is_visible() {
+            return;
+        }
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..6, 0..1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slots_wrap_and_follow_the_view() {
+        assert_eq!(slot_index((0, 0), TEXTURE_CHUNKS), slot_index((TEXTURE_CHUNKS, -TEXTURE_CHUNKS), TEXTURE_CHUNKS));
+        assert_ne!(slot_index((1, 0), TEXTURE_CHUNKS), slot_index((0, 1), TEXTURE_CHUNKS));
+
+        let mut tiles = MinimapTiles::new();
+        // Первый показ: все 9 слотов пустые
+        assert_eq!(tiles.outdated((0, 0), 1).len(), 9);
+        assert!(tiles.outdated((0, 0), 1).is_empty());
+
+        tiles.store((1, 1), [[10, 20, 30, 255]; CHUNK_TEXELS]);
+        let updates = tiles.outdated((0, 0), 1);
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].0, (1, 1));
+        assert!(updates[0].1.is_some());
+
+        // Сдвиг обзора на 32 чанка - те же слоты, но чужие чанки
+        assert_eq!(tiles.outdated((TEXTURE_CHUNKS, 0), 1).len(), 9);
+    }
+
+    #[test]
+    fn pending_chunks_come_nearest_first() {
+        let mut tiles = MinimapTiles::new();
+        for chunk in [(5, 0), (1, 1), (0, 0), (100, 0)] {
+            tiles.mark_loaded(chunk);
+        }
+        // Неизвестный чанк не перерисовывается по изменению
+        tiles.mark_changed((2, 2));
+        assert_eq!(tiles.take_pending((0, 0), 2), vec![(0, 0), (1, 1)]);
+        // Слишком далёкий выброшен
+        assert_eq!(tiles.take_pending((0, 0), 8), vec![(5, 0)]);
+    }
+
+    #[test]
+    fn columns_are_shaded_by_slope_and_water() {
+        let grass = [0.4, 0.7, 0.3];
+        let flat = column_color(grass, 64, 64, 0);
+        let uphill = column_color(grass, 65, 64, 0);
+        let downhill = column_color(grass, 63, 64, 0);
+        assert!(uphill[1] > flat[1] && flat[1] > downhill[1]);
+
+        let shallow = column_color(grass, 60, 60, 1);
+        let deep = column_color(grass, 50, 50, 8);
+        assert!(deep[2] > deep[1] && shallow[1] > deep[1]);
+        assert_eq!(flat[3], 255);
+    }
+}
@@ -5,16 +5,47 @@
 mod menu;
 mod text;
 mod crosshair;
+mod break_overlay;
 mod fps_counter;
+mod console;
+mod compass;
+mod clock;
+mod sculpt_palette;
+mod toast;
+mod log_viewer;
+mod profiler_overlay;
+mod loading_screen;
+mod container;
+mod minimap;
+mod world_map;
+mod beacons;
+mod ui_scale;
 pub mod hotbar;
 pub mod inventory;
 
 pub use menu::{GameMenu, MenuState, MenuAction, MenuSystem};
 pub use text::{TextRenderer, TextParams, TextAlign};
-pub use hotbar::{Hotbar, HotbarItem, HotbarRenderer, HotbarSlot};
-pub use crosshair::{Crosshair, BlockHighlight, UiVertex, WireVertex};
+pub use hotbar::{Hotbar, HotbarRenderer, HotbarSlot};
+pub use crosshair::{Crosshair, BlockHighlight, SelectionBox, UiVertex, WireVertex};
+pub use break_overlay::BreakOverlay;
 pub use fps_counter::FpsCounter;
 pub use inventory::{Inventory, InventoryRenderer};
+pub use console::ConsoleOverlay;
+pub use compass::Compass;
+pub use clock::Clock;
+pub use sculpt_palette::SculptPalette;
+pub use toast::Toasts;
+pub use log_viewer::LogViewer;
+pub use profiler_overlay::ProfilerOverlay;
+pub use loading_screen::LoadingScreen;
+pub use container::ContainerScreen;
+pub use minimap::{Minimap, MinimapTiles, ChunkColors, CHUNK_TEXELS, column_color};
+pub use world_map::WorldMapScreen;
+pub use beacons::Beacons;
+pub use ui_scale::{UiSettings, UiScale, MIN_UI_SCALE, MAX_UI_SCALE, MAX_SAFE_AREA};
+
+use crate::gpu::items::ItemStack;
+use crate::gpu::locale::tr;
 
 /// GPU рендерер для меню
 pub struct GuiRenderer {
@@ -24,6 +55,30 @@ pub struct GuiRenderer {
     hotbar: Hotbar,
     inventory_renderer: inventory::InventoryRenderer,
     inventory: Inventory,
+    /// Экран хранилища блока (сундук)
+    container: ContainerScreen,
+    console_overlay: ConsoleOverlay,
+    compass: Compass,
+    clock: Clock,
+    minimap: Minimap,
+    /// Полноэкранная карта мира (M)
+    world_map: WorldMapScreen,
+    /// Маяки меток на экране
+    beacons: Beacons,
+    sculpt_palette: SculptPalette,
+    toasts: Toasts,
+    log_viewer: LogViewer,
+    profiler_overlay: ProfilerOverlay,
+    loading_screen: LoadingScreen,
+    /// Масштаб интерфейса и безопасная зона
+    ui_settings: UiSettings,
+    /// Множитель DPI окна (для автоматического масштаба)
+    dpi: f64,
+    ui_scale: UiScale,
+    /// Размер окна (физические пиксели)
+    window_width: u32,
+    window_height: u32,
+    /// Размер экрана в пикселях интерфейса
     screen_width: u32,
     screen_height: u32,
 }
@@ -43,28 +98,84 @@ impl GuiRenderer {
         let hotbar = Hotbar::new();
         let inventory_renderer = inventory::InventoryRenderer::new(device, format, width, height);
         let inventory = Inventory::new();
+        let console_overlay = ConsoleOverlay::new(device, format, width, height);
+        let log_viewer = LogViewer::new(device, format, width, height);
+        let profiler_overlay = ProfilerOverlay::new(device, format, width, height);
+        let loading_screen = LoadingScreen::new(device, format, width, height);
+        let minimap = Minimap::new(device, format, width, height);
+        let world_map = WorldMapScreen::new(device, format, width, height);
+        let beacons = Beacons::new(device, format, width, height);
         
-        Self { 
+        let mut gui = Self { 
             menu_system,
             text_renderer,
             hotbar_renderer,
             hotbar,
             inventory_renderer,
             inventory,
+            container: ContainerScreen::new(),
+            console_overlay,
+            compass: Compass::new(),
+            clock: Clock::new(),
+            minimap,
+            world_map,
+            beacons,
+            sculpt_palette: SculptPalette::new(),
+            toasts: Toasts::new(),
+            log_viewer,
+            profiler_overlay,
+            loading_screen,
+            ui_settings: UiSettings::default(),
+            dpi: 1.0,
+            ui_scale: UiScale::default(),
+            window_width: width,
+            window_height: height,
             screen_width: width,
             screen_height: height,
-        }
+        };
+        gui.resize(queue, width, height);
+        gui
     }
     
-    pub fn resize(&mut self, queue: &wgpu::Queue, width: u32, height: u32) {
+    /// Размер окна в физических пикселях; виджеты получают размер в
+    /// пикселях интерфейса
+    pub fn resize(&mut self, queue: &wgpu::Queue, window_width: u32, window_height: u32) {
+        self.window_width = window_width;
+        self.window_height = window_height;
+        self.ui_scale = UiScale::new(&self.ui_settings, self.dpi, window_width, window_height);
+        self.text_renderer.resize(queue, window_width, window_height, self.ui_scale);
+        
+        let (width, height) = self.ui_scale.logical_size();
         self.menu_system.resize(width, height);
-        self.text_renderer.resize(queue, width, height);
         self.hotbar_renderer.resize(width, height);
         self.inventory_renderer.resize(width, height);
+        self.console_overlay.resize(width, height);
+        self.log_viewer.resize(width, height);
+        self.profiler_overlay.resize(width, height);
+        self.loading_screen.resize(width, height);
+        self.minimap.resize(width, height);
+        self.world_map.resize(width, height);
+        self.beacons.resize(self.ui_scale, width, height);
         self.screen_width = width;
         self.screen_height = height;
     }
     
+    /// Новые настройки масштаба (меню, /ui)
+    pub fn set_ui_settings(&mut self, queue: &wgpu::Queue, settings: &UiSettings) {
+        self.ui_settings = *settings;
+        self.resize(queue, self.window_width, self.window_height);
+    }
+    
+    /// Окно перенесли на монитор с другим DPI
+    pub fn set_dpi(&mut self, queue: &wgpu::Queue, dpi: f64) {
+        self.dpi = dpi;
+        self.resize(queue, self.window_width, self.window_height);
+    }
+    
+    pub fn ui_scale(&self) -> &UiScale {
+        &self.ui_scale
+    }
+    
     pub fn menu_system(&mut self) -> &mut MenuSystem {
         &mut self.menu_system
     }
@@ -73,6 +184,16 @@ impl GuiRenderer {
         &mut self.hotbar
     }
     
+    /// Применить цвета интерфейса из пакетов ресурсов
+    pub fn apply_ui_colors(&mut self, colors: &crate::gpu::resource_pack::UiColors) {
+        self.menu_system.set_accent(colors.accent);
+        self.hotbar_renderer.set_accent(colors.accent);
+    }
+    
+    pub fn hotbar_ref(&self) -> &Hotbar {
+        &self.hotbar
+    }
+    
     pub fn inventory(&mut self) -> &mut Inventory {
         &mut self.inventory
     }
@@ -89,10 +210,122 @@ impl GuiRenderer {
         &mut self.inventory_renderer
     }
     
+    pub fn container(&mut self) -> &mut ContainerScreen {
+        &mut self.container
+    }
+    
+    pub fn container_ref(&self) -> &ContainerScreen {
+        &self.container
+    }
+    
+    pub fn compass(&mut self) -> &mut Compass {
+        &mut self.compass
+    }
+    
+    pub fn clock(&mut self) -> &mut Clock {
+        &mut self.clock
+    }
+    
+    pub fn minimap(&mut self) -> &mut Minimap {
+        &mut self.minimap
+    }
+    
+    pub fn world_map(&mut self) -> &mut WorldMapScreen {
+        &mut self.world_map
+    }
+    
+    pub fn world_map_ref(&self) -> &WorldMapScreen {
+        &self.world_map
+    }
+
+    pub fn beacons(&mut self) -> &mut Beacons {
+        &mut self.beacons
+    }
+    
+    pub fn sculpt_palette(&mut self) -> &mut SculptPalette {
+        &mut self.sculpt_palette
+    }
+    
+    pub fn toasts(&mut self) -> &mut Toasts {
+        &mut self.toasts
+    }
+    
+    pub fn log_viewer(&mut self) -> &mut LogViewer {
+        &mut self.log_viewer
+    }
+    
+    pub fn profiler_overlay(&mut self) -> &mut ProfilerOverlay {
+        &mut self.profiler_overlay
+    }
+    
+    pub fn loading_screen(&mut self) -> &mut LoadingScreen {
+        &mut self.loading_screen
+    }
+    
     pub fn screen_size(&self) -> (f32, f32) {
         (self.screen_width as f32, self.screen_height as f32)
     }
     
+    /// Рендерит экран загрузки мира (очищает кадр)
+    pub fn render_loading(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        queue: &wgpu::Queue,
+    ) {
+        let texts = self.loading_screen.prepare(queue);
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Loading Screen Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(loading_screen::LOADING_BACKGROUND),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            self.ui_scale.set_viewport(&mut render_pass);
+            
+            self.loading_screen.render(&mut render_pass);
+        }
+        self.text_renderer.render(device, encoder, view, queue, &texts);
+    }
+    
+    /// Числа в правом нижнем углу слотов хотбара и сундука (стопки больше 1)
+    fn stack_count_texts(&self) -> Vec<TextParams> {
+        let (screen_w, screen_h) = self.screen_size();
+        let count_text = |stack: &Option<ItemStack>, (x, y): (f32, f32)| {
+            stack.filter(|s| s.count > 1).map(|s| TextParams {
+                x: x + hotbar::SLOT_SIZE - 6.0,
+                y: y + hotbar::SLOT_SIZE - 22.0,
+                text: s.count.to_string(),
+                size: 16.0,
+                color: [1.0, 1.0, 1.0, 1.0],
+                align: TextAlign::Right,
+                max_width: None,
+            })
+        };
+
+        let mut texts = Vec::new();
+        if self.hotbar.is_visible() {
+            for (i, stack) in self.hotbar.slots().iter().enumerate() {
+                texts.extend(count_text(stack, hotbar::slot_pos(i, screen_w, screen_h)));
+            }
+        }
+        if self.container.is_visible() {
+            for (i, stack) in self.container.slots().iter().enumerate() {
+                texts.extend(count_text(stack, self.container.slot_pos(i, screen_w, screen_h)));
+            }
+        }
+        texts
+    }
+    
     /// Рендерит меню используя encoder (создаёт свой render pass)
     pub fn render(
         &mut self,
@@ -101,6 +334,7 @@ impl GuiRenderer {
         view: &wgpu::TextureView,
         queue: &wgpu::Queue,
         mouse_pos: (f32, f32),
+        console: &crate::gpu::console::Console,
     ) {
         // Рендерим хотбар (всегда, если не в меню)
         if !self.menu_system.is_visible() && self.hotbar.is_visible() {
@@ -118,10 +352,76 @@ impl GuiRenderer {
                 timestamp_writes: None,
                 occlusion_query_set: None,
             });
+            self.ui_scale.set_viewport(&mut render_pass);
             
             self.hotbar_renderer.render(&mut render_pass, queue, &self.hotbar);
         }
         
+        // Экран сундука над хотбаром
+        if self.container.is_visible() {
+            {
+                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Container Render Pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Load,
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+                self.ui_scale.set_viewport(&mut render_pass);
+                
+                self.hotbar_renderer.render_container(&mut render_pass, queue, &self.container);
+            }
+            
+            let (panel_x, panel_y, panel_w, _) = self.container.panel_rect(self.screen_width as f32, self.screen_height as f32);
+            let mut texts = vec![
+                TextParams {
+                    x: panel_x + panel_w / 2.0,
+                    y: panel_y + 20.0,
+                    text: tr(self.container.title()).to_uppercase(),
+                    size: 20.0,
+                    color: [0.0, 0.94, 1.0, 1.0],
+                    align: TextAlign::Center,
+                    max_width: None,
+                },
+            ];
+            texts.extend(self.stack_count_texts());
+            self.text_renderer.render(device, encoder, view, queue, &texts);
+            return;
+        }
+        
+        // Карта мира на весь экран
+        if self.world_map.is_visible() {
+            let texts = self.world_map.prepare(queue);
+            {
+                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("World Map Render Pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Load,
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+                self.ui_scale.set_viewport(&mut render_pass);
+                
+                self.world_map.render(&mut render_pass);
+            }
+            self.text_renderer.render(device, encoder, view, queue, &texts);
+            return;
+        }
+        
         // Рендерим инвентарь
         if self.inventory.is_visible() {
             self.inventory_renderer.update_inventory_scroll(&mut self.inventory);
@@ -141,6 +441,7 @@ impl GuiRenderer {
                     timestamp_writes: None,
                     occlusion_query_set: None,
                 });
+                self.ui_scale.set_viewport(&mut render_pass);
                 
                 self.inventory_renderer.render(&mut render_pass, queue, &self.inventory);
             }
@@ -161,6 +462,7 @@ impl GuiRenderer {
                     timestamp_writes: None,
                     occlusion_query_set: None,
                 });
+                self.ui_scale.set_viewport(&mut render_pass);
                 
                 self.inventory_renderer.render_dragging(&mut render_pass, queue, &self.inventory, mouse_pos.0, mouse_pos.1);
             }
@@ -169,22 +471,143 @@ impl GuiRenderer {
             let (panel_x, panel_y) = self.inventory_renderer.panel_pos();
             let (panel_w, _) = self.inventory_renderer.panel_size();
             
-            let texts = vec![
+            let mut texts = vec![
                 TextParams {
                     x: panel_x + panel_w / 2.0,
                     y: panel_y + 18.0,
-                    text: "INVENTORY".to_string(),
+                    text: tr("inventory.title").to_uppercase(),
                     size: 20.0,
                     color: [0.0, 0.94, 1.0, 1.0],
                     align: TextAlign::Center,
                     max_width: None,
                 },
             ];
+            texts.extend(self.stack_count_texts());
             self.text_renderer.render(device, encoder, view, queue, &texts);
             return;
         }
         
         if !self.menu_system.is_visible() {
+            // Консоль / чат поверх хотбара
+            let mut texts = self.console_overlay.prepare(queue, console);
+            if !texts.is_empty() {
+                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Console Render Pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Load,
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+                self.ui_scale.set_viewport(&mut render_pass);
+                
+                self.console_overlay.render(&mut render_pass);
+            }
+            // Журнал сессии (F3) поверх HUD
+            let log_texts = self.log_viewer.prepare(queue);
+            if !log_texts.is_empty() {
+                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Log Viewer Render Pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Load,
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+                self.ui_scale.set_viewport(&mut render_pass);
+                
+                self.log_viewer.render(&mut render_pass);
+            }
+            texts.extend(log_texts);
+            // Тайминги кадра (F4)
+            let profiler_texts = self.profiler_overlay.prepare(queue);
+            if !profiler_texts.is_empty() {
+                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Profiler Overlay Render Pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Load,
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+                self.ui_scale.set_viewport(&mut render_pass);
+                
+                self.profiler_overlay.render(&mut render_pass);
+            }
+            texts.extend(profiler_texts);
+            // Маяки меток поверх мира
+            if self.hotbar.is_visible() {
+                let beacon_texts = self.beacons.prepare(queue);
+                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Beacons Render Pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Load,
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+                self.ui_scale.set_viewport(&mut render_pass);
+                
+                self.beacons.render(&mut render_pass);
+                texts.extend(beacon_texts);
+            }
+            // Миникарта в правом верхнем углу
+            if self.hotbar.is_visible() && self.minimap.is_visible() {
+                let minimap_texts = self.minimap.prepare(queue);
+                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Minimap Render Pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Load,
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+                self.ui_scale.set_viewport(&mut render_pass);
+                
+                self.minimap.render(&mut render_pass);
+                texts.extend(minimap_texts);
+            }
+            // Компас, часы, палитра лепки и уведомления вместе с остальным HUD
+            if self.hotbar.is_visible() {
+                texts.push(self.compass.text_params(self.screen_width as f32));
+                texts.push(self.clock.text_params(self.screen_width as f32));
+                texts.extend(self.sculpt_palette.text_params(self.screen_width as f32, self.screen_height as f32));
+                texts.extend(self.toasts.text_params(self.screen_width as f32, self.screen_height as f32));
+                texts.extend(self.stack_count_texts());
+            }
+            if !texts.is_empty() {
+                self.text_renderer.render(device, encoder, view, queue, &texts);
+            }
             return;
         }
         
@@ -204,6 +627,7 @@ impl GuiRenderer {
                 timestamp_writes: None,
                 occlusion_query_set: None,
             });
+            self.ui_scale.set_viewport(&mut render_pass);
             
             self.menu_system.render(&mut render_pass, queue);
         }
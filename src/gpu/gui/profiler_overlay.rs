@@ -0,0 +1,194 @@
+// ============================================
+// Profiler Overlay - Тайминги кадра (F4)
+// ============================================
+// Полосы средних замеров gpu::profiler за последние кадры в правом
+// верхнем углу: CPU голубым, GPU оранжевым. Вся ширина полосы -
+// бюджет кадра 60 FPS, длиннее не рисуется. Открытый оверлей включает
+// запись профайлера, закрытый - выключает.
+
+use wgpu::util::DeviceExt;
+
+use crate::gpu::profiler::{self, Timing};
+
+use super::{TextParams, TextAlign, UiVertex};
+
+const MARGIN: f32 = 10.0;
+const WIDTH: f32 = 360.0;
+const LINE_HEIGHT: f32 = 18.0;
+const FONT_SIZE: f32 = 14.0;
+/// Колонки: имя, полоса, миллисекунды
+const LABEL_WIDTH: f32 = 110.0;
+const VALUE_WIDTH: f32 = 70.0;
+const BAR_HEIGHT: f32 = 10.0;
+/// Строк замеров (остальные не показываются)
+const MAX_ROWS: usize = 16;
+/// Бюджет кадра на всю ширину полосы (мс)
+const FRAME_BUDGET_MS: f32 = 1000.0 / 60.0;
+
+const BACKGROUND: [f32; 4] = [0.0, 0.0, 0.0, 0.7];
+const CPU_COLOR: [f32; 4] = [0.35, 0.75, 1.0, 0.9];
+const GPU_COLOR: [f32; 4] = [1.0, 0.6, 0.2, 0.9];
+/// Фон + полосы
+const MAX_VERTICES: usize = (MAX_ROWS + 1) * 6;
+
+/// Оверлей профайлера
+pub struct ProfilerOverlay {
+    pipeline: wgpu::RenderPipeline,
+    vertex_buffer: wgpu::Buffer,
+    vertex_count: u32,
+    visible: bool,
+    screen_width: f32,
+    screen_height: f32,
+}
+
+impl ProfilerOverlay {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat, width: u32, height: u32) -> Self {
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Profiler Overlay Vertex Buffer"),
+            contents: bytemuck::cast_slice(&[UiVertex { position: [0.0; 2], color: [0.0; 4] }; MAX_VERTICES]),
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Profiler Overlay Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/ui.wgsl").into()),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Profiler Overlay Pipeline Layout"),
+            bind_group_layouts: &[],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Profiler Overlay Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[UiVertex::desc()],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            pipeline,
+            vertex_buffer,
+            vertex_count: 0,
+            visible: false,
+            screen_width: width as f32,
+            screen_height: height as f32,
+        }
+    }
+
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.screen_width = width as f32;
+        self.screen_height = height as f32;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Показать/скрыть (вместе с записью профайлера)
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+        profiler::set_enabled(visible);
+    }
+
+    pub fn toggle(&mut self) {
+        self.set_visible(!self.visible);
+    }
+
+    /// Подложка, полосы и подписи. Скрыто - пусто
+    pub fn prepare(&mut self, queue: &wgpu::Queue) -> Vec<TextParams> {
+        self.vertex_count = 0;
+        if !self.visible {
+            return Vec::new();
+        }
+
+        let timings: Vec<Timing> = profiler::recent_timings().into_iter().take(MAX_ROWS).collect();
+        let x0 = self.screen_width - MARGIN - WIDTH;
+        let y0 = MARGIN;
+        let height = (timings.len() + 1) as f32 * LINE_HEIGHT + 8.0;
+        let bar_x = x0 + 6.0 + LABEL_WIDTH;
+        let bar_width = WIDTH - 12.0 - LABEL_WIDTH - VALUE_WIDTH;
+
+        let to_ndc = |px: f32, py: f32| [px / self.screen_width * 2.0 - 1.0, 1.0 - py / self.screen_height * 2.0];
+        let quad = |(x0, y0, x1, y1): (f32, f32, f32, f32), color: [f32; 4]| {
+            [(x0, y0), (x1, y0), (x1, y1), (x0, y0), (x1, y1), (x0, y1)]
+                .map(|(px, py)| UiVertex { position: to_ndc(px, py), color })
+        };
+
+        let mut vertices: Vec<UiVertex> = quad((x0, y0, x0 + WIDTH, y0 + height), BACKGROUND).to_vec();
+        for (i, timing) in timings.iter().enumerate() {
+            let row_y = y0 + 4.0 + (i + 1) as f32 * LINE_HEIGHT + (LINE_HEIGHT - BAR_HEIGHT) / 2.0;
+            let fill = (timing.ms / FRAME_BUDGET_MS).clamp(0.0, 1.0) * bar_width;
+            let color = if timing.gpu { GPU_COLOR } else { CPU_COLOR };
+            vertices.extend(quad((bar_x, row_y, bar_x + fill.max(1.0), row_y + BAR_HEIGHT), color));
+        }
+        queue.write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice(&vertices));
+        self.vertex_count = vertices.len() as u32;
+
+        let header = if timings.is_empty() { "Profiler: collecting..." } else { "Profiler (ms per frame)" };
+        let mut texts = vec![TextParams {
+            x: x0 + 6.0,
+            y: y0 + 4.0,
+            text: header.to_string(),
+            size: FONT_SIZE,
+            color: [0.0, 0.94, 1.0, 1.0],
+            align: TextAlign::Left,
+            max_width: Some(WIDTH - 12.0),
+        }];
+        for (i, timing) in timings.iter().enumerate() {
+            let y = y0 + 4.0 + (i + 1) as f32 * LINE_HEIGHT;
+            let track = if timing.gpu { "gpu" } else { "cpu" };
+            texts.push(TextParams {
+                x: x0 + 6.0,
+                y,
+                text: format!("{} {}", track, timing.name),
+                size: FONT_SIZE,
+                color: [1.0, 1.0, 1.0, 1.0],
+                align: TextAlign::Left,
+                max_width: Some(LABEL_WIDTH),
+            });
+            texts.push(TextParams {
+                x: x0 + WIDTH - 6.0,
+                y,
+                text: format!("{:.2}", timing.ms),
+                size: FONT_SIZE,
+                color: [1.0, 1.0, 1.0, 1.0],
+                align: TextAlign::Right,
+                max_width: None,
+            });
+        }
+        texts
+    }
+
+    pub fn render<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+        if self.vertex_count == 0 {
+            return;
+        }
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.draw(0..self.vertex_count, 0..1);
+    }
+}
@@ -0,0 +1,81 @@
+// ============================================
+// Sculpt Palette - Палитра инструментов лепки
+// ============================================
+// Строка над хотбаром, пока выбран уровень субвокселя: все кисти,
+// текущая в скобках, и размер клетки. B - следующая кисть, Q - уровень.
+
+use crate::gpu::locale::tr;
+use crate::gpu::subvoxel::{SculptTool, SubVoxelLevel};
+
+use super::hotbar::{BOTTOM_PADDING, SLOT_SIZE};
+use super::text::{TextParams, TextAlign};
+
+const FONT_SIZE: f32 = 18.0;
+/// Зазор между строкой и хотбаром
+const GAP: f32 = 28.0;
+
+/// Палитра на HUD
+pub struct SculptPalette {
+    text: String,
+}
+
+impl SculptPalette {
+    pub fn new() -> Self {
+        Self { text: String::new() }
+    }
+
+    /// Пересчитать строку (на полных блоках палитра скрыта)
+    pub fn update(&mut self, tool: SculptTool, level: SubVoxelLevel) {
+        self.text = palette_text(tool, level);
+    }
+
+    pub fn text_params(&self, screen_width: f32, screen_height: f32) -> Option<TextParams> {
+        if self.text.is_empty() {
+            return None;
+        }
+        Some(TextParams {
+            x: screen_width / 2.0,
+            y: screen_height - BOTTOM_PADDING - SLOT_SIZE - GAP - FONT_SIZE,
+            text: self.text.clone(),
+            size: FONT_SIZE,
+            color: [1.0, 1.0, 1.0, 0.9],
+            align: TextAlign::Center,
+            max_width: None,
+        })
+    }
+}
+
+impl Default for SculptPalette {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Текст палитры: "Single  [Box]  Sphere  Erase  |  1/8"
+pub fn palette_text(tool: SculptTool, level: SubVoxelLevel) -> String {
+    if level == SubVoxelLevel::Full {
+        return String::new();
+    }
+    let tools: Vec<String> = SculptTool::ALL
+        .iter()
+        .map(|&t| {
+            let name = tr(&format!("sculpt.{}", t.name().to_lowercase()));
+            if t == tool { format!("[{}]", name) } else { name }
+        })
+        .collect();
+    format!("{}  |  {}", tools.join("  "), level.name())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marks_selected_tool() {
+        assert_eq!(
+            palette_text(SculptTool::Box, SubVoxelLevel::Eighth),
+            "Single  [Box]  Sphere  Erase  |  1/8"
+        );
+        assert_eq!(palette_text(SculptTool::Erase, SubVoxelLevel::Full), "");
+    }
+}
@@ -1,12 +1,45 @@
 // ============================================
 // Text Renderer - Рендеринг текста через wgpu_text
 // ============================================
+// Основной шрифт (Roboto: латиница, кириллица, греческий) встроен.
+// Глифы растеризуются по мере появления в кэш-атлас glyph_brush, который
+// сам растёт. Символ, которого нет ни в одном загруженном шрифте (CJK),
+// один раз запускает поиск запасного шрифта: файлы в assets/fonts, затем
+// системные шрифты. Строка режется на участки по шрифту, в котором есть
+// глиф, ширина для выравнивания меряется по метрикам шрифтов.
+// Позиции и кегль приходят в пикселях интерфейса и переводятся в пиксели
+// окна масштабом интерфейса.
+
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 
 use wgpu_text::glyph_brush::{
-    ab_glyph::FontRef, Section, Text,
+    ab_glyph::{Font, FontArc, FontRef, FontVec, PxScale, ScaleFont}, FontId, Section, Text,
 };
 use wgpu_text::BrushBuilder;
 
+use super::UiScale;
+
+/// Папка со шрифтами рядом с игрой (запасные шрифты пользователя)
+const FONTS_DIR: &str = "assets/fonts";
+
+/// Системные шрифты с CJK
+const SYSTEM_FONTS: &[&str] = &[
+    "C:\\Windows\\Fonts\\msyh.ttc",
+    "C:\\Windows\\Fonts\\YuGothM.ttc",
+    "C:\\Windows\\Fonts\\malgun.ttf",
+    "C:\\Windows\\Fonts\\simsun.ttc",
+    "/System/Library/Fonts/PingFang.ttc",
+    "/System/Library/Fonts/Hiragino Sans GB.ttc",
+    "/System/Library/Fonts/AppleSDGothicNeo.ttc",
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/google-noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/truetype/wqy/wqy-microhei.ttc",
+    "/usr/share/fonts/wenquanyi/wqy-microhei/wqy-microhei.ttc",
+    "/usr/share/fonts/truetype/droid/DroidSansFallbackFull.ttf",
+];
+
 /// Выравнивание текста
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TextAlign {
@@ -58,11 +91,82 @@ impl TextParams {
     }
 }
 
+/// Шрифт, в котором есть глиф символа (None - ни в одном)
+fn font_for(fonts: &[FontArc], c: char) -> Option<usize> {
+    fonts.iter().position(|font| font.glyph_id(c).0 != 0)
+}
+
+/// Участки строки по шрифтам. Пробелы и символы без глифа остаются в
+/// текущем участке
+fn font_runs(fonts: &[FontArc], text: &str) -> Vec<(Range<usize>, usize)> {
+    let mut runs: Vec<(Range<usize>, usize)> = Vec::new();
+    for (start, c) in text.char_indices() {
+        let end = start + c.len_utf8();
+        let font = if c.is_whitespace() { None } else { font_for(fonts, c) };
+        match runs.last_mut() {
+            Some((range, current)) if font.is_none() || font == Some(*current) => range.end = end,
+            _ => runs.push((start..end, font.unwrap_or(0))),
+        }
+    }
+    runs
+}
+
+/// Ширина строки в пикселях по метрикам шрифтов
+fn text_width(fonts: &[FontArc], text: &str, size: f32) -> f32 {
+    font_runs(fonts, text)
+        .into_iter()
+        .map(|(range, font)| {
+            let scaled = fonts[font].as_scaled(PxScale::from(size));
+            text[range].chars().map(|c| scaled.h_advance(scaled.glyph_id(c))).sum::<f32>()
+        })
+        .sum()
+}
+
+/// Прочитать шрифт (.ttc - первый в коллекции)
+fn load_font(path: &Path) -> Option<FontArc> {
+    let data = std::fs::read(path).ok()?;
+    match FontVec::try_from_vec_and_index(data, 0) {
+        Ok(font) => Some(FontArc::new(font)),
+        Err(e) => {
+            log::warn!("Шрифт {} не прочитан: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Первый шрифт из assets/fonts или системных, в котором есть глиф
+fn find_fallback_font(missing: char) -> Option<FontArc> {
+    let mut bundled: Vec<PathBuf> = std::fs::read_dir(FONTS_DIR)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| matches!(path.extension().and_then(|ext| ext.to_str()), Some("ttf" | "otf" | "ttc")))
+        .collect();
+    bundled.sort();
+    let candidates = bundled.into_iter().chain(SYSTEM_FONTS.iter().map(PathBuf::from));
+    for path in candidates.filter(|path| path.is_file()) {
+        if let Some(font) = load_font(&path).filter(|font| font.glyph_id(missing).0 != 0) {
+            log::info!("Запасной шрифт для '{}': {}", missing, path.display());
+            return Some(font);
+        }
+    }
+    log::warn!("Нет шрифта с глифом '{}' (положите .ttf в {})", missing, FONTS_DIR);
+    None
+}
+
 /// GPU рендерер текста
 pub struct TextRenderer {
-    brush: wgpu_text::TextBrush<FontRef<'static>>,
+    brush: wgpu_text::TextBrush<FontArc>,
+    /// Основной шрифт и подгруженные запасные (индекс - FontId)
+    fonts: Vec<FontArc>,
+    /// Запасной шрифт уже искали (удачно или нет)
+    fallback_searched: bool,
+    format: wgpu::TextureFormat,
+    /// Размер окна (физические пиксели)
     screen_width: u32,
     screen_height: u32,
+    ui_scale: UiScale,
 }
 
 impl TextRenderer {
@@ -75,19 +179,49 @@ impl TextRenderer {
     ) -> Self {
         // Загружаем шрифт
         let font_data: &'static [u8] = include_bytes!("../../../assets/fonts/Roboto-Regular.ttf");
-        let font = FontRef::try_from_slice(font_data).expect("Failed to load font");
+        let font = FontArc::new(FontRef::try_from_slice(font_data).expect("Failed to load font"));
+        let fonts = vec![font];
         
-        let brush = BrushBuilder::using_font(font)
+        let brush = BrushBuilder::using_fonts(fonts.clone())
             .build(device, width, height, format);
         
         Self {
             brush,
+            fonts,
+            fallback_searched: false,
+            format,
             screen_width: width,
             screen_height: height,
+            ui_scale: UiScale::default(),
+        }
+    }
+    
+    /// Ширина строки в пикселях интерфейса (для раскладки виджетов)
+    pub fn measure(&self, text: &str, size: f32) -> f32 {
+        text_width(&self.fonts, text, size)
+    }
+    
+    /// Символ без глифа: один раз ищем запасной шрифт и пересобираем кисть
+    fn ensure_glyphs(&mut self, device: &wgpu::Device, texts: &[TextParams]) {
+        if self.fallback_searched {
+            return;
+        }
+        let missing = texts
+            .iter()
+            .flat_map(|params| params.text.chars())
+            .find(|&c| !c.is_whitespace() && !c.is_control() && font_for(&self.fonts, c).is_none());
+        let Some(missing) = missing else { return };
+        self.fallback_searched = true;
+        if let Some(font) = find_fallback_font(missing) {
+            self.fonts.push(font);
+            self.brush = BrushBuilder::using_fonts(self.fonts.clone())
+                .build(device, self.screen_width, self.screen_height, self.format);
         }
     }
     
-    pub fn resize(&mut self, queue: &wgpu::Queue, width: u32, height: u32) {
+    /// Размер окна и масштаб интерфейса
+    pub fn resize(&mut self, queue: &wgpu::Queue, width: u32, height: u32, ui_scale: UiScale) {
+        self.ui_scale = ui_scale;
         self.screen_width = width;
         self.screen_height = height;
         self.brush.resize_view(width as f32, height as f32, queue);
@@ -105,26 +239,34 @@ impl TextRenderer {
         if texts.is_empty() {
             return;
         }
+        self.ensure_glyphs(device, texts);
         
         // Собираем все секции
+        let fonts = &self.fonts;
+        let ui_scale = self.ui_scale;
         let sections: Vec<Section> = texts.iter().map(|params| {
-            // Вычисляем ширину текста приблизительно
-            let approx_width = params.text.chars().count() as f32 * params.size * 0.5;
+            let width = text_width(fonts, &params.text, params.size);
             
             // Корректируем позицию в зависимости от выравнивания
             let pos_x = match params.align {
-                TextAlign::Center => params.x - approx_width / 2.0,
-                TextAlign::Right => params.x - approx_width,
+                TextAlign::Center => params.x - width / 2.0,
+                TextAlign::Right => params.x - width,
                 TextAlign::Left => params.x,
             };
             
-            Section::default()
-                .add_text(
-                    Text::new(&params.text)
-                        .with_scale(params.size)
-                        .with_color(params.color),
-                )
-                .with_screen_position((pos_x, params.y))
+            // Участок на каждый шрифт
+            let size = params.size * ui_scale.factor();
+            font_runs(fonts, &params.text).into_iter().fold(
+                Section::default().with_screen_position(ui_scale.to_physical(pos_x, params.y)),
+                |section, (range, font)| {
+                    section.add_text(
+                        Text::new(&params.text[range])
+                            .with_scale(size)
+                            .with_color(params.color)
+                            .with_font_id(FontId(font)),
+                    )
+                },
+            )
         }).collect();
         
         // Передаём все секции одним вызовом
@@ -151,3 +293,30 @@ impl TextRenderer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roboto() -> Vec<FontArc> {
+        let font = FontRef::try_from_slice(include_bytes!("../../../assets/fonts/Roboto-Regular.ttf")).unwrap();
+        vec![FontArc::new(font)]
+    }
+
+    #[test]
+    fn cyrillic_is_covered_by_the_main_font() {
+        let fonts = roboto();
+        assert_eq!(font_runs(&fonts, "Привет, мир"), vec![(0..20, 0)]);
+        assert_eq!(font_for(&fonts, 'Ж'), Some(0));
+        assert_eq!(font_for(&fonts, '中'), None);
+    }
+
+    #[test]
+    fn width_adds_up_over_characters() {
+        let fonts = roboto();
+        let (ab, a, b) = (text_width(&fonts, "ab", 20.0), text_width(&fonts, "a", 20.0), text_width(&fonts, "b", 20.0));
+        assert!((ab - (a + b)).abs() < 1e-3);
+        assert!(text_width(&fonts, "Инвентарь", 20.0) > text_width(&fonts, "Инвентарь", 10.0));
+        assert_eq!(text_width(&fonts, "", 20.0), 0.0);
+    }
+}
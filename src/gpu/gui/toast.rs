@@ -0,0 +1,74 @@
+// ============================================
+// Toast - Короткие уведомления поверх HUD
+// ============================================
+// Надпись в верхней трети экрана: появляется, висит несколько секунд и
+// гаснет. Новые уведомления ждут в очереди, пока не погаснет текущее.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use super::text::{TextParams, TextAlign};
+
+/// Сколько уведомление видно целиком
+const HOLD: Duration = Duration::from_millis(2500);
+/// Появление и затухание
+const FADE: Duration = Duration::from_millis(600);
+const FONT_SIZE: f32 = 36.0;
+/// Очередь не копится бесконечно (ускоренное время суток)
+const MAX_QUEUED: usize = 4;
+
+/// Уведомления на HUD
+pub struct Toasts {
+    current: Option<(String, Instant)>,
+    queue: VecDeque<String>,
+}
+
+impl Toasts {
+    pub fn new() -> Self {
+        Self { current: None, queue: VecDeque::new() }
+    }
+
+    /// Показать уведомление (после текущего)
+    pub fn push(&mut self, text: impl Into<String>) {
+        if self.queue.len() >= MAX_QUEUED {
+            self.queue.pop_front();
+        }
+        self.queue.push_back(text.into());
+    }
+
+    /// Текст текущего уведомления, None - показывать нечего
+    pub fn text_params(&mut self, screen_width: f32, screen_height: f32) -> Option<TextParams> {
+        let now = Instant::now();
+        let total = FADE + HOLD + FADE;
+        if self.current.as_ref().is_some_and(|(_, shown)| now.duration_since(*shown) >= total) {
+            self.current = None;
+        }
+        if self.current.is_none() {
+            self.current = self.queue.pop_front().map(|text| (text, now));
+        }
+
+        let (text, shown) = self.current.as_ref()?;
+        let age = now.duration_since(*shown);
+        let alpha = if age < FADE {
+            age.as_secs_f32() / FADE.as_secs_f32()
+        } else {
+            ((total - age).as_secs_f32() / FADE.as_secs_f32()).min(1.0)
+        };
+
+        Some(TextParams {
+            x: screen_width / 2.0,
+            y: screen_height * 0.25,
+            text: text.clone(),
+            size: FONT_SIZE,
+            color: [1.0, 1.0, 1.0, alpha],
+            align: TextAlign::Center,
+            max_width: None,
+        })
+    }
+}
+
+impl Default for Toasts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
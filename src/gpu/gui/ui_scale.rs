@@ -0,0 +1,147 @@
+// ============================================
+// UI Scale - Масштаб интерфейса и безопасная зона
+// ============================================
+// Виджеты размечены в логических пикселях (SLOT_SIZE, кегль и т.п.) и
+// получают логический размер экрана: физический без полей безопасной
+// зоны, делённый на масштаб. Проходы GUI рисуются во viewport безопасной
+// зоны, TextRenderer переводит позиции и кегль в физические пиксели,
+// курсор переводится обратно (to_ui). Масштаб "auto" (0) - DPI окна, но
+// не меньше высоты экрана / 1080 с шагом 0.25: 4K при 100% в системе - x2.
+// Настраивается в settings.json -> ui и командой /ui.
+
+use serde::{Serialize, Deserialize};
+
+pub const MIN_UI_SCALE: f32 = 0.5;
+pub const MAX_UI_SCALE: f32 = 3.0;
+/// Наибольшее поле безопасной зоны (доля экрана с каждой стороны)
+pub const MAX_SAFE_AREA: f32 = 0.1;
+/// Высота экрана, под которую размечен интерфейс
+const REFERENCE_HEIGHT: f32 = 1080.0;
+/// Шаг автоматического масштаба
+const AUTO_STEP: f32 = 0.25;
+
+/// Настройки интерфейса (секция ui в settings.json)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiSettings {
+    /// Масштаб интерфейса; 0 - автоматически
+    pub scale: f32,
+    /// Поле безопасной зоны с каждой стороны (доля экрана, 0-0.1)
+    pub safe_area: f32,
+}
+
+impl Default for UiSettings {
+    fn default() -> Self {
+        Self { scale: 0.0, safe_area: 0.0 }
+    }
+}
+
+impl UiSettings {
+    pub fn is_auto(&self) -> bool {
+        self.scale <= 0.0
+    }
+}
+
+/// Перевод логических пикселей интерфейса в физические и обратно
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UiScale {
+    factor: f32,
+    /// Поле безопасной зоны слева и сверху (физические пиксели)
+    offset: (f32, f32),
+    /// Размер безопасной зоны (физические пиксели)
+    area: (f32, f32),
+}
+
+impl Default for UiScale {
+    fn default() -> Self {
+        Self { factor: 1.0, offset: (0.0, 0.0), area: (1.0, 1.0) }
+    }
+}
+
+impl UiScale {
+    pub fn new(settings: &UiSettings, dpi: f64, width: u32, height: u32) -> Self {
+        let factor = if settings.is_auto() {
+            Self::auto_factor(dpi, height)
+        } else {
+            settings.scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE)
+        };
+        let safe_area = settings.safe_area.clamp(0.0, MAX_SAFE_AREA);
+        let offset = ((width as f32 * safe_area).round(), (height as f32 * safe_area).round());
+        let area = (
+            (width as f32 - offset.0 * 2.0).max(1.0),
+            (height as f32 - offset.1 * 2.0).max(1.0),
+        );
+        Self { factor, offset, area }
+    }
+
+    /// Масштаб по DPI окна и высоте экрана
+    pub fn auto_factor(dpi: f64, height: u32) -> f32 {
+        let by_height = (height as f32 / REFERENCE_HEIGHT / AUTO_STEP).floor() * AUTO_STEP;
+        (dpi as f32).max(by_height).clamp(MIN_UI_SCALE, MAX_UI_SCALE)
+    }
+
+    pub fn factor(&self) -> f32 {
+        self.factor
+    }
+
+    /// Размер экрана для раскладки виджетов
+    pub fn logical_size(&self) -> (u32, u32) {
+        (
+            ((self.area.0 / self.factor).round() as u32).max(1),
+            ((self.area.1 / self.factor).round() as u32).max(1),
+        )
+    }
+
+    /// Размер окна (физические пиксели)
+    pub fn window_size(&self) -> (f32, f32) {
+        (self.area.0 + self.offset.0 * 2.0, self.area.1 + self.offset.1 * 2.0)
+    }
+
+    /// Пиксели окна (курсор) -> пиксели интерфейса
+    pub fn to_ui(&self, x: f32, y: f32) -> (f32, f32) {
+        ((x - self.offset.0) / self.factor, (y - self.offset.1) / self.factor)
+    }
+
+    /// Пиксели интерфейса -> пиксели окна
+    pub fn to_physical(&self, x: f32, y: f32) -> (f32, f32) {
+        (x * self.factor + self.offset.0, y * self.factor + self.offset.1)
+    }
+
+    /// Проход GUI рисует только в безопасной зоне
+    pub fn set_viewport(&self, render_pass: &mut wgpu::RenderPass) {
+        render_pass.set_viewport(self.offset.0, self.offset.1, self.area.0, self.area.1, 0.0, 1.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_scale_follows_dpi_and_screen_height() {
+        assert_eq!(UiScale::auto_factor(1.0, 1080), 1.0);
+        assert_eq!(UiScale::auto_factor(1.0, 2160), 2.0);
+        assert_eq!(UiScale::auto_factor(1.0, 1440), 1.25);
+        assert_eq!(UiScale::auto_factor(1.5, 1080), 1.5);
+        assert_eq!(UiScale::auto_factor(1.0, 600), 1.0);
+        assert_eq!(UiScale::auto_factor(4.0, 1080), MAX_UI_SCALE);
+    }
+
+    #[test]
+    fn manual_scale_and_safe_area_map_cursor_into_logical_pixels() {
+        let settings = UiSettings { scale: 2.0, safe_area: 0.05 };
+        let scale = UiScale::new(&settings, 1.0, 3840, 2160);
+        assert_eq!(scale.factor(), 2.0);
+        assert_eq!(scale.logical_size(), (1728, 972));
+        assert_eq!(scale.to_ui(192.0, 108.0), (0.0, 0.0));
+        assert_eq!(scale.to_ui(3648.0, 2052.0), (1728.0, 972.0));
+        assert_eq!(scale.to_physical(100.0, 50.0), (392.0, 208.0));
+        assert_eq!(scale.window_size(), (3840.0, 2160.0));
+
+        // Вне пределов - к ближайшему допустимому
+        let clamped = UiScale::new(&UiSettings { scale: 10.0, safe_area: 0.5 }, 1.0, 1920, 1080);
+        assert_eq!(clamped.factor(), MAX_UI_SCALE);
+        assert_eq!(clamped.to_ui(192.0, 108.0), (0.0, 0.0));
+        assert_eq!(UiScale::new(&UiSettings::default(), 1.0, 1920, 1080), UiScale { area: (1920.0, 1080.0), ..UiScale::default() });
+    }
+}
@@ -0,0 +1,372 @@
+// ============================================
+// World Map - Полноэкранная карта разведанного мира
+// ============================================
+// M открывает карту всех чанков, которые игрок видел (ExploredMap).
+// Цвета лежат в кольцевой текстуре 2048x2048, как у миникарты, но в
+// слоты пишутся только чанки видимой области: при панорамировании
+// уехавшие за край чанки вытесняются новыми. Отдаление ограничено так,
+// чтобы видимая область помещалась в текстуру.
+//
+// ЛКМ с перетаскиванием - сдвиг, колесо - масштаб под курсором,
+// ПКМ - метка в точке под курсором (по метке - следующий цвет),
+// СКМ по метке - убрать её. Место смерти - красный крест.
+
+use bytemuck::{Pod, Zeroable};
+use ultraviolet::Vec3;
+use wgpu::util::DeviceExt;
+
+use crate::gpu::locale::{tr, tr_args};
+use crate::gpu::save::{ExploredMap, Waypoint, MAX_WAYPOINTS};
+use crate::gpu::terrain::CHUNK_SIZE;
+
+use super::minimap::{create_tile_texture, ChunkColors, TileSlots, BLANK_TILE};
+use super::text::{TextParams, TextAlign};
+
+/// Чанков по стороне текстуры
+const TEXTURE_CHUNKS: i32 = 128;
+const TEXTURE_SIZE: f32 = (TEXTURE_CHUNKS * CHUNK_SIZE) as f32;
+/// Масштаб (пикселей на блок): при открытии, предел и шаг колеса
+const DEFAULT_ZOOM: f32 = 2.0;
+const MAX_ZOOM: f32 = 16.0;
+const ZOOM_STEP: f32 = 1.25;
+const TITLE_SIZE: f32 = 22.0;
+const FONT_SIZE: f32 = 16.0;
+/// Клик ближе этого (пиксели) попадает в метку
+const PICK_RADIUS: f32 = 10.0;
+const DEATH_COLOR: [f32; 4] = [0.9, 0.15, 0.15, 1.0];
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct WorldMapUniforms {
+    /// Центр обзора (мировые x, z), пикселей на блок, yaw игрока
+    view: [f32; 4],
+    /// Игрок (x, z), размер текстуры, число меток
+    player: [f32; 4],
+    /// Размер экрана в пикселях, есть ли место смерти, -
+    screen: [f32; 4],
+    /// Место смерти: x, z, -, -
+    death: [f32; 4],
+    /// Метки: x, z, -, -
+    markers: [[f32; 4]; MAX_WAYPOINTS],
+    /// Цвета меток
+    marker_colors: [[f32; 4]; MAX_WAYPOINTS],
+}
+
+/// Экран карты мира
+pub struct WorldMapScreen {
+    slots: TileSlots,
+    texture: wgpu::Texture,
+    pipeline: wgpu::RenderPipeline,
+    uniform_buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+    visible: bool,
+    /// Центр обзора (мировые x, z)
+    center: (f32, f32),
+    /// Пикселей на блок
+    zoom: f32,
+    position: Vec3,
+    yaw: f32,
+    /// Чанки, ждущие записи в текстуру
+    uploads: Vec<((i32, i32), ChunkColors)>,
+    waypoints: Vec<Waypoint>,
+    death: Option<[i32; 3]>,
+    /// Курсор и идёт ли перетаскивание
+    cursor: (f32, f32),
+    dragging: bool,
+    screen_width: f32,
+    screen_height: f32,
+}
+
+impl WorldMapScreen {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat, width: u32, height: u32) -> Self {
+        let texture = create_tile_texture(device, "World Map Texture", TEXTURE_CHUNKS);
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("World Map Uniform Buffer"),
+            contents: bytemuck::cast_slice(&[WorldMapUniforms::zeroed()]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("World Map Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("World Map Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: uniform_buffer.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::TextureView(&view) },
+            ],
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("World Map Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/world_map.wgsl").into()),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("World Map Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("World Map Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            slots: TileSlots::new(TEXTURE_CHUNKS),
+            texture,
+            pipeline,
+            uniform_buffer,
+            bind_group,
+            visible: false,
+            center: (0.0, 0.0),
+            zoom: DEFAULT_ZOOM,
+            position: Vec3::zero(),
+            yaw: 0.0,
+            uploads: Vec::new(),
+            waypoints: Vec::new(),
+            death: None,
+            cursor: (0.0, 0.0),
+            dragging: false,
+            screen_width: width as f32,
+            screen_height: height as f32,
+        }
+    }
+
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.screen_width = width as f32;
+        self.screen_height = height as f32;
+        self.zoom = self.zoom.max(self.min_zoom());
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Открыть с центром на игроке
+    pub fn open(&mut self) {
+        self.visible = true;
+        self.dragging = false;
+        self.center = (self.position.x, self.position.z);
+        self.zoom = self.zoom.max(self.min_zoom());
+    }
+
+    pub fn close(&mut self) {
+        self.visible = false;
+        self.dragging = false;
+    }
+
+    /// Самое сильное отдаление: видимая область (с запасом в чанк по
+    /// краям) помещается в кольцевую текстуру
+    fn min_zoom(&self) -> f32 {
+        self.screen_width.max(self.screen_height) / (TEXTURE_SIZE - 2.0 * CHUNK_SIZE as f32)
+    }
+
+    /// Мировые x, z под точкой экрана
+    pub fn world_at(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            self.center.0 + (x - self.screen_width / 2.0) / self.zoom,
+            self.center.1 + (y - self.screen_height / 2.0) / self.zoom,
+        )
+    }
+
+    /// Точка экрана для мировых x, z
+    fn screen_at(&self, x: f32, z: f32) -> (f32, f32) {
+        (
+            (x - self.center.0) * self.zoom + self.screen_width / 2.0,
+            (z - self.center.1) * self.zoom + self.screen_height / 2.0,
+        )
+    }
+
+    /// Мировые x, z под курсором
+    pub fn cursor_world(&self) -> (f32, f32) {
+        self.world_at(self.cursor.0, self.cursor.1)
+    }
+
+    /// Метка под курсором
+    pub fn hovered_waypoint(&self) -> Option<&str> {
+        self.waypoints
+            .iter()
+            .map(|waypoint| {
+                let (x, y) = self.screen_at(waypoint.pos[0] as f32 + 0.5, waypoint.pos[2] as f32 + 0.5);
+                (waypoint, (x - self.cursor.0).hypot(y - self.cursor.1))
+            })
+            .filter(|&(_, distance)| distance < PICK_RADIUS)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(waypoint, _)| waypoint.name.as_str())
+    }
+
+    /// Масштаб колесом: точка под курсором остаётся на месте
+    pub fn scroll(&mut self, notches: f32) {
+        let (x, y) = self.cursor;
+        let anchor = self.world_at(x, y);
+        self.zoom = (self.zoom * ZOOM_STEP.powf(notches)).clamp(self.min_zoom(), MAX_ZOOM);
+        self.center = (
+            anchor.0 - (x - self.screen_width / 2.0) / self.zoom,
+            anchor.1 - (y - self.screen_height / 2.0) / self.zoom,
+        );
+    }
+
+    /// ЛКМ нажата/отпущена: начать/закончить перетаскивание
+    pub fn set_dragging(&mut self, dragging: bool) {
+        self.dragging = dragging;
+    }
+
+    /// Курсор сдвинулся: при перетаскивании карта едет за ним
+    pub fn move_cursor(&mut self, x: f32, y: f32) {
+        if self.dragging {
+            self.center.0 -= (x - self.cursor.0) / self.zoom;
+            self.center.1 -= (y - self.cursor.1) / self.zoom;
+        }
+        self.cursor = (x, y);
+    }
+
+    /// У чанка новые цвета: слот перепишется, если чанк на экране
+    pub fn invalidate(&mut self, chunk: (i32, i32)) {
+        self.slots.invalidate(chunk);
+    }
+
+    /// Кадр: позиция игрока, чанки видимой области и метки
+    pub fn update(&mut self, position: Vec3, yaw: f32, map: &ExploredMap) {
+        self.position = position;
+        self.yaw = yaw;
+        if !self.visible {
+            return;
+        }
+
+        let chunk_of = |(x, z): (f32, f32)| ((x.floor() as i32).div_euclid(CHUNK_SIZE), (z.floor() as i32).div_euclid(CHUNK_SIZE));
+        let min = chunk_of(self.world_at(0.0, 0.0));
+        let max = chunk_of(self.world_at(self.screen_width, self.screen_height));
+        for chunk in self.slots.outdated(min, max, |chunk| map.tile(chunk).is_some()) {
+            self.uploads.push((chunk, map.tile(chunk).copied().unwrap_or(BLANK_TILE)));
+        }
+        if self.waypoints.as_slice() != map.waypoints() {
+            self.waypoints = map.waypoints().to_vec();
+        }
+        self.death = map.death();
+    }
+
+    /// Дописать текстуру и параметры; заголовок, подписи меток и подсказка
+    pub fn prepare(&mut self, queue: &wgpu::Queue) -> Vec<TextParams> {
+        for (chunk, colors) in self.uploads.drain(..) {
+            self.slots.write(queue, &self.texture, chunk, &colors);
+        }
+
+        let mut markers = [[0.0; 4]; MAX_WAYPOINTS];
+        let mut marker_colors = [[0.0; 4]; MAX_WAYPOINTS];
+        for (i, waypoint) in self.waypoints.iter().take(MAX_WAYPOINTS).enumerate() {
+            let [r, g, b] = waypoint.color.map(|c| c as f32 / 255.0);
+            markers[i] = [waypoint.pos[0] as f32 + 0.5, waypoint.pos[2] as f32 + 0.5, 0.0, 0.0];
+            marker_colors[i] = [r, g, b, 1.0];
+        }
+        let death = self.death.map_or([0.0; 4], |[x, _, z]| [x as f32 + 0.5, z as f32 + 0.5, 0.0, 0.0]);
+        let uniforms = WorldMapUniforms {
+            view: [self.center.0, self.center.1, self.zoom, self.yaw],
+            player: [self.position.x, self.position.z, TEXTURE_SIZE, self.waypoints.len().min(MAX_WAYPOINTS) as f32],
+            screen: [self.screen_width, self.screen_height, if self.death.is_some() { 1.0 } else { 0.0 }, 0.0],
+            death,
+            markers,
+            marker_colors,
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+
+        let text = |x: f32, y: f32, text: String, size: f32, color: [f32; 4]| TextParams {
+            x,
+            y,
+            text,
+            size,
+            color,
+            align: TextAlign::Center,
+            max_width: None,
+        };
+        let mut texts = vec![text(self.screen_width / 2.0, 12.0, tr("world_map.title").to_uppercase(), TITLE_SIZE, [0.0, 0.94, 1.0, 1.0])];
+        let labels = self
+            .waypoints
+            .iter()
+            .map(|waypoint| (waypoint.pos, waypoint.name.clone(), waypoint.color.map(|c| c as f32 / 255.0)))
+            .chain(self.death.map(|pos| (pos, tr("world_map.death"), [DEATH_COLOR[0], DEATH_COLOR[1], DEATH_COLOR[2]])));
+        for (pos, name, [r, g, b]) in labels {
+            let (x, y) = self.screen_at(pos[0] as f32 + 0.5, pos[2] as f32 + 0.5);
+            if x >= 0.0 && y >= 0.0 && x <= self.screen_width && y <= self.screen_height {
+                texts.push(text(x, y + 10.0, name, FONT_SIZE, [r, g, b, 1.0]));
+            }
+        }
+        let (cursor_x, cursor_z) = self.cursor_world();
+        texts.push(text(
+            self.screen_width / 2.0,
+            self.screen_height - 54.0,
+            tr_args("world_map.cursor", &[
+                ("x", &(cursor_x.floor() as i32)),
+                ("z", &(cursor_z.floor() as i32)),
+                ("scale", &format!("{:.2}", 1.0 / self.zoom)),
+            ]),
+            FONT_SIZE,
+            [1.0, 1.0, 1.0, 0.9],
+        ));
+        texts.push(text(
+            self.screen_width / 2.0,
+            self.screen_height - 30.0,
+            tr("world_map.hint"),
+            FONT_SIZE,
+            [0.7, 0.7, 0.7, 0.9],
+        ));
+        texts
+    }
+
+    pub fn render<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..6, 0..1);
+    }
+}
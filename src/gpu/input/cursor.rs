@@ -0,0 +1,146 @@
+// ============================================
+// Cursor - Захват курсора мыши
+// ============================================
+// Три состояния: игра (курсор захвачен и скрыт), интерфейс (меню,
+// инвентарь, консоль - курсор свободен) и окно без фокуса (Alt+Tab -
+// курсор отпущен всегда). При возврате фокуса захват восстанавливается,
+// если игра ждёт его; неудачный захват повторяется по клику в окне.
+// Без сырого ввода мышь читается по движению курсора (с ускорением ОС),
+// курсор после каждого события возвращается в центр окна.
+
+use serde::{Serialize, Deserialize};
+use winit::window::{CursorGrabMode, Window};
+
+/// Способ захвата курсора в игре (настройка)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CursorLock {
+    /// Курсор закреплён на месте (надёжнее на нескольких мониторах)
+    #[default]
+    Locked,
+    /// Курсор не выходит за окно, но двигается (нужно некоторым
+    /// программам записи экрана и удалённым рабочим столам)
+    Confined,
+}
+
+/// Захват курсора: что нужно игре и что сейчас применено к окну
+#[derive(Debug, Clone, Copy)]
+pub struct CursorState {
+    /// Игра ждёт захвата (не открыт интерфейс)
+    gameplay: bool,
+    focused: bool,
+    /// Курсор действительно захвачен
+    grabbed: bool,
+    lock: CursorLock,
+    /// Сырой ввод мыши (DeviceEvent) вместо движения курсора
+    raw_input: bool,
+}
+
+impl CursorState {
+    pub fn new() -> Self {
+        Self { gameplay: false, focused: true, grabbed: false, lock: CursorLock::default(), raw_input: true }
+    }
+
+    pub fn raw_input(&self) -> bool {
+        self.raw_input
+    }
+
+    /// Курсор захвачен - ввод мыши идёт в камеру
+    pub fn is_grabbed(&self) -> bool {
+        self.grabbed
+    }
+
+    /// Игра ждёт захвата, но окно без фокуса или захват не удался
+    pub fn needs_regrab(&self) -> bool {
+        self.gameplay && self.focused && !self.grabbed
+    }
+
+    /// Переход игра / интерфейс
+    pub fn set_gameplay(&mut self, window: Option<&Window>, gameplay: bool) {
+        self.gameplay = gameplay;
+        self.apply(window);
+    }
+
+    /// Окно получило или потеряло фокус
+    pub fn set_focused(&mut self, window: Option<&Window>, focused: bool) {
+        self.focused = focused;
+        self.apply(window);
+    }
+
+    /// Сменить способ захвата (применяется сразу, если курсор захвачен)
+    pub fn set_lock(&mut self, window: Option<&Window>, lock: CursorLock) {
+        if self.lock != lock {
+            self.lock = lock;
+            self.grabbed = false;
+            self.apply(window);
+        }
+    }
+
+    /// Включить/выключить сырой ввод (без него курсор нужен подвижным,
+    /// поэтому захват переключается на Confined)
+    pub fn set_raw_input(&mut self, window: Option<&Window>, raw_input: bool) {
+        if self.raw_input != raw_input {
+            self.raw_input = raw_input;
+            self.grabbed = false;
+            self.apply(window);
+        }
+    }
+
+    /// Смещение курсора от центра окна без сырого ввода (курсор
+    /// возвращается в центр). None - мышь читается из DeviceEvent
+    pub fn cursor_delta(&self, window: Option<&Window>, x: f64, y: f64) -> Option<(f64, f64)> {
+        if self.raw_input || !self.grabbed {
+            return None;
+        }
+        let window = window?;
+        let size = window.inner_size();
+        let (cx, cy) = ((size.width / 2) as f64, (size.height / 2) as f64);
+        let delta = (x - cx, y - cy);
+        if delta == (0.0, 0.0) {
+            return None;
+        }
+        let _ = window.set_cursor_position(winit::dpi::PhysicalPosition::new(cx, cy));
+        Some(delta)
+    }
+
+    /// Привести окно к нужному состоянию
+    pub fn apply(&mut self, window: Option<&Window>) {
+        let Some(window) = window else { return };
+        let want = self.gameplay && self.focused;
+        if want == self.grabbed {
+            return;
+        }
+
+        if want {
+            // На другом мониторе курсор мог остаться за окном:
+            // сначала возвращаем его в центр окна
+            let size = window.inner_size();
+            let _ = window.set_cursor_position(winit::dpi::PhysicalPosition::new(size.width / 2, size.height / 2));
+            let (first, second) = match self.lock {
+                CursorLock::Locked if self.raw_input => (CursorGrabMode::Locked, CursorGrabMode::Confined),
+                _ => (CursorGrabMode::Confined, CursorGrabMode::Locked),
+            };
+            match window.set_cursor_grab(first).or_else(|_| window.set_cursor_grab(second)) {
+                Ok(()) => {
+                    window.set_cursor_visible(false);
+                    self.grabbed = true;
+                }
+                Err(e) => {
+                    // Повторим по клику в окне
+                    log::warn!("Не удалось захватить курсор: {}", e);
+                    self.grabbed = false;
+                }
+            }
+        } else {
+            let _ = window.set_cursor_grab(CursorGrabMode::None);
+            window.set_cursor_visible(true);
+            self.grabbed = false;
+        }
+    }
+}
+
+impl Default for CursorState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
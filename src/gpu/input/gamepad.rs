@@ -0,0 +1,126 @@
+// ============================================
+// Gamepad - Подключение геймпадов и force feedback
+// ============================================
+// Активный геймпад - последний подключённый (или последний,
+// с которого пришло событие). Эффект gilrs останавливается при drop,
+// поэтому проигрываемые эффекты держим до истечения длительности.
+
+use std::time::{Duration, Instant};
+
+use gilrs::ff::{BaseEffect, BaseEffectType, Effect, EffectBuilder, Replay, Ticks};
+use gilrs::{EventType, GamepadId, Gilrs};
+
+use super::haptics::{HapticEvent, HapticSettings};
+
+/// Система геймпадов
+pub struct GamepadSystem {
+    /// None - gilrs недоступен на этой платформе
+    gilrs: Option<Gilrs>,
+    /// Геймпад, на который отправляется вибрация
+    active: Option<GamepadId>,
+    /// Проигрываемые эффекты и момент окончания
+    playing: Vec<(Effect, Instant)>,
+    pub settings: HapticSettings,
+}
+
+impl GamepadSystem {
+    pub fn new() -> Self {
+        let gilrs = match Gilrs::new() {
+            Ok(gilrs) => Some(gilrs),
+            Err(e) => {
+                log::warn!("Не удалось инициализировать геймпады: {}", e);
+                None
+            }
+        };
+
+        // Уже подключённые геймпады
+        let active = gilrs.as_ref().and_then(|gilrs| {
+            gilrs.gamepads().map(|(id, _)| id).last()
+        });
+        if let (Some(gilrs), Some(id)) = (&gilrs, active) {
+            log::info!("Найден геймпад: {}", gilrs.gamepad(id).name());
+        }
+
+        Self {
+            gilrs,
+            active,
+            playing: Vec::new(),
+            settings: HapticSettings::default(),
+        }
+    }
+
+    /// Подключён ли геймпад
+    pub fn is_connected(&self) -> bool {
+        self.active.is_some()
+    }
+
+    /// Обработать события подключения и убрать завершённые эффекты
+    /// Возвращает true, если с геймпада был ввод (кнопки, стики)
+    pub fn update(&mut self) -> bool {
+        let Some(gilrs) = &mut self.gilrs else { return false };
+        let mut had_input = false;
+
+        while let Some(event) = gilrs.next_event() {
+            match event.event {
+                EventType::Connected => {
+                    log::info!("Подключён: {}", gilrs.gamepad(event.id).name());
+                    self.active = Some(event.id);
+                }
+                EventType::Disconnected => {
+                    if self.active == Some(event.id) {
+                        self.active = gilrs.gamepads().map(|(id, _)| id).find(|&id| id != event.id);
+                    }
+                }
+                _ => {
+                    self.active = Some(event.id);
+                    had_input = true;
+                }
+            }
+        }
+
+        let now = Instant::now();
+        self.playing.retain(|(_, until)| *until > now);
+        had_input
+    }
+
+    /// Проиграть вибрацию для события (если геймпад подключён и событие включено)
+    pub fn play(&mut self, event: HapticEvent) {
+        let (Some(gilrs), Some(id)) = (&mut self.gilrs, self.active) else { return };
+        let Some((strong, weak, duration_ms)) = self.settings.magnitudes(event) else { return };
+        if !gilrs.gamepad(id).is_ff_supported() {
+            return;
+        }
+
+        let scheduling = Replay {
+            play_for: Ticks::from_ms(duration_ms),
+            ..Default::default()
+        };
+        let mut builder = EffectBuilder::new();
+        if strong > 0 {
+            builder.add_effect(BaseEffect {
+                kind: BaseEffectType::Strong { magnitude: strong },
+                scheduling,
+                envelope: Default::default(),
+            });
+        }
+        if weak > 0 {
+            builder.add_effect(BaseEffect {
+                kind: BaseEffectType::Weak { magnitude: weak },
+                scheduling,
+                envelope: Default::default(),
+            });
+        }
+
+        let effect = match builder.gamepads(&[id]).finish(gilrs) {
+            Ok(effect) => effect,
+            Err(e) => {
+                log::warn!("Не удалось создать эффект: {}", e);
+                return;
+            }
+        };
+        if effect.play().is_ok() {
+            let until = Instant::now() + Duration::from_millis(duration_ms as u64);
+            self.playing.push((effect, until));
+        }
+    }
+}
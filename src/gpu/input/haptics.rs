@@ -0,0 +1,91 @@
+// ============================================
+// Haptics - События и настройки вибрации
+// ============================================
+// Вибрация лёгкая: короткий импульс на слабом/сильном моторе.
+// Итоговая сила = сила события * общий ползунок интенсивности.
+
+use serde::{Serialize, Deserialize};
+
+/// Событие, вызывающее вибрацию
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HapticEvent {
+    /// Блок (или суб-воксель) сломан
+    BlockBreak,
+    /// Урон игроку (пока - жёсткое приземление)
+    Damage,
+    /// Подтверждение в интерфейсе (кнопки меню)
+    UiConfirm,
+}
+
+impl HapticEvent {
+    pub const ALL: [HapticEvent; 3] = [HapticEvent::BlockBreak, HapticEvent::Damage, HapticEvent::UiConfirm];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HapticEvent::BlockBreak => "Break",
+            HapticEvent::Damage => "Damage",
+            HapticEvent::UiConfirm => "UI",
+        }
+    }
+
+    /// Сила (сильный мотор, слабый мотор) в долях 0-1 и длительность в мс
+    pub fn pulse(self) -> (f32, f32, u32) {
+        match self {
+            HapticEvent::BlockBreak => (0.25, 0.45, 60),
+            HapticEvent::Damage => (0.8, 0.5, 180),
+            HapticEvent::UiConfirm => (0.0, 0.3, 35),
+        }
+    }
+}
+
+/// Настройки вибрации
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HapticSettings {
+    /// Общая интенсивность (0 = выключено, 1 = максимум)
+    pub intensity: f32,
+    pub block_break: bool,
+    pub damage: bool,
+    pub ui_confirm: bool,
+}
+
+impl Default for HapticSettings {
+    fn default() -> Self {
+        Self {
+            intensity: 0.6,
+            block_break: true,
+            damage: true,
+            ui_confirm: true,
+        }
+    }
+}
+
+impl HapticSettings {
+    /// Включено ли событие
+    pub fn is_enabled(&self, event: HapticEvent) -> bool {
+        match event {
+            HapticEvent::BlockBreak => self.block_break,
+            HapticEvent::Damage => self.damage,
+            HapticEvent::UiConfirm => self.ui_confirm,
+        }
+    }
+
+    /// Переключить событие
+    pub fn toggle(&mut self, event: HapticEvent) {
+        match event {
+            HapticEvent::BlockBreak => self.block_break = !self.block_break,
+            HapticEvent::Damage => self.damage = !self.damage,
+            HapticEvent::UiConfirm => self.ui_confirm = !self.ui_confirm,
+        }
+    }
+
+    /// Магнитуды моторов для gilrs (0 - событие не проигрывается)
+    pub fn magnitudes(&self, event: HapticEvent) -> Option<(u16, u16, u32)> {
+        if !self.is_enabled(event) || self.intensity <= 0.0 {
+            return None;
+        }
+        let (strong, weak, duration_ms) = event.pulse();
+        let scale = |value: f32| (value * self.intensity.clamp(0.0, 1.0) * u16::MAX as f32) as u16;
+        Some((scale(strong), scale(weak), duration_ms))
+    }
+}
@@ -0,0 +1,14 @@
+// ============================================
+// Input Module - Геймпад и вибрация
+// ============================================
+// Клавиатура и мышь обрабатываются в systems::InputSystem,
+// здесь - устройства через gilrs (подключение, force feedback)
+// и состояние захвата курсора
+
+mod haptics;
+mod gamepad;
+mod cursor;
+
+pub use haptics::{HapticEvent, HapticSettings};
+pub use gamepad::GamepadSystem;
+pub use cursor::{CursorLock, CursorState};
@@ -0,0 +1,13 @@
+// ============================================
+// Items - Предметы и стопки
+// ============================================
+// Предметы отделены от блоков: блок - частный случай предмета, а
+// инструменты ставить нельзя. Хотбар, инвентарь и сундуки хранят стопки.
+
+mod registry;
+mod stack;
+mod tool;
+
+pub use registry::*;
+pub use stack::{ItemStack, insert_stack};
+pub use tool::{tool_speed, can_harvest};
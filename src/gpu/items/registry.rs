@@ -0,0 +1,193 @@
+// ============================================
+// Item Registry - Реестр предметов
+// ============================================
+// Каждый блок - предмет с тем же номером (0-255), его имя и цвета
+// берутся из реестра блоков. Остальные предметы (инструменты) имеют
+// номера с 256 и описаны в assets/items/default_items.json.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use crate::gpu::blocks::{BlockCategory, BlockType, ToolKind, ToolTier, global_registry, get_block_name, get_face_colors};
+
+/// Числовой ID предмета
+pub type ItemType = u16;
+
+/// Первый номер предмета, который не является блоком
+pub const FIRST_ITEM_ID: ItemType = 256;
+
+/// Палочка выделения: ЛКМ и ПКМ задают углы области для /copy
+pub const SELECTION_WAND: ItemType = 269;
+
+/// Размер стопки блоков и предметов без явного max_stack
+pub const DEFAULT_MAX_STACK: u32 = 64;
+
+fn default_max_stack() -> u32 {
+    DEFAULT_MAX_STACK
+}
+
+/// Определение предмета из JSON
+#[derive(Debug, Clone, Deserialize)]
+pub struct ItemDefinition {
+    /// Строковый ID ("pickaxe")
+    pub id: String,
+    /// Числовой ID (>= 256)
+    pub numeric_id: ItemType,
+    pub name: String,
+    /// Цвет иконки (RGB)
+    pub color: [f32; 3],
+    #[serde(default = "default_max_stack")]
+    pub max_stack: u32,
+    /// Инструмент для ломания (hand - не инструмент)
+    #[serde(default)]
+    pub tool: ToolKind,
+    /// Уровень инструмента: какие блоки с requires_tool он добывает
+    #[serde(default)]
+    pub tier: Option<ToolTier>,
+    /// Множители скорости ломания по категориям блоков
+    #[serde(default)]
+    pub speeds: HashMap<BlockCategory, f32>,
+}
+
+#[derive(Deserialize)]
+struct ItemsFile {
+    items: Vec<ItemDefinition>,
+}
+
+/// Реестр предметов, не являющихся блоками
+#[derive(Debug, Default)]
+pub struct ItemRegistry {
+    items: HashMap<ItemType, ItemDefinition>,
+}
+
+impl ItemRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Загрузить предметы из JSON. Возвращает количество загруженных
+    pub fn load_from_json(&mut self, json: &str) -> Result<usize, String> {
+        let file: ItemsFile = serde_json::from_str(json)
+            .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+
+        for def in &file.items {
+            if def.numeric_id < FIRST_ITEM_ID {
+                return Err(format!("Item '{}' uses block id {} (items start at {})", def.id, def.numeric_id, FIRST_ITEM_ID));
+            }
+            if def.max_stack == 0 {
+                return Err(format!("Item '{}' has max_stack 0", def.id));
+            }
+            if def.speeds.values().any(|&speed| speed <= 0.0) {
+                return Err(format!("Item '{}' has a non-positive speed multiplier", def.id));
+            }
+        }
+
+        let count = file.items.len();
+        for def in file.items {
+            self.items.insert(def.numeric_id, def);
+        }
+        Ok(count)
+    }
+
+    pub fn get(&self, item: ItemType) -> Option<&ItemDefinition> {
+        self.items.get(&item)
+    }
+
+    /// Все предметы по возрастанию ID
+    pub fn all_items(&self) -> Vec<&ItemDefinition> {
+        let mut items: Vec<&ItemDefinition> = self.items.values().collect();
+        items.sort_by_key(|def| def.numeric_id);
+        items
+    }
+}
+
+static ITEM_REGISTRY: OnceLock<ItemRegistry> = OnceLock::new();
+
+/// Глобальный реестр предметов
+pub fn item_registry() -> &'static ItemRegistry {
+    ITEM_REGISTRY.get_or_init(|| {
+        let mut registry = ItemRegistry::new();
+        if let Err(e) = registry.load_from_json(include_str!("../../../assets/items/default_items.json")) {
+            log::warn!("Failed to load default items: {}", e);
+        }
+        registry
+    })
+}
+
+/// Предмет-блок
+pub fn block_item(block: BlockType) -> ItemType {
+    block as ItemType
+}
+
+/// Блок, если предмет - блок
+pub fn item_block(item: ItemType) -> Option<BlockType> {
+    BlockType::try_from(item).ok()
+}
+
+/// Известен ли предмет (блок в реестре блоков или предмет из JSON)
+pub fn item_exists(item: ItemType) -> bool {
+    match item_block(item) {
+        Some(block) => global_registry().read().is_ok_and(|registry| registry.get_by_numeric(block).is_some()),
+        None => item_registry().get(item).is_some(),
+    }
+}
+
+/// Максимальный размер стопки
+pub fn max_stack(item: ItemType) -> u32 {
+    match item_block(item) {
+        Some(_) => DEFAULT_MAX_STACK,
+        None => item_registry().get(item).map_or(1, |def| def.max_stack),
+    }
+}
+
+/// Название предмета
+pub fn item_name(item: ItemType) -> String {
+    match item_block(item) {
+        Some(block) => get_block_name(block),
+        None => item_registry().get(item).map_or_else(|| format!("Item #{}", item), |def| def.name.clone()),
+    }
+}
+
+/// Цвета иконки: (верх, бока)
+pub fn item_colors(item: ItemType) -> ([f32; 3], [f32; 3]) {
+    match item_block(item) {
+        Some(block) => get_face_colors(block),
+        None => {
+            let [r, g, b] = item_registry().get(item).map_or([0.5, 0.5, 0.5], |def| def.color);
+            ([r, g, b], [r * 0.75, g * 0.75, b * 0.75])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::blocks::STONE;
+
+    #[test]
+    fn default_items_load() {
+        let mut registry = ItemRegistry::new();
+        let count = registry.load_from_json(include_str!("../../../assets/items/default_items.json")).unwrap();
+        assert_eq!(count, registry.all_items().len());
+        let pickaxe = registry.all_items().into_iter().find(|def| def.id == "iron_pickaxe").unwrap();
+        assert_eq!(pickaxe.tool, ToolKind::Pickaxe);
+        assert_eq!(pickaxe.tier, Some(ToolTier::Iron));
+        assert_eq!(pickaxe.max_stack, 1);
+    }
+
+    #[test]
+    fn rejects_block_ids() {
+        let json = r#"{ "items": [{ "id": "fake", "numeric_id": 12, "name": "Fake", "color": [1, 1, 1] }] }"#;
+        assert!(ItemRegistry::new().load_from_json(json).is_err());
+    }
+
+    #[test]
+    fn blocks_are_items() {
+        let item = block_item(STONE);
+        assert_eq!(item_block(item), Some(STONE));
+        assert_eq!(max_stack(item), DEFAULT_MAX_STACK);
+        assert_eq!(item_block(FIRST_ITEM_ID), None);
+    }
+}
@@ -0,0 +1,158 @@
+// ============================================
+// Item Stack - Стопка предметов
+// ============================================
+// Правила стопок общие для хотбара, инвентаря и сундуков: одинаковые
+// предметы сливаются до max_stack, стопка делится пополам, остаток
+// вставки возвращается вызывающему.
+
+use crate::gpu::blocks::BlockType;
+use crate::gpu::save::SavedHotbarItem;
+
+use super::registry::{ItemType, block_item, item_block, item_exists, max_stack};
+
+/// Стопка одинаковых предметов (count >= 1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ItemStack {
+    pub item: ItemType,
+    pub count: u32,
+}
+
+impl ItemStack {
+    pub fn new(item: ItemType, count: u32) -> Self {
+        Self { item, count: count.max(1) }
+    }
+
+    /// Один блок
+    pub fn of_block(block: BlockType) -> Self {
+        Self::new(block_item(block), 1)
+    }
+
+    /// Полная стопка предмета
+    pub fn full(item: ItemType) -> Self {
+        Self::new(item, max_stack(item))
+    }
+
+    /// Блок для установки (None - предмет не ставится)
+    pub fn block(&self) -> Option<BlockType> {
+        item_block(self.item)
+    }
+
+    pub fn max_stack(&self) -> u32 {
+        max_stack(self.item)
+    }
+
+    /// Долить other в эту стопку до максимума. Возвращает остаток
+    /// (весь other, если предметы разные)
+    pub fn merge(&mut self, other: ItemStack) -> Option<ItemStack> {
+        if other.item != self.item {
+            return Some(other);
+        }
+        let moved = other.count.min(self.max_stack().saturating_sub(self.count));
+        self.count += moved;
+        let rest = other.count - moved;
+        (rest > 0).then_some(Self::new(other.item, rest))
+    }
+
+    /// Отделить меньшую половину стопки (один предмет не делится)
+    pub fn split_half(&mut self) -> Option<ItemStack> {
+        let half = self.count / 2;
+        if half == 0 {
+            return None;
+        }
+        self.count -= half;
+        Some(Self::new(self.item, half))
+    }
+
+    /// Стопка из сохранения (неизвестные предметы отбрасываются)
+    pub fn from_saved(saved: SavedHotbarItem) -> Option<Self> {
+        if !item_exists(saved.item) || saved.count == 0 {
+            return None;
+        }
+        Some(Self::new(saved.item, saved.count.min(max_stack(saved.item))))
+    }
+
+    /// Снимок стопки для сохранения
+    pub fn to_saved(&self) -> SavedHotbarItem {
+        SavedHotbarItem {
+            item: self.item,
+            count: self.count,
+        }
+    }
+}
+
+/// Положить стопку в набор слотов: сначала долить неполные стопки того
+/// же предмета, затем занять пустые слоты. Возвращает то, что не поместилось
+pub fn insert_stack(slots: &mut [Option<ItemStack>], stack: ItemStack) -> Option<ItemStack> {
+    let mut rest = Some(stack);
+    for slot in slots.iter_mut().flatten() {
+        let Some(remaining) = rest else { return None };
+        rest = slot.merge(remaining);
+    }
+    for slot in slots.iter_mut().filter(|slot| slot.is_none()) {
+        let Some(mut remaining) = rest else { return None };
+        // В пустой слот помещается не больше максимума
+        let max = remaining.max_stack();
+        rest = (remaining.count > max).then(|| ItemStack::new(remaining.item, remaining.count - max));
+        remaining.count = remaining.count.min(max);
+        *slot = Some(remaining);
+    }
+    rest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::blocks::{STONE, DIRT};
+    use crate::gpu::items::FIRST_ITEM_ID;
+
+    fn stone(count: u32) -> ItemStack {
+        ItemStack::new(block_item(STONE), count)
+    }
+
+    #[test]
+    fn merge_stops_at_max_stack() {
+        let mut stack = stone(60);
+        assert_eq!(stack.merge(stone(10)), Some(stone(6)));
+        assert_eq!(stack.count, 64);
+        // Разные предметы не сливаются
+        let dirt = ItemStack::of_block(DIRT);
+        assert_eq!(stack.merge(dirt), Some(dirt));
+    }
+
+    #[test]
+    fn split_leaves_larger_half() {
+        let mut stack = stone(7);
+        assert_eq!(stack.split_half(), Some(stone(3)));
+        assert_eq!(stack.count, 4);
+        let mut single = stone(1);
+        assert_eq!(single.split_half(), None);
+    }
+
+    #[test]
+    fn insert_fills_stacks_then_empty_slots() {
+        let mut slots = [Some(stone(62)), None, Some(ItemStack::of_block(DIRT)), None];
+        assert_eq!(insert_stack(&mut slots, stone(100)), None);
+        assert_eq!(slots[0], Some(stone(64)));
+        assert_eq!(slots[1], Some(stone(64)));
+        assert_eq!(slots[3], Some(stone(34)));
+
+        // Места нет - остаток возвращается
+        assert_eq!(insert_stack(&mut slots, stone(5)), Some(stone(5)));
+    }
+
+    #[test]
+    fn tools_do_not_stack() {
+        let pickaxe = ItemStack::full(FIRST_ITEM_ID);
+        assert_eq!(pickaxe.count, 1);
+        let mut slots = [Some(pickaxe), None];
+        assert_eq!(insert_stack(&mut slots, pickaxe), None);
+        assert_eq!(slots[1], Some(pickaxe));
+    }
+
+    #[test]
+    fn unknown_saved_items_are_dropped() {
+        let saved = SavedHotbarItem { item: 9999, count: 3 };
+        assert_eq!(ItemStack::from_saved(saved), None);
+        assert_eq!(ItemStack::from_saved(stone(5).to_saved()), Some(stone(5)));
+    }
+}
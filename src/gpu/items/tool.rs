@@ -0,0 +1,85 @@
+// ============================================
+// Tools - Скорость ломания и добыча
+// ============================================
+// Инструмент ускоряет ломание блоков своих категорий (speeds в JSON
+// предмета) и нужен для добычи блоков с requires_tool: кирка ниже
+// требуемого уровня ломает алмазную руду, но та ничего не роняет.
+
+use crate::gpu::blocks::{BlockCategory, BlockType, ToolKind, ToolTier, global_registry};
+
+use super::registry::{ItemDefinition, ItemType, item_registry};
+
+/// Множитель скорости ломания блока предметом в руке
+/// (1.0 - рука или блок не его категории)
+pub fn tool_speed(held: Option<ItemType>, block: BlockType) -> f32 {
+    let Some(category) = global_registry().read().ok()
+        .and_then(|registry| registry.get_by_numeric(block).map(|def| def.category)) else { return 1.0 };
+    speed_for(held.and_then(|item| item_registry().get(item)), category)
+}
+
+/// Роняет ли блок что-нибудь, если сломать его предметом в руке
+pub fn can_harvest(held: Option<ItemType>, block: BlockType) -> bool {
+    let Some((tool, requires)) = global_registry().read().ok()
+        .and_then(|registry| registry.get_by_numeric(block).map(|def| (def.tool, def.requires_tool))) else { return true };
+    harvests(held.and_then(|item| item_registry().get(item)), tool, requires)
+}
+
+fn speed_for(held: Option<&ItemDefinition>, category: BlockCategory) -> f32 {
+    held.and_then(|def| def.speeds.get(&category)).copied().unwrap_or(1.0)
+}
+
+fn harvests(held: Option<&ItemDefinition>, tool: ToolKind, requires: Option<ToolTier>) -> bool {
+    let Some(min_tier) = requires else { return true };
+    held.is_some_and(|def| def.tool == tool && def.tier.is_some_and(|tier| tier >= min_tier))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::items::ItemRegistry;
+
+    fn default_items() -> ItemRegistry {
+        let mut registry = ItemRegistry::new();
+        registry.load_from_json(include_str!("../../../assets/items/default_items.json")).unwrap();
+        registry
+    }
+
+    fn find<'a>(registry: &'a ItemRegistry, id: &str) -> &'a ItemDefinition {
+        registry.all_items().into_iter().find(|def| def.id == id).unwrap()
+    }
+
+    #[test]
+    fn tiers_speed_up_their_categories() {
+        let registry = default_items();
+        let wooden = find(&registry, "wooden_pickaxe");
+        let diamond = find(&registry, "diamond_pickaxe");
+        assert!(speed_for(Some(diamond), BlockCategory::Ore) > speed_for(Some(wooden), BlockCategory::Ore));
+        assert!(speed_for(Some(wooden), BlockCategory::Ore) > 1.0);
+        // Кирка не ускоряет рубку дерева, рука - ничего
+        assert_eq!(speed_for(Some(diamond), BlockCategory::Wood), 1.0);
+        assert_eq!(speed_for(None, BlockCategory::Stone), 1.0);
+    }
+
+    #[test]
+    fn required_tier_gates_drops() {
+        let registry = default_items();
+        let stone_pickaxe = find(&registry, "stone_pickaxe");
+        let iron_pickaxe = find(&registry, "iron_pickaxe");
+        let iron_shovel = find(&registry, "iron_shovel");
+        let needs_iron = Some(ToolTier::Iron);
+
+        assert!(!harvests(None, ToolKind::Pickaxe, needs_iron));
+        assert!(!harvests(Some(stone_pickaxe), ToolKind::Pickaxe, needs_iron));
+        assert!(!harvests(Some(iron_shovel), ToolKind::Pickaxe, needs_iron));
+        assert!(harvests(Some(iron_pickaxe), ToolKind::Pickaxe, needs_iron));
+        assert!(harvests(Some(iron_pickaxe), ToolKind::Pickaxe, Some(ToolTier::Wood)));
+        // Блоки без требования роняются и с руки
+        assert!(harvests(None, ToolKind::Shovel, None));
+    }
+
+    #[test]
+    fn rejects_zero_speed() {
+        let json = r#"{ "items": [{ "id": "bad", "numeric_id": 300, "name": "Bad", "color": [1, 1, 1], "speeds": { "stone": 0.0 } }] }"#;
+        assert!(ItemRegistry::new().load_from_json(json).is_err());
+    }
+}
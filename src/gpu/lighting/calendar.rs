@@ -0,0 +1,115 @@
+// ============================================
+// Calendar - Счётчик игровых дней
+// ============================================
+// Новый день начинается на рассвете (time = 0.25). Счётчик следит за
+// временем суток из DayNightCycle и ловит переход через рассвет.
+// Перестановка времени командой (/time set) день не засчитывает.
+
+/// Время рассвета (см. TimeOfDay)
+pub const SUNRISE: f32 = 0.25;
+/// Время заката - с него можно спать до утра
+pub const SUNSET: f32 = 0.75;
+
+/// Ночь: от заката до рассвета
+pub fn is_night(time: f32) -> bool {
+    !(SUNRISE..SUNSET).contains(&time.rem_euclid(1.0))
+}
+
+/// Счётчик прошедших дней мира
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DayCounter {
+    /// Полных дней с создания мира (первый день - 0)
+    days: u32,
+    /// Последнее увиденное время суток
+    time: Option<f32>,
+}
+
+impl DayCounter {
+    /// Счётчик из сохранения: дни и время суток на момент записи
+    pub fn new(days: u32, time: Option<f32>) -> Self {
+        Self { days, time }
+    }
+
+    /// Прошедшие дни (для сохранения)
+    pub fn days(&self) -> u32 {
+        self.days
+    }
+
+    /// Номер текущего дня для игрока ("Day 1" в новом мире)
+    pub fn day_number(&self) -> u32 {
+        self.days + 1
+    }
+
+    /// Последнее время суток (None - ещё не было ни одного кадра)
+    pub fn time(&self) -> Option<f32> {
+        self.time
+    }
+
+    /// Учесть текущее время суток. Возвращает номер наступившего дня,
+    /// если с прошлого вызова прошёл рассвет
+    pub fn update(&mut self, time: f32) -> Option<u32> {
+        let previous = self.time.replace(time)?;
+        let sunrise = if time >= previous {
+            previous < SUNRISE && time >= SUNRISE
+        } else {
+            // Переход через полночь
+            previous < SUNRISE || time >= SUNRISE
+        };
+        if !sunrise {
+            return None;
+        }
+        self.days += 1;
+        Some(self.day_number())
+    }
+
+    /// Время суток переставлено вручную - без подсчёта рассвета
+    pub fn sync(&mut self, time: f32) {
+        self.time = Some(time);
+    }
+
+    /// Задать номер текущего дня (/day set)
+    pub fn set_day_number(&mut self, day: u32) {
+        self.days = day.saturating_sub(1);
+    }
+}
+
+impl Default for DayCounter {
+    fn default() -> Self {
+        Self::new(0, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_sunrises() {
+        let mut counter = DayCounter::default();
+        assert_eq!(counter.update(0.35), None);
+        assert_eq!(counter.update(0.9), None);
+        assert_eq!(counter.update(0.1), None);
+        assert_eq!(counter.update(0.26), Some(2));
+        // Рассвет сразу после полуночи за один кадр
+        counter.sync(0.99);
+        assert_eq!(counter.update(0.3), Some(3));
+        assert_eq!(counter.days(), 2);
+    }
+
+    #[test]
+    fn sync_skips_sunrise() {
+        let mut counter = DayCounter::new(4, Some(0.1));
+        counter.sync(0.5);
+        assert_eq!(counter.update(0.6), None);
+        assert_eq!(counter.day_number(), 5);
+    }
+
+    #[test]
+    fn skipping_night_counts_a_day() {
+        assert!(is_night(0.8) && is_night(0.1));
+        assert!(!is_night(SUNRISE) && !is_night(0.5));
+
+        let mut counter = DayCounter::new(0, Some(0.9));
+        assert_eq!(counter.update(SUNRISE), Some(2));
+    }
+}
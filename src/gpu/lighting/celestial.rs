@@ -1,11 +1,18 @@
 // ============================================
 // Celestial Bodies - Солнце и Луна
 // ============================================
-// Система смены дня и ночи
+// Система смены дня и ночи, фазы луны и звёздное небо
 
 use ultraviolet::Vec3;
 use std::f32::consts::{PI, TAU};
 
+/// Длина суток в реальных минутах при speed = 1.0
+pub const DEFAULT_DAY_LENGTH_MINUTES: f32 = 24.0;
+/// Дней в лунном цикле (одна фаза на ночь)
+pub const LUNAR_CYCLE_DAYS: u32 = 8;
+/// Ось вращения небосвода - перпендикуляр к пути солнца (см. Sun::update)
+pub const SKY_AXIS: [f32; 3] = [-0.953, 0.0, 0.301];
+
 /// Время суток (0.0 - 1.0, где 0.0 = полночь, 0.5 = полдень)
 #[derive(Clone, Copy, Debug)]
 pub struct TimeOfDay {
@@ -23,10 +30,15 @@ impl TimeOfDay {
         }
     }
 
+    /// Скорость, при которой сутки длятся minutes реальных минут
+    pub fn speed_for_day_length(minutes: f32) -> f32 {
+        DEFAULT_DAY_LENGTH_MINUTES / minutes.max(f32::EPSILON)
+    }
+
     /// Обновить время
     pub fn update(&mut self, dt: f32) {
         // 1 игровой день = 24 минуты при speed = 1.0
-        self.time += dt * self.speed / (24.0 * 60.0);
+        self.time += dt * self.speed / (DEFAULT_DAY_LENGTH_MINUTES * 60.0);
         self.time = self.time.rem_euclid(1.0);
     }
 
@@ -137,11 +149,66 @@ impl Sun {
     }
 }
 
+/// Фаза луны. Меняется раз в игровой день, первая ночь мира - полнолуние
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MoonPhase {
+    New,
+    WaxingCrescent,
+    FirstQuarter,
+    WaxingGibbous,
+    Full,
+    WaningGibbous,
+    LastQuarter,
+    WaningCrescent,
+}
+
+impl MoonPhase {
+    pub const ALL: [MoonPhase; LUNAR_CYCLE_DAYS as usize] = [
+        MoonPhase::New,
+        MoonPhase::WaxingCrescent,
+        MoonPhase::FirstQuarter,
+        MoonPhase::WaxingGibbous,
+        MoonPhase::Full,
+        MoonPhase::WaningGibbous,
+        MoonPhase::LastQuarter,
+        MoonPhase::WaningCrescent,
+    ];
+
+    /// Фаза в ночь после дня days (DayCounter::days)
+    pub fn from_day(days: u32) -> Self {
+        let full = MoonPhase::Full as u32;
+        Self::ALL[((days + full) % LUNAR_CYCLE_DAYS) as usize]
+    }
+
+    /// Доля цикла (0.0 - новолуние, 0.5 - полнолуние) - для шейдера
+    pub fn fraction(self) -> f32 {
+        self as u32 as f32 / LUNAR_CYCLE_DAYS as f32
+    }
+
+    /// Освещённая часть диска (0.0 - 1.0)
+    pub fn illumination(self) -> f32 {
+        (1.0 - (self.fraction() * TAU).cos()) * 0.5
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            MoonPhase::New => "новолуние",
+            MoonPhase::WaxingCrescent => "растущий серп",
+            MoonPhase::FirstQuarter => "первая четверть",
+            MoonPhase::WaxingGibbous => "растущая луна",
+            MoonPhase::Full => "полнолуние",
+            MoonPhase::WaningGibbous => "убывающая луна",
+            MoonPhase::LastQuarter => "последняя четверть",
+            MoonPhase::WaningCrescent => "убывающий серп",
+        }
+    }
+}
+
 /// Луна
 pub struct Moon {
     pub body: CelestialBody,
-    /// Фаза луны (0.0 - 1.0)
-    pub phase: f32,
+    /// Фаза луны
+    pub phase: MoonPhase,
 }
 
 impl Moon {
@@ -152,7 +219,7 @@ impl Moon {
                 intensity: 0.15,
                 ..CelestialBody::new()
             },
-            phase: 0.5, // Полнолуние
+            phase: MoonPhase::Full,
         }
     }
 
@@ -172,15 +239,14 @@ impl Moon {
         // Видимость
         self.body.visibility = smoothstep(-0.1, 0.1, height);
 
-        // Интенсивность зависит от фазы и высоты
-        let phase_intensity = 0.5 + 0.5 * (self.phase * TAU).cos().abs();
+        // Интенсивность зависит от фазы и высоты (в новолуние - только звёзды)
+        let phase_intensity = 0.2 + 0.8 * self.phase.illumination();
         self.body.intensity = 0.15 * phase_intensity * smoothstep(-0.05, 0.2, height);
     }
 
-    /// Обновить фазу луны (медленный цикл ~29.5 дней)
-    pub fn update_phase(&mut self, dt: f32) {
-        self.phase += dt / (29.5 * 24.0 * 60.0 * 60.0);
-        self.phase = self.phase.rem_euclid(1.0);
+    /// Фаза по номеру прошедшего дня
+    pub fn set_day(&mut self, days: u32) {
+        self.phase = MoonPhase::from_day(days);
     }
 }
 
@@ -196,6 +262,10 @@ pub struct DayNightCycle {
     pub sky_color: Vec3,
     /// Цвет тумана
     pub fog_color: Vec3,
+    /// Облачность от погоды (0 = ясно, 1 = сплошные тучи)
+    pub overcast: f32,
+    /// Видимость звёзд (0.0 днём - 1.0 ясной ночью)
+    pub star_visibility: f32,
 }
 
 impl DayNightCycle {
@@ -208,6 +278,8 @@ impl DayNightCycle {
             ambient_intensity: 0.3,
             sky_color: Vec3::new(0.5, 0.7, 1.0),
             fog_color: Vec3::new(0.7, 0.8, 0.9),
+            overcast: 0.0,
+            star_visibility: 0.0,
         };
         cycle.update(0.0);
         cycle
@@ -219,20 +291,54 @@ impl DayNightCycle {
         self.update(0.0);
     }
 
+    /// Установить облачность (затемняет небо, туман и ambient)
+    pub fn set_overcast(&mut self, overcast: f32) {
+        self.overcast = overcast.clamp(0.0, 1.0);
+    }
+
     /// Установить скорость времени
     pub fn set_speed(&mut self, speed: f32) {
         self.time.speed = speed;
     }
 
+    /// Фаза луны по счётчику дней мира
+    pub fn set_day(&mut self, days: u32) {
+        self.moon.set_day(days);
+    }
+
+    /// Угол поворота небосвода вокруг SKY_AXIS (звёзды идут вместе с солнцем)
+    pub fn sky_rotation(&self) -> f32 {
+        self.time.sun_angle()
+    }
+
     /// Обновить всю систему
     pub fn update(&mut self, dt: f32) {
         self.time.update(dt);
         self.sun.update(&self.time);
         self.moon.update(&self.time);
-        self.moon.update_phase(dt);
         
         self.update_ambient();
         self.update_sky();
+        self.apply_overcast();
+    }
+
+    /// Тучи: небо и туман уходят в серый и темнеют, свет слабеет
+    fn apply_overcast(&mut self) {
+        if self.overcast <= 0.0 {
+            return;
+        }
+        let t = self.overcast;
+        let grey = |c: Vec3| {
+            let luma = c.x * 0.3 + c.y * 0.59 + c.z * 0.11;
+            Vec3::broadcast(luma * 0.55)
+        };
+        self.sky_color = self.sky_color + (grey(self.sky_color) - self.sky_color) * t;
+        self.fog_color = self.fog_color + (grey(self.fog_color) * 1.2 - self.fog_color) * t;
+        self.ambient_intensity *= 1.0 - 0.35 * t;
+        self.star_visibility *= 1.0 - t;
+        // Прямой свет сквозь тучи (пересчитывается в update, не накапливается)
+        self.sun.body.intensity *= 1.0 - 0.6 * t;
+        self.moon.body.intensity *= 1.0 - 0.6 * t;
     }
 
     fn update_ambient(&mut self) {
@@ -252,10 +358,13 @@ impl DayNightCycle {
             );
             self.ambient_intensity = 0.15 + 0.15 * t;
         } else {
-            // Ночь
+            // Ночь - в полнолуние светлее, в новолуние темнее
             self.ambient_color = Vec3::new(0.3, 0.35, 0.5);
-            self.ambient_intensity = 0.1 + 0.05 * self.moon.body.intensity;
+            self.ambient_intensity = 0.07 + 0.06 * self.moon.phase.illumination();
         }
+
+        // Звёзды проступают в сумерках, когда солнце уходит за горизонт
+        self.star_visibility = 1.0 - smoothstep(-0.2, 0.05, sun_h);
     }
 
     fn update_sky(&mut self) {
@@ -339,3 +448,30 @@ fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
     let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
     t * t * (3.0 - 2.0 * t)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moon_phase_cycle() {
+        assert_eq!(MoonPhase::from_day(0), MoonPhase::Full);
+        assert_eq!(MoonPhase::from_day(4), MoonPhase::New);
+        assert_eq!(MoonPhase::from_day(LUNAR_CYCLE_DAYS), MoonPhase::Full);
+        assert!((MoonPhase::Full.illumination() - 1.0).abs() < 1e-6);
+        assert!(MoonPhase::New.illumination() < 1e-6);
+        assert!((MoonPhase::FirstQuarter.illumination() - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn darker_nights_at_new_moon() {
+        let mut cycle = DayNightCycle::new();
+        cycle.set_time(0.0);
+        let full = (cycle.ambient_intensity, cycle.moon.body.intensity);
+        cycle.set_day(4);
+        cycle.update(0.0);
+        assert!(cycle.ambient_intensity < full.0);
+        assert!(cycle.moon.body.intensity < full.1);
+        assert_eq!(cycle.star_visibility, 1.0);
+    }
+}
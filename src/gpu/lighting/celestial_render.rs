@@ -1,12 +1,21 @@
 // ============================================
-// Celestial Bodies Renderer - Солнце и Луна
+// Celestial Bodies Renderer - Солнце, Луна и звёзды
 // ============================================
+// Звёзды - инстансы-квады на куполе, сгенерированные из фиксированного
+// зерна. Купол вращается вместе с солнцем и проступает ночью.
+
+use std::f32::consts::TAU;
 
 use bytemuck::{Pod, Zeroable};
 use wgpu::util::DeviceExt;
 use ultraviolet::{Vec3, Mat4};
 
-use crate::gpu::lighting::DayNightCycle;
+use crate::gpu::lighting::{DayNightCycle, SKY_AXIS};
+
+/// Звёзд на небе
+const STAR_COUNT: u32 = 1500;
+/// Зерно звёздного неба (одинаковое во всех мирах)
+const STAR_SEED: u64 = 0x57A2_F1E1_D000_0001;
 
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
@@ -36,6 +45,84 @@ impl CelestialVertex {
     }
 }
 
+/// Звезда на куполе (инстанс)
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct StarInstance {
+    /// Направление на звезду (нормализованное)
+    pub direction: [f32; 3],
+    /// Размер относительно расстояния до неба
+    pub size: f32,
+    pub color: [f32; 3],
+    /// Фаза мерцания (радианы)
+    pub twinkle: f32,
+}
+
+impl StarInstance {
+    pub fn desc() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<StarInstance>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &[
+                wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 2,
+                    format: wgpu::VertexFormat::Float32x3,
+                },
+                wgpu::VertexAttribute {
+                    offset: 12,
+                    shader_location: 3,
+                    format: wgpu::VertexFormat::Float32,
+                },
+                wgpu::VertexAttribute {
+                    offset: 16,
+                    shader_location: 4,
+                    format: wgpu::VertexFormat::Float32x3,
+                },
+                wgpu::VertexAttribute {
+                    offset: 28,
+                    shader_location: 5,
+                    format: wgpu::VertexFormat::Float32,
+                },
+            ],
+        }
+    }
+}
+
+/// Звёздное небо из зерна: равномерно по сфере, тусклых больше, чем ярких
+fn generate_stars(count: u32, seed: u64) -> Vec<StarInstance> {
+    // SplitMix64
+    let mut state = seed;
+    let mut next = move || {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        ((z ^ (z >> 31)) >> 40) as f32 / (1u64 << 24) as f32
+    };
+
+    (0..count)
+        .map(|_| {
+            let y = next() * 2.0 - 1.0;
+            let angle = next() * TAU;
+            let r = (1.0 - y * y).sqrt();
+            let brightness = next().powi(3);
+            // Холодные голубоватые и тёплые желтоватые звёзды
+            let warmth = next();
+            let color = [
+                (0.75 + 0.25 * warmth) * (0.35 + 0.65 * brightness),
+                (0.82 + 0.03 * warmth) * (0.35 + 0.65 * brightness),
+                (1.0 - 0.3 * warmth) * (0.35 + 0.65 * brightness),
+            ];
+            StarInstance {
+                direction: [r * angle.cos(), y, r * angle.sin()],
+                size: 0.0015 + 0.003 * brightness,
+                color,
+                twinkle: next() * TAU,
+            }
+        })
+        .collect()
+}
 
 /// Uniform данные - все vec3 заменены на vec4 для WGSL alignment
 #[repr(C)]
@@ -48,6 +135,8 @@ pub struct CelestialUniforms {
     pub moon_direction: [f32; 4],     // 16 bytes (xyz + visibility)
     pub moon_color: [f32; 4],         // 16 bytes (rgb + phase)
     pub time_of_day: [f32; 4],        // 16 bytes (time + pad)
+    pub sky_axis: [f32; 4],           // 16 bytes (ось небосвода xyz + угол)
+    pub stars: [f32; 4],              // 16 bytes (видимость, время, pad, pad)
 }
 
 impl Default for CelestialUniforms {
@@ -60,6 +149,8 @@ impl Default for CelestialUniforms {
             moon_direction: [0.0, -1.0, 0.0, 0.0],
             moon_color: [0.8, 0.85, 1.0, 0.5],
             time_of_day: [0.5, 0.0, 0.0, 0.0],
+            sky_axis: [SKY_AXIS[0], SKY_AXIS[1], SKY_AXIS[2], 0.0],
+            stars: [0.0; 4],
         }
     }
 }
@@ -70,11 +161,13 @@ pub struct CelestialRenderer {
     uniform_buffer: wgpu::Buffer,
     bind_group: wgpu::BindGroup,
     pipeline: wgpu::RenderPipeline,
+    star_buffer: wgpu::Buffer,
+    star_pipeline: wgpu::RenderPipeline,
 }
 
 
 impl CelestialRenderer {
-    pub fn new(device: &wgpu::Device, surface_format: wgpu::TextureFormat) -> Self {
+    pub fn new(device: &wgpu::Device, surface_format: wgpu::TextureFormat, sample_count: u32) -> Self {
         let vertices = vec![
             CelestialVertex { position: [-1.0, -1.0, 0.0], uv: [0.0, 1.0] },
             CelestialVertex { position: [ 1.0, -1.0, 0.0], uv: [1.0, 1.0] },
@@ -131,18 +224,46 @@ impl CelestialRenderer {
             push_constant_ranges: &[],
         });
 
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Celestial Pipeline"),
-            layout: Some(&pipeline_layout),
+        let pipeline = Self::create_pipeline(
+            device, &pipeline_layout, &shader, (surface_format, sample_count),
+            "Celestial Pipeline", ("vs_main", "fs_main"), &[CelestialVertex::desc()],
+        );
+        let star_pipeline = Self::create_pipeline(
+            device, &pipeline_layout, &shader, (surface_format, sample_count),
+            "Star Pipeline", ("vs_star", "fs_star"), &[CelestialVertex::desc(), StarInstance::desc()],
+        );
+
+        let star_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Star Instances"),
+            contents: bytemuck::cast_slice(&generate_stars(STAR_COUNT, STAR_SEED)),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        Self { vertex_buffer, index_buffer, uniform_buffer, bind_group, pipeline, star_buffer, star_pipeline }
+    }
+
+    /// Аддитивный пайплайн неба: глубина только читается
+    fn create_pipeline(
+        device: &wgpu::Device,
+        layout: &wgpu::PipelineLayout,
+        shader: &wgpu::ShaderModule,
+        (surface_format, sample_count): (wgpu::TextureFormat, u32),
+        label: &str,
+        (vs_entry, fs_entry): (&str, &str),
+        buffers: &[wgpu::VertexBufferLayout],
+    ) -> wgpu::RenderPipeline {
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some(label),
+            layout: Some(layout),
             vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: Some("vs_main"),
-                buffers: &[CelestialVertex::desc()],
+                module: shader,
+                entry_point: Some(vs_entry),
+                buffers,
                 compilation_options: Default::default(),
             },
             fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: Some("fs_main"),
+                module: shader,
+                entry_point: Some(fs_entry),
                 targets: &[Some(wgpu::ColorTargetState {
                     format: surface_format,
                     blend: Some(wgpu::BlendState {
@@ -174,16 +295,17 @@ impl CelestialRenderer {
                 stencil: Default::default(),
                 bias: Default::default(),
             }),
-            multisample: wgpu::MultisampleState::default(),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                ..Default::default()
+            },
             multiview: None,
             cache: None,
-        });
-
-        Self { vertex_buffer, index_buffer, uniform_buffer, bind_group, pipeline }
+        })
     }
 
 
-    pub fn update(&self, queue: &wgpu::Queue, view_proj: [[f32; 4]; 4], camera_pos: Vec3, day_night: &DayNightCycle) {
+    pub fn update(&self, queue: &wgpu::Queue, view_proj: [[f32; 4]; 4], camera_pos: Vec3, time: f32, day_night: &DayNightCycle) {
         let sun_dir = day_night.sun.body.direction;
         let moon_dir = day_night.moon.body.direction;
         let sun_col = day_night.sun.body.color;
@@ -195,17 +317,25 @@ impl CelestialRenderer {
             sun_direction: [sun_dir.x, sun_dir.y, sun_dir.z, day_night.sun.body.visibility],
             sun_color: [sun_col.x, sun_col.y, sun_col.z, 0.12],
             moon_direction: [moon_dir.x, moon_dir.y, moon_dir.z, day_night.moon.body.visibility],
-            moon_color: [moon_col.x, moon_col.y, moon_col.z, day_night.moon.phase],
+            moon_color: [moon_col.x, moon_col.y, moon_col.z, day_night.moon.phase.fraction()],
             time_of_day: [day_night.time.time, 0.0, 0.0, 0.0],
+            sky_axis: [SKY_AXIS[0], SKY_AXIS[1], SKY_AXIS[2], day_night.sky_rotation()],
+            stars: [day_night.star_visibility, time, 0.0, 0.0],
         };
         queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
     }
 
     pub fn render<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
-        render_pass.set_pipeline(&self.pipeline);
         render_pass.set_bind_group(0, &self.bind_group, &[]);
         render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
         render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+
+        // Звёзды под солнцем и луной
+        render_pass.set_pipeline(&self.star_pipeline);
+        render_pass.set_vertex_buffer(1, self.star_buffer.slice(..));
+        render_pass.draw_indexed(0..6, 0, 0..STAR_COUNT);
+
+        render_pass.set_pipeline(&self.pipeline);
         render_pass.draw_indexed(0..6, 0, 0..2);
     }
 }
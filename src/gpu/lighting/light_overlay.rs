@@ -0,0 +1,205 @@
+// ============================================
+// Light Overlay - Тепловая карта освещённости
+// ============================================
+// Режим для строителей: верхние грани блоков вокруг игрока
+// закрашиваются по уровню света от светящихся блоков. Красный -
+// ниже порога безопасного спавна, дальше жёлтый и зелёный.
+//
+// Полноценного распространения блочного света в движке пока нет,
+// поэтому уровень оценивается как в Minecraft без учёта препятствий:
+// light_level источника минус манхэттенское расстояние до клетки
+// над гранью. Небесный свет не учитывается (спавн зависит от блочного).
+// Источники - светящиеся блоки из изменений мира и LightSources (факелы).
+
+use bytemuck::{Pod, Zeroable};
+use ultraviolet::Vec3;
+
+use crate::gpu::blocks::{global_registry, AIR};
+use crate::gpu::terrain::generation::is_solid_3d;
+use crate::gpu::terrain::WorldChanges;
+
+use super::LightSources;
+
+/// Уровень света, ниже которого поверхность считается опасной
+const SPAWN_SAFE_LIGHT: u8 = 8;
+/// Максимальный уровень света
+const MAX_LIGHT: u8 = 15;
+/// Радиус оверлея вокруг игрока (блоки)
+const OVERLAY_RADIUS: i32 = 16;
+/// Поиск поверхностей по вертикали относительно ног игрока
+const SEARCH_UP: i32 = 6;
+const SEARCH_DOWN: i32 = 10;
+/// Подъём квада над гранью (против z-fighting)
+const SURFACE_OFFSET: f32 = 0.02;
+
+/// Режим оверлея (F9 переключает по кругу)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverlayMode {
+    #[default]
+    Off,
+    /// Тепловая карта освещённости
+    LightLevel,
+}
+
+impl OverlayMode {
+    pub const ALL: [OverlayMode; 2] = [OverlayMode::Off, OverlayMode::LightLevel];
+
+    /// Следующий режим по кругу
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&mode| mode == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            OverlayMode::Off => "Off",
+            OverlayMode::LightLevel => "Light Level",
+        }
+    }
+}
+
+/// Вершина оверлея
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct OverlayVertex {
+    pub position: [f32; 3],
+    pub color: [f32; 4],
+}
+
+/// Светящийся блок рядом с игроком
+struct LightSource {
+    pos: [i32; 3],
+    level: u8,
+}
+
+/// CPU часть оверлея: пересобирает меш при смене блока игрока или мира
+pub struct LightOverlay {
+    /// Блок игрока, версии мира и источников света, для которых собран меш
+    built_for: Option<([i32; 3], u64, u64)>,
+    vertices: Vec<OverlayVertex>,
+}
+
+impl LightOverlay {
+    pub fn new() -> Self {
+        Self {
+            built_for: None,
+            vertices: Vec::new(),
+        }
+    }
+
+    /// Сбросить меш (пересоберётся при следующем включении)
+    pub fn invalidate(&mut self) {
+        self.built_for = None;
+    }
+
+    /// Пересобрать меш, если нужно. Some - новые вершины для загрузки в GPU
+    pub fn rebuild(&mut self, player_pos: Vec3, world_changes: &WorldChanges, lights: &LightSources) -> Option<&[OverlayVertex]> {
+        let center = [
+            player_pos.x.floor() as i32,
+            player_pos.y.floor() as i32,
+            player_pos.z.floor() as i32,
+        ];
+        let key = (center, world_changes.version(), lights.version());
+        if self.built_for == Some(key) {
+            return None;
+        }
+        self.built_for = Some(key);
+
+        let sources = collect_sources(center, world_changes, lights);
+        let is_solid = |x: i32, y: i32, z: i32| match world_changes.get_block(x, y, z) {
+            Some(block) => block != AIR,
+            None => is_solid_3d(x as f32, y as f32, z as f32),
+        };
+
+        self.vertices.clear();
+        for x in (center[0] - OVERLAY_RADIUS)..=(center[0] + OVERLAY_RADIUS) {
+            for z in (center[2] - OVERLAY_RADIUS)..=(center[2] + OVERLAY_RADIUS) {
+                let (dx, dz) = (x - center[0], z - center[2]);
+                if dx * dx + dz * dz > OVERLAY_RADIUS * OVERLAY_RADIUS {
+                    continue;
+                }
+
+                // Все поверхности столбца в диапазоне (пол, крыша, уступы)
+                let mut above_solid = is_solid(x, center[1] + SEARCH_UP + 1, z);
+                for y in ((center[1] - SEARCH_DOWN)..=(center[1] + SEARCH_UP)).rev() {
+                    let solid = is_solid(x, y, z);
+                    if solid && !above_solid {
+                        let level = light_at(&sources, [x, y + 1, z]);
+                        push_quad(&mut self.vertices, x, y + 1, z, level_color(level));
+                    }
+                    above_solid = solid;
+                }
+            }
+        }
+
+        Some(&self.vertices)
+    }
+}
+
+impl Default for LightOverlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Светящиеся блоки из изменений мира и зарегистрированные источники,
+/// достающие до области оверлея
+fn collect_sources(center: [i32; 3], world_changes: &WorldChanges, lights: &LightSources) -> Vec<LightSource> {
+    let Ok(registry) = global_registry().read() else { return Vec::new() };
+    let reach = OVERLAY_RADIUS + MAX_LIGHT as i32;
+    let in_reach = |pos: [i32; 3]| {
+        (pos[0] - center[0]).abs() <= reach
+            && (pos[2] - center[2]).abs() <= reach
+            && pos[1] >= center[1] - SEARCH_DOWN - MAX_LIGHT as i32
+            && pos[1] <= center[1] + SEARCH_UP + MAX_LIGHT as i32
+    };
+
+    let blocks = world_changes
+        .iter()
+        .filter(|(pos, _)| in_reach([pos.x, pos.y, pos.z]))
+        .filter_map(|(pos, block)| {
+            let level = registry.get_by_numeric(*block)?.light_level;
+            (level > 0).then_some(LightSource { pos: [pos.x, pos.y, pos.z], level })
+        });
+    let registered = lights
+        .iter()
+        .filter(|&(pos, level)| level > 0 && in_reach(pos))
+        .map(|(pos, level)| LightSource { pos, level });
+    blocks.chain(registered).collect()
+}
+
+/// Уровень блочного света в клетке (без учёта препятствий)
+fn light_at(sources: &[LightSource], cell: [i32; 3]) -> u8 {
+    sources
+        .iter()
+        .map(|source| {
+            let distance = (source.pos[0] - cell[0]).abs()
+                + (source.pos[1] - cell[1]).abs()
+                + (source.pos[2] - cell[2]).abs();
+            (source.level.min(MAX_LIGHT) as i32 - distance).max(0) as u8
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Цвет по уровню: красный ниже порога, затем жёлтый -> зелёный
+fn level_color(level: u8) -> [f32; 4] {
+    if level < SPAWN_SAFE_LIGHT {
+        // Чем темнее, тем насыщеннее красный
+        let darkness = 1.0 - level as f32 / SPAWN_SAFE_LIGHT as f32;
+        return [0.9, 0.1, 0.08, 0.25 + darkness * 0.25];
+    }
+    let t = (level - SPAWN_SAFE_LIGHT) as f32 / (MAX_LIGHT - SPAWN_SAFE_LIGHT) as f32;
+    [0.95 - t * 0.75, 0.85, 0.15, 0.35]
+}
+
+/// Горизонтальный квад на высоте y (верх блока y - 1)
+fn push_quad(vertices: &mut Vec<OverlayVertex>, x: i32, y: i32, z: i32, color: [f32; 4]) {
+    let (x0, z0) = (x as f32, z as f32);
+    let (x1, z1) = (x0 + 1.0, z0 + 1.0);
+    let y = y as f32 + SURFACE_OFFSET;
+
+    for position in [[x0, y, z0], [x1, y, z0], [x1, y, z1], [x0, y, z0], [x1, y, z1], [x0, y, z1]] {
+        vertices.push(OverlayVertex { position, color });
+    }
+}
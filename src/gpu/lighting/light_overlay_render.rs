@@ -0,0 +1,129 @@
+// ============================================
+// Light Overlay Renderer - Отрисовка тепловой карты
+// ============================================
+// Полупрозрачные квады поверх граней в основном проходе.
+// Меш приходит с CPU только при пересборке, буфер растёт по мере надобности.
+
+use std::sync::Arc;
+
+use wgpu::util::DeviceExt;
+
+use crate::gpu::render::bind_groups::BindGroupLayouts;
+
+use super::light_overlay::OverlayVertex;
+
+pub struct LightOverlayRenderer {
+    device: Arc<wgpu::Device>,
+    pipeline: wgpu::RenderPipeline,
+    vertex_buffer: Option<wgpu::Buffer>,
+    vertex_count: u32,
+    enabled: bool,
+}
+
+impl LightOverlayRenderer {
+    pub fn new(
+        device: Arc<wgpu::Device>,
+        surface_format: wgpu::TextureFormat,
+        sample_count: u32,
+        layouts: &BindGroupLayouts,
+    ) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Light Overlay Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/light_overlay.wgsl").into()),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Light Overlay Layout"),
+            bind_group_layouts: &[&layouts.uniform],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Light Overlay Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<OverlayVertex>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Vertex,
+                    attributes: &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x4],
+                }],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                cull_mode: None,
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::GreaterEqual, // Reversed-Z
+                stencil: Default::default(),
+                bias: Default::default(),
+            }),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                ..Default::default()
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            device,
+            pipeline,
+            vertex_buffer: None,
+            vertex_count: 0,
+            enabled: false,
+        }
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Залить пересобранный меш
+    pub fn update(&mut self, queue: &wgpu::Queue, vertices: &[OverlayVertex]) {
+        self.vertex_count = vertices.len() as u32;
+        if vertices.is_empty() {
+            return;
+        }
+
+        let size = std::mem::size_of_val(vertices) as wgpu::BufferAddress;
+        match &self.vertex_buffer {
+            Some(buffer) if buffer.size() >= size => {
+                queue.write_buffer(buffer, 0, bytemuck::cast_slice(vertices));
+            }
+            _ => {
+                self.vertex_buffer = Some(self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("Light Overlay VB"),
+                    contents: bytemuck::cast_slice(vertices),
+                    usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                }));
+            }
+        }
+    }
+
+    pub fn render<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>, uniform_bind_group: &'a wgpu::BindGroup) {
+        if !self.enabled || self.vertex_count == 0 {
+            return;
+        }
+        let Some(buffer) = &self.vertex_buffer else { return };
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, uniform_bind_group, &[]);
+        render_pass.set_vertex_buffer(0, buffer.slice(..));
+        render_pass.draw(0..self.vertex_count, 0..1);
+    }
+}
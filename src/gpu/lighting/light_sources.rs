@@ -0,0 +1,72 @@
+// ============================================
+// Light Sources - Источники блочного света вне сетки блоков
+// ============================================
+// Светящиеся блоки в WorldChanges находятся по реестру (light_level).
+// Объекты, которые живут не в сетке блоков (факелы из субвокселей),
+// регистрируют свой свет здесь; версия растёт при каждом изменении,
+// чтобы зависящие от света меши пересобирались.
+
+use std::collections::HashMap;
+
+/// Точечные источники блочного света: клетка -> уровень
+pub struct LightSources {
+    sources: HashMap<[i32; 3], u8>,
+    version: u64,
+}
+
+impl LightSources {
+    pub fn new() -> Self {
+        Self {
+            sources: HashMap::new(),
+            version: 0,
+        }
+    }
+
+    /// Зарегистрировать источник (повторный вызов меняет уровень)
+    pub fn add(&mut self, pos: [i32; 3], level: u8) {
+        if self.sources.insert(pos, level) != Some(level) {
+            self.version += 1;
+        }
+    }
+
+    /// Убрать источник
+    pub fn remove(&mut self, pos: [i32; 3]) {
+        if self.sources.remove(&pos).is_some() {
+            self.version += 1;
+        }
+    }
+
+    /// Версия для отслеживания изменений
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Все источники
+    pub fn iter(&self) -> impl Iterator<Item = ([i32; 3], u8)> + '_ {
+        self.sources.iter().map(|(&pos, &level)| (pos, level))
+    }
+}
+
+impl Default for LightSources {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_tracks_real_changes() {
+        let mut lights = LightSources::new();
+        lights.add([1, 2, 3], 14);
+        let version = lights.version();
+        lights.add([1, 2, 3], 14);
+        lights.remove([9, 9, 9]);
+        assert_eq!(lights.version(), version);
+        lights.remove([1, 2, 3]);
+        assert!(lights.version() > version);
+        assert_eq!(lights.iter().count(), 0);
+    }
+}
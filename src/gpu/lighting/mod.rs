@@ -10,10 +10,18 @@ mod light;
 mod cascade;
 mod celestial;
 mod celestial_render;
+mod calendar;
+mod light_overlay;
+mod light_overlay_render;
+mod light_sources;
 
 pub use csm::CascadedShadowMaps;
 pub use shadow_map::ShadowMap;
 pub use light::{DirectionalLight, SunLight};
 pub use cascade::{Cascade, CascadeConfig};
-pub use celestial::{DayNightCycle, TimeOfDay, Sun, Moon, CelestialBody};
+pub use celestial::{DayNightCycle, TimeOfDay, Sun, Moon, CelestialBody, MoonPhase, DEFAULT_DAY_LENGTH_MINUTES, LUNAR_CYCLE_DAYS, SKY_AXIS};
 pub use celestial_render::CelestialRenderer;
+pub use calendar::{DayCounter, is_night, SUNRISE, SUNSET};
+pub use light_overlay::{LightOverlay, OverlayMode, OverlayVertex};
+pub use light_overlay_render::LightOverlayRenderer;
+pub use light_sources::LightSources;
@@ -27,4 +27,13 @@ fn vs_main(in: VertexInput) -> VertexOutput {
     return out;
 }
 
+// Субвоксели: позиция PackedVertex в 1/16 блока + угол чанка
+@vertex
+fn vs_subvoxel(@location(0) packed: vec4<u32>, @location(2) origin: vec3<f32>) -> VertexOutput {
+    var out: VertexOutput;
+    let position = origin + vec3<f32>(packed.xyz) / 16.0;
+    out.clip_position = shadow.light_view_proj * vec4<f32>(position, 1.0);
+    return out;
+}
+
 // Fragment shader не нужен - только depth write
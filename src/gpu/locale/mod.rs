@@ -0,0 +1,220 @@
+// ============================================
+// Locale - Перевод строк интерфейса
+// ============================================
+// Строки интерфейса берутся по ключу ("menu.resume") из файла языка:
+// плоский JSON ключ -> строка, подстановки в фигурных скобках
+// ("Day {day}"). Встроены assets/lang/*.json; файл lang/<код>.json рядом
+// с игрой заменяет встроенный или добавляет новый язык. Нет ключа в
+// текущем языке - английский, нет и там - сам ключ.
+// Язык задаётся в settings.json -> locale.language ("auto" - по системе)
+// и командой /lang.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::Path;
+use std::sync::{OnceLock, RwLock};
+
+use serde::{Serialize, Deserialize};
+
+/// Папка с файлами языков рядом с игрой
+pub const LANG_DIR: &str = "lang";
+
+/// Язык по умолчанию и запасной для недостающих ключей
+pub const FALLBACK_LANGUAGE: &str = "en";
+
+/// Язык из системы (LANG и т.п.)
+pub const AUTO_LANGUAGE: &str = "auto";
+
+/// Встроенные языки
+const BUILTIN: &[(&str, &str)] = &[
+    ("en", include_str!("../../../assets/lang/en.json")),
+    ("ru", include_str!("../../../assets/lang/ru.json")),
+    ("zh", include_str!("../../../assets/lang/zh.json")),
+];
+
+/// Коды встроенных языков (автодополнение /lang)
+pub const LANGUAGES: &[&str] = &["en", "ru", "zh"];
+
+/// Настройки языка (секция locale в settings.json)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LocaleSettings {
+    /// Код языка ("ru") или "auto"
+    pub language: String,
+}
+
+impl Default for LocaleSettings {
+    fn default() -> Self {
+        Self { language: AUTO_LANGUAGE.to_string() }
+    }
+}
+
+/// Строки одного языка
+#[derive(Debug, Clone)]
+pub struct Locale {
+    code: String,
+    strings: HashMap<String, String>,
+}
+
+impl Locale {
+    pub fn from_json(code: &str, json: &str) -> Result<Self, String> {
+        let strings = serde_json::from_str(json).map_err(|e| format!("{}: {}", code, e))?;
+        Ok(Self { code: code.to_string(), strings })
+    }
+
+    /// Встроенный язык
+    pub fn builtin(code: &str) -> Option<Self> {
+        let (_, json) = BUILTIN.iter().find(|(builtin, _)| *builtin == code)?;
+        Some(Self::from_json(code, json).expect("встроенный файл языка"))
+    }
+
+    /// lang/<код>.json, иначе встроенный
+    pub fn load(code: &str) -> Result<Self, String> {
+        let path = Path::new(LANG_DIR).join(format!("{}.json", code));
+        match std::fs::read_to_string(&path) {
+            Ok(json) => Self::from_json(code, &json),
+            Err(_) => Self::builtin(code).ok_or_else(|| format!("Нет языка '{}' (встроены: {})", code, LANGUAGES.join(", "))),
+        }
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.strings.get(key).map(String::as_str)
+    }
+
+    /// Название языка на нём самом
+    pub fn name(&self) -> &str {
+        self.get("language.name").unwrap_or(&self.code)
+    }
+}
+
+/// Текущий язык и запасной английский
+struct Catalog {
+    current: Locale,
+    fallback: Locale,
+}
+
+impl Catalog {
+    fn new(current: Locale) -> Self {
+        let fallback = Locale::builtin(FALLBACK_LANGUAGE).expect("встроенный английский");
+        Self { current, fallback }
+    }
+
+    fn translate<'a>(&'a self, key: &'a str) -> &'a str {
+        self.current.get(key).or_else(|| self.fallback.get(key)).unwrap_or(key)
+    }
+}
+
+static CATALOG: OnceLock<RwLock<Catalog>> = OnceLock::new();
+
+fn catalog() -> &'static RwLock<Catalog> {
+    CATALOG.get_or_init(|| RwLock::new(Catalog::new(Locale::builtin(FALLBACK_LANGUAGE).expect("встроенный английский"))))
+}
+
+/// Строка интерфейса по ключу
+pub fn tr(key: &str) -> String {
+    catalog().read().unwrap().translate(key).to_string()
+}
+
+/// Строка с подстановками: tr_args("toast.day", &[("day", &5)]) -> "Day 5"
+pub fn tr_args(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    substitute(&tr(key), args)
+}
+
+/// Подставить {имя} из args; неизвестные скобки остаются как есть
+fn substitute(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut result = template.to_string();
+    for (name, value) in args {
+        result = result.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    result
+}
+
+/// Код текущего языка
+pub fn language() -> String {
+    catalog().read().unwrap().current.code().to_string()
+}
+
+/// Название текущего языка
+pub fn language_name() -> String {
+    catalog().read().unwrap().current.name().to_string()
+}
+
+/// Переключить язык
+pub fn set_language(code: &str) -> Result<(), String> {
+    let locale = Locale::load(code)?;
+    log::info!("Язык интерфейса: {} ({})", locale.name(), locale.code());
+    *catalog().write().unwrap() = Catalog::new(locale);
+    Ok(())
+}
+
+/// Язык из настроек при старте (ошибка - английский)
+pub fn configure(settings: &LocaleSettings) {
+    let code = if settings.language == AUTO_LANGUAGE {
+        system_language().unwrap_or_else(|| FALLBACK_LANGUAGE.to_string())
+    } else {
+        settings.language.clone()
+    };
+    if let Err(e) = set_language(&code) {
+        log::warn!("{}, язык по умолчанию", e);
+        let _ = set_language(FALLBACK_LANGUAGE);
+    }
+}
+
+/// Встроенный язык системы из LC_ALL / LC_MESSAGES / LANG
+fn system_language() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find_map(|value| language_code(&value))
+        .filter(|code| LANGUAGES.contains(&code.as_str()))
+}
+
+/// "ru_RU.UTF-8" -> "ru"
+fn language_code(value: &str) -> Option<String> {
+    let code: String = value.chars().take_while(char::is_ascii_alphabetic).collect();
+    (code.len() == 2 || code.len() == 3).then(|| code.to_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_keys_fall_back_to_english_then_the_key() {
+        let current = Locale::from_json("test", r#"{ "menu.resume": "Назад в игру" }"#).unwrap();
+        let catalog = Catalog::new(current);
+        assert_eq!(catalog.translate("menu.resume"), "Назад в игру");
+        assert_eq!(catalog.translate("menu.settings"), "Settings");
+        assert_eq!(catalog.translate("no.such.key"), "no.such.key");
+        assert!(Locale::from_json("bad", r#"{ "key": 1 }"#).is_err());
+    }
+
+    #[test]
+    fn arguments_are_substituted_by_name() {
+        assert_eq!(substitute("{name} set at {x} {y}", &[("name", &"home"), ("x", &5), ("y", &-3)]), "home set at 5 -3");
+        assert_eq!(substitute("Day {day} {other}", &[("day", &2)]), "Day 2 {other}");
+    }
+
+    #[test]
+    fn builtin_languages_only_use_english_keys() {
+        let english = Locale::builtin(FALLBACK_LANGUAGE).unwrap();
+        for code in LANGUAGES {
+            let locale = Locale::builtin(code).unwrap();
+            let mut unknown: Vec<&String> = locale.strings.keys().filter(|key| english.get(key).is_none()).collect();
+            unknown.sort();
+            assert!(unknown.is_empty(), "{}: {:?}", code, unknown);
+        }
+    }
+
+    #[test]
+    fn system_locale_values_map_to_codes() {
+        assert_eq!(language_code("ru_RU.UTF-8").as_deref(), Some("ru"));
+        assert_eq!(language_code("zh_CN").as_deref(), Some("zh"));
+        assert_eq!(language_code("C"), None);
+        assert_eq!(language_code("POSIX"), None);
+    }
+}
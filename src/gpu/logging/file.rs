@@ -0,0 +1,81 @@
+// ============================================
+// Session File - Файл журнала текущей сессии
+// ============================================
+// latest.log - текущая сессия. При запуске (и при превышении размера)
+// он сдвигается в session-1.log, session-1 в session-2 и т.д.;
+// самый старый архив удаляется.
+
+use std::fs::{self, File};
+use std::io::{self, LineWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Папка журналов внутри папки конфигурации
+pub const LOG_DIR_NAME: &str = "logs";
+/// Журнал текущей сессии
+pub const LATEST_LOG: &str = "latest.log";
+/// Сколько прошлых журналов хранить
+const MAX_SESSION_LOGS: usize = 5;
+/// Предел размера файла, после которого он сдвигается в архив
+const MAX_LOG_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Открытый журнал сессии
+pub struct SessionFile {
+    dir: PathBuf,
+    writer: LineWriter<File>,
+    written: u64,
+}
+
+impl SessionFile {
+    /// Сдвинуть прошлые журналы и начать новый latest.log
+    pub fn open(dir: &Path) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        rotate(dir)?;
+        let file = File::create(dir.join(LATEST_LOG))?;
+        Ok(Self { dir: dir.to_path_buf(), writer: LineWriter::new(file), written: 0 })
+    }
+
+    pub fn path(&self) -> PathBuf {
+        self.dir.join(LATEST_LOG)
+    }
+
+    /// Дописать строку (переполненный файл уходит в архив)
+    pub fn write_line(&mut self, line: &str) -> io::Result<()> {
+        let len = line.len() as u64 + 1;
+        if self.written > 0 && self.written + len > MAX_LOG_BYTES {
+            self.writer.flush()?;
+            rotate(&self.dir)?;
+            self.writer = LineWriter::new(File::create(self.path())?);
+            self.written = 0;
+        }
+        writeln!(self.writer, "{}", line)?;
+        self.written += len;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+fn archived(dir: &Path, index: usize) -> PathBuf {
+    dir.join(format!("session-{}.log", index))
+}
+
+/// latest.log -> session-1.log, session-N -> session-(N+1)
+fn rotate(dir: &Path) -> io::Result<()> {
+    let oldest = archived(dir, MAX_SESSION_LOGS);
+    if oldest.exists() {
+        fs::remove_file(oldest)?;
+    }
+    for index in (1..MAX_SESSION_LOGS).rev() {
+        let from = archived(dir, index);
+        if from.exists() {
+            fs::rename(from, archived(dir, index + 1))?;
+        }
+    }
+    let latest = dir.join(LATEST_LOG);
+    if latest.exists() {
+        fs::rename(latest, archived(dir, 1))?;
+    }
+    Ok(())
+}
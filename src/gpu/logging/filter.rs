@@ -0,0 +1,109 @@
+// ============================================
+// Log Filter - Уровни логирования по модулям
+// ============================================
+// Синтаксис как у RUST_LOG: "info,terrain=debug,wgpu_core=warn".
+// Голый уровень - для всех целей, "цель=уровень" - для модуля и
+// его подмодулей. Цели игры пишутся без префикса крейта
+// ("systems::save_system"), внешние крейты - как есть.
+
+use std::fmt;
+
+use log::{Level, LevelFilter};
+
+/// Фильтр уровней по целям (модулям)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFilter {
+    default: LevelFilter,
+    /// (префикс цели, уровень), длинные префиксы первыми
+    directives: Vec<(String, LevelFilter)>,
+}
+
+impl LogFilter {
+    /// Разобрать строку фильтра. Уровень по умолчанию без явного - info
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut default = LevelFilter::Info;
+        let mut directives: Vec<(String, LevelFilter)> = Vec::new();
+
+        for part in spec.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+            match part.split_once('=') {
+                Some((target, level)) => {
+                    let target = target.trim();
+                    if target.is_empty() {
+                        return Err(format!("'{}' - нет имени модуля", part));
+                    }
+                    let level = parse_level(level.trim())?;
+                    directives.retain(|(existing, _)| existing != target);
+                    directives.push((target.to_string(), level));
+                }
+                None => default = parse_level(part)?,
+            }
+        }
+
+        // Самый длинный (точный) префикс проверяется первым
+        directives.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(&b.0)));
+        Ok(Self { default, directives })
+    }
+
+    /// Уровень для цели
+    pub fn level_for(&self, target: &str) -> LevelFilter {
+        self.directives
+            .iter()
+            .find(|(prefix, _)| matches_module(target, prefix))
+            .map_or(self.default, |(_, level)| *level)
+    }
+
+    pub fn enabled(&self, level: Level, target: &str) -> bool {
+        level <= self.level_for(target)
+    }
+
+    /// Самый подробный уровень среди всех правил (для log::set_max_level)
+    pub fn max_level(&self) -> LevelFilter {
+        self.directives.iter().map(|(_, level)| *level).fold(self.default, Ord::max)
+    }
+}
+
+impl fmt::Display for LogFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.default.as_str().to_lowercase())?;
+        for (target, level) in &self.directives {
+            write!(f, ",{}={}", target, level.as_str().to_lowercase())?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_level(level: &str) -> Result<LevelFilter, String> {
+    level.parse().map_err(|_| format!("'{}' - не уровень (off|error|warn|info|debug|trace)", level))
+}
+
+/// Цель совпадает с модулем или лежит внутри него
+fn matches_module(target: &str, module: &str) -> bool {
+    target
+        .strip_prefix(module)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn longest_module_prefix_wins() {
+        let filter = LogFilter::parse("warn, terrain=debug, terrain::manager=error").unwrap();
+        assert_eq!(filter.level_for("systems::save_system"), LevelFilter::Warn);
+        assert_eq!(filter.level_for("terrain::lod"), LevelFilter::Debug);
+        assert_eq!(filter.level_for("terrain::manager::hybrid"), LevelFilter::Error);
+        // Совпадение только по границе модуля
+        assert_eq!(filter.level_for("terrain_extra"), LevelFilter::Warn);
+        assert_eq!(filter.max_level(), LevelFilter::Debug);
+    }
+
+    #[test]
+    fn round_trips_and_rejects_garbage() {
+        let filter = LogFilter::parse("systems=TRACE").unwrap();
+        assert_eq!(filter.to_string(), "info,systems=trace");
+        assert_eq!(LogFilter::parse(&filter.to_string()).unwrap(), filter);
+        assert!(LogFilter::parse("loud").is_err());
+        assert!(LogFilter::parse("=debug").is_err());
+    }
+}
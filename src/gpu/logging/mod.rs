@@ -0,0 +1,208 @@
+// ============================================
+// Logging - Журнал сессии поверх фасада log
+// ============================================
+// Все сообщения игры идут через log::info!/warn!/... Логгер пишет их
+// в stderr, в файл logs/latest.log в папке конфигурации и в кольцевой
+// буфер для окна журнала в игре. Уровни задаются фильтром по модулям
+// (settings.json -> logging.filter, команда /log или RUST_LOG).
+
+mod file;
+mod filter;
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use log::{Level, Log, Metadata, Record};
+use serde::{Serialize, Deserialize};
+
+use crate::gpu::core::config_dir;
+
+pub use file::{LOG_DIR_NAME, LATEST_LOG};
+pub use filter::LogFilter;
+
+/// Фильтр по умолчанию: игра - info, болтливые крейты wgpu - только warn
+pub const DEFAULT_FILTER: &str = "info,wgpu_core=warn,wgpu_hal=warn,naga=warn";
+/// Сколько последних записей держать для окна журнала
+const MAX_RECENT: usize = 500;
+
+/// Настройки журнала (секция logging в settings.json)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogSettings {
+    /// Фильтр уровней по модулям ("info,terrain=debug")
+    pub filter: String,
+    /// Дублировать записи в stderr
+    pub stderr: bool,
+}
+
+impl Default for LogSettings {
+    fn default() -> Self {
+        Self { filter: DEFAULT_FILTER.to_string(), stderr: true }
+    }
+}
+
+/// Запись журнала
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub level: Level,
+    /// Модуль без префикса крейта ("systems::save_system")
+    pub target: String,
+    pub message: String,
+    /// Время от начала сессии
+    pub elapsed: Duration,
+}
+
+impl fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{:>9.3}] {:<5} {}: {}", self.elapsed.as_secs_f32(), self.level, self.target, self.message)
+    }
+}
+
+struct LoggerState {
+    filter: LogFilter,
+    stderr: bool,
+    file: Option<file::SessionFile>,
+    recent: VecDeque<LogEntry>,
+}
+
+struct SessionLogger {
+    start: Instant,
+    state: Mutex<LoggerState>,
+}
+
+static LOGGER: OnceLock<SessionLogger> = OnceLock::new();
+
+/// Цель записи без префикса крейта игры
+fn short_target(target: &str) -> &str {
+    // module_path!() этого модуля - "<крейт>::gpu::logging"
+    let root = module_path!().trim_end_matches("logging");
+    target.strip_prefix(root).unwrap_or(target)
+}
+
+impl Log for SessionLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.state
+            .lock()
+            .is_ok_and(|state| state.filter.enabled(metadata.level(), short_target(metadata.target())))
+    }
+
+    fn log(&self, record: &Record) {
+        let target = short_target(record.target());
+        let Ok(mut state) = self.state.lock() else { return };
+        if !state.filter.enabled(record.level(), target) {
+            return;
+        }
+
+        let entry = LogEntry {
+            level: record.level(),
+            target: target.to_string(),
+            message: record.args().to_string(),
+            elapsed: self.start.elapsed(),
+        };
+        let line = entry.to_string();
+        if state.stderr {
+            eprintln!("{}", line);
+        }
+        if let Some(file) = &mut state.file {
+            if let Err(e) = file.write_line(&line) {
+                eprintln!("[LOG] Запись в журнал остановлена: {}", e);
+                state.file = None;
+            }
+        }
+        if state.recent.len() >= MAX_RECENT {
+            state.recent.pop_front();
+        }
+        state.recent.push_back(entry);
+    }
+
+    fn flush(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            if let Some(file) = &mut state.file {
+                let _ = file.flush();
+            }
+        }
+    }
+}
+
+/// Папка журналов сессий
+pub fn log_dir() -> PathBuf {
+    config_dir().join(LOG_DIR_NAME)
+}
+
+/// Установить логгер и начать новый файл сессии. Фильтр - из RUST_LOG
+/// или по умолчанию, пока не загружены настройки (см. configure)
+pub fn init() {
+    let filter = std::env::var("RUST_LOG")
+        .ok()
+        .and_then(|spec| LogFilter::parse(&spec).ok())
+        .unwrap_or_else(|| LogFilter::parse(DEFAULT_FILTER).expect("фильтр по умолчанию"));
+
+    let dir = log_dir();
+    let (file, file_error) = match file::SessionFile::open(&dir) {
+        Ok(file) => (Some(file), None),
+        Err(e) => (None, Some(e)),
+    };
+    let max_level = filter.max_level();
+
+    let logger = LOGGER.get_or_init(|| SessionLogger {
+        start: Instant::now(),
+        state: Mutex::new(LoggerState { filter, stderr: true, file, recent: VecDeque::new() }),
+    });
+    if log::set_logger(logger).is_err() {
+        return;
+    }
+    log::set_max_level(max_level);
+
+    let started = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs());
+    log::info!("Сессия начата (unix {}), версия {}", started, env!("CARGO_PKG_VERSION"));
+    match file_error {
+        None => log::info!("Журнал: {}", dir.join(LATEST_LOG).display()),
+        Some(e) => log::warn!("Не удалось открыть журнал в {}: {}", dir.display(), e),
+    }
+}
+
+/// Применить настройки журнала. RUST_LOG, если задан, важнее настроек
+pub fn configure(settings: &LogSettings) {
+    let Some(logger) = LOGGER.get() else { return };
+    if let Ok(mut state) = logger.state.lock() {
+        state.stderr = settings.stderr;
+    }
+    if std::env::var_os("RUST_LOG").is_some() {
+        log::info!("Фильтр из RUST_LOG: {}", filter_spec());
+        return;
+    }
+    if let Err(e) = set_filter(&settings.filter) {
+        log::warn!("Фильтр журнала '{}' не применён: {}", settings.filter, e);
+    }
+}
+
+/// Сменить фильтр на лету (консоль /log)
+pub fn set_filter(spec: &str) -> Result<(), String> {
+    let filter = LogFilter::parse(spec)?;
+    let Some(logger) = LOGGER.get() else { return Ok(()) };
+    let max_level = filter.max_level();
+    if let Ok(mut state) = logger.state.lock() {
+        state.filter = filter;
+    }
+    log::set_max_level(max_level);
+    Ok(())
+}
+
+/// Текущий фильтр строкой
+pub fn filter_spec() -> String {
+    LOGGER
+        .get()
+        .and_then(|logger| logger.state.lock().ok().map(|state| state.filter.to_string()))
+        .unwrap_or_else(|| DEFAULT_FILTER.to_string())
+}
+
+/// Последние записи (старые первыми), не более max
+pub fn recent(max: usize) -> Vec<LogEntry> {
+    let Some(logger) = LOGGER.get() else { return Vec::new() };
+    let Ok(state) = logger.state.lock() else { return Vec::new() };
+    let skip = state.recent.len().saturating_sub(max);
+    state.recent.iter().skip(skip).cloned().collect()
+}
@@ -6,6 +6,8 @@
 
 pub mod terrain;
 pub mod blocks;
+pub mod block_entity;
+pub mod items;
 pub mod lighting;
 pub mod render;
 pub mod gui;
@@ -14,6 +16,17 @@ pub mod audio;
 pub mod player;
 pub mod subvoxel;
 pub mod biomes;
+pub mod resource_pack;
+pub mod input;
+pub mod weather;
+pub mod ticks;
+pub mod clouds;
+pub mod particles;
+pub mod console;
+pub mod logging;
+pub mod locale;
+pub mod profiler;
+pub mod benchmark;
 
 // Новые модули после рефакторинга
 pub mod core;
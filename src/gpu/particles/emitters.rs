@@ -0,0 +1,247 @@
+// ============================================
+// Particle Emitters - Источники частиц
+// ============================================
+// Разовые эмиттеры (обломки блока) вызываются напрямую из систем,
+// непрерывные (шаги, брызги, дым) обновляются раз в кадр
+// по состоянию игрока и изменениям мира.
+
+use std::collections::HashMap;
+
+use ultraviolet::Vec3;
+
+use crate::gpu::blocks::{get_face_colors, global_registry, BlockType, AIR, WATER};
+use crate::gpu::player::Player;
+use crate::gpu::terrain::BlockPos;
+use crate::gpu::terrain::cache::column_biome;
+use crate::gpu::terrain::generation::world_gen_config;
+
+use super::particle::{Particle, ParticleSystem};
+
+/// Цвет воды для брызг (rgb + альфа)
+const WATER_COLOR: [f32; 4] = [0.55, 0.7, 0.95, 0.8];
+/// Радиус поиска светящихся блоков для дыма
+const SMOKE_RADIUS: i32 = 16;
+/// Интервал выпуска дыма одним блоком (секунды)
+const SMOKE_INTERVAL: f32 = 0.35;
+
+impl ParticleSystem {
+    /// Обломки сломанного блока (цвета верха и боков)
+    pub fn emit_block_break(&mut self, block_pos: [i32; 3], block: BlockType) {
+        let (top, side) = get_face_colors(block);
+        let center = Vec3::new(block_pos[0] as f32 + 0.5, block_pos[1] as f32 + 0.5, block_pos[2] as f32 + 0.5);
+        self.emit_debris(center, 0.5, top, side, 24, 0.08);
+    }
+
+    /// Обломки суб-вокселя (меньше и мельче)
+    pub fn emit_subvoxel_break(&mut self, center: Vec3, voxel_size: f32, block: BlockType) {
+        let (top, side) = get_face_colors(block);
+        let count = ((voxel_size * 24.0) as usize).clamp(4, 12);
+        self.emit_debris(center, voxel_size * 0.5, top, side, count, (voxel_size * 0.15).max(0.02));
+    }
+
+    fn emit_debris(&mut self, center: Vec3, extent: f32, top: [f32; 3], side: [f32; 3], count: usize, size: f32) {
+        for i in 0..count {
+            let offset = Vec3::new(self.random_signed(), self.random_signed(), self.random_signed()) * extent;
+            let velocity = Vec3::new(
+                offset.x * 4.0 + self.random_signed() * 0.8,
+                2.0 + self.random() * 2.5,
+                offset.z * 4.0 + self.random_signed() * 0.8,
+            );
+            // Треть обломков - цвет верха (трава на земле и т.п.)
+            let base = if i % 3 == 0 { top } else { side };
+            let shade = 0.85 + self.random() * 0.25;
+            let lifetime = 0.8 + self.random() * 0.7;
+            let particle = Particle {
+                position: center + offset,
+                velocity,
+                color: [base[0] * shade, base[1] * shade, base[2] * shade, 1.0],
+                size: size * (0.7 + self.random() * 0.6),
+                growth: 0.0,
+                age: 0.0,
+                lifetime,
+                gravity: 20.0,
+                drag: 0.5,
+                collides: true,
+            };
+            self.spawn(particle);
+        }
+    }
+
+    /// Пыль из-под ног (цвет блока под игроком)
+    pub fn emit_footstep(&mut self, feet: Vec3, block: BlockType) {
+        let (top, _) = get_face_colors(block);
+        for _ in 0..4 {
+            let velocity = Vec3::new(self.random_signed() * 0.8, 0.8 + self.random() * 0.8, self.random_signed() * 0.8);
+            let shade = 0.8 + self.random() * 0.2;
+            let lifetime = 0.35 + self.random() * 0.25;
+            let particle = Particle {
+                position: feet + Vec3::new(self.random_signed() * 0.2, 0.05, self.random_signed() * 0.2),
+                velocity,
+                color: [top[0] * shade, top[1] * shade, top[2] * shade, 0.9],
+                size: 0.04,
+                growth: 0.0,
+                age: 0.0,
+                lifetime,
+                gravity: 9.0,
+                drag: 2.0,
+                collides: true,
+            };
+            self.spawn(particle);
+        }
+    }
+
+    /// Брызги при входе в воду (strength 0-1 от скорости падения)
+    pub fn emit_splash(&mut self, position: Vec3, strength: f32) {
+        let count = 12 + (strength * 28.0) as usize;
+        for _ in 0..count {
+            let angle = self.random() * std::f32::consts::TAU;
+            let speed = 1.0 + self.random() * 2.0 * (0.5 + strength);
+            let velocity = Vec3::new(angle.cos() * speed, 3.0 + self.random() * 4.0 * strength, angle.sin() * speed);
+            let lifetime = 0.5 + self.random() * 0.4;
+            let particle = Particle {
+                position: Vec3::new(position.x, 0.05, position.z),
+                velocity,
+                color: WATER_COLOR,
+                size: 0.05,
+                growth: 0.0,
+                age: 0.0,
+                lifetime,
+                gravity: 18.0,
+                drag: 0.3,
+                collides: false,
+            };
+            self.spawn(particle);
+        }
+    }
+
+    /// Струйка дыма над светящимся блоком
+    pub fn emit_smoke(&mut self, block_pos: BlockPos) {
+        let position = Vec3::new(
+            block_pos.x as f32 + 0.5 + self.random_signed() * 0.15,
+            block_pos.y as f32 + 1.05,
+            block_pos.z as f32 + 0.5 + self.random_signed() * 0.15,
+        );
+        let grey = 0.3 + self.random() * 0.15;
+        let lifetime = 1.8 + self.random() * 1.2;
+        let particle = Particle {
+            position,
+            velocity: Vec3::new(self.random_signed() * 0.1, 0.6 + self.random() * 0.3, self.random_signed() * 0.1),
+            color: [grey, grey, grey, 0.45],
+            size: 0.08,
+            growth: 0.12,
+            age: 0.0,
+            lifetime,
+            gravity: -0.3,
+            drag: 0.6,
+            collides: false,
+        };
+        self.spawn(particle);
+    }
+}
+
+/// Состояние непрерывных эмиттеров
+pub struct ParticleEmitters {
+    /// Пройденное расстояние с последнего шага
+    step_distance: f32,
+    last_position: Option<Vec3>,
+    /// Таймер дыма
+    smoke_timer: f32,
+    /// Блоки с light_level > 0 (из реестра)
+    light_blocks: Vec<BlockType>,
+}
+
+impl ParticleEmitters {
+    pub fn new() -> Self {
+        let light_blocks = global_registry()
+            .read()
+            .map(|registry| {
+                registry
+                    .all_blocks()
+                    .filter(|def| def.light_level > 0 && def.numeric_id != AIR)
+                    .map(|def| def.numeric_id)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            step_distance: 0.0,
+            last_position: None,
+            smoke_timer: 0.0,
+            light_blocks,
+        }
+    }
+
+    /// Обновить эмиттеры за кадр
+    /// is_solid(x, y, z) - твёрдость блока (изменения мира + генерация)
+    pub fn update<F>(
+        &mut self,
+        particles: &mut ParticleSystem,
+        dt: f32,
+        player: &Player,
+        world_changes: &HashMap<BlockPos, BlockType>,
+        is_solid: F,
+    ) where
+        F: Fn(i32, i32, i32) -> bool,
+    {
+        let feet = player.position;
+        let last = self.last_position.replace(feet).unwrap_or(feet);
+
+        // Шаги: тот же шаг, что и у звука шагов
+        let moved = Vec3::new(feet.x - last.x, 0.0, feet.z - last.z).mag();
+        if player.on_ground && moved > 0.001 {
+            self.step_distance += moved;
+            let step = if player.is_sprinting { 2.8 } else { 3.5 };
+            if self.step_distance >= step {
+                self.step_distance = 0.0;
+                let (x, y, z) = (feet.x.floor() as i32, (feet.y - 0.1).floor() as i32, feet.z.floor() as i32);
+                if is_solid(x, y, z) {
+                    let block = world_changes
+                        .get(&BlockPos::new(x, y, z))
+                        .copied()
+                        .unwrap_or_else(|| column_biome(x, z).surface_block);
+                    particles.emit_footstep(feet, block);
+                }
+            }
+        }
+
+        // Брызги: ноги пересекли уровень моря над водой (не над сушей)
+        let sea_level = world_gen_config().sea_level;
+        if last.y >= sea_level as f32 && feet.y < sea_level as f32 {
+            let (x, z) = (feet.x.floor() as i32, feet.z.floor() as i32);
+            let water = match world_changes.get(&BlockPos::new(x, sea_level - 1, z)) {
+                Some(&block) => block == WATER,
+                None => !is_solid(x, sea_level - 1, z),
+            };
+            if water {
+                let fall_speed = (last.y - feet.y) / dt.max(0.0001);
+                particles.emit_splash(feet, (fall_speed / 20.0).clamp(0.0, 1.0));
+            }
+        }
+
+        // Дым над светящимися блоками рядом с игроком
+        self.smoke_timer += dt;
+        if self.smoke_timer >= SMOKE_INTERVAL && !self.light_blocks.is_empty() {
+            self.smoke_timer = 0.0;
+            let (px, py, pz) = (feet.x as i32, feet.y as i32, feet.z as i32);
+            for (pos, block) in world_changes {
+                if (pos.x - px).abs() > SMOKE_RADIUS
+                    || (pos.y - py).abs() > SMOKE_RADIUS
+                    || (pos.z - pz).abs() > SMOKE_RADIUS
+                    || !self.light_blocks.contains(block)
+                {
+                    continue;
+                }
+                // Закрытый сверху блок не дымит
+                if !is_solid(pos.x, pos.y + 1, pos.z) {
+                    particles.emit_smoke(*pos);
+                }
+            }
+        }
+    }
+}
+
+impl Default for ParticleEmitters {
+    fn default() -> Self {
+        Self::new()
+    }
+}
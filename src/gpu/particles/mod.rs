@@ -0,0 +1,14 @@
+// ============================================
+// Particles Module - Частицы
+// ============================================
+// - particle: пул частиц на CPU (простая физика, столкновение с блоками)
+// - emitters: обломки блоков, пыль шагов, брызги воды, дым светящихся блоков
+// - render: инстансинг billboard-квадов в основном проходе (тест глубины)
+
+mod particle;
+mod emitters;
+mod render;
+
+pub use particle::{Particle, ParticleInstance, ParticleSystem, MAX_PARTICLES};
+pub use emitters::ParticleEmitters;
+pub use render::ParticleRenderer;
@@ -0,0 +1,129 @@
+// ============================================
+// Particle - Пул частиц
+// ============================================
+// Частицы живут на CPU: их мало (тысячи), физика тривиальная,
+// а столкновения с блоками проще проверить здесь, чем в шейдере.
+// На GPU каждый кадр уходит только массив ParticleInstance.
+
+use bytemuck::{Pod, Zeroable};
+use ultraviolet::Vec3;
+
+/// Максимум одновременно живых частиц
+pub const MAX_PARTICLES: usize = 4096;
+
+/// Одна частица
+#[derive(Debug, Clone, Copy)]
+pub struct Particle {
+    pub position: Vec3,
+    pub velocity: Vec3,
+    /// rgb + начальная альфа
+    pub color: [f32; 4],
+    /// Половина стороны квадрата
+    pub size: f32,
+    /// Рост размера (блоков/с) - для дыма
+    pub growth: f32,
+    pub age: f32,
+    pub lifetime: f32,
+    /// Ускорение вниз (отрицательное - всплывает)
+    pub gravity: f32,
+    /// Сопротивление воздуха (1/с)
+    pub drag: f32,
+    /// Сталкивается ли с блоками
+    pub collides: bool,
+}
+
+/// Данные инстанса для GPU
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct ParticleInstance {
+    pub position_size: [f32; 4],  // xyz + half size
+    pub color: [f32; 4],          // rgb + alpha
+}
+
+/// Пул частиц
+pub struct ParticleSystem {
+    particles: Vec<Particle>,
+    rng: u32,
+}
+
+impl ParticleSystem {
+    pub fn new() -> Self {
+        Self {
+            particles: Vec::with_capacity(MAX_PARTICLES),
+            rng: 0x2545_F491,
+        }
+    }
+
+    /// Псевдослучайное число 0-1 (xorshift, для разброса частиц)
+    pub(super) fn random(&mut self) -> f32 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 17;
+        self.rng ^= self.rng << 5;
+        (self.rng >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    /// Случайное число в диапазоне -1..1
+    pub(super) fn random_signed(&mut self) -> f32 {
+        self.random() * 2.0 - 1.0
+    }
+
+    /// Добавить частицу (при переполнении новая отбрасывается)
+    pub fn spawn(&mut self, particle: Particle) {
+        if self.particles.len() < MAX_PARTICLES {
+            self.particles.push(particle);
+        }
+    }
+
+    /// Шаг симуляции
+    /// is_solid(x, y, z) - твёрдость блока для столкновений
+    pub fn update<F>(&mut self, dt: f32, is_solid: F)
+    where
+        F: Fn(i32, i32, i32) -> bool,
+    {
+        self.particles.retain_mut(|p| {
+            p.age += dt;
+            if p.age >= p.lifetime {
+                return false;
+            }
+
+            p.velocity.y -= p.gravity * dt;
+            p.velocity *= (1.0 - p.drag * dt).max(0.0);
+
+            let next = p.position + p.velocity * dt;
+            if p.collides && is_solid(next.x.floor() as i32, next.y.floor() as i32, next.z.floor() as i32) {
+                // Удар о блок: гасим вертикальную скорость с лёгким отскоком
+                p.velocity.y *= -0.25;
+                p.velocity.x *= 0.5;
+                p.velocity.z *= 0.5;
+                let slide = Vec3::new(next.x, p.position.y, next.z);
+                if !is_solid(slide.x.floor() as i32, slide.y.floor() as i32, slide.z.floor() as i32) {
+                    p.position = slide;
+                }
+            } else {
+                p.position = next;
+            }
+            true
+        });
+    }
+
+    /// Инстансы для рендера (альфа гаснет к концу жизни)
+    pub fn instances(&self) -> Vec<ParticleInstance> {
+        self.particles
+            .iter()
+            .map(|p| {
+                let life = p.age / p.lifetime;
+                let fade = 1.0 - life * life;
+                ParticleInstance {
+                    position_size: [p.position.x, p.position.y, p.position.z, p.size + p.growth * p.age],
+                    color: [p.color[0], p.color[1], p.color[2], p.color[3] * fade],
+                }
+            })
+            .collect()
+    }
+}
+
+impl Default for ParticleSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
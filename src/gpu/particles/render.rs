@@ -0,0 +1,140 @@
+// ============================================
+// Particle Renderer - Инстансинг частиц
+// ============================================
+// Без вершинного буфера: 6 вершин на квад, центр/размер/цвет
+// частицы из storage buffer по instance_index. Рисуется в основном
+// проходе после непрозрачной геометрии: тест глубины без записи.
+
+use std::sync::Arc;
+
+use crate::gpu::render::bind_groups::BindGroupLayouts;
+
+use super::particle::{ParticleInstance, MAX_PARTICLES};
+
+pub struct ParticleRenderer {
+    pipeline: wgpu::RenderPipeline,
+    instance_buffer: wgpu::Buffer,
+    instance_bind_group: wgpu::BindGroup,
+    count: u32,
+}
+
+impl ParticleRenderer {
+    pub fn new(
+        device: Arc<wgpu::Device>,
+        surface_format: wgpu::TextureFormat,
+        sample_count: u32,
+        layouts: &BindGroupLayouts,
+    ) -> Self {
+        let instance_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Particle Instances Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Storage { read_only: true },
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+
+        // Пул ограничен MAX_PARTICLES, поэтому буфер фиксированного размера
+        let instance_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Particle Instances"),
+            size: (MAX_PARTICLES * std::mem::size_of::<ParticleInstance>()) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let instance_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Particle Instances BG"),
+            layout: &instance_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: instance_buffer.as_entire_binding(),
+            }],
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Particle Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/particles.wgsl").into()),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Particle Layout"),
+            bind_group_layouts: &[&layouts.uniform, &layouts.light, &instance_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Particle Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                cull_mode: None,
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::Greater, // Reversed-Z
+                stencil: Default::default(),
+                bias: Default::default(),
+            }),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                ..Default::default()
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            pipeline,
+            instance_buffer,
+            instance_bind_group,
+            count: 0,
+        }
+    }
+
+    /// Залить инстансы кадра
+    pub fn update(&mut self, queue: &wgpu::Queue, instances: &[ParticleInstance]) {
+        let instances = &instances[..instances.len().min(MAX_PARTICLES)];
+        self.count = instances.len() as u32;
+        if !instances.is_empty() {
+            queue.write_buffer(&self.instance_buffer, 0, bytemuck::cast_slice(instances));
+        }
+    }
+
+    pub fn render<'a>(
+        &'a self,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        uniform_bind_group: &'a wgpu::BindGroup,
+        light_bind_group: &'a wgpu::BindGroup,
+    ) {
+        if self.count == 0 {
+            return;
+        }
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, uniform_bind_group, &[]);
+        render_pass.set_bind_group(1, light_bind_group, &[]);
+        render_pass.set_bind_group(2, &self.instance_bind_group, &[]);
+        render_pass.draw(0..6, 0..self.count);
+    }
+}
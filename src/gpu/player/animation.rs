@@ -0,0 +1,283 @@
+// ============================================
+// Player Animation - Скелетная анимация модели
+// ============================================
+// Модель игрока разбита на кости (тело, голова, руки, ноги), каждая
+// поворачивается вокруг своего шарнира по оси X модели. Клипы (покой,
+// ходьба, бег, прыжок, плавание) заданы ключевыми позами; между
+// ключами поза интерполируется, при смене клипа старый и новый клип
+// плавно смешиваются.
+//
+// Аниматор зависит только от AnimationState (скорость и флаги), а не
+// от Player: удалённым игрокам хватит состояния из сети.
+//
+// Углы: положительный - конечность вперёд, голова вверх, тело
+// наклонено вперёд.
+
+use super::player::Player;
+
+/// Кости модели игрока (индекс - номер кости в вершинах и шейдере)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bone {
+    Body = 0,
+    Head = 1,
+    LeftArm = 2,
+    RightArm = 3,
+    LeftLeg = 4,
+    RightLeg = 5,
+}
+
+pub const BONE_COUNT: usize = 6;
+
+/// Время смешивания клипов (секунды)
+const BLEND_TIME: f32 = 0.2;
+/// Горизонтальная скорость, ниже которой игрок стоит (блоков/с)
+const IDLE_SPEED: f32 = 0.5;
+/// Скорости, под которые рассчитаны циклы ходьбы и бега
+const WALK_REFERENCE_SPEED: f32 = 5.0;
+const SPRINT_REFERENCE_SPEED: f32 = 8.0;
+
+/// Поза: углы костей (радианы) и подъём тела (блоки)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Pose {
+    pub angles: [f32; BONE_COUNT],
+    pub bob: f32,
+}
+
+impl Pose {
+    const fn new(body: f32, head: f32, arms: [f32; 2], legs: [f32; 2], bob: f32) -> Self {
+        Self { angles: [body, head, arms[0], arms[1], legs[0], legs[1]], bob }
+    }
+
+    pub fn angle(&self, bone: Bone) -> f32 {
+        self.angles[bone as usize]
+    }
+
+    pub fn lerp(&self, other: &Pose, t: f32) -> Pose {
+        let mut angles = self.angles;
+        for (angle, target) in angles.iter_mut().zip(other.angles) {
+            *angle += (target - *angle) * t;
+        }
+        Pose { angles, bob: self.bob + (other.bob - self.bob) * t }
+    }
+}
+
+/// Ключевая поза клипа
+struct Keyframe {
+    time: f32,
+    pose: Pose,
+}
+
+const fn key(time: f32, pose: Pose) -> Keyframe {
+    Keyframe { time, pose }
+}
+
+/// Клипы: последняя поза совпадает с первой (цикл)
+const IDLE: [Keyframe; 3] = [
+    key(0.0, Pose::new(0.0, 0.0, [0.03, 0.03], [0.0, 0.0], 0.0)),
+    key(1.5, Pose::new(0.0, 0.04, [0.08, 0.08], [0.0, 0.0], -0.01)),
+    key(3.0, Pose::new(0.0, 0.0, [0.03, 0.03], [0.0, 0.0], 0.0)),
+];
+
+const WALK: [Keyframe; 5] = [
+    key(0.0, Pose::new(0.0, 0.0, [0.5, -0.5], [-0.6, 0.6], 0.0)),
+    key(0.2, Pose::new(0.0, 0.0, [0.0, 0.0], [0.0, 0.0], 0.04)),
+    key(0.4, Pose::new(0.0, 0.0, [-0.5, 0.5], [0.6, -0.6], 0.0)),
+    key(0.6, Pose::new(0.0, 0.0, [0.0, 0.0], [0.0, 0.0], 0.04)),
+    key(0.8, Pose::new(0.0, 0.0, [0.5, -0.5], [-0.6, 0.6], 0.0)),
+];
+
+const SPRINT: [Keyframe; 5] = [
+    key(0.0, Pose::new(0.15, -0.1, [0.9, -0.9], [-1.0, 1.0], 0.0)),
+    key(0.15, Pose::new(0.15, -0.1, [0.0, 0.0], [0.0, 0.0], 0.07)),
+    key(0.3, Pose::new(0.15, -0.1, [-0.9, 0.9], [1.0, -1.0], 0.0)),
+    key(0.45, Pose::new(0.15, -0.1, [0.0, 0.0], [0.0, 0.0], 0.07)),
+    key(0.6, Pose::new(0.15, -0.1, [0.9, -0.9], [-1.0, 1.0], 0.0)),
+];
+
+const JUMP: [Keyframe; 1] = [
+    key(0.0, Pose::new(0.05, 0.0, [0.5, 0.4], [0.5, -0.25], 0.0)),
+];
+
+const SWIM: [Keyframe; 4] = [
+    key(0.0, Pose::new(1.2, 1.0, [2.8, 2.8], [0.3, -0.3], 0.0)),
+    key(0.6, Pose::new(1.2, 1.0, [0.4, 0.4], [-0.3, 0.3], 0.0)),
+    key(0.9, Pose::new(1.2, 1.0, [1.6, 1.6], [0.3, -0.3], 0.0)),
+    key(1.2, Pose::new(1.2, 1.0, [2.8, 2.8], [0.3, -0.3], 0.0)),
+];
+
+/// Клип анимации
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationClip {
+    Idle,
+    Walk,
+    Sprint,
+    Jump,
+    Swim,
+}
+
+impl AnimationClip {
+    fn keyframes(self) -> &'static [Keyframe] {
+        match self {
+            AnimationClip::Idle => &IDLE,
+            AnimationClip::Walk => &WALK,
+            AnimationClip::Sprint => &SPRINT,
+            AnimationClip::Jump => &JUMP,
+            AnimationClip::Swim => &SWIM,
+        }
+    }
+
+    fn duration(self) -> f32 {
+        self.keyframes().last().map_or(0.0, |frame| frame.time)
+    }
+
+    /// Поза в момент time (по кругу)
+    pub fn sample(self, time: f32) -> Pose {
+        let frames = self.keyframes();
+        let duration = self.duration();
+        if duration <= 0.0 {
+            return frames[0].pose;
+        }
+        let time = time.rem_euclid(duration);
+        let next = frames.iter().position(|frame| frame.time > time).unwrap_or(frames.len() - 1);
+        let (a, b) = (&frames[next.saturating_sub(1)], &frames[next]);
+        let t = if b.time > a.time { (time - a.time) / (b.time - a.time) } else { 0.0 };
+        // Сглаживание у ключей: конечность замедляется в крайних точках
+        a.pose.lerp(&b.pose, t * t * (3.0 - 2.0 * t))
+    }
+}
+
+/// Что нужно аниматору о движении игрока
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct AnimationState {
+    /// Горизонтальная скорость (блоков/с)
+    pub speed: f32,
+    pub on_ground: bool,
+    pub in_water: bool,
+    pub sprinting: bool,
+}
+
+impl AnimationState {
+    pub fn from_player(player: &Player) -> Self {
+        Self {
+            speed: (player.velocity.x * player.velocity.x + player.velocity.z * player.velocity.z).sqrt(),
+            on_ground: player.on_ground || player.climbing,
+            in_water: player.in_water,
+            sprinting: player.is_sprinting,
+        }
+    }
+
+    fn clip(&self) -> AnimationClip {
+        if self.in_water {
+            AnimationClip::Swim
+        } else if !self.on_ground {
+            AnimationClip::Jump
+        } else if self.speed < IDLE_SPEED {
+            AnimationClip::Idle
+        } else if self.sprinting {
+            AnimationClip::Sprint
+        } else {
+            AnimationClip::Walk
+        }
+    }
+
+    /// Скорость проигрывания: шаги успевают за движением
+    fn playback_rate(&self, clip: AnimationClip) -> f32 {
+        match clip {
+            AnimationClip::Walk => (self.speed / WALK_REFERENCE_SPEED).clamp(0.5, 2.0),
+            AnimationClip::Sprint => (self.speed / SPRINT_REFERENCE_SPEED).clamp(0.5, 2.0),
+            _ => 1.0,
+        }
+    }
+}
+
+/// Проигрыватель клипов одного игрока
+pub struct PlayerAnimator {
+    clip: AnimationClip,
+    time: f32,
+    /// Предыдущий клип и его время - затухает за BLEND_TIME
+    previous: Option<(AnimationClip, f32)>,
+    blend: f32,
+}
+
+impl PlayerAnimator {
+    pub fn new() -> Self {
+        Self {
+            clip: AnimationClip::Idle,
+            time: 0.0,
+            previous: None,
+            blend: 1.0,
+        }
+    }
+
+    pub fn clip(&self) -> AnimationClip {
+        self.clip
+    }
+
+    /// Кадр анимации: текущая поза
+    pub fn update(&mut self, dt: f32, state: &AnimationState) -> Pose {
+        let clip = state.clip();
+        if clip != self.clip {
+            self.previous = Some((self.clip, self.time));
+            self.clip = clip;
+            self.time = 0.0;
+            self.blend = 0.0;
+        }
+
+        self.time += dt * state.playback_rate(clip);
+        self.blend = (self.blend + dt / BLEND_TIME).min(1.0);
+        let pose = clip.sample(self.time);
+        match self.previous {
+            Some((previous, time)) if self.blend < 1.0 => previous.sample(time).lerp(&pose, self.blend),
+            _ => {
+                self.previous = None;
+                pose
+            }
+        }
+    }
+}
+
+impl Default for PlayerAnimator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn walking(speed: f32) -> AnimationState {
+        AnimationState { speed, on_ground: true, in_water: false, sprinting: false }
+    }
+
+    #[test]
+    fn clips_loop_and_interpolate_between_keys() {
+        let walk = AnimationClip::Walk;
+        assert_eq!(walk.sample(0.0), walk.sample(walk.duration()));
+        // Середина между ключами 0.0 и 0.2: ровно половина пути
+        let mid = walk.sample(0.1);
+        assert!((mid.angle(Bone::LeftArm) - 0.25).abs() < 1e-5);
+        assert!((mid.bob - 0.02).abs() < 1e-5);
+        assert_eq!(AnimationClip::Jump.sample(5.0), JUMP[0].pose);
+    }
+
+    #[test]
+    fn animator_picks_clip_from_state_and_blends() {
+        let mut animator = PlayerAnimator::new();
+        animator.update(0.5, &walking(0.0));
+        assert_eq!(animator.clip(), AnimationClip::Idle);
+
+        let pose = animator.update(0.0, &walking(5.0));
+        assert_eq!(animator.clip(), AnimationClip::Walk);
+        // Сразу после смены клипа поза ещё от старого
+        assert_eq!(pose, AnimationClip::Idle.sample(0.5));
+
+        animator.update(BLEND_TIME, &walking(5.0));
+        let swim = AnimationState { in_water: true, ..walking(2.0) };
+        animator.update(1.0, &swim);
+        assert_eq!(animator.clip(), AnimationClip::Swim);
+        let airborne = AnimationState { on_ground: false, ..walking(5.0) };
+        animator.update(0.1, &airborne);
+        assert_eq!(animator.clip(), AnimationClip::Jump);
+    }
+}
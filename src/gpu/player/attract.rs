@@ -0,0 +1,232 @@
+// ============================================
+// Attract Mode - Демо-облёт мира в простое
+// ============================================
+// Если в главном меню нет ввода ATTRACT_IDLE_TIME секунд, камера
+// начинает облёт сгенерированного мира: по сиду выбираются горы и
+// океаны, камера летит между ними по сплайну Catmull-Rom.
+// Любой ввод возвращает обычную камеру и меню.
+//
+// Отдельного титульного экрана пока нет - его роль играет главное
+// меню. Заодно это нагрузочный тест стриминга: террейн и декорации
+// грузятся вокруг камеры.
+
+use ultraviolet::Vec3;
+
+use super::camera::Camera;
+use crate::gpu::terrain::get_height;
+
+/// Время простоя до запуска демо (секунды)
+pub const ATTRACT_IDLE_TIME: f32 = 120.0;
+
+/// Скорость полёта (блоков/с)
+const FLIGHT_SPEED: f32 = 18.0;
+/// Расстояние до следующей точки интереса
+const MIN_HOP: f32 = 120.0;
+const MAX_HOP: f32 = 320.0;
+/// Кандидатов на одну точку
+const CANDIDATES: usize = 32;
+/// Отклонение направления от текущего курса (радианы), чтобы не летать туда-обратно
+const MAX_TURN: f32 = 1.2;
+/// Высота над вершиной/водой в точке интереса
+const WAYPOINT_ALTITUDE: f32 = 30.0;
+/// Минимальный зазор над рельефом по пути
+const MIN_CLEARANCE: f32 = 12.0;
+/// Порог "гор" и "океана" по высоте рельефа
+const MOUNTAIN_HEIGHT: f32 = 55.0;
+const OCEAN_HEIGHT: f32 = -4.0;
+/// Сглаживание высоты и взгляда (1/с)
+const SMOOTHING: f32 = 1.5;
+
+/// Тип точки интереса
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Landmark {
+    Mountain,
+    Ocean,
+}
+
+/// Демо-камера
+pub struct AttractMode {
+    /// Секунд без ввода в меню
+    idle: f32,
+    active: bool,
+    /// Контрольные точки сплайна (летим между [1] и [2])
+    points: [Vec3; 4],
+    /// Прогресс по текущему сегменту 0-1
+    t: f32,
+    /// Следующий тип точки (чередуются)
+    next_landmark: Landmark,
+    /// Сглаженные высота и направление
+    height: f32,
+    forward: Vec3,
+    rng: u64,
+}
+
+impl AttractMode {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            idle: 0.0,
+            active: false,
+            points: [Vec3::zero(); 4],
+            t: 0.0,
+            next_landmark: Landmark::Mountain,
+            height: 0.0,
+            forward: Vec3::unit_z(),
+            rng: (seed ^ 0xD1B5_4A32_D192_ED03) | 1,
+        }
+    }
+
+    /// Псевдослучайное число 0-1 (xorshift, детерминировано сидом мира)
+    fn next_random(&mut self) -> f32 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        (self.rng >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Идёт ли демо
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Сообщить о вводе. true - демо было активно и прервано
+    /// (такой ввод не должен доходить до меню)
+    pub fn notify_input(&mut self) -> bool {
+        self.idle = 0.0;
+        if self.active {
+            self.active = false;
+            log::info!("Демо прервано");
+            return true;
+        }
+        false
+    }
+
+    /// Обновить демо. in_menu - открыто главное меню;
+    /// origin - откуда начинать облёт (позиция камеры игрока)
+    pub fn update(&mut self, dt: f32, in_menu: bool, origin: Vec3, camera: &mut Camera) {
+        if !in_menu {
+            self.idle = 0.0;
+            self.active = false;
+            return;
+        }
+
+        if !self.active {
+            self.idle += dt;
+            if self.idle < ATTRACT_IDLE_TIME {
+                return;
+            }
+            self.start(origin, camera.forward());
+        }
+
+        self.advance(dt, camera);
+    }
+
+    /// Начать облёт от текущей позиции камеры
+    fn start(&mut self, origin: Vec3, forward: Vec3) {
+        let heading = Vec3::new(forward.x, 0.0, forward.z);
+        let heading = if heading.mag_sq() > 0.0001 { heading.normalized() } else { Vec3::unit_z() };
+        let start = Vec3::new(origin.x, origin.y.max(Self::cruise_height(origin.x, origin.z)), origin.z);
+
+        self.points[0] = start - heading * MIN_HOP;
+        self.points[1] = start;
+        self.points[2] = self.pick_landmark(start, heading);
+        self.points[3] = self.pick_landmark(self.points[2], (self.points[2] - start).normalized());
+        self.t = 0.0;
+        self.height = start.y;
+        self.forward = heading;
+        self.active = true;
+        log::info!("Демо-облёт запущен");
+    }
+
+    /// Шаг по сплайну и установка камеры
+    fn advance(&mut self, dt: f32, camera: &mut Camera) {
+        let segment = (self.points[2] - self.points[1]).mag().max(1.0);
+        self.t += dt * FLIGHT_SPEED / segment;
+
+        while self.t >= 1.0 {
+            self.t -= 1.0;
+            let heading = (self.points[3] - self.points[2]).normalized();
+            let next = self.pick_landmark(self.points[3], heading);
+            self.points = [self.points[1], self.points[2], self.points[3], next];
+        }
+
+        let [p0, p1, p2, p3] = self.points;
+        let position = catmull_rom(p0, p1, p2, p3, self.t);
+        let tangent = catmull_rom_tangent(p0, p1, p2, p3, self.t);
+
+        // Не врезаемся в рельеф между точками
+        let target_height = position.y.max(Self::cruise_height(position.x, position.z));
+        let smooth = 1.0 - (-SMOOTHING * dt).exp();
+        self.height += (target_height - self.height) * smooth;
+
+        // Взгляд по курсу, слегка вниз на ландшафт
+        let look = Vec3::new(tangent.x, 0.0, tangent.z);
+        if look.mag_sq() > 0.0001 {
+            let look = (look.normalized() + Vec3::new(0.0, -0.3, 0.0)).normalized();
+            self.forward = (self.forward + (look - self.forward) * smooth).normalized();
+        }
+
+        camera.set_look(Vec3::new(position.x, self.height, position.z), self.forward);
+    }
+
+    /// Минимальная высота полёта над точкой
+    fn cruise_height(x: f32, z: f32) -> f32 {
+        get_height(x, z).max(0.0) + MIN_CLEARANCE
+    }
+
+    /// Выбрать следующую точку интереса впереди по курсу
+    fn pick_landmark(&mut self, from: Vec3, heading: Vec3) -> Vec3 {
+        let landmark = self.next_landmark;
+        self.next_landmark = match landmark {
+            Landmark::Mountain => Landmark::Ocean,
+            Landmark::Ocean => Landmark::Mountain,
+        };
+
+        let base_angle = heading.z.atan2(heading.x);
+        let (mut best_score, mut best_x, mut best_z, mut best_height) = (f32::NEG_INFINITY, from.x, from.z, 0.0);
+        for _ in 0..CANDIDATES {
+            let angle = base_angle + (self.next_random() * 2.0 - 1.0) * MAX_TURN;
+            let distance = MIN_HOP + self.next_random() * (MAX_HOP - MIN_HOP);
+            let x = from.x + angle.cos() * distance;
+            let z = from.z + angle.sin() * distance;
+            let height = get_height(x, z);
+
+            let score = match landmark {
+                Landmark::Mountain => height,
+                Landmark::Ocean => -height,
+            };
+            if score > best_score {
+                (best_score, best_x, best_z, best_height) = (score, x, z, height);
+            }
+        }
+
+        let (x, z, height) = (best_x, best_z, best_height);
+        let found = match landmark {
+            Landmark::Mountain => height >= MOUNTAIN_HEIGHT,
+            Landmark::Ocean => height <= OCEAN_HEIGHT,
+        };
+        if found {
+            log::debug!("Следующая точка: {:?} ({:.0}, {:.0})", landmark, x, z);
+        }
+        Vec3::new(x, height.max(0.0) + WAYPOINT_ALTITUDE, z)
+    }
+}
+
+/// Точка на сплайне Catmull-Rom между p1 и p2
+pub fn catmull_rom(p0: Vec3, p1: Vec3, p2: Vec3, p3: Vec3, t: f32) -> Vec3 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    (p1 * 2.0
+        + (p2 - p0) * t
+        + (p0 * 2.0 - p1 * 5.0 + p2 * 4.0 - p3) * t2
+        + (p1 * 3.0 - p0 - p2 * 3.0 + p3) * t3)
+        * 0.5
+}
+
+/// Производная сплайна (направление полёта)
+pub fn catmull_rom_tangent(p0: Vec3, p1: Vec3, p2: Vec3, p3: Vec3, t: f32) -> Vec3 {
+    let t2 = t * t;
+    ((p2 - p0)
+        + (p0 * 2.0 - p1 * 5.0 + p2 * 4.0 - p3) * (2.0 * t)
+        + (p1 * 3.0 - p0 - p2 * 3.0 + p3) * (3.0 * t2))
+        * 0.5
+}
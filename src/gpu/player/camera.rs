@@ -5,9 +5,30 @@
 // - Первое лицо (FirstPerson)
 // - Третье лицо сзади (ThirdPersonBack)
 // - Третье лицо спереди (ThirdPersonFront)
+//
+// В режимах 3-го лица камера висит на "руке" от плеча игрока;
+// рука укорачивается raycast'ом по блокам и суб-вокселям.
+//
+// FOV = базовый (из настроек) * модификаторы бега/полёта,
+// переход между значениями плавный.
+//
+// Крен (roll) задаёт только фоторежим; в игре он всегда 0.
 
 use ultraviolet::{Mat4, Vec3};
 use super::player::Player;
+use crate::gpu::core::{THIRD_PERSON_DISTANCE, SHOULDER_OFFSET, DEFAULT_FOV, MIN_FOV, MAX_FOV};
+
+/// Скорость возврата камеры на полную дистанцию после стены (1/с)
+const ARM_RETURN_SPEED: f32 = 8.0;
+
+/// Множитель FOV при беге
+const SPRINT_FOV_MULTIPLIER: f32 = 1.15;
+/// Множитель FOV в полёте (складывается с бегом)
+const FLIGHT_FOV_MULTIPLIER: f32 = 1.1;
+/// Скорость сглаживания FOV (1/с)
+const FOV_LERP_SPEED: f32 = 10.0;
+/// Верхний предел FOV с модификаторами (радианы), чтобы не было "рыбьего глаза"
+const MAX_EFFECTIVE_FOV: f32 = 2.6;
 
 /// Режим камеры
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -45,17 +66,32 @@ pub struct Camera {
     /// Дистанция от игрока в режиме 3-го лица
     pub third_person_distance: f32,
     
+    /// Смещение вправо от головы (через плечо), отрицательное - левое плечо
+    pub shoulder_offset: f32,
+    
     /// Минимальная дистанция (при коллизии со стеной)
     pub min_distance: f32,
     
+    /// Радиус "сферы" камеры - отступ от стен, чтобы near plane не резала блоки
+    pub collision_radius: f32,
+    
     /// Текущая реальная дистанция (после raycast)
     current_distance: f32,
     
     /// Параметры проекции
     pub aspect: f32,
+    /// Базовый FOV из настроек (радианы)
+    base_fov: f32,
+    /// Текущий FOV с учётом модификаторов (радианы)
     pub fov: f32,
     pub near: f32,
     pub far: f32,
+    
+    /// Крен вокруг направления взгляда (радианы, фоторежим)
+    pub roll: f32,
+    
+    /// Камера внутри блока воды (туман и звук под водой)
+    pub underwater: bool,
 }
 
 impl Camera {
@@ -64,13 +100,18 @@ impl Camera {
             position: Vec3::new(0.0, 50.0, 0.0),
             forward: Vec3::new(0.0, 0.0, 1.0),
             mode: CameraMode::FirstPerson,
-            third_person_distance: 5.0,
-            min_distance: 1.0,
-            current_distance: 5.0,
+            third_person_distance: THIRD_PERSON_DISTANCE,
+            shoulder_offset: SHOULDER_OFFSET,
+            min_distance: 0.3,
+            collision_radius: 0.2,
+            current_distance: THIRD_PERSON_DISTANCE,
             aspect,
-            fov: 70.0_f32.to_radians(),
+            base_fov: DEFAULT_FOV.to_radians(),
+            fov: DEFAULT_FOV.to_radians(),
             near: 0.1,
             far: 2000.0,
+            roll: 0.0,
+            underwater: false,
         }
     }
     
@@ -79,108 +120,125 @@ impl Camera {
         self.forward
     }
     
+    /// Поставить камеру вручную (демо-облёт), минуя привязку к игроку
+    pub fn set_look(&mut self, position: Vec3, forward: Vec3) {
+        self.position = position;
+        self.forward = forward.normalized();
+    }
+    
     /// Вектор вправо
     pub fn right(&self) -> Vec3 {
         self.forward.cross(Vec3::unit_y()).normalized()
     }
     
     /// Обновить камеру на основе позиции игрока
-    pub fn update_from_player(&mut self, player: &Player) {
+    /// raycast(начало, направление, дистанция) -> дистанция до препятствия
+    pub fn update_from_player<F>(&mut self, player: &Player, dt: f32, raycast: F)
+    where
+        F: Fn(Vec3, Vec3, f32) -> Option<f32>,
+    {
         let eye_pos = player.eye_position();
         let player_forward = player.forward();
         
-        match self.mode {
+        let (arm_direction, view_forward) = match self.mode {
             CameraMode::FirstPerson => {
                 // Камера точно в глазах
                 self.position = eye_pos;
                 self.forward = player_forward;
+                self.current_distance = 0.0;
+                return;
             }
-            
-            CameraMode::ThirdPersonBack => {
-                // Камера сзади игрока
-                // CameraPos = EyePos - Forward * Distance
-                let target_pos = eye_pos - player_forward * self.third_person_distance;
-                
-                // Raycast для коллизии со стенами
-                self.current_distance = self.raycast_distance(
-                    eye_pos,
-                    -player_forward,
-                    self.third_person_distance,
-                );
-                
-                self.position = eye_pos - player_forward * self.current_distance;
-                self.forward = player_forward;
-            }
-            
-            CameraMode::ThirdPersonFront => {
-                // Камера спереди, смотрит на игрока
-                let target_pos = eye_pos + player_forward * self.third_person_distance;
-                
-                // Raycast для коллизии
-                self.current_distance = self.raycast_distance(
-                    eye_pos,
-                    player_forward,
-                    self.third_person_distance,
-                );
-                
-                self.position = eye_pos + player_forward * self.current_distance;
-                // Смотрим на игрока (инвертированный forward)
-                self.forward = -player_forward;
-            }
-        }
-    }
-    
-    /// Raycast от головы игрока к желаемой позиции камеры
-    /// Возвращает безопасную дистанцию (не проходящую сквозь стены)
-    fn raycast_distance(&self, origin: Vec3, direction: Vec3, max_distance: f32) -> f32 {
-        // Простой raycast через сэмплирование
-        // В реальном проекте здесь был бы полноценный raycast по воксельной сетке
+            // Камера сзади игрока, смотрит туда же
+            CameraMode::ThirdPersonBack => (-player_forward, player_forward),
+            // Камера спереди, смотрит на игрока
+            CameraMode::ThirdPersonFront => (player_forward, -player_forward),
+        };
         
-        let step_size = 0.25;
-        let steps = (max_distance / step_size) as i32;
+        // Сначала смещаемся к плечу (тоже с проверкой стен)
+        let shoulder = self.arm_length(&raycast, eye_pos, player.right_horizontal() * self.shoulder_offset.signum(), self.shoulder_offset.abs());
+        let pivot = eye_pos + player.right_horizontal() * shoulder * self.shoulder_offset.signum();
         
-        for i in 1..=steps {
-            let dist = i as f32 * step_size;
-            let check_pos = origin + direction * dist;
-            
-            // Проверяем коллизию с террейном
-            // Используем простую проверку: если точка ниже поверхности — коллизия
-            let terrain_height = self.sample_terrain_height(check_pos.x, check_pos.z);
-            
-            if check_pos.y < terrain_height + 0.5 {
-                // Нашли коллизию — возвращаем предыдущую безопасную дистанцию
-                return ((i - 1) as f32 * step_size).max(self.min_distance);
-            }
+        // Затем отводим камеру назад/вперёд от плеча
+        let target = self.arm_length(&raycast, pivot, arm_direction, self.third_person_distance)
+            .max(self.min_distance);
+        
+        // Приближение мгновенное (нельзя заглядывать в стену),
+        // отдаление плавное, чтобы камера не дёргалась
+        self.current_distance = if target < self.current_distance {
+            target
+        } else {
+            let t = 1.0 - (-ARM_RETURN_SPEED * dt).exp();
+            self.current_distance + (target - self.current_distance) * t
+        };
+        
+        self.position = pivot + arm_direction * self.current_distance;
+        self.forward = view_forward;
+    }
+    
+    /// Плавно подвести FOV к базовому с учётом бега и полёта
+    pub fn update_fov(&mut self, dt: f32, sprinting: bool, flying: bool) {
+        let mut target = self.base_fov;
+        if sprinting {
+            target *= SPRINT_FOV_MULTIPLIER;
+        }
+        if flying {
+            target *= FLIGHT_FOV_MULTIPLIER;
         }
+        let target = target.min(MAX_EFFECTIVE_FOV);
         
-        // Нет коллизий — полная дистанция
-        max_distance
+        let t = 1.0 - (-FOV_LERP_SPEED * dt).exp();
+        self.fov += (target - self.fov) * t;
+    }
+    
+    /// Базовый FOV в градусах
+    pub fn base_fov_degrees(&self) -> f32 {
+        self.base_fov.to_degrees()
     }
     
-    /// Сэмплирование высоты террейна (упрощённое)
-    /// В реальном проекте это должно использовать ту же функцию что и генератор
-    fn sample_terrain_height(&self, x: f32, z: f32) -> f32 {
-        // Используем ту же функцию высоты что и террейн
-        use crate::gpu::terrain::get_height;
-        get_height(x, z)
+    /// Установить базовый FOV в градусах (текущий догонит плавно)
+    pub fn set_base_fov_degrees(&mut self, degrees: f32) {
+        self.base_fov = degrees.clamp(MIN_FOV, MAX_FOV).to_radians();
+    }
+    
+    /// Длина "руки" камеры до препятствия с учётом радиуса камеры
+    fn arm_length<F>(&self, raycast: &F, origin: Vec3, direction: Vec3, max_distance: f32) -> f32
+    where
+        F: Fn(Vec3, Vec3, f32) -> Option<f32>,
+    {
+        if max_distance <= 0.0 {
+            return 0.0;
+        }
+        match raycast(origin, direction, max_distance + self.collision_radius) {
+            Some(hit) => (hit - self.collision_radius).clamp(0.0, max_distance),
+            None => max_distance,
+        }
     }
     
     /// Матрица вида (View Matrix)
     pub fn view_matrix(&self) -> Mat4 {
         let target = self.position + self.forward;
-        Mat4::look_at(self.position, target, Vec3::unit_y())
+        Mat4::look_at(self.position, target, self.up())
+    }
+    
+    /// Вектор "вверх" с учётом крена
+    fn up(&self) -> Vec3 {
+        if self.roll == 0.0 {
+            return Vec3::unit_y();
+        }
+        let right = self.right();
+        let up = right.cross(self.forward).normalized();
+        up * self.roll.cos() + right * self.roll.sin()
     }
     
     /// Матрица проекции (Perspective с Reversed-Z для лучшей точности вдали)
     pub fn projection_matrix(&self) -> Mat4 {
         // Reversed-Z: меняем near и far местами
-        let mut proj = ultraviolet::projection::perspective_wgpu_dx(
+        ultraviolet::projection::perspective_wgpu_dx(
             self.fov,
             self.aspect,
             self.far,  // far вместо near
             self.near, // near вместо far
-        );
-        proj
+        )
     }
     
     /// Комбинированная матрица View-Projection
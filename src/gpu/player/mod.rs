@@ -6,8 +6,14 @@ mod player;
 mod player_model;
 mod camera;
 mod flight;
+mod attract;
+mod photo_mode;
+mod animation;
 
 pub use player::*;
 pub use player_model::*;
 pub use camera::*;
 pub use flight::*;
+pub use attract::*;
+pub use photo_mode::*;
+pub use animation::*;
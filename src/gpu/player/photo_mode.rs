@@ -0,0 +1,250 @@
+// ============================================
+// Photo Mode - Фоторежим (свободная камера)
+// ============================================
+// Входит из меню паузы: мир стоит, камера отвязана от игрока и летает
+// сквозь блоки. Доступны крен, FOV колесом, перемотка времени суток,
+// скрытие интерфейса и глубина резкости. Здесь только состояние и
+// управление; пауза мира и применение к рендеру - PhotoSystem.
+//
+// WASD - полёт, Space/Shift - вверх/вниз, Ctrl - быстрее,
+// Q/E - крен, R - сброс крена, колесо - FOV, [ ] - время суток,
+// H - интерфейс, F - глубина резкости, Z/X - дистанция фокуса.
+
+use ultraviolet::Vec3;
+use winit::keyboard::KeyCode;
+
+use super::camera::Camera;
+use crate::gpu::render::DepthOfField;
+
+/// Скорость полёта (блоков/с)
+const MOVE_SPEED: f32 = 8.0;
+/// Множитель скорости с Ctrl
+const FAST_MULTIPLIER: f32 = 5.0;
+/// Скорость крена (радиан/с)
+const ROLL_SPEED: f32 = 0.8;
+/// Пределы FOV (градусы) и шаг колеса
+const MIN_PHOTO_FOV: f32 = 10.0;
+const MAX_PHOTO_FOV: f32 = 120.0;
+const FOV_STEP: f32 = 5.0;
+/// Перемотка времени (доля суток в секунду)
+const TIME_SCRUB_SPEED: f32 = 0.1;
+/// Дистанция фокуса: начальная, пределы и множитель за секунду
+const DEFAULT_FOCUS: f32 = 16.0;
+const MIN_FOCUS: f32 = 1.0;
+const MAX_FOCUS: f32 = 512.0;
+const FOCUS_SPEED: f32 = 2.0;
+/// Сила размытия вне фокуса
+const DOF_STRENGTH: f32 = 1.0;
+/// Ограничение pitch, как у игрока
+const MAX_PITCH: f32 = 1.5;
+
+/// Зажатые клавиши фоторежима
+#[derive(Debug, Default, Clone, Copy)]
+struct HeldKeys {
+    forward: bool,
+    back: bool,
+    left: bool,
+    right: bool,
+    up: bool,
+    down: bool,
+    fast: bool,
+    roll_left: bool,
+    roll_right: bool,
+    time_back: bool,
+    time_forward: bool,
+    focus_near: bool,
+    focus_far: bool,
+}
+
+/// Состояние фоторежима
+pub struct PhotoMode {
+    active: bool,
+    position: Vec3,
+    yaw: f32,
+    pitch: f32,
+    roll: f32,
+    fov_degrees: f32,
+    /// Интерфейс скрыт (H)
+    pub hide_ui: bool,
+    depth_of_field: bool,
+    focus_distance: f32,
+    keys: HeldKeys,
+    mouse_dx: f32,
+    mouse_dy: f32,
+    /// Время суток и FOV до входа - вернутся при выходе
+    saved_time: f32,
+    saved_fov: f32,
+}
+
+impl PhotoMode {
+    pub fn new() -> Self {
+        Self {
+            active: false,
+            position: Vec3::zero(),
+            yaw: 0.0,
+            pitch: 0.0,
+            roll: 0.0,
+            fov_degrees: 70.0,
+            hide_ui: false,
+            depth_of_field: false,
+            focus_distance: DEFAULT_FOCUS,
+            keys: HeldKeys::default(),
+            mouse_dx: 0.0,
+            mouse_dy: 0.0,
+            saved_time: 0.0,
+            saved_fov: 70f32.to_radians(),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Войти: камера стартует с текущего вида
+    pub fn enter(&mut self, camera: &Camera, time_of_day: f32) {
+        let forward = camera.forward();
+        *self = Self {
+            active: true,
+            position: camera.position,
+            yaw: forward.z.atan2(forward.x),
+            pitch: forward.y.clamp(-1.0, 1.0).asin().clamp(-MAX_PITCH, MAX_PITCH),
+            fov_degrees: camera.fov.to_degrees().clamp(MIN_PHOTO_FOV, MAX_PHOTO_FOV),
+            saved_time: time_of_day,
+            saved_fov: camera.fov,
+            ..Self::new()
+        };
+    }
+
+    /// Выйти: крен и FOV камеры сброшены, возвращает время суток до входа
+    pub fn exit(&mut self, camera: &mut Camera) -> f32 {
+        self.active = false;
+        self.keys = HeldKeys::default();
+        camera.roll = 0.0;
+        camera.fov = self.saved_fov;
+        self.saved_time
+    }
+
+    /// Клавиша; false - фоторежим её не использует
+    pub fn handle_key(&mut self, key: KeyCode, pressed: bool) -> bool {
+        let keys = &mut self.keys;
+        match key {
+            KeyCode::KeyW => keys.forward = pressed,
+            KeyCode::KeyS => keys.back = pressed,
+            KeyCode::KeyA => keys.left = pressed,
+            KeyCode::KeyD => keys.right = pressed,
+            KeyCode::Space => keys.up = pressed,
+            KeyCode::ShiftLeft | KeyCode::ShiftRight => keys.down = pressed,
+            KeyCode::ControlLeft | KeyCode::ControlRight => keys.fast = pressed,
+            KeyCode::KeyQ => keys.roll_left = pressed,
+            KeyCode::KeyE => keys.roll_right = pressed,
+            KeyCode::BracketLeft => keys.time_back = pressed,
+            KeyCode::BracketRight => keys.time_forward = pressed,
+            KeyCode::KeyZ => keys.focus_near = pressed,
+            KeyCode::KeyX => keys.focus_far = pressed,
+            KeyCode::KeyR if pressed => self.roll = 0.0,
+            KeyCode::KeyH if pressed => self.hide_ui = !self.hide_ui,
+            KeyCode::KeyF if pressed => self.depth_of_field = !self.depth_of_field,
+            KeyCode::KeyR | KeyCode::KeyH | KeyCode::KeyF => {}
+            _ => return false,
+        }
+        true
+    }
+
+    /// Движение мыши (за кадр событий может быть несколько)
+    pub fn process_mouse(&mut self, dx: f64, dy: f64) {
+        self.mouse_dx += dx as f32;
+        self.mouse_dy += dy as f32;
+    }
+
+    /// Колесо: вверх - уже угол (приближение)
+    pub fn zoom(&mut self, scroll: f32) {
+        self.fov_degrees = (self.fov_degrees - scroll * FOV_STEP).clamp(MIN_PHOTO_FOV, MAX_PHOTO_FOV);
+    }
+
+    /// Глубина резкости включена
+    pub fn depth_of_field_enabled(&self) -> bool {
+        self.depth_of_field
+    }
+
+    /// Параметры глубины резкости для рендера
+    pub fn dof(&self) -> Option<DepthOfField> {
+        (self.active && self.depth_of_field).then_some(DepthOfField {
+            focus_distance: self.focus_distance,
+            strength: DOF_STRENGTH,
+        })
+    }
+
+    /// Кадр: камера по вводу. Возвращает сдвиг времени суток
+    pub fn update(&mut self, dt: f32, sensitivity: f32, invert_y: bool, camera: &mut Camera) -> f32 {
+        // Мышь - как у игрока
+        self.yaw += self.mouse_dx * sensitivity * dt;
+        let mouse_dy = if invert_y { -self.mouse_dy } else { self.mouse_dy };
+        self.pitch = (self.pitch - mouse_dy * sensitivity * dt).clamp(-MAX_PITCH, MAX_PITCH);
+        self.mouse_dx = 0.0;
+        self.mouse_dy = 0.0;
+
+        let keys = self.keys;
+        let axis = |positive: bool, negative: bool| positive as i32 as f32 - negative as i32 as f32;
+        self.roll += axis(keys.roll_right, keys.roll_left) * ROLL_SPEED * dt;
+
+        // Полёт по направлению взгляда, вверх/вниз - по вертикали мира
+        let forward = Vec3::new(
+            self.yaw.cos() * self.pitch.cos(),
+            self.pitch.sin(),
+            self.yaw.sin() * self.pitch.cos(),
+        );
+        let right = forward.cross(Vec3::unit_y()).normalized();
+        let direction = forward * axis(keys.forward, keys.back)
+            + right * axis(keys.right, keys.left)
+            + Vec3::unit_y() * axis(keys.up, keys.down);
+        if direction.mag_sq() > 0.0 {
+            let speed = if keys.fast { MOVE_SPEED * FAST_MULTIPLIER } else { MOVE_SPEED };
+            self.position += direction.normalized() * speed * dt;
+        }
+
+        let focus = axis(keys.focus_far, keys.focus_near);
+        self.focus_distance = (self.focus_distance * FOCUS_SPEED.powf(focus * dt)).clamp(MIN_FOCUS, MAX_FOCUS);
+
+        camera.set_look(self.position, forward);
+        camera.roll = self.roll;
+        camera.fov = self.fov_degrees.to_radians();
+
+        axis(keys.time_forward, keys.time_back) * TIME_SCRUB_SPEED * dt
+    }
+}
+
+impl Default for PhotoMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn photo_mode_starts_from_camera_and_restores_it() {
+        let mut camera = Camera::new(16.0 / 9.0);
+        camera.set_look(Vec3::new(5.0, 70.0, -3.0), Vec3::new(0.0, 0.0, 1.0));
+        let fov = camera.fov;
+        let mut photo = PhotoMode::new();
+        photo.enter(&camera, 0.3);
+        assert!(photo.is_active());
+
+        // Без ввода камера стоит там же и смотрит туда же
+        photo.handle_key(KeyCode::KeyE, true);
+        photo.zoom(100.0);
+        assert_eq!(photo.update(0.5, 1.0, false, &mut camera), 0.0);
+        assert!((camera.position - Vec3::new(5.0, 70.0, -3.0)).mag() < 1e-4);
+        assert!((camera.forward() - Vec3::unit_z()).mag() < 1e-4);
+        assert!((camera.roll - 0.4).abs() < 1e-5);
+        assert_eq!(camera.fov, MIN_PHOTO_FOV.to_radians());
+
+        assert!(!photo.handle_key(KeyCode::Escape, true));
+        assert_eq!(photo.exit(&mut camera), 0.3);
+        assert_eq!(camera.roll, 0.0);
+        assert_eq!(camera.fov, fov);
+        assert_eq!(photo.dof(), None);
+    }
+}
@@ -5,16 +5,32 @@
 // Хранит позицию ног, обрабатывает физику и ввод.
 // Является центром мира для генерации чанков.
 
+use serde::{Serialize, Deserialize};
 use ultraviolet::Vec3;
 use super::flight::FlightController;
+use crate::gpu::terrain::generation::world_border;
 
 /// Константы игрока
 pub const PLAYER_HEIGHT: f32 = 1.8;      // Полная высота игрока
 pub const EYE_HEIGHT: f32 = 1.62;        // Высота глаз от ног
+pub const SNEAK_HEIGHT: f32 = 1.5;       // Высота игрока крадучись
+pub const SNEAK_EYE_HEIGHT: f32 = 1.27;  // Высота глаз крадучись
 pub const PLAYER_RADIUS: f32 = 0.3;      // Радиус хитбокса (половина ширины)
 pub const GRAVITY: f32 = 28.0;           // Ускорение свободного падения
 pub const JUMP_VELOCITY: f32 = 9.0;      // Начальная скорость прыжка
 pub const TERMINAL_VELOCITY: f32 = 50.0; // Максимальная скорость падения
+const SUPPORT_PROBE: f32 = 0.05;         // Глубина проверки опоры под ногами
+const SWIM_SPEED_MULTIPLIER: f32 = 0.5;  // Замедление ходьбы при полном погружении
+const WATER_BUOYANCY: f32 = 22.0;        // Выталкивающая сила при полном погружении (чуть меньше GRAVITY)
+const WATER_DRAG: f32 = 5.0;             // Вязкость воды (затухание вертикальной скорости, 1/с)
+const SWIM_UP_ACCELERATION: f32 = 26.0;  // Всплытие на Space
+const SWIM_UP_SPEED: f32 = 4.0;          // Предельная скорость всплытия
+const WATER_SAMPLES: usize = 4;          // Точки проверки воды по высоте хитбокса
+const SWIM_SUBMERSION: f32 = 0.25;       // Погружение, с которого игрок плывёт
+const CLIMB_SPEED: f32 = 3.0;            // Подъём по лестнице
+const CLIMB_SLIDE_SPEED: f32 = 1.5;      // Сползание по лестнице (без удара при спуске)
+const CLIMB_REACH: f32 = 0.1;            // Зазор до лестницы, на котором за неё держатся
+const SNEAK_SPEED_MULTIPLIER: f32 = 0.3; // Замедление крадучись
 
 /// Игрок — физическая сущность в мире
 pub struct Player {
@@ -41,6 +57,18 @@ pub struct Player {
     
     /// Сейчас бежит
     pub is_sprinting: bool,
+    
+    /// Плывёт (тело в воде)
+    pub in_water: bool,
+    
+    /// Доля хитбокса в воде (0 - на суше, 1 - с головой)
+    pub submersion: f32,
+    
+    /// Держится за лестницу или лозу
+    pub climbing: bool,
+    
+    /// Крадётся: ниже ростом, медленнее, не сходит с края блока
+    pub sneaking: bool,
 }
 
 impl Player {
@@ -54,14 +82,28 @@ impl Player {
             move_speed: 5.0,
             sprint_speed: 8.0,
             is_sprinting: false,
+            in_water: false,
+            submersion: 0.0,
+            climbing: false,
+            sneaking: false,
         }
     }
     
+    /// Высота хитбокса (крадучись ниже)
+    pub fn height(&self) -> f32 {
+        if self.sneaking { SNEAK_HEIGHT } else { PLAYER_HEIGHT }
+    }
+    
+    /// Высота глаз от ног
+    pub fn eye_height(&self) -> f32 {
+        if self.sneaking { SNEAK_EYE_HEIGHT } else { EYE_HEIGHT }
+    }
+    
     /// Позиция глаз (для камеры от первого лица)
     pub fn eye_position(&self) -> Vec3 {
         Vec3::new(
             self.position.x,
-            self.position.y + EYE_HEIGHT,
+            self.position.y + self.eye_height(),
             self.position.z,
         )
     }
@@ -70,11 +112,20 @@ impl Player {
     pub fn body_center(&self) -> Vec3 {
         Vec3::new(
             self.position.x,
-            self.position.y + PLAYER_HEIGHT * 0.5,
+            self.position.y + self.height() * 0.5,
             self.position.z,
         )
     }
     
+    /// Был ли объём (AABB в мировых координатах) опорой под ногами:
+    /// пересекает след хитбокса и доходит до уровня ног
+    pub fn rests_on(&self, min: Vec3, max: Vec3) -> bool {
+        let p = self.position;
+        min.x < p.x + PLAYER_RADIUS && max.x > p.x - PLAYER_RADIUS
+            && min.z < p.z + PLAYER_RADIUS && max.z > p.z - PLAYER_RADIUS
+            && max.y >= p.y - SUPPORT_PROBE && min.y <= p.y
+    }
+    
     /// Направление взгляда (forward vector)
     pub fn forward(&self) -> Vec3 {
         Vec3::new(
@@ -104,14 +155,42 @@ impl Player {
     }
 }
 
+/// Клавиша, на которой игрок крадётся (вторая из Ctrl/Shift - бег)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SneakKey {
+    #[default]
+    Control,
+    Shift,
+}
+
+impl SneakKey {
+    pub fn matches(self, key: winit::keyboard::KeyCode) -> bool {
+        use winit::keyboard::KeyCode;
+        match self {
+            SneakKey::Control => key == KeyCode::ControlLeft,
+            SneakKey::Shift => key == KeyCode::ShiftLeft,
+        }
+    }
+}
+
 /// Тип функции проверки твёрдости блока
 /// Принимает (x, y, z) и возвращает true если блок твёрдый
 pub type BlockSolidChecker = Box<dyn Fn(i32, i32, i32, &std::collections::HashMap<crate::gpu::terrain::BlockPos, crate::gpu::blocks::BlockType>) -> bool + Send + Sync>;
 
+/// Тип функции проверки воды в блоке (сигнатура как у твёрдости)
+pub type BlockWaterChecker = BlockSolidChecker;
+
+/// Тип функции проверки лестницы/лозы в блоке (сигнатура как у твёрдости)
+pub type BlockClimbableChecker = BlockSolidChecker;
+
 /// Тип функции проверки коллизии с суб-вокселями
 /// Принимает AABB игрока (min_x, min_y, min_z, max_x, max_y, max_z) и возвращает true если есть коллизия
 pub type SubVoxelCollisionChecker = Box<dyn Fn(f32, f32, f32, f32, f32, f32) -> bool + Send + Sync>;
 
+/// Тип функции raycast по суб-вокселям: (начало, направление, дистанция) -> дистанция до попадания
+pub type SubVoxelRayChecker = Box<dyn Fn([f32; 3], [f32; 3], f32) -> Option<f32> + Send + Sync>;
+
 /// Контроллер игрока — обрабатывает ввод и физику
 pub struct PlayerController {
     // Состояние клавиш движения
@@ -121,6 +200,10 @@ pub struct PlayerController {
     pub right: bool,
     pub jump: bool,
     pub sprint: bool,
+    pub sneak: bool,
+    
+    /// Клавиша "красться" (из настроек управления)
+    pub sneak_key: SneakKey,
     
     // Дельта мыши
     mouse_dx: f32,
@@ -129,14 +212,29 @@ pub struct PlayerController {
     // Чувствительность мыши
     pub sensitivity: f32,
     
+    /// Инверсия вертикали мыши (из настроек управления)
+    pub invert_y: bool,
+    
     // Контроллер полёта
     pub flight: FlightController,
     
     // Функция проверки твёрдости блока
     block_solid_checker: Option<BlockSolidChecker>,
     
+    // Функция проверки воды в блоке
+    block_water_checker: Option<BlockWaterChecker>,
+    
+    // Функция проверки лестницы/лозы в блоке
+    block_climbable_checker: Option<BlockClimbableChecker>,
+    
+    // Упёрся в стену в прошлом шаге (выход из воды на берег)
+    against_wall: bool,
+    
     // Функция проверки коллизии с суб-вокселями
     subvoxel_collision_checker: Option<SubVoxelCollisionChecker>,
+    
+    // Функция raycast по суб-вокселям (камера)
+    subvoxel_ray_checker: Option<SubVoxelRayChecker>,
 }
 
 impl PlayerController {
@@ -148,12 +246,19 @@ impl PlayerController {
             right: false,
             jump: false,
             sprint: false,
+            sneak: false,
+            sneak_key: SneakKey::default(),
             mouse_dx: 0.0,
             mouse_dy: 0.0,
             sensitivity,
+            invert_y: false,
             flight: FlightController::new(),
             block_solid_checker: None,
+            block_water_checker: None,
+            block_climbable_checker: None,
+            against_wall: false,
             subvoxel_collision_checker: None,
+            subvoxel_ray_checker: None,
         }
     }
     
@@ -165,6 +270,22 @@ impl PlayerController {
         self.block_solid_checker = Some(Box::new(f));
     }
     
+    /// Установить функцию проверки воды в блоке
+    pub fn set_block_water_checker<F>(&mut self, f: F)
+    where
+        F: Fn(i32, i32, i32, &std::collections::HashMap<crate::gpu::terrain::BlockPos, crate::gpu::blocks::BlockType>) -> bool + Send + Sync + 'static,
+    {
+        self.block_water_checker = Some(Box::new(f));
+    }
+    
+    /// Установить функцию проверки лестницы/лозы в блоке
+    pub fn set_block_climbable_checker<F>(&mut self, f: F)
+    where
+        F: Fn(i32, i32, i32, &std::collections::HashMap<crate::gpu::terrain::BlockPos, crate::gpu::blocks::BlockType>) -> bool + Send + Sync + 'static,
+    {
+        self.block_climbable_checker = Some(Box::new(f));
+    }
+    
     /// Установить функцию проверки коллизии с суб-вокселями
     pub fn set_subvoxel_collision_checker<F>(&mut self, f: F)
     where
@@ -173,8 +294,61 @@ impl PlayerController {
         self.subvoxel_collision_checker = Some(Box::new(f));
     }
     
+    /// Установить функцию raycast по суб-вокселям
+    pub fn set_subvoxel_ray_checker<F>(&mut self, f: F)
+    where
+        F: Fn([f32; 3], [f32; 3], f32) -> Option<f32> + Send + Sync + 'static,
+    {
+        self.subvoxel_ray_checker = Some(Box::new(f));
+    }
+    
+    /// Raycast по блокам (DDA) и суб-вокселям
+    /// Возвращает дистанцию до первого твёрдого препятствия
+    pub fn raycast_solid(&self, origin: Vec3, direction: Vec3, max_distance: f32, world_changes: &std::collections::HashMap<crate::gpu::terrain::BlockPos, crate::gpu::blocks::BlockType>) -> Option<f32> {
+        let subvoxel_hit = self.subvoxel_ray_checker.as_ref().and_then(|checker| {
+            checker([origin.x, origin.y, origin.z], [direction.x, direction.y, direction.z], max_distance)
+        });
+        let max_distance = subvoxel_hit.unwrap_or(max_distance);
+        
+        // Amanatides & Woo: обходим блоки вдоль луча
+        let mut block = [origin.x.floor() as i32, origin.y.floor() as i32, origin.z.floor() as i32];
+        let origin = [origin.x, origin.y, origin.z];
+        let dir = [direction.x, direction.y, direction.z];
+        let mut step = [0i32; 3];
+        let mut t_max = [f32::INFINITY; 3];
+        let mut t_delta = [f32::INFINITY; 3];
+        for i in 0..3 {
+            if dir[i] > 0.0 {
+                step[i] = 1;
+                t_delta[i] = 1.0 / dir[i];
+                t_max[i] = (block[i] as f32 + 1.0 - origin[i]) * t_delta[i];
+            } else if dir[i] < 0.0 {
+                step[i] = -1;
+                t_delta[i] = -1.0 / dir[i];
+                t_max[i] = (origin[i] - block[i] as f32) * t_delta[i];
+            }
+        }
+        
+        loop {
+            let axis = if t_max[0] < t_max[1] {
+                if t_max[0] < t_max[2] { 0 } else { 2 }
+            } else if t_max[1] < t_max[2] { 1 } else { 2 };
+            
+            let t = t_max[axis];
+            if t >= max_distance {
+                return subvoxel_hit;
+            }
+            block[axis] += step[axis];
+            t_max[axis] += t_delta[axis];
+            
+            if self.is_block_solid(block[0], block[1], block[2], world_changes) {
+                return Some(t);
+            }
+        }
+    }
+    
     /// Проверить твёрдость блока
-    fn is_block_solid(&self, x: i32, y: i32, z: i32, world_changes: &std::collections::HashMap<crate::gpu::terrain::BlockPos, crate::gpu::blocks::BlockType>) -> bool {
+    pub fn is_block_solid(&self, x: i32, y: i32, z: i32, world_changes: &std::collections::HashMap<crate::gpu::terrain::BlockPos, crate::gpu::blocks::BlockType>) -> bool {
         if let Some(ref checker) = self.block_solid_checker {
             checker(x, y, z, world_changes)
         } else {
@@ -182,6 +356,36 @@ impl PlayerController {
         }
     }
     
+    /// Находится ли точка в блоке воды
+    pub fn is_in_water(&self, pos: Vec3, world_changes: &std::collections::HashMap<crate::gpu::terrain::BlockPos, crate::gpu::blocks::BlockType>) -> bool {
+        match self.block_water_checker {
+            Some(ref checker) => checker(pos.x.floor() as i32, pos.y.floor() as i32, pos.z.floor() as i32, world_changes),
+            None => false,
+        }
+    }
+    
+    /// Держится ли игрок с ногами в pos за лестницу: блок в хитбоксе
+    /// или вплотную к нему (на уровне ног или пояса)
+    pub fn touches_climbable(&self, pos: Vec3, world_changes: &std::collections::HashMap<crate::gpu::terrain::BlockPos, crate::gpu::blocks::BlockType>) -> bool {
+        let Some(ref checker) = self.block_climbable_checker else { return false };
+        let reach = PLAYER_RADIUS + CLIMB_REACH;
+        let offsets = [(0.0, 0.0), (reach, 0.0), (-reach, 0.0), (0.0, reach), (0.0, -reach)];
+        [0.1, PLAYER_HEIGHT * 0.5].iter().any(|&dy| {
+            offsets.iter().any(|&(dx, dz)| {
+                checker((pos.x + dx).floor() as i32, (pos.y + dy).floor() as i32, (pos.z + dz).floor() as i32, world_changes)
+            })
+        })
+    }
+    
+    /// Доля хитбокса в воде: точки по высоте тела от ног до макушки
+    pub fn submersion(&self, pos: Vec3, world_changes: &std::collections::HashMap<crate::gpu::terrain::BlockPos, crate::gpu::blocks::BlockType>) -> f32 {
+        let step = PLAYER_HEIGHT / WATER_SAMPLES as f32;
+        let wet = (0..WATER_SAMPLES)
+            .filter(|&i| self.is_in_water(pos + Vec3::new(0.0, (i as f32 + 0.5) * step, 0.0), world_changes))
+            .count();
+        wet as f32 / WATER_SAMPLES as f32
+    }
+    
     /// Проверить коллизию с суб-вокселями
     fn check_subvoxel_collision(&self, min_x: f32, min_y: f32, min_z: f32, max_x: f32, max_y: f32, max_z: f32) -> bool {
         if let Some(ref checker) = self.subvoxel_collision_checker {
@@ -192,12 +396,12 @@ impl PlayerController {
     }
     
     /// Проверить коллизию хитбокса игрока с миром
-    fn check_collision(&self, pos: Vec3, world_changes: &std::collections::HashMap<crate::gpu::terrain::BlockPos, crate::gpu::blocks::BlockType>) -> bool {
+    fn check_collision(&self, pos: Vec3, height: f32, world_changes: &std::collections::HashMap<crate::gpu::terrain::BlockPos, crate::gpu::blocks::BlockType>) -> bool {
         // AABB игрока
         let p_min_x = pos.x - PLAYER_RADIUS;
         let p_max_x = pos.x + PLAYER_RADIUS;
         let p_min_y = pos.y;
-        let p_max_y = pos.y + PLAYER_HEIGHT - 0.01;
+        let p_max_y = pos.y + height - 0.01;
         let p_min_z = pos.z - PLAYER_RADIUS;
         let p_max_z = pos.z + PLAYER_RADIUS;
         
@@ -239,23 +443,37 @@ impl PlayerController {
             KeyCode::KeyA => self.left = pressed,
             KeyCode::KeyD => self.right = pressed,
             KeyCode::Space => self.jump = pressed,
-            KeyCode::ControlLeft => self.sprint = pressed,
-            KeyCode::ShiftLeft => self.sprint = pressed, // Shift тоже для спринта
+            KeyCode::ControlLeft | KeyCode::ShiftLeft if self.sneak_key.matches(key) => self.sneak = pressed,
+            KeyCode::ControlLeft | KeyCode::ShiftLeft => self.sprint = pressed,
             _ => {}
         }
     }
     
-    /// Обработка мыши
+    /// Отпустить все клавиши движения (ввод ушёл в консоль)
+    pub fn release_keys(&mut self) {
+        self.forward = false;
+        self.backward = false;
+        self.left = false;
+        self.right = false;
+        self.jump = false;
+        self.sprint = false;
+        self.sneak = false;
+        self.flight.up = false;
+        self.flight.down = false;
+    }
+    
+    /// Обработка мыши (за кадр событий может быть несколько)
     pub fn process_mouse(&mut self, dx: f64, dy: f64) {
-        self.mouse_dx = dx as f32;
-        self.mouse_dy = dy as f32;
+        self.mouse_dx += dx as f32;
+        self.mouse_dy += dy as f32;
     }
     
     /// Обновление игрока
     pub fn update(&mut self, player: &mut Player, dt: f32, world_changes: &std::collections::HashMap<crate::gpu::terrain::BlockPos, crate::gpu::blocks::BlockType>) {
         // === Вращение от мыши ===
         player.yaw += self.mouse_dx * self.sensitivity * dt;
-        player.pitch -= self.mouse_dy * self.sensitivity * dt;
+        let mouse_dy = if self.invert_y { -self.mouse_dy } else { self.mouse_dy };
+        player.pitch -= mouse_dy * self.sensitivity * dt;
         
         // Ограничение pitch (не даём перевернуться)
         player.pitch = player.pitch.clamp(-1.5, 1.5);
@@ -280,6 +498,24 @@ impl PlayerController {
             move_dir = move_dir.normalized();
         }
         
+        // Бег (в полёте тоже - для FOV и ускоренного полёта)
+        player.is_sprinting = self.sprint && self.forward;
+        
+        // Тело в воде (в полёте вода не мешает)
+        player.submersion = self.submersion(player.position, world_changes);
+        player.in_water = player.submersion >= SWIM_SUBMERSION;
+        player.climbing = self.touches_climbable(player.position, world_changes);
+        
+        // Крадётся только на ногах; встать можно, если над головой есть место
+        if self.sneak && !self.flight.is_flying() && !player.in_water {
+            player.sneaking = true;
+        } else if player.sneaking && !self.check_collision(player.position, PLAYER_HEIGHT, world_changes) {
+            player.sneaking = false;
+        }
+        if player.sneaking {
+            player.is_sprinting = false;
+        }
+        
         // === Режим полёта ===
         if self.flight.is_flying() {
             // Скорость полёта
@@ -294,26 +530,58 @@ impl PlayerController {
             
             // Применяем скорость
             player.position += player.velocity * dt;
+            self.keep_inside_border(player);
             
             // В полёте всегда "на земле" для анимаций
             player.on_ground = false;
         } else {
             // === Обычная ходьба с гравитацией ===
             
-            // Скорость (бег или ходьба)
-            player.is_sprinting = self.sprint && self.forward;
-            let speed = if player.is_sprinting {
+            // Скорость (бег или ходьба), в воде медленнее
+            let mut speed = if player.is_sprinting {
                 player.sprint_speed
             } else {
                 player.move_speed
             };
+            if player.in_water {
+                speed *= 1.0 - (1.0 - SWIM_SPEED_MULTIPLIER) * player.submersion;
+            }
+            if player.sneaking {
+                speed *= SNEAK_SPEED_MULTIPLIER;
+            }
             
             // Горизонтальная скорость
             player.velocity.x = move_dir.x * speed;
             player.velocity.z = move_dir.z * speed;
             
             // === Гравитация и прыжок ===
-            if player.on_ground {
+            if player.in_water {
+                let head_above = !self.is_in_water(player.eye_position(), world_changes);
+                if self.jump && head_above && self.against_wall {
+                    // У берега голова над водой - выпрыгиваем на блок
+                    player.velocity.y = JUMP_VELOCITY;
+                } else {
+                    // Гравитация минус выталкивание: с головой игрок медленно
+                    // тонет, у поверхности на Space покачивается на воде
+                    let mut acceleration = WATER_BUOYANCY * player.submersion - GRAVITY;
+                    if self.jump {
+                        acceleration += SWIM_UP_ACCELERATION;
+                    }
+                    player.velocity.y += acceleration * dt;
+                    // Вязкость гасит и скорость входа в воду после падения
+                    player.velocity.y *= (-WATER_DRAG * player.submersion * dt).exp();
+                    player.velocity.y = player.velocity.y.min(SWIM_UP_SPEED);
+                }
+                player.on_ground = false;
+            } else if player.climbing && (self.forward || self.jump) {
+                // W или Space у лестницы - подъём
+                player.velocity.y = CLIMB_SPEED;
+                player.on_ground = false;
+            } else if player.climbing && !player.on_ground {
+                // Держится за лестницу - медленно сползает, падение с неё
+                // не набирает скорость для жёсткого приземления
+                player.velocity.y = (player.velocity.y - GRAVITY * dt).max(-CLIMB_SLIDE_SPEED);
+            } else if player.on_ground {
                 if self.jump {
                     player.velocity.y = JUMP_VELOCITY;
                     player.on_ground = false;
@@ -327,21 +595,51 @@ impl PlayerController {
             }
             
             // === Применяем движение с коллизиями ===
-            self.move_with_collision(player, dt, world_changes);
+            self.against_wall = self.move_with_collision(player, dt, world_changes);
+            self.against_wall |= self.keep_inside_border(player);
         }
     }
     
-    /// Движение с проверкой коллизий (раздельно по осям)
-    fn move_with_collision(&self, player: &mut Player, dt: f32, world_changes: &std::collections::HashMap<crate::gpu::terrain::BlockPos, crate::gpu::blocks::BlockType>) {
+    /// Не выпускать хитбокс за границу мира. Возвращает true, если
+    /// игрок упёрся в стену (как в обычную стену - для выпрыгивания из воды)
+    fn keep_inside_border(&self, player: &mut Player) -> bool {
+        let Some(border) = world_border() else { return false };
+        let x = border.clamp(player.position.x, PLAYER_RADIUS);
+        let z = border.clamp(player.position.z, PLAYER_RADIUS);
+        let mut blocked = false;
+        if x != player.position.x {
+            player.position.x = x;
+            player.velocity.x = 0.0;
+            blocked = true;
+        }
+        if z != player.position.z {
+            player.position.z = z;
+            player.velocity.z = 0.0;
+            blocked = true;
+        }
+        blocked
+    }
+    
+    /// Движение с проверкой коллизий (раздельно по осям).
+    /// Возвращает true, если движение по горизонтали упёрлось в стену
+    fn move_with_collision(&self, player: &mut Player, dt: f32, world_changes: &std::collections::HashMap<crate::gpu::terrain::BlockPos, crate::gpu::blocks::BlockType>) -> bool {
         let old_pos = player.position;
+        let height = player.height();
+        let mut blocked = false;
+        // Крадучись с края блока не сходят: шаг без опоры под ногами отменяется
+        let edge_guard = player.sneaking && player.on_ground;
+        let unsupported = |pos: Vec3| edge_guard && !self.has_support(pos, height, world_changes);
         
         // === Движение по X ===
         let new_x = old_pos.x + player.velocity.x * dt;
         let test_pos_x = Vec3::new(new_x, old_pos.y, old_pos.z);
         
-        if !self.check_collision(test_pos_x, world_changes) {
+        if unsupported(test_pos_x) {
+            player.velocity.x = 0.0;
+        } else if !self.check_collision(test_pos_x, height, world_changes) {
             player.position.x = new_x;
         } else {
+            blocked |= player.velocity.x != 0.0;
             player.velocity.x = 0.0;
         }
         
@@ -349,9 +647,12 @@ impl PlayerController {
         let new_z = old_pos.z + player.velocity.z * dt;
         let test_pos_z = Vec3::new(player.position.x, old_pos.y, new_z);
         
-        if !self.check_collision(test_pos_z, world_changes) {
+        if unsupported(test_pos_z) {
+            player.velocity.z = 0.0;
+        } else if !self.check_collision(test_pos_z, height, world_changes) {
             player.position.z = new_z;
         } else {
+            blocked |= player.velocity.z != 0.0;
             player.velocity.z = 0.0;
         }
         
@@ -359,7 +660,7 @@ impl PlayerController {
         let new_y = old_pos.y + player.velocity.y * dt;
         let test_pos_y = Vec3::new(player.position.x, new_y, player.position.z);
         
-        if !self.check_collision(test_pos_y, world_changes) {
+        if !self.check_collision(test_pos_y, height, world_changes) {
             player.position.y = new_y;
             player.on_ground = false;
         } else {
@@ -370,7 +671,7 @@ impl PlayerController {
                 // Выравниваем на верх блока
                 player.position.y = (old_pos.y.floor() as i32) as f32;
                 // Проверяем, не застряли ли
-                if self.check_collision(player.position, world_changes) {
+                if self.check_collision(player.position, height, world_changes) {
                     player.position.y = old_pos.y;
                 }
             }
@@ -379,8 +680,21 @@ impl PlayerController {
         
         // Дополнительная проверка on_ground (стоим ли на блоке)
         if !player.on_ground {
-            let below = Vec3::new(player.position.x, player.position.y - 0.05, player.position.z);
-            player.on_ground = self.check_collision(below, world_changes);
+            player.on_ground = self.has_support(player.position, height, world_changes);
+        }
+        blocked
+    }
+    
+    /// Есть ли под ногами блок или суб-воксель
+    fn has_support(&self, pos: Vec3, height: f32, world_changes: &std::collections::HashMap<crate::gpu::terrain::BlockPos, crate::gpu::blocks::BlockType>) -> bool {
+        self.check_collision(Vec3::new(pos.x, pos.y - SUPPORT_PROBE, pos.z), height, world_changes)
+    }
+    
+    /// Перепроверить опору после изменения мира под ногами: если её
+    /// больше нет, гравитация действует уже в ближайшем обновлении
+    pub fn invalidate_support(&self, player: &mut Player, world_changes: &std::collections::HashMap<crate::gpu::terrain::BlockPos, crate::gpu::blocks::BlockType>) {
+        if player.on_ground && !self.flight.is_flying() && !self.has_support(player.position, player.height(), world_changes) {
+            player.on_ground = false;
         }
     }
 }
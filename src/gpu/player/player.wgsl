@@ -1,7 +1,8 @@
 // ============================================
 // Player Model Shader
 // ============================================
-// Шейдер для рендеринга модели игрока в режиме 3-го лица
+// Шейдер для рендеринга модели игрока в режиме 3-го лица.
+// Вершина двигается костью (animation.rs), затем матрицей модели.
 
 struct Uniforms {
     view_proj: mat4x4<f32>,
@@ -11,6 +12,7 @@ struct Uniforms {
 
 struct ModelMatrix {
     model: mat4x4<f32>,
+    bones: array<mat4x4<f32>, 6>,
 }
 
 @group(0) @binding(0)
@@ -23,6 +25,7 @@ struct VertexInput {
     @location(0) position: vec3<f32>,
     @location(1) normal: vec3<f32>,
     @location(2) color: vec3<f32>,
+    @location(3) bone: u32,
 }
 
 struct VertexOutput {
@@ -36,17 +39,18 @@ struct VertexOutput {
 fn vs_main(in: VertexInput) -> VertexOutput {
     var out: VertexOutput;
     
-    // Применяем матрицу модели (позиция + поворот игрока)
-    let world_pos = model.model * vec4<f32>(in.position, 1.0);
+    // Кость (поза анимации), затем матрица модели (позиция + поворот игрока)
+    let transform = model.model * model.bones[in.bone];
+    let world_pos = transform * vec4<f32>(in.position, 1.0);
     
     out.clip_position = uniforms.view_proj * world_pos;
     out.world_pos = world_pos.xyz;
     
     // Трансформируем нормаль (только поворот, без масштаба)
     let normal_matrix = mat3x3<f32>(
-        model.model[0].xyz,
-        model.model[1].xyz,
-        model.model[2].xyz
+        transform[0].xyz,
+        transform[1].xyz,
+        transform[2].xyz
     );
     out.normal = normalize(normal_matrix * in.normal);
     out.color = in.color;
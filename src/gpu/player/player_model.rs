@@ -1,15 +1,36 @@
 // ============================================
 // Player Model - Меш и рендеринг модели игрока
 // ============================================
-// Простая модель игрока (куб/капсула) для режима 3-го лица
+// Простая модель игрока (куб/капсула) для режима 3-го лица.
+// Части тела привязаны к костям (animation.rs): шейдер умножает
+// вершину на матрицу её кости, матрицы считаются из позы аниматора.
+// Лицо модели смотрит в +Z.
 
 use bytemuck::{Pod, Zeroable};
+use std::f32::consts::FRAC_PI_2;
 use std::sync::Arc;
 use wgpu::util::DeviceExt;
 use ultraviolet::{Mat4, Vec3};
 
+use super::animation::{AnimationState, Bone, PlayerAnimator, Pose, BONE_COUNT};
 use super::player::{Player, PLAYER_HEIGHT, PLAYER_RADIUS};
 
+/// Шарниры костей в пространстве модели
+const BODY_BOTTOM: f32 = 0.4;
+const BODY_TOP: f32 = 1.4;
+const ARM_WIDTH: f32 = 0.12;
+const ARM_TOP: f32 = BODY_TOP - 0.1;
+/// Голова следует за взглядом не дальше этого угла
+const MAX_HEAD_PITCH: f32 = 0.8;
+
+/// Матрица модели и матрицы костей
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct ModelUniforms {
+    model: [[f32; 4]; 4],
+    bones: [[[f32; 4]; 4]; BONE_COUNT],
+}
+
 /// Вершина модели игрока (такая же как TerrainVertex для совместимости)
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
@@ -17,6 +38,8 @@ pub struct PlayerVertex {
     pub position: [f32; 3],
     pub normal: [f32; 3],
     pub color: [f32; 3],
+    /// Кость (Bone), к которой привязана вершина
+    pub bone: u32,
 }
 
 impl PlayerVertex {
@@ -40,6 +63,11 @@ impl PlayerVertex {
                     shader_location: 2,
                     format: wgpu::VertexFormat::Float32x3,
                 },
+                wgpu::VertexAttribute {
+                    offset: 36,
+                    shader_location: 3,
+                    format: wgpu::VertexFormat::Uint32,
+                },
             ],
         }
     }
@@ -63,14 +91,15 @@ impl PlayerMeshGenerator {
         let mut indices = Vec::new();
         
         // === Тело (центральный куб) ===
-        let body_bottom = 0.4;
-        let body_top = 1.4;
+        let body_bottom = BODY_BOTTOM;
+        let body_top = BODY_TOP;
         Self::add_box(
             &mut vertices, &mut indices,
             -half_w, body_bottom, -half_w * 0.6,
             half_w, body_top, half_w * 0.6,
             body_color,
         );
+        let mut bound = vertices.len();
         
         // === Голова ===
         let head_size = 0.35;
@@ -82,6 +111,7 @@ impl PlayerMeshGenerator {
             head_size, head_top, head_size,
             head_color,
         );
+        bound = Self::bind_bone(&mut vertices, bound, Bone::Head);
         
         // === Ноги ===
         let leg_width = half_w * 0.4;
@@ -94,6 +124,7 @@ impl PlayerMeshGenerator {
             -leg_gap, body_bottom, leg_width,
             leg_color,
         );
+        bound = Self::bind_bone(&mut vertices, bound, Bone::LeftLeg);
         
         // Правая нога
         Self::add_box(
@@ -102,11 +133,12 @@ impl PlayerMeshGenerator {
             half_w, body_bottom, leg_width,
             leg_color,
         );
+        bound = Self::bind_bone(&mut vertices, bound, Bone::RightLeg);
         
         // === Руки ===
-        let arm_width = 0.12;
+        let arm_width = ARM_WIDTH;
         let arm_length = 0.6;
-        let arm_top = body_top - 0.1;
+        let arm_top = ARM_TOP;
         let arm_bottom = arm_top - arm_length;
         
         // Левая рука
@@ -116,6 +148,7 @@ impl PlayerMeshGenerator {
             -half_w, arm_top, arm_width,
             body_color,
         );
+        bound = Self::bind_bone(&mut vertices, bound, Bone::LeftArm);
         
         // Правая рука
         Self::add_box(
@@ -124,12 +157,21 @@ impl PlayerMeshGenerator {
             half_w + arm_width, arm_top, arm_width,
             body_color,
         );
+        Self::bind_bone(&mut vertices, bound, Bone::RightArm);
         
         (vertices, indices)
     }
     
-    /// Добавить куб (box) в меш
-    fn add_box(
+    /// Привязать вершины начиная с from к кости; возвращает конец меша
+    fn bind_bone(vertices: &mut [PlayerVertex], from: usize, bone: Bone) -> usize {
+        for vertex in &mut vertices[from..] {
+            vertex.bone = bone as u32;
+        }
+        vertices.len()
+    }
+    
+    /// Добавить куб (box) в меш; грань Y+ идёт последней
+    pub fn add_box(
         vertices: &mut Vec<PlayerVertex>,
         indices: &mut Vec<u32>,
         x0: f32, y0: f32, z0: f32,
@@ -174,6 +216,7 @@ impl PlayerMeshGenerator {
                     position: corners[corner_idx],
                     normal,
                     color,
+                    bone: Bone::Body as u32,
                 });
             }
             
@@ -195,9 +238,11 @@ pub struct PlayerModel {
     index_buffer: wgpu::Buffer,
     index_count: u32,
     
-    // Uniform буфер для матрицы модели
+    // Uniform буфер для матриц модели и костей
     model_buffer: wgpu::Buffer,
     model_bind_group: wgpu::BindGroup,
+    
+    animator: PlayerAnimator,
 }
 
 impl PlayerModel {
@@ -219,11 +264,12 @@ impl PlayerModel {
             usage: wgpu::BufferUsages::INDEX,
         });
         
-        // Матрица модели (identity изначально)
-        let model_matrix: [[f32; 4]; 4] = Mat4::identity().into();
+        // Матрицы модели и костей (identity изначально)
+        let identity: [[f32; 4]; 4] = Mat4::identity().into();
+        let uniforms = ModelUniforms { model: identity, bones: [identity; BONE_COUNT] };
         let model_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Player Model Buffer"),
-            contents: bytemuck::cast_slice(&model_matrix),
+            contents: bytemuck::cast_slice(&[uniforms]),
             usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
         });
         
@@ -242,18 +288,44 @@ impl PlayerModel {
             index_count: indices.len() as u32,
             model_buffer,
             model_bind_group,
+            animator: PlayerAnimator::new(),
         }
     }
     
-    /// Обновить матрицу модели на основе позиции игрока
-    pub fn update(&self, queue: &wgpu::Queue, player: &Player) {
-        // Матрица трансформации: перемещение + поворот по yaw
-        let translation = Mat4::from_translation(player.position);
-        let rotation = Mat4::from_rotation_y(player.yaw);
-        let model_matrix = translation * rotation;
+    /// Обновить анимацию и матрицы по состоянию игрока
+    pub fn update(&mut self, queue: &wgpu::Queue, player: &Player, dt: f32) {
+        let pose = self.animator.update(dt, &AnimationState::from_player(player));
+        
+        // Перемещение + поворот по yaw: лицо модели (+Z) - по взгляду
+        let translation = Mat4::from_translation(player.position + Vec3::new(0.0, pose.bob, 0.0));
+        let rotation = Mat4::from_rotation_y(FRAC_PI_2 - player.yaw);
+        let uniforms = ModelUniforms {
+            model: (translation * rotation).into(),
+            bones: Self::bone_matrices(&pose, player.pitch),
+        };
+        queue.write_buffer(&self.model_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+    }
+    
+    /// Матрицы костей: поворот вокруг шарнира, голова и руки - вместе с телом
+    fn bone_matrices(pose: &Pose, look_pitch: f32) -> [[[f32; 4]; 4]; BONE_COUNT] {
+        // Конечность вперёд - поворот по -X (низ уходит в +Z)
+        let joint = |pivot: Vec3, angle: f32| {
+            Mat4::from_translation(pivot) * Mat4::from_rotation_x(-angle) * Mat4::from_translation(-pivot)
+        };
+        let hip = (PLAYER_RADIUS + 0.02) * 0.5;
+        let shoulder = PLAYER_RADIUS + ARM_WIDTH * 0.5;
+        
+        // Наклон тела вперёд - по +X (верх уходит в +Z)
+        let body = joint(Vec3::new(0.0, BODY_BOTTOM, 0.0), -pose.angle(Bone::Body));
+        let head_pitch = pose.angle(Bone::Head) + look_pitch.clamp(-MAX_HEAD_PITCH, MAX_HEAD_PITCH);
+        let head = body * joint(Vec3::new(0.0, BODY_TOP, 0.0), head_pitch);
+        let left_arm = body * joint(Vec3::new(-shoulder, ARM_TOP - 0.05, 0.0), pose.angle(Bone::LeftArm));
+        let right_arm = body * joint(Vec3::new(shoulder, ARM_TOP - 0.05, 0.0), pose.angle(Bone::RightArm));
+        let left_leg = joint(Vec3::new(-hip, BODY_BOTTOM, 0.0), pose.angle(Bone::LeftLeg));
+        let right_leg = joint(Vec3::new(hip, BODY_BOTTOM, 0.0), pose.angle(Bone::RightLeg));
         
-        let matrix_data: [[f32; 4]; 4] = model_matrix.into();
-        queue.write_buffer(&self.model_buffer, 0, bytemuck::cast_slice(&matrix_data));
+        // Порядок - как в enum Bone
+        [body, head, left_arm, right_arm, left_leg, right_leg].map(Into::into)
     }
     
     /// Рендеринг модели
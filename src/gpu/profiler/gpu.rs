@@ -0,0 +1,227 @@
+// ============================================
+// GPU Timer - Timestamp queries вокруг проходов
+// ============================================
+// У каждого прохода пара запросов: начало первого render pass и конец
+// последнего (тени - несколько каскадов подряд). После проходов
+// запросы резолвятся в буфер и копируются в буфер для чтения; пока
+// прошлое чтение не закончено, кадры не замеряются. Нужна фича
+// TIMESTAMP_QUERY, без неё таймер не создаётся.
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::{Span, GPU_THREAD};
+
+/// Фича устройства для замеров GPU (опциональная)
+pub const TIMESTAMP_FEATURES: wgpu::Features = wgpu::Features::TIMESTAMP_QUERY;
+
+const MAP_PENDING: u8 = 0;
+const MAP_READY: u8 = 1;
+const MAP_FAILED: u8 = 2;
+
+/// Замеряемый проход
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpuPass {
+    Shadow,
+    Main,
+    SubVoxel,
+    Ui,
+}
+
+impl GpuPass {
+    pub const ALL: [GpuPass; 4] = [GpuPass::Shadow, GpuPass::Main, GpuPass::SubVoxel, GpuPass::Ui];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            GpuPass::Shadow => "shadow",
+            GpuPass::Main => "main",
+            GpuPass::SubVoxel => "subvoxel",
+            GpuPass::Ui => "ui",
+        }
+    }
+
+    fn index(self) -> u32 {
+        self as u32
+    }
+}
+
+const QUERY_COUNT: u32 = GpuPass::ALL.len() as u32 * 2;
+
+/// Запросы кадра, ждущие чтения
+struct PendingReadback {
+    /// Какие запросы записаны (бит на запрос)
+    written: u32,
+    /// Время профайлера на момент submit (начало GPU интервалов в trace)
+    submitted: Duration,
+    /// None - буфер ещё не отправлен на чтение (до submit)
+    state: Option<Arc<AtomicU8>>,
+}
+
+/// Замеры проходов через timestamp queries
+pub struct GpuTimer {
+    query_set: wgpu::QuerySet,
+    resolve_buffer: wgpu::Buffer,
+    readback_buffer: wgpu::Buffer,
+    /// Наносекунд на тик
+    period: f32,
+    /// Замеряется ли текущий кадр
+    active: bool,
+    written: Cell<u32>,
+    pending: Option<PendingReadback>,
+}
+
+impl GpuTimer {
+    /// None - устройство без TIMESTAMP_QUERY
+    pub fn new(device: &wgpu::Device, queue: &wgpu::Queue) -> Option<Self> {
+        if !device.features().contains(TIMESTAMP_FEATURES) {
+            return None;
+        }
+        let query_set = device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: Some("Profiler Timestamp Query Set"),
+            ty: wgpu::QueryType::Timestamp,
+            count: QUERY_COUNT,
+        });
+        let size = QUERY_COUNT as u64 * std::mem::size_of::<u64>() as u64;
+        let resolve_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Profiler Resolve Buffer"),
+            size,
+            usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Profiler Readback Buffer"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        Some(Self {
+            query_set,
+            resolve_buffer,
+            readback_buffer,
+            period: queue.get_timestamp_period(),
+            active: false,
+            written: Cell::new(0),
+            pending: None,
+        })
+    }
+
+    /// Начать кадр: забрать прошлые замеры; кадр замеряется, если
+    /// профайлер включён и буфер чтения свободен
+    pub fn begin_frame(&mut self, device: &wgpu::Device) {
+        self.poll(device);
+        self.active = super::is_enabled() && self.pending.is_none();
+        self.written.set(0);
+    }
+
+    /// Запросы для render pass прохода: first - первый pass прохода
+    /// (пишет начало), last - последний (пишет конец)
+    pub fn pass_writes(&self, pass: GpuPass, first: bool, last: bool) -> Option<wgpu::RenderPassTimestampWrites<'_>> {
+        if !self.active || !(first || last) {
+            return None;
+        }
+        let begin = pass.index() * 2;
+        let mut written = self.written.get();
+        if first {
+            written |= 1 << begin;
+        }
+        if last {
+            written |= 1 << (begin + 1);
+        }
+        self.written.set(written);
+        Some(wgpu::RenderPassTimestampWrites {
+            query_set: &self.query_set,
+            beginning_of_pass_write_index: first.then_some(begin),
+            end_of_pass_write_index: last.then_some(begin + 1),
+        })
+    }
+
+    /// Резолв запросов кадра (последней командой перед submit)
+    pub fn resolve(&mut self, encoder: &mut wgpu::CommandEncoder) {
+        if !std::mem::take(&mut self.active) || self.written.get() == 0 {
+            return;
+        }
+        encoder.resolve_query_set(&self.query_set, 0..QUERY_COUNT, &self.resolve_buffer, 0);
+        encoder.copy_buffer_to_buffer(&self.resolve_buffer, 0, &self.readback_buffer, 0, self.resolve_buffer.size());
+        self.pending = Some(PendingReadback { written: self.written.get(), submitted: super::now(), state: None });
+    }
+
+    /// Запросить чтение (после submit)
+    pub fn after_submit(&mut self) {
+        let Some(pending) = &mut self.pending else { return };
+        if pending.state.is_some() {
+            return;
+        }
+        let state = Arc::new(AtomicU8::new(MAP_PENDING));
+        let callback_state = Arc::clone(&state);
+        self.readback_buffer.slice(..).map_async(wgpu::MapMode::Read, move |result| {
+            let value = if result.is_ok() { MAP_READY } else { MAP_FAILED };
+            callback_state.store(value, Ordering::Release);
+        });
+        pending.state = Some(state);
+    }
+
+    /// Забрать готовые замеры в профайлер
+    fn poll(&mut self, device: &wgpu::Device) {
+        let Some(state) = self.pending.as_ref().and_then(|p| p.state.as_ref()) else { return };
+        let _ = device.poll(wgpu::PollType::Poll);
+        match state.load(Ordering::Acquire) {
+            MAP_PENDING => {}
+            MAP_FAILED => self.pending = None,
+            _ => {
+                let Some(pending) = self.pending.take() else { return };
+                let ticks: Vec<u64> = {
+                    let data = self.readback_buffer.slice(..).get_mapped_range();
+                    bytemuck::cast_slice(&data).to_vec()
+                };
+                self.readback_buffer.unmap();
+                super::push_spans(pass_spans(&ticks, pending.written, self.period, pending.submitted));
+            }
+        }
+    }
+}
+
+/// Интервалы проходов из тиков. Начало кадра на GPU считается
+/// моментом submit (часы GPU и CPU не связаны)
+fn pass_spans(ticks: &[u64], written: u32, period: f32, submitted: Duration) -> Vec<Span> {
+    let complete = |pass: &GpuPass| {
+        let bits = 0b11 << (pass.index() * 2);
+        written & bits == bits
+    };
+    let passes: Vec<(GpuPass, u64, u64)> = GpuPass::ALL
+        .iter()
+        .filter(|pass| complete(pass))
+        .map(|&pass| (pass, ticks[pass.index() as usize * 2], ticks[pass.index() as usize * 2 + 1]))
+        .collect();
+    let Some(origin) = passes.iter().map(|&(_, begin, _)| begin).min() else { return Vec::new() };
+    let to_duration = |ticks: u64| Duration::from_nanos((ticks as f64 * period as f64) as u64);
+    passes
+        .into_iter()
+        .map(|(pass, begin, end)| Span {
+            name: pass.name(),
+            thread: GPU_THREAD,
+            start: submitted + to_duration(begin.saturating_sub(origin)),
+            duration: to_duration(end.saturating_sub(begin)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spans_skip_incomplete_passes() {
+        // Тени 100..400, main 400..1000, subvoxel не рисовался, у ui только начало
+        let ticks = [100, 400, 400, 1000, 0, 0, 1200, 0];
+        let written = 0b0100_1111;
+        let spans = pass_spans(&ticks, written, 2.0, Duration::from_millis(5));
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].name, "shadow");
+        assert_eq!(spans[0].start, Duration::from_millis(5));
+        assert_eq!(spans[0].duration, Duration::from_nanos(600));
+        assert_eq!(spans[1].start, Duration::from_millis(5) + Duration::from_nanos(600));
+        assert!(spans.iter().all(Span::is_gpu));
+    }
+}
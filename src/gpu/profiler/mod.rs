@@ -0,0 +1,214 @@
+// ============================================
+// Profiler - Тайминги кадра (CPU и GPU)
+// ============================================
+// CPU: profiler::scope("update") возвращает guard, который при drop
+// пишет интервал в текущий кадр. GPU: GpuTimer ставит timestamp
+// queries вокруг проходов рендера, результаты приходят через кадр-два
+// и дописываются к последнему кадру. Кадры лежат в кольце: оверлей
+// (F4) показывает средние, /profile dump пишет Chrome trace.
+//
+// Пока профайлер выключен, scope не читает часы и ничего не пишет.
+
+mod gpu;
+mod trace;
+
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+pub use gpu::{GpuPass, GpuTimer, TIMESTAMP_FEATURES};
+pub use trace::{write_chrome_trace, PROFILE_DIR_NAME};
+
+/// Сколько кадров хранить (trace)
+const MAX_FRAMES: usize = 600;
+/// По скольким последним кадрам считать средние для оверлея
+pub const AVERAGE_FRAMES: usize = 60;
+
+/// Поток GPU в записях (CPU потоки нумеруются с 1)
+pub const GPU_THREAD: u32 = 0;
+
+/// Интервал внутри кадра
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Span {
+    pub name: &'static str,
+    /// GPU_THREAD или номер CPU потока
+    pub thread: u32,
+    /// Начало от старта профайлера
+    pub start: Duration,
+    pub duration: Duration,
+}
+
+impl Span {
+    pub fn is_gpu(&self) -> bool {
+        self.thread == GPU_THREAD
+    }
+}
+
+/// Интервалы одного кадра
+#[derive(Debug, Clone, Default)]
+pub struct FrameRecord {
+    pub spans: Vec<Span>,
+}
+
+/// Средний замер за кадр для оверлея
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Timing {
+    pub name: &'static str,
+    pub gpu: bool,
+    pub ms: f32,
+}
+
+struct Profiler {
+    start: Instant,
+    enabled: AtomicBool,
+    /// Кольцо завершённых кадров; последний - текущий
+    frames: Mutex<VecDeque<FrameRecord>>,
+}
+
+static PROFILER: OnceLock<Profiler> = OnceLock::new();
+static NEXT_THREAD: AtomicU32 = AtomicU32::new(1);
+
+thread_local! {
+    static THREAD_ID: Cell<u32> = const { Cell::new(0) };
+}
+
+fn profiler() -> &'static Profiler {
+    PROFILER.get_or_init(|| Profiler {
+        start: Instant::now(),
+        enabled: AtomicBool::new(false),
+        frames: Mutex::new(VecDeque::from([FrameRecord::default()])),
+    })
+}
+
+/// Номер текущего потока для trace
+fn thread_id() -> u32 {
+    THREAD_ID.with(|id| {
+        if id.get() == 0 {
+            id.set(NEXT_THREAD.fetch_add(1, Ordering::Relaxed));
+        }
+        id.get()
+    })
+}
+
+/// Включить запись (выключение чистит историю)
+pub fn set_enabled(enabled: bool) {
+    let profiler = profiler();
+    if profiler.enabled.swap(enabled, Ordering::Relaxed) && !enabled {
+        if let Ok(mut frames) = profiler.frames.lock() {
+            frames.clear();
+            frames.push_back(FrameRecord::default());
+        }
+    }
+}
+
+pub fn is_enabled() -> bool {
+    PROFILER.get().is_some_and(|profiler| profiler.enabled.load(Ordering::Relaxed))
+}
+
+/// Время от старта профайлера
+pub fn now() -> Duration {
+    profiler().start.elapsed()
+}
+
+/// Замер CPU до конца области видимости guard'а
+#[must_use = "интервал пишется при drop guard'а"]
+pub struct CpuScope {
+    name: &'static str,
+    start: Option<Duration>,
+}
+
+impl Drop for CpuScope {
+    fn drop(&mut self) {
+        let Some(start) = self.start else { return };
+        let span = Span { name: self.name, thread: thread_id(), start, duration: now().saturating_sub(start) };
+        push_spans(std::iter::once(span));
+    }
+}
+
+/// Начать замер CPU
+pub fn scope(name: &'static str) -> CpuScope {
+    CpuScope { name, start: is_enabled().then(now) }
+}
+
+/// Дописать интервалы к текущему кадру (GPU результаты - с опозданием)
+pub fn push_spans(spans: impl IntoIterator<Item = Span>) {
+    if !is_enabled() {
+        return;
+    }
+    if let Ok(mut frames) = profiler().frames.lock() {
+        if let Some(frame) = frames.back_mut() {
+            frame.spans.extend(spans);
+        }
+    }
+}
+
+/// Закрыть кадр (раз в кадр, после submit)
+pub fn end_frame() {
+    if !is_enabled() {
+        return;
+    }
+    if let Ok(mut frames) = profiler().frames.lock() {
+        if frames.len() >= MAX_FRAMES {
+            frames.pop_front();
+        }
+        frames.push_back(FrameRecord::default());
+    }
+}
+
+/// Завершённые кадры (для trace)
+pub fn recorded_frames() -> Vec<FrameRecord> {
+    let Some(profiler) = PROFILER.get() else { return Vec::new() };
+    let Ok(frames) = profiler.frames.lock() else { return Vec::new() };
+    // Последний кадр ещё пишется
+    frames.iter().take(frames.len().saturating_sub(1)).cloned().collect()
+}
+
+/// Средние за последние AVERAGE_FRAMES завершённых кадров
+pub fn recent_timings() -> Vec<Timing> {
+    let frames = recorded_frames();
+    let skip = frames.len().saturating_sub(AVERAGE_FRAMES);
+    average_timings(&frames[skip..])
+}
+
+/// Среднее время на кадр по имени; порядок - первое появление, CPU
+/// и GPU с одним именем считаются отдельно
+pub fn average_timings(frames: &[FrameRecord]) -> Vec<Timing> {
+    let mut timings: Vec<Timing> = Vec::new();
+    for span in frames.iter().flat_map(|frame| &frame.spans) {
+        let ms = span.duration.as_secs_f32() * 1000.0;
+        match timings.iter_mut().find(|t| t.name == span.name && t.gpu == span.is_gpu()) {
+            Some(timing) => timing.ms += ms,
+            None => timings.push(Timing { name: span.name, gpu: span.is_gpu(), ms }),
+        }
+    }
+    let count = frames.len().max(1) as f32;
+    for timing in &mut timings {
+        timing.ms /= count;
+    }
+    timings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(name: &'static str, thread: u32, ms: u64) -> Span {
+        Span { name, thread, start: Duration::ZERO, duration: Duration::from_millis(ms) }
+    }
+
+    #[test]
+    fn averages_per_frame_and_track() {
+        let frames = vec![
+            FrameRecord { spans: vec![span("update", 1, 2), span("shadow", GPU_THREAD, 4), span("upload", 1, 1)] },
+            FrameRecord { spans: vec![span("update", 1, 4), span("upload", 1, 1), span("upload", 1, 1)] },
+        ];
+        let timings = average_timings(&frames);
+        assert_eq!(timings.len(), 3);
+        assert_eq!(timings[0], Timing { name: "update", gpu: false, ms: 3.0 });
+        assert_eq!(timings[1], Timing { name: "shadow", gpu: true, ms: 2.0 });
+        assert!((timings[2].ms - 1.5).abs() < 1e-6);
+        assert!(average_timings(&[]).is_empty());
+    }
+}
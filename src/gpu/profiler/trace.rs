@@ -0,0 +1,89 @@
+// ============================================
+// Chrome Trace - Запись кадров для chrome://tracing
+// ============================================
+// Формат Trace Event: каждый интервал - событие "X" (начало и
+// длительность в микросекундах), поток GPU и CPU потоки подписаны
+// метаданными. Файл открывается в chrome://tracing или Perfetto.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::{json, Value};
+
+use crate::gpu::core::config_dir;
+use crate::gpu::render::timestamp_name;
+
+use super::{FrameRecord, GPU_THREAD};
+
+/// Папка trace файлов в папке конфигурации
+pub const PROFILE_DIR_NAME: &str = "profiles";
+
+/// События trace для кадров
+fn trace_events(frames: &[FrameRecord]) -> Vec<Value> {
+    let spans = frames.iter().flat_map(|frame| &frame.spans);
+    let threads: BTreeSet<u32> = spans.clone().map(|span| span.thread).collect();
+
+    let names = threads.iter().map(|&thread| {
+        let name = if thread == GPU_THREAD { "GPU".to_string() } else { format!("CPU {}", thread) };
+        json!({ "ph": "M", "name": "thread_name", "pid": 1, "tid": thread, "args": { "name": name } })
+    });
+    let events = spans.map(|span| {
+        json!({
+            "ph": "X",
+            "name": span.name,
+            "cat": if span.is_gpu() { "gpu" } else { "cpu" },
+            "pid": 1,
+            "tid": span.thread,
+            "ts": span.start.as_nanos() as f64 / 1000.0,
+            "dur": span.duration.as_nanos() as f64 / 1000.0,
+        })
+    });
+    names.chain(events).collect()
+}
+
+/// Записать кадры в profiles/<время>.json
+pub fn write_chrome_trace(frames: &[FrameRecord]) -> Result<PathBuf, String> {
+    if frames.is_empty() {
+        return Err("нет записанных кадров (включите оверлей - F4)".to_string());
+    }
+    let dir = config_dir().join(PROFILE_DIR_NAME);
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs());
+    let path = dir.join(format!("{}.json", timestamp_name(secs)));
+    write_trace_file(&path, frames)?;
+    Ok(path)
+}
+
+fn write_trace_file(path: &Path, frames: &[FrameRecord]) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let trace = json!({ "traceEvents": trace_events(frames), "displayTimeUnit": "ms" });
+    let file = std::fs::File::create(path).map_err(|e| e.to_string())?;
+    serde_json::to_writer(std::io::BufWriter::new(file), &trace).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::gpu::profiler::Span;
+
+    #[test]
+    fn events_name_threads_and_use_microseconds() {
+        let frames = vec![FrameRecord {
+            spans: vec![
+                Span { name: "update", thread: 1, start: Duration::from_millis(2), duration: Duration::from_micros(1500) },
+                Span { name: "main", thread: GPU_THREAD, start: Duration::from_millis(3), duration: Duration::from_millis(4) },
+            ],
+        }];
+        let events = trace_events(&frames);
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[0]["args"]["name"], "GPU");
+        assert_eq!(events[1]["args"]["name"], "CPU 1");
+        assert_eq!(events[2]["ts"], 2000.0);
+        assert_eq!(events[2]["dur"], 1500.0);
+        assert_eq!(events[3]["cat"], "gpu");
+    }
+}
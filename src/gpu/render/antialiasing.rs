@@ -0,0 +1,212 @@
+// ============================================
+// Anti-Aliasing - MSAA и FXAA
+// ============================================
+// MSAA: 3D проходы рисуют в мультисэмпловые цвет и глубину, цвет
+// резолвится в HDR цель после каждого прохода. Число сэмплов задаёт
+// все 3D пайплайны, поэтому выбирается при создании рендерера и
+// меняется после перезапуска. Вода читает глубину сцены текстурой -
+// при MSAA перед ней глубина сворачивается в обычную (DepthResolve).
+// FXAA: дешёвый проход по готовому кадру после тонмаппинга,
+// переключается сразу.
+
+use serde::{Serialize, Deserialize};
+
+use super::depth::create_depth_texture;
+use super::post::HDR_FORMAT;
+
+/// Сглаживание
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AntiAliasing {
+    #[default]
+    Off,
+    /// Постобработка по яркости: почти бесплатно, слегка мылит
+    Fxaa,
+    Msaa2x,
+    Msaa4x,
+}
+
+impl AntiAliasing {
+    pub fn next(self) -> Self {
+        match self {
+            AntiAliasing::Off => AntiAliasing::Fxaa,
+            AntiAliasing::Fxaa => AntiAliasing::Msaa2x,
+            AntiAliasing::Msaa2x => AntiAliasing::Msaa4x,
+            AntiAliasing::Msaa4x => AntiAliasing::Off,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AntiAliasing::Off => "Off",
+            AntiAliasing::Fxaa => "FXAA",
+            AntiAliasing::Msaa2x => "MSAA 2x",
+            AntiAliasing::Msaa4x => "MSAA 4x",
+        }
+    }
+
+    /// Сэмплов на пиксель в 3D проходах
+    pub fn sample_count(self) -> u32 {
+        match self {
+            AntiAliasing::Msaa2x => 2,
+            AntiAliasing::Msaa4x => 4,
+            AntiAliasing::Off | AntiAliasing::Fxaa => 1,
+        }
+    }
+
+    pub fn fxaa(self) -> bool {
+        self == AntiAliasing::Fxaa
+    }
+}
+
+/// Фича устройства, без которой доступны только 1 и 4 сэмпла
+pub const MSAA_FEATURES: wgpu::Features = wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES;
+
+/// Число сэмплов, которое поддерживают HDR цель и глубина
+/// (4x есть везде, 2x - только с MSAA_FEATURES)
+pub fn supported_sample_count(adapter: &wgpu::Adapter, requested: u32) -> u32 {
+    if requested <= 1 || requested == 4 {
+        return requested.max(1);
+    }
+    let supports = |format: wgpu::TextureFormat| {
+        adapter.get_texture_format_features(format).flags.sample_count_supported(requested)
+    };
+    if adapter.features().contains(MSAA_FEATURES)
+        && supports(HDR_FORMAT)
+        && supports(wgpu::TextureFormat::Depth32Float)
+    {
+        requested
+    } else {
+        log::warn!("MSAA {}x не поддерживается, используется 4x", requested);
+        4
+    }
+}
+
+/// Сворачивание мультисэмпловой глубины в обычную для прохода воды.
+/// Берётся ближайший сэмпл (reversed-Z - максимум)
+pub struct DepthResolve {
+    layout: wgpu::BindGroupLayout,
+    pipeline: wgpu::RenderPipeline,
+    bind_group: wgpu::BindGroup,
+    view: wgpu::TextureView,
+}
+
+impl DepthResolve {
+    pub fn new(device: &wgpu::Device, scene_size: (u32, u32), msaa_depth: &wgpu::TextureView) -> Self {
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Depth Resolve BGL"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Depth,
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: true,
+                },
+                count: None,
+            }],
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Depth Resolve Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/depth_resolve.wgsl").into()),
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Depth Resolve PL"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Depth Resolve Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_fullscreen"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::Always,
+                stencil: Default::default(),
+                bias: Default::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        let view = create_depth_texture(device, scene_size, 1);
+        let bind_group = Self::create_bind_group(device, &layout, msaa_depth);
+
+        Self { layout, pipeline, bind_group, view }
+    }
+
+    /// Пересоздать под новый размер (мультисэмпловая глубина тоже новая)
+    pub fn resize(&mut self, device: &wgpu::Device, scene_size: (u32, u32), msaa_depth: &wgpu::TextureView) {
+        self.view = create_depth_texture(device, scene_size, 1);
+        self.bind_group = Self::create_bind_group(device, &self.layout, msaa_depth);
+    }
+
+    /// Обычная глубина сцены (читает шейдер воды)
+    pub fn view(&self) -> &wgpu::TextureView {
+        &self.view
+    }
+
+    pub fn render(&self, encoder: &mut wgpu::CommandEncoder) {
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Depth Resolve Pass"),
+            color_attachments: &[],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &self.view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(0.0),
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, &self.bind_group, &[]);
+        pass.draw(0..3, 0..1);
+    }
+
+    fn create_bind_group(
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        msaa_depth: &wgpu::TextureView,
+    ) -> wgpu::BindGroup {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Depth Resolve BG"),
+            layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(msaa_depth),
+            }],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_msaa_modes_multisample() {
+        assert_eq!(AntiAliasing::Off.sample_count(), 1);
+        assert_eq!(AntiAliasing::Fxaa.sample_count(), 1);
+        assert_eq!(AntiAliasing::Msaa4x.sample_count(), 4);
+        assert!(AntiAliasing::Fxaa.fxaa());
+        assert!(!AntiAliasing::Msaa2x.fxaa());
+        assert_eq!(AntiAliasing::Msaa4x.next(), AntiAliasing::Off);
+    }
+}
@@ -1,11 +1,15 @@
 use wgpu::util::DeviceExt;
 
+use super::sampling::SamplingSettings;
+use crate::gpu::blocks::texture_atlas::TEXTURED_MASK_WORDS;
+
 pub struct BindGroupLayouts {
     pub uniform: wgpu::BindGroupLayout,
     pub light: wgpu::BindGroupLayout,
     pub shadow: wgpu::BindGroupLayout,
     pub shadow_pass: wgpu::BindGroupLayout,
     pub atlas: wgpu::BindGroupLayout,
+    pub water: wgpu::BindGroupLayout,
 }
 
 impl BindGroupLayouts {
@@ -84,9 +88,42 @@ impl BindGroupLayouts {
             }],
         });
 
-        // Текстурный атлас для кастомных блоков
+        // Текстурный атлас блоков
         let atlas = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             label: Some("Atlas Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2Array,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        // Проход воды: копия кадра и глубина сцены для отражений
+        let water = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Water Layout"),
             entries: &[
                 wgpu::BindGroupLayoutEntry {
                     binding: 0,
@@ -101,9 +138,29 @@ impl BindGroupLayouts {
                 wgpu::BindGroupLayoutEntry {
                     binding: 1,
                     visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Depth,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
                     ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
                     count: None,
                 },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
             ],
         });
 
@@ -113,6 +170,7 @@ impl BindGroupLayouts {
             shadow,
             shadow_pass,
             atlas,
+            water,
         }
     }
 }
@@ -167,30 +225,47 @@ impl CoreBindGroups {
     }
 }
 
-/// Ресурсы текстурного атласа для кастомных блоков
+/// Параметры сэмплинга атласа для шейдера
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct AtlasParams {
+    mip_bias: f32,
+    _pad: [f32; 3],
+    /// Бит на тайл: текстура из атласа, иначе процедурная
+    textured: [u32; TEXTURED_MASK_WORDS],
+}
+
+/// Ресурсы текстурного атласа блоков
+/// (на GPU - массив, слой на тайл)
 pub struct AtlasResources {
     pub texture: wgpu::Texture,
+    /// Слоёв в массиве (ATLAS_LAYERS, если устройство позволяет)
+    layers: u32,
     pub view: wgpu::TextureView,
     pub sampler: wgpu::Sampler,
+    pub params_buffer: wgpu::Buffer,
+    params: AtlasParams,
     pub bind_group: wgpu::BindGroup,
 }
 
 impl AtlasResources {
-    pub fn new(device: &wgpu::Device, queue: &wgpu::Queue, layout: &wgpu::BindGroupLayout) -> Self {
-        use crate::gpu::blocks::texture_atlas::{BlockTextureAtlas, ATLAS_PIXELS};
+    pub fn new(device: &wgpu::Device, queue: &wgpu::Queue, layout: &wgpu::BindGroupLayout, sampling: &SamplingSettings) -> Self {
+        use crate::gpu::blocks::texture_atlas::{ATLAS_LAYERS, ATLAS_MIP_LEVELS, TEXTURE_SIZE};
         
-        // Генерируем атлас из реестра блоков
-        let atlas = BlockTextureAtlas::from_registry();
+        let layers = device.limits().max_texture_array_layers.min(ATLAS_LAYERS);
+        if layers < ATLAS_LAYERS {
+            log::warn!("Устройство даёт {} слоёв текстур из {}: лишние тайлы возьмут последний слой", layers, ATLAS_LAYERS);
+        }
         
-        // Создаём GPU текстуру
+        // Создаём GPU текстуру с цепочкой мипов
         let texture = device.create_texture(&wgpu::TextureDescriptor {
             label: Some("Block Atlas Texture"),
             size: wgpu::Extent3d {
-                width: ATLAS_PIXELS,
-                height: ATLAS_PIXELS,
-                depth_or_array_layers: 1,
+                width: TEXTURE_SIZE,
+                height: TEXTURE_SIZE,
+                depth_or_array_layers: layers,
             },
-            mip_level_count: 1,
+            mip_level_count: ATLAS_MIP_LEVELS,
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
             format: wgpu::TextureFormat::Rgba8UnormSrgb,
@@ -198,61 +273,105 @@ impl AtlasResources {
             view_formats: &[],
         });
         
-        // Загружаем данные атласа в текстуру
-        queue.write_texture(
-            wgpu::TexelCopyTextureInfo {
-                texture: &texture,
-                mip_level: 0,
-                origin: wgpu::Origin3d::ZERO,
-                aspect: wgpu::TextureAspect::All,
-            },
-            &atlas.data,
-            wgpu::TexelCopyBufferLayout {
-                offset: 0,
-                bytes_per_row: Some(ATLAS_PIXELS * 4),
-                rows_per_image: Some(ATLAS_PIXELS),
-            },
-            wgpu::Extent3d {
-                width: ATLAS_PIXELS,
-                height: ATLAS_PIXELS,
-                depth_or_array_layers: 1,
-            },
-        );
+        // Генерируем атлас из реестра блоков и загружаем в текстуру
+        let textured = Self::upload(queue, &texture, layers);
         
-        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
-        
-        // Nearest-neighbor сэмплер для пиксельного стиля
-        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
-            label: Some("Atlas Sampler"),
-            address_mode_u: wgpu::AddressMode::ClampToEdge,
-            address_mode_v: wgpu::AddressMode::ClampToEdge,
-            address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Nearest,
-            min_filter: wgpu::FilterMode::Nearest,
-            mipmap_filter: wgpu::FilterMode::Nearest,
+        let view = texture.create_view(&wgpu::TextureViewDescriptor {
+            dimension: Some(wgpu::TextureViewDimension::D2Array),
             ..Default::default()
         });
+        let sampler = sampling.create_atlas_sampler(device);
         
-        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        let params = AtlasParams { mip_bias: sampling.mip_bias, _pad: [0.0; 3], textured };
+        let params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Atlas Params"),
+            contents: bytemuck::cast_slice(&[params]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        
+        let bind_group = Self::create_bind_group(device, layout, &view, &sampler, &params_buffer);
+        
+        Self {
+            texture,
+            layers,
+            view,
+            sampler,
+            params_buffer,
+            params,
+            bind_group,
+        }
+    }
+    
+    /// Применить настройки сэмплинга (пересоздаёт сэмплер и bind group)
+    pub fn apply_sampling(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, layout: &wgpu::BindGroupLayout, sampling: &SamplingSettings) {
+        self.sampler = sampling.create_atlas_sampler(device);
+        self.params.mip_bias = sampling.mip_bias;
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::cast_slice(&[self.params]));
+        self.bind_group = Self::create_bind_group(device, layout, &self.view, &self.sampler, &self.params_buffer);
+    }
+    
+    fn create_bind_group(
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        view: &wgpu::TextureView,
+        sampler: &wgpu::Sampler,
+        params_buffer: &wgpu::Buffer,
+    ) -> wgpu::BindGroup {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("Atlas Bind Group"),
             layout,
             entries: &[
                 wgpu::BindGroupEntry {
                     binding: 0,
-                    resource: wgpu::BindingResource::TextureView(&view),
+                    resource: wgpu::BindingResource::TextureView(view),
                 },
                 wgpu::BindGroupEntry {
                     binding: 1,
-                    resource: wgpu::BindingResource::Sampler(&sampler),
+                    resource: wgpu::BindingResource::Sampler(sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: params_buffer.as_entire_binding(),
                 },
             ],
-        });
+        })
+    }
+    
+    /// Пересобрать атлас (после смены пакетов ресурсов или модов)
+    /// Размер текстуры не меняется, поэтому bind group остаётся валидной
+    pub fn rebuild(&mut self, queue: &wgpu::Queue) {
+        self.params.textured = Self::upload(queue, &self.texture, self.layers);
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::cast_slice(&[self.params]));
+    }
+    
+    /// Залить слои и мипы; возвращает маску тайлов с текстурой
+    fn upload(queue: &wgpu::Queue, texture: &wgpu::Texture, layers: u32) -> [u32; TEXTURED_MASK_WORDS] {
+        use crate::gpu::blocks::texture_atlas::{BlockTextureAtlas, TEXTURE_SIZE};
         
-        Self {
-            texture,
-            view,
-            sampler,
-            bind_group,
+        let atlas = BlockTextureAtlas::from_registry();
+        
+        for (mip_level, data) in atlas.layer_levels(layers).iter().enumerate() {
+            let size = TEXTURE_SIZE >> mip_level;
+            queue.write_texture(
+                wgpu::TexelCopyTextureInfo {
+                    texture,
+                    mip_level: mip_level as u32,
+                    origin: wgpu::Origin3d::ZERO,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                data,
+                wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(size * 4),
+                    rows_per_image: Some(size),
+                },
+                wgpu::Extent3d {
+                    width: size,
+                    height: size,
+                    depth_or_array_layers: layers,
+                },
+            );
         }
+        atlas.textured_mask()
     }
 }
@@ -0,0 +1,168 @@
+// ============================================
+// World Border Renderer - Стена границы мира
+// ============================================
+// Полупрозрачная анимированная стена по краю мира. Геометрия целиком
+// строится в шейдере вокруг камеры; CPU пишет uniform и решает,
+// рисовать ли стену (издалека она не видна).
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::util::DeviceExt;
+use ultraviolet::{Mat4, Vec3};
+
+use crate::gpu::terrain::generation::world_border;
+
+/// Дистанция до стены, с которой она начинает гаснуть и пропадает
+const FADE_START: f32 = 12.0;
+const FADE_END: f32 = 48.0;
+
+/// Цвет полос (HDR, слегка светится)
+const BORDER_COLOR: [f32; 4] = [0.35, 0.7, 1.4, 0.55];
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct BorderUniforms {
+    pub view_proj: [[f32; 4]; 4],  // 64 bytes
+    pub camera_pos: [f32; 4],      // xyz + time
+    pub params: [f32; 4],          // radius, fade_start, fade_end, _
+    pub color: [f32; 4],
+}
+
+impl Default for BorderUniforms {
+    fn default() -> Self {
+        Self {
+            view_proj: Mat4::identity().into(),
+            camera_pos: [0.0; 4],
+            params: [0.0, FADE_START, FADE_END, 0.0],
+            color: BORDER_COLOR,
+        }
+    }
+}
+
+pub struct WorldBorderRenderer {
+    uniform_buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+    pipeline: wgpu::RenderPipeline,
+    /// Камера достаточно близко к стене в этом кадре
+    visible: bool,
+}
+
+impl WorldBorderRenderer {
+    pub fn new(device: &wgpu::Device, surface_format: wgpu::TextureFormat, sample_count: u32) -> Self {
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("World Border UB"),
+            contents: bytemuck::cast_slice(&[BorderUniforms::default()]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("World Border BGL"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("World Border BG"),
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("World Border Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/world_border.wgsl").into()),
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("World Border PL"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("World Border Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                // Стена видна с обеих сторон
+                cull_mode: None,
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::GreaterEqual, // Reversed-Z
+                stencil: Default::default(),
+                bias: Default::default(),
+            }),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                ..Default::default()
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            uniform_buffer,
+            bind_group,
+            pipeline,
+            visible: false,
+        }
+    }
+
+    pub fn update(&mut self, queue: &wgpu::Queue, view_proj: [[f32; 4]; 4], camera_pos: Vec3, time: f32) {
+        let Some(border) = world_border() else {
+            self.visible = false;
+            return;
+        };
+
+        // Расстояние до ближайшей стены (снаружи - отрицательное, стена тоже видна)
+        let to_wall = border.radius - camera_pos.x.abs().max(camera_pos.z.abs());
+        self.visible = to_wall < FADE_END;
+        if !self.visible {
+            return;
+        }
+
+        let uniforms = BorderUniforms {
+            view_proj,
+            camera_pos: [camera_pos.x, camera_pos.y, camera_pos.z, time],
+            params: [border.radius, FADE_START, FADE_END, 0.0],
+            color: BORDER_COLOR,
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+    }
+
+    pub fn render<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+        if !self.visible {
+            return;
+        }
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        // 4 стены по 6 вершин
+        render_pass.draw(0..24, 0..1);
+    }
+}
@@ -1,17 +1,19 @@
+/// Буфер глубины сцены размера 3D кадра (sample_count > 1 - для MSAA)
 pub fn create_depth_texture(
     device: &wgpu::Device,
-    config: &wgpu::SurfaceConfiguration,
+    (width, height): (u32, u32),
+    sample_count: u32,
 ) -> wgpu::TextureView {
     device
         .create_texture(&wgpu::TextureDescriptor {
             label: Some("Depth"),
             size: wgpu::Extent3d {
-                width: config.width,
-                height: config.height,
+                width,
+                height,
                 depth_or_array_layers: 1,
             },
             mip_level_count: 1,
-            sample_count: 1,
+            sample_count,
             dimension: wgpu::TextureDimension::D2,
             format: wgpu::TextureFormat::Depth32Float,
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
@@ -0,0 +1,105 @@
+// ============================================
+// Fog - Дальний туман по высоте
+// ============================================
+// Туман экспоненциальный и гуще в низинах (спадает с высотой над
+// уровнем моря). Конец тумана привязан к внешней дистанции LOD:
+// у края прорисовки туман сплошной и сливается с небом, поэтому
+// подгрузка чанков не видна. Начало и плотность - в настройках.
+// Цвет тумана слегка тонируется климатом в точке камеры.
+
+use serde::{Serialize, Deserialize};
+use ultraviolet::Vec3;
+
+use crate::gpu::terrain::voxel::CHUNK_SIZE;
+
+/// Диапазон множителя плотности в настройках
+pub const MIN_FOG_DENSITY: f32 = 0.0;
+pub const MAX_FOG_DENSITY: f32 = 4.0;
+/// Начало тумана - не ближе этой доли дальности
+const MAX_FOG_START: f32 = 0.95;
+/// Спад плотности с высотой (1/блок): вдвое реже каждые ~46 блоков
+pub const FOG_HEIGHT_FALLOFF: f32 = 0.015;
+/// Показатель экспоненты у края дальности при density = 1 (~95% тумана)
+const EDGE_OPTICAL_DEPTH: f32 = 3.0;
+/// Сила оттенка климата
+const BIOME_TINT_STRENGTH: f32 = 0.35;
+
+/// Настройки тумана (секция graphics.fog в settings.json)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FogSettings {
+    /// Множитель плотности (0 - только сплошной туман у края дальности)
+    pub density: f32,
+    /// Начало тумана как доля внешней дистанции LOD (0-0.95)
+    pub start: f32,
+}
+
+impl Default for FogSettings {
+    fn default() -> Self {
+        Self { density: 1.0, start: 0.6 }
+    }
+}
+
+impl FogSettings {
+    /// Начало и конец тумана в блоках для внешней дистанции LOD (в чанках)
+    pub fn range(&self, outer_lod_chunks: i32) -> (f32, f32) {
+        let end = (outer_lod_chunks.max(1) * CHUNK_SIZE) as f32;
+        (end * self.start.clamp(0.0, MAX_FOG_START), end)
+    }
+
+    /// Плотность на блок пути: при density = 1 на уровне моря к концу
+    /// диапазона туман почти сплошной
+    pub fn density_per_block(&self, start: f32, end: f32) -> f32 {
+        let density = self.density.clamp(MIN_FOG_DENSITY, MAX_FOG_DENSITY);
+        density * EDGE_OPTICAL_DEPTH / (end - start).max(1.0)
+    }
+}
+
+/// Оттенок тумана по климату: в жарких сухих местах песочный,
+/// в холодных - голубоватый, во влажных - зеленовато-серый
+pub fn biome_fog_tint(temperature: f32, humidity: f32) -> Vec3 {
+    let heat = ((temperature - 0.5) * 2.0).clamp(-1.0, 1.0);
+    let humidity = humidity.clamp(0.0, 1.0);
+
+    let tint = if heat > 0.0 {
+        mix(Vec3::one(), Vec3::new(1.0, 0.88, 0.7), heat * (1.0 - humidity))
+    } else {
+        mix(Vec3::one(), Vec3::new(0.85, 0.92, 1.05), -heat)
+    };
+    let damp = ((humidity - 0.5) * 2.0).max(0.0);
+    let tint = mix(tint, tint * Vec3::new(0.88, 0.96, 0.9), damp);
+
+    mix(Vec3::one(), tint, BIOME_TINT_STRENGTH)
+}
+
+fn mix(a: Vec3, b: Vec3, t: f32) -> Vec3 {
+    a + (b - a) * t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fog_ends_at_outer_lod() {
+        let fog = FogSettings::default();
+        let (start, end) = fog.range(64);
+        assert_eq!(end, 1024.0);
+        assert!((start - 614.4).abs() < 0.01);
+        let density = fog.density_per_block(start, end);
+        assert!((density * (end - start) - EDGE_OPTICAL_DEPTH).abs() < 1e-4);
+
+        let late = FogSettings { density: 0.0, start: 2.0 };
+        assert!(late.range(8).0 < late.range(8).1);
+        assert_eq!(late.density_per_block(0.0, 100.0), 0.0);
+    }
+
+    #[test]
+    fn climate_tints() {
+        let desert = biome_fog_tint(1.0, 0.0);
+        let tundra = biome_fog_tint(0.0, 0.5);
+        assert!(desert.x > desert.z);
+        assert!(tundra.z > tundra.x);
+        assert_eq!(biome_fog_tint(0.5, 0.5), Vec3::one());
+    }
+}
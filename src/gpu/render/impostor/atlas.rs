@@ -0,0 +1,311 @@
+// ============================================
+// Impostor Atlas - Запекание деревьев в атлас
+// ============================================
+// Каждый тип дерева рендерится один раз при старте
+// в свой слот (64x128) ортографической камерой сбоку
+
+use wgpu::util::DeviceExt;
+use ultraviolet::projection::orthographic_wgpu_dx;
+
+use crate::gpu::biomes::features::TreeType;
+use crate::gpu::blocks::{
+    get_face_colors, BlockType,
+    OAK_LOG, OAK_LEAVES, BIRCH_LOG, BIRCH_LEAVES, SPRUCE_LOG, SPRUCE_LEAVES,
+};
+use crate::gpu::terrain::TerrainVertex;
+
+/// Размер слота в пикселях
+pub const SLOT_WIDTH: u32 = 64;
+pub const SLOT_HEIGHT: u32 = 128;
+/// Количество слотов (должно совпадать с ATLAS_SLOTS в impostor.wgsl)
+pub const ATLAS_SLOTS: u32 = 4;
+
+/// Границы запекаемого дерева в мировых единицах (IMPOSTOR_WIDTH/HEIGHT в шейдере)
+const BAKE_HALF_WIDTH: f32 = 2.5;
+const BAKE_HEIGHT: f32 = 10.0;
+
+pub struct ImpostorAtlas {
+    pub texture: wgpu::Texture,
+    pub view: wgpu::TextureView,
+    pub sampler: wgpu::Sampler,
+}
+
+impl ImpostorAtlas {
+    /// Создать атлас и запечь в него все типы деревьев
+    pub fn bake(device: &wgpu::Device, queue: &wgpu::Queue) -> Self {
+        let format = wgpu::TextureFormat::Rgba8UnormSrgb;
+        let size = wgpu::Extent3d {
+            width: SLOT_WIDTH * ATLAS_SLOTS,
+            height: SLOT_HEIGHT,
+            depth_or_array_layers: 1,
+        };
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Impostor Atlas"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let depth_view = device
+            .create_texture(&wgpu::TextureDescriptor {
+                label: Some("Impostor Bake Depth"),
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Depth32Float,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                view_formats: &[],
+            })
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        // Ортографическая камера: смотрим вдоль -Z, дерево в центре слота
+        let proj = orthographic_wgpu_dx(
+            -BAKE_HALF_WIDTH, BAKE_HALF_WIDTH,
+            0.0, BAKE_HEIGHT,
+            -BAKE_HALF_WIDTH, BAKE_HALF_WIDTH,
+        );
+        let proj: [[f32; 4]; 4] = proj.into();
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Impostor Bake UB"),
+            contents: bytemuck::cast_slice(&[proj]),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Impostor Bake BGL"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Impostor Bake BG"),
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Impostor Bake Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../../shaders/impostor_bake.wgsl").into()),
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Impostor Bake PL"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Impostor Bake Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[TerrainVertex::desc()],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: Some(wgpu::Face::Back),
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::Less,
+                stencil: Default::default(),
+                bias: Default::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        let meshes: Vec<(TreeType, wgpu::Buffer, wgpu::Buffer, u32)> = TreeType::ALL
+            .iter()
+            .map(|&tree_type| {
+                let (vertices, indices) = build_tree_mesh(tree_type);
+                let vb = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("Impostor Bake VB"),
+                    contents: bytemuck::cast_slice(&vertices),
+                    usage: wgpu::BufferUsages::VERTEX,
+                });
+                let ib = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("Impostor Bake IB"),
+                    contents: bytemuck::cast_slice(&indices),
+                    usage: wgpu::BufferUsages::INDEX,
+                });
+                (tree_type, vb, ib, indices.len() as u32)
+            })
+            .collect();
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Impostor Bake Encoder"),
+        });
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Impostor Bake Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &depth_view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Discard,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            pass.set_pipeline(&pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            for (tree_type, vb, ib, index_count) in &meshes {
+                let slot = tree_type.index() as f32;
+                pass.set_viewport(
+                    slot * SLOT_WIDTH as f32, 0.0,
+                    SLOT_WIDTH as f32, SLOT_HEIGHT as f32,
+                    0.0, 1.0,
+                );
+                pass.set_vertex_buffer(0, vb.slice(..));
+                pass.set_index_buffer(ib.slice(..), wgpu::IndexFormat::Uint32);
+                pass.draw_indexed(0..*index_count, 0, 0..1);
+            }
+        }
+        queue.submit(std::iter::once(encoder.finish()));
+
+        // Линейная фильтрация - импосторы видны только издалека
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Impostor Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
+        });
+
+        Self { texture, view, sampler }
+    }
+}
+
+/// Блочная модель дерева в локальных координатах (основание ствола в 0,0,0)
+/// Повторяет place_basic_tree / place_spruce_tree с эталонной высотой 6
+fn build_tree_mesh(tree_type: TreeType) -> (Vec<TerrainVertex>, Vec<u32>) {
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+    let height = 6;
+
+    let (log, leaves) = match tree_type {
+        TreeType::Oak => (OAK_LOG, OAK_LEAVES),
+        TreeType::Birch => (BIRCH_LOG, BIRCH_LEAVES),
+        TreeType::Spruce => (SPRUCE_LOG, SPRUCE_LEAVES),
+    };
+
+    let mut blocks: Vec<(i32, i32, i32, BlockType)> = Vec::new();
+    match tree_type {
+        TreeType::Spruce => {
+            let height = height + 1;
+            let top_y = height;
+            blocks.push((0, top_y, 0, leaves));
+            blocks.push((0, top_y + 1, 0, leaves));
+            for y in 2..top_y {
+                let layer = (top_y - y) % 4;
+                let radius = if layer == 1 || layer == 3 { 2 } else { 1 };
+                for dx in -1i32..=1 {
+                    for dz in -1i32..=1 {
+                        let in_layer = if radius == 1 { dx.abs() + dz.abs() == 1 } else { true };
+                        if in_layer && (dx, dz) != (0, 0) {
+                            blocks.push((dx, y, dz, leaves));
+                        }
+                    }
+                }
+            }
+            for y in 0..height {
+                blocks.push((0, y, 0, log));
+            }
+        }
+        TreeType::Oak | TreeType::Birch => {
+            for y in (height - 3)..=(height + 1) {
+                let y_offset = y - height;
+                let radius: i32 = if y_offset >= 0 { 1 } else { 2 };
+                for x in -radius..=radius {
+                    for z in -radius..=radius {
+                        if x.abs() == radius && z.abs() == radius && y_offset < 0 {
+                            continue;
+                        }
+                        if (x, z) == (0, 0) && y < height {
+                            continue;
+                        }
+                        blocks.push((x, y, z, leaves));
+                    }
+                }
+            }
+            for y in 0..height {
+                blocks.push((0, y, 0, log));
+            }
+        }
+    }
+
+    for (x, y, z, block) in blocks {
+        push_cube(&mut vertices, &mut indices, [x as f32 - 0.5, y as f32, z as f32 - 0.5], block);
+    }
+
+    (vertices, indices)
+}
+
+fn push_cube(vertices: &mut Vec<TerrainVertex>, indices: &mut Vec<u32>, min: [f32; 3], block: BlockType) {
+    let (top, side) = get_face_colors(block);
+    let [x0, y0, z0] = min;
+    let (x1, y1, z1) = (x0 + 1.0, y0 + 1.0, z0 + 1.0);
+
+    let faces: [([f32; 3], [f32; 3], [[f32; 3]; 4]); 6] = [
+        ([0.0, 1.0, 0.0], top, [[x0, y1, z0], [x0, y1, z1], [x1, y1, z1], [x1, y1, z0]]),
+        ([0.0, -1.0, 0.0], side, [[x0, y0, z0], [x1, y0, z0], [x1, y0, z1], [x0, y0, z1]]),
+        ([1.0, 0.0, 0.0], side, [[x1, y0, z0], [x1, y1, z0], [x1, y1, z1], [x1, y0, z1]]),
+        ([-1.0, 0.0, 0.0], side, [[x0, y0, z1], [x0, y1, z1], [x0, y1, z0], [x0, y0, z0]]),
+        ([0.0, 0.0, 1.0], side, [[x1, y0, z1], [x1, y1, z1], [x0, y1, z1], [x0, y0, z1]]),
+        ([0.0, 0.0, -1.0], side, [[x0, y0, z0], [x0, y1, z0], [x1, y1, z0], [x1, y0, z0]]),
+    ];
+
+    for (normal, color, corners) in faces {
+        let base = vertices.len() as u32;
+        for corner in corners {
+            vertices.push(TerrainVertex::new(corner, normal, color));
+        }
+        indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+    }
+}
@@ -0,0 +1,11 @@
+// ============================================
+// Tree Impostors - Billboard деревья для дальних LOD
+// ============================================
+// Деревья на LOD > 1 рисуются как повёрнутые к камере квады
+// с текстурой, запечённой один раз на тип дерева
+
+mod atlas;
+mod renderer;
+
+pub use atlas::{ImpostorAtlas, SLOT_WIDTH, SLOT_HEIGHT, ATLAS_SLOTS};
+pub use renderer::ImpostorRenderer;
@@ -0,0 +1,266 @@
+// ============================================
+// Impostor Renderer - Billboard деревья на дальних LOD
+// ============================================
+// Один draw call на видимый LOD чанк: 6 вершин x N деревьев,
+// данные деревьев в storage buffer, непрерывно по чанкам
+
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::sync::Arc;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::gpu::biomes::features::TreeImpostor;
+use crate::gpu::render::bind_groups::BindGroupLayouts;
+use crate::gpu::terrain::ChunkKey;
+
+use super::atlas::ImpostorAtlas;
+
+/// Данные импостора в storage buffer (32 байта)
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct ImpostorInstance {
+    /// xyz = основание, w = высота ствола
+    position_height: [f32; 4],
+    /// x = слот атласа
+    params: [f32; 4],
+}
+
+pub struct ImpostorRenderer {
+    device: Arc<wgpu::Device>,
+    pipeline: wgpu::RenderPipeline,
+    layout: wgpu::BindGroupLayout,
+    atlas: ImpostorAtlas,
+    buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+    /// Вместимость буфера в инстансах
+    capacity: usize,
+    /// Импосторы по LOD чанкам
+    chunks: HashMap<ChunkKey, Vec<ImpostorInstance>>,
+    /// Диапазоны в storage buffer после последней заливки
+    ranges: Vec<(ChunkKey, Range<u32>)>,
+    dirty: bool,
+}
+
+impl ImpostorRenderer {
+    pub fn new(
+        device: Arc<wgpu::Device>,
+        queue: &wgpu::Queue,
+        surface_format: wgpu::TextureFormat,
+        sample_count: u32,
+        layouts: &BindGroupLayouts,
+    ) -> Self {
+        let atlas = ImpostorAtlas::bake(&device, queue);
+
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Impostor Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Impostor Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../../shaders/impostor.wgsl").into()),
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Impostor PL"),
+            bind_group_layouts: &[&layouts.uniform, &layouts.light, &layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Impostor Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                cull_mode: None, // Billboard всегда повёрнут к камере
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::Greater, // Reversed-Z
+                stencil: Default::default(),
+                bias: Default::default(),
+            }),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                ..Default::default()
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        let capacity = 4096;
+        let (buffer, bind_group) = Self::create_buffer(&device, &layout, &atlas, capacity);
+
+        Self {
+            device,
+            pipeline,
+            layout,
+            atlas,
+            buffer,
+            bind_group,
+            capacity,
+            chunks: HashMap::new(),
+            ranges: Vec::new(),
+            dirty: false,
+        }
+    }
+
+    fn create_buffer(
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        atlas: &ImpostorAtlas,
+        capacity: usize,
+    ) -> (wgpu::Buffer, wgpu::BindGroup) {
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Impostor Instances"),
+            size: (capacity.max(1) * std::mem::size_of::<ImpostorInstance>()) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Impostor BG"),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&atlas.view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(&atlas.sampler),
+                },
+            ],
+        });
+        (buffer, bind_group)
+    }
+
+    /// Установить импосторы LOD чанка (пустой список удаляет чанк)
+    pub fn set_chunk(&mut self, key: ChunkKey, impostors: &[TreeImpostor]) {
+        if impostors.is_empty() {
+            if self.chunks.remove(&key).is_some() {
+                self.dirty = true;
+            }
+            return;
+        }
+
+        let instances = impostors
+            .iter()
+            .map(|tree| ImpostorInstance {
+                position_height: [tree.position[0], tree.position[1], tree.position[2], tree.height],
+                params: [tree.tree_type.index() as f32, 0.0, 0.0, 0.0],
+            })
+            .collect();
+        self.chunks.insert(key, instances);
+        self.dirty = true;
+    }
+
+    /// Удалить чанки, которых нет в списке нужных
+    pub fn retain_only(&mut self, valid_keys: &HashSet<ChunkKey>) {
+        let before = self.chunks.len();
+        self.chunks.retain(|key, _| valid_keys.contains(key));
+        if self.chunks.len() != before {
+            self.dirty = true;
+        }
+    }
+
+    /// Залить изменения в storage buffer
+    pub fn flush(&mut self, queue: &wgpu::Queue) {
+        if !self.dirty {
+            return;
+        }
+        self.dirty = false;
+
+        let mut packed: Vec<ImpostorInstance> = Vec::new();
+        self.ranges.clear();
+        for (key, instances) in &self.chunks {
+            let start = packed.len() as u32;
+            packed.extend_from_slice(instances);
+            self.ranges.push((*key, start..packed.len() as u32));
+        }
+
+        if packed.len() > self.capacity {
+            self.capacity = packed.len().next_power_of_two();
+            let (buffer, bind_group) = Self::create_buffer(&self.device, &self.layout, &self.atlas, self.capacity);
+            self.buffer = buffer;
+            self.bind_group = bind_group;
+        }
+        if !packed.is_empty() {
+            queue.write_buffer(&self.buffer, 0, bytemuck::cast_slice(&packed));
+        }
+    }
+
+    /// Отрисовка импосторов видимых LOD чанков
+    /// is_chunk_visible(x, z, scale) - frustum culling
+    pub fn render<'a, F>(
+        &'a self,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        uniform_bind_group: &'a wgpu::BindGroup,
+        light_bind_group: &'a wgpu::BindGroup,
+        is_chunk_visible: F,
+    ) where
+        F: Fn(i32, i32, i32) -> bool,
+    {
+        if self.ranges.is_empty() {
+            return;
+        }
+
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, uniform_bind_group, &[]);
+        render_pass.set_bind_group(1, light_bind_group, &[]);
+        render_pass.set_bind_group(2, &self.bind_group, &[]);
+
+        for (key, range) in &self.ranges {
+            if is_chunk_visible(key.x, key.z, key.scale) {
+                render_pass.draw(0..6, range.clone());
+            }
+        }
+    }
+}
@@ -0,0 +1,155 @@
+// ============================================
+// Decoration Meshes - Меши декораций для инстансинга
+// ============================================
+// Один маленький меш на тип декорации, строится один раз
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::gpu::biomes::DecorationKind;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct DecorationVertex {
+    pub position: [f32; 3],
+    pub normal: [f32; 3],
+    /// Множитель оттенка инстанса (0 = собственный цвет, 1 = tint)
+    pub color: [f32; 3],
+    pub tint_weight: f32,
+}
+
+impl DecorationVertex {
+    pub fn desc() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<DecorationVertex>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &[
+                wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 0,
+                    format: wgpu::VertexFormat::Float32x3,
+                },
+                wgpu::VertexAttribute {
+                    offset: 12,
+                    shader_location: 1,
+                    format: wgpu::VertexFormat::Float32x3,
+                },
+                wgpu::VertexAttribute {
+                    offset: 24,
+                    shader_location: 2,
+                    format: wgpu::VertexFormat::Float32x3,
+                },
+                wgpu::VertexAttribute {
+                    offset: 36,
+                    shader_location: 3,
+                    format: wgpu::VertexFormat::Float32,
+                },
+            ],
+        }
+    }
+
+    fn new(position: [f32; 3], normal: [f32; 3], color: [f32; 3], tint_weight: f32) -> Self {
+        Self { position, normal, color, tint_weight }
+    }
+}
+
+/// Данные инстанса в storage buffer (32 байта, выравнивание vec4)
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct DecorationInstance {
+    /// xyz = позиция основания, w = поворот вокруг Y
+    pub position_rotation: [f32; 4],
+    /// rgb = оттенок, a = масштаб
+    pub tint_scale: [f32; 4],
+}
+
+/// CPU-меш декорации
+pub struct DecorationMesh {
+    pub vertices: Vec<DecorationVertex>,
+    pub indices: Vec<u32>,
+}
+
+impl DecorationMesh {
+    /// Построить меш для типа декорации
+    pub fn build(kind: DecorationKind) -> Self {
+        match kind {
+            DecorationKind::GrassTuft => Self::cross_quads(0.45, 0.6, [1.0, 1.0, 1.0], 1.0),
+            DecorationKind::Flower => Self::flower(),
+            DecorationKind::Pebble => Self::pebble(),
+            DecorationKind::DeadBush => Self::dead_bush(),
+        }
+    }
+
+    /// Два перекрещенных квада (классическая "трава крестом")
+    fn cross_quads(half_width: f32, height: f32, color: [f32; 3], tint_weight: f32) -> Self {
+        let mut mesh = Self { vertices: Vec::new(), indices: Vec::new() };
+        let d = half_width * std::f32::consts::FRAC_1_SQRT_2;
+        mesh.add_double_sided_quad([-d, 0.0, -d], [d, 0.0, d], height, color, tint_weight);
+        mesh.add_double_sided_quad([-d, 0.0, d], [d, 0.0, -d], height, color, tint_weight);
+        mesh
+    }
+
+    /// Стебель + цветок-головка
+    fn flower() -> Self {
+        let mut mesh = Self::cross_quads(0.08, 0.45, [0.25, 0.55, 0.2], 0.0);
+        let head = Self::box_mesh([-0.1, 0.45, -0.1], [0.1, 0.6, 0.1], [1.0, 1.0, 1.0], 1.0);
+        mesh.append(head);
+        mesh
+    }
+
+    /// Плоский камешек
+    fn pebble() -> Self {
+        Self::box_mesh([-0.12, 0.0, -0.09], [0.12, 0.07, 0.09], [1.0, 1.0, 1.0], 1.0)
+    }
+
+    /// Сухой куст: два креста из тонких веток разной высоты
+    fn dead_bush() -> Self {
+        let mut mesh = Self::cross_quads(0.4, 0.55, [1.0, 1.0, 1.0], 1.0);
+        let mut low = Self::cross_quads(0.3, 0.35, [0.85, 0.85, 0.85], 1.0);
+        // Второй крест повёрнут на 45°: ветки во все стороны
+        for vertex in &mut low.vertices {
+            let [x, y, z] = vertex.position;
+            vertex.position = [(x - z) * std::f32::consts::FRAC_1_SQRT_2, y, (x + z) * std::f32::consts::FRAC_1_SQRT_2];
+        }
+        mesh.append(low);
+        mesh
+    }
+
+    fn append(&mut self, other: DecorationMesh) {
+        let base = self.vertices.len() as u32;
+        self.vertices.extend(other.vertices);
+        self.indices.extend(other.indices.into_iter().map(|i| i + base));
+    }
+
+    fn add_double_sided_quad(&mut self, a: [f32; 3], b: [f32; 3], height: f32, color: [f32; 3], tint_weight: f32) {
+        // Нормаль вверх: трава освещается как поверхность земли, без тёмных граней
+        let normal = [0.0, 1.0, 0.0];
+        let base = self.vertices.len() as u32;
+        self.vertices.push(DecorationVertex::new(a, normal, color, tint_weight));
+        self.vertices.push(DecorationVertex::new(b, normal, color, tint_weight));
+        self.vertices.push(DecorationVertex::new([b[0], height, b[2]], normal, color, tint_weight));
+        self.vertices.push(DecorationVertex::new([a[0], height, a[2]], normal, color, tint_weight));
+        self.indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+        self.indices.extend_from_slice(&[base, base + 2, base + 1, base, base + 3, base + 2]);
+    }
+
+    fn box_mesh(min: [f32; 3], max: [f32; 3], color: [f32; 3], tint_weight: f32) -> Self {
+        let mut mesh = Self { vertices: Vec::new(), indices: Vec::new() };
+        let [x0, y0, z0] = min;
+        let [x1, y1, z1] = max;
+        let faces: [([f32; 3], [[f32; 3]; 4]); 5] = [
+            ([0.0, 1.0, 0.0], [[x0, y1, z0], [x0, y1, z1], [x1, y1, z1], [x1, y1, z0]]),
+            ([1.0, 0.0, 0.0], [[x1, y0, z0], [x1, y1, z0], [x1, y1, z1], [x1, y0, z1]]),
+            ([-1.0, 0.0, 0.0], [[x0, y0, z1], [x0, y1, z1], [x0, y1, z0], [x0, y0, z0]]),
+            ([0.0, 0.0, 1.0], [[x1, y0, z1], [x1, y1, z1], [x0, y1, z1], [x0, y0, z1]]),
+            ([0.0, 0.0, -1.0], [[x0, y0, z0], [x0, y1, z0], [x1, y1, z0], [x1, y0, z0]]),
+        ];
+        for (normal, corners) in faces {
+            let base = mesh.vertices.len() as u32;
+            for corner in corners {
+                mesh.vertices.push(DecorationVertex::new(corner, normal, color, tint_weight));
+            }
+            mesh.indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+        }
+        mesh
+    }
+}
@@ -0,0 +1,11 @@
+// ============================================
+// Instanced Rendering - Инстансинг повторяющихся мешей
+// ============================================
+// Трава, цветы, камешки (в будущем - мебель) рисуются
+// одним мешем на тип + трансформы инстансов в storage buffer
+
+mod mesh;
+mod renderer;
+
+pub use mesh::{DecorationMesh, DecorationVertex, DecorationInstance};
+pub use renderer::{DecorationRenderer, DECORATION_RADIUS};
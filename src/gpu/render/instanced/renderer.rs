@@ -0,0 +1,343 @@
+// ============================================
+// Decoration Renderer - Инстансинг декораций
+// ============================================
+// Один vertex/index буфер на тип меша + storage buffer с инстансами.
+// Инстансы одного типа лежат в буфере непрерывно по чанкам,
+// поэтому куллинг по чанку = пропуск диапазона first_instance..end
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
+
+use wgpu::util::DeviceExt;
+use ultraviolet::Vec3;
+
+use crate::gpu::biomes::{DecorationKind, scatter_chunk_decorations};
+use crate::gpu::render::bind_groups::BindGroupLayouts;
+use crate::gpu::terrain::{WorldChanges, CHUNK_SIZE};
+
+use super::mesh::{DecorationMesh, DecorationVertex, DecorationInstance};
+
+/// Радиус декораций вокруг камеры (в чанках)
+pub const DECORATION_RADIUS: i32 = 6;
+
+/// Меш одного типа декорации на GPU
+struct GpuDecorationMesh {
+    vertex_buffer: wgpu::Buffer,
+    index_buffer: wgpu::Buffer,
+    index_count: u32,
+}
+
+/// Storage buffer инстансов одного типа
+struct InstanceBuffer {
+    buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+    /// Вместимость в инстансах
+    capacity: usize,
+}
+
+/// Инстансы одного чанка, по типам
+type ChunkInstances = [Vec<DecorationInstance>; DecorationKind::COUNT];
+
+pub struct DecorationRenderer {
+    device: Arc<wgpu::Device>,
+    pipeline: wgpu::RenderPipeline,
+    instance_layout: wgpu::BindGroupLayout,
+    meshes: Vec<GpuDecorationMesh>,
+    instance_buffers: Vec<InstanceBuffer>,
+    /// CPU данные инстансов по чанкам
+    chunks: HashMap<(i32, i32), ChunkInstances>,
+    /// Диапазоны инстансов в storage buffer для каждого чанка
+    ranges: Vec<((i32, i32), [Range<u32>; DecorationKind::COUNT])>,
+    dirty: bool,
+    last_center: Option<(i32, i32)>,
+    last_world_version: u64,
+    enabled: bool,
+}
+
+impl DecorationRenderer {
+    pub fn new(
+        device: Arc<wgpu::Device>,
+        surface_format: wgpu::TextureFormat,
+        sample_count: u32,
+        layouts: &BindGroupLayouts,
+    ) -> Self {
+        let instance_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Decoration Instances Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Storage { read_only: true },
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Decoration Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../../shaders/instanced.wgsl").into()),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Decoration Layout"),
+            bind_group_layouts: &[&layouts.uniform, &layouts.light, &instance_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Decoration Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[DecorationVertex::desc()],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: Some(wgpu::Face::Back),
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::Greater, // Reversed-Z
+                stencil: Default::default(),
+                bias: Default::default(),
+            }),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                ..Default::default()
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        let meshes = DecorationKind::ALL
+            .iter()
+            .map(|&kind| {
+                let mesh = DecorationMesh::build(kind);
+                GpuDecorationMesh {
+                    vertex_buffer: device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                        label: Some(&format!("Decoration {:?} VB", kind)),
+                        contents: bytemuck::cast_slice(&mesh.vertices),
+                        usage: wgpu::BufferUsages::VERTEX,
+                    }),
+                    index_buffer: device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                        label: Some(&format!("Decoration {:?} IB", kind)),
+                        contents: bytemuck::cast_slice(&mesh.indices),
+                        usage: wgpu::BufferUsages::INDEX,
+                    }),
+                    index_count: mesh.indices.len() as u32,
+                }
+            })
+            .collect();
+
+        let instance_buffers = DecorationKind::ALL
+            .iter()
+            .map(|_| Self::create_instance_buffer(&device, &instance_layout, 1024))
+            .collect();
+
+        Self {
+            device,
+            pipeline,
+            instance_layout,
+            meshes,
+            instance_buffers,
+            chunks: HashMap::new(),
+            ranges: Vec::new(),
+            dirty: false,
+            last_center: None,
+            last_world_version: 0,
+            enabled: true,
+        }
+    }
+
+    fn create_instance_buffer(
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        capacity: usize,
+    ) -> InstanceBuffer {
+        let capacity = capacity.max(1);
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Decoration Instances"),
+            size: (capacity * std::mem::size_of::<DecorationInstance>()) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Decoration Instances BG"),
+            layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: buffer.as_entire_binding(),
+            }],
+        });
+        InstanceBuffer { buffer, bind_group, capacity }
+    }
+
+    /// Включить/выключить декорации
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Количество инстансов на GPU (для отладки)
+    pub fn instance_count(&self) -> usize {
+        self.chunks
+            .values()
+            .map(|per_kind| per_kind.iter().map(Vec::len).sum::<usize>())
+            .sum()
+    }
+
+    /// Обновить набор чанков вокруг камеры и перезалить инстансы при изменениях
+    pub fn update(&mut self, queue: &wgpu::Queue, camera_pos: Vec3, world_changes: &WorldChanges) {
+        if !self.enabled {
+            return;
+        }
+
+        let center = (
+            (camera_pos.x / CHUNK_SIZE as f32).floor() as i32,
+            (camera_pos.z / CHUNK_SIZE as f32).floor() as i32,
+        );
+
+        let version = world_changes.version();
+        let world_changed = version != self.last_world_version;
+
+        if self.last_center != Some(center) || world_changed {
+            let changes = world_changes.get_all_changes_copy();
+
+            // Правки мира делаются в радиусе досягаемости игрока,
+            // поэтому пересыпаем только чанки рядом с камерой
+            if world_changed {
+                for dz in -1..=1 {
+                    for dx in -1..=1 {
+                        self.chunks.remove(&(center.0 + dx, center.1 + dz));
+                    }
+                }
+                self.last_world_version = version;
+            }
+
+            self.chunks.retain(|&(cx, cz), _| {
+                (cx - center.0).abs() <= DECORATION_RADIUS && (cz - center.1).abs() <= DECORATION_RADIUS
+            });
+
+            for dz in -DECORATION_RADIUS..=DECORATION_RADIUS {
+                for dx in -DECORATION_RADIUS..=DECORATION_RADIUS {
+                    let key = (center.0 + dx, center.1 + dz);
+                    self.chunks.entry(key).or_insert_with(|| {
+                        let mut per_kind: ChunkInstances = Default::default();
+                        for placement in scatter_chunk_decorations(key.0, key.1, &changes) {
+                            per_kind[placement.kind.index()].push(DecorationInstance {
+                                position_rotation: [
+                                    placement.position[0],
+                                    placement.position[1],
+                                    placement.position[2],
+                                    placement.rotation,
+                                ],
+                                tint_scale: [
+                                    placement.tint[0],
+                                    placement.tint[1],
+                                    placement.tint[2],
+                                    placement.scale,
+                                ],
+                            });
+                        }
+                        per_kind
+                    });
+                }
+            }
+
+            self.last_center = Some(center);
+            self.dirty = true;
+        }
+
+        if self.dirty {
+            self.upload(queue);
+            self.dirty = false;
+        }
+    }
+
+    /// Собрать инстансы по типам и залить в storage buffers
+    fn upload(&mut self, queue: &wgpu::Queue) {
+        let mut packed: [Vec<DecorationInstance>; DecorationKind::COUNT] = Default::default();
+        self.ranges.clear();
+
+        for (&key, per_kind) in &self.chunks {
+            let ranges: [Range<u32>; DecorationKind::COUNT] = std::array::from_fn(|kind| {
+                let start = packed[kind].len() as u32;
+                packed[kind].extend_from_slice(&per_kind[kind]);
+                start..packed[kind].len() as u32
+            });
+            self.ranges.push((key, ranges));
+        }
+
+        for (kind, instances) in packed.iter().enumerate() {
+            if instances.len() > self.instance_buffers[kind].capacity {
+                let capacity = instances.len().next_power_of_two();
+                self.instance_buffers[kind] =
+                    Self::create_instance_buffer(&self.device, &self.instance_layout, capacity);
+            }
+            if !instances.is_empty() {
+                queue.write_buffer(&self.instance_buffers[kind].buffer, 0, bytemuck::cast_slice(instances));
+            }
+        }
+    }
+
+    /// Отрисовка видимых чанков
+    /// is_chunk_visible - проверка видимости чанка (frustum culling)
+    pub fn render<'a, F>(
+        &'a self,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        uniform_bind_group: &'a wgpu::BindGroup,
+        light_bind_group: &'a wgpu::BindGroup,
+        is_chunk_visible: F,
+    ) where
+        F: Fn(i32, i32) -> bool,
+    {
+        if !self.enabled || self.ranges.is_empty() {
+            return;
+        }
+
+        let visible: Vec<&[Range<u32>; DecorationKind::COUNT]> = self.ranges
+            .iter()
+            .filter(|((cx, cz), _)| is_chunk_visible(*cx, *cz))
+            .map(|(_, ranges)| ranges)
+            .collect();
+
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, uniform_bind_group, &[]);
+        render_pass.set_bind_group(1, light_bind_group, &[]);
+
+        for kind in DecorationKind::ALL {
+            let mesh = &self.meshes[kind.index()];
+            render_pass.set_bind_group(2, &self.instance_buffers[kind.index()].bind_group, &[]);
+            render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
+            render_pass.set_index_buffer(mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+
+            for ranges in &visible {
+                let range = ranges[kind.index()].clone();
+                if !range.is_empty() {
+                    render_pass.draw_indexed(0..mesh.index_count, 0, range);
+                }
+            }
+        }
+    }
+}
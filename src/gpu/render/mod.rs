@@ -4,9 +4,28 @@
 
 mod uniforms;
 mod shadow;
+mod water;
 mod pipelines;
 mod bind_groups;
 mod depth;
 mod renderer;
+mod instanced;
+mod impostor;
+mod palette;
+mod sampling;
+mod fog;
+mod post;
+mod antialiasing;
+mod resolution;
+mod screenshot;
+mod border;
+mod viewmodel;
 
-pub use renderer::Renderer;
+pub use renderer::{Renderer, MemoryPressure};
+pub use palette::PaletteTheme;
+pub use sampling::{SamplingSettings, TextureFilter, ANISOTROPY_LEVELS};
+pub use fog::FogSettings;
+pub use post::{DepthOfField, PostSettings};
+pub use antialiasing::AntiAliasing;
+pub use resolution::{ResolutionSettings, MIN_RENDER_SCALE, MAX_RENDER_SCALE};
+pub use screenshot::timestamp_name;
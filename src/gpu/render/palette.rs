@@ -0,0 +1,49 @@
+// ============================================
+// Palette Theme - Цветовая тема мира
+// ============================================
+// Тема применяется в шейдерах как LUT поверх базовых цветов блоков
+// (apply_palette в terrain_shadows/instanced/impostor.wgsl),
+// поэтому переключение не требует перестройки мешей.
+
+/// Цветовая тема мира
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PaletteTheme {
+    /// Исходные цвета блоков
+    #[default]
+    Default,
+    /// Мягкие, высветленные цвета
+    Pastel,
+    /// Приглушённые цвета с жёстким контрастом
+    Gritty,
+}
+
+impl PaletteTheme {
+    pub const ALL: [PaletteTheme; 3] = [PaletteTheme::Default, PaletteTheme::Pastel, PaletteTheme::Gritty];
+
+    /// Индекс темы (передаётся в шейдер и в сохранение)
+    pub fn index(self) -> u8 {
+        match self {
+            PaletteTheme::Default => 0,
+            PaletteTheme::Pastel => 1,
+            PaletteTheme::Gritty => 2,
+        }
+    }
+
+    /// Тема по индексу (неизвестный индекс = Default)
+    pub fn from_index(index: u8) -> Self {
+        Self::ALL.get(index as usize).copied().unwrap_or_default()
+    }
+
+    /// Следующая тема по кругу
+    pub fn next(self) -> Self {
+        Self::from_index((self.index() + 1) % Self::ALL.len() as u8)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PaletteTheme::Default => "Default",
+            PaletteTheme::Pastel => "Pastel",
+            PaletteTheme::Gritty => "Gritty",
+        }
+    }
+}
@@ -1,11 +1,22 @@
 use crate::gpu::terrain::TerrainVertex;
+use crate::gpu::terrain::mesh::FadeInstance;
 use crate::gpu::player::PlayerVertex;
+use crate::gpu::subvoxel::{ChunkOrigin, PackedVertex};
 
 use super::bind_groups::BindGroupLayouts;
 
 pub struct Pipelines {
     pub terrain: wgpu::RenderPipeline,
+    /// Непрозрачный terrain в переходе между LOD (screen-door дизеринг)
+    pub terrain_dither: wgpu::RenderPipeline,
+    /// Полупрозрачные грани terrain: смешивание, глубина только читается
+    pub translucent: wgpu::RenderPipeline,
+    /// Поверхность воды: волны, френель и экранные отражения
+    pub water: wgpu::RenderPipeline,
     pub shadow: wgpu::RenderPipeline,
+    /// Субвоксели: PackedVertex + угол чанка, освещение как у terrain
+    pub subvoxel: wgpu::RenderPipeline,
+    pub subvoxel_shadow: wgpu::RenderPipeline,
     pub player: wgpu::RenderPipeline,
 }
 
@@ -13,6 +24,7 @@ impl Pipelines {
     pub fn new(
         device: &wgpu::Device,
         surface_format: wgpu::TextureFormat,
+        sample_count: u32,
         layouts: &BindGroupLayouts,
         model_layout: &wgpu::BindGroupLayout,
     ) -> Self {
@@ -21,6 +33,11 @@ impl Pipelines {
             source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/terrain_shadows.wgsl").into()),
         });
 
+        let water_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Water Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/water.wgsl").into()),
+        });
+
         let shadow_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("Shadow Shader"),
             source: wgpu::ShaderSource::Wgsl(include_str!("../lighting/shadow.wgsl").into()),
@@ -37,6 +54,12 @@ impl Pipelines {
             push_constant_ranges: &[],
         });
 
+        let water_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Water Layout"),
+            bind_group_layouts: &[&layouts.uniform, &layouts.light, &layouts.shadow, &layouts.water],
+            push_constant_ranges: &[],
+        });
+
         let shadow_pl_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("Shadow PL Layout"),
             bind_group_layouts: &[&layouts.shadow_pass],
@@ -82,7 +105,133 @@ impl Pipelines {
                 bias: Default::default(),
             }),
             multisample: wgpu::MultisampleState {
-                count: 1,
+                count: sample_count,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        let terrain_dither = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Terrain Dither Pipeline"),
+            layout: Some(&terrain_layout),
+            vertex: wgpu::VertexState {
+                module: &terrain_shader,
+                entry_point: Some("vs_dither"),
+                buffers: &[TerrainVertex::desc(), FadeInstance::desc()],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &terrain_shader,
+                entry_point: Some("fs_dither"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: Some(wgpu::Face::Back),
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::Greater, // Reversed-Z
+                stencil: Default::default(),
+                bias: Default::default(),
+            }),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        let translucent = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Translucent Terrain Pipeline"),
+            layout: Some(&terrain_layout),
+            vertex: wgpu::VertexState {
+                module: &terrain_shader,
+                entry_point: Some("vs_main"),
+                buffers: &[TerrainVertex::desc()],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &terrain_shader,
+                entry_point: Some("fs_translucent"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                front_face: wgpu::FrontFace::Ccw,
+                // Поверхность воды видна и снизу
+                cull_mode: None,
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::Greater, // Reversed-Z
+                stencil: Default::default(),
+                bias: Default::default(),
+            }),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        let water = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Water Pipeline"),
+            layout: Some(&water_layout),
+            vertex: wgpu::VertexState {
+                module: &water_shader,
+                entry_point: Some("vs_water"),
+                buffers: &[TerrainVertex::desc()],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &water_shader,
+                entry_point: Some("fs_water"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                front_face: wgpu::FrontFace::Ccw,
+                // Поверхность видна и из-под воды
+                cull_mode: None,
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                // Глубина сцены читается шейдером, проход её не пишет
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::Greater, // Reversed-Z
+                stencil: Default::default(),
+                bias: Default::default(),
+            }),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
                 mask: !0,
                 alpha_to_coverage_enabled: false,
             },
@@ -122,6 +271,79 @@ impl Pipelines {
             cache: None,
         });
 
+        let subvoxel = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("SubVoxel Pipeline"),
+            layout: Some(&terrain_layout),
+            vertex: wgpu::VertexState {
+                module: &terrain_shader,
+                entry_point: Some("vs_subvoxel"),
+                buffers: &[PackedVertex::desc(), ChunkOrigin::desc()],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &terrain_shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: Some(wgpu::Face::Back),
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::Greater, // Reversed-Z
+                stencil: Default::default(),
+                bias: Default::default(),
+            }),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        let subvoxel_shadow = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("SubVoxel Shadow Pipeline"),
+            layout: Some(&shadow_pl_layout),
+            vertex: wgpu::VertexState {
+                module: &shadow_shader,
+                entry_point: Some("vs_subvoxel"),
+                buffers: &[PackedVertex::desc(), ChunkOrigin::desc()],
+                compilation_options: Default::default(),
+            },
+            fragment: None,
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: Some(wgpu::Face::Back),
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::Less, // Shadow pass остаётся Less
+                stencil: Default::default(),
+                bias: wgpu::DepthBiasState {
+                    constant: 4,
+                    slope_scale: 2.0,
+                    clamp: 0.0,
+                },
+            }),
+            multisample: Default::default(),
+            multiview: None,
+            cache: None,
+        });
+
         let player = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
             label: Some("Player Pipeline"),
             layout: Some(&player_layout),
@@ -155,7 +377,7 @@ impl Pipelines {
                 bias: Default::default(),
             }),
             multisample: wgpu::MultisampleState {
-                count: 1,
+                count: sample_count,
                 mask: !0,
                 alpha_to_coverage_enabled: false,
             },
@@ -165,7 +387,12 @@ impl Pipelines {
 
         Self {
             terrain,
+            terrain_dither,
+            translucent,
+            water,
             shadow,
+            subvoxel,
+            subvoxel_shadow,
             player,
         }
     }
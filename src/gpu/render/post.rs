@@ -0,0 +1,546 @@
+// ============================================
+// Post Process - HDR кадр и постобработка
+// ============================================
+// 3D сцена рисуется в HDR-текстуру (Rgba16Float), цепочка перед UI:
+// bright-pass -> размытие bloom (половина разрешения) -> композиция
+// в surface: bloom, экспозиция, ACES и виньетка. Яркое (солнце,
+// светящиеся блоки) выходит за 1.0 и даёт ореол. UI и GUI рисуются
+// уже в surface поверх результата; композиция же растягивает кадр
+// при масштабе разрешения != 1. С FXAA композиция идёт в
+// промежуточную LDR-текстуру, и FXAA пишет уже в surface.
+// Глубина резкости (фоторежим): кадр размывается в половинном
+// разрешении, композиция смешивает резкий и размытый кадр по
+// расстоянию из буфера глубины.
+// При MSAA 3D проходы рисуют в мультисэмпловую текстуру (SceneTarget)
+// с resolve в HDR цель.
+
+use serde::{Serialize, Deserialize};
+use wgpu::util::DeviceExt;
+
+use super::antialiasing::AntiAliasing;
+
+/// Формат HDR-цели сцены (все 3D пайплайны рисуют в него)
+pub const HDR_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+
+/// Ступени экспозиции в меню
+const EXPOSURE_LEVELS: [f32; 6] = [0.5, 0.75, 1.0, 1.25, 1.5, 2.0];
+/// Порог яркости bloom и ширина мягкого перехода
+const BLOOM_THRESHOLD: f32 = 0.9;
+const BLOOM_KNEE: f32 = 0.3;
+const BLOOM_STRENGTH: f32 = 0.6;
+const VIGNETTE_STRENGTH: f32 = 0.35;
+/// Резкая зона вокруг фокуса - доля расстояния до него
+const DOF_SHARP_RANGE: f32 = 0.15;
+
+/// Настройки постобработки (секция graphics.post в settings.json)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PostSettings {
+    pub bloom: bool,
+    /// ACES; без него HDR просто обрезается до 1.0
+    pub tonemapping: bool,
+    pub vignette: bool,
+    pub exposure: f32,
+}
+
+impl Default for PostSettings {
+    fn default() -> Self {
+        Self {
+            bloom: true,
+            tonemapping: true,
+            vignette: false,
+            exposure: 1.0,
+        }
+    }
+}
+
+impl PostSettings {
+    /// Следующая ступень экспозиции (по кругу)
+    pub fn next_exposure(self) -> f32 {
+        let index = EXPOSURE_LEVELS
+            .iter()
+            .position(|&e| (e - self.exposure).abs() < 0.01)
+            .unwrap_or(EXPOSURE_LEVELS.len() - 1);
+        EXPOSURE_LEVELS[(index + 1) % EXPOSURE_LEVELS.len()]
+    }
+
+    fn uniform(&self) -> PostUniform {
+        PostUniform {
+            params: [
+                self.exposure.clamp(EXPOSURE_LEVELS[0], EXPOSURE_LEVELS[EXPOSURE_LEVELS.len() - 1]),
+                if self.bloom { BLOOM_STRENGTH } else { 0.0 },
+                if self.tonemapping { 1.0 } else { 0.0 },
+                if self.vignette { VIGNETTE_STRENGTH } else { 0.0 },
+            ],
+            bloom: [BLOOM_THRESHOLD, BLOOM_KNEE, 0.0, 0.0],
+            dof: [0.0; 4],
+            clip: [0.1, 1000.0, 0.0, 0.0],
+        }
+    }
+}
+
+/// Глубина резкости (только фоторежим, в настройки не сохраняется)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DepthOfField {
+    /// Расстояние до плоскости фокуса (блоки)
+    pub focus_distance: f32,
+    /// Сила размытия вне фокуса 0-1
+    pub strength: f32,
+}
+
+impl DepthOfField {
+    fn params(self) -> [f32; 4] {
+        let focus = self.focus_distance.max(0.5);
+        [focus, (focus * DOF_SHARP_RANGE).max(0.5), self.strength.clamp(0.0, 1.0), 0.0]
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct PostUniform {
+    params: [f32; 4],  // экспозиция, сила bloom (0 - выкл), тонмаппинг (0/1), виньетка
+    bloom: [f32; 4],   // порог, мягкость порога, -, -
+    dof: [f32; 4],     // фокус, резкая зона, сила (0 - выкл), -
+    clip: [f32; 4],    // near, far камеры (линейная глубина), -, -
+}
+
+/// Цвет 3D проходов: при MSAA мультисэмпловая текстура с resolve в HDR
+#[derive(Clone, Copy)]
+pub struct SceneTarget<'a> {
+    view: &'a wgpu::TextureView,
+    resolve_target: Option<&'a wgpu::TextureView>,
+}
+
+impl<'a> SceneTarget<'a> {
+    /// Цветовое вложение прохода (содержимое сохраняется для следующих проходов)
+    pub fn attachment(&self, load: wgpu::LoadOp<wgpu::Color>) -> wgpu::RenderPassColorAttachment<'a> {
+        wgpu::RenderPassColorAttachment {
+            view: self.view,
+            resolve_target: self.resolve_target,
+            ops: wgpu::Operations {
+                load,
+                store: wgpu::StoreOp::Store,
+            },
+        }
+    }
+}
+
+/// Цели, зависящие от размера окна
+struct PostTargets {
+    hdr_texture: wgpu::Texture,
+    hdr_view: wgpu::TextureView,
+    /// Мультисэмпловый цвет сцены (только при MSAA)
+    msaa_view: Option<wgpu::TextureView>,
+    /// Кадр после тонмаппинга - вход FXAA
+    ldr_view: wgpu::TextureView,
+    /// Половина разрешения: [0] - bright-pass и итог размытия, [1] - промежуточная
+    bloom_views: [wgpu::TextureView; 2],
+    /// Размытый кадр для глубины резкости (половина разрешения, как bloom)
+    dof_views: [wgpu::TextureView; 2],
+    bright_bind_group: wgpu::BindGroup,
+    blur_h_bind_group: wgpu::BindGroup,
+    blur_v_bind_group: wgpu::BindGroup,
+    dof_down_bind_group: wgpu::BindGroup,
+    dof_h_bind_group: wgpu::BindGroup,
+    dof_v_bind_group: wgpu::BindGroup,
+    composite_bind_group: wgpu::BindGroup,
+    fxaa_bind_group: wgpu::BindGroup,
+}
+
+pub struct PostProcess {
+    settings: PostSettings,
+    depth_of_field: Option<DepthOfField>,
+    /// near и far камеры (для линейной глубины)
+    clip: (f32, f32),
+    /// Выбранное сглаживание (MSAA из него - после перезапуска)
+    anti_aliasing: AntiAliasing,
+    sample_count: u32,
+    layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    uniform_buffer: wgpu::Buffer,
+    bright_pipeline: wgpu::RenderPipeline,
+    blur_h_pipeline: wgpu::RenderPipeline,
+    blur_v_pipeline: wgpu::RenderPipeline,
+    composite_pipeline: wgpu::RenderPipeline,
+    fxaa_pipeline: wgpu::RenderPipeline,
+    targets: PostTargets,
+}
+
+impl PostProcess {
+    pub fn new(
+        device: &wgpu::Device,
+        config: &wgpu::SurfaceConfiguration,
+        scene_size: (u32, u32),
+        depth_view: &wgpu::TextureView,
+        anti_aliasing: AntiAliasing,
+        sample_count: u32,
+    ) -> Self {
+        let settings = PostSettings::default();
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Post UB"),
+            contents: bytemuck::cast_slice(&[settings.uniform()]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let texture_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Post BGL"),
+            entries: &[
+                texture_entry(0),
+                texture_entry(1),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Depth,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                texture_entry(5),
+            ],
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Post Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Post Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/post.wgsl").into()),
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Post PL"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+        let create_pipeline = |entry_point: &str, format: wgpu::TextureFormat| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Post Pipeline"),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_fullscreen"),
+                    buffers: &[],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some(entry_point),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format,
+                        blend: None,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+                cache: None,
+            })
+        };
+        let bright_pipeline = create_pipeline("fs_bright", HDR_FORMAT);
+        let blur_h_pipeline = create_pipeline("fs_blur_h", HDR_FORMAT);
+        let blur_v_pipeline = create_pipeline("fs_blur_v", HDR_FORMAT);
+        let composite_pipeline = create_pipeline("fs_composite", config.format);
+        let fxaa_pipeline = create_pipeline("fs_fxaa", config.format);
+
+        let targets = Self::create_targets(device, &layout, &sampler, &uniform_buffer, config, scene_size, depth_view, sample_count);
+
+        Self {
+            settings,
+            depth_of_field: None,
+            clip: (0.1, 1000.0),
+            anti_aliasing,
+            sample_count,
+            layout,
+            sampler,
+            uniform_buffer,
+            bright_pipeline,
+            blur_h_pipeline,
+            blur_v_pipeline,
+            composite_pipeline,
+            fxaa_pipeline,
+            targets,
+        }
+    }
+
+    /// Пересоздать цели под новый размер окна или масштаб разрешения
+    /// (depth_view - глубина сцены того же размера, без MSAA)
+    pub fn resize(&mut self, device: &wgpu::Device, config: &wgpu::SurfaceConfiguration, scene_size: (u32, u32), depth_view: &wgpu::TextureView) {
+        self.targets = Self::create_targets(device, &self.layout, &self.sampler, &self.uniform_buffer, config, scene_size, depth_view, self.sample_count);
+    }
+
+    pub fn settings(&self) -> PostSettings {
+        self.settings
+    }
+
+    pub fn set_settings(&mut self, queue: &wgpu::Queue, settings: PostSettings) {
+        self.settings = settings;
+        self.write_uniform(queue);
+    }
+
+    /// Глубина резкости (None - выкл)
+    pub fn set_depth_of_field(&mut self, queue: &wgpu::Queue, depth_of_field: Option<DepthOfField>) {
+        if self.depth_of_field != depth_of_field {
+            self.depth_of_field = depth_of_field;
+            self.write_uniform(queue);
+        }
+    }
+
+    /// Плоскости отсечения камеры (раз в кадр, пишется при изменении)
+    pub fn set_clip_planes(&mut self, queue: &wgpu::Queue, near: f32, far: f32) {
+        if self.clip != (near, far) {
+            self.clip = (near, far);
+            self.write_uniform(queue);
+        }
+    }
+
+    fn write_uniform(&self, queue: &wgpu::Queue) {
+        let mut uniform = self.settings.uniform();
+        if let Some(depth_of_field) = self.depth_of_field {
+            uniform.dof = depth_of_field.params();
+        }
+        uniform.clip = [self.clip.0, self.clip.1, 0.0, 0.0];
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniform]));
+    }
+
+    pub fn anti_aliasing(&self) -> AntiAliasing {
+        self.anti_aliasing
+    }
+
+    /// FXAA включается сразу, число сэмплов MSAA задано при создании
+    pub fn set_anti_aliasing(&mut self, anti_aliasing: AntiAliasing) {
+        self.anti_aliasing = anti_aliasing;
+    }
+
+    /// Цель 3D проходов
+    pub fn scene_target(&self) -> SceneTarget<'_> {
+        match &self.targets.msaa_view {
+            Some(msaa_view) => SceneTarget { view: msaa_view, resolve_target: Some(&self.targets.hdr_view) },
+            None => SceneTarget { view: &self.targets.hdr_view, resolve_target: None },
+        }
+    }
+
+    /// Текстура кадра (источник копии для отражений в воде; при MSAA - уже резолвнутая)
+    pub fn hdr_texture(&self) -> &wgpu::Texture {
+        &self.targets.hdr_texture
+    }
+
+    /// Цепочка постобработки: HDR кадр -> surface
+    pub fn render(&self, encoder: &mut wgpu::CommandEncoder, output: &wgpu::TextureView) {
+        let targets = &self.targets;
+        if self.settings.bloom {
+            fullscreen_pass(encoder, "Bloom Bright Pass", &targets.bloom_views[0], &self.bright_pipeline, &targets.bright_bind_group);
+            fullscreen_pass(encoder, "Bloom Blur H Pass", &targets.bloom_views[1], &self.blur_h_pipeline, &targets.blur_h_bind_group);
+            fullscreen_pass(encoder, "Bloom Blur V Pass", &targets.bloom_views[0], &self.blur_v_pipeline, &targets.blur_v_bind_group);
+        }
+        if self.depth_of_field.is_some() {
+            // Два прохода гаусса в половинном разрешении - размытие шире bloom
+            fullscreen_pass(encoder, "DOF Downsample Pass", &targets.dof_views[1], &self.blur_h_pipeline, &targets.dof_down_bind_group);
+            fullscreen_pass(encoder, "DOF Blur V Pass", &targets.dof_views[0], &self.blur_v_pipeline, &targets.dof_v_bind_group);
+            fullscreen_pass(encoder, "DOF Blur H Pass", &targets.dof_views[1], &self.blur_h_pipeline, &targets.dof_h_bind_group);
+            fullscreen_pass(encoder, "DOF Blur V Pass", &targets.dof_views[0], &self.blur_v_pipeline, &targets.dof_v_bind_group);
+        }
+        if self.anti_aliasing.fxaa() {
+            fullscreen_pass(encoder, "Post Composite Pass", &targets.ldr_view, &self.composite_pipeline, &targets.composite_bind_group);
+            fullscreen_pass(encoder, "FXAA Pass", output, &self.fxaa_pipeline, &targets.fxaa_bind_group);
+        } else {
+            fullscreen_pass(encoder, "Post Composite Pass", output, &self.composite_pipeline, &targets.composite_bind_group);
+        }
+    }
+
+    fn create_targets(
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        sampler: &wgpu::Sampler,
+        uniform_buffer: &wgpu::Buffer,
+        config: &wgpu::SurfaceConfiguration,
+        scene_size: (u32, u32),
+        depth_view: &wgpu::TextureView,
+        sample_count: u32,
+    ) -> PostTargets {
+        let create_texture = |label, (width, height): (u32, u32), format, samples, usage| {
+            device.create_texture(&wgpu::TextureDescriptor {
+                label: Some(label),
+                size: wgpu::Extent3d {
+                    width: width.max(1),
+                    height: height.max(1),
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: samples,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage,
+                view_formats: &[],
+            })
+        };
+        let target_usage = wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING;
+
+        // COPY_SRC - копия кадра до воды для отражений
+        // 3D цели - в масштабе разрешения, LDR для FXAA - размера окна
+        let hdr_texture = create_texture("HDR Scene", scene_size, HDR_FORMAT, 1, target_usage | wgpu::TextureUsages::COPY_SRC);
+        let hdr_view = hdr_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let msaa_view = (sample_count > 1).then(|| {
+            create_texture("HDR Scene MSAA", scene_size, HDR_FORMAT, sample_count, wgpu::TextureUsages::RENDER_ATTACHMENT)
+                .create_view(&wgpu::TextureViewDescriptor::default())
+        });
+        let ldr_view = create_texture("Post LDR", (config.width, config.height), config.format, 1, target_usage)
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let half_target = |label| {
+            create_texture(label, (scene_size.0 / 2, scene_size.1 / 2), HDR_FORMAT, 1, target_usage)
+                .create_view(&wgpu::TextureViewDescriptor::default())
+        };
+        let bloom_views = [0, 1].map(|_| half_target("Bloom"));
+        let dof_views = [0, 1].map(|_| half_target("Depth Of Field"));
+
+        let bind_group = |source: &wgpu::TextureView, bloom: &wgpu::TextureView, dof: &wgpu::TextureView| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Post BG"),
+                layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(source),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(bloom),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::Sampler(sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: uniform_buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 4,
+                        resource: wgpu::BindingResource::TextureView(depth_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 5,
+                        resource: wgpu::BindingResource::TextureView(dof),
+                    },
+                ],
+            })
+        };
+        // Bloom, глубина и размытый кадр читаются только композицией; в
+        // остальных проходах подставлены любые, не совпадающие с целью
+        let bright_bind_group = bind_group(&hdr_view, &bloom_views[1], &dof_views[0]);
+        let blur_h_bind_group = bind_group(&bloom_views[0], &hdr_view, &dof_views[0]);
+        let blur_v_bind_group = bind_group(&bloom_views[1], &hdr_view, &dof_views[0]);
+        let dof_down_bind_group = bind_group(&hdr_view, &bloom_views[0], &dof_views[0]);
+        let dof_h_bind_group = bind_group(&dof_views[0], &bloom_views[0], &hdr_view);
+        let dof_v_bind_group = bind_group(&dof_views[1], &bloom_views[0], &hdr_view);
+        let composite_bind_group = bind_group(&hdr_view, &bloom_views[0], &dof_views[0]);
+        let fxaa_bind_group = bind_group(&ldr_view, &hdr_view, &dof_views[0]);
+
+        PostTargets {
+            hdr_texture,
+            hdr_view,
+            msaa_view,
+            ldr_view,
+            bloom_views,
+            dof_views,
+            bright_bind_group,
+            blur_h_bind_group,
+            blur_v_bind_group,
+            dof_down_bind_group,
+            dof_h_bind_group,
+            dof_v_bind_group,
+            composite_bind_group,
+            fxaa_bind_group,
+        }
+    }
+}
+
+/// Полноэкранный треугольник в цель (цель перезаписывается целиком)
+fn fullscreen_pass(
+    encoder: &mut wgpu::CommandEncoder,
+    label: &str,
+    target: &wgpu::TextureView,
+    pipeline: &wgpu::RenderPipeline,
+    bind_group: &wgpu::BindGroup,
+) {
+    let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some(label),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view: target,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                store: wgpu::StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+    pass.set_pipeline(pipeline);
+    pass.set_bind_group(0, bind_group, &[]);
+    pass.draw(0..3, 0..1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exposure_cycles_and_toggles_reach_uniform() {
+        let mut post = PostSettings::default();
+        assert_eq!(post.next_exposure(), 1.25);
+        post.exposure = 2.0;
+        assert_eq!(post.next_exposure(), 0.5);
+
+        post.bloom = false;
+        post.vignette = true;
+        let uniform = post.uniform();
+        assert_eq!(uniform.params[1], 0.0);
+        assert_eq!(uniform.params[3], VIGNETTE_STRENGTH);
+        assert_eq!(uniform.dof[2], 0.0);
+    }
+
+    #[test]
+    fn depth_of_field_keeps_a_sharp_zone() {
+        let params = DepthOfField { focus_distance: 40.0, strength: 2.0 }.params();
+        assert_eq!(params, [40.0, 6.0, 1.0, 0.0]);
+        // Фокус вплотную - резкая зона не схлопывается
+        assert_eq!(DepthOfField { focus_distance: 0.0, strength: 0.5 }.params()[1], 0.5);
+    }
+}
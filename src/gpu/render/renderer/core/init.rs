@@ -3,24 +3,48 @@ use std::sync::Arc;
 use crate::gpu::render::depth::create_depth_texture;
 use crate::gpu::render::bind_groups::{BindGroupLayouts, CoreBindGroups, AtlasResources};
 use crate::gpu::render::shadow::ShadowResources;
+use crate::gpu::render::water::WaterResources;
 use crate::gpu::render::pipelines::Pipelines;
+use crate::gpu::render::instanced::DecorationRenderer;
+use crate::gpu::render::impostor::ImpostorRenderer;
+use crate::gpu::render::palette::PaletteTheme;
+use crate::gpu::render::sampling::SamplingSettings;
+use crate::gpu::render::fog::FogSettings;
+use crate::gpu::render::post::{PostProcess, HDR_FORMAT};
+use crate::gpu::render::resolution::{RenderScale, ResolutionSettings};
+use crate::gpu::render::screenshot::Screenshot;
+use crate::gpu::render::border::WorldBorderRenderer;
+use crate::gpu::render::viewmodel::ViewModelRenderer;
+use crate::gpu::blocks::texture_atlas::ATLAS_LAYERS;
+use crate::gpu::profiler::{GpuTimer, TIMESTAMP_FEATURES};
+use crate::gpu::render::antialiasing::{AntiAliasing, DepthResolve, MSAA_FEATURES, supported_sample_count};
 
 use crate::gpu::player::PlayerModel;
-use crate::gpu::gui::{Crosshair, BlockHighlight};
+use crate::gpu::gui::{Crosshair, BlockHighlight, BreakOverlay, SelectionBox};
 use crate::gpu::terrain::{HybridTerrainManager, GpuChunkManager, SectionTerrainManager};
 use crate::gpu::gui::FpsCounter;
 use crate::gpu::lighting::DayNightCycle;
-use crate::gpu::lighting::CelestialRenderer;
+use crate::gpu::lighting::{CelestialRenderer, LightOverlayRenderer};
+use crate::gpu::weather::WeatherRenderer;
+use crate::gpu::clouds::CloudRenderer;
+use crate::gpu::particles::ParticleRenderer;
+use crate::gpu::render::renderer::culling::{GpuCulling, OcclusionCulling, UndergroundCulling};
+use crate::gpu::render::renderer::lod_budget::LodBudget;
+use crate::gpu::render::renderer::lod_fade::LodFade;
+use crate::gpu::render::renderer::memory_pressure::MemoryWatchdog;
 
 use super::state::{RenderComponents, LightingResources, TerrainResources};
 
 /// Инициализация GPU устройства и surface
-pub async fn init_gpu(window: Arc<winit::window::Window>) -> (
+pub async fn init_gpu(window: Arc<winit::window::Window>, anti_aliasing: AntiAliasing) -> (
     wgpu::Surface<'static>,
     Arc<wgpu::Device>,
     Arc<wgpu::Queue>,
     wgpu::SurfaceConfiguration,
     winit::dpi::PhysicalSize<u32>,
+    wgpu::AdapterInfo,
+    u32,
+    bool,
 ) {
     let size = window.inner_size();
     let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
@@ -38,12 +62,33 @@ pub async fn init_gpu(window: Arc<winit::window::Window>) -> (
         .await
         .unwrap();
 
+    // MSAA 2x требует фичу адаптера (4x доступен везде)
+    let sample_count = supported_sample_count(&adapter, anti_aliasing.sample_count());
+    let mut required_features = if sample_count == 2 { MSAA_FEATURES } else { wgpu::Features::empty() };
+    // GPU отсечение чанков: compute + indirect, точный счётчик - если есть
+    let gpu_culling = GpuCulling::is_supported(&adapter);
+    if gpu_culling {
+        required_features |= GpuCulling::optional_features(&adapter);
+    } else {
+        log::info!("Compute шейдеры или indirect отрисовка недоступны: отсечение чанков на CPU");
+    }
+    // Замеры проходов для профайлера (F4) - если адаптер умеет
+    if adapter.features().contains(TIMESTAMP_FEATURES) {
+        required_features |= TIMESTAMP_FEATURES;
+    } else {
+        log::info!("Timestamp queries недоступны: профайлер покажет только CPU");
+    }
+
     let (device, queue) = adapter
         .request_device(
             &wgpu::DeviceDescriptor {
                 label: Some("GPU Device"),
-                required_features: wgpu::Features::empty(),
-                required_limits: wgpu::Limits::default(),
+                required_features,
+                // Текстуры блоков - массив со слоем на тайл
+                required_limits: wgpu::Limits {
+                    max_texture_array_layers: adapter.limits().max_texture_array_layers.min(ATLAS_LAYERS),
+                    ..wgpu::Limits::default()
+                },
                 memory_hints: Default::default(),
                 trace: wgpu::Trace::Off,
             },
@@ -62,8 +107,10 @@ pub async fn init_gpu(window: Arc<winit::window::Window>) -> (
         .copied()
         .unwrap_or(surface_caps.formats[0]);
 
+    // Отражения в воде копируют HDR кадр; COPY_SRC surface - только для скриншотов
+    let copy_usage = surface_caps.usages & wgpu::TextureUsages::COPY_SRC;
     let config = wgpu::SurfaceConfiguration {
-        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | copy_usage,
         format: surface_format,
         width: size.width,
         height: size.height,
@@ -74,7 +121,7 @@ pub async fn init_gpu(window: Arc<winit::window::Window>) -> (
     };
     surface.configure(&device, &config);
 
-    (surface, device, queue, config, size)
+    (surface, device, queue, config, size, adapter.get_info(), sample_count, gpu_culling)
 }
 
 /// Инициализация всех компонентов рендеринга
@@ -82,8 +129,18 @@ pub fn init_components(
     device: &Arc<wgpu::Device>,
     queue: &Arc<wgpu::Queue>,
     config: &wgpu::SurfaceConfiguration,
+    adapter_info: &wgpu::AdapterInfo,
+    anti_aliasing: AntiAliasing,
+    sample_count: u32,
+    gpu_culling: bool,
 ) -> (RenderComponents, LightingResources, TerrainResources) {
-    let depth_texture = create_depth_texture(device, config);
+    // 3D цели в масштабе разрешения (настройки применятся позже)
+    let render_scale = RenderScale::new(ResolutionSettings::default());
+    let scene_size = render_scale.scene_size(config.width, config.height);
+    let depth_texture = create_depth_texture(device, scene_size, sample_count);
+    // При MSAA вода читает свёрнутую копию глубины
+    let depth_resolve = (sample_count > 1).then(|| DepthResolve::new(device, scene_size, &depth_texture));
+    let water_depth = depth_resolve.as_ref().map_or(&depth_texture, DepthResolve::view);
 
     // Bind group layouts
     let layouts = BindGroupLayouts::new(device);
@@ -92,31 +149,50 @@ pub fn init_components(
     // Core bind groups
     let core_bind_groups = CoreBindGroups::new(device, &layouts);
 
-    // Atlas resources (текстурный атлас для кастомных блоков)
-    let atlas = AtlasResources::new(device, queue, &layouts.atlas);
+    // Настройки фильтрации текстур (меняются из меню графики)
+    let sampling = SamplingSettings::default();
+
+    // Atlas resources (текстурный атлас блоков)
+    let atlas = AtlasResources::new(device, queue, &layouts.atlas, &sampling);
 
     // Shadow resources
-    let shadow = ShadowResources::new(device, &layouts.shadow, &layouts.shadow_pass);
+    let shadow = ShadowResources::new(device, &layouts.shadow, &layouts.shadow_pass, &sampling);
+
+    // Water resources (копия кадра для отражений)
+    let water = WaterResources::new(device, &layouts.water, scene_size, water_depth);
+
+    // HDR цель сцены и постобработка (3D пайплайны рисуют в HDR_FORMAT)
+    let post = PostProcess::new(device, config, scene_size, water_depth, anti_aliasing, sample_count);
 
     // Pipelines
-    let pipelines = Pipelines::new(device, config.format, &layouts, &model_layout);
+    let pipelines = Pipelines::new(device, HDR_FORMAT, sample_count, &layouts, &model_layout);
 
-    // Terrain
-    let mut gpu_chunks = GpuChunkManager::new(Arc::clone(device));
-    let mut terrain_manager = HybridTerrainManager::new();
-    let initial_mesh = terrain_manager.generate_initial(0.0, 0.0);
+    // Terrain (начальную область вокруг игрока загружает экран загрузки мира)
+    let gpu_chunks = GpuChunkManager::new(Arc::clone(device), Arc::clone(queue));
+    let terrain_manager = HybridTerrainManager::new();
     let section_manager = SectionTerrainManager::new();
 
-    for chunk_data in &initial_mesh.new_chunks {
-        gpu_chunks.upload(chunk_data.key, &chunk_data.vertices, &chunk_data.indices);
-    }
+    let impostors = ImpostorRenderer::new(Arc::clone(device), queue, HDR_FORMAT, sample_count, &layouts);
 
     // Other components
     let player_model = PlayerModel::new(device, &model_layout);
     let crosshair = Crosshair::new(device, config.format);
-    let block_highlight = BlockHighlight::new(device, config.format);
+    let block_highlight = BlockHighlight::new(device, HDR_FORMAT, sample_count);
+    let selection_box = SelectionBox::new(device, HDR_FORMAT, sample_count);
+    let break_overlay = BreakOverlay::new(device, HDR_FORMAT, sample_count);
     let fps_counter = FpsCounter::new(device, Arc::clone(queue), config.format);
-    let celestial = CelestialRenderer::new(device, config.format);
+    let celestial = CelestialRenderer::new(device, HDR_FORMAT, sample_count);
+    let weather = WeatherRenderer::new(device, HDR_FORMAT, sample_count);
+    let clouds = CloudRenderer::new(device, HDR_FORMAT, sample_count);
+    let world_border = WorldBorderRenderer::new(device, HDR_FORMAT, sample_count);
+    let view_model = ViewModelRenderer::new(device, HDR_FORMAT, sample_count);
+    let decorations = DecorationRenderer::new(Arc::clone(device), HDR_FORMAT, sample_count, &layouts);
+    let particles = ParticleRenderer::new(Arc::clone(device), HDR_FORMAT, sample_count, &layouts);
+    let light_overlay = LightOverlayRenderer::new(Arc::clone(device), HDR_FORMAT, sample_count, &layouts);
+    let occlusion = OcclusionCulling::new(Arc::clone(device), HDR_FORMAT, sample_count);
+    let gpu_culling = gpu_culling.then(|| GpuCulling::new(Arc::clone(device), Arc::clone(queue)));
+    let screenshot = Screenshot::new(config.usage.contains(wgpu::TextureUsages::COPY_SRC));
+    let gpu_timer = GpuTimer::new(device, queue);
 
     let mut day_night = DayNightCycle::new();
     day_night.set_time(0.35);
@@ -126,24 +202,53 @@ pub fn init_components(
         pipelines,
         gpu_chunks,
         player_model,
+        view_model,
         crosshair,
         block_highlight,
+        selection_box,
+        break_overlay,
         fps_counter,
         celestial,
+        decorations,
+        impostors,
+        weather,
+        clouds,
+        world_border,
+        particles,
+        light_overlay,
+        occlusion,
+        gpu_culling,
+        screenshot,
+        gpu_timer,
     };
 
     let lighting = LightingResources {
         core_bind_groups,
         shadow,
         day_night,
+        palette_theme: PaletteTheme::default(),
+        sampling,
+        fog: FogSettings::default(),
         layouts,
         atlas,
     };
 
+    let lod_distances = terrain_manager.get_lod_distances();
     let terrain = TerrainResources {
         depth_texture,
+        depth_resolve,
+        water,
+        post,
+        render_scale,
         terrain_manager,
+        lod_budget: LodBudget::from_adapter(adapter_info),
+        lod_distances,
+        memory: MemoryWatchdog::from_adapter(adapter_info),
+        underground: UndergroundCulling::new(),
+        lod_fade: LodFade::new(device),
         section_manager,
+        chunks_received: 0,
+        loaded_columns: Vec::new(),
     };
 
     (components, lighting, terrain)
@@ -1,17 +1,36 @@
 use std::sync::Arc;
 use ultraviolet::{Mat4, Vec3};
 
-use crate::gpu::render::uniforms::Uniforms;
 use crate::gpu::render::shadow::ShadowResources;
+use crate::gpu::render::water::WaterResources;
 use crate::gpu::render::pipelines::Pipelines;
 use crate::gpu::render::bind_groups::{CoreBindGroups, AtlasResources};
+use crate::gpu::render::instanced::DecorationRenderer;
+use crate::gpu::render::impostor::ImpostorRenderer;
+use crate::gpu::render::palette::PaletteTheme;
+use crate::gpu::render::sampling::SamplingSettings;
+use crate::gpu::render::fog::FogSettings;
+use crate::gpu::render::post::PostProcess;
+use crate::gpu::render::antialiasing::DepthResolve;
+use crate::gpu::render::resolution::RenderScale;
+use crate::gpu::render::screenshot::Screenshot;
+use crate::gpu::profiler::GpuTimer;
+use crate::gpu::render::border::WorldBorderRenderer;
+use crate::gpu::render::viewmodel::ViewModelRenderer;
 
-use crate::gpu::player::PlayerModel;
-use crate::gpu::gui::{Crosshair, BlockHighlight};
+use crate::gpu::player::{Camera, PlayerModel};
+use crate::gpu::gui::{Crosshair, BlockHighlight, BreakOverlay, SelectionBox};
 use crate::gpu::terrain::{HybridTerrainManager, GpuChunkManager, SectionTerrainManager};
 use crate::gpu::gui::FpsCounter;
 use crate::gpu::lighting::DayNightCycle;
-use crate::gpu::lighting::CelestialRenderer;
+use crate::gpu::lighting::{CelestialRenderer, LightOverlayRenderer};
+use crate::gpu::weather::WeatherRenderer;
+use crate::gpu::clouds::CloudRenderer;
+use crate::gpu::particles::ParticleRenderer;
+use crate::gpu::render::renderer::culling::{GpuCulling, OcclusionCulling, UndergroundCulling};
+use crate::gpu::render::renderer::lod_budget::LodBudget;
+use crate::gpu::render::renderer::lod_fade::LodFade;
+use crate::gpu::render::renderer::memory_pressure::MemoryWatchdog;
 
 /// Основное состояние рендерера (GPU ресурсы)
 pub struct RendererState {
@@ -20,6 +39,8 @@ pub struct RendererState {
     pub queue: Arc<wgpu::Queue>,
     pub config: wgpu::SurfaceConfiguration,
     pub size: winit::dpi::PhysicalSize<u32>,
+    /// Сэмплов MSAA в 3D проходах (задаётся при создании)
+    pub sample_count: u32,
 }
 
 /// Компоненты рендеринга
@@ -27,10 +48,30 @@ pub struct RenderComponents {
     pub pipelines: Pipelines,
     pub gpu_chunks: GpuChunkManager,
     pub player_model: PlayerModel,
+    /// Рука и блок в руке от первого лица
+    pub view_model: ViewModelRenderer,
     pub crosshair: Crosshair,
     pub block_highlight: BlockHighlight,
+    /// Рамка выделения палочкой
+    pub selection_box: SelectionBox,
+    pub break_overlay: BreakOverlay,
     pub fps_counter: FpsCounter,
     pub celestial: CelestialRenderer,
+    pub decorations: DecorationRenderer,
+    pub impostors: ImpostorRenderer,
+    pub weather: WeatherRenderer,
+    pub clouds: CloudRenderer,
+    /// Стена границы мира
+    pub world_border: WorldBorderRenderer,
+    pub particles: ParticleRenderer,
+    pub light_overlay: LightOverlayRenderer,
+    pub occlusion: OcclusionCulling,
+    /// Отсечение и indirect отрисовка terrain на GPU (None - нет compute)
+    pub gpu_culling: Option<GpuCulling>,
+    /// Снимок кадра по F2
+    pub screenshot: Screenshot,
+    /// Замеры проходов для профайлера (None - нет timestamp queries)
+    pub gpu_timer: Option<GpuTimer>,
 }
 
 /// Ресурсы освещения и теней
@@ -38,6 +79,10 @@ pub struct LightingResources {
     pub core_bind_groups: CoreBindGroups,
     pub shadow: ShadowResources,
     pub day_night: DayNightCycle,
+    pub palette_theme: PaletteTheme,
+    pub sampling: SamplingSettings,
+    /// Плотность и начало дальнего тумана
+    pub fog: FogSettings,
     pub layouts: crate::gpu::render::bind_groups::BindGroupLayouts,
     pub atlas: AtlasResources,
 }
@@ -45,35 +90,77 @@ pub struct LightingResources {
 /// Ресурсы террейна
 pub struct TerrainResources {
     pub depth_texture: wgpu::TextureView,
+    /// Обычная копия мультисэмпловой глубины для воды (только при MSAA)
+    pub depth_resolve: Option<DepthResolve>,
+    /// Копия кадра и параметры прохода воды (зависят от размера окна)
+    pub water: WaterResources,
+    /// HDR цель сцены и постобработка (зависят от размера окна)
+    pub post: PostProcess,
+    /// Масштаб 3D кадра (статический или по FPS)
+    pub render_scale: RenderScale,
     pub terrain_manager: HybridTerrainManager,
-    #[allow(dead_code)]
+    /// Потолок дистанций LOD для текущей видеокарты
+    pub lod_budget: LodBudget,
+    /// Дистанции LOD из настроек (до снижения из-за видеопамяти)
+    pub lod_distances: [i32; 4],
+    /// Снижение дальности при нехватке видеопамяти
+    pub memory: MemoryWatchdog,
+    /// Потолок пещеры над камерой (отсечение поверхности под землёй)
+    pub underground: UndergroundCulling,
+    /// Плавная смена LOD (старые меши доживают до замены)
+    pub lod_fade: LodFade,
+    /// Вертикальная дальность: секции для мешинга и прорисовки
     pub section_manager: SectionTerrainManager,
+    /// Чанков получено от генератора за сессию (benchmark)
+    pub chunks_received: u64,
+    /// Столбцы с новыми воксельными мешами (забирает миникарта)
+    pub loaded_columns: Vec<(i32, i32)>,
 }
 
-/// Кэшированные данные камеры
+/// Кэшированные данные камеры (матрицы и параметры проекции текущего кадра)
 pub struct CachedCamera {
     pub view_proj: [[f32; 4]; 4],
     pub view: Mat4,
     pub proj: Mat4,
     pub position: Vec3,
+    /// Параметры проекции, из которых собрана proj (FOV меняется динамически)
+    pub fov: f32,
+    pub aspect: f32,
+    pub near: f32,
+    pub far: f32,
+    /// Камера под водой (цвет очистки - толща воды)
+    pub underwater: bool,
 }
 
 impl Default for CachedCamera {
     fn default() -> Self {
-        Self {
+        let mut cached = Self {
             view_proj: Mat4::identity().into(),
             view: Mat4::identity(),
             proj: Mat4::identity(),
             position: Vec3::zero(),
-        }
+            fov: 0.0,
+            aspect: 1.0,
+            near: 0.0,
+            far: 0.0,
+            underwater: false,
+        };
+        cached.update(&Camera::new(16.0 / 9.0));
+        cached
     }
 }
 
 impl CachedCamera {
-    pub fn update(&mut self, uniforms: &Uniforms, view: Mat4, proj: Mat4, position: Vec3) {
-        self.view_proj = uniforms.view_proj;
-        self.view = view;
-        self.proj = proj;
-        self.position = position;
+    /// Снять матрицы с камеры (один раз за кадр)
+    pub fn update(&mut self, camera: &Camera) {
+        self.view = camera.view_matrix();
+        self.proj = camera.projection_matrix();
+        self.view_proj = (self.proj * self.view).into();
+        self.position = camera.position;
+        self.fov = camera.fov;
+        self.aspect = camera.aspect;
+        self.near = camera.near;
+        self.far = camera.far;
+        self.underwater = camera.underwater;
     }
 }
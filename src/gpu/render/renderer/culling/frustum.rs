@@ -1,8 +1,7 @@
 use ultraviolet::Vec3;
 
-const CHUNK_SIZE: i32 = 16;
-const MIN_Y: f32 = -64.0;
-const MAX_Y: f32 = 320.0;
+use crate::gpu::terrain::gpu::GpuChunk;
+use crate::gpu::terrain::voxel::constants::{CHUNK_SIZE, MIN_HEIGHT, WORLD_HEIGHT};
 
 /// Извлекает 6 плоскостей frustum из view-projection матрицы
 /// Каждая плоскость: (nx, ny, nz, d) где nx*x + ny*y + nz*z + d >= 0 означает "внутри"
@@ -33,14 +32,29 @@ fn is_aabb_outside_plane(plane: &[f32; 4], min: Vec3, max: Vec3) -> bool {
     plane[0] * px + plane[1] * py + plane[2] * pz + plane[3] < 0.0
 }
 
-/// Frustum culling: проверяет видимость AABB чанка
+/// Frustum culling: проверяет видимость столба чанка на всю высоту мира
 pub fn is_chunk_visible(view_proj: &[[f32; 4]; 4], chunk_x: i32, chunk_z: i32, scale: i32) -> bool {
+    is_column_visible(view_proj, chunk_x, chunk_z, scale, MIN_HEIGHT as f32, WORLD_HEIGHT as f32)
+}
+
+/// То же по высоте, занятой мешем чанка (AABB вершин): пустое небо над
+/// рельефом и пустоты под ним не держат чанк видимым
+pub fn is_gpu_chunk_visible(view_proj: &[[f32; 4]; 4], chunk: &GpuChunk) -> bool {
+    let (min_y, max_y) = if chunk.min[1] <= chunk.max[1] {
+        (chunk.min[1], chunk.max[1])
+    } else {
+        (MIN_HEIGHT as f32, WORLD_HEIGHT as f32)
+    };
+    is_column_visible(view_proj, chunk.key.x, chunk.key.z, chunk.key.scale, min_y, max_y)
+}
+
+fn is_column_visible(view_proj: &[[f32; 4]; 4], chunk_x: i32, chunk_z: i32, scale: i32, min_y: f32, max_y: f32) -> bool {
     let size = (CHUNK_SIZE * scale.max(1)) as f32;
     let min_x = (chunk_x * CHUNK_SIZE) as f32;
     let min_z = (chunk_z * CHUNK_SIZE) as f32;
     
-    let min = Vec3::new(min_x, MIN_Y, min_z);
-    let max = Vec3::new(min_x + size, MAX_Y, min_z + size);
+    let min = Vec3::new(min_x, min_y, min_z);
+    let max = Vec3::new(min_x + size, max_y, min_z + size);
     
     let planes = extract_frustum_planes(view_proj);
     
@@ -51,3 +65,30 @@ pub fn is_chunk_visible(view_proj: &[[f32; 4]; 4], chunk_x: i32, chunk_z: i32, s
     }
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::player::Camera;
+
+    fn view_proj(position: Vec3, forward: Vec3) -> [[f32; 4]; 4] {
+        let mut camera = Camera::new(16.0 / 9.0);
+        camera.set_look(position, forward);
+        camera.view_projection_matrix().into()
+    }
+
+    #[test]
+    fn chunks_high_above_old_limit_stay_visible() {
+        // Камера на y = 900 смотрит вдоль +Z на чанк перед собой
+        let vp = view_proj(Vec3::new(8.0, 900.0, -24.0), Vec3::unit_z());
+        assert!(is_chunk_visible(&vp, 0, 0, 1));
+        assert!(is_column_visible(&vp, 0, 0, 1, 896.0, 904.0));
+        // Чанк за спиной отсекается
+        assert!(!is_chunk_visible(&vp, 0, -4, 1));
+
+        // Глубоко внизу тоже
+        let vp = view_proj(Vec3::new(8.0, -480.0, -24.0), Vec3::unit_z());
+        assert!(is_column_visible(&vp, 0, 0, 1, -484.0, -476.0));
+        assert!(!is_column_visible(&vp, 0, 0, 1, 60.0, 80.0));
+    }
+}
@@ -0,0 +1,422 @@
+// ============================================
+// GPU Culling - Отсечение чанков compute шейдером
+// ============================================
+// Каждая непрозрачная секция чанка - запись с AABB и параметрами
+// отрисовки. Compute проход (cull.wgsl) проверяет записи (frustum,
+// вертикальная дальность, LOD под землёй, occlusion) и складывает
+// выжившие в indirect буфер, после чего основной проход рисует их
+// multi_draw_indexed_indirect на страницу пула, без цикла по чанкам.
+//
+// Записи сгруппированы по парам страниц (вершины, индексы): у группы
+// свой диапазон в indirect буфере и свой счётчик. С фичей
+// MULTI_DRAW_INDIRECT_COUNT рисуется ровно число выживших, без неё -
+// весь диапазон группы (обнулённые слоты ничего не рисуют).
+//
+// Записи пересобираются только при смене набора чанков или закрытых
+// рельефом чанков; каждый кадр обновляются лишь плоскости frustum.
+// Чанки в переходе между LOD (lod_fade) в записи не попадают.
+// Теневой проход по-прежнему отсекает чанки на CPU (свой frustum на
+// каскад). Без compute шейдеров или indirect отрисовки работает
+// старый CPU цикл в основном проходе.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::util::DrawIndexedIndirectArgs;
+
+use crate::gpu::terrain::GpuChunkManager;
+use crate::gpu::render::renderer::lod_fade::LodFade;
+
+use super::frustum::extract_frustum_planes;
+use super::OcclusionCulling;
+
+/// Потоков в рабочей группе (как в cull.wgsl)
+const WORKGROUP_SIZE: u32 = 64;
+/// Начальная ёмкость буферов записей (растёт степенями двойки)
+const INITIAL_RECORDS: usize = 4096;
+const INITIAL_GROUPS: usize = 16;
+
+const FLAG_VOXEL: u32 = 1;
+const FLAG_OCCLUDED: u32 = 2;
+const GROUP_SHIFT: u32 = 8;
+
+const DRAW_ARGS_SIZE: u64 = std::mem::size_of::<DrawIndexedIndirectArgs>() as u64;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Pod, Zeroable)]
+struct DrawRecord {
+    aabb_min: [f32; 4],
+    aabb_max: [f32; 4],
+    index_count: u32,
+    first_index: u32,
+    base_vertex: i32,
+    /// Бит 0 - воксельный чанк, бит 1 - закрыт рельефом, с бита 8 - группа
+    flags: u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct CullUniforms {
+    planes: [[f32; 4]; 6],
+    /// min_y, max_y, вертикальная дальность (0/1), под землёй (0/1)
+    params: [f32; 4],
+    /// Число записей, остальное - выравнивание
+    counts: [u32; 4],
+}
+
+/// Записи одной пары страниц: слоты start..start + len indirect буфера
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct DrawGroup {
+    pages: (usize, usize),
+    start: u32,
+    len: u32,
+}
+
+/// Параметры отсечения кадра
+pub struct CullParams {
+    pub view_proj: [[f32; 4]; 4],
+    pub vertical_range: Option<(f32, f32)>,
+    pub underground: bool,
+}
+
+/// Буферы, размер которых зависит от числа записей и групп
+struct CullBuffers {
+    records: wgpu::Buffer,
+    group_starts: wgpu::Buffer,
+    draws: wgpu::Buffer,
+    counts: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+    record_capacity: usize,
+    group_capacity: usize,
+}
+
+/// GPU отсечение и indirect отрисовка непрозрачного terrain
+pub struct GpuCulling {
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    pipeline: wgpu::ComputePipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    uniform_buffer: wgpu::Buffer,
+    buffers: CullBuffers,
+    groups: Vec<DrawGroup>,
+    record_count: u32,
+    /// Поколения чанков, occlusion и переходов LOD, для которых собраны записи
+    built_for: Option<(u64, u64, u64)>,
+    /// Фича MULTI_DRAW_INDIRECT_COUNT включена на устройстве
+    indirect_count: bool,
+}
+
+impl GpuCulling {
+    /// Адаптер умеет compute шейдеры и indirect отрисовку
+    pub fn is_supported(adapter: &wgpu::Adapter) -> bool {
+        let flags = adapter.get_downlevel_capabilities().flags;
+        flags.contains(wgpu::DownlevelFlags::COMPUTE_SHADERS | wgpu::DownlevelFlags::INDIRECT_EXECUTION)
+    }
+
+    /// Фичи, которые стоит запросить у адаптера (точный счётчик отрисовок)
+    pub fn optional_features(adapter: &wgpu::Adapter) -> wgpu::Features {
+        adapter.features() & wgpu::Features::MULTI_DRAW_INDIRECT_COUNT
+    }
+
+    pub fn new(device: Arc<wgpu::Device>, queue: Arc<wgpu::Queue>) -> Self {
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Cull Uniform Buffer"),
+            size: std::mem::size_of::<CullUniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let storage = |binding: u32, read_only: bool| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Cull Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                storage(1, true),
+                storage(2, true),
+                storage(3, false),
+                storage(4, false),
+            ],
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Cull Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../../../shaders/cull.wgsl").into()),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Cull Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Cull Pipeline"),
+            layout: Some(&pipeline_layout),
+            module: &shader,
+            entry_point: Some("cs_main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        let buffers = CullBuffers::new(&device, &bind_group_layout, &uniform_buffer, INITIAL_RECORDS, INITIAL_GROUPS);
+        let indirect_count = device.features().contains(wgpu::Features::MULTI_DRAW_INDIRECT_COUNT);
+
+        Self {
+            device,
+            queue,
+            pipeline,
+            bind_group_layout,
+            uniform_buffer,
+            buffers,
+            groups: Vec::new(),
+            record_count: 0,
+            built_for: None,
+            indirect_count,
+        }
+    }
+
+    /// Отсечь записи на GPU (до основного прохода в том же encoder)
+    pub fn prepare(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        gpu_chunks: &GpuChunkManager,
+        occlusion: &OcclusionCulling,
+        lod_fade: &LodFade,
+        params: &CullParams,
+    ) {
+        let generations = (gpu_chunks.generation(), occlusion.generation(), lod_fade.generation());
+        if self.built_for != Some(generations) {
+            self.rebuild(gpu_chunks, occlusion, lod_fade);
+            self.built_for = Some(generations);
+        }
+
+        let (min_y, max_y) = params.vertical_range.unwrap_or((0.0, 0.0));
+        let uniforms = CullUniforms {
+            planes: extract_frustum_planes(&params.view_proj),
+            params: [
+                min_y,
+                max_y,
+                if params.vertical_range.is_some() { 1.0 } else { 0.0 },
+                if params.underground { 1.0 } else { 0.0 },
+            ],
+            counts: [self.record_count, 0, 0, 0],
+        };
+        self.queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+
+        // Слоты прошлого кадра обнуляются: лишние слоты группы ничего не рисуют
+        encoder.clear_buffer(&self.buffers.draws, 0, None);
+        encoder.clear_buffer(&self.buffers.counts, 0, None);
+        if self.record_count == 0 {
+            return;
+        }
+
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("Cull Pass"),
+            timestamp_writes: None,
+        });
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, &self.buffers.bind_group, &[]);
+        pass.dispatch_workgroups(self.record_count.div_ceil(WORKGROUP_SIZE), 1, 1);
+    }
+
+    /// Нарисовать выжившие записи (pipeline и bind groups terrain уже выставлены)
+    pub fn draw(&self, render_pass: &mut wgpu::RenderPass, gpu_chunks: &GpuChunkManager) {
+        for (index, group) in self.groups.iter().enumerate() {
+            let Some((vertices, indices)) = gpu_chunks.page_buffers(group.pages) else { continue };
+            render_pass.set_vertex_buffer(0, vertices.slice(..));
+            render_pass.set_index_buffer(indices.slice(..), wgpu::IndexFormat::Uint32);
+            let offset = group.start as u64 * DRAW_ARGS_SIZE;
+            if self.indirect_count {
+                render_pass.multi_draw_indexed_indirect_count(
+                    &self.buffers.draws,
+                    offset,
+                    &self.buffers.counts,
+                    (index * std::mem::size_of::<u32>()) as u64,
+                    group.len,
+                );
+            } else {
+                render_pass.multi_draw_indexed_indirect(&self.buffers.draws, offset, group.len);
+            }
+        }
+    }
+
+    /// Пересобрать записи секций и группы страниц
+    fn rebuild(&mut self, gpu_chunks: &GpuChunkManager, occlusion: &OcclusionCulling, lod_fade: &LodFade) {
+        let mut entries = Vec::new();
+        // Чанки в переходе между LOD рисуются дизерингом отдельно
+        for chunk in gpu_chunks.iter().filter(|chunk| !lod_fade.is_fading(&chunk.key)) {
+            let mut flags = 0;
+            if chunk.key.is_voxel() {
+                flags |= FLAG_VOXEL;
+            }
+            if occlusion.is_occluded(&chunk.key) {
+                flags |= FLAG_OCCLUDED;
+            }
+            for section in &chunk.sections {
+                let record = DrawRecord {
+                    aabb_min: [chunk.min[0], section.min_y, chunk.min[2], 0.0],
+                    aabb_max: [chunk.max[0], section.max_y, chunk.max[2], 0.0],
+                    index_count: section.count,
+                    first_index: chunk.first_index() + section.start,
+                    base_vertex: chunk.base_vertex(),
+                    flags,
+                };
+                entries.push((chunk.pages(), record));
+            }
+        }
+        let (records, groups) = group_records(entries);
+
+        if records.len() > self.buffers.record_capacity || groups.len() > self.buffers.group_capacity {
+            let record_capacity = records.len().next_power_of_two().max(self.buffers.record_capacity);
+            let group_capacity = groups.len().next_power_of_two().max(self.buffers.group_capacity);
+            self.buffers = CullBuffers::new(
+                &self.device,
+                &self.bind_group_layout,
+                &self.uniform_buffer,
+                record_capacity,
+                group_capacity,
+            );
+        }
+
+        if !records.is_empty() {
+            let starts: Vec<u32> = groups.iter().map(|group| group.start).collect();
+            self.queue.write_buffer(&self.buffers.records, 0, bytemuck::cast_slice(&records));
+            self.queue.write_buffer(&self.buffers.group_starts, 0, bytemuck::cast_slice(&starts));
+        }
+        self.record_count = records.len() as u32;
+        self.groups = groups;
+    }
+}
+
+impl CullBuffers {
+    fn new(
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        uniform_buffer: &wgpu::Buffer,
+        record_capacity: usize,
+        group_capacity: usize,
+    ) -> Self {
+        let buffer = |label: &str, size: u64, usage: wgpu::BufferUsages| {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size,
+                usage: usage | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            })
+        };
+        let records = buffer(
+            "Cull Record Buffer",
+            (record_capacity * std::mem::size_of::<DrawRecord>()) as u64,
+            wgpu::BufferUsages::STORAGE,
+        );
+        let group_starts = buffer(
+            "Cull Group Start Buffer",
+            (group_capacity * std::mem::size_of::<u32>()) as u64,
+            wgpu::BufferUsages::STORAGE,
+        );
+        let draws = buffer(
+            "Cull Draw Buffer",
+            record_capacity as u64 * DRAW_ARGS_SIZE,
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::INDIRECT,
+        );
+        let counts = buffer(
+            "Cull Count Buffer",
+            (group_capacity * std::mem::size_of::<u32>()) as u64,
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::INDIRECT,
+        );
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Cull Bind Group"),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: uniform_buffer.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 1, resource: records.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 2, resource: group_starts.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 3, resource: draws.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 4, resource: counts.as_entire_binding() },
+            ],
+        });
+
+        Self { records, group_starts, draws, counts, bind_group, record_capacity, group_capacity }
+    }
+}
+
+/// Упорядочить записи по парам страниц и проставить номер группы в flags
+fn group_records(entries: Vec<((usize, usize), DrawRecord)>) -> (Vec<DrawRecord>, Vec<DrawGroup>) {
+    let mut by_pages: BTreeMap<(usize, usize), Vec<DrawRecord>> = BTreeMap::new();
+    for (pages, record) in entries {
+        by_pages.entry(pages).or_default().push(record);
+    }
+
+    let mut records = Vec::new();
+    let mut groups = Vec::with_capacity(by_pages.len());
+    for (index, (pages, group_records)) in by_pages.into_iter().enumerate() {
+        groups.push(DrawGroup { pages, start: records.len() as u32, len: group_records.len() as u32 });
+        let group_bits = (index as u32) << GROUP_SHIFT;
+        records.extend(group_records.into_iter().map(|record| DrawRecord {
+            flags: record.flags | group_bits,
+            ..record
+        }));
+    }
+    (records, groups)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(first_index: u32, flags: u32) -> DrawRecord {
+        DrawRecord { index_count: 6, first_index, flags, ..Default::default() }
+    }
+
+    #[test]
+    fn records_are_grouped_by_page_pair() {
+        let entries = vec![
+            ((1, 0), record(0, FLAG_VOXEL)),
+            ((0, 0), record(10, 0)),
+            ((1, 0), record(20, FLAG_OCCLUDED)),
+            ((0, 1), record(30, FLAG_VOXEL)),
+        ];
+        let (records, groups) = group_records(entries);
+
+        assert_eq!(
+            groups,
+            vec![
+                DrawGroup { pages: (0, 0), start: 0, len: 1 },
+                DrawGroup { pages: (0, 1), start: 1, len: 1 },
+                DrawGroup { pages: (1, 0), start: 2, len: 2 },
+            ]
+        );
+        let firsts: Vec<u32> = records.iter().map(|r| r.first_index).collect();
+        assert_eq!(firsts, vec![10, 30, 0, 20]);
+        // Номер группы в старших битах, флаги сохранены
+        assert_eq!(records[1].flags, FLAG_VOXEL | (1 << GROUP_SHIFT));
+        assert_eq!(records[3].flags, FLAG_OCCLUDED | (2 << GROUP_SHIFT));
+    }
+
+    #[test]
+    fn gpu_layouts_match_shader() {
+        assert_eq!(std::mem::size_of::<DrawRecord>(), 48);
+        assert_eq!(std::mem::size_of::<CullUniforms>(), 128);
+        assert_eq!(DRAW_ARGS_SIZE, 20);
+    }
+}
@@ -1,3 +1,9 @@
 mod frustum;
+mod gpu_culling;
+mod occlusion;
+mod underground;
 
-pub use frustum::is_chunk_visible;
+pub use frustum::{is_chunk_visible, is_gpu_chunk_visible};
+pub use gpu_culling::{CullParams, GpuCulling};
+pub use occlusion::OcclusionCulling;
+pub use underground::UndergroundCulling;
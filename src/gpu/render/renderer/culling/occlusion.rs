@@ -0,0 +1,405 @@
+// ============================================
+// Occlusion Culling - Отсечение чанков, закрытых рельефом
+// ============================================
+// После непрозрачного terrain в том же проходе рисуются AABB чанков
+// (без записи цвета и глубины), каждый внутри своего occlusion query.
+// Результаты читаются с GPU асинхронно, через кадр-другой: чанк, у
+// которого ни один фрагмент прокси не прошёл тест глубины, в следующих
+// кадрах не рисуется. Его прокси продолжает проверяться, так что когда
+// чанк снова выглянет из-за горы, он вернётся с задержкой в пару кадров.
+//
+// Чанки рядом с камерой не проверяются: на них задержка была бы заметна.
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+
+use bytemuck::{Pod, Zeroable};
+use ultraviolet::Vec3;
+use wgpu::util::DeviceExt;
+
+use crate::gpu::terrain::{ChunkKey, GpuChunkManager};
+
+use super::frustum::is_gpu_chunk_visible;
+
+/// Максимум запросов за кадр (остальные чанки просто рисуются)
+const MAX_QUERIES: usize = 4096;
+/// Вершин на прокси (12 треугольников)
+const BOX_VERTICES: usize = 36;
+/// Ближе этого расстояния до AABB чанк не отсекается (блоки)
+const MIN_OCCLUSION_DISTANCE: f32 = 32.0;
+/// Запас вокруг AABB, чтобы прокси не прятался за собственной геометрией
+const PROXY_MARGIN: f32 = 1.0;
+
+/// Состояния чтения результатов
+const MAP_PENDING: u8 = 0;
+const MAP_READY: u8 = 1;
+const MAP_FAILED: u8 = 2;
+
+const PROXY_ATTRIBUTES: [wgpu::VertexAttribute; 1] = wgpu::vertex_attr_array![0 => Float32x3];
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct OcclusionUniforms {
+    view_proj: [[f32; 4]; 4],
+}
+
+/// Запросы, отправленные на GPU и ждущие чтения
+struct PendingReadback {
+    keys: Vec<ChunkKey>,
+    state: Arc<AtomicU8>,
+}
+
+/// Occlusion culling чанков через occlusion queries
+pub struct OcclusionCulling {
+    device: Arc<wgpu::Device>,
+    pipeline: wgpu::RenderPipeline,
+    uniform_buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+    vertex_buffer: wgpu::Buffer,
+    query_set: wgpu::QuerySet,
+    resolve_buffer: wgpu::Buffer,
+    readback_buffer: wgpu::Buffer,
+    /// Чанки, проверяемые в текущем кадре (индекс = номер запроса)
+    queried: Vec<ChunkKey>,
+    pending: Option<PendingReadback>,
+    occluded: HashSet<ChunkKey>,
+    /// Растёт при смене набора закрытых чанков
+    generation: u64,
+}
+
+impl OcclusionCulling {
+    pub fn new(device: Arc<wgpu::Device>, surface_format: wgpu::TextureFormat, sample_count: u32) -> Self {
+        let uniforms = OcclusionUniforms {
+            view_proj: ultraviolet::Mat4::identity().into(),
+        };
+
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Occlusion Uniform Buffer"),
+            contents: bytemuck::cast_slice(&[uniforms]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Occlusion Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Occlusion Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+        });
+
+        let vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Occlusion Proxy Vertex Buffer"),
+            size: (MAX_QUERIES * BOX_VERTICES * std::mem::size_of::<[f32; 3]>()) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let query_set = device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: Some("Occlusion Query Set"),
+            ty: wgpu::QueryType::Occlusion,
+            count: MAX_QUERIES as u32,
+        });
+
+        let results_size = (MAX_QUERIES * std::mem::size_of::<u64>()) as u64;
+        let resolve_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Occlusion Resolve Buffer"),
+            size: results_size,
+            usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Occlusion Readback Buffer"),
+            size: results_size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Occlusion Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../../../shaders/occlusion.wgsl").into()),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Occlusion Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Occlusion Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<[f32; 3]>() as u64,
+                    step_mode: wgpu::VertexStepMode::Vertex,
+                    attributes: &PROXY_ATTRIBUTES,
+                }],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::empty(),
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                cull_mode: None,
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::GreaterEqual, // Reversed-Z
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                ..Default::default()
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            device,
+            pipeline,
+            uniform_buffer,
+            bind_group,
+            vertex_buffer,
+            query_set,
+            resolve_buffer,
+            readback_buffer,
+            queried: Vec::new(),
+            pending: None,
+            occluded: HashSet::new(),
+            generation: 0,
+        }
+    }
+
+    /// Забрать готовые результаты и подготовить прокси на этот кадр
+    pub fn prepare(
+        &mut self,
+        queue: &wgpu::Queue,
+        chunks: &GpuChunkManager,
+        view_proj: [[f32; 4]; 4],
+        camera: Vec3,
+    ) {
+        self.queried.clear();
+        self.read_results();
+
+        // Подошли к закрытому чанку - рисуем сразу, не дожидаясь запроса
+        for chunk in chunks.iter() {
+            if is_near(chunk.min, chunk.max, camera) {
+                self.occluded.remove(&chunk.key);
+            }
+        }
+
+        // Пока прошлые результаты не прочитаны, новых запросов не шлём
+        if self.pending.is_some() {
+            return;
+        }
+
+        let mut vertices = Vec::new();
+        for chunk in chunks.iter() {
+            if self.queried.len() == MAX_QUERIES {
+                break;
+            }
+            // Пустой меш: AABB не определён
+            if chunk.index_count == 0 || chunk.min[0] > chunk.max[0] {
+                continue;
+            }
+            if is_near(chunk.min, chunk.max, camera)
+                || !is_gpu_chunk_visible(&view_proj, chunk)
+            {
+                continue;
+            }
+            push_box(&mut vertices, chunk.min, chunk.max);
+            self.queried.push(chunk.key);
+        }
+
+        if !vertices.is_empty() {
+            queue.write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice(&vertices));
+            queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[OcclusionUniforms { view_proj }]));
+        }
+    }
+
+    /// Прочитать результаты, если GPU их уже отдал
+    fn read_results(&mut self) {
+        let Some(pending) = &self.pending else { return };
+        let _ = self.device.poll(wgpu::PollType::Poll);
+        match pending.state.load(Ordering::Acquire) {
+            MAP_PENDING => return,
+            MAP_FAILED => {
+                // Чтение сорвалось - просто пробуем в следующем кадре заново
+                self.pending = None;
+                return;
+            }
+            _ => {}
+        }
+
+        let pending = self.pending.take().unwrap();
+        let size = (pending.keys.len() * std::mem::size_of::<u64>()) as u64;
+        let slice = self.readback_buffer.slice(..size);
+        {
+            let data = slice.get_mapped_range();
+            let samples = data.chunks_exact(8).map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()));
+            let occluded = occluded_keys(&pending.keys, samples);
+            if occluded != self.occluded {
+                self.occluded = occluded;
+                self.generation += 1;
+            }
+        }
+        self.readback_buffer.unmap();
+    }
+
+    /// Набор запросов для main pass (None - в этом кадре запросов нет)
+    pub fn query_set(&self) -> Option<&wgpu::QuerySet> {
+        (!self.queried.is_empty()).then_some(&self.query_set)
+    }
+
+    /// Прокси-боксы, каждый в своём запросе. После непрозрачного terrain
+    pub fn render_proxies(&self, render_pass: &mut wgpu::RenderPass) {
+        if self.queried.is_empty() {
+            return;
+        }
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        for i in 0..self.queried.len() {
+            let first = (i * BOX_VERTICES) as u32;
+            render_pass.begin_occlusion_query(i as u32);
+            render_pass.draw(first..first + BOX_VERTICES as u32, 0..1);
+            render_pass.end_occlusion_query();
+        }
+    }
+
+    /// Скопировать результаты в буфер чтения (после main pass)
+    pub fn resolve(&self, encoder: &mut wgpu::CommandEncoder) {
+        if self.queried.is_empty() {
+            return;
+        }
+        let count = self.queried.len() as u32;
+        let size = (self.queried.len() * std::mem::size_of::<u64>()) as u64;
+        encoder.resolve_query_set(&self.query_set, 0..count, &self.resolve_buffer, 0);
+        encoder.copy_buffer_to_buffer(&self.resolve_buffer, 0, &self.readback_buffer, 0, size);
+    }
+
+    /// Запросить чтение результатов (после submit)
+    pub fn after_submit(&mut self) {
+        if self.queried.is_empty() || self.pending.is_some() {
+            return;
+        }
+        let keys = std::mem::take(&mut self.queried);
+        let size = (keys.len() * std::mem::size_of::<u64>()) as u64;
+        let state = Arc::new(AtomicU8::new(MAP_PENDING));
+        let callback_state = Arc::clone(&state);
+        self.readback_buffer.slice(..size).map_async(wgpu::MapMode::Read, move |result| {
+            let value = if result.is_ok() { MAP_READY } else { MAP_FAILED };
+            callback_state.store(value, Ordering::Release);
+        });
+        self.pending = Some(PendingReadback { keys, state });
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Чанк закрыт рельефом (по последним прочитанным запросам)
+    pub fn is_occluded(&self, key: &ChunkKey) -> bool {
+        self.occluded.contains(key)
+    }
+}
+
+/// Камера ближе MIN_OCCLUSION_DISTANCE к AABB (или внутри него)
+fn is_near(min: [f32; 3], max: [f32; 3], camera: Vec3) -> bool {
+    let camera = [camera.x, camera.y, camera.z];
+    let distance_sq: f32 = (0..3)
+        .map(|i| {
+            let d = (min[i] - camera[i]).max(camera[i] - max[i]).max(0.0);
+            d * d
+        })
+        .sum();
+    distance_sq < MIN_OCCLUSION_DISTANCE * MIN_OCCLUSION_DISTANCE
+}
+
+/// Чанки, у прокси которых не прошёл ни один фрагмент
+fn occluded_keys(keys: &[ChunkKey], samples: impl Iterator<Item = u64>) -> HashSet<ChunkKey> {
+    keys.iter()
+        .zip(samples)
+        .filter(|(_, samples)| *samples == 0)
+        .map(|(key, _)| *key)
+        .collect()
+}
+
+/// 12 треугольников AABB с запасом PROXY_MARGIN
+fn push_box(vertices: &mut Vec<[f32; 3]>, min: [f32; 3], max: [f32; 3]) {
+    let (a, b) = (min.map(|v| v - PROXY_MARGIN), max.map(|v| v + PROXY_MARGIN));
+    let corner = |i: usize| [
+        if i & 1 == 0 { a[0] } else { b[0] },
+        if i & 2 == 0 { a[1] } else { b[1] },
+        if i & 4 == 0 { a[2] } else { b[2] },
+    ];
+    // Грани: -X, +X, -Y, +Y, -Z, +Z (по 4 угла)
+    const FACES: [[usize; 4]; 6] = [
+        [0, 2, 6, 4],
+        [1, 5, 7, 3],
+        [0, 4, 5, 1],
+        [2, 3, 7, 6],
+        [0, 1, 3, 2],
+        [4, 6, 7, 5],
+    ];
+    for face in FACES {
+        for i in [0, 1, 2, 0, 2, 3] {
+            vertices.push(corner(face[i]));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_samples_mark_chunk_occluded() {
+        let keys = [ChunkKey::new(0, 0, 1), ChunkKey::new(1, 0, 1), ChunkKey::new(2, 0, 4)];
+        let occluded = occluded_keys(&keys, [0, 17, 0].into_iter());
+        assert!(occluded.contains(&keys[0]) && occluded.contains(&keys[2]));
+        assert!(!occluded.contains(&keys[1]));
+    }
+
+    #[test]
+    fn proxy_box_covers_margin_and_near_check() {
+        let mut vertices = Vec::new();
+        push_box(&mut vertices, [0.0, 10.0, 0.0], [16.0, 20.0, 16.0]);
+        assert_eq!(vertices.len(), BOX_VERTICES);
+        assert!(vertices.iter().any(|v| v[1] == 10.0 - PROXY_MARGIN));
+        assert!(vertices.iter().any(|v| v[0] == 16.0 + PROXY_MARGIN));
+
+        assert!(is_near([0.0, 0.0, 0.0], [16.0, 16.0, 16.0], Vec3::new(8.0, 40.0, 8.0)));
+        assert!(!is_near([0.0, 0.0, 0.0], [16.0, 16.0, 16.0], Vec3::new(100.0, 8.0, 8.0)));
+    }
+}
@@ -0,0 +1,136 @@
+// ============================================
+// Underground Culling - Отсечение поверхности из пещер
+// ============================================
+// Дешёвая эвристика по столбцам: если над камерой и над сеткой
+// столбцов вокруг неё есть сплошной потолок ниже поверхности, то
+// поверхность сверху увидеть нельзя. Тогда рисуем только секции
+// ниже самого высокого потолка, а дальние LOD (это только
+// поверхность) пропускаем совсем.
+//
+// Проверка во все стороны, а не только по взгляду: иначе при
+// повороте камеры поверхность будет мигать.
+
+use ultraviolet::Vec3;
+
+use crate::gpu::blocks::{AIR, WATER};
+use crate::gpu::terrain::generation::terrain_generator;
+use crate::gpu::terrain::WorldChanges;
+
+/// Радиус и шаг сетки столбцов вокруг камеры (блоки)
+const SAMPLE_RADIUS: i32 = 24;
+const SAMPLE_STEP: i32 = 4;
+/// Минимальная глубина камеры под поверхностью
+const MIN_DEPTH: i32 = 6;
+/// Сколько твёрдых блоков подряд считается потолком
+const CEILING_THICKNESS: i32 = 2;
+/// Запас над самым высоким потолком (своды между столбцами сетки)
+const CEILING_MARGIN: f32 = 4.0;
+
+/// Высота, выше которой геометрию из камеры не видно.
+/// None - камера не под землёй (или в потолке есть просвет)
+pub fn estimate_ceiling(
+    camera: Vec3,
+    is_opaque: impl Fn(i32, i32, i32) -> bool,
+    surface: impl Fn(i32, i32) -> i32,
+) -> Option<f32> {
+    let (cx, cy, cz) = (camera.x.floor() as i32, camera.y.floor() as i32, camera.z.floor() as i32);
+    if surface(cx, cz) - cy < MIN_DEPTH {
+        return None;
+    }
+
+    let mut highest = cy;
+    for dx in (-SAMPLE_RADIUS..=SAMPLE_RADIUS).step_by(SAMPLE_STEP as usize) {
+        for dz in (-SAMPLE_RADIUS..=SAMPLE_RADIUS).step_by(SAMPLE_STEP as usize) {
+            if dx * dx + dz * dz > SAMPLE_RADIUS * SAMPLE_RADIUS {
+                continue;
+            }
+            let (x, z) = (cx + dx, cz + dz);
+            // Любой открытый столбец - просвет к поверхности
+            let bottom = column_ceiling(x, z, cy, &is_opaque, surface(x, z))?;
+            highest = highest.max(bottom);
+        }
+    }
+    Some(highest as f32 + CEILING_MARGIN)
+}
+
+/// Низ первого сплошного потолка над from_y (до поверхности включительно)
+fn column_ceiling(x: i32, z: i32, from_y: i32, is_opaque: &impl Fn(i32, i32, i32) -> bool, surface: i32) -> Option<i32> {
+    let mut run = 0;
+    for y in (from_y + 1)..=surface {
+        if is_opaque(x, y, z) {
+            run += 1;
+            if run == CEILING_THICKNESS {
+                return Some(y + 1 - CEILING_THICKNESS);
+            }
+        } else {
+            run = 0;
+        }
+    }
+    None
+}
+
+/// Кэш оценки потолка: пересчёт только при смене блока камеры или мира
+pub struct UndergroundCulling {
+    built_for: Option<([i32; 3], u64)>,
+    ceiling: Option<f32>,
+}
+
+impl UndergroundCulling {
+    pub fn new() -> Self {
+        Self { built_for: None, ceiling: None }
+    }
+
+    /// Обновить оценку для позиции камеры
+    pub fn update(&mut self, camera: Vec3, world_changes: &WorldChanges) {
+        let block = [camera.x.floor() as i32, camera.y.floor() as i32, camera.z.floor() as i32];
+        let key = (block, world_changes.version());
+        if self.built_for == Some(key) {
+            return;
+        }
+        self.built_for = Some(key);
+
+        let generator = terrain_generator();
+        let is_opaque = |x: i32, y: i32, z: i32| {
+            let block = world_changes.get_block(x, y, z).unwrap_or_else(|| generator.block_at(x, y, z));
+            block != AIR && block != WATER
+        };
+        self.ceiling = estimate_ceiling(camera, is_opaque, |x, z| generator.height(x as f32, z as f32) as i32);
+    }
+
+    /// Высота потолка, если камера под землёй
+    pub fn ceiling(&self) -> Option<f32> {
+        self.ceiling
+    }
+}
+
+impl Default for UndergroundCulling {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SURFACE: i32 = 64;
+
+    #[test]
+    fn closed_cave_reports_ceiling() {
+        // Пещера высотой 5 блоков от y = 20 под сплошной толщей
+        let is_opaque = |_x: i32, y: i32, _z: i32| !(20..25).contains(&y);
+        let ceiling = estimate_ceiling(Vec3::new(0.5, 21.0, 0.5), is_opaque, |_, _| SURFACE);
+        assert_eq!(ceiling, Some(25.0 + CEILING_MARGIN));
+    }
+
+    #[test]
+    fn shaft_to_surface_disables_culling() {
+        // Та же пещера, но с шахтой до поверхности в 8 блоках от камеры
+        let is_opaque = |x: i32, y: i32, z: i32| !((20..25).contains(&y) || (x == 8 && z == 0 && y >= 20));
+        assert_eq!(estimate_ceiling(Vec3::new(0.5, 21.0, 0.5), is_opaque, |_, _| SURFACE), None);
+
+        // У поверхности отсечения нет
+        let solid_below = |_x: i32, y: i32, _z: i32| y < SURFACE;
+        assert_eq!(estimate_ceiling(Vec3::new(0.5, 62.0, 0.5), solid_below, |_, _| SURFACE), None);
+    }
+}
@@ -0,0 +1,61 @@
+// ============================================
+// LOD Budget - Допустимые дистанции LOD для видеокарты
+// ============================================
+// wgpu не сообщает объём видеопамяти, поэтому потолок дальности
+// выбирается по типу адаптера: дискретной карте хватает на полные
+// 64 чанка, встроенной и программной - меньше.
+
+/// Минимальная дистанция LOD0 (воксельные чанки вокруг игрока)
+const MIN_LOD0_DISTANCE: i32 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LodBudget {
+    /// Максимальная дистанция LOD3 в чанках
+    pub max_distance: i32,
+}
+
+impl LodBudget {
+    pub fn from_adapter(info: &wgpu::AdapterInfo) -> Self {
+        let max_distance = match info.device_type {
+            wgpu::DeviceType::DiscreteGpu => 64,
+            wgpu::DeviceType::IntegratedGpu => 48,
+            wgpu::DeviceType::VirtualGpu | wgpu::DeviceType::Other => 40,
+            wgpu::DeviceType::Cpu => 24,
+        };
+        Self { max_distance }
+    }
+
+    /// Привести дистанции к допустимым: строго возрастают
+    /// и не выходят за потолок видеокарты
+    pub fn clamp(&self, distances: [i32; 4]) -> [i32; 4] {
+        let mut out = distances;
+        out[0] = out[0].clamp(MIN_LOD0_DISTANCE, self.max_distance - 3);
+        for i in 1..4 {
+            // Оставляем место под следующие уровни
+            let limit = self.max_distance - (3 - i) as i32;
+            out[i] = out[i].max(out[i - 1] + 1).min(limit);
+        }
+        out
+    }
+}
+
+impl Default for LodBudget {
+    fn default() -> Self {
+        Self { max_distance: 64 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamp_keeps_levels_increasing_under_budget() {
+        let budget = LodBudget { max_distance: 48 };
+        assert_eq!(budget.clamp([8, 16, 32, 48]), [8, 16, 32, 48]);
+        assert_eq!(budget.clamp([34, 34, 34, 34]), [34, 35, 36, 37]);
+        assert_eq!(budget.clamp([16, 32, 48, 64]), [16, 32, 47, 48]);
+        assert_eq!(budget.clamp([64, 64, 64, 64]), [45, 46, 47, 48]);
+        assert_eq!(budget.clamp([0, 2, 1, 3]), [4, 5, 6, 7]);
+    }
+}
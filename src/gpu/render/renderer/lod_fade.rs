@@ -0,0 +1,366 @@
+// ============================================
+// LOD Fade - Плавная смена уровня детализации
+// ============================================
+// Чанк, пропавший из нужных, не удаляется сразу: старый меш рисуется,
+// пока новые чанки не покроют его площадь целиком (или не выйдет
+// срок ожидания). Новые меши поверх старых до этого не рисуются.
+// Затем старый и новые за FADE_SECS перетекают друг в друга
+// screen-door дизерингом с общими порогами (fs_dither): каждый
+// пиксель рисует ровно один из двух мешей, без смешивания и сортировки.
+//
+// Через дизеринг рисуется только непрозрачный terrain основного
+// прохода; GPU и CPU отсечение такие чанки пропускают. Тени, вода
+// и полупрозрачные грани берут меши как раньше.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::gpu::terrain::lod::LodLevel;
+use crate::gpu::terrain::mesh::FadeInstance;
+use crate::gpu::terrain::{ChunkKey, GpuChunk, GpuChunkManager};
+
+/// Длительность перехода (секунды)
+const FADE_SECS: f32 = 0.5;
+/// Сколько старый меш ждёт замену, прежде чем исчезнуть без неё
+const MAX_WAIT_SECS: f32 = 2.0;
+/// Слотов в буфере дизеринга; лишние чанки перехода в этом кадре не рисуются
+const MAX_FADING: usize = 4096;
+const INSTANCE_SIZE: u64 = std::mem::size_of::<FadeInstance>() as u64;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FadeState {
+    /// Новый меш ждёт, пока старые под ним не будут заменены целиком
+    Waiting,
+    /// Проявляется (доля пикселей 0-1)
+    FadeIn(f32),
+    /// Старый меш рисуется до замены (секунды ожидания)
+    Retired(f32),
+    /// Исчезает (доля пикселей, уже отданных новым)
+    FadeOut(f32),
+}
+
+impl FadeState {
+    fn is_old(self) -> bool {
+        matches!(self, FadeState::Retired(_) | FadeState::FadeOut(_))
+    }
+}
+
+/// Состояния переходов (без GPU ресурсов)
+#[derive(Default)]
+struct Transitions {
+    states: HashMap<ChunkKey, FadeState>,
+    /// Нужные чанки последнего пакета генерации
+    required: HashSet<ChunkKey>,
+    /// Растёт при смене набора чанков перехода
+    generation: u64,
+}
+
+impl Transitions {
+    /// Пропавшие из нужных чанки уходят на пенсию вместо удаления.
+    /// Возвращает ключи, которые нужно оставить на GPU
+    fn retire(&mut self, resident: impl Iterator<Item = ChunkKey>, required: &HashSet<ChunkKey>) -> HashSet<ChunkKey> {
+        let mut changed = false;
+        self.states.retain(|key, state| {
+            let keep = match *state {
+                // Снова нужный старый меш рисуется как обычно
+                FadeState::Retired(_) | FadeState::FadeOut(_) => !required.contains(key),
+                // Ненужный новый: ещё не показан - удаляется, проявляется - исчезает
+                FadeState::Waiting => required.contains(key),
+                FadeState::FadeIn(t) => {
+                    if !required.contains(key) {
+                        *state = FadeState::FadeOut(1.0 - t);
+                    }
+                    true
+                }
+            };
+            changed |= !keep;
+            keep
+        });
+        for key in resident {
+            if !required.contains(&key) && !self.states.contains_key(&key) {
+                self.states.insert(key, FadeState::Retired(0.0));
+                changed = true;
+            }
+        }
+        if changed {
+            self.generation += 1;
+        }
+
+        self.required = required.clone();
+        let mut keep = required.clone();
+        keep.extend(self.states.iter().filter(|(_, state)| state.is_old()).map(|(key, _)| *key));
+        keep
+    }
+
+    /// Новый меш поверх старого другого LOD ждёт, пока заменят весь старый
+    fn add_new(&mut self, key: ChunkKey) {
+        if self.states.contains_key(&key) {
+            return;
+        }
+        let replaces = self.states.iter().any(|(old, state)| {
+            state.is_old() && lod_scale(old) != lod_scale(&key) && overlaps(old, &key)
+        });
+        if replaces {
+            self.states.insert(key, FadeState::Waiting);
+            self.generation += 1;
+        }
+    }
+
+    /// Продвинуть переходы. Возвращает старые меши, которые пора удалить
+    fn advance(&mut self, dt: f32, is_resident: impl Fn(&ChunkKey) -> bool) -> Vec<ChunkKey> {
+        let step = dt / FADE_SECS;
+        let mut finished = Vec::new();
+        let mut removed = Vec::new();
+        for (key, state) in self.states.iter_mut() {
+            if !is_resident(key) {
+                finished.push(*key);
+                continue;
+            }
+            match state {
+                FadeState::Waiting => {}
+                FadeState::Retired(wait) => *wait += dt,
+                FadeState::FadeIn(t) | FadeState::FadeOut(t) => {
+                    *t += step;
+                    if *t >= 1.0 {
+                        finished.push(*key);
+                        if matches!(state, FadeState::FadeOut(_)) {
+                            removed.push(*key);
+                        }
+                    }
+                }
+            }
+        }
+        for key in &finished {
+            self.states.remove(key);
+        }
+        if !finished.is_empty() {
+            self.generation += 1;
+        }
+
+        // Заменённые целиком (или заждавшиеся) старые меши начинают исчезать
+        let replaced: Vec<ChunkKey> = self.states.iter()
+            .filter(|(key, state)| match state {
+                FadeState::Retired(wait) => *wait >= MAX_WAIT_SECS || self.is_replaced(key, &is_resident),
+                _ => false,
+            })
+            .map(|(key, _)| *key)
+            .collect();
+        for key in replaced {
+            self.states.insert(key, FadeState::FadeOut(0.0));
+        }
+
+        // Новые проявляются вместе с исчезающими: под ними не осталось ждущих
+        let pending: Vec<ChunkKey> = self.states.iter()
+            .filter(|(_, state)| matches!(state, FadeState::Retired(_)))
+            .map(|(key, _)| *key)
+            .collect();
+        for (key, state) in self.states.iter_mut() {
+            if *state == FadeState::Waiting && !pending.iter().any(|old| overlaps(old, key)) {
+                *state = FadeState::FadeIn(0.0);
+            }
+        }
+        removed
+    }
+
+    /// Все нужные чанки на площади старого уже загружены
+    fn is_replaced(&self, old: &ChunkKey, is_resident: impl Fn(&ChunkKey) -> bool) -> bool {
+        covering_keys(old)
+            .filter(|key| self.required.contains(key))
+            .all(|key| is_resident(&key))
+    }
+}
+
+/// Переходы между LOD и буфер их параметров дизеринга
+pub struct LodFade {
+    transitions: Transitions,
+    instance_buffer: wgpu::Buffer,
+    /// Чанки, рисуемые дизерингом в этом кадре, и смещения их параметров
+    slots: Vec<(ChunkKey, u64)>,
+}
+
+impl LodFade {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let instance_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("LOD Fade Instance Buffer"),
+            size: MAX_FADING as u64 * INSTANCE_SIZE,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        Self { transitions: Transitions::default(), instance_buffer, slots: Vec::new() }
+    }
+
+    /// Пакет генерации пришёл: ключи, которые GpuChunkManager должен сохранить
+    pub fn retire(&mut self, gpu_chunks: &GpuChunkManager, required: &HashSet<ChunkKey>) -> HashSet<ChunkKey> {
+        self.transitions.retire(gpu_chunks.iter().map(|chunk| chunk.key), required)
+    }
+
+    /// Загружен чанк, которого не было на GPU
+    pub fn add_new(&mut self, key: ChunkKey) {
+        self.transitions.add_new(key);
+    }
+
+    /// Продвинуть переходы, удалить отслужившие меши и записать параметры кадра
+    pub fn update(&mut self, queue: &wgpu::Queue, dt: f32, gpu_chunks: &mut GpuChunkManager) {
+        self.slots.clear();
+        if self.transitions.states.is_empty() {
+            return;
+        }
+        for key in self.transitions.advance(dt, |key| gpu_chunks.contains(key)) {
+            gpu_chunks.remove(&key);
+        }
+
+        let mut instances = Vec::new();
+        for (key, state) in &self.transitions.states {
+            let instance = match *state {
+                FadeState::Waiting => continue,
+                FadeState::FadeIn(t) => FadeInstance { coverage: t, fade_out: 0.0 },
+                FadeState::Retired(_) => FadeInstance { coverage: 0.0, fade_out: 1.0 },
+                FadeState::FadeOut(t) => FadeInstance { coverage: t, fade_out: 1.0 },
+            };
+            if instances.len() == MAX_FADING {
+                break;
+            }
+            self.slots.push((*key, instances.len() as u64 * INSTANCE_SIZE));
+            instances.push(instance);
+        }
+        if !instances.is_empty() {
+            queue.write_buffer(&self.instance_buffer, 0, bytemuck::cast_slice(&instances));
+        }
+    }
+
+    /// Чанк в переходе (обычная отрисовка его пропускает)
+    pub fn is_fading(&self, key: &ChunkKey) -> bool {
+        self.transitions.states.contains_key(key)
+    }
+
+    /// Растёт при смене набора чанков перехода (GPU отсечение пересобирает записи)
+    pub fn generation(&self) -> u64 {
+        self.transitions.generation
+    }
+
+    /// Нарисовать чанки перехода (bind groups terrain уже выставлены)
+    pub fn draw(
+        &self,
+        render_pass: &mut wgpu::RenderPass,
+        pipeline: &wgpu::RenderPipeline,
+        gpu_chunks: &GpuChunkManager,
+        visible: impl Fn(&GpuChunk) -> bool,
+    ) {
+        if self.slots.is_empty() {
+            return;
+        }
+        render_pass.set_pipeline(pipeline);
+        for (key, offset) in &self.slots {
+            let Some(chunk) = gpu_chunks.get(key) else { continue };
+            if !visible(chunk) {
+                continue;
+            }
+            render_pass.set_vertex_buffer(0, chunk.vertex_slice());
+            render_pass.set_vertex_buffer(1, self.instance_buffer.slice(*offset..*offset + INSTANCE_SIZE));
+            render_pass.set_index_buffer(chunk.index_slice(), wgpu::IndexFormat::Uint32);
+            render_pass.draw_indexed(0..chunk.index_count, 0, 0..1);
+        }
+    }
+}
+
+/// Уровень детализации ключа (секции и столбцы вокселей - один уровень)
+fn lod_scale(key: &ChunkKey) -> i32 {
+    if key.is_voxel() { 1 } else { key.scale }
+}
+
+/// Площадь чанка в координатах чанков: x0, z0, x1, z1 (правая граница не входит)
+fn footprint(key: &ChunkKey) -> (i32, i32, i32, i32) {
+    let size = lod_scale(key);
+    (key.x, key.z, key.x + size, key.z + size)
+}
+
+fn overlaps(a: &ChunkKey, b: &ChunkKey) -> bool {
+    let (ax0, az0, ax1, az1) = footprint(a);
+    let (bx0, bz0, bx1, bz1) = footprint(b);
+    ax0 < bx1 && bx0 < ax1 && az0 < bz1 && bz0 < az1
+}
+
+/// Ключи всех уровней LOD, пересекающие площадь чанка
+fn covering_keys(old: &ChunkKey) -> impl Iterator<Item = ChunkKey> {
+    let (x0, z0, x1, z1) = footprint(old);
+    LodLevel::DEFAULT_LEVELS.iter().map(|level| level.scale).flat_map(move |scale| {
+        let start_x = x0.div_euclid(scale) * scale;
+        let start_z = z0.div_euclid(scale) * scale;
+        (start_z..z1).step_by(scale as usize).flat_map(move |z| {
+            (start_x..x1).step_by(scale as usize).map(move |x| ChunkKey::new(x, z, scale))
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn required(keys: &[ChunkKey]) -> HashSet<ChunkKey> {
+        keys.iter().copied().collect()
+    }
+
+    #[test]
+    fn covering_keys_span_all_levels() {
+        let keys: Vec<ChunkKey> = covering_keys(&ChunkKey::new(4, -2, 2)).collect();
+        assert!(keys.contains(&ChunkKey::new(5, -1, 1)));
+        assert!(keys.contains(&ChunkKey::new(4, -2, 2)));
+        assert!(keys.contains(&ChunkKey::new(4, -4, 4)));
+        assert!(keys.contains(&ChunkKey::new(0, -8, 8)));
+        assert_eq!(keys.len(), 4 + 1 + 1 + 1);
+        assert!(keys.iter().all(|key| overlaps(key, &ChunkKey::new(4, -2, 2))));
+    }
+
+    #[test]
+    fn old_mesh_stays_until_replaced_then_cross_fades() {
+        let old = ChunkKey::new(0, 0, 2);
+        let fine: Vec<ChunkKey> = [(0, 0), (1, 0), (0, 1), (1, 1)].iter().map(|&(x, z)| ChunkKey::new(x, z, 1)).collect();
+        let mut transitions = Transitions::default();
+
+        let keep = transitions.retire([old].into_iter(), &required(&fine));
+        assert!(keep.contains(&old));
+
+        // Пришла часть замены: старый ждёт, новые скрыты
+        let mut resident: HashSet<ChunkKey> = required(&[old, fine[0], fine[1]]);
+        transitions.add_new(fine[0]);
+        transitions.add_new(fine[1]);
+        assert!(transitions.advance(0.1, |key| resident.contains(key)).is_empty());
+        assert_eq!(transitions.states[&old], FadeState::Retired(0.1));
+        assert_eq!(transitions.states[&fine[0]], FadeState::Waiting);
+
+        // Замена целиком: переход начинается у всех сразу
+        resident.extend([fine[2], fine[3]]);
+        transitions.add_new(fine[2]);
+        transitions.add_new(fine[3]);
+        transitions.advance(0.1, |key| resident.contains(key));
+        assert_eq!(transitions.states[&old], FadeState::FadeOut(0.0));
+        assert_eq!(transitions.states[&fine[3]], FadeState::FadeIn(0.0));
+
+        let removed = transitions.advance(FADE_SECS, |key| resident.contains(key));
+        assert_eq!(removed, vec![old]);
+        assert!(transitions.states.is_empty());
+    }
+
+    #[test]
+    fn retired_mesh_gives_up_waiting() {
+        let old = ChunkKey::new(0, 0, 4);
+        let mut transitions = Transitions::default();
+        transitions.retire([old].into_iter(), &required(&[ChunkKey::new(0, 0, 2)]));
+
+        transitions.advance(MAX_WAIT_SECS, |key| *key == old);
+        transitions.advance(0.0, |key| *key == old);
+        assert_eq!(transitions.states[&old], FadeState::FadeOut(0.0));
+    }
+
+    #[test]
+    fn required_again_returns_to_normal_drawing() {
+        let old = ChunkKey::new(8, 8, 8);
+        let mut transitions = Transitions::default();
+        transitions.retire([old].into_iter(), &HashSet::new());
+        let generation = transitions.generation;
+
+        let keep = transitions.retire([old].into_iter(), &required(&[old]));
+        assert!(keep.contains(&old));
+        assert!(transitions.states.is_empty());
+        assert!(transitions.generation > generation);
+    }
+}
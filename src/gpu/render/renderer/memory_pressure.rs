@@ -0,0 +1,131 @@
+// ============================================
+// Memory Pressure - Снижение дальности при нехватке видеопамяти
+// ============================================
+// Сигналы давления: ошибка выделения буфера (OutOfMemory) или меши
+// террейна больше бюджета адаптера. Тогда дистанции LOD уменьшаются
+// по шагам; после долгого запаса памяти шаги по одному отменяются.
+// wgpu не сообщает объём видеопамяти, бюджет - по типу адаптера
+// (как в LodBudget).
+
+use std::time::{Duration, Instant};
+
+/// Множитель дистанций за один шаг
+const STEP_SCALE: f32 = 0.75;
+/// Максимум шагов (0.75^4 ≈ 0.32 от выбранной дальности)
+const MAX_STEPS: u32 = 4;
+/// Пауза после шага: новые меши строятся не сразу
+const STEP_COOLDOWN: Duration = Duration::from_secs(5);
+/// Сколько держаться ниже RESTORE_FRACTION бюджета перед возвратом шага
+const RESTORE_DELAY: Duration = Duration::from_secs(30);
+/// Возврат шага только при большом запасе (иначе качели)
+const RESTORE_FRACTION: f64 = 0.5;
+const MIB: u64 = 1024 * 1024;
+
+/// Изменение дальности из-за памяти
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryPressure {
+    /// Дальность уменьшена (номер шага)
+    Reduced(u32),
+    /// Шаг отменён (оставшийся шаг, 0 - настройки восстановлены)
+    Restored(u32),
+}
+
+/// Сторож видеопамяти террейна
+#[derive(Debug, Clone, Copy)]
+pub struct MemoryWatchdog {
+    /// Бюджет мешей террейна (байты)
+    budget: u64,
+    step: u32,
+    last_step: Option<Instant>,
+    /// С какого момента памяти с запасом
+    calm_since: Option<Instant>,
+}
+
+impl MemoryWatchdog {
+    pub fn new(budget: u64) -> Self {
+        Self { budget, step: 0, last_step: None, calm_since: None }
+    }
+
+    pub fn from_adapter(info: &wgpu::AdapterInfo) -> Self {
+        let budget = match info.device_type {
+            wgpu::DeviceType::DiscreteGpu => 2048 * MIB,
+            wgpu::DeviceType::IntegratedGpu => 768 * MIB,
+            wgpu::DeviceType::VirtualGpu | wgpu::DeviceType::Other => 512 * MIB,
+            wgpu::DeviceType::Cpu => 256 * MIB,
+        };
+        Self::new(budget)
+    }
+
+    /// Множитель дистанций LOD на текущем шаге
+    pub fn scale(&self) -> f32 {
+        STEP_SCALE.powi(self.step as i32)
+    }
+
+    /// Дальность уже минимальна - уменьшать нечего
+    pub fn exhausted(&self) -> bool {
+        self.step >= MAX_STEPS
+    }
+
+    /// Проверка раз в кадр: used - память мешей, out_of_memory - была
+    /// ошибка выделения с прошлой проверки
+    pub fn check(&mut self, used: u64, out_of_memory: bool, now: Instant) -> Option<MemoryPressure> {
+        let cooling = self.last_step.is_some_and(|at| now.duration_since(at) < STEP_COOLDOWN);
+
+        if out_of_memory || used > self.budget {
+            self.calm_since = None;
+            // Ошибка выделения - шаг сразу, превышение бюджета - после паузы
+            if self.exhausted() || (cooling && !out_of_memory) {
+                return None;
+            }
+            self.step += 1;
+            self.last_step = Some(now);
+            return Some(MemoryPressure::Reduced(self.step));
+        }
+
+        if self.step == 0 || (used as f64) > self.budget as f64 * RESTORE_FRACTION {
+            self.calm_since = None;
+            return None;
+        }
+        let calm_since = *self.calm_since.get_or_insert(now);
+        if cooling || now.duration_since(calm_since) < RESTORE_DELAY {
+            return None;
+        }
+        self.step -= 1;
+        self.last_step = Some(now);
+        self.calm_since = None;
+        Some(MemoryPressure::Restored(self.step))
+    }
+}
+
+/// Дистанции LOD с учётом шага давления (до LodBudget::clamp)
+pub fn scale_distances(distances: [i32; 4], scale: f32) -> [i32; 4] {
+    distances.map(|distance| (distance as f32 * scale).round() as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_down_and_restores() {
+        let mut watchdog = MemoryWatchdog::new(100);
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+
+        assert_eq!(watchdog.check(50, false, at(0)), None);
+        assert_eq!(watchdog.check(150, false, at(1)), Some(MemoryPressure::Reduced(1)));
+        // Превышение бюджета ждёт, пока перестроятся меши
+        assert_eq!(watchdog.check(150, false, at(2)), None);
+        // Ошибка выделения - без паузы
+        assert_eq!(watchdog.check(90, true, at(3)), Some(MemoryPressure::Reduced(2)));
+        assert_eq!(scale_distances([8, 16, 32, 64], watchdog.scale()), [5, 9, 18, 36]);
+
+        // Между половиной и полным бюджетом шаги остаются
+        assert_eq!(watchdog.check(80, false, at(100)), None);
+        assert_eq!(watchdog.check(40, false, at(101)), None);
+        assert_eq!(watchdog.check(40, false, at(131)), Some(MemoryPressure::Restored(1)));
+        assert_eq!(watchdog.check(40, false, at(140)), None);
+        assert_eq!(watchdog.check(40, false, at(170)), Some(MemoryPressure::Restored(0)));
+        assert_eq!(watchdog.scale(), 1.0);
+    }
+}
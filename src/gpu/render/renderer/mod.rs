@@ -1,5 +1,8 @@
 pub mod core;
 mod culling;
+mod lod_budget;
+mod lod_fade;
+mod memory_pressure;
 mod passes;
 mod systems;
 
@@ -8,9 +11,25 @@ use std::sync::Arc;
 use crate::gpu::render::depth::create_depth_texture;
 use crate::gpu::player::Camera;
 use crate::gpu::player::Player;
-use crate::gpu::terrain::WorldChanges;
+use crate::gpu::blocks::BlockType;
+use crate::gpu::terrain::{WorldChanges, HybridTerrainManager, GeneratedChunkData};
+use crate::gpu::render::palette::PaletteTheme;
+use crate::gpu::render::sampling::SamplingSettings;
+use crate::gpu::render::fog::FogSettings;
+use crate::gpu::render::post::{DepthOfField, PostSettings};
+use crate::gpu::render::antialiasing::AntiAliasing;
+use crate::gpu::render::resolution::ResolutionSettings;
+use crate::gpu::render::uniforms::underwater_fog_color;
+use crate::gpu::weather::WeatherParams;
+use crate::gpu::clouds::CloudQuality;
+use crate::gpu::particles::ParticleInstance;
+use crate::gpu::lighting::{MoonPhase, OverlayMode, OverlayVertex};
 
 use core::{RendererState, RenderComponents, LightingResources, TerrainResources, CachedCamera};
+use culling::CullParams;
+use memory_pressure::scale_distances;
+
+pub use memory_pressure::MemoryPressure;
 
 pub struct Renderer {
     state: RendererState,
@@ -21,12 +40,14 @@ pub struct Renderer {
 }
 
 impl Renderer {
-    pub async fn new(window: Arc<winit::window::Window>) -> Self {
-        let (surface, device, queue, config, size) = core::init_gpu(window).await;
-        let (components, lighting, terrain) = core::init_components(&device, &queue, &config);
+    /// Сглаживание нужно сразу: число сэмплов MSAA задаёт все 3D пайплайны
+    pub async fn new(window: Arc<winit::window::Window>, anti_aliasing: AntiAliasing) -> Self {
+        let (surface, device, queue, config, size, adapter_info, sample_count, gpu_culling) = core::init_gpu(window, anti_aliasing).await;
+        let (components, lighting, terrain) =
+            core::init_components(&device, &queue, &config, &adapter_info, anti_aliasing, sample_count, gpu_culling);
 
         Self {
-            state: RendererState { surface, device, queue, config, size },
+            state: RendererState { surface, device, queue, config, size, sample_count },
             components,
             lighting,
             terrain,
@@ -40,7 +61,29 @@ impl Renderer {
             self.state.config.width = new_size.width;
             self.state.config.height = new_size.height;
             self.state.surface.configure(&self.state.device, &self.state.config);
-            self.terrain.depth_texture = create_depth_texture(&self.state.device, &self.state.config);
+            self.recreate_scene_targets();
+        }
+    }
+
+    /// Пересоздать 3D цели под размер окна и масштаб разрешения
+    fn recreate_scene_targets(&mut self) {
+        let device = &self.state.device;
+        let scene_size = self.terrain.render_scale.scene_size(self.state.config.width, self.state.config.height);
+        self.terrain.depth_texture = create_depth_texture(device, scene_size, self.state.sample_count);
+        if let Some(resolve) = &mut self.terrain.depth_resolve {
+            resolve.resize(device, scene_size, &self.terrain.depth_texture);
+        }
+        let water_depth = self.terrain.depth_resolve.as_ref().map_or(&self.terrain.depth_texture, |r| r.view());
+        self.terrain.water.resize(device, &self.lighting.layouts.water, scene_size, water_depth);
+        self.terrain.post.resize(device, &self.state.config, scene_size, water_depth);
+    }
+
+    /// Замер FPS; в динамическом режиме масштаб подстраивается под цель
+    fn update_fps(&mut self) {
+        let Some(fps) = self.components.fps_counter.update() else { return };
+        if self.terrain.render_scale.adapt(fps) {
+            log::debug!("Масштаб разрешения: {:.0}% ({} FPS)", self.terrain.render_scale.current() * 100.0, fps);
+            self.recreate_scene_targets();
         }
     }
 
@@ -78,6 +121,16 @@ impl Renderer {
         );
     }
     
+    /// Трещины на ломаемом блоке (None - ломание не идёт)
+    pub fn update_break_overlay(&mut self, target: Option<([i32; 3], f32)>) {
+        self.components.break_overlay.update(&self.state.queue, self.cached.view_proj, target);
+    }
+    
+    /// Рамка выделения палочкой (углы min и max включительно, None - скрыть)
+    pub fn update_selection_box(&mut self, bounds: Option<([i32; 3], [i32; 3])>) {
+        self.components.selection_box.update(&self.state.queue, self.cached.view_proj, bounds);
+    }
+    
     /// Обновить выделение с произвольной позицией и размером (для суб-вокселей)
     pub fn update_block_highlight_sized(&self, pos: [f32; 3], size: f32) {
         self.components.block_highlight.update_with_size(
@@ -89,10 +142,15 @@ impl Renderer {
     }
 
     pub fn render(&mut self, render_player: bool, highlight_block: Option<[i32; 3]>) -> Result<(), wgpu::SurfaceError> {
-        self.components.fps_counter.update();
+        self.update_fps();
+        if let Some(timer) = &mut self.components.gpu_timer {
+            timer.begin_frame(&self.state.device);
+        }
 
         let output = self.state.surface.get_current_texture()?;
         let view = output.texture.create_view(&wgpu::TextureViewDescriptor::default());
+        // 3D проходы рисуют в HDR цель, в surface - постобработка и UI
+        let scene = self.terrain.post.scene_target();
         let mut encoder = self.state.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
             label: Some("Render Encoder"),
         });
@@ -104,14 +162,16 @@ impl Renderer {
             &self.components.pipelines,
             &self.components.gpu_chunks,
             None, // No subvoxels in basic render
+            self.components.gpu_timer.as_ref(),
         );
 
         // Main 3D pass
+        self.cull_chunks(&mut encoder);
         passes::main_pass::render(
             &mut encoder,
-            &view,
+            scene,
             &self.terrain.depth_texture,
-            self.lighting.day_night.sky_color,
+            self.clear_color(),
             &self.cached.view_proj,
             &self.components.pipelines,
             &self.lighting.core_bind_groups,
@@ -120,12 +180,72 @@ impl Renderer {
             &self.components,
             render_player,
             highlight_block,
+            self.vertical_range(),
+            self.terrain.underground.ceiling().is_some(),
+            &self.terrain.lod_fade,
         );
+        self.components.occlusion.resolve(&mut encoder);
+
+        // Water pass (волны и отражения)
+        if let Some(resolve) = &self.terrain.depth_resolve {
+            resolve.render(&mut encoder);
+        }
+        passes::water::render(
+            &mut encoder,
+            scene,
+            self.terrain.post.hdr_texture(),
+            &self.terrain.depth_texture,
+            self.cached.position,
+            &self.cached.view_proj,
+            &self.components.pipelines,
+            &self.lighting.core_bind_groups,
+            &self.lighting.shadow,
+            &self.terrain.water,
+            &self.components,
+            self.vertical_range(),
+            self.terrain.underground.ceiling().is_some(),
+        );
+
+        // Translucent pass (стекло, лёд)
+        passes::translucent::render(
+            &mut encoder,
+            scene,
+            &self.terrain.depth_texture,
+            self.cached.position,
+            &self.cached.view_proj,
+            &self.components.pipelines,
+            &self.lighting.core_bind_groups,
+            &self.lighting.shadow,
+            &self.lighting.atlas,
+            &self.components,
+            self.vertical_range(),
+            self.terrain.underground.ceiling().is_some(),
+        );
+
+        // Weather pass
+        passes::weather::render(&mut encoder, scene, &self.terrain.depth_texture, &self.components);
+
+        // Рука от первого лица - поверх мира и осадков
+        passes::viewmodel::render(&mut encoder, scene, &self.terrain.depth_texture, &self.components);
+
+        // Post-process (bloom, экспозиция, тонмаппинг) в surface
+        self.terrain.post.render(&mut encoder, &view);
 
         // UI pass
         passes::ui::render(&mut encoder, &view, &self.components);
 
+        // Скриншот - готовый кадр вместе с интерфейсом
+        self.components.screenshot.capture(&self.state.device, &mut encoder, &output.texture);
+
+        if let Some(timer) = &mut self.components.gpu_timer {
+            timer.resolve(&mut encoder);
+        }
         self.state.queue.submit(std::iter::once(encoder.finish()));
+        self.components.occlusion.after_submit();
+        self.components.screenshot.after_submit();
+        if let Some(timer) = &mut self.components.gpu_timer {
+            timer.after_submit();
+        }
         output.present();
         Ok(())
     }
@@ -135,10 +255,15 @@ impl Renderer {
     where
         F: FnOnce(&wgpu::Device, &mut wgpu::CommandEncoder, &wgpu::TextureView, &wgpu::Queue),
     {
-        self.components.fps_counter.update();
+        self.update_fps();
+        if let Some(timer) = &mut self.components.gpu_timer {
+            timer.begin_frame(&self.state.device);
+        }
 
         let output = self.state.surface.get_current_texture()?;
         let view = output.texture.create_view(&wgpu::TextureViewDescriptor::default());
+        // 3D проходы рисуют в HDR цель, в surface - постобработка и UI
+        let scene = self.terrain.post.scene_target();
         let mut encoder = self.state.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
             label: Some("Render Encoder"),
         });
@@ -150,14 +275,16 @@ impl Renderer {
             &self.components.pipelines,
             &self.components.gpu_chunks,
             None, // No subvoxels in basic render_with_gui
+            self.components.gpu_timer.as_ref(),
         );
 
         // Main 3D pass
+        self.cull_chunks(&mut encoder);
         passes::main_pass::render(
             &mut encoder,
-            &view,
+            scene,
             &self.terrain.depth_texture,
-            self.lighting.day_night.sky_color,
+            self.clear_color(),
             &self.cached.view_proj,
             &self.components.pipelines,
             &self.lighting.core_bind_groups,
@@ -166,15 +293,75 @@ impl Renderer {
             &self.components,
             render_player,
             highlight_block,
+            self.vertical_range(),
+            self.terrain.underground.ceiling().is_some(),
+            &self.terrain.lod_fade,
+        );
+        self.components.occlusion.resolve(&mut encoder);
+
+        // Water pass (волны и отражения)
+        if let Some(resolve) = &self.terrain.depth_resolve {
+            resolve.render(&mut encoder);
+        }
+        passes::water::render(
+            &mut encoder,
+            scene,
+            self.terrain.post.hdr_texture(),
+            &self.terrain.depth_texture,
+            self.cached.position,
+            &self.cached.view_proj,
+            &self.components.pipelines,
+            &self.lighting.core_bind_groups,
+            &self.lighting.shadow,
+            &self.terrain.water,
+            &self.components,
+            self.vertical_range(),
+            self.terrain.underground.ceiling().is_some(),
         );
 
+        // Translucent pass (стекло, лёд)
+        passes::translucent::render(
+            &mut encoder,
+            scene,
+            &self.terrain.depth_texture,
+            self.cached.position,
+            &self.cached.view_proj,
+            &self.components.pipelines,
+            &self.lighting.core_bind_groups,
+            &self.lighting.shadow,
+            &self.lighting.atlas,
+            &self.components,
+            self.vertical_range(),
+            self.terrain.underground.ceiling().is_some(),
+        );
+
+        // Weather pass
+        passes::weather::render(&mut encoder, scene, &self.terrain.depth_texture, &self.components);
+
+        // Рука от первого лица - поверх мира и осадков
+        passes::viewmodel::render(&mut encoder, scene, &self.terrain.depth_texture, &self.components);
+
+        // Post-process (bloom, экспозиция, тонмаппинг) в surface
+        self.terrain.post.render(&mut encoder, &view);
+
         // UI pass
         passes::ui::render(&mut encoder, &view, &self.components);
         
         // GUI pass (меню и т.п.)
         gui_render(&self.state.device, &mut encoder, &view, &self.state.queue);
 
+        // Скриншот - готовый кадр вместе с интерфейсом
+        self.components.screenshot.capture(&self.state.device, &mut encoder, &output.texture);
+
+        if let Some(timer) = &mut self.components.gpu_timer {
+            timer.resolve(&mut encoder);
+        }
         self.state.queue.submit(std::iter::once(encoder.finish()));
+        self.components.occlusion.after_submit();
+        self.components.screenshot.after_submit();
+        if let Some(timer) = &mut self.components.gpu_timer {
+            timer.after_submit();
+        }
         output.present();
         Ok(())
     }
@@ -184,16 +371,21 @@ impl Renderer {
         &mut self, 
         render_player: bool, 
         highlight_block: Option<[i32; 3]>,
-        subvoxel_renderer: Option<&crate::gpu::subvoxel::SubVoxelRenderer>,
+        subvoxel_renderer: Option<&crate::gpu::subvoxel::OptimizedSubVoxelRenderer>,
         gui_render: F
     ) -> Result<(), wgpu::SurfaceError>
     where
         F: FnOnce(&wgpu::Device, &mut wgpu::CommandEncoder, &wgpu::TextureView, &wgpu::Queue),
     {
-        self.components.fps_counter.update();
+        self.update_fps();
+        if let Some(timer) = &mut self.components.gpu_timer {
+            timer.begin_frame(&self.state.device);
+        }
 
         let output = self.state.surface.get_current_texture()?;
         let view = output.texture.create_view(&wgpu::TextureViewDescriptor::default());
+        // 3D проходы рисуют в HDR цель, в surface - постобработка и UI
+        let scene = self.terrain.post.scene_target();
         let mut encoder = self.state.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
             label: Some("Render Encoder"),
         });
@@ -205,14 +397,16 @@ impl Renderer {
             &self.components.pipelines,
             &self.components.gpu_chunks,
             subvoxel_renderer,
+            self.components.gpu_timer.as_ref(),
         );
 
         // Main 3D pass
+        self.cull_chunks(&mut encoder);
         passes::main_pass::render(
             &mut encoder,
-            &view,
+            scene,
             &self.terrain.depth_texture,
-            self.lighting.day_night.sky_color,
+            self.clear_color(),
             &self.cached.view_proj,
             &self.components.pipelines,
             &self.lighting.core_bind_groups,
@@ -221,35 +415,106 @@ impl Renderer {
             &self.components,
             render_player,
             highlight_block,
+            self.vertical_range(),
+            self.terrain.underground.ceiling().is_some(),
+            &self.terrain.lod_fade,
         );
+        self.components.occlusion.resolve(&mut encoder);
         
         // SubVoxel pass
         if let Some(sv_renderer) = subvoxel_renderer {
             if sv_renderer.has_content() {
                 passes::subvoxel::render(
                     &mut encoder,
-                    &view,
+                    scene,
                     &self.terrain.depth_texture,
                     &self.components.pipelines,
                     &self.lighting.core_bind_groups,
                     &self.lighting.shadow,
                     &self.lighting.atlas,
                     sv_renderer,
+                    self.components.gpu_timer.as_ref(),
                 );
             }
         }
 
+        // Water pass (волны и отражения)
+        if let Some(resolve) = &self.terrain.depth_resolve {
+            resolve.render(&mut encoder);
+        }
+        passes::water::render(
+            &mut encoder,
+            scene,
+            self.terrain.post.hdr_texture(),
+            &self.terrain.depth_texture,
+            self.cached.position,
+            &self.cached.view_proj,
+            &self.components.pipelines,
+            &self.lighting.core_bind_groups,
+            &self.lighting.shadow,
+            &self.terrain.water,
+            &self.components,
+            self.vertical_range(),
+            self.terrain.underground.ceiling().is_some(),
+        );
+
+        // Translucent pass (стекло, лёд)
+        passes::translucent::render(
+            &mut encoder,
+            scene,
+            &self.terrain.depth_texture,
+            self.cached.position,
+            &self.cached.view_proj,
+            &self.components.pipelines,
+            &self.lighting.core_bind_groups,
+            &self.lighting.shadow,
+            &self.lighting.atlas,
+            &self.components,
+            self.vertical_range(),
+            self.terrain.underground.ceiling().is_some(),
+        );
+
+        // Weather pass
+        passes::weather::render(&mut encoder, scene, &self.terrain.depth_texture, &self.components);
+
+        // Рука от первого лица - поверх мира и осадков
+        passes::viewmodel::render(&mut encoder, scene, &self.terrain.depth_texture, &self.components);
+
+        // Post-process (bloom, экспозиция, тонмаппинг) в surface
+        self.terrain.post.render(&mut encoder, &view);
+
         // UI pass
         passes::ui::render(&mut encoder, &view, &self.components);
         
         // GUI pass
         gui_render(&self.state.device, &mut encoder, &view, &self.state.queue);
 
+        // Скриншот - готовый кадр вместе с интерфейсом
+        self.components.screenshot.capture(&self.state.device, &mut encoder, &output.texture);
+
+        if let Some(timer) = &mut self.components.gpu_timer {
+            timer.resolve(&mut encoder);
+        }
         self.state.queue.submit(std::iter::once(encoder.finish()));
+        self.components.occlusion.after_submit();
+        self.components.screenshot.after_submit();
+        if let Some(timer) = &mut self.components.gpu_timer {
+            timer.after_submit();
+        }
         output.present();
         Ok(())
     }
 
+    /// Снять следующий кадр в PNG (Err - снимок сейчас невозможен)
+    pub fn request_screenshot(&mut self) -> Result<(), String> {
+        self.components.screenshot.request()
+    }
+
+    /// Результат сохранения скриншота, когда он готов
+    pub fn poll_screenshot(&mut self) -> Option<Result<std::path::PathBuf, String>> {
+        self.components.screenshot.poll(&self.state.device)
+    }
+
     pub fn set_time_of_day(&mut self, time: f32) {
         self.lighting.day_night.set_time(time);
     }
@@ -262,6 +527,62 @@ impl Renderer {
         self.lighting.day_night.time.time
     }
 
+    /// Фаза луны по числу прошедших дней мира
+    pub fn set_moon_day(&mut self, days: u32) {
+        self.lighting.day_night.set_day(days);
+    }
+
+    pub fn moon_phase(&self) -> MoonPhase {
+        self.lighting.day_night.moon.phase
+    }
+
+    pub fn set_palette_theme(&mut self, theme: PaletteTheme) {
+        self.lighting.palette_theme = theme;
+    }
+
+    pub fn palette_theme(&self) -> PaletteTheme {
+        self.lighting.palette_theme
+    }
+
+    /// Применить настройки фильтрации текстур (атлас и тени)
+    pub fn set_sampling(&mut self, sampling: SamplingSettings) {
+        let device = &self.state.device;
+        self.lighting.atlas.apply_sampling(device, &self.state.queue, &self.lighting.layouts.atlas, &sampling);
+        self.lighting.shadow.apply_sampling(device, &self.lighting.layouts.shadow, &sampling);
+        self.lighting.sampling = sampling;
+    }
+
+    pub fn sampling(&self) -> SamplingSettings {
+        self.lighting.sampling
+    }
+
+    /// Применить погоду: облачность неба, слой облаков и частицы осадков
+    pub fn set_weather(&mut self, params: WeatherParams) {
+        self.lighting.day_night.set_overcast(params.overcast);
+        self.components.clouds.set_weather(&params);
+        self.components.weather.set_params(params);
+    }
+
+    /// Залить частицы кадра (обломки, пыль, брызги, дым)
+    pub fn set_particles(&mut self, instances: &[ParticleInstance]) {
+        self.components.particles.update(&self.state.queue, instances);
+    }
+
+    /// Включить/выключить оверлей
+    pub fn set_overlay_mode(&mut self, mode: OverlayMode) {
+        self.components.light_overlay.set_enabled(mode == OverlayMode::LightLevel);
+    }
+
+    /// Залить пересобранный меш тепловой карты освещённости
+    pub fn set_light_overlay(&mut self, vertices: &[OverlayVertex]) {
+        self.components.light_overlay.update(&self.state.queue, vertices);
+    }
+
+    /// Пересобрать текстурный атлас блоков из текущих пакетов ресурсов
+    pub fn reload_block_atlas(&mut self) {
+        self.lighting.atlas.rebuild(&self.state.queue);
+    }
+
     pub fn size(&self) -> winit::dpi::PhysicalSize<u32> {
         self.state.size
     }
@@ -288,14 +609,211 @@ impl Renderer {
         &self.lighting.core_bind_groups.uniform_bind_group
     }
     
+    /// Включить/выключить экранные отражения в воде
+    pub fn set_water_reflections(&mut self, enabled: bool) {
+        self.terrain.water.reflections = enabled;
+    }
+
+    pub fn water_reflections(&self) -> bool {
+        self.terrain.water.reflections
+    }
+
+    pub fn set_fog(&mut self, fog: FogSettings) {
+        self.lighting.fog = fog;
+    }
+
+    pub fn fog(&self) -> FogSettings {
+        self.lighting.fog
+    }
+
+    pub fn set_post(&mut self, post: PostSettings) {
+        self.terrain.post.set_settings(&self.state.queue, post);
+    }
+
+    pub fn post(&self) -> PostSettings {
+        self.terrain.post.settings()
+    }
+
+    /// Рука от первого лица: видимость, блок в руке и зажато ли ломание
+    pub fn set_view_model(&mut self, visible: bool, held: Option<BlockType>, breaking: bool) {
+        self.components.view_model.set_state(&self.state.queue, visible, held, breaking);
+    }
+
+    /// Взмах руки (установка блока)
+    pub fn swing_hand(&mut self) {
+        self.components.view_model.swing();
+    }
+
+    /// Глубина резкости фоторежима (None - выкл)
+    pub fn set_depth_of_field(&mut self, depth_of_field: Option<DepthOfField>) {
+        self.terrain.post.set_depth_of_field(&self.state.queue, depth_of_field);
+    }
+
+    pub fn set_resolution(&mut self, resolution: ResolutionSettings) {
+        if self.terrain.render_scale.set_settings(resolution) {
+            self.recreate_scene_targets();
+        }
+    }
+
+    pub fn resolution(&self) -> ResolutionSettings {
+        self.terrain.render_scale.settings()
+    }
+
+    pub fn set_anti_aliasing(&mut self, anti_aliasing: AntiAliasing) {
+        if anti_aliasing.sample_count() != self.state.sample_count && anti_aliasing != self.terrain.post.anti_aliasing() {
+            log::info!("{}: число сэмплов изменится после перезапуска", anti_aliasing.name());
+        }
+        self.terrain.post.set_anti_aliasing(anti_aliasing);
+    }
+
+    pub fn anti_aliasing(&self) -> AntiAliasing {
+        self.terrain.post.anti_aliasing()
+    }
+
+    pub fn set_cloud_quality(&mut self, quality: CloudQuality) {
+        self.components.clouds.set_quality(quality);
+    }
+
+    pub fn cloud_quality(&self) -> CloudQuality {
+        self.components.clouds.quality()
+    }
+    
     /// Установить дистанции LOD (в чанках)
-    /// distances: [LOD0, LOD1, LOD2, LOD3] - максимальные дистанции для каждого уровня
-    pub fn set_lod_distances(&mut self, distances: [i32; 4]) {
-        self.terrain.terrain_manager.set_lod_distances(distances);
+    /// distances: [LOD0, LOD1, LOD2, LOD3] - максимальные дистанции для каждого уровня.
+    /// Дистанции выравниваются по возрастанию и ограничиваются потолком видеокарты,
+    /// возвращаются фактически применённые
+    pub fn set_lod_distances(&mut self, distances: [i32; 4]) -> [i32; 4] {
+        let applied = self.terrain.lod_budget.clamp(distances);
+        if applied != distances {
+            log::info!("Distances {:?} adjusted to {:?} (max {} chunks)",
+                distances, applied, self.terrain.lod_budget.max_distance);
+        }
+        self.terrain.lod_distances = applied;
+        self.apply_lod_distances();
+        applied
+    }
+    
+    /// Передать дистанции менеджеру террейна с учётом нехватки видеопамяти
+    fn apply_lod_distances(&mut self) {
+        let scaled = scale_distances(self.terrain.lod_distances, self.terrain.memory.scale());
+        self.terrain.terrain_manager.set_lod_distances(self.terrain.lod_budget.clamp(scaled));
+    }
+    
+    /// Проверить видеопамять (раз в кадр). При нехватке дистанции LOD
+    /// снижаются, при долгом запасе - возвращаются к настройкам.
+    /// surface_out_of_memory - кадр не получен из-за нехватки памяти
+    pub fn update_memory_pressure(&mut self, surface_out_of_memory: bool) -> Option<MemoryPressure> {
+        let out_of_memory = self.components.gpu_chunks.take_out_of_memory() || surface_out_of_memory;
+        let used = self.components.gpu_chunks.memory_bytes();
+        let change = self.terrain.memory.check(used, out_of_memory, std::time::Instant::now())?;
+        self.apply_lod_distances();
+        log::info!("Memory pressure {:?}: mesh memory {} MiB, distances {:?}",
+            change, used / (1024 * 1024), self.get_lod_distances());
+        Some(change)
+    }
+    
+    /// Память мешей террейна на GPU (байт)
+    pub fn mesh_memory_bytes(&self) -> u64 {
+        self.components.gpu_chunks.memory_bytes()
+    }
+    
+    /// Чанков террейна получено от генератора за сессию
+    pub fn chunks_received(&self) -> u64 {
+        self.terrain.chunks_received
+    }
+    
+    /// Столбцы чанков, получившие воксельный меш с прошлого вызова
+    pub fn take_loaded_columns(&mut self) -> Vec<(i32, i32)> {
+        std::mem::take(&mut self.terrain.loaded_columns)
+    }
+    
+    /// Дальность уже снижена до минимума
+    pub fn memory_pressure_exhausted(&self) -> bool {
+        self.terrain.memory.exhausted()
     }
     
     /// Получить текущие дистанции LOD
     pub fn get_lod_distances(&self) -> [i32; 4] {
         self.terrain.terrain_manager.get_lod_distances()
     }
+    
+    /// Вертикальная дальность прорисовки в секциях (None - весь столбец)
+    pub fn set_vertical_distance(&mut self, sections: Option<i32>) {
+        self.terrain.section_manager.set_vertical_distance(sections);
+    }
+    
+    /// Фон кадра: небо, под водой - цвет толщи воды
+    fn clear_color(&self) -> ultraviolet::Vec3 {
+        if self.cached.underwater {
+            underwater_fog_color(&self.lighting.day_night)
+        } else {
+            self.lighting.day_night.sky_color
+        }
+    }
+    
+    /// GPU отсечение чанков основного прохода (без compute - CPU цикл в проходе)
+    fn cull_chunks(&mut self, encoder: &mut wgpu::CommandEncoder) {
+        let params = CullParams {
+            view_proj: self.cached.view_proj,
+            vertical_range: self.vertical_range(),
+            underground: self.terrain.underground.ceiling().is_some(),
+        };
+        let components = &mut self.components;
+        if let Some(culling) = &mut components.gpu_culling {
+            culling.prepare(encoder, &components.gpu_chunks, &components.occlusion, &self.terrain.lod_fade, &params);
+        }
+    }
+
+    /// Диапазон высот для прорисовки воксельных чанков вокруг камеры
+    /// (под землёй - не выше потолка пещеры)
+    fn vertical_range(&self) -> Option<(f32, f32)> {
+        let range = self.terrain.section_manager.draw_range(self.cached.position.y);
+        match (range, self.terrain.underground.ceiling()) {
+            (Some((min_y, max_y)), Some(ceiling)) => Some((min_y, max_y.min(ceiling))),
+            (None, Some(ceiling)) => Some((f32::MIN, ceiling)),
+            (range, None) => range,
+        }
+    }
+    
+    /// Установить качество дальних LOD (0-1, 1.0 - без упрощения)
+    pub fn set_lod_quality(&mut self, quality: f32) {
+        self.terrain.terrain_manager.set_lod_quality(quality);
+    }
+
+    /// Заменить менеджер terrain на загруженный вместе с миром
+    /// (он уже сгенерировал область вокруг игрока)
+    pub fn set_terrain_manager(&mut self, manager: HybridTerrainManager) {
+        self.terrain.terrain_manager = manager;
+        self.apply_lod_distances();
+    }
+
+    /// Загрузить на GPU чанк начальной области (экран загрузки)
+    pub fn upload_chunk(&mut self, chunk: &GeneratedChunkData) {
+        if chunk.key.is_voxel() {
+            self.terrain.loaded_columns.push((chunk.key.x, chunk.key.z));
+        }
+        self.components.gpu_chunks.upload(chunk.key, &chunk.vertices, &chunk.indices);
+        self.components.impostors.set_chunk(chunk.key, &chunk.impostors);
+    }
+
+    /// Отправить накопленные импосторы после upload_chunk
+    pub fn flush_uploads(&mut self) {
+        self.components.impostors.flush(&self.state.queue);
+    }
+
+    /// Кадр без 3D сцены: только интерфейс поверх очищенного surface
+    pub fn render_overlay<F>(&mut self, gui_render: F) -> Result<(), wgpu::SurfaceError>
+    where
+        F: FnOnce(&wgpu::Device, &mut wgpu::CommandEncoder, &wgpu::TextureView, &wgpu::Queue),
+    {
+        let output = self.state.surface.get_current_texture()?;
+        let view = output.texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let mut encoder = self.state.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Overlay Encoder"),
+        });
+        gui_render(&self.state.device, &mut encoder, &view, &self.state.queue);
+        self.state.queue.submit(std::iter::once(encoder.finish()));
+        output.present();
+        Ok(())
+    }
 }
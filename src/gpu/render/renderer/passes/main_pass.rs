@@ -1,16 +1,20 @@
 use crate::gpu::render::pipelines::Pipelines;
 use crate::gpu::render::bind_groups::{CoreBindGroups, AtlasResources};
 use crate::gpu::render::shadow::ShadowResources;
+use crate::gpu::render::post::SceneTarget;
 
 use crate::gpu::render::renderer::core::{RenderComponents, LightingResources};
-use crate::gpu::render::renderer::culling::is_chunk_visible;
+use crate::gpu::render::renderer::culling::{is_chunk_visible, is_gpu_chunk_visible};
+use crate::gpu::render::renderer::lod_fade::LodFade;
+use crate::gpu::terrain::mesh::MeshSection;
+use crate::gpu::profiler::GpuPass;
 
 /// Main 3D pass — основной рендеринг сцены
 pub fn render<'a>(
     encoder: &'a mut wgpu::CommandEncoder,
-    view: &'a wgpu::TextureView,
+    scene: SceneTarget<'a>,
     depth_texture: &'a wgpu::TextureView,
-    sky_color: ultraviolet::Vec3,
+    clear_color: ultraviolet::Vec3,
     cached_view_proj: &[[f32; 4]; 4],
     pipelines: &'a Pipelines,
     core_bind_groups: &'a CoreBindGroups,
@@ -19,22 +23,18 @@ pub fn render<'a>(
     components: &'a RenderComponents,
     render_player: bool,
     highlight_block: Option<[i32; 3]>,
+    vertical_range: Option<(f32, f32)>,
+    underground: bool,
+    lod_fade: &LodFade,
 ) {
     let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
         label: Some("Main Pass"),
-        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-            view,
-            resolve_target: None,
-            ops: wgpu::Operations {
-                load: wgpu::LoadOp::Clear(wgpu::Color {
-                    r: sky_color.x as f64,
-                    g: sky_color.y as f64,
-                    b: sky_color.z as f64,
-                    a: 1.0,
-                }),
-                store: wgpu::StoreOp::Store,
-            },
-        })],
+        color_attachments: &[Some(scene.attachment(wgpu::LoadOp::Clear(wgpu::Color {
+            r: clear_color.x as f64,
+            g: clear_color.y as f64,
+            b: clear_color.z as f64,
+            a: 1.0,
+        })))],
         depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
             view: depth_texture,
             depth_ops: Some(wgpu::Operations {
@@ -43,8 +43,8 @@ pub fn render<'a>(
             }),
             stencil_ops: None,
         }),
-        timestamp_writes: None,
-        occlusion_query_set: None,
+        timestamp_writes: components.gpu_timer.as_ref().and_then(|t| t.pass_writes(GpuPass::Main, true, true)),
+        occlusion_query_set: components.occlusion.query_set(),
     });
 
     // Celestial (sun/moon) — на заднем плане
@@ -57,14 +57,40 @@ pub fn render<'a>(
     render_pass.set_bind_group(2, &shadow.bind_group, &[]);
     render_pass.set_bind_group(3, &atlas.bind_group, &[]);
 
-    for gpu_chunk in components.gpu_chunks.iter() {
-        if is_chunk_visible(cached_view_proj, gpu_chunk.key.x, gpu_chunk.key.z, gpu_chunk.key.scale) {
-            render_pass.set_vertex_buffer(0, gpu_chunk.vertex_buffer.slice(..));
-            render_pass.set_index_buffer(gpu_chunk.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
-            render_pass.draw_indexed(0..gpu_chunk.index_count, 0, 0..1);
-        }
+    if let Some(culling) = &components.gpu_culling {
+        // Отсечено compute проходом (GpuCulling::prepare)
+        culling.draw(&mut render_pass, &components.gpu_chunks);
+    } else {
+        draw_visible_chunks(&mut render_pass, components, lod_fade, cached_view_proj, vertical_range, underground);
+    }
+
+    // Чанки в переходе между LOD - дизерингом старого и нового меша
+    lod_fade.draw(&mut render_pass, &pipelines.terrain_dither, &components.gpu_chunks, |chunk| {
+        !(underground && !chunk.key.is_voxel())
+            && is_gpu_chunk_visible(cached_view_proj, chunk)
+    });
+
+    // Прокси чанков для occlusion queries (после непрозрачного terrain)
+    components.occlusion.render_proxies(&mut render_pass);
+
+    // Tree impostors (дальние LOD)
+    if !underground {
+        components.impostors.render(
+            &mut render_pass,
+            &core_bind_groups.uniform_bind_group,
+            &core_bind_groups.light_bind_group,
+            |x, z, scale| is_chunk_visible(cached_view_proj, x, z, scale),
+        );
     }
 
+    // Decorations (инстансинг, куллинг по чанкам)
+    components.decorations.render(
+        &mut render_pass,
+        &core_bind_groups.uniform_bind_group,
+        &core_bind_groups.light_bind_group,
+        |x, z| is_chunk_visible(cached_view_proj, x, z, 1),
+    );
+
     // Player
     if render_player {
         render_pass.set_pipeline(&pipelines.player);
@@ -72,8 +98,81 @@ pub fn render<'a>(
         components.player_model.render(&mut render_pass);
     }
 
+    // Light overlay (тепловая карта освещённости, F9)
+    components.light_overlay.render(&mut render_pass, &core_bind_groups.uniform_bind_group);
+
+    // Particles (после непрозрачной геометрии, без записи глубины)
+    components.particles.render(
+        &mut render_pass,
+        &core_bind_groups.uniform_bind_group,
+        &core_bind_groups.light_bind_group,
+    );
+
+    // Трещины ломаемого блока
+    components.break_overlay.render(&mut render_pass);
+
     // Block highlight
     if highlight_block.is_some() {
         components.block_highlight.render(&mut render_pass);
     }
+    
+    // Рамка выделения палочкой
+    components.selection_box.render(&mut render_pass);
+}
+
+/// CPU отсечение и отрисовка чанков (без GPU отсечения)
+fn draw_visible_chunks(
+    render_pass: &mut wgpu::RenderPass,
+    components: &RenderComponents,
+    lod_fade: &LodFade,
+    cached_view_proj: &[[f32; 4]; 4],
+    vertical_range: Option<(f32, f32)>,
+    underground: bool,
+) {
+    for gpu_chunk in components.gpu_chunks.iter().filter(|chunk| !lod_fade.is_fading(&chunk.key)) {
+        // Под землёй дальние LOD (только поверхность) не видны
+        if underground && !gpu_chunk.key.is_voxel() {
+            continue;
+        }
+        // Закрыт рельефом по результатам occlusion queries
+        if components.occlusion.is_occluded(&gpu_chunk.key) {
+            continue;
+        }
+        if is_gpu_chunk_visible(cached_view_proj, gpu_chunk) {
+            render_pass.set_vertex_buffer(0, gpu_chunk.vertex_slice());
+            render_pass.set_index_buffer(gpu_chunk.index_slice(), wgpu::IndexFormat::Uint32);
+            match vertical_range {
+                // Вертикальный куллинг только для воксельных чанков:
+                // LOD - это поверхность, её видно с любой высоты
+                Some((min_y, max_y)) if gpu_chunk.key.is_voxel() => {
+                    draw_sections_in_range(render_pass, &gpu_chunk.sections, min_y, max_y);
+                }
+                _ => render_pass.draw_indexed(0..gpu_chunk.index_count, 0, 0..1),
+            }
+        }
+    }
+}
+
+/// Рисует секции меша в диапазоне высот; соседние секции идут одним вызовом
+fn draw_sections_in_range(
+    render_pass: &mut wgpu::RenderPass,
+    sections: &[MeshSection],
+    min_y: f32,
+    max_y: f32,
+) {
+    let mut run: Option<std::ops::Range<u32>> = None;
+    for section in sections.iter().filter(|s| s.overlaps(min_y, max_y)) {
+        let range = section.start..section.start + section.count;
+        run = match run {
+            Some(current) if current.end == range.start => Some(current.start..range.end),
+            Some(current) => {
+                render_pass.draw_indexed(current, 0, 0..1);
+                Some(range)
+            }
+            None => Some(range),
+        };
+    }
+    if let Some(current) = run {
+        render_pass.draw_indexed(current, 0, 0..1);
+    }
 }
@@ -1,4 +1,8 @@
 pub mod shadow;
 pub mod main_pass;
+pub mod translucent;
+pub mod water;
 pub mod ui;
 pub mod subvoxel;
+pub mod weather;
+pub mod viewmodel;
@@ -1,9 +1,10 @@
 use crate::gpu::terrain::GpuChunkManager;
 use crate::gpu::render::pipelines::Pipelines;
 use crate::gpu::render::shadow::ShadowResources;
-use crate::gpu::subvoxel::SubVoxelRenderer;
+use crate::gpu::subvoxel::OptimizedSubVoxelRenderer;
+use crate::gpu::profiler::{GpuPass, GpuTimer};
 
-use crate::gpu::render::renderer::culling::is_chunk_visible;
+use crate::gpu::render::renderer::culling::{is_chunk_visible, is_gpu_chunk_visible};
 
 /// Shadow pass — рендеринг теней для всех каскадов
 pub fn render(
@@ -11,9 +12,11 @@ pub fn render(
     shadow: &ShadowResources,
     pipelines: &Pipelines,
     gpu_chunks: &GpuChunkManager,
-    subvoxel_renderer: Option<&SubVoxelRenderer>,
+    subvoxel_renderer: Option<&OptimizedSubVoxelRenderer>,
+    timer: Option<&GpuTimer>,
 ) {
-    for i in 0..shadow.config.num_cascades {
+    let cascades = shadow.config.num_cascades;
+    for i in 0..cascades {
         let cascade_matrix = shadow.uniform.light_vp[i];
         
         let mut shadow_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
@@ -27,7 +30,8 @@ pub fn render(
                 }),
                 stencil_ops: None,
             }),
-            timestamp_writes: None,
+            // Замер от первого каскада до конца последнего
+            timestamp_writes: timer.and_then(|t| t.pass_writes(GpuPass::Shadow, i == 0, i + 1 == cascades)),
             occlusion_query_set: None,
         });
 
@@ -36,17 +40,23 @@ pub fn render(
 
         // Рендерим terrain chunks
         for gpu_chunk in gpu_chunks.iter() {
-            if is_chunk_visible(&cascade_matrix, gpu_chunk.key.x, gpu_chunk.key.z, gpu_chunk.key.scale) {
-                shadow_pass.set_vertex_buffer(0, gpu_chunk.vertex_buffer.slice(..));
-                shadow_pass.set_index_buffer(gpu_chunk.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+            if is_gpu_chunk_visible(&cascade_matrix, gpu_chunk) {
+                shadow_pass.set_vertex_buffer(0, gpu_chunk.vertex_slice());
+                shadow_pass.set_index_buffer(gpu_chunk.index_slice(), wgpu::IndexFormat::Uint32);
                 shadow_pass.draw_indexed(0..gpu_chunk.index_count, 0, 0..1);
             }
         }
         
-        // Рендерим субвоксели в shadow map (по чанкам)
-        if let Some(sv_renderer) = subvoxel_renderer {
-            for (vertex_buffer, index_buffer, num_indices) in sv_renderer.iter_chunks() {
+        // Субвоксели в shadow map: свой pipeline (PackedVertex + угол
+        // чанка), чанки вне каскада отсекаются как у terrain
+        if let Some(sv_renderer) = subvoxel_renderer.filter(|r| r.has_content()) {
+            shadow_pass.set_pipeline(&pipelines.subvoxel_shadow);
+            for (key, vertex_buffer, index_buffer, origin_buffer, num_indices) in sv_renderer.iter_chunk_buffers() {
+                if !is_chunk_visible(&cascade_matrix, key.x, key.z, 1) {
+                    continue;
+                }
                 shadow_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+                shadow_pass.set_vertex_buffer(1, origin_buffer.slice(..));
                 shadow_pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
                 shadow_pass.draw_indexed(0..num_indices, 0, 0..1);
             }
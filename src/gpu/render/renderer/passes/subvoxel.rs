@@ -1,30 +1,26 @@
 use crate::gpu::render::pipelines::Pipelines;
 use crate::gpu::render::bind_groups::{CoreBindGroups, AtlasResources};
 use crate::gpu::render::shadow::ShadowResources;
-use crate::gpu::subvoxel::SubVoxelRenderer;
+use crate::gpu::render::post::SceneTarget;
+use crate::gpu::subvoxel::OptimizedSubVoxelRenderer;
+use crate::gpu::profiler::{GpuPass, GpuTimer};
 
 /// SubVoxel pass — рендеринг ку-вокселей
 /// Оптимизировано: рендерит каждый чанк отдельным draw call
 pub fn render<'a>(
     encoder: &'a mut wgpu::CommandEncoder,
-    view: &'a wgpu::TextureView,
+    scene: SceneTarget<'a>,
     depth_texture: &'a wgpu::TextureView,
     pipelines: &'a Pipelines,
     core_bind_groups: &'a CoreBindGroups,
     shadow: &'a ShadowResources,
     atlas: &'a AtlasResources,
-    subvoxel_renderer: &'a SubVoxelRenderer,
+    subvoxel_renderer: &'a OptimizedSubVoxelRenderer,
+    timer: Option<&'a GpuTimer>,
 ) {
     let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
         label: Some("SubVoxel Pass"),
-        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-            view,
-            resolve_target: None,
-            ops: wgpu::Operations {
-                load: wgpu::LoadOp::Load, // Не очищаем, рисуем поверх
-                store: wgpu::StoreOp::Store,
-            },
-        })],
+        color_attachments: &[Some(scene.attachment(wgpu::LoadOp::Load))], // Не очищаем, рисуем поверх
         depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
             view: depth_texture,
             depth_ops: Some(wgpu::Operations {
@@ -33,20 +29,21 @@ pub fn render<'a>(
             }),
             stencil_ops: None,
         }),
-        timestamp_writes: None,
+        timestamp_writes: timer.and_then(|t| t.pass_writes(GpuPass::SubVoxel, true, true)),
         occlusion_query_set: None,
     });
 
-    // Используем terrain pipeline для суб-вокселей (тот же формат вершин)
-    render_pass.set_pipeline(&pipelines.terrain);
+    // Освещение и тени как у terrain, вершины - PackedVertex
+    render_pass.set_pipeline(&pipelines.subvoxel);
     render_pass.set_bind_group(0, &core_bind_groups.uniform_bind_group, &[]);
     render_pass.set_bind_group(1, &core_bind_groups.light_bind_group, &[]);
     render_pass.set_bind_group(2, &shadow.bind_group, &[]);
     render_pass.set_bind_group(3, &atlas.bind_group, &[]);
     
     // Рендерим каждый чанк отдельно
-    for (vertex_buffer, index_buffer, num_indices) in subvoxel_renderer.iter_chunks() {
+    for (_, vertex_buffer, index_buffer, origin_buffer, num_indices) in subvoxel_renderer.iter_chunk_buffers() {
         render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+        render_pass.set_vertex_buffer(1, origin_buffer.slice(..));
         render_pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
         render_pass.draw_indexed(0..num_indices, 0, 0..1);
     }
@@ -0,0 +1,91 @@
+use ultraviolet::Vec3;
+
+use crate::gpu::render::pipelines::Pipelines;
+use crate::gpu::render::bind_groups::{CoreBindGroups, AtlasResources};
+use crate::gpu::render::shadow::ShadowResources;
+use crate::gpu::render::post::SceneTarget;
+
+use crate::gpu::render::renderer::core::RenderComponents;
+use crate::gpu::render::renderer::culling::is_gpu_chunk_visible;
+use crate::gpu::terrain::GpuChunk;
+use crate::gpu::terrain::mesh::MeshSection;
+
+/// Translucent pass — стекло, лёд поверх непрозрачной сцены и воды.
+/// Чанки рисуются от дальних к ближним, глубина только читается
+pub fn render<'a>(
+    encoder: &'a mut wgpu::CommandEncoder,
+    scene: SceneTarget<'a>,
+    depth_texture: &'a wgpu::TextureView,
+    camera_pos: Vec3,
+    cached_view_proj: &[[f32; 4]; 4],
+    pipelines: &'a Pipelines,
+    core_bind_groups: &'a CoreBindGroups,
+    shadow: &'a ShadowResources,
+    atlas: &'a AtlasResources,
+    components: &'a RenderComponents,
+    vertical_range: Option<(f32, f32)>,
+    underground: bool,
+) {
+    let chunks = back_to_front(components, camera_pos, cached_view_proj, underground, |chunk| &chunk.translucent_sections);
+    if chunks.is_empty() {
+        return;
+    }
+
+    let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("Translucent Pass"),
+        color_attachments: &[Some(scene.attachment(wgpu::LoadOp::Load))],
+        depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+            view: depth_texture,
+            depth_ops: Some(wgpu::Operations {
+                load: wgpu::LoadOp::Load,
+                store: wgpu::StoreOp::Store,
+            }),
+            stencil_ops: None,
+        }),
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+
+    render_pass.set_pipeline(&pipelines.translucent);
+    render_pass.set_bind_group(0, &core_bind_groups.uniform_bind_group, &[]);
+    render_pass.set_bind_group(1, &core_bind_groups.light_bind_group, &[]);
+    render_pass.set_bind_group(2, &shadow.bind_group, &[]);
+    render_pass.set_bind_group(3, &atlas.bind_group, &[]);
+
+    for gpu_chunk in chunks {
+        render_pass.set_vertex_buffer(0, gpu_chunk.vertex_slice());
+        render_pass.set_index_buffer(gpu_chunk.index_slice(), wgpu::IndexFormat::Uint32);
+        for section in &gpu_chunk.translucent_sections {
+            // Вертикальный куллинг как в main pass (только воксельные чанки)
+            if let Some((min_y, max_y)) = vertical_range {
+                if gpu_chunk.key.is_voxel() && !section.overlaps(min_y, max_y) {
+                    continue;
+                }
+            }
+            render_pass.draw_indexed(section.start..section.start + section.count, 0, 0..1);
+        }
+    }
+}
+
+/// Видимые чанки с непустыми секциями `sections`, от дальних к ближним:
+/// смешивание ближних ложится поверх дальних
+pub(super) fn back_to_front<'a>(
+    components: &'a RenderComponents,
+    camera_pos: Vec3,
+    cached_view_proj: &[[f32; 4]; 4],
+    underground: bool,
+    sections: fn(&GpuChunk) -> &Vec<MeshSection>,
+) -> Vec<&'a GpuChunk> {
+    let mut chunks: Vec<(&GpuChunk, f32)> = components.gpu_chunks.iter()
+        .filter(|chunk| !sections(chunk).is_empty())
+        .filter(|chunk| !(underground && !chunk.key.is_voxel()))
+        .filter(|chunk| !components.occlusion.is_occluded(&chunk.key))
+        .filter(|chunk| is_gpu_chunk_visible(cached_view_proj, chunk))
+        .map(|chunk| {
+            let center = Vec3::from(chunk.center());
+            (chunk, (center - camera_pos).mag_sq())
+        })
+        .collect();
+    chunks.sort_by(|a, b| b.1.total_cmp(&a.1));
+    chunks.into_iter().map(|(chunk, _)| chunk).collect()
+}
@@ -1,4 +1,5 @@
 use crate::gpu::render::renderer::core::RenderComponents;
+use crate::gpu::profiler::GpuPass;
 
 /// UI pass — рендеринг интерфейса (crosshair, FPS)
 pub fn render<'a>(
@@ -17,7 +18,7 @@ pub fn render<'a>(
             },
         })],
         depth_stencil_attachment: None,
-        timestamp_writes: None,
+        timestamp_writes: components.gpu_timer.as_ref().and_then(|t| t.pass_writes(GpuPass::Ui, true, true)),
         occlusion_query_set: None,
     });
     
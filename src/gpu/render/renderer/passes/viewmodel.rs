@@ -0,0 +1,29 @@
+use crate::gpu::render::post::SceneTarget;
+
+use crate::gpu::render::renderer::core::RenderComponents;
+
+/// View model pass — рука от первого лица поверх всей сцены. Глубина
+/// общая с миром, но рука пишет её в своём ближнем диапазоне
+pub fn render<'a>(
+    encoder: &'a mut wgpu::CommandEncoder,
+    scene: SceneTarget<'a>,
+    depth_texture: &'a wgpu::TextureView,
+    components: &'a RenderComponents,
+) {
+    let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("View Model Pass"),
+        color_attachments: &[Some(scene.attachment(wgpu::LoadOp::Load))],
+        depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+            view: depth_texture,
+            depth_ops: Some(wgpu::Operations {
+                load: wgpu::LoadOp::Load,
+                store: wgpu::StoreOp::Store,
+            }),
+            stencil_ops: None,
+        }),
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+
+    components.view_model.render(&mut render_pass);
+}
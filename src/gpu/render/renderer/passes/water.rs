@@ -0,0 +1,71 @@
+use ultraviolet::Vec3;
+
+use crate::gpu::render::pipelines::Pipelines;
+use crate::gpu::render::bind_groups::CoreBindGroups;
+use crate::gpu::render::shadow::ShadowResources;
+use crate::gpu::render::water::WaterResources;
+use crate::gpu::render::post::SceneTarget;
+
+use crate::gpu::render::renderer::core::RenderComponents;
+use super::translucent::back_to_front;
+
+/// Water pass — поверхность воды поверх непрозрачной сцены.
+/// Перед проходом кадр копируется (источник отражений), глубина
+/// подключена только на чтение и одновременно читается шейдером
+/// (при MSAA шейдер читает свёрнутую копию - DepthResolve)
+pub fn render<'a>(
+    encoder: &'a mut wgpu::CommandEncoder,
+    scene: SceneTarget<'a>,
+    frame: &wgpu::Texture,
+    depth_texture: &'a wgpu::TextureView,
+    camera_pos: Vec3,
+    cached_view_proj: &[[f32; 4]; 4],
+    pipelines: &'a Pipelines,
+    core_bind_groups: &'a CoreBindGroups,
+    shadow: &'a ShadowResources,
+    water: &'a WaterResources,
+    components: &'a RenderComponents,
+    vertical_range: Option<(f32, f32)>,
+    underground: bool,
+) {
+    let chunks = back_to_front(components, camera_pos, cached_view_proj, underground, |chunk| &chunk.water_sections);
+    if chunks.is_empty() {
+        return;
+    }
+
+    if water.reflections_active() {
+        water.copy_scene(encoder, frame);
+    }
+
+    let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("Water Pass"),
+        color_attachments: &[Some(scene.attachment(wgpu::LoadOp::Load))],
+        depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+            view: depth_texture,
+            // Read-only: глубина одновременно привязана как текстура
+            depth_ops: None,
+            stencil_ops: None,
+        }),
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+
+    render_pass.set_pipeline(&pipelines.water);
+    render_pass.set_bind_group(0, &core_bind_groups.uniform_bind_group, &[]);
+    render_pass.set_bind_group(1, &core_bind_groups.light_bind_group, &[]);
+    render_pass.set_bind_group(2, &shadow.bind_group, &[]);
+    render_pass.set_bind_group(3, &water.bind_group, &[]);
+
+    for gpu_chunk in chunks {
+        render_pass.set_vertex_buffer(0, gpu_chunk.vertex_slice());
+        render_pass.set_index_buffer(gpu_chunk.index_slice(), wgpu::IndexFormat::Uint32);
+        for section in &gpu_chunk.water_sections {
+            if let Some((min_y, max_y)) = vertical_range {
+                if gpu_chunk.key.is_voxel() && !section.overlaps(min_y, max_y) {
+                    continue;
+                }
+            }
+            render_pass.draw_indexed(section.start..section.start + section.count, 0, 0..1);
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use crate::gpu::render::post::SceneTarget;
+
+use crate::gpu::render::renderer::core::RenderComponents;
+
+/// Weather pass — облака, стена границы мира и осадки поверх всей геометрии
+/// (включая суб-воксели). Полупрозрачные, поэтому идут последними перед UI;
+/// depth только читается
+pub fn render<'a>(
+    encoder: &'a mut wgpu::CommandEncoder,
+    scene: SceneTarget<'a>,
+    depth_texture: &'a wgpu::TextureView,
+    components: &'a RenderComponents,
+) {
+    let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("Weather Pass"),
+        color_attachments: &[Some(scene.attachment(wgpu::LoadOp::Load))],
+        depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+            view: depth_texture,
+            depth_ops: Some(wgpu::Operations {
+                load: wgpu::LoadOp::Load,
+                store: wgpu::StoreOp::Store,
+            }),
+            stencil_ops: None,
+        }),
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+
+    // Облака дальше стены и осадков - первыми
+    components.clouds.render(&mut render_pass);
+    components.world_border.render(&mut render_pass);
+    components.weather.render(&mut render_pass);
+}
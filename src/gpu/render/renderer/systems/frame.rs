@@ -2,6 +2,10 @@ use crate::gpu::render::uniforms::{Uniforms, LightUniform};
 use crate::gpu::player::Camera;
 use crate::gpu::player::Player;
 use crate::gpu::terrain::WorldChanges;
+use crate::gpu::terrain::generation::world_gen_config;
+use crate::gpu::biomes::climate_map;
+use crate::gpu::render::fog::biome_fog_tint;
+use crate::gpu::profiler;
 
 use crate::gpu::render::renderer::core::{RenderComponents, LightingResources, TerrainResources, CachedCamera};
 
@@ -18,14 +22,27 @@ pub fn update(
     terrain: &mut TerrainResources,
     cached: &mut CachedCamera,
 ) {
-    // День/ночь
+    // День/ночь и облака (смещение облаков нужно теням в Uniforms)
     lighting.day_night.update(dt);
+    components.clouds.advance(dt);
 
     // Uniforms
+    cached.update(camera);
     let mut uniforms = Uniforms::new();
     uniforms.update(camera, time);
     uniforms.update_day_night(&lighting.day_night);
-    cached.update(&uniforms, camera.view_matrix(), camera.projection_matrix(), camera.position);
+    uniforms.set_palette_theme(lighting.palette_theme);
+    uniforms.set_sea_level(world_gen_config().sea_level);
+    uniforms.set_clouds(components.clouds.layer().shadow_params());
+    let climate = climate_map().sample(camera.position.x, camera.position.z);
+    uniforms.set_fog(
+        &lighting.fog,
+        terrain.terrain_manager.get_lod_distances()[3],
+        biome_fog_tint(climate.temperature, climate.humidity),
+    );
+    if cached.underwater {
+        uniforms.set_underwater(&lighting.day_night);
+    }
     
     queue.write_buffer(
         &lighting.core_bind_groups.uniform_buffer,
@@ -47,27 +64,71 @@ pub fn update(
         bytemuck::cast_slice(&[light]),
     );
 
+    // Water (плоскости камеры для линеаризации глубины в SSR)
+    terrain.water.update(queue, cached.near, cached.far);
+    terrain.post.set_clip_planes(queue, cached.near, cached.far);
+
     // Shadows
     lighting.shadow.update(queue, camera.position, &lighting.day_night);
 
     // Celestial
-    components.celestial.update(queue, cached.view_proj, camera.position, &lighting.day_night);
+    components.celestial.update(queue, cached.view_proj, camera.position, time, &lighting.day_night);
+
+    // Clouds
+    components.clouds.update(queue, cached.view_proj, camera.position, &lighting.day_night);
+
+    // Weather (частицы осадков)
+    components.weather.update(queue, cached.view_proj, camera.position, time, &lighting.day_night);
+
+    // Стена границы мира
+    components.world_border.update(queue, cached.view_proj, camera.position, time);
+
+    // Decorations
+    components.decorations.update(queue, camera.position, world_changes);
 
     // Player model
-    components.player_model.update(queue, player);
+    components.player_model.update(queue, player, dt);
+
+    // Рука от первого лица: светлее днём, но не в полной темноте
+    let brightness = (0.4 + lighting.day_night.ambient_intensity * 1.5).min(1.0);
+    components.view_model.update(queue, dt, camera.aspect, brightness);
 
-    // Terrain
+    // Terrain (стриминг вокруг камеры - в демо-облёте она далеко от игрока);
+    // воксельные меши - только в вертикальной дальности от камеры
+    if let Some(band) = terrain.section_manager.update(camera.position.y) {
+        terrain.terrain_manager.set_vertical_band(band);
+    }
     terrain.terrain_manager.update(
-        player.position.x,
-        player.position.z,
+        camera.position.x,
+        camera.position.z,
         &world_changes.get_all_changes_copy(),
         world_changes.version(),
     );
 
+    // Под землёй поверхность не видна
+    terrain.underground.update(camera.position, world_changes);
+
     if let Some(mesh) = terrain.terrain_manager.try_get_mesh() {
-        components.gpu_chunks.retain_only(&mesh.required_keys);
+        let _scope = profiler::scope("upload");
+        // Пропавшие чанки доживают до замены (плавная смена LOD)
+        let keep = terrain.lod_fade.retire(&components.gpu_chunks, &mesh.required_keys);
+        components.gpu_chunks.retain_only(&keep);
+        components.impostors.retain_only(&mesh.required_keys);
+        terrain.chunks_received += mesh.new_chunks.len() as u64;
         for chunk_data in mesh.new_chunks {
+            if !components.gpu_chunks.contains(&chunk_data.key) {
+                terrain.lod_fade.add_new(chunk_data.key);
+            }
+            if chunk_data.key.is_voxel() {
+                terrain.loaded_columns.push((chunk_data.key.x, chunk_data.key.z));
+            }
             components.gpu_chunks.upload(chunk_data.key, &chunk_data.vertices, &chunk_data.indices);
+            components.impostors.set_chunk(chunk_data.key, &chunk_data.impostors);
         }
+        components.impostors.flush(queue);
     }
+    terrain.lod_fade.update(queue, dt, &mut components.gpu_chunks);
+
+    // Occlusion queries: результаты прошлых кадров и прокси на этот
+    components.occlusion.prepare(queue, &components.gpu_chunks, cached.view_proj, camera.position);
 }
@@ -1,4 +1,4 @@
-use crate::gpu::terrain::voxel::{VoxelChunk, ChunkNeighbors, CHUNK_SIZE, MIN_HEIGHT};
+use crate::gpu::terrain::voxel::{VoxelChunk, ChunkNeighbors, CHUNK_SIZE, SECTION_SIZE, section_index, section_min_y};
 use crate::gpu::terrain::{GpuChunkManager, ChunkKey};
 use crate::gpu::terrain::WorldChanges;
 
@@ -12,9 +12,9 @@ pub fn instant_chunk_update(
 ) {
     let chunk_x = block_x.div_euclid(CHUNK_SIZE);
     let chunk_z = block_z.div_euclid(CHUNK_SIZE);
-    let section_y = (block_y - MIN_HEIGHT).div_euclid(16);
-    let section_min_y = MIN_HEIGHT + section_y * 16;
-    let section_max_y = section_min_y + 15;
+    let section = section_index(block_y);
+    let min_y = section_min_y(section);
+    let max_y = min_y + SECTION_SIZE - 1;
 
     let changes = world_changes.get_all_changes_copy();
     let chunk = VoxelChunk::new(chunk_x, chunk_z, &changes);
@@ -24,10 +24,10 @@ pub fn instant_chunk_update(
         pos_z: None,
         neg_z: None,
     };
-    let (vertices, indices) = chunk.generate_mesh_section(&neighbors, section_min_y, section_max_y);
+    let (vertices, indices) = chunk.generate_mesh_section(&neighbors, min_y, max_y);
 
     if !vertices.is_empty() {
-        let key = ChunkKey::new_section(chunk_x, chunk_z, section_y);
+        let key = ChunkKey::new_section(chunk_x, chunk_z, section as i32);
         gpu_chunks.upload(key, &vertices, &indices);
     }
 }
@@ -0,0 +1,134 @@
+// ============================================
+// Resolution - Масштаб внутреннего разрешения
+// ============================================
+// 3D проходы рисуются в цели размера окна * scale, композиция
+// постобработки растягивает кадр до surface (билинейно), UI - в
+// родном разрешении. Динамический режим раз в секунду по замеру
+// FpsCounter уменьшает масштаб, пока FPS ниже цели, и возвращает
+// его (не выше выбранного) при запасе.
+
+use serde::{Serialize, Deserialize};
+
+pub const MIN_RENDER_SCALE: f32 = 0.5;
+pub const MAX_RENDER_SCALE: f32 = 2.0;
+/// Шаг динамического масштаба за один замер FPS
+const DYNAMIC_STEP: f32 = 0.1;
+/// Во сколько раз FPS должен превышать цель, чтобы масштаб рос обратно
+const DYNAMIC_HEADROOM: f32 = 1.2;
+
+/// Настройки разрешения (секция graphics.resolution в settings.json)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResolutionSettings {
+    /// Масштаб 3D кадра (0.5-2.0); в динамическом режиме - потолок
+    pub scale: f32,
+    pub dynamic: bool,
+    /// Цель динамического режима
+    pub target_fps: u32,
+}
+
+impl Default for ResolutionSettings {
+    fn default() -> Self {
+        Self { scale: 1.0, dynamic: false, target_fps: 60 }
+    }
+}
+
+impl ResolutionSettings {
+    fn max_scale(&self) -> f32 {
+        self.scale.clamp(MIN_RENDER_SCALE, MAX_RENDER_SCALE)
+    }
+}
+
+/// Текущий масштаб с учётом динамического режима
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderScale {
+    settings: ResolutionSettings,
+    current: f32,
+}
+
+impl RenderScale {
+    pub fn new(settings: ResolutionSettings) -> Self {
+        Self { settings, current: settings.max_scale() }
+    }
+
+    pub fn settings(&self) -> ResolutionSettings {
+        self.settings
+    }
+
+    pub fn current(&self) -> f32 {
+        self.current
+    }
+
+    /// Новые настройки; true - масштаб изменился (цели пересоздать)
+    pub fn set_settings(&mut self, settings: ResolutionSettings) -> bool {
+        self.settings = settings;
+        let scale = if settings.dynamic {
+            self.current.min(settings.max_scale())
+        } else {
+            settings.max_scale()
+        };
+        self.set_current(scale)
+    }
+
+    /// Подстроить масштаб по замеру FPS; true - масштаб изменился
+    pub fn adapt(&mut self, fps: u32) -> bool {
+        if !self.settings.dynamic || self.settings.target_fps == 0 {
+            return false;
+        }
+        let target = self.settings.target_fps as f32;
+        let fps = fps as f32;
+        let scale = if fps < target {
+            (self.current - DYNAMIC_STEP).max(MIN_RENDER_SCALE)
+        } else if fps > target * DYNAMIC_HEADROOM {
+            (self.current + DYNAMIC_STEP).min(self.settings.max_scale())
+        } else {
+            self.current
+        };
+        self.set_current(scale)
+    }
+
+    /// Размер 3D целей для окна
+    pub fn scene_size(&self, width: u32, height: u32) -> (u32, u32) {
+        (
+            ((width as f32 * self.current).round() as u32).max(1),
+            ((height as f32 * self.current).round() as u32).max(1),
+        )
+    }
+
+    fn set_current(&mut self, scale: f32) -> bool {
+        let changed = (scale - self.current).abs() > 0.001;
+        self.current = scale;
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn static_scale_sizes_targets() {
+        let mut scale = RenderScale::new(ResolutionSettings::default());
+        assert_eq!(scale.scene_size(1920, 1080), (1920, 1080));
+        assert!(scale.set_settings(ResolutionSettings { scale: 0.5, ..Default::default() }));
+        assert_eq!(scale.scene_size(1920, 1080), (960, 540));
+        assert!(!scale.adapt(10));
+    }
+
+    #[test]
+    fn dynamic_scale_follows_fps() {
+        let mut scale = RenderScale::new(ResolutionSettings { scale: 1.0, dynamic: true, target_fps: 60 });
+        assert!(scale.adapt(40));
+        assert!(scale.current() < 1.0);
+        for _ in 0..20 {
+            scale.adapt(30);
+        }
+        assert_eq!(scale.current(), MIN_RENDER_SCALE);
+        // В зоне около цели масштаб не дёргается
+        assert!(!scale.adapt(65));
+        for _ in 0..20 {
+            scale.adapt(144);
+        }
+        assert_eq!(scale.current(), 1.0);
+    }
+}
@@ -0,0 +1,126 @@
+// ============================================
+// Sampling - Настройки фильтрации текстур
+// ============================================
+// Nearest - чёткие пиксели (по умолчанию), Linear - сглаженный дальний
+// террейн. Анизотропия в wgpu работает только при полностью линейной
+// фильтрации, поэтому в режиме Nearest она игнорируется.
+// Mip bias применяется в шейдере (масштаб производных для textureSampleGrad).
+
+use serde::{Serialize, Deserialize};
+
+/// Фильтрация текстур атласа
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TextureFilter {
+    #[default]
+    Nearest,
+    Linear,
+}
+
+impl TextureFilter {
+    pub fn next(self) -> Self {
+        match self {
+            TextureFilter::Nearest => TextureFilter::Linear,
+            TextureFilter::Linear => TextureFilter::Nearest,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TextureFilter::Nearest => "Nearest",
+            TextureFilter::Linear => "Linear",
+        }
+    }
+}
+
+/// Допустимые уровни анизотропии
+pub const ANISOTROPY_LEVELS: [u16; 5] = [1, 2, 4, 8, 16];
+/// Шаг и диапазон mip bias
+const MIP_BIAS_STEP: f32 = 0.5;
+const MIP_BIAS_MIN: f32 = -1.5;
+const MIP_BIAS_MAX: f32 = 2.0;
+
+/// Настройки сэмплеров (атлас блоков и карты теней)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SamplingSettings {
+    /// Фильтрация атласа блоков
+    pub filter: TextureFilter,
+    /// Фильтрация карты теней: Linear - аппаратный PCF, Nearest - жёсткие края
+    pub shadow_filter: TextureFilter,
+    /// 1 = выключено
+    pub anisotropy: u16,
+    /// Сдвиг уровня мипа: < 0 - резче, > 0 - мягче
+    pub mip_bias: f32,
+}
+
+impl Default for SamplingSettings {
+    fn default() -> Self {
+        Self {
+            filter: TextureFilter::Nearest,
+            shadow_filter: TextureFilter::Linear,
+            anisotropy: 1,
+            mip_bias: 0.0,
+        }
+    }
+}
+
+impl SamplingSettings {
+    /// Следующий уровень анизотропии (по кругу)
+    pub fn next_anisotropy(self) -> u16 {
+        let index = ANISOTROPY_LEVELS.iter().position(|&a| a == self.anisotropy).unwrap_or(0);
+        ANISOTROPY_LEVELS[(index + 1) % ANISOTROPY_LEVELS.len()]
+    }
+
+    /// Следующий mip bias (по кругу)
+    pub fn next_mip_bias(self) -> f32 {
+        let next = self.mip_bias + MIP_BIAS_STEP;
+        if next > MIP_BIAS_MAX + f32::EPSILON { MIP_BIAS_MIN } else { next }
+    }
+
+    /// Фактическая анизотропия с учётом ограничений wgpu
+    pub fn effective_anisotropy(&self) -> u16 {
+        match self.filter {
+            TextureFilter::Nearest => 1,
+            TextureFilter::Linear => self.anisotropy.clamp(1, 16),
+        }
+    }
+
+    /// Сэмплер текстурного атласа
+    pub fn create_atlas_sampler(&self, device: &wgpu::Device) -> wgpu::Sampler {
+        let (mag_filter, min_filter) = match self.filter {
+            TextureFilter::Nearest => (wgpu::FilterMode::Nearest, wgpu::FilterMode::Nearest),
+            TextureFilter::Linear => (wgpu::FilterMode::Linear, wgpu::FilterMode::Linear),
+        };
+        device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Atlas Sampler"),
+            // Тайл повторяется по склеенному кваду (UV 0..ширина)
+            address_mode_u: wgpu::AddressMode::Repeat,
+            address_mode_v: wgpu::AddressMode::Repeat,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter,
+            min_filter,
+            // Линейный переход между мипами против муара вдали
+            mipmap_filter: wgpu::FilterMode::Linear,
+            anisotropy_clamp: self.effective_anisotropy(),
+            ..Default::default()
+        })
+    }
+
+    /// Сэмплер сравнения для карты теней
+    pub fn create_shadow_sampler(&self, device: &wgpu::Device) -> wgpu::Sampler {
+        let filter = match self.shadow_filter {
+            TextureFilter::Nearest => wgpu::FilterMode::Nearest,
+            TextureFilter::Linear => wgpu::FilterMode::Linear,
+        };
+        device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Shadow Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: filter,
+            min_filter: filter,
+            compare: Some(wgpu::CompareFunction::LessEqual),
+            ..Default::default()
+        })
+    }
+}
@@ -0,0 +1,240 @@
+// ============================================
+// Screenshot - Снимок кадра в PNG (F2)
+// ============================================
+// После UI и GUI surface копируется в буфер, буфер мапится после
+// submit, а PNG кодируется и пишется в screenshots/ отдельным потоком,
+// чтобы кадр не ждал диска. Имя - время UTC (2026-10-16_14-03-22.png).
+// Нужен surface с COPY_SRC; без него снимки недоступны.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::gpu::core::config_dir;
+
+/// Папка снимков в папке конфигурации
+pub const SCREENSHOT_DIR_NAME: &str = "screenshots";
+
+const MAP_PENDING: u8 = 0;
+const MAP_READY: u8 = 1;
+const MAP_FAILED: u8 = 2;
+
+/// Результат сохранения: путь к PNG или текст ошибки
+pub type ScreenshotResult = Result<PathBuf, String>;
+
+/// Кадр, скопированный в буфер и ждущий чтения
+struct PendingCapture {
+    buffer: wgpu::Buffer,
+    width: u32,
+    height: u32,
+    padded_row: u32,
+    /// Surface в BGRA - каналы меняются при чтении
+    bgra: bool,
+    /// None - буфер ещё не отправлен на чтение (до submit)
+    state: Option<Arc<AtomicU8>>,
+}
+
+/// Снимки экрана
+pub struct Screenshot {
+    supported: bool,
+    requested: bool,
+    pending: Option<PendingCapture>,
+    saved_tx: Sender<ScreenshotResult>,
+    saved_rx: Receiver<ScreenshotResult>,
+}
+
+impl Screenshot {
+    /// supported - surface создан с COPY_SRC
+    pub fn new(supported: bool) -> Self {
+        let (saved_tx, saved_rx) = mpsc::channel();
+        Self { supported, requested: false, pending: None, saved_tx, saved_rx }
+    }
+
+    /// Снять следующий кадр
+    pub fn request(&mut self) -> Result<(), String> {
+        if !self.supported {
+            return Err("surface не поддерживает копирование".to_string());
+        }
+        if self.pending.is_some() {
+            return Err("предыдущий снимок ещё сохраняется".to_string());
+        }
+        self.requested = true;
+        Ok(())
+    }
+
+    /// Скопировать готовый кадр в буфер (последней командой перед submit)
+    pub fn capture(&mut self, device: &wgpu::Device, encoder: &mut wgpu::CommandEncoder, texture: &wgpu::Texture) {
+        if !std::mem::take(&mut self.requested) {
+            return;
+        }
+        let bgra = match texture.format() {
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => true,
+            wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => false,
+            format => {
+                let _ = self.saved_tx.send(Err(format!("формат {:?} не поддерживается", format)));
+                return;
+            }
+        };
+
+        let size = texture.size();
+        let padded_row = (size.width * 4).div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT) * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Screenshot Buffer"),
+            size: padded_row as u64 * size.height as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row),
+                    rows_per_image: Some(size.height),
+                },
+            },
+            size,
+        );
+        self.pending = Some(PendingCapture {
+            buffer,
+            width: size.width,
+            height: size.height,
+            padded_row,
+            bgra,
+            state: None,
+        });
+    }
+
+    /// Запросить чтение буфера (после submit)
+    pub fn after_submit(&mut self) {
+        let Some(pending) = &mut self.pending else { return };
+        if pending.state.is_some() {
+            return;
+        }
+        let state = Arc::new(AtomicU8::new(MAP_PENDING));
+        let callback_state = Arc::clone(&state);
+        pending.buffer.slice(..).map_async(wgpu::MapMode::Read, move |result| {
+            let value = if result.is_ok() { MAP_READY } else { MAP_FAILED };
+            callback_state.store(value, Ordering::Release);
+        });
+        pending.state = Some(state);
+    }
+
+    /// Забрать прочитанный кадр и отдать его потоку записи.
+    /// Возвращает результат очередного сохранения, если он готов
+    pub fn poll(&mut self, device: &wgpu::Device) -> Option<ScreenshotResult> {
+        if let Some(state) = self.pending.as_ref().and_then(|p| p.state.as_ref()) {
+            let _ = device.poll(wgpu::PollType::Poll);
+            match state.load(Ordering::Acquire) {
+                MAP_PENDING => {}
+                MAP_FAILED => {
+                    self.pending = None;
+                    let _ = self.saved_tx.send(Err("не удалось прочитать кадр".to_string()));
+                }
+                _ => self.save_pending(),
+            }
+        }
+        self.saved_rx.try_recv().ok()
+    }
+
+    fn save_pending(&mut self) {
+        let Some(pending) = self.pending.take() else { return };
+        let pixels = {
+            let data = pending.buffer.slice(..).get_mapped_range();
+            unpad_rgba(&data, pending.width, pending.height, pending.padded_row, pending.bgra)
+        };
+        pending.buffer.unmap();
+
+        let (width, height) = (pending.width, pending.height);
+        let saved_tx = self.saved_tx.clone();
+        std::thread::spawn(move || {
+            let dir = config_dir().join(SCREENSHOT_DIR_NAME);
+            let result = save_png(&dir, width, height, &pixels);
+            let _ = saved_tx.send(result);
+        });
+    }
+}
+
+/// Строки буфера без выравнивания, RGBA с непрозрачной альфой
+fn unpad_rgba(data: &[u8], width: u32, height: u32, padded_row: u32, bgra: bool) -> Vec<u8> {
+    let row_bytes = (width * 4) as usize;
+    let mut pixels = Vec::with_capacity(row_bytes * height as usize);
+    for row in data.chunks_exact(padded_row as usize).take(height as usize) {
+        for pixel in row[..row_bytes].chunks_exact(4) {
+            let (r, b) = if bgra { (pixel[2], pixel[0]) } else { (pixel[0], pixel[2]) };
+            pixels.extend_from_slice(&[r, pixel[1], b, 255]);
+        }
+    }
+    pixels
+}
+
+/// Записать PNG с именем по текущему времени (без перезаписи)
+fn save_png(dir: &Path, width: u32, height: u32, rgba: &[u8]) -> ScreenshotResult {
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs());
+    let stem = timestamp_name(secs);
+    let mut path = dir.join(format!("{}.png", stem));
+    let mut index = 1;
+    while path.exists() {
+        path = dir.join(format!("{}_{}.png", stem, index));
+        index += 1;
+    }
+
+    let file = std::fs::File::create(&path).map_err(|e| e.to_string())?;
+    let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(rgba))
+        .map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+/// Время UTC для имени файла: 2026-10-16_14-03-22
+pub fn timestamp_name(unix_secs: u64) -> String {
+    let days = (unix_secs / 86_400) as i64;
+    let secs = unix_secs % 86_400;
+    // Дни от эпохи в григорианскую дату (алгоритм Хиннанта)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}_{:02}-{:02}-{:02}",
+        year, month, day, secs / 3600, secs / 60 % 60, secs % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamp_is_utc_date() {
+        assert_eq!(timestamp_name(0), "1970-01-01_00-00-00");
+        assert_eq!(timestamp_name(1_700_000_000), "2023-11-14_22-13-20");
+        assert_eq!(timestamp_name(951_782_400), "2000-02-29_00-00-00");
+    }
+
+    #[test]
+    fn unpad_drops_alignment_and_swaps_bgra() {
+        // 1x2 BGRA, строки выровнены до 8 байт
+        let data = [1, 2, 3, 0, 9, 9, 9, 9, 4, 5, 6, 0, 9, 9, 9, 9];
+        assert_eq!(unpad_rgba(&data, 1, 2, 8, true), vec![3, 2, 1, 255, 6, 5, 4, 255]);
+        assert_eq!(unpad_rgba(&data, 1, 2, 8, false), vec![1, 2, 3, 255, 4, 5, 6, 255]);
+    }
+}
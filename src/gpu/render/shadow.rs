@@ -2,6 +2,7 @@ use wgpu::util::DeviceExt;
 use ultraviolet::{Vec3, Mat4};
 
 use super::uniforms::ShadowUniform;
+use super::sampling::SamplingSettings;
 use crate::gpu::lighting::{CascadeConfig, DayNightCycle};
 
 pub struct ShadowResources {
@@ -22,6 +23,7 @@ impl ShadowResources {
         device: &wgpu::Device,
         shadow_layout: &wgpu::BindGroupLayout,
         shadow_pass_layout: &wgpu::BindGroupLayout,
+        sampling: &SamplingSettings,
     ) -> Self {
         let config = CascadeConfig::large_world();
         let num_cascades = config.num_cascades as u32;
@@ -66,16 +68,7 @@ impl ShadowResources {
             ..Default::default()
         });
 
-        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
-            label: Some("Shadow Sampler"),
-            address_mode_u: wgpu::AddressMode::ClampToEdge,
-            address_mode_v: wgpu::AddressMode::ClampToEdge,
-            address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Linear,
-            compare: Some(wgpu::CompareFunction::LessEqual),
-            ..Default::default()
-        });
+        let sampler = sampling.create_shadow_sampler(device);
 
         let uniform = ShadowUniform::default();
         let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -84,24 +77,7 @@ impl ShadowResources {
             usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
         });
 
-        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Shadow BG"),
-            layout: shadow_layout,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: wgpu::BindingResource::TextureView(&array_view),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::Sampler(&sampler),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 2,
-                    resource: uniform_buffer.as_entire_binding(),
-                },
-            ],
-        });
+        let bind_group = Self::create_bind_group(device, shadow_layout, &array_view, &sampler, &uniform_buffer);
 
         let pass_buffers: Vec<_> = (0..num_cascades)
             .map(|i| {
@@ -128,7 +104,7 @@ impl ShadowResources {
             })
             .collect();
 
-        println!("CSM Shadows: {} cascades @ {}x{}", num_cascades, shadow_res, shadow_res);
+        log::info!("CSM Shadows: {} cascades @ {}x{}", num_cascades, shadow_res, shadow_res);
 
         Self {
             texture,
@@ -144,6 +120,39 @@ impl ShadowResources {
         }
     }
 
+    /// Применить настройки сэмплинга (пересоздаёт сэмплер и bind group)
+    pub fn apply_sampling(&mut self, device: &wgpu::Device, shadow_layout: &wgpu::BindGroupLayout, sampling: &SamplingSettings) {
+        self.sampler = sampling.create_shadow_sampler(device);
+        self.bind_group = Self::create_bind_group(device, shadow_layout, &self.array_view, &self.sampler, &self.uniform_buffer);
+    }
+
+    fn create_bind_group(
+        device: &wgpu::Device,
+        shadow_layout: &wgpu::BindGroupLayout,
+        array_view: &wgpu::TextureView,
+        sampler: &wgpu::Sampler,
+        uniform_buffer: &wgpu::Buffer,
+    ) -> wgpu::BindGroup {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Shadow BG"),
+            layout: shadow_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(array_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+            ],
+        })
+    }
+
     pub fn compute_cascade_matrix(&self, cascade_idx: usize, camera_pos: Vec3, day_night: &DayNightCycle) -> Mat4 {
         let dist = self.config.cascade_distances[cascade_idx];
         let light_dir = day_night.shadow_light_direction();
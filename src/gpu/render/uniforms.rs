@@ -1,8 +1,10 @@
 use bytemuck::{Pod, Zeroable};
-use ultraviolet::Mat4;
+use ultraviolet::{Mat4, Vec3};
 
 use crate::gpu::player::Camera;
 use crate::gpu::lighting::DayNightCycle;
+use crate::gpu::render::palette::PaletteTheme;
+use crate::gpu::render::fog::{FogSettings, FOG_HEIGHT_FALLOFF};
 
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
@@ -13,7 +15,35 @@ pub struct Uniforms {
     pub sky_color: [f32; 3],
     pub time_of_day: f32,
     pub fog_color: [f32; 3],
-    pub _pad: f32,
+    /// Индекс цветовой темы (PaletteTheme)
+    pub palette_theme: f32,
+    /// Уровень воды мира: от него считается высотный туман
+    pub sea_level: f32,
+    /// Начало и конец тумана (блоки от камеры)
+    pub fog_start: f32,
+    pub fog_end: f32,
+    /// 1.0 - камера под водой (туман по полному расстоянию)
+    pub underwater: f32,
+    /// Тени облаков: покрытие (0 - нет), высота слоя, смещение X/Z
+    pub clouds: [f32; 4],
+    /// Туман по высоте: плотность на блок, спад с высотой, pad, pad
+    pub fog: [f32; 4],
+}
+
+/// Дальность тумана над водой до первого кадра (дальше - от дистанции LOD)
+const FOG_START: f32 = 800.0;
+const FOG_END: f32 = 1000.0;
+/// Дальность тумана под водой
+const UNDERWATER_FOG_START: f32 = 0.0;
+const UNDERWATER_FOG_END: f32 = 28.0;
+/// Цвет толщи воды днём
+const UNDERWATER_FOG_COLOR: [f32; 3] = [0.05, 0.22, 0.38];
+
+/// Цвет тумана под водой: синий, темнеет вместе с небом
+pub fn underwater_fog_color(cycle: &DayNightCycle) -> Vec3 {
+    let sky = cycle.sky_color;
+    let brightness = (sky.x * 0.3 + sky.y * 0.59 + sky.z * 0.11).clamp(0.15, 1.0);
+    Vec3::from(UNDERWATER_FOG_COLOR) * brightness
 }
 
 impl Uniforms {
@@ -25,7 +55,13 @@ impl Uniforms {
             sky_color: [0.5, 0.7, 1.0],
             time_of_day: 0.5,
             fog_color: [0.7, 0.8, 0.9],
-            _pad: 0.0,
+            palette_theme: 0.0,
+            sea_level: 0.0,
+            fog_start: FOG_START,
+            fog_end: FOG_END,
+            underwater: 0.0,
+            clouds: [0.0; 4],
+            fog: [0.0, FOG_HEIGHT_FALLOFF, 0.0, 0.0],
         }
     }
 
@@ -40,6 +76,37 @@ impl Uniforms {
         self.fog_color = cycle.fog_color.into();
         self.time_of_day = cycle.time.time;
     }
+
+    pub fn set_palette_theme(&mut self, theme: PaletteTheme) {
+        self.palette_theme = theme.index() as f32;
+    }
+
+    pub fn set_sea_level(&mut self, sea_level: i32) {
+        self.sea_level = sea_level as f32;
+    }
+
+    /// Параметры слоя облаков для теней (CloudLayer::shadow_params)
+    pub fn set_clouds(&mut self, params: [f32; 4]) {
+        self.clouds = params;
+    }
+
+    /// Туман до внешней дистанции LOD, цвет тонируется климатом
+    /// (после update_day_night - цвет тумана берётся оттуда)
+    pub fn set_fog(&mut self, fog: &FogSettings, outer_lod_chunks: i32, tint: Vec3) {
+        let (start, end) = fog.range(outer_lod_chunks);
+        self.fog_start = start;
+        self.fog_end = end;
+        self.fog_color = (Vec3::from(self.fog_color) * tint).into();
+        self.fog = [fog.density_per_block(start, end), FOG_HEIGHT_FALLOFF, 0.0, 0.0];
+    }
+
+    /// Камера в воде: короткий синий туман вместо обычного
+    pub fn set_underwater(&mut self, cycle: &DayNightCycle) {
+        self.fog_color = underwater_fog_color(cycle).into();
+        self.fog_start = UNDERWATER_FOG_START;
+        self.fog_end = UNDERWATER_FOG_END;
+        self.underwater = 1.0;
+    }
 }
 
 #[repr(C)]
@@ -85,3 +152,15 @@ impl Default for ShadowUniform {
         }
     }
 }
+
+/// Параметры прохода воды
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct WaterUniform {
+    /// Ближняя и дальняя плоскости камеры (линеаризация глубины для SSR)
+    pub near: f32,
+    pub far: f32,
+    /// 1.0 - экранные отражения включены, 0.0 - только небо
+    pub reflections: f32,
+    pub _pad: f32,
+}
@@ -0,0 +1,296 @@
+// ============================================
+// View Model Renderer - Рука от первого лица
+// ============================================
+// Рука и выбранный в хотбаре блок рисуются отдельным проходом после
+// мира: геометрия в пространстве камеры, своя проекция с постоянным
+// FOV (бег и фоторежим руку не растягивают) и сжатый ближний диапазон
+// глубины (см. viewmodel.wgsl). Установка блока даёт один взмах,
+// удержание ломания - взмахи подряд.
+
+use bytemuck::{Pod, Zeroable};
+use std::f32::consts::PI;
+use wgpu::util::DeviceExt;
+use ultraviolet::{Mat4, Vec3};
+
+use crate::gpu::blocks::{get_face_colors, BlockType};
+use crate::gpu::player::{PlayerMeshGenerator, PlayerVertex};
+
+/// FOV руки (градусы), не зависит от настроек камеры
+const VIEWMODEL_FOV: f32 = 70.0;
+const VIEWMODEL_NEAR: f32 = 0.05;
+const VIEWMODEL_FAR: f32 = 10.0;
+/// Длительность взмаха (секунды)
+const SWING_DURATION: f32 = 0.25;
+/// Взмах: поворот вниз (радианы) и выпад вперёд-к центру (блоки)
+const SWING_ANGLE: f32 = 0.9;
+const SWING_REACH: f32 = 0.12;
+
+/// Плечо в пространстве камеры (справа снизу) и наклон руки
+const SHOULDER: Vec3 = Vec3::new(0.42, -0.38, -0.35);
+const ARM_YAW: f32 = 0.25;
+const ARM_PITCH: f32 = 0.15;
+
+/// Рукав, кисть и блок в руке
+const SLEEVE_COLOR: [f32; 3] = [0.2, 0.4, 0.8];
+const SKIN_COLOR: [f32; 3] = [0.9, 0.75, 0.6];
+const ARM_HALF_WIDTH: f32 = 0.08;
+const SLEEVE_LENGTH: f32 = 0.55;
+const HAND_LENGTH: f32 = 0.15;
+const BLOCK_HALF_SIZE: f32 = 0.125;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct ViewModelUniforms {
+    projection: [[f32; 4]; 4],
+    arm: [[f32; 4]; 4],
+    block: [[f32; 4]; 4],
+    light: [f32; 4], // яркость, -, -, -
+}
+
+/// Поворот вниз и выпад руки на прогрессе взмаха 0-1
+fn swing_offset(progress: f32) -> (f32, f32) {
+    let s = (progress.clamp(0.0, 1.0) * PI).sin();
+    (SWING_ANGLE * s, SWING_REACH * s)
+}
+
+/// Меш: рука (первые arm_indices индексов), за ней куб блока
+fn build_mesh(block_colors: ([f32; 3], [f32; 3])) -> (Vec<PlayerVertex>, Vec<u32>, u32) {
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+    let w = ARM_HALF_WIDTH;
+
+    // Рука от плеча вдоль -Z (вперёд от камеры)
+    PlayerMeshGenerator::add_box(&mut vertices, &mut indices, -w, -w, -SLEEVE_LENGTH, w, w, 0.0, SLEEVE_COLOR);
+    let hand_end = -SLEEVE_LENGTH - HAND_LENGTH;
+    PlayerMeshGenerator::add_box(&mut vertices, &mut indices, -w, -w, hand_end, w, w, -SLEEVE_LENGTH, SKIN_COLOR);
+    let arm_indices = indices.len() as u32;
+
+    // Блок: бока цветом стороны, верх (последняя грань add_box) - верха
+    let (top, side) = block_colors;
+    let b = BLOCK_HALF_SIZE;
+    PlayerMeshGenerator::add_box(&mut vertices, &mut indices, -b, -b, -b, b, b, b, side);
+    let top_start = vertices.len() - 4;
+    for vertex in &mut vertices[top_start..] {
+        vertex.color = top;
+    }
+
+    (vertices, indices, arm_indices)
+}
+
+pub struct ViewModelRenderer {
+    vertex_buffer: wgpu::Buffer,
+    index_buffer: wgpu::Buffer,
+    arm_indices: u32,
+    index_count: u32,
+    uniform_buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+    pipeline: wgpu::RenderPipeline,
+    /// Рука видна (первое лицо, не демо и не фоторежим)
+    visible: bool,
+    /// Блок в руке (None - пустая рука или не блок)
+    held: Option<BlockType>,
+    /// Зажато ломание - взмахи подряд
+    breaking: bool,
+    /// Прогресс текущего взмаха 0-1
+    swing: Option<f32>,
+}
+
+impl ViewModelRenderer {
+    pub fn new(device: &wgpu::Device, surface_format: wgpu::TextureFormat, sample_count: u32) -> Self {
+        let (vertices, indices, arm_indices) = build_mesh(([0.5; 3], [0.4; 3]));
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("View Model VB"),
+            contents: bytemuck::cast_slice(&vertices),
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        });
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("View Model IB"),
+            contents: bytemuck::cast_slice(&indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("View Model UB"),
+            contents: bytemuck::cast_slice(&[ViewModelUniforms::zeroed()]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("View Model BGL"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("View Model BG"),
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("View Model Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/viewmodel.wgsl").into()),
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("View Model PL"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("View Model Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[PlayerVertex::desc()],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                cull_mode: Some(wgpu::Face::Back),
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::GreaterEqual, // Reversed-Z
+                stencil: Default::default(),
+                bias: Default::default(),
+            }),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                ..Default::default()
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            vertex_buffer,
+            index_buffer,
+            arm_indices,
+            index_count: indices.len() as u32,
+            uniform_buffer,
+            bind_group,
+            pipeline,
+            visible: false,
+            held: None,
+            breaking: false,
+            swing: None,
+        }
+    }
+
+    /// Состояние руки на кадр: видимость, блок в руке, зажато ли ломание
+    pub fn set_state(&mut self, queue: &wgpu::Queue, visible: bool, held: Option<BlockType>, breaking: bool) {
+        self.visible = visible;
+        self.breaking = breaking;
+        if held != self.held {
+            self.held = held;
+            if let Some(block) = held {
+                let (vertices, _, _) = build_mesh(get_face_colors(block));
+                queue.write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice(&vertices));
+            }
+        }
+    }
+
+    /// Один взмах (установка блока)
+    pub fn swing(&mut self) {
+        self.swing = Some(0.0);
+    }
+
+    /// Анимация и матрицы; brightness - освещённость по времени суток
+    pub fn update(&mut self, queue: &wgpu::Queue, dt: f32, aspect: f32, brightness: f32) {
+        let step = dt / SWING_DURATION;
+        self.swing = match self.swing {
+            Some(progress) if progress + step < 1.0 => Some(progress + step),
+            // Взмах закончен, а ломание продолжается - следующий
+            _ if self.breaking => Some(0.0),
+            _ => None,
+        };
+        if !self.visible {
+            return;
+        }
+
+        let (angle, reach) = swing_offset(self.swing.unwrap_or(0.0));
+        let arm = Mat4::from_translation(SHOULDER + Vec3::new(-reach, 0.0, -reach))
+            * Mat4::from_rotation_y(ARM_YAW)
+            * Mat4::from_rotation_x(ARM_PITCH - angle);
+        let hand = -SLEEVE_LENGTH - HAND_LENGTH * 0.5;
+        let block = arm
+            * Mat4::from_translation(Vec3::new(0.0, ARM_HALF_WIDTH + BLOCK_HALF_SIZE, hand))
+            * Mat4::from_rotation_y(0.6);
+
+        let uniforms = ViewModelUniforms {
+            // Reversed-Z, как у камеры: near и far местами
+            projection: ultraviolet::projection::perspective_wgpu_dx(
+                VIEWMODEL_FOV.to_radians(),
+                aspect,
+                VIEWMODEL_FAR,
+                VIEWMODEL_NEAR,
+            )
+            .into(),
+            arm: arm.into(),
+            block: block.into(),
+            light: [brightness, 0.0, 0.0, 0.0],
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+    }
+
+    pub fn render<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+        if !self.visible {
+            return;
+        }
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        render_pass.draw_indexed(0..self.arm_indices, 0, 0..1);
+        if self.held.is_some() {
+            render_pass.draw_indexed(self.arm_indices..self.index_count, 0, 1..2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swing_peaks_mid_way_and_returns() {
+        assert_eq!(swing_offset(0.0), (0.0, 0.0));
+        let (angle, reach) = swing_offset(0.5);
+        assert!((angle - SWING_ANGLE).abs() < 1e-5 && (reach - SWING_REACH).abs() < 1e-5);
+        assert!(swing_offset(1.0).0.abs() < 1e-5);
+    }
+
+    #[test]
+    fn mesh_keeps_block_after_arm() {
+        let (vertices, indices, arm_indices) = build_mesh(([1.0, 0.0, 0.0], [0.0, 1.0, 0.0]));
+        // Две коробки руки и куб: по 24 вершины и 36 индексов
+        assert_eq!(vertices.len(), 72);
+        assert_eq!(arm_indices, 72);
+        assert_eq!(indices.len(), 108);
+        assert_eq!(vertices[71].color, [1.0, 0.0, 0.0]);
+        assert_eq!(vertices[48].color, [0.0, 1.0, 0.0]);
+    }
+}
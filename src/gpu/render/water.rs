@@ -0,0 +1,147 @@
+use wgpu::util::DeviceExt;
+
+use super::uniforms::WaterUniform;
+use super::post::HDR_FORMAT;
+
+/// Ресурсы прохода воды: копия HDR кадра до воды и глубина сцены
+/// для экранных отражений (SSR)
+pub struct WaterResources {
+    pub scene_texture: wgpu::Texture,
+    pub sampler: wgpu::Sampler,
+    pub uniform_buffer: wgpu::Buffer,
+    pub bind_group: wgpu::BindGroup,
+    pub uniform: WaterUniform,
+    /// Отражения включены в настройках
+    pub reflections: bool,
+}
+
+impl WaterResources {
+    pub fn new(
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        scene_size: (u32, u32),
+        depth_view: &wgpu::TextureView,
+    ) -> Self {
+        let (scene_texture, scene_view) = Self::create_scene_texture(device, scene_size);
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Water Scene Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let uniform = WaterUniform {
+            near: 0.1,
+            far: 1000.0,
+            reflections: 0.0,
+            _pad: 0.0,
+        };
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Water Uniform"),
+            contents: bytemuck::cast_slice(&[uniform]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let bind_group = Self::create_bind_group(device, layout, &scene_view, depth_view, &sampler, &uniform_buffer);
+
+        Self {
+            scene_texture,
+            sampler,
+            uniform_buffer,
+            bind_group,
+            uniform,
+            reflections: true,
+        }
+    }
+
+    /// Пересоздать копию кадра под новый размер (глубина тоже новая)
+    pub fn resize(
+        &mut self,
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        scene_size: (u32, u32),
+        depth_view: &wgpu::TextureView,
+    ) {
+        let (scene_texture, scene_view) = Self::create_scene_texture(device, scene_size);
+        self.bind_group = Self::create_bind_group(device, layout, &scene_view, depth_view, &self.sampler, &self.uniform_buffer);
+        self.scene_texture = scene_texture;
+    }
+
+    /// Отражения реально считаются в этом кадре
+    pub fn reflections_active(&self) -> bool {
+        self.reflections
+    }
+
+    pub fn update(&mut self, queue: &wgpu::Queue, near: f32, far: f32) {
+        self.uniform.near = near;
+        self.uniform.far = far;
+        self.uniform.reflections = if self.reflections_active() { 1.0 } else { 0.0 };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[self.uniform]));
+    }
+
+    /// Скопировать HDR кадр до воды (источник отражений)
+    pub fn copy_scene(&self, encoder: &mut wgpu::CommandEncoder, frame: &wgpu::Texture) {
+        encoder.copy_texture_to_texture(
+            frame.as_image_copy(),
+            self.scene_texture.as_image_copy(),
+            self.scene_texture.size(),
+        );
+    }
+
+    fn create_scene_texture(
+        device: &wgpu::Device,
+        (width, height): (u32, u32),
+    ) -> (wgpu::Texture, wgpu::TextureView) {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Water Scene Copy"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: HDR_FORMAT,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        (texture, view)
+    }
+
+    fn create_bind_group(
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        scene_view: &wgpu::TextureView,
+        depth_view: &wgpu::TextureView,
+        sampler: &wgpu::Sampler,
+        uniform_buffer: &wgpu::Buffer,
+    ) -> wgpu::BindGroup {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Water BG"),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(scene_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(depth_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+            ],
+        })
+    }
+}
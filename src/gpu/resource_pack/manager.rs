@@ -0,0 +1,198 @@
+// ============================================
+// Resource Pack Manager - Порядок и выбор пакетов
+// ============================================
+// Включённые пакеты ищутся по порядку: первый найденный файл побеждает,
+// иначе используется встроенный ресурс игры.
+
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+
+use serde::{Serialize, Deserialize};
+
+use super::pack::ResourcePack;
+
+/// Директория с пакетами ресурсов
+pub const RESOURCE_PACKS_DIR: &str = "resourcepacks";
+/// Файл с порядком включённых пакетов
+pub const RESOURCE_PACKS_OPTIONS: &str = "resourcepacks.json";
+
+/// Цвета интерфейса (ui/colors.json в пакете)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct UiColors {
+    /// Акцентный цвет (неоновые рамки, заголовки)
+    pub accent: [f32; 3],
+}
+
+impl Default for UiColors {
+    fn default() -> Self {
+        Self { accent: [0.0, 0.94, 1.0] }
+    }
+}
+
+/// Сохраняемые настройки пакетов
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PackOptions {
+    /// Имена включённых пакетов, от высшего приоритета к низшему
+    enabled: Vec<String>,
+}
+
+/// Менеджер пакетов ресурсов
+pub struct ResourcePackManager {
+    dir: PathBuf,
+    /// Все найденные пакеты
+    available: Vec<ResourcePack>,
+    /// Включённые пакеты (имена) в порядке приоритета
+    enabled: Vec<String>,
+}
+
+impl ResourcePackManager {
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        let mut manager = Self {
+            dir: dir.as_ref().to_path_buf(),
+            available: Vec::new(),
+            enabled: Vec::new(),
+        };
+        manager.rescan();
+        manager.load_options();
+        manager
+    }
+
+    /// Пересканировать директорию пакетов
+    pub fn rescan(&mut self) {
+        self.available.clear();
+        if let Ok(entries) = fs::read_dir(&self.dir) {
+            for entry in entries.flatten() {
+                if let Some(pack) = ResourcePack::open(&entry.path()) {
+                    self.available.push(pack);
+                }
+            }
+        }
+        self.available.sort_by(|a, b| a.name.cmp(&b.name));
+
+        // Пакеты, которых больше нет на диске, выключаем
+        let available = &self.available;
+        self.enabled.retain(|name| available.iter().any(|pack| &pack.name == name));
+    }
+
+    /// Все пакеты: сначала включённые по приоритету, затем остальные
+    /// Возвращает (имя, включён)
+    pub fn list(&self) -> Vec<(String, bool)> {
+        let mut list: Vec<(String, bool)> = self.enabled.iter().map(|name| (name.clone(), true)).collect();
+        for pack in &self.available {
+            if !self.enabled.contains(&pack.name) {
+                list.push((pack.name.clone(), false));
+            }
+        }
+        list
+    }
+
+    /// Включить/выключить пакет (включённый получает высший приоритет)
+    pub fn toggle(&mut self, name: &str) {
+        if let Some(index) = self.enabled.iter().position(|n| n == name) {
+            self.enabled.remove(index);
+        } else if self.available.iter().any(|pack| pack.name == name) {
+            self.enabled.insert(0, name.to_string());
+        }
+    }
+
+    /// Поднять приоритет включённого пакета
+    pub fn move_up(&mut self, name: &str) {
+        if let Some(index) = self.enabled.iter().position(|n| n == name) {
+            if index > 0 {
+                self.enabled.swap(index, index - 1);
+            }
+        }
+    }
+
+    /// Понизить приоритет включённого пакета
+    pub fn move_down(&mut self, name: &str) {
+        if let Some(index) = self.enabled.iter().position(|n| n == name) {
+            if index + 1 < self.enabled.len() {
+                self.enabled.swap(index, index + 1);
+            }
+        }
+    }
+
+    /// Прочитать ресурс из включённых пакетов
+    pub fn read(&self, namespaced: &str) -> Option<Vec<u8>> {
+        self.enabled
+            .iter()
+            .filter_map(|name| self.available.iter().find(|pack| &pack.name == name))
+            .find_map(|pack| pack.read(namespaced))
+    }
+
+    /// Прочитать PNG текстуру как RGBA8: (ширина, высота, пиксели)
+    pub fn read_texture(&self, namespaced: &str) -> Option<(u32, u32, Vec<u8>)> {
+        let bytes = self.read(namespaced)?;
+        match decode_png_rgba(&bytes) {
+            Ok(texture) => Some(texture),
+            Err(e) => {
+                log::error!("Ошибка чтения {}: {}", namespaced, e);
+                None
+            }
+        }
+    }
+
+    /// Цвета интерфейса с учётом пакетов
+    pub fn ui_colors(&self) -> UiColors {
+        self.read("ui/colors.json")
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    /// Сохранить порядок включённых пакетов
+    pub fn save_options(&self) {
+        let options = PackOptions { enabled: self.enabled.clone() };
+        match serde_json::to_string_pretty(&options) {
+            Ok(json) => {
+                if let Err(e) = fs::write(RESOURCE_PACKS_OPTIONS, json) {
+                    log::warn!("Не удалось сохранить {}: {}", RESOURCE_PACKS_OPTIONS, e);
+                }
+            }
+            Err(e) => log::error!("Ошибка сериализации: {}", e),
+        }
+    }
+
+    fn load_options(&mut self) {
+        let Ok(json) = fs::read_to_string(RESOURCE_PACKS_OPTIONS) else { return };
+        if let Ok(options) = serde_json::from_str::<PackOptions>(&json) {
+            self.enabled = options
+                .enabled
+                .into_iter()
+                .filter(|name| self.available.iter().any(|pack| &pack.name == name))
+                .collect();
+        }
+    }
+}
+
+/// Декодировать PNG в RGBA8
+fn decode_png_rgba(bytes: &[u8]) -> Result<(u32, u32, Vec<u8>), String> {
+    let mut decoder = png::Decoder::new(Cursor::new(bytes));
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(|e| e.to_string())?;
+    let mut buf = vec![0u8; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).map_err(|e| e.to_string())?;
+    buf.truncate(info.buffer_size());
+
+    let rgba = match info.color_type {
+        png::ColorType::Rgba => buf,
+        png::ColorType::Rgb => buf.chunks_exact(3).flat_map(|p| [p[0], p[1], p[2], 255]).collect(),
+        png::ColorType::GrayscaleAlpha => buf.chunks_exact(2).flat_map(|p| [p[0], p[0], p[0], p[1]]).collect(),
+        png::ColorType::Grayscale => buf.iter().flat_map(|&g| [g, g, g, 255]).collect(),
+        png::ColorType::Indexed => return Err("indexed PNG not expanded".to_string()),
+    };
+    Ok((info.width, info.height, rgba))
+}
+
+// ============================================
+// Global Manager Singleton
+// ============================================
+
+static GLOBAL_PACKS: OnceLock<RwLock<ResourcePackManager>> = OnceLock::new();
+
+/// Получить глобальный менеджер пакетов ресурсов
+pub fn global_resource_packs() -> &'static RwLock<ResourcePackManager> {
+    GLOBAL_PACKS.get_or_init(|| RwLock::new(ResourcePackManager::new(RESOURCE_PACKS_DIR)))
+}
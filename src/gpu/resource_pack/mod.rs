@@ -0,0 +1,16 @@
+// ============================================
+// Resource Packs - Переопределение текстур, звуков и цветов UI
+// ============================================
+// Пакет - папка или .zip в resourcepacks/ со структурой:
+//   assets/<namespace>/textures/block/<block_id>.png
+//   assets/<namespace>/sounds/<name>.wav
+//   assets/<namespace>/ui/colors.json
+
+mod pack;
+mod manager;
+
+pub use pack::{ResourcePack, PackSource, DEFAULT_NAMESPACE, pack_path};
+pub use manager::{
+    ResourcePackManager, UiColors, global_resource_packs,
+    RESOURCE_PACKS_DIR, RESOURCE_PACKS_OPTIONS,
+};
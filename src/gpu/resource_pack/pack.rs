@@ -0,0 +1,73 @@
+// ============================================
+// Resource Pack - Один пакет ресурсов (папка или zip)
+// ============================================
+// Ресурсы адресуются namespaced путём "namespace:path", который
+// отображается в файл пакета assets/<namespace>/<path>
+
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Namespace по умолчанию (если в пути нет "ns:")
+pub const DEFAULT_NAMESPACE: &str = "kimi";
+
+/// Где лежат файлы пакета
+#[derive(Debug, Clone)]
+pub enum PackSource {
+    Folder(PathBuf),
+    Zip(PathBuf),
+}
+
+/// Пакет ресурсов
+#[derive(Debug, Clone)]
+pub struct ResourcePack {
+    /// Имя пакета (имя папки или zip без расширения)
+    pub name: String,
+    pub source: PackSource,
+}
+
+/// Преобразовать "namespace:path" в путь внутри пакета
+pub fn pack_path(namespaced: &str) -> String {
+    let (namespace, path) = namespaced
+        .split_once(':')
+        .unwrap_or((DEFAULT_NAMESPACE, namespaced));
+    format!("assets/{}/{}", namespace, path.trim_start_matches('/'))
+}
+
+impl ResourcePack {
+    /// Открыть пакет по пути (папка или .zip)
+    pub fn open(path: &Path) -> Option<Self> {
+        let name = path.file_stem()?.to_str()?.to_string();
+
+        if path.is_dir() {
+            return Some(Self { name, source: PackSource::Folder(path.to_path_buf()) });
+        }
+
+        let is_zip = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.eq_ignore_ascii_case("zip"))
+            .unwrap_or(false);
+        if is_zip {
+            return Some(Self { name, source: PackSource::Zip(path.to_path_buf()) });
+        }
+
+        None
+    }
+
+    /// Прочитать ресурс по namespaced пути
+    pub fn read(&self, namespaced: &str) -> Option<Vec<u8>> {
+        let inner = pack_path(namespaced);
+        match &self.source {
+            PackSource::Folder(root) => fs::read(root.join(inner)).ok(),
+            PackSource::Zip(archive_path) => {
+                let file = File::open(archive_path).ok()?;
+                let mut archive = zip::ZipArchive::new(file).ok()?;
+                let mut entry = archive.by_name(&inner).ok()?;
+                let mut bytes = Vec::with_capacity(entry.size() as usize);
+                entry.read_to_end(&mut bytes).ok()?;
+                Some(bytes)
+            }
+        }
+    }
+}
@@ -0,0 +1,21 @@
+// ============================================
+// Saved Block Entities - Данные блоков в сохранении
+// ============================================
+
+use serde::{Serialize, Deserialize};
+
+use super::hotbar::SavedHotbarItem;
+
+/// Сущность блока с позицией
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedBlockEntity {
+    pub pos: [i32; 3],
+    pub data: SavedBlockEntityData,
+}
+
+/// Данные сущности по типу блока
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SavedBlockEntityData {
+    /// Содержимое сундука (None = пустой слот)
+    Chest { slots: Vec<Option<SavedHotbarItem>> },
+}
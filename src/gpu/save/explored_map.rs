@@ -0,0 +1,317 @@
+// ============================================
+// Explored Map - Разведанные чанки и метки карты мира
+// ============================================
+// Каждый чанк, растеризованный миникартой, запоминается здесь: карта
+// мира (M) показывает всё, что игрок когда-либо видел, а не только
+// окрестности. Вместе с чанками хранятся метки (waypoints) с цветом и
+// место последней смерти игрока.
+//
+// Файл map.dat в папке мира: MAGIC + версия + ZSTD(bincode(MapFile)).
+// Цвета хранятся без альфы (RGB) - разведанный столбец всегда непрозрачен.
+// Версия 1 (метки без цвета, без места смерти) читается.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::gpu::terrain::CHUNK_SIZE;
+
+use super::world_file::SaveError;
+
+/// Файл карты в папке мира
+pub const EXPLORED_MAP_FILE: &str = "map.dat";
+/// Больше меток не ставится
+pub const MAX_WAYPOINTS: usize = 32;
+
+/// Сигнатура файла карты
+const MAP_MAGIC: [u8; 4] = *b"KVMP";
+/// Версия формата
+const MAP_VERSION: u32 = 2;
+const TILE_TEXELS: usize = (CHUNK_SIZE * CHUNK_SIZE) as usize;
+
+/// Цвета меток по имени; новые метки берут их по кругу
+pub const WAYPOINT_COLORS: [(&str, [u8; 3]); 8] = [
+    ("yellow", [255, 205, 50]),
+    ("cyan", [60, 220, 240]),
+    ("green", [90, 220, 90]),
+    ("orange", [255, 140, 40]),
+    ("purple", [190, 110, 255]),
+    ("blue", [70, 130, 255]),
+    ("white", [240, 240, 240]),
+    ("red", [255, 70, 70]),
+];
+
+/// Имя метки смерти в /waypoint
+pub const DEATH_MARKER: &str = "death";
+
+/// Цвет метки по имени
+pub fn waypoint_color(name: &str) -> Option<[u8; 3]> {
+    WAYPOINT_COLORS.iter().find(|(color, _)| *color == name).map(|&(_, rgb)| rgb)
+}
+
+/// Цвета столбцов чанка (RGBA, строка - одна z), как у миникарты
+pub type MapTile = [[u8; 4]; TILE_TEXELS];
+
+/// Метка на карте
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Waypoint {
+    pub name: String,
+    pub pos: [i32; 3],
+    pub color: [u8; 3],
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedTile {
+    chunk: [i32; 2],
+    /// RGB столбцов, TILE_TEXELS * 3 байт
+    rgb: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct MapFile {
+    tiles: Vec<SavedTile>,
+    waypoints: Vec<Waypoint>,
+    death: Option<[i32; 3]>,
+}
+
+/// Метка версии 1
+#[derive(Deserialize)]
+struct WaypointV1 {
+    name: String,
+    pos: [i32; 3],
+}
+
+#[derive(Deserialize)]
+struct MapFileV1 {
+    tiles: Vec<SavedTile>,
+    waypoints: Vec<WaypointV1>,
+}
+
+/// Разведанная карта мира
+#[derive(Default)]
+pub struct ExploredMap {
+    tiles: HashMap<(i32, i32), Box<MapTile>>,
+    waypoints: Vec<Waypoint>,
+    /// Место последней смерти
+    death: Option<[i32; 3]>,
+}
+
+impl ExploredMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Запомнить (или обновить) цвета разведанного чанка
+    pub fn explore(&mut self, chunk: (i32, i32), colors: &MapTile) {
+        match self.tiles.get_mut(&chunk) {
+            Some(tile) => **tile = *colors,
+            None => {
+                self.tiles.insert(chunk, Box::new(*colors));
+            }
+        }
+    }
+
+    pub fn tile(&self, chunk: (i32, i32)) -> Option<&MapTile> {
+        self.tiles.get(&chunk).map(|tile| &**tile)
+    }
+
+    pub fn tile_count(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn waypoints(&self) -> &[Waypoint] {
+        &self.waypoints
+    }
+
+    /// Поставить метку (метка с тем же именем переносится). Без цвета -
+    /// прежний цвет метки или следующий по кругу.
+    /// false - меток уже MAX_WAYPOINTS
+    pub fn add_waypoint(&mut self, name: &str, pos: [i32; 3], color: Option<[u8; 3]>) -> bool {
+        if let Some(waypoint) = self.waypoints.iter_mut().find(|waypoint| waypoint.name == name) {
+            waypoint.pos = pos;
+            waypoint.color = color.unwrap_or(waypoint.color);
+            return true;
+        }
+        if self.waypoints.len() >= MAX_WAYPOINTS {
+            return false;
+        }
+        let color = color.unwrap_or(WAYPOINT_COLORS[self.waypoints.len() % WAYPOINT_COLORS.len()].1);
+        self.waypoints.push(Waypoint { name: name.to_string(), pos, color });
+        true
+    }
+
+    /// Перекрасить метку (false - такой нет)
+    pub fn set_waypoint_color(&mut self, name: &str, color: [u8; 3]) -> bool {
+        match self.waypoints.iter_mut().find(|waypoint| waypoint.name == name) {
+            Some(waypoint) => {
+                waypoint.color = color;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Убрать метку по имени (false - такой нет). DEATH_MARKER без метки
+    /// с таким именем убирает место смерти
+    pub fn remove_waypoint(&mut self, name: &str) -> bool {
+        let before = self.waypoints.len();
+        self.waypoints.retain(|waypoint| waypoint.name != name);
+        if self.waypoints.len() != before {
+            return true;
+        }
+        name == DEATH_MARKER && self.death.take().is_some()
+    }
+
+    pub fn death(&self) -> Option<[i32; 3]> {
+        self.death
+    }
+
+    /// Запомнить место смерти (прежнее забывается)
+    pub fn set_death(&mut self, pos: [i32; 3]) {
+        self.death = Some(pos);
+    }
+
+    /// Свободное имя вида "waypoint-N" (удобно набирать в /waypoint)
+    pub fn next_waypoint_name(&self) -> String {
+        (1..)
+            .map(|n| format!("waypoint-{}", n))
+            .find(|name| self.waypoints.iter().all(|waypoint| &waypoint.name != name))
+            .unwrap_or_default()
+    }
+
+    /// Записать в файл
+    pub fn save(&self, path: &Path) -> Result<(), SaveError> {
+        let file = MapFile {
+            tiles: self
+                .tiles
+                .iter()
+                .map(|(&(cx, cz), tile)| SavedTile {
+                    chunk: [cx, cz],
+                    rgb: tile.iter().flat_map(|&[r, g, b, _]| [r, g, b]).collect(),
+                })
+                .collect(),
+            waypoints: self.waypoints.clone(),
+            death: self.death,
+        };
+        let body = bincode::serialize(&file).map_err(|e| SaveError::Serialize(e.to_string()))?;
+        let compressed = zstd::encode_all(&body[..], 3).map_err(|e| SaveError::Compression(e.to_string()))?;
+
+        let mut bytes = Vec::with_capacity(compressed.len() + 8);
+        bytes.extend_from_slice(&MAP_MAGIC);
+        bytes.extend_from_slice(&MAP_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&compressed);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, bytes)?;
+        Ok(())
+    }
+
+    /// Прочитать из файла (повреждённые чанки пропускаются)
+    pub fn load(path: &Path) -> Result<Self, SaveError> {
+        let bytes = fs::read(path)?;
+        if bytes.len() < 8 || bytes[..4] != MAP_MAGIC {
+            return Err(SaveError::InvalidMagic);
+        }
+        let version = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+        if version != MAP_VERSION && version != 1 {
+            return Err(SaveError::UnsupportedVersion(version));
+        }
+        let body = zstd::decode_all(&bytes[8..]).map_err(|e| SaveError::Compression(e.to_string()))?;
+        let file: MapFile = if version == 1 {
+            let old: MapFileV1 = bincode::deserialize(&body).map_err(|e| SaveError::Deserialize(e.to_string()))?;
+            let waypoints = old
+                .waypoints
+                .into_iter()
+                .enumerate()
+                .map(|(i, waypoint)| Waypoint {
+                    name: waypoint.name,
+                    pos: waypoint.pos,
+                    color: WAYPOINT_COLORS[i % WAYPOINT_COLORS.len()].1,
+                })
+                .collect();
+            MapFile { tiles: old.tiles, waypoints, death: None }
+        } else {
+            bincode::deserialize(&body).map_err(|e| SaveError::Deserialize(e.to_string()))?
+        };
+
+        let mut map = Self { tiles: HashMap::with_capacity(file.tiles.len()), waypoints: file.waypoints, death: file.death };
+        for tile in file.tiles {
+            if tile.rgb.len() != TILE_TEXELS * 3 {
+                log::warn!("Карта: повреждённый чанк {:?} пропущен", tile.chunk);
+                continue;
+            }
+            let mut colors: MapTile = [[0; 4]; TILE_TEXELS];
+            for (color, rgb) in colors.iter_mut().zip(tile.rgb.chunks_exact(3)) {
+                *color = [rgb[0], rgb[1], rgb[2], 255];
+            }
+            map.tiles.insert((tile.chunk[0], tile.chunk[1]), Box::new(colors));
+        }
+        map.waypoints.truncate(MAX_WAYPOINTS);
+        Ok(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn waypoints_are_renamed_moved_and_capped() {
+        let mut map = ExploredMap::new();
+        assert_eq!(map.next_waypoint_name(), "waypoint-1");
+        assert!(map.add_waypoint("waypoint-1", [0, 64, 0], None));
+        assert!(map.add_waypoint("home", [10, 70, -5], waypoint_color("red")));
+        assert_eq!(map.next_waypoint_name(), "waypoint-2");
+        // Цвета по кругу, если не заданы
+        assert_eq!(map.waypoints()[0].color, WAYPOINT_COLORS[0].1);
+        // Та же метка переносится, а не дублируется, и сохраняет цвет
+        assert!(map.add_waypoint("home", [1, 2, 3], None));
+        assert_eq!(map.waypoints().len(), 2);
+        assert_eq!(map.waypoints()[1].pos, [1, 2, 3]);
+        assert_eq!(map.waypoints()[1].color, [255, 70, 70]);
+        assert!(map.set_waypoint_color("home", [1, 1, 1]));
+        assert!(!map.set_waypoint_color("away", [1, 1, 1]));
+        assert!(map.remove_waypoint("waypoint-1"));
+        assert!(!map.remove_waypoint("waypoint-1"));
+
+        for i in 0..MAX_WAYPOINTS {
+            map.add_waypoint(&format!("w{}", i), [i as i32, 0, 0], None);
+        }
+        assert_eq!(map.waypoints().len(), MAX_WAYPOINTS);
+        assert!(!map.add_waypoint("extra", [0, 0, 0], None));
+    }
+
+    #[test]
+    fn death_marker_is_replaced_and_removed_by_name() {
+        let mut map = ExploredMap::new();
+        assert!(!map.remove_waypoint(DEATH_MARKER));
+        map.set_death([1, 2, 3]);
+        map.set_death([4, 5, 6]);
+        assert_eq!(map.death(), Some([4, 5, 6]));
+        assert!(map.remove_waypoint(DEATH_MARKER));
+        assert_eq!(map.death(), None);
+    }
+
+    #[test]
+    fn map_roundtrip() {
+        let path = std::env::temp_dir().join("kimi_voxel_explored_map_test").join(EXPLORED_MAP_FILE);
+        let mut map = ExploredMap::new();
+        map.explore((-3, 7), &[[10, 20, 30, 255]; TILE_TEXELS]);
+        map.explore((0, 0), &[[1, 2, 3, 255]; TILE_TEXELS]);
+        map.explore((0, 0), &[[4, 5, 6, 255]; TILE_TEXELS]);
+        map.add_waypoint("home", [5, 64, -5], waypoint_color("cyan"));
+        map.set_death([-20, 40, 3]);
+        map.save(&path).unwrap();
+
+        let loaded = ExploredMap::load(&path).unwrap();
+        assert_eq!(loaded.tile_count(), 2);
+        assert_eq!(loaded.tile((-3, 7)).unwrap()[17], [10, 20, 30, 255]);
+        assert_eq!(loaded.tile((0, 0)).unwrap()[0], [4, 5, 6, 255]);
+        assert_eq!(loaded.waypoints(), map.waypoints());
+        assert_eq!(loaded.death(), Some([-20, 40, 3]));
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+}
@@ -1,36 +1,145 @@
 // ============================================
 // Save Header - Заголовок файла сохранения
 // ============================================
+// Версия 1: сид + позиция игрока.
+// Версия 2: позиция игрока + все параметры генерации мира.
+// Версия 3: в параметрах генерации добавлен тип мира (preset).
+// Версия 4: радиус границы мира.
+// Версия 5: точка возрождения игрока (кровать).
+// Старые заголовки читаются: недостающие параметры берутся стандартные,
+// тип мира - обычный, границы нет, возрождение на спавне мира.
+
+use std::io::Read;
 
 use serde::{Serialize, Deserialize};
 
+use crate::gpu::terrain::generation::{WorldGenConfig, WorldPreset};
+
+use super::world_file::SaveError;
+
 /// Магическое число "RUST" в ASCII
 pub const MAGIC_NUMBER: [u8; 4] = [0x52, 0x55, 0x53, 0x54];
 
 /// Версия формата сохранения
-pub const SAVE_VERSION: u32 = 1;
+pub const SAVE_VERSION: u32 = 5;
 
-/// Заголовок файла сохранения (28 байт)
+/// Заголовок файла сохранения
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SaveHeader {
     /// Магическое число для валидации
     pub magic: [u8; 4],
     /// Версия формата
     pub version: u32,
-    /// Сид мира
-    pub seed: u64,
     /// Позиция игрока
     pub player_pos: [f32; 3],
+    /// Параметры генерации мира (включая сид)
+    pub world_gen: WorldGenConfig,
+    /// Блок возрождения (кровать), None - спавн мира
+    pub respawn: Option<[i32; 3]>,
+}
+
+/// Хвост заголовка версии 1 (после magic и version)
+#[derive(Deserialize)]
+struct HeaderTailV1 {
+    seed: u64,
+    player_pos: [f32; 3],
+}
+
+/// Хвост заголовка версии 2: параметры генерации без типа мира
+#[derive(Deserialize)]
+struct HeaderTailV2 {
+    player_pos: [f32; 3],
+    seed: u64,
+    sea_level: i32,
+    cave_density: f32,
+    biome_scale: f32,
+    terrain_amplitude: f32,
+}
+
+/// Хвост заголовка версии 3: параметры генерации без границы мира
+#[derive(Deserialize)]
+struct HeaderTailV3 {
+    player_pos: [f32; 3],
+    seed: u64,
+    preset: WorldPreset,
+    sea_level: i32,
+    cave_density: f32,
+    biome_scale: f32,
+    terrain_amplitude: f32,
 }
 
 impl SaveHeader {
-    pub fn new(seed: u64, player_pos: [f32; 3]) -> Self {
+    pub fn new(world_gen: WorldGenConfig, player_pos: [f32; 3]) -> Self {
         Self {
             magic: MAGIC_NUMBER,
             version: SAVE_VERSION,
-            seed,
             player_pos,
+            world_gen,
+            respawn: None,
+        }
+    }
+
+    pub fn with_respawn(mut self, respawn: Option<[i32; 3]>) -> Self {
+        self.respawn = respawn;
+        self
+    }
+
+    /// Прочитать заголовок любой поддерживаемой версии
+    pub fn read_from(reader: &mut impl Read) -> Result<Self, SaveError> {
+        let mut prefix = [0u8; 8];
+        reader.read_exact(&mut prefix)?;
+        let magic = [prefix[0], prefix[1], prefix[2], prefix[3]];
+        let version = u32::from_le_bytes([prefix[4], prefix[5], prefix[6], prefix[7]]);
+
+        if magic != MAGIC_NUMBER {
+            return Err(SaveError::InvalidMagic);
         }
+
+        let (player_pos, world_gen, respawn) = match version {
+            1 => {
+                let tail: HeaderTailV1 = bincode::deserialize_from(reader)
+                    .map_err(|e| SaveError::Deserialize(e.to_string()))?;
+                (tail.player_pos, WorldGenConfig::with_seed(tail.seed), None)
+            }
+            2 => {
+                let tail: HeaderTailV2 = bincode::deserialize_from(reader)
+                    .map_err(|e| SaveError::Deserialize(e.to_string()))?;
+                let world_gen = WorldGenConfig {
+                    seed: tail.seed,
+                    preset: WorldPreset::Default,
+                    sea_level: tail.sea_level,
+                    cave_density: tail.cave_density,
+                    biome_scale: tail.biome_scale,
+                    terrain_amplitude: tail.terrain_amplitude,
+                    border_radius: 0,
+                };
+                (tail.player_pos, world_gen, None)
+            }
+            3 => {
+                let tail: HeaderTailV3 = bincode::deserialize_from(reader)
+                    .map_err(|e| SaveError::Deserialize(e.to_string()))?;
+                let world_gen = WorldGenConfig {
+                    seed: tail.seed,
+                    preset: tail.preset,
+                    sea_level: tail.sea_level,
+                    cave_density: tail.cave_density,
+                    biome_scale: tail.biome_scale,
+                    terrain_amplitude: tail.terrain_amplitude,
+                    border_radius: 0,
+                };
+                (tail.player_pos, world_gen, None)
+            }
+            4 => {
+                let (player_pos, world_gen) = bincode::deserialize_from(reader)
+                    .map_err(|e| SaveError::Deserialize(e.to_string()))?;
+                (player_pos, world_gen, None)
+            }
+            SAVE_VERSION => bincode::deserialize_from(reader)
+                .map_err(|e| SaveError::Deserialize(e.to_string()))?,
+            other => return Err(SaveError::UnsupportedVersion(other)),
+        };
+
+        Ok(Self::new(world_gen, player_pos).with_respawn(respawn))
     }
 
     /// Проверка валидности заголовка
@@ -41,6 +150,99 @@ impl SaveHeader {
 
 impl Default for SaveHeader {
     fn default() -> Self {
-        Self::new(0, [0.0, 64.0, 0.0])
+        Self::new(WorldGenConfig::default(), [0.0, 64.0, 0.0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_v1_header_with_default_generation() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&MAGIC_NUMBER);
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&999u64.to_le_bytes());
+        for value in [1.0f32, 2.0, 3.0] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+
+        let header = SaveHeader::read_from(&mut &bytes[..]).unwrap();
+        assert_eq!(header.world_gen, WorldGenConfig::with_seed(999));
+        assert_eq!(header.player_pos, [1.0, 2.0, 3.0]);
+        assert!(header.is_valid());
+    }
+
+    #[test]
+    fn reads_v2_header_as_default_preset() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&MAGIC_NUMBER);
+        bytes.extend_from_slice(&2u32.to_le_bytes());
+        for value in [1.0f32, 2.0, 3.0] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes.extend_from_slice(&5u64.to_le_bytes());
+        bytes.extend_from_slice(&10i32.to_le_bytes());
+        for value in [0.5f32, 2.0, 1.5] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+
+        let header = SaveHeader::read_from(&mut &bytes[..]).unwrap();
+        assert_eq!(header.world_gen.seed, 5);
+        assert_eq!(header.world_gen.preset, WorldPreset::Default);
+        assert_eq!(header.world_gen.sea_level, 10);
+        assert_eq!(header.world_gen.terrain_amplitude, 1.5);
+        assert_eq!(header.player_pos, [1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn reads_v3_header_without_border() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&MAGIC_NUMBER);
+        bytes.extend_from_slice(&3u32.to_le_bytes());
+        for value in [1.0f32, 2.0, 3.0] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes.extend_from_slice(&5u64.to_le_bytes());
+        bytes.extend_from_slice(&2u32.to_le_bytes()); // WorldPreset::Void
+        bytes.extend_from_slice(&10i32.to_le_bytes());
+        for value in [0.5f32, 2.0, 1.5] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+
+        let header = SaveHeader::read_from(&mut &bytes[..]).unwrap();
+        assert_eq!(header.world_gen.preset, WorldPreset::Void);
+        assert_eq!(header.world_gen.biome_scale, 2.0);
+        assert_eq!(header.world_gen.border_radius, 0);
+        assert_eq!(header.player_pos, [1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn reads_v4_header_without_respawn() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&MAGIC_NUMBER);
+        bytes.extend_from_slice(&4u32.to_le_bytes());
+        bytes.extend_from_slice(&bincode::serialize(&([1.0f32, 2.0, 3.0], WorldGenConfig::with_seed(9))).unwrap());
+
+        let header = SaveHeader::read_from(&mut &bytes[..]).unwrap();
+        assert_eq!(header.world_gen, WorldGenConfig::with_seed(9));
+        assert_eq!(header.respawn, None);
+    }
+
+    #[test]
+    fn v5_header_roundtrip() {
+        let mut world_gen = WorldGenConfig::with_seed(7);
+        world_gen.preset = WorldPreset::Superflat;
+        world_gen.sea_level = 12;
+        world_gen.cave_density = 0.5;
+        world_gen.border_radius = 500;
+        let saved = SaveHeader::new(world_gen, [4.0, 5.0, 6.0]).with_respawn(Some([3, 70, -8]));
+        let bytes = bincode::serialize(&saved).unwrap();
+
+        let header = SaveHeader::read_from(&mut &bytes[..]).unwrap();
+        assert_eq!(header.world_gen, world_gen);
+        assert_eq!(header.player_pos, [4.0, 5.0, 6.0]);
+        assert_eq!(header.respawn, Some([3, 70, -8]));
     }
 }
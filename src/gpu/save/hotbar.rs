@@ -0,0 +1,25 @@
+// ============================================
+// Saved Hotbar - Содержимое хотбара в сохранении
+// ============================================
+
+use serde::{Serialize, Deserialize};
+
+use crate::gpu::items::ItemType;
+
+/// Стопка в слоте хотбара или сундука
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedHotbarItem {
+    /// Предмет (блоки - с тем же номером)
+    pub item: ItemType,
+    pub count: u32,
+}
+
+/// Слоты хотбара и выбранный индекс
+/// Цвета граней не сохраняются - они восстанавливаются по предмету
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedHotbar {
+    /// Слоты (None = пустой слот)
+    pub slots: Vec<Option<SavedHotbarItem>>,
+    /// Индекс выбранного слота
+    pub selected: u32,
+}
@@ -1,14 +1,31 @@
 // ============================================
 // Save System - Система сохранения мира
 // ============================================
-// Формат world.dat с палитрой и ZSTD сжатием
+// Мир хранится директорией с регионами 32x32 чанка (палитра + ZSTD),
+// старый монолитный world.dat мигрируется при первой загрузке.
+// Миры лежат в папке сохранений, список и метаданные - WorldManager
 
 mod header;
 mod chunk;
 mod palette;
 mod world_file;
+mod region;
+mod region_world;
+mod hotbar;
+mod block_entity;
+mod subvoxel_section;
+mod schematic;
+mod explored_map;
+mod world_manager;
 
 pub use header::{SaveHeader, MAGIC_NUMBER, SAVE_VERSION};
 pub use chunk::CompressedChunk;
 pub use palette::BlockPalette;
-pub use world_file::WorldFile;
+pub use world_file::{WorldFile, LoadedWorld, SaveError};
+pub use region::{RegionFile, REGION_SIZE};
+pub use region_world::RegionWorld;
+pub use hotbar::{SavedHotbar, SavedHotbarItem};
+pub use block_entity::{SavedBlockEntity, SavedBlockEntityData};
+pub use schematic::Schematic;
+pub use explored_map::{ExploredMap, MapTile, Waypoint, waypoint_color, DEATH_MARKER, EXPLORED_MAP_FILE, MAX_WAYPOINTS, WAYPOINT_COLORS};
+pub use world_manager::{WorldManager, WorldMetadata, WorldEntry, METADATA_FILE, THUMBNAIL_FILE};
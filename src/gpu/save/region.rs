@@ -0,0 +1,325 @@
+// ============================================
+// Region File - Регион 32x32 чанка в одном файле
+// ============================================
+// Формат по мотивам Anvil:
+//
+// [Заголовок: 3 сектора по 4 КиБ]
+//   magic (4) + version (4) + 1024 x (offset_sectors: u32, length_bytes: u32)
+// [Данные чанков: выровнены по секторам]
+//   каждый чанк = ZSTD(bincode(Vec<SavedSection>))
+//
+// Запись инкрементальная: новая копия чанка ложится в первый свободный
+// промежуток секторов (карта занятости строится из таблицы смещений при
+// открытии), старые сектора освобождаются после обновления таблицы.
+// Сбой посреди записи оставляет прежнюю копию, а освободившееся место
+// переиспользуется, так что файл не растёт бесконечно.
+
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use super::world_file::{SaveError, SavedSection};
+
+/// Размер стороны региона в чанках
+pub const REGION_SIZE: i32 = 32;
+/// Количество чанков в регионе
+pub const REGION_CHUNKS: usize = (REGION_SIZE * REGION_SIZE) as usize;
+/// Размер сектора в байтах
+pub const SECTOR_SIZE: u64 = 4096;
+
+/// Магическое число "VXRG"
+const REGION_MAGIC: [u8; 4] = [0x56, 0x58, 0x52, 0x47];
+/// Версия формата региона
+const REGION_VERSION: u32 = 1;
+/// Заголовок занимает 3 сектора (8 + 1024 * 8 байт = 8200)
+const HEADER_SECTORS: u32 = 3;
+
+/// Координаты региона по координатам чанка
+#[inline]
+pub fn region_coords(chunk_x: i32, chunk_z: i32) -> (i32, i32) {
+    (chunk_x.div_euclid(REGION_SIZE), chunk_z.div_euclid(REGION_SIZE))
+}
+
+/// Индекс чанка внутри региона
+#[inline]
+fn local_index(chunk_x: i32, chunk_z: i32) -> usize {
+    let lx = chunk_x.rem_euclid(REGION_SIZE);
+    let lz = chunk_z.rem_euclid(REGION_SIZE);
+    (lz * REGION_SIZE + lx) as usize
+}
+
+/// Запись в таблице смещений
+#[derive(Debug, Clone, Copy, Default)]
+struct ChunkLocation {
+    /// Смещение в секторах (0 = чанк отсутствует)
+    offset: u32,
+    /// Длина данных в байтах
+    length: u32,
+}
+
+impl ChunkLocation {
+    fn sectors(&self) -> u32 {
+        (self.length as u64).div_ceil(SECTOR_SIZE) as u32
+    }
+
+    fn is_empty(&self) -> bool {
+        self.offset == 0
+    }
+}
+
+/// Открытый файл региона
+pub struct RegionFile {
+    file: File,
+    locations: Vec<ChunkLocation>,
+    /// Занятость секторов (заголовок и данные чанков), за концом - свободно
+    used_sectors: Vec<bool>,
+}
+
+impl RegionFile {
+    /// Путь к файлу региона в директории мира
+    pub fn path_for(region_dir: &Path, region_x: i32, region_z: i32) -> PathBuf {
+        region_dir.join(format!("r.{}.{}.vxr", region_x, region_z))
+    }
+
+    /// Открыть существующий регион или создать новый
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, SaveError> {
+        let path = path.as_ref();
+        let exists = path.exists();
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+
+        if !exists || file.metadata()?.len() < HEADER_SECTORS as u64 * SECTOR_SIZE {
+            let mut region = Self {
+                file,
+                locations: vec![ChunkLocation::default(); REGION_CHUNKS],
+                used_sectors: vec![true; HEADER_SECTORS as usize],
+            };
+            region.write_header()?;
+            return Ok(region);
+        }
+
+        let mut header = vec![0u8; (HEADER_SECTORS as u64 * SECTOR_SIZE) as usize];
+        file.seek(SeekFrom::Start(0))?;
+        file.read_exact(&mut header)?;
+
+        if header[0..4] != REGION_MAGIC {
+            return Err(SaveError::InvalidMagic);
+        }
+        let version = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+        if version != REGION_VERSION {
+            return Err(SaveError::UnsupportedVersion(version));
+        }
+
+        let locations = (0..REGION_CHUNKS)
+            .map(|i| {
+                let base = 8 + i * 8;
+                let offset = u32::from_le_bytes(header[base..base + 4].try_into().unwrap());
+                let length = u32::from_le_bytes(header[base + 4..base + 8].try_into().unwrap());
+                ChunkLocation { offset, length }
+            })
+            .collect();
+
+        let mut region = Self { file, locations, used_sectors: vec![true; HEADER_SECTORS as usize] };
+        for i in 0..REGION_CHUNKS {
+            let location = region.locations[i];
+            if !location.is_empty() {
+                region.mark_sectors(location.offset, location.sectors(), true);
+            }
+        }
+        Ok(region)
+    }
+
+    /// Есть ли данные для чанка
+    pub fn has_chunk(&self, chunk_x: i32, chunk_z: i32) -> bool {
+        !self.locations[local_index(chunk_x, chunk_z)].is_empty()
+    }
+
+    /// Координаты всех чанков с данными (локальные 0..32)
+    pub fn stored_chunks(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.locations
+            .iter()
+            .enumerate()
+            .filter(|(_, loc)| !loc.is_empty())
+            .map(|(i, _)| (i as i32 % REGION_SIZE, i as i32 / REGION_SIZE))
+    }
+
+    /// Прочитать секции чанка
+    pub fn read_chunk(&mut self, chunk_x: i32, chunk_z: i32) -> Result<Option<Vec<SavedSection>>, SaveError> {
+        let location = self.locations[local_index(chunk_x, chunk_z)];
+        if location.is_empty() {
+            return Ok(None);
+        }
+
+        let mut compressed = vec![0u8; location.length as usize];
+        self.file.seek(SeekFrom::Start(location.offset as u64 * SECTOR_SIZE))?;
+        self.file.read_exact(&mut compressed)?;
+
+        let bytes = zstd::decode_all(&compressed[..])
+            .map_err(|e| SaveError::Compression(e.to_string()))?;
+        let sections: Vec<SavedSection> = bincode::deserialize(&bytes)
+            .map_err(|e| SaveError::Deserialize(e.to_string()))?;
+        Ok(Some(sections))
+    }
+
+    /// Записать секции чанка (пустой список удаляет чанк из таблицы)
+    pub fn write_chunk(&mut self, chunk_x: i32, chunk_z: i32, sections: &[SavedSection]) -> Result<(), SaveError> {
+        let index = local_index(chunk_x, chunk_z);
+        let old = self.locations[index];
+
+        if sections.is_empty() {
+            self.locations[index] = ChunkLocation::default();
+            self.write_location(index)?;
+            self.release(old);
+            return Ok(());
+        }
+
+        let bytes = bincode::serialize(sections)
+            .map_err(|e| SaveError::Serialize(e.to_string()))?;
+        let compressed = zstd::encode_all(&bytes[..], 3)
+            .map_err(|e| SaveError::Compression(e.to_string()))?;
+
+        let needed = (compressed.len() as u64).div_ceil(SECTOR_SIZE) as u32;
+
+        // Старые сектора ещё заняты: новая копия их не затирает
+        let offset = self.allocate(needed);
+
+        // Дополняем до границы сектора
+        let length = compressed.len() as u32;
+        let mut padded = compressed;
+        padded.resize((needed as u64 * SECTOR_SIZE) as usize, 0);
+
+        self.file.seek(SeekFrom::Start(offset as u64 * SECTOR_SIZE))?;
+        self.file.write_all(&padded)?;
+
+        self.mark_sectors(offset, needed, true);
+        self.locations[index] = ChunkLocation { offset, length };
+        self.write_location(index)?;
+        self.release(old);
+        Ok(())
+    }
+
+    /// Первый свободный промежуток из `needed` секторов. Свободный хвост
+    /// карты продолжается за концом файла
+    fn allocate(&self, needed: u32) -> u32 {
+        let mut run = 0;
+        for (sector, &used) in self.used_sectors.iter().enumerate() {
+            run = if used { 0 } else { run + 1 };
+            if run == needed {
+                return sector as u32 + 1 - needed;
+            }
+        }
+        self.used_sectors.len() as u32 - run
+    }
+
+    /// Освободить сектора прежней копии чанка
+    fn release(&mut self, location: ChunkLocation) {
+        if !location.is_empty() {
+            self.mark_sectors(location.offset, location.sectors(), false);
+        }
+    }
+
+    fn mark_sectors(&mut self, offset: u32, count: u32, used: bool) {
+        let end = (offset + count) as usize;
+        if self.used_sectors.len() < end {
+            self.used_sectors.resize(end, false);
+        }
+        self.used_sectors[offset as usize..end].fill(used);
+    }
+
+    /// Сбросить данные на диск
+    pub fn flush(&mut self) -> Result<(), SaveError> {
+        self.file.flush()?;
+        Ok(())
+    }
+
+    fn write_header(&mut self) -> Result<(), SaveError> {
+        let mut header = Vec::with_capacity((HEADER_SECTORS as u64 * SECTOR_SIZE) as usize);
+        header.extend_from_slice(&REGION_MAGIC);
+        header.extend_from_slice(&REGION_VERSION.to_le_bytes());
+        for location in &self.locations {
+            header.extend_from_slice(&location.offset.to_le_bytes());
+            header.extend_from_slice(&location.length.to_le_bytes());
+        }
+        header.resize((HEADER_SECTORS as u64 * SECTOR_SIZE) as usize, 0);
+
+        self.file.seek(SeekFrom::Start(0))?;
+        self.file.write_all(&header)?;
+        Ok(())
+    }
+
+    fn write_location(&mut self, index: usize) -> Result<(), SaveError> {
+        let location = self.locations[index];
+        let mut entry = [0u8; 8];
+        entry[0..4].copy_from_slice(&location.offset.to_le_bytes());
+        entry[4..8].copy_from_slice(&location.length.to_le_bytes());
+
+        self.file.seek(SeekFrom::Start(8 + index as u64 * 8))?;
+        self.file.write_all(&entry)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::save::WorldFile;
+    use crate::gpu::terrain::BlockPos;
+
+    /// Чанк из `count` секций случайных блоков (почти не сжимается:
+    /// секция - примерно сектор)
+    fn noisy_chunk(count: i32, seed: &mut u32) -> Vec<SavedSection> {
+        let mut changes = Vec::new();
+        for sy in 0..count {
+            for i in 0..4096 {
+                *seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                let block = (*seed >> 24) as u8 | 1;
+                changes.push((BlockPos::new(i % 16, sy * 16 + i / 256, i / 16 % 16), block));
+            }
+        }
+        WorldFile::sections_from_changes(changes)
+    }
+
+    #[test]
+    fn rewrites_reuse_freed_sectors() {
+        let dir = std::env::temp_dir().join("kimi_voxel_region_sectors_test");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = RegionFile::path_for(&dir, 0, 0);
+
+        let mut seed = 7;
+        let mut max_sectors = 0;
+        let mut last = Vec::new();
+        let neighbor_sectors;
+        {
+            let mut region = RegionFile::open(&path).unwrap();
+            // Соседний чанк не даёт освобождённому месту оказаться хвостом
+            region.write_chunk(1, 0, &noisy_chunk(1, &mut seed)).unwrap();
+            neighbor_sectors = region.locations[1].sectors();
+            for round in 0..40 {
+                last = noisy_chunk(1 + round / 4, &mut seed);
+                region.write_chunk(0, 0, &last).unwrap();
+                max_sectors = max_sectors.max(region.locations[0].sectors());
+            }
+            region.flush().unwrap();
+        }
+
+        // Растущий чанк ходит по нескольким промежуткам, а не копится в
+        // конце (без переиспользования файл - сумма всех его размеров)
+        let size = std::fs::metadata(&path).unwrap().len();
+        let bound = (HEADER_SECTORS + neighbor_sectors + 4 * max_sectors) as u64 * SECTOR_SIZE;
+        assert!(size <= bound, "регион {} байт, предел {}", size, bound);
+
+        // Карта занятости восстанавливается из таблицы, данные целы
+        let mut region = RegionFile::open(&path).unwrap();
+        assert_eq!(region.read_chunk(0, 0).unwrap().map(|s| s.len()), Some(last.len()));
+        assert!(region.read_chunk(1, 0).unwrap().is_some());
+        let occupied = region.used_sectors.iter().filter(|&&used| used).count() as u32;
+        assert_eq!(occupied, HEADER_SECTORS + neighbor_sectors + region.locations[0].sectors());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
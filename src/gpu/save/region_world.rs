@@ -0,0 +1,290 @@
+// ============================================
+// Region World - Мир как директория с регионами
+// ============================================
+// Структура:
+//   world/level.dat          - заголовок (параметры генерации, позиция) + глобальные данные
+//   world/region/r.X.Z.vxr   - регионы 32x32 чанка
+//
+// Сохраняются только чанки, изменённые с прошлого сохранения.
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{Read, Write, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use serde::{Serialize, Deserialize};
+
+use crate::gpu::blocks::BlockType;
+use crate::gpu::terrain::{BlockPos, WorldChanges, CHUNK_SIZE};
+use crate::gpu::subvoxel::SubVoxel;
+
+use super::header::SaveHeader;
+use super::hotbar::SavedHotbar;
+use super::block_entity::SavedBlockEntity;
+use super::region::{RegionFile, region_coords, REGION_SIZE};
+use super::subvoxel_section::{SavedSubVoxelChunk, encode_subvoxels, decode_subvoxels};
+use super::world_file::{WorldFile, LoadedWorld, SaveError};
+
+/// Имя файла с глобальными данными мира
+const LEVEL_FILE: &str = "level.dat";
+/// Поддиректория с регионами
+const REGION_DIR: &str = "region";
+
+/// Глобальные данные мира (сжимается ZSTD)
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LevelBody {
+    /// Суб-воксели по чанкам (палитра + упакованное октодерево)
+    #[serde(default)]
+    subvoxel_chunks: Vec<SavedSubVoxelChunk>,
+    /// Хотбар игрока
+    #[serde(default)]
+    hotbar: Option<SavedHotbar>,
+    /// Цветовая тема мира
+    #[serde(default)]
+    palette_theme: u8,
+    /// Данные блоков (содержимое сундуков)
+    #[serde(default)]
+    block_entities: Vec<SavedBlockEntity>,
+}
+
+/// Мир в региональном формате
+pub struct RegionWorld {
+    root: PathBuf,
+    /// Открытые файлы регионов
+    regions: HashMap<(i32, i32), RegionFile>,
+}
+
+impl RegionWorld {
+    /// Существует ли мир в этой директории
+    pub fn exists(root: impl AsRef<Path>) -> bool {
+        root.as_ref().join(LEVEL_FILE).exists()
+    }
+
+    /// Открыть (или создать) директорию мира
+    pub fn open(root: impl AsRef<Path>) -> Result<Self, SaveError> {
+        let root = root.as_ref().to_path_buf();
+        fs::create_dir_all(root.join(REGION_DIR))?;
+        Ok(Self {
+            root,
+            regions: HashMap::new(),
+        })
+    }
+
+    /// Записать level.dat
+    pub fn save_level(
+        &self,
+        header: SaveHeader,
+        subvoxels: Vec<SubVoxel>,
+        hotbar: Option<SavedHotbar>,
+        palette_theme: u8,
+        block_entities: Vec<SavedBlockEntity>,
+    ) -> Result<(), SaveError> {
+        // Пишем во временный файл и переименовываем, чтобы не потерять мир при сбое
+        let tmp_path = self.root.join(format!("{}.tmp", LEVEL_FILE));
+        {
+            let mut writer = BufWriter::new(File::create(&tmp_path)?);
+
+            let header_bytes = bincode::serialize(&header)
+                .map_err(|e| SaveError::Serialize(e.to_string()))?;
+            writer.write_all(&header_bytes)?;
+
+            let body = LevelBody {
+                subvoxel_chunks: encode_subvoxels(&subvoxels)?,
+                hotbar,
+                palette_theme,
+                block_entities,
+            };
+            let body_bytes = bincode::serialize(&body)
+                .map_err(|e| SaveError::Serialize(e.to_string()))?;
+            let compressed = zstd::encode_all(&body_bytes[..], 3)
+                .map_err(|e| SaveError::Compression(e.to_string()))?;
+            writer.write_all(&compressed)?;
+            writer.flush()?;
+        }
+        fs::rename(&tmp_path, self.root.join(LEVEL_FILE))?;
+        Ok(())
+    }
+
+    /// Записать изменения указанных чанков
+    /// Возвращает количество записанных чанков
+    pub fn save_chunks(
+        &mut self,
+        world_changes: &WorldChanges,
+        chunks: impl IntoIterator<Item = (i32, i32)>,
+    ) -> Result<usize, SaveError> {
+        let mut written = 0;
+        for (chunk_x, chunk_z) in chunks {
+            let changes = world_changes.get_changes_for_chunk(chunk_x, chunk_z, CHUNK_SIZE);
+            let sections = WorldFile::sections_from_changes(changes);
+            self.region_for(chunk_x, chunk_z)?.write_chunk(chunk_x, chunk_z, &sections)?;
+            written += 1;
+        }
+
+        for region in self.regions.values_mut() {
+            region.flush()?;
+        }
+        Ok(written)
+    }
+
+    /// Загрузить весь мир (level.dat + все регионы)
+    pub fn load(&mut self) -> Result<LoadedWorld, SaveError> {
+        let (header, body) = self.load_level()?;
+
+        let mut changes: HashMap<BlockPos, BlockType> = HashMap::new();
+        for (region_x, region_z) in self.list_regions()? {
+            let path = RegionFile::path_for(&self.root.join(REGION_DIR), region_x, region_z);
+            let mut region = RegionFile::open(path)?;
+            let stored: Vec<(i32, i32)> = region.stored_chunks().collect();
+            for (lx, lz) in stored {
+                let chunk_x = region_x * REGION_SIZE + lx;
+                let chunk_z = region_z * REGION_SIZE + lz;
+                if let Some(sections) = region.read_chunk(chunk_x, chunk_z)? {
+                    WorldFile::extract_changes_into(&sections, &mut changes);
+                }
+            }
+            self.regions.insert((region_x, region_z), region);
+        }
+
+        Ok(LoadedWorld {
+            world_gen: header.world_gen,
+            player_pos: header.player_pos,
+            respawn: header.respawn,
+            changes,
+            subvoxels: decode_subvoxels(&body.subvoxel_chunks),
+            hotbar: body.hotbar,
+            palette_theme: body.palette_theme,
+            block_entities: body.block_entities,
+        })
+    }
+
+    /// Перенести мир из старого монолитного world.dat
+    /// Старый файл переименовывается в *.bak
+    pub fn migrate_from_legacy(
+        legacy_path: impl AsRef<Path>,
+        root: impl AsRef<Path>,
+    ) -> Result<LoadedWorld, SaveError> {
+        let legacy_path = legacy_path.as_ref();
+        let loaded = WorldFile::load(legacy_path)?;
+
+        let mut world = Self::open(root)?;
+        let mut world_changes = WorldChanges::new();
+        for (pos, block) in &loaded.changes {
+            world_changes.set_block(*pos, *block);
+        }
+        let chunks = world_changes.take_unsaved_chunks();
+        world.save_chunks(&world_changes, chunks)?;
+        let header = SaveHeader::new(loaded.world_gen, loaded.player_pos).with_respawn(loaded.respawn);
+        world.save_level(header, loaded.subvoxels.clone(), None, loaded.palette_theme, Vec::new())?;
+
+        let mut backup = legacy_path.as_os_str().to_owned();
+        backup.push(".bak");
+        fs::rename(legacy_path, backup)?;
+
+        Ok(loaded)
+    }
+
+    /// Прочитать только заголовок level.dat (сид и позиция без загрузки мира)
+    pub fn read_header(root: impl AsRef<Path>) -> Result<SaveHeader, SaveError> {
+        let mut reader = BufReader::new(File::open(root.as_ref().join(LEVEL_FILE))?);
+        SaveHeader::read_from(&mut reader)
+    }
+
+    fn load_level(&self) -> Result<(SaveHeader, LevelBody), SaveError> {
+        let mut reader = BufReader::new(File::open(self.root.join(LEVEL_FILE))?);
+
+        let header = SaveHeader::read_from(&mut reader)?;
+
+        let mut compressed = Vec::new();
+        reader.read_to_end(&mut compressed)?;
+        let body_bytes = zstd::decode_all(&compressed[..])
+            .map_err(|e| SaveError::Compression(e.to_string()))?;
+        let body: LevelBody = bincode::deserialize(&body_bytes)
+            .map_err(|e| SaveError::Deserialize(e.to_string()))?;
+
+        Ok((header, body))
+    }
+
+    /// Координаты всех файлов регионов на диске
+    fn list_regions(&self) -> Result<Vec<(i32, i32)>, SaveError> {
+        let mut regions = Vec::new();
+        for entry in fs::read_dir(self.root.join(REGION_DIR))? {
+            let name = entry?.file_name();
+            let Some(name) = name.to_str() else { continue };
+            let mut parts = name.split('.');
+            if let (Some("r"), Some(x), Some(z), Some("vxr")) = (parts.next(), parts.next(), parts.next(), parts.next()) {
+                if let (Ok(x), Ok(z)) = (x.parse(), z.parse()) {
+                    regions.push((x, z));
+                }
+            }
+        }
+        Ok(regions)
+    }
+
+    fn region_for(&mut self, chunk_x: i32, chunk_z: i32) -> Result<&mut RegionFile, SaveError> {
+        let coords = region_coords(chunk_x, chunk_z);
+        if !self.regions.contains_key(&coords) {
+            let path = RegionFile::path_for(&self.root.join(REGION_DIR), coords.0, coords.1);
+            self.regions.insert(coords, RegionFile::open(path)?);
+        }
+        Ok(self.regions.get_mut(&coords).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::blocks::{STONE, DIRT, AIR};
+    use crate::gpu::save::{SavedHotbarItem, SavedBlockEntityData};
+    use crate::gpu::terrain::generation::WorldGenConfig;
+
+    #[test]
+    fn test_region_incremental_roundtrip() {
+        let root = std::env::temp_dir().join("kimi_voxel_region_test");
+        let _ = fs::remove_dir_all(&root);
+
+        let mut world_changes = WorldChanges::new();
+        world_changes.set_block(BlockPos::new(10, 64, 10), STONE);
+        world_changes.set_block(BlockPos::new(-40, 12, 700), DIRT);
+        world_changes.set_block(BlockPos::new(12, 64, 10), AIR);
+
+        {
+            let mut world = RegionWorld::open(&root).unwrap();
+            let chunks = world_changes.take_unsaved_chunks();
+            assert_eq!(world.save_chunks(&world_changes, chunks).unwrap(), 2);
+            let hotbar = SavedHotbar {
+                slots: vec![Some(SavedHotbarItem { item: DIRT.into(), count: 1 }), None],
+                selected: 1,
+            };
+            let header = SaveHeader::new(WorldGenConfig::with_seed(777), [1.0, 2.0, 3.0]).with_respawn(Some([5, 64, 5]));
+            let chest = SavedBlockEntity {
+                pos: [3, 70, -3],
+                data: SavedBlockEntityData::Chest {
+                    slots: vec![None, Some(SavedHotbarItem { item: STONE.into(), count: 12 })],
+                },
+            };
+            world.save_level(header, Vec::new(), Some(hotbar), 2, vec![chest]).unwrap();
+        }
+
+        // Второе сохранение трогает только изменённый чанк
+        world_changes.set_block(BlockPos::new(11, 64, 10), DIRT);
+        {
+            let mut world = RegionWorld::open(&root).unwrap();
+            let chunks = world_changes.take_unsaved_chunks();
+            assert_eq!(world.save_chunks(&world_changes, chunks).unwrap(), 1);
+        }
+
+        let loaded = RegionWorld::open(&root).unwrap().load().unwrap();
+        assert_eq!(loaded.world_gen.seed, 777);
+        assert_eq!(loaded.respawn, Some([5, 64, 5]));
+        assert_eq!(loaded.hotbar.map(|h| h.selected), Some(1));
+        assert_eq!(loaded.palette_theme, 2);
+        assert_eq!(loaded.block_entities.len(), 1);
+        assert_eq!(loaded.block_entities[0].pos, [3, 70, -3]);
+        assert_eq!(loaded.changes.len(), 4);
+        assert_eq!(loaded.changes.get(&BlockPos::new(-40, 12, 700)), Some(&DIRT));
+        assert_eq!(loaded.changes.get(&BlockPos::new(11, 64, 10)), Some(&DIRT));
+        assert_eq!(loaded.changes.get(&BlockPos::new(12, 64, 10)), Some(&AIR));
+
+        fs::remove_dir_all(&root).ok();
+    }
+}
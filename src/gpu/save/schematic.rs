@@ -0,0 +1,312 @@
+// ============================================
+// Schematic - Копия области блоков и субвокселей
+// ============================================
+// Область мира (параллелепипед) вместе с субвокселями внутри неё.
+// Смещение offset - от блока игрока при копировании до угла области:
+// вставка кладёт постройку так же относительно игрока.
+//
+// Файл .schem: MAGIC + версия + ZSTD(bincode(SchematicFile)). Блоки
+// хранятся палитрой строковых ID реестра, поэтому файл переносится
+// между мирами и модами (неизвестные блоки загружаются воздухом).
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::gpu::blocks::{global_registry, BlockType, AIR};
+use crate::gpu::subvoxel::{SubVoxel, SubVoxelLevel, SubVoxelPos, SubVoxelWorld};
+
+use super::world_file::SaveError;
+
+/// Сигнатура файла схематики
+const SCHEMATIC_MAGIC: [u8; 4] = *b"KVSC";
+/// Версия формата
+const SCHEMATIC_VERSION: u32 = 1;
+
+/// Субвоксель в файле: позиция блока от угла области + индекс палитры
+#[derive(Serialize, Deserialize)]
+struct SavedSubVoxel {
+    block: [i32; 3],
+    sub: [u8; 3],
+    level: SubVoxelLevel,
+    palette: u16,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SchematicFile {
+    size: [i32; 3],
+    offset: [i32; 3],
+    palette: Vec<String>,
+    /// Индексы палитры, порядок x -> z -> y
+    blocks: Vec<u16>,
+    subvoxels: Vec<SavedSubVoxel>,
+}
+
+/// Скопированная область
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schematic {
+    /// Размер в блоках
+    size: [i32; 3],
+    /// От блока игрока до минимального угла области
+    offset: [i32; 3],
+    /// Блоки, порядок x -> z -> y
+    blocks: Vec<BlockType>,
+    /// Субвоксели, позиции блоков от минимального угла
+    subvoxels: Vec<SubVoxel>,
+}
+
+impl Schematic {
+    /// Скопировать область min..=max. origin - блок игрока
+    pub fn capture(
+        min: [i32; 3],
+        max: [i32; 3],
+        origin: [i32; 3],
+        block_at: impl Fn(i32, i32, i32) -> BlockType,
+        storage: &SubVoxelWorld,
+    ) -> Self {
+        let size = [max[0] - min[0] + 1, max[1] - min[1] + 1, max[2] - min[2] + 1];
+        let mut blocks = Vec::with_capacity((size[0] * size[1] * size[2]) as usize);
+        for y in 0..size[1] {
+            for z in 0..size[2] {
+                for x in 0..size[0] {
+                    blocks.push(block_at(min[0] + x, min[1] + y, min[2] + z));
+                }
+            }
+        }
+
+        let mut subvoxels = storage.get_in_region(min[0], min[1], min[2], max[0], max[1], max[2]);
+        for sv in &mut subvoxels {
+            sv.pos.block_x -= min[0];
+            sv.pos.block_y -= min[1];
+            sv.pos.block_z -= min[2];
+        }
+
+        Self {
+            size,
+            offset: [min[0] - origin[0], min[1] - origin[1], min[2] - origin[2]],
+            blocks,
+            subvoxels,
+        }
+    }
+
+    pub fn size(&self) -> [i32; 3] {
+        self.size
+    }
+
+    /// Минимальный угол при вставке у блока игрока origin
+    pub fn corner(&self, origin: [i32; 3]) -> [i32; 3] {
+        [origin[0] + self.offset[0], origin[1] + self.offset[1], origin[2] + self.offset[2]]
+    }
+
+    /// Блоки с позициями от минимального угла
+    pub fn blocks(&self) -> impl Iterator<Item = ([i32; 3], BlockType)> + '_ {
+        let [sx, _, sz] = self.size;
+        self.blocks.iter().enumerate().map(move |(i, &block)| {
+            let i = i as i32;
+            ([i % sx, i / (sx * sz), (i / sx) % sz], block)
+        })
+    }
+
+    /// Субвоксели с позициями блоков от минимального угла
+    pub fn subvoxels(&self) -> &[SubVoxel] {
+        &self.subvoxels
+    }
+
+    /// Повёрнутая вокруг вертикали на quarter_turns * 90° (по часовой сверху)
+    pub fn rotated(&self, quarter_turns: u32) -> Self {
+        (0..quarter_turns % 4).fold(self.clone(), |schematic, _| schematic.rotated_once())
+    }
+
+    /// Поворот на 90°: (x, z) -> (size_z - 1 - z, x)
+    fn rotated_once(&self) -> Self {
+        let [sx, sy, sz] = self.size;
+        let size = [sz, sy, sx];
+        let mut blocks = vec![AIR; self.blocks.len()];
+        for ([x, y, z], block) in self.blocks() {
+            let (nx, nz) = (sz - 1 - z, x);
+            blocks[((y * sx + nz) * sz + nx) as usize] = block;
+        }
+
+        let subvoxels = self
+            .subvoxels
+            .iter()
+            .map(|sv| {
+                let pos = sv.pos;
+                let last = pos.level.divisions() - 1;
+                let rotated = SubVoxelPos::new(
+                    sz - 1 - pos.block_z,
+                    pos.block_y,
+                    pos.block_x,
+                    last - pos.sub_z,
+                    pos.sub_y,
+                    pos.sub_x,
+                    pos.level,
+                );
+                SubVoxel { pos: rotated, block_type: sv.block_type }
+            })
+            .collect();
+
+        // Клетка q = offset + local переходит в (-q_z, q_x)
+        let [ox, oy, oz] = self.offset;
+        Self { size, offset: [-oz - sz + 1, oy, ox], blocks, subvoxels }
+    }
+
+    /// Записать в файл (ID блоков из реестра)
+    pub fn save(&self, path: &Path) -> Result<(), SaveError> {
+        let file = {
+            let registry = global_registry().read().map_err(|_| SaveError::Serialize("Block registry lock poisoned".into()))?;
+            self.to_file(|block| registry.get_by_numeric(block).map_or_else(|| "air".to_string(), |def| def.id.clone()))
+        };
+        let body = bincode::serialize(&file).map_err(|e| SaveError::Serialize(e.to_string()))?;
+        let compressed = zstd::encode_all(&body[..], 3).map_err(|e| SaveError::Compression(e.to_string()))?;
+
+        let mut bytes = Vec::with_capacity(compressed.len() + 8);
+        bytes.extend_from_slice(&SCHEMATIC_MAGIC);
+        bytes.extend_from_slice(&SCHEMATIC_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&compressed);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, bytes)?;
+        Ok(())
+    }
+
+    /// Прочитать из файла (неизвестные блоки - воздух)
+    pub fn load(path: &Path) -> Result<Self, SaveError> {
+        let bytes = fs::read(path)?;
+        if bytes.len() < 8 || bytes[..4] != SCHEMATIC_MAGIC {
+            return Err(SaveError::InvalidMagic);
+        }
+        let version = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+        if version != SCHEMATIC_VERSION {
+            return Err(SaveError::UnsupportedVersion(version));
+        }
+        let body = zstd::decode_all(&bytes[8..]).map_err(|e| SaveError::Compression(e.to_string()))?;
+        let file: SchematicFile = bincode::deserialize(&body).map_err(|e| SaveError::Deserialize(e.to_string()))?;
+
+        let registry = global_registry().read().map_err(|_| SaveError::Deserialize("Block registry lock poisoned".into()))?;
+        Self::from_file(file, |id| {
+            let block = registry.get_numeric_id(id);
+            if block.is_none() {
+                log::warn!("Схематика: неизвестный блок '{}', заменён воздухом", id);
+            }
+            block
+        })
+    }
+
+    fn to_file(&self, id_of: impl Fn(BlockType) -> String) -> SchematicFile {
+        let mut palette: Vec<BlockType> = Vec::new();
+        let mut index_of = |block: BlockType| match palette.iter().position(|&known| known == block) {
+            Some(index) => index as u16,
+            None => {
+                palette.push(block);
+                (palette.len() - 1) as u16
+            }
+        };
+
+        let blocks = self.blocks.iter().map(|&block| index_of(block)).collect();
+        let subvoxels = self
+            .subvoxels
+            .iter()
+            .map(|sv| SavedSubVoxel {
+                block: [sv.pos.block_x, sv.pos.block_y, sv.pos.block_z],
+                sub: [sv.pos.sub_x, sv.pos.sub_y, sv.pos.sub_z],
+                level: sv.pos.level,
+                palette: index_of(sv.block_type),
+            })
+            .collect();
+
+        SchematicFile {
+            size: self.size,
+            offset: self.offset,
+            palette: palette.into_iter().map(id_of).collect(),
+            blocks,
+            subvoxels,
+        }
+    }
+
+    fn from_file(file: SchematicFile, numeric_of: impl Fn(&str) -> Option<BlockType>) -> Result<Self, SaveError> {
+        let volume = file.size.iter().map(|&side| side.max(0) as usize).product::<usize>();
+        if file.size.iter().any(|&side| side <= 0) || file.blocks.len() != volume {
+            return Err(SaveError::Deserialize("Schematic size does not match its blocks".into()));
+        }
+        let palette: Vec<BlockType> = file.palette.iter().map(|id| numeric_of(id).unwrap_or(AIR)).collect();
+        let block = |index: u16| {
+            palette.get(index as usize).copied()
+                .ok_or_else(|| SaveError::Deserialize(format!("Palette index {} out of range", index)))
+        };
+
+        let blocks = file.blocks.iter().map(|&index| block(index)).collect::<Result<_, _>>()?;
+        let mut subvoxels = Vec::with_capacity(file.subvoxels.len());
+        for saved in &file.subvoxels {
+            let [bx, by, bz] = saved.block;
+            let [sx, sy, sz] = saved.sub;
+            let pos = SubVoxelPos::new(bx, by, bz, sx, sy, sz, saved.level);
+            subvoxels.push(SubVoxel { pos, block_type: block(saved.palette)? });
+        }
+
+        Ok(Self { size: file.size, offset: file.offset, blocks, subvoxels })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::blocks::{DIRT, STONE};
+
+    /// Столбик 2x1x3: камень в углу (0, 0, 0), земля в (1, 0, 2),
+    /// субвоксель 1/2 в клетке (0, 0, 1)
+    fn sample() -> Schematic {
+        let block_at = |x: i32, y: i32, z: i32| match (x, y, z) {
+            (10, 5, 20) => STONE,
+            (11, 5, 22) => DIRT,
+            _ => AIR,
+        };
+        let mut storage = SubVoxelWorld::new();
+        storage.set(SubVoxelPos::new(10, 5, 21, 1, 0, 0, SubVoxelLevel::Half), STONE);
+        storage.set(SubVoxelPos::new(50, 5, 21, 0, 0, 0, SubVoxelLevel::Half), STONE);
+        Schematic::capture([10, 5, 20], [11, 5, 22], [8, 5, 20], block_at, &storage)
+    }
+
+    #[test]
+    fn captures_blocks_and_subvoxels_in_region() {
+        let schematic = sample();
+        assert_eq!(schematic.size(), [2, 1, 3]);
+        assert_eq!(schematic.corner([8, 5, 20]), [10, 5, 20]);
+        let solid: Vec<_> = schematic.blocks().filter(|(_, block)| *block != AIR).collect();
+        assert_eq!(solid, vec![([0, 0, 0], STONE), ([1, 0, 2], DIRT)]);
+        assert_eq!(schematic.subvoxels().len(), 1);
+        assert_eq!(schematic.subvoxels()[0].pos.block_z, 1);
+    }
+
+    #[test]
+    fn quarter_turn_rotates_blocks_subvoxels_and_offset() {
+        let schematic = sample().rotated(1);
+        assert_eq!(schematic.size(), [3, 1, 2]);
+        // (x, z) -> (size_z - 1 - z, x)
+        let solid: Vec<_> = schematic.blocks().filter(|(_, block)| *block != AIR).collect();
+        assert_eq!(solid, vec![([2, 0, 0], STONE), ([0, 0, 1], DIRT)]);
+        let sv = schematic.subvoxels()[0].pos;
+        assert_eq!((sv.block_x, sv.block_z, sv.sub_x, sv.sub_z), (1, 0, 1, 1));
+        // Камень был на 2 блока к +X от игрока - теперь на 2 блока к +Z
+        let corner = schematic.corner([0, 0, 0]);
+        assert_eq!([corner[0] + 2, corner[2]], [0, 2]);
+        assert_eq!(sample().rotated(4), sample());
+    }
+
+    #[test]
+    fn file_roundtrip_by_block_ids() {
+        let schematic = sample();
+        let names = |block: BlockType| format!("block_{}", block);
+        let file = schematic.to_file(names);
+        assert_eq!(file.palette.len(), 3);
+        let loaded = Schematic::from_file(file, |id| id.strip_prefix("block_")?.parse().ok()).unwrap();
+        assert_eq!(loaded, schematic);
+
+        // Неизвестный блок становится воздухом
+        let file = schematic.to_file(names);
+        let loaded = Schematic::from_file(file, |id| (id != "block_1").then(|| id[6..].parse().ok()).flatten()).unwrap();
+        assert!(loaded.blocks().all(|(_, block)| block != STONE));
+    }
+}
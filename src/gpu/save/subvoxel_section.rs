@@ -0,0 +1,314 @@
+// ============================================
+// SubVoxel Section - Суб-воксели в сохранении
+// ============================================
+// Суб-воксели группируются по чанкам 16x16. У каждого чанка своя
+// палитра типов блоков, а каждый блок с суб-вокселями хранится как
+// упакованное октодерево (обход в прямом порядке):
+//
+//   0x00, child_mask, дети...   - ветка (только существующие дети)
+//   0x01..=0xFE                 - лист: индекс палитры + 1
+//   0xFF, u16 LE                - лист с индексом палитры от 0xFE
+//
+// Глубина листа = уровень суб-вокселя (0 = Full, 1 = Half, 2 = Quarter, 3 = Eighth, 4 = Sixteenth).
+// Если уровни перекрываются, сохраняется более крупный суб-воксель.
+
+use std::collections::BTreeMap;
+
+use serde::{Serialize, Deserialize};
+
+use crate::gpu::blocks::{BlockType, AIR};
+use crate::gpu::subvoxel::{SubVoxel, SubVoxelLevel, SubVoxelPos, SubVoxelChunkKey};
+
+use super::world_file::SaveError;
+
+/// Маркер ветки в упакованном октодереве
+const BRANCH: u8 = 0x00;
+/// Маркер листа с двухбайтовым индексом палитры
+const WIDE_LEAF: u8 = 0xFF;
+
+/// Блок с суб-вокселями внутри чанка
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct SavedSubVoxelBlock {
+    /// Локальные координаты в чанке (0-15)
+    x: u8,
+    z: u8,
+    /// Мировая высота блока
+    y: i32,
+    /// Упакованное октодерево
+    tree: Vec<u8>,
+}
+
+/// Суб-воксели одного чанка
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct SavedSubVoxelChunk {
+    cx: i32,
+    cz: i32,
+    /// Палитра: индекс -> тип блока
+    palette: Vec<BlockType>,
+    blocks: Vec<SavedSubVoxelBlock>,
+}
+
+/// Узел октодерева при сборке
+enum Node {
+    Empty,
+    Leaf(u16),
+    Branch(Box<[Node; 8]>),
+}
+
+impl Node {
+    fn empty_children() -> Box<[Node; 8]> {
+        Box::new([
+            Node::Empty, Node::Empty, Node::Empty, Node::Empty,
+            Node::Empty, Node::Empty, Node::Empty, Node::Empty,
+        ])
+    }
+
+    /// Вставить лист по пути октантов
+    /// Более крупный суб-воксель поглощает мелкие внутри себя
+    fn insert(&mut self, path: &[u8], palette_index: u16) {
+        let Some((&octant, rest)) = path.split_first() else {
+            *self = Node::Leaf(palette_index);
+            return;
+        };
+        match self {
+            Node::Leaf(_) => {}
+            Node::Empty => {
+                *self = Node::Branch(Self::empty_children());
+                self.insert(path, palette_index);
+            }
+            Node::Branch(children) => children[octant as usize].insert(rest, palette_index),
+        }
+    }
+
+    fn pack(&self, out: &mut Vec<u8>) {
+        match self {
+            Node::Empty => {}
+            Node::Leaf(index) => match u8::try_from(*index + 1) {
+                Ok(tag) if tag != WIDE_LEAF => out.push(tag),
+                _ => {
+                    out.push(WIDE_LEAF);
+                    out.extend_from_slice(&index.to_le_bytes());
+                }
+            },
+            Node::Branch(children) => {
+                let mask = children
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, child)| !matches!(child, Node::Empty))
+                    .fold(0u8, |mask, (i, _)| mask | (1 << i));
+                out.push(BRANCH);
+                out.push(mask);
+                for child in children.iter() {
+                    child.pack(out);
+                }
+            }
+        }
+    }
+}
+
+/// Путь октантов от корня блока до суб-вокселя
+fn octant_path(pos: &SubVoxelPos) -> Vec<u8> {
+    let depth = pos.level as u8;
+    (0..depth)
+        .map(|level| {
+            // Бит координаты на этом уровне (старший бит = ближе к корню)
+            let shift = depth - 1 - level;
+            let bx = (pos.sub_x >> shift) & 1;
+            let by = (pos.sub_y >> shift) & 1;
+            let bz = (pos.sub_z >> shift) & 1;
+            bx | (by << 1) | (bz << 2)
+        })
+        .collect()
+}
+
+/// Упаковать суб-воксели по чанкам
+pub(crate) fn encode_subvoxels(subvoxels: &[SubVoxel]) -> Result<Vec<SavedSubVoxelChunk>, SaveError> {
+    // Чанк -> (палитра, блок -> октодерево); BTreeMap даёт стабильный порядок
+    let mut chunks: BTreeMap<(i32, i32), (Vec<BlockType>, BTreeMap<(u8, i32, u8), Node>)> = BTreeMap::new();
+
+    // Сначала крупные уровни, чтобы они поглощали мелкие
+    let mut sorted: Vec<&SubVoxel> = subvoxels.iter().filter(|sv| sv.block_type != AIR).collect();
+    sorted.sort_by_key(|sv| sv.pos.level as u8);
+
+    for sv in sorted {
+        let key = SubVoxelChunkKey::from_block_pos(sv.pos.block_x, sv.pos.block_z);
+        let (palette, blocks) = chunks.entry((key.x, key.z)).or_default();
+
+        let palette_index = match palette.iter().position(|&b| b == sv.block_type) {
+            Some(index) => index,
+            None => {
+                palette.push(sv.block_type);
+                palette.len() - 1
+            }
+        };
+        let palette_index = u16::try_from(palette_index).map_err(|_| {
+            SaveError::Serialize(format!("Палитра суб-вокселей чанка ({}, {}) слишком велика", key.x, key.z))
+        })?;
+
+        let local = (
+            sv.pos.block_x.rem_euclid(16) as u8,
+            sv.pos.block_y,
+            sv.pos.block_z.rem_euclid(16) as u8,
+        );
+        blocks
+            .entry(local)
+            .or_insert(Node::Empty)
+            .insert(&octant_path(&sv.pos), palette_index);
+    }
+
+    Ok(chunks
+        .into_iter()
+        .map(|((cx, cz), (palette, blocks))| SavedSubVoxelChunk {
+            cx,
+            cz,
+            palette,
+            blocks: blocks
+                .into_iter()
+                .map(|((x, y, z), root)| {
+                    let mut tree = Vec::new();
+                    root.pack(&mut tree);
+                    SavedSubVoxelBlock { x, z, y, tree }
+                })
+                .collect(),
+        })
+        .collect())
+}
+
+/// Распаковать суб-воксели из чанков
+pub(crate) fn decode_subvoxels(chunks: &[SavedSubVoxelChunk]) -> Vec<SubVoxel> {
+    let mut result = Vec::new();
+    for chunk in chunks {
+        for block in &chunk.blocks {
+            let block_x = chunk.cx * 16 + block.x as i32;
+            let block_z = chunk.cz * 16 + block.z as i32;
+            let mut cursor = 0;
+            unpack_node(
+                &block.tree,
+                &mut cursor,
+                &chunk.palette,
+                0,
+                [0, 0, 0],
+                &mut |sub, depth, block_type| {
+                    if let Some(level) = SubVoxelLevel::from_depth(depth) {
+                        result.push(SubVoxel {
+                            pos: SubVoxelPos::new(block_x, block.y, block_z, sub[0], sub[1], sub[2], level),
+                            block_type,
+                        });
+                    }
+                },
+            );
+        }
+    }
+    result
+}
+
+/// Рекурсивный разбор упакованного узла
+fn unpack_node(
+    tree: &[u8],
+    cursor: &mut usize,
+    palette: &[BlockType],
+    depth: u8,
+    sub: [u8; 3],
+    emit: &mut impl FnMut([u8; 3], u8, BlockType),
+) {
+    let Some(&tag) = tree.get(*cursor) else { return };
+    *cursor += 1;
+
+    if tag != BRANCH {
+        let index = if tag == WIDE_LEAF {
+            let Some(bytes) = tree.get(*cursor..*cursor + 2) else { return };
+            *cursor += 2;
+            u16::from_le_bytes([bytes[0], bytes[1]]) as usize
+        } else {
+            (tag - 1) as usize
+        };
+        if let Some(&block_type) = palette.get(index) {
+            emit(sub, depth, block_type);
+        }
+        return;
+    }
+
+    let Some(&mask) = tree.get(*cursor) else { return };
+    *cursor += 1;
+    for octant in 0..8u8 {
+        if mask & (1 << octant) == 0 {
+            continue;
+        }
+        let child = [
+            (sub[0] << 1) | (octant & 1),
+            (sub[1] << 1) | ((octant >> 1) & 1),
+            (sub[2] << 1) | ((octant >> 2) & 1),
+        ];
+        unpack_node(tree, cursor, palette, depth + 1, child, emit);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use crate::gpu::blocks::{STONE, DIRT, GRASS};
+
+    fn index_by_pos(subvoxels: &[SubVoxel]) -> HashMap<SubVoxelPos, BlockType> {
+        subvoxels.iter().map(|sv| (sv.pos, sv.block_type)).collect()
+    }
+
+    #[test]
+    fn test_subvoxel_octree_roundtrip() {
+        let subvoxels = vec![
+            SubVoxel { pos: SubVoxelPos::full(3, 70, -5), block_type: STONE },
+            SubVoxel { pos: SubVoxelPos::new(3, 71, -5, 1, 0, 1, SubVoxelLevel::Half), block_type: DIRT },
+            SubVoxel { pos: SubVoxelPos::new(3, 71, -5, 0, 3, 2, SubVoxelLevel::Quarter), block_type: GRASS },
+            SubVoxel { pos: SubVoxelPos::new(40, 12, 40, 3, 3, 3, SubVoxelLevel::Quarter), block_type: STONE },
+            SubVoxel { pos: SubVoxelPos::new(40, 13, 40, 7, 0, 5, SubVoxelLevel::Eighth), block_type: DIRT },
+            SubVoxel { pos: SubVoxelPos::new(40, 13, 40, 0, 15, 9, SubVoxelLevel::Sixteenth), block_type: GRASS },
+        ];
+
+        let chunks = encode_subvoxels(&subvoxels).unwrap();
+        assert_eq!(chunks.len(), 2);
+
+        let decoded = decode_subvoxels(&chunks);
+        assert_eq!(index_by_pos(&decoded), index_by_pos(&subvoxels));
+    }
+
+    #[test]
+    fn test_subvoxel_palette_past_one_byte_roundtrip() {
+        // Все непрозрачные типы в одном чанке: последние индексы палитры
+        // уже не помещаются в однобайтовый лист
+        let subvoxels: Vec<SubVoxel> = (1..=BlockType::MAX)
+            .map(|i| {
+                SubVoxel {
+                    pos: SubVoxelPos::new((i % 16) as i32, 40 + (i / 64) as i32, 5, i % 4, (i / 16) % 4, 0, SubVoxelLevel::Quarter),
+                    block_type: i,
+                }
+            })
+            .collect();
+
+        let chunks = encode_subvoxels(&subvoxels).unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].palette.len(), 255);
+        assert_eq!(index_by_pos(&decode_subvoxels(&chunks)), index_by_pos(&subvoxels));
+
+        // Сам формат листа держит и больше 256 записей палитры
+        let palette: Vec<BlockType> = (0..300u16).map(|i| (i % 255 + 1) as BlockType).collect();
+        let mut root = Node::Empty;
+        for index in 0..300u16 {
+            let sub = [(index % 16) as u8, ((index / 16) % 16) as u8, (index / 256) as u8];
+            let pos = SubVoxelPos::new(0, 0, 0, sub[0], sub[1], sub[2], SubVoxelLevel::Sixteenth);
+            root.insert(&octant_path(&pos), index);
+        }
+        let mut tree = Vec::new();
+        root.pack(&mut tree);
+
+        let mut leaves = HashMap::new();
+        unpack_node(&tree, &mut 0, &palette, 0, [0, 0, 0], &mut |sub, depth, block_type| {
+            assert_eq!(depth, 4);
+            leaves.insert(sub, block_type);
+        });
+        assert_eq!(leaves.len(), 300);
+        for index in 0..300u16 {
+            let sub = [(index % 16) as u8, ((index / 16) % 16) as u8, (index / 256) as u8];
+            assert_eq!(leaves[&sub], palette[index as usize]);
+        }
+    }
+}
@@ -19,16 +19,19 @@ use serde::{Serialize, Deserialize};
 
 use crate::gpu::blocks::BlockType;
 use crate::gpu::terrain::{BlockPos, WorldChanges};
-use crate::gpu::subvoxel::{SubVoxel, SubVoxelStorage};
+use crate::gpu::terrain::generation::WorldGenConfig;
+use crate::gpu::subvoxel::{SubVoxel, SubVoxelWorld};
 
-use super::header::{SaveHeader, MAGIC_NUMBER, SAVE_VERSION};
+use super::header::SaveHeader;
+use super::hotbar::SavedHotbar;
+use super::block_entity::SavedBlockEntity;
 
 const SECTION_SIZE: i32 = 16;
 const SECTION_VOLUME: usize = 16 * 16 * 16; // 4096
 
 /// Сжатая секция с палитрой
 #[derive(Debug, Serialize, Deserialize)]
-struct SavedSection {
+pub(crate) struct SavedSection {
     /// Координаты секции (chunk_x, section_y, chunk_z)
     cx: i32,
     sy: i32,
@@ -55,10 +58,19 @@ struct SaveBody {
 /// Результат загрузки мира
 #[derive(Debug)]
 pub struct LoadedWorld {
-    pub seed: u64,
+    /// Параметры генерации (сид и опции мира)
+    pub world_gen: WorldGenConfig,
     pub player_pos: [f32; 3],
+    /// Точка возрождения игрока (кровать)
+    pub respawn: Option<[i32; 3]>,
     pub changes: HashMap<BlockPos, BlockType>,
     pub subvoxels: Vec<SubVoxel>,
+    /// Хотбар игрока (нет в старом world.dat)
+    pub hotbar: Option<SavedHotbar>,
+    /// Индекс цветовой темы мира (PaletteTheme)
+    pub palette_theme: u8,
+    /// Данные блоков (нет в старом world.dat)
+    pub block_entities: Vec<SavedBlockEntity>,
 }
 
 /// Ошибки сохранения/загрузки
@@ -70,6 +82,8 @@ pub enum SaveError {
     InvalidMagic,
     UnsupportedVersion(u32),
     Compression(String),
+    /// Недопустимое или занятое имя мира
+    InvalidWorldName(String),
 }
 
 impl From<std::io::Error> for SaveError {
@@ -85,16 +99,16 @@ impl WorldFile {
     /// Сохранить мир в файл
     pub fn save(
         path: impl AsRef<Path>,
-        seed: u64,
+        world_gen: WorldGenConfig,
         player_pos: [f32; 3],
         world_changes: &WorldChanges,
-        subvoxel_storage: &SubVoxelStorage,
+        subvoxel_storage: &SubVoxelWorld,
     ) -> Result<(), SaveError> {
         let file = File::create(path)?;
         let mut writer = BufWriter::new(file);
 
         // 1. Записываем заголовок
-        let header = SaveHeader::new(seed, player_pos);
+        let header = SaveHeader::new(world_gen, player_pos);
         let header_bytes = bincode::serialize(&header)
             .map_err(|e| SaveError::Serialize(e.to_string()))?;
         writer.write_all(&header_bytes)?;
@@ -124,19 +138,7 @@ impl WorldFile {
         let mut reader = BufReader::new(file);
 
         // 1. Читаем заголовок
-        let header_size = bincode::serialized_size(&SaveHeader::default()).unwrap_or(32) as usize;
-        let mut header_bytes = vec![0u8; header_size];
-        reader.read_exact(&mut header_bytes)?;
-
-        let header: SaveHeader = bincode::deserialize(&header_bytes)
-            .map_err(|e| SaveError::Deserialize(e.to_string()))?;
-
-        if header.magic != MAGIC_NUMBER {
-            return Err(SaveError::InvalidMagic);
-        }
-        if header.version != SAVE_VERSION {
-            return Err(SaveError::UnsupportedVersion(header.version));
-        }
+        let header = SaveHeader::read_from(&mut reader)?;
 
         // 2. Читаем и распаковываем тело
         let mut compressed = Vec::new();
@@ -152,16 +154,28 @@ impl WorldFile {
         let changes = Self::extract_changes(&body.sections);
 
         Ok(LoadedWorld {
-            seed: header.seed,
+            world_gen: header.world_gen,
             player_pos: header.player_pos,
+            respawn: header.respawn,
             changes,
             subvoxels: body.subvoxels,
+            hotbar: None,
+            palette_theme: 0,
+            block_entities: Vec::new(),
         })
     }
 
     /// Группируем изменения по секциям 16x16x16
     fn build_sections(world_changes: &WorldChanges) -> Vec<SavedSection> {
-        let all_changes = world_changes.get_all_changes_copy();
+        Self::sections_from_changes(world_changes.get_all_changes_copy())
+    }
+
+    /// Упаковать произвольный набор изменений в секции
+    /// (используется и региональным форматом для одного чанка)
+    pub(crate) fn sections_from_changes(
+        all_changes: impl IntoIterator<Item = (BlockPos, BlockType)>,
+    ) -> Vec<SavedSection> {
+        let all_changes: Vec<(BlockPos, BlockType)> = all_changes.into_iter().collect();
         if all_changes.is_empty() {
             return Vec::new();
         }
@@ -268,7 +282,12 @@ impl WorldFile {
     /// Извлекаем изменения из секций
     fn extract_changes(sections: &[SavedSection]) -> HashMap<BlockPos, BlockType> {
         let mut changes = HashMap::new();
+        Self::extract_changes_into(sections, &mut changes);
+        changes
+    }
 
+    /// Извлечь изменения из секций в существующую карту
+    pub(crate) fn extract_changes_into(sections: &[SavedSection], changes: &mut HashMap<BlockPos, BlockType>) {
         for section in sections {
             let base_x = section.cx * SECTION_SIZE;
             let base_y = section.sy * SECTION_SIZE;
@@ -294,8 +313,6 @@ impl WorldFile {
                 }
             }
         }
-
-        changes
     }
 }
 
@@ -310,14 +327,14 @@ mod tests {
         world_changes.set_block(BlockPos::new(11, 64, 10), BlockType::Dirt);
         world_changes.set_block(BlockPos::new(12, 64, 10), BlockType::Air); // Сломанный блок!
         
-        let subvoxel_storage = SubVoxelStorage::new();
+        let subvoxel_storage = SubVoxelWorld::new();
 
         let path = "test_world3.dat";
         
-        WorldFile::save(path, 12345, [10.0, 65.0, 10.0], &world_changes, &subvoxel_storage).unwrap();
+        WorldFile::save(path, WorldGenConfig::with_seed(12345), [10.0, 65.0, 10.0], &world_changes, &subvoxel_storage).unwrap();
         let loaded = WorldFile::load(path).unwrap();
 
-        assert_eq!(loaded.seed, 12345);
+        assert_eq!(loaded.world_gen.seed, 12345);
         assert_eq!(loaded.changes.len(), 3);
         assert_eq!(loaded.changes.get(&BlockPos::new(10, 64, 10)), Some(&BlockType::Stone));
         assert_eq!(loaded.changes.get(&BlockPos::new(12, 64, 10)), Some(&BlockType::Air));
@@ -0,0 +1,399 @@
+// ============================================
+// World Manager - Список миров и их метаданные
+// ============================================
+// Структура:
+//   saves/<dir>/world.json      - метаданные (имя, генерация, время, режим)
+//   saves/<dir>/thumbnail.png   - превью для меню выбора мира
+//   saves/<dir>/level.dat, region/ - сам мир (RegionWorld)
+//
+// Имя папки получается из имени мира и дальше не меняется: меню
+// показывает имя из метаданных, операции адресуют мир по папке.
+// Созданный, но ещё не сыгранный мир - это только world.json.
+
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Serialize, Deserialize};
+
+use crate::gpu::console::GameMode;
+use crate::gpu::terrain::generation::WorldGenConfig;
+
+use super::region_world::RegionWorld;
+use super::world_file::{LoadedWorld, SaveError};
+
+/// Файл метаданных в папке мира
+pub const METADATA_FILE: &str = "world.json";
+/// Превью мира
+pub const THUMBNAIL_FILE: &str = "thumbnail.png";
+/// Максимальная длина имени папки
+const MAX_DIR_NAME: usize = 32;
+
+/// Метаданные мира для меню
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorldMetadata {
+    /// Отображаемое имя
+    pub name: String,
+    /// Параметры генерации (включая сид)
+    pub world_gen: WorldGenConfig,
+    /// Последняя игра (секунды Unix)
+    #[serde(default)]
+    pub last_played: u64,
+    /// Суммарное время в мире (секунды)
+    #[serde(default)]
+    pub playtime_secs: u64,
+    #[serde(default)]
+    pub game_mode: GameMode,
+    /// Точка появления (ищется при первом входе, меняется /setspawn)
+    #[serde(default)]
+    pub spawn: Option<[i32; 3]>,
+    /// Радиус защиты спавна: строить в нём могут только операторы (0 - нет)
+    #[serde(default)]
+    pub spawn_protection: i32,
+    /// Прошедшие игровые дни (день сменяется на рассвете)
+    #[serde(default)]
+    pub days: u32,
+    /// Время суток при сохранении (None - утро нового мира)
+    #[serde(default)]
+    pub time_of_day: Option<f32>,
+}
+
+impl WorldMetadata {
+    pub fn new(name: impl Into<String>, world_gen: WorldGenConfig) -> Self {
+        Self {
+            name: name.into(),
+            world_gen,
+            last_played: unix_now(),
+            playtime_secs: 0,
+            game_mode: GameMode::default(),
+            spawn: None,
+            spawn_protection: 0,
+            days: 0,
+            time_of_day: None,
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.world_gen.seed
+    }
+}
+
+/// Мир в списке
+#[derive(Debug, Clone)]
+pub struct WorldEntry {
+    /// Имя папки (идентификатор для load/delete/duplicate)
+    pub dir_name: String,
+    pub path: PathBuf,
+    pub metadata: WorldMetadata,
+    /// Есть ли уже level.dat (мир хоть раз сохранялся)
+    pub has_level: bool,
+}
+
+impl WorldEntry {
+    /// Путь к превью, если оно есть
+    pub fn thumbnail(&self) -> Option<PathBuf> {
+        let path = self.path.join(THUMBNAIL_FILE);
+        path.exists().then_some(path)
+    }
+}
+
+/// Управление мирами в папке сохранений
+pub struct WorldManager {
+    root: PathBuf,
+}
+
+impl WorldManager {
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        Self { root: root.as_ref().to_path_buf() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Папка мира по имени папки (без выхода за пределы root)
+    pub fn path_of(&self, dir_name: &str) -> Result<PathBuf, SaveError> {
+        let mut components = Path::new(dir_name).components();
+        match (components.next(), components.next()) {
+            (Some(Component::Normal(_)), None) => Ok(self.root.join(dir_name)),
+            _ => Err(SaveError::InvalidWorldName(dir_name.to_string())),
+        }
+    }
+
+    /// Все миры, последние сыгранные первыми
+    pub fn list(&self) -> Result<Vec<WorldEntry>, SaveError> {
+        if !self.root.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut worlds = Vec::new();
+        for entry in fs::read_dir(&self.root)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            let Some(dir_name) = entry.file_name().to_str().map(str::to_string) else { continue };
+            match self.entry(&dir_name) {
+                Ok(Some(world)) => worlds.push(world),
+                Ok(None) => {}
+                Err(e) => log::warn!("Мир {}/ пропущен: {:?}", dir_name, e),
+            }
+        }
+
+        worlds.sort_by(|a, b| {
+            b.metadata.last_played.cmp(&a.metadata.last_played).then_with(|| a.metadata.name.cmp(&b.metadata.name))
+        });
+        Ok(worlds)
+    }
+
+    /// Мир по имени папки. None - в папке нет ни метаданных, ни мира
+    pub fn entry(&self, dir_name: &str) -> Result<Option<WorldEntry>, SaveError> {
+        let path = self.path_of(dir_name)?;
+        let has_level = RegionWorld::exists(&path);
+        let metadata = match read_metadata(&path)? {
+            Some(metadata) => metadata,
+            // Мир без world.json (создан до менеджера): сид из заголовка
+            None if has_level => {
+                let header = RegionWorld::read_header(&path)?;
+                let mut metadata = WorldMetadata::new(dir_name, header.world_gen);
+                metadata.last_played = modified_secs(&path);
+                metadata
+            }
+            None => return Ok(None),
+        };
+        Ok(Some(WorldEntry { dir_name: dir_name.to_string(), path, metadata, has_level }))
+    }
+
+    /// Создать новый мир (только метаданные - мир генерируется при входе)
+    pub fn create(&self, name: &str, world_gen: WorldGenConfig) -> Result<WorldEntry, SaveError> {
+        let dir_name = self.free_dir_name(name)?;
+        let path = self.path_of(&dir_name)?;
+        fs::create_dir_all(&path)?;
+
+        let metadata = WorldMetadata::new(name.trim(), world_gen);
+        write_metadata(&path, &metadata)?;
+        Ok(WorldEntry { dir_name, path, metadata, has_level: false })
+    }
+
+    /// Загрузить мир и отметить время входа
+    pub fn load(&self, dir_name: &str) -> Result<LoadedWorld, SaveError> {
+        let path = self.path_of(dir_name)?;
+        let loaded = RegionWorld::open(&path)?.load()?;
+
+        let mut metadata = read_metadata(&path)?.unwrap_or_else(|| WorldMetadata::new(dir_name, loaded.world_gen));
+        metadata.last_played = unix_now();
+        write_metadata(&path, &metadata)?;
+        Ok(loaded)
+    }
+
+    /// Удалить мир целиком
+    pub fn delete(&self, dir_name: &str) -> Result<(), SaveError> {
+        let path = self.path_of(dir_name)?;
+        fs::remove_dir_all(path)?;
+        Ok(())
+    }
+
+    /// Копия мира под новым именем (время игры сохраняется)
+    pub fn duplicate(&self, dir_name: &str, new_name: &str) -> Result<WorldEntry, SaveError> {
+        let source = self
+            .entry(dir_name)?
+            .ok_or_else(|| SaveError::InvalidWorldName(dir_name.to_string()))?;
+
+        let new_dir = self.free_dir_name(new_name)?;
+        let path = self.path_of(&new_dir)?;
+        copy_dir(&source.path, &path)?;
+
+        let mut metadata = source.metadata;
+        metadata.name = new_name.trim().to_string();
+        write_metadata(&path, &metadata)?;
+        Ok(WorldEntry { dir_name: new_dir, path, metadata, has_level: source.has_level })
+    }
+
+    /// Записать итоги сессии: генерация, режим и общее время в мире
+    pub fn record_session(
+        &self,
+        dir_name: &str,
+        world_gen: WorldGenConfig,
+        game_mode: GameMode,
+        playtime: Duration,
+    ) -> Result<(), SaveError> {
+        let path = self.path_of(dir_name)?;
+        fs::create_dir_all(&path)?;
+
+        let mut metadata = read_metadata(&path)?.unwrap_or_else(|| WorldMetadata::new(dir_name, world_gen));
+        metadata.world_gen = world_gen;
+        metadata.game_mode = game_mode;
+        metadata.playtime_secs = playtime.as_secs();
+        metadata.last_played = unix_now();
+        write_metadata(&path, &metadata)
+    }
+
+    /// Записать спавн мира и радиус его защиты
+    pub fn set_spawn(&self, dir_name: &str, world_gen: WorldGenConfig, spawn: [i32; 3], protection: i32) -> Result<(), SaveError> {
+        let path = self.path_of(dir_name)?;
+        fs::create_dir_all(&path)?;
+
+        let mut metadata = read_metadata(&path)?.unwrap_or_else(|| WorldMetadata::new(dir_name, world_gen));
+        metadata.spawn = Some(spawn);
+        metadata.spawn_protection = protection;
+        write_metadata(&path, &metadata)
+    }
+
+    /// Записать игровой календарь: прошедшие дни и время суток
+    pub fn set_calendar(&self, dir_name: &str, world_gen: WorldGenConfig, days: u32, time_of_day: Option<f32>) -> Result<(), SaveError> {
+        let path = self.path_of(dir_name)?;
+        fs::create_dir_all(&path)?;
+
+        let mut metadata = read_metadata(&path)?.unwrap_or_else(|| WorldMetadata::new(dir_name, world_gen));
+        metadata.days = days;
+        metadata.time_of_day = time_of_day;
+        write_metadata(&path, &metadata)
+    }
+
+    /// Сохранить превью мира (RGBA8)
+    pub fn save_thumbnail(&self, dir_name: &str, width: u32, height: u32, rgba: &[u8]) -> Result<(), SaveError> {
+        let path = self.path_of(dir_name)?.join(THUMBNAIL_FILE);
+        let mut encoder = png::Encoder::new(BufWriter::new(File::create(path)?), width, height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(rgba))
+            .map_err(|e| SaveError::Serialize(e.to_string()))
+    }
+
+    /// Перенести мир из другой папки (старый world/ рядом с игрой)
+    pub fn adopt(&self, source: impl AsRef<Path>, dir_name: &str) -> Result<(), SaveError> {
+        let path = self.path_of(dir_name)?;
+        if path.exists() {
+            return Err(SaveError::InvalidWorldName(dir_name.to_string()));
+        }
+        fs::create_dir_all(&self.root)?;
+        fs::rename(source, path)?;
+        Ok(())
+    }
+
+    /// Свободное имя папки для мира: имя, имя_2, имя_3...
+    fn free_dir_name(&self, name: &str) -> Result<String, SaveError> {
+        let base = dir_name_for(name);
+        let mut candidate = base.clone();
+        let mut n = 2;
+        while self.path_of(&candidate)?.exists() {
+            candidate = format!("{}_{}", base, n);
+            n += 1;
+        }
+        Ok(candidate)
+    }
+}
+
+/// Имя папки из имени мира: буквы и цифры, остальное - '_'
+fn dir_name_for(name: &str) -> String {
+    let slug: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' { c } else { '_' })
+        .take(MAX_DIR_NAME)
+        .collect();
+    let slug = slug.trim_matches('_');
+    if slug.is_empty() {
+        "world".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+fn read_metadata(path: &Path) -> Result<Option<WorldMetadata>, SaveError> {
+    let path = path.join(METADATA_FILE);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let json = fs::read_to_string(path)?;
+    serde_json::from_str(&json)
+        .map(Some)
+        .map_err(|e| SaveError::Deserialize(e.to_string()))
+}
+
+/// Через временный файл, как level.dat
+fn write_metadata(path: &Path, metadata: &WorldMetadata) -> Result<(), SaveError> {
+    let json = serde_json::to_string_pretty(metadata).map_err(|e| SaveError::Serialize(e.to_string()))?;
+    let tmp_path = path.join(format!("{}.tmp", METADATA_FILE));
+    fs::write(&tmp_path, json)?;
+    fs::rename(tmp_path, path.join(METADATA_FILE))?;
+    Ok(())
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<(), SaveError> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// Время изменения папки (для миров без метаданных)
+fn modified_secs(path: &Path) -> u64 {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_duplicate_record_and_delete() {
+        let root = std::env::temp_dir().join("kimi_voxel_world_manager_test");
+        let _ = fs::remove_dir_all(&root);
+        let manager = WorldManager::new(&root);
+        assert!(manager.list().unwrap().is_empty());
+
+        let first = manager.create("My World!", WorldGenConfig::with_seed(42)).unwrap();
+        assert_eq!(first.dir_name, "My_World");
+        let second = manager.create("My World!", WorldGenConfig::with_seed(7)).unwrap();
+        assert_eq!(second.dir_name, "My_World_2");
+
+        manager
+            .record_session(&first.dir_name, WorldGenConfig::with_seed(42), GameMode::Creative, Duration::from_secs(90))
+            .unwrap();
+        let copy = manager.duplicate(&first.dir_name, "Copy").unwrap();
+        assert_eq!(copy.metadata.seed(), 42);
+        assert_eq!(copy.metadata.playtime_secs, 90);
+        assert_eq!(copy.metadata.game_mode, GameMode::Creative);
+
+        // Спавн и календарь переживают запись сессии
+        manager.set_spawn(&first.dir_name, WorldGenConfig::with_seed(42), [8, 70, -16], 5).unwrap();
+        manager.set_calendar(&first.dir_name, WorldGenConfig::with_seed(42), 12, Some(0.6)).unwrap();
+        manager
+            .record_session(&first.dir_name, WorldGenConfig::with_seed(42), GameMode::Creative, Duration::from_secs(120))
+            .unwrap();
+        let first_meta = manager.entry(&first.dir_name).unwrap().unwrap().metadata;
+        assert_eq!((first_meta.spawn, first_meta.spawn_protection), (Some([8, 70, -16]), 5));
+        assert_eq!((first_meta.days, first_meta.time_of_day), (12, Some(0.6)));
+        assert_eq!(manager.list().unwrap().len(), 3);
+
+        manager.delete(&second.dir_name).unwrap();
+        let names: Vec<String> = manager.list().unwrap().into_iter().map(|w| w.metadata.name).collect();
+        assert_eq!(names.len(), 2);
+        assert!(names.contains(&"Copy".to_string()) && names.contains(&"My World!".to_string()));
+
+        // Выход из папки сохранений запрещён
+        assert!(manager.delete("../escape").is_err());
+
+        fs::remove_dir_all(&root).ok();
+    }
+}
@@ -0,0 +1,84 @@
+// ============================================
+// Break Overlay Shader - Трещины на ломаемом блоке
+// ============================================
+// Трещины - рёбра ячеек Вороного в пиксельной сетке 16x16 (как
+// текстуры блоков). С прогрессом они расползаются от центра грани,
+// стадий 10, как у классической анимации.
+
+struct Uniforms {
+    view_proj: mat4x4<f32>,
+    block_pos: vec3<f32>,
+    progress: f32,
+}
+
+@group(0) @binding(0)
+var<uniform> uniforms: Uniforms;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) uv_face: vec3<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) face: f32,
+}
+
+// Чуть больше блока - без z-fighting с гранями террейна
+const INFLATE: f32 = 1.004;
+const PIXELS: f32 = 16.0;
+const STAGES: f32 = 10.0;
+
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    let world_pos = uniforms.block_pos + 0.5 + (in.position - 0.5) * INFLATE;
+    out.clip_position = uniforms.view_proj * vec4<f32>(world_pos, 1.0);
+    out.uv = in.uv_face.xy;
+    out.face = in.uv_face.z;
+    return out;
+}
+
+fn hash22(p: vec2<f32>) -> vec2<f32> {
+    let q = vec2<f32>(dot(p, vec2<f32>(127.1, 311.7)), dot(p, vec2<f32>(269.5, 183.3)));
+    return fract(sin(q) * 43758.5453);
+}
+
+// Расстояние до ребра ячеек Вороного (F2 - F1)
+fn crack_distance(p: vec2<f32>) -> f32 {
+    let cell = floor(p);
+    let f = fract(p);
+    var d1 = 8.0;
+    var d2 = 8.0;
+    for (var y = -1; y <= 1; y++) {
+        for (var x = -1; x <= 1; x++) {
+            let o = vec2<f32>(f32(x), f32(y));
+            let d = length(o + hash22(cell + o) - f);
+            if d < d1 {
+                d2 = d1;
+                d1 = d;
+            } else if d < d2 {
+                d2 = d;
+            }
+        }
+    }
+    return d2 - d1;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let stage = floor(clamp(uniforms.progress, 0.0, 0.999) * STAGES);
+
+    // Центр пикселя 16x16
+    let p = (floor(in.uv * PIXELS) + 0.5) / PIXELS;
+
+    // У каждой грани свой рисунок
+    let edge = crack_distance(p * 3.0 + in.face * 7.31);
+    let reach = (stage + 1.0) / STAGES * 0.75;
+    if edge > 0.12 || length(p - 0.5) > reach {
+        discard;
+    }
+
+    return vec4<f32>(0.05, 0.05, 0.05, 0.35 + stage * 0.03);
+}
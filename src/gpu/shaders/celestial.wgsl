@@ -1,5 +1,5 @@
 // ============================================
-// Celestial Bodies Shader - Солнце и Луна
+// Celestial Bodies Shader - Солнце, Луна и звёзды
 // ============================================
 
 struct CelestialUniforms {
@@ -8,8 +8,10 @@ struct CelestialUniforms {
     sun_direction: vec4<f32>,     // xyz + visibility
     sun_color: vec4<f32>,         // rgb + size
     moon_direction: vec4<f32>,    // xyz + visibility
-    moon_color: vec4<f32>,        // rgb + phase
+    moon_color: vec4<f32>,        // rgb + phase (0 - новолуние, 0.5 - полнолуние)
     time_of_day: vec4<f32>,       // time + pad
+    sky_axis: vec4<f32>,          // ось небосвода xyz + угол поворота
+    stars: vec4<f32>,             // видимость, время (сек), pad, pad
 }
 
 @group(0) @binding(0)
@@ -89,7 +91,8 @@ fn draw_sun(uv: vec2<f32>) -> vec4<f32> {
     let rays = (sin(angle * 12.0) * 0.5 + 0.5) * 0.3;
     let ray_intensity = rays * smoothstep(glow_radius, core_radius * 1.5, dist) * smoothstep(0.0, core_radius * 2.0, dist);
     
-    let sun_core_color = vec3<f32>(1.0, 1.0, 0.95);
+    // Ядро ярче 1.0: в HDR кадре даёт ореол bloom
+    let sun_core_color = vec3<f32>(1.0, 1.0, 0.95) * 4.0;
     let sun_glow_color = uniforms.sun_color.xyz;
     let sun_ray_color = vec3<f32>(1.0, 0.8, 0.4);
     
@@ -112,18 +115,15 @@ fn draw_moon(uv: vec2<f32>) -> vec4<f32> {
     let moon_disk = smoothstep(moon_radius, moon_radius - 0.02, dist);
     let glow = smoothstep(glow_radius, moon_radius, dist) * 0.3;
     
-    let phase = uniforms.moon_color.w;
-    let phase_offset = (phase - 0.5) * 2.0;
-    let shadow_center = vec2<f32>(0.5 + phase_offset * 0.4, 0.5);
-    let shadow_dist = length(uv - shadow_center);
-    let shadow = smoothstep(moon_radius * 0.9, moon_radius * 0.7, shadow_dist);
-    
-    var phase_factor = 1.0;
-    if (phase < 0.5) {
-        phase_factor = 1.0 - shadow * (1.0 - phase * 2.0);
-    } else {
-        phase_factor = 1.0 - shadow * ((phase - 0.5) * 2.0);
-    }
+    // Освещённость сферы: свет падает под углом phase * 2PI
+    // (0 - из-за луны, 0.25 - справа, 0.5 - со стороны наблюдателя)
+    let p = (uv - center) / moon_radius;
+    let normal = vec3<f32>(p.x, -p.y, sqrt(max(1.0 - dot(p, p), 0.0)));
+    let phase_angle = uniforms.moon_color.w * 6.2831853;
+    let light_dir = vec3<f32>(sin(phase_angle), 0.0, -cos(phase_angle));
+    let lit = smoothstep(-0.05, 0.05, dot(normal, light_dir));
+    // Пепельный свет: тёмная часть диска чуть видна
+    let phase_factor = max(lit, 0.04);
     
     let crater_noise = crater_pattern(uv * 8.0);
     let surface_detail = 1.0 - crater_noise * 0.15;
@@ -131,7 +131,8 @@ fn draw_moon(uv: vec2<f32>) -> vec4<f32> {
     let moon_color = uniforms.moon_color.xyz * surface_detail;
     
     var color = moon_color * moon_disk * phase_factor;
-    color += uniforms.moon_color.xyz * 0.5 * glow;
+    // Ореол слабеет вместе с освещённой частью
+    color += uniforms.moon_color.xyz * 0.5 * glow * (0.5 - 0.5 * cos(phase_angle));
     
     let alpha = (moon_disk * phase_factor + glow) * uniforms.moon_direction.w;
     
@@ -169,3 +170,70 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         return draw_moon(in.uv);
     }
 }
+
+// ============================================
+// Звёзды
+// ============================================
+
+struct StarInput {
+    @location(0) position: vec3<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) direction: vec3<f32>,
+    @location(3) size: f32,
+    @location(4) color: vec3<f32>,
+    @location(5) twinkle: f32,
+}
+
+struct StarOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec3<f32>,
+}
+
+/// Поворот вокруг оси (формула Родрига)
+fn rotate_around(v: vec3<f32>, axis: vec3<f32>, angle: f32) -> vec3<f32> {
+    let c = cos(angle);
+    let s = sin(angle);
+    return v * c + cross(axis, v) * s + axis * dot(axis, v) * (1.0 - c);
+}
+
+@vertex
+fn vs_star(in: StarInput) -> StarOutput {
+    var out: StarOutput;
+    out.uv = in.uv;
+
+    let direction = rotate_around(in.direction, uniforms.sky_axis.xyz, uniforms.sky_axis.w);
+    // Днём, у горизонта и за диском луны звёзд не видно
+    let horizon = smoothstep(-0.02, 0.15, direction.y);
+    let behind_moon = uniforms.moon_direction.w > 0.5 && dot(direction, uniforms.moon_direction.xyz) > 0.997;
+    let twinkle = 0.75 + 0.25 * sin(uniforms.stars.y * 3.0 + in.twinkle);
+    let brightness = uniforms.stars.x * horizon * twinkle;
+    if (brightness < 0.01 || behind_moon) {
+        out.clip_position = vec4<f32>(0.0, 0.0, -2.0, 1.0);
+        out.color = vec3<f32>(0.0);
+        return out;
+    }
+
+    let center = uniforms.camera_pos.xyz + direction * SKY_DISTANCE;
+    let to_camera = -direction;
+    var up = vec3<f32>(0.0, 1.0, 0.0);
+    if (abs(dot(to_camera, up)) > 0.99) {
+        up = vec3<f32>(0.0, 0.0, 1.0);
+    }
+    let right = normalize(cross(up, to_camera));
+    let billboard_up = normalize(cross(to_camera, right));
+
+    let scale = SKY_DISTANCE * in.size;
+    let world_pos = center + right * in.position.x * scale + billboard_up * in.position.y * scale;
+
+    out.clip_position = uniforms.view_proj * vec4<f32>(world_pos, 1.0);
+    out.color = in.color * brightness;
+    return out;
+}
+
+@fragment
+fn fs_star(in: StarOutput) -> @location(0) vec4<f32> {
+    let dist = length(in.uv - vec2<f32>(0.5, 0.5));
+    let alpha = smoothstep(0.5, 0.1, dist);
+    return vec4<f32>(in.color, alpha);
+}
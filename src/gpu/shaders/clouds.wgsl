@@ -0,0 +1,175 @@
+// ============================================
+// Clouds Shader - Слой облаков
+// ============================================
+// Без вершинного буфера: квад вокруг камеры на высоте плоскости.
+// Плотность - fbm из value noise по мировым XZ со смещением ветра.
+// Шум облаков (cloud_*) продублирован в terrain_shadows.wgsl для теней -
+// менять нужно в обоих файлах.
+
+struct CloudUniforms {
+    view_proj: mat4x4<f32>,
+    camera_pos: vec4<f32>,   // xyz + высота плоскости
+    params: vec4<f32>,       // покрытие, высота слоя, толщина, режим (0 flat, 1 volumetric)
+    offset: vec4<f32>,       // смещение X/Z, радиус, начало растворения
+    light_dir: vec4<f32>,    // к солнцу/луне xyz + интенсивность
+    light_color: vec4<f32>,  // rgb + ambient
+    fog_color: vec4<f32>,    // rgb + облачность
+}
+
+@group(0) @binding(0)
+var<uniform> uniforms: CloudUniforms;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_pos: vec3<f32>,
+}
+
+// Шагов raymarch сквозь слой и предел длины луча (у горизонта)
+const VOLUME_STEPS: i32 = 16;
+const MAX_MARCH: f32 = 320.0;
+const ABSORPTION: f32 = 0.08;
+
+// === Шум облаков (общий с terrain_shadows.wgsl) ===
+
+const CLOUD_SCALE: f32 = 0.01;
+
+fn cloud_hash(cell: vec2<i32>) -> f32 {
+    var x = (bitcast<u32>(cell.x) * 1597334673u) ^ (bitcast<u32>(cell.y) * 3812015801u);
+    x = (x ^ (x >> 16u)) * 2246822519u;
+    x = x ^ (x >> 13u);
+    return f32(x) / 4294967295.0;
+}
+
+fn cloud_noise(p: vec2<f32>) -> f32 {
+    let cell = vec2<i32>(floor(p));
+    let f = fract(p);
+    let u = f * f * (3.0 - 2.0 * f);
+    let a = cloud_hash(cell);
+    let b = cloud_hash(cell + vec2<i32>(1, 0));
+    let c = cloud_hash(cell + vec2<i32>(0, 1));
+    let d = cloud_hash(cell + vec2<i32>(1, 1));
+    return mix(mix(a, b, u.x), mix(c, d, u.x), u.y);
+}
+
+fn cloud_fbm(xz: vec2<f32>) -> f32 {
+    var p = xz * CLOUD_SCALE;
+    var value = 0.0;
+    var amplitude = 0.5;
+    for (var i = 0; i < 4; i++) {
+        value += cloud_noise(p) * amplitude;
+        p = p * 2.03 + vec2<f32>(17.3, 9.1);
+        amplitude *= 0.5;
+    }
+    return value / 0.9375;
+}
+
+// Плотность на плоскости слоя: 0 - небо, 1 - сплошное облако
+fn cloud_density(xz: vec2<f32>, coverage: f32) -> f32 {
+    let threshold = 1.0 - coverage;
+    return smoothstep(threshold, threshold + 0.2, cloud_fbm(xz));
+}
+
+// === Вершины ===
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex: u32) -> VertexOutput {
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(-1.0, -1.0), vec2<f32>(1.0, -1.0), vec2<f32>(1.0, 1.0),
+        vec2<f32>(-1.0, -1.0), vec2<f32>(1.0, 1.0), vec2<f32>(-1.0, 1.0),
+    );
+    let corner = corners[vertex];
+    let radius = uniforms.offset.z;
+    let camera = uniforms.camera_pos.xyz;
+    let world = vec3<f32>(camera.x + corner.x * radius, uniforms.camera_pos.w, camera.z + corner.y * radius);
+
+    var out: VertexOutput;
+    out.clip_position = uniforms.view_proj * vec4<f32>(world, 1.0);
+    out.world_pos = world;
+    return out;
+}
+
+// === Освещение ===
+
+fn ambient_light() -> vec3<f32> {
+    return vec3<f32>(uniforms.light_color.w * 1.6);
+}
+
+fn direct_light() -> vec3<f32> {
+    return uniforms.light_color.rgb * uniforms.light_dir.w * 0.8;
+}
+
+// Плоские облака: плотные места темнее снизу, тучи серее
+fn shade_flat(world_pos: vec3<f32>) -> vec4<f32> {
+    let density = cloud_density(world_pos.xz - uniforms.offset.xy, uniforms.params.x);
+    let below = select(0.0, 1.0, uniforms.camera_pos.y < world_pos.y);
+    var color = ambient_light() + direct_light();
+    color *= mix(1.0, 0.7, density * below);
+    return vec4<f32>(color, density * 0.85);
+}
+
+// Плотность в объёме: края слоя скругляются (эрозия у низа и верха)
+fn volume_density(pos: vec3<f32>) -> f32 {
+    let h = clamp((pos.y - uniforms.params.y) / uniforms.params.z, 0.0, 1.0);
+    let profile = 4.0 * h * (1.0 - h);
+    let threshold = 1.0 - uniforms.params.x;
+    let value = cloud_fbm(pos.xz - uniforms.offset.xy);
+    return clamp((value - threshold) * 4.0 - (1.0 - profile), 0.0, 1.0);
+}
+
+// Raymarch от грани слоя сквозь его толщину
+fn shade_volume(entry: vec3<f32>) -> vec4<f32> {
+    let dir = normalize(entry - uniforms.camera_pos.xyz);
+    let march = min(uniforms.params.z / max(abs(dir.y), 0.001), MAX_MARCH);
+    let step_len = march / f32(VOLUME_STEPS);
+    let to_light = normalize(uniforms.light_dir.xyz);
+
+    var transmittance = 1.0;
+    var color = vec3<f32>(0.0);
+    for (var i = 0; i < VOLUME_STEPS; i++) {
+        let pos = entry + dir * (f32(i) + 0.5) * step_len;
+        let density = volume_density(pos);
+        if (density < 0.001) {
+            continue;
+        }
+        // Самозатенение: плотность на шаг ближе к источнику света
+        let occluder = volume_density(pos + to_light * 6.0);
+        let h = clamp((pos.y - uniforms.params.y) / uniforms.params.z, 0.0, 1.0);
+        let sample_color = ambient_light() * (0.6 + 0.4 * h) + direct_light() * exp(-occluder * 2.5);
+
+        let absorb = exp(-density * step_len * ABSORPTION);
+        color += transmittance * (1.0 - absorb) * sample_color;
+        transmittance *= absorb;
+        if (transmittance < 0.02) {
+            break;
+        }
+    }
+
+    let alpha = 1.0 - transmittance;
+    return vec4<f32>(color / max(alpha, 0.001), alpha);
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let camera_y = uniforms.camera_pos.y;
+    let base = uniforms.params.y;
+    let inside = camera_y > base && camera_y < base + uniforms.params.z;
+
+    // Внутри слоя объём не собрать с грани - рисуем плоским
+    var cloud: vec4<f32>;
+    if (uniforms.params.w > 0.5 && !inside) {
+        cloud = shade_volume(in.world_pos);
+    } else {
+        cloud = shade_flat(in.world_pos);
+    }
+    if (cloud.a < 0.01) {
+        discard;
+    }
+
+    // Тучи в грозу темнее, вдали облака растворяются в тумане
+    var color = cloud.rgb * (1.0 - 0.45 * uniforms.fog_color.w);
+    let dist = length(in.world_pos.xz - uniforms.camera_pos.xz);
+    let fade = smoothstep(uniforms.offset.w, uniforms.offset.z, dist);
+    color = mix(min(color, vec3<f32>(1.0)), uniforms.fog_color.rgb, fade * 0.7);
+
+    return vec4<f32>(color, cloud.a * (1.0 - fade));
+}
@@ -0,0 +1,85 @@
+// ============================================
+// Cull Shader - GPU отсечение секций чанков
+// ============================================
+// Поток на запись (непрозрачная секция чанка): frustum, вертикальная
+// дальность, LOD под землёй и закрытость по occlusion queries. Выжившие
+// записи упаковываются в начало диапазона своей группы страниц в
+// indirect буфере (счётчик группы - атомарный).
+
+struct CullUniforms {
+    planes: array<vec4<f32>, 6>,
+    params: vec4<f32>,  // min_y, max_y, вертикальная дальность (0/1), под землёй (0/1)
+    counts: vec4<u32>,  // число записей, -, -, -
+}
+
+struct DrawRecord {
+    aabb_min: vec4<f32>,
+    aabb_max: vec4<f32>,
+    index_count: u32,
+    first_index: u32,
+    base_vertex: i32,
+    flags: u32,  // бит 0 - воксельный чанк, бит 1 - закрыт рельефом, с бита 8 - группа
+}
+
+struct DrawArgs {
+    index_count: u32,
+    instance_count: u32,
+    first_index: u32,
+    base_vertex: i32,
+    first_instance: u32,
+}
+
+const FLAG_VOXEL: u32 = 1u;
+const FLAG_OCCLUDED: u32 = 2u;
+
+@group(0) @binding(0)
+var<uniform> cull: CullUniforms;
+@group(0) @binding(1)
+var<storage, read> records: array<DrawRecord>;
+@group(0) @binding(2)
+var<storage, read> group_starts: array<u32>;
+@group(0) @binding(3)
+var<storage, read_write> draws: array<DrawArgs>;
+@group(0) @binding(4)
+var<storage, read_write> group_counts: array<atomic<u32>>;
+
+// AABB целиком снаружи хотя бы одной плоскости
+fn outside_frustum(aabb_min: vec3<f32>, aabb_max: vec3<f32>) -> bool {
+    for (var i = 0; i < 6; i++) {
+        let plane = cull.planes[i];
+        let corner = select(aabb_min, aabb_max, plane.xyz >= vec3<f32>(0.0));
+        if (dot(plane.xyz, corner) + plane.w < 0.0) {
+            return true;
+        }
+    }
+    return false;
+}
+
+@compute @workgroup_size(64)
+fn cs_main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let index = id.x;
+    if (index >= cull.counts.x) {
+        return;
+    }
+    let record = records[index];
+    let voxel = (record.flags & FLAG_VOXEL) != 0u;
+
+    if ((record.flags & FLAG_OCCLUDED) != 0u) {
+        return;
+    }
+    // Под землёй дальние LOD (только поверхность) не видны
+    if (cull.params.w > 0.5 && !voxel) {
+        return;
+    }
+    // Вертикальная дальность - только для воксельных чанков
+    if (cull.params.z > 0.5 && voxel && (record.aabb_max.y < cull.params.x || record.aabb_min.y > cull.params.y)) {
+        return;
+    }
+    if (outside_frustum(record.aabb_min.xyz, record.aabb_max.xyz)) {
+        return;
+    }
+
+    let group = record.flags >> 8u;
+    let slot = group_starts[group] + atomicAdd(&group_counts[group], 1u);
+    draws[slot] = DrawArgs(record.index_count, 1u, record.first_index, record.base_vertex, 0u);
+}
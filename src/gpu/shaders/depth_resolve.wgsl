@@ -0,0 +1,23 @@
+// ============================================
+// Depth Resolve - Мультисэмпловая глубина в обычную
+// ============================================
+// Для шейдера воды при MSAA. Reversed-Z: ближайший сэмпл - максимум.
+
+@group(0) @binding(0)
+var msaa_depth: texture_depth_multisampled_2d;
+
+@vertex
+fn vs_fullscreen(@builtin(vertex_index) vertex: u32) -> @builtin(position) vec4<f32> {
+    let xy = vec2<f32>(f32((vertex << 1u) & 2u), f32(vertex & 2u));
+    return vec4<f32>(xy * 2.0 - 1.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @builtin(frag_depth) f32 {
+    let texel = vec2<i32>(position.xy);
+    var depth = 0.0;
+    for (var i = 0; i < i32(textureNumSamples(msaa_depth)); i++) {
+        depth = max(depth, textureLoad(msaa_depth, texel, i));
+    }
+    return depth;
+}
@@ -5,7 +5,8 @@
 struct Uniforms {
     view_proj: mat4x4<f32>,
     block_pos: vec3<f32>,
-    block_size: f32,
+    // Размер по осям (блок - одинаковый, выделение - параллелепипед)
+    block_size: vec3<f32>,
 }
 
 @group(0) @binding(0)
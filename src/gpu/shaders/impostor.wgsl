@@ -0,0 +1,133 @@
+// ============================================
+// Tree Impostor Shader - Billboard деревья
+// ============================================
+// Цилиндрические billboards (вращение вокруг Y к камере)
+
+struct Uniforms {
+    view_proj: mat4x4<f32>,
+    camera_pos: vec3<f32>,
+    time: f32,
+    sky_color: vec3<f32>,
+    time_of_day: f32,
+    fog_color: vec3<f32>,
+    palette_theme: f32,
+    sea_level: f32,
+    fog_start: f32,
+    fog_end: f32,
+    underwater: f32,
+}
+
+struct LightData {
+    direction: vec3<f32>,
+    intensity: f32,
+    color: vec3<f32>,
+    _padding: f32,
+}
+
+struct Impostor {
+    position_height: vec4<f32>,  // xyz основание + высота ствола
+    params: vec4<f32>,           // x = слот атласа
+}
+
+// Размер слота атласа в мировых единицах (совпадает с bake-проекцией)
+const IMPOSTOR_WIDTH: f32 = 5.0;
+const IMPOSTOR_HEIGHT: f32 = 10.0;
+const ATLAS_SLOTS: f32 = 4.0;
+const REFERENCE_HEIGHT: f32 = 6.0;
+
+@group(0) @binding(0)
+var<uniform> uniforms: Uniforms;
+
+@group(1) @binding(0)
+var<uniform> light: LightData;
+
+@group(2) @binding(0)
+var<storage, read> impostors: array<Impostor>;
+@group(2) @binding(1)
+var atlas_texture: texture_2d<f32>;
+@group(2) @binding(2)
+var atlas_sampler: sampler;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) world_pos: vec3<f32>,
+}
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32,
+) -> VertexOutput {
+    var out: VertexOutput;
+    let imp = impostors[instance_index];
+
+    // Квад из 6 вершин без vertex buffer
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0), vec2<f32>(1.0, 0.0), vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, 0.0), vec2<f32>(1.0, 1.0), vec2<f32>(0.0, 1.0),
+    );
+    let corner = corners[vertex_index];
+
+    let base = imp.position_height.xyz;
+    let scale = imp.position_height.w / REFERENCE_HEIGHT;
+
+    var to_camera = uniforms.camera_pos.xz - base.xz;
+    if (length(to_camera) < 0.001) {
+        to_camera = vec2<f32>(0.0, 1.0);
+    }
+    let dir = normalize(to_camera);
+    let right = vec3<f32>(dir.y, 0.0, -dir.x);
+
+    let offset = right * (corner.x - 0.5) * IMPOSTOR_WIDTH * scale
+        + vec3<f32>(0.0, corner.y * IMPOSTOR_HEIGHT * scale, 0.0);
+    let world_pos = base + offset;
+
+    out.clip_position = uniforms.view_proj * vec4<f32>(world_pos, 1.0);
+    out.world_pos = world_pos;
+
+    let slot = imp.params.x;
+    out.uv = vec2<f32>((slot + corner.x) / ATLAS_SLOTS, 1.0 - corner.y);
+    return out;
+}
+
+// Цветовая тема мира (LUT поверх базовых цветов блоков)
+// 0 = default, 1 = pastel, 2 = gritty
+fn apply_palette(color: vec3<f32>) -> vec3<f32> {
+    let theme = u32(uniforms.palette_theme + 0.5);
+    if (theme == 0u) {
+        return color;
+    }
+    let luma = dot(color, vec3<f32>(0.299, 0.587, 0.114));
+    if (theme == 1u) {
+        // Пастель: меньше насыщенности и контраста, подъём к тёплому белому
+        let desat = mix(vec3<f32>(luma), color, 0.6);
+        let soft = mix(vec3<f32>(0.5), desat, 0.85);
+        return mix(soft, vec3<f32>(1.0, 0.96, 0.93), 0.2);
+    }
+    // Gritty: приглушённые цвета, жёсткий контраст, тёплый сдвиг
+    let desat = mix(vec3<f32>(luma), color, 0.7);
+    let graded = clamp((desat - 0.5) * 1.25 + 0.5, vec3<f32>(0.0), vec3<f32>(1.0));
+    return graded * vec3<f32>(1.0, 0.93, 0.8);
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let tex = textureSample(atlas_texture, atlas_sampler, in.uv);
+    if (tex.a < 0.5) {
+        discard;
+    }
+
+    // Billboard не имеет настоящей нормали - освещаем как поверхность сверху
+    let ndotl = max(-light.direction.y, 0.0);
+    let lighting = 0.3 + ndotl * light.intensity * 0.7;
+    var color = apply_palette(tex.rgb) * light.color * lighting;
+
+    // Под водой туман по полному расстоянию, иначе по горизонтали
+    let offset = in.world_pos - uniforms.camera_pos;
+    let dist = select(length(offset.xz), length(offset), uniforms.underwater > 0.5);
+    let fog = smoothstep(uniforms.fog_start, uniforms.fog_end, dist);
+    color = mix(color, uniforms.fog_color, fog);
+
+    return vec4<f32>(color, 1.0);
+}
@@ -0,0 +1,42 @@
+// ============================================
+// Impostor Bake Shader
+// ============================================
+// Рендер блочного дерева в слот атласа (ортографическая проекция)
+
+struct BakeUniforms {
+    proj: mat4x4<f32>,
+}
+
+@group(0) @binding(0)
+var<uniform> bake: BakeUniforms;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) color: vec3<f32>,
+    @location(3) block_id: u32,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) normal: vec3<f32>,
+    @location(1) color: vec3<f32>,
+}
+
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.clip_position = bake.proj * vec4<f32>(in.position, 1.0);
+    out.normal = in.normal;
+    out.color = in.color;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Запекаем только мягкое затенение граней,
+    // дневной свет накладывается при рендере billboard
+    let bake_light = normalize(vec3<f32>(0.4, 0.8, 0.6));
+    let shade = 0.6 + 0.4 * max(dot(in.normal, bake_light), 0.0);
+    return vec4<f32>(in.color * shade, 1.0);
+}
@@ -0,0 +1,122 @@
+// ============================================
+// Instanced Decorations Shader
+// ============================================
+// Один меш на тип декорации, трансформы инстансов в storage buffer
+
+struct Uniforms {
+    view_proj: mat4x4<f32>,
+    camera_pos: vec3<f32>,
+    time: f32,
+    sky_color: vec3<f32>,
+    time_of_day: f32,
+    fog_color: vec3<f32>,
+    palette_theme: f32,
+    sea_level: f32,
+    fog_start: f32,
+    fog_end: f32,
+    underwater: f32,
+}
+
+struct LightData {
+    direction: vec3<f32>,
+    intensity: f32,
+    color: vec3<f32>,
+    _padding: f32,
+}
+
+struct Instance {
+    position_rotation: vec4<f32>,  // xyz + rotation Y
+    tint_scale: vec4<f32>,         // rgb + scale
+}
+
+@group(0) @binding(0)
+var<uniform> uniforms: Uniforms;
+
+@group(1) @binding(0)
+var<uniform> light: LightData;
+
+@group(2) @binding(0)
+var<storage, read> instances: array<Instance>;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) color: vec3<f32>,
+    @location(3) tint_weight: f32,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_pos: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) color: vec3<f32>,
+}
+
+@vertex
+fn vs_main(in: VertexInput, @builtin(instance_index) instance_index: u32) -> VertexOutput {
+    var out: VertexOutput;
+    let inst = instances[instance_index];
+
+    let c = cos(inst.position_rotation.w);
+    let s = sin(inst.position_rotation.w);
+    let scale = inst.tint_scale.w;
+
+    var local = in.position * scale;
+
+    // Лёгкое покачивание верхушек на ветру
+    let sway = sin(uniforms.time * 1.7 + inst.position_rotation.x * 0.3 + inst.position_rotation.z * 0.2);
+    local.x += sway * 0.04 * in.position.y;
+
+    let rotated = vec3<f32>(
+        local.x * c - local.z * s,
+        local.y,
+        local.x * s + local.z * c,
+    );
+    let normal = vec3<f32>(
+        in.normal.x * c - in.normal.z * s,
+        in.normal.y,
+        in.normal.x * s + in.normal.z * c,
+    );
+
+    let world_pos = rotated + inst.position_rotation.xyz;
+    out.clip_position = uniforms.view_proj * vec4<f32>(world_pos, 1.0);
+    out.world_pos = world_pos;
+    out.normal = normal;
+    out.color = mix(in.color, in.color * inst.tint_scale.rgb, in.tint_weight);
+    return out;
+}
+
+// Цветовая тема мира (LUT поверх базовых цветов блоков)
+// 0 = default, 1 = pastel, 2 = gritty
+fn apply_palette(color: vec3<f32>) -> vec3<f32> {
+    let theme = u32(uniforms.palette_theme + 0.5);
+    if (theme == 0u) {
+        return color;
+    }
+    let luma = dot(color, vec3<f32>(0.299, 0.587, 0.114));
+    if (theme == 1u) {
+        // Пастель: меньше насыщенности и контраста, подъём к тёплому белому
+        let desat = mix(vec3<f32>(luma), color, 0.6);
+        let soft = mix(vec3<f32>(0.5), desat, 0.85);
+        return mix(soft, vec3<f32>(1.0, 0.96, 0.93), 0.2);
+    }
+    // Gritty: приглушённые цвета, жёсткий контраст, тёплый сдвиг
+    let desat = mix(vec3<f32>(luma), color, 0.7);
+    let graded = clamp((desat - 0.5) * 1.25 + 0.5, vec3<f32>(0.0), vec3<f32>(1.0));
+    return graded * vec3<f32>(1.0, 0.93, 0.8);
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let ndotl = max(dot(in.normal, -light.direction), 0.0);
+    let lighting = 0.3 + ndotl * light.intensity * 0.7;
+    var color = apply_palette(in.color) * light.color * lighting;
+
+    // Под водой туман по полному расстоянию, иначе по горизонтали
+    let offset = in.world_pos - uniforms.camera_pos;
+    let dist = select(length(offset.xz), length(offset), uniforms.underwater > 0.5);
+    let fog = smoothstep(uniforms.fog_start, uniforms.fog_end, dist);
+    color = mix(color, uniforms.fog_color, fog);
+
+    return vec4<f32>(color, 1.0);
+}
@@ -0,0 +1,54 @@
+// ============================================
+// Light Overlay Shader - Тепловая карта света
+// ============================================
+// Плоские полупрозрачные квады, цвет задан на CPU по уровню света.
+// Без тумана и освещения: оверлей должен читаться и ночью.
+
+struct Uniforms {
+    view_proj: mat4x4<f32>,
+    camera_pos: vec3<f32>,
+    time: f32,
+    sky_color: vec3<f32>,
+    time_of_day: f32,
+    fog_color: vec3<f32>,
+    palette_theme: f32,
+}
+
+@group(0) @binding(0)
+var<uniform> uniforms: Uniforms;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) color: vec4<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+    @location(1) local: vec2<f32>,
+}
+
+@vertex
+fn vs_main(in: VertexInput, @builtin(vertex_index) vertex: u32) -> VertexOutput {
+    var out: VertexOutput;
+    // Порядок вершин квада совпадает с push_quad в light_overlay.rs
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0), vec2<f32>(1.0, 0.0), vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, 0.0), vec2<f32>(1.0, 1.0), vec2<f32>(0.0, 1.0),
+    );
+    out.clip_position = uniforms.view_proj * vec4<f32>(in.position, 1.0);
+    out.color = in.color;
+    out.local = corners[vertex % 6u];
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Тонкая рамка вокруг клетки, чтобы было видно границы блоков
+    let edge = min(min(in.local.x, 1.0 - in.local.x), min(in.local.y, 1.0 - in.local.y));
+    var alpha = in.color.a;
+    if (edge < 0.04) {
+        alpha = min(alpha + 0.3, 0.9);
+    }
+    return vec4<f32>(in.color.rgb, alpha);
+}
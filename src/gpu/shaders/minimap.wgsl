@@ -0,0 +1,69 @@
+// ============================================
+// Minimap Shader - Миникарта
+// ============================================
+// Квадрат из 6 вершин без буфера. Фрагмент переводится в мировые x, z
+// вокруг игрока и читает кольцевую текстуру цветов столбцов.
+// Север (-Z) сверху, в центре - стрелка взгляда игрока.
+
+struct MinimapUniforms {
+    rect: vec4<f32>,  // x0, y0, x1, y1 (NDC)
+    view: vec4<f32>,  // центр x, z, блоков по ширине, yaw
+    size: vec4<f32>,  // размер карты (пиксели), размер текстуры, -, -
+}
+
+@group(0) @binding(0)
+var<uniform> uniforms: MinimapUniforms;
+@group(0) @binding(1)
+var colors: texture_2d<f32>;
+
+const BACKGROUND: vec4<f32> = vec4<f32>(0.05, 0.06, 0.08, 0.85);
+const BORDER: vec4<f32> = vec4<f32>(0.35, 0.4, 0.45, 1.0);
+const MARKER: vec4<f32> = vec4<f32>(1.0, 1.0, 1.0, 1.0);
+const BORDER_WIDTH: f32 = 2.0;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0), vec2<f32>(1.0, 0.0), vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, 0.0), vec2<f32>(1.0, 1.0), vec2<f32>(0.0, 1.0),
+    );
+    let uv = corners[index];
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(mix(uniforms.rect.xy, uniforms.rect.zw, uv), 0.0, 1.0);
+    out.uv = uv;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let map_size = uniforms.size.x;
+    // Пиксели от центра карты: x - восток, y - юг
+    let local = (in.uv - 0.5) * map_size;
+
+    if (max(abs(local.x), abs(local.y)) > map_size * 0.5 - BORDER_WIDTH) {
+        return BORDER;
+    }
+
+    // Стрелка игрока: вперёд по yaw (x - cos, z - sin, как в Player)
+    let forward = vec2<f32>(cos(uniforms.view.w), sin(uniforms.view.w));
+    let right = vec2<f32>(-forward.y, forward.x);
+    let ahead = dot(local, forward);
+    let side = abs(dot(local, right));
+    if (ahead > -4.0 && ahead < 7.0 && side < (7.0 - ahead) * 0.45) {
+        return MARKER;
+    }
+
+    let world = uniforms.view.xy + (in.uv - 0.5) * uniforms.view.z;
+    let texture_size = i32(uniforms.size.y);
+    let texel = ((vec2<i32>(floor(world)) % texture_size) + texture_size) % texture_size;
+    let color = textureLoad(colors, texel, 0);
+    if (color.a == 0.0) {
+        return BACKGROUND;
+    }
+    return vec4<f32>(color.rgb, 0.9);
+}
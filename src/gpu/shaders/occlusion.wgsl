@@ -0,0 +1,21 @@
+// ============================================
+// Occlusion Proxy Shader - AABB чанков для occlusion queries
+// ============================================
+// Цвет не пишется: важно только, прошёл ли хоть один фрагмент тест глубины.
+
+struct Uniforms {
+    view_proj: mat4x4<f32>,
+}
+
+@group(0) @binding(0)
+var<uniform> uniforms: Uniforms;
+
+@vertex
+fn vs_main(@location(0) position: vec3<f32>) -> @builtin(position) vec4<f32> {
+    return uniforms.view_proj * vec4<f32>(position, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(0.0);
+}
@@ -0,0 +1,89 @@
+// ============================================
+// Particles Shader - Billboard частицы
+// ============================================
+// 6 вершин на частицу, данные инстанса в storage buffer.
+// Квад всегда повёрнут к камере, освещение - как у окружения.
+
+struct Uniforms {
+    view_proj: mat4x4<f32>,
+    camera_pos: vec3<f32>,
+    time: f32,
+    sky_color: vec3<f32>,
+    time_of_day: f32,
+    fog_color: vec3<f32>,
+    palette_theme: f32,
+    sea_level: f32,
+    fog_start: f32,
+    fog_end: f32,
+    underwater: f32,
+}
+
+struct LightData {
+    direction: vec3<f32>,
+    intensity: f32,
+    color: vec3<f32>,
+    _padding: f32,
+}
+
+struct Particle {
+    position_size: vec4<f32>,  // xyz + half size
+    color: vec4<f32>,          // rgb + alpha
+}
+
+@group(0) @binding(0)
+var<uniform> uniforms: Uniforms;
+
+@group(1) @binding(0)
+var<uniform> light: LightData;
+
+@group(2) @binding(0)
+var<storage, read> particles: array<Particle>;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_pos: vec3<f32>,
+    @location(1) color: vec4<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex: u32, @builtin(instance_index) instance: u32) -> VertexOutput {
+    var out: VertexOutput;
+    let p = particles[instance];
+    let center = p.position_size.xyz;
+    let size = p.position_size.w;
+
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(-1.0, -1.0), vec2<f32>(1.0, -1.0), vec2<f32>(1.0, 1.0),
+        vec2<f32>(-1.0, -1.0), vec2<f32>(1.0, 1.0), vec2<f32>(-1.0, 1.0),
+    );
+    let corner = corners[vertex];
+
+    // Базис billboard: right горизонтален, up перпендикулярен взгляду
+    let to_camera = normalize(uniforms.camera_pos - center + vec3<f32>(0.0, 0.0001, 0.0));
+    let right = normalize(cross(vec3<f32>(0.0, 1.0, 0.0), to_camera) + vec3<f32>(0.0001, 0.0, 0.0));
+    let up = cross(to_camera, right);
+
+    let world_pos = center + (right * corner.x + up * corner.y) * size;
+    out.clip_position = uniforms.view_proj * vec4<f32>(world_pos, 1.0);
+    out.world_pos = world_pos;
+    out.color = p.color;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    if (in.color.a < 0.01) {
+        discard;
+    }
+    // Частицы не имеют нормали - освещаем как грань, повёрнутую к солнцу наполовину
+    let lighting = 0.35 + light.intensity * 0.5;
+    var color = in.color.rgb * light.color * lighting;
+
+    // Под водой туман по полному расстоянию, иначе по горизонтали
+    let offset = in.world_pos - uniforms.camera_pos;
+    let dist = select(length(offset.xz), length(offset), uniforms.underwater > 0.5);
+    let fog = smoothstep(uniforms.fog_start, uniforms.fog_end, dist);
+    color = mix(color, uniforms.fog_color, fog);
+
+    return vec4<f32>(color, in.color.a);
+}
@@ -0,0 +1,185 @@
+// ============================================
+// Post Shader - Постобработка HDR кадра
+// ============================================
+// Полноэкранный треугольник без вершинного буфера.
+// fs_bright  - яркие места кадра в bloom-текстуру (половина разрешения)
+// fs_blur_h / fs_blur_v - раздельное гауссово размытие bloom
+// fs_composite - кадр + bloom, глубина резкости, экспозиция, ACES,
+//                виньетка -> surface
+// fs_fxaa - сглаживание готового LDR кадра (если включено)
+
+struct PostUniforms {
+    params: vec4<f32>,  // экспозиция, сила bloom (0 - выкл), тонмаппинг (0/1), виньетка
+    bloom: vec4<f32>,   // порог, мягкость порога, -, -
+    dof: vec4<f32>,     // фокус, резкая зона, сила (0 - выкл), -
+    clip: vec4<f32>,    // near, far камеры, -, -
+}
+
+@group(0) @binding(0)
+var source_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var bloom_texture: texture_2d<f32>;
+@group(0) @binding(2)
+var post_sampler: sampler;
+@group(0) @binding(3)
+var<uniform> uniforms: PostUniforms;
+@group(0) @binding(4)
+var depth_texture: texture_depth_2d;
+@group(0) @binding(5)
+var dof_texture: texture_2d<f32>;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_fullscreen(@builtin(vertex_index) vertex: u32) -> VertexOutput {
+    // Треугольник, накрывающий экран: (-1,-1), (3,-1), (-1,3)
+    let xy = vec2<f32>(f32((vertex << 1u) & 2u), f32(vertex & 2u));
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(xy * 2.0 - 1.0, 0.0, 1.0);
+    out.uv = vec2<f32>(xy.x, 1.0 - xy.y);
+    return out;
+}
+
+fn luminance(color: vec3<f32>) -> f32 {
+    return dot(color, vec3<f32>(0.2126, 0.7152, 0.0722));
+}
+
+// === Bloom ===
+
+@fragment
+fn fs_bright(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(source_texture, post_sampler, in.uv).rgb;
+    // Мягкий порог: квадратичный переход в зоне [порог - knee, порог + knee]
+    let threshold = uniforms.bloom.x;
+    let knee = uniforms.bloom.y;
+    let brightness = luminance(color);
+    let soft = clamp(brightness - threshold + knee, 0.0, 2.0 * knee);
+    let contribution = max(soft * soft / (4.0 * knee + 0.0001), brightness - threshold);
+    return vec4<f32>(color * max(contribution, 0.0) / max(brightness, 0.0001), 1.0);
+}
+
+// 9-таповый гаусс: центр + 4 пары
+fn blur(uv: vec2<f32>, direction: vec2<f32>) -> vec4<f32> {
+    var weights = array<f32, 5>(0.227027, 0.1945946, 0.1216216, 0.054054, 0.016216);
+    let texel = direction / vec2<f32>(textureDimensions(source_texture));
+    var color = textureSample(source_texture, post_sampler, uv).rgb * weights[0];
+    for (var i = 1; i < 5; i++) {
+        let offset = texel * f32(i);
+        color += textureSample(source_texture, post_sampler, uv + offset).rgb * weights[i];
+        color += textureSample(source_texture, post_sampler, uv - offset).rgb * weights[i];
+    }
+    return vec4<f32>(color, 1.0);
+}
+
+@fragment
+fn fs_blur_h(in: VertexOutput) -> @location(0) vec4<f32> {
+    return blur(in.uv, vec2<f32>(1.0, 0.0));
+}
+
+@fragment
+fn fs_blur_v(in: VertexOutput) -> @location(0) vec4<f32> {
+    return blur(in.uv, vec2<f32>(0.0, 1.0));
+}
+
+// === Композиция ===
+
+// ACES filmic (аппроксимация Narkowicz)
+fn aces(color: vec3<f32>) -> vec3<f32> {
+    let a = 2.51;
+    let b = 0.03;
+    let c = 2.43;
+    let d = 0.59;
+    let e = 0.14;
+    return clamp((color * (a * color + b)) / (color * (c * color + d) + e), vec3<f32>(0.0), vec3<f32>(1.0));
+}
+
+// Reversed-Z: глубина -> расстояние от камеры
+fn linear_depth(depth: f32) -> f32 {
+    let near = uniforms.clip.x;
+    let far = uniforms.clip.y;
+    return near * far / (depth * (far - near) + near);
+}
+
+// Доля размытого кадра: 0 в резкой зоне у фокуса, дальше растёт
+fn circle_of_confusion(uv: vec2<f32>) -> f32 {
+    let size = vec2<f32>(textureDimensions(depth_texture));
+    let texel = vec2<i32>(clamp(uv * size, vec2<f32>(0.0), size - 1.0));
+    let distance = linear_depth(textureLoad(depth_texture, texel, 0));
+    let focus = uniforms.dof.x;
+    let blur = (abs(distance - focus) - uniforms.dof.y) / (focus * 0.5 + 1.0);
+    return clamp(blur, 0.0, 1.0) * uniforms.dof.z;
+}
+
+@fragment
+fn fs_composite(in: VertexOutput) -> @location(0) vec4<f32> {
+    var color = textureSample(source_texture, post_sampler, in.uv).rgb;
+    if (uniforms.dof.z > 0.0) {
+        let blurred = textureSample(dof_texture, post_sampler, in.uv).rgb;
+        color = mix(color, blurred, circle_of_confusion(in.uv));
+    }
+    if (uniforms.params.y > 0.0) {
+        color += textureSample(bloom_texture, post_sampler, in.uv).rgb * uniforms.params.y;
+    }
+
+    color *= uniforms.params.x;
+    if (uniforms.params.z > 0.5) {
+        color = aces(color);
+    } else {
+        color = clamp(color, vec3<f32>(0.0), vec3<f32>(1.0));
+    }
+
+    // Виньетка: затемнение к углам экрана
+    let centered = in.uv - 0.5;
+    let vignette = 1.0 - uniforms.params.w * smoothstep(0.2, 0.75, length(centered));
+    color *= vignette;
+
+    return vec4<f32>(color, 1.0);
+}
+
+// === FXAA ===
+
+const FXAA_SPAN_MAX: f32 = 8.0;
+const FXAA_REDUCE_MUL: f32 = 0.125;
+const FXAA_REDUCE_MIN: f32 = 0.0078125;
+
+// Яркость в гамме: LDR текстура sRGB и при чтении уже линейна
+fn fxaa_luma(color: vec3<f32>) -> f32 {
+    return sqrt(luminance(color));
+}
+
+@fragment
+fn fs_fxaa(in: VertexOutput) -> @location(0) vec4<f32> {
+    let texel = 1.0 / vec2<f32>(textureDimensions(source_texture));
+    let center = textureSample(source_texture, post_sampler, in.uv).rgb;
+    let nw = fxaa_luma(textureSample(source_texture, post_sampler, in.uv + vec2<f32>(-1.0, -1.0) * texel).rgb);
+    let ne = fxaa_luma(textureSample(source_texture, post_sampler, in.uv + vec2<f32>(1.0, -1.0) * texel).rgb);
+    let sw = fxaa_luma(textureSample(source_texture, post_sampler, in.uv + vec2<f32>(-1.0, 1.0) * texel).rgb);
+    let se = fxaa_luma(textureSample(source_texture, post_sampler, in.uv + vec2<f32>(1.0, 1.0) * texel).rgb);
+    let m = fxaa_luma(center);
+
+    let luma_min = min(m, min(min(nw, ne), min(sw, se)));
+    let luma_max = max(m, max(max(nw, ne), max(sw, se)));
+
+    // Направление вдоль края (поперёк градиента яркости)
+    var dir = vec2<f32>(-((nw + ne) - (sw + se)), (nw + sw) - (ne + se));
+    let reduce = max((nw + ne + sw + se) * 0.25 * FXAA_REDUCE_MUL, FXAA_REDUCE_MIN);
+    let scale = 1.0 / (min(abs(dir.x), abs(dir.y)) + reduce);
+    dir = clamp(dir * scale, vec2<f32>(-FXAA_SPAN_MAX), vec2<f32>(FXAA_SPAN_MAX)) * texel;
+
+    let near = 0.5 * (
+        textureSample(source_texture, post_sampler, in.uv + dir * (1.0 / 3.0 - 0.5)).rgb +
+        textureSample(source_texture, post_sampler, in.uv + dir * (2.0 / 3.0 - 0.5)).rgb
+    );
+    let far = near * 0.5 + 0.25 * (
+        textureSample(source_texture, post_sampler, in.uv - dir * 0.5).rgb +
+        textureSample(source_texture, post_sampler, in.uv + dir * 0.5).rgb
+    );
+
+    // Широкая выборка зацепила соседний край - берём узкую
+    let far_luma = fxaa_luma(far);
+    let outside = far_luma < luma_min || far_luma > luma_max;
+    return vec4<f32>(select(far, near, outside), 1.0);
+}
@@ -10,7 +10,13 @@ struct Uniforms {
     sky_color: vec3<f32>,
     time_of_day: f32,
     fog_color: vec3<f32>,
-    _pad: f32,
+    palette_theme: f32,
+    sea_level: f32,
+    fog_start: f32,
+    fog_end: f32,
+    underwater: f32,
+    clouds: vec4<f32>,   // покрытие (0 - облаков нет), высота слоя, смещение X/Z
+    fog: vec4<f32>,      // плотность на блок, спад с высотой, pad, pad
 }
 
 struct LightData {
@@ -45,17 +51,30 @@ var shadow_sampler: sampler_comparison;
 @group(2) @binding(2)
 var<uniform> shadow_data: ShadowData;
 
-// Текстурный атлас для кастомных блоков (ID >= 100)
+// Текстуры блоков (тайлы с текстурой, см. is_textured_tile): массив, слой на тайл,
+// сэмплер Repeat - склеенный квад повторяет тайл по UV вершины
 @group(3) @binding(0)
-var atlas_texture: texture_2d<f32>;
+var atlas_texture: texture_2d_array<f32>;
 @group(3) @binding(1)
 var atlas_sampler: sampler;
 
+// Настройки сэмплинга атласа (render::sampling) и маска тайлов с
+// текстурой (BlockTextureAtlas::textured_mask, бит на тайл)
+struct AtlasParams {
+    mip_bias: f32,
+    textured: array<vec4<u32>, 8>,
+}
+
+@group(3) @binding(2)
+var<uniform> atlas_params: AtlasParams;
+
 struct VertexInput {
     @location(0) position: vec3<f32>,
     @location(1) normal: vec3<f32>,
     @location(2) color: vec3<f32>,
     @location(3) block_id: u32,
+    // UV в тайлах от угла квада (0..ширина, 0..высота)
+    @location(4) uv: vec2<f32>,
 }
 
 struct VertexOutput {
@@ -65,10 +84,55 @@ struct VertexOutput {
     @location(2) color: vec3<f32>,
     @location(3) view_depth: f32,
     @location(4) block_id: u32,
+    // Дизеринг смены LOD: доля пикселей нового меша, меш исчезает (0/1)
+    @location(5) @interpolate(flat) fade: vec2<f32>,
+    @location(6) uv: vec2<f32>,
+}
+
+// Чанк в переходе между уровнями LOD (render::renderer::lod_fade)
+struct FadeInput {
+    @location(5) fade: vec2<f32>,
 }
 
 @vertex
 fn vs_main(in: VertexInput) -> VertexOutput {
+    return transform_vertex(in, vec2<f32>(1.0, 0.0));
+}
+
+@vertex
+fn vs_dither(in: VertexInput, instance: FadeInput) -> VertexOutput {
+    return transform_vertex(in, instance.fade);
+}
+
+// Субвоксели: PackedVertex (позиция в 1/16 блока от угла чанка,
+// индекс нормали, RGBA8) + угол чанка instance-атрибутом
+struct SubVoxelInput {
+    @location(0) packed: vec4<u32>,
+    @location(1) color: u32,
+    @location(2) origin: vec3<f32>,
+}
+
+@vertex
+fn vs_subvoxel(sv: SubVoxelInput) -> VertexOutput {
+    // Порядок как у NormalIndex: +X, -X, +Y, -Y, +Z, -Z
+    var normals = array<vec3<f32>, 6>(
+        vec3<f32>(1.0, 0.0, 0.0),
+        vec3<f32>(-1.0, 0.0, 0.0),
+        vec3<f32>(0.0, 1.0, 0.0),
+        vec3<f32>(0.0, -1.0, 0.0),
+        vec3<f32>(0.0, 0.0, 1.0),
+        vec3<f32>(0.0, 0.0, -1.0),
+    );
+    var in: VertexInput;
+    in.position = sv.origin + vec3<f32>(sv.packed.xyz) / 16.0;
+    in.normal = normals[min(sv.packed.w & 7u, 5u)];
+    in.color = unpack4x8unorm(sv.color).rgb;
+    in.block_id = 0u;
+    in.uv = vec2<f32>(0.0);
+    return transform_vertex(in, vec2<f32>(1.0, 0.0));
+}
+
+fn transform_vertex(in: VertexInput, fade: vec2<f32>) -> VertexOutput {
     var out: VertexOutput;
     
     let world_pos = vec4<f32>(in.position, 1.0);
@@ -77,9 +141,11 @@ fn vs_main(in: VertexInput) -> VertexOutput {
     out.normal = in.normal;
     out.color = in.color;
     out.block_id = in.block_id;
+    out.uv = in.uv;
     
     // Расстояние от камеры для выбора каскада
     out.view_depth = length(in.position - uniforms.camera_pos);
+    out.fade = fade;
     
     return out;
 }
@@ -174,6 +240,60 @@ fn calculate_shadow(world_pos: vec3<f32>, normal: vec3<f32>, view_depth: f32) ->
     return sample_shadow_pcf(world_pos, normal, cascade);
 }
 
+// === Cloud Shadows ===
+// Шум - копия из clouds.wgsl (менять в обоих файлах)
+
+const CLOUD_SCALE: f32 = 0.01;
+/// Насколько облако гасит прямой свет
+const CLOUD_SHADOW_STRENGTH: f32 = 0.65;
+
+fn cloud_hash(cell: vec2<i32>) -> f32 {
+    var x = (bitcast<u32>(cell.x) * 1597334673u) ^ (bitcast<u32>(cell.y) * 3812015801u);
+    x = (x ^ (x >> 16u)) * 2246822519u;
+    x = x ^ (x >> 13u);
+    return f32(x) / 4294967295.0;
+}
+
+fn cloud_noise(p: vec2<f32>) -> f32 {
+    let cell = vec2<i32>(floor(p));
+    let f = fract(p);
+    let u = f * f * (3.0 - 2.0 * f);
+    let a = cloud_hash(cell);
+    let b = cloud_hash(cell + vec2<i32>(1, 0));
+    let c = cloud_hash(cell + vec2<i32>(0, 1));
+    let d = cloud_hash(cell + vec2<i32>(1, 1));
+    return mix(mix(a, b, u.x), mix(c, d, u.x), u.y);
+}
+
+fn cloud_fbm(xz: vec2<f32>) -> f32 {
+    var p = xz * CLOUD_SCALE;
+    var value = 0.0;
+    var amplitude = 0.5;
+    for (var i = 0; i < 4; i++) {
+        value += cloud_noise(p) * amplitude;
+        p = p * 2.03 + vec2<f32>(17.3, 9.1);
+        amplitude *= 0.5;
+    }
+    return value / 0.9375;
+}
+
+fn cloud_density(xz: vec2<f32>, coverage: f32) -> f32 {
+    let threshold = 1.0 - coverage;
+    return smoothstep(threshold, threshold + 0.2, cloud_fbm(xz));
+}
+
+// Тень облака: луч к источнику света до плоскости облаков
+fn cloud_shadow(world_pos: vec3<f32>) -> f32 {
+    let coverage = uniforms.clouds.x;
+    let altitude = uniforms.clouds.y;
+    if (coverage <= 0.0 || world_pos.y >= altitude || light.direction.y > -0.05) {
+        return 1.0;
+    }
+    let t = (altitude - world_pos.y) / -light.direction.y;
+    let hit = world_pos.xz - light.direction.xz * t;
+    return 1.0 - cloud_density(hit - uniforms.clouds.zw, coverage) * CLOUD_SHADOW_STRENGTH;
+}
+
 // === Texture Functions ===
 
 fn hash2(p: vec2<f32>) -> f32 {
@@ -182,13 +302,14 @@ fn hash2(p: vec2<f32>) -> f32 {
     return fract(16.0 * k.x * fract(q.x * q.y * (q.x + q.y)));
 }
 
-fn get_block_uv(world_pos: vec3<f32>, normal: vec3<f32>) -> vec2<f32> {
+// Координаты на плоскости грани (без fract, для производных)
+fn get_face_coords(world_pos: vec3<f32>, normal: vec3<f32>) -> vec2<f32> {
     if (abs(normal.y) > 0.5) {
-        return fract(world_pos.xz);
+        return world_pos.xz;
     } else if (abs(normal.x) > 0.5) {
-        return fract(vec2<f32>(world_pos.z, world_pos.y));
+        return vec2<f32>(world_pos.z, world_pos.y);
     } else {
-        return fract(vec2<f32>(world_pos.x, world_pos.y));
+        return vec2<f32>(world_pos.x, world_pos.y);
     }
 }
 
@@ -214,30 +335,115 @@ fn get_texture_variation(base_color: vec3<f32>, uv: vec2<f32>, world_pos: vec3<f
 
 // === Texture Atlas Functions ===
 
-const ATLAS_SIZE: f32 = 16.0;  // 16x16 блоков в атласе
+// block_id вершины: биты 0-7 - тип блока, 8-21 - тайл атласа,
+// 22 - мокрая грань у кромки воды, 23 - грань под водой,
+// 24-31 - непрозрачность полупрозрачного блока (0 - непрозрачный)
+fn vertex_block(packed: u32) -> u32 {
+    return packed & 0xFFu;
+}
 
-// Получить UV в атласе для блока
-fn get_atlas_uv(block_id: u32, local_uv: vec2<f32>) -> vec2<f32> {
-    let atlas_x = f32(block_id % u32(ATLAS_SIZE));
-    let atlas_y = f32(block_id / u32(ATLAS_SIZE));
-    
-    // Небольшой отступ от краёв чтобы избежать bleeding
-    let padding = 0.001;
-    let clamped_uv = clamp(local_uv, vec2<f32>(padding), vec2<f32>(1.0 - padding));
-    
-    let u = (atlas_x + clamped_uv.x) / ATLAS_SIZE;
-    let v = (atlas_y + clamped_uv.y) / ATLAS_SIZE;
-    
-    return vec2<f32>(u, v);
+fn vertex_tile(packed: u32) -> u32 {
+    return (packed >> 8u) & 0x3FFFu;
 }
 
-// Проверить, кастомный ли блок (ID >= 100)
-fn is_custom_block(block_id: u32) -> bool {
-    return block_id >= 100u;
+fn vertex_wet(packed: u32) -> bool {
+    return (packed & (1u << 22u)) != 0u;
 }
 
-@fragment
-fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+fn vertex_submerged(packed: u32) -> bool {
+    return (packed & (1u << 23u)) != 0u;
+}
+
+fn vertex_alpha(packed: u32) -> f32 {
+    return f32(packed >> 24u) / 255.0;
+}
+
+// Есть ли у тайла текстура из пакета ресурсов или определения блока
+fn is_textured_tile(tile: u32) -> bool {
+    let word = atlas_params.textured[tile / 128u][(tile / 32u) % 4u];
+    return ((word >> (tile % 32u)) & 1u) == 1u;
+}
+
+// Цветовая тема мира (LUT поверх базовых цветов блоков)
+// 0 = default, 1 = pastel, 2 = gritty
+fn apply_palette(color: vec3<f32>) -> vec3<f32> {
+    let theme = u32(uniforms.palette_theme + 0.5);
+    if (theme == 0u) {
+        return color;
+    }
+    let luma = dot(color, vec3<f32>(0.299, 0.587, 0.114));
+    if (theme == 1u) {
+        // Пастель: меньше насыщенности и контраста, подъём к тёплому белому
+        let desat = mix(vec3<f32>(luma), color, 0.6);
+        let soft = mix(vec3<f32>(0.5), desat, 0.85);
+        return mix(soft, vec3<f32>(1.0, 0.96, 0.93), 0.2);
+    }
+    // Gritty: приглушённые цвета, жёсткий контраст, тёплый сдвиг
+    let desat = mix(vec3<f32>(luma), color, 0.7);
+    let graded = clamp((desat - 0.5) * 1.25 + 0.5, vec3<f32>(0.0), vec3<f32>(1.0));
+    return graded * vec3<f32>(1.0, 0.93, 0.8);
+}
+
+// Процедурные каустики: два слоя бегущих интерференционных волн
+fn caustics(pos: vec2<f32>, time: f32) -> f32 {
+    let p = pos * 0.6;
+    let a = sin(p.x * 1.7 + time * 0.9 + sin(p.y * 1.3 + time * 0.6) * 1.5);
+    let b = sin(p.y * 1.9 - time * 0.7 + sin(p.x * 1.1 - time * 0.5) * 1.5);
+    let c = sin((p.x + p.y) * 1.2 + time * 0.4);
+    let v = (a + b + c) / 3.0;
+    // Узкие яркие линии там, где волны сходятся
+    return pow(1.0 - abs(v), 6.0);
+}
+
+// Подводные каустики и мокрая полоса у кромки воды. Флаги ставит мешер
+// по соседству с водой, так что эффекты работают в озёрах и реках на любой
+// высоте, а не только у уровня моря
+fn apply_water_effects(color: vec3<f32>, world_pos: vec3<f32>, normal: vec3<f32>, packed: u32, lit: f32) -> vec3<f32> {
+    if (vertex_submerged(packed)) {
+        // Каустики видны только на освещённых гранях
+        let facing = max(normal.y, 0.0) * 0.7 + 0.3;
+        let pattern = caustics(world_pos.xz + normal.xz * world_pos.y * 0.5, uniforms.time);
+        return color * (1.0 + pattern * 0.35 * facing * lit);
+    }
+    if (vertex_wet(packed)) {
+        return color * 0.7;
+    }
+    return color;
+}
+
+// Туман: под водой - линейный по полному расстоянию. Над водой -
+// экспоненциальный по высоте (гуще в низинах), у края дальности LOD
+// сплошной; вблизи цвета тумана, вдали - цвета неба
+fn apply_fog(color: vec3<f32>, world_pos: vec3<f32>) -> vec3<f32> {
+    let offset = world_pos - uniforms.camera_pos;
+    if (uniforms.underwater > 0.5) {
+        let fog = smoothstep(uniforms.fog_start, uniforms.fog_end, length(offset));
+        return mix(color, uniforms.fog_color, fog);
+    }
+
+    let dist = length(offset);
+    let horizontal = length(offset.xz);
+    let density = uniforms.fog.x;
+    let falloff = uniforms.fog.y;
+
+    // Плотность вдоль луча: exp(-falloff * h), проинтегрированная от камеры до точки
+    let camera_height = max(uniforms.camera_pos.y - uniforms.sea_level, -32.0);
+    let k = falloff * offset.y;
+    let along_ray = select((1.0 - exp(-k)) / k, 1.0, abs(k) < 0.001);
+    let optical_depth = density * exp(-falloff * camera_height) * along_ray * max(dist - uniforms.fog_start, 0.0);
+    let height_fog = 1.0 - exp(-optical_depth);
+
+    // Край прорисовки всегда в тумане - подгрузка чанков не видна
+    let edge = smoothstep(uniforms.fog_end * 0.85, uniforms.fog_end, horizontal);
+    let fog = max(height_fog, edge);
+
+    let far = smoothstep(uniforms.fog_start, uniforms.fog_end, horizontal);
+    let fog_color = mix(uniforms.fog_color, uniforms.sky_color, far);
+    return mix(color, fog_color, fog);
+}
+
+// Освещённый цвет фрагмента с туманом (общий для обоих проходов)
+fn shade_fragment(in: VertexOutput) -> vec3<f32> {
     // Направленное освещение
     let ndotl = max(dot(in.normal, -light.direction), 0.0);
     
@@ -247,8 +453,8 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     else if (abs(in.normal.x) > 0.5) { face_light = 0.75; }
     else { face_light = 0.6; }
     
-    // Тени с normal offset bias
-    let shadow = calculate_shadow(in.world_pos, in.normal, in.view_depth);
+    // Тени с normal offset bias и тени облаков
+    let shadow = calculate_shadow(in.world_pos, in.normal, in.view_depth) * cloud_shadow(in.world_pos);
     
     // Финальное освещение
     let ambient = 0.3;
@@ -256,25 +462,66 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let lighting = (ambient + diffuse * 0.7) * face_light;
     
     // UV координаты на грани блока
-    let uv = get_block_uv(in.world_pos, in.normal);
+    let face_coords = get_face_coords(in.world_pos, in.normal);
+    let uv = fract(face_coords);
+    
+    // UV вершины непрерывны по кваду, повтор делает сэмплер - выбор
+    // мипа не ломается на границах блоков
+    // Mip bias: производные x2 = на один мип-уровень мягче
+    let uv_scale = exp2(atlas_params.mip_bias);
+    let uv_dx = dpdx(in.uv) * uv_scale;
+    let uv_dy = dpdy(in.uv) * uv_scale;
     
     var color: vec3<f32>;
     
-    // Кастомные блоки (ID >= 100) используют текстурный атлас
-    if (is_custom_block(in.block_id)) {
-        let atlas_uv = get_atlas_uv(in.block_id, uv);
-        let tex_color = textureSample(atlas_texture, atlas_sampler, atlas_uv);
+    // Блоки с текстурой (пакет ресурсов или определение) - из атласа
+    let tile = vertex_tile(in.block_id);
+    if (is_textured_tile(tile)) {
+        let layer = i32(tile);
+        let tex_color = textureSampleGrad(atlas_texture, atlas_sampler, in.uv, layer, uv_dx, uv_dy);
         color = tex_color.rgb * lighting;
     } else {
         // Стандартные блоки - процедурные текстуры
         let tex_var = get_texture_variation(in.color, uv, in.world_pos);
-        color = in.color * light.color * (1.0 + tex_var) * lighting;
+        color = apply_palette(in.color) * light.color * (1.0 + tex_var) * lighting;
     }
+
+    color = apply_water_effects(color, in.world_pos, in.normal, in.block_id, light.intensity * shadow);
     
-    // Туман с динамическим цветом
-    let dist = length(in.world_pos.xz - uniforms.camera_pos.xz);
-    let fog = smoothstep(800.0, 1000.0, dist);
-    color = mix(color, uniforms.fog_color, fog);
-    
-    return vec4<f32>(color, 1.0);
+    return apply_fog(color, in.world_pos);
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return vec4<f32>(shade_fragment(in), 1.0);
+}
+
+// Порог screen-door дизеринга: матрица Байера 4x4
+fn dither_threshold(pixel: vec2<f32>) -> f32 {
+    var bayer = array<f32, 16>(
+        0.0, 8.0, 2.0, 10.0,
+        12.0, 4.0, 14.0, 6.0,
+        3.0, 11.0, 1.0, 9.0,
+        15.0, 7.0, 13.0, 5.0,
+    );
+    let p = vec2<u32>(pixel) % 4u;
+    return (bayer[p.y * 4u + p.x] + 0.5) / 16.0;
+}
+
+// Смена LOD: старый и новый меш делят пиксели по одному порогу,
+// исчезающий рисует ровно те, что ещё не занял проявляющийся
+@fragment
+fn fs_dither(in: VertexOutput) -> @location(0) vec4<f32> {
+    let threshold = dither_threshold(in.clip_position.xy);
+    let fading_out = in.fade.y > 0.5;
+    if ((threshold < in.fade.x) == fading_out) {
+        discard;
+    }
+    return vec4<f32>(shade_fragment(in), 1.0);
+}
+
+// Полупрозрачный проход (стекло, лёд): альфа из вершины
+@fragment
+fn fs_translucent(in: VertexOutput) -> @location(0) vec4<f32> {
+    return vec4<f32>(shade_fragment(in), vertex_alpha(in.block_id));
 }
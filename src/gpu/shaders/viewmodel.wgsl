@@ -0,0 +1,57 @@
+// ============================================
+// View Model Shader - Рука и блок от первого лица
+// ============================================
+// Геометрия задана в пространстве камеры и рисуется своей проекцией.
+// Глубина сжата в ближний диапазон [VIEWMODEL_DEPTH, 1] (reversed-Z),
+// поэтому рука не уходит в стены, а мир за ней не перекрывает её.
+// instance 0 - рука, instance 1 - блок в руке.
+
+struct ViewModelUniforms {
+    projection: mat4x4<f32>,
+    arm: mat4x4<f32>,
+    block: mat4x4<f32>,
+    light: vec4<f32>,  // яркость, -, -, -
+}
+
+@group(0) @binding(0)
+var<uniform> uniforms: ViewModelUniforms;
+
+const VIEWMODEL_DEPTH: f32 = 0.99;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) color: vec3<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) normal: vec3<f32>,
+    @location(1) color: vec3<f32>,
+}
+
+@vertex
+fn vs_main(in: VertexInput, @builtin(instance_index) instance: u32) -> VertexOutput {
+    var model = uniforms.arm;
+    if (instance == 1u) {
+        model = uniforms.block;
+    }
+
+    var clip = uniforms.projection * model * vec4<f32>(in.position, 1.0);
+    clip.z = (VIEWMODEL_DEPTH + clip.z / clip.w * (1.0 - VIEWMODEL_DEPTH)) * clip.w;
+
+    var out: VertexOutput;
+    out.clip_position = clip;
+    out.normal = normalize((model * vec4<f32>(in.normal, 0.0)).xyz);
+    out.color = in.color;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Свет сверху-спереди относительно камеры, грани как у блоков
+    let light_dir = normalize(vec3<f32>(0.3, 0.8, 0.5));
+    let ndotl = max(dot(in.normal, light_dir), 0.0);
+    let lighting = (0.45 + ndotl * 0.55) * uniforms.light.x;
+    return vec4<f32>(in.color * lighting, 1.0);
+}
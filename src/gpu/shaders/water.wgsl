@@ -0,0 +1,249 @@
+// ============================================
+// Water Shader - Волны, френель и экранные отражения
+// ============================================
+// Поверхность воды качается по времени, нормаль берётся из суммы
+// волн. Отражение - цвет неба или, если включено, луч по буферу
+// глубины в копию кадра (SSR).
+
+struct Uniforms {
+    view_proj: mat4x4<f32>,
+    camera_pos: vec3<f32>,
+    time: f32,
+    sky_color: vec3<f32>,
+    time_of_day: f32,
+    fog_color: vec3<f32>,
+    palette_theme: f32,
+    sea_level: f32,
+    fog_start: f32,
+    fog_end: f32,
+    underwater: f32,
+}
+
+struct LightData {
+    direction: vec3<f32>,
+    intensity: f32,
+    color: vec3<f32>,
+    _padding: f32,
+}
+
+struct ShadowData {
+    light_vp_0: mat4x4<f32>,
+    light_vp_1: mat4x4<f32>,
+    light_vp_2: mat4x4<f32>,
+    light_vp_3: mat4x4<f32>,
+    cascade_splits: vec4<f32>,
+    num_cascades: u32,
+    texel_size: f32,
+    bias: f32,
+    _pad: f32,
+}
+
+struct WaterParams {
+    near: f32,
+    far: f32,
+    reflections: f32,
+    _pad: f32,
+}
+
+@group(0) @binding(0)
+var<uniform> uniforms: Uniforms;
+
+@group(1) @binding(0)
+var<uniform> light: LightData;
+
+@group(2) @binding(0)
+var shadow_map: texture_depth_2d_array;
+@group(2) @binding(1)
+var shadow_sampler: sampler_comparison;
+@group(2) @binding(2)
+var<uniform> shadow_data: ShadowData;
+
+// Кадр до воды и его глубина (render::water)
+@group(3) @binding(0)
+var scene_color: texture_2d<f32>;
+@group(3) @binding(1)
+var scene_depth: texture_depth_2d;
+@group(3) @binding(2)
+var scene_sampler: sampler;
+@group(3) @binding(3)
+var<uniform> water: WaterParams;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) color: vec3<f32>,
+    @location(3) block_id: u32,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_pos: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) color: vec3<f32>,
+    @location(3) view_depth: f32,
+    @location(4) alpha: f32,
+}
+
+// Высота волн, блоки
+const WAVE_AMPLITUDE: f32 = 0.08;
+const SSR_STEPS: i32 = 32;
+
+// Сумма бегущих волн в диапазоне [-1, 1]
+fn wave_height(p: vec2<f32>, time: f32) -> f32 {
+    let a = sin(dot(p, vec2<f32>(0.8, 0.6)) * 0.9 + time * 1.3);
+    let b = sin(dot(p, vec2<f32>(-0.5, 0.9)) * 1.4 + time * 1.7);
+    let c = sin(dot(p, vec2<f32>(0.3, -0.95)) * 2.3 + time * 2.3);
+    return a * 0.5 + b * 0.3 + c * 0.2;
+}
+
+// Нормаль поверхности по конечным разностям мелкой ряби
+fn wave_normal(p: vec2<f32>, time: f32) -> vec3<f32> {
+    let e = 0.1;
+    let ripple = p * 3.0;
+    let h = wave_height(ripple, time * 1.5);
+    let dx = wave_height(ripple + vec2<f32>(e, 0.0), time * 1.5) - h;
+    let dz = wave_height(ripple + vec2<f32>(0.0, e), time * 1.5) - h;
+    return normalize(vec3<f32>(-dx * 0.6, e, -dz * 0.6));
+}
+
+@vertex
+fn vs_water(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+
+    // Смещение зависит только от позиции - общие вершины соседних
+    // граней сдвигаются одинаково и щелей нет. Вода только опускается,
+    // чтобы не вылезать в блок над ней
+    let wave = (wave_height(in.position.xz, uniforms.time) * 0.5 - 0.5) * WAVE_AMPLITUDE;
+    let position = in.position + vec3<f32>(0.0, wave, 0.0);
+
+    out.clip_position = uniforms.view_proj * vec4<f32>(position, 1.0);
+    out.world_pos = position;
+    out.normal = in.normal;
+    out.color = in.color;
+    out.view_depth = length(position - uniforms.camera_pos);
+    out.alpha = f32(in.block_id >> 24u) / 255.0;
+    return out;
+}
+
+// === Тени (один отсчёт, вода их только приглушает) ===
+
+fn select_cascade(view_depth: f32) -> u32 {
+    if (view_depth < shadow_data.cascade_splits.x) { return 0u; }
+    if (view_depth < shadow_data.cascade_splits.y) { return 1u; }
+    if (view_depth < shadow_data.cascade_splits.z) { return 2u; }
+    return 3u;
+}
+
+fn get_light_matrix(cascade: u32) -> mat4x4<f32> {
+    switch (cascade) {
+        case 0u: { return shadow_data.light_vp_0; }
+        case 1u: { return shadow_data.light_vp_1; }
+        case 2u: { return shadow_data.light_vp_2; }
+        default: { return shadow_data.light_vp_3; }
+    }
+}
+
+fn calculate_shadow(world_pos: vec3<f32>, view_depth: f32) -> f32 {
+    if (shadow_data.num_cascades == 0u) {
+        return 1.0;
+    }
+    let last_split = shadow_data.cascade_splits[shadow_data.num_cascades - 1u];
+    if (view_depth > last_split) {
+        return 1.0;
+    }
+
+    let cascade = select_cascade(view_depth);
+    let light_space = get_light_matrix(cascade) * vec4<f32>(world_pos + vec3<f32>(0.0, 0.1, 0.0), 1.0);
+    let ndc = light_space.xyz / light_space.w;
+    let uv = vec2<f32>(ndc.x * 0.5 + 0.5, -ndc.y * 0.5 + 0.5);
+    if (uv.x < 0.0 || uv.x > 1.0 || uv.y < 0.0 || uv.y > 1.0 || ndc.z < 0.0 || ndc.z > 1.0) {
+        return 1.0;
+    }
+    return textureSampleCompareLevel(shadow_map, shadow_sampler, uv, i32(cascade), ndc.z - 0.001);
+}
+
+// === Экранные отражения ===
+
+// Глубина reversed-Z (1 - near, 0 - far) в расстояние вдоль взгляда
+fn linear_depth(z: f32) -> f32 {
+    return water.near * water.far / (z * (water.far - water.near) + water.near);
+}
+
+// Луч по буферу глубины: rgb - цвет попадания, a - его вес
+// (0 - луч ушёл за экран или ни во что не попал)
+fn trace_reflection(origin: vec3<f32>, dir: vec3<f32>) -> vec4<f32> {
+    let size = vec2<f32>(textureDimensions(scene_depth));
+    var stride = 0.5;
+    var pos = origin;
+
+    for (var i = 0; i < SSR_STEPS; i++) {
+        pos += dir * stride;
+        stride *= 1.15;
+
+        let clip = uniforms.view_proj * vec4<f32>(pos, 1.0);
+        if (clip.w <= 0.0) {
+            break;
+        }
+        let ndc = clip.xyz / clip.w;
+        let uv = vec2<f32>(ndc.x * 0.5 + 0.5, -ndc.y * 0.5 + 0.5);
+        if (uv.x < 0.0 || uv.x > 1.0 || uv.y < 0.0 || uv.y > 1.0) {
+            break;
+        }
+
+        let texel = vec2<i32>(min(uv * size, size - vec2<f32>(1.0)));
+        let scene_w = linear_depth(textureLoad(scene_depth, texel, 0));
+        let behind = clip.w - scene_w;
+        // Луч зашёл за геометрию, но не слишком далеко (не через стену)
+        if (behind > 0.0 && behind < stride * 2.0 + 0.5) {
+            let edge = max(abs(ndc.x), abs(ndc.y));
+            let fade = (1.0 - smoothstep(0.8, 1.0, edge)) * (1.0 - f32(i) / f32(SSR_STEPS));
+            let color = textureSampleLevel(scene_color, scene_sampler, uv, 0.0).rgb;
+            return vec4<f32>(color, fade);
+        }
+    }
+    return vec4<f32>(0.0);
+}
+
+@fragment
+fn fs_water(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Рябь только на горизонтальной поверхности
+    var normal = in.normal;
+    if (abs(in.normal.y) > 0.5) {
+        normal = wave_normal(in.world_pos.xz, uniforms.time) * sign(in.normal.y);
+    }
+
+    let to_frag = normalize(in.world_pos - uniforms.camera_pos);
+    let shadow = calculate_shadow(in.world_pos, in.view_depth);
+
+    // Собственный цвет толщи воды
+    let ndotl = max(dot(normal, -light.direction), 0.0);
+    let lighting = 0.3 + ndotl * light.intensity * shadow * 0.7;
+    let body = in.color * light.color * lighting;
+
+    // Френель (Шлик): у горизонта вода почти зеркальная
+    let cos_theta = clamp(dot(-to_frag, normal), 0.0, 1.0);
+    let fresnel = 0.02 + 0.98 * pow(1.0 - cos_theta, 5.0);
+
+    let reflect_dir = reflect(to_frag, normal);
+    var reflection = uniforms.sky_color;
+    if (water.reflections > 0.5) {
+        let hit = trace_reflection(in.world_pos, reflect_dir);
+        reflection = mix(reflection, hit.rgb, hit.a);
+    }
+
+    // Блик солнца
+    let specular = pow(max(dot(reflect_dir, -light.direction), 0.0), 128.0) * light.intensity * shadow;
+
+    var color = mix(body, reflection, fresnel) + light.color * specular;
+
+    // Туман как у terrain
+    // Под водой туман по полному расстоянию, иначе по горизонтали
+    let offset = in.world_pos - uniforms.camera_pos;
+    let dist = select(length(offset.xz), length(offset), uniforms.underwater > 0.5);
+    let fog = smoothstep(uniforms.fog_start, uniforms.fog_end, dist);
+    color = mix(color, uniforms.fog_color, fog);
+
+    // Отражение непрозрачно: под острым углом дно не видно
+    let alpha = clamp(in.alpha + fresnel * (1.0 - in.alpha), 0.0, 1.0);
+    return vec4<f32>(color, alpha);
+}
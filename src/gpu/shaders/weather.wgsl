@@ -0,0 +1,116 @@
+// ============================================
+// Weather Shader - Дождь и снег
+// ============================================
+// Без вершинного буфера: 6 вершин на частицу, позиция из хеша
+// instance_index. Коробка AREA x HEIGHT x AREA вокруг камеры
+// заворачивается в мировых координатах, поэтому частицы не "едут"
+// вместе с камерой.
+
+struct WeatherUniforms {
+    view_proj: mat4x4<f32>,
+    camera_pos: vec4<f32>,   // xyz + time
+    params: vec4<f32>,       // intensity, kind (0 дождь, 1 снег), wind_x, wind_z
+    color: vec4<f32>,        // rgb + alpha
+}
+
+@group(0) @binding(0)
+var<uniform> uniforms: WeatherUniforms;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) fade: f32,
+}
+
+const AREA: f32 = 40.0;
+const HEIGHT: f32 = 30.0;
+
+fn hash(n: u32) -> f32 {
+    var x = n * 747796405u + 2891336453u;
+    x = ((x >> ((x >> 28u) + 4u)) ^ x) * 277803737u;
+    x = (x >> 22u) ^ x;
+    return f32(x) / 4294967295.0;
+}
+
+// Заворот координаты в коробку вокруг камеры (мировая привязка)
+fn wrap(seed: f32, offset: f32, camera: f32, size: f32) -> f32 {
+    return camera + (fract(seed + (offset - camera) / size) - 0.5) * size;
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex: u32, @builtin(instance_index) instance: u32) -> VertexOutput {
+    var out: VertexOutput;
+
+    let camera = uniforms.camera_pos.xyz;
+    let time = uniforms.camera_pos.w;
+    let is_snow = uniforms.params.y > 0.5;
+    let wind = uniforms.params.zw;
+
+    let h = vec3<f32>(hash(instance * 3u), hash(instance * 3u + 1u), hash(instance * 3u + 2u));
+
+    // Скорость падения: снег медленный и покачивается, дождь быстрый
+    var fall_speed = 14.0 + h.y * 4.0;
+    var sway = vec2<f32>(0.0, 0.0);
+    if (is_snow) {
+        fall_speed = 1.2 + h.y * 0.8;
+        let phase = time * (0.8 + h.x) + h.z * 6.2831;
+        sway = vec2<f32>(sin(phase), cos(phase * 0.7)) * 0.4;
+    }
+    let drift = wind * time * select(1.0, 0.6, is_snow);
+
+    var center: vec3<f32>;
+    center.x = wrap(h.x, drift.x + sway.x, camera.x, AREA);
+    center.z = wrap(h.z, drift.y + sway.y, camera.z, AREA);
+    center.y = wrap(h.y, -fall_speed * time, camera.y, HEIGHT);
+
+    // Квад: дождь - вытянутая вдоль скорости полоска, снег - хлопья
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(-1.0, -1.0), vec2<f32>(1.0, -1.0), vec2<f32>(1.0, 1.0),
+        vec2<f32>(-1.0, -1.0), vec2<f32>(1.0, 1.0), vec2<f32>(-1.0, 1.0),
+    );
+    let corner = corners[vertex];
+
+    let to_camera = camera - center;
+    let right = normalize(vec3<f32>(to_camera.z, 0.0, -to_camera.x) + vec3<f32>(0.0001, 0.0, 0.0));
+    var up: vec3<f32>;
+    var half_size: vec2<f32>;
+    if (is_snow) {
+        up = normalize(cross(right, to_camera));
+        half_size = vec2<f32>(0.05, 0.05);
+    } else {
+        // Полоска наклоняется по ветру
+        up = normalize(vec3<f32>(-wind.x, fall_speed, -wind.y));
+        half_size = vec2<f32>(0.012, 0.35);
+    }
+
+    let world = center + right * corner.x * half_size.x + up * corner.y * half_size.y;
+    out.clip_position = uniforms.view_proj * vec4<f32>(world, 1.0);
+    out.uv = corner;
+
+    // Гасим частицы у краёв коробки, чтобы не было видно заворота
+    let horizontal = length(to_camera.xz) / (AREA * 0.5);
+    let vertical = abs(to_camera.y) / (HEIGHT * 0.5);
+    out.fade = (1.0 - smoothstep(0.7, 1.0, horizontal)) * (1.0 - smoothstep(0.7, 1.0, vertical));
+
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    var alpha = uniforms.color.a * in.fade;
+    if (uniforms.params.y > 0.5) {
+        // Круглые хлопья
+        let d = length(in.uv);
+        if (d > 1.0) {
+            discard;
+        }
+        alpha *= 1.0 - d * d;
+    } else {
+        // Мягкие края полоски
+        alpha *= 1.0 - abs(in.uv.x);
+    }
+    if (alpha < 0.01) {
+        discard;
+    }
+    return vec4<f32>(uniforms.color.rgb, alpha);
+}
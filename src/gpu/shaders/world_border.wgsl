@@ -0,0 +1,81 @@
+// ============================================
+// World Border Shader - Стена границы мира
+// ============================================
+// Без вершинного буфера: 4 стены по 6 вершин. Каждая стена - кусок
+// SPAN x SPAN вокруг проекции камеры на неё (обрезан углами квадрата).
+// Бегущие диагональные полосы; стена проявляется при приближении.
+
+struct BorderUniforms {
+    view_proj: mat4x4<f32>,
+    camera_pos: vec4<f32>,   // xyz + time
+    params: vec4<f32>,       // radius, fade_start, fade_end, _
+    color: vec4<f32>,        // rgb + alpha
+}
+
+@group(0) @binding(0)
+var<uniform> uniforms: BorderUniforms;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_pos: vec3<f32>,
+    @location(1) along: f32,
+}
+
+const SPAN: f32 = 128.0;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex: u32) -> VertexOutput {
+    var out: VertexOutput;
+
+    let camera = uniforms.camera_pos.xyz;
+    let radius = uniforms.params.x;
+    let wall = vertex / 6u;
+
+    // Два треугольника квада
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0), vec2<f32>(1.0, 0.0), vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, 0.0), vec2<f32>(1.0, 1.0), vec2<f32>(0.0, 1.0),
+    );
+    let corner = corners[vertex % 6u];
+
+    // Стены 0, 1 - по X (+/-), 2, 3 - по Z; along - координата вдоль стены
+    let side = select(-1.0, 1.0, wall % 2u == 0u);
+    let camera_along = select(camera.x, camera.z, wall < 2u);
+    let along_min = clamp(camera_along - SPAN, -radius, radius);
+    let along_max = clamp(camera_along + SPAN, -radius, radius);
+    let along = mix(along_min, along_max, corner.x);
+    let y = camera.y + (corner.y - 0.5) * 2.0 * SPAN;
+
+    var world = vec3<f32>(along, y, side * radius);
+    if (wall < 2u) {
+        world = vec3<f32>(side * radius, y, along);
+    }
+
+    out.clip_position = uniforms.view_proj * vec4<f32>(world, 1.0);
+    out.world_pos = world;
+    out.along = along;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let time = uniforms.camera_pos.w;
+
+    // Полосы ползут по диагонали
+    let stripe = fract((in.along + in.world_pos.y) * 0.125 - time * 0.4);
+    if (stripe > 0.5) {
+        discard;
+    }
+
+    // Чем ближе игрок к стене, тем она заметнее
+    let distance = length(in.world_pos - uniforms.camera_pos.xyz);
+    let fade = 1.0 - smoothstep(uniforms.params.y, uniforms.params.z, distance);
+    // Мягкие края полос
+    let edge = smoothstep(0.0, 0.08, stripe) * smoothstep(0.5, 0.42, stripe);
+
+    let alpha = uniforms.color.a * fade * edge;
+    if (alpha < 0.01) {
+        discard;
+    }
+    return vec4<f32>(uniforms.color.rgb, alpha);
+}
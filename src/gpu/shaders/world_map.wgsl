@@ -0,0 +1,106 @@
+// ============================================
+// World Map Shader - Полноэкранная карта мира
+// ============================================
+// Квадрат на весь экран из 6 вершин без буфера. Пиксель переводится в
+// мировые x, z через центр обзора и масштаб (пикселей на блок) и читает
+// кольцевую текстуру разведанных чанков. Поверх - метки (ромбы своего
+// цвета, место смерти - крест) и стрелка игрока.
+
+const MAX_MARKERS: u32 = 32u;
+
+struct WorldMapUniforms {
+    view: vec4<f32>,    // центр x, z, пикселей на блок, yaw игрока
+    player: vec4<f32>,  // игрок x, z, размер текстуры, число меток
+    screen: vec4<f32>,  // ширина, высота экрана (пиксели), есть ли место смерти, -
+    death: vec4<f32>,   // место смерти x, z, -, -
+    markers: array<vec4<f32>, MAX_MARKERS>,  // x, z метки, -, -
+    marker_colors: array<vec4<f32>, MAX_MARKERS>,
+}
+
+@group(0) @binding(0)
+var<uniform> uniforms: WorldMapUniforms;
+@group(0) @binding(1)
+var colors: texture_2d<f32>;
+
+const BACKGROUND: vec4<f32> = vec4<f32>(0.04, 0.05, 0.07, 0.96);
+const PLAYER: vec4<f32> = vec4<f32>(1.0, 1.0, 1.0, 1.0);
+const OUTLINE: vec4<f32> = vec4<f32>(0.0, 0.0, 0.0, 1.0);
+const DEATH: vec4<f32> = vec4<f32>(0.9, 0.15, 0.15, 1.0);
+const MARKER_SIZE: f32 = 6.0;
+const ARROW_SCALE: f32 = 1.6;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0), vec2<f32>(1.0, 0.0), vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, 0.0), vec2<f32>(1.0, 1.0), vec2<f32>(0.0, 1.0),
+    );
+    let uv = corners[index];
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+    out.uv = uv;
+    return out;
+}
+
+/// Мировые x, z -> пиксели экрана
+fn to_screen(world: vec2<f32>) -> vec2<f32> {
+    return (world - uniforms.view.xy) * uniforms.view.z + uniforms.screen.xy * 0.5;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let pixel = in.uv * uniforms.screen.xy;
+
+    // Стрелка игрока: вперёд по yaw (x - cos, z - sin, как в Player)
+    let local = (pixel - to_screen(uniforms.player.xy)) / ARROW_SCALE;
+    let forward = vec2<f32>(cos(uniforms.view.w), sin(uniforms.view.w));
+    let right = vec2<f32>(-forward.y, forward.x);
+    let ahead = dot(local, forward);
+    let side = abs(dot(local, right));
+    if (ahead > -4.0 && ahead < 7.0 && side < (7.0 - ahead) * 0.45) {
+        return PLAYER;
+    }
+    if (ahead > -5.0 && ahead < 8.5 && side < (8.5 - ahead) * 0.5) {
+        return OUTLINE;
+    }
+
+    // Место смерти: крест по диагоналям с тёмной обводкой
+    if (uniforms.screen.z > 0.5) {
+        let offset = abs(pixel - to_screen(uniforms.death.xy));
+        let stroke = abs(offset.x - offset.y);
+        let reach = max(offset.x, offset.y);
+        if (reach < MARKER_SIZE && stroke < 2.0) {
+            return DEATH;
+        }
+        if (reach < MARKER_SIZE + 1.5 && stroke < 3.5) {
+            return OUTLINE;
+        }
+    }
+
+    // Метки: ромб своего цвета с тёмной обводкой
+    let count = min(u32(uniforms.player.w), MAX_MARKERS);
+    for (var i = 0u; i < count; i = i + 1u) {
+        let offset = abs(pixel - to_screen(uniforms.markers[i].xy));
+        let distance = offset.x + offset.y;
+        if (distance < MARKER_SIZE) {
+            return uniforms.marker_colors[i];
+        }
+        if (distance < MARKER_SIZE + 1.5) {
+            return OUTLINE;
+        }
+    }
+
+    let world = uniforms.view.xy + (pixel - uniforms.screen.xy * 0.5) / uniforms.view.z;
+    let texture_size = i32(uniforms.player.z);
+    let texel = ((vec2<i32>(floor(world)) % texture_size) + texture_size) % texture_size;
+    let color = textureLoad(colors, texel, 0);
+    if (color.a == 0.0) {
+        return BACKGROUND;
+    }
+    return vec4<f32>(color.rgb, 1.0);
+}
@@ -9,22 +9,22 @@ use std::collections::HashMap;
 use crate::gpu::blocks::{BlockType, AIR};
 use super::super::octree::CompactOctree;
 
-/// Упакованный ключ блока внутри чанка (20 бит: 4+8+4 для x,y,z)
+/// Упакованный ключ блока внутри чанка (24 бита: 4+4+16 для x,z,y)
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct PackedBlockKey(u32);
 
 impl PackedBlockKey {
     #[inline]
-    pub fn new(x: u8, y: u8, z: u8) -> Self {
+    pub fn new(x: u8, y: u16, z: u8) -> Self {
         debug_assert!(x < 16 && z < 16);
         Self(((y as u32) << 8) | ((z as u32) << 4) | (x as u32))
     }
 
     #[inline]
-    pub fn unpack(self) -> (u8, u8, u8) {
+    pub fn unpack(self) -> (u8, u16, u8) {
         let x = (self.0 & 0xF) as u8;
         let z = ((self.0 >> 4) & 0xF) as u8;
-        let y = ((self.0 >> 8) & 0xFF) as u8;
+        let y = ((self.0 >> 8) & 0xFFFF) as u16;
         (x, y, z)
     }
 
@@ -32,7 +32,7 @@ impl PackedBlockKey {
     pub fn x(self) -> u8 { (self.0 & 0xF) as u8 }
     
     #[inline]
-    pub fn y(self) -> u8 { ((self.0 >> 8) & 0xFF) as u8 }
+    pub fn y(self) -> u16 { ((self.0 >> 8) & 0xFFFF) as u16 }
     
     #[inline]
     pub fn z(self) -> u8 { ((self.0 >> 4) & 0xF) as u8 }
@@ -41,6 +41,8 @@ impl PackedBlockKey {
 /// Разреженное хранилище субвокселей для чанка
 /// Память: O(N) где N = количество блоков с субвокселями
 /// Типичный чанк: 10-100 блоков = 1-10 КБ вместо 3.5 МБ
+/// (Clone - снимок для фонового мешинга)
+#[derive(Clone)]
 pub struct SparseChunkStorage {
     /// HashMap вместо плоского массива
     blocks: HashMap<PackedBlockKey, CompactOctree>,
@@ -48,9 +50,10 @@ pub struct SparseChunkStorage {
     version: u64,
     /// Флаг грязности
     dirty: bool,
-    /// Кэш min/max Y для быстрого доступа
-    min_y: u8,
-    max_y: u8,
+    /// Габариты занятых блоков (локальные x, y, z) - для raycast и
+    /// диапазона Y мешинга
+    min: [u16; 3],
+    max: [u16; 3],
 }
 
 impl SparseChunkStorage {
@@ -59,8 +62,8 @@ impl SparseChunkStorage {
             blocks: HashMap::with_capacity(16), // Начинаем с малого
             version: 0,
             dirty: false,
-            min_y: 255,
-            max_y: 0,
+            min: [u16::MAX; 3],
+            max: [0; 3],
         }
     }
 
@@ -90,14 +93,20 @@ impl SparseChunkStorage {
     }
 
     #[inline]
-    pub fn y_range(&self) -> (u8, u8) {
-        (self.min_y, self.max_y)
+    pub fn y_range(&self) -> (u16, u16) {
+        (self.min[1], self.max[1])
+    }
+
+    /// Габариты занятых блоков: (min, max) локальные x, y, z включительно
+    #[inline]
+    pub fn block_bounds(&self) -> Option<([u16; 3], [u16; 3])> {
+        (!self.blocks.is_empty()).then_some((self.min, self.max))
     }
 
     /// Установить субвоксель
     pub fn set(
         &mut self,
-        block_x: u8, block_y: u8, block_z: u8,
+        block_x: u8, block_y: u16, block_z: u8,
         sub_x: u8, sub_y: u8, sub_z: u8,
         depth: u8,
         block_type: BlockType,
@@ -110,7 +119,7 @@ impl SparseChunkStorage {
                 octree.remove(sub_x, sub_y, sub_z, depth);
                 if octree.is_empty() {
                     self.blocks.remove(&key);
-                    self.update_y_bounds();
+                    self.update_bounds();
                 }
             }
         } else {
@@ -118,9 +127,11 @@ impl SparseChunkStorage {
             let octree = self.blocks.entry(key).or_insert_with(CompactOctree::new);
             octree.set(sub_x, sub_y, sub_z, depth, block_type);
             
-            // Обновляем Y bounds
-            self.min_y = self.min_y.min(block_y);
-            self.max_y = self.max_y.max(block_y);
+            // Обновляем габариты
+            for (i, value) in [block_x as u16, block_y, block_z as u16].into_iter().enumerate() {
+                self.min[i] = self.min[i].min(value);
+                self.max[i] = self.max[i].max(value);
+            }
         }
 
         self.dirty = true;
@@ -131,7 +142,7 @@ impl SparseChunkStorage {
     #[inline]
     pub fn get(
         &self,
-        block_x: u8, block_y: u8, block_z: u8,
+        block_x: u8, block_y: u16, block_z: u8,
         sub_x: u8, sub_y: u8, sub_z: u8,
         depth: u8,
     ) -> Option<BlockType> {
@@ -141,7 +152,7 @@ impl SparseChunkStorage {
 
     /// Получить октодерево блока
     #[inline]
-    pub fn get_block(&self, block_x: u8, block_y: u8, block_z: u8) -> Option<&CompactOctree> {
+    pub fn get_block(&self, block_x: u8, block_y: u16, block_z: u8) -> Option<&CompactOctree> {
         let key = PackedBlockKey::new(block_x, block_y, block_z);
         self.blocks.get(&key)
     }
@@ -154,24 +165,20 @@ impl SparseChunkStorage {
 
     /// Проверка solid в точке (для culling)
     #[inline]
-    pub fn is_solid_at(&self, block_x: u8, block_y: u8, block_z: u8, sub_x: u8, sub_y: u8, sub_z: u8, depth: u8) -> bool {
+    pub fn is_solid_at(&self, block_x: u8, block_y: u16, block_z: u8, sub_x: u8, sub_y: u8, sub_z: u8, depth: u8) -> bool {
         self.get(block_x, block_y, block_z, sub_x, sub_y, sub_z, depth).is_some()
     }
 
     /// Обновить Y bounds после удаления
-    fn update_y_bounds(&mut self) {
-        if self.blocks.is_empty() {
-            self.min_y = 255;
-            self.max_y = 0;
-            return;
-        }
-
-        self.min_y = 255;
-        self.max_y = 0;
+    fn update_bounds(&mut self) {
+        self.min = [u16::MAX; 3];
+        self.max = [0; 3];
         for key in self.blocks.keys() {
-            let y = key.y();
-            self.min_y = self.min_y.min(y);
-            self.max_y = self.max_y.max(y);
+            let (x, y, z) = key.unpack();
+            for (i, value) in [x as u16, y, z as u16].into_iter().enumerate() {
+                self.min[i] = self.min[i].min(value);
+                self.max[i] = self.max[i].max(value);
+            }
         }
     }
 
@@ -128,6 +128,8 @@ impl ChunkSubVoxelStorage {
             1 => 0,
             2 => 1,
             4 => 2,
+            8 => 3,
+            16 => 4,
             _ => return,
         };
 
@@ -171,6 +173,8 @@ impl ChunkSubVoxelStorage {
             1 => 0,
             2 => 1,
             4 => 2,
+            8 => 3,
+            16 => 4,
             _ => return None,
         };
 
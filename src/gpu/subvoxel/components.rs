@@ -5,13 +5,21 @@
 // Использует:
 // - SparseChunkStorage вместо ChunkSubVoxelStorage (O(N) память)
 // - CompactOctree вместо LinearOctree (4 байта на узел)
-
-use std::collections::HashMap;
-use crate::gpu::blocks::{BlockType, AIR};
-use super::chunk::{SubVoxelChunkKey, SparseChunkStorage};
+//
+// Субвоксели разных уровней в одном блоке - одно октодерево: мелкая
+// клетка внутри крупного листа дробит его, восемь одинаковых соседей
+// схлопываются обратно. Поэтому наружу (сохранение, схематики) отдаются
+// листья дерева, а их уровень - глубина листа.
+
+use std::collections::{HashMap, HashSet};
+use serde::{Serialize, Deserialize};
+use crate::gpu::blocks::{BlockType, AIR, TORCH};
+use crate::gpu::terrain::MIN_HEIGHT;
+use crate::gpu::terrain::voxel::constants::WORLD_HEIGHT;
+use super::chunk::{SubVoxelChunkKey, SparseChunkStorage, PackedBlockKey};
 
 /// Уровень детализации субвокселя
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum SubVoxelLevel {
     /// Полный блок 1x1x1
     Full = 0,
@@ -19,60 +27,106 @@ pub enum SubVoxelLevel {
     Half = 1,
     /// Четвертинный блок 1/4 (64 в одном полном)
     Quarter = 2,
+    /// Восьмушка 1/8 (512 в одном полном) - для мелкой детализации
+    Eighth = 3,
+    /// Шестнадцатая 1/16 (4096 в одном полном)
+    Sixteenth = 4,
 }
 
 impl SubVoxelLevel {
+    /// Размер блока на этом уровне
     #[inline]
     pub fn size(&self) -> f32 {
         match self {
             SubVoxelLevel::Full => 1.0,
             SubVoxelLevel::Half => 0.5,
             SubVoxelLevel::Quarter => 0.25,
+            SubVoxelLevel::Eighth => 0.125,
+            SubVoxelLevel::Sixteenth => 0.0625,
         }
     }
 
+    /// Количество делений на ось
     #[inline]
     pub fn divisions(&self) -> u8 {
         match self {
             SubVoxelLevel::Full => 1,
             SubVoxelLevel::Half => 2,
             SubVoxelLevel::Quarter => 4,
+            SubVoxelLevel::Eighth => 8,
+            SubVoxelLevel::Sixteenth => 16,
         }
     }
 
+    /// Глубина в октодереве
     #[inline]
     pub fn depth(&self) -> u8 {
         match self {
             SubVoxelLevel::Full => 0,
             SubVoxelLevel::Half => 1,
             SubVoxelLevel::Quarter => 2,
+            SubVoxelLevel::Eighth => 3,
+            SubVoxelLevel::Sixteenth => 4,
         }
     }
 
+    /// Уровень по глубине октодерева
+    #[inline]
+    pub fn from_depth(depth: u8) -> Option<Self> {
+        match depth {
+            0 => Some(SubVoxelLevel::Full),
+            1 => Some(SubVoxelLevel::Half),
+            2 => Some(SubVoxelLevel::Quarter),
+            3 => Some(SubVoxelLevel::Eighth),
+            4 => Some(SubVoxelLevel::Sixteenth),
+            _ => None,
+        }
+    }
+
+    /// Следующий уровень (меньше)
     pub fn next(&self) -> Self {
         match self {
             SubVoxelLevel::Full => SubVoxelLevel::Half,
             SubVoxelLevel::Half => SubVoxelLevel::Quarter,
-            SubVoxelLevel::Quarter => SubVoxelLevel::Full,
+            SubVoxelLevel::Quarter => SubVoxelLevel::Eighth,
+            SubVoxelLevel::Eighth => SubVoxelLevel::Sixteenth,
+            SubVoxelLevel::Sixteenth => SubVoxelLevel::Full, // Цикл обратно
+        }
+    }
+
+    /// Название уровня
+    pub fn name(&self) -> &'static str {
+        match self {
+            SubVoxelLevel::Full => "1x1x1",
+            SubVoxelLevel::Half => "1/2",
+            SubVoxelLevel::Quarter => "1/4",
+            SubVoxelLevel::Eighth => "1/8",
+            SubVoxelLevel::Sixteenth => "1/16",
         }
     }
 }
 
 impl Default for SubVoxelLevel {
     fn default() -> Self {
-        SubVoxelLevel::Quarter
+        SubVoxelLevel::Full
     }
 }
 
 /// Позиция субвокселя в мире
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// Для Half: sub_x/y/z = 0 или 1
+/// Для Quarter: sub_x/y/z = 0, 1, 2 или 3
+/// Для Eighth / Sixteenth: 0..8 / 0..16
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct SubVoxelPos {
+    /// Позиция базового блока
     pub block_x: i32,
     pub block_y: i32,
     pub block_z: i32,
+    /// Позиция внутри блока (зависит от уровня)
     pub sub_x: u8,
     pub sub_y: u8,
     pub sub_z: u8,
+    /// Уровень детализации
     pub level: SubVoxelLevel,
 }
 
@@ -86,6 +140,21 @@ impl SubVoxelPos {
         Self { block_x, block_y, block_z, sub_x, sub_y, sub_z, level }
     }
 
+    /// Создать позицию для полного блока
+    #[inline]
+    pub fn full(x: i32, y: i32, z: i32) -> Self {
+        Self::new(x, y, z, 0, 0, 0, SubVoxelLevel::Full)
+    }
+
+    /// Мировые координаты центра субвокселя
+    #[inline]
+    pub fn world_center(&self) -> [f32; 3] {
+        let half = self.level.size() / 2.0;
+        let [x, y, z] = self.world_min();
+        [x + half, y + half, z + half]
+    }
+
+    /// Мировые координаты минимального угла субвокселя
     #[inline]
     pub fn world_min(&self) -> [f32; 3] {
         let size = self.level.size();
@@ -101,14 +170,34 @@ impl SubVoxelPos {
         SubVoxelChunkKey::from_block_pos(self.block_x, self.block_z)
     }
 
+    /// Блок внутри чанка. Y хранится от MIN_HEIGHT, None - вне высоты
+    /// мира
     #[inline]
-    pub fn local_block(&self) -> (u8, u8, u8) {
+    pub fn local_block(&self) -> Option<(u8, u16, u8)> {
+        if !(MIN_HEIGHT..WORLD_HEIGHT).contains(&self.block_y) {
+            return None;
+        }
         let local_x = self.block_x.rem_euclid(16) as u8;
         let local_z = self.block_z.rem_euclid(16) as u8;
-        (local_x, self.block_y as u8, local_z)
+        let local_y = (self.block_y - MIN_HEIGHT) as u16;
+        Some((local_x, local_y, local_z))
     }
 }
 
+/// Субвоксель с типом блока
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SubVoxel {
+    pub pos: SubVoxelPos,
+    pub block_type: BlockType,
+}
+
+/// Мировой блок из ключа чанка и ключа блока в нём
+#[inline]
+pub fn block_world_pos(chunk: SubVoxelChunkKey, key: PackedBlockKey) -> [i32; 3] {
+    let (x, y, z) = key.unpack();
+    [chunk.x * 16 + x as i32, y as i32 + MIN_HEIGHT, chunk.z * 16 + z as i32]
+}
+
 // ============================================
 // SubVoxelWorld - ОПТИМИЗИРОВАННАЯ ВЕРСИЯ
 // ============================================
@@ -118,8 +207,8 @@ impl SubVoxelPos {
 pub struct SubVoxelWorld {
     /// Разреженные хранилища по чанкам
     chunks: HashMap<SubVoxelChunkKey, SparseChunkStorage>,
-    /// Грязные чанки
-    dirty_chunks: Vec<SubVoxelChunkKey>,
+    /// Грязные чанки (ждут мешинга)
+    dirty_chunks: HashSet<SubVoxelChunkKey>,
     /// Глобальная версия
     version: u64,
 }
@@ -128,16 +217,41 @@ impl SubVoxelWorld {
     pub fn new() -> Self {
         Self {
             chunks: HashMap::new(),
-            dirty_chunks: Vec::new(),
+            dirty_chunks: HashSet::new(),
             version: 0,
         }
     }
 
-    /// Установить субвоксель
+    /// Установить субвоксель (AIR - удалить)
     pub fn set(&mut self, pos: SubVoxelPos, block_type: BlockType) {
+        if self.write(pos, block_type) {
+            self.version += 1;
+        }
+    }
+
+    /// Записать много субвокселей разом (AIR - удалить). Версия растёт
+    /// один раз. Возвращает число изменённых клеток
+    pub fn set_many(&mut self, cells: impl IntoIterator<Item = (SubVoxelPos, BlockType)>) -> usize {
+        let mut changed = 0;
+        for (pos, block_type) in cells {
+            if self.get(&pos) != (block_type != AIR).then_some(block_type) && self.write(pos, block_type) {
+                changed += 1;
+            }
+        }
+        if changed > 0 {
+            self.version += 1;
+        }
+        changed
+    }
+
+    /// Запись в октодерево чанка без версии. false - вне высоты мира
+    fn write(&mut self, pos: SubVoxelPos, block_type: BlockType) -> bool {
+        let Some((local_x, local_y, local_z)) = pos.local_block() else { return false };
         let chunk_key = pos.chunk_key();
-        let (local_x, local_y, local_z) = pos.local_block();
-        
+        if block_type == AIR && !self.chunks.contains_key(&chunk_key) {
+            return false;
+        }
+
         let chunk = self.chunks.entry(chunk_key).or_insert_with(SparseChunkStorage::new);
         chunk.set(
             local_x, local_y, local_z,
@@ -145,71 +259,94 @@ impl SubVoxelWorld {
             pos.level.depth(),
             block_type,
         );
-
-        if !self.dirty_chunks.contains(&chunk_key) {
-            self.dirty_chunks.push(chunk_key);
-        }
-
         if chunk.is_empty() {
             self.chunks.remove(&chunk_key);
         }
-
-        self.version += 1;
+        self.dirty_chunks.insert(chunk_key);
+        true
     }
 
-    /// Получить субвоксель
+    /// Получить субвоксель (клетка внутри более крупного листа читается
+    /// как его тип)
     #[inline]
     pub fn get(&self, pos: &SubVoxelPos) -> Option<BlockType> {
-        let chunk_key = pos.chunk_key();
-        let (local_x, local_y, local_z) = pos.local_block();
-        
-        self.chunks.get(&chunk_key)?.get(
+        let (local_x, local_y, local_z) = pos.local_block()?;
+        self.chunks.get(&pos.chunk_key())?.get(
             local_x, local_y, local_z,
             pos.sub_x, pos.sub_y, pos.sub_z,
             pos.level.depth(),
         )
     }
 
-    /// Удалить субвоксель
-    #[inline]
-    pub fn remove(&mut self, pos: &SubVoxelPos) {
+    /// Удалить субвоксель, вернуть его тип
+    pub fn remove(&mut self, pos: &SubVoxelPos) -> Option<BlockType> {
+        let removed = self.get(pos)?;
         self.set(*pos, AIR);
+        Some(removed)
     }
 
     /// Получить и очистить грязные чанки
     #[inline]
     pub fn take_dirty_chunks(&mut self) -> Vec<SubVoxelChunkKey> {
-        std::mem::take(&mut self.dirty_chunks)
+        self.dirty_chunks.drain().collect()
     }
 
-    /// Получить хранилище чанка
+    /// Хранилища всех чанков (для мешинга)
     #[inline]
-    pub fn get_chunk(&self, key: &SubVoxelChunkKey) -> Option<&SparseChunkStorage> {
-        self.chunks.get(key)
+    pub fn chunks(&self) -> &HashMap<SubVoxelChunkKey, SparseChunkStorage> {
+        &self.chunks
     }
 
-    /// Итератор по чанкам
     #[inline]
-    pub fn iter_chunks(&self) -> impl Iterator<Item = (&SubVoxelChunkKey, &SparseChunkStorage)> {
-        self.chunks.iter()
+    pub fn version(&self) -> u64 {
+        self.version
     }
 
-    #[inline]
-    pub fn chunk_count(&self) -> usize {
-        self.chunks.len()
+    /// Блоков, в которых есть субвоксели
+    pub fn block_count(&self) -> usize {
+        self.chunks.values().map(|c| c.block_count()).sum()
     }
 
-    #[inline]
-    pub fn version(&self) -> u64 {
-        self.version
+    /// Листья всех октодеревьев (для сохранения)
+    pub fn get_all(&self) -> Vec<SubVoxel> {
+        let mut result = Vec::new();
+        for (&chunk_key, chunk) in &self.chunks {
+            for (key, _) in chunk.iter_blocks() {
+                push_leaves(chunk, chunk_key, key, &mut result);
+            }
+        }
+        result
+    }
+
+    /// Загрузить субвоксели (всё прежнее содержимое заменяется)
+    pub fn load(&mut self, subvoxels: Vec<SubVoxel>) {
+        self.dirty_chunks.extend(self.chunks.keys().copied());
+        self.chunks.clear();
+        for sv in subvoxels {
+            self.write(sv.pos, sv.block_type);
+        }
+        self.version += 1;
     }
 
-    /// Общее использование памяти (байт)
-    pub fn memory_usage(&self) -> usize {
-        self.chunks.values().map(|c| c.memory_usage()).sum()
+    /// Листья в блоках области min..=max
+    pub fn get_in_region(&self, min_x: i32, min_y: i32, min_z: i32, max_x: i32, max_y: i32, max_z: i32) -> Vec<SubVoxel> {
+        let mut result = Vec::new();
+        for cx in min_x.div_euclid(16)..=max_x.div_euclid(16) {
+            for cz in min_z.div_euclid(16)..=max_z.div_euclid(16) {
+                let chunk_key = SubVoxelChunkKey::new(cx, cz);
+                let Some(chunk) = self.chunks.get(&chunk_key) else { continue };
+                for (key, _) in chunk.iter_blocks() {
+                    let [x, y, z] = block_world_pos(chunk_key, key);
+                    if (min_x..=max_x).contains(&x) && (min_y..=max_y).contains(&y) && (min_z..=max_z).contains(&z) {
+                        push_leaves(chunk, chunk_key, key, &mut result);
+                    }
+                }
+            }
+        }
+        result
     }
 
-    /// Проверка коллизии AABB
+    /// Проверка коллизии AABB (сквозь факелы проходят)
     pub fn check_aabb_collision(
         &self,
         min_x: f32, min_y: f32, min_z: f32,
@@ -224,7 +361,7 @@ impl SubVoxelWorld {
             for cz in min_chunk_z..=max_chunk_z {
                 let key = SubVoxelChunkKey::new(cx, cz);
                 if let Some(chunk) = self.chunks.get(&key) {
-                    if check_chunk_aabb_collision(chunk, cx, cz, min_x, min_y, min_z, max_x, max_y, max_z) {
+                    if check_chunk_aabb_collision(chunk, key, min_x, min_y, min_z, max_x, max_y, max_z) {
                         return true;
                     }
                 }
@@ -240,21 +377,34 @@ impl Default for SubVoxelWorld {
     }
 }
 
+/// Листья одного блока как субвоксели своего уровня
+fn push_leaves(chunk: &SparseChunkStorage, chunk_key: SubVoxelChunkKey, key: PackedBlockKey, out: &mut Vec<SubVoxel>) {
+    let (bx, by, bz) = key.unpack();
+    let Some(octree) = chunk.get_block(bx, by, bz) else { return };
+    let [block_x, block_y, block_z] = block_world_pos(chunk_key, key);
+    for (x, y, z, size, block_type) in octree.iter_solid() {
+        let divisions = (1.0 / size).round();
+        let Some(level) = SubVoxelLevel::from_depth(divisions.log2().round() as u8) else { continue };
+        let sub = |v: f32| (v * divisions).round() as u8;
+        out.push(SubVoxel {
+            pos: SubVoxelPos::new(block_x, block_y, block_z, sub(x), sub(y), sub(z), level),
+            block_type,
+        });
+    }
+}
+
 /// Проверка коллизии AABB с чанком
 fn check_chunk_aabb_collision(
     chunk: &SparseChunkStorage,
-    chunk_x: i32, chunk_z: i32,
+    chunk_key: SubVoxelChunkKey,
     min_x: f32, min_y: f32, min_z: f32,
     max_x: f32, max_y: f32, max_z: f32,
 ) -> bool {
-    let base_x = chunk_x * 16;
-    let base_z = chunk_z * 16;
-
     for (key, octree) in chunk.iter_blocks() {
-        let (bx, by, bz) = key.unpack();
-        let block_x = (base_x + bx as i32) as f32;
+        let [bx, by, bz] = block_world_pos(chunk_key, key);
+        let block_x = bx as f32;
         let block_y = by as f32;
-        let block_z = (base_z + bz as i32) as f32;
+        let block_z = bz as f32;
 
         // Быстрая проверка AABB блока
         if max_x <= block_x || min_x >= block_x + 1.0 ||
@@ -264,7 +414,10 @@ fn check_chunk_aabb_collision(
         }
 
         // Детальная проверка через октодерево
-        for (sx, sy, sz, size, _bt) in octree.iter_solid() {
+        for (sx, sy, sz, size, block_type) in octree.iter_solid() {
+            if block_type == TORCH {
+                continue;
+            }
             let sv_min_x = block_x + sx;
             let sv_min_y = block_y + sy;
             let sv_min_z = block_z + sz;
@@ -281,3 +434,67 @@ fn check_chunk_aabb_collision(
     }
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::blocks::{DIRT, STONE};
+
+    #[test]
+    fn leaves_roundtrip_below_zero_height() {
+        let mut world = SubVoxelWorld::new();
+        let half = SubVoxelPos::new(-3, -20, 17, 1, 0, 1, SubVoxelLevel::Half);
+        let sixteenth = SubVoxelPos::new(-3, -20, 17, 15, 15, 0, SubVoxelLevel::Sixteenth);
+        world.set(half, STONE);
+        world.set(sixteenth, DIRT);
+
+        let mut all = world.get_all();
+        all.sort_by_key(|sv| sv.pos.level as u8);
+        assert_eq!(all, vec![
+            SubVoxel { pos: half, block_type: STONE },
+            SubVoxel { pos: sixteenth, block_type: DIRT },
+        ]);
+        assert_eq!(world.get_in_region(-3, -20, 17, -3, -20, 17).len(), 2);
+        assert!(world.get_in_region(-2, -20, 17, 0, -20, 17).is_empty());
+    }
+
+    #[test]
+    fn subvoxels_span_whole_world_height() {
+        let mut world = SubVoxelWorld::new();
+        let low = SubVoxelPos::new(1, MIN_HEIGHT, 1, 0, 0, 0, SubVoxelLevel::Half);
+        let high = SubVoxelPos::new(1, WORLD_HEIGHT - 1, 1, 1, 1, 1, SubVoxelLevel::Half);
+        world.set(low, STONE);
+        world.set(high, DIRT);
+        world.set(SubVoxelPos::new(1, WORLD_HEIGHT, 1, 0, 0, 0, SubVoxelLevel::Half), STONE);
+
+        assert_eq!(world.get(&low), Some(STONE));
+        assert_eq!(world.get(&high), Some(DIRT));
+        assert_eq!(world.get_all().len(), 2);
+    }
+
+    #[test]
+    fn coarse_leaf_covers_finer_cells() {
+        let mut world = SubVoxelWorld::new();
+        world.set(SubVoxelPos::new(4, 70, 4, 0, 0, 0, SubVoxelLevel::Half), STONE);
+        let quarter = SubVoxelPos::new(4, 70, 4, 1, 1, 0, SubVoxelLevel::Quarter);
+        assert_eq!(world.remove(&quarter), Some(STONE));
+        assert_eq!(world.get(&quarter), None);
+        // Остальные семь четвертинок половинки на месте
+        assert_eq!(world.get_all().len(), 7);
+    }
+
+    #[test]
+    fn dirty_chunks_follow_edits() {
+        let mut world = SubVoxelWorld::new();
+        let pos = SubVoxelPos::new(-1, 64, 40, 0, 0, 0, SubVoxelLevel::Quarter);
+        world.set(pos, STONE);
+        assert_eq!(world.take_dirty_chunks(), vec![SubVoxelChunkKey::new(-1, 2)]);
+        assert_eq!(world.set_many([(pos, STONE)]), 0);
+        assert!(world.take_dirty_chunks().is_empty());
+
+        world.remove(&pos);
+        assert_eq!(world.take_dirty_chunks(), vec![SubVoxelChunkKey::new(-1, 2)]);
+        assert!(world.chunks().is_empty());
+        assert!(!world.check_aabb_collision(-1.0, 64.0, 40.0, 0.0, 65.0, 41.0));
+    }
+}
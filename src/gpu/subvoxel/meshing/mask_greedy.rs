@@ -9,7 +9,7 @@
 // Память: O(W*H) для маски одного слоя
 
 use crate::gpu::blocks::{BlockType, get_face_colors, STONE};
-use super::packed_vertex::{PackedVertex, NormalIndex, pack_color};
+use super::packed_vertex::{PackedVertex, NormalIndex, pack_color, POSITION_UNITS};
 
 /// Размер маски (64x64 для субвокселей в чанке)
 pub const MASK_SIZE: usize = 64;
@@ -21,6 +21,8 @@ pub struct MaskGreedyContext {
     mask: [u64; MASK_SIZE],
     /// Типы блоков для маски
     types: [[u8; MASK_SIZE]; MASK_SIZE],
+    /// Единиц PackedVertex на клетку сетки (зависит от уровня)
+    unit: f32,
     /// Выходные буферы
     pub vertices: Vec<PackedVertex>,
     pub indices: Vec<u32>,
@@ -31,6 +33,7 @@ impl MaskGreedyContext {
         Self {
             mask: [0; MASK_SIZE],
             types: [[0; MASK_SIZE]; MASK_SIZE],
+            unit: POSITION_UNITS / 4.0,
             vertices: Vec::with_capacity(4096),
             indices: Vec::with_capacity(8192),
         }
@@ -59,6 +62,11 @@ pub trait VoxelAccess {
     
     /// Границы данных
     fn bounds(&self) -> (i32, i32, i32, i32, i32, i32); // min_x, min_y, min_z, max_x, max_y, max_z
+
+    /// Клеток сетки на блок (по умолчанию сетка 1/4)
+    fn divisions(&self) -> u8 {
+        4
+    }
 }
 
 /// Greedy meshing через битовые маски
@@ -68,6 +76,7 @@ pub fn greedy_mesh_masked<V: VoxelAccess>(
     chunk_offset: [f32; 3],
 ) {
     ctx.clear();
+    ctx.unit = POSITION_UNITS / voxels.divisions() as f32;
     
     let (min_x, min_y, min_z, max_x, max_y, max_z) = voxels.bounds();
     
@@ -96,12 +105,13 @@ fn mesh_axis<V: VoxelAccess>(
         return; // Слишком большой слой
     }
 
-    // Проходим по слоям вдоль оси
+    // Проходим по слоям вдоль оси. Клетки за границами не мешатся:
+    // VoxelAccess может отдавать там соседей (рамку тайла)
     for d in axis_min..=axis_max + 1 {
         ctx.clear_mask();
         
         // Заполняем маску для положительного направления
-        for v in 0..v_size {
+        for v in (0..v_size).filter(|_| d > axis_min) {
             for u in 0..u_size {
                 let (x, y, z, nx, ny, nz) = match axis {
                     Axis::X => (d - 1, u_min + u as i32, v_min + v as i32, d, u_min + u as i32, v_min + v as i32),
@@ -128,11 +138,11 @@ fn mesh_axis<V: VoxelAccess>(
             Axis::Y => NormalIndex::PosY,
             Axis::Z => NormalIndex::PosZ,
         };
-        greedy_merge_layer(ctx, d as f32, axis, normal, chunk_offset, u_size, v_size, true);
+        greedy_merge_layer(ctx, d as f32, axis, normal, chunk_offset, (u_min, v_min), u_size, v_size, true);
         
         // Заполняем маску для отрицательного направления
         ctx.clear_mask();
-        for v in 0..v_size {
+        for v in (0..v_size).filter(|_| d <= axis_max) {
             for u in 0..u_size {
                 let (x, y, z, nx, ny, nz) = match axis {
                     Axis::X => (d, u_min + u as i32, v_min + v as i32, d - 1, u_min + u as i32, v_min + v as i32),
@@ -157,7 +167,7 @@ fn mesh_axis<V: VoxelAccess>(
             Axis::Y => NormalIndex::NegY,
             Axis::Z => NormalIndex::NegZ,
         };
-        greedy_merge_layer(ctx, d as f32, axis, normal, chunk_offset, u_size, v_size, false);
+        greedy_merge_layer(ctx, d as f32, axis, normal, chunk_offset, (u_min, v_min), u_size, v_size, false);
     }
 }
 
@@ -168,6 +178,7 @@ fn greedy_merge_layer(
     axis: Axis,
     normal: NormalIndex,
     chunk_offset: [f32; 3],
+    origin: (i32, i32),
     u_size: usize,
     v_size: usize,
     positive: bool,
@@ -212,10 +223,10 @@ fn greedy_merge_layer(
                 }
             }
             
-            // Генерируем квад
+            // Генерируем квад (индексы маски -> координаты от начала границ)
             emit_quad_packed(
                 ctx,
-                d, u as f32, v as f32,
+                d, (origin.0 + u as i32) as f32, (origin.1 + v as i32) as f32,
                 width as f32, height as f32,
                 axis, normal, chunk_offset,
                 block_type, positive,
@@ -250,25 +261,27 @@ fn emit_quad_packed(
     };
     
     // Конвертируем координаты в позиции вершин
+    let unit = ctx.unit;
     let (p0, p1, p2, p3) = match axis {
         Axis::X => {
-            let x = (d + offset[0]) * 4.0;
-            let y0 = (u + offset[1]) * 4.0;
-            let y1 = (u + w + offset[1]) * 4.0;
-            let z0 = (v + offset[2]) * 4.0;
-            let z1 = (v + h + offset[2]) * 4.0;
+            let x = (d + offset[0]) * unit;
+            let y0 = (u + offset[1]) * unit;
+            let y1 = (u + w + offset[1]) * unit;
+            let z0 = (v + offset[2]) * unit;
+            let z1 = (v + h + offset[2]) * unit;
+            // u = y, v = z: y x z = +X, поэтому обход сначала по u
             if positive {
-                ([x, y0, z0], [x, y0, z1], [x, y1, z1], [x, y1, z0])
-            } else {
                 ([x, y0, z0], [x, y1, z0], [x, y1, z1], [x, y0, z1])
+            } else {
+                ([x, y0, z0], [x, y0, z1], [x, y1, z1], [x, y1, z0])
             }
         }
         Axis::Y => {
-            let y = (d + offset[1]) * 4.0;
-            let x0 = (u + offset[0]) * 4.0;
-            let x1 = (u + w + offset[0]) * 4.0;
-            let z0 = (v + offset[2]) * 4.0;
-            let z1 = (v + h + offset[2]) * 4.0;
+            let y = (d + offset[1]) * unit;
+            let x0 = (u + offset[0]) * unit;
+            let x1 = (u + w + offset[0]) * unit;
+            let z0 = (v + offset[2]) * unit;
+            let z1 = (v + h + offset[2]) * unit;
             if positive {
                 ([x0, y, z0], [x0, y, z1], [x1, y, z1], [x1, y, z0])
             } else {
@@ -276,11 +289,11 @@ fn emit_quad_packed(
             }
         }
         Axis::Z => {
-            let z = (d + offset[2]) * 4.0;
-            let x0 = (u + offset[0]) * 4.0;
-            let x1 = (u + w + offset[0]) * 4.0;
-            let y0 = (v + offset[1]) * 4.0;
-            let y1 = (v + h + offset[1]) * 4.0;
+            let z = (d + offset[2]) * unit;
+            let x0 = (u + offset[0]) * unit;
+            let x1 = (u + w + offset[0]) * unit;
+            let y0 = (v + offset[1]) * unit;
+            let y1 = (v + h + offset[1]) * unit;
             if positive {
                 ([x0, y0, z], [x1, y0, z], [x1, y1, z], [x0, y1, z])
             } else {
@@ -290,29 +303,137 @@ fn emit_quad_packed(
     };
     
     // Добавляем вершины
-    let to_u8 = |v: f32| v.clamp(0.0, 255.0) as u8;
+    let to_u16 = |v: f32| v.round().clamp(0.0, u16::MAX as f32) as u16;
     
     ctx.vertices.push(PackedVertex {
-        pos_x: to_u8(p0[0]), pos_y: to_u8(p0[1]), pos_z: to_u8(p0[2]),
-        normal_flags: normal as u8,
+        pos_x: to_u16(p0[0]), pos_y: to_u16(p0[1]), pos_z: to_u16(p0[2]),
+        normal_flags: normal as u16,
         color,
     });
     ctx.vertices.push(PackedVertex {
-        pos_x: to_u8(p1[0]), pos_y: to_u8(p1[1]), pos_z: to_u8(p1[2]),
-        normal_flags: normal as u8,
+        pos_x: to_u16(p1[0]), pos_y: to_u16(p1[1]), pos_z: to_u16(p1[2]),
+        normal_flags: normal as u16,
         color,
     });
     ctx.vertices.push(PackedVertex {
-        pos_x: to_u8(p2[0]), pos_y: to_u8(p2[1]), pos_z: to_u8(p2[2]),
-        normal_flags: normal as u8,
+        pos_x: to_u16(p2[0]), pos_y: to_u16(p2[1]), pos_z: to_u16(p2[2]),
+        normal_flags: normal as u16,
         color,
     });
     ctx.vertices.push(PackedVertex {
-        pos_x: to_u8(p3[0]), pos_y: to_u8(p3[1]), pos_z: to_u8(p3[2]),
-        normal_flags: normal as u8,
+        pos_x: to_u16(p3[0]), pos_y: to_u16(p3[1]), pos_z: to_u16(p3[2]),
+        normal_flags: normal as u16,
         color,
     });
     
     // Индексы
     ctx.indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::blocks::DIRT;
+    use crate::gpu::terrain::mesh::validate::{assert_mesh_matches, fuzz_cases, FuzzVolume, Quad};
+
+    impl VoxelAccess for FuzzVolume {
+        fn get(&self, x: i32, y: i32, z: i32) -> Option<BlockType> {
+            let block = FuzzVolume::get(self, x, y, z);
+            (block != 0).then_some(block)
+        }
+
+        fn bounds(&self) -> (i32, i32, i32, i32, i32, i32) {
+            let max = self.max();
+            (self.origin[0], self.origin[1], self.origin[2], max[0], max[1], max[2])
+        }
+    }
+
+    fn normal_vector(flags: u16) -> [i32; 3] {
+        match flags & 7 {
+            0 => [1, 0, 0],
+            1 => [-1, 0, 0],
+            2 => [0, 1, 0],
+            3 => [0, -1, 0],
+            4 => [0, 0, 1],
+            _ => [0, 0, -1],
+        }
+    }
+
+    fn check(volume: &FuzzVolume, ctx: &mut MaskGreedyContext, context: &str) {
+        greedy_mesh_masked(volume, ctx, [0.0; 3]);
+        assert_eq!(ctx.indices.len(), ctx.vertices.len() / 4 * 6);
+
+        // Сетка 1/4, вершины в 1/16: клетка = 4 единицы
+        let quads: Vec<Quad> = ctx
+            .vertices
+            .chunks_exact(4)
+            .map(|quad| Quad {
+                corners: std::array::from_fn(|i| {
+                    [quad[i].pos_x as i32 / 4, quad[i].pos_y as i32 / 4, quad[i].pos_z as i32 / 4]
+                }),
+                normal: normal_vector(quad[0].normal_flags),
+            })
+            .collect();
+        assert_mesh_matches(&quads, &volume.naive_faces(), context);
+    }
+
+    #[test]
+    fn fuzz_mask_greedy_matches_naive() {
+        let mut ctx = MaskGreedyContext::new();
+        for case in 0..fuzz_cases() {
+            let fill = [0.1, 0.5, 0.9][case as usize % 3];
+            let volume = FuzzVolume::random(case, [12, 10, 14], fill, &[STONE, DIRT]);
+            check(&volume, &mut ctx, &format!("mask case {} fill {}", case, fill));
+        }
+    }
+
+    #[test]
+    fn mask_greedy_respects_bounds_origin() {
+        // Как SparseChunkVoxelAccess: границы по Y начинаются не с нуля
+        let mut ctx = MaskGreedyContext::new();
+        for case in 0..fuzz_cases() / 4 {
+            let volume = FuzzVolume::random(3000 + case, [10, 8, 10], 0.5, &[STONE, DIRT]).with_origin([2, 5, 3]);
+            check(&volume, &mut ctx, &format!("mask case {} with origin", case));
+        }
+    }
+
+    /// Один субвоксель 1/16 в клетке (3, 5, 7)
+    struct SixteenthCell;
+
+    impl VoxelAccess for SixteenthCell {
+        fn get(&self, x: i32, y: i32, z: i32) -> Option<BlockType> {
+            ((x, y, z) == (3, 5, 7)).then_some(STONE)
+        }
+
+        fn bounds(&self) -> (i32, i32, i32, i32, i32, i32) {
+            (0, 0, 0, 15, 15, 15)
+        }
+
+        fn divisions(&self) -> u8 {
+            16
+        }
+    }
+
+    #[test]
+    fn sixteenth_grid_maps_one_unit_per_cell() {
+        let mut ctx = MaskGreedyContext::new();
+        greedy_mesh_masked(&SixteenthCell, &mut ctx, [0.0; 3]);
+        assert_eq!(ctx.vertices.len(), 6 * 4);
+        for vertex in &ctx.vertices {
+            assert!((3..=4).contains(&vertex.pos_x));
+            assert!((5..=6).contains(&vertex.pos_y));
+            assert!((7..=8).contains(&vertex.pos_z));
+        }
+    }
+
+    #[test]
+    fn mask_greedy_mirrored_per_axis() {
+        let mut ctx = MaskGreedyContext::new();
+        for case in 0..fuzz_cases() / 4 {
+            let volume = FuzzVolume::random(2000 + case, [9, 11, 13], 0.4, &[STONE, DIRT]);
+            for axis in 0..3 {
+                check(&volume.mirrored(axis), &mut ctx, &format!("mask case {} mirrored axis {}", case, axis));
+            }
+        }
+    }
+}
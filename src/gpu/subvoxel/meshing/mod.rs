@@ -9,7 +9,7 @@
 //
 // Два формата вершин:
 // - SubVoxelVertex: 36 байт (legacy)
-// - PackedVertex: 8 байт (рекомендуется)
+// - PackedVertex: 12 байт (рекомендуется)
 
 mod greedy;
 mod chunk_grid;
@@ -3,24 +3,28 @@
 // ============================================
 //
 // Узел занимает 4 байта вместо 16+:
-// - 1 байт: тип данных (Empty/Solid/Branch) + BlockType
-// - 1 байт: child_mask (какие дети существуют)
+// - 1 байт: BlockType листа (0 - пусто или ветка)
+// - 1 байт: child_mask (какие дети существуют, не 0 - ветка)
 // - 2 байта: offset к первому ребёнку
 //
 // Дети хранятся компактно: только существующие, без пустых слотов.
 
 use crate::gpu::blocks::{BlockType, AIR};
 
-/// Максимальная глубина (0=1 блок, 1=1/2, 2=1/4)
-pub const MAX_DEPTH: u8 = 2;
+/// Максимальная глубина (0=1 блок, 1=1/2, 2=1/4, 3=1/8, 4=1/16)
+pub const MAX_DEPTH: u8 = 4;
+
+/// Порог пересборки массива узлов: схлопнутые ветки оставляют
+/// недостижимых детей, а offset - всего u16. Полное дерево глубины 4 -
+/// 4681 узел, так что после пересборки запас большой
+const REPACK_THRESHOLD: usize = 32768;
 
 /// Компактный узел октодерева (4 байта)
 #[derive(Clone, Copy)]
 #[repr(C, packed)]
 pub struct CompactNode {
-    /// Биты 0-5: BlockType (0 = Empty, 1-63 = solid types)
-    /// Бит 6: is_branch
-    /// Бит 7: reserved
+    /// BlockType листа целиком (0 = Empty или ветка): типов блоков
+    /// больше 63, так что флаг ветки живёт в child_mask
     pub data: u8,
     /// Битовая маска детей (какие из 8 существуют)
     pub child_mask: u8,
@@ -38,7 +42,7 @@ impl CompactNode {
     #[inline]
     pub fn solid(block_type: BlockType) -> Self {
         Self {
-            data: block_type as u8,
+            data: block_type,
             child_mask: 0,
             child_offset: 0,
         }
@@ -47,7 +51,7 @@ impl CompactNode {
     #[inline]
     pub fn branch(child_mask: u8, child_offset: u16) -> Self {
         Self {
-            data: 0x40, // is_branch bit
+            data: 0,
             child_mask,
             child_offset,
         }
@@ -60,18 +64,18 @@ impl CompactNode {
 
     #[inline]
     pub fn is_solid(&self) -> bool {
-        self.data != 0 && (self.data & 0x40) == 0
+        self.data != 0 && self.child_mask == 0
     }
 
     #[inline]
     pub fn is_branch(&self) -> bool {
-        (self.data & 0x40) != 0
+        self.child_mask != 0
     }
 
     #[inline]
     pub fn block_type(&self) -> Option<BlockType> {
         if self.is_solid() {
-            Some(self.data & 0x3F)
+            Some(self.data)
         } else {
             None
         }
@@ -96,6 +100,7 @@ impl CompactNode {
 
 /// Компактное октодерево
 /// Все узлы в одном Vec, дети хранятся компактно
+#[derive(Clone)]
 pub struct CompactOctree {
     nodes: Vec<CompactNode>,
 }
@@ -128,6 +133,35 @@ impl CompactOctree {
         let target_depth = depth.min(MAX_DEPTH);
         self.set_recursive(0, x, y, z, 0, target_depth, block_type);
         self.try_simplify(0);
+
+        if !self.nodes[0].is_branch() {
+            // Корень схлопнулся - остальные узлы недостижимы
+            self.nodes.truncate(1);
+        } else if self.nodes.len() > REPACK_THRESHOLD {
+            self.repack();
+        }
+    }
+
+    /// Пересобрать массив, оставив только достижимые узлы
+    fn repack(&mut self) {
+        let mut packed = vec![self.nodes[0]];
+        self.repack_children(0, 0, &mut packed);
+        self.nodes = packed;
+    }
+
+    fn repack_children(&self, old_idx: u16, new_idx: usize, packed: &mut Vec<CompactNode>) {
+        let node = self.nodes[old_idx as usize];
+        if !node.is_branch() {
+            return;
+        }
+        let offset = packed.len();
+        packed[new_idx] = CompactNode::branch(node.child_mask, offset as u16);
+
+        let children: Vec<u16> = (0..8u8).filter_map(|octant| node.child_index(octant)).collect();
+        packed.extend(children.iter().map(|&child| self.nodes[child as usize]));
+        for (i, &child) in children.iter().enumerate() {
+            self.repack_children(child, offset + i, packed);
+        }
     }
 
     fn set_recursive(
@@ -157,15 +191,14 @@ impl CompactOctree {
         
         if !node.is_branch() {
             // Превращаем в branch, создаём детей
-            let parent_data = node.data;
+            let parent = node.block_type();
             let child_offset = self.nodes.len() as u16;
             
             // Создаём всех 8 детей с данными родителя
             for _ in 0..8 {
-                if parent_data == 0 {
-                    self.nodes.push(CompactNode::EMPTY);
-                } else {
-                    self.nodes.push(CompactNode::solid(parent_data & 0x3F));
+                match parent {
+                    Some(block_type) => self.nodes.push(CompactNode::solid(block_type)),
+                    None => self.nodes.push(CompactNode::EMPTY),
                 }
             }
             
@@ -369,3 +402,55 @@ impl<'a> Iterator for CompactOctreeIterator<'a> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::blocks::{COPPER_BLOCK, DIRT, STONE};
+
+    #[test]
+    fn sixteenth_cells_set_and_read_back() {
+        let mut octree = CompactOctree::new();
+        octree.set(15, 0, 9, 4, STONE);
+        octree.set(3, 4, 1, 3, DIRT);
+
+        assert_eq!(octree.get(15, 0, 9, 4), Some(STONE));
+        assert_eq!(octree.get(14, 0, 9, 4), None);
+        // Клетка 1/8 накрывает восемь клеток 1/16
+        assert_eq!(octree.get(7, 9, 3, 4), Some(DIRT));
+        assert_eq!(octree.get(6, 8, 2, 4), Some(DIRT));
+    }
+
+    #[test]
+    fn keeps_block_types_above_63() {
+        let mut octree = CompactOctree::new();
+        octree.set(3, 0, 3, 2, COPPER_BLOCK);
+        octree.set(0, 0, 0, 1, STONE);
+        assert_eq!(octree.get(3, 0, 3, 2), Some(COPPER_BLOCK));
+        assert_eq!(octree.get(0, 0, 0, 2), Some(STONE));
+        assert!(octree.iter_solid().any(|(_, _, _, _, block)| block == COPPER_BLOCK));
+    }
+
+    #[test]
+    fn filled_and_cleared_tree_collapses() {
+        let mut octree = CompactOctree::new();
+        for x in 0..16 {
+            for y in 0..16 {
+                for z in 0..16 {
+                    octree.set(x, y, z, 4, STONE);
+                }
+            }
+        }
+        assert_eq!(octree.node_count(), 1);
+        assert_eq!(octree.get(5, 5, 5, 0), Some(STONE));
+
+        for x in 0..16 {
+            for y in 0..16 {
+                for z in 0..16 {
+                    octree.remove(x, y, z, 4);
+                }
+            }
+        }
+        assert!(octree.is_empty());
+    }
+}
@@ -10,8 +10,8 @@ use crate::gpu::blocks::{BlockType, AIR};
 /// Невалидный индекс (аналог null)
 pub const INVALID_INDEX: u32 = u32::MAX;
 
-/// Максимальная глубина (0=1 блок, 1=1/2, 2=1/4)
-pub const MAX_DEPTH: u8 = 2;
+/// Максимальная глубина (0=1 блок, 1=1/2, 2=1/4, 3=1/8, 4=1/16)
+pub const MAX_DEPTH: u8 = 4;
 
 /// Данные узла
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
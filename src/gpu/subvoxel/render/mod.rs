@@ -4,4 +4,4 @@
 
 mod renderer;
 
-pub use renderer::OptimizedSubVoxelRenderer;
+pub use renderer::{OptimizedSubVoxelRenderer, ChunkOrigin};
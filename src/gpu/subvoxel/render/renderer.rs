@@ -3,140 +3,104 @@
 // ============================================
 //
 // Использует:
-// - PackedVertex (8 байт вместо 36)
-// - MaskGreedyContext (без сортировки)
+// - PackedVertex (12 байт вместо 36)
+// - Мешинг в пуле потоков (systems::mesh_system)
 // - SparseChunkStorage (O(N) память)
+//
+// Вершины PackedVertex лежат в координатах чанка, поэтому у каждого
+// чанка есть маленький instance-буфер с углом чанка в мире: шейдер
+// (vs_subvoxel) прибавляет его к позиции.
 
 use std::collections::HashMap;
-use crate::gpu::subvoxel::meshing::{PackedVertex, MaskGreedyContext, VoxelAccess, greedy_mesh_masked};
-use crate::gpu::subvoxel::chunk::{SubVoxelChunkKey, SparseChunkStorage};
+use bytemuck::{Pod, Zeroable};
+use crate::gpu::subvoxel::meshing::PackedVertex;
+use crate::gpu::subvoxel::chunk::SubVoxelChunkKey;
 use crate::gpu::subvoxel::components::SubVoxelWorld;
-use crate::gpu::blocks::BlockType;
+use crate::gpu::subvoxel::systems::{
+    MeshingSystemContext, mark_chunk_dirty, process_meshing_queue, take_changed_meshes, get_chunk_mesh,
+};
+use crate::gpu::terrain::MIN_HEIGHT;
+use crate::gpu::profiler;
+
+/// Угол чанка в мире (instance-атрибут)
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ChunkOrigin {
+    pub origin: [f32; 3],
+}
+
+impl ChunkOrigin {
+    pub const ATTRIBS: [wgpu::VertexAttribute; 1] = wgpu::vertex_attr_array![
+        2 => Float32x3,
+    ];
+
+    pub fn desc() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Self>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &Self::ATTRIBS,
+        }
+    }
+
+    fn of(key: SubVoxelChunkKey) -> Self {
+        Self { origin: [(key.x * 16) as f32, MIN_HEIGHT as f32, (key.z * 16) as f32] }
+    }
+}
 
 /// GPU данные для одного чанка
 struct ChunkGpuData {
     vertex_buffer: wgpu::Buffer,
     index_buffer: wgpu::Buffer,
+    origin_buffer: wgpu::Buffer,
     num_indices: u32,
-    version: u64,
 }
 
 /// Рендерер субвокселей (оптимизированный)
 pub struct OptimizedSubVoxelRenderer {
     /// GPU буферы по чанкам
     chunk_buffers: HashMap<SubVoxelChunkKey, ChunkGpuData>,
-    /// Контекст для meshing (переиспользуется)
-    mesh_ctx: MaskGreedyContext,
+    /// Очередь и пул мешинга
+    meshing: MeshingSystemContext,
 }
 
 impl OptimizedSubVoxelRenderer {
     pub fn new(_device: &wgpu::Device) -> Self {
         Self {
             chunk_buffers: HashMap::new(),
-            mesh_ctx: MaskGreedyContext::new(),
+            meshing: MeshingSystemContext::new(),
         }
     }
 
-    /// Обновить только грязные чанки
-    pub fn update_dirty_chunks(
-        &mut self,
-        device: &wgpu::Device,
-        queue: &wgpu::Queue,
-        world: &mut SubVoxelWorld,
-    ) {
-        let dirty_chunks = world.take_dirty_chunks();
-
-        if dirty_chunks.is_empty() {
-            return;
+    /// Отправить грязные чанки в пул и залить готовые меши в GPU
+    pub fn update(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, world: &mut SubVoxelWorld) {
+        for key in world.take_dirty_chunks() {
+            mark_chunk_dirty(&mut self.meshing, key, 0);
+        }
+        {
+            let _scope = profiler::scope("meshing");
+            process_meshing_queue(&mut self.meshing, world.chunks());
         }
 
-        for chunk_key in dirty_chunks {
-            if let Some(chunk) = world.get_chunk(&chunk_key) {
-                if chunk.is_empty() {
-                    self.chunk_buffers.remove(&chunk_key);
-                    continue;
+        let _scope = profiler::scope("upload");
+        for key in take_changed_meshes(&mut self.meshing) {
+            match get_chunk_mesh(&self.meshing, key).filter(|mesh| !mesh.is_empty()) {
+                Some(mesh) => {
+                    upload_chunk(&mut self.chunk_buffers, device, queue, key, &mesh.vertices, &mesh.indices);
                 }
-
-                // Генерируем меш через mask greedy
-                let chunk_offset = [
-                    (chunk_key.x * 16) as f32,
-                    0.0,
-                    (chunk_key.z * 16) as f32,
-                ];
-
-                let voxel_access = SparseChunkVoxelAccess::new(chunk);
-                greedy_mesh_masked(&voxel_access, &mut self.mesh_ctx, chunk_offset);
-
-                if self.mesh_ctx.vertices.is_empty() {
-                    self.chunk_buffers.remove(&chunk_key);
-                } else {
-                    let vertices = std::mem::take(&mut self.mesh_ctx.vertices);
-                    let indices = std::mem::take(&mut self.mesh_ctx.indices);
-                    self.update_chunk_buffers(
-                        device, queue, chunk_key,
-                        vertices, indices, chunk.version()
-                    );
+                None => {
+                    self.chunk_buffers.remove(&key);
                 }
-            } else {
-                self.chunk_buffers.remove(&chunk_key);
             }
         }
     }
 
-    fn update_chunk_buffers(
-        &mut self,
-        device: &wgpu::Device,
-        queue: &wgpu::Queue,
-        chunk_key: SubVoxelChunkKey,
-        vertices: Vec<PackedVertex>,
-        indices: Vec<u32>,
-        version: u64,
-    ) {
-        let vertex_size = vertices.len() * std::mem::size_of::<PackedVertex>();
-        let index_size = indices.len() * std::mem::size_of::<u32>();
-
-        let needs_recreate = self.chunk_buffers.get(&chunk_key)
-            .map(|data| {
-                data.vertex_buffer.size() < vertex_size as u64 ||
-                data.index_buffer.size() < index_size as u64
-            })
-            .unwrap_or(true);
-
-        if needs_recreate {
-            let vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some(&format!("SubVoxel Chunk {:?} Vertex", chunk_key)),
-                size: (vertex_size * 2).max(256) as u64, // Меньше минимум т.к. вершины компактнее
-                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-                mapped_at_creation: false,
-            });
-
-            let index_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some(&format!("SubVoxel Chunk {:?} Index", chunk_key)),
-                size: (index_size * 2).max(256) as u64,
-                usage: wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
-                mapped_at_creation: false,
-            });
-
-            self.chunk_buffers.insert(chunk_key, ChunkGpuData {
-                vertex_buffer,
-                index_buffer,
-                num_indices: 0,
-                version: 0,
-            });
-        }
-
-        if let Some(gpu_data) = self.chunk_buffers.get_mut(&chunk_key) {
-            queue.write_buffer(&gpu_data.vertex_buffer, 0, bytemuck::cast_slice(&vertices));
-            queue.write_buffer(&gpu_data.index_buffer, 0, bytemuck::cast_slice(&indices));
-            gpu_data.num_indices = indices.len() as u32;
-            gpu_data.version = version;
-        }
-    }
-
-    pub fn iter_chunk_buffers(&self) -> impl Iterator<Item = (&wgpu::Buffer, &wgpu::Buffer, u32)> {
-        self.chunk_buffers.values()
-            .filter(|d| d.num_indices > 0)
-            .map(|d| (&d.vertex_buffer, &d.index_buffer, d.num_indices))
+    /// (чанк, вершины, индексы, угол чанка, число индексов)
+    pub fn iter_chunk_buffers(
+        &self,
+    ) -> impl Iterator<Item = (SubVoxelChunkKey, &wgpu::Buffer, &wgpu::Buffer, &wgpu::Buffer, u32)> {
+        self.chunk_buffers.iter()
+            .filter(|(_, d)| d.num_indices > 0)
+            .map(|(&key, d)| (key, &d.vertex_buffer, &d.index_buffer, &d.origin_buffer, d.num_indices))
     }
 
     pub fn total_indices(&self) -> u32 {
@@ -159,44 +123,59 @@ impl OptimizedSubVoxelRenderer {
     }
 }
 
-// ============================================
-// Адаптер VoxelAccess для SparseChunkStorage
-// ============================================
-
-struct SparseChunkVoxelAccess<'a> {
-    storage: &'a SparseChunkStorage,
-    min_y: i32,
-    max_y: i32,
-}
-
-impl<'a> SparseChunkVoxelAccess<'a> {
-    fn new(storage: &'a SparseChunkStorage) -> Self {
-        let (min_y, max_y) = storage.y_range();
-        Self {
-            storage,
-            min_y: min_y as i32 * 4,
-            max_y: (max_y as i32 + 1) * 4 - 1,
-        }
-    }
-}
-
-impl<'a> VoxelAccess for SparseChunkVoxelAccess<'a> {
-    fn get(&self, x: i32, y: i32, z: i32) -> Option<BlockType> {
-        if x < 0 || x >= 64 || z < 0 || z >= 64 || y < self.min_y || y > self.max_y {
-            return None;
-        }
-
-        let block_x = (x / 4) as u8;
-        let block_z = (z / 4) as u8;
-        let block_y = (y / 4) as u8;
-        let sub_x = (x % 4) as u8;
-        let sub_y = (y % 4) as u8;
-        let sub_z = (z % 4) as u8;
-
-        self.storage.get(block_x, block_y, block_z, sub_x, sub_y, sub_z, 2)
+/// Залить меш чанка (буферы растут с запасом, угол пишется один раз)
+fn upload_chunk(
+    chunk_buffers: &mut HashMap<SubVoxelChunkKey, ChunkGpuData>,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    chunk_key: SubVoxelChunkKey,
+    vertices: &[PackedVertex],
+    indices: &[u32],
+) {
+    let vertex_size = std::mem::size_of_val(vertices);
+    let index_size = std::mem::size_of_val(indices);
+
+    let needs_recreate = chunk_buffers.get(&chunk_key)
+        .map(|data| {
+            data.vertex_buffer.size() < vertex_size as u64 ||
+            data.index_buffer.size() < index_size as u64
+        })
+        .unwrap_or(true);
+
+    if needs_recreate {
+        let vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(&format!("SubVoxel Chunk {:?} Vertex", chunk_key)),
+            size: (vertex_size * 2).max(256) as u64, // Меньше минимум т.к. вершины компактнее
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let index_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(&format!("SubVoxel Chunk {:?} Index", chunk_key)),
+            size: (index_size * 2).max(256) as u64,
+            usage: wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let origin_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(&format!("SubVoxel Chunk {:?} Origin", chunk_key)),
+            size: std::mem::size_of::<ChunkOrigin>() as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        queue.write_buffer(&origin_buffer, 0, bytemuck::bytes_of(&ChunkOrigin::of(chunk_key)));
+
+        chunk_buffers.insert(chunk_key, ChunkGpuData {
+            vertex_buffer,
+            index_buffer,
+            origin_buffer,
+            num_indices: 0,
+        });
     }
 
-    fn bounds(&self) -> (i32, i32, i32, i32, i32, i32) {
-        (0, self.min_y, 0, 63, self.max_y, 63)
+    if let Some(gpu_data) = chunk_buffers.get_mut(&chunk_key) {
+        queue.write_buffer(&gpu_data.vertex_buffer, 0, bytemuck::cast_slice(vertices));
+        queue.write_buffer(&gpu_data.index_buffer, 0, bytemuck::cast_slice(indices));
+        gpu_data.num_indices = indices.len() as u32;
     }
 }
@@ -0,0 +1,168 @@
+// ============================================
+// Sculpt Brushes - Кисти для субвокселей
+// ============================================
+// Кисть превращает протяжку мышью (клетка нажатия -> клетка отпускания)
+// в набор клеток одного уровня: коробка между двумя клетками или шар
+// с центром в первой и радиусом до второй. Клетки считаются в сетке
+// уровня (блок * деления + индекс), поэтому мазок свободно переходит
+// через границы блоков.
+
+use crate::gpu::blocks::BlockType;
+
+use super::{SubVoxelLevel, SubVoxelPos};
+
+/// Максимум клеток за один мазок
+pub const MAX_BRUSH_CELLS: usize = 131072;
+
+/// Инструмент лепки (палитра переключается клавишей B)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SculptTool {
+    /// Обычная установка по одному субвокселю
+    Single,
+    /// Заливка коробки между клетками нажатия и отпускания
+    Box,
+    /// Заливка шаром: центр - клетка нажатия, радиус - до отпускания
+    Sphere,
+    /// Стирание шаром
+    Erase,
+}
+
+impl SculptTool {
+    pub const ALL: [SculptTool; 4] = [
+        SculptTool::Single,
+        SculptTool::Box,
+        SculptTool::Sphere,
+        SculptTool::Erase,
+    ];
+
+    pub fn next(self) -> Self {
+        match self {
+            SculptTool::Single => SculptTool::Box,
+            SculptTool::Box => SculptTool::Sphere,
+            SculptTool::Sphere => SculptTool::Erase,
+            SculptTool::Erase => SculptTool::Single,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SculptTool::Single => "Single",
+            SculptTool::Box => "Box",
+            SculptTool::Sphere => "Sphere",
+            SculptTool::Erase => "Erase",
+        }
+    }
+}
+
+/// Начатый мазок: клетка нажатия и блок заливки (None - стирание)
+#[derive(Debug, Clone, Copy)]
+pub struct SculptStroke {
+    pub start: SubVoxelPos,
+    pub block: Option<BlockType>,
+}
+
+/// Клетка в сетке уровня
+fn grid_cell(pos: &SubVoxelPos) -> [i32; 3] {
+    let div = pos.level.divisions() as i32;
+    [
+        pos.block_x * div + pos.sub_x as i32,
+        pos.block_y * div + pos.sub_y as i32,
+        pos.block_z * div + pos.sub_z as i32,
+    ]
+}
+
+fn from_grid_cell(cell: [i32; 3], level: SubVoxelLevel) -> SubVoxelPos {
+    let div = level.divisions() as i32;
+    SubVoxelPos::new(
+        cell[0].div_euclid(div),
+        cell[1].div_euclid(div),
+        cell[2].div_euclid(div),
+        cell[0].rem_euclid(div) as u8,
+        cell[1].rem_euclid(div) as u8,
+        cell[2].rem_euclid(div) as u8,
+        level,
+    )
+}
+
+/// Клетки мазка от start до end (end - на уровне start).
+/// None - мазок больше MAX_BRUSH_CELLS
+pub fn brush_cells(tool: SculptTool, start: SubVoxelPos, end: SubVoxelPos) -> Option<Vec<SubVoxelPos>> {
+    let level = start.level;
+    let a = grid_cell(&start);
+    let b = grid_cell(&end);
+
+    let (min, max, radius_sq) = match tool {
+        SculptTool::Single => return Some(vec![start]),
+        SculptTool::Box => (
+            [a[0].min(b[0]), a[1].min(b[1]), a[2].min(b[2])],
+            [a[0].max(b[0]), a[1].max(b[1]), a[2].max(b[2])],
+            None,
+        ),
+        SculptTool::Sphere | SculptTool::Erase => {
+            let radius_sq: i64 = (0..3).map(|i| ((b[i] - a[i]) as i64).pow(2)).sum();
+            let radius = (radius_sq as f64).sqrt().ceil() as i32;
+            (
+                [a[0] - radius, a[1] - radius, a[2] - radius],
+                [a[0] + radius, a[1] + radius, a[2] + radius],
+                Some(radius_sq),
+            )
+        }
+    };
+
+    let mut cells = Vec::new();
+    for x in min[0]..=max[0] {
+        for y in min[1]..=max[1] {
+            for z in min[2]..=max[2] {
+                if let Some(radius_sq) = radius_sq {
+                    let dist_sq = ((x - a[0]) as i64).pow(2) + ((y - a[1]) as i64).pow(2) + ((z - a[2]) as i64).pow(2);
+                    if dist_sq > radius_sq {
+                        continue;
+                    }
+                }
+                if cells.len() == MAX_BRUSH_CELLS {
+                    return None;
+                }
+                cells.push(from_grid_cell([x, y, z], level));
+            }
+        }
+    }
+    Some(cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn box_spans_block_boundary() {
+        // Клетки 1/4: (0,0,3) блока 0 и (1,0,0) блока 1 по Z
+        let start = SubVoxelPos::new(0, 0, 0, 0, 0, 3, SubVoxelLevel::Quarter);
+        let end = SubVoxelPos::new(0, 0, 1, 1, 0, 0, SubVoxelLevel::Quarter);
+        let cells = brush_cells(SculptTool::Box, start, end).unwrap();
+        // 2 по X, 1 по Y, 2 по Z
+        assert_eq!(cells.len(), 4);
+        assert!(cells.contains(&SubVoxelPos::new(0, 0, 1, 0, 0, 0, SubVoxelLevel::Quarter)));
+        assert!(cells.contains(&end));
+    }
+
+    #[test]
+    fn sphere_is_round_and_centered() {
+        let start = SubVoxelPos::new(-1, 4, 2, 7, 0, 3, SubVoxelLevel::Eighth);
+        let end = SubVoxelPos::new(0, 4, 2, 1, 0, 3, SubVoxelLevel::Eighth);
+        let cells = brush_cells(SculptTool::Sphere, start, end).unwrap();
+        // Радиус 2: центр, 6 соседей на расстоянии 1 и 2, рёбра и т.д.
+        assert_eq!(cells.len(), 33);
+        assert!(cells.contains(&start));
+        assert!(cells.contains(&end));
+        // Угол куба 2x2x2 вне шара
+        assert!(!cells.contains(&SubVoxelPos::new(0, 4, 2, 1, 2, 5, SubVoxelLevel::Eighth)));
+    }
+
+    #[test]
+    fn oversized_stroke_is_rejected() {
+        let start = SubVoxelPos::new(0, 0, 0, 0, 0, 0, SubVoxelLevel::Sixteenth);
+        let end = SubVoxelPos::new(8, 8, 8, 0, 0, 0, SubVoxelLevel::Sixteenth);
+        assert!(brush_cells(SculptTool::Box, start, end).is_none());
+        assert_eq!(brush_cells(SculptTool::Single, start, end).unwrap(), vec![start]);
+    }
+}
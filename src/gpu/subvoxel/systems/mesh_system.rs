@@ -5,13 +5,20 @@
 // Использует:
 // - SparseChunkStorage (O(N) память)
 // - MaskGreedyContext (битовые маски, без сортировки)
-// - PackedVertex (8 байт вместо 36)
+// - PackedVertex (12 байт вместо 36)
+//
+// Мешинг идёт в пуле потоков: за кадр в пул уходит не больше
+// max_chunks_per_frame задач со снимком хранилища чанка, готовые меши
+// приходят по каналу и забираются в начале следующего вызова
+// process_meshing_queue. Тяжёлые правки не останавливают кадр.
 
+use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
-use crate::gpu::blocks::BlockType;
-use crate::gpu::subvoxel::chunk::{SubVoxelChunkKey, SparseChunkStorage, PackedBlockKey};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use crate::gpu::blocks::{BlockType, AIR};
+use crate::gpu::subvoxel::chunk::{SubVoxelChunkKey, SparseChunkStorage};
 use crate::gpu::subvoxel::meshing::{
-    PackedVertex, MaskGreedyContext, VoxelAccess, greedy_mesh_masked,
+    PackedVertex, MaskGreedyContext, VoxelAccess, greedy_mesh_masked, MASK_SIZE,
 };
 
 // ============================================
@@ -58,47 +65,110 @@ impl ChunkMesh {
 }
 
 // ============================================
-// Адаптер для VoxelAccess
+// Сетка тайла для VoxelAccess
 // ============================================
-
-/// Адаптер SparseChunkStorage -> VoxelAccess для mask greedy
-struct SparseChunkVoxelAccess<'a> {
-    storage: &'a SparseChunkStorage,
-    min_y: i32,
-    max_y: i32,
+//
+// Маска greedy - 64 клетки, поэтому чанк мешится тайлами по 64 клетки
+// самого мелкого уровня в чанке (1/16 - тайл 4 блока, 1/4 - 16 блоков).
+// Тайл распаковывается в плотную сетку с рамкой в одну клетку: рамка -
+// соседи, по которым отсекаются грани на стыках тайлов.
+
+/// Сторона сетки тайла с рамкой
+const GRID_SIDE: i32 = MASK_SIZE as i32 + 2;
+
+/// Плотная сетка одного тайла чанка (координаты - клетки чанка, Y от
+/// MIN_HEIGHT)
+struct TileGrid {
+    cells: Vec<BlockType>,
+    divisions: i32,
+    /// Клетка чанка в углу сетки (с рамкой)
+    origin: [i32; 3],
+    /// Занятые клетки тайла (включительно)
+    min: [i32; 3],
+    max: [i32; 3],
 }
 
-impl<'a> SparseChunkVoxelAccess<'a> {
-    fn new(storage: &'a SparseChunkStorage) -> Self {
-        let (min_y, max_y) = storage.y_range();
+impl TileGrid {
+    fn new(divisions: i32) -> Self {
         Self {
-            storage,
-            min_y: min_y as i32 * 4, // В субвоксельных координатах
-            max_y: (max_y as i32 + 1) * 4 - 1,
+            cells: vec![AIR; (GRID_SIDE * GRID_SIDE * GRID_SIDE) as usize],
+            divisions,
+            origin: [0; 3],
+            min: [0; 3],
+            max: [0; 3],
         }
     }
+
+    /// Распаковать тайл: блоки min_block..=max_block и соседей рамки
+    fn fill(&mut self, storage: &SparseChunkStorage, tile: [i32; 3], min_block: [i32; 3], max_block: [i32; 3]) {
+        let d = self.divisions;
+        let tile_cells = MASK_SIZE as i32;
+        self.cells.fill(AIR);
+        self.origin = [tile[0] * tile_cells - 1, tile[1] * tile_cells - 1, tile[2] * tile_cells - 1];
+        self.min = [min_block[0] * d, min_block[1] * d, min_block[2] * d];
+        self.max = [(max_block[0] + 1) * d - 1, (max_block[1] + 1) * d - 1, (max_block[2] + 1) * d - 1];
+
+        let grid_end = [self.origin[0] + GRID_SIDE, self.origin[1] + GRID_SIDE, self.origin[2] + GRID_SIDE];
+        for (key, octree) in storage.iter_blocks() {
+            let (bx, by, bz) = key.unpack();
+            let block = [bx as i32 * d, by as i32 * d, bz as i32 * d];
+            if (0..3).any(|i| block[i] + d <= self.origin[i] || block[i] >= grid_end[i]) {
+                continue;
+            }
+            for (x, y, z, size, block_type) in octree.iter_solid() {
+                let len = (size * d as f32).round() as i32;
+                let start = [
+                    block[0] + (x * d as f32).round() as i32,
+                    block[1] + (y * d as f32).round() as i32,
+                    block[2] + (z * d as f32).round() as i32,
+                ];
+                let from: [i32; 3] = std::array::from_fn(|i| start[i].max(self.origin[i]));
+                let to: [i32; 3] = std::array::from_fn(|i| (start[i] + len).min(grid_end[i]));
+                for cy in from[1]..to[1] {
+                    for cz in from[2]..to[2] {
+                        for cx in from[0]..to[0] {
+                            let index = self.index(cx, cy, cz);
+                            self.cells[index] = block_type;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn index(&self, x: i32, y: i32, z: i32) -> usize {
+        let (x, y, z) = (x - self.origin[0], y - self.origin[1], z - self.origin[2]);
+        ((y * GRID_SIDE + z) * GRID_SIDE + x) as usize
+    }
 }
 
-impl<'a> VoxelAccess for SparseChunkVoxelAccess<'a> {
+impl VoxelAccess for TileGrid {
     fn get(&self, x: i32, y: i32, z: i32) -> Option<BlockType> {
-        if x < 0 || x >= 64 || z < 0 || z >= 64 || y < self.min_y || y > self.max_y {
+        let inside = [x, y, z].iter().zip(self.origin).all(|(&v, o)| v >= o && v < o + GRID_SIDE);
+        if !inside {
             return None;
         }
-
-        // Конвертируем субвоксельные координаты в блок + sub
-        let block_x = (x / 4) as u8;
-        let block_z = (z / 4) as u8;
-        let block_y = (y / 4) as u8;
-        let sub_x = (x % 4) as u8;
-        let sub_y = (y % 4) as u8;
-        let sub_z = (z % 4) as u8;
-
-        self.storage.get(block_x, block_y, block_z, sub_x, sub_y, sub_z, 2)
+        let block_type = self.cells[self.index(x, y, z)];
+        (block_type != AIR).then_some(block_type)
     }
 
     fn bounds(&self) -> (i32, i32, i32, i32, i32, i32) {
-        (0, self.min_y, 0, 63, self.max_y, 63)
+        (self.min[0], self.min[1], self.min[2], self.max[0], self.max[1], self.max[2])
     }
+
+    fn divisions(&self) -> u8 {
+        self.divisions as u8
+    }
+}
+
+/// Клеток на блок у самого мелкого листа чанка
+fn finest_divisions(storage: &SparseChunkStorage) -> i32 {
+    let min_size = storage
+        .iter_blocks()
+        .flat_map(|(_, octree)| octree.iter_solid().map(|(_, _, _, size, _)| size))
+        .fold(1.0f32, f32::min);
+    (1.0 / min_size).round() as i32
 }
 
 // ============================================
@@ -108,8 +178,11 @@ impl<'a> VoxelAccess for SparseChunkVoxelAccess<'a> {
 /// Конфигурация мешинга
 #[derive(Clone)]
 pub struct MeshingConfig {
+    /// Задач, отправляемых в пул за кадр
     pub max_chunks_per_frame: usize,
     pub priority_radius: i32,
+    /// Потоков пула (0 - по числу ядер минус одно для рендера)
+    pub worker_threads: usize,
 }
 
 impl Default for MeshingConfig {
@@ -117,16 +190,36 @@ impl Default for MeshingConfig {
         Self {
             max_chunks_per_frame: 4,
             priority_radius: 2,
+            worker_threads: 0,
         }
     }
 }
 
+/// Готовый меш из пула
+struct MeshJobResult {
+    key: SubVoxelChunkKey,
+    mesh: ChunkMesh,
+}
+
+thread_local! {
+    /// Контекст mask greedy на поток пула (переиспользуемый)
+    static GREEDY_CTX: RefCell<MaskGreedyContext> = RefCell::new(MaskGreedyContext::new());
+}
+
 /// Контекст системы мешинга
 pub struct MeshingSystemContext {
-    /// Контекст mask greedy (переиспользуемый)
-    greedy_ctx: MaskGreedyContext,
+    /// Пул потоков мешинга
+    pool: rayon::ThreadPool,
     /// Очередь грязных чанков
     dirty_queue: Vec<DirtyChunk>,
+    /// Чанки, чьи задачи ещё в пуле
+    in_flight: HashSet<SubVoxelChunkKey>,
+    /// Чанки, выгруженные пока их задача в пуле (результат выбрасывается)
+    cancelled: HashSet<SubVoxelChunkKey>,
+    result_tx: Sender<MeshJobResult>,
+    result_rx: Receiver<MeshJobResult>,
+    /// Чанки, чей меш сменился или пропал (для загрузки на GPU)
+    changed: Vec<SubVoxelChunkKey>,
     /// Готовые меши
     meshes: HashMap<SubVoxelChunkKey, ChunkMesh>,
     /// Конфигурация
@@ -135,18 +228,28 @@ pub struct MeshingSystemContext {
 
 impl MeshingSystemContext {
     pub fn new() -> Self {
-        Self {
-            greedy_ctx: MaskGreedyContext::new(),
-            dirty_queue: Vec::with_capacity(64),
-            meshes: HashMap::with_capacity(256),
-            config: MeshingConfig::default(),
-        }
+        Self::with_config(MeshingConfig::default())
     }
 
     pub fn with_config(config: MeshingConfig) -> Self {
+        let threads = match config.worker_threads {
+            0 => std::thread::available_parallelism().map_or(2, |n| n.get()).saturating_sub(1).max(1),
+            n => n,
+        };
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(|i| format!("subvoxel-mesh-{}", i))
+            .build()
+            .expect("пул мешинга субвокселей");
+        let (result_tx, result_rx) = channel();
         Self {
-            greedy_ctx: MaskGreedyContext::new(),
+            pool,
             dirty_queue: Vec::with_capacity(64),
+            in_flight: HashSet::new(),
+            cancelled: HashSet::new(),
+            result_tx,
+            result_rx,
+            changed: Vec::new(),
             meshes: HashMap::with_capacity(256),
             config,
         }
@@ -183,58 +286,108 @@ pub fn update_priorities(ctx: &mut MeshingSystemContext, player_chunk_x: i32, pl
     }
 }
 
-/// Обрабатывает очередь мешинга
-/// Возвращает количество обработанных чанков
+/// Обрабатывает очередь мешинга: забирает готовые меши из пула и
+/// отправляет в пул следующие грязные чанки.
+/// Возвращает количество чанков, чьи меши обновились
 pub fn process_meshing_queue(
     ctx: &mut MeshingSystemContext,
     storages: &HashMap<SubVoxelChunkKey, SparseChunkStorage>,
 ) -> usize {
-    if ctx.dirty_queue.is_empty() {
-        return 0;
-    }
+    let mut updated = collect_finished(ctx);
 
     // Сортируем по приоритету
     ctx.dirty_queue.sort_by(|a, b| b.priority.cmp(&a.priority));
 
-    let max_chunks = ctx.config.max_chunks_per_frame;
-    let mut processed = 0;
+    let max_jobs = ctx.config.max_chunks_per_frame;
+    let mut spawned = 0;
+    let mut index = 0;
 
-    while processed < max_chunks && !ctx.dirty_queue.is_empty() {
-        let dirty = ctx.dirty_queue.remove(0);
-        
-        let Some(storage) = storages.get(&dirty.key) else {
-            // Чанк удалён - удаляем меш
-            ctx.meshes.remove(&dirty.key);
+    while spawned < max_jobs && index < ctx.dirty_queue.len() {
+        // Чанк уже в пуле - ждёт, пока вернётся прошлая задача,
+        // иначе старый меш мог бы прийти позже нового
+        if ctx.in_flight.contains(&ctx.dirty_queue[index].key) {
+            index += 1;
+            continue;
+        }
+        let dirty = ctx.dirty_queue.remove(index);
+
+        let Some(storage) = storages.get(&dirty.key).filter(|storage| !storage.is_empty()) else {
+            // Чанк удалён или пуст - удаляем меш
+            if ctx.meshes.remove(&dirty.key).is_some() {
+                ctx.changed.push(dirty.key);
+                updated += 1;
+            }
             continue;
         };
 
-        if storage.is_empty() {
-            ctx.meshes.remove(&dirty.key);
-            processed += 1;
+        // Задача работает со снимком: хранилище дальше меняется в кадре
+        let snapshot = storage.clone();
+        let key = dirty.key;
+        let result_tx = ctx.result_tx.clone();
+        ctx.in_flight.insert(key);
+        ctx.pool.spawn(move || {
+            let mesh = mesh_storage(&snapshot);
+            let _ = result_tx.send(MeshJobResult { key, mesh });
+        });
+        spawned += 1;
+    }
+
+    updated
+}
+
+/// Забрать готовые меши из канала (не блокирует)
+fn collect_finished(ctx: &mut MeshingSystemContext) -> usize {
+    let mut updated = 0;
+    while let Ok(result) = ctx.result_rx.try_recv() {
+        ctx.in_flight.remove(&result.key);
+        if ctx.cancelled.remove(&result.key) {
             continue;
         }
+        ctx.meshes.insert(result.key, result.mesh);
+        ctx.changed.push(result.key);
+        updated += 1;
+    }
+    updated
+}
 
-        // Генерируем меш через mask greedy
-        let chunk_offset = [
-            (dirty.key.x * 16) as f32,
-            0.0,
-            (dirty.key.z * 16) as f32,
-        ];
-
-        let voxel_access = SparseChunkVoxelAccess::new(storage);
-        greedy_mesh_masked(&voxel_access, &mut ctx.greedy_ctx, chunk_offset);
-
-        // Сохраняем результат
-        ctx.meshes.insert(dirty.key, ChunkMesh {
-            vertices: std::mem::take(&mut ctx.greedy_ctx.vertices),
-            indices: std::mem::take(&mut ctx.greedy_ctx.indices),
-            version: storage.version(),
-        });
-
-        processed += 1;
+/// Меш одного чанка через mask greedy (в потоке пула). Позиции вершин -
+/// от угла чанка на высоте MIN_HEIGHT, угол рендерер подаёт отдельно
+fn mesh_storage(storage: &SparseChunkStorage) -> ChunkMesh {
+    let divisions = finest_divisions(storage);
+    let tile_blocks = MASK_SIZE as i32 / divisions;
+
+    // Занятые блоки по тайлам: (min, max) в блоках чанка
+    let mut tiles: HashMap<[i32; 3], ([i32; 3], [i32; 3])> = HashMap::new();
+    for (key, _) in storage.iter_blocks() {
+        let (x, y, z) = key.unpack();
+        let block = [x as i32, y as i32, z as i32];
+        let tile = block.map(|v| v.div_euclid(tile_blocks));
+        let entry = tiles.entry(tile).or_insert((block, block));
+        for i in 0..3 {
+            entry.0[i] = entry.0[i].min(block[i]);
+            entry.1[i] = entry.1[i].max(block[i]);
+        }
     }
 
-    processed
+    let mut mesh = ChunkMesh { version: storage.version(), ..ChunkMesh::default() };
+    let mut grid = TileGrid::new(divisions);
+    GREEDY_CTX.with(|greedy_ctx| {
+        let mut greedy_ctx = greedy_ctx.borrow_mut();
+        for (tile, (min_block, max_block)) in tiles {
+            grid.fill(storage, tile, min_block, max_block);
+            greedy_mesh_masked(&grid, &mut greedy_ctx, [0.0; 3]);
+            let base = mesh.vertices.len() as u32;
+            mesh.vertices.append(&mut greedy_ctx.vertices);
+            mesh.indices.extend(greedy_ctx.indices.drain(..).map(|index| index + base));
+        }
+    });
+    mesh
+}
+
+
+/// Забирает чанки, чей меш сменился или пропал с прошлого вызова
+pub fn take_changed_meshes(ctx: &mut MeshingSystemContext) -> Vec<SubVoxelChunkKey> {
+    std::mem::take(&mut ctx.changed)
 }
 
 /// Получает меш чанка
@@ -253,12 +406,16 @@ pub fn get_all_meshes(ctx: &MeshingSystemContext) -> &HashMap<SubVoxelChunkKey,
 pub fn remove_chunk_mesh(ctx: &mut MeshingSystemContext, key: SubVoxelChunkKey) {
     ctx.meshes.remove(&key);
     ctx.dirty_queue.retain(|d| d.key != key);
+    if ctx.in_flight.contains(&key) {
+        ctx.cancelled.insert(key);
+    }
 }
 
 /// Очищает все меши
 pub fn clear_all_meshes(ctx: &mut MeshingSystemContext) {
     ctx.meshes.clear();
     ctx.dirty_queue.clear();
+    ctx.cancelled.extend(ctx.in_flight.iter().copied());
 }
 
 // ============================================
@@ -269,6 +426,8 @@ pub fn clear_all_meshes(ctx: &mut MeshingSystemContext) {
 pub struct MeshingStats {
     pub total_meshes: usize,
     pub dirty_queue_size: usize,
+    /// Задач в пуле мешинга
+    pub in_flight: usize,
     pub total_vertices: usize,
     pub total_indices: usize,
     pub total_memory_bytes: usize,
@@ -283,8 +442,83 @@ pub fn get_meshing_stats(ctx: &MeshingSystemContext) -> MeshingStats {
     MeshingStats {
         total_meshes: ctx.meshes.len(),
         dirty_queue_size: ctx.dirty_queue.len(),
+        in_flight: ctx.in_flight.len(),
         total_vertices,
         total_indices,
         total_memory_bytes: total_memory,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::blocks::STONE;
+    use std::time::{Duration, Instant};
+
+    fn ctx_with_one_worker() -> MeshingSystemContext {
+        MeshingSystemContext::with_config(MeshingConfig { worker_threads: 1, ..MeshingConfig::default() })
+    }
+
+    /// Крутить очередь, пока пул не вернёт все задачи
+    fn drain(ctx: &mut MeshingSystemContext, storages: &HashMap<SubVoxelChunkKey, SparseChunkStorage>) {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            process_meshing_queue(ctx, storages);
+            if ctx.dirty_queue.is_empty() && ctx.in_flight.is_empty() {
+                return;
+            }
+            assert!(Instant::now() < deadline, "пул мешинга не ответил");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn meshes_chunk_on_worker() {
+        let key = SubVoxelChunkKey::new(1, -2);
+        let mut storage = SparseChunkStorage::new();
+        storage.set(3, 10, 5, 1, 1, 1, 2, STONE);
+        let storages = HashMap::from([(key, storage)]);
+
+        let mut ctx = ctx_with_one_worker();
+        mark_chunk_dirty(&mut ctx, key, 0);
+        drain(&mut ctx, &storages);
+
+        let mesh = get_chunk_mesh(&ctx, key).expect("меш чанка");
+        // Один субвоксель - 6 граней
+        assert_eq!(mesh.index_count(), 36);
+        assert_eq!(get_meshing_stats(&ctx).in_flight, 0);
+        assert_eq!(take_changed_meshes(&mut ctx), vec![key]);
+    }
+
+    #[test]
+    fn tiles_cull_faces_at_their_seam() {
+        // 1/16: тайл - 4 блока, клетки по обе стороны стыка x = 4
+        let mut storage = SparseChunkStorage::new();
+        storage.set(3, 2, 0, 15, 0, 0, 4, STONE);
+        storage.set(4, 2, 0, 0, 0, 0, 4, STONE);
+
+        let mesh = mesh_storage(&storage);
+        // По 5 граней на клетку: общая грань не рисуется
+        assert_eq!(mesh.index_count(), 60);
+        // Позиции - от угла чанка в 1/16 блока
+        assert!(mesh.vertices.iter().all(|v| (63..=65).contains(&v.pos_x) && (32..=33).contains(&v.pos_y)));
+    }
+
+    #[test]
+    fn removed_chunk_drops_late_result() {
+        let key = SubVoxelChunkKey::new(0, 0);
+        let mut storage = SparseChunkStorage::new();
+        storage.set(0, 0, 0, 0, 0, 0, 2, STONE);
+        let storages = HashMap::from([(key, storage)]);
+
+        let mut ctx = ctx_with_one_worker();
+        mark_chunk_dirty(&mut ctx, key, 0);
+        process_meshing_queue(&mut ctx, &storages);
+        // Чанк выгружен, пока задача в пуле
+        remove_chunk_mesh(&mut ctx, key);
+        drain(&mut ctx, &storages);
+
+        assert!(get_chunk_mesh(&ctx, key).is_none());
+        assert!(ctx.cancelled.is_empty());
+    }
+}
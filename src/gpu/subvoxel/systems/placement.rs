@@ -6,7 +6,7 @@ use super::super::components::{SubVoxelPos, SubVoxelLevel};
 use super::raycast::SubVoxelHit;
 
 /// Вычислить позицию субвокселя из мировых координат
-pub fn world_to_subvoxel_pos(
+pub fn world_to_subvoxel(
     world_x: f32, world_y: f32, world_z: f32,
     level: SubVoxelLevel,
 ) -> SubVoxelPos {
@@ -39,5 +39,27 @@ pub fn placement_pos_from_hit(hit: &SubVoxelHit, level: SubVoxelLevel) -> SubVox
     let place_y = hit.hit_point[1] + hit.hit_normal[1] * (size * 0.5);
     let place_z = hit.hit_point[2] + hit.hit_normal[2] * (size * 0.5);
     
-    world_to_subvoxel_pos(place_x, place_y, place_z, level)
+    world_to_subvoxel(place_x, place_y, place_z, level)
+}
+
+/// Проверка пересечения субвокселя с AABB игрока
+pub fn subvoxel_intersects_player(
+    pos: &SubVoxelPos,
+    player_x: f32, player_y: f32, player_z: f32,
+    player_radius: f32, player_height: f32,
+) -> bool {
+    let size = pos.level.size();
+    let [sv_x, sv_y, sv_z] = pos.world_min();
+    
+    // AABB игрока
+    let p_min_x = player_x - player_radius;
+    let p_max_x = player_x + player_radius;
+    let p_min_y = player_y;
+    let p_max_y = player_y + player_height;
+    let p_min_z = player_z - player_radius;
+    let p_max_z = player_z + player_radius;
+    
+    p_max_x > sv_x && p_min_x < sv_x + size &&
+    p_max_y > sv_y && p_min_y < sv_y + size &&
+    p_max_z > sv_z && p_min_z < sv_z + size
 }
@@ -1,10 +1,18 @@
 // ============================================
 // Raycast System - Рейкаст по субвокселям (ОПТИМИЗИРОВАННЫЙ)
 // ============================================
+// Луч идёт по сетке чанков 16x16 (2D DDA по X/Z) в порядке удаления.
+// Листья проверяются только в чанках, чей AABB занятых блоков луч
+// пересекает. Как только найденное попадание ближе входа в следующий
+// чанк - дальше не идём.
 
 use crate::gpu::blocks::BlockType;
-use super::super::components::{SubVoxelPos, SubVoxelLevel, SubVoxelWorld};
-use super::super::chunk::{SubVoxelChunkKey, SparseChunkStorage};
+use super::super::components::{block_world_pos, SubVoxelPos, SubVoxelLevel, SubVoxelWorld};
+use super::super::chunk::{SubVoxelChunkKey, SparseChunkStorage, PackedBlockKey};
+
+/// Сторона чанка субвокселей в блоках
+const CHUNK_BLOCKS: f32 = 16.0;
+use super::placement::world_to_subvoxel;
 
 /// Результат raycast по субвокселям
 #[derive(Clone, Copy, Debug)]
@@ -16,107 +24,179 @@ pub struct SubVoxelHit {
     pub distance: f32,
 }
 
-/// Raycast через субвоксели мира
-pub fn subvoxel_raycast(
-    world: &SubVoxelWorld,
-    origin: [f32; 3],
-    direction: [f32; 3],
-    max_distance: f32,
-    level: SubVoxelLevel,
-) -> Option<SubVoxelHit> {
-    let mut closest_hit: Option<SubVoxelHit> = None;
-
-    let end = [
-        origin[0] + direction[0] * max_distance,
-        origin[1] + direction[1] * max_distance,
-        origin[2] + direction[2] * max_distance,
-    ];
-
-    let min_chunk_x = (origin[0].min(end[0]).floor() as i32).div_euclid(16);
-    let max_chunk_x = (origin[0].max(end[0]).ceil() as i32).div_euclid(16);
-    let min_chunk_z = (origin[2].min(end[2]).floor() as i32).div_euclid(16);
-    let max_chunk_z = (origin[2].max(end[2]).ceil() as i32).div_euclid(16);
-
-    for cx in min_chunk_x..=max_chunk_x {
-        for cz in min_chunk_z..=max_chunk_z {
-            let key = SubVoxelChunkKey::new(cx, cz);
-            if let Some(chunk) = world.get_chunk(&key) {
-                if let Some(hit) = raycast_chunk(chunk, cx, cz, origin, direction, max_distance, level) {
-                    if closest_hit.is_none() || hit.distance < closest_hit.as_ref().unwrap().distance {
-                        closest_hit = Some(hit);
-                    }
+/// Ближайший лист октодерева на луче
+struct LeafHit {
+    block: [i32; 3],
+    /// Угол листа внутри блока и его размер
+    offset: [f32; 3],
+    size: f32,
+    block_type: BlockType,
+    normal: [f32; 3],
+    distance: f32,
+}
+
+impl SubVoxelWorld {
+    /// Raycast через субвоксели мира. Попадание - существующий лист, но
+    /// не крупнее level: в крупном листе берётся клетка уровня под точкой
+    /// попадания (так ломается кусок уровня, а не весь лист)
+    pub fn raycast(
+        &self,
+        origin: [f32; 3],
+        direction: [f32; 3],
+        max_distance: f32,
+        level: SubVoxelLevel,
+    ) -> Option<SubVoxelHit> {
+        let leaf = self.closest_leaf(origin, direction, max_distance)?;
+        let hit_point = [
+            origin[0] + direction[0] * leaf.distance,
+            origin[1] + direction[1] * leaf.distance,
+            origin[2] + direction[2] * leaf.distance,
+        ];
+
+        let divisions = (1.0 / leaf.size).round();
+        let leaf_level = SubVoxelLevel::from_depth(divisions.log2().round() as u8)?;
+        let pos = if leaf_level.depth() >= level.depth() {
+            let sub = |i: usize| (leaf.offset[i] * divisions).round() as u8;
+            SubVoxelPos::new(leaf.block[0], leaf.block[1], leaf.block[2], sub(0), sub(1), sub(2), leaf_level)
+        } else {
+            // Чуть внутрь грани, в которую попал луч
+            let inside = |i: usize| hit_point[i] - leaf.normal[i] * level.size() * 0.5;
+            world_to_subvoxel(inside(0), inside(1), inside(2), level)
+        };
+
+        Some(SubVoxelHit {
+            pos,
+            block_type: leaf.block_type,
+            hit_point,
+            hit_normal: leaf.normal,
+            distance: leaf.distance,
+        })
+    }
+
+    /// Дистанция до ближайшего субвокселя на луче (для коллизии камеры)
+    pub fn ray_distance(&self, origin: [f32; 3], direction: [f32; 3], max_distance: f32) -> Option<f32> {
+        self.closest_leaf(origin, direction, max_distance).map(|leaf| leaf.distance)
+    }
+
+    fn closest_leaf(&self, origin: [f32; 3], direction: [f32; 3], max_distance: f32) -> Option<LeafHit> {
+        let end = [
+            origin[0] + direction[0] * max_distance,
+            origin[1] + direction[1] * max_distance,
+            origin[2] + direction[2] * max_distance,
+        ];
+        let ray_min = [origin[0].min(end[0]), origin[1].min(end[1]), origin[2].min(end[2])];
+        let ray_max = [origin[0].max(end[0]), origin[1].max(end[1]), origin[2].max(end[2])];
+
+        let mut closest: Option<LeafHit> = None;
+        self.march_chunks(origin, direction, max_distance, |key, chunk| {
+            let limit = closest.as_ref().map_or(max_distance, |hit| hit.distance);
+            if let Some(hit) = raycast_chunk(chunk, key, origin, direction, limit, ray_min, ray_max) {
+                closest = Some(hit);
+            }
+            closest.as_ref().map(|hit| hit.distance)
+        });
+        closest
+    }
+
+    /// Обойти чанки вдоль луча от ближнего к дальнему. visit проверяет
+    /// листья чанка и возвращает лучшее попадание на данный момент
+    fn march_chunks(
+        &self,
+        origin: [f32; 3],
+        direction: [f32; 3],
+        max_distance: f32,
+        mut visit: impl FnMut(SubVoxelChunkKey, &SparseChunkStorage) -> Option<f32>,
+    ) {
+        if self.chunks().is_empty() {
+            return;
+        }
+
+        let mut cell = [(origin[0] / CHUNK_BLOCKS).floor() as i32, (origin[2] / CHUNK_BLOCKS).floor() as i32];
+        let mut step = [0; 2];
+        let mut t_max = [f32::INFINITY; 2];
+        let mut t_delta = [f32::INFINITY; 2];
+        for (i, axis) in [0, 2].into_iter().enumerate() {
+            let d = direction[axis];
+            if d.abs() < 1e-8 {
+                continue;
+            }
+            step[i] = if d > 0.0 { 1 } else { -1 };
+            let boundary = (cell[i] + if d > 0.0 { 1 } else { 0 }) as f32 * CHUNK_BLOCKS;
+            t_max[i] = (boundary - origin[axis]) / d;
+            t_delta[i] = CHUNK_BLOCKS / d.abs();
+        }
+
+        let mut best: Option<f32> = None;
+        loop {
+            let key = SubVoxelChunkKey::new(cell[0], cell[1]);
+            if let Some(chunk) = self.chunks().get(&key) {
+                let hit = chunk_aabb(chunk, key).and_then(|(min, max)| ray_aabb_intersection(origin, direction, min, max));
+                if hit.is_some_and(|(t, _)| t < max_distance) {
+                    best = visit(key, chunk);
                 }
             }
+
+            // Вход в следующий чанк дальше луча или найденного попадания
+            let axis = if t_max[0] < t_max[1] { 0 } else { 1 };
+            let t_next = t_max[axis];
+            if t_next > max_distance || best.is_some_and(|t| t <= t_next) {
+                break;
+            }
+            cell[axis] += step[axis];
+            t_max[axis] += t_delta[axis];
         }
     }
+}
 
-    closest_hit
+/// AABB занятых блоков чанка в мировых координатах
+fn chunk_aabb(chunk: &SparseChunkStorage, key: SubVoxelChunkKey) -> Option<([f32; 3], [f32; 3])> {
+    let (min, max) = chunk.block_bounds()?;
+    let low = block_world_pos(key, PackedBlockKey::new(min[0] as u8, min[1], min[2] as u8));
+    let high = block_world_pos(key, PackedBlockKey::new(max[0] as u8, max[1], max[2] as u8));
+    Some((low.map(|v| v as f32), high.map(|v| v as f32 + 1.0)))
 }
 
 /// Raycast через один чанк (использует SparseChunkStorage)
 fn raycast_chunk(
     chunk: &SparseChunkStorage,
-    chunk_x: i32, chunk_z: i32,
+    chunk_key: SubVoxelChunkKey,
     origin: [f32; 3],
     direction: [f32; 3],
     max_distance: f32,
-    level: SubVoxelLevel,
-) -> Option<SubVoxelHit> {
-    let mut closest_hit: Option<SubVoxelHit> = None;
-    let base_x = chunk_x * 16;
-    let base_z = chunk_z * 16;
-    let divisions = level.divisions();
+    ray_min: [f32; 3],
+    ray_max: [f32; 3],
+) -> Option<LeafHit> {
+    let mut closest_hit: Option<LeafHit> = None;
 
     for (block_key, octree) in chunk.iter_blocks() {
-        let (bx, by, bz) = block_key.unpack();
-        let block_world_x = base_x + bx as i32;
-        let block_world_y = by as i32;
-        let block_world_z = base_z + bz as i32;
-
-        // Быстрая проверка AABB блока
-        let block_min = [block_world_x as f32, block_world_y as f32, block_world_z as f32];
+        let block = block_world_pos(chunk_key, block_key);
+        let block_min = [block[0] as f32, block[1] as f32, block[2] as f32];
         let block_max = [block_min[0] + 1.0, block_min[1] + 1.0, block_min[2] + 1.0];
-        
+
+        // Быстрые проверки: блок в габаритах луча и луч задевает блок
+        if (0..3).any(|i| block_max[i] < ray_min[i] || block_min[i] > ray_max[i]) {
+            continue;
+        }
         if ray_aabb_intersection(origin, direction, block_min, block_max).is_none() {
             continue;
         }
 
-        // Детальная проверка субвокселей
+        // Детальная проверка листьев
         for (sx, sy, sz, size, block_type) in octree.iter_solid() {
-            let min_x = block_world_x as f32 + sx;
-            let min_y = block_world_y as f32 + sy;
-            let min_z = block_world_z as f32 + sz;
-            let max_x = min_x + size;
-            let max_y = min_y + size;
-            let max_z = min_z + size;
-
-            if let Some((t, normal)) = ray_aabb_intersection(
-                origin, direction,
-                [min_x, min_y, min_z],
-                [max_x, max_y, max_z],
-            ) {
-                if t > 0.0 && t < max_distance {
-                    if closest_hit.is_none() || t < closest_hit.as_ref().unwrap().distance {
-                        let sub_x = ((sx / size) as u8).min(divisions - 1);
-                        let sub_y = ((sy / size) as u8).min(divisions - 1);
-                        let sub_z = ((sz / size) as u8).min(divisions - 1);
-
-                        closest_hit = Some(SubVoxelHit {
-                            pos: SubVoxelPos::new(
-                                block_world_x, block_world_y, block_world_z,
-                                sub_x, sub_y, sub_z,
-                                level,
-                            ),
-                            block_type,
-                            hit_point: [
-                                origin[0] + direction[0] * t,
-                                origin[1] + direction[1] * t,
-                                origin[2] + direction[2] * t,
-                            ],
-                            hit_normal: normal,
-                            distance: t,
-                        });
-                    }
+            let min = [block_min[0] + sx, block_min[1] + sy, block_min[2] + sz];
+            let max = [min[0] + size, min[1] + size, min[2] + size];
+
+            if let Some((t, normal)) = ray_aabb_intersection(origin, direction, min, max) {
+                let limit = closest_hit.as_ref().map_or(max_distance, |hit| hit.distance);
+                if t > 0.0 && t < limit {
+                    closest_hit = Some(LeafHit {
+                        block,
+                        offset: [sx, sy, sz],
+                        size,
+                        block_type,
+                        normal,
+                        distance: t,
+                    });
                 }
             }
         }
@@ -126,6 +206,7 @@ fn raycast_chunk(
 }
 
 /// Ray-AABB intersection
+/// Returns (t, normal) where t is distance along ray and normal is hit face normal
 fn ray_aabb_intersection(
     origin: [f32; 3],
     direction: [f32; 3],
@@ -168,3 +249,74 @@ fn ray_aabb_intersection(
 
     Some((t_min, normal))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::blocks::{AIR, STONE};
+
+    fn quarter(x: i32, y: i32, z: i32) -> SubVoxelPos {
+        SubVoxelPos::new(x, y, z, 0, 0, 0, SubVoxelLevel::Quarter)
+    }
+
+    #[test]
+    fn visits_only_chunks_along_ray_in_order() {
+        let mut world = SubVoxelWorld::new();
+        world.set(quarter(40, 10, 2), STONE); // чанк (2, 0) на луче
+        world.set(quarter(20, 10, 2), STONE); // чанк (1, 0) на луче
+        world.set(quarter(20, 10, 40), STONE); // чанк (1, 2) в стороне
+
+        let mut visited = Vec::new();
+        world.march_chunks([0.5, 10.1, 2.1], [1.0, 0.0, 0.0], 64.0, |key, _| {
+            visited.push(key.x);
+            None
+        });
+        assert_eq!(visited, vec![1, 2]);
+
+        // Попадание в ближнем чанке - дальний не проверяется
+        let mut visits = 0;
+        world.march_chunks([0.5, 10.1, 2.1], [1.0, 0.0, 0.0], 64.0, |_, _| {
+            visits += 1;
+            Some(19.5)
+        });
+        assert_eq!(visits, 1);
+        assert!((world.ray_distance([0.5, 10.1, 2.1], [1.0, 0.0, 0.0], 64.0).unwrap() - 19.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn removal_shrinks_and_drops_chunk_bounds() {
+        let mut world = SubVoxelWorld::new();
+        let (low, high) = (quarter(1, 10, 1), quarter(1, 60, 1));
+        world.set(low, STONE);
+        world.set(high, STONE);
+        world.set(low, AIR);
+        let key = SubVoxelChunkKey::new(0, 0);
+        assert_eq!(chunk_aabb(&world.chunks()[&key], key).unwrap().0[1], 60.0);
+
+        world.set(high, AIR);
+        assert!(world.chunks().is_empty());
+    }
+
+    #[test]
+    fn hit_is_leaf_or_cell_of_level() {
+        let mut world = SubVoxelWorld::new();
+        world.set(SubVoxelPos::new(2, 10, -1, 0, 0, 0, SubVoxelLevel::Half), STONE);
+        world.set(SubVoxelPos::new(2, 10, -1, 7, 0, 0, SubVoxelLevel::Eighth), STONE);
+        // Луч сверху вниз в угол половинки
+        let origin = [2.1, 12.0, -0.9];
+        let down = [0.0, -1.0, 0.0];
+
+        let coarse = world.raycast(origin, down, 5.0, SubVoxelLevel::Quarter).unwrap();
+        assert_eq!(coarse.pos, SubVoxelPos::new(2, 10, -1, 0, 1, 0, SubVoxelLevel::Quarter));
+        assert_eq!(coarse.hit_normal, [0.0, 1.0, 0.0]);
+        assert!((coarse.distance - 1.5).abs() < 1e-5);
+
+        let whole = world.raycast(origin, down, 5.0, SubVoxelLevel::Full).unwrap();
+        assert_eq!(whole.pos, SubVoxelPos::new(2, 10, -1, 0, 0, 0, SubVoxelLevel::Half));
+
+        // Восьмушка мельче уровня - сама и есть попадание
+        let fine = world.raycast([2.95, 12.0, -0.95], down, 5.0, SubVoxelLevel::Half).unwrap();
+        assert_eq!(fine.pos, SubVoxelPos::new(2, 10, -1, 7, 0, 0, SubVoxelLevel::Eighth));
+        assert!(world.ray_distance([2.95, 12.0, -0.95], down, 1.0).is_none());
+    }
+}
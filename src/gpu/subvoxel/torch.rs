@@ -0,0 +1,156 @@
+// ============================================
+// Torch Model - Факел из субвокселей
+// ============================================
+// Факел - не полный блок, а палочка из трёх субвокселей 1/4 внутри
+// клетки: на полу по центру, на стене - прижата к опоре. Сторона опоры
+// не хранится отдельно: она читается по тому, какая модель стоит в
+// клетке, поэтому после загрузки мира факелы восстанавливаются из
+// обычных сохранённых субвокселей.
+
+use ultraviolet::Vec3;
+
+use crate::gpu::blocks::TORCH;
+
+use super::{SubVoxelLevel, SubVoxelPos, SubVoxelWorld};
+
+/// Сторона, к которой прикреплён факел
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TorchFacing {
+    /// Стоит на блоке снизу
+    Floor,
+    /// Висит на стене со стороны -X / +X / -Z / +Z
+    WallNegX,
+    WallPosX,
+    WallNegZ,
+    WallPosZ,
+}
+
+impl TorchFacing {
+    pub const ALL: [TorchFacing; 5] = [
+        TorchFacing::Floor,
+        TorchFacing::WallNegX,
+        TorchFacing::WallPosX,
+        TorchFacing::WallNegZ,
+        TorchFacing::WallPosZ,
+    ];
+
+    /// Крепление по нормали грани, на которую ставят факел
+    /// (None - потолок: висячих факелов нет)
+    pub fn from_normal(normal: Vec3) -> Option<Self> {
+        match (normal.x as i32, normal.y as i32, normal.z as i32) {
+            (0, 1, 0) => Some(TorchFacing::Floor),
+            (1, 0, 0) => Some(TorchFacing::WallNegX),
+            (-1, 0, 0) => Some(TorchFacing::WallPosX),
+            (0, 0, 1) => Some(TorchFacing::WallNegZ),
+            (0, 0, -1) => Some(TorchFacing::WallPosZ),
+            _ => None,
+        }
+    }
+
+    /// Смещение опорного блока от клетки факела
+    pub fn support_offset(self) -> [i32; 3] {
+        match self {
+            TorchFacing::Floor => [0, -1, 0],
+            TorchFacing::WallNegX => [-1, 0, 0],
+            TorchFacing::WallPosX => [1, 0, 0],
+            TorchFacing::WallNegZ => [0, 0, -1],
+            TorchFacing::WallPosZ => [0, 0, 1],
+        }
+    }
+
+    /// Субвоксели модели внутри клетки (снизу вверх, верхний - пламя)
+    fn model(self) -> [[u8; 3]; 3] {
+        let (x, z, y0) = match self {
+            TorchFacing::Floor => (1, 1, 0),
+            TorchFacing::WallNegX => (0, 1, 1),
+            TorchFacing::WallPosX => (3, 1, 1),
+            TorchFacing::WallNegZ => (1, 0, 1),
+            TorchFacing::WallPosZ => (1, 3, 1),
+        };
+        [[x, y0, z], [x, y0 + 1, z], [x, y0 + 2, z]]
+    }
+
+    fn positions(self, pos: [i32; 3]) -> impl Iterator<Item = SubVoxelPos> {
+        self.model().into_iter().map(move |[sx, sy, sz]| {
+            SubVoxelPos::new(pos[0], pos[1], pos[2], sx, sy, sz, SubVoxelLevel::Quarter)
+        })
+    }
+}
+
+/// Поставить модель факела в клетку
+pub fn place_torch(storage: &mut SubVoxelWorld, pos: [i32; 3], facing: TorchFacing) {
+    for sub in facing.positions(pos) {
+        storage.set(sub, TORCH);
+    }
+}
+
+/// Крепление факела в клетке (None - факела нет)
+pub fn torch_facing(storage: &SubVoxelWorld, pos: [i32; 3]) -> Option<TorchFacing> {
+    TorchFacing::ALL
+        .into_iter()
+        .find(|facing| facing.positions(pos).all(|sub| storage.get(&sub) == Some(TORCH)))
+}
+
+/// Убрать из клетки оставшиеся субвоксели факела (после удара по одному
+/// из них модель уже неполная). true - что-то было удалено
+pub fn remove_torch(storage: &mut SubVoxelWorld, pos: [i32; 3]) -> bool {
+    let mut removed = false;
+    for facing in TorchFacing::ALL {
+        for sub in facing.positions(pos) {
+            if storage.get(&sub) == Some(TORCH) {
+                storage.remove(&sub);
+                removed = true;
+            }
+        }
+    }
+    removed
+}
+
+/// Клетки всех целых факелов (восстановление света после загрузки)
+pub fn torch_cells(storage: &SubVoxelWorld) -> Vec<[i32; 3]> {
+    let mut cells: Vec<[i32; 3]> = storage
+        .get_all()
+        .into_iter()
+        .filter(|sv| sv.block_type == TORCH && sv.pos.level == SubVoxelLevel::Quarter)
+        .map(|sv| [sv.pos.block_x, sv.pos.block_y, sv.pos.block_z])
+        .collect();
+    cells.sort_unstable();
+    cells.dedup();
+    cells.retain(|&cell| torch_facing(storage, cell).is_some());
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn facing_follows_clicked_face() {
+        assert_eq!(TorchFacing::from_normal(Vec3::unit_y()), Some(TorchFacing::Floor));
+        assert_eq!(TorchFacing::from_normal(-Vec3::unit_y()), None);
+        // Клик по грани +X стены: стена слева от факела
+        let facing = TorchFacing::from_normal(Vec3::unit_x()).unwrap();
+        assert_eq!(facing.support_offset(), [-1, 0, 0]);
+    }
+
+    #[test]
+    fn placed_torch_reads_back_and_removes() {
+        let mut storage = SubVoxelWorld::new();
+        place_torch(&mut storage, [4, 10, -3], TorchFacing::WallPosZ);
+        assert_eq!(torch_facing(&storage, [4, 10, -3]), Some(TorchFacing::WallPosZ));
+        assert_eq!(torch_cells(&storage), vec![[4, 10, -3]]);
+
+        assert!(remove_torch(&mut storage, [4, 10, -3]));
+        assert_eq!(storage.block_count(), 0);
+        assert!(!remove_torch(&mut storage, [4, 10, -3]));
+    }
+
+    #[test]
+    fn broken_model_is_not_a_torch() {
+        let mut storage = SubVoxelWorld::new();
+        place_torch(&mut storage, [0, 0, 0], TorchFacing::Floor);
+        storage.remove(&SubVoxelPos::new(0, 0, 0, 1, 2, 1, SubVoxelLevel::Quarter));
+        assert_eq!(torch_facing(&storage, [0, 0, 0]), None);
+        assert!(torch_cells(&storage).is_empty());
+    }
+}
@@ -5,32 +5,50 @@
 use crate::gpu::core::GameResources;
 use crate::gpu::blocks::MouseButton;
 use crate::gpu::terrain::BlockPos;
-use crate::gpu::subvoxel::{SubVoxelLevel, SubVoxelHit, world_to_subvoxel, subvoxel_intersects_player, placement_pos_from_hit};
-use crate::gpu::player::{PLAYER_HEIGHT, PLAYER_RADIUS};
-use crate::gpu::blocks::BlockType;
+use crate::gpu::terrain::generation::{hash3d, in_spawn_protection, terrain_generator};
+use crate::gpu::subvoxel::{SubVoxelLevel, SubVoxelHit, SubVoxelPos, TorchFacing, world_to_subvoxel, subvoxel_intersects_player, placement_pos_from_hit};
+use crate::gpu::player::PLAYER_RADIUS;
+use crate::gpu::blocks::{BlockHit, BlockType, UseAction, DIRT, GRASS, TORCH, WATER, is_sapling, sapling_for_leaves, use_action};
+use crate::gpu::console::GameMode;
+use crate::gpu::items::{ItemStack, ItemType, can_harvest, tool_speed};
+use crate::gpu::input::HapticEvent;
+use crate::gpu::systems::{SpawnSystem, ContainerSystem, SchematicSystem, SculptSystem, TorchSystem};
+use ultraviolet::Vec3;
+
+/// Максимальная дальность палочки строителя (блоки)
+const MAX_BUILDER_WAND_REACH: f32 = 16.0;
+
+/// Шанс саженца из блока листвы и из одного субвокселя листвы
+const SAPLING_CHANCE: f32 = 0.05;
+const LEAF_SUBVOXEL_SAPLING_CHANCE: f32 = 0.005;
+
+/// Обработчик ПКМ по блоку (true - клик обработан)
+type UseHandler = fn(&mut GameResources, &BlockHit) -> bool;
+
+/// Обработчик действия on_use из реестра блоков
+fn use_handler(action: UseAction) -> UseHandler {
+    match action {
+        UseAction::SetRespawn => SpawnSystem::use_respawn_block,
+        UseAction::OpenContainer => ContainerSystem::open,
+    }
+}
+
+/// Множитель скорости ломания: режим игры и предмет в руке (пустая
+/// рука ломает с базовой скоростью)
+fn break_speed(game_mode: GameMode, held: Option<ItemType>, target: Option<BlockType>) -> f32 {
+    game_mode.break_speed() * target.map_or(1.0, |block| tool_speed(held, block))
+}
 
 /// Система взаимодействия с блоками
 pub struct BlockInteractionSystem;
 
 impl BlockInteractionSystem {
-    /// Обработка левой кнопки мыши (ломание)
+    /// Нажатие левой кнопки мыши: суб-воксель ломается сразу,
+    /// обычный блок - удержанием (см. update_breaking)
     pub fn handle_break(resources: &mut GameResources) {
-        let eye_pos = resources.player.eye_position();
-        let forward = resources.player.forward();
-        let origin = [eye_pos.x, eye_pos.y, eye_pos.z];
-        let direction = [forward.x, forward.y, forward.z];
-        
-        // Ищем ближайший суб-воксель
-        let mut closest_subvoxel: Option<(SubVoxelHit, f32)> = None;
-        {
-            let subvoxels = resources.subvoxel_storage.read().unwrap();
-            for level in [SubVoxelLevel::Quarter, SubVoxelLevel::Half] {
-                if let Some(hit) = subvoxels.raycast(origin, direction, 5.0, level) {
-                    if closest_subvoxel.is_none() || hit.distance < closest_subvoxel.as_ref().unwrap().1 {
-                        closest_subvoxel = Some((hit, hit.distance));
-                    }
-                }
-            }
+        // Палочка выделения: первый угол вместо ломания
+        if SchematicSystem::use_wand(resources, 0) {
+            return;
         }
         
         // Проверяем обычный блок
@@ -38,17 +56,77 @@ impl BlockInteractionSystem {
             .map(|b| b.distance)
             .unwrap_or(f32::MAX);
         
-        if let Some((hit, dist)) = closest_subvoxel {
-            if dist < block_dist {
+        if let Some(hit) = Self::closest_subvoxel_hit(resources) {
+            if hit.distance < block_dist {
+                let [x, _, z] = hit.pos.world_min();
+                if !Self::can_edit(resources, x.floor() as i32, z.floor() as i32) {
+                    return;
+                }
                 // Ломаем суб-воксель
-                let mut subvoxels = resources.subvoxel_storage.write().unwrap();
-                subvoxels.remove(&hit.pos);
+                let removed = resources.subvoxel_storage.write().unwrap().remove(&hit.pos);
+                if let Some(block) = removed {
+                    if block == TORCH {
+                        TorchSystem::on_subvoxel_broken(resources, [hit.pos.block_x, hit.pos.block_y, hit.pos.block_z]);
+                    }
+                    let min = hit.pos.world_min();
+                    let roll = hash3d((min[0] * 16.0) as i32, (min[1] * 16.0) as i32, (min[2] * 16.0) as i32);
+                    Self::drop_sapling(resources, block, roll, LEAF_SUBVOXEL_SAPLING_CHANCE);
+                    let min = Vec3::new(min[0], min[1], min[2]);
+                    let size = hit.pos.level.size();
+                    resources.particles.emit_subvoxel_break(min + Vec3::broadcast(size * 0.5), size, block);
+                    Self::invalidate_support(resources, min, min + Vec3::broadcast(size));
+                }
+                if let Some(renderer) = &mut resources.renderer {
+                    renderer.swing_hand();
+                }
+                resources.gamepad.play(HapticEvent::BlockBreak);
                 return;
             }
         }
         
-        // Ломаем обычный блок
-        if let Some(broken) = resources.block_breaker.process_mouse_button(MouseButton::Left, true) {
+        // Начинаем ломать обычный блок
+        if let Some([x, _, z]) = resources.block_breaker.target_block().map(|b| b.block_pos) {
+            if !Self::can_edit(resources, x, z) {
+                return;
+            }
+        }
+        resources.block_breaker.process_mouse_button(MouseButton::Left, true);
+    }
+    
+    /// Отпускание левой кнопки мыши
+    pub fn handle_break_release(resources: &mut GameResources) {
+        resources.block_breaker.process_mouse_button(MouseButton::Left, false);
+    }
+    
+    /// Прогресс ломания за кадр и эффекты сломанного блока
+    pub fn update_breaking(resources: &mut GameResources, dt: f32) {
+        // Без захвата курсора (меню, инвентарь) кнопка могла быть отпущена незаметно
+        if !resources.cursor.is_grabbed() {
+            resources.block_breaker.stop_breaking();
+        }
+        // Прицел с зажатой кнопкой увели на защищённый спавн
+        if let Some([x, _, z]) = resources.block_breaker.target_block().map(|b| b.block_pos) {
+            if !Self::can_edit_silent(resources, x, z) {
+                resources.block_breaker.stop_breaking();
+            }
+        }
+        // Предмет в руке ускоряет ломание блоков своих категорий
+        let held = resources.gui_renderer.as_ref()
+            .and_then(|gui| gui.hotbar_ref().selected_item().map(|stack| stack.item));
+        let target = resources.block_breaker.target_block().map(|hit| hit.block_type);
+        resources.block_breaker.set_break_speed(break_speed(resources.game_mode, held, target));
+        
+        if let Some(broken) = resources.block_breaker.update(&resources.player, dt) {
+            Self::collect_drop(resources, held, broken.block_type);
+            let [x, y, z] = broken.block_pos;
+            Self::drop_sapling(resources, broken.block_type, hash3d(x, y, z), SAPLING_CHANCE);
+            resources.gamepad.play(HapticEvent::BlockBreak);
+            resources.particles.emit_block_break(broken.block_pos, broken.block_type);
+            ContainerSystem::on_block_broken(resources, broken.block_pos);
+            TorchSystem::on_block_broken(resources, broken.block_pos);
+            resources.block_ticks.notify_changed(BlockPos::from_array(broken.block_pos));
+            let min = Vec3::new(broken.block_pos[0] as f32, broken.block_pos[1] as f32, broken.block_pos[2] as f32);
+            Self::invalidate_support(resources, min, min + Vec3::one());
             if let Some(renderer) = &mut resources.renderer {
                 let changes = resources.world_changes.read().unwrap();
                 renderer.instant_chunk_update(
@@ -61,8 +139,62 @@ impl BlockInteractionSystem {
         }
     }
     
+    /// Добыча в выживании: сломанный блок попадает в хотбар, если предмет
+    /// в руке подходит (без нужной кирки руда ничего не роняет)
+    fn collect_drop(resources: &mut GameResources, held: Option<ItemType>, block: BlockType) {
+        if resources.game_mode != GameMode::Survival || !can_harvest(held, block) {
+            return;
+        }
+        if let Some(gui) = &mut resources.gui_renderer {
+            // Хотбар полон - добыча пропадает
+            gui.hotbar().insert(ItemStack::of_block(block));
+        }
+    }
+    
+    /// Листва изредка роняет саженец своей породы (в выживании)
+    fn drop_sapling(resources: &mut GameResources, leaves: BlockType, roll: f32, chance: f32) {
+        let Some(sapling) = sapling_for_leaves(leaves) else { return };
+        if resources.game_mode != GameMode::Survival || roll >= chance {
+            return;
+        }
+        if let Some(gui) = &mut resources.gui_renderer {
+            gui.hotbar().insert(ItemStack::of_block(sapling));
+        }
+    }
+    
+    /// Хук изменения мира: если изменённый объём был опорой игрока,
+    /// сразу перепроверить землю под ногами (иначе игрок висит до шага)
+    pub fn invalidate_support(resources: &mut GameResources, min: Vec3, max: Vec3) {
+        if !resources.player.rests_on(min, max) {
+            return;
+        }
+        let changes = resources.world_changes.read().unwrap().get_all_changes_copy();
+        resources.player_controller.invalidate_support(&mut resources.player, &changes);
+    }
+    
     /// Обработка правой кнопки мыши (установка)
     pub fn handle_place(resources: &mut GameResources) {
+        // Палочка выделения: второй угол
+        if SchematicSystem::use_wand(resources, 1) {
+            return;
+        }
+        
+        // Alt + ПКМ: палочка строителя вместо блока из хотбара
+        if resources.builder_wand_held && resources.settings.controls.builder_wand {
+            Self::extend_target_block(resources);
+            return;
+        }
+        
+        // ПКМ по блоку с on_use (кровать, сундук) - действие вместо установки
+        if Self::use_target(resources) {
+            return;
+        }
+        
+        // Кисть лепки: мазок до отпускания ПКМ
+        if SculptSystem::begin(resources) {
+            return;
+        }
+        
         // Получаем тип блока из хотбара
         let block_type = if let Some(gui) = &mut resources.gui_renderer {
             gui.hotbar().selected_block_type()
@@ -72,6 +204,12 @@ impl BlockInteractionSystem {
         
         let Some(block_type) = block_type else { return };
         
+        // Факел - модель из субвокселей на любом уровне
+        if block_type == TORCH {
+            Self::place_torch(resources);
+            return;
+        }
+        
         if resources.current_subvoxel_level == SubVoxelLevel::Full {
             Self::place_full_block(resources, block_type);
         } else {
@@ -79,62 +217,126 @@ impl BlockInteractionSystem {
         }
     }
     
+    /// on_use блока под прицелом. true - клик обработан. Shift - обычная установка
+    fn use_target(resources: &mut GameResources) -> bool {
+        if resources.player.sneaking {
+            return false;
+        }
+        let Some(hit) = resources.block_breaker.target_block().copied() else { return false };
+        // Субвоксель перед блоком перехватывает клик
+        if Self::closest_subvoxel_hit(resources).is_some_and(|sv| sv.distance < hit.distance) {
+            return false;
+        }
+        match use_action(hit.block_type) {
+            Some(action) => use_handler(action)(resources, &hit),
+            None => false,
+        }
+    }
+    
+    /// Палочка строителя: продлить блок под прицелом на один наружу
+    /// тем же типом, независимо от выбранного в хотбаре
+    fn extend_target_block(resources: &mut GameResources) {
+        let reach = resources.settings.controls.builder_wand_reach.clamp(1.0, MAX_BUILDER_WAND_REACH);
+        let Some(hit) = resources.block_breaker.raycast(&resources.player, reach) else { return };
+        // Воду не продлеваем; нулевая нормаль - глаза внутри блока
+        if hit.block_type == WATER || hit.hit_normal == Vec3::zero() {
+            return;
+        }
+        
+        let place_pos = [
+            hit.block_pos[0] + hit.hit_normal.x as i32,
+            hit.block_pos[1] + hit.hit_normal.y as i32,
+            hit.block_pos[2] + hit.hit_normal.z as i32,
+        ];
+        Self::place_full_block_at(resources, place_pos, hit.block_type);
+    }
+    
+    /// Факел на грань блока под прицелом (не на субвоксели и не на потолок)
+    fn place_torch(resources: &mut GameResources) {
+        let Some(hit) = resources.block_breaker.target_block().copied() else { return };
+        if Self::closest_subvoxel_hit(resources).is_some_and(|sv| sv.distance < hit.distance) {
+            return;
+        }
+        let Some(facing) = TorchFacing::from_normal(hit.hit_normal) else { return };
+        let offset = facing.support_offset();
+        let pos = [
+            hit.block_pos[0] - offset[0],
+            hit.block_pos[1] - offset[1],
+            hit.block_pos[2] - offset[2],
+        ];
+        if Self::can_edit(resources, pos[0], pos[2]) {
+            TorchSystem::place(resources, pos, facing);
+        }
+    }
+    
     /// Установка полного блока
     fn place_full_block(resources: &mut GameResources, block_type: BlockType) {
         if let Some(place_pos) = resources.block_breaker.placement_pos() {
-            if !Self::block_intersects_player(resources, place_pos) {
-                // Ставим блок
-                let mut changes = resources.world_changes.write().unwrap();
-                changes.set_block(
-                    BlockPos::new(place_pos[0], place_pos[1], place_pos[2]),
-                    block_type,
-                );
-                drop(changes);
-                
-                if let Some(renderer) = &mut resources.renderer {
-                    let changes = resources.world_changes.read().unwrap();
-                    renderer.instant_chunk_update(
-                        place_pos[0],
-                        place_pos[1],
-                        place_pos[2],
-                        &changes,
-                    );
-                }
-                
-                // Звук установки блока
-                if let Some(audio) = &mut resources.audio_system {
-                    audio.play_place_block();
-                }
-            }
+            Self::place_full_block_at(resources, place_pos, block_type);
         }
     }
     
-    /// Установка суб-вокселя
-    fn place_subvoxel(resources: &mut GameResources, block_type: BlockType) {
-        let eye_pos = resources.player.eye_position();
-        let forward = resources.player.forward();
-        let origin = [eye_pos.x, eye_pos.y, eye_pos.z];
-        let direction = [forward.x, forward.y, forward.z];
+    /// Установка полного блока в заданную позицию
+    fn place_full_block_at(resources: &mut GameResources, place_pos: [i32; 3], block_type: BlockType) {
+        if Self::block_intersects_player(resources, place_pos) || !Self::can_edit(resources, place_pos[0], place_pos[2]) {
+            return;
+        }
+        // Клетку занимает факел
+        if TorchSystem::is_torch(resources, place_pos) {
+            return;
+        }
+        // Саженцы - только на землю и траву
+        if is_sapling(block_type) && !matches!(Self::block_at(resources, place_pos[0], place_pos[1] - 1, place_pos[2]), DIRT | GRASS) {
+            return;
+        }
         
-        // Ищем ближайший суб-воксель любого уровня
-        let mut closest_hit: Option<SubVoxelHit> = None;
-        {
-            let subvoxels = resources.subvoxel_storage.read().unwrap();
-            for level in [SubVoxelLevel::Quarter, SubVoxelLevel::Half] {
-                if let Some(hit) = subvoxels.raycast(origin, direction, 5.0, level) {
-                    if closest_hit.is_none() || hit.distance < closest_hit.as_ref().unwrap().distance {
-                        closest_hit = Some(hit);
-                    }
-                }
-            }
+        // Ставим блок
+        let pos = BlockPos::new(place_pos[0], place_pos[1], place_pos[2]);
+        let mut changes = resources.world_changes.write().unwrap();
+        changes.set_block(pos, block_type);
+        drop(changes);
+        resources.block_entities.place(pos, block_type);
+        resources.block_ticks.notify_changed(pos);
+        
+        if let Some(renderer) = &mut resources.renderer {
+            renderer.swing_hand();
+            let changes = resources.world_changes.read().unwrap();
+            renderer.instant_chunk_update(
+                place_pos[0],
+                place_pos[1],
+                place_pos[2],
+                &changes,
+            );
+        }
+        
+        // Звук установки блока
+        if let Some(audio) = &mut resources.audio_system {
+            audio.play_place_block(block_type, SubVoxelLevel::Full);
         }
+    }
+    
+    /// Блок мира: изменения игрока поверх генератора
+    pub fn block_at(resources: &GameResources, x: i32, y: i32, z: i32) -> BlockType {
+        let changed = resources.world_changes.read().unwrap().get_block(x, y, z);
+        changed.unwrap_or_else(|| terrain_generator().block_at(x, y, z))
+    }
+    
+    /// Отпускание правой кнопки мыши: применить мазок кисти
+    pub fn handle_place_release(resources: &mut GameResources) {
+        SculptSystem::finish(resources);
+    }
+    
+    /// Клетка текущего уровня, куда встанет суб-воксель по ПКМ
+    pub fn subvoxel_placement_target(resources: &GameResources) -> Option<SubVoxelPos> {
+        // Ищем ближайший суб-воксель
+        let closest_hit = Self::closest_subvoxel_hit(resources);
         
         // Также проверяем обычный блок
         let block_dist = resources.block_breaker.target_block()
             .map(|b| b.distance)
             .unwrap_or(f32::MAX);
         
-        let subvoxel_pos = if let Some(hit) = closest_hit {
+        if let Some(hit) = closest_hit {
             if hit.distance < block_dist {
                 // Ставим рядом с существующим суб-вокселем
                 Some(placement_pos_from_hit(&hit, resources.current_subvoxel_level))
@@ -155,9 +357,16 @@ impl BlockInteractionSystem {
             ))
         } else {
             None
-        };
-        
-        if let Some(subvoxel_pos) = subvoxel_pos {
+        }
+    }
+    
+    /// Установка суб-вокселя
+    fn place_subvoxel(resources: &mut GameResources, block_type: BlockType) {
+        if let Some(subvoxel_pos) = Self::subvoxel_placement_target(resources) {
+            let [x, _, z] = subvoxel_pos.world_min();
+            if !Self::can_edit(resources, x.floor() as i32, z.floor() as i32) {
+                return;
+            }
             let mut subvoxels = resources.subvoxel_storage.write().unwrap();
             // Проверяем что позиция не занята
             if subvoxels.get(&subvoxel_pos).is_none() {
@@ -168,30 +377,75 @@ impl BlockInteractionSystem {
                     resources.player.position.y,
                     resources.player.position.z,
                     PLAYER_RADIUS,
-                    PLAYER_HEIGHT
+                    resources.player.height()
                 ) {
                     subvoxels.set(subvoxel_pos, block_type);
                     drop(subvoxels);
+                    if let Some(renderer) = &mut resources.renderer {
+                        renderer.swing_hand();
+                    }
                     
-                    // Звук установки блока
+                    // Звук установки суб-вокселя (выше и тише блока)
                     if let Some(audio) = &mut resources.audio_system {
-                        audio.play_place_block();
+                        audio.play_place_block(block_type, subvoxel_pos.level);
                     }
                 }
             }
         }
     }
     
-    /// Обработка средней кнопки мыши (pick block)
+    /// Можно ли менять блоки колонки: в защите спавна - только операторам
+    pub fn can_edit_silent(resources: &GameResources, x: i32, z: i32) -> bool {
+        resources.is_op || !in_spawn_protection(resources.world_spawn, resources.spawn_protection, x, z)
+    }
+    
+    /// То же с сообщением игроку
+    pub(super) fn can_edit(resources: &mut GameResources, x: i32, z: i32) -> bool {
+        let allowed = Self::can_edit_silent(resources, x, z);
+        if !allowed {
+            resources.console.error("Спавн защищён: строить здесь могут только операторы");
+        }
+        allowed
+    }
+    
+    /// Обработка средней кнопки мыши (pick block): блок под прицелом
+    /// (ближайший из суб-вокселя и обычного блока) попадает в хотбар
     pub fn handle_pick_block(resources: &mut GameResources) {
-        if let Some(target) = resources.block_breaker.target_block() {
-            let block_type = target.block_type;
-            if let Some(gui) = &mut resources.gui_renderer {
-                gui.hotbar().pick_block(block_type);
+        let target = resources.block_breaker.target_block().map(|b| (b.block_type, b.distance));
+        let block_dist = target.map_or(f32::MAX, |(_, distance)| distance);
+        
+        let block_type = match Self::closest_subvoxel_hit(resources) {
+            Some(hit) if hit.distance < block_dist => {
+                // Суб-воксель: заодно переключаемся на его размер
+                resources.current_subvoxel_level = hit.pos.level;
+                hit.block_type
             }
+            _ => match target {
+                Some((block_type, _)) => {
+                    resources.current_subvoxel_level = SubVoxelLevel::Full;
+                    block_type
+                }
+                None => return,
+            },
+        };
+        
+        if let Some(gui) = &mut resources.gui_renderer {
+            gui.hotbar().pick_block(block_type);
         }
     }
     
+    /// Ближайший суб-воксель на луче взгляда: лист хранилища или, если
+    /// он крупнее текущего уровня, клетка текущего уровня внутри него
+    pub fn closest_subvoxel_hit(resources: &GameResources) -> Option<SubVoxelHit> {
+        let eye_pos = resources.player.eye_position();
+        let forward = resources.player.forward();
+        let origin = [eye_pos.x, eye_pos.y, eye_pos.z];
+        let direction = [forward.x, forward.y, forward.z];
+        
+        resources.subvoxel_storage.read().unwrap()
+            .raycast(origin, direction, 5.0, resources.current_subvoxel_level)
+    }
+    
     /// Проверяет, пересекается ли блок с хитбоксом игрока
     fn block_intersects_player(resources: &GameResources, block_pos: [i32; 3]) -> bool {
         let player_pos = resources.player.position;
@@ -200,7 +454,7 @@ impl BlockInteractionSystem {
         let player_min_x = player_pos.x - PLAYER_RADIUS;
         let player_max_x = player_pos.x + PLAYER_RADIUS;
         let player_min_y = player_pos.y;
-        let player_max_y = player_pos.y + PLAYER_HEIGHT;
+        let player_max_y = player_pos.y + resources.player.height();
         let player_min_z = player_pos.z - PLAYER_RADIUS;
         let player_max_z = player_pos.z + PLAYER_RADIUS;
         
@@ -218,3 +472,24 @@ impl BlockInteractionSystem {
         player_max_z > block_min_z && player_min_z < block_max_z
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::blocks::{BlockCategory, STONE};
+    use crate::gpu::items::item_registry;
+
+    #[test]
+    fn break_speed_comes_only_from_held_item() {
+        // Пустая рука на камне - базовая скорость, других источников нет
+        assert_eq!(break_speed(GameMode::Survival, None, Some(STONE)), 1.0);
+        assert_eq!(break_speed(GameMode::Survival, None, None), 1.0);
+
+        // Кирка ускоряет ровно на свой множитель, один раз
+        let pickaxe = item_registry().all_items().into_iter().find(|def| def.id == "iron_pickaxe").unwrap();
+        assert_eq!(
+            break_speed(GameMode::Survival, Some(pickaxe.numeric_id), Some(STONE)),
+            pickaxe.speeds[&BlockCategory::Stone]
+        );
+    }
+}
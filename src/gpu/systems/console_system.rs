@@ -0,0 +1,423 @@
+// ============================================
+// Console System - Ввод и выполнение команд консоли
+// ============================================
+// Пока консоль открыта, клавиатура идёт сюда, а не в управление.
+
+use ultraviolet::Vec3;
+use winit::{
+    event::{ElementState, KeyEvent},
+    keyboard::{KeyCode, PhysicalKey},
+};
+
+use crate::gpu::blocks::{global_registry, AIR};
+use crate::gpu::console::{parse_command, Command, Coord, DayCommand, EditCommand, EditRegion, GameMode, PregenCommand, ProfileCommand, SchemCommand, TimeCommand, UiCommand, WaypointCommand, COMMANDS, MAX_COORD};
+use crate::gpu::core::GameResources;
+use crate::gpu::lighting::{is_night, MoonPhase, SUNRISE};
+use crate::gpu::logging;
+use crate::gpu::locale::{self, tr_args, LANGUAGES};
+use crate::gpu::player::{MovementMode, PLAYER_RADIUS};
+use crate::gpu::profiler;
+use crate::gpu::save::{DEATH_MARKER, MAX_WAYPOINTS};
+use crate::gpu::terrain::voxel::constants::WORLD_HEIGHT;
+use crate::gpu::terrain::{edit_volume, EditOperation, PregenTask, MIN_HEIGHT};
+use crate::gpu::terrain::generation::world_border;
+
+use super::InputSystem;
+use super::MenuSystem;
+use super::SaveSystem;
+use super::SpawnSystem;
+use super::SchematicSystem;
+use super::WorldEditSystem;
+
+/// Система консоли
+pub struct ConsoleSystem;
+
+impl ConsoleSystem {
+    /// Открыть консоль (T - пустая строка, '/' - сразу команда)
+    pub fn open(resources: &mut GameResources, prefix: &str) {
+        resources.console.open(prefix);
+        resources.player_controller.release_keys();
+        resources.block_breaker.stop_breaking();
+        InputSystem::grab_cursor(resources, false);
+    }
+
+    fn close(resources: &mut GameResources) {
+        resources.console.close();
+        InputSystem::grab_cursor(resources, true);
+    }
+
+    /// Клавиша при открытой консоли
+    pub fn handle_key(resources: &mut GameResources, event: &KeyEvent) {
+        if event.state != ElementState::Pressed {
+            return;
+        }
+
+        match event.physical_key {
+            PhysicalKey::Code(KeyCode::Escape) => Self::close(resources),
+            PhysicalKey::Code(KeyCode::Enter | KeyCode::NumpadEnter) => {
+                let line = resources.console.submit();
+                Self::close(resources);
+                if let Some(line) = line {
+                    Self::execute_line(resources, &line);
+                }
+            }
+            PhysicalKey::Code(KeyCode::Backspace) => resources.console.backspace(),
+            PhysicalKey::Code(KeyCode::Tab) => {
+                let names = Self::block_names();
+                resources.console.complete(&names);
+            }
+            PhysicalKey::Code(KeyCode::ArrowUp) => resources.console.history_prev(),
+            PhysicalKey::Code(KeyCode::ArrowDown) => resources.console.history_next(),
+            _ => {
+                if let Some(text) = &event.text {
+                    resources.console.type_text(text);
+                }
+            }
+        }
+    }
+
+    /// Имена блоков из реестра для автодополнения
+    fn block_names() -> Vec<String> {
+        let registry = global_registry().read().unwrap();
+        let mut names: Vec<String> = registry.all_blocks().map(|def| def.id.clone()).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Выполнить введённую строку: '/' - команда, иначе сообщение чата
+    pub fn execute_line(resources: &mut GameResources, line: &str) {
+        resources.console.echo(line);
+        if !line.starts_with('/') {
+            return;
+        }
+
+        log::info!("{}", line);
+        match parse_command(line) {
+            Ok(command) => Self::execute(resources, command),
+            Err(e) => resources.console.error(e),
+        }
+    }
+
+    fn execute(resources: &mut GameResources, command: Command) {
+        match command {
+            Command::Help => {
+                for (name, help) in COMMANDS {
+                    resources.console.info(format!("{} {}", name, help));
+                }
+            }
+
+            Command::Teleport(coords) => {
+                let target = clamp_to_world(Self::resolve(coords, resources.player.position));
+                resources.player.position = target;
+                resources.player.velocity = Vec3::zero();
+                resources.console.info(format!("Телепорт на {:.1} {:.1} {:.1}", target.x, target.y, target.z));
+            }
+
+            Command::Time(TimeCommand::Set(time)) => {
+                if let Some(renderer) = &mut resources.renderer {
+                    renderer.set_time_of_day(time);
+                }
+                // Перестановка времени - не рассвет, день не меняется
+                resources.day_counter.sync(time);
+                resources.console.info(format!("Время суток: {:.2}", time));
+            }
+            Command::Time(TimeCommand::Query) => {
+                if let Some(renderer) = &resources.renderer {
+                    resources.console.info(format!("Время суток: {:.2}", renderer.time_of_day()));
+                }
+            }
+
+            Command::Time(TimeCommand::Pause(paused)) => {
+                resources.settings.time.paused = paused;
+                Self::apply_time_speed(resources);
+                resources.console.info(if paused { "Время остановлено" } else { "Время идёт" });
+            }
+
+            Command::DayLength(Some(minutes)) => {
+                // В settings.json попадёт по кнопке Save
+                resources.settings.time.day_length_minutes = minutes;
+                Self::apply_time_speed(resources);
+                resources.console.info(format!("Длина суток: {} мин", minutes));
+            }
+            Command::DayLength(None) => {
+                let time = resources.settings.time;
+                let paused = if time.paused { " (время остановлено)" } else { "" };
+                resources.console.info(format!("Длина суток: {} мин{}", time.day_length_minutes, paused));
+            }
+
+            Command::Sleep => {
+                let Some(renderer) = &mut resources.renderer else { return };
+                if !is_night(renderer.time_of_day()) {
+                    resources.console.error("Спать можно только ночью");
+                    return;
+                }
+                // Без sync: счётчик дней увидит рассвет и начнёт новый день
+                renderer.set_time_of_day(SUNRISE);
+                resources.console.info("Ночь пропущена");
+            }
+
+            Command::Day(DayCommand::Set(day)) => {
+                resources.day_counter.set_day_number(day);
+                resources.console.info(format!("День {}", day));
+            }
+            Command::Day(DayCommand::Query) => {
+                let phase = MoonPhase::from_day(resources.day_counter.days());
+                resources.console.info(format!("День {}, луна: {}", resources.day_counter.day_number(), phase.name()));
+            }
+
+            Command::Give(name) => {
+                let block = global_registry().read().unwrap().get_numeric_id(&name);
+                match block {
+                    Some(block) if block != AIR => {
+                        if let Some(gui) = &mut resources.gui_renderer {
+                            gui.hotbar().pick_block(block);
+                        }
+                        resources.console.info(format!("Выдан блок {}", name));
+                    }
+                    _ => resources.console.error(format!("Неизвестный блок '{}'", name)),
+                }
+            }
+
+            Command::GameMode(mode) => {
+                Self::set_game_mode(resources, mode);
+                resources.console.info(format!("Режим игры: {}", mode.name()));
+            }
+
+            Command::Seed => {
+                resources.console.info(format!("Сид: {}", resources.world_seed));
+            }
+
+            Command::Edit { region, edit } => Self::edit(resources, region, &edit),
+            Command::Undo => WorldEditSystem::undo(resources),
+
+            Command::Pregen(PregenCommand::Start { radius, mesh }) => {
+                if resources.pregen.is_some() {
+                    resources.console.error("Прегенерация уже идёт (/pregen cancel)");
+                } else {
+                    let pos = resources.player.position;
+                    resources.pregen = Some(PregenTask::start(pos.x, pos.z, radius, mesh));
+                    resources.console.info(format!("Прегенерация: радиус {} чанков", radius));
+                }
+            }
+            Command::Pregen(PregenCommand::Cancel) => match resources.pregen.take() {
+                Some(task) => {
+                    let (done, total) = task.progress();
+                    task.cancel();
+                    resources.console.info(format!("Прегенерация отменена на {}/{}", done, total));
+                }
+                None => resources.console.error("Прегенерация не запущена"),
+            },
+
+            Command::SetSpawn(_) | Command::SpawnProtection(_) if !resources.is_op => {
+                resources.console.error("Команда доступна только операторам");
+            }
+            Command::SetSpawn(coords) => {
+                let origin = resources.player.position;
+                let target = clamp_to_world(coords.map_or(origin, |coords| Self::resolve(coords, origin)));
+                resources.world_spawn = [target.x.floor() as i32, target.y.floor() as i32, target.z.floor() as i32];
+                SaveSystem::save_spawn(resources);
+                let [x, y, z] = resources.world_spawn;
+                resources.console.info(format!("Спавн мира: {} {} {}", x, y, z));
+            }
+            Command::Spawn => {
+                let [x, y, z] = SpawnSystem::respawn(resources);
+                resources.console.info(format!("Телепорт на точку возрождения {} {} {}", x, y, z));
+            }
+            Command::Kill => SpawnSystem::die(resources, "toast.died"),
+            Command::SpawnProtection(radius) => {
+                resources.spawn_protection = radius;
+                SaveSystem::save_spawn(resources);
+                if radius == 0 {
+                    resources.console.info("Защита спавна выключена");
+                } else {
+                    resources.console.info(format!("Защита спавна: радиус {} блоков", radius));
+                }
+            }
+
+            Command::Log(None) => {
+                resources.console.info(format!("Журнал: {}", logging::filter_spec()));
+            }
+            Command::Log(Some(spec)) => match logging::set_filter(&spec) {
+                Ok(()) => {
+                    // В settings.json попадёт по кнопке Save
+                    resources.settings.logging.filter = logging::filter_spec();
+                    resources.console.info(format!("Журнал: {}", resources.settings.logging.filter));
+                }
+                Err(e) => resources.console.error(e),
+            },
+
+            Command::Lang(None) => {
+                resources.console.info(format!("Язык: {} ({}), встроены: {}", locale::language_name(), locale::language(), LANGUAGES.join(", ")));
+            }
+            Command::Lang(Some(code)) => match locale::set_language(&code) {
+                Ok(()) => {
+                    // В settings.json попадёт по кнопке Save
+                    resources.settings.locale.language = code;
+                    MenuSystem::relabel(resources);
+                    let name = locale::language_name();
+                    resources.console.info(format!("Язык: {}", name));
+                    if let Some(gui) = &mut resources.gui_renderer {
+                        gui.toasts().push(tr_args("toast.language", &[("name", &name)]));
+                    }
+                }
+                Err(e) => resources.console.error(e),
+            },
+
+            Command::Copy => SchematicSystem::copy(resources),
+            Command::Paste(quarter_turns) => SchematicSystem::paste(resources, quarter_turns),
+            Command::Schem(SchemCommand::Save(name)) => SchematicSystem::save(resources, &name),
+            Command::Schem(SchemCommand::Load(name)) => SchematicSystem::load(resources, &name),
+
+            Command::Profile(ProfileCommand::Show(visible)) => {
+                if let Some(gui) = &mut resources.gui_renderer {
+                    gui.profiler_overlay().set_visible(visible);
+                }
+                resources.console.info(if visible { "Профайлер включён (F4)" } else { "Профайлер выключен" });
+            }
+            Command::Profile(ProfileCommand::Dump) => match profiler::write_chrome_trace(&profiler::recorded_frames()) {
+                Ok(path) => resources.console.info(format!("Trace записан: {}", path.display())),
+                Err(e) => resources.console.error(format!("Trace не записан: {}", e)),
+            },
+
+            Command::Waypoint(WaypointCommand::Add { name, pos, color }) => {
+                let origin = resources.player.position;
+                let target = pos.map_or(origin, |coords| Self::resolve(coords, origin));
+                let pos = [target.x.floor() as i32, target.y.floor() as i32, target.z.floor() as i32];
+                if resources.explored_map.add_waypoint(&name, pos, color) {
+                    resources.console.info(format!("Метка {}: {} {} {}", name, pos[0], pos[1], pos[2]));
+                } else {
+                    resources.console.error(format!("Меток не больше {}", MAX_WAYPOINTS));
+                }
+            }
+            Command::Waypoint(WaypointCommand::Color { name, color }) => {
+                if resources.explored_map.set_waypoint_color(&name, color) {
+                    resources.console.info(format!("Метка {} перекрашена", name));
+                } else {
+                    resources.console.error(format!("Нет метки '{}'", name));
+                }
+            }
+            Command::Waypoint(WaypointCommand::Remove(name)) => {
+                if resources.explored_map.remove_waypoint(&name) {
+                    resources.console.info(format!("Метка {} удалена", name));
+                } else {
+                    resources.console.error(format!("Нет метки '{}'", name));
+                }
+            }
+            Command::Waypoint(WaypointCommand::List) => {
+                let map = &resources.explored_map;
+                if map.waypoints().is_empty() && map.death().is_none() {
+                    resources.console.info("Меток нет (ПКМ на карте мира или /waypoint add)");
+                }
+                let mut lines: Vec<String> = map
+                    .waypoints()
+                    .iter()
+                    .map(|waypoint| format!("{}: {} {} {}", waypoint.name, waypoint.pos[0], waypoint.pos[1], waypoint.pos[2]))
+                    .collect();
+                if let Some([x, y, z]) = map.death() {
+                    lines.push(format!("{} (место смерти): {} {} {}", DEATH_MARKER, x, y, z));
+                }
+                for line in lines {
+                    resources.console.info(line);
+                }
+            }
+
+            Command::Ui(command) => {
+                // В settings.json попадёт по кнопке Save
+                match command {
+                    UiCommand::Show => {}
+                    UiCommand::Scale(scale) => resources.settings.ui.scale = scale.unwrap_or(0.0),
+                    UiCommand::SafeArea(fraction) => resources.settings.ui.safe_area = fraction,
+                }
+                let settings = resources.settings.ui;
+                let Some(gui) = &mut resources.gui_renderer else { return };
+                if let Some(renderer) = &resources.renderer {
+                    gui.set_ui_settings(renderer.queue(), &settings);
+                }
+                let mode = if settings.is_auto() { " (auto)" } else { "" };
+                let factor = gui.ui_scale().factor();
+                resources.console.info(format!("Интерфейс: масштаб {:.2}{}, поля {}%", factor, mode, settings.safe_area * 100.0));
+            }
+        }
+    }
+
+    /// Скорость времени из настроек (длина суток и пауза)
+    pub fn apply_time_speed(resources: &mut GameResources) {
+        if let Some(renderer) = &mut resources.renderer {
+            renderer.set_time_speed(resources.settings.time.time_speed());
+        }
+    }
+
+    /// Применить режим игры (сохраняется в метаданных мира)
+    pub fn set_game_mode(resources: &mut GameResources, mode: GameMode) {
+        resources.player_controller.flight.mode = match mode {
+            GameMode::Survival => MovementMode::Walking,
+            GameMode::Creative => MovementMode::Flying,
+        };
+        resources.block_breaker.set_break_speed(mode.break_speed());
+        resources.game_mode = mode;
+    }
+
+    fn resolve(coords: [Coord; 3], origin: Vec3) -> Vec3 {
+        Vec3::new(coords[0].resolve(origin.x), coords[1].resolve(origin.y), coords[2].resolve(origin.z))
+    }
+
+    /// /fill, /replace, /hollow, /walls: правка выделения или области по углам
+    fn edit(resources: &mut GameResources, region: EditRegion, edit: &EditCommand) {
+        let block_id = |name: &str| global_registry().read().unwrap().get_numeric_id(name).ok_or_else(|| format!("Неизвестный блок '{}'", name));
+        let operation = match edit {
+            EditCommand::Fill(name) => block_id(name).map(EditOperation::Fill),
+            EditCommand::Replace { from, to } => block_id(from).and_then(|from| Ok(EditOperation::Replace { from, to: block_id(to)? })),
+            EditCommand::Hollow(name) => block_id(name).map(EditOperation::Hollow),
+            EditCommand::Walls(name) => block_id(name).map(EditOperation::Walls),
+        };
+        let operation = match operation {
+            Ok(operation) => operation,
+            Err(e) => {
+                resources.console.error(e);
+                return;
+            }
+        };
+
+        let (min, max) = match region {
+            EditRegion::Corners(from, to) => {
+                let origin = resources.player.position;
+                let (a, b) = (Self::resolve(from, origin), Self::resolve(to, origin));
+                let min = [a.x.min(b.x).floor() as i32, a.y.min(b.y).floor() as i32, a.z.min(b.z).floor() as i32];
+                let max = [a.x.max(b.x).floor() as i32, a.y.max(b.y).floor() as i32, a.z.max(b.z).floor() as i32];
+                (min, max)
+            }
+            EditRegion::Selection => match SchematicSystem::selection_bounds(resources) {
+                Some(bounds) => bounds,
+                None => {
+                    resources.console.error("Нет выделения: ЛКМ и ПКМ палочкой выделения по углам или координаты углов");
+                    return;
+                }
+            },
+        };
+        let min = [min[0], min[1].max(MIN_HEIGHT), min[2]];
+        let max = [max[0], max[1].min(WORLD_HEIGHT - 1), max[2]];
+        // Огромные и вырожденные области отсекаются до очереди
+        let volume = match edit_volume(min, max) {
+            Ok(volume) => volume,
+            Err(e) => {
+                resources.console.error(e);
+                return;
+            }
+        };
+        WorldEditSystem::start(resources, operation, min, max, volume);
+    }
+}
+
+/// Точка телепорта внутри мира: X и Z - у границы мира (без неё - в
+/// пределах MAX_COORD), Y - в пределах высот мира
+fn clamp_to_world(target: Vec3) -> Vec3 {
+    let horizontal = |value: f32| match world_border() {
+        Some(border) => border.clamp(value, PLAYER_RADIUS),
+        None => value.clamp(-MAX_COORD, MAX_COORD),
+    };
+    Vec3::new(
+        horizontal(target.x),
+        target.y.clamp(MIN_HEIGHT as f32, WORLD_HEIGHT as f32),
+        horizontal(target.z),
+    )
+}
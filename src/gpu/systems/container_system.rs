@@ -0,0 +1,146 @@
+// ============================================
+// Container System - Сундуки и их экран
+// ============================================
+// ПКМ по сундуку открывает его экран (Shift - обычная установка блока).
+// ЛКМ по слоту сундука переносит стопку в хотбар, ПКМ - половину стопки;
+// по слоту хотбара - в сундук. Экран закрывается Escape/E, при уходе от сундука и если
+// сундук сломан. Содержимое сломанного игроком сундука уходит в хотбар.
+
+use std::collections::HashMap;
+
+use ultraviolet::Vec3;
+
+use crate::gpu::block_entity::BlockEntity;
+use crate::gpu::blocks::{BlockHit, BlockType};
+use crate::gpu::core::GameResources;
+use crate::gpu::locale::{tr, tr_args};
+use crate::gpu::terrain::BlockPos;
+use crate::gpu::systems::InputSystem;
+
+/// Дальше этого расстояния до сундука экран закрывается (блоки)
+const MAX_CONTAINER_DISTANCE: f32 = 6.0;
+
+/// Система контейнеров блоков
+pub struct ContainerSystem;
+
+impl ContainerSystem {
+    /// ПКМ по сундуку (on_use open_container): открыть его экран.
+    /// true - клик обработан
+    pub fn open(resources: &mut GameResources, hit: &BlockHit) -> bool {
+        let [x, y, z] = hit.block_pos;
+        let pos = BlockPos::new(x, y, z);
+        let Some(BlockEntity::Chest(chest)) = resources.block_entities.get_or_create(pos, hit.block_type) else {
+            return false;
+        };
+        let Some(gui) = &mut resources.gui_renderer else { return false };
+        gui.container().open("container.chest", chest.slots());
+        chest.set_open(true);
+
+        resources.open_container = Some(pos);
+        resources.player_controller.release_keys();
+        resources.block_breaker.stop_breaking();
+        InputSystem::grab_cursor(resources, false);
+        true
+    }
+
+    /// Открыт ли экран контейнера
+    pub fn is_open(resources: &GameResources) -> bool {
+        resources.open_container.is_some()
+    }
+
+    /// Закрыть экран контейнера
+    pub fn close(resources: &mut GameResources) {
+        if let Some(pos) = resources.open_container.take() {
+            if let Some(BlockEntity::Chest(chest)) = resources.block_entities.get_mut(&pos) {
+                chest.set_open(false);
+            }
+        }
+        if let Some(gui) = &mut resources.gui_renderer {
+            gui.container().close();
+        }
+        InputSystem::grab_cursor(resources, true);
+    }
+
+    /// Клик при открытом экране: перенос стопки (half - половины)
+    /// между сундуком и хотбаром
+    pub fn handle_click(resources: &mut GameResources, half: bool) {
+        let Some(pos) = resources.open_container else { return };
+        let Some(gui) = &mut resources.gui_renderer else { return };
+        let Some(BlockEntity::Chest(chest)) = resources.block_entities.get_mut(&pos) else { return };
+
+        let (mx, my) = resources.mouse_pos;
+        let (width, height) = gui.screen_size();
+        if let Some(slot) = gui.container_ref().slot_at(mx, my, width, height) {
+            let taken = if half { chest.take_half(slot) } else { chest.take(slot) };
+            if let Some(stack) = taken {
+                if let Some(rest) = gui.hotbar().insert(stack) {
+                    chest.restore(slot, rest);
+                    gui.toasts().push(tr("toast.hotbar_full"));
+                }
+            }
+        } else if let Some(slot) = gui.hotbar_ref().slot_at(mx, my, width, height) {
+            let taken = if half { gui.hotbar().take_half(slot) } else { gui.hotbar().take(slot) };
+            if let Some(stack) = taken {
+                if let Some(rest) = chest.insert(stack) {
+                    gui.hotbar().put(slot, rest);
+                    gui.toasts().push(tr("toast.chest_full"));
+                }
+            }
+        }
+        gui.container().set_slots(chest.slots());
+    }
+
+    /// Наведённый слот экрана
+    pub fn update_hover(resources: &mut GameResources) {
+        let (mx, my) = resources.mouse_pos;
+        if let Some(gui) = &mut resources.gui_renderer {
+            let (width, height) = gui.screen_size();
+            let hovered = gui.container_ref().slot_at(mx, my, width, height);
+            gui.container().set_hovered(hovered);
+        }
+    }
+
+    /// Кадр: тик сущностей блоков, анимация и автозакрытие экрана
+    pub fn update(resources: &mut GameResources, dt: f32, changes_map: &HashMap<BlockPos, BlockType>) {
+        let removed = resources.block_entities.tick(dt, |pos| changes_map.get(&pos).copied());
+        for (pos, _) in &removed {
+            log::debug!("Сущность блока удалена: {} {} {}", pos.x, pos.y, pos.z);
+        }
+
+        let Some(pos) = resources.open_container else { return };
+        let center = Vec3::new(pos.x as f32 + 0.5, pos.y as f32 + 0.5, pos.z as f32 + 0.5);
+        let too_far = (resources.player.position - center).mag() > MAX_CONTAINER_DISTANCE;
+        let lid = match resources.block_entities.get_mut(&pos) {
+            Some(BlockEntity::Chest(chest)) if !too_far => chest.lid(),
+            _ => {
+                Self::close(resources);
+                return;
+            }
+        };
+        if let Some(gui) = &mut resources.gui_renderer {
+            gui.container().set_lid(lid);
+        }
+    }
+
+    /// Игрок сломал блок: содержимое сундука - в хотбар
+    pub fn on_block_broken(resources: &mut GameResources, block_pos: [i32; 3]) {
+        let [x, y, z] = block_pos;
+        let pos = BlockPos::new(x, y, z);
+        let Some(BlockEntity::Chest(mut chest)) = resources.block_entities.remove(&pos) else { return };
+        if resources.open_container == Some(pos) {
+            Self::close(resources);
+        }
+
+        let Some(gui) = &mut resources.gui_renderer else { return };
+        let lost: u32 = chest
+            .drain()
+            .into_iter()
+            .filter_map(|stack| gui.hotbar().insert(stack))
+            .map(|rest| rest.count)
+            .sum();
+        if lost > 0 {
+            log::info!("Сундук сломан, не поместилось в хотбар: {} шт.", lost);
+            gui.toasts().push(tr_args("toast.chest_lost", &[("count", &lost)]));
+        }
+    }
+}
@@ -6,34 +6,41 @@ use std::sync::{Arc, RwLock};
 use std::time::Instant;
 use winit::window::Window;
 
-use crate::gpu::core::GameResources;
-use crate::gpu::player::Camera;
+use crate::gpu::core::{GameResources, Settings};
+use crate::gpu::player::{Camera, AttractMode, PhotoMode};
 use crate::gpu::player::{Player, PlayerController};
 use crate::gpu::render::Renderer;
 use crate::gpu::blocks::BlockBreaker;
-use crate::gpu::terrain::WorldChanges;
+use crate::gpu::block_entity::BlockEntities;
+use crate::gpu::terrain::{WorldChanges, WorldEdit};
 use crate::gpu::gui::{GameMenu, GuiRenderer};
-use crate::gpu::subvoxel::{SubVoxelStorage, SubVoxelLevel};
-use crate::gpu::subvoxel::SubVoxelRenderer;
+use crate::gpu::subvoxel::{SculptTool, SubVoxelWorld, SubVoxelLevel};
+use crate::gpu::subvoxel::OptimizedSubVoxelRenderer;
 use crate::gpu::audio::AudioSystem;
-use crate::gpu::terrain::{get_height, CaveParams, is_cave};
-use crate::gpu::blocks::AIR;
-use crate::gpu::systems::save_system::SaveSystem;
+use crate::gpu::input::{GamepadSystem, CursorState};
+use crate::gpu::weather::WeatherSystem;
+use crate::gpu::ticks::BlockTicks;
+use crate::gpu::particles::{ParticleSystem, ParticleEmitters};
+use crate::gpu::lighting::{LightOverlay, OverlayMode};
+use crate::gpu::terrain::generation::terrain_generator;
+use crate::gpu::blocks::{AIR, WATER, BlockType, is_climbable};
+use crate::gpu::systems::save_system::{SaveSystem, LoadedWorld};
 use crate::gpu::biomes::FoliageCache;
+use crate::gpu::console::{Console, GameMode};
+use crate::gpu::systems::{ConsoleSystem, MenuSystem, TorchSystem};
+use crate::gpu::resource_pack::global_resource_packs;
 
 /// Система инициализации
 pub struct InitSystem;
 
 impl InitSystem {
-    /// Создать начальные ресурсы игры
-    pub fn create_resources() -> GameResources {
-        let loaded = SaveSystem::load_or_create();
-        
+    /// Создать ресурсы игры из загруженного мира
+    pub fn create_resources(loaded: LoadedWorld, settings: Settings) -> GameResources {
         let mut player = Player::new(loaded.start_x, loaded.start_y, loaded.start_z);
         player.move_speed = 8.0;
         player.sprint_speed = 320.0; // x40 от базовой скорости
         
-        let mut player_controller = PlayerController::new(0.5);
+        let mut player_controller = PlayerController::new(settings.controls.mouse_sensitivity);
         
         // Устанавливаем функцию проверки твёрдости блока
         player_controller.set_block_solid_checker(|bx, by, bz, world_changes: &std::collections::HashMap<crate::gpu::terrain::BlockPos, crate::gpu::blocks::BlockType>| {
@@ -41,30 +48,30 @@ impl InitSystem {
             
             let pos = BlockPos::new(bx, by, bz);
             
-            // Сначала проверяем изменения мира
+            // Сначала проверяем изменения мира (через воду можно плыть)
             if let Some(&block_type) = world_changes.get(&pos) {
-                return block_type != AIR;
-            }
-            
-            // Если нет изменений - используем процедурную генерацию
-            let base_height = get_height(bx as f32, bz as f32) as i32;
-            
-            // Выше поверхности - воздух
-            if by > base_height {
-                return false;
+                return block_type != AIR && block_type != WATER;
             }
             
-            // Проверяем пещеры
-            let cave_params = CaveParams::default();
-            let cave_ceiling = base_height - cave_params.surface_offset;
-            
-            if by >= cave_params.min_height && by < cave_ceiling {
-                if is_cave(bx, by, bz, &cave_params) {
-                    return false;
-                }
+            // Если нет изменений - используем генератор мира
+            terrain_generator().block_at(bx, by, bz) != AIR
+        });
+        
+        // Вода: поставленная игроком или залитая генератором
+        player_controller.set_block_water_checker(|bx, by, bz, world_changes: &std::collections::HashMap<crate::gpu::terrain::BlockPos, crate::gpu::blocks::BlockType>| {
+            match world_changes.get(&crate::gpu::terrain::BlockPos::new(bx, by, bz)) {
+                Some(&block_type) => block_type == WATER,
+                None => terrain_generator().is_water(bx, by, bz),
             }
-            
-            true
+        });
+        
+        // Лестницы и лозы ставит только игрок - генератор их не создаёт.
+        // Флаги из реестра читаются один раз, а не на каждую проверку
+        let climbable: [bool; 256] = std::array::from_fn(|block| is_climbable(block as BlockType));
+        player_controller.set_block_climbable_checker(move |bx, by, bz, world_changes: &std::collections::HashMap<crate::gpu::terrain::BlockPos, crate::gpu::blocks::BlockType>| {
+            world_changes
+                .get(&crate::gpu::terrain::BlockPos::new(bx, by, bz))
+                .is_some_and(|&block_type| climbable[block_type as usize])
         });
         
         // Создаём хранилище изменений мира
@@ -72,8 +79,9 @@ impl InitSystem {
         SaveSystem::apply_loaded_changes(&world_changes, loaded.changes);
         
         // Создаём хранилище суб-вокселей
-        let mut subvoxel_storage_inner = SubVoxelStorage::new();
+        let mut subvoxel_storage_inner = SubVoxelWorld::new();
         SaveSystem::apply_loaded_subvoxels(&mut subvoxel_storage_inner, loaded.subvoxels);
+        let light_sources = TorchSystem::restore_lights(&subvoxel_storage_inner);
         let subvoxel_storage = Arc::new(RwLock::new(subvoxel_storage_inner));
         
         // Устанавливаем checker для коллизий с суб-вокселями
@@ -83,7 +91,14 @@ impl InitSystem {
             storage.check_aabb_collision(min_x, min_y, min_z, max_x, max_y, max_z)
         });
         
-        GameResources {
+        // Raycast по суб-вокселям для коллизии камеры
+        let subvoxel_storage_clone = Arc::clone(&subvoxel_storage);
+        player_controller.set_subvoxel_ray_checker(move |origin, direction, max_distance| {
+            let storage = subvoxel_storage_clone.read().unwrap();
+            storage.ray_distance(origin, direction, max_distance)
+        });
+        
+        let mut resources = GameResources {
             window: None,
             renderer: None,
             gui_renderer: None,
@@ -91,28 +106,61 @@ impl InitSystem {
             player,
             player_controller,
             camera: Camera::new(16.0 / 9.0),
+            attract: AttractMode::new(loaded.world_seed),
+            photo_mode: PhotoMode::new(),
             block_breaker: BlockBreaker::new(Arc::clone(&world_changes)),
             world_changes,
             subvoxel_storage,
+            block_entities: BlockEntities::from_saved(&loaded.block_entities),
+            light_sources,
+            selection: [None; 2],
+            clipboard: None,
+            world_edit: WorldEdit::new(),
+            explored_map: loaded.explored_map,
             current_subvoxel_level: SubVoxelLevel::Full,
+            sculpt_tool: SculptTool::Single,
+            sculpt_stroke: None,
             foliage_cache: FoliageCache::new(),
+            pregen: None,
+            weather: WeatherSystem::new(loaded.world_seed),
+            block_ticks: BlockTicks::new(loaded.world_seed),
+            particles: ParticleSystem::new(),
+            particle_emitters: ParticleEmitters::new(),
+            overlay_mode: OverlayMode::Off,
+            light_overlay: LightOverlay::new(),
             menu: GameMenu::new(1280, 720),
+            pending_hotbar: loaded.hotbar,
+            open_container: None,
+            console: Console::new(),
             audio_system: None,
+            settings,
+            gamepad: GamepadSystem::new(),
             start_time: Instant::now(),
             last_frame: Instant::now(),
-            cursor_grabbed: false,
+            cursor: CursorState::new(),
             mouse_pos: (0.0, 0.0),
             menu_mouse_pressed: false,
+            builder_wand_held: false,
             world_seed: loaded.world_seed,
-        }
+            world_dir: loaded.world_dir,
+            game_mode: GameMode::default(),
+            world_spawn: loaded.spawn,
+            respawn_point: loaded.respawn,
+            spawn_protection: loaded.spawn_protection,
+            is_op: true,
+            day_counter: loaded.day_counter,
+            playtime_base: loaded.playtime,
+            palette_theme: loaded.palette_theme,
+        };
+        ConsoleSystem::set_game_mode(&mut resources, loaded.game_mode);
+        resources
     }
     
-    /// Инициализация рендеринга (вызывается при resumed)
-    pub fn init_rendering(resources: &mut GameResources, window: Arc<Window>) {
-        let renderer = pollster::block_on(Renderer::new(window.clone()));
-        
-        // GUI рендерер
-        let gui_renderer = GuiRenderer::new(
+    /// Рендерер и GUI для окна (до загрузки мира - для экрана загрузки)
+    pub fn create_renderer(window: Arc<Window>, settings: &Settings) -> (Renderer, GuiRenderer) {
+        let dpi = window.scale_factor();
+        let renderer = pollster::block_on(Renderer::new(window, settings.graphics.anti_aliasing));
+        let mut gui_renderer = GuiRenderer::new(
             renderer.device(),
             renderer.queue(),
             renderer.surface_format(),
@@ -120,9 +168,32 @@ impl InitSystem {
             renderer.size().width,
             renderer.size().height,
         );
+        gui_renderer.set_dpi(renderer.queue(), dpi);
+        gui_renderer.set_ui_settings(renderer.queue(), &settings.ui);
+        if let Ok(packs) = global_resource_packs().read() {
+            gui_renderer.apply_ui_colors(&packs.ui_colors());
+        }
+        (renderer, gui_renderer)
+    }
+    
+    /// Подключить рендеринг к ресурсам загруженного мира
+    pub fn init_rendering(
+        resources: &mut GameResources,
+        window: Arc<Window>,
+        mut renderer: Renderer,
+        mut gui_renderer: GuiRenderer,
+    ) {
+        renderer.set_palette_theme(resources.palette_theme);
+        if let Some(time) = resources.day_counter.time() {
+            renderer.set_time_of_day(time);
+        }
+        
+        if let Some(saved) = resources.pending_hotbar.take() {
+            gui_renderer.hotbar().apply_saved(&saved);
+        }
         
         // Рендерер суб-вокселей
-        let subvoxel_renderer = SubVoxelRenderer::new(renderer.device());
+        let subvoxel_renderer = OptimizedSubVoxelRenderer::new(renderer.device());
         
         // Аудио система
         Self::init_audio(resources);
@@ -133,6 +204,9 @@ impl InitSystem {
         resources.renderer = Some(renderer);
         resources.gui_renderer = Some(gui_renderer);
         resources.subvoxel_renderer = Some(subvoxel_renderer);
+        
+        // Сохранённые настройки: рендерер, меню и звук уже созданы
+        MenuSystem::apply_settings(resources);
     }
     
     /// Инициализация аудио системы
@@ -140,7 +214,7 @@ impl InitSystem {
         match AudioSystem::new() {
             Ok(mut audio) => {
                 if let Err(e) = audio.load_sounds() {
-                    eprintln!("[AUDIO] Не удалось загрузить звуки: {}", e);
+                    log::warn!("Не удалось загрузить звуки: {}", e);
                 }
                 
                 // Устанавливаем функцию проверки блоков для рейтрейсинга звука
@@ -153,28 +227,14 @@ impl InitSystem {
                         }
                     }
                     
-                    // Процедурная генерация
-                    let base_height = get_height(bx as f32, bz as f32) as i32;
-                    if by > base_height {
-                        return false;
-                    }
-                    
-                    // Проверяем пещеры
-                    let cave_params = CaveParams::default();
-                    let cave_ceiling = base_height - cave_params.surface_offset;
-                    if by >= cave_params.min_height && by < cave_ceiling {
-                        if is_cave(bx, by, bz, &cave_params) {
-                            return false;
-                        }
-                    }
-                    
-                    true
+                    // Генератор мира
+                    terrain_generator().block_at(bx, by, bz) != AIR
                 });
                 
                 resources.audio_system = Some(audio);
             }
             Err(e) => {
-                eprintln!("[AUDIO] Не удалось инициализировать аудио: {}", e);
+                log::warn!("Не удалось инициализировать аудио: {}", e);
             }
         }
     }
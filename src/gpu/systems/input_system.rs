@@ -6,11 +6,14 @@ use std::sync::Arc;
 use winit::{
     event::{ElementState, KeyEvent, DeviceEvent},
     keyboard::{KeyCode, PhysicalKey},
-    window::{CursorGrabMode, Window},
 };
 
 use crate::gpu::core::GameResources;
+use crate::gpu::locale::tr;
 use crate::gpu::gui::MenuAction;
+use crate::gpu::terrain::{PregenTask, DEFAULT_PREGEN_RADIUS};
+
+use super::{ConsoleSystem, ContainerSystem, PhotoSystem, SculptSystem, WorldMapSystem};
 
 /// Система обработки клавиатуры
 pub struct InputSystem;
@@ -24,9 +27,27 @@ impl InputSystem {
     ) -> Option<InputAction> {
         let pressed = state == ElementState::Pressed;
         
+        // Фоторежим забирает клавиатуру (кроме скриншота)
+        if resources.photo_mode.is_active() {
+            Self::process_photo_key(resources, keycode, pressed);
+            return None;
+        }
+        
         match keycode {
             // Escape - открыть/закрыть меню
             KeyCode::Escape if pressed => {
+                // Открыт сундук - закрываем его
+                if ContainerSystem::is_open(resources) {
+                    ContainerSystem::close(resources);
+                    return Some(InputAction::ContainerClose);
+                }
+                
+                // Открыта карта мира - закрываем её
+                if WorldMapSystem::is_open(resources) {
+                    WorldMapSystem::close(resources);
+                    return None;
+                }
+                
                 // Если открыт инвентарь - закрываем его
                 if let Some(gui) = &mut resources.gui_renderer {
                     if gui.inventory().is_visible() {
@@ -51,7 +72,11 @@ impl InputSystem {
             
             // E - открыть/закрыть инвентарь
             KeyCode::KeyE if pressed => {
-                if !resources.menu.is_visible() {
+                if ContainerSystem::is_open(resources) {
+                    ContainerSystem::close(resources);
+                    return Some(InputAction::ContainerClose);
+                }
+                if !resources.menu.is_visible() && !WorldMapSystem::is_open(resources) {
                     if let Some(gui) = &mut resources.gui_renderer {
                         gui.inventory().toggle();
                         
@@ -69,10 +94,80 @@ impl InputSystem {
             // Q - переключить размер суб-вокселя
             KeyCode::KeyQ if pressed => {
                 resources.current_subvoxel_level = resources.current_subvoxel_level.next();
-                println!("[SUBVOXEL] Размер блока: {}", resources.current_subvoxel_level.name());
+                log::info!("Размер блока: {}", resources.current_subvoxel_level.name());
                 Some(InputAction::SubvoxelLevelChange)
             }
             
+            // B - следующая кисть лепки субвокселей
+            KeyCode::KeyB if pressed => {
+                resources.sculpt_tool = resources.sculpt_tool.next();
+                resources.sculpt_stroke = None;
+                log::info!("Кисть: {}", resources.sculpt_tool.name());
+                None
+            }
+            
+            // M - карта мира
+            KeyCode::KeyM if pressed => {
+                WorldMapSystem::toggle(resources);
+                None
+            }
+            
+            // N - масштаб миникарты (после самого мелкого - скрыть)
+            KeyCode::KeyN if pressed => {
+                if let Some(gui) = &mut resources.gui_renderer {
+                    match gui.minimap().cycle_zoom() {
+                        Some(blocks) => log::info!("Миникарта: {} блоков", blocks),
+                        None => log::info!("Миникарта скрыта"),
+                    }
+                }
+                None
+            }
+            
+            // C - разрезать блок под прицелом на субвоксели
+            KeyCode::KeyC if pressed => {
+                if resources.cursor.is_grabbed() {
+                    SculptSystem::carve_target_block(resources);
+                }
+                None
+            }
+            
+            // F2 - скриншот в PNG
+            KeyCode::F2 if pressed => {
+                Self::request_screenshot(resources);
+                None
+            }
+            
+            // F3 - окно журнала сессии
+            KeyCode::F3 if pressed => {
+                if let Some(gui) = &mut resources.gui_renderer {
+                    gui.log_viewer().toggle();
+                }
+                None
+            }
+            
+            // F4 - оверлей таймингов кадра
+            KeyCode::F4 if pressed => {
+                if let Some(gui) = &mut resources.gui_renderer {
+                    gui.profiler_overlay().toggle();
+                }
+                None
+            }
+            
+            // PageUp/PageDown - листать открытый журнал
+            KeyCode::PageUp | KeyCode::PageDown if pressed => {
+                if let Some(gui) = &mut resources.gui_renderer {
+                    let viewer = gui.log_viewer();
+                    if viewer.is_visible() {
+                        if keycode == KeyCode::PageUp {
+                            viewer.scroll_up();
+                        } else {
+                            viewer.scroll_down();
+                        }
+                    }
+                }
+                None
+            }
+            
             // F5 - переключить режим камеры
             KeyCode::F5 if pressed => {
                 resources.camera.toggle_mode();
@@ -84,6 +179,39 @@ impl InputSystem {
                 Some(InputAction::SaveWorld)
             }
             
+            // F7 - сменить цветовую тему мира
+            KeyCode::F7 if pressed => {
+                resources.palette_theme = resources.palette_theme.next();
+                log::info!("Тема: {}", resources.palette_theme.name());
+                Some(InputAction::PaletteThemeChange)
+            }
+            
+            // F8 - статистика мира вокруг игрока (консоль + JSON)
+            KeyCode::F8 if pressed => {
+                Some(InputAction::WorldStats)
+            }
+            
+            // F9 - следующий оверлей (тепловая карта освещённости)
+            KeyCode::F9 if pressed => {
+                resources.overlay_mode = resources.overlay_mode.next();
+                resources.light_overlay.invalidate();
+                log::info!("Режим: {}", resources.overlay_mode.name());
+                Some(InputAction::OverlayModeChange)
+            }
+            
+            // F10 - прегенерация вокруг игрока (повторно - отмена)
+            KeyCode::F10 if pressed => {
+                if let Some(task) = resources.pregen.take() {
+                    let (done, total) = task.progress();
+                    log::info!("Отмена на {}/{}", done, total);
+                    task.cancel();
+                } else {
+                    let pos = resources.player.position;
+                    resources.pregen = Some(PregenTask::start(pos.x, pos.z, DEFAULT_PREGEN_RADIUS, true));
+                }
+                None
+            }
+            
             // +/- для дистанции камеры
             KeyCode::Equal | KeyCode::NumpadAdd if pressed => {
                 resources.camera.third_person_distance = 
@@ -96,9 +224,14 @@ impl InputSystem {
                 None
             }
             
-            // T - переключить время
-            KeyCode::KeyT if pressed => {
-                Some(InputAction::CycleTime)
+            // T или '/' - консоль команд (время суток - /time set)
+            KeyCode::KeyT | KeyCode::Slash if pressed => {
+                let inventory_open = resources.gui_renderer.as_ref().is_some_and(|gui| gui.inventory_ref().is_visible());
+                if !resources.menu.is_visible() && !inventory_open && !ContainerSystem::is_open(resources) && !WorldMapSystem::is_open(resources) {
+                    let prefix = if keycode == KeyCode::Slash { "/" } else { "" };
+                    ConsoleSystem::open(resources, prefix);
+                }
+                None
             }
             
             // [ и ] - скорость времени
@@ -109,6 +242,12 @@ impl InputSystem {
                 Some(InputAction::FastTime)
             }
             
+            // Alt - модификатор палочки строителя (Alt + ПКМ)
+            KeyCode::AltLeft | KeyCode::AltRight => {
+                resources.builder_wand_held = pressed;
+                None
+            }
+            
             // Клавиши 1-9 для хотбара
             _ => {
                 if !resources.menu.is_visible() {
@@ -140,10 +279,67 @@ impl InputSystem {
         }
     }
     
-    /// Обработка движения мыши
+    /// Клавиши фоторежима: Esc - выход, F2 - скриншот, остальное - камере
+    fn process_photo_key(resources: &mut GameResources, keycode: KeyCode, pressed: bool) {
+        match keycode {
+            KeyCode::Escape if pressed => PhotoSystem::exit(resources),
+            KeyCode::F2 if pressed => Self::request_screenshot(resources),
+            KeyCode::KeyF if pressed => {
+                resources.photo_mode.handle_key(keycode, pressed);
+                let enabled = resources.photo_mode.depth_of_field_enabled();
+                if let Some(gui) = &mut resources.gui_renderer {
+                    gui.toasts().push(tr(if enabled { "toast.depth_of_field_on" } else { "toast.depth_of_field_off" }));
+                }
+            }
+            _ => {
+                resources.photo_mode.handle_key(keycode, pressed);
+            }
+        }
+    }
+    
+    /// Скриншот в PNG (F2)
+    fn request_screenshot(resources: &mut GameResources) {
+        if let Some(renderer) = &mut resources.renderer {
+            if let Err(e) = renderer.request_screenshot() {
+                log::warn!("Скриншот недоступен: {}", e);
+            }
+        }
+    }
+    
+    /// Обработка движения мыши (сырой ввод)
     pub fn process_mouse_motion(resources: &mut GameResources, delta: (f64, f64)) {
-        if resources.cursor_grabbed && !resources.menu.is_visible() {
-            resources.player_controller.process_mouse(delta.0, delta.1);
+        if resources.cursor.raw_input() && resources.cursor.is_grabbed() && !resources.menu.is_visible() {
+            if resources.photo_mode.is_active() {
+                resources.photo_mode.process_mouse(delta.0, delta.1);
+            } else {
+                resources.player_controller.process_mouse(delta.0, delta.1);
+            }
+        }
+    }
+    
+    /// Движение курсора: без сырого ввода - смещение от центра окна
+    pub fn process_cursor_moved(resources: &mut GameResources, x: f64, y: f64) {
+        // GUI размечен в пикселях интерфейса (масштаб, безопасная зона)
+        resources.mouse_pos = match &resources.gui_renderer {
+            Some(gui) => gui.ui_scale().to_ui(x as f32, y as f32),
+            None => (x as f32, y as f32),
+        };
+        if resources.menu.is_visible() {
+            return;
+        }
+        // Открытая карта мира тянется за курсором
+        if let Some(gui) = &mut resources.gui_renderer {
+            if gui.world_map_ref().is_visible() {
+                gui.world_map().move_cursor(resources.mouse_pos.0, resources.mouse_pos.1);
+                return;
+            }
+        }
+        if let Some((dx, dy)) = resources.cursor.cursor_delta(resources.window.as_deref(), x, y) {
+            if resources.photo_mode.is_active() {
+                resources.photo_mode.process_mouse(dx, dy);
+            } else {
+                resources.player_controller.process_mouse(dx, dy);
+            }
         }
     }
     
@@ -155,9 +351,19 @@ impl InputSystem {
         };
         
         if scroll != 0 {
-            // Если открыт инвентарь - скроллим его
+            // Фоторежим: колесо меняет FOV
+            if resources.photo_mode.is_active() {
+                resources.photo_mode.zoom(scroll as f32);
+                return;
+            }
+            
+            // Если открыт инвентарь - скроллим его, карта мира - масштабируется
             // scroll > 0 когда крутим вверх, < 0 когда вниз
             if let Some(gui) = &mut resources.gui_renderer {
+                if gui.world_map_ref().is_visible() {
+                    gui.world_map().scroll(scroll as f32);
+                    return;
+                }
                 if gui.inventory().is_visible() {
                     gui.inventory().scroll_by(scroll as f32 * 0.5);
                     return;
@@ -165,7 +371,7 @@ impl InputSystem {
             }
             
             // Иначе скроллим хотбар
-            if resources.cursor_grabbed && !resources.menu.is_visible() {
+            if resources.cursor.is_grabbed() && !resources.menu.is_visible() {
                 if let Some(gui) = &mut resources.gui_renderer {
                     gui.hotbar().scroll(-scroll);
                 }
@@ -173,19 +379,26 @@ impl InputSystem {
         }
     }
     
-    /// Захват/освобождение курсора
+    /// Захват (игра) / освобождение (меню, инвентарь, консоль) курсора
     pub fn grab_cursor(resources: &mut GameResources, grab: bool) {
-        if let Some(window) = &resources.window {
-            resources.cursor_grabbed = grab;
-            if grab {
-                let _ = window.set_cursor_grab(CursorGrabMode::Confined)
-                    .or_else(|_| window.set_cursor_grab(CursorGrabMode::Locked));
-                window.set_cursor_visible(false);
-            } else {
-                let _ = window.set_cursor_grab(CursorGrabMode::None);
-                window.set_cursor_visible(true);
-            }
+        resources.cursor.set_gameplay(resources.window.as_deref(), grab);
+    }
+    
+    /// Фокус окна: без фокуса (Alt+Tab) курсор отпускается, а при
+    /// возврате снова захватывается, если открыта игра
+    pub fn focus_changed(resources: &mut GameResources, focused: bool) {
+        if !focused {
+            // Отпускание клавиш в другом окне не придёт
+            resources.player_controller.release_keys();
+            resources.block_breaker.stop_breaking();
+            resources.builder_wand_held = false;
         }
+        resources.cursor.set_focused(resources.window.as_deref(), focused);
+    }
+    
+    /// Повторить неудавшийся захват курсора (клик в окне)
+    pub fn regrab_cursor(resources: &mut GameResources) {
+        resources.cursor.apply(resources.window.as_deref());
     }
 }
 
@@ -194,10 +407,13 @@ impl InputSystem {
 pub enum InputAction {
     MenuToggle,
     InventoryToggle,
+    ContainerClose,
     SubvoxelLevelChange,
     CameraToggle,
     SaveWorld,
-    CycleTime,
+    PaletteThemeChange,
     SlowTime,
     FastTime,
+    WorldStats,
+    OverlayModeChange,
 }
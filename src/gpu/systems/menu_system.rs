@@ -4,10 +4,15 @@
 
 use winit::event_loop::ActiveEventLoop;
 
-use crate::gpu::core::GameResources;
+use crate::gpu::core::{GameResources, Settings, LodPreset, lod_distance_to_slider};
 use crate::gpu::gui::MenuAction;
 use crate::gpu::systems::input_system::InputSystem;
 use crate::gpu::systems::save_system::SaveSystem;
+use crate::gpu::systems::console_system::ConsoleSystem;
+use crate::gpu::systems::{ContainerSystem, PhotoSystem};
+use crate::gpu::items::ItemStack;
+use crate::gpu::resource_pack::global_resource_packs;
+use crate::gpu::input::HapticEvent;
 
 /// Система обработки меню
 pub struct MenuSystem;
@@ -42,7 +47,7 @@ impl MenuSystem {
         if let Some(gui) = &mut resources.gui_renderer {
             if gui.inventory_ref().is_visible() {
                 // Проверяем есть ли перетаскиваемый блок
-                if let Some(block_type) = gui.inventory().dragging() {
+                if let Some(item) = gui.inventory().dragging() {
                     let mx = resources.mouse_pos.0;
                     let my = resources.mouse_pos.1;
                     
@@ -50,9 +55,10 @@ impl MenuSystem {
                     let (screen_w, screen_h) = gui.screen_size();
                     
                     if gui.hotbar().handle_click(mx, my, screen_w, screen_h) {
-                        // Кликнули на слот хотбара - добавляем туда блок
+                        // Кликнули на слот хотбара - кладём туда полную стопку
+                        // (тот же предмет доливается до максимума)
                         let selected_slot = gui.hotbar().selected();
-                        gui.hotbar().set_item(selected_slot, Some(crate::gpu::gui::hotbar::HotbarItem::from_block(block_type)));
+                        gui.hotbar().put(selected_slot, ItemStack::full(item));
                     }
                     
                     // Завершаем перетаскивание
@@ -64,6 +70,17 @@ impl MenuSystem {
         should_grab_cursor
     }
     
+    /// ПКМ по слоту хотбара при открытом инвентаре: разделить стопку
+    pub fn handle_split(resources: &mut GameResources) {
+        let (mx, my) = resources.mouse_pos;
+        if let Some(gui) = &mut resources.gui_renderer {
+            let (screen_w, screen_h) = gui.screen_size();
+            if let Some(slot) = gui.hotbar_ref().slot_at(mx, my, screen_w, screen_h) {
+                gui.hotbar().split(slot);
+            }
+        }
+    }
+    
     /// Обработка клика по меню или инвентарю (legacy - для совместимости)
     pub fn handle_click(
         resources: &mut GameResources,
@@ -80,9 +97,9 @@ impl MenuSystem {
                 
                 // Проверяем клик по слоту
                 if let Some(slot_index) = slot_at {
-                    let block_type = gui.inventory().handle_click(slot_index);
-                    if let Some(bt) = block_type {
-                        gui.hotbar().pick_block(bt);
+                    let item = gui.inventory().handle_click(slot_index);
+                    if let Some(item) = item {
+                        gui.hotbar().pick_item(item);
                         Some(true) // Нужно grab cursor
                     } else {
                         Some(false)
@@ -117,6 +134,10 @@ impl MenuSystem {
             resources.menu.process_click(resources.mouse_pos.0, resources.mouse_pos.1)
         };
         
+        if action != MenuAction::None {
+            resources.gamepad.play(HapticEvent::UiConfirm);
+        }
+        
         match action {
             MenuAction::Resume => {
                 resources.menu.hide();
@@ -126,8 +147,142 @@ impl MenuSystem {
                 InputSystem::grab_cursor(resources, true);
                 false
             }
+            MenuAction::PhotoMode => {
+                PhotoSystem::enter(resources);
+                false
+            }
             MenuAction::SaveSettings => {
-                Self::apply_lod_settings(resources);
+                Self::collect_settings(resources);
+                Self::apply_settings(resources);
+                let path = Settings::path();
+                match resources.settings.save() {
+                    Ok(()) => log::info!("Настройки сохранены в {}", path.display()),
+                    Err(e) => log::error!("Ошибка записи {}: {}", path.display(), e),
+                }
+                false
+            }
+            MenuAction::ResourcePacks => {
+                if let Ok(mut packs) = global_resource_packs().write() {
+                    packs.rescan();
+                }
+                Self::refresh_pack_list(resources);
+                false
+            }
+            MenuAction::TogglePack(index) | MenuAction::MovePackUp(index) | MenuAction::MovePackDown(index) => {
+                if let Ok(mut packs) = global_resource_packs().write() {
+                    if let Some((name, _)) = packs.list().get(index).cloned() {
+                        match action {
+                            MenuAction::TogglePack(_) => packs.toggle(&name),
+                            MenuAction::MovePackUp(_) => packs.move_up(&name),
+                            _ => packs.move_down(&name),
+                        }
+                    }
+                }
+                Self::refresh_pack_list(resources);
+                false
+            }
+            MenuAction::ApplyPacks => {
+                Self::apply_resource_packs(resources);
+                false
+            }
+            MenuAction::CycleTextureFilter
+            | MenuAction::CycleShadowFilter
+            | MenuAction::CycleAnisotropy
+            | MenuAction::CycleMipBias => {
+                Self::cycle_sampling(resources, action);
+                false
+            }
+            MenuAction::ToggleWaterReflections => {
+                // Применяется сразу, как и фильтрация; в файл - по Save
+                if let Some(renderer) = &mut resources.renderer {
+                    let enabled = !renderer.water_reflections();
+                    renderer.set_water_reflections(enabled);
+                    log::info!("Отражения в воде: {}", if enabled { "вкл" } else { "выкл" });
+                    if let Some(gui) = &mut resources.gui_renderer {
+                        gui.menu_system().set_water_reflections(enabled);
+                    }
+                }
+                false
+            }
+            MenuAction::CycleClouds => {
+                // Применяется сразу, как и отражения; в файл - по Save
+                if let Some(renderer) = &mut resources.renderer {
+                    let quality = renderer.cloud_quality().next();
+                    renderer.set_cloud_quality(quality);
+                    log::info!("Облака: {}", quality.name());
+                    if let Some(gui) = &mut resources.gui_renderer {
+                        gui.menu_system().set_clouds(quality);
+                    }
+                }
+                false
+            }
+            MenuAction::ToggleBloom
+            | MenuAction::ToggleTonemapping
+            | MenuAction::ToggleVignette
+            | MenuAction::CycleExposure => {
+                Self::cycle_post(resources, action);
+                false
+            }
+            MenuAction::CycleAntiAliasing => {
+                // FXAA применяется сразу, MSAA - после перезапуска; в файл - по Save
+                if let Some(renderer) = &mut resources.renderer {
+                    let anti_aliasing = renderer.anti_aliasing().next();
+                    renderer.set_anti_aliasing(anti_aliasing);
+                    log::info!("Сглаживание: {}", anti_aliasing.name());
+                    if let Some(gui) = &mut resources.gui_renderer {
+                        gui.menu_system().set_anti_aliasing(anti_aliasing);
+                    }
+                }
+                false
+            }
+            MenuAction::ToggleDynamicResolution => {
+                // Переключается сразу, масштаб со слайдера - по Save
+                if let Some(renderer) = &mut resources.renderer {
+                    let mut resolution = renderer.resolution();
+                    resolution.dynamic = !resolution.dynamic;
+                    renderer.set_resolution(resolution);
+                    log::info!("Динамическое разрешение: {}", if resolution.dynamic { "вкл" } else { "выкл" });
+                    if let Some(gui) = &mut resources.gui_renderer {
+                        gui.menu_system().set_resolution(&resolution);
+                    }
+                }
+                false
+            }
+            MenuAction::CycleLodPreset => {
+                // Из Custom начинаем с нижнего уровня; применяется по Save
+                if let Some(gui) = &mut resources.gui_renderer {
+                    let menu = gui.menu_system();
+                    let preset = menu.get_lod_preset().map_or(LodPreset::Low, LodPreset::next);
+                    menu.set_lod_preset(preset);
+                }
+                false
+            }
+            MenuAction::ToggleLodAdvanced => {
+                if let Some(gui) = &mut resources.gui_renderer {
+                    gui.menu_system().toggle_lod_advanced();
+                }
+                false
+            }
+            MenuAction::ToggleHaptic(event) => {
+                resources.gamepad.settings.toggle(event);
+                if let Some(gui) = &mut resources.gui_renderer {
+                    gui.menu_system().set_haptics(&resources.gamepad.settings);
+                }
+                false
+            }
+            MenuAction::ToggleInvertY | MenuAction::ToggleRawInput => {
+                // Применяется сразу, как и вибрация; в файл - по Save
+                if action == MenuAction::ToggleInvertY {
+                    resources.player_controller.invert_y = !resources.player_controller.invert_y;
+                } else {
+                    let raw_input = !resources.cursor.raw_input();
+                    resources.cursor.set_raw_input(resources.window.as_deref(), raw_input);
+                }
+                let (invert_y, raw_input) = (resources.player_controller.invert_y, resources.cursor.raw_input());
+                if let Some(gui) = &mut resources.gui_renderer {
+                    let menu = gui.menu_system();
+                    menu.set_mouse(menu.get_mouse_sensitivity(), invert_y, raw_input);
+                }
                 false
             }
             MenuAction::QuitToDesktop => {
@@ -141,6 +296,11 @@ impl MenuSystem {
     
     /// Обновление hover состояния меню и инвентаря
     pub fn update_hover(resources: &mut GameResources) {
+        if ContainerSystem::is_open(resources) {
+            ContainerSystem::update_hover(resources);
+            return;
+        }
+        
         // Обновляем инвентарь
         if let Some(gui) = &mut resources.gui_renderer {
             if gui.inventory_ref().is_visible() {
@@ -171,24 +331,181 @@ impl MenuSystem {
         }
     }
     
-    /// Применение настроек LOD
-    fn apply_lod_settings(resources: &mut GameResources) {
-        let distances = if let Some(gui) = &mut resources.gui_renderer {
-            let lod_values = gui.menu_system().get_lod_values();
-            // Конвертируем 0-1 в дистанции чанков (4-64)
-            Some([
-                (lod_values[0] * 60.0 + 4.0) as i32,
-                (lod_values[1] * 60.0 + 4.0) as i32,
-                (lod_values[2] * 60.0 + 4.0) as i32,
-                (lod_values[3] * 60.0 + 4.0) as i32,
-            ])
-        } else {
-            None
+    /// Обновить список пакетов на экране пакетов ресурсов
+    fn refresh_pack_list(resources: &mut GameResources) {
+        let list = global_resource_packs().read().map(|packs| packs.list()).unwrap_or_default();
+        if let Some(gui) = &mut resources.gui_renderer {
+            gui.menu_system().set_resource_packs(list);
+        }
+    }
+    
+    /// Применить пакеты ресурсов: атлас, звуки, цвета UI
+    fn apply_resource_packs(resources: &mut GameResources) {
+        let ui_colors = match global_resource_packs().read() {
+            Ok(packs) => {
+                packs.save_options();
+                packs.ui_colors()
+            }
+            Err(_) => return,
         };
         
-        if let (Some(distances), Some(renderer)) = (distances, &mut resources.renderer) {
-            renderer.set_lod_distances(distances);
-            println!("[LOD] Applied distances: {:?}", distances);
+        if let Some(renderer) = &mut resources.renderer {
+            renderer.reload_block_atlas();
+        }
+        if let Some(gui) = &mut resources.gui_renderer {
+            gui.apply_ui_colors(&ui_colors);
+        }
+        if let Some(audio) = &mut resources.audio_system {
+            if let Err(e) = audio.load_sounds() {
+                log::warn!("Не удалось перезагрузить звуки: {}", e);
+            }
+        }
+        log::info!("Пакеты ресурсов применены");
+    }
+    
+    /// Переключить параметр фильтрации текстур и применить к рендереру
+    fn cycle_sampling(resources: &mut GameResources, action: MenuAction) {
+        let Some(renderer) = &mut resources.renderer else { return };
+        let mut sampling = renderer.sampling();
+        match action {
+            MenuAction::CycleTextureFilter => sampling.filter = sampling.filter.next(),
+            MenuAction::CycleShadowFilter => sampling.shadow_filter = sampling.shadow_filter.next(),
+            MenuAction::CycleAnisotropy => sampling.anisotropy = sampling.next_anisotropy(),
+            MenuAction::CycleMipBias => sampling.mip_bias = sampling.next_mip_bias(),
+            _ => return,
+        }
+        renderer.set_sampling(sampling);
+        log::info!("Фильтрация: {:?}", sampling);
+        
+        if let Some(gui) = &mut resources.gui_renderer {
+            gui.menu_system().set_sampling(&sampling);
+        }
+    }
+    
+    /// Переключить параметр постобработки и применить к рендереру
+    fn cycle_post(resources: &mut GameResources, action: MenuAction) {
+        let Some(renderer) = &mut resources.renderer else { return };
+        let mut post = renderer.post();
+        match action {
+            MenuAction::ToggleBloom => post.bloom = !post.bloom,
+            MenuAction::ToggleTonemapping => post.tonemapping = !post.tonemapping,
+            MenuAction::ToggleVignette => post.vignette = !post.vignette,
+            MenuAction::CycleExposure => post.exposure = post.next_exposure(),
+            _ => return,
+        }
+        renderer.set_post(post);
+        log::info!("Постобработка: {:?}", post);
+        
+        if let Some(gui) = &mut resources.gui_renderer {
+            gui.menu_system().set_post(&post);
+        }
+    }
+    
+    /// Забрать значения из меню настроек в resources.settings
+    fn collect_settings(resources: &mut GameResources) {
+        let settings = &mut resources.settings;
+        if let Some(gui) = &mut resources.gui_renderer {
+            let menu = gui.menu_system();
+            settings.graphics.lod_sliders = menu.get_lod_values();
+            settings.graphics.lod_quality = menu.get_lod_quality();
+            settings.graphics.vertical_distance = menu.get_vertical_distance();
+            settings.graphics.fov = menu.get_fov();
+            settings.graphics.resolution.scale = menu.get_render_scale();
+            settings.controls.mouse_sensitivity = menu.get_mouse_sensitivity();
+            resources.gamepad.settings.intensity = menu.get_haptic_intensity();
+        }
+        if let Some(renderer) = &resources.renderer {
+            settings.graphics.sampling = renderer.sampling();
+            settings.graphics.water_reflections = renderer.water_reflections();
+            settings.graphics.clouds = renderer.cloud_quality();
+            settings.graphics.post = renderer.post();
+            settings.graphics.anti_aliasing = renderer.anti_aliasing();
+            settings.graphics.resolution.dynamic = renderer.resolution().dynamic;
+        }
+        // Переключатели вибрации и мыши применяются сразу, берём текущие
+        settings.controls.haptics = resources.gamepad.settings;
+        settings.controls.invert_y = resources.player_controller.invert_y;
+        settings.controls.raw_input = resources.cursor.raw_input();
+    }
+    
+    /// Применить resources.settings к рендереру, камере, звуку и меню
+    /// (при старте и по кнопке Save)
+    pub fn apply_settings(resources: &mut GameResources) {
+        let settings = resources.settings.clone();
+        let graphics = &settings.graphics;
+        // Рендерер может урезать дистанции под видеокарту - меню показывает применённые
+        let mut lod_sliders = graphics.lod_sliders;
+        
+        if let Some(renderer) = &mut resources.renderer {
+            let distances = renderer.set_lod_distances(graphics.lod_distances());
+            lod_sliders = distances.map(lod_distance_to_slider);
+            renderer.set_lod_quality(graphics.lod_quality);
+            renderer.set_vertical_distance(graphics.vertical_distance);
+            renderer.set_water_reflections(graphics.water_reflections);
+            renderer.set_cloud_quality(graphics.clouds);
+            renderer.set_fog(graphics.fog);
+            renderer.set_post(graphics.post);
+            renderer.set_anti_aliasing(graphics.anti_aliasing);
+            renderer.set_resolution(graphics.resolution);
+            if renderer.sampling() != graphics.sampling {
+                renderer.set_sampling(graphics.sampling);
+            }
+            log::info!("Applied distances: {:?}", distances);
+            log::info!("Far LOD quality: {}%", (graphics.lod_quality * 100.0).round() as i32);
+            match graphics.vertical_distance {
+                Some(sections) => log::info!("Vertical distance: {} sections", sections),
+                None => log::info!("Vertical distance: full column"),
+            }
+        }
+        
+        ConsoleSystem::apply_time_speed(resources);
+        resources.camera.set_base_fov_degrees(graphics.fov);
+        resources.player_controller.sensitivity = settings.controls.mouse_sensitivity;
+        resources.player_controller.invert_y = settings.controls.invert_y;
+        resources.cursor.set_raw_input(resources.window.as_deref(), settings.controls.raw_input);
+        resources.player_controller.sneak_key = settings.controls.sneak_key;
+        resources.cursor.set_lock(resources.window.as_deref(), settings.controls.cursor_lock);
+        resources.gamepad.settings = settings.controls.haptics;
+        if let Some(audio) = &mut resources.audio_system {
+            audio.set_master_volume(settings.audio.master_volume);
+        }
+        
+        if let Some(gui) = &mut resources.gui_renderer {
+            if let Some(renderer) = &resources.renderer {
+                gui.set_ui_settings(renderer.queue(), &settings.ui);
+            }
+            let menu = gui.menu_system();
+            menu.set_lod_values(lod_sliders);
+            menu.set_lod_quality(graphics.lod_quality);
+            menu.set_vertical_distance(graphics.vertical_distance);
+            menu.set_fov(graphics.fov);
+            menu.set_sampling(&graphics.sampling);
+            menu.set_water_reflections(graphics.water_reflections);
+            menu.set_clouds(graphics.clouds);
+            menu.set_post(&graphics.post);
+            menu.set_anti_aliasing(graphics.anti_aliasing);
+            menu.set_resolution(&graphics.resolution);
+            menu.set_haptics(&settings.controls.haptics);
+            menu.set_mouse(settings.controls.mouse_sensitivity, settings.controls.invert_y, settings.controls.raw_input);
+        }
+    }
+    
+    /// Перевести подписи меню после смены языка: значения - текущие
+    /// (применённые, но, может быть, ещё не сохранённые)
+    pub fn relabel(resources: &mut GameResources) {
+        let Some(gui) = &mut resources.gui_renderer else { return };
+        let menu = gui.menu_system();
+        menu.relabel();
+        if let Some(renderer) = &resources.renderer {
+            menu.set_sampling(&renderer.sampling());
+            menu.set_water_reflections(renderer.water_reflections());
+            menu.set_clouds(renderer.cloud_quality());
+            menu.set_post(&renderer.post());
+            menu.set_anti_aliasing(renderer.anti_aliasing());
+            menu.set_resolution(&renderer.resolution());
         }
+        menu.set_haptics(&resources.gamepad.settings);
+        let sensitivity = menu.get_mouse_sensitivity();
+        menu.set_mouse(sensitivity, resources.player_controller.invert_y, resources.cursor.raw_input());
     }
 }
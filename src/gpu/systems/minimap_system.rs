@@ -0,0 +1,124 @@
+// ============================================
+// Minimap System - Растеризация чанков для миникарты
+// ============================================
+// Чанки с новыми воксельными мешами (от рендерера) и чанки, изменённые
+// игроком (грязные чанки WorldChanges), ставятся в очередь миникарты.
+// За кадр растеризуется несколько ближайших: для каждого столбца ищется
+// верхний блок (изменения мира поверх генератора), его цвет верхней
+// грани затеняется по высоте северного соседа и воде над ним.
+// Готовые цвета запоминаются и в разведанной карте мира.
+
+use std::collections::HashMap;
+
+use crate::gpu::blocks::{get_block_color, BlockType, AIR};
+use crate::gpu::core::GameResources;
+use crate::gpu::gui::{column_color, ChunkColors, CHUNK_TEXELS};
+use crate::gpu::terrain::generation::{terrain_generator, world_gen_config, TerrainGenerator};
+use crate::gpu::terrain::{WorldChanges, CHUNK_SIZE, MIN_HEIGHT};
+
+/// Чанков растеризуется за кадр
+const CHUNKS_PER_FRAME: usize = 4;
+/// Глубже поверхности верхний блок не ищется
+const SCAN_DEPTH: i32 = 24;
+/// Столбец без блоков (пустота)
+const VOID_COLOR: [u8; 4] = [12, 12, 16, 255];
+
+/// Система миникарты
+pub struct MinimapSystem;
+
+impl MinimapSystem {
+    /// Кадр: новые и изменённые чанки в очередь, несколько - на карту
+    pub fn update(resources: &mut GameResources) {
+        let loaded = resources.renderer.as_mut().map(|renderer| renderer.take_loaded_columns()).unwrap_or_default();
+        let changed = resources.world_changes.write().unwrap().take_dirty_chunks();
+        let Some(gui) = &mut resources.gui_renderer else { return };
+
+        let position = resources.player.position;
+        let center = (
+            (position.x.floor() as i32).div_euclid(CHUNK_SIZE),
+            (position.z.floor() as i32).div_euclid(CHUNK_SIZE),
+        );
+        let minimap = gui.minimap();
+        minimap.update(position, resources.player.yaw);
+        let tiles = minimap.tiles();
+        for chunk in loaded {
+            tiles.mark_loaded(chunk);
+        }
+        for chunk in changed {
+            tiles.mark_changed(chunk);
+        }
+        tiles.evict(center);
+
+        let chunks = tiles.take_pending(center, CHUNKS_PER_FRAME);
+        if chunks.is_empty() {
+            return;
+        }
+        let changes = resources.world_changes.read().unwrap();
+        // Самое высокое изменение столбца (поставленные выше рельефа блоки)
+        // для всех чанков кадра - одним проходом по изменениям
+        let mut highest: HashMap<(i32, i32), i32> = HashMap::new();
+        for (pos, &block) in changes.iter() {
+            let (chunk_x, chunk_z) = pos.chunk_key();
+            if block != AIR && chunks.iter().any(|&chunk| chunk == (chunk_x, chunk_z) || chunk == (chunk_x, chunk_z + 1)) {
+                let top = highest.entry((pos.x, pos.z)).or_insert(pos.y);
+                *top = (*top).max(pos.y);
+            }
+        }
+
+        let generator = terrain_generator();
+        for &(chunk_x, chunk_z) in &chunks {
+            let colors = Self::rasterize(chunk_x, chunk_z, generator, &changes, &highest);
+            resources.explored_map.explore((chunk_x, chunk_z), &colors);
+            tiles.store((chunk_x, chunk_z), colors);
+        }
+        let world_map = gui.world_map();
+        for chunk in chunks {
+            world_map.invalidate(chunk);
+        }
+    }
+
+    /// Цвета столбцов чанка
+    fn rasterize(
+        chunk_x: i32,
+        chunk_z: i32,
+        generator: &dyn TerrainGenerator,
+        changes: &WorldChanges,
+        highest: &HashMap<(i32, i32), i32>,
+    ) -> ChunkColors {
+        let change_at = |x: i32, y: i32, z: i32| changes.get_block(x, y, z);
+        let sea_level = world_gen_config().sea_level;
+        let (base_x, base_z) = (chunk_x * CHUNK_SIZE, chunk_z * CHUNK_SIZE);
+        let top_block = |x: i32, z: i32| -> Option<(i32, BlockType)> {
+            let ground = generator.height(x as f32, z as f32) as i32;
+            let start = ground.max(highest.get(&(x, z)).copied().unwrap_or(i32::MIN));
+            (start - SCAN_DEPTH..=start)
+                .rev()
+                .filter(|&y| y >= MIN_HEIGHT)
+                .map(|y| (y, change_at(x, y, z).unwrap_or_else(|| generator.block_at(x, y, z))))
+                .find(|&(_, block)| block != AIR)
+        };
+
+        let mut colors: ChunkColors = [VOID_COLOR; CHUNK_TEXELS];
+        // Высоты предыдущей (северной) строки
+        let mut north: Vec<i32> = (0..CHUNK_SIZE)
+            .map(|lx| top_block(base_x + lx, base_z - 1).map_or(MIN_HEIGHT, |(y, _)| y))
+            .collect();
+        for lz in 0..CHUNK_SIZE {
+            for lx in 0..CHUNK_SIZE {
+                let (x, z) = (base_x + lx, base_z + lz);
+                let north_height = north[lx as usize];
+                let Some((y, block)) = top_block(x, z) else {
+                    north[lx as usize] = MIN_HEIGHT;
+                    continue;
+                };
+                let flooded = y + 1 < sea_level
+                    && change_at(x, y + 1, z).is_none()
+                    && generator.is_water(x, y + 1, z);
+                let water_depth = if flooded { sea_level - 1 - y } else { 0 };
+                colors[(lz * CHUNK_SIZE + lx) as usize] = column_color(get_block_color(block), y, north_height, water_depth);
+                north[lx as usize] = y;
+            }
+        }
+        colors
+    }
+}
@@ -9,11 +9,31 @@ mod save_system;
 mod update_system;
 mod render_system;
 mod init_system;
+mod console_system;
+mod spawn_system;
+mod container_system;
+mod torch_system;
+mod schematic_system;
+mod sculpt_system;
+mod photo_system;
+mod world_edit_system;
+mod minimap_system;
+mod world_map_system;
 
 pub use input_system::{InputSystem, InputAction};
 pub use block_interaction_system::BlockInteractionSystem;
 pub use menu_system::MenuSystem;
-pub use save_system::SaveSystem;
+pub use save_system::{SaveSystem, LoadedWorld};
 pub use update_system::UpdateSystem;
 pub use render_system::RenderSystem;
 pub use init_system::InitSystem;
+pub use console_system::ConsoleSystem;
+pub use spawn_system::SpawnSystem;
+pub use container_system::ContainerSystem;
+pub use torch_system::TorchSystem;
+pub use schematic_system::SchematicSystem;
+pub use sculpt_system::SculptSystem;
+pub use photo_system::PhotoSystem;
+pub use world_edit_system::WorldEditSystem;
+pub use minimap_system::MinimapSystem;
+pub use world_map_system::WorldMapSystem;
@@ -0,0 +1,66 @@
+// ============================================
+// Photo System - Фоторежим
+// ============================================
+// Пока фоторежим активен, UpdateSystem не тикает мир (игрок, погода,
+// тики блоков, частицы стоят), а время суток не идёт само - его
+// перематывают [ и ]. При выходе время суток и его скорость
+// возвращаются, камера снова следует за игроком.
+
+use crate::gpu::core::GameResources;
+use crate::gpu::locale::tr;
+
+use super::{ConsoleSystem, InputSystem};
+
+/// Система фоторежима
+pub struct PhotoSystem;
+
+impl PhotoSystem {
+    /// Войти из меню паузы
+    pub fn enter(resources: &mut GameResources) {
+        resources.menu.hide();
+        if let Some(gui) = &mut resources.gui_renderer {
+            gui.menu_system().hide();
+        }
+        InputSystem::grab_cursor(resources, true);
+        resources.player_controller.release_keys();
+        resources.block_breaker.stop_breaking();
+
+        let time_of_day = resources.renderer.as_ref().map_or(0.0, |renderer| renderer.time_of_day());
+        resources.photo_mode.enter(&resources.camera, time_of_day);
+        if let Some(renderer) = &mut resources.renderer {
+            renderer.set_time_speed(0.0);
+        }
+        resources.console.info("Фоторежим: мир на паузе, Esc - выход");
+        if let Some(gui) = &mut resources.gui_renderer {
+            gui.toasts().push(tr("toast.photo_mode"));
+        }
+    }
+
+    /// Выйти (Esc): время и камера как до входа
+    pub fn exit(resources: &mut GameResources) {
+        let time_of_day = resources.photo_mode.exit(&mut resources.camera);
+        if let Some(renderer) = &mut resources.renderer {
+            renderer.set_time_of_day(time_of_day);
+            renderer.set_depth_of_field(None);
+        }
+        ConsoleSystem::apply_time_speed(resources);
+    }
+
+    /// Кадр фоторежима вместо тика мира
+    pub fn update(resources: &mut GameResources, dt: f32) {
+        let sensitivity = resources.player_controller.sensitivity;
+        let invert_y = resources.player_controller.invert_y;
+        let scrub = resources.photo_mode.update(dt, sensitivity, invert_y, &mut resources.camera);
+
+        let changes = resources.world_changes.read().unwrap().get_all_changes_copy();
+        resources.camera.underwater = resources.player_controller.is_in_water(resources.camera.position, &changes);
+
+        let depth_of_field = resources.photo_mode.dof();
+        if let Some(renderer) = &mut resources.renderer {
+            if scrub != 0.0 {
+                renderer.set_time_of_day((renderer.time_of_day() + scrub).rem_euclid(1.0));
+            }
+            renderer.set_depth_of_field(depth_of_field);
+        }
+    }
+}
@@ -5,8 +5,11 @@
 use winit::event_loop::ActiveEventLoop;
 
 use crate::gpu::core::GameResources;
-use crate::gpu::subvoxel::SubVoxelLevel;
+use crate::gpu::locale::{tr, tr_args};
+use crate::gpu::lighting::OverlayMode;
+use crate::gpu::render::MemoryPressure;
 use crate::gpu::systems::menu_system::MenuSystem;
+use crate::gpu::systems::{SchematicSystem, MinimapSystem, WorldMapSystem};
 
 /// Система рендеринга
 pub struct RenderSystem;
@@ -18,7 +21,20 @@ impl RenderSystem {
         
         // Обновляем рендерер
         {
+            renderer.set_weather(resources.weather.params());
+            renderer.set_particles(&resources.particles.instances());
             let changes = resources.world_changes.read().unwrap();
+            if resources.overlay_mode == OverlayMode::LightLevel {
+                if let Some(vertices) = resources.light_overlay.rebuild(resources.player.position, &changes, &resources.light_sources) {
+                    renderer.set_light_overlay(vertices);
+                }
+            }
+            // Рука от первого лица: не в демо-облёте и не в фоторежиме
+            let first_person = !resources.camera.should_render_player()
+                && !resources.attract.is_active()
+                && !resources.photo_mode.is_active();
+            let held = resources.gui_renderer.as_ref().and_then(|gui| gui.hotbar_ref().selected_block_type());
+            renderer.set_view_model(first_person, held, resources.block_breaker.breaking_block().is_some());
             renderer.update(&resources.camera, &resources.player, time, dt, &changes);
         }
         
@@ -27,35 +43,71 @@ impl RenderSystem {
             let mut subvoxels = resources.subvoxel_storage.write().unwrap();
             resources.foliage_cache.update(
                 &mut subvoxels,
-                resources.player.position.x,
-                resources.player.position.z,
+                resources.camera.position.x,
+                resources.camera.position.z,
                 4, // render distance в чанках для листвы
             );
         }
         
         // Обновляем суб-воксели
         if let Some(sv_renderer) = &mut resources.subvoxel_renderer {
-            let subvoxels = resources.subvoxel_storage.read().unwrap();
-            sv_renderer.update(renderer.device(), renderer.queue(), &subvoxels);
+            let mut subvoxels = resources.subvoxel_storage.write().unwrap();
+            sv_renderer.update(renderer.device(), renderer.queue(), &mut subvoxels);
         }
         
         // Raycast для выделения
         let (highlight_block, should_highlight) = Self::calculate_highlight(resources);
         
+        // Трещины на ломаемом блоке
+        let breaking = resources.block_breaker.breaking_block();
+        if let Some(renderer) = &mut resources.renderer {
+            renderer.update_break_overlay(breaking);
+        }
+        
+        // Рамка выделения палочкой (в фоторежиме скрыта)
+        let selection = SchematicSystem::selection_bounds(resources).filter(|_| !resources.photo_mode.is_active());
+        if let Some(renderer) = &mut resources.renderer {
+            renderer.update_selection_box(selection);
+        }
+        
         // Обновляем hover меню
         MenuSystem::update_hover(resources);
         
+        // Видеопамять: при нехватке дальность снижается, а не падаем
+        if let Some(change) = resources.renderer.as_mut().and_then(|renderer| renderer.update_memory_pressure(false)) {
+            Self::notify_memory_pressure(resources, change);
+        }
+        
+        // Компас на спавн, часы по солнцу и палитра лепки
+        let time_of_day = resources.renderer.as_ref().map(|renderer| renderer.time_of_day());
+        if let Some(gui) = &mut resources.gui_renderer {
+            gui.compass().update(resources.player.position, resources.player.yaw, resources.world_spawn);
+            if let Some(time) = time_of_day {
+                gui.clock().update(time, resources.settings.time.paused);
+            }
+            gui.sculpt_palette().update(resources.sculpt_tool, resources.current_subvoxel_level);
+        }
+        MinimapSystem::update(resources);
+        WorldMapSystem::update(resources);
+        
         // Рендерим
-        let render_player = resources.camera.should_render_player();
+        // Камера фоторежима отвязана от игрока - он виден в кадре
+        let photo = resources.photo_mode.is_active();
+        let render_player = photo || resources.camera.should_render_player();
         let sv_renderer = resources.subvoxel_renderer.as_ref();
-        let highlight_for_render = if should_highlight { Some([0, 0, 0]) } else { None };
+        let highlight_for_render = if should_highlight && !photo { Some([0, 0, 0]) } else { None };
         let mouse_pos = resources.mouse_pos;
+        // Во время демо-облёта и по H в фоторежиме интерфейс скрыт
+        let show_gui = !resources.attract.is_active() && !(photo && resources.photo_mode.hide_ui);
         
         let result = if resources.gui_renderer.is_some() {
+            let console = &resources.console;
             let gui = resources.gui_renderer.as_mut().unwrap();
             let renderer = resources.renderer.as_mut().unwrap();
             renderer.render_with_subvoxels(render_player, highlight_for_render, sv_renderer, |device, encoder, view, queue| {
-                gui.render(device, encoder, view, queue, mouse_pos);
+                if show_gui {
+                    gui.render(device, encoder, view, queue, mouse_pos, console);
+                }
             })
         } else {
             let renderer = resources.renderer.as_mut().unwrap();
@@ -69,9 +121,47 @@ impl RenderSystem {
                 renderer.resize(renderer.size());
             }
             Err(wgpu::SurfaceError::OutOfMemory) => {
-                event_loop.exit();
+                // Выходим, только если дальность снижать уже некуда
+                let renderer = resources.renderer.as_mut().unwrap();
+                if renderer.memory_pressure_exhausted() {
+                    event_loop.exit();
+                } else if let Some(change) = renderer.update_memory_pressure(true) {
+                    Self::notify_memory_pressure(resources, change);
+                }
             }
-            Err(e) => eprintln!("Render error: {:?}", e),
+            Err(e) => log::error!("Ошибка рендера: {:?}", e),
+        }
+        
+        // Скриншот (F2) записан на диск
+        if let Some(saved) = resources.renderer.as_mut().and_then(|renderer| renderer.poll_screenshot()) {
+            Self::notify_screenshot(resources, saved);
+        }
+    }
+    
+    /// Уведомление о сохранённом скриншоте
+    fn notify_screenshot(resources: &mut GameResources, saved: Result<std::path::PathBuf, String>) {
+        match saved {
+            Ok(path) => {
+                resources.console.info(format!("Скриншот: {}", path.display()));
+                let name = path.file_name().map_or_else(String::new, |name| name.to_string_lossy().into_owned());
+                if let Some(gui) = &mut resources.gui_renderer {
+                    gui.toasts().push(tr_args("toast.screenshot", &[("name", &name)]));
+                }
+            }
+            Err(e) => log::warn!("Скриншот не сохранён: {}", e),
+        }
+    }
+    
+    /// Уведомление об изменении дальности из-за видеопамяти
+    fn notify_memory_pressure(resources: &mut GameResources, change: MemoryPressure) {
+        let (message, toast) = match change {
+            MemoryPressure::Reduced(_) => ("Мало видеопамяти: дальность прорисовки снижена", "toast.low_video_memory"),
+            MemoryPressure::Restored(0) => ("Видеопамяти достаточно: дальность прорисовки восстановлена", "toast.distance_restored"),
+            MemoryPressure::Restored(_) => ("Видеопамяти достаточно: дальность прорисовки увеличена", "toast.distance_increased"),
+        };
+        resources.console.info(message);
+        if let Some(gui) = &mut resources.gui_renderer {
+            gui.toasts().push(tr(toast));
         }
     }
     
@@ -82,18 +172,9 @@ impl RenderSystem {
         let origin = [eye_pos.x, eye_pos.y, eye_pos.z];
         let direction = [forward.x, forward.y, forward.z];
         
-        // Ищем ближайший суб-воксель
-        let mut closest_subvoxel: Option<crate::gpu::subvoxel::SubVoxelHit> = None;
-        {
-            let subvoxels = resources.subvoxel_storage.read().unwrap();
-            for level in [SubVoxelLevel::Quarter, SubVoxelLevel::Half] {
-                if let Some(hit) = subvoxels.raycast(origin, direction, 5.0, level) {
-                    if closest_subvoxel.is_none() || hit.distance < closest_subvoxel.as_ref().unwrap().distance {
-                        closest_subvoxel = Some(hit);
-                    }
-                }
-            }
-        }
+        // Ближайший суб-воксель: клетка текущего уровня или лист крупнее
+        let closest_subvoxel = resources.subvoxel_storage.read().unwrap()
+            .raycast(origin, direction, 5.0, resources.current_subvoxel_level);
         
         // Получаем позицию обычного блока
         let block_hit = resources.block_breaker.target_block();
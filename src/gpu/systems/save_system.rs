@@ -3,80 +3,335 @@
 // ============================================
 
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
-use crate::gpu::core::{GameResources, SAVE_FILE, DEFAULT_SEED};
-use crate::gpu::save::WorldFile;
+use crate::gpu::core::{GameResources, SAVE_FILE, LEGACY_WORLD_DIR, SAVES_DIR, DEFAULT_WORLD};
+use crate::gpu::save::{ExploredMap, RegionWorld, SaveHeader, SavedHotbar, SavedBlockEntity, WorldManager, EXPLORED_MAP_FILE};
+use crate::gpu::console::GameMode;
+use crate::gpu::render::PaletteTheme;
+use crate::gpu::lighting::DayCounter;
 use crate::gpu::terrain::{WorldChanges, BlockPos};
 use crate::gpu::blocks::BlockType;
-use crate::gpu::subvoxel::{SubVoxelStorage, SubVoxel};
-use crate::gpu::terrain::get_height;
+use crate::gpu::subvoxel::{SubVoxelWorld, SubVoxel};
+use crate::gpu::terrain::generation::{WorldGenConfig, WorldPreset, world_gen_config, set_world_gen_config};
+use crate::gpu::terrain::generation::{SuperflatSettings, SUPERFLAT_FILE, generator_for, set_terrain_generator, world_spawn};
+use crate::gpu::terrain::stats::{scan_world, WORLD_STATS_FILE};
 
 /// Система сохранения/загрузки
 pub struct SaveSystem;
 
 /// Данные загруженного мира
 pub struct LoadedWorld {
+    /// Папка мира в SAVES_DIR
+    pub world_dir: String,
+    pub game_mode: GameMode,
+    /// Время в мире до этой сессии
+    pub playtime: Duration,
     pub start_x: f32,
     pub start_y: f32,
     pub start_z: f32,
+    /// Точка появления мира
+    pub spawn: [i32; 3],
+    /// Блок возрождения игрока (кровать)
+    pub respawn: Option<[i32; 3]>,
+    /// Радиус защиты спавна (0 - нет)
+    pub spawn_protection: i32,
+    /// Игровой календарь: прошедшие дни и время суток
+    pub day_counter: DayCounter,
     pub world_seed: u64,
     pub changes: HashMap<BlockPos, BlockType>,
     pub subvoxels: Vec<SubVoxel>,
+    pub hotbar: Option<SavedHotbar>,
+    pub palette_theme: PaletteTheme,
+    /// Данные блоков (сундуки)
+    pub block_entities: Vec<SavedBlockEntity>,
+    /// Карта мира: разведанные чанки и метки
+    pub explored_map: ExploredMap,
 }
 
 impl SaveSystem {
-    /// Загрузить мир из файла или создать новый
-    pub fn load_or_create() -> LoadedWorld {
-        if let Ok(loaded) = WorldFile::load(SAVE_FILE) {
-            println!("[SAVE] Загружен мир из {}", SAVE_FILE);
-            println!("[SAVE] Seed: {}, Позиция: {:?}, Изменений: {}, Суб-вокселей: {}", 
-                loaded.seed, loaded.player_pos, loaded.changes.len(), loaded.subvoxels.len());
+    /// Загрузить мир из директории, мигрировать старый world.dat или создать новый.
+    /// `new_world` - параметры генерации для нового мира (у сохранённого
+    /// мира они берутся из заголовка)
+    pub fn load_or_create(world_name: &str, new_world: WorldGenConfig) -> LoadedWorld {
+        let manager = Self::world_manager();
+        let world_dir = if manager.path_of(world_name).is_ok() {
+            world_name.to_string()
+        } else {
+            log::warn!("Недопустимое имя мира '{}', открывается {}", world_name, DEFAULT_WORLD);
+            DEFAULT_WORLD.to_string()
+        };
+        
+        // Метаданные есть и у созданного из меню, но ещё не сыгранного мира
+        let metadata = match manager.entry(&world_dir) {
+            Ok(entry) => entry.map(|entry| entry.metadata),
+            Err(e) => {
+                log::error!("Ошибка чтения метаданных {}: {:?}", world_dir, e);
+                None
+            }
+        };
+        let game_mode = metadata.as_ref().map(|m| m.game_mode).unwrap_or_default();
+        let playtime = Duration::from_secs(metadata.as_ref().map_or(0, |m| m.playtime_secs));
+        let saved_spawn = metadata.as_ref().and_then(|m| m.spawn);
+        let spawn_protection = metadata.as_ref().map_or(0, |m| m.spawn_protection);
+        let day_counter = metadata.as_ref().map_or_else(DayCounter::default, |m| DayCounter::new(m.days, m.time_of_day));
+        log::info!("Мир: {}/{}", SAVES_DIR, world_dir);
+        let explored_map = Self::load_explored_map(&manager, &world_dir);
+        
+        if let Some(loaded) = Self::load_existing(&manager, &world_dir) {
+            log::info!("Seed: {}, Позиция: {:?}, Изменений: {}, Суб-вокселей: {}, Сущностей блоков: {}", 
+                loaded.world_gen.seed, loaded.player_pos, loaded.changes.len(), loaded.subvoxels.len(), loaded.block_entities.len());
+            Self::apply_world_gen(&manager, &world_dir, loaded.world_gen);
+            let spawn = saved_spawn.unwrap_or_else(|| Self::find_spawn(&manager, &world_dir, spawn_protection));
             
             LoadedWorld {
+                world_dir,
+                game_mode,
+                playtime,
                 start_x: loaded.player_pos[0],
                 start_y: loaded.player_pos[1],
                 start_z: loaded.player_pos[2],
-                world_seed: loaded.seed,
+                spawn,
+                respawn: loaded.respawn,
+                spawn_protection,
+                day_counter,
+                world_seed: loaded.world_gen.seed,
                 changes: loaded.changes,
                 subvoxels: loaded.subvoxels,
+                hotbar: loaded.hotbar,
+                palette_theme: PaletteTheme::from_index(loaded.palette_theme),
+                block_entities: loaded.block_entities,
+                explored_map,
             }
         } else {
-            // Новый мир
-            let start_x = 0.0;
-            let start_z = 0.0;
-            let start_y = get_height(start_x, start_z) + 2.0;
-            println!("[SAVE] Новый мир (seed: {})", DEFAULT_SEED);
+            // Новый мир: параметры применяются до первого get_height.
+            // Мир, созданный из меню, берёт их из метаданных
+            let new_world = metadata.map_or(new_world, |m| m.world_gen);
+            log::info!("Новый мир (seed: {})", new_world.seed);
+            Self::apply_world_gen(&manager, &world_dir, new_world);
+            if let Err(e) = manager.record_session(&world_dir, new_world, game_mode, playtime) {
+                log::error!("Ошибка записи метаданных {}: {:?}", world_dir, e);
+            }
+            let spawn = saved_spawn.unwrap_or_else(|| Self::find_spawn(&manager, &world_dir, spawn_protection));
             
             LoadedWorld {
-                start_x,
-                start_y,
-                start_z,
-                world_seed: DEFAULT_SEED,
+                world_dir,
+                game_mode,
+                playtime,
+                start_x: spawn[0] as f32 + 0.5,
+                start_y: spawn[1] as f32 + 1.0,
+                start_z: spawn[2] as f32 + 0.5,
+                spawn,
+                respawn: None,
+                spawn_protection,
+                day_counter,
+                world_seed: new_world.seed,
                 changes: HashMap::new(),
                 subvoxels: Vec::new(),
+                hotbar: None,
+                palette_theme: PaletteTheme::default(),
+                block_entities: Vec::new(),
+                explored_map,
             }
         }
     }
     
-    /// Сохранить мир в файл
+    /// Найти безопасный спавн (первый вход в мир) и запомнить его
+    fn find_spawn(manager: &WorldManager, world_dir: &str, protection: i32) -> [i32; 3] {
+        let spawn = world_spawn();
+        log::info!("Спавн мира: {} {} {}", spawn[0], spawn[1], spawn[2]);
+        if let Err(e) = manager.set_spawn(world_dir, *world_gen_config(), spawn, protection) {
+            log::error!("Ошибка записи спавна {}: {:?}", world_dir, e);
+        }
+        spawn
+    }
+    
+    /// Карта мира из map.dat (нет файла - пустая)
+    fn load_explored_map(manager: &WorldManager, world_dir: &str) -> ExploredMap {
+        let path = manager.root().join(world_dir).join(EXPLORED_MAP_FILE);
+        if !path.exists() {
+            return ExploredMap::new();
+        }
+        match ExploredMap::load(&path) {
+            Ok(map) => {
+                log::info!("Карта мира: {} чанков, {} меток", map.tile_count(), map.waypoints().len());
+                map
+            }
+            Err(e) => {
+                log::error!("Ошибка чтения карты {}: {:?}", path.display(), e);
+                ExploredMap::new()
+            }
+        }
+    }
+    
+    /// Записать спавн и защиту текущего мира (после /setspawn)
+    pub fn save_spawn(resources: &GameResources) {
+        let manager = Self::world_manager();
+        if let Err(e) = manager.set_spawn(&resources.world_dir, *world_gen_config(), resources.world_spawn, resources.spawn_protection) {
+            log::error!("Ошибка записи спавна {}: {:?}", resources.world_dir, e);
+        }
+    }
+    
+    /// Миры в папке сохранений
+    pub fn world_manager() -> WorldManager {
+        WorldManager::new(SAVES_DIR)
+    }
+    
+    /// Сделать параметры мира текущими для генерации
+    fn apply_world_gen(manager: &WorldManager, world_dir: &str, world_gen: WorldGenConfig) {
+        if !set_world_gen_config(world_gen) {
+            log::warn!("Генерация уже запущена с другими параметрами: {:?}", world_gen_config());
+        }
+        log::info!(
+            "Генерация: {}, уровень моря {}, пещеры x{}, биомы x{}, рельеф x{}, граница {}",
+            world_gen.preset.name(), world_gen.sea_level, world_gen.cave_density, world_gen.biome_scale, world_gen.terrain_amplitude,
+            match world_gen.border_radius {
+                0 => "нет".to_string(),
+                radius => format!("±{}", radius),
+            }
+        );
+        
+        let superflat = if world_gen.preset == WorldPreset::Superflat {
+            Self::superflat_settings(&manager.root().join(world_dir))
+        } else {
+            SuperflatSettings::default()
+        };
+        if !set_terrain_generator(generator_for(world_gen.preset, &superflat)) {
+            log::warn!("Генератор мира уже выбран, тип {} не применён", world_gen.preset.name());
+        }
+    }
+    
+    /// Слои плоского мира из директории мира. Если файла нет - пишется
+    /// стек по умолчанию (его можно заранее положить туда для нового мира)
+    fn superflat_settings(world_dir: &Path) -> SuperflatSettings {
+        let path = world_dir.join(SUPERFLAT_FILE);
+        if path.exists() {
+            match SuperflatSettings::load(&path) {
+                Ok(settings) => return settings,
+                Err(e) => {
+                    log::error!("Ошибка чтения {}: {}, слои по умолчанию", path.display(), e);
+                    return SuperflatSettings::default();
+                }
+            }
+        }
+        
+        let settings = SuperflatSettings::default();
+        let written = std::fs::create_dir_all(world_dir)
+            .map_err(|e| e.to_string())
+            .and_then(|_| settings.save(&path));
+        if let Err(e) = written {
+            log::warn!("Не удалось записать {}: {}", path.display(), e);
+        }
+        settings
+    }
+    
+    /// Прочитать сохранённый мир (региональный или старый формат).
+    /// Миры до списка миров (world/ и world.dat рядом с игрой)
+    /// переносятся в мир по умолчанию
+    fn load_existing(manager: &WorldManager, world_dir: &str) -> Option<crate::gpu::save::LoadedWorld> {
+        let path = manager.root().join(world_dir);
+        let is_default = world_dir == DEFAULT_WORLD;
+        
+        if is_default && !path.exists() && RegionWorld::exists(LEGACY_WORLD_DIR) {
+            match manager.adopt(LEGACY_WORLD_DIR, world_dir) {
+                Ok(()) => log::info!("{}/ перенесён в {}/", LEGACY_WORLD_DIR, path.display()),
+                Err(e) => log::error!("Ошибка переноса {}/: {:?}", LEGACY_WORLD_DIR, e),
+            }
+        }
+        
+        if RegionWorld::exists(&path) {
+            match manager.load(world_dir) {
+                Ok(loaded) => {
+                    log::info!("Загружен мир из {}/", path.display());
+                    return Some(loaded);
+                }
+                Err(e) => log::error!("Ошибка загрузки {}/: {:?}", path.display(), e),
+            }
+        }
+        
+        if is_default && Path::new(SAVE_FILE).exists() {
+            match RegionWorld::migrate_from_legacy(SAVE_FILE, &path) {
+                Ok(loaded) => {
+                    log::info!("{} перенесён в {}/ (старый файл: {}.bak)", SAVE_FILE, path.display(), SAVE_FILE);
+                    return Some(loaded);
+                }
+                Err(e) => log::error!("Ошибка миграции {}: {:?}", SAVE_FILE, e),
+            }
+        }
+        
+        None
+    }
+    
+    /// Просканировать мир вокруг игрока в фоне (статистика для настройки генерации)
+    pub fn scan_world_stats(resources: &GameResources, radius_chunks: i32) {
+        let position = resources.player.position;
+        let changes = resources.world_changes.read().unwrap().get_all_changes_copy();
+        log::info!("Сканирование радиуса {} чанков...", radius_chunks);
+        
+        std::thread::spawn(move || {
+            Self::write_world_stats(position.x, position.z, radius_chunks, &changes);
+        });
+    }
+    
+    /// Офлайн-сканирование сохранённого мира без окна (--world-stats)
+    pub fn scan_world_stats_offline(radius_chunks: i32, world_name: &str, new_world: WorldGenConfig) {
+        let loaded = Self::load_or_create(world_name, new_world);
+        Self::write_world_stats(loaded.start_x, loaded.start_z, radius_chunks, &loaded.changes);
+    }
+    
+    fn write_world_stats(x: f32, z: f32, radius_chunks: i32, changes: &HashMap<BlockPos, BlockType>) {
+        let stats = scan_world(x, z, radius_chunks, changes);
+        stats.print_summary();
+        match stats.save_json(WORLD_STATS_FILE) {
+            Ok(()) => log::info!("Отчёт сохранён в {}", WORLD_STATS_FILE),
+            Err(e) => log::error!("Ошибка записи {}: {}", WORLD_STATS_FILE, e),
+        }
+    }
+    
+    /// Сохранить мир: только изменённые чанки + level.dat + метаданные
     pub fn save_world(resources: &GameResources) {
-        let player_pos = [
+        let manager = Self::world_manager();
+        let world_path = manager.root().join(&resources.world_dir);
+        let header = SaveHeader::new(*world_gen_config(), [
             resources.player.position.x,
             resources.player.position.y,
             resources.player.position.z,
-        ];
+        ])
+        .with_respawn(resources.respawn_point);
         
-        let changes = resources.world_changes.read().unwrap();
+        // GUI может быть ещё не создан - тогда хотбар лежит в pending
+        let hotbar = match &resources.gui_renderer {
+            Some(gui) => Some(gui.hotbar_ref().to_saved()),
+            None => resources.pending_hotbar.clone(),
+        };
+        
+        let mut changes = resources.world_changes.write().unwrap();
         let subvoxels = resources.subvoxel_storage.read().unwrap();
         
-        match WorldFile::save(SAVE_FILE, resources.world_seed, player_pos, &changes, &subvoxels) {
-            Ok(_) => {
-                println!("[SAVE] Мир сохранён в {} ({} изменений, {} суб-вокселей)", 
-                    SAVE_FILE, changes.change_count(), subvoxels.count());
+        let unsaved = changes.take_unsaved_chunks();
+        let playtime = resources.playtime_base + resources.start_time.elapsed();
+        let result = RegionWorld::open(&world_path).and_then(|mut world| {
+            let written = world.save_chunks(&changes, unsaved.iter().copied())?;
+            world.save_level(header, subvoxels.get_all(), hotbar, resources.palette_theme.index(), resources.block_entities.to_saved())?;
+            manager.record_session(&resources.world_dir, *world_gen_config(), resources.game_mode, playtime)?;
+            manager.set_calendar(&resources.world_dir, *world_gen_config(), resources.day_counter.days(), resources.day_counter.time())?;
+            if resources.explored_map.tile_count() > 0 || !resources.explored_map.waypoints().is_empty() {
+                resources.explored_map.save(&world_path.join(EXPLORED_MAP_FILE))?;
+            }
+            Ok(written)
+        });
+        
+        match result {
+            Ok(written) => {
+                log::info!("Мир сохранён в {}/ ({} чанков записано, {} изменений, {} блоков с суб-вокселями, {} сущностей блоков)", 
+                    world_path.display(), written, changes.change_count(), subvoxels.block_count(), resources.block_entities.len());
             }
             Err(e) => {
-                eprintln!("[SAVE] Ошибка сохранения: {:?}", e);
+                // Повторим запись этих чанков при следующем сохранении
+                changes.mark_unsaved(unsaved);
+                log::error!("Ошибка сохранения: {:?}", e);
             }
         }
     }
@@ -91,12 +346,14 @@ impl SaveSystem {
             for (pos, block) in loaded_changes {
                 changes.set_block(pos, block);
             }
+            // Загруженные чанки уже лежат на диске
+            changes.clear_unsaved();
         }
     }
     
     /// Применить загруженные суб-воксели
     pub fn apply_loaded_subvoxels(
-        subvoxel_storage: &mut SubVoxelStorage,
+        subvoxel_storage: &mut SubVoxelWorld,
         loaded_subvoxels: Vec<SubVoxel>,
     ) {
         if !loaded_subvoxels.is_empty() {
@@ -0,0 +1,171 @@
+// ============================================
+// Schematic System - Выделение, копирование и вставка построек
+// ============================================
+// Палочка выделения в руке: ЛКМ по блоку - первый угол, ПКМ - второй.
+// /copy копирует выделенную область (блоки и субвоксели) в буфер,
+// /paste вставляет буфер у игрока с поворотом на 90°, /schem пишет
+// буфер в файл и читает обратно. Вставка идёт через WorldChanges и
+// хранилище субвокселей, как и правки игрока.
+
+use std::path::PathBuf;
+
+use ultraviolet::Vec3;
+
+use crate::gpu::core::{GameResources, SCHEMATICS_DIR};
+use crate::gpu::locale::tr_args;
+use crate::gpu::items::SELECTION_WAND;
+use crate::gpu::save::Schematic;
+use crate::gpu::terrain::generation::terrain_generator;
+use crate::gpu::terrain::voxel::constants::WORLD_HEIGHT;
+use crate::gpu::terrain::{BlockPos, MIN_HEIGHT};
+use crate::gpu::blocks::AIR;
+
+use super::{BlockInteractionSystem, TorchSystem};
+
+/// Максимум блоков в копируемой области
+const MAX_COPY_VOLUME: i64 = 131072;
+
+/// Система схематик
+pub struct SchematicSystem;
+
+impl SchematicSystem {
+    /// Клик с палочкой выделения: задать угол (0 - ЛКМ, 1 - ПКМ).
+    /// true - в руке палочка, клик обработан
+    pub fn use_wand(resources: &mut GameResources, corner: usize) -> bool {
+        let held = resources.gui_renderer.as_ref()
+            .and_then(|gui| gui.hotbar_ref().selected_item().map(|stack| stack.item));
+        if held != Some(SELECTION_WAND) {
+            return false;
+        }
+        let Some(pos) = resources.block_breaker.target_block().map(|hit| hit.block_pos) else { return true };
+
+        resources.selection[corner] = Some(pos);
+        let [x, y, z] = pos;
+        let key = if corner == 0 { "toast.corner_first" } else { "toast.corner_second" };
+        let corner_set = tr_args(key, &[("x", &x), ("y", &y), ("z", &z)]);
+        let message = match Self::selection_bounds(resources) {
+            Some((min, max)) => {
+                let size = format!("{}x{}x{}", max[0] - min[0] + 1, max[1] - min[1] + 1, max[2] - min[2] + 1);
+                tr_args("toast.selection_size", &[("corner", &corner_set), ("size", &size)])
+            }
+            None => corner_set,
+        };
+        if let Some(gui) = &mut resources.gui_renderer {
+            gui.toasts().push(message);
+        }
+        true
+    }
+
+    /// /copy: выделение в буфер (смещение - от блока игрока)
+    pub fn copy(resources: &mut GameResources) {
+        let Some((min, max)) = Self::selection_bounds(resources) else {
+            resources.console.error("Нет выделения: ЛКМ и ПКМ палочкой выделения по углам");
+            return;
+        };
+        let volume = (0..3).map(|i| (max[i] - min[i] + 1) as i64).product::<i64>();
+        if volume > MAX_COPY_VOLUME {
+            resources.console.error(format!("Слишком большая область: {} блоков (максимум {})", volume, MAX_COPY_VOLUME));
+            return;
+        }
+
+        let schematic = {
+            let changes = resources.world_changes.read().unwrap();
+            let generator = terrain_generator();
+            let block_at = |x: i32, y: i32, z: i32| changes.get_block(x, y, z).unwrap_or_else(|| generator.block_at(x, y, z));
+            let storage = resources.subvoxel_storage.read().unwrap();
+            Schematic::capture(min, max, Self::player_block(resources), block_at, &storage)
+        };
+        resources.console.info(format!("Скопировано {} блоков и {} субвокселей", volume, schematic.subvoxels().len()));
+        resources.clipboard = Some(schematic);
+    }
+
+    /// /paste: буфер у игрока, повёрнутый на quarter_turns * 90°
+    pub fn paste(resources: &mut GameResources, quarter_turns: u32) {
+        let Some(schematic) = resources.clipboard.as_ref().map(|clipboard| clipboard.rotated(quarter_turns)) else {
+            resources.console.error("Буфер пуст (/copy или /schem load)");
+            return;
+        };
+        let corner = schematic.corner(Self::player_block(resources));
+        let [sx, sy, sz] = schematic.size();
+        let max = [corner[0] + sx - 1, corner[1] + sy - 1, corner[2] + sz - 1];
+
+        let mut placed = 0;
+        {
+            let mut changes = resources.world_changes.write().unwrap();
+            for ([x, y, z], block) in schematic.blocks() {
+                let y = corner[1] + y;
+                if (MIN_HEIGHT..WORLD_HEIGHT).contains(&y) {
+                    changes.set_block(BlockPos::new(corner[0] + x, y, corner[2] + z), block);
+                    placed += 1;
+                }
+            }
+        }
+        {
+            // Субвоксели области заменяются субвокселями буфера
+            let mut storage = resources.subvoxel_storage.write().unwrap();
+            for old in storage.get_in_region(corner[0], corner[1], corner[2], max[0], max[1], max[2]) {
+                storage.remove(&old.pos);
+            }
+            for sv in schematic.subvoxels() {
+                let mut pos = sv.pos;
+                pos.block_x += corner[0];
+                pos.block_y += corner[1];
+                pos.block_z += corner[2];
+                if sv.block_type != AIR {
+                    storage.set(pos, sv.block_type);
+                }
+            }
+            // Вставленные факелы светят
+            resources.light_sources = TorchSystem::restore_lights(&storage);
+        }
+
+        let lower = Vec3::new(corner[0] as f32, corner[1] as f32, corner[2] as f32);
+        let upper = Vec3::new((max[0] + 1) as f32, (max[1] + 1) as f32, (max[2] + 1) as f32);
+        BlockInteractionSystem::invalidate_support(resources, lower, upper);
+        resources.console.info(format!("Вставлено {} блоков и {} субвокселей", placed, schematic.subvoxels().len()));
+    }
+
+    /// /schem save: буфер в файл
+    pub fn save(resources: &mut GameResources, name: &str) {
+        let Some(schematic) = &resources.clipboard else {
+            resources.console.error("Буфер пуст (/copy)");
+            return;
+        };
+        let path = Self::path_of(name);
+        match schematic.save(&path) {
+            Ok(()) => resources.console.info(format!("Схематика сохранена: {}", path.display())),
+            Err(e) => resources.console.error(format!("Не удалось сохранить {}: {:?}", path.display(), e)),
+        }
+    }
+
+    /// /schem load: файл в буфер
+    pub fn load(resources: &mut GameResources, name: &str) {
+        let path = Self::path_of(name);
+        match Schematic::load(&path) {
+            Ok(schematic) => {
+                let [sx, sy, sz] = schematic.size();
+                resources.console.info(format!("Схематика {} ({}x{}x{}) в буфере, /paste - вставить", name, sx, sy, sz));
+                resources.clipboard = Some(schematic);
+            }
+            Err(e) => resources.console.error(format!("Не удалось загрузить {}: {:?}", path.display(), e)),
+        }
+    }
+
+    /// Углы выделения (min, max), если заданы оба
+    pub fn selection_bounds(resources: &GameResources) -> Option<([i32; 3], [i32; 3])> {
+        let [Some(a), Some(b)] = resources.selection else { return None };
+        let min = [a[0].min(b[0]), a[1].min(b[1]), a[2].min(b[2])];
+        let max = [a[0].max(b[0]), a[1].max(b[1]), a[2].max(b[2])];
+        Some((min, max))
+    }
+
+    /// Блок, в котором стоят ноги игрока
+    fn player_block(resources: &GameResources) -> [i32; 3] {
+        let pos = resources.player.position;
+        [pos.x.floor() as i32, pos.y.floor() as i32, pos.z.floor() as i32]
+    }
+
+    fn path_of(name: &str) -> PathBuf {
+        PathBuf::from(SCHEMATICS_DIR).join(format!("{}.schem", name))
+    }
+}
@@ -0,0 +1,223 @@
+// ============================================
+// Sculpt System - Лепка субвокселей кистями
+// ============================================
+// С кистью (B) и уровнем меньше блока ПКМ начинает мазок, отпускание
+// ПКМ его применяет: коробка или шар клеток текущего уровня заливается
+// блоком из хотбара или стирается. Все клетки пишутся в хранилище одним
+// пакетом, так что меш пересобирается один раз.
+// C - разрезать блок под прицелом на субвоксели того же типа, чтобы
+// дальше вырезать из него кистями.
+
+use std::collections::HashMap;
+
+use ultraviolet::Vec3;
+
+use crate::gpu::blocks::{use_action, BlockType, AIR, TORCH, WATER};
+use crate::gpu::core::GameResources;
+use crate::gpu::player::PLAYER_RADIUS;
+use crate::gpu::subvoxel::{
+    brush_cells, subvoxel_intersects_player, world_to_subvoxel, SculptStroke, SculptTool, SubVoxelLevel, SubVoxelPos,
+};
+use crate::gpu::terrain::BlockPos;
+
+use super::BlockInteractionSystem;
+
+/// Система лепки
+pub struct SculptSystem;
+
+impl SculptSystem {
+    /// ПКМ нажата: начать мазок. true - кисть активна, клик забран
+    pub fn begin(resources: &mut GameResources) -> bool {
+        let tool = resources.sculpt_tool;
+        if tool == SculptTool::Single || resources.current_subvoxel_level == SubVoxelLevel::Full {
+            return false;
+        }
+        let block = if tool == SculptTool::Erase {
+            None
+        } else {
+            let held = resources.gui_renderer.as_mut().and_then(|gui| gui.hotbar().selected_block_type());
+            // Без блока в руке и с факелом - обычная установка
+            match held {
+                Some(block) if block != TORCH => Some(block),
+                _ => return false,
+            }
+        };
+        if let Some(start) = Self::target_cell(resources, block.is_none()) {
+            resources.sculpt_stroke = Some(SculptStroke { start, block });
+        }
+        true
+    }
+
+    /// ПКМ отпущена: применить мазок
+    pub fn finish(resources: &mut GameResources) {
+        let Some(stroke) = resources.sculpt_stroke.take() else { return };
+        let erase = stroke.block.is_none();
+        // Уровень сменили посреди мазка - мазок в одну клетку
+        let end = Self::target_cell(resources, erase)
+            .filter(|end| end.level == stroke.start.level)
+            .unwrap_or(stroke.start);
+        let Some(cells) = brush_cells(resources.sculpt_tool, stroke.start, end) else {
+            resources.console.error("Слишком большой мазок: уменьшите кисть или возьмите уровень крупнее");
+            return;
+        };
+
+        // Проверки по блокам, а не по клеткам: в блоке до 4096 клеток
+        let mut editable: HashMap<[i32; 3], bool> = HashMap::new();
+        let mut block_allowed = |resources: &GameResources, pos: &SubVoxelPos| {
+            let key = [pos.block_x, pos.block_y, pos.block_z];
+            *editable.entry(key).or_insert_with(|| {
+                BlockInteractionSystem::can_edit_silent(resources, key[0], key[2])
+                    && (erase || matches!(BlockInteractionSystem::block_at(resources, key[0], key[1], key[2]), AIR | WATER))
+            })
+        };
+
+        let writes: Vec<(SubVoxelPos, BlockType)> = {
+            let storage = resources.subvoxel_storage.read().unwrap();
+            let player = resources.player.position;
+            let height = resources.player.height();
+            cells
+                .into_iter()
+                .filter(|pos| match (stroke.block, storage.get(pos)) {
+                    // Факелы убираются только рукой (у них свет и опора)
+                    (None, existing) => existing.is_some_and(|block| block != TORCH),
+                    (Some(_), existing) => {
+                        existing.is_none()
+                            && !subvoxel_intersects_player(pos, player.x, player.y, player.z, PLAYER_RADIUS, height)
+                    }
+                })
+                .filter(|pos| block_allowed(resources, pos))
+                .map(|pos| (pos, stroke.block.unwrap_or(AIR)))
+                .collect()
+        };
+        if writes.is_empty() {
+            return;
+        }
+
+        let (min, max) = bounds(&writes);
+        resources.subvoxel_storage.write().unwrap().set_many(writes);
+
+        match stroke.block {
+            Some(block) => {
+                if let Some(audio) = &mut resources.audio_system {
+                    audio.play_place_block(block, stroke.start.level);
+                }
+            }
+            None => BlockInteractionSystem::invalidate_support(resources, min, max),
+        }
+    }
+
+    /// Заменить блок под прицелом субвокселями того же типа (уровень -
+    /// текущий, на полных блоках - 1/4)
+    pub fn carve_target_block(resources: &mut GameResources) {
+        let Some(hit) = resources.block_breaker.target_block().copied() else { return };
+        // Субвоксель перед блоком закрывает его
+        if BlockInteractionSystem::closest_subvoxel_hit(resources).is_some_and(|sv| sv.distance < hit.distance) {
+            return;
+        }
+        let [x, y, z] = hit.block_pos;
+        let block = hit.block_type;
+        // Кровать и сундук со своим действием целиком, вода - не блок
+        if block == AIR || block == WATER || use_action(block).is_some() {
+            return;
+        }
+        if !BlockInteractionSystem::can_edit(resources, x, z) {
+            return;
+        }
+
+        let (level, cells) = carve_cells([x, y, z], block, resources.current_subvoxel_level);
+        let pos = BlockPos::new(x, y, z);
+        resources.world_changes.write().unwrap().set_block(pos, AIR);
+        resources.subvoxel_storage.write().unwrap().set_many(cells);
+        resources.block_ticks.notify_changed(pos);
+        if let Some(renderer) = &mut resources.renderer {
+            let changes = resources.world_changes.read().unwrap();
+            renderer.instant_chunk_update(x, y, z, &changes);
+        }
+        if let Some(audio) = &mut resources.audio_system {
+            audio.play_place_block(block, level);
+        }
+        log::info!("Блок ({}, {}, {}) разрезан на субвоксели {}", x, y, z, level.name());
+    }
+
+    /// Клетка текущего уровня под прицелом: для заливки - куда встанет
+    /// субвоксель, для стирания - та, в которую смотрит игрок
+    fn target_cell(resources: &GameResources, erase: bool) -> Option<SubVoxelPos> {
+        if !erase {
+            return BlockInteractionSystem::subvoxel_placement_target(resources);
+        }
+        let level = resources.current_subvoxel_level;
+        let block_dist = resources.block_breaker.target_block().map_or(f32::MAX, |hit| hit.distance);
+        let (point, normal) = match BlockInteractionSystem::closest_subvoxel_hit(resources) {
+            Some(hit) if hit.distance < block_dist => (Vec3::from(hit.hit_point), Vec3::from(hit.hit_normal)),
+            _ => {
+                let hit = resources.block_breaker.target_block()?;
+                (hit.hit_point, hit.hit_normal)
+            }
+        };
+        // Чуть внутрь грани, в которую попал луч
+        let inside = point - normal * (level.size() * 0.5);
+        Some(world_to_subvoxel(inside.x, inside.y, inside.z, level))
+    }
+}
+
+/// Клетки, которыми блок заменяется при разрезании: уровень текущий,
+/// на полных блоках - 1/4. Клетки заполняют блок целиком
+fn carve_cells([x, y, z]: [i32; 3], block: BlockType, current: SubVoxelLevel) -> (SubVoxelLevel, Vec<(SubVoxelPos, BlockType)>) {
+    let level = match current {
+        SubVoxelLevel::Full => SubVoxelLevel::Quarter,
+        level => level,
+    };
+    let div = level.divisions();
+    let cells = (0..div)
+        .flat_map(|sx| (0..div).flat_map(move |sy| (0..div).map(move |sz| (sx, sy, sz))))
+        .map(|(sx, sy, sz)| (SubVoxelPos::new(x, y, z, sx, sy, sz, level), block))
+        .collect();
+    (level, cells)
+}
+
+/// Границы мазка в мировых координатах
+fn bounds(writes: &[(SubVoxelPos, BlockType)]) -> (Vec3, Vec3) {
+    writes.iter().fold(
+        (Vec3::broadcast(f32::MAX), Vec3::broadcast(f32::MIN)),
+        |(min, max), (pos, _)| {
+            let corner = Vec3::from(pos.world_min());
+            (min.min_by_component(corner), max.max_by_component(corner + Vec3::broadcast(pos.level.size())))
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use crate::gpu::blocks::STONE;
+
+    /// Клетки одного блока, без повторов, ровно весь блок
+    fn assert_fills_block(level: SubVoxelLevel, cells: &[(SubVoxelPos, BlockType)]) {
+        let div = level.divisions() as usize;
+        assert_eq!(cells.len(), div * div * div);
+        let subs: HashSet<_> = cells
+            .iter()
+            .map(|(pos, block)| {
+                assert_eq!((pos.block_x, pos.block_y, pos.block_z), (3, -7, 12));
+                assert_eq!((pos.level, *block), (level, STONE));
+                (pos.sub_x, pos.sub_y, pos.sub_z)
+            })
+            .collect();
+        assert_eq!(subs.len(), cells.len());
+        assert!(subs.iter().all(|&(sx, sy, sz)| (sx as usize) < div && (sy as usize) < div && (sz as usize) < div));
+    }
+
+    #[test]
+    fn carve_cells_cover_the_block() {
+        for (current, expected) in [
+            (SubVoxelLevel::Half, SubVoxelLevel::Half),
+            (SubVoxelLevel::Quarter, SubVoxelLevel::Quarter),
+            (SubVoxelLevel::Full, SubVoxelLevel::Quarter),
+        ] {
+            let (level, cells) = carve_cells([3, -7, 12], STONE, current);
+            assert_eq!(level, expected, "с уровня {:?}", current);
+            assert_fills_block(level, &cells);
+        }
+    }
+}
@@ -0,0 +1,99 @@
+// ============================================
+// Spawn System - Смерть и возрождение игрока
+// ============================================
+// Игрок возрождается у своей кровати (ПКМ по блоку возрождения), а если
+// кровати нет или над ней нет места - на спавне мира. Точка возрождения
+// хранится в заголовке сохранения. Смерть - падение в пустоту под миром
+// и /kill.
+
+use ultraviolet::Vec3;
+
+use crate::gpu::blocks::{is_respawn_point, BlockHit};
+use crate::gpu::core::GameResources;
+use crate::gpu::locale::tr;
+use crate::gpu::terrain::MIN_HEIGHT;
+
+/// Насколько ниже дна мира игрок погибает (блоки)
+const VOID_DEATH_DEPTH: f32 = 64.0;
+
+/// Система смерти и возрождения
+pub struct SpawnSystem;
+
+impl SpawnSystem {
+    /// Проверка смерти за кадр (после физики игрока)
+    pub fn update(resources: &mut GameResources) {
+        if resources.player.position.y < MIN_HEIGHT as f32 - VOID_DEATH_DEPTH {
+            Self::die(resources, "toast.fell_out");
+        }
+    }
+
+    /// Смерть: сообщение (ключ в файле языка) и возрождение
+    pub fn die(resources: &mut GameResources, message: &str) {
+        log::info!("Игрок погиб: {}", message);
+        // Место смерти - метка на карте и маяк на экране
+        let position = resources.player.position;
+        resources.explored_map.set_death([position.x.floor() as i32, position.y.floor() as i32, position.z.floor() as i32]);
+        if let Some(gui) = &mut resources.gui_renderer {
+            gui.toasts().push(tr(message));
+        }
+        Self::respawn(resources);
+    }
+
+    /// Телепорт в точку возрождения. Возвращает блок, в котором стоят ноги
+    pub fn respawn(resources: &mut GameResources) -> [i32; 3] {
+        let target = match resources.respawn_point {
+            Some(bed) if Self::bed_usable(resources, bed) => [bed[0], bed[1] + 1, bed[2]],
+            Some(_) => {
+                // Кровать сломали или застроили - дальше возрождаемся на спавне
+                resources.respawn_point = None;
+                if let Some(gui) = &mut resources.gui_renderer {
+                    gui.toasts().push(tr("toast.bed_missing"));
+                }
+                resources.world_spawn
+            }
+            None => resources.world_spawn,
+        };
+
+        let [x, y, z] = target;
+        resources.player.position = Vec3::new(x as f32 + 0.5, y as f32, z as f32 + 0.5);
+        resources.player.velocity = Vec3::zero();
+        resources.player.on_ground = false;
+        target
+    }
+
+    /// ПКМ по блоку возрождения (on_use set_respawn): запомнить его как
+    /// точку возрождения. true - клик обработан (блок не ставится)
+    pub fn use_respawn_block(resources: &mut GameResources, hit: &BlockHit) -> bool {
+        if !is_respawn_point(hit.block_type) {
+            return false;
+        }
+
+        let bed = hit.block_pos;
+        if !Self::bed_usable(resources, bed) {
+            if let Some(gui) = &mut resources.gui_renderer {
+                gui.toasts().push(tr("toast.bed_no_room"));
+            }
+            return true;
+        }
+        if resources.respawn_point != Some(bed) {
+            resources.respawn_point = Some(bed);
+            log::info!("Точка возрождения: {} {} {}", bed[0], bed[1], bed[2]);
+            if let Some(gui) = &mut resources.gui_renderer {
+                gui.toasts().push(tr("toast.respawn_set"));
+            }
+        }
+        true
+    }
+
+    /// Кровать на месте и над ней помещается игрок
+    fn bed_usable(resources: &GameResources, bed: [i32; 3]) -> bool {
+        let changes = resources.world_changes.read().unwrap();
+        // Кровати не генерируются - только поставленные игроком
+        if !changes.get_block(bed[0], bed[1], bed[2]).is_some_and(is_respawn_point) {
+            return false;
+        }
+        let changes_map = changes.get_all_changes_copy();
+        drop(changes);
+        (1..=2).all(|up| !resources.player_controller.is_block_solid(bed[0], bed[1] + up, bed[2], &changes_map))
+    }
+}
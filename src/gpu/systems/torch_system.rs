@@ -0,0 +1,115 @@
+// ============================================
+// Torch System - Факелы
+// ============================================
+// Факел ставится на верх или боковую грань непрозрачного блока (модель
+// из субвокселей, см. subvoxel/torch.rs) и регистрирует источник света
+// в LightSources. Если опору сломать, факел отваливается и в выживании
+// падает в хотбар, как и сломанный рукой.
+
+use crate::gpu::blocks::{global_registry, BlockType, AIR, TORCH};
+use crate::gpu::console::GameMode;
+use crate::gpu::core::GameResources;
+use crate::gpu::items::ItemStack;
+use crate::gpu::lighting::LightSources;
+use crate::gpu::subvoxel::{
+    place_torch, remove_torch, torch_cells, torch_facing, SubVoxelLevel, SubVoxelWorld, TorchFacing,
+};
+use crate::gpu::systems::BlockInteractionSystem;
+use ultraviolet::Vec3;
+
+/// Система факелов
+pub struct TorchSystem;
+
+impl TorchSystem {
+    /// Поставить факел в клетку pos с опорой по facing.
+    /// Клетка должна быть пустой, опора - непрозрачным твёрдым блоком
+    pub fn place(resources: &mut GameResources, pos: [i32; 3], facing: TorchFacing) {
+        let offset = facing.support_offset();
+        let support = BlockInteractionSystem::block_at(resources, pos[0] + offset[0], pos[1] + offset[1], pos[2] + offset[2]);
+        if !holds_torch(support) || BlockInteractionSystem::block_at(resources, pos[0], pos[1], pos[2]) != AIR {
+            return;
+        }
+        {
+            let mut storage = resources.subvoxel_storage.write().unwrap();
+            let [x, y, z] = pos;
+            if !storage.get_in_region(x, y, z, x, y, z).is_empty() {
+                return;
+            }
+            place_torch(&mut storage, pos, facing);
+        }
+        resources.light_sources.add(pos, torch_light());
+
+        if let Some(audio) = &mut resources.audio_system {
+            audio.play_place_block(TORCH, SubVoxelLevel::Quarter);
+        }
+    }
+
+    /// Стоит ли в клетке факел
+    pub fn is_torch(resources: &GameResources, pos: [i32; 3]) -> bool {
+        torch_facing(&resources.subvoxel_storage.read().unwrap(), pos).is_some()
+    }
+
+    /// Игрок сломал субвоксель факела: убрать остаток модели и свет
+    pub fn on_subvoxel_broken(resources: &mut GameResources, pos: [i32; 3]) {
+        remove_torch(&mut resources.subvoxel_storage.write().unwrap(), pos);
+        resources.light_sources.remove(pos);
+        Self::drop_torch(resources);
+    }
+
+    /// Сломан блок: факелы, которые на нём держались, отваливаются
+    pub fn on_block_broken(resources: &mut GameResources, block_pos: [i32; 3]) {
+        for facing in TorchFacing::ALL {
+            let offset = facing.support_offset();
+            let pos = [block_pos[0] - offset[0], block_pos[1] - offset[1], block_pos[2] - offset[2]];
+            let attached = {
+                let mut storage = resources.subvoxel_storage.write().unwrap();
+                torch_facing(&storage, pos) == Some(facing) && remove_torch(&mut storage, pos)
+            };
+            if !attached {
+                continue;
+            }
+            resources.light_sources.remove(pos);
+            let center = Vec3::new(pos[0] as f32 + 0.5, pos[1] as f32 + 0.5, pos[2] as f32 + 0.5);
+            resources.particles.emit_subvoxel_break(center, SubVoxelLevel::Quarter.size(), TORCH);
+            Self::drop_torch(resources);
+        }
+    }
+
+    /// Источники света факелов из загруженных субвокселей
+    pub fn restore_lights(storage: &SubVoxelWorld) -> LightSources {
+        let mut lights = LightSources::new();
+        let level = torch_light();
+        for pos in torch_cells(storage) {
+            lights.add(pos, level);
+        }
+        lights
+    }
+
+    /// Факел в хотбар (в выживании)
+    fn drop_torch(resources: &mut GameResources) {
+        if resources.game_mode != GameMode::Survival {
+            return;
+        }
+        if let Some(gui) = &mut resources.gui_renderer {
+            gui.hotbar().insert(ItemStack::of_block(TORCH));
+        }
+    }
+}
+
+/// Блок держит факел: твёрдый и непрозрачный (не стекло, не листва)
+fn holds_torch(block: BlockType) -> bool {
+    global_registry()
+        .read()
+        .ok()
+        .and_then(|registry| registry.get_by_numeric(block).map(|def| def.solid && !def.transparent))
+        .unwrap_or(false)
+}
+
+/// Уровень света факела из реестра
+fn torch_light() -> u8 {
+    global_registry()
+        .read()
+        .ok()
+        .and_then(|registry| registry.get_by_numeric(TORCH).map(|def| def.light_level))
+        .unwrap_or(0)
+}
@@ -2,7 +2,20 @@
 // Update System - Обновление игровой логики
 // ============================================
 
+use std::collections::HashMap;
+
 use crate::gpu::core::GameResources;
+use crate::gpu::locale::tr_args;
+use crate::gpu::terrain::BlockPos;
+use crate::gpu::blocks::BlockType;
+use crate::gpu::biomes::place_leaf_subvoxels;
+use crate::gpu::input::HapticEvent;
+use crate::gpu::gui::MenuState;
+use crate::gpu::systems::block_interaction_system::BlockInteractionSystem;
+use crate::gpu::systems::{SpawnSystem, ContainerSystem, PhotoSystem, WorldEditSystem};
+
+/// Скорость падения, при которой приземление считается ударом (блоков/с)
+const HARD_LANDING_SPEED: f32 = 18.0;
 
 /// Система обновления игровой логики
 pub struct UpdateSystem;
@@ -10,25 +23,142 @@ pub struct UpdateSystem;
 impl UpdateSystem {
     /// Основной цикл обновления
     pub fn update(resources: &mut GameResources, dt: f32, _time: f32) {
-        // 1. Обновляем игрока (физика, движение)
-        Self::update_player(resources, dt);
+        // Фоторежим: мир на паузе, двигается только камера
+        if resources.photo_mode.is_active() {
+            PhotoSystem::update(resources, dt);
+            return;
+        }
+        
+        // 1. Ломание блоков до физики: снятая из-под ног опора
+        // действует уже в этом кадре (прогресс при зажатой ЛКМ)
+        BlockInteractionSystem::update_breaking(resources, dt);
+        
+        // 2. Обновляем игрока (физика, движение)
+        let was_on_ground = resources.player.on_ground;
+        let fall_speed = -resources.player.velocity.y;
+        let changes_map = Self::update_player(resources, dt);
+        
+        // Жёсткое приземление - вибрация как от урона
+        if !was_on_ground && resources.player.on_ground && fall_speed > HARD_LANDING_SPEED {
+            resources.gamepad.play(HapticEvent::Damage);
+        }
+        
+        // Падение в пустоту - возрождение
+        SpawnSystem::update(resources);
+        
+        // Сущности блоков (сундуки) и экран открытого сундука
+        ContainerSystem::update(resources, dt, &changes_map);
+        
+        // 3. Обновляем камеру (с коллизией плеча камеры о мир)
+        Self::update_camera(resources, dt, &changes_map);
         
-        // 2. Обновляем камеру
-        resources.camera.update_from_player(&resources.player);
+        // Демо-облёт в простое главного меню (перекрывает камеру игрока)
+        let in_menu = resources.menu.state() == MenuState::Main;
+        let origin = resources.camera.position;
+        resources.attract.update(dt, in_menu, origin, &mut resources.camera);
         
-        // 3. Обновляем аудио
+        // Камера в воде: туман в рендере и глухой звук
+        resources.camera.underwater = resources.player_controller.is_in_water(resources.camera.position, &changes_map);
+        if let Some(audio) = &mut resources.audio_system {
+            audio.set_underwater(resources.camera.underwater);
+        }
+        
+        // 4. Погода (до аудио - осадки глушат звук)
+        Self::update_weather(resources, dt);
+        
+        // Тики блоков: трава, рост саженцев
+        Self::update_block_ticks(resources, dt);
+        
+        // 5. Обновляем аудио
         Self::update_audio(resources, dt);
         
-        // 4. Обновляем систему ломания блоков
-        resources.block_breaker.update(&resources.player, dt);
+        // 6. Геймпад (подключение, завершение вибрации)
+        if resources.gamepad.update() {
+            resources.attract.notify_input();
+        }
+        
+        // 7. Частицы (шаги, брызги, дым + симуляция)
+        Self::update_particles(resources, dt, &changes_map);
+        
+        // 8. Прегенерация: завершённая задача больше не нужна
+        if resources.pregen.as_ref().is_some_and(|task| task.is_finished()) {
+            resources.pregen = None;
+        }
+        
+        // 9. Календарь: на рассвете - уведомление о новом дне
+        Self::update_calendar(resources);
+        
+        // 10. Правки мира из консоли: порция блоков за кадр
+        WorldEditSystem::update(resources);
     }
     
     /// Обновление игрока
-    fn update_player(resources: &mut GameResources, dt: f32) {
+    /// Возвращает копию изменений мира для остальных проверок кадра
+    fn update_player(resources: &mut GameResources, dt: f32) -> HashMap<BlockPos, BlockType> {
         let changes = resources.world_changes.read().unwrap();
         let changes_map = changes.get_all_changes_copy();
         drop(changes);
         resources.player_controller.update(&mut resources.player, dt, &changes_map);
+        changes_map
+    }
+    
+    /// Обновление камеры
+    fn update_camera(resources: &mut GameResources, dt: f32, changes_map: &HashMap<BlockPos, BlockType>) {
+        let controller = &resources.player_controller;
+        resources.camera.update_from_player(&resources.player, dt, |origin, direction, max_distance| {
+            controller.raycast_solid(origin, direction, max_distance, changes_map)
+        });
+        resources.camera.update_fov(dt, resources.player.is_sprinting, controller.flight.is_flying());
+    }
+    
+    /// Обновление погоды и снежного покрова
+    fn update_weather(resources: &mut GameResources, dt: f32) {
+        let player_pos = resources.player.position;
+        resources.weather.update(dt, player_pos);
+        
+        if let Ok(mut changes) = resources.world_changes.write() {
+            resources.weather.accumulate_snow(dt, player_pos, &mut changes);
+        }
+        
+        if let Some(audio) = &mut resources.audio_system {
+            audio.set_weather_damping(resources.weather.params().intensity);
+        }
+    }
+    
+    /// Тики блоков; листва выросших деревьев - в субвоксели
+    fn update_block_ticks(resources: &mut GameResources, dt: f32) {
+        if let Ok(mut changes) = resources.world_changes.write() {
+            resources.block_ticks.update(dt, resources.player.position, &mut changes);
+        }
+        let leaves = resources.block_ticks.take_leaves();
+        if leaves.is_empty() {
+            return;
+        }
+        let mut storage = resources.subvoxel_storage.write().unwrap();
+        for leaf in leaves {
+            place_leaf_subvoxels(&mut storage, leaf.world_x, leaf.world_y, leaf.world_z, leaf.block_type);
+        }
+    }
+    
+    /// Подсчёт дней по времени суток рендерера и фаза луны по дню
+    fn update_calendar(resources: &mut GameResources) {
+        let Some(renderer) = &mut resources.renderer else { return };
+        let new_day = resources.day_counter.update(renderer.time_of_day());
+        renderer.set_moon_day(resources.day_counter.days());
+        if let Some(day) = new_day {
+            log::info!("День {}, {}", day, renderer.moon_phase().name());
+            if let Some(gui) = &mut resources.gui_renderer {
+                gui.toasts().push(tr_args("toast.day", &[("day", &day)]));
+            }
+        }
+    }
+    
+    /// Обновление эмиттеров и симуляции частиц
+    fn update_particles(resources: &mut GameResources, dt: f32, changes_map: &HashMap<BlockPos, BlockType>) {
+        let controller = &resources.player_controller;
+        let is_solid = |x, y, z| controller.is_block_solid(x, y, z, changes_map);
+        resources.particle_emitters.update(&mut resources.particles, dt, &resources.player, changes_map, is_solid);
+        resources.particles.update(dt, is_solid);
     }
     
     /// Обновление аудио системы
@@ -46,6 +176,7 @@ impl UpdateSystem {
                 is_moving,
                 resources.player.on_ground,
                 resources.player.is_sprinting,
+                resources.player.sneaking,
                 resources.player_controller.jump,
                 dt,
             );
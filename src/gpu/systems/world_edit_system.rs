@@ -0,0 +1,73 @@
+// ============================================
+// World Edit System - Правки областей из консоли
+// ============================================
+// ConsoleSystem разбирает /fill, /replace, /hollow, /walls и /undo и
+// ставит правку в очередь WorldEdit, а эта система каждый кадр
+// применяет следующую порцию блоков. Опора игрока перепроверяется
+// после каждой порции, как после обычных правок мира.
+
+use ultraviolet::Vec3;
+
+use crate::gpu::core::GameResources;
+use crate::gpu::locale::tr_args;
+use crate::gpu::terrain::generation::terrain_generator;
+use crate::gpu::terrain::{EditOperation, EDIT_BLOCKS_PER_FRAME};
+
+use super::BlockInteractionSystem;
+
+/// Система правок мира
+pub struct WorldEditSystem;
+
+impl WorldEditSystem {
+    /// Поставить правку области [min, max] из volume блоков в очередь
+    /// (область проверена edit_volume)
+    pub fn start(resources: &mut GameResources, operation: EditOperation, min: [i32; 3], max: [i32; 3], volume: usize) {
+        let queued = resources.world_edit.is_busy();
+        resources.world_edit.queue(operation, min, max);
+        if queued {
+            resources.console.info(format!("Правка {} блоков ждёт окончания предыдущей", volume));
+        } else if volume > EDIT_BLOCKS_PER_FRAME {
+            resources.console.info(format!("Правка {} блоков применяется по частям", volume));
+        }
+    }
+
+    /// /undo: откатить последнюю законченную правку
+    pub fn undo(resources: &mut GameResources) {
+        if resources.world_edit.is_busy() {
+            resources.console.error("Дождитесь окончания текущей правки");
+            return;
+        }
+        if !resources.world_edit.undo() {
+            resources.console.error("Нечего откатывать");
+        }
+    }
+
+    /// Кадр: следующая порция очереди
+    pub fn update(resources: &mut GameResources) {
+        let step = {
+            let mut changes = resources.world_changes.write().unwrap();
+            let generator = terrain_generator();
+            resources.world_edit.step(EDIT_BLOCKS_PER_FRAME, &mut changes, |x, y, z| generator.block_at(x, y, z))
+        };
+        let Some(step) = step else { return };
+
+        let lower = Vec3::new(step.min[0] as f32, step.min[1] as f32, step.min[2] as f32);
+        let upper = Vec3::new((step.max[0] + 1) as f32, (step.max[1] + 1) as f32, (step.max[2] + 1) as f32);
+        BlockInteractionSystem::invalidate_support(resources, lower, upper);
+
+        let Some(summary) = step.finished else { return };
+        let message = if summary.undo {
+            tr_args("toast.undone", &[("count", &summary.changed)])
+        } else {
+            tr_args("toast.blocks_changed", &[("count", &summary.changed)])
+        };
+        if summary.undo {
+            resources.console.info(format!("Откат: восстановлено {} блоков", summary.changed));
+        } else {
+            resources.console.info(format!("Изменено {} блоков (/undo - откатить)", summary.changed));
+        }
+        if let Some(gui) = &mut resources.gui_renderer {
+            gui.toasts().push(message);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+// ============================================
+// World Map System - Карта мира и метки
+// ============================================
+// M открывает полноэкранную карту разведанных чанков и отпускает курсор,
+// как инвентарь. Пока карта открыта, мышь управляет ею: ЛКМ тянет,
+// колесо масштабирует, ПКМ ставит метку в точке под курсором (высота -
+// поверхность генератора) или перекрашивает метку под курсором, СКМ
+// убирает метку. Метки и место последней смерти видны в мире маяками
+// с расстоянием.
+
+use winit::event::MouseButton;
+
+use crate::gpu::core::GameResources;
+use crate::gpu::locale::{tr, tr_args};
+use crate::gpu::save::WAYPOINT_COLORS;
+use crate::gpu::terrain::generation::terrain_generator;
+
+use super::InputSystem;
+
+/// Система карты мира
+pub struct WorldMapSystem;
+
+impl WorldMapSystem {
+    pub fn is_open(resources: &GameResources) -> bool {
+        resources.gui_renderer.as_ref().is_some_and(|gui| gui.world_map_ref().is_visible())
+    }
+
+    /// M: открыть карту или закрыть открытую
+    pub fn toggle(resources: &mut GameResources) {
+        if Self::is_open(resources) {
+            Self::close(resources);
+            return;
+        }
+        if resources.menu.is_visible() {
+            return;
+        }
+        let Some(gui) = &mut resources.gui_renderer else { return };
+        if gui.inventory().is_visible() {
+            return;
+        }
+        let map = gui.world_map();
+        map.update(resources.player.position, resources.player.yaw, &resources.explored_map);
+        map.open();
+        InputSystem::grab_cursor(resources, false);
+    }
+
+    pub fn close(resources: &mut GameResources) {
+        if let Some(gui) = &mut resources.gui_renderer {
+            gui.world_map().close();
+        }
+        InputSystem::grab_cursor(resources, true);
+    }
+
+    /// Кадр: игрок и разведанные чанки на открытой карте, маяки меток
+    pub fn update(resources: &mut GameResources) {
+        let Some(gui) = &mut resources.gui_renderer else { return };
+        gui.world_map().update(resources.player.position, resources.player.yaw, &resources.explored_map);
+        if resources.photo_mode.is_active() {
+            gui.beacons().clear();
+        } else {
+            gui.beacons().update(
+                resources.camera.view_projection_matrix(),
+                resources.player.position,
+                resources.explored_map.waypoints(),
+                resources.explored_map.death(),
+            );
+        }
+    }
+
+    /// Кнопка мыши над открытой картой
+    pub fn handle_mouse(resources: &mut GameResources, button: MouseButton, pressed: bool) {
+        let Some(gui) = &mut resources.gui_renderer else { return };
+        match button {
+            MouseButton::Left => gui.world_map().set_dragging(pressed),
+            MouseButton::Right if pressed => {
+                if let Some(name) = gui.world_map_ref().hovered_waypoint().map(str::to_string) {
+                    // Следующий цвет по кругу
+                    let map = &mut resources.explored_map;
+                    let current = map.waypoints().iter().find(|waypoint| waypoint.name == name).map(|waypoint| waypoint.color);
+                    let index = WAYPOINT_COLORS.iter().position(|&(_, color)| Some(color) == current).map_or(0, |i| i + 1);
+                    let (color_name, color) = WAYPOINT_COLORS[index % WAYPOINT_COLORS.len()];
+                    map.set_waypoint_color(&name, color);
+                    gui.toasts().push(tr_args("toast.waypoint_color", &[("name", &name), ("color", &color_name)]));
+                    return;
+                }
+                let (x, z) = gui.world_map().cursor_world();
+                let (x, z) = (x.floor() as i32, z.floor() as i32);
+                let y = terrain_generator().height(x as f32, z as f32) as i32 + 1;
+                let name = resources.explored_map.next_waypoint_name();
+                if resources.explored_map.add_waypoint(&name, [x, y, z], None) {
+                    log::info!("Метка '{}' в ({}, {}, {})", name, x, y, z);
+                    gui.toasts().push(tr_args("toast.waypoint_set", &[("name", &name), ("x", &x), ("y", &y), ("z", &z)]));
+                } else {
+                    gui.toasts().push(tr("toast.too_many_waypoints"));
+                }
+            }
+            MouseButton::Middle if pressed => {
+                if let Some(name) = gui.world_map_ref().hovered_waypoint().map(str::to_string) {
+                    resources.explored_map.remove_waypoint(&name);
+                    log::info!("Метка '{}' удалена", name);
+                    gui.toasts().push(tr_args("toast.waypoint_removed", &[("name", &name)]));
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -19,4 +19,9 @@ impl ChunkKey {
     pub fn new_section(chunk_x: i32, chunk_z: i32, section_y: i32) -> Self {
         Self { x: chunk_x, z: chunk_z, scale: 1000 + section_y }
     }
+    
+    /// Воксельный меш (полный столбец или секция), а не LOD heightmap
+    pub fn is_voxel(&self) -> bool {
+        self.scale == 1 || self.scale >= 1000
+    }
 }
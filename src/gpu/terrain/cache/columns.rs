@@ -0,0 +1,164 @@
+// ============================================
+// Column Cache - LRU кэш высот и биомов столбцов
+// ============================================
+// Высоту и биом одних и тех же столбцов спрашивают генерация чанков,
+// коллизии, ломание блоков, снег и декорации. Кэш общий для всех
+// потоков: 16 шардов под Mutex (по хэшу координат), в каждом -
+// приближённый LRU из двух поколений. Новые записи идут в текущее
+// поколение; когда оно заполнено, оно становится старым, а прежнее
+// старое выбрасывается. Попадание в старое поколение переносит запись
+// в текущее, так что вытесняются давно не нужные столбцы.
+//
+// Кэшируются только целые координаты. Рельеф от них зависит
+// однозначно: параметры мира задаются один раз за процесс (в отладке
+// перезагрузка биомов сбрасывает кэш).
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use crate::gpu::biomes::{biome_registry, biome_selector, get_biome_height, BiomeDefinition, BiomeId};
+
+/// Столбцов в кэше (по каждому виду) - около 500 чанков
+const MAX_COLUMNS: usize = 1 << 17;
+const SHARD_BITS: u32 = 4;
+const SHARDS: usize = 1 << SHARD_BITS;
+
+/// Два поколения записей одного шарда
+struct Generations<V> {
+    current: HashMap<(i32, i32), V>,
+    previous: HashMap<(i32, i32), V>,
+    /// Размер поколения
+    capacity: usize,
+}
+
+impl<V: Copy> Generations<V> {
+    fn new(capacity: usize) -> Self {
+        Self { current: HashMap::new(), previous: HashMap::new(), capacity }
+    }
+
+    fn get(&mut self, key: (i32, i32)) -> Option<V> {
+        if let Some(value) = self.current.get(&key) {
+            return Some(*value);
+        }
+        let value = self.previous.remove(&key)?;
+        self.insert(key, value);
+        Some(value)
+    }
+
+    fn insert(&mut self, key: (i32, i32), value: V) {
+        if self.current.len() >= self.capacity {
+            self.previous = std::mem::take(&mut self.current);
+        }
+        self.current.insert(key, value);
+    }
+}
+
+/// Потокобезопасный LRU по столбцам (x, z)
+pub struct ColumnLru<V> {
+    shards: Box<[Mutex<Generations<V>>]>,
+}
+
+impl<V: Copy> ColumnLru<V> {
+    /// capacity - сколько столбцов гарантированно помнится
+    pub fn new(capacity: usize) -> Self {
+        let per_shard = capacity.div_ceil(SHARDS).max(1);
+        Self { shards: (0..SHARDS).map(|_| Mutex::new(Generations::new(per_shard))).collect() }
+    }
+
+    /// Значение из кэша или посчитанное compute (вне блокировки)
+    pub fn get_or_insert_with(&self, x: i32, z: i32, compute: impl FnOnce() -> V) -> V {
+        let shard = &self.shards[shard_index(x, z)];
+        if let Some(value) = shard.lock().ok().and_then(|mut shard| shard.get((x, z))) {
+            return value;
+        }
+        let value = compute();
+        if let Ok(mut shard) = shard.lock() {
+            shard.insert((x, z), value);
+        }
+        value
+    }
+
+    /// Забыть все столбцы
+    #[cfg(debug_assertions)]
+    pub fn clear(&self) {
+        for shard in self.shards.iter() {
+            if let Ok(mut shard) = shard.lock() {
+                shard.current.clear();
+                shard.previous.clear();
+            }
+        }
+    }
+}
+
+fn shard_index(x: i32, z: i32) -> usize {
+    let hash = (x as u32).wrapping_mul(0x9E37_79B1) ^ (z as u32).wrapping_mul(0x85EB_CA77);
+    (hash >> (32 - SHARD_BITS)) as usize
+}
+
+static HEIGHTS: OnceLock<ColumnLru<f32>> = OnceLock::new();
+static BIOMES: OnceLock<ColumnLru<BiomeId>> = OnceLock::new();
+
+/// Высота шумового мира по биомам (целые координаты - через кэш)
+pub fn column_height(x: f32, z: f32) -> f32 {
+    if x.fract() != 0.0 || z.fract() != 0.0 {
+        return get_biome_height(x, z);
+    }
+    HEIGHTS
+        .get_or_init(|| ColumnLru::new(MAX_COLUMNS))
+        .get_or_insert_with(x as i32, z as i32, || get_biome_height(x, z))
+}
+
+/// Биом столбца
+pub fn column_biome(x: i32, z: i32) -> &'static BiomeDefinition {
+    let id = BIOMES
+        .get_or_init(|| ColumnLru::new(MAX_COLUMNS))
+        .get_or_insert_with(x, z, || biome_selector().get_biome(x, z));
+    biome_registry().get(id)
+}
+
+/// Сбросить кэши после перезагрузки определений биомов
+#[cfg(debug_assertions)]
+pub fn clear_columns() {
+    if let Some(cache) = HEIGHTS.get() {
+        cache.clear();
+    }
+    if let Some(cache) = BIOMES.get() {
+        cache.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recently_used_columns_survive_eviction() {
+        let mut cache = Generations::new(2);
+        cache.insert((0, 0), 1);
+        cache.insert((1, 0), 2);
+        // Поколение заполнено: (0, 0) и (1, 0) уходят в старое
+        cache.insert((2, 0), 3);
+        // Попадание в старом поколении продлевает жизнь записи
+        assert_eq!(cache.get((0, 0)), Some(1));
+        // Текущее снова полно: (1, 0) не трогали - он выбрасывается
+        cache.insert((3, 0), 4);
+        assert_eq!(cache.get((1, 0)), None);
+        assert_eq!(cache.get((0, 0)), Some(1));
+        assert_eq!(cache.get((2, 0)), Some(3));
+    }
+
+    #[test]
+    fn compute_runs_once_per_column() {
+        let cache = ColumnLru::new(64);
+        let mut calls = 0;
+        for _ in 0..3 {
+            let value = cache.get_or_insert_with(-7, 12, || {
+                calls += 1;
+                42.0
+            });
+            assert_eq!(value, 42.0);
+        }
+        assert_eq!(calls, 1);
+        assert!(shard_index(i32::MIN, i32::MAX) < SHARDS);
+    }
+}
@@ -1,3 +1,7 @@
 mod chunk_key;
+mod columns;
 
 pub use chunk_key::ChunkKey;
+pub use columns::{column_biome, column_height};
+#[cfg(debug_assertions)]
+pub use columns::clear_columns;
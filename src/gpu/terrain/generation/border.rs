@@ -0,0 +1,77 @@
+// ============================================
+// World Border - Граница мира
+// ============================================
+// Квадрат со стороной 2 * radius вокруг (0, 0). Радиус хранится
+// в параметрах генерации (заголовок сохранения); 0 - граница выключена.
+// Игрок не выходит за стену, чанки за ней не загружаются.
+
+use crate::gpu::terrain::voxel::CHUNK_SIZE;
+
+use super::config::world_gen_config;
+
+/// Граница мира
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WorldBorder {
+    /// Половина стороны квадрата (блоки)
+    pub radius: f32,
+}
+
+impl WorldBorder {
+    /// None - граница выключена
+    pub fn new(radius: u32) -> Option<Self> {
+        (radius > 0).then_some(Self { radius: radius as f32 })
+    }
+
+    /// Координата X или Z внутри границы с зазором margin от стены
+    #[inline]
+    pub fn clamp(&self, value: f32, margin: f32) -> f32 {
+        let limit = (self.radius - margin).max(0.0);
+        value.clamp(-limit, limit)
+    }
+
+    /// Пересекает ли границу область span x span чанков от (chunk_x, chunk_z)
+    pub fn contains_chunks(&self, chunk_x: i32, chunk_z: i32, span: i32) -> bool {
+        let overlaps = |chunk: i32| {
+            let min = (chunk * CHUNK_SIZE) as f32;
+            let max = ((chunk + span) * CHUNK_SIZE) as f32;
+            max > -self.radius && min < self.radius
+        };
+        overlaps(chunk_x) && overlaps(chunk_z)
+    }
+}
+
+/// Граница текущего мира
+pub fn world_border() -> Option<WorldBorder> {
+    WorldBorder::new(world_gen_config().border_radius)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_without_radius() {
+        assert_eq!(WorldBorder::new(0), None);
+    }
+
+    #[test]
+    fn clamps_inside_with_margin() {
+        let border = WorldBorder::new(100).unwrap();
+        assert_eq!(border.clamp(150.0, 0.3), 99.7);
+        assert_eq!(border.clamp(-150.0, 0.3), -99.7);
+        assert_eq!(border.clamp(42.0, 0.3), 42.0);
+    }
+
+    #[test]
+    fn chunks_past_the_wall_are_outside() {
+        let border = WorldBorder::new(40).unwrap();
+        // Стена в x = 40 проходит через чанк 2 (32..48)
+        assert!(border.contains_chunks(2, 0, 1));
+        assert!(!border.contains_chunks(3, 0, 1));
+        assert!(border.contains_chunks(-3, -3, 1));
+        assert!(!border.contains_chunks(-4, 0, 1));
+        // LOD область из 8 чанков задевает границу краем
+        assert!(border.contains_chunks(-8, 0, 8));
+        assert!(!border.contains_chunks(8, 0, 8));
+    }
+}
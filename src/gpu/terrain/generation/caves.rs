@@ -1,8 +1,25 @@
 // ============================================
 // Cave System - 3D Noise для пещер
 // ============================================
+// Обычные ходы - порог 3D шума. Поверх них - редкие большие залы
+// (низкочастотный сплющенный шум) и вертикальные шахты (одна на
+// ячейку 48x48 с некоторой вероятностью). Подземный биом выбирается
+// отдельным 3D климатическим шумом: пышные пещеры со светящимися
+// лозами, сталактитовые пещеры и глубокая тьма у дна мира.
 
-use super::noise::noise3d;
+use crate::gpu::blocks::{BlockType, MOSS_BLOCK, GLOW_VINES, DRIPSTONE_BLOCK, POINTED_DRIPSTONE, SCULK, SCULK_SENSOR};
+
+use super::noise::{noise3d, hash3d};
+use super::config::{world_gen_config, world_noise_offset};
+
+/// Размер ячейки вертикальных шахт (блоки)
+const SHAFT_CELL: i32 = 48;
+/// Доля ячеек с шахтой
+const SHAFT_CHANCE: f32 = 0.12;
+/// Масштаб климатического шума подземных биомов
+const CAVE_CLIMATE_SCALE: f32 = 0.012;
+/// Глубокая тьма - только ниже этой высоты
+const DEEP_DARK_MAX_Y: i32 = -30;
 
 /// Параметры генерации пещер
 #[derive(Clone, Copy)]
@@ -12,16 +29,19 @@ pub struct CaveParams {
     pub surface_offset: i32,
     pub min_height: i32,
     pub vertical_squeeze: f32,
+    /// Порог шума больших залов (выше - реже)
+    pub room_threshold: f32,
 }
 
 impl Default for CaveParams {
     fn default() -> Self {
         Self {
             scale: 0.025,
-            threshold: 0.48,
+            threshold: world_gen_config().cave_threshold(0.48),
             surface_offset: 8,
             min_height: -64,
             vertical_squeeze: 0.5,
+            room_threshold: world_gen_config().cave_threshold(0.72),
         }
     }
 }
@@ -29,10 +49,151 @@ impl Default for CaveParams {
 /// Проверяет, является ли блок пещерой
 #[inline]
 pub fn is_cave(x: i32, y: i32, z: i32, params: &CaveParams) -> bool {
-    let fx = x as f32 * params.scale;
+    let (offset_x, offset_z) = world_noise_offset();
+    let fx = (x as f32 + offset_x) * params.scale;
     let fy = y as f32 * params.scale * params.vertical_squeeze;
-    let fz = z as f32 * params.scale;
+    let fz = (z as f32 + offset_z) * params.scale;
     
     let cave_noise = noise3d(fx, fy, fz);
-    cave_noise > params.threshold
+    cave_noise > params.threshold || is_cavern_room(fx, fy, fz, params) || is_shaft(x, y, z)
+}
+
+/// Большой зал: тот же масштаб координат, но в 3 раза реже
+/// по горизонтали и сплющен по высоте - широкие низкие полости
+#[inline]
+fn is_cavern_room(fx: f32, fy: f32, fz: f32, params: &CaveParams) -> bool {
+    noise3d(fx * 0.33 + 57.0, fy * 0.5, fz * 0.33 + 57.0) > params.room_threshold
+}
+
+/// Вертикальная шахта: ствол радиуса 2-3 блока, слегка виляющий по высоте,
+/// от случайной глубины до потолка пещер
+#[inline]
+fn is_shaft(x: i32, y: i32, z: i32) -> bool {
+    let cell_x = x.div_euclid(SHAFT_CELL);
+    let cell_z = z.div_euclid(SHAFT_CELL);
+    if hash3d(cell_x, 911, cell_z) >= SHAFT_CHANCE {
+        return false;
+    }
+
+    let bottom = -50 + (hash3d(cell_x, 913, cell_z) * 40.0) as i32;
+    if y < bottom {
+        return false;
+    }
+
+    let margin = 8.0;
+    let span = SHAFT_CELL as f32 - margin * 2.0;
+    let wobble = (y as f32 * 0.15).sin() * 1.5;
+    let center_x = (cell_x * SHAFT_CELL) as f32 + margin + hash3d(cell_x, 917, cell_z) * span + wobble;
+    let center_z = (cell_z * SHAFT_CELL) as f32 + margin + hash3d(cell_x, 919, cell_z) * span - wobble;
+    let radius = 2.0 + hash3d(cell_x, 921, cell_z);
+
+    let dx = x as f32 + 0.5 - center_x;
+    let dz = z as f32 + 0.5 - center_z;
+    dx * dx + dz * dz <= radius * radius
+}
+
+/// Подземный биом
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CaveBiome {
+    /// Обычная каменная пещера
+    Stone,
+    /// Мох и светящиеся лозы
+    Lush,
+    /// Сталактиты и сталагмиты
+    Dripstone,
+    /// Скалк у дна мира, почти без света
+    DeepDark,
+}
+
+impl CaveBiome {
+    /// Блок пола (None - порода как есть)
+    pub fn floor_block(self) -> Option<BlockType> {
+        match self {
+            CaveBiome::Stone => None,
+            CaveBiome::Lush => Some(MOSS_BLOCK),
+            CaveBiome::Dripstone => Some(DRIPSTONE_BLOCK),
+            CaveBiome::DeepDark => Some(SCULK),
+        }
+    }
+
+    /// Блок потолка
+    pub fn ceiling_block(self) -> Option<BlockType> {
+        match self {
+            CaveBiome::Stone => None,
+            CaveBiome::Lush => Some(MOSS_BLOCK),
+            CaveBiome::Dripstone => Some(DRIPSTONE_BLOCK),
+            CaveBiome::DeepDark => Some(SCULK),
+        }
+    }
+
+    /// Что свисает с потолка: блок и длина (rng - случайное число клетки)
+    pub fn hanging(self, rng: f32) -> Option<(BlockType, i32)> {
+        match self {
+            CaveBiome::Lush if rng < 0.18 => Some((GLOW_VINES, 1 + (rng * 1000.0) as i32 % 4)),
+            CaveBiome::Dripstone if rng < 0.12 => Some((POINTED_DRIPSTONE, 1 + (rng * 1000.0) as i32 % 3)),
+            _ => None,
+        }
+    }
+
+    /// Что растёт с пола
+    pub fn rising(self, rng: f32) -> Option<(BlockType, i32)> {
+        match self {
+            CaveBiome::Dripstone if rng < 0.08 => Some((POINTED_DRIPSTONE, 1 + (rng * 1000.0) as i32 % 2)),
+            CaveBiome::DeepDark if rng < 0.015 => Some((SCULK_SENSOR, 1)),
+            _ => None,
+        }
+    }
+}
+
+/// Подземный биом в точке (по 3D климатическому шуму)
+pub fn cave_biome(x: i32, y: i32, z: i32) -> CaveBiome {
+    let (offset_x, offset_z) = world_noise_offset();
+    let fx = (x as f32 + offset_x) * CAVE_CLIMATE_SCALE;
+    let fy = y as f32 * CAVE_CLIMATE_SCALE * 1.5;
+    let fz = (z as f32 + offset_z) * CAVE_CLIMATE_SCALE;
+
+    if y < DEEP_DARK_MAX_Y && noise3d(fx + 300.0, fy, fz + 300.0) > 0.58 {
+        return CaveBiome::DeepDark;
+    }
+
+    let humidity = noise3d(fx + 100.0, fy, fz + 100.0);
+    if humidity > 0.62 {
+        CaveBiome::Lush
+    } else if humidity < 0.36 {
+        CaveBiome::Dripstone
+    } else {
+        CaveBiome::Stone
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shafts_are_narrow_columns() {
+        // Найти ячейку с шахтой и проверить, что ствол узкий
+        let (cell_x, cell_z) = (0..200)
+            .flat_map(|cx| (0..4).map(move |cz| (cx, cz)))
+            .find(|&(cx, cz)| hash3d(cx, 911, cz) < SHAFT_CHANCE)
+            .expect("ячейка с шахтой");
+        let y = 20;
+        let base_x = cell_x * SHAFT_CELL;
+        let base_z = cell_z * SHAFT_CELL;
+        let open: usize = (0..SHAFT_CELL)
+            .flat_map(|dx| (0..SHAFT_CELL).map(move |dz| (dx, dz)))
+            .filter(|&(dx, dz)| is_shaft(base_x + dx, y, base_z + dz))
+            .count();
+        assert!(open > 4 && open < 40, "площадь сечения шахты: {}", open);
+        assert!(!is_shaft(base_x, -64, base_z));
+    }
+
+    #[test]
+    fn deep_dark_stays_deep() {
+        for x in (0..2000).step_by(37) {
+            for y in (DEEP_DARK_MAX_Y..60).step_by(7) {
+                assert_ne!(cave_biome(x, y, x / 2), CaveBiome::DeepDark);
+            }
+        }
+    }
 }
@@ -2,17 +2,38 @@
 // Terrain Colors - Цвета по биому
 // ============================================
 
-use crate::gpu::blocks::get_face_colors;
-use crate::gpu::biomes::biome_selector;
+use crate::gpu::blocks::{get_face_colors, GRASS};
+use crate::gpu::biomes::{biome_selector, biome_registry};
 
-/// Получить цвет террейна по координатам (использует биом)
+use super::generator::terrain_generator;
+
+/// Цвет поверхности для LOD (зависит от генератора мира)
 #[inline]
 pub fn get_color(x: f32, z: f32, is_top: bool) -> [f32; 3] {
-    let biome = biome_selector().get_biome_def(x as i32, z as i32);
-    let block = biome.surface_block;
+    terrain_generator().surface_color(x, z, is_top)
+}
+
+/// Цвет по биомам (смесь биомов на границах)
+#[inline]
+pub fn get_biome_color(x: f32, z: f32, is_top: bool) -> [f32; 3] {
+    let blend = biome_selector().get_biome_blend(x, z);
+    let registry = biome_registry();
     
-    let (top_color, side_color) = get_face_colors(block);
-    if is_top { top_color } else { side_color }
+    let mut color = [0.0; 3];
+    for (biome_id, weight) in blend.iter() {
+        let biome = registry.get(biome_id);
+        let (top_color, side_color) = get_face_colors(biome.surface_block);
+        let (base, tint) = if is_top {
+            let tint = if biome.surface_block == GRASS { biome.grass_tint } else { [1.0; 3] };
+            (top_color, tint)
+        } else {
+            (side_color, [1.0; 3])
+        };
+        color[0] += base[0] * tint[0] * weight;
+        color[1] += base[1] * tint[1] * weight;
+        color[2] += base[2] * tint[2] * weight;
+    }
+    color
 }
 
 /// Старая версия для совместимости (deprecated)
@@ -0,0 +1,200 @@
+// ============================================
+// World Generation Config - Параметры генерации мира
+// ============================================
+// Задаются при создании мира (флаги командной строки), хранятся
+// в заголовке сохранения и применяются один раз до начала генерации.
+// Генерация читает их через world_gen_config() - так же, как
+// climate_map() и biome_selector().
+//
+// Значения по умолчанию дают прежний мир бит в бит:
+// сид DEFAULT_SEED не сдвигает шум, множители равны 1.
+
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::gpu::core::DEFAULT_SEED;
+
+use super::noise::hash3d;
+
+/// Максимальный сдвиг области шума от сида (блоки)
+const SEED_OFFSET_RANGE: f32 = 65536.0;
+
+/// Допустимый радиус границы мира (блоки)
+const MIN_BORDER_RADIUS: u32 = 32;
+const MAX_BORDER_RADIUS: u32 = 1_000_000;
+
+/// Тип мира (генератор рельефа)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum WorldPreset {
+    /// Шумовой рельеф с биомами, пещерами и деревьями
+    #[default]
+    Default,
+    /// Плоский мир из слоёв (world/superflat.json)
+    Superflat,
+    /// Пустота с платформой на спавне
+    Void,
+}
+
+impl WorldPreset {
+    /// Разбор имени из командной строки
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "default" | "normal" => Some(Self::Default),
+            "superflat" | "flat" => Some(Self::Superflat),
+            "void" | "empty" => Some(Self::Void),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Superflat => "superflat",
+            Self::Void => "void",
+        }
+    }
+}
+
+/// Параметры генерации мира
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WorldGenConfig {
+    pub seed: u64,
+    /// Генератор рельефа
+    pub preset: WorldPreset,
+    /// Уровень моря: ниже - вода в пустотах
+    pub sea_level: i32,
+    /// Плотность пещер (0 - нет, 1 - стандартно, 2 - максимум)
+    pub cave_density: f32,
+    /// Масштаб биомов (2 - биомы вдвое крупнее)
+    pub biome_scale: f32,
+    /// Множитель рельефа по высоте
+    pub terrain_amplitude: f32,
+    /// Граница мира: половина стороны квадрата вокруг (0, 0), 0 - без границы
+    pub border_radius: u32,
+}
+
+impl Default for WorldGenConfig {
+    fn default() -> Self {
+        Self::with_seed(DEFAULT_SEED)
+    }
+}
+
+impl WorldGenConfig {
+    /// Стандартные параметры с заданным сидом
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            seed,
+            preset: WorldPreset::Default,
+            sea_level: 0,
+            cave_density: 1.0,
+            biome_scale: 1.0,
+            terrain_amplitude: 1.0,
+            border_radius: 0,
+        }
+    }
+
+    /// Привести значения к допустимым диапазонам
+    pub fn sanitized(self) -> Self {
+        Self {
+            seed: self.seed,
+            preset: self.preset,
+            sea_level: self.sea_level.clamp(-24, 96),
+            cave_density: self.cave_density.clamp(0.0, 2.0),
+            biome_scale: self.biome_scale.clamp(0.25, 8.0),
+            terrain_amplitude: self.terrain_amplitude.clamp(0.1, 4.0),
+            // Граница не ближе пары чанков от спавна
+            border_radius: match self.border_radius {
+                0 => 0,
+                radius => radius.clamp(MIN_BORDER_RADIUS, MAX_BORDER_RADIUS),
+            },
+        }
+    }
+
+    /// Параметры из командной строки:
+    /// --seed N --preset default|superflat|void --sea-level N --cave-density F
+    /// --biome-scale F --terrain-amplitude F --border N
+    pub fn from_args(args: &[String]) -> Self {
+        let value = |flag: &str| {
+            args.iter()
+                .position(|arg| arg == flag)
+                .and_then(|i| args.get(i + 1))
+                .map(String::as_str)
+        };
+        let defaults = Self::default();
+
+        // Сид: число или произвольная строка (хешируется)
+        let seed = match value("--seed") {
+            Some(text) => text.parse().unwrap_or_else(|_| hash_seed(text)),
+            None => defaults.seed,
+        };
+
+        let preset = match value("--preset") {
+            Some(name) => WorldPreset::parse(name).unwrap_or_else(|| {
+                log::warn!("Неизвестный тип мира '{}', используется default", name);
+                defaults.preset
+            }),
+            None => defaults.preset,
+        };
+
+        Self {
+            seed,
+            preset,
+            sea_level: value("--sea-level").and_then(|v| v.parse().ok()).unwrap_or(defaults.sea_level),
+            cave_density: value("--cave-density").and_then(|v| v.parse().ok()).unwrap_or(defaults.cave_density),
+            biome_scale: value("--biome-scale").and_then(|v| v.parse().ok()).unwrap_or(defaults.biome_scale),
+            terrain_amplitude: value("--terrain-amplitude").and_then(|v| v.parse().ok()).unwrap_or(defaults.terrain_amplitude),
+            border_radius: value("--border").and_then(|v| v.parse().ok()).unwrap_or(defaults.border_radius),
+        }
+        .sanitized()
+    }
+
+    /// Сдвиг области шума рельефа от сида (0 для DEFAULT_SEED)
+    pub fn noise_offset(&self) -> (f32, f32) {
+        let delta = self.seed ^ DEFAULT_SEED;
+        if delta == 0 {
+            return (0.0, 0.0);
+        }
+        let (lo, hi) = (delta as i32, (delta >> 32) as i32);
+        (
+            (hash3d(lo, hi, 1) - 0.5) * 2.0 * SEED_OFFSET_RANGE,
+            (hash3d(lo, hi, 2) - 0.5) * 2.0 * SEED_OFFSET_RANGE,
+        )
+    }
+
+    /// Сид климатической карты (42 - исторический сид для DEFAULT_SEED)
+    pub fn climate_seed(&self) -> u64 {
+        42 + (self.seed ^ DEFAULT_SEED) % 10_000
+    }
+
+    /// Порог шума пещер с учётом плотности
+    pub fn cave_threshold(&self, base_threshold: f32) -> f32 {
+        1.0 - (1.0 - base_threshold) * self.cave_density
+    }
+}
+
+/// Строковый сид -> число (FNV-1a)
+fn hash_seed(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+static WORLD_GEN_CONFIG: OnceLock<WorldGenConfig> = OnceLock::new();
+
+/// Текущие параметры генерации (по умолчанию, если мир ещё не задал свои)
+pub fn world_gen_config() -> &'static WorldGenConfig {
+    WORLD_GEN_CONFIG.get_or_init(WorldGenConfig::default)
+}
+
+/// Сдвиг шума текущего мира (кэшируется - вызывается на каждый блок)
+pub fn world_noise_offset() -> (f32, f32) {
+    static NOISE_OFFSET: OnceLock<(f32, f32)> = OnceLock::new();
+    *NOISE_OFFSET.get_or_init(|| world_gen_config().noise_offset())
+}
+
+/// Установить параметры мира. Должно вызываться до первой генерации:
+/// false - генерация уже началась с другими параметрами
+pub fn set_world_gen_config(config: WorldGenConfig) -> bool {
+    WORLD_GEN_CONFIG.set(config).is_ok() || *world_gen_config() == config
+}
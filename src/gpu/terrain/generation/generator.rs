@@ -0,0 +1,140 @@
+// ============================================
+// Terrain Generator - Сменные генераторы рельефа
+// ============================================
+// Тип мира выбирается при создании (WorldPreset) и определяет
+// генератор: шумовой мир с биомами, плоский мир из слоёв или пустоту.
+// Генератор задаётся один раз до первой генерации, как и параметры
+// мира; get_height, is_solid_3d, get_color и VoxelChunk::new идут
+// через него, поэтому остальной код не знает о типе мира.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::gpu::blocks::{BlockType, AIR, DEEPSLATE};
+use crate::gpu::biomes::BiomeTerrainGen;
+use crate::gpu::terrain::voxel::{VoxelChunk, ChunkGenerationResult};
+use crate::gpu::terrain::voxel::constants::TERRAIN_FLOOR;
+use crate::gpu::terrain::BlockPos;
+use crate::gpu::terrain::cache::{column_biome, column_height};
+
+use super::caves::{CaveParams, is_cave};
+use super::color::get_biome_color;
+use super::config::{world_gen_config, WorldPreset};
+use super::height_cache::cached_height;
+use super::superflat::{SuperflatGenerator, SuperflatSettings};
+use super::void::VoidGenerator;
+
+/// Генератор рельефа мира (вызывается из потоков генерации)
+pub trait TerrainGenerator: Send + Sync {
+    /// Высота верхнего твёрдого блока столбца
+    fn height(&self, x: f32, z: f32) -> f32;
+
+    /// Твёрдость точки (снег, освещение)
+    fn is_solid(&self, x: f32, y: f32, z: f32) -> bool;
+
+    /// Блок без изменений мира - быстрая оценка для коллизий,
+    /// звука и ломания блоков (без руд и 3D карнизов)
+    fn block_at(&self, x: i32, y: i32, z: i32) -> BlockType;
+
+    /// Вода без изменений мира: ниже уровня моря вне тверди
+    /// (как заливает generate_block)
+    fn is_water(&self, x: i32, y: i32, z: i32) -> bool {
+        y < world_gen_config().sea_level && !self.is_solid(x as f32, y as f32, z as f32)
+    }
+
+    /// Воксельный чанк с учётом изменений мира
+    fn generate_chunk(
+        &self,
+        chunk_x: i32,
+        chunk_z: i32,
+        world_changes: &HashMap<BlockPos, BlockType>,
+    ) -> ChunkGenerationResult;
+
+    /// Цвет поверхности для LOD
+    fn surface_color(&self, x: f32, z: f32, is_top: bool) -> [f32; 3] {
+        get_biome_color(x, z, is_top)
+    }
+
+    /// Деревья, трава и цветы (и их импосторы на LOD)
+    fn has_features(&self) -> bool {
+        true
+    }
+
+    /// Есть ли рельеф для дальних LOD чанков
+    fn has_lod_terrain(&self) -> bool {
+        true
+    }
+}
+
+/// Шумовой мир с биомами, пещерами и деревьями
+pub struct NoiseGenerator;
+
+impl TerrainGenerator for NoiseGenerator {
+    fn height(&self, x: f32, z: f32) -> f32 {
+        cached_height(x, z).unwrap_or_else(|| column_height(x, z))
+    }
+
+    fn is_solid(&self, x: f32, y: f32, z: f32) -> bool {
+        BiomeTerrainGen::is_solid(x, y, z)
+    }
+
+    fn block_at(&self, x: i32, y: i32, z: i32) -> BlockType {
+        let terrain_height = self.height(x as f32, z as f32) as i32;
+
+        // Над поверхностью и под дном рельефа - воздух
+        if y > terrain_height || y < TERRAIN_FLOOR {
+            return AIR;
+        }
+
+        let cave_params = CaveParams::default();
+        let cave_ceiling = terrain_height - cave_params.surface_offset;
+        if y >= cave_params.min_height && y < cave_ceiling && is_cave(x, y, z, &cave_params) {
+            return AIR;
+        }
+
+        // Слои биома по глубине
+        let biome = column_biome(x, z);
+        if y < TERRAIN_FLOOR + 3 {
+            DEEPSLATE
+        } else if y < terrain_height - 4 {
+            biome.deep_block
+        } else if y < terrain_height {
+            biome.subsurface_block
+        } else {
+            biome.surface_block
+        }
+    }
+
+    fn generate_chunk(
+        &self,
+        chunk_x: i32,
+        chunk_z: i32,
+        world_changes: &HashMap<BlockPos, BlockType>,
+    ) -> ChunkGenerationResult {
+        VoxelChunk::generate_noise(chunk_x, chunk_z, world_changes)
+    }
+}
+
+static TERRAIN_GENERATOR: OnceLock<Box<dyn TerrainGenerator>> = OnceLock::new();
+
+/// Генератор для типа мира (слои используются только плоским миром)
+pub fn generator_for(preset: WorldPreset, superflat: &SuperflatSettings) -> Box<dyn TerrainGenerator> {
+    match preset {
+        WorldPreset::Default => Box::new(NoiseGenerator),
+        WorldPreset::Superflat => Box::new(SuperflatGenerator::new(superflat)),
+        WorldPreset::Void => Box::new(VoidGenerator),
+    }
+}
+
+/// Генератор текущего мира (по типу из параметров мира, если не задан явно)
+pub fn terrain_generator() -> &'static dyn TerrainGenerator {
+    TERRAIN_GENERATOR
+        .get_or_init(|| generator_for(world_gen_config().preset, &SuperflatSettings::default()))
+        .as_ref()
+}
+
+/// Установить генератор мира. Должно вызываться до первой генерации:
+/// false - генератор уже выбран
+pub fn set_terrain_generator(generator: Box<dyn TerrainGenerator>) -> bool {
+    TERRAIN_GENERATOR.set(generator).is_ok()
+}
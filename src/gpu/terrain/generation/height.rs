@@ -2,12 +2,14 @@
 // Height Map - Генерация карты высот с биомами
 // ============================================
 
-use crate::gpu::biomes::{BiomeTerrainGen, get_biome_height};
+use crate::gpu::biomes::BiomeTerrainGen;
 
-/// Базовая высота террейна (теперь с учётом биомов)
+use super::generator::terrain_generator;
+
+/// Высота поверхности текущего генератора мира
 #[inline]
 pub fn get_height(x: f32, z: f32) -> f32 {
-    get_biome_height(x, z)
+    terrain_generator().height(x, z)
 }
 
 /// Высота для LOD (центрированная)
@@ -29,5 +31,5 @@ pub fn get_3d_density(x: f32, y: f32, z: f32) -> f32 {
 /// Проверка твёрдости блока в 3D (для гор)
 #[inline]
 pub fn is_solid_3d(x: f32, y: f32, z: f32) -> bool {
-    BiomeTerrainGen::is_solid(x, y, z)
+    terrain_generator().is_solid(x, y, z)
 }
@@ -0,0 +1,67 @@
+// ============================================
+// Height Cache - Кэш карты высот по чанкам
+// ============================================
+// Смешанная по биомам высота - самая дорогая часть генерации
+// (4 угла решётки, климат, шум). Прегенерация заранее считает высоты
+// столбцов и кладёт их сюда, get_height сначала смотрит в кэш.
+// Пока кэш пуст, проверка стоит одну атомарную загрузку.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{OnceLock, RwLock};
+
+use crate::gpu::terrain::voxel::CHUNK_SIZE;
+
+/// Предел кэша (~1 КБ на чанк)
+pub const MAX_CACHED_CHUNKS: usize = 32768;
+
+const COLUMNS: usize = (CHUNK_SIZE * CHUNK_SIZE) as usize;
+
+type ChunkHeights = Box<[f32; COLUMNS]>;
+
+static HEIGHT_CACHE: OnceLock<RwLock<HashMap<(i32, i32), ChunkHeights>>> = OnceLock::new();
+static CACHED_CHUNKS: AtomicUsize = AtomicUsize::new(0);
+
+fn height_cache() -> &'static RwLock<HashMap<(i32, i32), ChunkHeights>> {
+    HEIGHT_CACHE.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Высота из кэша (только для целых координат)
+#[inline]
+pub fn cached_height(x: f32, z: f32) -> Option<f32> {
+    if CACHED_CHUNKS.load(Ordering::Relaxed) == 0 || x.fract() != 0.0 || z.fract() != 0.0 {
+        return None;
+    }
+    let (x, z) = (x as i32, z as i32);
+    let key = (x.div_euclid(CHUNK_SIZE), z.div_euclid(CHUNK_SIZE));
+    let index = (z.rem_euclid(CHUNK_SIZE) * CHUNK_SIZE + x.rem_euclid(CHUNK_SIZE)) as usize;
+
+    let cache = height_cache().read().ok()?;
+    cache.get(&key).map(|heights| heights[index])
+}
+
+/// Есть ли чанк в кэше
+pub fn has_chunk_heights(chunk_x: i32, chunk_z: i32) -> bool {
+    CACHED_CHUNKS.load(Ordering::Relaxed) > 0
+        && height_cache().read().map(|cache| cache.contains_key(&(chunk_x, chunk_z))).unwrap_or(false)
+}
+
+/// Посчитать и сохранить высоты чанка. false - кэш заполнен
+pub fn cache_chunk_heights(chunk_x: i32, chunk_z: i32, height: impl Fn(f32, f32) -> f32) -> bool {
+    if CACHED_CHUNKS.load(Ordering::Relaxed) >= MAX_CACHED_CHUNKS {
+        return false;
+    }
+
+    // Считаем без блокировки, пишем одной короткой записью
+    let mut heights: ChunkHeights = Box::new([0.0; COLUMNS]);
+    let (base_x, base_z) = (chunk_x * CHUNK_SIZE, chunk_z * CHUNK_SIZE);
+    for (index, value) in heights.iter_mut().enumerate() {
+        let (lx, lz) = (index as i32 % CHUNK_SIZE, index as i32 / CHUNK_SIZE);
+        *value = height((base_x + lx) as f32, (base_z + lz) as f32);
+    }
+
+    let Ok(mut cache) = height_cache().write() else { return false };
+    cache.insert((chunk_x, chunk_z), heights);
+    CACHED_CHUNKS.store(cache.len(), Ordering::Relaxed);
+    true
+}
@@ -1,9 +1,23 @@
 pub mod noise;
 pub mod caves;
+pub mod ores;
 pub mod height;
 pub mod color;
+pub mod height_cache;
+pub mod config;
+pub mod border;
+pub mod generator;
+pub mod superflat;
+pub mod void;
+pub mod spawn;
 
-pub use caves::{CaveParams, is_cave};
+pub use caves::{CaveParams, CaveBiome, is_cave, cave_biome};
+pub use ores::ore_config;
 pub use height::{get_height, get_lod_height, is_solid_3d};
 pub use color::get_color;
 pub use noise::{noise3d, hash3d};
+pub use config::{WorldGenConfig, WorldPreset, world_gen_config, world_noise_offset, set_world_gen_config};
+pub use border::{WorldBorder, world_border};
+pub use generator::{TerrainGenerator, generator_for, terrain_generator, set_terrain_generator};
+pub use superflat::{SuperflatSettings, SUPERFLAT_FILE};
+pub use spawn::{world_spawn, in_spawn_protection};
@@ -0,0 +1,327 @@
+// ============================================
+// Ore Distribution - Руды из JSON
+// ============================================
+// Распределение руд описано в assets/worldgen/ores.json:
+// - ores: жилы по порогу 3D шума. Кривая height задаёт вес по высоте
+//   (кусочно-линейно, вне кривой - 0), вес снижает долю руды
+// - large_veins: редкие большие жилы - трубка руды с наполнителем
+//   длиной в несколько чанков. Жила задаётся регионом 64x64 по хэшу
+//   сида, поэтому любой чанк получает свой кусок одинаково, без
+//   обмена данными между чанками.
+
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use crate::gpu::blocks::{global_registry, BlockType};
+
+use super::noise::{noise3d, hash3d};
+use super::config::world_noise_offset;
+
+/// Размер региона, в котором может начаться большая жила
+const VEIN_REGION: i32 = 64;
+/// Длина большой жилы не больше региона: хватает соседних регионов 3x3
+const MAX_VEIN_LENGTH: f32 = VEIN_REGION as f32 - 4.0;
+
+#[derive(Debug, Deserialize)]
+struct OresFile {
+    #[serde(default)]
+    ores: Vec<OreDef>,
+    #[serde(default)]
+    large_veins: Vec<LargeVeinDef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OreDef {
+    block: String,
+    /// Точки [y, вес] по возрастанию y
+    height: Vec<(f32, f32)>,
+    /// Характерный размер жилы в блоках (масштаб шума 1 / vein_size)
+    vein_size: f32,
+    /// Доля объёма под рудой при весе 1 (0-1)
+    frequency: f32,
+    /// Сдвиг шума, чтобы руды не совпадали
+    #[serde(default)]
+    noise_offset: f32,
+}
+
+#[derive(Debug, Deserialize)]
+struct LargeVeinDef {
+    ore: String,
+    filler: String,
+    min_y: i32,
+    max_y: i32,
+    /// Доля регионов с жилой
+    chance: f32,
+    /// Длина [мин, макс] в блоках
+    length: (f32, f32),
+    radius: f32,
+    /// Доля руды внутри жилы, остальное - наполнитель
+    ore_ratio: f32,
+}
+
+/// Руда с кривой по высоте
+#[derive(Debug, Clone)]
+pub struct OreRule {
+    pub block: BlockType,
+    height: Vec<(f32, f32)>,
+    scale: f32,
+    frequency: f32,
+    offset: f32,
+}
+
+impl OreRule {
+    /// Вес по высоте: линейно между точками кривой, вне кривой - 0
+    pub fn weight_at(&self, y: f32) -> f32 {
+        let (first, last) = match (self.height.first(), self.height.last()) {
+            (Some(first), Some(last)) => (*first, *last),
+            _ => return 0.0,
+        };
+        if y < first.0 || y > last.0 {
+            return 0.0;
+        }
+        for pair in self.height.windows(2) {
+            let ((y0, w0), (y1, w1)) = (pair[0], pair[1]);
+            if y <= y1 {
+                let t = if y1 > y0 { (y - y0) / (y1 - y0) } else { 1.0 };
+                return w0 + (w1 - w0) * t;
+            }
+        }
+        last.1
+    }
+
+    fn matches(&self, x: i32, y: i32, z: i32, offset_x: f32, offset_z: f32) -> bool {
+        let weight = self.weight_at(y as f32);
+        if weight <= 0.0 {
+            return false;
+        }
+        let noise = noise3d(
+            (x as f32 + offset_x) * self.scale + self.offset,
+            y as f32 * self.scale,
+            (z as f32 + offset_z) * self.scale + self.offset,
+        );
+        noise > 1.0 - self.frequency * weight
+    }
+}
+
+/// Большая жила
+#[derive(Debug, Clone)]
+pub struct LargeVein {
+    pub ore: BlockType,
+    pub filler: BlockType,
+    min_y: i32,
+    max_y: i32,
+    chance: f32,
+    length: (f32, f32),
+    radius: f32,
+    ore_ratio: f32,
+    /// Соль хэша: у каждой жилы свои регионы
+    salt: i32,
+}
+
+/// Отрезок большой жилы в мировых координатах
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VeinSegment {
+    pub start: [f32; 3],
+    pub end: [f32; 3],
+}
+
+impl LargeVein {
+    /// Жила региона (если в нём есть)
+    pub fn segment(&self, region_x: i32, region_z: i32) -> Option<VeinSegment> {
+        let random = |k: i32| hash3d(region_x, self.salt + k, region_z);
+        if random(0) >= self.chance {
+            return None;
+        }
+
+        let size = VEIN_REGION as f32;
+        let start = [
+            (region_x as f32 + random(1)) * size,
+            self.min_y as f32 + random(2) * (self.max_y - self.min_y).max(0) as f32,
+            (region_z as f32 + random(3)) * size,
+        ];
+        let length = (self.length.0 + random(4) * (self.length.1 - self.length.0)).min(MAX_VEIN_LENGTH);
+        let yaw = random(5) * std::f32::consts::TAU;
+        // Почти горизонтальная: уклон до ±0.3
+        let slope = (random(6) - 0.5) * 0.6;
+        let end = [
+            start[0] + yaw.cos() * length,
+            start[1] + slope * length,
+            start[2] + yaw.sin() * length,
+        ];
+        Some(VeinSegment { start, end })
+    }
+
+    /// Блок жилы в точке (руда или наполнитель)
+    fn block_at(&self, x: i32, y: i32, z: i32) -> Option<BlockType> {
+        let margin = self.radius + 1.0;
+        if (y as f32) < self.min_y as f32 - MAX_VEIN_LENGTH * 0.3 - margin
+            || (y as f32) > self.max_y as f32 + MAX_VEIN_LENGTH * 0.3 + margin
+        {
+            return None;
+        }
+
+        let region_x = x.div_euclid(VEIN_REGION);
+        let region_z = z.div_euclid(VEIN_REGION);
+        let point = [x as f32 + 0.5, y as f32 + 0.5, z as f32 + 0.5];
+        for dz in -1..=1 {
+            for dx in -1..=1 {
+                let Some(segment) = self.segment(region_x + dx, region_z + dz) else { continue };
+                let (distance, t) = segment.distance(point);
+                // Жила утончается к концам
+                let radius = self.radius * (1.0 - (2.0 * t - 1.0).powi(4) * 0.6);
+                if distance <= radius {
+                    let ore = hash3d(x, y + self.salt, z) < self.ore_ratio;
+                    return Some(if ore { self.ore } else { self.filler });
+                }
+            }
+        }
+        None
+    }
+}
+
+impl VeinSegment {
+    /// Расстояние до отрезка и параметр ближайшей точки (0-1)
+    fn distance(&self, point: [f32; 3]) -> (f32, f32) {
+        let d = [self.end[0] - self.start[0], self.end[1] - self.start[1], self.end[2] - self.start[2]];
+        let p = [point[0] - self.start[0], point[1] - self.start[1], point[2] - self.start[2]];
+        let len2 = d[0] * d[0] + d[1] * d[1] + d[2] * d[2];
+        let t = if len2 > 0.0 { ((p[0] * d[0] + p[1] * d[1] + p[2] * d[2]) / len2).clamp(0.0, 1.0) } else { 0.0 };
+        let q = [p[0] - d[0] * t, p[1] - d[1] * t, p[2] - d[2] * t];
+        ((q[0] * q[0] + q[1] * q[1] + q[2] * q[2]).sqrt(), t)
+    }
+}
+
+/// Распределение руд
+#[derive(Debug, Clone, Default)]
+pub struct OreConfig {
+    pub ores: Vec<OreRule>,
+    pub large_veins: Vec<LargeVein>,
+}
+
+impl OreConfig {
+    /// Разобрать JSON (блоки - строковые ID реестра)
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let file: OresFile = serde_json::from_str(json)
+            .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+
+        let ores = file.ores
+            .into_iter()
+            .map(|ore| {
+                if ore.vein_size <= 0.0 {
+                    return Err(format!("ore '{}': vein_size must be positive", ore.block));
+                }
+                if ore.height.windows(2).any(|pair| pair[1].0 < pair[0].0) {
+                    return Err(format!("ore '{}': height points must be sorted by y", ore.block));
+                }
+                Ok(OreRule {
+                    block: resolve_block(&ore.block)?,
+                    height: ore.height,
+                    scale: 1.0 / ore.vein_size,
+                    frequency: ore.frequency.clamp(0.0, 1.0),
+                    offset: ore.noise_offset,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let large_veins = file.large_veins
+            .into_iter()
+            .enumerate()
+            .map(|(i, vein)| {
+                Ok(LargeVein {
+                    ore: resolve_block(&vein.ore)?,
+                    filler: resolve_block(&vein.filler)?,
+                    min_y: vein.min_y,
+                    max_y: vein.max_y.max(vein.min_y),
+                    chance: vein.chance.clamp(0.0, 1.0),
+                    length: (vein.length.0.max(1.0), vein.length.1.max(vein.length.0)),
+                    radius: vein.radius.max(0.5),
+                    ore_ratio: vein.ore_ratio.clamp(0.0, 1.0),
+                    salt: 7919 * (i as i32 + 1),
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(Self { ores, large_veins })
+    }
+
+    /// Руда (или наполнитель большой жилы) в точке
+    pub fn ore_at(&self, x: i32, y: i32, z: i32) -> Option<BlockType> {
+        if let Some(block) = self.large_veins.iter().find_map(|vein| vein.block_at(x, y, z)) {
+            return Some(block);
+        }
+        let (offset_x, offset_z) = world_noise_offset();
+        self.ores
+            .iter()
+            .find(|ore| ore.matches(x, y, z, offset_x, offset_z))
+            .map(|ore| ore.block)
+    }
+}
+
+fn resolve_block(id: &str) -> Result<BlockType, String> {
+    let registry = global_registry().read().map_err(|_| "block registry lock poisoned".to_string())?;
+    registry.get_numeric_id(id).ok_or_else(|| format!("unknown block '{}'", id))
+}
+
+static ORE_CONFIG: OnceLock<OreConfig> = OnceLock::new();
+
+/// Распределение руд (встроенный assets/worldgen/ores.json)
+pub fn ore_config() -> &'static OreConfig {
+    ORE_CONFIG.get_or_init(|| {
+        OreConfig::from_json(include_str!("../../../../assets/worldgen/ores.json")).unwrap_or_else(|e| {
+            log::error!("Руды не загружены: {}", e);
+            OreConfig::default()
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_config() -> OreConfig {
+        OreConfig::from_json(include_str!("../../../../assets/worldgen/ores.json")).unwrap()
+    }
+
+    #[test]
+    fn height_curve_interpolates() {
+        let config = OreConfig::from_json(r#"{"ores": [{
+            "block": "iron_ore", "height": [[-40, 0.0], [0, 1.0], [20, 0.5]],
+            "vein_size": 10.0, "frequency": 0.2
+        }]}"#).unwrap();
+        let ore = &config.ores[0];
+        assert_eq!(ore.weight_at(-41.0), 0.0);
+        assert!((ore.weight_at(-20.0) - 0.5).abs() < 1e-6);
+        assert_eq!(ore.weight_at(0.0), 1.0);
+        assert!((ore.weight_at(10.0) - 0.75).abs() < 1e-6);
+        assert_eq!(ore.weight_at(21.0), 0.0);
+    }
+
+    #[test]
+    fn invalid_ores_are_rejected() {
+        let unknown = r#"{"ores": [{"block": "no_such_ore", "height": [[0, 1.0]], "vein_size": 5.0, "frequency": 0.1}]}"#;
+        assert!(OreConfig::from_json(unknown).unwrap_err().contains("no_such_ore"));
+        let unsorted = r#"{"ores": [{"block": "coal_ore", "height": [[10, 1.0], [0, 1.0]], "vein_size": 5.0, "frequency": 0.1}]}"#;
+        assert!(OreConfig::from_json(unsorted).is_err());
+    }
+
+    #[test]
+    fn large_veins_span_several_chunks() {
+        let config = default_config();
+        let vein = &config.large_veins[0];
+        let (region_x, region_z, segment) = (0..400)
+            .flat_map(|rx| (0..4).map(move |rz| (rx, rz)))
+            .find_map(|(rx, rz)| vein.segment(rx, rz).map(|segment| (rx, rz, segment)))
+            .expect("регион с жилой");
+
+        let horizontal = ((segment.end[0] - segment.start[0]).powi(2) + (segment.end[2] - segment.start[2]).powi(2)).sqrt();
+        assert!(horizontal > 16.0, "жила короче чанка: {}", horizontal);
+        // Детерминированно: тот же регион - та же жила
+        assert_eq!(vein.segment(region_x, region_z), Some(segment));
+
+        // Середина жилы - руда или наполнитель, независимо от чанка запроса
+        let mid = [0, 1, 2].map(|i| ((segment.start[i] + segment.end[i]) * 0.5).floor() as i32);
+        let block = vein.block_at(mid[0], mid[1], mid[2]);
+        assert!(block == Some(vein.ore) || block == Some(vein.filler));
+    }
+}
@@ -0,0 +1,100 @@
+// ============================================
+// Spawn - Безопасная точка появления в мире
+// ============================================
+// Спавн ищется один раз при создании мира по кольцам вокруг (0, 0):
+// первая колонка с сушей выше уровня моря, твёрдым грунтом в несколько
+// блоков (не крыша пещеры) и свободным местом над ней.
+
+use crate::gpu::blocks::AIR;
+
+use super::generator::terrain_generator;
+use super::config::world_gen_config;
+
+/// Шаг между проверяемыми колонками (блоки)
+const SEARCH_STEP: i32 = 8;
+/// Радиус поиска (блоки)
+const SEARCH_RADIUS: i32 = 256;
+/// Сколько блоков под ногами должно быть твёрдыми
+const GROUND_DEPTH: i32 = 3;
+/// Свободная высота над поверхностью (рост игрока)
+const HEADROOM: i32 = 2;
+
+/// Спавн текущего мира: [x, y, z] блока, в котором стоят ноги
+pub fn world_spawn() -> [i32; 3] {
+    let generator = terrain_generator();
+    find_safe_spawn(
+        world_gen_config().sea_level,
+        |x, z| generator.height(x as f32, z as f32) as i32,
+        // 3D шум даёт карнизы, block_at - пещеры
+        |x, y, z| generator.is_solid(x as f32, y as f32, z as f32) && generator.block_at(x, y, z) != AIR,
+    )
+}
+
+/// Поиск спавна по кольцам от центра. Если подходящей колонки нет
+/// (океан, пустой мир) - над поверхностью в (0, 0)
+pub fn find_safe_spawn(
+    sea_level: i32,
+    height: impl Fn(i32, i32) -> i32,
+    solid: impl Fn(i32, i32, i32) -> bool,
+) -> [i32; 3] {
+    let is_safe = |x: i32, z: i32| -> Option<i32> {
+        let top = height(x, z);
+        // Вода стоит ниже уровня моря
+        if top < sea_level {
+            return None;
+        }
+        let ground = (0..GROUND_DEPTH).all(|depth| solid(x, top - depth, z));
+        let open = (1..=HEADROOM).all(|up| !solid(x, top + up, z));
+        (ground && open).then_some(top + 1)
+    };
+
+    for ring in 0..=SEARCH_RADIUS / SEARCH_STEP {
+        for (x, z) in ring_columns(ring) {
+            let (x, z) = (x * SEARCH_STEP, z * SEARCH_STEP);
+            if let Some(y) = is_safe(x, z) {
+                return [x, y, z];
+            }
+        }
+    }
+    [0, height(0, 0) + 1, 0]
+}
+
+/// Колонки квадратного кольца радиуса ring (в шагах поиска)
+fn ring_columns(ring: i32) -> impl Iterator<Item = (i32, i32)> {
+    (-ring..=ring)
+        .flat_map(move |x| (-ring..=ring).map(move |z| (x, z)))
+        .filter(move |&(x, z)| x.abs().max(z.abs()) == ring)
+}
+
+/// Попадает ли колонка в защиту спавна (квадрат радиуса radius,
+/// 0 - защиты нет)
+pub fn in_spawn_protection(spawn: [i32; 3], radius: i32, x: i32, z: i32) -> bool {
+    radius > 0 && (x - spawn[0]).abs().max((z - spawn[2]).abs()) <= radius
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_ocean_and_cave_roofs() {
+        // Океан при x < 16, у x = 16 под поверхностью пещера
+        let height = |x: i32, _z: i32| if x < 16 { 50 } else { 70 };
+        let solid = |x: i32, y: i32, z: i32| y <= height(x, z) && !(x == 16 && y == 69);
+        let spawn = find_safe_spawn(62, height, solid);
+        assert_eq!(spawn, [24, 71, -24]);
+    }
+
+    #[test]
+    fn falls_back_to_origin() {
+        let spawn = find_safe_spawn(62, |_, _| 40, |_, y, _| y <= 40);
+        assert_eq!(spawn, [0, 41, 0]);
+    }
+
+    #[test]
+    fn protection_radius() {
+        assert!(in_spawn_protection([10, 64, 10], 4, 14, 6));
+        assert!(!in_spawn_protection([10, 64, 10], 4, 15, 10));
+        assert!(!in_spawn_protection([10, 64, 10], 0, 10, 10));
+    }
+}
@@ -0,0 +1,148 @@
+// ============================================
+// Superflat - Плоский мир из слоёв
+// ============================================
+// Слои задаются в JSON снизу вверх и хранятся рядом с миром
+// (saves/<мир>/superflat.json). Перед первым запуском нового плоского мира
+// файл можно положить туда вручную - иначе пишется стек по умолчанию:
+//
+// { "base_y": 0, "layers": [ { "block": "deepslate", "thickness": 1 },
+//                            { "block": "stone", "thickness": 2 }, ... ] }
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::gpu::blocks::{get_face_colors, resolve_block, BlockType, AIR, GRASS};
+use crate::gpu::biomes::biome_selector;
+use crate::gpu::terrain::voxel::{VoxelChunk, ChunkGenerationResult, MIN_HEIGHT};
+use crate::gpu::terrain::BlockPos;
+
+use super::generator::TerrainGenerator;
+
+/// Имя файла слоёв в директории мира
+pub const SUPERFLAT_FILE: &str = "superflat.json";
+
+/// Слой плоского мира
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlatLayer {
+    /// Строковый ID блока ("stone", "grass", ...)
+    pub block: String,
+    /// Толщина в блоках
+    pub thickness: u32,
+}
+
+/// Стек слоёв плоского мира
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SuperflatSettings {
+    /// Высота нижнего слоя
+    pub base_y: i32,
+    /// Слои снизу вверх
+    pub layers: Vec<FlatLayer>,
+}
+
+impl Default for SuperflatSettings {
+    fn default() -> Self {
+        let layer = |block: &str, thickness| FlatLayer { block: block.to_string(), thickness };
+        Self {
+            base_y: 0,
+            layers: vec![layer("deepslate", 1), layer("stone", 2), layer("dirt", 2), layer("grass", 1)],
+        }
+    }
+}
+
+impl SuperflatSettings {
+    /// Прочитать слои из JSON
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        serde_json::from_str(&json).map_err(|e| e.to_string())
+    }
+
+    /// Сохранить слои в JSON
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, json).map_err(|e| e.to_string())
+    }
+}
+
+/// Генератор плоского мира
+pub struct SuperflatGenerator {
+    base_y: i32,
+    /// Блок на каждой высоте от base_y
+    column: Vec<BlockType>,
+}
+
+impl SuperflatGenerator {
+    pub fn new(settings: &SuperflatSettings) -> Self {
+        let column = settings
+            .layers
+            .iter()
+            .flat_map(|layer| std::iter::repeat(resolve_block(&layer.block)).take(layer.thickness as usize))
+            .collect();
+        Self { base_y: settings.base_y, column }
+    }
+
+    /// Высота верхнего блока (MIN_HEIGHT - 1 без слоёв)
+    fn top_y(&self) -> i32 {
+        if self.column.is_empty() {
+            MIN_HEIGHT - 1
+        } else {
+            self.base_y + self.column.len() as i32 - 1
+        }
+    }
+
+    fn top_block(&self) -> BlockType {
+        self.column.last().copied().unwrap_or(AIR)
+    }
+}
+
+impl TerrainGenerator for SuperflatGenerator {
+    fn height(&self, _x: f32, _z: f32) -> f32 {
+        self.top_y() as f32
+    }
+
+    fn is_solid(&self, x: f32, y: f32, z: f32) -> bool {
+        self.block_at(x.floor() as i32, y.floor() as i32, z.floor() as i32) != AIR
+    }
+
+    fn block_at(&self, _x: i32, y: i32, _z: i32) -> BlockType {
+        usize::try_from(y - self.base_y)
+            .ok()
+            .and_then(|i| self.column.get(i).copied())
+            .unwrap_or(AIR)
+    }
+
+    /// Воды в мире нет
+    fn is_water(&self, _x: i32, _y: i32, _z: i32) -> bool {
+        false
+    }
+
+    fn generate_chunk(
+        &self,
+        chunk_x: i32,
+        chunk_z: i32,
+        world_changes: &HashMap<BlockPos, BlockType>,
+    ) -> ChunkGenerationResult {
+        let heights = self.base_y..self.top_y() + 1;
+        let chunk = VoxelChunk::from_fn(chunk_x, chunk_z, heights, world_changes, |x, y, z| self.block_at(x, y, z));
+        ChunkGenerationResult { chunk, leaf_subvoxels: Vec::new() }
+    }
+
+    /// Цвет верхнего слоя (трава - с оттенком биома, как у вокселей)
+    fn surface_color(&self, x: f32, z: f32, is_top: bool) -> [f32; 3] {
+        let block = self.top_block();
+        let (top, side) = get_face_colors(block);
+        if !is_top {
+            return side;
+        }
+        if block != GRASS {
+            return top;
+        }
+        let tint = biome_selector().grass_tint(x, z);
+        [top[0] * tint[0], top[1] * tint[1], top[2] * tint[2]]
+    }
+
+    fn has_features(&self) -> bool {
+        false
+    }
+}
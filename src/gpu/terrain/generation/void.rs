@@ -0,0 +1,78 @@
+// ============================================
+// Void - Пустой мир с платформой на спавне
+// ============================================
+// Только каменная платформа с травой сверху вокруг (0, 0).
+// Дальних LOD чанков и декораций нет.
+
+use std::collections::HashMap;
+
+use crate::gpu::blocks::{BlockType, AIR, GRASS, STONE};
+use crate::gpu::terrain::voxel::{VoxelChunk, ChunkGenerationResult, MIN_HEIGHT};
+use crate::gpu::terrain::BlockPos;
+
+use super::generator::TerrainGenerator;
+
+/// Высота верхнего слоя платформы
+const PLATFORM_Y: i32 = 64;
+/// Половина стороны платформы (блоки)
+const PLATFORM_RADIUS: i32 = 3;
+/// Толщина платформы
+const PLATFORM_THICKNESS: i32 = 3;
+
+/// Генератор пустого мира
+pub struct VoidGenerator;
+
+impl VoidGenerator {
+    fn on_platform(x: i32, z: i32) -> bool {
+        x.abs() <= PLATFORM_RADIUS && z.abs() <= PLATFORM_RADIUS
+    }
+}
+
+impl TerrainGenerator for VoidGenerator {
+    /// Вне платформы - ниже дна мира
+    fn height(&self, x: f32, z: f32) -> f32 {
+        if Self::on_platform(x.floor() as i32, z.floor() as i32) {
+            PLATFORM_Y as f32
+        } else {
+            (MIN_HEIGHT - 1) as f32
+        }
+    }
+
+    fn is_solid(&self, x: f32, y: f32, z: f32) -> bool {
+        self.block_at(x.floor() as i32, y.floor() as i32, z.floor() as i32) != AIR
+    }
+
+    fn block_at(&self, x: i32, y: i32, z: i32) -> BlockType {
+        if !Self::on_platform(x, z) || y > PLATFORM_Y || y <= PLATFORM_Y - PLATFORM_THICKNESS {
+            AIR
+        } else if y == PLATFORM_Y {
+            GRASS
+        } else {
+            STONE
+        }
+    }
+
+    /// Воды в мире нет
+    fn is_water(&self, _x: i32, _y: i32, _z: i32) -> bool {
+        false
+    }
+
+    fn generate_chunk(
+        &self,
+        chunk_x: i32,
+        chunk_z: i32,
+        world_changes: &HashMap<BlockPos, BlockType>,
+    ) -> ChunkGenerationResult {
+        let heights = PLATFORM_Y - PLATFORM_THICKNESS + 1..PLATFORM_Y + 1;
+        let chunk = VoxelChunk::from_fn(chunk_x, chunk_z, heights, world_changes, |x, y, z| self.block_at(x, y, z));
+        ChunkGenerationResult { chunk, leaf_subvoxels: Vec::new() }
+    }
+
+    fn has_features(&self) -> bool {
+        false
+    }
+
+    fn has_lod_terrain(&self) -> bool {
+        false
+    }
+}
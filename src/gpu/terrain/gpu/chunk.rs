@@ -1,43 +1,154 @@
 // ============================================
 // GPU Chunk - Буферы чанка на GPU
 // ============================================
+// Вершины и индексы - диапазоны в страницах ChunkBufferPool.
 
-use wgpu::util::DeviceExt;
 use crate::gpu::terrain::cache::ChunkKey;
-use crate::gpu::terrain::mesh::TerrainVertex;
+use crate::gpu::blocks::WATER;
+use crate::gpu::terrain::mesh::{MeshSection, TerrainVertex, sort_by_section};
+use super::pool::{BufferAllocation, ChunkBufferPool};
+
+const VERTEX_SIZE: u64 = std::mem::size_of::<TerrainVertex>() as u64;
+/// Индексы выравниваются с запасом (начало диапазона - на 256 байт)
+const INDEX_ALIGNMENT: u64 = 256;
+
+/// Пулы вершин и индексов всех чанков
+pub struct ChunkBuffers {
+    pub vertices: ChunkBufferPool,
+    pub indices: ChunkBufferPool,
+}
+
+impl ChunkBuffers {
+    pub fn new() -> Self {
+        Self {
+            vertices: ChunkBufferPool::new("Chunk Vertex Page", wgpu::BufferUsages::VERTEX, VERTEX_SIZE),
+            indices: ChunkBufferPool::new("Chunk Index Page", wgpu::BufferUsages::INDEX, INDEX_ALIGNMENT),
+        }
+    }
+
+    /// Вернуть диапазоны чанка в пулы
+    pub fn free(&mut self, chunk: &GpuChunk) {
+        self.vertices.free(&chunk.vertices);
+        self.indices.free(&chunk.indices);
+    }
+
+    pub fn capacity_bytes(&self) -> u64 {
+        self.vertices.capacity_bytes() + self.indices.capacity_bytes()
+    }
+}
 
 /// GPU буферы для одного чанка
 pub struct GpuChunk {
     pub key: ChunkKey,
-    pub vertex_buffer: wgpu::Buffer,
-    pub index_buffer: wgpu::Buffer,
+    vertices: BufferAllocation,
+    indices: BufferAllocation,
+    /// Число индексов непрозрачной части (она идёт первой в буфере)
     pub index_count: u32,
+    /// Диапазоны индексов по секциям высоты (вертикальный куллинг)
+    pub sections: Vec<MeshSection>,
+    /// Секции полупрозрачных граней (после непрозрачных в том же буфере)
+    pub translucent_sections: Vec<MeshSection>,
+    /// Секции поверхности воды (в самом конце буфера, свой pipeline)
+    pub water_sections: Vec<MeshSection>,
+    /// AABB вершин меша (прокси для occlusion queries)
+    pub min: [f32; 3],
+    pub max: [f32; 3],
 }
 
 impl GpuChunk {
+    /// None - в видеопамяти не нашлось места (в пулах ничего не занято)
     pub fn new(
+        buffers: &mut ChunkBuffers,
         device: &wgpu::Device,
+        queue: &wgpu::Queue,
         key: ChunkKey,
         vertices: &[TerrainVertex],
         indices: &[u32],
-    ) -> Self {
-        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some(&format!("Chunk {:?} Vertices", key)),
-            contents: bytemuck::cast_slice(vertices),
-            usage: wgpu::BufferUsages::VERTEX,
-        });
-
-        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some(&format!("Chunk {:?} Indices", key)),
-            contents: bytemuck::cast_slice(indices),
-            usage: wgpu::BufferUsages::INDEX,
-        });
+    ) -> Option<Self> {
+        // Полупрозрачные треугольники и вода - в конец буфера, отдельными проходами
+        let mut opaque = Vec::new();
+        let mut translucent = Vec::new();
+        let mut water = Vec::new();
+        for triangle in indices.chunks_exact(3) {
+            let vertex = &vertices[triangle[0] as usize];
+            let part = if !vertex.is_translucent() {
+                &mut opaque
+            } else if vertex.block_type() == WATER {
+                &mut water
+            } else {
+                &mut translucent
+            };
+            part.extend_from_slice(triangle);
+        }
+        let (mut indices, sections) = sort_by_section(vertices, &opaque);
+        let index_count = indices.len() as u32;
+        let translucent_sections = append_sections(&mut indices, vertices, &translucent);
+        let water_sections = append_sections(&mut indices, vertices, &water);
 
-        Self {
-            key,
-            vertex_buffer,
-            index_buffer,
-            index_count: indices.len() as u32,
+        let mut min = [f32::MAX; 3];
+        let mut max = [f32::MIN; 3];
+        for vertex in vertices {
+            for i in 0..3 {
+                min[i] = min[i].min(vertex.position[i]);
+                max[i] = max[i].max(vertex.position[i]);
+            }
         }
+
+        let vertex_allocation = buffers.vertices.upload(device, queue, bytemuck::cast_slice(vertices))?;
+        let Some(index_allocation) = buffers.indices.upload(device, queue, bytemuck::cast_slice(&indices)) else {
+            buffers.vertices.free(&vertex_allocation);
+            return None;
+        };
+
+        Some(Self {
+            key,
+            vertices: vertex_allocation,
+            indices: index_allocation,
+            index_count,
+            sections,
+            translucent_sections,
+            water_sections,
+            min,
+            max,
+        })
+    }
+
+    pub fn vertex_slice(&self) -> wgpu::BufferSlice<'_> {
+        self.vertices.slice()
+    }
+
+    pub fn index_slice(&self) -> wgpu::BufferSlice<'_> {
+        self.indices.slice()
+    }
+
+    /// Страницы вершин и индексов (отрисовка страницы целиком)
+    pub fn pages(&self) -> (usize, usize) {
+        (self.vertices.page(), self.indices.page())
+    }
+
+    /// Первая вершина чанка в своей странице
+    pub fn base_vertex(&self) -> i32 {
+        (self.vertices.offset() / VERTEX_SIZE) as i32
+    }
+
+    /// Первый индекс чанка в своей странице
+    pub fn first_index(&self) -> u32 {
+        (self.indices.offset() / std::mem::size_of::<u32>() as u64) as u32
+    }
+
+    /// Центр AABB (сортировка полупрозрачных чанков по дальности)
+    pub fn center(&self) -> [f32; 3] {
+        std::array::from_fn(|i| (self.min[i] + self.max[i]) * 0.5)
+    }
+}
+
+/// Дописать треугольники в конец буфера, вернуть их секции со сдвигом
+fn append_sections(indices: &mut Vec<u32>, vertices: &[TerrainVertex], part: &[u32]) -> Vec<MeshSection> {
+    let offset = indices.len() as u32;
+    let (sorted, mut sections) = sort_by_section(vertices, part);
+    for section in &mut sections {
+        section.start += offset;
     }
+    indices.extend(sorted);
+    sections
 }
@@ -7,19 +7,30 @@ use std::sync::Arc;
 
 use crate::gpu::terrain::cache::ChunkKey;
 use crate::gpu::terrain::mesh::TerrainVertex;
-use super::chunk::GpuChunk;
+use super::chunk::{GpuChunk, ChunkBuffers};
 
 /// Менеджер GPU буферов чанков
 pub struct GpuChunkManager {
     chunks: HashMap<ChunkKey, GpuChunk>,
+    /// Страницы, из которых выделяются меши всех чанков
+    buffers: ChunkBuffers,
     device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    /// Загрузка не удалась из-за нехватки видеопамяти
+    out_of_memory: bool,
+    /// Растёт при любом изменении набора чанков (GPU отсечение пересобирает записи)
+    generation: u64,
 }
 
 impl GpuChunkManager {
-    pub fn new(device: Arc<wgpu::Device>) -> Self {
+    pub fn new(device: Arc<wgpu::Device>, queue: Arc<wgpu::Queue>) -> Self {
         Self {
             chunks: HashMap::with_capacity(1024),
+            buffers: ChunkBuffers::new(),
             device,
+            queue,
+            out_of_memory: false,
+            generation: 0,
         }
     }
 
@@ -29,13 +40,72 @@ impl GpuChunkManager {
             return;
         }
         
-        let gpu_chunk = GpuChunk::new(&self.device, key, vertices, indices);
-        self.chunks.insert(key, gpu_chunk);
+        self.generation += 1;
+        // Прежний меш чанка освобождаем до выделения нового
+        if let Some(old) = self.chunks.remove(&key) {
+            self.buffers.free(&old);
+        }
+        // Без памяти новую страницу не выделить - такой чанк не рисуем
+        match GpuChunk::new(&mut self.buffers, &self.device, &self.queue, key, vertices, indices) {
+            Some(gpu_chunk) => {
+                self.chunks.insert(key, gpu_chunk);
+            }
+            None => {
+                log::warn!("Чанк {:?} не загружен: нет видеопамяти", key);
+                self.out_of_memory = true;
+            }
+        }
+    }
+
+    /// Была ли нехватка видеопамяти с прошлого вызова
+    pub fn take_out_of_memory(&mut self) -> bool {
+        std::mem::take(&mut self.out_of_memory)
+    }
+
+    /// Видеопамять под меши чанков (байты, вместе со свободным местом страниц)
+    pub fn memory_bytes(&self) -> u64 {
+        self.buffers.capacity_bytes()
     }
 
     /// Удаляет чанки которых нет в списке нужных
     pub fn retain_only(&mut self, valid_keys: &std::collections::HashSet<ChunkKey>) {
-        self.chunks.retain(|key, _| valid_keys.contains(key));
+        let buffers = &mut self.buffers;
+        let before = self.chunks.len();
+        self.chunks.retain(|key, chunk| {
+            let keep = valid_keys.contains(key);
+            if !keep {
+                buffers.free(chunk);
+            }
+            keep
+        });
+        if self.chunks.len() != before {
+            self.generation += 1;
+        }
+    }
+
+    /// Удаляет чанк (отслуживший меш после смены LOD)
+    pub fn remove(&mut self, key: &ChunkKey) {
+        if let Some(chunk) = self.chunks.remove(key) {
+            self.buffers.free(&chunk);
+            self.generation += 1;
+        }
+    }
+
+    pub fn get(&self, key: &ChunkKey) -> Option<&GpuChunk> {
+        self.chunks.get(key)
+    }
+
+    pub fn contains(&self, key: &ChunkKey) -> bool {
+        self.chunks.contains_key(key)
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Буферы страниц вершин и индексов (см. GpuChunk::pages)
+    pub fn page_buffers(&self, (vertex_page, index_page): (usize, usize)) -> Option<(&wgpu::Buffer, &wgpu::Buffer)> {
+        Some((self.buffers.vertices.page_buffer(vertex_page)?, self.buffers.indices.page_buffer(index_page)?))
     }
 
     /// Итератор по всем GPU чанкам для рендеринга
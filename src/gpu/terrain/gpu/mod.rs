@@ -1,5 +1,6 @@
 mod chunk;
 mod manager;
+mod pool;
 
 pub use chunk::GpuChunk;
 pub use manager::GpuChunkManager;
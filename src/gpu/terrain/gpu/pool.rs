@@ -0,0 +1,210 @@
+// ============================================
+// Chunk Buffer Pool - Подвыделение буферов чанков
+// ============================================
+// Меши чанков живут в больших страницах (по 16 МБ), а не в отдельном
+// буфере на чанк: загрузка - это поиск свободного диапазона (first-fit
+// по free-list, соседние свободные диапазоны сливаются) и один
+// write_buffer. Меш больше страницы получает свою страницу по размеру.
+// Полностью освободившиеся страницы (кроме первой) отдаются обратно,
+// чтобы снижение дальности при нехватке видеопамяти её возвращало.
+// Начало вершинного диапазона кратно размеру вершины: GPU отсечение
+// рисует страницу целиком с base_vertex = смещение / размер вершины.
+
+use std::ops::Range;
+
+/// Размер обычной страницы
+const PAGE_SIZE: u64 = 16 << 20;
+
+/// Свободные диапазоны страницы (по возрастанию, без соседствующих)
+#[derive(Debug)]
+struct FreeList {
+    size: u64,
+    ranges: Vec<Range<u64>>,
+}
+
+impl FreeList {
+    fn new(size: u64) -> Self {
+        Self { size, ranges: vec![0..size] }
+    }
+
+    /// Первый подходящий диапазон (size уже выровнен)
+    fn allocate(&mut self, size: u64) -> Option<u64> {
+        let index = self.ranges.iter().position(|range| range.end - range.start >= size)?;
+        let range = &mut self.ranges[index];
+        let offset = range.start;
+        range.start += size;
+        if range.is_empty() {
+            self.ranges.remove(index);
+        }
+        Some(offset)
+    }
+
+    fn free(&mut self, freed: Range<u64>) {
+        let index = self.ranges.partition_point(|range| range.start < freed.start);
+        let merges_prev = index > 0 && self.ranges[index - 1].end == freed.start;
+        let merges_next = index < self.ranges.len() && self.ranges[index].start == freed.end;
+        match (merges_prev, merges_next) {
+            (true, true) => {
+                self.ranges[index - 1].end = self.ranges[index].end;
+                self.ranges.remove(index);
+            }
+            (true, false) => self.ranges[index - 1].end = freed.end,
+            (false, true) => self.ranges[index].start = freed.start,
+            (false, false) => self.ranges.insert(index, freed),
+        }
+    }
+
+    fn is_unused(&self) -> bool {
+        self.ranges.len() == 1 && self.ranges[0] == (0..self.size)
+    }
+}
+
+struct Page {
+    buffer: wgpu::Buffer,
+    free: FreeList,
+}
+
+/// Диапазон, выделенный чанку в странице пула
+#[derive(Debug)]
+pub struct BufferAllocation {
+    page: usize,
+    buffer: wgpu::Buffer,
+    range: Range<u64>,
+}
+
+impl BufferAllocation {
+    pub fn slice(&self) -> wgpu::BufferSlice<'_> {
+        self.buffer.slice(self.range.clone())
+    }
+
+    /// Слот страницы в пуле
+    pub fn page(&self) -> usize {
+        self.page
+    }
+
+    /// Смещение в странице (байты)
+    pub fn offset(&self) -> u64 {
+        self.range.start
+    }
+}
+
+/// Пул страниц одного типа (вершины или индексы)
+pub struct ChunkBufferPool {
+    label: &'static str,
+    usage: wgpu::BufferUsages,
+    /// Выравнивание начала и размера диапазонов (кратно 4 - для write_buffer)
+    alignment: u64,
+    /// Слоты страниц: освобождённые становятся None, индексы не сдвигаются
+    pages: Vec<Option<Page>>,
+}
+
+impl ChunkBufferPool {
+    pub fn new(label: &'static str, usage: wgpu::BufferUsages, alignment: u64) -> Self {
+        debug_assert!(alignment % wgpu::COPY_BUFFER_ALIGNMENT == 0);
+        Self { label, usage: usage | wgpu::BufferUsages::COPY_DST, alignment, pages: Vec::new() }
+    }
+
+    /// Выделить диапазон и записать в него данные.
+    /// None - новую страницу не дала видеопамять
+    pub fn upload(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, data: &[u8]) -> Option<BufferAllocation> {
+        let size = (data.len() as u64).div_ceil(self.alignment) * self.alignment;
+        let found = self.pages.iter_mut().enumerate().find_map(|(index, page)| {
+            let page = page.as_mut()?;
+            page.free.allocate(size).map(|offset| (index, offset))
+        });
+        let (page, offset) = match found {
+            Some(found) => found,
+            None => (self.add_page(device, size)?, 0),
+        };
+
+        let buffer = self.pages[page].as_ref()?.buffer.clone();
+        queue.write_buffer(&buffer, offset, data);
+        Some(BufferAllocation { page, buffer, range: offset..offset + size })
+    }
+
+    /// Вернуть диапазон в пул
+    pub fn free(&mut self, allocation: &BufferAllocation) {
+        let Some(page) = self.pages.get_mut(allocation.page).and_then(Option::as_mut) else { return };
+        page.free.free(allocation.range.clone());
+        if allocation.page > 0 && page.free.is_unused() {
+            self.pages[allocation.page] = None;
+        }
+    }
+
+    /// Буфер страницы (None - слот освобождён)
+    pub fn page_buffer(&self, page: usize) -> Option<&wgpu::Buffer> {
+        self.pages.get(page)?.as_ref().map(|page| &page.buffer)
+    }
+
+    /// Видеопамять под страницы (байты)
+    pub fn capacity_bytes(&self) -> u64 {
+        self.pages.iter().flatten().map(|page| page.buffer.size()).sum()
+    }
+
+    fn add_page(&mut self, device: &wgpu::Device, min_size: u64) -> Option<usize> {
+        let size = min_size.max(PAGE_SIZE / self.alignment * self.alignment);
+        device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(self.label),
+            size,
+            usage: self.usage,
+            mapped_at_creation: false,
+        });
+        if let Some(error) = pollster::block_on(device.pop_error_scope()) {
+            log::warn!("Страница {} ({} МБ) не выделена: {}", self.label, size >> 20, error);
+            return None;
+        }
+
+        let mut free = FreeList::new(size);
+        free.allocate(min_size);
+        let page = Page { buffer, free };
+        match self.pages.iter().position(Option::is_none) {
+            Some(slot) => {
+                self.pages[slot] = Some(page);
+                Some(slot)
+            }
+            None => {
+                self.pages.push(Some(page));
+                Some(self.pages.len() - 1)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn free_list_reuses_and_merges_ranges() {
+        let mut free = FreeList::new(1024);
+        assert_eq!(free.allocate(256), Some(0));
+        assert_eq!(free.allocate(256), Some(256));
+        assert_eq!(free.allocate(512), Some(512));
+        assert_eq!(free.allocate(256), None);
+
+        // Дырка в середине переиспользуется
+        free.free(256..512);
+        assert_eq!(free.allocate(256), Some(256));
+
+        // Освобождение в любом порядке сливается в одну страницу
+        free.free(0..256);
+        free.free(512..1024);
+        assert!(!free.is_unused());
+        free.free(256..512);
+        assert!(free.is_unused());
+        assert_eq!(free.ranges, vec![0..1024]);
+    }
+
+    #[test]
+    fn free_list_first_fit_skips_small_holes() {
+        let mut free = FreeList::new(2048);
+        for _ in 0..4 {
+            free.allocate(512);
+        }
+        free.free(0..512);
+        free.free(1024..2048);
+        assert_eq!(free.allocate(1024), Some(1024));
+        assert_eq!(free.allocate(512), Some(0));
+    }
+}
@@ -1,3 +1,5 @@
 mod levels;
+mod stitching;
 
 pub use levels::LodLevel;
+pub use stitching::{skirt_bottom, EdgeCell};
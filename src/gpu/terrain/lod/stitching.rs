@@ -0,0 +1,84 @@
+// ============================================
+// LOD Stitching - Сшивка краёв чанков разных LOD
+// ============================================
+// Соседний чанк может быть на уровень мельче или крупнее: его край
+// сэмплирован с другим шагом, и между верхними гранями остаётся щель.
+// Юбка клетки края опускается до самой низкой высоты, которую сосед
+// (мельче или крупнее) показывает вдоль этого отрезка, плюс запас.
+// Так щель закрывается с любой стороны, а плоские стыки не тянут
+// лишних вертикальных стен. Воксельные чанки LOD0 закрывают свой край
+// сами: без соседа-вокселя граница рисуется стеной до дна.
+
+/// Запас юбки под самой низкой высотой соседа (целые блоки LOD0 + погрешность)
+const SKIRT_MARGIN: f32 = 2.0;
+
+/// Отрезок края чанка длиной в одну клетку LOD
+#[derive(Debug, Clone, Copy)]
+pub struct EdgeCell {
+    /// Мировые координаты начала отрезка
+    pub x: f32,
+    pub z: f32,
+    /// Единичное направление вдоль края
+    pub along: (f32, f32),
+    /// Единичное направление наружу из чанка
+    pub outward: (f32, f32),
+}
+
+/// Нижняя кромка юбки клетки края с высотой top.
+/// height(x, z, scale) - высота клетки LOD с началом в (x, z)
+pub fn skirt_bottom(top: f32, cell: EdgeCell, scale: i32, mut height: impl FnMut(f32, f32, i32) -> f32) -> f32 {
+    let finer = (scale / 2).max(1);
+    let steps = scale / finer;
+    let mut lowest = top;
+
+    for step in 0..steps {
+        // Середина подотрезка, на полблока снаружи чанка
+        let offset = (step * finer) as f32 + finer as f32 * 0.5;
+        let px = cell.x + cell.along.0 * offset + cell.outward.0 * 0.5;
+        let pz = cell.z + cell.along.1 * offset + cell.outward.1 * 0.5;
+        for neighbor_scale in [finer, scale * 2] {
+            let n = neighbor_scale as f32;
+            let h = height((px / n).floor() * n, (pz / n).floor() * n, neighbor_scale);
+            lowest = lowest.min(h);
+        }
+    }
+
+    lowest - SKIRT_MARGIN
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge(x: f32, z: f32) -> EdgeCell {
+        EdgeCell { x, z, along: (1.0, 0.0), outward: (0.0, -1.0) }
+    }
+
+    #[test]
+    fn flat_seam_gets_shallow_skirt() {
+        let bottom = skirt_bottom(64.0, edge(0.0, 0.0), 4, |_, _, _| 64.0);
+        assert_eq!(bottom, 64.0 - SKIRT_MARGIN);
+    }
+
+    #[test]
+    fn skirt_reaches_lowest_neighbor_sample() {
+        // Мелкий сосед (шаг 2) проваливается во второй половине отрезка
+        let bottom = skirt_bottom(70.0, edge(8.0, 16.0), 4, |x, z, scale| {
+            assert!(z < 16.0, "сэмплы берутся снаружи чанка");
+            if scale == 2 && x >= 10.0 { 40.0 } else { 70.0 }
+        });
+        assert_eq!(bottom, 40.0 - SKIRT_MARGIN);
+    }
+
+    #[test]
+    fn neighbor_cells_are_grid_aligned() {
+        let mut origins = Vec::new();
+        let cell = EdgeCell { x: 32.0, z: 8.0, along: (0.0, 1.0), outward: (1.0, 0.0) };
+        skirt_bottom(0.0, cell, 2, |x, z, scale| {
+            origins.push((x, z, scale));
+            0.0
+        });
+        // Клетки соседей справа от края x = 32: шаг 1 и шаг 4
+        assert_eq!(origins, vec![(32.0, 8.0, 1), (32.0, 8.0, 4), (32.0, 9.0, 1), (32.0, 8.0, 4)]);
+    }
+}
@@ -1,34 +1,55 @@
 use std::collections::{HashMap, HashSet};
-use rayon::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
 
-use crate::gpu::terrain::voxel::{VoxelChunk, ChunkNeighbors, CHUNK_SIZE, MeshingContext};
+use crate::gpu::terrain::voxel::{VoxelChunk, ChunkNeighbors, CHUNK_SIZE};
+use crate::gpu::terrain::voxel::thread_local::with_meshing_context;
 use crate::gpu::terrain::mesh::TerrainVertex;
 use crate::gpu::terrain::cache::ChunkKey;
 use crate::gpu::terrain::lod::LodLevel;
+use crate::gpu::terrain::generation::{TerrainGenerator, terrain_generator, world_border};
 use crate::gpu::terrain::BlockPos;
 use crate::gpu::blocks::BlockType;
+use crate::gpu::biomes::features::TreeImpostor;
 
 use super::types::{GeneratedChunkData, GeneratedMesh};
 use super::lod_mesh::generate_lod_chunk;
+use super::pregen::pregenerated_lod_chunk;
+use super::jobs::{MeshJobs, SharedPlayerChunk};
 
-/// Генератор terrain с кэшированием и zero-allocation контекстом
+/// Как часто проверять, не ушёл ли игрок, пока ждём задачи мешей
+const CANCEL_POLL: Duration = Duration::from_millis(5);
+
+/// Генератор terrain с кэшированием; меши строит пул задач с отменой
 pub(super) struct HybridGenerator {
-    voxel_cache: HashMap<(i32, i32), VoxelChunk>,
+    /// Генератор рельефа текущего мира
+    terrain: &'static dyn TerrainGenerator,
+    /// Воксели делятся с задачами мешей (чанк и его соседи)
+    voxel_cache: HashMap<(i32, i32), Arc<VoxelChunk>>,
     mesh_cache: HashMap<ChunkKey, (Vec<TerrainVertex>, Vec<u32>)>,
+    /// Импосторы деревьев для LOD чанков
+    impostor_cache: HashMap<ChunkKey, Vec<TreeImpostor>>,
     cache_version: u64,
     lod_levels: [LodLevel; 4],
-    /// Переиспользуемый контекст для генерации мешей (zero-allocation)
-    meshing_ctx: MeshingContext,
+    /// Полоса высот воксельных мешей (None - весь столбец)
+    vertical_band: Option<(i32, i32)>,
+    jobs: MeshJobs,
+    /// Текущий чанк игрока (обновляет менеджер, пока идёт генерация)
+    player_chunk: SharedPlayerChunk,
 }
 
 impl HybridGenerator {
-    pub fn new() -> Self {
+    pub fn new(player_chunk: SharedPlayerChunk) -> Self {
         Self {
+            terrain: terrain_generator(),
             voxel_cache: HashMap::new(),
             mesh_cache: HashMap::new(),
+            impostor_cache: HashMap::new(),
             cache_version: 0,
             lod_levels: LodLevel::DEFAULT_LEVELS,
-            meshing_ctx: MeshingContext::new(),
+            vertical_band: None,
+            jobs: MeshJobs::new(),
+            player_chunk,
         }
     }
     
@@ -38,6 +59,19 @@ impl HybridGenerator {
         self.lod_levels[2] = LodLevel { min_chunks: distances[1], max_chunks: distances[2], scale: 4 };
         self.lod_levels[3] = LodLevel { min_chunks: distances[2], max_chunks: distances[3], scale: 8 };
         self.mesh_cache.clear();
+        self.impostor_cache.clear();
+    }
+    
+    /// Сменить полосу высот: воксельные меши перестроятся, LOD остаются.
+    /// Чанки, у которых выгружены нужные новой полосе секции, генерируются
+    /// заново
+    pub fn set_vertical_band(&mut self, band: Option<(i32, i32)>) {
+        if self.vertical_band != band {
+            self.vertical_band = band;
+            self.mesh_cache.retain(|key, _| !key.is_voxel());
+            let (min_y, max_y) = loaded_heights(band);
+            self.voxel_cache.retain(|_, chunk| chunk.covers_heights(min_y, max_y));
+        }
     }
 
     pub fn generate(
@@ -54,11 +88,10 @@ impl HybridGenerator {
         
         let (required_keys, chunks_to_generate) = self.collect_chunks_to_generate(center_cx, center_cz);
         
-        // Воксельные чанки - последовательно (нужен кэш соседей)
-        self.generate_voxel_chunks(&chunks_to_generate, world_changes);
-        
-        // LOD чанки - параллельно
-        self.generate_lod_chunks_parallel(&chunks_to_generate);
+        // Воксели - последовательно (нужен кэш соседей), меши - задачами пула
+        self.spawn_voxel_meshes(&chunks_to_generate, world_changes);
+        self.spawn_lod_meshes(&chunks_to_generate);
+        self.collect_job_results((center_cx, center_cz));
         
         let new_chunks = self.collect_new_chunks(&chunks_to_generate);
         self.cleanup_caches(center_cx, center_cz, &required_keys);
@@ -84,87 +117,90 @@ impl HybridGenerator {
     }
     
     fn collect_chunks_to_generate(&self, center_cx: i32, center_cz: i32) -> (HashSet<ChunkKey>, Vec<(ChunkKey, bool)>) {
-        let mut required_keys = HashSet::new();
-        let mut chunks_to_generate = Vec::new();
-        
-        for lod in &self.lod_levels {
-            for dz in -lod.max_chunks..=lod.max_chunks {
-                for dx in -lod.max_chunks..=lod.max_chunks {
-                    let dist = dx.abs().max(dz.abs());
-                    if dist < lod.min_chunks || dist >= lod.max_chunks { continue; }
-                    
-                    let world_cx = center_cx + dx;
-                    let world_cz = center_cz + dz;
-                    
-                    let (final_cx, final_cz) = if lod.scale > 1 {
-                        (world_cx.div_euclid(lod.scale) * lod.scale, world_cz.div_euclid(lod.scale) * lod.scale)
-                    } else {
-                        (world_cx, world_cz)
-                    };
-                    
-                    let key = ChunkKey::new(final_cx, final_cz, lod.scale);
-                    if required_keys.contains(&key) { continue; }
-                    required_keys.insert(key);
-                    
-                    if !self.mesh_cache.contains_key(&key) {
-                        chunks_to_generate.push((key, lod.scale == 1));
+        let ordered = required_keys_ordered(&self.lod_levels, center_cx, center_cz);
+        let chunks_to_generate = ordered.iter()
+            .filter(|key| !self.mesh_cache.contains_key(key))
+            .map(|key| (*key, key.scale == 1))
+            .collect();
+        (ordered.into_iter().collect(), chunks_to_generate)
+    }
+    
+    fn spawn_voxel_meshes(&mut self, chunks: &[(ChunkKey, bool)], world_changes: &HashMap<BlockPos, BlockType>) {
+        let (min_y, max_y) = self.vertical_band.unwrap_or((i32::MIN, i32::MAX));
+        for &(key, is_voxel) in chunks {
+            if !is_voxel { continue; }
+            
+            // Чанк и соседи должны быть в кэше до постановки задачи
+            for (dx, dz) in [(0, 0), (-1, 0), (1, 0), (0, -1), (0, 1)] {
+                let pos = (key.x + dx, key.z + dz);
+                if !self.voxel_cache.contains_key(&pos) {
+                    let mut chunk = self.terrain.generate_chunk(pos.0, pos.1, world_changes).chunk;
+                    // Секции вне полосы не мешатся - держать их незачем
+                    if self.vertical_band.is_some() {
+                        let (low, high) = loaded_heights(self.vertical_band);
+                        chunk.retain_heights(low, high);
                     }
+                    self.voxel_cache.insert(pos, Arc::new(chunk));
                 }
             }
+            
+            let get = |dx: i32, dz: i32| self.voxel_cache.get(&(key.x + dx, key.z + dz)).cloned();
+            let (Some(chunk), pos_x, neg_x, pos_z, neg_z) = (get(0, 0), get(1, 0), get(-1, 0), get(0, 1), get(0, -1)) else {
+                continue;
+            };
+            self.jobs.spawn(key, move || {
+                let neighbors = ChunkNeighbors {
+                    pos_x: pos_x.as_deref(),
+                    neg_x: neg_x.as_deref(),
+                    pos_z: pos_z.as_deref(),
+                    neg_z: neg_z.as_deref(),
+                };
+                // Zero-allocation контекст своего потока пула
+                let (vertices, indices) = with_meshing_context(|ctx| {
+                    chunk.generate_mesh_in_range_with_context(&neighbors, min_y, max_y, ctx)
+                });
+                (vertices, indices, Vec::new())
+            });
         }
-        
-        (required_keys, chunks_to_generate)
     }
     
-    fn generate_voxel_chunks(&mut self, chunks: &[(ChunkKey, bool)], world_changes: &HashMap<BlockPos, BlockType>) {
-        let voxel_keys: Vec<_> = chunks.iter()
-            .filter(|(_, is_voxel)| *is_voxel)
-            .map(|(key, _)| *key)
-            .collect();
-        
-        for key in voxel_keys {
-            let (vertices, indices) = self.generate_voxel_chunk(key.x, key.z, world_changes);
-            self.mesh_cache.insert(key, (vertices, indices));
+    fn spawn_lod_meshes(&mut self, chunks: &[(ChunkKey, bool)]) {
+        // Пустой мир: вдали рисовать нечего
+        if !self.terrain.has_lod_terrain() {
+            return;
         }
-    }
-    
-    fn generate_lod_chunks_parallel(&mut self, chunks: &[(ChunkKey, bool)]) {
-        let lod_keys: Vec<_> = chunks.iter()
-            .filter(|(_, is_voxel)| !*is_voxel)
-            .map(|(key, _)| *key)
-            .collect();
-        
-        let results: Vec<_> = lod_keys.par_iter()
-            .map(|key| (*key, generate_lod_chunk(key.x, key.z, key.scale)))
-            .collect();
         
-        for (key, (vertices, indices)) in results {
-            self.mesh_cache.insert(key, (vertices, indices));
+        for &(key, is_voxel) in chunks {
+            if is_voxel { continue; }
+            self.jobs.spawn(key, move || {
+                pregenerated_lod_chunk(&key)
+                    .unwrap_or_else(|| generate_lod_chunk(key.x, key.z, key.scale))
+            });
         }
     }
     
-    fn generate_voxel_chunk(&mut self, cx: i32, cz: i32, world_changes: &HashMap<BlockPos, BlockType>) -> (Vec<TerrainVertex>, Vec<u32>) {
-        // Ensure chunk and neighbors exist
-        if !self.voxel_cache.contains_key(&(cx, cz)) {
-            self.voxel_cache.insert((cx, cz), VoxelChunk::new(cx, cz, world_changes));
-        }
-        for (dx, dz) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
-            if !self.voxel_cache.contains_key(&(cx + dx, cz + dz)) {
-                self.voxel_cache.insert((cx + dx, cz + dz), VoxelChunk::new(cx + dx, cz + dz, world_changes));
+    /// Дождаться задач; если игрок сменил чанк, задачи выпавших из
+    /// дальности чанков отменяются (их меши в кэш не попадут)
+    fn collect_job_results(&mut self, mut center: (i32, i32)) {
+        while self.jobs.in_flight() > 0 {
+            let player_chunk = self.player_chunk.load();
+            if player_chunk != center {
+                center = player_chunk;
+                let required: HashSet<_> = required_keys_ordered(&self.lod_levels, center.0, center.1).into_iter().collect();
+                let cancelled = self.jobs.cancel_unless(|key| required.contains(key));
+                if cancelled > 0 {
+                    log::debug!("Отменено задач мешей: {} (игрок в чанке {:?})", cancelled, center);
+                }
+            }
+            
+            let Some(result) = self.jobs.recv_timeout(CANCEL_POLL) else { continue };
+            if let Some((vertices, indices, impostors)) = result.mesh {
+                self.mesh_cache.insert(result.key, (vertices, indices));
+                if result.key.scale > 1 {
+                    self.impostor_cache.insert(result.key, impostors);
+                }
             }
         }
-        
-        let neighbors = ChunkNeighbors {
-            pos_x: self.voxel_cache.get(&(cx + 1, cz)),
-            neg_x: self.voxel_cache.get(&(cx - 1, cz)),
-            pos_z: self.voxel_cache.get(&(cx, cz + 1)),
-            neg_z: self.voxel_cache.get(&(cx, cz - 1)),
-        };
-        
-        // Используем zero-allocation контекст
-        self.voxel_cache.get(&(cx, cz))
-            .map(|c| c.generate_mesh_with_context(&neighbors, &mut self.meshing_ctx))
-            .unwrap_or_default()
     }
     
     fn collect_new_chunks(&self, chunks: &[(ChunkKey, bool)]) -> Vec<GeneratedChunkData> {
@@ -176,6 +212,7 @@ impl HybridGenerator {
                             key: *key,
                             vertices: vertices.clone(),
                             indices: indices.clone(),
+                            impostors: self.impostor_cache.get(key).cloned().unwrap_or_default(),
                         })
                     } else {
                         None
@@ -191,5 +228,49 @@ impl HybridGenerator {
             (cx - center_cx).abs().max((cz - center_cz).abs()) < max_dist
         });
         self.mesh_cache.retain(|key, _| required_keys.contains(key));
+        self.impostor_cache.retain(|key, _| required_keys.contains(key));
     }
 }
+
+/// Высоты, которые нужны мешингу полосы: сама полоса и по блоку над и
+/// под ней (видимость граней на её краях)
+fn loaded_heights(band: Option<(i32, i32)>) -> (i32, i32) {
+    band.map_or((i32::MIN, i32::MAX), |(min_y, max_y)| (min_y - 1, max_y + 1))
+}
+
+/// Ключи всех чанков в дальности LOD, от ближних уровней к дальним
+/// (порядок задаёт очередь задач мешей). Чанки за границей мира не нужны
+fn required_keys_ordered(lod_levels: &[LodLevel; 4], center_cx: i32, center_cz: i32) -> Vec<ChunkKey> {
+    let border = world_border();
+    let mut seen = HashSet::new();
+    let mut keys = Vec::new();
+    
+    for lod in lod_levels {
+        for dz in -lod.max_chunks..=lod.max_chunks {
+            for dx in -lod.max_chunks..=lod.max_chunks {
+                let dist = dx.abs().max(dz.abs());
+                if dist < lod.min_chunks || dist >= lod.max_chunks { continue; }
+                
+                let world_cx = center_cx + dx;
+                let world_cz = center_cz + dz;
+                
+                let (final_cx, final_cz) = if lod.scale > 1 {
+                    (world_cx.div_euclid(lod.scale) * lod.scale, world_cz.div_euclid(lod.scale) * lod.scale)
+                } else {
+                    (world_cx, world_cz)
+                };
+                
+                if border.is_some_and(|border| !border.contains_chunks(final_cx, final_cz, lod.scale)) {
+                    continue;
+                }
+                
+                let key = ChunkKey::new(final_cx, final_cz, lod.scale);
+                if seen.insert(key) {
+                    keys.push(key);
+                }
+            }
+        }
+    }
+    
+    keys
+}
@@ -0,0 +1,166 @@
+// ============================================
+// Mesh Jobs - Пул генерации мешей с отменой
+// ============================================
+// Меши чанков (воксельные и LOD) строятся задачами в собственном пуле
+// rayon, результаты приходят по каналу. У каждой задачи свой токен
+// отмены: если игрок ушёл и чанк выпал из дальности, пока задача ждёт
+// в очереди, она завершается сразу, не тратя CPU. Готовый меш
+// отменённой задачи тоже отбрасывается.
+
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::gpu::biomes::features::TreeImpostor;
+use crate::gpu::terrain::cache::ChunkKey;
+use crate::gpu::terrain::mesh::TerrainVertex;
+
+/// Меш чанка: вершины, индексы и импосторы деревьев (только LOD)
+pub(super) type ChunkMesh = (Vec<TerrainVertex>, Vec<u32>, Vec<TreeImpostor>);
+
+/// Токен отмены задачи одного чанка
+#[derive(Clone, Default)]
+pub(super) struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Результат задачи (mesh: None - отменена или упала)
+pub(super) struct MeshJobResult {
+    pub key: ChunkKey,
+    pub mesh: Option<ChunkMesh>,
+}
+
+/// Пул задач генерации мешей
+pub(super) struct MeshJobs {
+    pool: rayon::ThreadPool,
+    tokens: HashMap<ChunkKey, CancelToken>,
+    result_tx: Sender<MeshJobResult>,
+    result_rx: Receiver<MeshJobResult>,
+}
+
+impl MeshJobs {
+    /// Потоков на ядро меньше: одно остаётся рендеру
+    pub fn new() -> Self {
+        let threads = std::thread::available_parallelism().map_or(2, |n| n.get()).saturating_sub(1).max(1);
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(|i| format!("mesh-{}", i))
+            .build()
+            .expect("пул генерации мешей");
+        let (result_tx, result_rx) = channel();
+        Self { pool, tokens: HashMap::new(), result_tx, result_rx }
+    }
+
+    /// Поставить задачу чанка в очередь пула
+    pub fn spawn<F>(&mut self, key: ChunkKey, job: F)
+    where
+        F: FnOnce() -> ChunkMesh + Send + 'static,
+    {
+        let token = CancelToken::default();
+        if let Some(previous) = self.tokens.insert(key, token.clone()) {
+            previous.cancel();
+        }
+        let result_tx = self.result_tx.clone();
+        self.pool.spawn(move || {
+            let mesh = if token.is_cancelled() {
+                None
+            } else {
+                // Паника в задаче не должна оставить генератор ждать вечно
+                panic::catch_unwind(AssertUnwindSafe(job)).ok()
+            };
+            let mesh = mesh.filter(|_| !token.is_cancelled());
+            let _ = result_tx.send(MeshJobResult { key, mesh });
+        });
+    }
+
+    /// Отменить задачи чанков, для которых keep вернул false
+    pub fn cancel_unless(&self, keep: impl Fn(&ChunkKey) -> bool) -> usize {
+        let mut cancelled = 0;
+        for (key, token) in &self.tokens {
+            if !keep(key) && !token.is_cancelled() {
+                token.cancel();
+                cancelled += 1;
+            }
+        }
+        cancelled
+    }
+
+    /// Задач в очереди и в работе
+    pub fn in_flight(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// Дождаться результата очередной задачи (None - за timeout не пришёл)
+    pub fn recv_timeout(&mut self, timeout: Duration) -> Option<MeshJobResult> {
+        let result = self.result_rx.recv_timeout(timeout).ok()?;
+        self.tokens.remove(&result.key);
+        Some(result)
+    }
+}
+
+/// Чанк игрока, общий для менеджера и фонового генератора
+/// (генератор по нему отменяет задачи выпавших из дальности чанков)
+#[derive(Clone)]
+pub(super) struct SharedPlayerChunk(Arc<AtomicU64>);
+
+impl SharedPlayerChunk {
+    pub fn new(chunk_x: i32, chunk_z: i32) -> Self {
+        Self(Arc::new(AtomicU64::new(pack(chunk_x, chunk_z))))
+    }
+
+    pub fn store(&self, chunk_x: i32, chunk_z: i32) {
+        self.0.store(pack(chunk_x, chunk_z), Ordering::Relaxed);
+    }
+
+    pub fn load(&self) -> (i32, i32) {
+        let packed = self.0.load(Ordering::Relaxed);
+        ((packed >> 32) as u32 as i32, packed as u32 as i32)
+    }
+}
+
+fn pack(chunk_x: i32, chunk_z: i32) -> u64 {
+    ((chunk_x as u32 as u64) << 32) | chunk_z as u32 as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn player_chunk_round_trips_negative_coords() {
+        let chunk = SharedPlayerChunk::new(0, 0);
+        chunk.store(-5, 1_000_000);
+        assert_eq!(chunk.load(), (-5, 1_000_000));
+        chunk.store(i32::MIN, -1);
+        assert_eq!(chunk.load(), (i32::MIN, -1));
+    }
+
+    #[test]
+    fn cancelled_job_result_is_dropped() {
+        let mut jobs = MeshJobs::new();
+        let (start_tx, start_rx) = channel::<()>();
+        let key = ChunkKey::new(3, 4, 1);
+        jobs.spawn(key, move || {
+            let _ = start_rx.recv();
+            (Vec::new(), vec![1, 2, 3], Vec::new())
+        });
+        assert_eq!(jobs.cancel_unless(|k| *k != key), 1);
+        start_tx.send(()).unwrap();
+
+        let result = jobs.recv_timeout(Duration::from_secs(5)).expect("задача завершилась");
+        assert_eq!(result.key, key);
+        assert!(result.mesh.is_none());
+        assert_eq!(jobs.in_flight(), 0);
+    }
+}
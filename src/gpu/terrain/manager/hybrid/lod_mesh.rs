@@ -1,9 +1,14 @@
 use crate::gpu::terrain::voxel::CHUNK_SIZE;
 use crate::gpu::terrain::mesh::TerrainVertex;
-use crate::gpu::terrain::generation::{get_lod_height, get_color};
+use crate::gpu::terrain::generation::{get_lod_height, get_color, terrain_generator};
+use crate::gpu::terrain::lod::{skirt_bottom, EdgeCell};
+use crate::gpu::biomes::features::{sample_lod_trees, TreeImpostor};
+
+use super::simplify::{flatten_small_features, lod_quality, merge_coplanar_quads, should_simplify};
 
 /// Генерация LOD чанка (thread-safe, для параллельной обработки)
-pub fn generate_lod_chunk(cx: i32, cz: i32, scale: i32) -> (Vec<TerrainVertex>, Vec<u32>) {
+/// Возвращает меш и деревья-импосторы для этого чанка
+pub fn generate_lod_chunk(cx: i32, cz: i32, scale: i32) -> (Vec<TerrainVertex>, Vec<u32>, Vec<TreeImpostor>) {
     let mut vertices = Vec::with_capacity(5000);
     let mut indices = Vec::with_capacity(7500);
     
@@ -24,11 +29,37 @@ pub fn generate_lod_chunk(cx: i32, cz: i32, scale: i32) -> (Vec<TerrainVertex>,
         }
     }
     
+    // Дальние LOD: срезаем детали меньше пикселя
+    let quality = lod_quality();
+    let simplify = should_simplify(scale, quality);
+    if simplify {
+        flatten_small_features(&mut heights, size, scale, quality, |x, z| {
+            get_lod_height(
+                (base_x + (x - 1) * scale) as f32,
+                (base_z + (z - 1) * scale) as f32,
+                scale
+            )
+        });
+    }
+    
     generate_top_faces(&mut vertices, &mut indices, &heights, base_x, base_z, scale);
     generate_side_faces(&mut vertices, &mut indices, &heights, base_x, base_z, scale, s);
     generate_skirts(&mut vertices, &mut indices, &heights, base_x, base_z, scale, s);
     
-    (vertices, indices)
+    if simplify {
+        (vertices, indices) = merge_coplanar_quads(vertices, indices, quality);
+    }
+    
+    // Деревья стоят на верхних гранях - берём высоты из той же heightmap
+    let impostors = if terrain_generator().has_features() {
+        sample_lod_trees(base_x, base_z, scale, |lx, lz| {
+            heights[((lz + 1) * size + (lx + 1)) as usize]
+        })
+    } else {
+        Vec::new()
+    };
+    
+    (vertices, indices, impostors)
 }
 
 
@@ -89,10 +120,10 @@ fn generate_top_faces(
             let d = (depth * scale) as f32;
             
             let base_v = vertices.len() as u32;
-            vertices.push(TerrainVertex { position: [wx, h, wz], normal: [0.0, 1.0, 0.0], color, block_id: 0 });
-            vertices.push(TerrainVertex { position: [wx, h, wz + d], normal: [0.0, 1.0, 0.0], color, block_id: 0 });
-            vertices.push(TerrainVertex { position: [wx + w, h, wz + d], normal: [0.0, 1.0, 0.0], color, block_id: 0 });
-            vertices.push(TerrainVertex { position: [wx + w, h, wz], normal: [0.0, 1.0, 0.0], color, block_id: 0 });
+            vertices.push(TerrainVertex { position: [wx, h, wz], normal: [0.0, 1.0, 0.0], color, block_id: 0, uv: [0.0; 2] });
+            vertices.push(TerrainVertex { position: [wx, h, wz + d], normal: [0.0, 1.0, 0.0], color, block_id: 0, uv: [0.0; 2] });
+            vertices.push(TerrainVertex { position: [wx + w, h, wz + d], normal: [0.0, 1.0, 0.0], color, block_id: 0, uv: [0.0; 2] });
+            vertices.push(TerrainVertex { position: [wx + w, h, wz], normal: [0.0, 1.0, 0.0], color, block_id: 0, uv: [0.0; 2] });
             indices.extend_from_slice(&[base_v, base_v + 1, base_v + 2, base_v, base_v + 2, base_v + 3]);
         }
     }
@@ -131,7 +162,7 @@ fn generate_side_faces(
     }
 }
 
-/// Юбки по краям чанка
+/// Юбки по краям чанка - глубиной до соседа другого LOD
 fn generate_skirts(
     vertices: &mut Vec<TerrainVertex>,
     indices: &mut Vec<u32>,
@@ -142,35 +173,42 @@ fn generate_skirts(
     s: f32,
 ) {
     let size = CHUNK_SIZE + 2;
-    let skirt_depth = 8.0;
+    let far = (CHUNK_SIZE * scale) as f32;
+    let bottom = |h: f32, x: f32, z: f32, along: (f32, f32), outward: (f32, f32)| {
+        skirt_bottom(h, EdgeCell { x, z, along, outward }, scale, get_lod_height)
+    };
     
     // -Z edge
     for x in 0..CHUNK_SIZE {
         let h = heights[(1 * size + (x + 1)) as usize];
         let wx = (base_x + x * scale) as f32;
         let wz = base_z as f32;
-        add_skirt_z(vertices, indices, wx, wz, h, h - skirt_depth, s, -1.0, wx, wz);
+        let h_bottom = bottom(h, wx, wz, (1.0, 0.0), (0.0, -1.0));
+        add_skirt_z(vertices, indices, wx, wz, h, h_bottom, s, -1.0, wx, wz);
     }
     // +Z edge
     for x in 0..CHUNK_SIZE {
         let h = heights[(CHUNK_SIZE * size + (x + 1)) as usize];
         let wx = (base_x + x * scale) as f32;
-        let wz = (base_z + CHUNK_SIZE * scale) as f32;
-        add_skirt_z(vertices, indices, wx, wz, h, h - skirt_depth, s, 1.0, wx, wz);
+        let wz = base_z as f32 + far;
+        let h_bottom = bottom(h, wx, wz, (1.0, 0.0), (0.0, 1.0));
+        add_skirt_z(vertices, indices, wx, wz, h, h_bottom, s, 1.0, wx, wz);
     }
     // -X edge
     for z in 0..CHUNK_SIZE {
         let h = heights[((z + 1) * size + 1) as usize];
         let wx = base_x as f32;
         let wz = (base_z + z * scale) as f32;
-        add_skirt_x(vertices, indices, wx, wz, h, h - skirt_depth, s, -1.0, wx, wz);
+        let h_bottom = bottom(h, wx, wz, (0.0, 1.0), (-1.0, 0.0));
+        add_skirt_x(vertices, indices, wx, wz, h, h_bottom, s, -1.0, wx, wz);
     }
     // +X edge
     for z in 0..CHUNK_SIZE {
         let h = heights[((z + 1) * size + CHUNK_SIZE) as usize];
-        let wx = (base_x + CHUNK_SIZE * scale) as f32;
+        let wx = base_x as f32 + far;
         let wz = (base_z + z * scale) as f32;
-        add_skirt_x(vertices, indices, wx, wz, h, h - skirt_depth, s, 1.0, wx, wz);
+        let h_bottom = bottom(h, wx, wz, (0.0, 1.0), (1.0, 0.0));
+        add_skirt_x(vertices, indices, wx, wz, h, h_bottom, s, 1.0, wx, wz);
     }
 }
 
@@ -179,15 +217,15 @@ fn add_side_x(vertices: &mut Vec<TerrainVertex>, indices: &mut Vec<u32>, x: f32,
     let normal = [nx, 0.0, 0.0];
     let base = vertices.len() as u32;
     if nx < 0.0 {
-        vertices.push(TerrainVertex { position: [x, h_low, z], normal, color, block_id: 0 });
-        vertices.push(TerrainVertex { position: [x, h_low, z + s], normal, color, block_id: 0 });
-        vertices.push(TerrainVertex { position: [x, h_high, z + s], normal, color, block_id: 0 });
-        vertices.push(TerrainVertex { position: [x, h_high, z], normal, color, block_id: 0 });
+        vertices.push(TerrainVertex { position: [x, h_low, z], normal, color, block_id: 0, uv: [0.0; 2] });
+        vertices.push(TerrainVertex { position: [x, h_low, z + s], normal, color, block_id: 0, uv: [0.0; 2] });
+        vertices.push(TerrainVertex { position: [x, h_high, z + s], normal, color, block_id: 0, uv: [0.0; 2] });
+        vertices.push(TerrainVertex { position: [x, h_high, z], normal, color, block_id: 0, uv: [0.0; 2] });
     } else {
-        vertices.push(TerrainVertex { position: [x, h_low, z + s], normal, color, block_id: 0 });
-        vertices.push(TerrainVertex { position: [x, h_low, z], normal, color, block_id: 0 });
-        vertices.push(TerrainVertex { position: [x, h_high, z], normal, color, block_id: 0 });
-        vertices.push(TerrainVertex { position: [x, h_high, z + s], normal, color, block_id: 0 });
+        vertices.push(TerrainVertex { position: [x, h_low, z + s], normal, color, block_id: 0, uv: [0.0; 2] });
+        vertices.push(TerrainVertex { position: [x, h_low, z], normal, color, block_id: 0, uv: [0.0; 2] });
+        vertices.push(TerrainVertex { position: [x, h_high, z], normal, color, block_id: 0, uv: [0.0; 2] });
+        vertices.push(TerrainVertex { position: [x, h_high, z + s], normal, color, block_id: 0, uv: [0.0; 2] });
     }
     indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
 }
@@ -197,15 +235,15 @@ fn add_side_z(vertices: &mut Vec<TerrainVertex>, indices: &mut Vec<u32>, x: f32,
     let normal = [0.0, 0.0, nz];
     let base = vertices.len() as u32;
     if nz > 0.0 {
-        vertices.push(TerrainVertex { position: [x, h_low, z], normal, color, block_id: 0 });
-        vertices.push(TerrainVertex { position: [x + s, h_low, z], normal, color, block_id: 0 });
-        vertices.push(TerrainVertex { position: [x + s, h_high, z], normal, color, block_id: 0 });
-        vertices.push(TerrainVertex { position: [x, h_high, z], normal, color, block_id: 0 });
+        vertices.push(TerrainVertex { position: [x, h_low, z], normal, color, block_id: 0, uv: [0.0; 2] });
+        vertices.push(TerrainVertex { position: [x + s, h_low, z], normal, color, block_id: 0, uv: [0.0; 2] });
+        vertices.push(TerrainVertex { position: [x + s, h_high, z], normal, color, block_id: 0, uv: [0.0; 2] });
+        vertices.push(TerrainVertex { position: [x, h_high, z], normal, color, block_id: 0, uv: [0.0; 2] });
     } else {
-        vertices.push(TerrainVertex { position: [x + s, h_low, z], normal, color, block_id: 0 });
-        vertices.push(TerrainVertex { position: [x, h_low, z], normal, color, block_id: 0 });
-        vertices.push(TerrainVertex { position: [x, h_high, z], normal, color, block_id: 0 });
-        vertices.push(TerrainVertex { position: [x + s, h_high, z], normal, color, block_id: 0 });
+        vertices.push(TerrainVertex { position: [x + s, h_low, z], normal, color, block_id: 0, uv: [0.0; 2] });
+        vertices.push(TerrainVertex { position: [x, h_low, z], normal, color, block_id: 0, uv: [0.0; 2] });
+        vertices.push(TerrainVertex { position: [x, h_high, z], normal, color, block_id: 0, uv: [0.0; 2] });
+        vertices.push(TerrainVertex { position: [x + s, h_high, z], normal, color, block_id: 0, uv: [0.0; 2] });
     }
     indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
 }
@@ -215,15 +253,15 @@ fn add_skirt_x(vertices: &mut Vec<TerrainVertex>, indices: &mut Vec<u32>, x: f32
     let normal = [nx, 0.0, 0.0];
     let base = vertices.len() as u32;
     if nx < 0.0 {
-        vertices.push(TerrainVertex { position: [x, h_bottom, z], normal, color, block_id: 0 });
-        vertices.push(TerrainVertex { position: [x, h_bottom, z + s], normal, color, block_id: 0 });
-        vertices.push(TerrainVertex { position: [x, h_top, z + s], normal, color, block_id: 0 });
-        vertices.push(TerrainVertex { position: [x, h_top, z], normal, color, block_id: 0 });
+        vertices.push(TerrainVertex { position: [x, h_bottom, z], normal, color, block_id: 0, uv: [0.0; 2] });
+        vertices.push(TerrainVertex { position: [x, h_bottom, z + s], normal, color, block_id: 0, uv: [0.0; 2] });
+        vertices.push(TerrainVertex { position: [x, h_top, z + s], normal, color, block_id: 0, uv: [0.0; 2] });
+        vertices.push(TerrainVertex { position: [x, h_top, z], normal, color, block_id: 0, uv: [0.0; 2] });
     } else {
-        vertices.push(TerrainVertex { position: [x, h_bottom, z + s], normal, color, block_id: 0 });
-        vertices.push(TerrainVertex { position: [x, h_bottom, z], normal, color, block_id: 0 });
-        vertices.push(TerrainVertex { position: [x, h_top, z], normal, color, block_id: 0 });
-        vertices.push(TerrainVertex { position: [x, h_top, z + s], normal, color, block_id: 0 });
+        vertices.push(TerrainVertex { position: [x, h_bottom, z + s], normal, color, block_id: 0, uv: [0.0; 2] });
+        vertices.push(TerrainVertex { position: [x, h_bottom, z], normal, color, block_id: 0, uv: [0.0; 2] });
+        vertices.push(TerrainVertex { position: [x, h_top, z], normal, color, block_id: 0, uv: [0.0; 2] });
+        vertices.push(TerrainVertex { position: [x, h_top, z + s], normal, color, block_id: 0, uv: [0.0; 2] });
     }
     indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
 }
@@ -233,15 +271,15 @@ fn add_skirt_z(vertices: &mut Vec<TerrainVertex>, indices: &mut Vec<u32>, x: f32
     let normal = [0.0, 0.0, nz];
     let base = vertices.len() as u32;
     if nz > 0.0 {
-        vertices.push(TerrainVertex { position: [x, h_bottom, z], normal, color, block_id: 0 });
-        vertices.push(TerrainVertex { position: [x + s, h_bottom, z], normal, color, block_id: 0 });
-        vertices.push(TerrainVertex { position: [x + s, h_top, z], normal, color, block_id: 0 });
-        vertices.push(TerrainVertex { position: [x, h_top, z], normal, color, block_id: 0 });
+        vertices.push(TerrainVertex { position: [x, h_bottom, z], normal, color, block_id: 0, uv: [0.0; 2] });
+        vertices.push(TerrainVertex { position: [x + s, h_bottom, z], normal, color, block_id: 0, uv: [0.0; 2] });
+        vertices.push(TerrainVertex { position: [x + s, h_top, z], normal, color, block_id: 0, uv: [0.0; 2] });
+        vertices.push(TerrainVertex { position: [x, h_top, z], normal, color, block_id: 0, uv: [0.0; 2] });
     } else {
-        vertices.push(TerrainVertex { position: [x + s, h_bottom, z], normal, color, block_id: 0 });
-        vertices.push(TerrainVertex { position: [x, h_bottom, z], normal, color, block_id: 0 });
-        vertices.push(TerrainVertex { position: [x, h_top, z], normal, color, block_id: 0 });
-        vertices.push(TerrainVertex { position: [x + s, h_top, z], normal, color, block_id: 0 });
+        vertices.push(TerrainVertex { position: [x + s, h_bottom, z], normal, color, block_id: 0, uv: [0.0; 2] });
+        vertices.push(TerrainVertex { position: [x, h_bottom, z], normal, color, block_id: 0, uv: [0.0; 2] });
+        vertices.push(TerrainVertex { position: [x, h_top, z], normal, color, block_id: 0, uv: [0.0; 2] });
+        vertices.push(TerrainVertex { position: [x + s, h_top, z], normal, color, block_id: 0, uv: [0.0; 2] });
     }
     indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
 }
@@ -5,15 +5,21 @@ use std::thread::{self, JoinHandle};
 use crate::gpu::terrain::voxel::CHUNK_SIZE;
 use crate::gpu::terrain::BlockPos;
 use crate::gpu::blocks::BlockType;
+use crate::gpu::profiler;
 
 use super::types::{GenerateRequest, GeneratedMesh};
 use super::generator::HybridGenerator;
+use super::jobs::SharedPlayerChunk;
+use super::pregen::clear_pregenerated_lod;
+use super::simplify::set_lod_quality;
 
 /// Асинхронный менеджер terrain с фоновой генерацией
 pub struct HybridTerrainManager {
     request_tx: Sender<GenerateRequest>,
     result_rx: Receiver<GeneratedMesh>,
     _worker: JoinHandle<()>,
+    /// Чанк игрока для отмены устаревших задач мешей в генераторе
+    player_chunk: SharedPlayerChunk,
     current_chunk_x: i32,
     current_chunk_z: i32,
     pending: bool,
@@ -21,21 +27,33 @@ pub struct HybridTerrainManager {
     last_sent_version: u64,
     lod_distances: [i32; 4],
     lod_changed: bool,
+    /// Полоса высот воксельных мешей (вертикальная дальность)
+    vertical_band: Option<(i32, i32)>,
+    band_changed: bool,
 }
 
 impl HybridTerrainManager {
     pub fn new() -> Self {
         let (request_tx, request_rx) = channel::<GenerateRequest>();
         let (result_tx, result_rx) = channel::<GeneratedMesh>();
+        let player_chunk = SharedPlayerChunk::new(0, 0);
 
+        let worker_player_chunk = player_chunk.clone();
         let worker = thread::spawn(move || {
-            let mut generator = HybridGenerator::new();
+            // Генератор мира создаётся по первому запросу: менеджер может
+            // появиться раньше, чем загрузка мира задаст его параметры
+            let mut generator = None;
             loop {
                 match request_rx.recv() {
                     Ok(request) => {
+                        let generator = generator.get_or_insert_with(|| HybridGenerator::new(worker_player_chunk.clone()));
                         if let Some(distances) = request.lod_distances {
                             generator.set_lod_distances(distances);
                         }
+                        if let Some(band) = request.vertical_band {
+                            generator.set_vertical_band(band);
+                        }
+                        let _scope = profiler::scope("meshing");
                         let mesh = generator.generate(
                             request.player_x,
                             request.player_z,
@@ -53,6 +71,7 @@ impl HybridTerrainManager {
             request_tx,
             result_rx,
             _worker: worker,
+            player_chunk,
             current_chunk_x: i32::MIN,
             current_chunk_z: i32::MIN,
             pending: false,
@@ -60,6 +79,8 @@ impl HybridTerrainManager {
             last_sent_version: 0,
             lod_distances: [8, 16, 32, 64],
             lod_changed: false,
+            vertical_band: None,
+            band_changed: false,
         }
     }
     
@@ -74,23 +95,54 @@ impl HybridTerrainManager {
         self.lod_distances
     }
     
-    pub fn generate_initial(&mut self, player_x: f32, player_z: f32) -> GeneratedMesh {
-        let mut generator = HybridGenerator::new();
-        let mesh = generator.generate(player_x, player_z, &HashMap::new(), 0);
+    /// Полоса высот, в которой мешатся воксельные чанки
+    /// (SectionTerrainManager; None - весь столбец)
+    pub fn set_vertical_band(&mut self, band: Option<(i32, i32)>) {
+        if self.vertical_band != band {
+            self.vertical_band = band;
+            self.band_changed = true;
+        }
+    }
+    
+    /// Качество дальних LOD (1.0 - без упрощения)
+    pub fn set_lod_quality(&mut self, quality: f32) {
+        if set_lod_quality(quality) {
+            // Старые меши построены с прежним качеством
+            clear_pregenerated_lod();
+            self.lod_changed = true;
+        }
+    }
+    
+    /// Сгенерировать область вокруг игрока сразу, в текущем потоке
+    /// (экран загрузки мира)
+    pub fn generate_initial(
+        &mut self,
+        player_x: f32,
+        player_z: f32,
+        world_changes: &HashMap<BlockPos, BlockType>,
+        changes_version: u64,
+    ) -> GeneratedMesh {
         self.current_chunk_x = (player_x / CHUNK_SIZE as f32).floor() as i32;
         self.current_chunk_z = (player_z / CHUNK_SIZE as f32).floor() as i32;
-        mesh
+        self.player_chunk.store(self.current_chunk_x, self.current_chunk_z);
+        self.last_sent_version = changes_version;
+        let mut generator = HybridGenerator::new(self.player_chunk.clone());
+        generator.set_vertical_band(self.vertical_band);
+        generator.generate(player_x, player_z, world_changes, changes_version)
     }
     
     pub fn update(&mut self, player_x: f32, player_z: f32, world_changes: &HashMap<BlockPos, BlockType>, changes_version: u64) {
         let chunk_x = (player_x / CHUNK_SIZE as f32).floor() as i32;
         let chunk_z = (player_z / CHUNK_SIZE as f32).floor() as i32;
         self.changes_version = changes_version;
+        // Генератор видит смену чанка сразу, даже посреди генерации
+        self.player_chunk.store(chunk_x, chunk_z);
         
         let need_regen = chunk_x != self.current_chunk_x 
             || chunk_z != self.current_chunk_z
             || changes_version != self.last_sent_version
-            || self.lod_changed;
+            || self.lod_changed
+            || self.band_changed;
         
         if need_regen && !self.pending {
             let lod_distances = if self.lod_changed {
@@ -99,6 +151,7 @@ impl HybridTerrainManager {
             } else {
                 None
             };
+            let vertical_band = std::mem::take(&mut self.band_changed).then_some(self.vertical_band);
             
             let request = GenerateRequest {
                 player_x,
@@ -106,6 +159,7 @@ impl HybridTerrainManager {
                 world_changes: world_changes.clone(),
                 changes_version,
                 lod_distances,
+                vertical_band,
             };
             
             if self.request_tx.send(request).is_ok() {
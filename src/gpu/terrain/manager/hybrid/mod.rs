@@ -2,6 +2,10 @@ mod types;
 mod lod_mesh;
 mod generator;
 mod manager;
+mod pregen;
+mod jobs;
+mod simplify;
 
 pub use types::{GeneratedChunkData, GeneratedMesh};
 pub use manager::HybridTerrainManager;
+pub use pregen::{PregenTask, parse_pregen_args, DEFAULT_PREGEN_RADIUS};
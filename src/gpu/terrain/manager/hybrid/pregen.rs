@@ -0,0 +1,235 @@
+// ============================================
+// Pregeneration - Фоновая прегенерация области
+// ============================================
+// `/pregen radius N [mesh]`: заранее считает карту высот всех чанков
+// в радиусе N от игрока (самая дорогая часть генерации - смешивание
+// биомов), а с флагом mesh ещё и LOD меши для масштабов 2/4/8, чтобы
+// облёт или быстрый полёт не упирался в генерацию.
+//
+// Работает в одном фоновом потоке (не отнимает пул rayon у основного
+// генератора), чанки идут от центра по кольцам, между пачками -
+// короткая пауза. Прогресс печатается каждые 10%.
+//
+// Кэш высот нужен только шумовому миру; у плоского мира считаются
+// лишь LOD меши, у пустого прегенерировать нечего.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::gpu::biomes::features::TreeImpostor;
+use crate::gpu::biomes::get_biome_height;
+use crate::gpu::terrain::cache::ChunkKey;
+use crate::gpu::terrain::generation::height_cache::{cache_chunk_heights, has_chunk_heights};
+use crate::gpu::terrain::generation::{terrain_generator, world_gen_config, WorldPreset};
+use crate::gpu::terrain::mesh::TerrainVertex;
+use crate::gpu::terrain::voxel::CHUNK_SIZE;
+
+use super::lod_mesh::generate_lod_chunk;
+
+/// Радиус по умолчанию (чанки)
+pub const DEFAULT_PREGEN_RADIUS: i32 = 16;
+/// Максимальный радиус (ограничен размером кэша высот)
+pub const MAX_PREGEN_RADIUS: i32 = 64;
+/// Чанков за пачку и пауза между пачками (троттлинг)
+const PREGEN_BATCH: usize = 16;
+const PREGEN_PAUSE: Duration = Duration::from_millis(4);
+/// Масштабы LOD, для которых строятся меши
+const PREMESH_SCALES: [i32; 3] = [2, 4, 8];
+/// Бюджет памяти на готовые LOD меши
+const PREMESH_BUDGET_BYTES: usize = 256 * 1024 * 1024;
+
+type LodMesh = (Vec<TerrainVertex>, Vec<u32>, Vec<TreeImpostor>);
+
+static LOD_MESH_STORE: OnceLock<RwLock<HashMap<ChunkKey, Arc<LodMesh>>>> = OnceLock::new();
+static LOD_MESH_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+fn lod_mesh_store() -> &'static RwLock<HashMap<ChunkKey, Arc<LodMesh>>> {
+    LOD_MESH_STORE.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Готовый LOD меш из прегенерации (None - генерировать как обычно)
+pub(super) fn pregenerated_lod_chunk(key: &ChunkKey) -> Option<LodMesh> {
+    if LOD_MESH_BYTES.load(Ordering::Relaxed) == 0 {
+        return None;
+    }
+    let store = lod_mesh_store().read().ok()?;
+    store.get(key).map(|mesh| (**mesh).clone())
+}
+
+/// Сбросить готовые LOD меши (сменилось качество дальних LOD)
+pub(super) fn clear_pregenerated_lod() {
+    if let Ok(mut store) = lod_mesh_store().write() {
+        store.clear();
+        LOD_MESH_BYTES.store(0, Ordering::Relaxed);
+    }
+}
+
+fn mesh_bytes(mesh: &LodMesh) -> usize {
+    mesh.0.len() * std::mem::size_of::<TerrainVertex>()
+        + mesh.1.len() * std::mem::size_of::<u32>()
+        + mesh.2.len() * std::mem::size_of::<TreeImpostor>()
+}
+
+/// Разбор аргументов команды: `radius N [mesh]`
+pub fn parse_pregen_args(args: &[&str]) -> Result<(i32, bool), String> {
+    let mut radius = DEFAULT_PREGEN_RADIUS;
+    let mut mesh = false;
+    let mut args = args.iter();
+
+    while let Some(&arg) = args.next() {
+        match arg {
+            "radius" => {
+                let value = args.next().ok_or("radius: ожидается число")?;
+                radius = value.parse().map_err(|_| format!("radius: не число '{}'", value))?;
+            }
+            "mesh" => mesh = true,
+            other => return Err(format!("неизвестный аргумент '{}'", other)),
+        }
+    }
+
+    if !(1..=MAX_PREGEN_RADIUS).contains(&radius) {
+        return Err(format!("radius должен быть 1..={}", MAX_PREGEN_RADIUS));
+    }
+    Ok((radius, mesh))
+}
+
+/// Общий прогресс задачи
+struct PregenProgress {
+    done: AtomicUsize,
+    total: usize,
+    cancelled: AtomicBool,
+    finished: AtomicBool,
+}
+
+/// Фоновая задача прегенерации
+pub struct PregenTask {
+    progress: Arc<PregenProgress>,
+    _worker: JoinHandle<()>,
+}
+
+impl PregenTask {
+    /// Запустить прегенерацию вокруг мировой точки (x, z)
+    pub fn start(x: f32, z: f32, radius: i32, mesh: bool) -> Self {
+        let center = (
+            (x / CHUNK_SIZE as f32).floor() as i32,
+            (z / CHUNK_SIZE as f32).floor() as i32,
+        );
+        let radius = radius.clamp(1, MAX_PREGEN_RADIUS);
+
+        // Кольца от центра: ближние чанки понадобятся раньше
+        let mut columns: Vec<(i32, i32)> = if world_gen_config().preset == WorldPreset::Default {
+            (-radius..=radius)
+                .flat_map(|dz| (-radius..=radius).map(move |dx| (dx, dz)))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        columns.sort_by_key(|&(dx, dz)| dx.abs().max(dz.abs()));
+
+        let mesh_keys: Vec<ChunkKey> = if mesh && terrain_generator().has_lod_terrain() {
+            PREMESH_SCALES
+                .iter()
+                .flat_map(|&scale| {
+                    let min = |c: i32| (c - radius).div_euclid(scale);
+                    let max = |c: i32| (c + radius).div_euclid(scale);
+                    (min(center.1)..=max(center.1)).flat_map(move |kz| {
+                        (min(center.0)..=max(center.0)).map(move |kx| ChunkKey::new(kx * scale, kz * scale, scale))
+                    })
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let progress = Arc::new(PregenProgress {
+            done: AtomicUsize::new(0),
+            total: columns.len() + mesh_keys.len(),
+            cancelled: AtomicBool::new(false),
+            finished: AtomicBool::new(false),
+        });
+
+        log::info!(
+            "Старт: радиус {} чанков вокруг ({}, {}), {} чанков{}",
+            radius, center.0, center.1, columns.len(),
+            if mesh { format!(" + {} LOD мешей", mesh_keys.len()) } else { String::new() },
+        );
+
+        let worker_progress = Arc::clone(&progress);
+        let worker = thread::spawn(move || {
+            run_pregen(center, &columns, &mesh_keys, &worker_progress);
+            worker_progress.finished.store(true, Ordering::Relaxed);
+        });
+
+        Self { progress, _worker: worker }
+    }
+
+    /// (готово, всего)
+    pub fn progress(&self) -> (usize, usize) {
+        (self.progress.done.load(Ordering::Relaxed), self.progress.total)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.progress.finished.load(Ordering::Relaxed)
+    }
+
+    /// Остановить (поток завершится после текущей пачки)
+    pub fn cancel(&self) {
+        self.progress.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+fn run_pregen(center: (i32, i32), columns: &[(i32, i32)], mesh_keys: &[ChunkKey], progress: &PregenProgress) {
+    let start = Instant::now();
+    let mut last_percent = 0;
+    let mut step = |progress: &PregenProgress| {
+        let done = progress.done.fetch_add(1, Ordering::Relaxed) + 1;
+        let percent = done * 100 / progress.total.max(1);
+        if percent / 10 > last_percent / 10 {
+            log::info!("{}% ({}/{})", percent, done, progress.total);
+        }
+        last_percent = percent;
+        if done % PREGEN_BATCH == 0 {
+            thread::sleep(PREGEN_PAUSE);
+        }
+    };
+
+    // Карта высот
+    for &(dx, dz) in columns {
+        if progress.cancelled.load(Ordering::Relaxed) {
+            log::info!("Отменено");
+            return;
+        }
+        let (cx, cz) = (center.0 + dx, center.1 + dz);
+        if !has_chunk_heights(cx, cz) && !cache_chunk_heights(cx, cz, get_biome_height) {
+            log::info!("Кэш высот заполнен, остановка");
+            return;
+        }
+        step(progress);
+    }
+
+    // LOD меши (читают высоты уже из кэша)
+    for key in mesh_keys {
+        if progress.cancelled.load(Ordering::Relaxed) {
+            log::info!("Отменено");
+            return;
+        }
+        if LOD_MESH_BYTES.load(Ordering::Relaxed) >= PREMESH_BUDGET_BYTES {
+            log::info!("Бюджет памяти LOD мешей исчерпан, остановка");
+            return;
+        }
+        let cached = lod_mesh_store().read().map(|store| store.contains_key(key)).unwrap_or(true);
+        if !cached {
+            let mesh = generate_lod_chunk(key.x, key.z, key.scale);
+            LOD_MESH_BYTES.fetch_add(mesh_bytes(&mesh), Ordering::Relaxed);
+            if let Ok(mut store) = lod_mesh_store().write() {
+                store.insert(*key, Arc::new(mesh));
+            }
+        }
+        step(progress);
+    }
+
+    log::info!("Готово за {:.1} с", start.elapsed().as_secs_f32());
+}
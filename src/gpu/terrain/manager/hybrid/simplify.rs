@@ -0,0 +1,321 @@
+// ============================================
+// LOD Simplify - Упрощение мешей дальних LOD
+// ============================================
+// Поверх greedy meshing для дальних LOD (масштаб >= 4):
+// 1. Мелкие бугры и ямы heightmap (перепад не больше допуска)
+//    срезаются до соседей - на таком расстоянии они меньше пикселя.
+// 2. Копланарные квады с близкими цветами сливаются в прямоугольники.
+// Качество 1.0 - упрощение выключено, 0.0 - максимальное.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use crate::gpu::terrain::mesh::{face_uv, TerrainVertex};
+
+/// Минимальный масштаб LOD, с которого меш упрощается
+pub(super) const MIN_SIMPLIFY_SCALE: i32 = 4;
+/// Допуск цвета (макс. разница компоненты) при качестве 0
+const MAX_COLOR_TOLERANCE: f32 = 0.12;
+
+/// Качество дальних LOD (биты f32, по умолчанию 1.0)
+static LOD_QUALITY: AtomicU32 = AtomicU32::new(0x3F80_0000);
+
+/// Текущее качество дальних LOD (0-1)
+pub(super) fn lod_quality() -> f32 {
+    f32::from_bits(LOD_QUALITY.load(Ordering::Relaxed))
+}
+
+/// Установить качество дальних LOD. true - значение изменилось
+pub(super) fn set_lod_quality(quality: f32) -> bool {
+    let quality = quality.clamp(0.0, 1.0);
+    LOD_QUALITY.swap(quality.to_bits(), Ordering::Relaxed) != quality.to_bits()
+}
+
+/// Нужно ли упрощать чанк этого масштаба
+pub(super) fn should_simplify(scale: i32, quality: f32) -> bool {
+    scale >= MIN_SIMPLIFY_SCALE && quality < 1.0
+}
+
+/// Срезать одиночные бугры и ямы heightmap с перепадом до допуска.
+/// heights - сетка size x size; sample(x, z) - высота в координатах
+/// сетки за её краем. Решение для каждой ячейки зависит только от
+/// исходных высот, поэтому соседние чанки сходятся на общих краях.
+pub(super) fn flatten_small_features(
+    heights: &mut [f32],
+    size: i32,
+    scale: i32,
+    quality: f32,
+    sample: impl Fn(i32, i32) -> f32,
+) {
+    let tolerance = (1.0 - quality) * scale as f32;
+    if tolerance <= 0.0 {
+        return;
+    }
+
+    let original = heights.to_vec();
+    let height_at = |x: i32, z: i32| {
+        if (0..size).contains(&x) && (0..size).contains(&z) {
+            original[(z * size + x) as usize]
+        } else {
+            sample(x, z)
+        }
+    };
+
+    for z in 0..size {
+        for x in 0..size {
+            let h = original[(z * size + x) as usize];
+            let neighbors = [height_at(x + 1, z), height_at(x - 1, z), height_at(x, z + 1), height_at(x, z - 1)];
+            let max = neighbors.iter().copied().fold(f32::MIN, f32::max);
+            let min = neighbors.iter().copied().fold(f32::MAX, f32::min);
+
+            if h > max && h - max <= tolerance {
+                heights[(z * size + x) as usize] = max;
+            } else if h < min && min - h <= tolerance {
+                heights[(z * size + x) as usize] = min;
+            }
+        }
+    }
+}
+
+/// Прямоугольник квада в плоскости (u, v) = ((axis+1)%3, (axis+2)%3)
+#[derive(Clone, Copy)]
+struct Rect {
+    u0: f32,
+    u1: f32,
+    v0: f32,
+    v1: f32,
+    color: [f32; 3],
+    /// Площадь-взвешенная сумма цвета слитых квадов
+    color_sum: [f32; 3],
+    area: f32,
+    block_id: u32,
+}
+
+/// Плоскость квадов: ось нормали, знак и координата
+type PlaneKey = (usize, bool, u32);
+
+/// Слить копланарные квады с близкими цветами. Ожидает квады по 4
+/// вершины и 6 индексов, как их пишет lod_mesh; остальное не трогает.
+pub(super) fn merge_coplanar_quads(
+    vertices: Vec<TerrainVertex>,
+    indices: Vec<u32>,
+    quality: f32,
+) -> (Vec<TerrainVertex>, Vec<u32>) {
+    let color_tolerance = (1.0 - quality) * MAX_COLOR_TOLERANCE;
+
+    let mut planes: HashMap<PlaneKey, Vec<Rect>> = HashMap::new();
+    let mut out_vertices = Vec::with_capacity(vertices.len() / 2);
+    let mut out_indices = Vec::with_capacity(indices.len() / 2);
+
+    for quad in indices.chunks(6) {
+        match quad_rect(&vertices, quad) {
+            Some((key, rect)) => planes.entry(key).or_default().push(rect),
+            None => {
+                // Не квад - переносим как есть
+                let base = out_vertices.len() as u32;
+                for (i, &index) in quad.iter().enumerate() {
+                    out_vertices.push(vertices[index as usize]);
+                    out_indices.push(base + i as u32);
+                }
+            }
+        }
+    }
+
+    // Детерминированный порядок вывода
+    let mut keys: Vec<_> = planes.keys().copied().collect();
+    keys.sort_unstable();
+
+    for key in keys {
+        let mut rects = planes.remove(&key).unwrap_or_default();
+        // Сначала вдоль u, затем получившиеся полосы вдоль v
+        rects = merge_pass(rects, color_tolerance, false);
+        rects = merge_pass(rects, color_tolerance, true);
+        for rect in rects {
+            emit_rect(&mut out_vertices, &mut out_indices, key, &rect);
+        }
+    }
+
+    (out_vertices, out_indices)
+}
+
+/// Прямоугольник квада или None, если это не выровненный по осям квад
+fn quad_rect(vertices: &[TerrainVertex], quad: &[u32]) -> Option<(PlaneKey, Rect)> {
+    if quad.len() != 6 {
+        return None;
+    }
+    let corners = [quad[0], quad[1], quad[2], quad[5]].map(|i| vertices[i as usize]);
+
+    let normal = corners[0].normal;
+    let axis = (0..3).max_by(|&a, &b| normal[a].abs().total_cmp(&normal[b].abs()))?;
+    let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+    let plane = corners[0].position[axis];
+    if corners.iter().any(|c| c.position[axis] != plane || c.normal != normal || c.color != corners[0].color) {
+        return None;
+    }
+
+    let (mut u0, mut u1, mut v0, mut v1) = (f32::MAX, f32::MIN, f32::MAX, f32::MIN);
+    for c in &corners {
+        u0 = u0.min(c.position[u]);
+        u1 = u1.max(c.position[u]);
+        v0 = v0.min(c.position[v]);
+        v1 = v1.max(c.position[v]);
+    }
+    // Все углы должны лежать на углах прямоугольника
+    let on_corner = |c: &TerrainVertex| {
+        (c.position[u] == u0 || c.position[u] == u1) && (c.position[v] == v0 || c.position[v] == v1)
+    };
+    if !corners.iter().all(on_corner) || u0 == u1 || v0 == v1 {
+        return None;
+    }
+
+    let area = (u1 - u0) * (v1 - v0);
+    let color = corners[0].color;
+    let rect = Rect {
+        u0,
+        u1,
+        v0,
+        v1,
+        color,
+        color_sum: color.map(|c| c * area),
+        area,
+        block_id: corners[0].block_id,
+    };
+    Some(((axis, normal[axis] > 0.0, plane.to_bits()), rect))
+}
+
+/// Один проход слияния соседних прямоугольников с общим ребром.
+/// along_v: false - сливаем по u (одинаковые v0, v1), true - по v
+fn merge_pass(mut rects: Vec<Rect>, color_tolerance: f32, along_v: bool) -> Vec<Rect> {
+    // (начало ребра, конец ребра, координата вдоль слияния)
+    let key = |r: &Rect| if along_v { (r.u0, r.u1, r.v0) } else { (r.v0, r.v1, r.u0) };
+    rects.sort_unstable_by(|a, b| {
+        let (a, b) = (key(a), key(b));
+        a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)).then(a.2.total_cmp(&b.2))
+    });
+
+    let mut merged: Vec<Rect> = Vec::with_capacity(rects.len());
+    for rect in rects {
+        if let Some(last) = merged.last_mut() {
+            let same_edge = if along_v {
+                last.u0 == rect.u0 && last.u1 == rect.u1 && last.v1 == rect.v0
+            } else {
+                last.v0 == rect.v0 && last.v1 == rect.v1 && last.u1 == rect.u0
+            };
+            if same_edge && last.block_id == rect.block_id && colors_close(last.color, rect.color, color_tolerance) {
+                if along_v {
+                    last.v1 = rect.v1;
+                } else {
+                    last.u1 = rect.u1;
+                }
+                for i in 0..3 {
+                    last.color_sum[i] += rect.color_sum[i];
+                }
+                last.area += rect.area;
+                continue;
+            }
+        }
+        merged.push(rect);
+    }
+    merged
+}
+
+fn colors_close(a: [f32; 3], b: [f32; 3], tolerance: f32) -> bool {
+    (0..3).all(|i| (a[i] - b[i]).abs() <= tolerance)
+}
+
+/// Квад прямоугольника с обходом как у исходных граней LOD
+fn emit_rect(
+    vertices: &mut Vec<TerrainVertex>,
+    indices: &mut Vec<u32>,
+    (axis, positive, plane): PlaneKey,
+    rect: &Rect,
+) {
+    let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+    let mut normal = [0.0; 3];
+    normal[axis] = if positive { 1.0 } else { -1.0 };
+    let color = rect.color_sum.map(|c| c / rect.area);
+
+    let corners = if positive {
+        [(rect.u0, rect.v0), (rect.u1, rect.v0), (rect.u1, rect.v1), (rect.u0, rect.v1)]
+    } else {
+        [(rect.u0, rect.v0), (rect.u0, rect.v1), (rect.u1, rect.v1), (rect.u1, rect.v0)]
+    };
+
+    let base = vertices.len() as u32;
+    for (cu, cv) in corners {
+        let mut position = [0.0; 3];
+        position[axis] = f32::from_bits(plane);
+        position[u] = cu;
+        position[v] = cv;
+        // UV от угла прямоугольника: текстура повторяется по блокам
+        let mut local = [0.0; 3];
+        local[u] = cu - rect.u0;
+        local[v] = cv - rect.v0;
+        let uv = face_uv(local, normal);
+        vertices.push(TerrainVertex { position, normal, color, block_id: rect.block_id, uv });
+    }
+    indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn top_quad(vertices: &mut Vec<TerrainVertex>, indices: &mut Vec<u32>, x: f32, z: f32, color: [f32; 3]) {
+        let normal = [0.0, 1.0, 0.0];
+        let base = vertices.len() as u32;
+        for (dx, dz) in [(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0)] {
+            vertices.push(TerrainVertex { position: [x + dx, 10.0, z + dz], normal, color, block_id: 0, uv: [0.0; 2] });
+        }
+        indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+    }
+
+    #[test]
+    fn merges_grid_of_close_colors_into_one_quad() {
+        let (mut vertices, mut indices) = (Vec::new(), Vec::new());
+        for z in 0..4 {
+            for x in 0..4 {
+                let shade = 0.5 + (x + z) as f32 * 0.005;
+                top_quad(&mut vertices, &mut indices, x as f32, z as f32, [shade, 0.6, 0.2]);
+            }
+        }
+
+        let (vertices, indices) = merge_coplanar_quads(vertices, indices, 0.0);
+        assert_eq!(indices.len(), 6);
+        let xs: Vec<f32> = vertices.iter().map(|v| v.position[0]).collect();
+        let zs: Vec<f32> = vertices.iter().map(|v| v.position[2]).collect();
+        assert_eq!(xs.iter().copied().fold(f32::MAX, f32::min), 0.0);
+        assert_eq!(xs.iter().copied().fold(f32::MIN, f32::max), 4.0);
+        assert_eq!(zs.iter().copied().fold(f32::MAX, f32::min), 0.0);
+        assert_eq!(zs.iter().copied().fold(f32::MIN, f32::max), 4.0);
+    }
+
+    #[test]
+    fn keeps_distinct_colors_and_winding() {
+        let (mut vertices, mut indices) = (Vec::new(), Vec::new());
+        top_quad(&mut vertices, &mut indices, 0.0, 0.0, [0.2, 0.6, 0.2]);
+        top_quad(&mut vertices, &mut indices, 1.0, 0.0, [0.8, 0.8, 0.3]);
+
+        let (merged, merged_indices) = merge_coplanar_quads(vertices.clone(), indices, 1.0);
+        assert_eq!(merged_indices.len(), 12);
+
+        // Нормаль треугольника совпадает с нормалью вершин
+        let p = |i: u32| ultraviolet::Vec3::from(merged[i as usize].position);
+        let (a, b, c) = (p(merged_indices[0]), p(merged_indices[1]), p(merged_indices[2]));
+        assert!((b - a).cross(c - a).y > 0.0);
+    }
+
+    #[test]
+    fn flattens_single_bump_within_tolerance() {
+        let size = 3;
+        let mut heights = vec![10.0; 9];
+        heights[4] = 12.0;
+        flatten_small_features(&mut heights, size, 4, 0.0, |_, _| 10.0);
+        assert_eq!(heights[4], 10.0);
+
+        let mut cliff = vec![10.0; 9];
+        cliff[4] = 30.0;
+        flatten_small_features(&mut cliff, size, 4, 0.0, |_, _| 10.0);
+        assert_eq!(cliff[4], 30.0);
+    }
+}
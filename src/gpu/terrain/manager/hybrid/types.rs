@@ -4,6 +4,7 @@ use crate::gpu::terrain::mesh::TerrainVertex;
 use crate::gpu::terrain::cache::ChunkKey;
 use crate::gpu::terrain::BlockPos;
 use crate::gpu::blocks::BlockType;
+use crate::gpu::biomes::features::TreeImpostor;
 
 /// Запрос на генерацию terrain
 pub(super) struct GenerateRequest {
@@ -12,6 +13,8 @@ pub(super) struct GenerateRequest {
     pub world_changes: HashMap<BlockPos, BlockType>,
     pub changes_version: u64,
     pub lod_distances: Option<[i32; 4]>,
+    /// Новая полоса высот воксельных мешей (None внутри - весь столбец)
+    pub vertical_band: Option<Option<(i32, i32)>>,
 }
 
 /// Данные сгенерированного чанка
@@ -19,6 +22,8 @@ pub struct GeneratedChunkData {
     pub key: ChunkKey,
     pub vertices: Vec<TerrainVertex>,
     pub indices: Vec<u32>,
+    /// Деревья-импосторы (только для LOD чанков)
+    pub impostors: Vec<TreeImpostor>,
 }
 
 /// Результат генерации мешей
@@ -2,4 +2,5 @@ mod hybrid;
 mod section;
 
 pub use hybrid::{HybridTerrainManager, GeneratedMesh, GeneratedChunkData};
+pub use hybrid::{PregenTask, parse_pregen_args, DEFAULT_PREGEN_RADIUS};
 pub use section::SectionTerrainManager;
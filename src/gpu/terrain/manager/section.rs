@@ -1,30 +1,123 @@
 // ============================================
-// Section Terrain Manager - Секционная генерация
+// Section Terrain Manager - Вертикальная дальность секций
 // ============================================
+// Своя настройка, отдельная от горизонтальных дистанций LOD: сколько
+// секций 16 блоков вверх и вниз от камеры видно. Рисуются секции в
+// этой дальности, а мешатся - в полосе чуть шире (запас на случай,
+// пока новые меши в пути). Полоса сдвигается, только когда камера
+// ушла больше чем на секцию от её центра, иначе каждый шаг через
+// границу секции пересобирал бы все воксельные чанки.
+//
+// Полоса же задаёт, какие секции 16³ держат воксельные чанки: секции
+// вне неё выгружаются после генерации, а при сдвиге полосы чанки без
+// нужных секций генерируются заново. Поэтому высота мира
+// (MIN_HEIGHT..WORLD_HEIGHT) не стоит памяти вне дальности.
 
-use std::collections::HashSet;
-use std::sync::mpsc::{channel, Receiver, Sender};
-use std::thread::{self, JoinHandle};
+use crate::gpu::terrain::voxel::{MIN_HEIGHT, SECTION_COUNT, SECTION_SIZE};
 
-use crate::gpu::terrain::cache::ChunkKey;
+/// Секций запаса полосы мешинга над и под дальностью прорисовки
+const BAND_MARGIN: i32 = 2;
 
-/// Секционный менеджер (заглушка для совместимости)
+/// Вертикальная дальность секций вокруг камеры
 pub struct SectionTerrainManager {
-    _worker: JoinHandle<()>,
+    /// Дальность в секциях (None - весь столбец)
+    vertical_distance: Option<i32>,
+    /// Секция камеры, вокруг которой построена полоса мешинга
+    band_center: Option<i32>,
 }
 
 impl SectionTerrainManager {
     pub fn new() -> Self {
-        let (tx, rx) = channel::<()>();
-        let worker = thread::spawn(move || {
-            loop {
-                match rx.recv() {
-                    Ok(_) => {}
-                    Err(_) => break,
-                }
+        Self {
+            vertical_distance: None,
+            band_center: None,
+        }
+    }
+
+    /// Вертикальная дальность в секциях (None - весь столбец)
+    pub fn set_vertical_distance(&mut self, sections: Option<i32>) {
+        if self.vertical_distance != sections {
+            self.vertical_distance = sections;
+            // Новая полоса посчитается в следующем update; без дальности
+            // центр остаётся, чтобы update вернул весь столбец
+            if sections.is_some() {
+                self.band_center = None;
             }
-        });
-        
-        Self { _worker: worker }
+        }
+    }
+
+    /// Высоты, в которых рисуются воксельные секции
+    pub fn draw_range(&self, camera_y: f32) -> Option<(f32, f32)> {
+        self.vertical_distance.map(|sections| {
+            let reach = (sections * SECTION_SIZE) as f32;
+            (camera_y - reach, camera_y + reach)
+        })
+    }
+
+    /// Сдвинуть полосу мешинга за камерой. Some - полоса изменилась
+    /// (внутри - новые высоты [min, max] или None для всего столбца),
+    /// воксельные чанки нужно перестроить
+    pub fn update(&mut self, camera_y: f32) -> Option<Option<(i32, i32)>> {
+        let Some(sections) = self.vertical_distance else {
+            // Без дальности полоса - весь столбец (центр не нужен)
+            return self.band_center.take().map(|_| None);
+        };
+        let section = (camera_y.floor() as i32 - MIN_HEIGHT).div_euclid(SECTION_SIZE);
+        // Выше и ниже мира полоса одна и та же
+        let section = section.clamp(0, SECTION_COUNT as i32 - 1);
+        if self.band_center.is_some_and(|center| (section - center).abs() <= 1) {
+            return None;
+        }
+        self.band_center = Some(section);
+        Some(Some(band_around(section, sections)))
+    }
+}
+
+impl Default for SectionTerrainManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Полоса высот мешинга вокруг секции
+fn band_around(section: i32, sections: i32) -> (i32, i32) {
+    let reach = sections + BAND_MARGIN;
+    let low = (section - reach).max(0);
+    let high = (section + reach).min(SECTION_COUNT as i32 - 1);
+    (MIN_HEIGHT + low * SECTION_SIZE, MIN_HEIGHT + (high + 1) * SECTION_SIZE - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::terrain::voxel::constants::WORLD_HEIGHT;
+
+    #[test]
+    fn band_follows_camera_with_hysteresis() {
+        let mut sections = SectionTerrainManager::new();
+        assert_eq!(sections.update(40.0), None);
+
+        sections.set_vertical_distance(Some(1));
+        // Камера в секции 34 (y 32..47): секции 31..37
+        assert_eq!(sections.update(40.0), Some(Some((-16, 95))));
+        // Соседняя секция - полоса ещё покрывает дальность
+        assert_eq!(sections.update(50.0), None);
+        assert_eq!(sections.update(70.0), Some(Some((16, 127))));
+        assert_eq!(sections.draw_range(70.0), Some((54.0, 86.0)));
+
+        sections.set_vertical_distance(None);
+        assert_eq!(sections.update(70.0), Some(None));
+        assert_eq!(sections.update(70.0), None);
+    }
+
+    #[test]
+    fn band_is_clamped_to_world() {
+        let mut sections = SectionTerrainManager::new();
+        sections.set_vertical_distance(Some(2));
+        assert_eq!(sections.update(-1000.0), Some(Some((MIN_HEIGHT, MIN_HEIGHT + 5 * SECTION_SIZE - 1))));
+        // Дальше вниз полоса та же
+        assert_eq!(sections.update(-5000.0), None);
+        // И у верха мира
+        assert_eq!(sections.update(5000.0), Some(Some((WORLD_HEIGHT - 5 * SECTION_SIZE, WORLD_HEIGHT - 1))));
     }
 }
@@ -1,3 +1,7 @@
 mod vertex;
+mod sections;
+#[cfg(test)]
+pub(crate) mod validate;
 
-pub use vertex::TerrainVertex;
+pub use vertex::{face_uv, FadeInstance, TerrainVertex};
+pub use sections::{MeshSection, sort_by_section};
@@ -0,0 +1,82 @@
+// ============================================
+// Mesh Sections - Разбиение меша по высоте
+// ============================================
+// Треугольники меша сортируются по секциям 16 блоков (по нижней точке),
+// у каждой секции - диапазон индексов и фактические min/max Y её
+// геометрии. Так можно рисовать только секции рядом с игроком по
+// вертикали, не перестраивая меш.
+
+use crate::gpu::terrain::voxel::{section_index, SECTION_COUNT};
+
+use super::TerrainVertex;
+
+/// Диапазон индексов меша одной секции
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MeshSection {
+    /// Нижняя и верхняя точки геометрии секции
+    pub min_y: f32,
+    pub max_y: f32,
+    /// Первый индекс и число индексов
+    pub start: u32,
+    pub count: u32,
+}
+
+impl MeshSection {
+    /// Пересекается ли геометрия секции с диапазоном высот
+    #[inline]
+    pub fn overlaps(&self, min_y: f32, max_y: f32) -> bool {
+        self.max_y >= min_y && self.min_y <= max_y
+    }
+}
+
+/// Отсортировать треугольники по секциям. Возвращает новые индексы и
+/// непустые секции снизу вверх
+pub fn sort_by_section(vertices: &[TerrainVertex], indices: &[u32]) -> (Vec<u32>, Vec<MeshSection>) {
+    let mut buckets: Vec<Vec<u32>> = vec![Vec::new(); SECTION_COUNT];
+    let mut bounds = vec![(f32::MAX, f32::MIN); SECTION_COUNT];
+
+    for triangle in indices.chunks_exact(3) {
+        let ys = [0, 1, 2].map(|i| vertices[triangle[i] as usize].position[1]);
+        let low = ys[0].min(ys[1]).min(ys[2]);
+        let high = ys[0].max(ys[1]).max(ys[2]);
+
+        // Юбки LOD и грани у пределов мира уходят за крайние секции
+        let section = section_index(low.floor() as i32).min(SECTION_COUNT - 1);
+        buckets[section].extend_from_slice(triangle);
+        bounds[section] = (bounds[section].0.min(low), bounds[section].1.max(high));
+    }
+
+    let mut sorted = Vec::with_capacity(indices.len());
+    let mut sections = Vec::new();
+    for (bucket, (min_y, max_y)) in buckets.into_iter().zip(bounds) {
+        if bucket.is_empty() {
+            continue;
+        }
+        sections.push(MeshSection { min_y, max_y, start: sorted.len() as u32, count: bucket.len() as u32 });
+        sorted.extend(bucket);
+    }
+    (sorted, sections)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vertex(y: f32) -> TerrainVertex {
+        TerrainVertex { position: [0.0, y, 0.0], normal: [0.0, 1.0, 0.0], color: [1.0; 3], block_id: 0, uv: [0.0; 2] }
+    }
+
+    #[test]
+    fn groups_triangles_by_lowest_point() {
+        let vertices = vec![vertex(70.0), vertex(70.0), vertex(71.0), vertex(-20.0), vertex(-20.0), vertex(40.0)];
+        let indices = vec![0, 1, 2, 3, 4, 5];
+
+        let (sorted, sections) = sort_by_section(&vertices, &indices);
+        assert_eq!(sorted, vec![3, 4, 5, 0, 1, 2]);
+        assert_eq!(sections.len(), 2);
+        assert_eq!((sections[0].min_y, sections[0].max_y), (-20.0, 40.0));
+        assert_eq!((sections[1].start, sections[1].count), (3, 3));
+        assert!(sections[0].overlaps(30.0, 100.0));
+        assert!(!sections[1].overlaps(-50.0, 60.0));
+    }
+}
@@ -0,0 +1,197 @@
+// ============================================
+// Mesh Validation - Проверка greedy мешей (только тесты)
+// ============================================
+// Эталон - наивный меш: по единичной грани на каждую сторону
+// твёрдого вокселя, соседствующую с пустотой. Greedy меш
+// растеризуется обратно в единичные грани и сравнивается с эталоном:
+// - каждый квад - прямоугольник в плоскости оси, обход CCW снаружи
+// - квады не перекрываются и не дублируются
+// - покрытие совпадает с эталоном клетка в клетку (площадь)
+// - поверхность замкнута: каждое единичное ребро a->b имеет пару b->a
+//
+// Объёмы случайные (xorshift, воспроизводимо по номеру случая).
+// Число случаев можно поднять переменной окружения MESH_FUZZ_CASES.
+
+use std::collections::{HashMap, HashSet};
+
+/// Единичная грань: (ось, знак нормали, координата плоскости, a, b),
+/// a и b - координаты по осям (ось + 1) % 3 и (ось + 2) % 3
+pub type UnitFace = (usize, i32, i32, i32, i32);
+
+/// Квад меша в целых координатах вокселей
+#[derive(Debug, Clone, Copy)]
+pub struct Quad {
+    /// Углы в порядке обхода (индексы 0-1-2, 0-2-3)
+    pub corners: [[i32; 3]; 4],
+    /// Нормаль (единичный вектор по оси)
+    pub normal: [i32; 3],
+}
+
+/// Случайный объём size^3: 0 - пусто, иначе тип из `types`
+pub struct FuzzVolume {
+    /// Минимальный угол объёма
+    pub origin: [i32; 3],
+    pub size: [i32; 3],
+    cells: Vec<u8>,
+}
+
+impl FuzzVolume {
+    /// Объём с заданной долей заполнения
+    pub fn random(seed: u64, size: [i32; 3], fill: f32, types: &[u8]) -> Self {
+        let mut state = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        let count = (size[0] * size[1] * size[2]) as usize;
+        let cells = (0..count)
+            .map(|_| {
+                let roll = (next() % 10_000) as f32 / 10_000.0;
+                if roll < fill { types[(next() % types.len() as u64) as usize] } else { 0 }
+            })
+            .collect();
+        Self { origin: [0; 3], size, cells }
+    }
+
+    /// Сдвинуть объём (проверка границ, не начинающихся с нуля)
+    pub fn with_origin(mut self, origin: [i32; 3]) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Отражение по оси (проверка симметрии Pos/Neg веток меширов)
+    pub fn mirrored(&self, axis: usize) -> Self {
+        let mut cells = self.cells.clone();
+        for z in 0..self.size[2] {
+            for y in 0..self.size[1] {
+                for x in 0..self.size[0] {
+                    let mut p = [x, y, z];
+                    p[axis] = self.size[axis] - 1 - p[axis];
+                    cells[self.index(x, y, z)] = self.cells[self.index(p[0], p[1], p[2])];
+                }
+            }
+        }
+        Self { origin: self.origin, size: self.size, cells }
+    }
+
+    /// Индекс по локальным координатам
+    fn index(&self, x: i32, y: i32, z: i32) -> usize {
+        ((z * self.size[1] + y) * self.size[0] + x) as usize
+    }
+
+    /// Тип в мировой точке (вне объёма - пусто)
+    pub fn get(&self, x: i32, y: i32, z: i32) -> u8 {
+        let local = [x - self.origin[0], y - self.origin[1], z - self.origin[2]];
+        let inside = (0..3).all(|i| (0..self.size[i]).contains(&local[i]));
+        if inside { self.cells[self.index(local[0], local[1], local[2])] } else { 0 }
+    }
+
+    /// Мировые границы (min, max включительно)
+    pub fn max(&self) -> [i32; 3] {
+        [0, 1, 2].map(|i| self.origin[i] + self.size[i] - 1)
+    }
+
+    /// Наивный меш: все открытые грани твёрдых вокселей
+    pub fn naive_faces(&self) -> HashSet<UnitFace> {
+        let mut faces = HashSet::new();
+        let max = self.max();
+        for z in self.origin[2]..=max[2] {
+            for y in self.origin[1]..=max[1] {
+                for x in self.origin[0]..=max[0] {
+                    if self.get(x, y, z) == 0 {
+                        continue;
+                    }
+                    for axis in 0..3 {
+                        for sign in [1, -1] {
+                            let mut n = [x, y, z];
+                            n[axis] += sign;
+                            if self.get(n[0], n[1], n[2]) == 0 {
+                                let p = [x, y, z];
+                                let plane = p[axis] + if sign > 0 { 1 } else { 0 };
+                                faces.insert((axis, sign, plane, p[(axis + 1) % 3], p[(axis + 2) % 3]));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        faces
+    }
+}
+
+/// Число случайных объёмов на тест
+pub fn fuzz_cases() -> u64 {
+    std::env::var("MESH_FUZZ_CASES").ok().and_then(|v| v.parse().ok()).unwrap_or(48)
+}
+
+fn sub(a: [i32; 3], b: [i32; 3]) -> [i32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn cross(a: [i32; 3], b: [i32; 3]) -> [i32; 3] {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+/// Проверить greedy меш против наивного эталона
+pub fn assert_mesh_matches(quads: &[Quad], expected: &HashSet<UnitFace>, context: &str) {
+    let mut covered: HashSet<UnitFace> = HashSet::new();
+    let mut edges: HashMap<([i32; 3], [i32; 3]), i32> = HashMap::new();
+
+    for quad in quads {
+        let axis = quad.normal.iter().position(|&n| n != 0)
+            .unwrap_or_else(|| panic!("{}: квад без нормали {:?}", context, quad));
+        let sign = quad.normal[axis];
+        let [c0, c1, c2, c3] = quad.corners;
+        let (ua, va) = ((axis + 1) % 3, (axis + 2) % 3);
+
+        // Плоский прямоугольник вдоль осей
+        assert!(quad.corners.iter().all(|c| c[axis] == c0[axis]), "{}: квад не в плоскости {:?}", context, quad);
+        assert_eq!(sub(c1, c0), sub(c2, c3), "{}: не прямоугольник {:?}", context, quad);
+        for (a, b) in [(c0, c1), (c1, c2)] {
+            let d = sub(b, a);
+            assert_eq!(d.iter().filter(|&&v| v != 0).count(), 1, "{}: ребро не по оси {:?}", context, quad);
+        }
+
+        // Обход CCW снаружи: нормаль треугольника совпадает с нормалью грани
+        let area = cross(sub(c1, c0), sub(c2, c0));
+        assert!(area[axis] * sign > 0, "{}: неверный обход (winding) {:?}", context, quad);
+
+        // Растеризация в единичные грани
+        let (u0, u1) = (quad.corners.iter().map(|c| c[ua]).min().unwrap(), quad.corners.iter().map(|c| c[ua]).max().unwrap());
+        let (v0, v1) = (quad.corners.iter().map(|c| c[va]).min().unwrap(), quad.corners.iter().map(|c| c[va]).max().unwrap());
+        for u in u0..u1 {
+            for v in v0..v1 {
+                let face = (axis, sign, c0[axis], u, v);
+                assert!(covered.insert(face), "{}: перекрытие квадов в {:?}", context, face);
+            }
+        }
+
+        // Направленные единичные рёбра контура
+        for (a, b) in [(c0, c1), (c1, c2), (c2, c3), (c3, c0)] {
+            let d = sub(b, a);
+            let len = d.iter().map(|v| v.abs()).sum::<i32>();
+            let step = [d[0].signum(), d[1].signum(), d[2].signum()];
+            for i in 0..len {
+                let from = [a[0] + step[0] * i, a[1] + step[1] * i, a[2] + step[2] * i];
+                let to = [from[0] + step[0], from[1] + step[1], from[2] + step[2]];
+                *edges.entry((from, to)).or_default() += 1;
+            }
+        }
+    }
+
+    let missing = expected.difference(&covered).count();
+    let extra = covered.difference(expected).count();
+    assert!(
+        missing == 0 && extra == 0,
+        "{}: площадь не совпадает с эталоном (нет {} граней, лишних {})",
+        context, missing, extra
+    );
+
+    // Замкнутость: T-стыки greedy квадов разбиты на единичные рёбра
+    for (&(from, to), &count) in &edges {
+        let back = edges.get(&(to, from)).copied().unwrap_or(0);
+        assert_eq!(count, back, "{}: дыра в поверхности на ребре {:?} -> {:?}", context, from, to);
+    }
+}
@@ -9,6 +9,9 @@ pub struct TerrainVertex {
     pub normal: [f32; 3],
     pub color: [f32; 3],
     pub block_id: u32,  // ID блока для текстурного атласа
+    /// UV в тайлах от угла квада: склеенная грань w x h блоков получает
+    /// 0..w и 0..h, текстура повторяется (сэмплер Repeat)
+    pub uv: [f32; 2],
 }
 
 impl TerrainVertex {
@@ -37,17 +40,71 @@ impl TerrainVertex {
                     shader_location: 3,
                     format: wgpu::VertexFormat::Uint32,
                 },
+                wgpu::VertexAttribute {
+                    offset: std::mem::size_of::<[f32; 10]>() as wgpu::BufferAddress,
+                    shader_location: 4,
+                    format: wgpu::VertexFormat::Float32x2,
+                },
             ],
         }
     }
     
     /// Создать вершину (block_id = 0 по умолчанию)
     pub fn new(position: [f32; 3], normal: [f32; 3], color: [f32; 3]) -> Self {
-        Self { position, normal, color, block_id: 0 }
+        Self { position, normal, color, block_id: 0, uv: [0.0; 2] }
     }
     
     /// Создать вершину с block_id
     pub fn with_block(position: [f32; 3], normal: [f32; 3], color: [f32; 3], block_id: u8) -> Self {
-        Self { position, normal, color, block_id: block_id as u32 }
+        Self { position, normal, color, block_id: block_id as u32, uv: [0.0; 2] }
+    }
+    
+    /// Тип блока грани (младший байт block_id)
+    #[inline]
+    pub fn block_type(&self) -> u8 {
+        (self.block_id & 0xFF) as u8
+    }
+    
+    /// Грань полупрозрачного блока (мешер пишет непрозрачность в старший байт block_id)
+    #[inline]
+    pub fn is_translucent(&self) -> bool {
+        self.block_id >> 24 != 0
+    }
+}
+
+/// UV точки на грани в тайлах (те же оси, что get_face_coords в
+/// terrain_shadows.wgsl): верх/низ - (x, z), грани X - (z, y), грани Z - (x, y)
+#[inline]
+pub fn face_uv(local: [f32; 3], normal: [f32; 3]) -> [f32; 2] {
+    if normal[1].abs() > 0.5 {
+        [local[0], local[2]]
+    } else if normal[0].abs() > 0.5 {
+        [local[2], local[1]]
+    } else {
+        [local[0], local[1]]
+    }
+}
+
+/// Дизеринг чанка при смене LOD (второй вершинный буфер, шаг - инстанс)
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable, Default, PartialEq)]
+pub struct FadeInstance {
+    /// Доля пикселей, уже отданных новому мешу (0-1)
+    pub coverage: f32,
+    /// 1 - меш исчезает (рисует пиксели, ещё не занятые новым), 0 - проявляется
+    pub fade_out: f32,
+}
+
+impl FadeInstance {
+    pub fn desc() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<FadeInstance>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &[wgpu::VertexAttribute {
+                offset: 0,
+                shader_location: 5,
+                format: wgpu::VertexFormat::Float32x2,
+            }],
+        }
     }
 }
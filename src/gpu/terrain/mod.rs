@@ -10,12 +10,16 @@ pub mod gpu;
 pub mod lod;
 pub mod manager;
 pub mod world_changes;
+pub mod world_edit;
+pub mod stats;
 
 // Re-exports
 pub use mesh::TerrainVertex;
 pub use cache::ChunkKey;
-pub use gpu::GpuChunkManager;
+pub use gpu::{GpuChunkManager, GpuChunk};
 pub use voxel::{VoxelChunk, ChunkNeighbors, CHUNK_SIZE, MIN_HEIGHT};
 pub use manager::{HybridTerrainManager, GeneratedMesh, GeneratedChunkData, SectionTerrainManager};
+pub use manager::{PregenTask, parse_pregen_args, DEFAULT_PREGEN_RADIUS};
 pub use generation::{get_height, get_lod_height, CaveParams, is_cave};
 pub use world_changes::{WorldChanges, BlockPos};
+pub use world_edit::{WorldEdit, EditOperation, EditStep, EditSummary, edit_volume, EDIT_BLOCKS_PER_FRAME, MAX_EDIT_VOLUME};
@@ -0,0 +1,236 @@
+// ============================================
+// World Stats - Статистика сгенерированного мира
+// ============================================
+// Сканирует чанки в радиусе вокруг точки (с учётом изменений мира)
+// и собирает таблицы для настройки генерации:
+// - частота блоков
+// - плотность руд по полосам глубины (на 1000 твёрдых блоков)
+// - покрытие биомов (доля столбцов)
+//
+// Чанки генерируются параллельно (rayon), результат печатается
+// в консоль и сохраняется в JSON.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::path::Path;
+
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::gpu::blocks::{global_registry, get_block_name, BlockCategory, BlockType, AIR, WATER};
+use crate::gpu::biomes::{biome_registry, biome_selector, BiomeId};
+
+use super::voxel::constants::{CHUNK_SIZE, MIN_HEIGHT};
+use super::voxel::VoxelChunk;
+use super::BlockPos;
+
+/// Высота полосы глубины для руд
+const DEPTH_BAND: i32 = 16;
+/// Файл отчёта по умолчанию
+pub const WORLD_STATS_FILE: &str = "world_stats.json";
+/// Радиус сканирования по умолчанию (чанки)
+pub const DEFAULT_SCAN_RADIUS: i32 = 8;
+
+/// Частота одного блока
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockFrequency {
+    pub id: BlockType,
+    pub name: String,
+    pub count: u64,
+    /// Доля среди всех не-воздушных блоков (%)
+    pub percent: f64,
+}
+
+/// Руды в полосе глубины
+#[derive(Debug, Clone, Serialize)]
+pub struct OreBand {
+    pub min_y: i32,
+    pub max_y: i32,
+    pub solid_blocks: u64,
+    /// (имя руды, количество, на 1000 твёрдых блоков)
+    pub ores: Vec<(String, u64, f64)>,
+}
+
+/// Покрытие биома
+#[derive(Debug, Clone, Serialize)]
+pub struct BiomeCoverage {
+    pub name: String,
+    pub columns: u64,
+    pub percent: f64,
+}
+
+/// Отчёт сканирования
+#[derive(Debug, Clone, Serialize)]
+pub struct WorldStats {
+    pub center: [i32; 2],
+    pub radius_chunks: i32,
+    pub chunks_scanned: usize,
+    pub total_blocks: u64,
+    pub blocks: Vec<BlockFrequency>,
+    pub ore_bands: Vec<OreBand>,
+    pub biomes: Vec<BiomeCoverage>,
+}
+
+/// Сырые счётчики одного чанка (складываются при reduce)
+#[derive(Default)]
+struct Counts {
+    blocks: HashMap<BlockType, u64>,
+    /// band -> (твёрдые блоки, руда -> количество)
+    bands: HashMap<i32, (u64, HashMap<BlockType, u64>)>,
+    biomes: HashMap<BiomeId, u64>,
+}
+
+impl Counts {
+    fn merge(mut self, other: Counts) -> Counts {
+        for (block, count) in other.blocks {
+            *self.blocks.entry(block).or_default() += count;
+        }
+        for (band, (solid, ores)) in other.bands {
+            let entry = self.bands.entry(band).or_default();
+            entry.0 += solid;
+            for (ore, count) in ores {
+                *entry.1.entry(ore).or_default() += count;
+            }
+        }
+        for (biome, count) in other.biomes {
+            *self.biomes.entry(biome).or_default() += count;
+        }
+        self
+    }
+}
+
+/// Просканировать чанки в радиусе вокруг (x, z)
+pub fn scan_world(center_x: f32, center_z: f32, radius_chunks: i32, world_changes: &HashMap<BlockPos, BlockType>) -> WorldStats {
+    let center_cx = (center_x / CHUNK_SIZE as f32).floor() as i32;
+    let center_cz = (center_z / CHUNK_SIZE as f32).floor() as i32;
+
+    let chunks: Vec<(i32, i32)> = (-radius_chunks..=radius_chunks)
+        .flat_map(|dz| (-radius_chunks..=radius_chunks).map(move |dx| (center_cx + dx, center_cz + dz)))
+        .collect();
+
+    let ore_blocks: Vec<BlockType> = global_registry()
+        .read()
+        .map(|registry| {
+            registry
+                .all_blocks()
+                .filter(|def| def.category == BlockCategory::Ore)
+                .map(|def| def.numeric_id)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let counts = chunks
+        .par_iter()
+        .map(|&(cx, cz)| scan_chunk(cx, cz, world_changes, &ore_blocks))
+        .reduce(Counts::default, Counts::merge);
+
+    build_report([center_x as i32, center_z as i32], radius_chunks, chunks.len(), counts)
+}
+
+fn scan_chunk(cx: i32, cz: i32, world_changes: &HashMap<BlockPos, BlockType>, ore_blocks: &[BlockType]) -> Counts {
+    let chunk = VoxelChunk::new(cx, cz, world_changes);
+    let mut counts = Counts::default();
+
+    for lz in 0..CHUNK_SIZE {
+        for lx in 0..CHUNK_SIZE {
+            let biome = biome_selector().get_biome(cx * CHUNK_SIZE + lx, cz * CHUNK_SIZE + lz);
+            *counts.biomes.entry(biome).or_default() += 1;
+
+            for y in chunk.min_y..=chunk.max_y {
+                let block = chunk.get_local(lx, y, lz);
+                if block == AIR {
+                    continue;
+                }
+                *counts.blocks.entry(block).or_default() += 1;
+
+                if block == WATER {
+                    continue;
+                }
+                let band = (y - MIN_HEIGHT).div_euclid(DEPTH_BAND);
+                let entry = counts.bands.entry(band).or_default();
+                entry.0 += 1;
+                if ore_blocks.contains(&block) {
+                    *entry.1.entry(block).or_default() += 1;
+                }
+            }
+        }
+    }
+    counts
+}
+
+fn build_report(center: [i32; 2], radius_chunks: i32, chunks_scanned: usize, counts: Counts) -> WorldStats {
+    let total_blocks: u64 = counts.blocks.values().sum();
+    let mut blocks: Vec<BlockFrequency> = counts
+        .blocks
+        .iter()
+        .map(|(&id, &count)| BlockFrequency {
+            id,
+            name: get_block_name(id),
+            count,
+            percent: percent(count, total_blocks),
+        })
+        .collect();
+    blocks.sort_by_key(|block| Reverse(block.count));
+
+    let mut ore_bands: Vec<OreBand> = counts
+        .bands
+        .into_iter()
+        .map(|(band, (solid_blocks, ores))| {
+            let mut ores: Vec<(String, u64, f64)> = ores
+                .into_iter()
+                .map(|(ore, count)| (get_block_name(ore), count, count as f64 * 1000.0 / solid_blocks.max(1) as f64))
+                .collect();
+            ores.sort_by_key(|ore| Reverse(ore.1));
+            let min_y = MIN_HEIGHT + band * DEPTH_BAND;
+            OreBand { min_y, max_y: min_y + DEPTH_BAND - 1, solid_blocks, ores }
+        })
+        .collect();
+    ore_bands.sort_by_key(|band| band.min_y);
+
+    let total_columns: u64 = counts.biomes.values().sum();
+    let mut biomes: Vec<BiomeCoverage> = counts
+        .biomes
+        .iter()
+        .map(|(&id, &columns)| BiomeCoverage {
+            name: biome_registry().get(id).name.to_string(),
+            columns,
+            percent: percent(columns, total_columns),
+        })
+        .collect();
+    biomes.sort_by_key(|biome| Reverse(biome.columns));
+
+    WorldStats { center, radius_chunks, chunks_scanned, total_blocks, blocks, ore_bands, biomes }
+}
+
+fn percent(part: u64, total: u64) -> f64 {
+    if total == 0 { 0.0 } else { part as f64 * 100.0 / total as f64 }
+}
+
+impl WorldStats {
+    /// Краткая сводка в журнал
+    pub fn print_summary(&self) {
+        log::info!(
+            "Центр {:?}, радиус {} чанков ({} чанков, {} блоков)",
+            self.center, self.radius_chunks, self.chunks_scanned, self.total_blocks
+        );
+        log::info!("Блоки (топ 15):");
+        for block in self.blocks.iter().take(15) {
+            log::info!("  {:<24} {:>12} {:>7.2}%", block.name, block.count, block.percent);
+        }
+        log::info!("Руды по глубине (на 1000 твёрдых блоков):");
+        for band in self.ore_bands.iter().filter(|band| !band.ores.is_empty()) {
+            let ores: Vec<String> = band.ores.iter().map(|(name, _, density)| format!("{} {:.2}", name, density)).collect();
+            log::info!("  y {:>4}..{:<4} {}", band.min_y, band.max_y, ores.join(", "));
+        }
+        log::info!("Биомы:");
+        for biome in &self.biomes {
+            log::info!("  {:<24} {:>7.2}%", biome.name, biome.percent);
+        }
+    }
+
+    /// Сохранить отчёт в JSON
+    pub fn save_json<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, json).map_err(|e| e.to_string())
+    }
+}
@@ -4,28 +4,34 @@
 // Data-Driven: все данные блоков из JSON
 
 use std::collections::HashMap;
+use std::ops::Range;
 use crate::gpu::terrain::BlockPos;
+use crate::gpu::terrain::cache::column_biome;
 use crate::gpu::blocks::{BlockType, AIR, WATER, DEEPSLATE, GRANITE, DIORITE, ANDESITE, 
-    COAL_ORE, IRON_ORE, GOLD_ORE, DIAMOND_ORE, EMERALD_ORE, COPPER_ORE, SNOW, GRAVEL, GRASS, DIRT, get_face_colors};
-use crate::gpu::terrain::generation::{get_height, CaveParams, is_cave, noise3d, is_solid_3d, hash3d};
+    SNOW, GRAVEL, GRASS, DIRT, get_face_colors, is_solid};
+use crate::gpu::terrain::generation::{get_height, CaveParams, is_cave, cave_biome, noise3d, is_solid_3d, hash3d, ore_config, world_gen_config, terrain_generator};
 use crate::gpu::terrain::mesh::TerrainVertex;
-use crate::gpu::biomes::{biome_selector, BIOME_TAIGA, BIOME_TUNDRA, BIOME_FOREST};
-use crate::gpu::biomes::features::{ChunkWriter, place_basic_tree, place_spruce_tree, TreeType, LeafSubVoxel};
+use crate::gpu::blocks::texture_atlas::{AtlasFace, AtlasTiles, VERTEX_WET, VERTEX_SUBMERGED};
+use crate::gpu::biomes::{biome_selector, biome_registry};
+use crate::gpu::biomes::features::{ChunkWriter, place_basic_tree, place_spruce_tree, place_feature, FeatureKind, TreeType, LeafSubVoxel};
 
-use super::constants::{CHUNK_SIZE, WORLD_HEIGHT, MIN_HEIGHT};
+use super::constants::{CHUNK_SIZE, WORLD_HEIGHT, MIN_HEIGHT, TERRAIN_FLOOR};
+use super::section::{SectionColumn, section_index, section_min_y};
 
 /// Максимальная дополнительная высота для 3D структур над базовой высотой
 const HEIGHT_3D_MARGIN: i32 = 30;
+/// Глубина слоёв породы (strata) под поверхностью
+const STRATA_DEPTH: i32 = 24;
 use super::greedy::{greedy_mesh_layer_into, add_greedy_face_with_block, FaceDir, FaceInfo};
 use super::context::MeshingContext;
 
 /// Генерирует блок процедурно с учётом биома и 3D-шума
-fn generate_block(x: i32, y: i32, z: i32, _terrain_height: i32, cave_ceiling: i32, cave_params: &CaveParams) -> BlockType {
+fn generate_block(x: i32, y: i32, z: i32, terrain_height: i32, cave_ceiling: i32, cave_params: &CaveParams) -> BlockType {
     // 1. Сначала проверяем, есть ли тут вообще земля по 3D-шуму
     // Это создаёт карнизы, арки и сложные формы скал
     if !is_solid_3d(x as f32, y as f32, z as f32) {
-        // Если это ниже уровня моря (0), то вода, иначе воздух
-        if y < 0 {
+        // Если это ниже уровня моря, то вода, иначе воздух
+        if y < world_gen_config().sea_level {
             return WATER;
         }
         return AIR;
@@ -38,15 +44,17 @@ fn generate_block(x: i32, y: i32, z: i32, _terrain_height: i32, cave_ceiling: i3
         }
     }
     
-    // Получаем биом для этой позиции
-    let biome = biome_selector().get_biome_def(x, z);
+    // Получаем биом для этой позиции (на границе - дизеринг по весам смеси,
+    // хеш по столбцу, чтобы поверхность и подповерхность совпадали)
+    let blend = biome_selector().get_biome_blend(x as f32 + 0.5, z as f32 + 0.5);
+    let biome = biome_registry().get(blend.pick(hash3d(x, 0, z)));
     
     // 3. Определение типа блока
     // Проверяем, есть ли блок выше (для определения поверхности)
     let is_surface = !is_solid_3d(x as f32, (y + 1) as f32, z as f32);
     
     // Bedrock слой
-    if y < MIN_HEIGHT + 3 {
+    if y < TERRAIN_FLOOR + 3 {
         return DEEPSLATE;
     }
     
@@ -62,18 +70,25 @@ fn generate_block(x: i32, y: i32, z: i32, _terrain_height: i32, cave_ceiling: i3
             if gravel_noise > 0.5 {
                 return GRAVEL;
             }
-            return biome.deep_block; // Камень для гор
+            return biome.stratum(y).unwrap_or(biome.deep_block); // Камень для гор
         }
         return biome.surface_block; // Трава/Песок
     }
     
     // Чуть ниже поверхности (проверяем 4 блока вверх)
     if !is_solid_3d(x as f32, (y + 4) as f32, z as f32) {
-        return biome.subsurface_block; // Земля
+        return biome.stratum(y).unwrap_or(biome.subsurface_block); // Земля
+    }
+    
+    // Слои породы (бэдлендс) - на всю высоту обрывов
+    if y > terrain_height - STRATA_DEPTH {
+        if let Some(layer) = biome.stratum(y) {
+            return layer;
+        }
     }
     
     // Глубоко внутри - руды и камни
-    if let Some(ore) = generate_ore(x, y, z) {
+    if let Some(ore) = ore_config().ore_at(x, y, z) {
         return ore;
     }
     
@@ -101,59 +116,80 @@ fn generate_stone_variety(x: i32, y: i32, z: i32, base_stone: BlockType) -> Bloc
     }
 }
 
-/// Генерация руд
-fn generate_ore(x: i32, y: i32, z: i32) -> Option<BlockType> {
-    // Разные руды на разных глубинах
-    
-    // Уголь: -20 до 40, частый
-    if y >= -20 && y <= 40 {
-        let coal_noise = noise3d(x as f32 * 0.12 + 50.0, y as f32 * 0.12, z as f32 * 0.12 + 50.0);
-        if coal_noise > 0.75 {
-            return Some(COAL_ORE);
-        }
-    }
-    
-    // Медь: -30 до 30
-    if y >= -30 && y <= 30 {
-        let copper_noise = noise3d(x as f32 * 0.1 + 150.0, y as f32 * 0.1, z as f32 * 0.1 + 150.0);
-        if copper_noise > 0.78 {
-            return Some(COPPER_ORE);
-        }
-    }
-    
-    // Железо: -30 до 20
-    if y >= -30 && y <= 20 {
-        let iron_noise = noise3d(x as f32 * 0.11 + 200.0, y as f32 * 0.11, z as f32 * 0.11 + 200.0);
-        if iron_noise > 0.77 {
-            return Some(IRON_ORE);
-        }
-    }
+/// Облицовка пещер по подземному биому: пол и потолок, свисающие
+/// лозы и сталактиты, растущие сталагмиты. Изменения мира не трогаются
+fn decorate_caves(
+    blocks: &mut SectionColumn,
+    base_x: i32,
+    base_z: i32,
+    surface_heights: &[[i32; CHUNK_SIZE as usize]; CHUNK_SIZE as usize],
+    cave_params: &CaveParams,
+    world_changes: &HashMap<BlockPos, BlockType>,
+) {
+    // Слой deepslate у дна мира не облицовывается
+    let bottom = (TERRAIN_FLOOR + 4).max(cave_params.min_height + 1);
     
-    // Золото: -30 до 0, редкое
-    if y >= -30 && y <= 0 {
-        let gold_noise = noise3d(x as f32 * 0.09 + 300.0, y as f32 * 0.09, z as f32 * 0.09 + 300.0);
-        if gold_noise > 0.82 {
-            return Some(GOLD_ORE);
-        }
-    }
-    
-    // Изумруд: только в горах, -30 до 30
-    if y >= -30 && y <= 30 {
-        let emerald_noise = noise3d(x as f32 * 0.08 + 400.0, y as f32 * 0.08, z as f32 * 0.08 + 400.0);
-        if emerald_noise > 0.88 {
-            return Some(EMERALD_ORE);
+    for lz in 0..CHUNK_SIZE {
+        for lx in 0..CHUNK_SIZE {
+            let world_x = base_x + lx;
+            let world_z = base_z + lz;
+            let ceiling = surface_heights[lz as usize][lx as usize] - cave_params.surface_offset;
+            let natural = |y: i32| !world_changes.contains_key(&BlockPos::new(world_x, y, world_z));
+            // Верх последнего сталагмита: это не пол
+            let mut grown_top = i32::MIN;
+            
+            for y in bottom..ceiling {
+                if blocks.get(lx, y, lz) != AIR {
+                    continue;
+                }
+                let below = blocks.get(lx, y - 1, lz);
+                let above = blocks.get(lx, y + 1, lz);
+                
+                if is_solid(below) && y - 1 != grown_top && natural(y - 1) {
+                    let biome = cave_biome(world_x, y, world_z);
+                    if let Some(block) = biome.floor_block() {
+                        blocks.set(lx, y - 1, lz, block);
+                    }
+                    if let Some(growth) = biome.rising(hash3d(world_x, y, world_z)) {
+                        grown_top = grow_cave_column(blocks, lx, y, lz, 1, growth, &natural);
+                    }
+                }
+                
+                if is_solid(above) && natural(y + 1) {
+                    let biome = cave_biome(world_x, y, world_z);
+                    if let Some(block) = biome.ceiling_block() {
+                        blocks.set(lx, y + 1, lz, block);
+                    }
+                    if let Some(growth) = biome.hanging(hash3d(world_x, y + 7, world_z)) {
+                        grow_cave_column(blocks, lx, y, lz, -1, growth, &natural);
+                    }
+                }
+            }
         }
     }
-    
-    // Алмазы: -30 до -10, очень редкие
-    if y >= -30 && y <= -10 {
-        let diamond_noise = noise3d(x as f32 * 0.07 + 500.0, y as f32 * 0.07, z as f32 * 0.07 + 500.0);
-        if diamond_noise > 0.9 {
-            return Some(DIAMOND_ORE);
+}
+
+/// Вырастить столбик (блок, длина) по воздуху от y в направлении step.
+/// Возвращает высоту последнего поставленного блока
+fn grow_cave_column(
+    blocks: &mut SectionColumn,
+    lx: i32,
+    y: i32,
+    lz: i32,
+    step: i32,
+    (block, length): (BlockType, i32),
+    natural: &impl Fn(i32) -> bool,
+) -> i32 {
+    let mut last = i32::MIN;
+    for i in 0..length {
+        let cy = y + i * step;
+        if blocks.get(lx, cy, lz) != AIR || !natural(cy) {
+            break;
         }
+        blocks.set(lx, cy, lz, block);
+        last = cy;
     }
-    
-    None
+    last
 }
 
 /// Получить цвета для блока
@@ -162,13 +198,26 @@ fn get_block_colors(block: BlockType, _y: f32) -> ([f32; 3], [f32; 3]) {
     get_face_colors(block)
 }
 
-/// Воксельный чанк
+/// Оттенок травы по биому в углах последнего квада
+/// (на greedy-кваде через границу биомов цвет интерполируется)
+#[inline]
+fn tint_last_quad(vertices: &mut [TerrainVertex]) {
+    let start = vertices.len() - 4;
+    for vertex in &mut vertices[start..] {
+        let tint = biome_selector().grass_tint(vertex.position[0], vertex.position[2]);
+        vertex.color = [vertex.color[0] * tint[0], vertex.color[1] * tint[1], vertex.color[2] * tint[2]];
+    }
+}
+
+/// Воксельный чанк (столбец секций 16³)
 pub struct VoxelChunk {
-    blocks: Vec<BlockType>,
+    blocks: SectionColumn,
     pub chunk_x: i32,
     pub chunk_z: i32,
     pub min_y: i32,
     pub max_y: i32,
+    /// Высоты загруженных секций (None - весь столбец)
+    loaded: Option<(i32, i32)>,
 }
 
 /// Результат генерации чанка с субвокселями листвы
@@ -178,10 +227,11 @@ pub struct ChunkGenerationResult {
 }
 
 impl VoxelChunk {
-    /// Создать чанк и вернуть субвоксели листвы
-    pub fn new_with_subvoxels(chunk_x: i32, chunk_z: i32, world_changes: &HashMap<BlockPos, BlockType>) -> ChunkGenerationResult {
-        let total_height = (WORLD_HEIGHT - MIN_HEIGHT) as usize;
-        let mut blocks = vec![AIR; CHUNK_SIZE as usize * CHUNK_SIZE as usize * total_height];
+    /// Шумовой рельеф с биомами, пещерами и деревьями (NoiseGenerator).
+    /// Снаружи чанки создаются через VoxelChunk::new или генератор мира
+    pub(crate) fn generate_noise(chunk_x: i32, chunk_z: i32, world_changes: &HashMap<BlockPos, BlockType>) -> ChunkGenerationResult {
+        // Секции выше поверхности остаются однородным воздухом без аллокаций
+        let mut blocks = SectionColumn::new();
         
         let base_x = chunk_x * CHUNK_SIZE;
         let base_z = chunk_z * CHUNK_SIZE;
@@ -205,7 +255,7 @@ impl VoxelChunk {
                 
                 let gen_max_y = (terrain_height + HEIGHT_3D_MARGIN).min(WORLD_HEIGHT);
                 
-                for y in MIN_HEIGHT..gen_max_y {
+                for y in TERRAIN_FLOOR..gen_max_y {
                     let pos = BlockPos::new(world_x, y, world_z);
                     
                     let block = if let Some(&changed) = world_changes.get(&pos) {
@@ -217,16 +267,33 @@ impl VoxelChunk {
                     if block != AIR {
                         min_y = min_y.min(y);
                         max_y = max_y.max(y);
+                        blocks.set(lx, y, lz, block);
                     }
-                    
-                    let idx = Self::index(lx, y, lz);
-                    blocks[idx] = block;
                 }
             }
         }
         
+        // Постройки игрока ниже дна рельефа и выше генерации
+        for (pos, &block) in world_changes {
+            if block == AIR || pos.x.div_euclid(CHUNK_SIZE) != chunk_x || pos.z.div_euclid(CHUNK_SIZE) != chunk_z {
+                continue;
+            }
+            let (lx, lz) = (pos.x - base_x, pos.z - base_z);
+            let gen_max_y = surface_heights[lz as usize][lx as usize] + HEIGHT_3D_MARGIN;
+            if (TERRAIN_FLOOR..gen_max_y).contains(&pos.y) || !(MIN_HEIGHT..WORLD_HEIGHT).contains(&pos.y) {
+                continue;
+            }
+            min_y = min_y.min(pos.y);
+            max_y = max_y.max(pos.y);
+            blocks.set(lx, pos.y, lz, block);
+        }
+        
+        // --- Этап 1б: Подземные биомы (Cave Pass) ---
+        decorate_caves(&mut blocks, base_x, base_z, &surface_heights, &cave_params, world_changes);
+        
         // --- Этап 2: Декорация (Tree Pass) ---
         let mut tree_positions: Vec<(i32, i32, i32, u8, i32)> = Vec::new();
+        let mut feature_positions: Vec<(i32, i32, i32, FeatureKind, i32)> = Vec::new();
         
         for lz in 0..CHUNK_SIZE {
             for lx in 0..CHUNK_SIZE {
@@ -234,15 +301,10 @@ impl VoxelChunk {
                 let world_z = base_z + lz;
                 let terrain_height = surface_heights[lz as usize][lx as usize];
                 
-                let surface_idx = Self::index(lx, terrain_height, lz);
-                let surface_block = blocks.get(surface_idx).copied().unwrap_or(AIR);
-                if surface_block != GRASS && surface_block != DIRT {
-                    continue;
-                }
-                
-                let biome = biome_selector().get_biome_def(world_x, world_z);
+                let surface_block = blocks.get(lx, terrain_height, lz);
+                let biome = column_biome(world_x, world_z);
                 
-                if biome.tree_density > 0.0001 {
+                if (surface_block == GRASS || surface_block == DIRT) && biome.tree_density > 0.0001 {
                     let rng = hash3d(world_x, terrain_height, world_z);
                     
                     if rng < biome.tree_density {
@@ -250,6 +312,20 @@ impl VoxelChunk {
                         let y = terrain_height + 1;
                         max_y = max_y.max(y + tree_height + 2);
                         tree_positions.push((lx, lz, y, biome.id, tree_height));
+                        continue;
+                    }
+                }
+                
+                // Кактусы и огромные грибы (своё случайное число столбца)
+                let rng = hash3d(world_x, terrain_height + 101, world_z);
+                if let Some(kind) = biome.feature(rng) {
+                    let radius = kind.radius();
+                    let inside = (radius..CHUNK_SIZE - radius).contains(&lx) && (radius..CHUNK_SIZE - radius).contains(&lz);
+                    if inside && kind.fits_on(surface_block) {
+                        let height = kind.height(rng);
+                        let y = terrain_height + 1;
+                        max_y = max_y.max(y + height + 1);
+                        feature_positions.push((lx, lz, y, kind, height));
                     }
                 }
             }
@@ -260,54 +336,113 @@ impl VoxelChunk {
             let mut writer = ChunkWriter::new(&mut blocks, Some(world_changes), base_x, base_z);
             
             for (lx, lz, y, biome_id, tree_height) in tree_positions {
-                match biome_id {
-                    BIOME_TAIGA | BIOME_TUNDRA => {
+                let rng = hash3d(base_x + lx, y, base_z + lz);
+                match biome_registry().get(biome_id).tree_type(rng) {
+                    TreeType::Spruce => {
                         place_spruce_tree(&mut writer, lx, y, lz, tree_height + 1);
                     },
-                    BIOME_FOREST => {
-                        let world_x = base_x + lx;
-                        let world_z = base_z + lz;
-                        let rng = hash3d(world_x, y, world_z);
-                        if ((rng * 100.0) as i32) % 5 == 0 {
-                            place_basic_tree(&mut writer, lx, y, lz, TreeType::Birch, tree_height);
-                        } else {
-                            place_basic_tree(&mut writer, lx, y, lz, TreeType::Oak, tree_height);
-                        }
-                    },
-                    _ => {
-                        place_basic_tree(&mut writer, lx, y, lz, TreeType::Oak, tree_height);
+                    tree_type => {
+                        place_basic_tree(&mut writer, lx, y, lz, tree_type, tree_height);
                     }
                 }
             }
             
+            for (lx, lz, y, kind, height) in feature_positions {
+                place_feature(&mut writer, lx, y, lz, kind, height);
+            }
+            
             writer.take_leaf_subvoxels()
         };
         
+        // Однородные секции (океан, сплошной камень) сворачиваем в один блок
+        blocks.compact();
+        
         ChunkGenerationResult {
-            chunk: Self { blocks, chunk_x, chunk_z, min_y, max_y },
+            chunk: Self { blocks, chunk_x, chunk_z, min_y, max_y, loaded: None },
             leaf_subvoxels,
         }
     }
 
+    /// Чанк текущего генератора мира (субвоксели листвы отбрасываются)
     pub fn new(chunk_x: i32, chunk_z: i32, world_changes: &HashMap<BlockPos, BlockType>) -> Self {
-        // Для обратной совместимости - игнорируем субвоксели
-        Self::new_with_subvoxels(chunk_x, chunk_z, world_changes).chunk
+        terrain_generator().generate_chunk(chunk_x, chunk_z, world_changes).chunk
     }
-    
-    #[inline]
-    fn index(lx: i32, y: i32, lz: i32) -> usize {
-        let ly = y - MIN_HEIGHT;
-        (ly as usize) * (CHUNK_SIZE as usize * CHUNK_SIZE as usize) 
-            + (lz as usize) * (CHUNK_SIZE as usize) 
-            + (lx as usize)
+
+    /// Чанк из функции блока в мировых координатах (плоские и пустые миры).
+    /// Генерируются только высоты из `heights`, изменения мира ложатся поверх
+    pub fn from_fn(
+        chunk_x: i32,
+        chunk_z: i32,
+        heights: Range<i32>,
+        world_changes: &HashMap<BlockPos, BlockType>,
+        block_at: impl Fn(i32, i32, i32) -> BlockType,
+    ) -> Self {
+        let mut blocks = SectionColumn::new();
+        let base_x = chunk_x * CHUNK_SIZE;
+        let base_z = chunk_z * CHUNK_SIZE;
+        let heights = heights.start.max(MIN_HEIGHT)..heights.end.min(WORLD_HEIGHT);
+
+        let mut min_y = WORLD_HEIGHT;
+        let mut max_y = MIN_HEIGHT;
+
+        for lz in 0..CHUNK_SIZE {
+            for lx in 0..CHUNK_SIZE {
+                for y in heights.clone() {
+                    let block = block_at(base_x + lx, y, base_z + lz);
+                    if block != AIR {
+                        min_y = min_y.min(y);
+                        max_y = max_y.max(y);
+                        blocks.set(lx, y, lz, block);
+                    }
+                }
+            }
+        }
+
+        // Постройки игрока могут быть на любой высоте
+        for (pos, &block) in world_changes {
+            let in_chunk = pos.x.div_euclid(CHUNK_SIZE) == chunk_x && pos.z.div_euclid(CHUNK_SIZE) == chunk_z;
+            if !in_chunk || !(MIN_HEIGHT..WORLD_HEIGHT).contains(&pos.y) {
+                continue;
+            }
+            if block != AIR {
+                min_y = min_y.min(pos.y);
+                max_y = max_y.max(pos.y);
+            }
+            blocks.set(pos.x - base_x, pos.y, pos.z - base_z, block);
+        }
+
+        blocks.compact();
+        Self { blocks, chunk_x, chunk_z, min_y, max_y, loaded: None }
+    }
+
+    /// Оставить только секции, задевающие высоты [min_y, max_y];
+    /// остальные выгружаются и читаются как воздух
+    pub fn retain_heights(&mut self, min_y: i32, max_y: i32) {
+        let min_y = min_y.max(MIN_HEIGHT);
+        let max_y = max_y.min(WORLD_HEIGHT - 1);
+        if min_y > max_y {
+            self.blocks = SectionColumn::new();
+            self.loaded = Some((min_y, max_y));
+            return;
+        }
+        let (low, high) = (section_index(min_y), section_index(max_y));
+        self.blocks.retain_sections(low, high);
+        self.loaded = Some((section_min_y(low), section_min_y(high + 1) - 1));
+    }
+
+    /// Загружены ли все секции высот [min_y, max_y]
+    pub fn covers_heights(&self, min_y: i32, max_y: i32) -> bool {
+        self.loaded.is_none_or(|(low, high)| {
+            low <= min_y.max(MIN_HEIGHT) && max_y.min(WORLD_HEIGHT - 1) <= high
+        })
     }
     
     #[inline]
     pub fn get_local(&self, lx: i32, y: i32, lz: i32) -> BlockType {
-        if lx < 0 || lx >= CHUNK_SIZE || lz < 0 || lz >= CHUNK_SIZE || y < MIN_HEIGHT || y >= WORLD_HEIGHT {
+        if lx < 0 || lx >= CHUNK_SIZE || lz < 0 || lz >= CHUNK_SIZE {
             return AIR;
         }
-        self.blocks[Self::index(lx, y, lz)]
+        self.blocks.get(lx, y, lz)
     }
 
     
@@ -316,40 +451,64 @@ impl VoxelChunk {
         &self, 
         neighbors: &ChunkNeighbors, 
         ctx: &mut MeshingContext
+    ) -> (Vec<TerrainVertex>, Vec<u32>) {
+        self.generate_mesh_in_range_with_context(neighbors, self.min_y, self.max_y, ctx)
+    }
+    
+    /// Меш только блоков с высотой в [min_y, max_y] (вертикальная
+    /// дальность): грани на краях полосы не строятся - за ними блоки
+    /// вне дальности, которые не рисуются
+    pub fn generate_mesh_in_range_with_context(
+        &self,
+        neighbors: &ChunkNeighbors,
+        min_y: i32,
+        max_y: i32,
+        ctx: &mut MeshingContext,
     ) -> (Vec<TerrainVertex>, Vec<u32>) {
         ctx.clear_output();
+        ctx.refresh_tiles();
+        
+        let min_y = min_y.max(self.min_y);
+        let max_y = max_y.min(self.max_y);
+        if min_y > max_y {
+            return ctx.take_results();
+        }
         
         let base_x = self.chunk_x * CHUNK_SIZE;
         let base_z = self.chunk_z * CHUNK_SIZE;
         let chunk_size = CHUNK_SIZE as usize;
         
-        self.generate_y_faces(neighbors, ctx, base_x, base_z, chunk_size);
-        self.generate_x_faces(neighbors, ctx, base_x, base_z, chunk_size);
-        self.generate_z_faces(neighbors, ctx, base_x, base_z, chunk_size);
+        self.generate_y_faces(neighbors, ctx, base_x, base_z, chunk_size, (min_y, max_y));
+        self.generate_x_faces(neighbors, ctx, base_x, base_z, chunk_size, (min_y, max_y));
+        self.generate_z_faces(neighbors, ctx, base_x, base_z, chunk_size, (min_y, max_y));
         
         ctx.take_results()
     }
     
     #[inline]
-    fn generate_y_faces(&self, neighbors: &ChunkNeighbors, ctx: &mut MeshingContext, base_x: i32, base_z: i32, chunk_size: usize) {
-        for y in self.min_y..=self.max_y + 1 {
+    fn generate_y_faces(&self, neighbors: &ChunkNeighbors, ctx: &mut MeshingContext, base_x: i32, base_z: i32, chunk_size: usize, (min_y, max_y): (i32, i32)) {
+        for y in min_y..=max_y + 1 {
             ctx.clear_y_masks();
             
             for lz in 0..CHUNK_SIZE {
                 for lx in 0..CHUNK_SIZE {
                     let idx = (lz as usize) * chunk_size + (lx as usize);
                     
-                    if y > self.min_y {
+                    if y > min_y {
                         let block = self.get_local(lx, y - 1, lz);
-                        if block != AIR && block != WATER && self.is_face_visible(lx, y, lz, neighbors) {
-                            ctx.y_buffers.mask_pos[idx] = Some(FaceInfo::new(block, true));
+                        if block != AIR {
+                            if let Some(flags) = self.visible_face(block, lx, y, lz, neighbors, &ctx.tiles) {
+                                ctx.y_buffers.mask_pos[idx] = Some(FaceInfo::new(block, true, flags));
+                            }
                         }
                     }
                     
-                    if y <= self.max_y {
+                    if y <= max_y {
                         let block = self.get_local(lx, y, lz);
-                        if block != AIR && block != WATER && self.is_face_visible(lx, y - 1, lz, neighbors) {
-                            ctx.y_buffers.mask_neg[idx] = Some(FaceInfo::new(block, false));
+                        if block != AIR {
+                            if let Some(flags) = self.visible_face(block, lx, y - 1, lz, neighbors, &ctx.tiles) {
+                                ctx.y_buffers.mask_neg[idx] = Some(FaceInfo::new(block, false, flags));
+                            }
                         }
                     }
                 }
@@ -358,42 +517,49 @@ impl VoxelChunk {
             greedy_mesh_layer_into(&ctx.y_buffers.mask_pos[..chunk_size * chunk_size], &mut ctx.y_buffers.visited[..chunk_size * chunk_size], chunk_size, chunk_size, &mut ctx.greedy_results);
             for &(u, v, w, h, face) in &ctx.greedy_results {
                 let (top_color, _) = get_block_colors(face.block_type, y as f32);
-                add_greedy_face_with_block(&mut ctx.vertices, &mut ctx.indices, (base_x + u as i32) as f32, (y - 1) as f32, (base_z + v as i32) as f32, w as f32, h as f32, [0.0, 1.0, 0.0], top_color, FaceDir::PosY, face.block_type);
+                add_greedy_face_with_block(&mut ctx.vertices, &mut ctx.indices, (base_x + u as i32) as f32, (y - 1) as f32, (base_z + v as i32) as f32, w as f32, h as f32, [0.0, 1.0, 0.0], top_color, FaceDir::PosY, ctx.tiles.vertex_id(face.block_type, AtlasFace::Top) | face.flags);
+                if face.block_type == GRASS {
+                    tint_last_quad(&mut ctx.vertices);
+                }
             }
             
             ctx.y_buffers.clear_visited(chunk_size * chunk_size);
             greedy_mesh_layer_into(&ctx.y_buffers.mask_neg[..chunk_size * chunk_size], &mut ctx.y_buffers.visited[..chunk_size * chunk_size], chunk_size, chunk_size, &mut ctx.greedy_results);
             for &(u, v, w, h, face) in &ctx.greedy_results {
                 let (_, side_color) = get_block_colors(face.block_type, y as f32);
-                add_greedy_face_with_block(&mut ctx.vertices, &mut ctx.indices, (base_x + u as i32) as f32, y as f32, (base_z + v as i32) as f32, w as f32, h as f32, [0.0, -1.0, 0.0], side_color, FaceDir::NegY, face.block_type);
+                add_greedy_face_with_block(&mut ctx.vertices, &mut ctx.indices, (base_x + u as i32) as f32, y as f32, (base_z + v as i32) as f32, w as f32, h as f32, [0.0, -1.0, 0.0], side_color, FaceDir::NegY, ctx.tiles.vertex_id(face.block_type, AtlasFace::Bottom) | face.flags);
             }
         }
     }
 
     
     #[inline]
-    fn generate_x_faces(&self, neighbors: &ChunkNeighbors, ctx: &mut MeshingContext, base_x: i32, base_z: i32, chunk_size: usize) {
-        let height_range = (self.max_y - self.min_y + 1) as usize;
+    fn generate_x_faces(&self, neighbors: &ChunkNeighbors, ctx: &mut MeshingContext, base_x: i32, base_z: i32, chunk_size: usize, (min_y, max_y): (i32, i32)) {
+        let height_range = (max_y - min_y + 1) as usize;
         
         for lx in 0..=CHUNK_SIZE {
             ctx.clear_x_masks(height_range);
             
-            for y in self.min_y..=self.max_y {
+            for y in min_y..=max_y {
                 for lz in 0..CHUNK_SIZE {
-                    let y_idx = (y - self.min_y) as usize;
+                    let y_idx = (y - min_y) as usize;
                     let idx = y_idx * chunk_size + (lz as usize);
                     
                     if lx > 0 {
                         let block = self.get_local(lx - 1, y, lz);
-                        if block != AIR && block != WATER && self.is_face_visible(lx, y, lz, neighbors) {
-                            ctx.x_buffers.mask_pos[idx] = Some(FaceInfo::new(block, false));
+                        if block != AIR {
+                            if let Some(flags) = self.visible_face(block, lx, y, lz, neighbors, &ctx.tiles) {
+                                ctx.x_buffers.mask_pos[idx] = Some(FaceInfo::new(block, false, flags));
+                            }
                         }
                     }
                     
                     if lx < CHUNK_SIZE {
                         let block = self.get_local(lx, y, lz);
-                        if block != AIR && block != WATER && self.is_face_visible(lx - 1, y, lz, neighbors) {
-                            ctx.x_buffers.mask_neg[idx] = Some(FaceInfo::new(block, false));
+                        if block != AIR {
+                            if let Some(flags) = self.visible_face(block, lx - 1, y, lz, neighbors, &ctx.tiles) {
+                                ctx.x_buffers.mask_neg[idx] = Some(FaceInfo::new(block, false, flags));
+                            }
                         }
                     }
                 }
@@ -403,42 +569,46 @@ impl VoxelChunk {
             
             greedy_mesh_layer_into(&ctx.x_buffers.mask_pos[..mask_size], &mut ctx.x_buffers.visited[..mask_size], chunk_size, height_range, &mut ctx.greedy_results);
             for &(u, v, w, h, face) in &ctx.greedy_results {
-                let (_, side_color) = get_block_colors(face.block_type, (self.min_y + v as i32) as f32);
-                add_greedy_face_with_block(&mut ctx.vertices, &mut ctx.indices, (base_x + lx - 1) as f32, (self.min_y + v as i32) as f32, (base_z + u as i32) as f32, w as f32, h as f32, [1.0, 0.0, 0.0], side_color, FaceDir::PosX, face.block_type);
+                let (_, side_color) = get_block_colors(face.block_type, (min_y + v as i32) as f32);
+                add_greedy_face_with_block(&mut ctx.vertices, &mut ctx.indices, (base_x + lx - 1) as f32, (min_y + v as i32) as f32, (base_z + u as i32) as f32, w as f32, h as f32, [1.0, 0.0, 0.0], side_color, FaceDir::PosX, ctx.tiles.vertex_id(face.block_type, AtlasFace::Side) | face.flags);
             }
             
             ctx.x_buffers.clear_visited(mask_size);
             greedy_mesh_layer_into(&ctx.x_buffers.mask_neg[..mask_size], &mut ctx.x_buffers.visited[..mask_size], chunk_size, height_range, &mut ctx.greedy_results);
             for &(u, v, w, h, face) in &ctx.greedy_results {
-                let (_, side_color) = get_block_colors(face.block_type, (self.min_y + v as i32) as f32);
-                add_greedy_face_with_block(&mut ctx.vertices, &mut ctx.indices, (base_x + lx) as f32, (self.min_y + v as i32) as f32, (base_z + u as i32) as f32, w as f32, h as f32, [-1.0, 0.0, 0.0], side_color, FaceDir::NegX, face.block_type);
+                let (_, side_color) = get_block_colors(face.block_type, (min_y + v as i32) as f32);
+                add_greedy_face_with_block(&mut ctx.vertices, &mut ctx.indices, (base_x + lx) as f32, (min_y + v as i32) as f32, (base_z + u as i32) as f32, w as f32, h as f32, [-1.0, 0.0, 0.0], side_color, FaceDir::NegX, ctx.tiles.vertex_id(face.block_type, AtlasFace::Side) | face.flags);
             }
         }
     }
     
     #[inline]
-    fn generate_z_faces(&self, neighbors: &ChunkNeighbors, ctx: &mut MeshingContext, base_x: i32, base_z: i32, chunk_size: usize) {
-        let height_range = (self.max_y - self.min_y + 1) as usize;
+    fn generate_z_faces(&self, neighbors: &ChunkNeighbors, ctx: &mut MeshingContext, base_x: i32, base_z: i32, chunk_size: usize, (min_y, max_y): (i32, i32)) {
+        let height_range = (max_y - min_y + 1) as usize;
         
         for lz in 0..=CHUNK_SIZE {
             ctx.clear_z_masks(height_range);
             
-            for y in self.min_y..=self.max_y {
+            for y in min_y..=max_y {
                 for lx in 0..CHUNK_SIZE {
-                    let y_idx = (y - self.min_y) as usize;
+                    let y_idx = (y - min_y) as usize;
                     let idx = y_idx * chunk_size + (lx as usize);
                     
                     if lz > 0 {
                         let block = self.get_local(lx, y, lz - 1);
-                        if block != AIR && block != WATER && self.is_face_visible(lx, y, lz, neighbors) {
-                            ctx.z_buffers.mask_pos[idx] = Some(FaceInfo::new(block, false));
+                        if block != AIR {
+                            if let Some(flags) = self.visible_face(block, lx, y, lz, neighbors, &ctx.tiles) {
+                                ctx.z_buffers.mask_pos[idx] = Some(FaceInfo::new(block, false, flags));
+                            }
                         }
                     }
                     
                     if lz < CHUNK_SIZE {
                         let block = self.get_local(lx, y, lz);
-                        if block != AIR && block != WATER && self.is_face_visible(lx, y, lz - 1, neighbors) {
-                            ctx.z_buffers.mask_neg[idx] = Some(FaceInfo::new(block, false));
+                        if block != AIR {
+                            if let Some(flags) = self.visible_face(block, lx, y, lz - 1, neighbors, &ctx.tiles) {
+                                ctx.z_buffers.mask_neg[idx] = Some(FaceInfo::new(block, false, flags));
+                            }
                         }
                     }
                 }
@@ -448,15 +618,15 @@ impl VoxelChunk {
             
             greedy_mesh_layer_into(&ctx.z_buffers.mask_pos[..mask_size], &mut ctx.z_buffers.visited[..mask_size], chunk_size, height_range, &mut ctx.greedy_results);
             for &(u, v, w, h, face) in &ctx.greedy_results {
-                let (_, side_color) = get_block_colors(face.block_type, (self.min_y + v as i32) as f32);
-                add_greedy_face_with_block(&mut ctx.vertices, &mut ctx.indices, (base_x + u as i32) as f32, (self.min_y + v as i32) as f32, (base_z + lz - 1) as f32, w as f32, h as f32, [0.0, 0.0, 1.0], side_color, FaceDir::PosZ, face.block_type);
+                let (_, side_color) = get_block_colors(face.block_type, (min_y + v as i32) as f32);
+                add_greedy_face_with_block(&mut ctx.vertices, &mut ctx.indices, (base_x + u as i32) as f32, (min_y + v as i32) as f32, (base_z + lz - 1) as f32, w as f32, h as f32, [0.0, 0.0, 1.0], side_color, FaceDir::PosZ, ctx.tiles.vertex_id(face.block_type, AtlasFace::Side) | face.flags);
             }
             
             ctx.z_buffers.clear_visited(mask_size);
             greedy_mesh_layer_into(&ctx.z_buffers.mask_neg[..mask_size], &mut ctx.z_buffers.visited[..mask_size], chunk_size, height_range, &mut ctx.greedy_results);
             for &(u, v, w, h, face) in &ctx.greedy_results {
-                let (_, side_color) = get_block_colors(face.block_type, (self.min_y + v as i32) as f32);
-                add_greedy_face_with_block(&mut ctx.vertices, &mut ctx.indices, (base_x + u as i32) as f32, (self.min_y + v as i32) as f32, (base_z + lz) as f32, w as f32, h as f32, [0.0, 0.0, -1.0], side_color, FaceDir::NegZ, face.block_type);
+                let (_, side_color) = get_block_colors(face.block_type, (min_y + v as i32) as f32);
+                add_greedy_face_with_block(&mut ctx.vertices, &mut ctx.indices, (base_x + u as i32) as f32, (min_y + v as i32) as f32, (base_z + lz) as f32, w as f32, h as f32, [0.0, 0.0, -1.0], side_color, FaceDir::NegZ, ctx.tiles.vertex_id(face.block_type, AtlasFace::Side) | face.flags);
             }
         }
     }
@@ -470,12 +640,17 @@ impl VoxelChunk {
     
     pub fn generate_mesh_section_with_context(&self, neighbors: &ChunkNeighbors, section_min_y: i32, section_max_y: i32, ctx: &mut MeshingContext) -> (Vec<TerrainVertex>, Vec<u32>) {
         ctx.clear_output();
+        ctx.refresh_tiles();
         let base_x = self.chunk_x * CHUNK_SIZE;
         let base_z = self.chunk_z * CHUNK_SIZE;
         let chunk_size = CHUNK_SIZE as usize;
         let actual_min = section_min_y.max(self.min_y);
         let actual_max = section_max_y.min(self.max_y);
         if actual_min > actual_max { return ctx.take_results(); }
+        // Пустая секция граней не даёт (грани принадлежат твёрдым блокам)
+        if self.blocks.is_section_empty(section_index(section_min_y)) {
+            return ctx.take_results();
+        }
         
         // Simplified section mesh generation
         for y in actual_min..=actual_max + 1 {
@@ -485,14 +660,18 @@ impl VoxelChunk {
                     let idx = (lz as usize) * chunk_size + (lx as usize);
                     if y > actual_min && y - 1 <= actual_max {
                         let block = self.get_local(lx, y - 1, lz);
-                        if block != AIR && block != WATER && self.is_face_visible(lx, y, lz, neighbors) {
-                            ctx.y_buffers.mask_pos[idx] = Some(FaceInfo::new(block, true));
+                        if block != AIR {
+                            if let Some(flags) = self.visible_face(block, lx, y, lz, neighbors, &ctx.tiles) {
+                                ctx.y_buffers.mask_pos[idx] = Some(FaceInfo::new(block, true, flags));
+                            }
                         }
                     }
                     if y >= actual_min && y <= actual_max {
                         let block = self.get_local(lx, y, lz);
-                        if block != AIR && block != WATER && self.is_face_visible(lx, y - 1, lz, neighbors) {
-                            ctx.y_buffers.mask_neg[idx] = Some(FaceInfo::new(block, false));
+                        if block != AIR {
+                            if let Some(flags) = self.visible_face(block, lx, y - 1, lz, neighbors, &ctx.tiles) {
+                                ctx.y_buffers.mask_neg[idx] = Some(FaceInfo::new(block, false, flags));
+                            }
                         }
                     }
                 }
@@ -500,13 +679,16 @@ impl VoxelChunk {
             greedy_mesh_layer_into(&ctx.y_buffers.mask_pos[..chunk_size * chunk_size], &mut ctx.y_buffers.visited[..chunk_size * chunk_size], chunk_size, chunk_size, &mut ctx.greedy_results);
             for &(u, v, w, h, face) in &ctx.greedy_results {
                 let (top_color, _) = get_block_colors(face.block_type, y as f32);
-                add_greedy_face_with_block(&mut ctx.vertices, &mut ctx.indices, (base_x + u as i32) as f32, (y - 1) as f32, (base_z + v as i32) as f32, w as f32, h as f32, [0.0, 1.0, 0.0], top_color, FaceDir::PosY, face.block_type);
+                add_greedy_face_with_block(&mut ctx.vertices, &mut ctx.indices, (base_x + u as i32) as f32, (y - 1) as f32, (base_z + v as i32) as f32, w as f32, h as f32, [0.0, 1.0, 0.0], top_color, FaceDir::PosY, ctx.tiles.vertex_id(face.block_type, AtlasFace::Top) | face.flags);
+                if face.block_type == GRASS {
+                    tint_last_quad(&mut ctx.vertices);
+                }
             }
             ctx.y_buffers.clear_visited(chunk_size * chunk_size);
             greedy_mesh_layer_into(&ctx.y_buffers.mask_neg[..chunk_size * chunk_size], &mut ctx.y_buffers.visited[..chunk_size * chunk_size], chunk_size, chunk_size, &mut ctx.greedy_results);
             for &(u, v, w, h, face) in &ctx.greedy_results {
                 let (_, side_color) = get_block_colors(face.block_type, y as f32);
-                add_greedy_face_with_block(&mut ctx.vertices, &mut ctx.indices, (base_x + u as i32) as f32, y as f32, (base_z + v as i32) as f32, w as f32, h as f32, [0.0, -1.0, 0.0], side_color, FaceDir::NegY, face.block_type);
+                add_greedy_face_with_block(&mut ctx.vertices, &mut ctx.indices, (base_x + u as i32) as f32, y as f32, (base_z + v as i32) as f32, w as f32, h as f32, [0.0, -1.0, 0.0], side_color, FaceDir::NegY, ctx.tiles.vertex_id(face.block_type, AtlasFace::Bottom) | face.flags);
             }
         }
         ctx.take_results()
@@ -517,17 +699,49 @@ impl VoxelChunk {
         self.generate_mesh_section_with_context(neighbors, min_y, max_y, &mut ctx)
     }
     
+    /// Флаги вершин грани блока `block`, смотрящей в клетку (lx, y, lz),
+    /// или None, если грань не видна. Видна, когда за ней воздух или другой
+    /// полупрозрачный блок (камень под водой, вода за стеклом); соседние
+    /// одинаковые полупрозрачные блоки сливаются
+    #[inline]
+    fn visible_face(&self, block: BlockType, lx: i32, y: i32, lz: i32, neighbors: &ChunkNeighbors, tiles: &AtlasTiles) -> Option<u32> {
+        if y < MIN_HEIGHT { return None; }
+        if y >= WORLD_HEIGHT { return Some(0); }
+        // Незагруженный сосед: грань рисуем, про воду за ней не знаем
+        let Some(neighbor) = self.block_near(lx, y, lz, neighbors) else { return Some(0) };
+        if neighbor == AIR {
+            let wet = block != WATER && self.near_water(lx, y, lz, neighbors);
+            return Some(if wet { VERTEX_WET } else { 0 });
+        }
+        if neighbor != block && tiles.is_translucent(neighbor) {
+            return Some(if neighbor == WATER { VERTEX_SUBMERGED } else { 0 });
+        }
+        None
+    }
+
+    /// Есть ли вода у клетки воздуха (lx, y, lz): под ней или по сторонам
+    /// на её уровне и на блок ниже. Такие грани - полоса у кромки воды
+    fn near_water(&self, lx: i32, y: i32, lz: i32, neighbors: &ChunkNeighbors) -> bool {
+        let water = |x: i32, y: i32, z: i32| self.block_near(x, y, z, neighbors) == Some(WATER);
+        water(lx, y - 1, lz)
+            || [(1, 0), (-1, 0), (0, 1), (0, -1)]
+                .iter()
+                .any(|&(dx, dz)| water(lx + dx, y, lz + dz) || water(lx + dx, y - 1, lz + dz))
+    }
+
+    /// Блок в клетке (lx, y, lz), в том числе в соседнем чанке по одной оси.
+    /// None - сосед не загружен или клетка за углом чанка (по диагонали)
     #[inline]
-    fn is_face_visible(&self, lx: i32, y: i32, lz: i32, neighbors: &ChunkNeighbors) -> bool {
-        if lx >= 0 && lx < CHUNK_SIZE && lz >= 0 && lz < CHUNK_SIZE {
-            if y < MIN_HEIGHT || y >= WORLD_HEIGHT { return y >= WORLD_HEIGHT; }
-            return self.get_local(lx, y, lz) == AIR;
+    fn block_near(&self, lx: i32, y: i32, lz: i32, neighbors: &ChunkNeighbors) -> Option<BlockType> {
+        let inside = |c: i32| (0..CHUNK_SIZE).contains(&c);
+        match (inside(lx), inside(lz)) {
+            (true, true) => Some(self.get_local(lx, y, lz)),
+            (false, true) if lx < 0 => neighbors.neg_x.map(|c| c.get_local(CHUNK_SIZE - 1, y, lz)),
+            (false, true) => neighbors.pos_x.map(|c| c.get_local(0, y, lz)),
+            (true, false) if lz < 0 => neighbors.neg_z.map(|c| c.get_local(lx, y, CHUNK_SIZE - 1)),
+            (true, false) => neighbors.pos_z.map(|c| c.get_local(lx, y, 0)),
+            (false, false) => None,
         }
-        if lx < 0 { if let Some(neg_x) = neighbors.neg_x { return neg_x.get_local(CHUNK_SIZE - 1, y, lz) == AIR; } }
-        else if lx >= CHUNK_SIZE { if let Some(pos_x) = neighbors.pos_x { return pos_x.get_local(0, y, lz) == AIR; } }
-        if lz < 0 { if let Some(neg_z) = neighbors.neg_z { return neg_z.get_local(lx, y, CHUNK_SIZE - 1) == AIR; } }
-        else if lz >= CHUNK_SIZE { if let Some(pos_z) = neighbors.pos_z { return pos_z.get_local(lx, y, 0) == AIR; } }
-        true
     }
 }
 
@@ -537,3 +751,130 @@ pub struct ChunkNeighbors<'a> {
     pub pos_z: Option<&'a VoxelChunk>,
     pub neg_z: Option<&'a VoxelChunk>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::blocks::STONE;
+    use crate::gpu::terrain::mesh::validate::{assert_mesh_matches, fuzz_cases, FuzzVolume, Quad};
+
+    /// Чанк из случайного объёма (x, y, z) в углу (0, 0), без соседей
+    fn chunk_from_volume(volume: &FuzzVolume) -> VoxelChunk {
+        let mut blocks = SectionColumn::new();
+        for z in 0..volume.size[2] {
+            for y in 0..volume.size[1] {
+                for x in 0..volume.size[0] {
+                    blocks.set(x, y, z, volume.get(x, y, z));
+                }
+            }
+        }
+        VoxelChunk { blocks, chunk_x: 0, chunk_z: 0, min_y: 0, max_y: volume.size[1] - 1, loaded: None }
+    }
+
+    fn mesh_quads(chunk: &VoxelChunk) -> Vec<Quad> {
+        let neighbors = ChunkNeighbors { pos_x: None, neg_x: None, pos_z: None, neg_z: None };
+        let (vertices, indices) = chunk.generate_mesh(&neighbors);
+        assert_eq!(indices.len(), vertices.len() / 4 * 6);
+
+        vertices
+            .chunks_exact(4)
+            .map(|quad| Quad {
+                corners: std::array::from_fn(|i| quad[i].position.map(|p| p.round() as i32)),
+                normal: quad[0].normal.map(|n| n.round() as i32),
+            })
+            .collect()
+    }
+
+    fn check(volume: &FuzzVolume, context: &str) {
+        let quads = mesh_quads(&chunk_from_volume(volume));
+        assert_mesh_matches(&quads, &volume.naive_faces(), context);
+    }
+
+    #[test]
+    fn fuzz_terrain_greedy_matches_naive() {
+        for case in 0..fuzz_cases() {
+            let fill = [0.1, 0.5, 0.9][case as usize % 3];
+            let volume = FuzzVolume::random(case, [CHUNK_SIZE, 12, CHUNK_SIZE], fill, &[STONE, DIRT, GRASS]);
+            check(&volume, &format!("terrain case {} fill {}", case, fill));
+        }
+    }
+
+    #[test]
+    fn terrain_greedy_mirrored_per_axis() {
+        for case in 0..fuzz_cases() / 4 {
+            let volume = FuzzVolume::random(1000 + case, [CHUNK_SIZE, 10, CHUNK_SIZE], 0.4, &[STONE, DIRT]);
+            for axis in 0..3 {
+                check(&volume.mirrored(axis), &format!("terrain case {} mirrored axis {}", case, axis));
+            }
+        }
+    }
+
+    #[test]
+    fn solid_box_is_six_quads() {
+        let volume = FuzzVolume::random(7, [CHUNK_SIZE, 8, CHUNK_SIZE], 1.0, &[STONE]);
+        let quads = mesh_quads(&chunk_from_volume(&volume));
+        assert_eq!(quads.len(), 6);
+        assert_mesh_matches(&quads, &volume.naive_faces(), "solid box");
+    }
+
+    #[test]
+    fn faces_near_water_carry_flags() {
+        // Камень y 0..=2, на y = 2 при x >= 8 вместо камня вода
+        let mut blocks = SectionColumn::new();
+        for z in 0..CHUNK_SIZE {
+            for x in 0..CHUNK_SIZE {
+                for y in 0..3 {
+                    blocks.set(x, y, z, if y == 2 && x >= 8 { WATER } else { STONE });
+                }
+            }
+        }
+        let chunk = VoxelChunk { blocks, chunk_x: 0, chunk_z: 0, min_y: 0, max_y: 2, loaded: None };
+        let neighbors = ChunkNeighbors { pos_x: None, neg_x: None, pos_z: None, neg_z: None };
+        let (vertices, _) = chunk.generate_mesh(&neighbors);
+
+        let mut seen = (false, false);
+        for quad in vertices.chunks_exact(4) {
+            let v = &quad[0];
+            let flags = v.block_id & (VERTEX_WET | VERTEX_SUBMERGED);
+            let min_x = quad.iter().map(|q| q.position[0].round() as i32).min().unwrap();
+            let top_y = quad.iter().map(|q| q.position[1].round() as i32).max().unwrap();
+            if v.block_type() == WATER {
+                assert_eq!(flags, 0, "грани самой воды без флагов");
+            } else if v.normal[1] > 0.5 && top_y == 3 {
+                // Верх камня у кромки мокрый, дальше от воды - сухой
+                let expected = if min_x == 7 { VERTEX_WET } else { 0 };
+                assert_eq!(flags, expected, "верх камня от x = {}", min_x);
+                seen.0 |= flags == VERTEX_WET;
+            } else if v.normal[1] > 0.5 && top_y == 2 {
+                assert_eq!(flags, VERTEX_SUBMERGED, "дно под водой");
+                seen.1 = true;
+            } else if v.normal[0] > 0.5 && top_y == 3 && min_x == 8 {
+                assert_eq!(flags, VERTEX_SUBMERGED, "стенка камня в воду");
+            }
+        }
+        assert_eq!(seen, (true, true));
+    }
+
+    #[test]
+    fn builds_beyond_terrain_and_band_eviction() {
+        let changes = HashMap::from([
+            (BlockPos::new(3, -400, 5), STONE),
+            (BlockPos::new(3, 900, 5), DIRT),
+            (BlockPos::new(3, WORLD_HEIGHT, 5), DIRT), // выше предела
+        ]);
+        let mut chunk = VoxelChunk::from_fn(0, 0, 0..4, &changes, |_, _, _| STONE);
+        assert_eq!((chunk.min_y, chunk.max_y), (-400, 900));
+        assert_eq!(chunk.get_local(3, -400, 5), STONE);
+        assert_eq!(chunk.get_local(3, 900, 5), DIRT);
+        assert_eq!(chunk.get_local(3, WORLD_HEIGHT, 5), AIR);
+        assert!(chunk.covers_heights(i32::MIN, i32::MAX));
+
+        // Полоса у поверхности: стройки далеко вверху и внизу выгружены
+        chunk.retain_heights(-1, 20);
+        assert!(chunk.covers_heights(-1, 20));
+        assert!(!chunk.covers_heights(-1, 900));
+        assert_eq!(chunk.get_local(0, 3, 0), STONE);
+        assert_eq!(chunk.get_local(3, 900, 5), AIR);
+        assert_eq!(chunk.get_local(3, -400, 5), AIR);
+    }
+}
@@ -4,7 +4,21 @@
 
 /// Размер чанка в блоках
 pub const CHUNK_SIZE: i32 = 16;
-/// Высота мира
-pub const WORLD_HEIGHT: i32 = 128;
-/// Минимальная высота (bedrock)
-pub const MIN_HEIGHT: i32 = -32;
+/// Высота мира (предел строительства)
+pub const WORLD_HEIGHT: i32 = 1024;
+/// Минимальная высота мира
+pub const MIN_HEIGHT: i32 = -512;
+/// Дно рельефа: генераторы строят мир от этой высоты (bedrock над ней),
+/// ниже до MIN_HEIGHT - пустота
+pub const TERRAIN_FLOOR: i32 = -32;
+/// Высота секции столбца (секции 16³ хранятся независимо)
+pub const SECTION_SIZE: i32 = 16;
+/// Количество секций в столбце
+pub const SECTION_COUNT: usize = ((WORLD_HEIGHT - MIN_HEIGHT) / SECTION_SIZE) as usize;
+/// Полная высота столбца
+pub const COLUMN_HEIGHT: usize = (WORLD_HEIGHT - MIN_HEIGHT) as usize;
+
+// Пределы высоты должны делиться на секции
+const _: () = assert!((WORLD_HEIGHT - MIN_HEIGHT) % SECTION_SIZE == 0 && MIN_HEIGHT % SECTION_SIZE == 0);
+// Y блока субвокселей хранится в u16 от MIN_HEIGHT
+const _: () = assert!(WORLD_HEIGHT - MIN_HEIGHT <= u16::MAX as i32 + 1);
@@ -7,14 +7,15 @@
 // затем только очищается через clear() сохраняя capacity.
 
 use crate::gpu::terrain::mesh::TerrainVertex;
-use super::constants::CHUNK_SIZE;
+use crate::gpu::blocks::texture_atlas::{AtlasTiles, global_atlas_tiles};
+use super::constants::{CHUNK_SIZE, COLUMN_HEIGHT};
 use super::greedy::FaceInfo;
 
 /// Максимальный размер слоя для масок (16x16)
 const LAYER_SIZE: usize = (CHUNK_SIZE as usize) * (CHUNK_SIZE as usize);
 
 /// Максимальная высота для вертикальных масок
-const MAX_HEIGHT: usize = 160; // WORLD_HEIGHT - MIN_HEIGHT
+const MAX_HEIGHT: usize = COLUMN_HEIGHT;
 
 /// Размер вертикальной маски (16 * max_height)
 const VERTICAL_MASK_SIZE: usize = (CHUNK_SIZE as usize) * MAX_HEIGHT;
@@ -72,6 +73,9 @@ pub struct MeshingContext {
     
     /// Временный буфер для результатов greedy meshing
     pub greedy_results: Vec<(usize, usize, usize, usize, FaceInfo)>,
+    
+    /// Копия раскладки тайлов атласа (обновляется при смене версии)
+    pub tiles: AtlasTiles,
 }
 
 impl MeshingContext {
@@ -84,6 +88,17 @@ impl MeshingContext {
             vertices: Vec::with_capacity(8000),
             indices: Vec::with_capacity(12000),
             greedy_results: Vec::with_capacity(256),
+            tiles: global_atlas_tiles().read().map(|tiles| tiles.clone()).unwrap_or_else(|_| AtlasTiles::from_registry()),
+        }
+    }
+
+    /// Подтягивает раскладку тайлов, если атлас был перестроен
+    #[inline]
+    pub fn refresh_tiles(&mut self) {
+        if let Ok(global) = global_atlas_tiles().read() {
+            if global.version() != self.tiles.version() {
+                self.tiles = global.clone();
+            }
         }
     }
 
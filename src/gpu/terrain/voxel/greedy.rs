@@ -3,7 +3,7 @@
 // ============================================
 
 use crate::gpu::blocks::BlockType;
-use crate::gpu::terrain::mesh::TerrainVertex;
+use crate::gpu::terrain::mesh::{face_uv, TerrainVertex};
 
 #[derive(Clone, Copy)]
 pub enum FaceDir {
@@ -14,18 +14,23 @@ pub enum FaceDir {
 pub struct FaceInfo {
     pub block_type: BlockType,
     pub is_top: bool,
+    /// Флаги воды для вершин (VERTEX_WET / VERTEX_SUBMERGED): грани
+    /// с разными флагами не склеиваются
+    pub flags: u32,
 }
 
 impl FaceInfo {
     #[inline]
-    pub fn new(block_type: BlockType, is_top: bool) -> Self {
-        Self { block_type, is_top }
+    pub fn new(block_type: BlockType, is_top: bool, flags: u32) -> Self {
+        Self { block_type, is_top, flags }
     }
 }
 
 /// Zero-allocation greedy meshing для одного слоя
 /// 
-/// Записывает результаты в предоставленный буфер вместо создания нового Vec.
+/// Записывает результаты в предоставленный буфер вместо создания нового Vec:
+/// (u, v, ширина, высота, грань). Ширина и высота - заодно число повторов
+/// текстуры на кваде (см. add_greedy_face_with_block).
 /// Буфер visited должен быть предварительно очищен (заполнен false).
 #[inline]
 pub fn greedy_mesh_layer_into(
@@ -105,6 +110,7 @@ pub fn add_greedy_face(
 }
 
 /// Добавляет объединённую грань в буферы с block_id
+/// vertex_id - тип блока и тайл атласа (см. AtlasTiles::vertex_id)
 #[inline]
 pub fn add_greedy_face_with_block(
     vertices: &mut Vec<TerrainVertex>,
@@ -114,52 +120,60 @@ pub fn add_greedy_face_with_block(
     normal: [f32; 3],
     color: [f32; 3],
     dir: FaceDir,
-    block_id: u8,
+    vertex_id: u32,
 ) {
     let base = vertices.len() as u32;
-    let bid = block_id as u32;
     
-    match dir {
+    let corners = match dir {
         FaceDir::PosX => {
             let x1 = x + 1.0;
-            vertices.push(TerrainVertex { position: [x1, y, z + width_u], normal, color, block_id: bid });
-            vertices.push(TerrainVertex { position: [x1, y, z], normal, color, block_id: bid });
-            vertices.push(TerrainVertex { position: [x1, y + height_v, z], normal, color, block_id: bid });
-            vertices.push(TerrainVertex { position: [x1, y + height_v, z + width_u], normal, color, block_id: bid });
+            [[x1, y, z + width_u], [x1, y, z], [x1, y + height_v, z], [x1, y + height_v, z + width_u]]
         }
         FaceDir::NegX => {
-            vertices.push(TerrainVertex { position: [x, y, z], normal, color, block_id: bid });
-            vertices.push(TerrainVertex { position: [x, y, z + width_u], normal, color, block_id: bid });
-            vertices.push(TerrainVertex { position: [x, y + height_v, z + width_u], normal, color, block_id: bid });
-            vertices.push(TerrainVertex { position: [x, y + height_v, z], normal, color, block_id: bid });
+            [[x, y, z], [x, y, z + width_u], [x, y + height_v, z + width_u], [x, y + height_v, z]]
         }
         FaceDir::PosY => {
             let y1 = y + 1.0;
-            vertices.push(TerrainVertex { position: [x, y1, z], normal, color, block_id: bid });
-            vertices.push(TerrainVertex { position: [x, y1, z + height_v], normal, color, block_id: bid });
-            vertices.push(TerrainVertex { position: [x + width_u, y1, z + height_v], normal, color, block_id: bid });
-            vertices.push(TerrainVertex { position: [x + width_u, y1, z], normal, color, block_id: bid });
+            [[x, y1, z], [x, y1, z + height_v], [x + width_u, y1, z + height_v], [x + width_u, y1, z]]
         }
         FaceDir::NegY => {
-            vertices.push(TerrainVertex { position: [x, y, z + height_v], normal, color, block_id: bid });
-            vertices.push(TerrainVertex { position: [x, y, z], normal, color, block_id: bid });
-            vertices.push(TerrainVertex { position: [x + width_u, y, z], normal, color, block_id: bid });
-            vertices.push(TerrainVertex { position: [x + width_u, y, z + height_v], normal, color, block_id: bid });
+            [[x, y, z + height_v], [x, y, z], [x + width_u, y, z], [x + width_u, y, z + height_v]]
         }
         FaceDir::PosZ => {
             let z1 = z + 1.0;
-            vertices.push(TerrainVertex { position: [x, y, z1], normal, color, block_id: bid });
-            vertices.push(TerrainVertex { position: [x + width_u, y, z1], normal, color, block_id: bid });
-            vertices.push(TerrainVertex { position: [x + width_u, y + height_v, z1], normal, color, block_id: bid });
-            vertices.push(TerrainVertex { position: [x, y + height_v, z1], normal, color, block_id: bid });
+            [[x, y, z1], [x + width_u, y, z1], [x + width_u, y + height_v, z1], [x, y + height_v, z1]]
         }
         FaceDir::NegZ => {
-            vertices.push(TerrainVertex { position: [x + width_u, y, z], normal, color, block_id: bid });
-            vertices.push(TerrainVertex { position: [x, y, z], normal, color, block_id: bid });
-            vertices.push(TerrainVertex { position: [x, y + height_v, z], normal, color, block_id: bid });
-            vertices.push(TerrainVertex { position: [x + width_u, y + height_v, z], normal, color, block_id: bid });
+            [[x + width_u, y, z], [x, y, z], [x, y + height_v, z], [x + width_u, y + height_v, z]]
         }
+    };
+    
+    // UV от угла квада: текстура повторяется width_u x height_v раз,
+    // поэтому склейка граней не мешает текстурам
+    for position in corners {
+        let local = [position[0] - x, position[1] - y, position[2] - z];
+        let uv = face_uv(local, normal);
+        vertices.push(TerrainVertex { position, normal, color, block_id: vertex_id, uv });
     }
     
     indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merged_quad_repeats_texture_per_block() {
+        let (mut vertices, mut indices) = (Vec::new(), Vec::new());
+        add_greedy_face_with_block(&mut vertices, &mut indices, 5.0, 10.0, -7.0, 3.0, 2.0, [0.0, 0.0, 1.0], [1.0; 3], FaceDir::PosZ, 0);
+        let uvs: Vec<[f32; 2]> = vertices.iter().map(|v| v.uv).collect();
+        assert_eq!(uvs, vec![[0.0, 0.0], [3.0, 0.0], [3.0, 2.0], [0.0, 2.0]]);
+
+        // Верх: u - по X, v - по Z
+        vertices.clear();
+        add_greedy_face_with_block(&mut vertices, &mut indices, 0.0, 4.0, 0.0, 4.0, 1.0, [0.0, 1.0, 0.0], [1.0; 3], FaceDir::PosY, 0);
+        assert!(vertices.iter().all(|v| (0.0..=4.0).contains(&v.uv[0]) && (0.0..=1.0).contains(&v.uv[1])));
+        assert!(vertices.iter().any(|v| v.uv == [4.0, 1.0]));
+    }
+}
@@ -8,8 +8,10 @@ pub mod thread_local;
 
 mod greedy;
 mod chunk;
+mod section;
 
-pub use constants::{CHUNK_SIZE, MIN_HEIGHT};
+pub use constants::{CHUNK_SIZE, MIN_HEIGHT, SECTION_COUNT, SECTION_SIZE};
+pub use section::{ChunkSection, SectionColumn, section_index, section_min_y};
 pub use context::MeshingContext;
 pub use chunk::{VoxelChunk, ChunkNeighbors, ChunkGenerationResult};
 
@@ -0,0 +1,247 @@
+// ============================================
+// Chunk Sections - Вертикальные секции 16³
+// ============================================
+// Столбец чанка хранится как набор независимых секций 16x16x16.
+// Однородная секция (сплошной массив) хранит один блок и не занимает
+// памяти под массив, а воздух выше и ниже занятых секций не хранится
+// вовсе - поэтому высота мира (MIN_HEIGHT..WORLD_HEIGHT) стоит памяти
+// только там, где есть блоки. Секции вне вертикальной полосы мешинга
+// выгружаются (retain_sections).
+
+use crate::gpu::blocks::{BlockType, AIR};
+
+use super::constants::{CHUNK_SIZE, MIN_HEIGHT, SECTION_COUNT, SECTION_SIZE, WORLD_HEIGHT};
+
+/// Блоков в секции
+pub const SECTION_VOLUME: usize = (SECTION_SIZE * CHUNK_SIZE * CHUNK_SIZE) as usize;
+
+/// Индекс секции по мировой высоте
+#[inline]
+pub fn section_index(y: i32) -> usize {
+    (y - MIN_HEIGHT).div_euclid(SECTION_SIZE) as usize
+}
+
+/// Нижняя мировая высота секции
+#[inline]
+pub fn section_min_y(section: usize) -> i32 {
+    MIN_HEIGHT + section as i32 * SECTION_SIZE
+}
+
+/// Секция 16³
+#[derive(Clone)]
+pub enum ChunkSection {
+    /// Вся секция из одного блока
+    Uniform(BlockType),
+    /// Произвольные блоки (y, z, x)
+    Blocks(Box<[BlockType; SECTION_VOLUME]>),
+}
+
+impl ChunkSection {
+    #[inline]
+    fn index(lx: i32, ly: i32, lz: i32) -> usize {
+        ((ly * CHUNK_SIZE + lz) * CHUNK_SIZE + lx) as usize
+    }
+
+    /// Блок по локальным координатам секции
+    #[inline]
+    pub fn get(&self, lx: i32, ly: i32, lz: i32) -> BlockType {
+        match self {
+            ChunkSection::Uniform(block) => *block,
+            ChunkSection::Blocks(blocks) => blocks[Self::index(lx, ly, lz)],
+        }
+    }
+
+    /// Установить блок (однородная секция разворачивается в массив)
+    pub fn set(&mut self, lx: i32, ly: i32, lz: i32, block: BlockType) {
+        match self {
+            ChunkSection::Uniform(current) if *current == block => {}
+            ChunkSection::Uniform(current) => {
+                let mut blocks = Box::new([*current; SECTION_VOLUME]);
+                blocks[Self::index(lx, ly, lz)] = block;
+                *self = ChunkSection::Blocks(blocks);
+            }
+            ChunkSection::Blocks(blocks) => blocks[Self::index(lx, ly, lz)] = block,
+        }
+    }
+
+    /// Секция целиком из воздуха
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        matches!(self, ChunkSection::Uniform(AIR))
+    }
+
+    /// Свернуть массив в Uniform, если все блоки одинаковые
+    pub fn compact(&mut self) {
+        if let ChunkSection::Blocks(blocks) = self {
+            let first = blocks[0];
+            if blocks.iter().all(|&block| block == first) {
+                *self = ChunkSection::Uniform(first);
+            }
+        }
+    }
+}
+
+/// Столбец секций от MIN_HEIGHT до WORLD_HEIGHT. Хранятся только
+/// секции от нижней до верхней занятой; остальные - воздух
+#[derive(Clone, Default)]
+pub struct SectionColumn {
+    /// Индекс первой хранимой секции
+    first: usize,
+    sections: Vec<ChunkSection>,
+}
+
+impl SectionColumn {
+    /// Пустой столбец (только воздух, без аллокаций под секции)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    fn in_bounds(y: i32) -> bool {
+        (MIN_HEIGHT..WORLD_HEIGHT).contains(&y)
+    }
+
+    /// Блок по локальным (lx, lz) и мировой y. Вне пределов - воздух
+    #[inline]
+    pub fn get(&self, lx: i32, y: i32, lz: i32) -> BlockType {
+        if !Self::in_bounds(y) {
+            return AIR;
+        }
+        let section = section_index(y);
+        match self.section(section) {
+            Some(stored) => stored.get(lx, y - section_min_y(section), lz),
+            None => AIR,
+        }
+    }
+
+    /// Установить блок. Вне пределов - игнорируется
+    #[inline]
+    pub fn set(&mut self, lx: i32, y: i32, lz: i32, block: BlockType) {
+        if !Self::in_bounds(y) {
+            return;
+        }
+        let section = section_index(y);
+        if block == AIR && self.section(section).is_none() {
+            return;
+        }
+        let local_y = y - section_min_y(section);
+        self.section_mut(section).set(lx, local_y, lz, block);
+    }
+
+    /// Хранимая секция по индексу (None - воздух)
+    #[inline]
+    pub fn section(&self, index: usize) -> Option<&ChunkSection> {
+        self.sections.get(index.checked_sub(self.first)?)
+    }
+
+    /// Секция целиком из воздуха (в том числе не хранимая)
+    pub fn is_section_empty(&self, index: usize) -> bool {
+        self.section(index).is_none_or(ChunkSection::is_empty)
+    }
+
+    /// Хранимые секции: индексы первой и последней
+    pub fn stored_range(&self) -> Option<(usize, usize)> {
+        (!self.sections.is_empty()).then(|| (self.first, self.first + self.sections.len() - 1))
+    }
+
+    /// Секция для записи; столбец дорастает до неё воздухом
+    fn section_mut(&mut self, index: usize) -> &mut ChunkSection {
+        debug_assert!(index < SECTION_COUNT);
+        if self.sections.is_empty() {
+            self.first = index;
+        } else if index < self.first {
+            let grow = self.first - index;
+            self.sections.splice(0..0, std::iter::repeat_n(ChunkSection::Uniform(AIR), grow));
+            self.first = index;
+        }
+        let local = index - self.first;
+        if local >= self.sections.len() {
+            self.sections.resize(local + 1, ChunkSection::Uniform(AIR));
+        }
+        &mut self.sections[local]
+    }
+
+    /// Выгрузить секции вне [min_section, max_section]: они читаются
+    /// как воздух, пока столбец не сгенерирован заново
+    pub fn retain_sections(&mut self, min_section: usize, max_section: usize) {
+        let Some((first, last)) = self.stored_range() else { return };
+        if max_section < last {
+            self.sections.truncate((max_section + 1).saturating_sub(first));
+        }
+        if min_section > first {
+            let drop = (min_section - first).min(self.sections.len());
+            self.sections.drain(..drop);
+            self.first = min_section;
+        }
+        self.trim();
+    }
+
+    /// Свернуть однородные секции после генерации и отбросить воздух
+    /// сверху и снизу
+    pub fn compact(&mut self) {
+        for section in &mut self.sections {
+            section.compact();
+        }
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        while self.sections.last().is_some_and(ChunkSection::is_empty) {
+            self.sections.pop();
+        }
+        let leading = self.sections.iter().take_while(|section| section.is_empty()).count();
+        self.sections.drain(..leading);
+        self.first = if self.sections.is_empty() { 0 } else { self.first + leading };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::blocks::STONE;
+
+    #[test]
+    fn empty_column_stores_no_sections() {
+        let mut column = SectionColumn::new();
+        assert_eq!(column.get(3, 10, 7), AIR);
+        column.set(3, 10, 7, AIR);
+        assert_eq!(column.stored_range(), None);
+        assert!((0..SECTION_COUNT).all(|i| column.is_section_empty(i)));
+    }
+
+    #[test]
+    fn set_get_and_compact() {
+        let mut column = SectionColumn::new();
+        column.set(1, MIN_HEIGHT, 2, STONE);
+        column.set(1, WORLD_HEIGHT - 1, 2, STONE);
+        column.set(0, WORLD_HEIGHT, 0, STONE); // вне пределов
+        assert_eq!(column.get(1, MIN_HEIGHT, 2), STONE);
+        assert_eq!(column.get(1, WORLD_HEIGHT - 1, 2), STONE);
+        assert_eq!(column.get(0, WORLD_HEIGHT, 0), AIR);
+        assert_eq!(column.stored_range(), Some((0, SECTION_COUNT - 1)));
+
+        column.set(1, MIN_HEIGHT, 2, AIR);
+        column.compact();
+        assert!(column.is_section_empty(0));
+        assert_eq!(column.stored_range(), Some((SECTION_COUNT - 1, SECTION_COUNT - 1)));
+    }
+
+    #[test]
+    fn sections_outside_band_are_evicted() {
+        let mut column = SectionColumn::new();
+        for y in [-100, 0, 15, 16, 300] {
+            column.set(4, y, 4, STONE);
+        }
+        let (low, high) = (section_index(0), section_index(16));
+        column.retain_sections(low, high);
+        assert_eq!(column.stored_range(), Some((low, high)));
+        assert_eq!(column.get(4, 0, 4), STONE);
+        assert_eq!(column.get(4, 16, 4), STONE);
+        assert_eq!(column.get(4, -100, 4), AIR);
+        assert_eq!(column.get(4, 300, 4), AIR);
+
+        // Полоса без занятых секций
+        column.retain_sections(section_index(500), section_index(600));
+        assert_eq!(column.stored_range(), None);
+    }
+}
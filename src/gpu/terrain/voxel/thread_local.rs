@@ -23,7 +23,6 @@ thread_local! {
 /// });
 /// ```
 #[inline]
-#[allow(dead_code)]
 pub fn with_meshing_context<F, R>(f: F) -> R
 where
     F: FnOnce(&mut MeshingContext) -> R,
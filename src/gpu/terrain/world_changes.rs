@@ -3,7 +3,7 @@
 // ============================================
 // Хранит сломанные/поставленные блоки поверх процедурной генерации
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use crate::gpu::blocks::{BlockType, AIR};
 
 /// Ключ для блока в мире
@@ -39,6 +39,9 @@ pub struct WorldChanges {
     /// Чанки которые нужно перегенерировать
     dirty_chunks: Vec<(i32, i32)>,
     
+    /// Чанки, изменённые с последнего сохранения
+    unsaved_chunks: HashSet<(i32, i32)>,
+    
     /// Версия изменений (инкрементируется при каждом изменении)
     version: u64,
 }
@@ -48,6 +51,7 @@ impl WorldChanges {
         Self {
             changes: HashMap::new(),
             dirty_chunks: Vec::new(),
+            unsaved_chunks: HashSet::new(),
             version: 0,
         }
     }
@@ -67,6 +71,21 @@ impl WorldChanges {
         if !self.dirty_chunks.contains(&chunk_key) {
             self.dirty_chunks.push(chunk_key);
         }
+        self.unsaved_chunks.insert(chunk_key);
+    }
+    
+    /// Убрать изменение: блок снова от генератора
+    pub fn revert_block(&mut self, pos: BlockPos) {
+        if self.changes.remove(&pos).is_none() {
+            return;
+        }
+        self.version += 1;
+        
+        let chunk_key = pos.chunk_key();
+        if !self.dirty_chunks.contains(&chunk_key) {
+            self.dirty_chunks.push(chunk_key);
+        }
+        self.unsaved_chunks.insert(chunk_key);
     }
     
     /// Сломать блок (установить Air)
@@ -94,6 +113,21 @@ impl WorldChanges {
         !self.dirty_chunks.is_empty()
     }
     
+    /// Получить и очистить список несохранённых чанков
+    pub fn take_unsaved_chunks(&mut self) -> Vec<(i32, i32)> {
+        self.unsaved_chunks.drain().collect()
+    }
+    
+    /// Вернуть чанки в список несохранённых (если запись не удалась)
+    pub fn mark_unsaved(&mut self, chunks: impl IntoIterator<Item = (i32, i32)>) {
+        self.unsaved_chunks.extend(chunks);
+    }
+    
+    /// Сбросить список несохранённых чанков (после загрузки с диска)
+    pub fn clear_unsaved(&mut self) {
+        self.unsaved_chunks.clear();
+    }
+    
     /// Количество изменений
     pub fn change_count(&self) -> usize {
         self.changes.len()
@@ -104,6 +138,11 @@ impl WorldChanges {
         self.changes.clone()
     }
     
+    /// Итератор по всем изменениям (без копирования)
+    pub fn iter(&self) -> impl Iterator<Item = (&BlockPos, &BlockType)> {
+        self.changes.iter()
+    }
+    
     /// Получить изменения только для конкретного чанка
     pub fn get_changes_for_chunk(&self, chunk_x: i32, chunk_z: i32, chunk_size: i32) -> HashMap<BlockPos, BlockType> {
         let min_x = chunk_x * chunk_size;
@@ -0,0 +1,311 @@
+// ============================================
+// World Edit - Правки областей мира по кадрам
+// ============================================
+// /fill, /replace, /hollow и /walls ставятся в очередь и применяются к
+// WorldChanges порциями по EDIT_BLOCKS_PER_FRAME блоков за кадр: большая
+// область не подвешивает кадр, а чанки перестраиваются постепенно.
+//
+// Каждая завершённая правка оставляет запись отката - прежнее состояние
+// изменённых блоков (изменение мира или None, если блок был от
+// генератора). /undo ставит в очередь обратную правку из этой записи.
+
+use std::collections::VecDeque;
+
+use crate::gpu::blocks::{BlockType, AIR};
+
+use super::world_changes::{BlockPos, WorldChanges};
+
+/// Блоков правки за кадр
+pub const EDIT_BLOCKS_PER_FRAME: usize = 32768;
+/// Максимум блоков в одной правке
+pub const MAX_EDIT_VOLUME: usize = 524288;
+/// Сколько правок можно откатить
+const UNDO_DEPTH: usize = 10;
+
+/// Операция над параллелепипедом
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditOperation {
+    /// Заполнить целиком
+    Fill(BlockType),
+    /// Заменить один блок другим
+    Replace { from: BlockType, to: BlockType },
+    /// Оболочка из блока, внутри воздух
+    Hollow(BlockType),
+    /// Четыре боковые стены, внутренность не трогается
+    Walls(BlockType),
+}
+
+impl EditOperation {
+    /// Новый блок в pos области [min, max] (None - блок не меняется)
+    pub fn target(&self, pos: [i32; 3], min: [i32; 3], max: [i32; 3], current: BlockType) -> Option<BlockType> {
+        let edge = |axis: usize| pos[axis] == min[axis] || pos[axis] == max[axis];
+        let block = match *self {
+            EditOperation::Fill(block) => Some(block),
+            EditOperation::Replace { from, to } => (current == from).then_some(to),
+            EditOperation::Hollow(block) => Some(if edge(0) || edge(1) || edge(2) { block } else { AIR }),
+            EditOperation::Walls(block) => (edge(0) || edge(2)).then_some(block),
+        };
+        block.filter(|&block| block != current)
+    }
+}
+
+/// Число блоков области [min, max] для правки. Стороны считаются в i64:
+/// углы на краях i32 (например, /fill -3e9 0 0 3e9 0 0) не переполняются.
+/// Err - пустая область или больше MAX_EDIT_VOLUME блоков
+pub fn edit_volume(min: [i32; 3], max: [i32; 3]) -> Result<usize, String> {
+    let extents = (0..3).map(|i| (max[i] as i64 - min[i] as i64 + 1).max(0));
+    let volume = extents.try_fold(1i64, |volume, extent| volume.checked_mul(extent));
+    match volume {
+        Some(0) => Err("Область вне высот мира".to_string()),
+        Some(volume) if volume as u64 <= MAX_EDIT_VOLUME as u64 => Ok(volume as usize),
+        Some(volume) => Err(format!("Слишком большая область: {} блоков (максимум {})", volume, MAX_EDIT_VOLUME)),
+        None => Err(format!("Слишком большая область (максимум {} блоков)", MAX_EDIT_VOLUME)),
+    }
+}
+
+/// Прежнее состояние блока: изменение мира или None (блок генератора)
+type Previous = (BlockPos, Option<BlockType>);
+
+enum EditSource {
+    /// Обход области: y внутри, чанк за чанком по столбцам
+    Region { operation: EditOperation, cursor: usize },
+    /// Откат: прежние состояния, восстанавливаются с конца
+    Undo { entries: Vec<Previous>, total: usize },
+}
+
+struct EditJob {
+    source: EditSource,
+    min: [i32; 3],
+    max: [i32; 3],
+    /// Запись отката этой правки
+    previous: Vec<Previous>,
+}
+
+impl EditJob {
+    fn volume(&self) -> usize {
+        (0..3).map(|i| (self.max[i] - self.min[i] + 1) as usize).product()
+    }
+
+    fn total(&self) -> usize {
+        match &self.source {
+            EditSource::Region { .. } => self.volume(),
+            EditSource::Undo { total, .. } => *total,
+        }
+    }
+
+    fn done(&self) -> usize {
+        match &self.source {
+            EditSource::Region { cursor, .. } => *cursor,
+            EditSource::Undo { entries, total } => total - entries.len(),
+        }
+    }
+}
+
+/// Порция правки за кадр
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EditStep {
+    /// Область правки (для опоры и прочих проверок после изменения)
+    pub min: [i32; 3],
+    pub max: [i32; 3],
+    /// Правка закончена в этой порции
+    pub finished: Option<EditSummary>,
+}
+
+/// Итог законченной правки
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EditSummary {
+    /// Изменено (или восстановлено) блоков
+    pub changed: usize,
+    pub undo: bool,
+}
+
+/// Очередь правок и история отката
+pub struct WorldEdit {
+    jobs: VecDeque<EditJob>,
+    history: VecDeque<Vec<Previous>>,
+}
+
+impl WorldEdit {
+    pub fn new() -> Self {
+        Self { jobs: VecDeque::new(), history: VecDeque::new() }
+    }
+
+    /// Поставить правку области [min, max] в очередь (область проверена
+    /// edit_volume)
+    pub fn queue(&mut self, operation: EditOperation, min: [i32; 3], max: [i32; 3]) {
+        debug_assert!(edit_volume(min, max).is_ok());
+        self.jobs.push_back(EditJob {
+            source: EditSource::Region { operation, cursor: 0 },
+            min,
+            max,
+            previous: Vec::new(),
+        });
+    }
+
+    /// Откатить последнюю законченную правку (false - откатывать нечего)
+    pub fn undo(&mut self) -> bool {
+        let Some(entries) = self.history.pop_back() else { return false };
+        let mut min = [i32::MAX; 3];
+        let mut max = [i32::MIN; 3];
+        for (pos, _) in &entries {
+            for (i, v) in [pos.x, pos.y, pos.z].into_iter().enumerate() {
+                min[i] = min[i].min(v);
+                max[i] = max[i].max(v);
+            }
+        }
+        let total = entries.len();
+        self.jobs.push_back(EditJob { source: EditSource::Undo { entries, total }, min, max, previous: Vec::new() });
+        true
+    }
+
+    pub fn is_busy(&self) -> bool {
+        !self.jobs.is_empty()
+    }
+
+    /// Правок, которые можно откатить
+    pub fn undo_depth(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Прогресс текущей правки (сделано, всего)
+    pub fn progress(&self) -> Option<(usize, usize)> {
+        self.jobs.front().map(|job| (job.done(), job.total()))
+    }
+
+    /// Применить до budget блоков текущей правки.
+    /// generated - блок генератора там, где нет изменения мира
+    pub fn step(
+        &mut self,
+        budget: usize,
+        changes: &mut WorldChanges,
+        generated: impl Fn(i32, i32, i32) -> BlockType,
+    ) -> Option<EditStep> {
+        let job = self.jobs.front_mut()?;
+        let (min, max) = (job.min, job.max);
+        let sy = (max[1] - min[1] + 1) as usize;
+        let sz = max[2] - min[2] + 1;
+        let volume = job.volume();
+
+        let finished = match &mut job.source {
+            EditSource::Region { operation, cursor } => {
+                let end = (*cursor + budget).min(volume);
+                for index in *cursor..end {
+                    let column = (index / sy) as i32;
+                    let pos = [min[0] + column / sz, min[1] + (index % sy) as i32, min[2] + column % sz];
+                    let change = changes.get_block(pos[0], pos[1], pos[2]);
+                    let current = change.unwrap_or_else(|| generated(pos[0], pos[1], pos[2]));
+                    if let Some(block) = operation.target(pos, min, max, current) {
+                        let pos = BlockPos::from_array(pos);
+                        job.previous.push((pos, change));
+                        changes.set_block(pos, block);
+                    }
+                }
+                *cursor = end;
+                end == volume
+            }
+            EditSource::Undo { entries, .. } => {
+                for _ in 0..budget.min(entries.len()) {
+                    match entries.pop() {
+                        Some((pos, Some(block))) => changes.set_block(pos, block),
+                        Some((pos, None)) => changes.revert_block(pos),
+                        None => break,
+                    }
+                }
+                entries.is_empty()
+            }
+        };
+
+        if !finished {
+            return Some(EditStep { min, max, finished: None });
+        }
+        let job = self.jobs.pop_front()?;
+        let undo = matches!(job.source, EditSource::Undo { .. });
+        let changed = if undo { job.total() } else { job.previous.len() };
+        if !undo && !job.previous.is_empty() {
+            self.history.push_back(job.previous);
+            if self.history.len() > UNDO_DEPTH {
+                self.history.pop_front();
+            }
+        }
+        Some(EditStep { min, max, finished: Some(EditSummary { changed, undo }) })
+    }
+}
+
+impl Default for WorldEdit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::blocks::{DIRT, STONE};
+
+    /// Генератор: камень ниже y = 2, выше воздух
+    fn generated(_x: i32, y: i32, _z: i32) -> BlockType {
+        if y < 2 { STONE } else { AIR }
+    }
+
+    fn run(edit: &mut WorldEdit, changes: &mut WorldChanges, budget: usize) -> (usize, EditSummary) {
+        let mut steps = 0;
+        loop {
+            steps += 1;
+            let step = edit.step(budget, changes, generated).expect("правка в очереди");
+            if let Some(summary) = step.finished {
+                return (steps, summary);
+            }
+        }
+    }
+
+    #[test]
+    fn operations_pick_blocks_by_shape() {
+        let (min, max) = ([0, 0, 0], [2, 2, 2]);
+        assert_eq!(EditOperation::Hollow(DIRT).target([1, 1, 1], min, max, STONE), Some(AIR));
+        assert_eq!(EditOperation::Hollow(DIRT).target([1, 0, 1], min, max, STONE), Some(DIRT));
+        assert_eq!(EditOperation::Walls(DIRT).target([1, 0, 1], min, max, STONE), None);
+        assert_eq!(EditOperation::Walls(DIRT).target([0, 1, 1], min, max, AIR), Some(DIRT));
+        let replace = EditOperation::Replace { from: STONE, to: DIRT };
+        assert_eq!(replace.target([1, 1, 1], min, max, AIR), None);
+        assert_eq!(replace.target([1, 1, 1], min, max, STONE), Some(DIRT));
+        // Блок уже нужный - не трогаем
+        assert_eq!(EditOperation::Fill(STONE).target([0, 0, 0], min, max, STONE), None);
+    }
+
+    #[test]
+    fn edits_are_applied_over_several_steps_and_undone() {
+        let mut changes = WorldChanges::new();
+        changes.set_block(BlockPos::new(1, 3, 1), DIRT);
+        let mut edit = WorldEdit::new();
+
+        // 4x4x4 = 64 блока порциями по 10
+        edit.queue(EditOperation::Fill(STONE), [0, 0, 0], [3, 3, 3]);
+        let (steps, summary) = run(&mut edit, &mut changes, 10);
+        assert_eq!(steps, 7);
+        // Нижние 2 слоя уже камень
+        assert_eq!(summary, EditSummary { changed: 32, undo: false });
+        assert!(!edit.is_busy());
+        assert_eq!(changes.get_block(1, 3, 1), Some(STONE));
+        assert_eq!(changes.get_block(0, 0, 0), None);
+
+        assert!(edit.undo());
+        let (_, summary) = run(&mut edit, &mut changes, 10);
+        assert!(summary.undo);
+        // Изменение игрока вернулось, поставленные правкой блоки убраны
+        assert_eq!(changes.get_block(1, 3, 1), Some(DIRT));
+        assert_eq!(changes.get_block(2, 2, 2), None);
+        assert_eq!(changes.change_count(), 1);
+        assert!(!edit.undo());
+    }
+
+    #[test]
+    fn oversized_and_overflowing_regions_are_rejected() {
+        assert_eq!(edit_volume([0, 0, 0], [3, 3, 3]), Ok(64));
+        assert!(edit_volume([0, 5, 0], [3, 4, 3]).is_err());
+        assert!(edit_volume([0, 0, 0], [MAX_EDIT_VOLUME as i32, 0, 0]).is_err());
+        // /fill -3e9 0 0 3e9 0 0: углы упираются в края i32
+        assert!(edit_volume([i32::MIN, 0, 0], [i32::MAX, 0, 0]).is_err());
+        // Произведение сторон не помещается даже в i64
+        assert!(edit_volume([i32::MIN; 3], [i32::MAX; 3]).is_err());
+    }
+}
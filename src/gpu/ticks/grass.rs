@@ -0,0 +1,91 @@
+// ============================================
+// Grass Spread - Трава и земля
+// ============================================
+// Земля под открытым небом рядом с травой зарастает; трава под
+// непрозрачным блоком становится землёй. Небо - все блоки над клеткой
+// до верха мира прозрачны (полноценного небесного света в движке нет).
+
+use crate::gpu::blocks::{is_transparent, BlockType, DIRT, GRASS, SNOW};
+use crate::gpu::terrain::voxel::constants::WORLD_HEIGHT;
+use crate::gpu::terrain::BlockPos;
+
+use super::scheduler::{BlockSource, TickContext};
+
+/// Тик земли или травы
+pub(super) fn tick(ctx: &mut TickContext, pos: BlockPos, block: BlockType) {
+    if let Some(new_block) = next_block(ctx.world(), pos, block) {
+        ctx.set_block(pos, new_block);
+    }
+}
+
+/// Во что превращается клетка (None - без изменений)
+fn next_block(world: &dyn BlockSource, pos: BlockPos, block: BlockType) -> Option<BlockType> {
+    match block {
+        GRASS if covers(world.block(pos.x, pos.y + 1, pos.z)) => Some(DIRT),
+        DIRT if touches_grass(world, pos) && sees_sky(world, pos) => Some(GRASS),
+        _ => None,
+    }
+}
+
+/// Блок сверху душит траву (снежный покров - нет)
+fn covers(above: BlockType) -> bool {
+    above != SNOW && !is_transparent(above)
+}
+
+/// Над клеткой до верха мира только прозрачные блоки
+fn sees_sky(world: &dyn BlockSource, pos: BlockPos) -> bool {
+    (pos.y + 1..WORLD_HEIGHT).all(|y| is_transparent(world.block(pos.x, y, pos.z)))
+}
+
+/// Трава в соседнем столбце на уровень выше, вровень или ниже
+fn touches_grass(world: &dyn BlockSource, pos: BlockPos) -> bool {
+    (-1..=1).any(|dx: i32| {
+        (-1..=1).any(|dz: i32| {
+            (dx, dz) != (0, 0) && (-1..=1).any(|dy| world.block(pos.x + dx, pos.y + dy, pos.z + dz) == GRASS)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::gpu::blocks::{AIR, STONE};
+
+    /// Мир из воздуха с заданными блоками
+    struct TestWorld(HashMap<(i32, i32, i32), BlockType>);
+
+    impl BlockSource for TestWorld {
+        fn block(&self, x: i32, y: i32, z: i32) -> BlockType {
+            self.0.get(&(x, y, z)).copied().unwrap_or(AIR)
+        }
+    }
+
+    fn world(blocks: &[((i32, i32, i32), BlockType)]) -> TestWorld {
+        TestWorld(blocks.iter().copied().collect())
+    }
+
+    #[test]
+    fn dirt_next_to_grass_grows() {
+        let w = world(&[((0, 0, 0), DIRT), ((1, 1, 0), GRASS)]);
+        assert_eq!(next_block(&w, BlockPos::new(0, 0, 0), DIRT), Some(GRASS));
+        // Трава далеко
+        let lonely = world(&[((0, 0, 0), DIRT), ((2, 0, 0), GRASS)]);
+        assert_eq!(next_block(&lonely, BlockPos::new(0, 0, 0), DIRT), None);
+    }
+
+    #[test]
+    fn dirt_without_sky_stays_dirt() {
+        let w = world(&[((0, 0, 0), DIRT), ((1, 0, 0), GRASS), ((0, 20, 0), STONE)]);
+        assert_eq!(next_block(&w, BlockPos::new(0, 0, 0), DIRT), None);
+    }
+
+    #[test]
+    fn covered_grass_dies() {
+        let w = world(&[((0, 0, 0), GRASS), ((0, 1, 0), STONE)]);
+        assert_eq!(next_block(&w, BlockPos::new(0, 0, 0), GRASS), Some(DIRT));
+        let snowy = world(&[((0, 0, 0), GRASS), ((0, 1, 0), SNOW)]);
+        assert_eq!(next_block(&snowy, BlockPos::new(0, 0, 0), GRASS), None);
+    }
+}
@@ -0,0 +1,15 @@
+// ============================================
+// Block Ticks - Тики блоков
+// ============================================
+// Медленные изменения мира без участия игрока:
+// - scheduler: 20 тиков в секунду, случайные тики секций вокруг игрока
+//   и запланированные тики (после изменения блока сверху)
+// - grass: трава расползается по освещённой земле и гибнет под блоками
+// - sapling: саженцы вырастают в деревья
+// Новые правила регистрируются в scheduler::tick_rule.
+
+mod scheduler;
+mod grass;
+mod sapling;
+
+pub use scheduler::BlockTicks;
@@ -0,0 +1,109 @@
+// ============================================
+// Saplings - Рост деревьев из саженцев
+// ============================================
+// Саженец на земле или траве на случайном тике с шансом GROW_CHANCE
+// вырастает в дерево своей породы. Дерево строят те же place_basic_tree
+// и place_spruce_tree, что и при генерации чанка: ChunkWriter пишет
+// в готовый мир, листва становится субвокселями.
+
+use crate::gpu::biomes::features::{place_basic_tree, place_spruce_tree, ChunkWriter, LeafSubVoxel, TreeType};
+use crate::gpu::blocks::{BlockType, AIR, BIRCH_SAPLING, DIRT, GRASS, OAK_SAPLING, SPRUCE_SAPLING};
+use crate::gpu::terrain::generation::hash3d;
+use crate::gpu::terrain::voxel::constants::{CHUNK_SIZE, WORLD_HEIGHT};
+use crate::gpu::terrain::BlockPos;
+
+use super::scheduler::TickContext;
+
+/// Шанс вырасти на случайном тике (в среднем несколько минут)
+const GROW_CHANCE: f32 = 0.3;
+
+/// Тик саженца
+pub(super) fn tick(ctx: &mut TickContext, pos: BlockPos, block: BlockType) {
+    if ctx.roll >= GROW_CHANCE {
+        return;
+    }
+    let Some(tree_type) = sapling_tree(block) else { return };
+    // Высота - как у сгенерированных деревьев
+    let height = 5 + (hash3d(pos.x, pos.y, pos.z) * 1000.0) as i32 % 3;
+
+    let (blocks, leaves) = {
+        let block_at = |x: i32, y: i32, z: i32| ctx.block(x, y, z);
+        match grow_tree(&block_at, pos, tree_type, height) {
+            Some(tree) => tree,
+            None => return,
+        }
+    };
+    for (pos, block) in blocks {
+        ctx.set_block(pos, block);
+    }
+    ctx.add_leaves(leaves);
+}
+
+/// Порода дерева саженца
+fn sapling_tree(block: BlockType) -> Option<TreeType> {
+    match block {
+        OAK_SAPLING => Some(TreeType::Oak),
+        BIRCH_SAPLING => Some(TreeType::Birch),
+        SPRUCE_SAPLING => Some(TreeType::Spruce),
+        _ => None,
+    }
+}
+
+/// Блоки и листва дерева из саженца в pos (None - не на земле или
+/// над саженцем нет места под ствол и верхушку)
+fn grow_tree(
+    block_at: &dyn Fn(i32, i32, i32) -> BlockType,
+    pos: BlockPos,
+    tree_type: TreeType,
+    height: i32,
+) -> Option<(Vec<(BlockPos, BlockType)>, Vec<LeafSubVoxel>)> {
+    if !matches!(block_at(pos.x, pos.y - 1, pos.z), DIRT | GRASS) || pos.y + height + 2 >= WORLD_HEIGHT {
+        return None;
+    }
+    if !(1..height + 2).all(|dy| block_at(pos.x, pos.y + dy, pos.z) == AIR) {
+        return None;
+    }
+
+    // Саженец в центре чанка записи - крона не обрезается
+    let half = CHUNK_SIZE / 2;
+    let mut writer = ChunkWriter::for_world(block_at, pos.x - half, pos.z - half);
+    match tree_type {
+        // Ель на блок выше, как при генерации
+        TreeType::Spruce => place_spruce_tree(&mut writer, half, pos.y, half, height + 1),
+        tree_type => place_basic_tree(&mut writer, half, pos.y, half, tree_type, height),
+    }
+    Some((writer.take_placed_blocks(), writer.take_leaf_subvoxels()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::blocks::{OAK_LOG, STONE};
+
+    /// Земля на y = 0, саженец на y = 1, выше воздух
+    fn meadow(x: i32, y: i32, z: i32) -> BlockType {
+        match (x, y, z) {
+            (0, 1, 0) => OAK_SAPLING,
+            (_, 0, _) => GRASS,
+            _ => AIR,
+        }
+    }
+
+    #[test]
+    fn sapling_becomes_tree() {
+        let (blocks, leaves) = grow_tree(&meadow, BlockPos::new(0, 1, 0), TreeType::Oak, 5).unwrap();
+        assert!(blocks.contains(&(BlockPos::new(0, 1, 0), OAK_LOG)));
+        assert_eq!(blocks.iter().filter(|(_, block)| *block == OAK_LOG).count(), 5);
+        assert!(!leaves.is_empty());
+        // Крона по обе стороны ствола (не обрезана границей чанка)
+        assert!(leaves.iter().any(|leaf| leaf.world_x < 0) && leaves.iter().any(|leaf| leaf.world_x > 0));
+    }
+
+    #[test]
+    fn needs_soil_and_room() {
+        let on_stone = |x: i32, y: i32, z: i32| if y == 0 { STONE } else { meadow(x, y, z) };
+        assert!(grow_tree(&on_stone, BlockPos::new(0, 1, 0), TreeType::Oak, 5).is_none());
+        let roofed = |x: i32, y: i32, z: i32| if (x, y, z) == (0, 4, 0) { STONE } else { meadow(x, y, z) };
+        assert!(grow_tree(&roofed, BlockPos::new(0, 1, 0), TreeType::Oak, 5).is_none());
+    }
+}
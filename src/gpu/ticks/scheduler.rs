@@ -0,0 +1,252 @@
+// ============================================
+// Tick Scheduler - Случайные и запланированные тики
+// ============================================
+// Игровые тики идут с фиксированной частотой независимо от FPS. Каждый
+// тик в секциях 16³ вокруг игрока выбираются случайные блоки (как
+// random tick в Minecraft), затем выполняются наступившие запланированные
+// тики. Правило блока пишет изменения в TickContext; блоки ложатся в
+// WorldChanges, как и правки игрока, листва копится для субвокселей.
+// Очередь не сохраняется: после загрузки мир догоняет случайными тиками.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use ultraviolet::Vec3;
+
+use crate::gpu::blocks::{BlockType, DIRT, GRASS, OAK_SAPLING, BIRCH_SAPLING, SPRUCE_SAPLING};
+use crate::gpu::biomes::features::LeafSubVoxel;
+use crate::gpu::terrain::generation::terrain_generator;
+use crate::gpu::terrain::voxel::{SECTION_COUNT, SECTION_SIZE};
+use crate::gpu::terrain::{BlockPos, WorldChanges, CHUNK_SIZE, MIN_HEIGHT};
+
+use super::{grass, sapling};
+
+/// Игровых тиков в секунду
+const TICKS_PER_SECOND: f32 = 20.0;
+/// Максимум тиков за кадр (долгий кадр не догоняется целиком)
+const MAX_TICKS_PER_FRAME: u32 = 5;
+/// Радиус случайных тиков вокруг игрока (чанки)
+const RANDOM_TICK_RADIUS: i32 = 2;
+/// Секций выше и ниже секции игрока
+const RANDOM_TICK_SECTIONS: i32 = 1;
+/// Случайных блоков на секцию за тик
+const RANDOM_TICKS_PER_SECTION: u32 = 3;
+/// Задержка тика блока под изменённой клеткой (тики)
+const NEIGHBOR_TICK_DELAY: u64 = 40;
+
+/// Чтение блоков для правил тиков
+pub trait BlockSource {
+    fn block(&self, x: i32, y: i32, z: i32) -> BlockType;
+}
+
+/// Мир: изменения игрока поверх генератора
+struct World<'a> {
+    changes: &'a WorldChanges,
+}
+
+impl BlockSource for World<'_> {
+    fn block(&self, x: i32, y: i32, z: i32) -> BlockType {
+        self.changes.get_block(x, y, z).unwrap_or_else(|| terrain_generator().block_at(x, y, z))
+    }
+}
+
+/// Чтение мира и изменения одного тика блока
+pub struct TickContext<'a> {
+    world: &'a dyn BlockSource,
+    /// Случайное число 0-1 этого тика
+    pub roll: f32,
+    blocks: Vec<(BlockPos, BlockType)>,
+    leaves: Vec<LeafSubVoxel>,
+}
+
+impl TickContext<'_> {
+    pub fn world(&self) -> &dyn BlockSource {
+        self.world
+    }
+
+    pub fn block(&self, x: i32, y: i32, z: i32) -> BlockType {
+        self.world.block(x, y, z)
+    }
+
+    pub fn set_block(&mut self, pos: BlockPos, block: BlockType) {
+        self.blocks.push((pos, block));
+    }
+
+    /// Блоки листвы (станут субвокселями)
+    pub fn add_leaves(&mut self, leaves: Vec<LeafSubVoxel>) {
+        self.leaves.extend(leaves);
+    }
+}
+
+/// Правило тика блока
+type TickFn = fn(&mut TickContext, BlockPos, BlockType);
+
+/// Правило тика для типа блока (None - блок не тикает)
+fn tick_rule(block: BlockType) -> Option<TickFn> {
+    match block {
+        DIRT | GRASS => Some(grass::tick),
+        OAK_SAPLING | BIRCH_SAPLING | SPRUCE_SAPLING => Some(sapling::tick),
+        _ => None,
+    }
+}
+
+/// Планировщик тиков блоков
+pub struct BlockTicks {
+    /// Накопленное время до следующего тика (секунды)
+    accumulator: f32,
+    /// Номер текущего тика
+    tick: u64,
+    /// Запланированные тики: (номер тика, позиция)
+    scheduled: BinaryHeap<Reverse<(u64, [i32; 3])>>,
+    /// Листва выросших деревьев, ещё не ставшая субвокселями
+    leaves: Vec<LeafSubVoxel>,
+    rng: u64,
+}
+
+impl BlockTicks {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            accumulator: 0.0,
+            tick: 0,
+            scheduled: BinaryHeap::new(),
+            leaves: Vec::new(),
+            rng: (seed ^ 0xD1B5_4A32_D192_ED03) | 1,
+        }
+    }
+
+    /// Запланировать тик блока через delay тиков
+    pub fn schedule(&mut self, pos: BlockPos, delay: u64) {
+        self.scheduled.push(Reverse((self.tick + delay.max(1), [pos.x, pos.y, pos.z])));
+    }
+
+    /// Клетку изменили: блок под ней проверит, не накрыт ли он
+    pub fn notify_changed(&mut self, pos: BlockPos) {
+        self.schedule(BlockPos::new(pos.x, pos.y - 1, pos.z), NEIGHBOR_TICK_DELAY);
+    }
+
+    /// Прогнать тики, накопившиеся за кадр
+    pub fn update(&mut self, dt: f32, center: Vec3, changes: &mut WorldChanges) {
+        self.accumulator += dt;
+        let interval = 1.0 / TICKS_PER_SECOND;
+        let mut ticks = 0;
+        while self.accumulator >= interval && ticks < MAX_TICKS_PER_FRAME {
+            self.accumulator -= interval;
+            ticks += 1;
+            self.tick += 1;
+            self.random_ticks(center, changes);
+            for pos in self.take_due() {
+                self.tick_block(changes, pos);
+            }
+        }
+        // Остаток долгого кадра отбрасываем
+        self.accumulator = self.accumulator.min(interval);
+    }
+
+    /// Случайные тики в секциях вокруг игрока
+    fn random_ticks(&mut self, center: Vec3, changes: &mut WorldChanges) {
+        let chunk_x = (center.x.floor() as i32).div_euclid(CHUNK_SIZE);
+        let chunk_z = (center.z.floor() as i32).div_euclid(CHUNK_SIZE);
+        let section = (center.y.floor() as i32 - MIN_HEIGHT).div_euclid(SECTION_SIZE);
+        let sections = (section - RANDOM_TICK_SECTIONS).max(0)..=(section + RANDOM_TICK_SECTIONS).min(SECTION_COUNT as i32 - 1);
+
+        for cx in chunk_x - RANDOM_TICK_RADIUS..=chunk_x + RANDOM_TICK_RADIUS {
+            for cz in chunk_z - RANDOM_TICK_RADIUS..=chunk_z + RANDOM_TICK_RADIUS {
+                for sy in sections.clone() {
+                    for _ in 0..RANDOM_TICKS_PER_SECTION {
+                        let pos = BlockPos::new(
+                            cx * CHUNK_SIZE + self.next_offset(),
+                            MIN_HEIGHT + sy * SECTION_SIZE + self.next_offset(),
+                            cz * CHUNK_SIZE + self.next_offset(),
+                        );
+                        self.tick_block(changes, pos);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Забрать листву выросших деревьев (для SubVoxelWorld)
+    pub fn take_leaves(&mut self) -> Vec<LeafSubVoxel> {
+        std::mem::take(&mut self.leaves)
+    }
+
+    /// Применить правило тика к блоку
+    fn tick_block(&mut self, changes: &mut WorldChanges, pos: BlockPos) {
+        let world = World { changes: &*changes };
+        let block = world.block(pos.x, pos.y, pos.z);
+        let Some(rule) = tick_rule(block) else { return };
+
+        let mut ctx = TickContext { world: &world, roll: self.next_roll(), blocks: Vec::new(), leaves: Vec::new() };
+        rule(&mut ctx, pos, block);
+        let TickContext { blocks, leaves, .. } = ctx;
+        for (pos, new_block) in blocks {
+            if changes.get_block(pos.x, pos.y, pos.z) != Some(new_block) {
+                changes.set_block(pos, new_block);
+            }
+        }
+        self.leaves.extend(leaves);
+    }
+
+    /// Забрать наступившие запланированные тики
+    fn take_due(&mut self) -> Vec<BlockPos> {
+        let mut due = Vec::new();
+        while let Some(Reverse((tick, [x, y, z]))) = self.scheduled.peek().copied() {
+            if tick > self.tick {
+                break;
+            }
+            self.scheduled.pop();
+            due.push(BlockPos::new(x, y, z));
+        }
+        due
+    }
+
+    /// Псевдослучайные 24 бита (xorshift)
+    fn next_bits(&mut self) -> u64 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        self.rng >> 40
+    }
+
+    /// Случайное смещение внутри секции 0..16
+    fn next_offset(&mut self) -> i32 {
+        self.next_bits() as i32 % SECTION_SIZE
+    }
+
+    /// Случайное число 0-1
+    fn next_roll(&mut self) -> f32 {
+        self.next_bits() as f32 / (1u64 << 24) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scheduled_ticks_come_due_in_order() {
+        let mut ticks = BlockTicks::new(7);
+        ticks.schedule(BlockPos::new(0, 5, 0), 3);
+        ticks.schedule(BlockPos::new(1, 5, 0), 1);
+        ticks.tick = 1;
+        assert_eq!(ticks.take_due(), vec![BlockPos::new(1, 5, 0)]);
+        ticks.tick = 2;
+        assert!(ticks.take_due().is_empty());
+        ticks.tick = 3;
+        assert_eq!(ticks.take_due(), vec![BlockPos::new(0, 5, 0)]);
+    }
+
+    #[test]
+    fn change_schedules_block_below() {
+        let mut ticks = BlockTicks::new(7);
+        ticks.notify_changed(BlockPos::new(4, 10, -2));
+        ticks.tick = NEIGHBOR_TICK_DELAY;
+        assert_eq!(ticks.take_due(), vec![BlockPos::new(4, 9, -2)]);
+    }
+
+    #[test]
+    fn offsets_stay_inside_section() {
+        let mut ticks = BlockTicks::new(42);
+        assert!((0..1000).map(|_| ticks.next_offset()).all(|offset| (0..SECTION_SIZE).contains(&offset)));
+    }
+}
@@ -0,0 +1,15 @@
+// ============================================
+// Weather Module - Дождь, снег и грозы
+// ============================================
+// - state: смена Clear/Rain/Storm, вид осадков по температуре биома
+// - snow: снежный покров в холодных биомах
+// - render: GPU частицы осадков (рисуются в основном проходе)
+// Облачность затемняет небо через DayNightCycle::set_overcast,
+// осадки глушат звук через SoundModifiers::with_weather.
+
+mod state;
+mod snow;
+mod render;
+
+pub use state::{WeatherSystem, WeatherKind, WeatherParams, Precipitation, SNOW_TEMPERATURE};
+pub use render::WeatherRenderer;
@@ -0,0 +1,178 @@
+// ============================================
+// Weather Renderer - GPU частицы осадков
+// ============================================
+// Частицы целиком анимируются в вершинном шейдере по instance_index
+// и времени: позиция берётся из хеша, падение и ветер - из времени,
+// коробка частиц "прокручивается" вокруг камеры в мировых координатах.
+// CPU только пишет uniform и выбирает количество инстансов.
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::util::DeviceExt;
+use ultraviolet::{Mat4, Vec3};
+
+use crate::gpu::lighting::DayNightCycle;
+
+use super::state::{Precipitation, WeatherParams};
+
+/// Максимум частиц при интенсивности 1.0
+const MAX_PARTICLES: u32 = 8000;
+
+/// Uniform данные - vec3 упакованы в vec4 для WGSL alignment
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct WeatherUniforms {
+    pub view_proj: [[f32; 4]; 4],  // 64 bytes
+    pub camera_pos: [f32; 4],      // xyz + time
+    pub params: [f32; 4],          // intensity, kind (0 дождь, 1 снег), wind_x, wind_z
+    pub color: [f32; 4],           // rgb (с учётом освещения) + alpha
+}
+
+impl Default for WeatherUniforms {
+    fn default() -> Self {
+        Self {
+            view_proj: Mat4::identity().into(),
+            camera_pos: [0.0; 4],
+            params: [0.0; 4],
+            color: [1.0; 4],
+        }
+    }
+}
+
+pub struct WeatherRenderer {
+    uniform_buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+    pipeline: wgpu::RenderPipeline,
+    params: WeatherParams,
+}
+
+impl WeatherRenderer {
+    pub fn new(device: &wgpu::Device, surface_format: wgpu::TextureFormat, sample_count: u32) -> Self {
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Weather UB"),
+            contents: bytemuck::cast_slice(&[WeatherUniforms::default()]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Weather BGL"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Weather BG"),
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Weather Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/weather.wgsl").into()),
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Weather PL"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Weather Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                cull_mode: None,
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::GreaterEqual, // Reversed-Z
+                stencil: Default::default(),
+                bias: Default::default(),
+            }),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                ..Default::default()
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            uniform_buffer,
+            bind_group,
+            pipeline,
+            params: WeatherParams::default(),
+        }
+    }
+
+    /// Установить параметры погоды на кадр
+    pub fn set_params(&mut self, params: WeatherParams) {
+        self.params = params;
+    }
+
+    /// Количество частиц для текущей интенсивности
+    fn particle_count(&self) -> u32 {
+        if self.params.precipitation == Precipitation::None {
+            return 0;
+        }
+        (MAX_PARTICLES as f32 * self.params.intensity.clamp(0.0, 1.0)) as u32
+    }
+
+    pub fn update(&self, queue: &wgpu::Queue, view_proj: [[f32; 4]; 4], camera_pos: Vec3, time: f32, day_night: &DayNightCycle) {
+        if self.particle_count() == 0 {
+            return;
+        }
+
+        // Частицы освещены как окружение: тёмные ночью и под тучами
+        let light = (day_night.ambient_intensity * 2.5).clamp(0.15, 1.0);
+        let (kind, base, alpha) = match self.params.precipitation {
+            Precipitation::Snow => (1.0, [0.95, 0.97, 1.0], 0.85),
+            _ => (0.0, [0.65, 0.7, 0.8], 0.35),
+        };
+
+        let uniforms = WeatherUniforms {
+            view_proj,
+            camera_pos: [camera_pos.x, camera_pos.y, camera_pos.z, time],
+            params: [self.params.intensity, kind, self.params.wind[0], self.params.wind[1]],
+            color: [base[0] * light, base[1] * light, base[2] * light, alpha],
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+    }
+
+    pub fn render<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+        let count = self.particle_count();
+        if count == 0 {
+            return;
+        }
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..6, 0..count);
+    }
+}
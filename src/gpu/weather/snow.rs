@@ -0,0 +1,83 @@
+// ============================================
+// Snow Accumulation - Снежный покров
+// ============================================
+// Пока идёт снег, на случайные столбцы вокруг игрока в холодных биомах
+// кладётся один слой SNOW поверх поверхности. Скорость растёт с
+// интенсивностью. Деревья, вода и столбец под игроком пропускаются.
+
+use ultraviolet::Vec3;
+
+use crate::gpu::blocks::{AIR, SNOW};
+use crate::gpu::biomes::climate_map;
+use crate::gpu::terrain::generation::{hash3d, is_solid_3d, world_gen_config};
+use crate::gpu::terrain::{get_height, BlockPos, WorldChanges};
+use crate::gpu::terrain::cache::column_biome;
+
+use super::state::{Precipitation, WeatherSystem, SNOW_TEMPERATURE};
+
+/// Интервал выпадения при максимальной интенсивности (секунды)
+const SNOW_INTERVAL: f32 = 0.4;
+/// Радиус вокруг игрока (блоки)
+const SNOW_RADIUS: f32 = 24.0;
+/// Столбцы ближе этого к игроку не трогаем (чтобы не застрять)
+const PLAYER_CLEARANCE: f32 = 2.0;
+/// Диапазон поиска поверхности от высоты карты
+const SURFACE_SEARCH: i32 = 24;
+
+impl WeatherSystem {
+    /// Накопить снег вокруг игрока
+    pub fn accumulate_snow(&mut self, dt: f32, player_pos: Vec3, world_changes: &mut WorldChanges) {
+        let params = self.params();
+        if params.precipitation != Precipitation::Snow || params.intensity < 0.2 {
+            return;
+        }
+
+        self.snow_timer += dt * params.intensity;
+        while self.snow_timer >= SNOW_INTERVAL {
+            self.snow_timer -= SNOW_INTERVAL;
+
+            let angle = self.next_random() * std::f32::consts::TAU;
+            let distance = PLAYER_CLEARANCE + self.next_random() * (SNOW_RADIUS - PLAYER_CLEARANCE);
+            let x = (player_pos.x + angle.cos() * distance).floor() as i32;
+            let z = (player_pos.z + angle.sin() * distance).floor() as i32;
+
+            if let Some(y) = snow_target(x, z, world_changes) {
+                world_changes.set_block(BlockPos::new(x, y, z), SNOW);
+            }
+        }
+    }
+}
+
+/// Высота, куда можно положить снег в столбце (None - нельзя)
+fn snow_target(x: i32, z: i32, world_changes: &WorldChanges) -> Option<i32> {
+    if climate_map().sample(x as f32, z as f32).temperature >= SNOW_TEMPERATURE {
+        return None;
+    }
+
+    // Верхний непустой блок (изменения мира важнее генерации)
+    let base = get_height(x as f32, z as f32) as i32;
+    let sea_level = world_gen_config().sea_level;
+    let is_solid = |y: i32| match world_changes.get_block(x, y, z) {
+        Some(block) => block != AIR,
+        None => is_solid_3d(x as f32, y as f32, z as f32),
+    };
+    let top = ((base - SURFACE_SEARCH)..=(base + SURFACE_SEARCH))
+        .rev()
+        .find(|&y| is_solid(y) || (y < sea_level && world_changes.get_block(x, y, z).is_none()))?;
+
+    // Вода (ниже уровня моря генерация заполняет пустоту водой)
+    if top < sea_level && !is_solid(top) {
+        return None;
+    }
+    // Уже лежит снег
+    if world_changes.get_block(x, top, z) == Some(SNOW) {
+        return None;
+    }
+    // На месте ствола дерева (то же правило, что и в генерации чанка)
+    let biome = column_biome(x, z);
+    if biome.tree_density > 0.0001 && hash3d(x, top, z) < biome.tree_density {
+        return None;
+    }
+
+    Some(top + 1)
+}
@@ -0,0 +1,199 @@
+// ============================================
+// Weather State - Смена погоды
+// ============================================
+// Погода глобальная (Clear/Rain/Storm) и меняется через случайные
+// интервалы. Шанс осадков зависит от влажности в точке игрока,
+// а вид осадков - от температуры биома:
+//   холодно (t < 0.25)            - снег
+//   жарко и сухо (пустыня)        - осадков нет, только тучи
+//   остальное                     - дождь
+
+use ultraviolet::Vec3;
+
+use crate::gpu::biomes::climate_map;
+
+/// Порог температуры для снега (совпадает с холодными биомами)
+pub const SNOW_TEMPERATURE: f32 = 0.25;
+
+/// Длительность погоды (секунды)
+const MIN_WEATHER_DURATION: f32 = 120.0;
+const MAX_WEATHER_DURATION: f32 = 480.0;
+/// Скорость перехода между состояниями (1/с), ~10 секунд
+const TRANSITION_SPEED: f32 = 0.25;
+
+/// Состояние погоды
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeatherKind {
+    Clear,
+    Rain,
+    Storm,
+}
+
+impl WeatherKind {
+    /// Целевые (интенсивность осадков, облачность)
+    fn targets(self) -> (f32, f32) {
+        match self {
+            WeatherKind::Clear => (0.0, 0.0),
+            WeatherKind::Rain => (0.55, 0.5),
+            WeatherKind::Storm => (1.0, 0.8),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            WeatherKind::Clear => "Clear",
+            WeatherKind::Rain => "Rain",
+            WeatherKind::Storm => "Storm",
+        }
+    }
+}
+
+/// Вид осадков в точке игрока
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precipitation {
+    None,
+    Rain,
+    Snow,
+}
+
+/// Параметры погоды для рендера и звука (снимок на кадр)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeatherParams {
+    pub precipitation: Precipitation,
+    /// Интенсивность осадков 0-1
+    pub intensity: f32,
+    /// Облачность 0-1
+    pub overcast: f32,
+    /// Ветер (блоков/с по X и Z)
+    pub wind: [f32; 2],
+}
+
+impl Default for WeatherParams {
+    fn default() -> Self {
+        Self {
+            precipitation: Precipitation::None,
+            intensity: 0.0,
+            overcast: 0.0,
+            wind: [0.0, 0.0],
+        }
+    }
+}
+
+/// Система погоды
+pub struct WeatherSystem {
+    kind: WeatherKind,
+    /// Сглаженные значения
+    intensity: f32,
+    overcast: f32,
+    wind: [f32; 2],
+    /// Секунд до следующей смены
+    timer: f32,
+    /// Осадки в точке игрока
+    precipitation: Precipitation,
+    rng: u64,
+    /// Накопитель для выпадения снега
+    pub(super) snow_timer: f32,
+}
+
+impl WeatherSystem {
+    pub fn new(seed: u64) -> Self {
+        let mut weather = Self {
+            kind: WeatherKind::Clear,
+            intensity: 0.0,
+            overcast: 0.0,
+            wind: [0.0, 0.0],
+            timer: 0.0,
+            precipitation: Precipitation::None,
+            rng: (seed ^ 0x9E37_79B9_7F4A_7C15) | 1,
+            snow_timer: 0.0,
+        };
+        weather.timer = weather.random_duration();
+        weather
+    }
+
+    /// Псевдослучайное число 0-1 (xorshift, детерминировано сидом мира)
+    pub(super) fn next_random(&mut self) -> f32 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        (self.rng >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    fn random_duration(&mut self) -> f32 {
+        MIN_WEATHER_DURATION + self.next_random() * (MAX_WEATHER_DURATION - MIN_WEATHER_DURATION)
+    }
+
+    /// Выбрать следующую погоду по влажности в точке игрока
+    fn pick_next(&mut self, humidity: f32) -> WeatherKind {
+        let wet_chance = (0.15 + humidity * 0.5).clamp(0.0, 0.8);
+        let roll = self.next_random();
+        if roll >= wet_chance {
+            WeatherKind::Clear
+        } else if roll < wet_chance * 0.25 {
+            WeatherKind::Storm
+        } else {
+            WeatherKind::Rain
+        }
+    }
+
+    /// Обновить погоду
+    pub fn update(&mut self, dt: f32, player_pos: Vec3) {
+        let climate = climate_map().sample(player_pos.x, player_pos.z);
+
+        self.timer -= dt;
+        if self.timer <= 0.0 {
+            let next = self.pick_next(climate.humidity);
+            if next != self.kind {
+                log::info!("{} -> {}", self.kind.name(), next.name());
+            }
+            self.kind = next;
+            self.timer = self.random_duration();
+
+            // Новое направление ветра
+            let angle = self.next_random() * std::f32::consts::TAU;
+            let strength = match self.kind {
+                WeatherKind::Storm => 6.0,
+                _ => 1.5,
+            };
+            self.wind = [angle.cos() * strength, angle.sin() * strength];
+        }
+
+        self.precipitation = if climate.temperature < SNOW_TEMPERATURE {
+            Precipitation::Snow
+        } else if climate.temperature > 0.75 && climate.humidity < 0.25 {
+            Precipitation::None
+        } else {
+            Precipitation::Rain
+        };
+
+        let (target_intensity, target_overcast) = self.kind.targets();
+        let t = 1.0 - (-TRANSITION_SPEED * dt).exp();
+        self.intensity += (target_intensity - self.intensity) * t;
+        self.overcast += (target_overcast - self.overcast) * t;
+    }
+
+    /// Текущее состояние
+    pub fn kind(&self) -> WeatherKind {
+        self.kind
+    }
+
+    /// Принудительно сменить погоду (переход всё равно плавный)
+    pub fn set_kind(&mut self, kind: WeatherKind) {
+        self.kind = kind;
+        self.timer = self.random_duration();
+    }
+
+    /// Снимок для рендера и звука
+    pub fn params(&self) -> WeatherParams {
+        let intensity = match self.precipitation {
+            Precipitation::None => 0.0,
+            _ => self.intensity,
+        };
+        WeatherParams {
+            precipitation: self.precipitation,
+            intensity,
+            overcast: self.overcast,
+            wind: self.wind,
+        }
+    }
+}